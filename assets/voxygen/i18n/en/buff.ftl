@@ -97,6 +97,18 @@ buff-frigid = Frigid
 ## Lifesteal
 buff-lifesteal = Lifesteal
     .desc = Siphon your enemies' life away.
+## Poison Coating
+buff-poison_coating = Poison Coating
+    .desc = Your weapon drips with poison.
+    .stat = Poisons your foes on your next { $charges } hits.
+## Fire Coating
+buff-fire_coating = Fire Oil
+    .desc = Your weapon is slick with burning oil.
+    .stat = Sets your foes ablaze on your next { $charges } hits.
+## Frost Coating
+buff-frost_coating = Frost Salve
+    .desc = Your weapon is rimed with frost.
+    .stat = Freezes your foes on your next { $charges } hits.
 ## Salamander's Aspect
 buff-salamanderaspect = Salamander's Aspect
     .desc = You cannot burn and you move fast through lava.
@@ -136,6 +148,9 @@ buff-concussion = Concussion
 ## Staggered
 buff-staggered = Staggered
     .desc = You are off balance and more susceptible to heavy attacks.
+## Electrified
+buff-electrified = Electrified
+    .desc = You were struck by lightning and your muscles are still twitching, slowing your movements and attacks.
 ## Tenacity
 buff-tenacity = Tenacity
     .desc = You are not only able to shrug off heavier attacks, they energize you as well. However you are also slower.
@@ -145,5 +160,11 @@ buff-resilience = Resilience
 ## Util
 buff-text-over_seconds = over { $dur_secs } seconds
 buff-text-for_seconds = for { $dur_secs } seconds
+buff-text-remaining_damage = { $damage } damage remaining
+buff-text-dispel_debuffs = Removes up to { $count } negative effects.
+buff-text-dispel_buffs = Removes up to { $count } positive effects.
+buff-text-dispel = Removes up to { $count } effects.
 buff-mysterious = Mysterious effect
 buff-remove = Click to remove
+buff-pin = Right-click to pin
+buff-unpin = Right-click to unpin
@@ -45,6 +45,21 @@ command-no-buid-perms = You do not have permission to build.
 command-set-build-mode-off = Toggled build mode off.
 command-set-build-mode-on-persistent = Toggled build mode on. Experimental terrain persistence is enabled. The server will attempt to persist changes, but this is not guaranteed.
 command-set-build-mode-on-unpersistent = Toggled build mode on. Changes will not be persisted when a chunk unloads.
+command-set-creative-mode-off = Toggled creative mode off.
+command-set-creative-mode-on = Toggled creative mode on. You can fly, build anywhere and won't take damage.
+command-minigame-started = { $minigame } started, players can join it now.
+command-minigame-stopped = The minigame will end shortly.
+command-minigame-joined = You joined the minigame.
+command-minigame-left = You left the minigame.
+command-minigame-list = Running minigames: { $minigames }
+command-minigame-none-running = No minigames are running.
+command-race-checkpoint-added = Added checkpoint { $checkpoints } to { $track }. The leaderboard of the track was reset.
+command-race-removed = Removed race track { $track }.
+command-race-leaderboard = Best times on { $track }:
+  { $records }
+command-race-no-records = Nobody finished { $track } yet.
+command-race-tracks = Race tracks: { $tracks }
+command-race-no-tracks = There are no race tracks.
 command-invalid-alignment = Invalid alignment: { $alignment }
 command-kit-not-enough-slots = Inventory doesn't have enough slots
 command-lantern-unequiped = Please equip a lantern first
@@ -56,6 +71,8 @@ command-explosion-power-too-low = Explosion power must be more than { $power }
 command-disconnectall-confirm = Please run the command again with the second argument of "confirm" to confirm that
   you really want to disconnect all players from the server
 command-invalid-skill-group = { $group } is not a skill group!
+command-invalid-title = { $title } is not a title!
+command-title-no-character = Titles can only be given to players playing a character.
 command-unknown = Unknown command
 command-disabled-by-settings = Command disabled in server settings
 command-battlemode-intown = You need to be in town to change battle mode!
@@ -83,6 +100,8 @@ command-scale-set = Set scale to { $scale }
 command-repaired-items = Repaired all equipped items
 command-message-group-missing = You are using group chat but do not belong to a group. Use /world or
   /region to change chat.
+command-message-guild-missing = You are using guild chat but do not belong to a guild. Use /world or
+  /region to change chat.
 command-tell-request = { $sender } wants to talk to you.
 command-transform-invalid-presence = Cannot transform in the current presence
 command-aura-invalid-buff-parameters = Invalid buff parameters for aura
@@ -104,3 +123,14 @@ command-destroyed-tethers = All tethers destroyed! You are now free
 command-destroyed-no-tethers = You're not connected to any tethers
 command-dismounted = Dismounted
 command-no-dismount = You're not riding or being ridden
+command-report-self = You can't report yourself.
+command-report-too-many = You already have { $max } reports waiting for a moderator. Please wait until they are looked into.
+command-report-filed = Thank you, your report (#{ $id }) was sent to the moderators.
+command-report-notify = New report #{ $id } ({ $category }) against { $player }.
+command-report-resolved = Your report #{ $id } has been looked into by a moderator.
+command-link-account-disabled = Account linking is not enabled on this server.
+command-link-account-linked = Your account is already linked.
+command-link-account-code =
+    Your code is { $code }, it can be used for the next { $minutes } minutes. Don't share it with anyone.
+    { $instructions }
+command-link-account-done = Your account was linked successfully.
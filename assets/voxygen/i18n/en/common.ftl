@@ -87,6 +87,8 @@ common-kind-utility = Utility
 common-kind-ingredient = Ingredient
 common-kind-lantern = Lantern
 common-kind-recipegroup = Recipes
+common-kind-skillbook = Skill Book
+common-kind-treasure_map = Treasure Map
 common-hands-one = One-Handed
 common-hands-two = Two-Handed
 common-rand_appearance = Random appearance
@@ -118,6 +120,9 @@ common-sprite-mud = Mud
 common-sprite-grave = Grave
 common-sprite-crate = Crate
 common-sprite-signboard = Signboard
+common-sprite-notice_board = Notice Board
+common-sprite-guild_board = Guild Board
+common-sprite-guild_chest = Guild Storage
 common-sprite-street_lamp = Street Lamp
 common-sprite-lantern = Lantern
 common-sprite-seashell_lantern = Seashell Lantern
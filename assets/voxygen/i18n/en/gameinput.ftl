@@ -34,13 +34,17 @@ gameinput-wallleap = Wall Leap
 gameinput-togglelantern = Toggle Lantern
 gameinput-mount = Mount
 gameinput-stayfollow = Stay/Follow
+gameinput-scout = Send Bird to Scout
 gameinput-chat = Chat
 gameinput-command = Command
 gameinput-escape = Escape
 gameinput-map = Map
 gameinput-bag = Bag
 gameinput-trade = Trade
+gameinput-ask_directions = Ask for Directions
 gameinput-social = Social
+gameinput-questlog = Quest Log
+gameinput-calendar = Event Calendar
 gameinput-sit = Sit
 gameinput-spellbook = Spells
 gameinput-settings = Settings
@@ -55,6 +59,9 @@ gameinput-zoomout = Camera zoom out
 gameinput-zoomlock = Camera zoom lock
 gameinput-cameraclamp = Camera Clamp
 gameinput-dance = Dance
+gameinput-emotewheel = Emote Wheel
+gameinput-quickactionwheel = Quick Action Wheel
+gameinput-showinteractables = Show Interactables
 gameinput-select = Select Entity
 gameinput-acceptgroupinvite = Accept Group Invite
 gameinput-declinegroupinvite = Decline Group Invite
@@ -76,3 +83,4 @@ gameinput-mutemusic = Mute music volume
 gameinput-mutesfx = Mute SFX volume
 gameinput-muteambience = Mute ambience volume
 gameinput-togglewalk = Toggle Walking
+gameinput-cyclebuildpalette = Cycle Build Palette
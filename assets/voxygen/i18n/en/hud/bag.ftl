@@ -5,6 +5,7 @@ hud-bag-stats = Stats
 hud-bag-head = Head
 hud-bag-neck = Neck
 hud-bag-tabard = Tabard
+hud-bag-heraldry = Heraldry
 hud-bag-shoulders = Shoulders
 hud-bag-chest = Chest
 hud-bag-hands = Hands
@@ -33,6 +34,11 @@ hud-bag-combat_rating_desc =
     Calculated from your
     equipment and health.
 hud-bag-protection_desc = Damage reduction through armor.
+hud-bag-element_resistance = { $element } resistance: { $resistance }%
+hud-bag-element-fire = Fire
+hud-bag-element-frost = Frost
+hud-bag-element-shock = Shock
+hud-bag-element-poison = Poison
 hud-bag-stun_res_desc =
     Resilience against being stunned by consecutive hits.
     Regenerates like energy.
@@ -0,0 +1,12 @@
+hud-calendar = Event Calendar
+hud-calendar-empty = No events are coming up.
+hud-calendar-world_boss = A world boss appears
+hud-calendar-bonus_exp = { $multiplier }x experience
+hud-calendar-market_day = Market day in { $site }
+hud-calendar-festival = Festival in { $site }
+hud-calendar-starts_in = Starts in { $time }
+hud-calendar-ends_in = Ends in { $time }
+hud-calendar-days = { $days }d { $hours }h
+hud-calendar-hours = { $hours }h { $minutes }m
+hud-calendar-minutes = { $minutes }m
+calendar-event-started = { $event } has begun!
@@ -89,3 +89,12 @@ hud-loot-pickup-msg = { $amount ->
     [1] { $actor } picked up { $item }
     *[other] { $actor } picked up { $amount }x { $item }
 }
+
+## Server announcements, that admins can schedule by key
+
+hud-chat-announcement-rules = Please read and respect the rules of this server.
+hud-chat-announcement-restart = The server restarts every { $hours ->
+    [1] hour
+    *[other] { $hours } hours
+}.
+hud-chat-announcement-website = Visit { $url } for news about this server.
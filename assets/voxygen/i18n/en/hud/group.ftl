@@ -1,13 +1,25 @@
 hud-group = Group
 hud-group-invite_to_join = [{ $name }] invited you to their group!
 hud-group-invite_to_trade = [{ $name }] would like to trade with you.
+hud-group-invite_to_guild = [{ $name }] invites you to join their guild [{ $tag }].
 hud-group-invite = Invite
 hud-group-kick = Kick
 hud-group-assign_leader = Assign Leader
 hud-group-leave = Leave Group
 hud-group-dead = Dead
+hud-group-downed = Downed
 hud-group-out_of_range = Out of range
 hud-group-add_friend = Add to Friends
 hud-group-link_group = Link Groups
 hud-group-in_menu = In Menu
 hud-group-members = Group Members
+hud-group-loot_mode-free_for_all = Loot: Free for all
+hud-group-loot_mode-need_greed = Loot: Need/Greed
+hud-group-loot_mode-free_for_all-changed = Group loot is now free for all.
+hud-group-loot_mode-need_greed-changed = Loot from bosses and chests is now rolled for by the group.
+hud-loot_roll-title = Roll for loot
+hud-loot_roll-need = Need
+hud-loot_roll-greed = Greed
+hud-loot_roll-pass = Pass
+hud-loot_roll-won = { $player } won { $item } ({ $choice } { $roll })
+hud-loot_roll-nobody = Everyone passed on { $item }
@@ -0,0 +1,60 @@
+hud-guild = Guild
+hud-guild-invite = Guild Invite
+hud-guild-members = Members: { $count }
+hud-guild-online = online
+hud-guild-offline = offline
+hud-guild-promote = Promote
+hud-guild-demote = Demote
+hud-guild-kick = Kick
+hud-guild-leave = Leave
+hud-guild-disband = Disband
+hud-guild-rank-member = Member
+hud-guild-rank-officer = Officer
+hud-guild-rank-leader = Leader
+hud-guild-create = Found a Guild
+hud-guild-create-name = Name
+hud-guild-create-tag = Tag
+hud-guild-create-cost = Founding a guild costs { $cost } coins.
+hud-guild-create-confirm = Found
+hud-guild-created = { $leader } founded the guild { $name }
+hud-guild-member_joined = { $name } joined the guild
+hud-guild-member_left = { $name } left the guild
+hud-guild-member_kicked = { $name } was kicked from the guild by { $by }
+hud-guild-rank_changed = { $name } is now { $rank }
+hud-guild-disbanded = The guild { $name } was disbanded
+hud-guild-hall_claimed = { $name } claimed a guild hall
+hud-guild-notice_posted = { $name } posted a notice in the guild hall
+hud-guild-hall = Guild Hall
+hud-guild-hall-no_guild = Join a guild to claim this hall.
+hud-guild-hall-claim-cost = Claiming this hall costs { $cost } coins.
+hud-guild-hall-claim = Claim
+hud-guild-hall-not_ours = This hall belongs to another guild.
+hud-guild-hall-banner = Banner
+hud-guild-hall-design = Design
+hud-guild-hall-storage_rank = Storage access
+hud-guild-hall-notices = Notices
+hud-guild-hall-post = Post
+hud-guild-hall-remove = Remove
+hud-guild-storage = Guild Storage
+hud-guild-storage-capacity = { $used }/{ $capacity } slots used
+hud-guild-storage-access = { $rank } and above can take items
+hud-guild-error-already_in_guild = You are already in a guild.
+hud-guild-error-not_in_guild = You are not in a guild.
+hud-guild-error-target_in_guild = That player is already in a guild.
+hud-guild-error-not_a_member = That player is not in your guild.
+hud-guild-error-no_permission = Your rank doesn't allow that.
+hud-guild-error-invalid_name = Guild names need 3 to 24 letters, digits or spaces.
+hud-guild-error-invalid_tag = Guild tags need 2 to 5 letters or digits.
+hud-guild-error-name_taken = A guild with that name already exists.
+hud-guild-error-tag_taken = A guild with that tag already exists.
+hud-guild-error-not_enough_coins = You don't have enough coins.
+hud-guild-error-guild_full = The guild is full.
+hud-guild-error-no_hall = Your guild hasn't claimed a hall.
+hud-guild-error-hall_taken = Another guild already claimed this hall.
+hud-guild-error-already_owns_hall = Your guild already has a hall.
+hud-guild-error-not_a_hall = This is not a guild hall.
+hud-guild-error-too_far_from_hall = You are too far from your guild hall.
+hud-guild-error-storage_full = The guild storage is full.
+hud-guild-error-inventory_full = Your inventory is full.
+hud-guild-error-invalid_notice = Notices need up to 200 characters of text.
+hud-guild-error-internal = The guild couldn't be updated, try again later.
@@ -0,0 +1,34 @@
+hud-heraldry = Heraldry
+hud-heraldry-target-cape = Cape
+hud-heraldry-target-shield = Shield
+hud-heraldry-target-guild_banner = Guild Banner
+hud-heraldry-pattern = Pattern
+hud-heraldry-pattern-plain = Plain
+hud-heraldry-pattern-pale = Pale
+hud-heraldry-pattern-fess = Fess
+hud-heraldry-pattern-bend = Bend
+hud-heraldry-pattern-cross = Cross
+hud-heraldry-pattern-saltire = Saltire
+hud-heraldry-pattern-chevron = Chevron
+hud-heraldry-pattern-chief = Chief
+hud-heraldry-pattern-per_pale = Per Pale
+hud-heraldry-pattern-quartered = Quartered
+hud-heraldry-pattern-bordure = Bordure
+hud-heraldry-emblem = Emblem
+hud-heraldry-emblem-none = None
+hud-heraldry-emblem-star = Star
+hud-heraldry-emblem-crown = Crown
+hud-heraldry-emblem-sword = Sword
+hud-heraldry-emblem-tree = Tree
+hud-heraldry-emblem-moon = Moon
+hud-heraldry-emblem-skull = Skull
+hud-heraldry-emblem-tower = Tower
+hud-heraldry-emblem-hammer = Hammer
+hud-heraldry-field = Field
+hud-heraldry-charge = Pattern colour
+hud-heraldry-emblem_color = Emblem colour
+hud-heraldry-no_cape = Equip a cape to paint it.
+hud-heraldry-no_shield = Equip a shield to paint it.
+hud-heraldry-no_banner = Only officers of a guild with a hall can change its banner.
+hud-heraldry-apply = Apply
+hud-heraldry-clear = Clear
@@ -27,6 +27,11 @@ hud-map-mid_click = Set Waypoint
 hud-map-recenter = Recenter
 hud-map-marked_location = Marked Location
 hud-map-marked_location_remove = Click to remove
+hud-map-shrine = Waypoint Shrine
+hud-map-shrine-here = You are at this shrine
+hud-map-shrine-travel = Click to travel for { $cost } coins
+hud-map-shrine-visit = Stand at a shrine to travel here
+hud-map-ask_directions = Click a town to ask the way there
 hud-map-change_map_mode = Change Map Mode
 hud-map-toggle_minimap_voxel = Toggle Minimap Voxel View
 hud-map-zoom_minimap_explanation =
@@ -38,5 +43,8 @@ hud-map-adlet = Adlet Stronghold
 hud-map-haniwa = Haniwa Catacomb
 hud-map-cultist = Cultist Dungeon
 hud-map-sahagin = Sahagin Island
+hud-map-climbing_route = Climbing Route
 hud-map-terracotta = Terracotta Ruins
 hud-map-placed_by = Placed by { $name }
+hud-map-annotate = Annotate
+hud-map-annotation-undo = Undo
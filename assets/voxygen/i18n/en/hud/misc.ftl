@@ -2,7 +2,15 @@ hud-do_not_show_on_startup = Don't show this on startup
 hud-show_tips = Show Tips
 hud-quests = Quests
 hud-you_died = You Died
+hud-downed = You are Downed
+hud-downed-bleed_out = A teammate can revive you, you bleed out in { $seconds } seconds.
+hud-vario = Vario { $rate } m/s
 hud-waypoint_saved = Waypoint Saved
+hud-shrine_unlocked = Waypoint Shrine Unlocked
+hud-shrine_travel-not_at_shrine = You need to stand at a waypoint shrine to travel
+hud-shrine_travel-locked = You haven't unlocked that shrine yet
+hud-shrine_travel-in_combat = You can't travel while in combat
+hud-shrine_travel-not_enough_coins = You don't have enough coins to travel there
 hud-sp_arrow_txt = SP
 hud-inventory_full = Inventory Full
 hud-someone_else = someone else
@@ -15,6 +23,7 @@ hud-press_key_to_toggle_keybindings_fmt = Press { $key } to toggle key bindings
 hud-press_key_to_toggle_debug_info_fmt = Press { $key } to toggle debug info
 hud_items_lost_dur = Your equipped items have lost Durability.
 hud-press_key_to_respawn = Press { $key } to respawn at the last campfire you visited.
+hud-press_key_to_spectate_group = Press { $key } to watch your group members.
 hud-tutorial_btn = Tutorial
 hud-tutorial_click_here = Press [ { $key } ] to free your cursor and click this button!
 hud-tutorial_elements = Crafting
@@ -33,6 +42,8 @@ hud-temp_quest_text =
     Whenever you feel ready, try to get even better equipment from the many challenges marked on your map!
 hud-spell = Spells
 hud-diary = Diary
+hud-ability_loadout = Loadout { $number }
+hud-ability_loadout-desc = Abilities chosen for these weapons are saved per loadout. Loadouts can only be switched out of combat.
 hud-free_look_indicator = Free look active. Press { $key } to disable.
 hud-camera_clamp_indicator = Camera vertical clamp active. Press { $key } to disable.
 hud-auto_walk_indicator = Auto walk/swim active
@@ -55,14 +66,20 @@ hud-mine-needs_shovel = Needs Shovel
 hud-mine-needs_unhandled_case = Needs ???
 hud-talk = Talk
 hud-pet = Pet
+hud-revive = Revive
 hud-trade = Trade
+hud-ask_directions = Ask for Directions
 hud-mount = Mount
 hud-follow = Follow
 hud-stay = Stay
+hud-scout = Scout
 hud-sit = Sit
 hud-steer = Steer
 hud-lay = Lay
 hud-portal = Portal
+hud-shrine = Waypoint Shrine
+hud-gravestone = Your Gravestone
+hud-recover = Recover Items
 
 -server = Server
 -client = Client
@@ -81,4 +98,18 @@ hud-init-stage-client-request-server-version = [{ -client }]: Waiting for server
 hud-init-stage-client-authentication = [{ -client }]: Authenticating...
 hud-init-stage-client-load-init-data = [{ -client }]: Loading initialization data from server...
 hud-init-stage-client-starting-client = [{ -client }]: Preparing Client...
+hud-init-stage-client-queued = [{ -client }]: The server is full, waiting in queue at position { $position }...
+hud-init-stage-client-queued-eta = [{ -client }]: The server is full, waiting in queue at position { $position } (about { $minutes } min)...
 hud-init-stage-render-pipeline = Creating render pipeline ({ $done }/{ $total })
+hud-emote-wave = Wave
+hud-emote-point = Point
+hud-emote-bow = Bow
+hud-emote-laugh = Laugh
+hud-minigame-ended = { $minigame } ended: { $scores }
+hud-minigame-nobody = { $minigame } ended without any scores
+hud-race-split = Checkpoint { $checkpoint }/{ $checkpoints }: { $time }
+hud-race-finished = Finished { $track } in { $time }
+hud-race-personal-best = Finished { $track } in { $time }, a new personal best!
+hud-climbing_route-discovered = You discovered a climbing route, it is now shown on your map
+hud-treasure_map-read = The map marks a spot about { $distance }m { $direction }. Dig there with a shovel.
+hud-treasure_map-found = You unearthed a buried chest!
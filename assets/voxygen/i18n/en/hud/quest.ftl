@@ -1,7 +1,21 @@
-hud-quest = Quest
+hud-quest = Quest Log
 hud-quest-intro = Greetings, { $playername }!
 hud-quest-desc-fetch = Please help me find:
 hud-quest-desc-kill = Could you help me kill
 hud-quest-reward = I will reward you with:
 hud-quest-accept = Accept
 hud-quest-decline = Decline
+hud-bounty-board = Notice Board
+hud-bounty-board-empty = The town has no bounties to offer.
+hud-bounty-active = Bounties
+hud-bounty-active-empty = You don't pursue any bounties.
+hud-bounty-hunt = Hunt { $target }, last seen { $distance }m away, for { $reward } coins
+hud-bounty-recover = Recover the goods stolen by { $target }, { $distance }m away, for { $reward } coins
+hud-bounty-completed = Bounty on { $target } completed, you were paid { $reward } coins
+hud-bounty-lost = Someone else killed the monster you were hunting, the bounty was withdrawn
+hud-reputation = Reputation
+hud-reputation-empty = No faction has heard of you yet.
+hud-reputation-hostile = { $faction }: Hostile
+hud-reputation-neutral = { $faction }: Neutral
+hud-reputation-friendly = { $faction }: Friendly
+hud-reputation-honored = { $faction }: Honored
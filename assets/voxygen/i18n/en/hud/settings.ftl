@@ -32,6 +32,11 @@ hud-settings-speech_bubble = Speech Bubble
 hud-settings-speech_bubble_self = Show Own Speech Bubbles
 hud-settings-speech_bubble_dark_mode = Speech Bubble Dark Mode
 hud-settings-speech_bubble_icon = Speech Bubble Icon
+hud-settings-nameplates = Nameplates
+hud-settings-nameplate_hostiles_only = Only Show Hostiles
+hud-settings-nameplate_hide_full_health = Hide Full Health Bars
+hud-settings-nameplate_scale_by_distance = Scale by Distance
+hud-settings-nameplate_show_tags = Show Faction Tags
 hud-settings-energybar_numbers = Energy bar numbers
 hud-settings-always_show_bars = Always show the energy bar
 hud-settings-enable_poise_bar = Enable Poise bar
@@ -59,6 +64,7 @@ hud-settings-camera_clamp_behavior = Camera clamp behavior
 hud-settings-zoom_lock_behavior = Camera zoom lock behavior
 hud-settings-aim_offset_x = Horizontal Aim Offset
 hud-settings-aim_offset_y = Vertical Aim Offset
+hud-settings-interact_highlight = Interactable Highlight
 hud-settings-player_physics_behavior = Player physics (experimental)
 hud-settings-stop_auto_walk_on_input = Stop auto walk on movement
 hud-settings-auto_camera = Auto camera
@@ -105,6 +111,7 @@ hud-settings-gpu_profiler = Enable GPU timing (not supported everywhere)
 hud-settings-particles = Particles
 hud-settings-lossy_terrain_compression = Lossy terrain compression
 hud-settings-weapon_trails = Weapon trails
+hud-settings-cloth_physics = Cloth physics
 hud-settings-flashing_lights = Flashing lights
 hud-settings-flashing_lights_info = Enables all kinds of flashing, e.g. flickering or lightning strikes
 hud-settings-resolution = Resolution
@@ -119,6 +126,7 @@ hud-settings-shadow_rendering_mode-none = None
 hud-settings-shadow_rendering_mode-cheap = Cheap
 hud-settings-shadow_rendering_mode-map = Map
 hud-settings-shadow_rendering_mode-map-resolution = Resolution
+hud-settings-shadow_rendering_mode-map-point_shadows = Point Light Shadows
 hud-settings-rain_occlusion-resolution = Rain Occlusion Resolution
 hud-settings-lod_detail = LoD Detail
 hud-settings-save_window_size = Save window size
@@ -156,6 +164,7 @@ hud-settings-activity = Activity
 hud-settings-death = Death
 hud-settings-group = Group
 hud-settings-faction = Faction
+hud-settings-guild = Guild
 hud-settings-world = World
 hud-settings-region = Region
 hud-settings-say = Say
@@ -164,4 +173,5 @@ hud-settings-group_only = Group only
 hud-settings-reset_chat = Reset to Defaults
 hud-settings-third_party_integrations = Third-party Integrations
 hud-settings-enable_discord_integration = Enable Discord Integration
+hud-settings-telemetry = Share crash and performance reports
 hud-settings-subtitles = Subtitles
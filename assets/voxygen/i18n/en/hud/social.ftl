@@ -13,3 +13,4 @@ hud-social-name = Name
 hud-social-level = Level
 hud-social-zone = Zone
 hud-social-account = Account
+hud-social-verified = Verified account
@@ -12,6 +12,10 @@ subtitle-footsteps_grass = Walking on grass
 subtitle-footsteps_earth = Walking on dirt
 subtitle-footsteps_rock = Walking on rock
 subtitle-footsteps_snow = Walking on snow
+subtitle-footsteps_wood = Walking on wood
+subtitle-footsteps_water = Wading through water
+subtitle-landing = Landing
+subtitle-impact = Projectile impact
 subtitle-pickup_item = Item picked up
 subtitle-pickup_failed = Pickup failed
 
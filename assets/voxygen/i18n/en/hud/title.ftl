@@ -0,0 +1,18 @@
+hud-title-unlocked = You earned the title { $title }! Show it from the diary.
+hud-title-show = Show
+hud-title-shown = Shown
+hud-title-locked = Locked
+hud-title-hide = Hide Title
+hud-title-source-achievement = Achievement
+hud-title-source-quest = Quest
+hud-title-source-event = Event
+hud-title-wayfarer = Wayfarer
+hud-title-wayfarer-desc = { $source }: Unlock { $shrines } waypoint shrines.
+hud-title-treasure_hunter = Treasure Hunter
+hud-title-treasure_hunter-desc = { $source }: Dig up a cache marked on a treasure map.
+hud-title-bounty_hunter = Bounty Hunter
+hud-title-bounty_hunter-desc = { $source }: Complete a bounty from a notice board.
+hud-title-champion = Champion
+hud-title-champion-desc = { $source }: Win a server event.
+hud-title-reveller = Reveller
+hud-title-reveller-desc = { $source }: Take part in a server event.
@@ -12,6 +12,7 @@ hud-trade-has_accepted =
     has accepted
 hud-trade-accept = Accept
 hud-trade-decline = Decline
+hud-trade-haggle = Haggle
 hud-trade-invite_sent = Trade request sent to { $playername }.
 hud-trade-result-completed = Trade completed successfully.
 hud-trade-result-declined = Trade declined.
@@ -30,3 +31,17 @@ hud-trade-your_offer = Your offer
 hud-trade-their_offer = Their offer
 hud-trade-amount_input = Select an item
 hud-confirm-trade-for-nothing = Really give away these items in exchange for nothing?
+hud-trade-balance = Your offer: ~{ $ours } coins, their offer: ~{ $theirs } coins
+hud-trade-history = History
+hud-trade-history-empty = You haven't traded with anyone yet.
+hud-trade-history-entry = { $partner }: gave { $given }, received { $received }
+hud-trade-history-value = {" "}(~{ $given } for ~{ $received } coins)
+hud-trade-nothing = nothing
+hud-bank = Bank
+hud-bank-capacity = { $used }/{ $capacity } slots used
+hud-bank-upgrade = Upgrade ({ $cost } coins)
+hud-bank-fully_upgraded = Fully upgraded
+hud-trainer = Trainer
+hud-trainer-price = { $cost } coins
+hud-trainer-learn = Learn
+hud-trainer-nothing = There is nothing more I can teach you.
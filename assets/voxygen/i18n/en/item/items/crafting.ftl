@@ -196,9 +196,15 @@ object-v-coin = Coins
 object-collar = Collar
     .desc = Tames neutral wild animals within 5 blocks.
 
+object-hot_air_balloon = Hot Air Balloon
+    .desc = A packed hot air balloon, ready to be inflated and flown. Its burner takes on fuel while it rests on the ground.
+
 object-training_dummy = Training Dummy
     .desc = His name is William. Fire at will.
 
+object-treasure_map = Treasure Map
+    .desc = A weathered sketch of the land around a cross. Read it to learn where to dig.
+
 object-mortar_pestle = Mortar and Pestle
     .desc = Crushes and grinds things into a fine powder or paste. Needed to craft various items.
 
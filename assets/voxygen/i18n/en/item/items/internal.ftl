@@ -49,6 +49,9 @@ common-items-crafting_ing-animal_misc-feather = Feather
 common-items-tag_examples-gnarling = Attire of the Gnarling tribes
     .desc = Worn by Gnarlings and their Chieftains.
 
+common-items-tag_examples-pirate = Pirate garb
+    .desc = Worn by the crews of pirate hideouts.
+
 common-items-flowers-blue = Blue Flower
     .desc = Matches the color of the sky.
 
@@ -7,6 +7,9 @@ object-potion_curious = Curious Potion
 object-potion_agility = Potion of Agility
     .desc = Fly, you fools!
 
+object-potion_purifying = Purifying Potion
+    .desc = Cleanses the body of poisons and curses.
+
 object-potion_big = Large Potion
     .desc = Precious medicine, it makes for the largest rejuvenative flask yet.
 
@@ -19,6 +22,15 @@ object-potion_med = Medium Potion
 object-potion_minor = Minor Potion
     .desc = A small potion concocted from apples and honey.
 
+object-coating_poison = Poison Coating
+    .desc = Coats your weapon in venom that poisons your foes.
+
+object-coating_fire = Fire Oil
+    .desc = Coats your weapon in oil that sets your foes ablaze.
+
+object-coating_frost = Frost Salve
+    .desc = Coats your weapon in a salve that chills your foes to the bone.
+
 object-burning_charm = Blazing Charm
     .desc = Flame is your ally, harness its power to burn your foes.
 
@@ -0,0 +1,16 @@
+skill_book-sword = Treatise on the Blade
+    .desc = Studying it grants 2 sword skill points.
+skill_book-axe = Treatise on the Axe
+    .desc = Studying it grants 2 axe skill points.
+skill_book-hammer = Treatise on the Hammer
+    .desc = Studying it grants 2 hammer skill points.
+skill_book-bow = Treatise on Archery
+    .desc = Studying it grants 2 bow skill points.
+skill_book-staff = Treatise on Fire
+    .desc = Studying it grants 2 fire staff skill points.
+skill_book-sceptre = Treatise on Life
+    .desc = Studying it grants 2 sceptre skill points.
+skill_book-bow_shotgun = Tome of the Arrow Storm
+    .desc = Teaches the burst shot of the bow, as long as you have unlocked the bow.
+skill_book-staff_shockwave = Tome of the Shockwave
+    .desc = Teaches the shockwave of the fire staff, as long as you have unlocked the staff.
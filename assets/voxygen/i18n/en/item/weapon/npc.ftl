@@ -241,6 +241,9 @@ common-items-npc_weapons-unique-maneater = Maneater
 common-items-npc_weapons-unique-mossysnail = Starter Grace
     .desc = Fret not, newbies shant cry.
 
+common-items-npc_weapons-unique-naga_fangs = Naga Fangs
+    .desc = testing123
+
 common-items-npc_weapons-unique-organ = Organ Aura
     .desc = Motivational Tune
 
@@ -331,6 +334,9 @@ common-items-npc_weapons-unique-turret = Turret
 common-items-npc_weapons-unique-tursus_claws = Tursus Claws
     .desc = Was attached to a beast.
 
+common-items-npc_weapons-unique-viper_fangs = Viper Fangs
+    .desc = testing123
+
 common-items-npc_weapons-unique-wealdwyvern = Weald Wyvern
     .desc = testing123
 
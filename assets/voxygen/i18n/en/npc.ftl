@@ -93,9 +93,36 @@ npc-speech-merchant_trade_declined =
 npc-speech-merchant_trade_cancelled_hostile =
     .a0 = Sorry to cut it short, we have a problem to solve here!
     .a1 = We'll trade later, I need to take care of this first!
+npc-speech-merchant_haggle_accept =
+    .a0 = Oh, alright. { $discount }% off, just for you.
+    .a1 = You drive a hard bargain. Fine, { $discount }% off.
+    .a2 = I suppose I could knock { $discount }% off.
+npc-speech-merchant_haggle_decline =
+    .a0 = Sorry, my prices are fair as they are.
+    .a1 = I can't go any lower than that, I'm afraid.
+npc-speech-merchant_haggle_lowball =
+    .a0 = With an offer like that? You must be joking.
+    .a1 = Come back when you're serious.
+npc-speech-merchant_haggle_insulted =
+    .a0 = Haggling, are we? The price just went up.
+    .a1 = Keep that up and you'll be paying double.
+npc-speech-merchant_reputation_rare =
+    .a0 = I keep my finest wares for friends of our people.
+    .a1 = Earn our trust first, then we'll talk about that.
+npc-speech-merchant_haggle_exhausted =
+    .a0 = Enough haggling, do you want to buy something or not?
+    .a1 = My final offer stands.
 npc-speech-ambush =
     .a0 = It's unwise to travel alone!
     .a1 = Like stealing candy from a baby!
+npc-speech-disguise_seen_through =
+    .a0 = Wait a minute... you're not one of us!
+    .a1 = I don't recognise that face. Intruder!
+    .a2 = Nice outfit. Pity it doesn't fit you.
+npc-speech-necromancer_reanimate =
+    .a0 = Rise, and serve me!
+    .a1 = Death is no escape from my will.
+    .a2 = Your rest is over. Get up!
 npc-speech-villager_cultist_alarm =
     .a0 = Lookout! There is a cultist on the loose!
     .a1 = To arms! The cultists are attacking!
@@ -228,6 +255,9 @@ npc-speech-moving_on =
 npc-speech-migrating =
     .a0 = I'm no longer happy living here. Time to migrate to { $site }.
     .a1 = Time to move to { $site }, I've had it with this place.
+npc-speech-migrating_depleted =
+    .a0 = There's nothing left to gather around here. I hear the land near { $site } is bountiful.
+    .a1 = This land has been picked clean, I'm moving to { $site }.
 npc-speech-night_time =
     .a0 = It's dark, time to head home.
     .a1 = I'm tired.
@@ -241,6 +271,10 @@ npc-speech-guard_thought =
     .a0 = My brother's out fighting ogres. What do I get? Guard duty...
     .a1 = Just one more patrol, then I can head home.
     .a2 = No bandits are going to get past me.
+npc-speech-pirate_board =
+    .a0 = Prepare to be boarded!
+    .a1 = Hand over your cargo and nobody gets hurt!
+    .a2 = Grappling hooks, now!
 npc-speech-merchant_sell_undirected =
     .a0 = All my goods are of the highest quality!
     .a1 = Does anybody want to buy my wares?
@@ -262,6 +296,56 @@ npc-speech-tell_site =
     .a1 = You should visit { $site } some time.
     .a2 = If you travel { $dist } { $dir }, you can get to { $site }.
     .a3 = { $dir } you'll find { $site }, it's { $dist }.
+npc-speech-directions =
+    .a0 = { $site }? Follow the road { $dir }, it's { $dist }.
+    .a1 = Head { $dir } along the road, { $site } is { $dist }.
+    .a2 = Ah, { $site }! Stick to the road { $dir } and you can't miss it.
+npc-speech-directions_unknown =
+    .a0 = { $site }? Sorry, I don't know the way there.
+    .a1 = I've never heard of any road to { $site }.
+
+## Where is... queries
+## - $name is the name of the resident that was asked about
+## - $activity references npc-speech-where_is-activity-* variables below
+## - $place references npc-speech-where_is-place-* variables below
+## - $dir references npc-speech-dir-* variables
+## - $dist references npc-speech-dist-* variables
+## - $site represents hard-coded site in the world
+
+npc-speech-where_is =
+    .a0 = { $name } is { $activity } { $place }, { $dist } { $dir } of here.
+    .a1 = Looking for { $name }? Last I saw, they were { $activity } { $place }. That's { $dist } { $dir }.
+npc-speech-where_is_asleep =
+    .a0 = At this hour? { $name } is asleep in one of the houses { $dist } { $dir } of here.
+    .a1 = It's late, { $name } will have turned in. Try the houses { $dist } { $dir }, or come back in the morning.
+npc-speech-where_is_away =
+    .a0 = { $name } is off visiting { $site }, come back later.
+    .a1 = You just missed { $name }, they went to { $site }.
+npc-speech-where_is_travelling =
+    .a0 = { $name } is out on the road, come back later.
+    .a1 = { $name } left town a while ago, I don't know where to.
+npc-speech-where_is_unknown =
+    .a0 = { $name }? Nobody by that name lives here.
+    .a1 = Sorry, I can't help you find { $name }.
+npc-speech-where_is_me =
+    .a0 = That's me! What can I do for you?
+    .a1 = You're looking at them!
+npc-speech-where_is-activity-walking = walking
+npc-speech-where_is-activity-gathering = gathering
+npc-speech-where_is-activity-hunting = hunting
+npc-speech-where_is-activity-celebrating = celebrating
+npc-speech-where_is-activity-sitting = sitting
+npc-speech-where_is-activity-idle = hanging around
+npc-speech-where_is-place-house = by the houses
+npc-speech-where_is-place-tavern = at the tavern
+npc-speech-where_is-place-workshop = at the workshop
+npc-speech-where_is-place-plaza = in the plaza
+npc-speech-where_is-place-docks = down by the docks
+npc-speech-where_is-place-guild_hall = at the guild hall
+npc-speech-where_is-place-arena = at the arena
+npc-speech-where_is-place-temple = at the temple
+npc-speech-where_is-place-streets = out on the streets
+npc-speech-where_is-place-outskirts = on the outskirts of town
 
 npc-speech-site =
     .a0 = Don't you think our village is the best?
@@ -300,6 +384,30 @@ npc-speech-dist_near_to = very close
 ## NPC proposals
 
 npc-speech-arena = Let's sit over there!
+npc-speech-festival_join =
+    .a0 = The festival's on, to the plaza!
+    .a1 = I can hear the music from here!
+    .a2 = I wouldn't miss the festival for the world.
+npc-speech-festival =
+    .a0 = Isn't the festival of { $site } grand?
+    .a1 = The merchants are practically giving their wares away today!
+    .a2 = Stay until nightfall, the fireworks are a sight to behold.
+npc-speech-crier_raid =
+    .a0 = Hear ye! { $site } was raided, keep your wits about you!
+    .a1 = Dark news from { $site }: raiders struck, and not everyone made it.
+npc-speech-crier_bounty =
+    .a0 = Hear ye! A bounty is posted for { $body }, last seen { $dist } { $dir }!
+    .a1 = Wanted: { $body }, { $dir } of here. Ask at the notice board for the reward!
+npc-speech-crier_festival =
+    .a0 = Hear ye! The festival of { $site } is coming, don't miss it!
+    .a1 = Mark the date, { $site } will soon hold its festival!
+npc-speech-crier_site =
+    .a0 = Travellers bring word of { $site }, { $dist } { $dir } of here.
+    .a1 = Hear ye! Those bound for { $site } should head { $dir }.
+npc-speech-crier_no_news =
+    .a0 = Hear ye, hear ye! All is well!
+    .a1 = No news today, and that's good news!
+    .a3 = Dance with me, stranger!
 
 ## NPC reactions
 
@@ -315,6 +423,22 @@ npc-speech-witness_death =
     .a0 = No!
     .a1 = This is terrible!
     .a2 = Oh my goodness!
+npc-speech-witness_wreck =
+    .a0 = She's going down!
+    .a1 = The storm brought them down, I saw it with my own eyes!
+    .a2 = Nobody should be out in weather like this.
+npc-speech-salvage =
+    .a0 = I hear there's an airship come down nearby. Best get there before the scavengers do!
+    .a1 = Somebody ought to clear up that wreck, might as well be me.
+    .a2 = All that cargo just lying about. Seems a shame to let it go to waste.
+npc-speech-fire =
+    .a0 = Fire! Run!
+    .a1 = Everything is burning!
+    .a2 = Get away from the flames!
+npc-speech-fire_guard =
+    .a0 = Fire! Keep back, I'll deal with it!
+    .a1 = Someone fetch water!
+    .a2 = Not on my watch!
 npc-speech-welcome-aboard =
     .a0 = Welcome aboard!
     .a1 = Can I see your ticket... just kidding it's free!
@@ -31,9 +31,16 @@ layout(std140, set = 0, binding = 0) uniform u_globals {
     // 1 - ThirdPerson
     uint cam_mode;
     float sprite_render_distance;
+    // Brightness of the highlight of the selected interactable, 0 disables it
+    float interact_highlight;
+    // How strongly all nearby interactables are revealed, from 0 to 1
+    float interact_reveal;
     float globals_dummy; // Fix alignment.
 };
 
+// Interactables closer than this to the focus are revealed
+const float INTERACT_REVEAL_RANGE = 16.0;
+
 // Specifies the pattern used in the player dithering
 mat4 threshold_matrix = mat4(
     vec4(1.0 / 17.0,  9.0 / 17.0,  3.0 / 17.0, 11.0 / 17.0),
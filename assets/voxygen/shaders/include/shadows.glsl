@@ -17,11 +17,9 @@ layout(set = 1, binding = 3)
 uniform samplerShadow s_directed_shadow_maps;
 // uniform sampler2DArrayShadow t_directed_shadow_maps;
 
-// uniform samplerCubeArrayShadow t_shadow_maps;
-// uniform samplerCubeArray t_shadow_maps;
-// Use with samplerCubeShadow
+// One cube map per point light casting shadows, use with samplerCubeArrayShadow
 layout(set = 1, binding = 0)
-uniform textureCube t_point_shadow_maps;
+uniform textureCubeArray t_point_shadow_maps;
 layout(set = 1, binding = 1)
 uniform samplerShadow s_point_shadow_maps;
 // uniform samplerCube t_shadow_maps;
@@ -68,7 +66,8 @@ const vec3 sampleOffsetDirections[20] = vec3[]
 
 float ShadowCalculationPoint(uint lightIndex, vec3 fragToLight, vec3 fragNorm, /*float currentDepth*/vec3 fragPos)
 {
-    if (lightIndex != 0u) {
+    // Only the nearest lights have a shadow map, see `light_shadow_count.w`
+    if (lightIndex >= light_shadow_count.w) {
         return 1.0;
     };
 
@@ -77,7 +76,7 @@ float ShadowCalculationPoint(uint lightIndex, vec3 fragToLight, vec3 fragNorm, /
 
         // currentDepth = -currentDepth * 0.5 + 0.5;
 
-        float visibility = textureGrad(samplerCubeShadow(t_point_shadow_maps, s_point_shadow_maps), vec4(fragToLight, currentDepth), vec3(0), vec3(0));// / (screen_res.w/* - screen_res.z*/)/*1.0 -bias*//*-(currentDepth - bias) / screen_res.w*//*-screen_res.w*/);
+        float visibility = texture(samplerCubeArrayShadow(t_point_shadow_maps, s_point_shadow_maps), vec4(fragToLight, float(lightIndex)), currentDepth);// / (screen_res.w/* - screen_res.z*/)/*1.0 -bias*//*-(currentDepth - bias) / screen_res.w*//*-screen_res.w*/);
         /* if (visibility == 1.0 || visibility == 0.0) {
             return visibility;
         } */
@@ -99,6 +99,8 @@ const int AIRFLOW = 59;
 const int SPORE = 60;
 const int SURPRISE_EGG = 61;
 const int FLAME_TORNADO = 62;
+const int SNOW_FOOTPRINT = 63;
+const int SAND_FOOTPRINT = 64;
 
 // meters per second squared (acceleration)
 const float earth_gravity = 9.807;
@@ -1053,6 +1055,20 @@ void main() {
                 spin_in_axis(vec3(rand6, rand7, rand8), percent() * 10 + 3 * rand9)
             );
             break;
+        case SNOW_FOOTPRINT:
+        case SAND_FOOTPRINT:
+            f_reflect = 0.0;
+            // Flat print lying on the ground, facing the direction the entity walked in
+            float print_shade = 0.7 + rand0 * 0.05;
+            attr = Attr(
+                vec3(0, 0, 0.01),
+                vec3(3.0, 4.5, 0.2) * (1 - slow_start(0.2)),
+                inst_mode == SNOW_FOOTPRINT
+                    ? vec4(vec3(0.55, 0.6, 0.7) * print_shade, 1)
+                    : vec4(vec3(0.6, 0.45, 0.25) * print_shade, 1),
+                spin_in_axis(vec3(0, 0, 1), atan(inst_dir.y, inst_dir.x) - PI / 2.0)
+            );
+            break;
         default:
             attr = Attr(
                 linear_motion(
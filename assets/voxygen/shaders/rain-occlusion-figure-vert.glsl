@@ -33,7 +33,8 @@ uniform u_rain_occlusion {
     mat4 rain_dir_mat;
     float integrated_rain_vel;
     float rain_density;
-    vec2 occlusion_dummy; // Fix alignment.
+    float snow_cover;
+    float occlusion_dummy; // Fix alignment.
 };
 
 /* Accurate packed shadow maps for many lights at once!
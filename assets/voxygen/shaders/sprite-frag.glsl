@@ -134,7 +134,9 @@ void main() {
 
     surf_color = illuminate(max_light, view_dir, surf_color * emitted_light, surf_color * reflected_light);
 
-    surf_color += f_select * (surf_color + 0.1) * vec3(0.15, 0.15, 0.15);
+    // Glow brightest at the silhouette, so the highlight reads like an outline
+    float rim = pow(1.0 - abs(dot(f_norm, normalize(view_dir))), 2.0);
+    surf_color += f_select * ((surf_color + 0.1) * vec3(0.15, 0.15, 0.15) + rim * vec3(0.35, 0.3, 0.15));
 
     tgt_color = vec4(surf_color, 1.0 - clamp((distance(focus_pos.xy, f_pos.xy) - (sprite_render_distance - FADE_DIST)) / FADE_DIST, 0, 1));
     tgt_mat = uvec4(uvec3((f_norm + 1.0) * 127.0), MAT_FIGURE);
@@ -24,6 +24,7 @@ layout(location = 3) in vec4 inst_mat3;
 // TODO: is there a better way to pack the various vertex attributes?
 // TODO: ori is unused
 layout(location = 4) in uint inst_pos_ori_door;
+// The highest bit is set for sprites that can be interacted with
 layout(location = 5) in uint inst_vert_page; // NOTE: this could fit in less bits
 // TODO: do we need this many bits for light and glow?
 layout(location = 6) in float inst_light;
@@ -92,7 +93,7 @@ void main() {
     f_inst_light = vec2(inst_light, inst_glow);
 
     // Index of the vertex data in the 1D vertex texture
-    int vertex_index = int((uint(gl_VertexIndex) & VERT_PAGE_SIZE_BITS) + inst_vert_page * VERT_PAGE_SIZE);
+    int vertex_index = int((uint(gl_VertexIndex) & VERT_PAGE_SIZE_BITS) + (inst_vert_page & 0x7FFFFFFFu) * VERT_PAGE_SIZE);
     uvec2 pos_atlas_pos_norm_ao = verts[vertex_index];
     uint v_pos_norm = pos_atlas_pos_norm_ao.x;
     uint v_atlas_pos = pos_atlas_pos_norm_ao.y;
@@ -196,8 +197,11 @@ void main() {
     // NOTE: Could defer to fragment shader if we are vert heavy
     f_uv_pos = vec2((uvec2(v_atlas_pos) >> uvec2(0, 16)) & uvec2(0xFFFFu, 0xFFFFu));;
 
-    // Select glowing
-    f_select = (select_pos.w > 0 && select_pos.xyz == sprite_pos) ? 1.0 : 0.0;
+    // Select glowing, and revealing nearby interactables
+    float selected = (select_pos.w > 0 && select_pos.xyz == sprite_pos) ? interact_highlight * 2.0 : 0.0;
+    bool interactable = (inst_vert_page >> 31) != 0u;
+    float revealed = (interactable && distance(sprite_pos, focus_pos.xyz) < INTERACT_REVEAL_RANGE) ? interact_reveal : 0.0;
+    f_select = max(selected, revealed);
 
     gl_Position =
         all_mat *
@@ -308,6 +308,16 @@ void main() {
         }
     #endif
 
+    // Snow settled on top of surfaces exposed to the sky, fading out with distance
+    // since it only reflects the weather around the player
+    if (snow_cover > 0.0 && !faces_fluid && f_norm.z > 0.5) {
+        float snow = clamp((noise_2d((f_pos.xy + focus_off.xy) * 0.15) - 0.5) * 4.0 + snow_cover * 2.0 - 0.5, 0.0, 1.0)
+            * clamp((f_sky_exposure - 0.9) * 10.0, 0.0, 1.0)
+            * clamp(1.0 - distance(f_pos.xy, focus_pos.xy) / 1000.0, 0.0, 1.0);
+        f_col = mix(f_col, vec3(0.8, 0.85, 0.9), snow);
+        k_s = mix(k_s, vec3(0.3), snow);
+    }
+
     #if (REFLECTION_MODE >= REFLECTION_MODE_HIGH)
     // Reflections on ice
     if (f_kind == BLOCK_ICE && f_norm.z == 1.0) {
@@ -565,7 +575,7 @@ void main() {
     }
     #endif
 
-    float f_select = (select_pos.w > 0 && select_pos.xyz == floor(f_pos - f_norm * 0.5)) ? 1.0 : 0.0;
+    float f_select = (select_pos.w > 0 && select_pos.xyz == floor(f_pos - f_norm * 0.5)) ? interact_highlight * 2.0 : 0.0;
     surf_color += f_select * (surf_color + 0.1) * vec3(0.5, 0.5, 0.5);
 
     tgt_color = vec4(surf_color, f_alpha);
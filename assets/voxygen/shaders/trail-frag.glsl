@@ -20,6 +20,8 @@
 
 #include <globals.glsl>
 
+layout(location = 0) in vec4 f_color;
+
 layout(location = 0) out vec4 tgt_color;
 
 #include <sky.glsl>
@@ -29,8 +31,8 @@ layout(location = 0) out vec4 tgt_color;
 const float FADE_DIST = 32.0;
 
 void main() {
-    vec3 trail_color = vec3(.55, .92, 1.0);
-    float trail_alpha = 0.05;
+    vec3 trail_color = f_color.rgb;
+    float trail_alpha = f_color.a;
     // Controls how much light affects alpha variation. TODO: Maybe a better name?
     float light_variable = 0.075;
 
@@ -3,7 +3,11 @@
 #include <globals.glsl>
 
 layout(location = 0) in vec3 v_pos;
+layout(location = 1) in vec4 v_color;
+
+layout(location = 0) out vec4 f_color;
 
 void main() {
+    f_color = v_color;
     gl_Position = all_mat * vec4(v_pos - focus_off.xyz, 1);
 }
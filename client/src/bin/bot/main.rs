@@ -72,6 +72,7 @@ pub fn make_client(
             username,
             password,
             None,
+            None,
             |_| true,
             &|_| {},
             |_| {},
@@ -4,6 +4,7 @@
 
 pub mod addr;
 pub mod error;
+mod prediction;
 
 // Reexports
 pub use crate::error::Error;
@@ -13,15 +14,18 @@ pub use specs::{
     Builder, DispatcherBuilder, Entity as EcsEntity, Join, LendJoin, ReadStorage, World, WorldExt,
 };
 
-use crate::addr::ConnectionArgs;
+use crate::{addr::ConnectionArgs, prediction::Prediction};
 use byteorder::{ByteOrder, LittleEndian};
 use common::{
+    bounty::{Bounty, BountyTarget, NOTICE_BOARD_RANGE},
+    calendar::CalendarEntry,
     character::{CharacterId, CharacterItem},
+    cmd::WorldgenDebugField,
     comp::{
         self,
         chat::KillSource,
         controller::CraftEvent,
-        dialogue::Subject,
+        dialogue::{AskedLocation, Directions, Subject},
         group,
         inventory::item::{modular, tool, ItemKind},
         invite::{InviteKind, InviteResponse},
@@ -33,14 +37,21 @@ use common::{
     },
     event::{EventBus, LocalEvent, PluginHash, UpdateCharacterMetadata},
     grid::Grid,
+    guild::{GuildInfo, GuildManip},
+    heraldry::Heraldry,
     link::Is,
     lod,
+    loot_roll::{LootRollChoice, LootRollId},
+    minigame::Scoreboard,
     mounting::{Rider, VolumePos, VolumeRider},
     outcome::Outcome,
+    race::{self, RaceGhost, RaceProgress},
     recipe::{ComponentRecipeBook, RecipeBookManifest, RepairRecipeBook},
     resources::{GameMode, PlayerEntity, Time, TimeOfDay},
+    rtsim::FactionReputation,
     shared_server_config::ServerConstants,
     spiral::Spiral2d,
+    states::emote::EmoteKind,
     terrain::{
         block::Block, map::MapConfig, neighbors, site::DungeonKindMeta, BiomeKind,
         CoordinateConversions, SiteKindMeta, SpriteKind, TerrainChunk, TerrainChunkSize,
@@ -57,10 +68,12 @@ use common_net::{
     msg::{
         self,
         server::ServerDescription,
-        world_msg::{EconomyInfo, PoiInfo, SiteId, SiteInfo},
+        world_msg::{
+            EconomyInfo, PoiInfo, SiteId, SiteInfo, TreasureMapSnippet, WorldgenDebugOverlay,
+        },
         ChatTypeContext, ClientGeneral, ClientMsg, ClientRegister, ClientType, DisconnectReason,
         InviteAnswer, Notification, PingMsg, PlayerInfo, PlayerListUpdate, RegisterError,
-        ServerGeneral, ServerInit, ServerRegisterAnswer,
+        RegisterStatus, ResumeToken, ServerGeneral, ServerInit, ServerRegisterAnswer,
     },
     sync::WorldSyncExt,
 };
@@ -121,9 +134,20 @@ pub enum Event {
     CharacterJoined(UpdateCharacterMetadata),
     CharacterError(String),
     MapMarker(comp::MapMarkerUpdate),
+    ScoutPings(Vec<comp::ScoutPing>),
     StartSpectate(Vec3<f32>),
     SpectatePosition(Vec3<f32>),
     PluginDataReceived(Vec<u8>),
+    WorldgenDebug(WorldgenDebugField, Option<WorldgenDebugOverlay>),
+    OpenBank(Uid),
+    OpenTrainer(Uid),
+    LootRollEnded {
+        item: comp::FrontendItem,
+        winner: Option<(Uid, LootRollChoice, u32)>,
+    },
+    MinigameEnded(Scoreboard),
+    TreasureMap(TreasureMapSnippet),
+    OpenNoticeBoard,
 }
 
 #[derive(Debug)]
@@ -140,6 +164,11 @@ pub enum ClientInitStage {
     /// Prepare data received by the server to be used by the client (insert
     /// data into the ECS, render map)
     StartingClient,
+    /// The server is full, we're waiting in its login queue
+    Queued {
+        position: u32,
+        estimated_wait: Option<Duration>,
+    },
 }
 
 pub struct WorldData {
@@ -182,6 +211,17 @@ impl WorldData {
     pub fn max_chunk_alt(&self) -> f32 { self.map.2.y }
 }
 
+/// An item dropped for the client's group that the server asked the client to
+/// roll for
+pub struct PendingLootRoll {
+    pub id: LootRollId,
+    pub item: comp::FrontendItem,
+    pub received: Instant,
+    pub timeout: Duration,
+    /// The choice the client sent, if any
+    pub choice: Option<LootRollChoice>,
+}
+
 pub struct SiteInfoRich {
     pub site: SiteInfo,
     pub economy: Option<EconomyInfo>,
@@ -285,6 +325,7 @@ pub struct Client {
     lod_last_requested: Option<Instant>,
     lod_pos_fallback: Option<Vec2<f32>>,
     force_update_counter: u64,
+    prediction: Prediction,
 
     max_group_size: u32,
     // Client has received an invite (inviter uid, time out instant)
@@ -292,10 +333,36 @@ pub struct Client {
     group_leader: Option<Uid>,
     // Note: potentially representable as a client only component
     group_members: HashMap<Uid, group::Role>,
+    group_loot_mode: group::LootMode,
+    loot_rolls: Vec<PendingLootRoll>,
+    // Scores of the minigame the client takes part in
+    minigame: Option<Scoreboard>,
+    // Race track run in progress: the track, where it starts, when it started and
+    // the path taken so far
+    race: Option<(String, Vec3<f32>, f64, RaceGhost)>,
+    // Best run on every race track this session, played back as a ghost
+    race_ghosts: HashMap<String, RaceGhost>,
+    // Bounties offered on the notice board the client read last, and where it
+    // stands
+    notice_board: Option<(Vec3<i32>, Vec<Bounty>)>,
+    // Bounties the character pursues
+    bounties: Vec<Bounty>,
+    // Reputation of the character with the factions that know of it, as of the
+    // last request
+    reputation: Vec<FactionReputation>,
+    // The guild of the player, which belongs to the account rather than the
+    // character
+    guild: Option<GuildInfo>,
+    // Ongoing and upcoming events of the server's event calendar
+    event_calendar: Vec<CalendarEntry>,
+    // Directions an NPC gave the character, until it arrives
+    directions: Option<Directions>,
     // Pending invites that this client has sent out
     pending_invites: HashSet<Uid>,
     // The pending trade the client is involved in, and it's id
     pending_trade: Option<(TradeId, PendingTrade, Option<SitePrices>)>,
+    // Allows resuming the current session if the connection is lost
+    resume_token: Option<ResumeToken>,
 
     network: Option<Network>,
     participant: Option<Participant>,
@@ -436,6 +503,7 @@ impl Client {
         username: &str,
         password: &str,
         locale: Option<String>,
+        resume_token: Option<ResumeToken>,
         auth_trusted: impl FnMut(&str) -> bool,
         init_stage_update: &(dyn Fn(ClientInitStage) + Send + Sync),
         add_foreign_systems: impl Fn(&mut DispatcherBuilder) + Send + 'static,
@@ -606,9 +674,11 @@ impl Client {
             username,
             password,
             locale,
+            resume_token,
             auth_trusted,
             &server_info,
             &mut register_stream,
+            init_stage_update,
         )
         .await?;
 
@@ -1027,13 +1097,26 @@ impl Client {
             lod_pos_fallback: None,
 
             force_update_counter: 0,
+            prediction: Prediction::default(),
 
             max_group_size,
             invite: None,
             group_leader: None,
             group_members: HashMap::new(),
+            group_loot_mode: group::LootMode::default(),
+            loot_rolls: Vec::new(),
+            minigame: None,
+            race: None,
+            race_ghosts: HashMap::new(),
+            notice_board: None,
+            bounties: Vec::new(),
+            reputation: Vec::new(),
+            guild: None,
+            event_calendar: Vec::new(),
+            directions: None,
             pending_invites: HashSet::new(),
             pending_trade: None,
+            resume_token: None,
 
             network: Some(network),
             participant: Some(participant),
@@ -1076,9 +1159,11 @@ impl Client {
         username: &str,
         password: &str,
         locale: Option<String>,
+        resume_token: Option<ResumeToken>,
         mut auth_trusted: impl FnMut(&str) -> bool,
         server_info: &ServerInfo,
         register_stream: &mut Stream,
+        init_stage_update: &(dyn Fn(ClientInitStage) + Send + Sync),
     ) -> Result<(), Error> {
         // Authentication
         let token_or_username = match &server_info.auth_provider {
@@ -1116,19 +1201,32 @@ impl Client {
         register_stream.send(ClientRegister {
             token_or_username,
             locale,
+            resume_token,
         })?;
 
-        match register_stream.recv::<ServerRegisterAnswer>().await? {
-            Err(RegisterError::AuthError(err)) => Err(Error::AuthErr(err)),
-            Err(RegisterError::InvalidCharacter) => Err(Error::InvalidCharacter),
-            Err(RegisterError::NotOnWhitelist) => Err(Error::NotOnWhitelist),
-            Err(RegisterError::Kicked(err)) => Err(Error::Kicked(err)),
-            Err(RegisterError::Banned(reason)) => Err(Error::Banned(reason)),
-            Err(RegisterError::TooManyPlayers) => Err(Error::TooManyPlayers),
-            Ok(()) => {
-                debug!("Client registered successfully.");
-                Ok(())
-            },
+        loop {
+            match register_stream.recv::<ServerRegisterAnswer>().await? {
+                Err(RegisterError::AuthError(err)) => break Err(Error::AuthErr(err)),
+                Err(RegisterError::InvalidCharacter) => break Err(Error::InvalidCharacter),
+                Err(RegisterError::NotOnWhitelist) => break Err(Error::NotOnWhitelist),
+                Err(RegisterError::Kicked(err)) => break Err(Error::Kicked(err)),
+                Err(RegisterError::Banned(reason)) => break Err(Error::Banned(reason)),
+                Err(RegisterError::TooManyPlayers) => break Err(Error::TooManyPlayers),
+                Ok(RegisterStatus::Queued {
+                    position,
+                    estimated_wait,
+                }) => {
+                    debug!(?position, "Waiting in login queue");
+                    init_stage_update(ClientInitStage::Queued {
+                        position,
+                        estimated_wait,
+                    });
+                },
+                Ok(RegisterStatus::Registered) => {
+                    debug!("Client registered successfully.");
+                    break Ok(());
+                },
+            }
         }
     }
 
@@ -1169,7 +1267,7 @@ impl Client {
                     | ClientGeneral::Character(_, _)
                     | ClientGeneral::Spectate(_) => &mut self.character_screen_stream,
                     // Only in game
-                    ClientGeneral::ControllerInputs(_)
+                    ClientGeneral::ControllerInputs { .. }
                     | ClientGeneral::ControlEvent(_)
                     | ClientGeneral::ControlAction(_)
                     | ClientGeneral::SetViewDistance(_)
@@ -1395,6 +1493,36 @@ impl Client {
         self.control_action(ControlAction::InventoryAction(InventoryAction::Sort));
     }
 
+    pub fn bank_deposit(&mut self, banker: Uid, slot: InvSlotId) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::BankDeposit { banker, slot },
+        )));
+    }
+
+    pub fn bank_withdraw(&mut self, banker: Uid, slot: usize) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::BankWithdraw { banker, slot },
+        )));
+    }
+
+    pub fn bank_upgrade(&mut self, banker: Uid) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::BankUpgrade { banker },
+        )));
+    }
+
+    pub fn learn_skill(&mut self, trainer: Uid, skill: Skill) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::LearnSkill { trainer, skill },
+        )));
+    }
+
+    pub fn set_heraldry(&mut self, slot: Slot, heraldry: Option<Heraldry>) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::SetHeraldry { slot, heraldry },
+        )));
+    }
+
     pub fn perform_trade_action(&mut self, action: TradeAction) {
         if let Some((id, _, _)) = self.pending_trade {
             if let TradeAction::Decline = action {
@@ -1406,6 +1534,19 @@ impl Client {
         }
     }
 
+    /// Haggle with the other party of the pending trade over its prices
+    pub fn haggle(&mut self) {
+        if let Some(uid) = self.uid()
+            && let Some((_, trade, Some(_))) = &self.pending_trade
+            && let Some(partner) = trade.parties.iter().find(|party| **party != uid).copied()
+        {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::Interact(
+                partner,
+                Subject::Haggle,
+            )));
+        }
+    }
+
     pub fn is_dead(&self) -> bool { self.current::<comp::Health>().map_or(false, |h| h.is_dead) }
 
     pub fn is_gliding(&self) -> bool {
@@ -1466,6 +1607,16 @@ impl Client {
         }
     }
 
+    pub fn revive(&mut self, target_entity: EcsEntity) {
+        if self.is_dead() {
+            return;
+        }
+
+        if let Some(target_uid) = self.state.read_component_copied(target_entity) {
+            self.control_action(ControlAction::Revive { target_uid });
+        }
+    }
+
     pub fn npc_interact(&mut self, npc_entity: EcsEntity, subject: Subject) {
         // If we're dead, exit before sending message
         if self.is_dead() {
@@ -1479,6 +1630,19 @@ impl Client {
         }
     }
 
+    /// Ask an NPC the way to a site
+    pub fn ask_directions(&mut self, npc_entity: EcsEntity, site: SiteId) {
+        if let Some(site) = self.sites.get(&site).map(|site| &site.site)
+            && let Some(name) = site.name.clone()
+        {
+            let location = AskedLocation {
+                name,
+                origin: site.wpos,
+            };
+            self.npc_interact(npc_entity, Subject::Location(location));
+        }
+    }
+
     pub fn player_list(&self) -> &HashMap<Uid, PlayerInfo> { &self.player_list }
 
     pub fn character_list(&self) -> &CharacterList { &self.character_list }
@@ -1738,6 +1902,51 @@ impl Client {
 
     pub fn group_members(&self) -> &HashMap<Uid, group::Role> { &self.group_members }
 
+    pub fn group_loot_mode(&self) -> group::LootMode { self.group_loot_mode }
+
+    /// Loot rolls the client still has to choose for, oldest first
+    pub fn loot_rolls(&self) -> impl Iterator<Item = &PendingLootRoll> {
+        self.loot_rolls.iter().filter(|roll| roll.choice.is_none())
+    }
+
+    /// Latest scores of the minigame the client takes part in
+    pub fn minigame(&self) -> Option<&Scoreboard> { self.minigame.as_ref() }
+
+    /// Bounties offered on the notice board the client read last, as long as
+    /// the character stands next to it
+    pub fn notice_board(&self) -> Option<&[Bounty]> {
+        let (board, bounties) = self.notice_board.as_ref()?;
+        let pos = self.position()?;
+        (pos.distance_squared(board.as_() + 0.5) <= NOTICE_BOARD_RANGE.powi(2))
+            .then_some(bounties.as_slice())
+    }
+
+    pub fn close_notice_board(&mut self) { self.notice_board = None; }
+
+    /// Bounties the character pursues
+    pub fn bounties(&self) -> &[Bounty] { &self.bounties }
+
+    pub fn reputation(&self) -> &[FactionReputation] { &self.reputation }
+
+    pub fn guild(&self) -> Option<&GuildInfo> { self.guild.as_ref() }
+
+    /// Ongoing and upcoming events of the server's event calendar, soonest
+    /// first
+    pub fn event_calendar(&self) -> &[CalendarEntry] { &self.event_calendar }
+
+    /// The rest of the way to the site an NPC gave directions to, without the
+    /// points the character passed already
+    pub fn directions(&self) -> Option<&Directions> { self.directions.as_ref() }
+
+    /// Where the best run on the race track the client is running was at this
+    /// point of the current run
+    pub fn race_ghost_pos(&self) -> Option<Vec3<f32>> {
+        let (track, _, started_at, _) = self.race.as_ref()?;
+        self.race_ghosts
+            .get(track)?
+            .pos_at(self.state.get_time() - started_at)
+    }
+
     pub fn pending_invites(&self) -> &HashSet<Uid> { &self.pending_invites }
 
     pub fn pending_trade(&self) -> &Option<(TradeId, PendingTrade, Option<SitePrices>)> {
@@ -1786,6 +1995,25 @@ impl Client {
         )));
     }
 
+    pub fn set_group_loot_mode(&mut self, loot_mode: group::LootMode) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::GroupManip(
+            GroupManip::SetLootMode(loot_mode),
+        )));
+    }
+
+    pub fn choose_loot_roll(&mut self, id: LootRollId, choice: LootRollChoice) {
+        if let Some(roll) = self
+            .loot_rolls
+            .iter_mut()
+            .find(|roll| roll.id == id && roll.choice.is_none())
+        {
+            roll.choice = Some(choice);
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::GroupManip(
+                GroupManip::LootRoll(id, choice),
+            )));
+        }
+    }
+
     pub fn is_riding(&self) -> bool {
         self.state
             .ecs()
@@ -1831,6 +2059,13 @@ impl Client {
         }
     }
 
+    /// Send a bird companion out to scout around the map marker.
+    pub fn send_scout(&mut self, entity: EcsEntity) {
+        if let Some(uid) = self.state.read_component_copied(entity) {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::SendScout(uid)));
+        }
+    }
+
     pub fn respawn(&mut self) {
         if self
             .state
@@ -1867,6 +2102,31 @@ impl Client {
         write
     }
 
+    /// Returns the living group member a dead player can spectate after
+    /// `current`, or `None` once all of them have been cycled through.
+    pub fn next_group_spectate_target(&self, current: Option<EcsEntity>) -> Option<EcsEntity> {
+        let ecs = self.state.ecs();
+        let id_maps = ecs.read_resource::<IdMaps>();
+        let healths = ecs.read_storage::<comp::Health>();
+        let positions = ecs.read_storage::<comp::Pos>();
+        let mut members = self
+            .group_members
+            .iter()
+            .filter(|(_, role)| matches!(role, group::Role::Member))
+            .filter_map(|(uid, _)| id_maps.uid_entity(*uid))
+            .filter(|entity| {
+                *entity != self.entity()
+                    && positions.contains(*entity)
+                    && healths.get(*entity).map_or(false, |health| !health.is_dead)
+            })
+            .collect::<Vec<_>>();
+        members.sort();
+        match current {
+            Some(current) => members.into_iter().find(|entity| *entity > current),
+            None => members.first().copied(),
+        }
+    }
+
     /// Checks whether a player can swap their weapon+ability `Loadout` settings
     /// and sends the `ControlAction` event that signals to do the swap.
     pub fn swap_loadout(&mut self) { self.control_action(ControlAction::SwapEquippedWeapons) }
@@ -1919,6 +2179,8 @@ impl Client {
         }
     }
 
+    pub fn emote(&mut self, kind: EmoteKind) { self.control_action(ControlAction::Emote(kind)); }
+
     pub fn utter(&mut self, kind: UtteranceKind) {
         self.send_msg(ClientGeneral::ControlEvent(ControlEvent::Utterance(kind)));
     }
@@ -1977,6 +2239,56 @@ impl Client {
         )));
     }
 
+    pub fn activate_shrine(&mut self, shrine: Uid) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::ActivateShrine(
+            shrine,
+        )));
+    }
+
+    pub fn travel_to_shrine(&mut self, site: SiteId) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::TravelToShrine(
+            site,
+        )));
+    }
+
+    pub fn read_notice_board(&mut self, pos: Vec3<i32>) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::ReadNoticeBoard(
+            pos,
+        )));
+    }
+
+    /// Accepts a bounty from the notice board the client read last
+    pub fn accept_bounty(&mut self, target: BountyTarget) {
+        if let Some((board, _)) = &self.notice_board {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::AcceptBounty {
+                board: *board,
+                target,
+            }));
+        }
+    }
+
+    pub fn request_reputation(&mut self) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::RequestReputation));
+    }
+
+    /// Shows one of the unlocked titles of the character under their name, or
+    /// none
+    pub fn select_title(&mut self, title: Option<comp::Title>) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::SelectTitle(
+            title,
+        )));
+    }
+
+    pub fn guild_manip(&mut self, manip: GuildManip) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::GuildManip(manip)));
+    }
+
+    pub fn recover_gravestone(&mut self, gravestone: Uid) {
+        self.send_msg(ClientGeneral::ControlEvent(
+            ControlEvent::RecoverGravestone(gravestone),
+        ));
+    }
+
     fn control_action(&mut self, control_action: ControlAction) {
         if let Some(controller) = self
             .state
@@ -2122,6 +2434,12 @@ impl Client {
         }))
     }
 
+    pub fn switch_ability_loadout(&mut self, loadout: usize) {
+        self.send_msg(ClientGeneral::ControlEvent(
+            ControlEvent::SwitchAbilityLoadout(loadout),
+        ));
+    }
+
     /// Execute a single client tick, handle input and update the game state by
     /// the given duration.
     pub fn tick(&mut self, inputs: ControllerInputs, dt: Duration) -> Result<Vec<Event>, Error> {
@@ -2170,7 +2488,11 @@ impl Client {
                     "Couldn't access controller component on client entity"
                 );
             }
-            self.send_msg_err(ClientGeneral::ControllerInputs(Box::new(inputs)))?;
+            let input_seq = self.prediction.next_input_seq();
+            self.send_msg_err(ClientGeneral::ControllerInputs {
+                inputs: Box::new(inputs),
+                input_seq,
+            })?;
         }
 
         // 2) Build up a list of events for this frame, to be passed to the frontend.
@@ -2231,6 +2553,44 @@ impl Client {
             |_, _| {},
         );
 
+        // Smooth out corrections of the predicted movement, and remember the prediction
+        // to check it against the server later
+        if self.presence.is_some() {
+            let entity = self.entity();
+            let ecs = self.state.ecs();
+            if let Some(pos) = ecs.write_storage::<comp::Pos>().get_mut(entity) {
+                pos.0 += self.prediction.correction_step(dt.as_secs_f32());
+                let vel = ecs
+                    .read_storage::<comp::Vel>()
+                    .get(entity)
+                    .map_or(Vec3::zero(), |vel| vel.0);
+                self.prediction.record(pos.0, vel);
+            }
+        }
+
+        // Record the path of the current race run, the time only starts once the start
+        // of the track was left
+        let entity = self.entity();
+        if let Some((_, start, started_at, ghost)) = &mut self.race
+            && let Some(pos) = self.state.read_storage::<comp::Pos>().get(entity).copied()
+        {
+            let time = self.state.get_time();
+            if race::passes_checkpoint(pos.0, *start) {
+                *started_at = time;
+                *ghost = RaceGhost::default();
+            } else {
+                ghost.record(time - *started_at, pos.0);
+            }
+        }
+
+        // Follow the directions given by an NPC, they're forgotten on arrival
+        if let Some(directions) = &mut self.directions
+            && let Some(pos) = self.state.read_storage::<comp::Pos>().get(entity)
+            && directions.follow(pos.0.xy())
+        {
+            self.directions = None;
+        }
+
         // TODO: avoid emitting these in the first place OR actually use outcomes
         // generated locally on the client (if they can be deduplicated from
         // ones that the server generates or if the client can reliably generate
@@ -2474,6 +2834,64 @@ impl Client {
                     );
                 }
             },
+            ServerGeneral::PlayerListUpdate(PlayerListUpdate::VerifiedBadge(uid, verified)) => {
+                if let Some(player_info) = self.player_list.get_mut(&uid) {
+                    player_info.verified_badge = verified;
+                } else {
+                    warn!(
+                        "Received msg to update verified badge of uid {}, but they were not in \
+                         the list.",
+                        uid
+                    );
+                }
+            },
+            ServerGeneral::PlayerListUpdate(PlayerListUpdate::Faction(uid, faction)) => {
+                if let Some(player_info) = self.player_list.get_mut(&uid) {
+                    player_info.faction = faction;
+                } else {
+                    warn!(
+                        "Received msg to update faction of uid {}, but they were not in the list.",
+                        uid
+                    );
+                }
+            },
+            ServerGeneral::PlayerListUpdate(PlayerListUpdate::GuildTag(uid, guild_tag)) => {
+                if let Some(player_info) = self.player_list.get_mut(&uid) {
+                    player_info.guild_tag = guild_tag;
+                } else {
+                    warn!(
+                        "Received msg to update guild tag of uid {}, but they were not in the \
+                         list.",
+                        uid
+                    );
+                }
+            },
+            ServerGeneral::PlayerListUpdate(PlayerListUpdate::GuildBanner(uid, guild_banner)) => {
+                if let Some(player_info) = self.player_list.get_mut(&uid) {
+                    player_info.guild_banner = guild_banner;
+                } else {
+                    warn!(
+                        "Received msg to update guild banner of uid {}, but they were not in the \
+                         list.",
+                        uid
+                    );
+                }
+            },
+            ServerGeneral::PlayerListUpdate(PlayerListUpdate::Title(uid, title)) => {
+                if let Some(character) = self
+                    .player_list
+                    .get_mut(&uid)
+                    .and_then(|player_info| player_info.character.as_mut())
+                {
+                    character.title = title;
+                } else {
+                    warn!(
+                        "Received msg to update title of uid {}, but they have no character in \
+                         the list.",
+                        uid
+                    );
+                }
+            },
             ServerGeneral::PlayerListUpdate(PlayerListUpdate::SelectedCharacter(
                 uid,
                 char_info,
@@ -2494,6 +2912,7 @@ impl Client {
                         Some(character) => Some(msg::CharacterInfo {
                             name: character.name.to_string(),
                             gender: character.gender,
+                            title: character.title,
                         }),
                         None => {
                             warn!(
@@ -2608,6 +3027,10 @@ impl Client {
                     .apply_entity_sync_package(entity_sync_package, uid);
             },
             ServerGeneral::CompSync(comp_sync_package, force_counter) => {
+                // The server moved us, so our predictions are meaningless now
+                if force_counter != self.force_update_counter {
+                    self.prediction.reset();
+                }
                 self.force_update_counter = force_counter;
                 self.state
                     .ecs_mut()
@@ -2626,6 +3049,8 @@ impl Client {
             ServerGeneral::Notification(n) => {
                 frontend_events.push(Event::Notification(n));
             },
+            ServerGeneral::GuildUpdate(guild) => self.guild = guild,
+            ServerGeneral::EventCalendar(calendar) => self.event_calendar = calendar,
             ServerGeneral::PluginData(d) => {
                 let plugin_len = d.len();
                 tracing::info!(?plugin_len, "plugin data");
@@ -2707,6 +3132,7 @@ impl Client {
                     NewGroup { leader, members } => {
                         self.group_leader = Some(leader);
                         self.group_members = members.into_iter().collect();
+                        self.group_loot_mode = group::LootMode::default();
                         // Currently add/remove messages treat client as an implicit member
                         // of the group whereas this message explicitly includes them so to
                         // be consistent for now we will remove the client from the
@@ -2719,8 +3145,24 @@ impl Client {
                     NoGroup => {
                         self.group_leader = None;
                         self.group_members = HashMap::new();
+                        self.group_loot_mode = group::LootMode::default();
                         frontend_events.push(Event::MapMarker(comp::MapMarkerUpdate::ClearGroup));
                     },
+                    LootMode(loot_mode) => {
+                        self.group_loot_mode = loot_mode;
+                        frontend_events.push(Event::Chat(
+                            comp::ChatType::GroupMeta("Group".into()).into_msg(
+                                comp::Content::localized(match loot_mode {
+                                    group::LootMode::FreeForAll => {
+                                        "hud-group-loot_mode-free_for_all-changed"
+                                    },
+                                    group::LootMode::NeedGreed => {
+                                        "hud-group-loot_mode-need_greed-changed"
+                                    },
+                                }),
+                            ),
+                        ));
+                    },
                 }
             },
             ServerGeneral::Invite {
@@ -2758,6 +3200,7 @@ impl Client {
             // Cleanup for when the client goes back to the `presence = None`
             ServerGeneral::ExitInGameSuccess => {
                 self.presence = None;
+                self.resume_token = None;
                 self.clean_state();
             },
             ServerGeneral::InventoryUpdate(inventory, events) => {
@@ -2830,18 +3273,102 @@ impl Client {
             ServerGeneral::MapMarker(event) => {
                 frontend_events.push(Event::MapMarker(event));
             },
+            ServerGeneral::ScoutPings(pings) => {
+                frontend_events.push(Event::ScoutPings(pings));
+            },
             ServerGeneral::WeatherUpdate(weather) => {
                 self.weather.weather_update(weather);
             },
             ServerGeneral::LocalWindUpdate(wind) => {
                 self.weather.local_wind_update(wind);
             },
+            ServerGeneral::PlayerPhysicsAck {
+                input_seq,
+                pos,
+                vel,
+            } => {
+                if let Some((pos_fix, vel_fix)) =
+                    self.prediction.acknowledge(input_seq, pos.0, vel.0)
+                {
+                    let entity = self.entity();
+                    let ecs = self.state.ecs();
+                    if let Some(pos) = ecs.write_storage::<comp::Pos>().get_mut(entity) {
+                        pos.0 += pos_fix;
+                    }
+                    if let Some(vel) = ecs.write_storage::<comp::Vel>().get_mut(entity) {
+                        vel.0 += vel_fix;
+                    }
+                }
+            },
             ServerGeneral::SpectatePosition(pos) => {
                 frontend_events.push(Event::SpectatePosition(pos));
             },
             ServerGeneral::UpdateRecipes => {
                 self.update_available_recipes();
             },
+            ServerGeneral::SessionToken(token) => self.resume_token = Some(token),
+            ServerGeneral::WorldgenDebug(field, overlay) => {
+                frontend_events.push(Event::WorldgenDebug(field, overlay));
+            },
+            ServerGeneral::LootRoll { id, item, timeout } => {
+                self.loot_rolls.push(PendingLootRoll {
+                    id,
+                    item,
+                    received: Instant::now(),
+                    timeout,
+                    choice: None,
+                });
+            },
+            ServerGeneral::LootRollEnded { id, winner } => {
+                if let Some(index) = self.loot_rolls.iter().position(|roll| roll.id == id) {
+                    let roll = self.loot_rolls.remove(index);
+                    frontend_events.push(Event::LootRollEnded {
+                        item: roll.item,
+                        winner,
+                    });
+                } else {
+                    warn!("Received the result of a loot roll that was not pending");
+                }
+            },
+            ServerGeneral::MinigameScoreboard(scoreboard) => self.minigame = Some(scoreboard),
+            ServerGeneral::MinigameEnded(scoreboard) => {
+                self.minigame = None;
+                frontend_events.push(Event::MinigameEnded(scoreboard));
+            },
+            ServerGeneral::RaceProgress(progress) => match progress {
+                RaceProgress::Started { track, start } => {
+                    self.race = Some((track, start, self.state.get_time(), RaceGhost::default()));
+                },
+                RaceProgress::Finished {
+                    track,
+                    personal_best,
+                    ..
+                } => {
+                    if let Some((run_track, _, _, ghost)) = self.race.take()
+                        && run_track == track
+                        && (personal_best || !self.race_ghosts.contains_key(&track))
+                    {
+                        self.race_ghosts.insert(track, ghost);
+                    }
+                },
+                RaceProgress::Aborted => self.race = None,
+            },
+            ServerGeneral::TreasureMap(snippet) => {
+                frontend_events.push(Event::TreasureMap(snippet));
+            },
+            ServerGeneral::NoticeBoard { pos, bounties } => {
+                self.notice_board = Some((pos, bounties));
+                frontend_events.push(Event::OpenNoticeBoard);
+            },
+            ServerGeneral::Bounties(bounties) => self.bounties = bounties,
+            ServerGeneral::Reputation(reputation) => self.reputation = reputation,
+            ServerGeneral::OpenBank(banker) => {
+                frontend_events.push(Event::OpenBank(banker));
+            },
+            ServerGeneral::OpenTrainer(trainer) => {
+                frontend_events.push(Event::OpenTrainer(trainer));
+            },
+            ServerGeneral::Directions(directions) => self.directions = Some(directions),
             _ => unreachable!("Not a in_game message"),
         }
         Ok(())
@@ -2908,6 +3435,15 @@ impl Client {
                 events.push(Event::StartSpectate(spawn_point));
                 debug!("client is now in ingame state on server");
             },
+            ServerGeneral::SessionResumed {
+                presence,
+                view_distances,
+            } => {
+                debug!("client resumed its previous session");
+                self.presence = Some(presence);
+                self.set_view_distances_local(view_distances);
+                events.push(Event::CharacterJoined(UpdateCharacterMetadata::default()));
+            },
             _ => unreachable!("Not a character_screen msg"),
         }
         Ok(())
@@ -3044,6 +3580,10 @@ impl Client {
 
     pub fn registered(&self) -> bool { self.registered }
 
+    /// Token that allows resuming the current session after losing the
+    /// connection, if the server handed one out.
+    pub fn resume_token(&self) -> Option<ResumeToken> { self.resume_token }
+
     pub fn get_tick(&self) -> u64 { self.tick }
 
     pub fn get_ping_ms(&self) -> f64 { self.last_ping_delta * 1000.0 }
@@ -3191,10 +3731,12 @@ impl Client {
             | comp::ChatType::NpcSay(uid)
             | comp::ChatType::Group(uid, _)
             | comp::ChatType::Faction(uid, _)
+            | comp::ChatType::Guild(uid, _)
             | comp::ChatType::Npc(uid) => add_data_of(uid),
             comp::ChatType::CommandError
             | comp::ChatType::CommandInfo
             | comp::ChatType::FactionMeta(_)
+            | comp::ChatType::GuildMeta(_)
             | comp::ChatType::GroupMeta(_)
             | comp::ChatType::Meta => (),
         };
@@ -3353,6 +3895,7 @@ mod tests {
             username,
             password,
             None,
+            None,
             |suggestion: &str| suggestion == auth_server,
             &|_| {},
             |_| {},
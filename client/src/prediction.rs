@@ -0,0 +1,154 @@
+//! Prediction of the player's own movement while the server is authoritative
+//! over its physics, so that the player doesn't have to wait a round trip for
+//! their inputs to take effect.
+
+use std::collections::VecDeque;
+use vek::*;
+
+/// How many predicted states are kept around while waiting for the server to
+/// acknowledge the inputs that produced them
+const MAX_PENDING: usize = 256;
+/// Errors beyond this distance (in blocks) are corrected at once rather than
+/// smoothed out, the prediction went too far off to hide it anyway
+const SNAP_DISTANCE: f32 = 4.0;
+/// How quickly outstanding corrections are applied to the position, per second
+const CORRECTION_RATE: f32 = 10.0;
+
+/// Physics state predicted after applying the inputs with sequence number `seq`
+struct PredictedState {
+    seq: u64,
+    pos: Vec3<f32>,
+    vel: Vec3<f32>,
+}
+
+#[derive(Default)]
+pub(crate) struct Prediction {
+    next_seq: u64,
+    pending: VecDeque<PredictedState>,
+    /// Part of the corrections so far that hasn't been applied to the position
+    /// yet
+    correction: Vec3<f32>,
+}
+
+impl Prediction {
+    /// Sequence number for the next inputs sent to the server
+    pub fn next_input_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        seq
+    }
+
+    /// Remembers the state predicted from the latest inputs, to check it
+    /// against the server once it acknowledges them
+    pub fn record(&mut self, pos: Vec3<f32>, vel: Vec3<f32>) {
+        let Some(seq) = self.next_seq.checked_sub(1) else {
+            return;
+        };
+        if self.pending.len() >= MAX_PENDING {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(PredictedState {
+            // Predict where we end up once the outstanding correction is applied
+            pos: pos + self.correction,
+            vel,
+            seq,
+        });
+    }
+
+    /// Reconciles the prediction with the state the server reached after
+    /// applying the inputs up to `seq`. The movement caused by the inputs the
+    /// server hasn't seen yet is replayed on top of that state, by shifting it
+    /// by the prediction error.
+    ///
+    /// Returns the adjustments to make to the current position and velocity.
+    pub fn acknowledge(
+        &mut self,
+        seq: u64,
+        pos: Vec3<f32>,
+        vel: Vec3<f32>,
+    ) -> Option<(Vec3<f32>, Vec3<f32>)> {
+        while self.pending.front().map_or(false, |state| state.seq < seq) {
+            self.pending.pop_front();
+        }
+        if self.pending.front()?.seq != seq {
+            return None;
+        }
+        let predicted = self.pending.pop_front()?;
+
+        let pos_error = pos - predicted.pos;
+        let vel_error = vel - predicted.vel;
+        for state in &mut self.pending {
+            state.pos += pos_error;
+            state.vel += vel_error;
+        }
+
+        if pos_error.magnitude_squared() > SNAP_DISTANCE.powi(2) {
+            let pos_fix = self.correction + pos_error;
+            self.correction = Vec3::zero();
+            Some((pos_fix, vel_error))
+        } else {
+            self.correction += pos_error;
+            Some((Vec3::zero(), vel_error))
+        }
+    }
+
+    /// Part of the outstanding correction to apply to the position this tick
+    pub fn correction_step(&mut self, dt: f32) -> Vec3<f32> {
+        let step = self.correction * (1.0 - (-CORRECTION_RATE * dt).exp());
+        self.correction -= step;
+        step
+    }
+
+    /// Forgets all predictions, e.g. because the server moved the player
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.correction = Vec3::zero();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unacknowledged_movement_is_kept() {
+        let mut prediction = Prediction::default();
+        for i in 0..3 {
+            prediction.next_input_seq();
+            prediction.record(Vec3::unit_x() * i as f32, Vec3::unit_x());
+        }
+
+        // The server ended up half a block further along after the first input
+        let (pos_fix, vel_fix) = prediction
+            .acknowledge(0, Vec3::new(0.5, 0.0, 0.0), Vec3::unit_x())
+            .unwrap();
+        assert_eq!((pos_fix, vel_fix), (Vec3::zero(), Vec3::zero()));
+        assert_eq!(prediction.correction, Vec3::new(0.5, 0.0, 0.0));
+
+        // The following predictions are shifted by the same error, so acknowledging
+        // them with the matching state needs no further correction
+        assert_eq!(
+            prediction.acknowledge(2, Vec3::new(2.5, 0.0, 0.0), Vec3::unit_x()),
+            Some((Vec3::zero(), Vec3::zero()))
+        );
+        assert_eq!(prediction.correction, Vec3::new(0.5, 0.0, 0.0));
+        assert!(
+            prediction
+                .acknowledge(2, Vec3::zero(), Vec3::zero())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn large_errors_snap() {
+        let mut prediction = Prediction::default();
+        prediction.next_input_seq();
+        prediction.record(Vec3::zero(), Vec3::zero());
+
+        let (pos_fix, _) = prediction
+            .acknowledge(0, Vec3::new(0.0, 10.0, 0.0), Vec3::zero())
+            .unwrap();
+        assert_eq!(pos_fix, Vec3::new(0.0, 10.0, 0.0));
+        assert_eq!(prediction.correction_step(1.0), Vec3::zero());
+    }
+}
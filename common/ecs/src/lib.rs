@@ -1,7 +1,9 @@
 mod metrics;
+mod schedule;
 mod system;
 
 pub use metrics::{PhysicsMetrics, SysMetrics};
+pub use schedule::{analyze_schedule, ScheduleStats, SystemInfo, SystemSchedule};
 pub use system::{
     dispatch, gen_stats, run_now, CpuTimeStats, CpuTimeline, Job, Origin, ParMode, Phase, System,
 };
@@ -1,9 +1,11 @@
-use crate::system::CpuTimeline;
+use crate::{schedule::SystemInfo, system::CpuTimeline};
 use std::{collections::HashMap, sync::Mutex};
 
 #[derive(Default)]
 pub struct SysMetrics {
     pub stats: Mutex<HashMap<String, CpuTimeline>>,
+    /// Systems that ran so far, by [`crate::System::sys_name`]
+    pub systems: Mutex<HashMap<String, SystemInfo>>,
 }
 
 #[derive(Default)]
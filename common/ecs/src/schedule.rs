@@ -0,0 +1,214 @@
+use crate::system::CpuTimeline;
+use std::{collections::HashMap, time::Instant};
+
+/// A system added with [`crate::dispatch`], recorded the first time it runs so
+/// that its measurements can be related to the systems it depends on
+#[derive(Clone, Debug)]
+pub struct SystemInfo {
+    pub name: &'static str,
+    /// [`crate::System::sys_name`] of the systems that have to finish before
+    /// this one can start
+    pub deps: Vec<String>,
+}
+
+/// How a system was scheduled during a tick. All times are in ns, relative to
+/// the start of the tick.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemSchedule {
+    pub start_ns: u64,
+    pub end_ns: u64,
+    /// Time between the last dependency finishing and the system starting,
+    /// spent waiting for systems accessing the same resources or for a free
+    /// thread
+    pub wait_ns: u64,
+    /// How much later the system could have finished without delaying any of
+    /// the systems depending on it, or the end of the tick
+    pub slack_ns: u64,
+}
+
+#[derive(Default, Debug)]
+pub struct ScheduleStats {
+    pub systems: HashMap<String, SystemSchedule>,
+    /// The chain of dependencies that ended last, in the order the systems ran.
+    /// Speeding up any system not on it doesn't shorten the tick.
+    pub critical_path: Vec<String>,
+    /// An order to dispatch the systems in that respects their dependencies
+    /// and starts the longest chains of work first
+    pub suggested_order: Vec<String>,
+}
+
+/// Relates the measured run times of the systems of a tick to the
+/// dependencies they were dispatched with, to find out which systems
+/// serialize the tick.
+pub fn analyze_schedule(
+    timelines: &HashMap<String, CpuTimeline>,
+    systems: &HashMap<String, SystemInfo>,
+    tick_work_start: Instant,
+) -> ScheduleStats {
+    let ns = |time: Instant| time.saturating_duration_since(tick_work_start).as_nanos() as u64;
+    let times = timelines
+        .iter()
+        .filter_map(|(name, timeline)| {
+            let start = timeline.measures.first()?.0;
+            let end = timeline.measures.last()?.0;
+            Some((name.as_str(), (ns(start), ns(end))))
+        })
+        .collect::<HashMap<_, _>>();
+    // Dependencies that didn't run this tick can't have held anything up
+    let deps = systems
+        .values()
+        .filter(|info| times.contains_key(info.name))
+        .map(|info| {
+            let deps = info
+                .deps
+                .iter()
+                .filter_map(|dep| systems.get(dep))
+                .map(|dep| dep.name)
+                .filter(|dep| times.contains_key(dep))
+                .collect::<Vec<_>>();
+            (info.name, deps)
+        })
+        .collect::<HashMap<_, _>>();
+    let deps_of = |name: &str| deps.get(name).map_or(&[][..], |deps| &deps[..]);
+    let mut dependents = times
+        .keys()
+        .map(|name| (*name, Vec::new()))
+        .collect::<HashMap<_, _>>();
+    for (dependent, deps) in &deps {
+        for dep in deps {
+            dependents.entry(*dep).or_default().push(*dependent);
+        }
+    }
+    let tick_end = times.values().map(|(_, end)| *end).max().unwrap_or(0);
+
+    let schedules = times
+        .iter()
+        .map(|(name, (start, end))| {
+            let ready = deps_of(name).iter().map(|dep| times[dep].1).max();
+            let needed = dependents[name]
+                .iter()
+                .map(|dependent| times[dependent].0)
+                .min()
+                .unwrap_or(tick_end);
+            (name.to_string(), SystemSchedule {
+                start_ns: *start,
+                end_ns: *end,
+                wait_ns: start.saturating_sub(ready.unwrap_or(0)),
+                slack_ns: needed.saturating_sub(*end),
+            })
+        })
+        .collect();
+
+    // Follow the dependencies that finished last back from the system that ended
+    // the tick
+    let mut critical_path = Vec::new();
+    let mut current = times
+        .iter()
+        .max_by_key(|(_, (_, end))| *end)
+        .map(|(n, _)| *n);
+    while let Some(name) = current {
+        critical_path.push(name.to_string());
+        current = deps_of(name)
+            .iter()
+            .max_by_key(|dep| times[*dep].1)
+            .copied();
+    }
+    critical_path.reverse();
+
+    // The length of the longest chain of work each system starts, including
+    // itself
+    fn rank<'a>(
+        name: &'a str,
+        lengths: &HashMap<&'a str, u64>,
+        dependents: &HashMap<&'a str, Vec<&'a str>>,
+        ranks: &mut HashMap<&'a str, u64>,
+    ) -> u64 {
+        if let Some(rank) = ranks.get(name) {
+            return *rank;
+        }
+        let rest = dependents
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|dependent| rank(*dependent, lengths, dependents, ranks))
+            .max()
+            .unwrap_or(0);
+        let rank = lengths[name] + rest;
+        ranks.insert(name, rank);
+        rank
+    }
+    let lengths = times
+        .iter()
+        .map(|(name, (start, end))| (*name, end.saturating_sub(*start)))
+        .collect::<HashMap<_, _>>();
+    let mut ranks = HashMap::new();
+    for name in times.keys() {
+        rank(*name, &lengths, &dependents, &mut ranks);
+    }
+
+    // Among the systems whose dependencies are already placed, always place the
+    // one starting the longest chain next
+    let mut suggested_order = Vec::with_capacity(times.len());
+    let mut remaining = times.keys().copied().collect::<Vec<_>>();
+    while !remaining.is_empty() {
+        let next = remaining
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| {
+                deps_of(name)
+                    .iter()
+                    .all(|dep| suggested_order.iter().any(|placed| placed == dep))
+            })
+            .max_by_key(|(_, name)| (ranks[**name], std::cmp::Reverse(**name)))
+            .map(|(i, _)| i);
+        // Can't happen as long as the dependencies have no cycles, but don't hang if
+        // they somehow do
+        let Some(next) = next else {
+            break;
+        };
+        suggested_order.push(remaining.swap_remove(next).to_string());
+    }
+
+    ScheduleStats {
+        systems: schedules,
+        critical_path,
+        suggested_order,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParMode;
+    use std::time::Duration;
+
+    #[test]
+    fn critical_path_and_slack() {
+        let tick_start = Instant::now();
+        let ms = |ms| tick_start + Duration::from_millis(ms);
+        let mut timelines = HashMap::new();
+        let mut systems = HashMap::new();
+        for (name, start, end, deps) in [
+            ("a", 0, 2, vec![]),
+            ("b", 0, 5, vec![]),
+            ("c", 6, 8, vec!["a_sys", "b_sys"]),
+            ("d", 2, 3, vec!["a_sys"]),
+        ] {
+            timelines.insert(name.to_string(), CpuTimeline {
+                measures: vec![(ms(start), ParMode::Single), (ms(end), ParMode::None)],
+            });
+            systems.insert(format!("{name}_sys"), SystemInfo {
+                name,
+                deps: deps.into_iter().map(str::to_string).collect(),
+            });
+        }
+
+        let stats = analyze_schedule(&timelines, &systems, tick_start);
+        assert_eq!(stats.critical_path, ["b", "c"]);
+        assert_eq!(stats.systems["c"].wait_ns, 1_000_000);
+        assert_eq!(stats.systems["a"].slack_ns, 0);
+        assert_eq!(stats.systems["b"].slack_ns, 1_000_000);
+        assert_eq!(stats.systems["d"].slack_ns, 5_000_000);
+        assert_eq!(stats.suggested_order, ["b", "a", "c", "d"]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::metrics::SysMetrics;
+use crate::{metrics::SysMetrics, schedule::SystemInfo};
 use specs::{ReadExpect, RunNow};
 use std::{collections::HashMap, time::Instant};
 
@@ -51,7 +51,7 @@ pub struct CpuTimeline {
     ///   End it will always contain
     /// ParMode::None, which means from that point on 0 CPU threads work in this
     /// system
-    measures: Vec<(Instant, ParMode)>,
+    pub(crate) measures: Vec<(Instant, ParMode)>,
 }
 
 #[derive(Default)]
@@ -251,7 +251,9 @@ pub fn dispatch<'a, 'b, T>(builder: &mut specs::DispatcherBuilder<'a, 'b>, dep:
 where
     T: for<'c> System<'c> + Send + 'a + Default,
 {
-    builder.add(Job::<T>::default(), &T::sys_name(), dep);
+    let mut job = Job::<T>::default();
+    job.deps = dep.iter().map(|dep| dep.to_string()).collect();
+    builder.add(job, &T::sys_name(), dep);
 }
 
 pub fn run_now<'a, 'b, T>(world: &'a specs::World)
@@ -269,6 +271,8 @@ where
 {
     pub own: Box<T>,
     pub cpu_stats: CpuTimeline,
+    deps: Vec<String>,
+    registered: bool,
 }
 
 impl<'a, T> specs::System<'a> for Job<T>
@@ -291,6 +295,17 @@ where
             .lock()
             .unwrap()
             .insert(T::NAME.to_string(), self.cpu_stats.clone());
+        if !self.registered {
+            self.registered = true;
+            data.1
+                .systems
+                .lock()
+                .unwrap()
+                .insert(T::sys_name(), SystemInfo {
+                    name: T::NAME,
+                    deps: std::mem::take(&mut self.deps),
+                });
+        }
     }
 }
 
@@ -302,6 +317,8 @@ where
         Self {
             own: Box::<T>::default(),
             cpu_stats: CpuTimeline::default(),
+            deps: Vec::new(),
+            registered: false,
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::{world_msg::SiteId, PingMsg};
+use super::{world_msg::SiteId, PingMsg, ResumeToken};
 use common::{
     character::CharacterId, comp, comp::Skill, event::PluginHash, terrain::block::Block,
     ViewDistances,
@@ -40,6 +40,9 @@ pub enum ClientType {
 pub struct ClientRegister {
     pub token_or_username: String,
     pub locale: Option<String>,
+    /// Reattach to the entity of a session that lost its connection instead
+    /// of starting a new one.
+    pub resume_token: Option<ResumeToken>,
 }
 
 /// Messages sent from the client to the server
@@ -63,7 +66,14 @@ pub enum ClientGeneral {
     Character(CharacterId, ViewDistances),
     Spectate(ViewDistances),
     //Only in game
-    ControllerInputs(Box<comp::ControllerInputs>),
+    /// `input_seq` increases with every inputs sent, and is acknowledged by
+    /// the server with [`ServerGeneral::PlayerPhysicsAck`]
+    ///
+    /// [`ServerGeneral::PlayerPhysicsAck`]: super::ServerGeneral::PlayerPhysicsAck
+    ControllerInputs {
+        inputs: Box<comp::ControllerInputs>,
+        input_seq: u64,
+    },
     ControlEvent(comp::ControlEvent),
     ControlAction(comp::ControlAction),
     SetViewDistance(ViewDistances),
@@ -124,7 +134,7 @@ impl ClientMsg {
                             c_type == ClientType::Game && presence.is_none()
                         },
                         //Only in game
-                        ClientGeneral::ControllerInputs(_)
+                        ClientGeneral::ControllerInputs { .. }
                         | ClientGeneral::ControlEvent(_)
                         | ClientGeneral::ControlAction(_)
                         | ClientGeneral::SetViewDistance(_)
@@ -14,8 +14,9 @@ pub use self::{
     ecs_packet::EcsCompPacket,
     server::{
         CharacterInfo, ChatTypeContext, DisconnectReason, InviteAnswer, Notification, PlayerInfo,
-        PlayerListUpdate, RegisterError, SerializedTerrainChunk, ServerGeneral, ServerInfo,
-        ServerInit, ServerMsg, ServerRegisterAnswer,
+        PlayerListUpdate, RegisterError, RegisterStatus, SerializedTerrainChunk, ServerGeneral,
+        ServerInfo, ServerInit, ServerMsg, ServerRegisterAnswer, ShrineTravelError,
+        WorldFingerprint,
     },
     world_msg::WorldMapMsg,
 };
@@ -26,3 +27,8 @@ pub enum PingMsg {
     Ping,
     Pong,
 }
+
+/// Handed out to in-game clients, allowing them to reattach to their entity
+/// after losing the connection to the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ResumeToken(pub u128);
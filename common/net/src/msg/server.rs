@@ -1,17 +1,29 @@
 use super::{
-    world_msg::EconomyInfo, ClientType, CompressedData, EcsCompPacket, PingMsg, QuadPngEncoding,
+    world_msg::{EconomyInfo, TreasureMapSnippet, WorldgenDebugOverlay},
+    ClientType, CompressedData, EcsCompPacket, PingMsg, QuadPngEncoding, ResumeToken,
     TriPngEncoding, WidePacking, WireChonk,
 };
 use crate::sync;
 use common::{
-    calendar::Calendar,
+    bounty::Bounty,
+    calendar::{Calendar, CalendarEntry, CalendarEvent},
     character::{self, CharacterItem},
-    comp::{self, body::Gender, invite::InviteKind, item::MaterialStatManifest, Content},
+    cmd::WorldgenDebugField,
+    comp::{
+        self, body::Gender, dialogue::Directions, invite::InviteKind, item::MaterialStatManifest,
+        Content,
+    },
     event::{PluginHash, UpdateCharacterMetadata},
+    guild::{GuildError, GuildInfo},
+    heraldry::Heraldry,
     lod,
+    loot_roll::{LootRollChoice, LootRollId},
+    minigame::Scoreboard,
     outcome::Outcome,
+    race::RaceProgress,
     recipe::{ComponentRecipeBook, RecipeBookManifest, RepairRecipeBook},
-    resources::{Time, TimeOfDay, TimeScale},
+    resources::{BattleMode, Time, TimeOfDay, TimeScale},
+    rtsim::FactionReputation,
     shared_server_config::ServerConstants,
     terrain::{Block, TerrainChunk, TerrainChunkMeta, TerrainChunkSize},
     trade::{PendingTrade, SitePrices, TradeId, TradeResult},
@@ -50,6 +62,48 @@ pub struct ServerInfo {
     pub git_hash: String,
     pub git_date: String,
     pub auth_provider: Option<String>,
+    #[serde(default)]
+    pub world: WorldFingerprint,
+}
+
+/// Characteristics of the world a server is running.
+///
+/// These are sent before joining so that they can be displayed to players, and
+/// so that clients can tell whether anything they cached about the world (such
+/// as the map) is still valid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct WorldFingerprint {
+    /// Identifies the world seed without revealing it directly.
+    pub seed_hash: u64,
+    /// See [`common::terrain::map::WORLDGEN_VERSION`].
+    pub worldgen_version: u32,
+    /// Base two logarithm of the world size in chunks.
+    pub map_size_lg: Vec2<u32>,
+    pub calendar_events: Vec<CalendarEvent>,
+    /// The battle mode players start with.
+    pub battle_mode: BattleMode,
+    /// Whether players may pick their own battle mode.
+    pub battle_mode_choosable: bool,
+}
+
+impl WorldFingerprint {
+    pub fn hash_seed(seed: u32) -> u64 {
+        // SplitMix64 finalizer, chosen because it is stable across platforms and
+        // compiler versions
+        let mut x = (seed as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        x ^ (x >> 31)
+    }
+
+    /// Whether data cached for a world with the `other` fingerprint (such as
+    /// the map) can still be used for this world. Unlike equality, this
+    /// ignores characteristics that don't affect the terrain.
+    pub fn same_terrain(&self, other: &Self) -> bool {
+        self.seed_hash == other.seed_hash
+            && self.worldgen_version == other.worldgen_version
+            && self.map_size_lg == other.map_size_lg
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -79,7 +133,20 @@ pub enum ServerInit {
     },
 }
 
-pub type ServerRegisterAnswer = Result<(), RegisterError>;
+pub type ServerRegisterAnswer = Result<RegisterStatus, RegisterError>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RegisterStatus {
+    /// The client is registered, the initial sync follows.
+    Registered,
+    /// The server is full and the client waits in the login queue. Sent again
+    /// whenever the position changes, until the client is registered.
+    Queued {
+        /// Position in the queue, starting at 1.
+        position: u32,
+        estimated_wait: Option<Duration>,
+    },
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SerializedTerrainChunk {
@@ -151,6 +218,12 @@ pub enum ServerGeneral {
     CharacterEdited(character::CharacterId),
     CharacterSuccess,
     SpectatorSuccess(Vec3<f32>),
+    /// The client reattached to the entity of a session that lost its
+    /// connection, so it is in-game right away
+    SessionResumed {
+        presence: comp::PresenceKind,
+        view_distances: common::ViewDistances,
+    },
     //Ingame related
     GroupUpdate(comp::group::ChangeNotification<Uid>),
     /// Indicate to the client that they are invited to join a group
@@ -164,6 +237,44 @@ pub enum ServerGeneral {
     InvitePending(Uid),
     /// Update the HUD of the clients in the group
     GroupInventoryUpdate(comp::FrontendItem, Uid),
+    /// Ask a group member to roll for an item dropped for their group
+    LootRoll {
+        id: LootRollId,
+        item: comp::FrontendItem,
+        timeout: Duration,
+    },
+    /// A loot roll was decided, with the winner, their choice and their roll
+    /// if anyone won the item
+    LootRollEnded {
+        id: LootRollId,
+        winner: Option<(Uid, LootRollChoice, u32)>,
+    },
+    /// Current scores of the minigame the client takes part in
+    MinigameScoreboard(Scoreboard),
+    /// The minigame the client took part in ended, or they left it, with the
+    /// final scores
+    MinigameEnded(Scoreboard),
+    /// The client's run on a race track started or ended
+    RaceProgress(RaceProgress),
+    /// The client read a treasure map
+    TreasureMap(TreasureMapSnippet),
+    /// Bounties posted on the notice board at `pos`, sent when the client
+    /// reads it
+    NoticeBoard {
+        pos: Vec3<i32>,
+        bounties: Vec<Bounty>,
+    },
+    /// Bounties the client's character pursues, sent when they change
+    Bounties(Vec<Bounty>),
+    /// Reputation of the client's character with the factions that know of it,
+    /// sent on request
+    Reputation(Vec<FactionReputation>),
+    /// The guild of the player and its members, sent on login and when it
+    /// changes. `None` if they aren't in a guild.
+    GuildUpdate(Option<GuildInfo>),
+    /// Ongoing and upcoming events of the server's event calendar, sent on
+    /// login and when they change
+    EventCalendar(Vec<CalendarEntry>),
     /// Note: this could potentially include all the failure cases such as
     /// inviting yourself in which case the `InvitePending` message could be
     /// removed and the client could consider their invite pending until
@@ -215,8 +326,19 @@ pub enum ServerGeneral {
     /// Economic information about sites
     SiteEconomy(EconomyInfo),
     MapMarker(comp::MapMarkerUpdate),
+    /// What the client's bird companion spotted while out scouting
+    ScoutPings(Vec<comp::ScoutPing>),
     WeatherUpdate(SharedWeatherGrid),
     LocalWindUpdate(Vec2<f32>),
+    /// Authoritative physics state of the client's own entity, after applying
+    /// the controller inputs up to `input_seq`. Sent instead of regular
+    /// physics updates to clients with server-authoritative physics, so that
+    /// they can reconcile their predicted movement with it.
+    PlayerPhysicsAck {
+        input_seq: u64,
+        pos: comp::Pos,
+        vel: comp::Vel,
+    },
     /// Suggest the client to spectate a position. Called after client has
     /// requested teleport etc.
     SpectatePosition(Vec3<f32>),
@@ -225,6 +347,18 @@ pub enum ServerGeneral {
     /// Update the list of available recipes. Usually called after a new recipe
     /// is acquired
     UpdateRecipes,
+    /// Token the client can use to resume this session if it loses its
+    /// connection
+    SessionToken(ResumeToken),
+    /// Show worldgen data with the debug overlay, or hide the given field
+    WorldgenDebug(WorldgenDebugField, Option<WorldgenDebugOverlay>),
+    /// Open the bank kept by the given banker
+    OpenBank(Uid),
+    /// Open the list of skills taught by the given trainer
+    OpenTrainer(Uid),
+    /// Directions an NPC gave the client's character, shown on the map and
+    /// minimap until it arrives
+    Directions(Directions),
 }
 
 impl ServerGeneral {
@@ -247,6 +381,11 @@ pub enum PlayerListUpdate {
     SelectedCharacter(Uid, CharacterInfo),
     LevelChange(Uid, u32),
     Moderator(Uid, bool),
+    VerifiedBadge(Uid, bool),
+    Faction(Uid, Option<String>),
+    GuildTag(Uid, Option<String>),
+    GuildBanner(Uid, Option<Heraldry>),
+    Title(Uid, Option<comp::Title>),
     Remove(Uid),
     Alias(Uid, String),
 }
@@ -258,6 +397,15 @@ pub struct PlayerInfo {
     pub player_alias: String,
     pub character: Option<CharacterInfo>,
     pub uuid: Uuid,
+    /// Whether the player linked their account and shows a verified badge.
+    pub verified_badge: bool,
+    /// The faction the player joined, shown as a tag on their nameplate.
+    pub faction: Option<String>,
+    /// Tag of the player's guild, shown instead of the faction.
+    pub guild_tag: Option<String>,
+    /// Banner of the player's guild, shown on their cape once the guild
+    /// claimed a hall.
+    pub guild_banner: Option<Heraldry>,
 }
 
 /// used for localisation, filled by client and used by i18n code
@@ -271,6 +419,8 @@ pub struct ChatTypeContext {
 pub struct CharacterInfo {
     pub name: String,
     pub gender: Option<Gender>,
+    /// Title the character chose to show under their name.
+    pub title: Option<comp::Title>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -283,6 +433,18 @@ pub enum InviteAnswer {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Notification {
     WaypointSaved,
+    ShrineUnlocked,
+    ShrineTravelFailed(ShrineTravelError),
+    GuildFailed(GuildError),
+}
+
+/// Why a character couldn't fast travel to a waypoint shrine
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ShrineTravelError {
+    NotAtShrine,
+    Locked,
+    InCombat,
+    NotEnoughCoins,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -326,7 +488,9 @@ impl ServerMsg {
                         | ServerGeneral::CharacterCreated(_) => {
                             c_type != ClientType::ChatOnly && presence.is_none()
                         },
-                        ServerGeneral::CharacterSuccess | ServerGeneral::SpectatorSuccess(_) => {
+                        ServerGeneral::CharacterSuccess
+                        | ServerGeneral::SpectatorSuccess(_)
+                        | ServerGeneral::SessionResumed { .. } => {
                             c_type == ClientType::Game && presence.is_none()
                         },
                         //Ingame related
@@ -337,6 +501,15 @@ impl ServerMsg {
                         | ServerGeneral::ExitInGameSuccess
                         | ServerGeneral::InventoryUpdate(_, _)
                         | ServerGeneral::GroupInventoryUpdate(_, _)
+                        | ServerGeneral::LootRoll { .. }
+                        | ServerGeneral::LootRollEnded { .. }
+                        | ServerGeneral::MinigameScoreboard(_)
+                        | ServerGeneral::MinigameEnded(_)
+                        | ServerGeneral::RaceProgress(_)
+                        | ServerGeneral::TreasureMap(_)
+                        | ServerGeneral::NoticeBoard { .. }
+                        | ServerGeneral::Bounties(_)
+                        | ServerGeneral::Reputation(_)
                         | ServerGeneral::TerrainChunkUpdate { .. }
                         | ServerGeneral::TerrainBlockUpdates(_)
                         | ServerGeneral::SetViewDistance(_)
@@ -346,10 +519,17 @@ impl ServerMsg {
                         | ServerGeneral::FinishedTrade(_)
                         | ServerGeneral::SiteEconomy(_)
                         | ServerGeneral::MapMarker(_)
+                        | ServerGeneral::ScoutPings(_)
                         | ServerGeneral::WeatherUpdate(_)
                         | ServerGeneral::LocalWindUpdate(_)
+                        | ServerGeneral::PlayerPhysicsAck { .. }
                         | ServerGeneral::SpectatePosition(_)
-                        | ServerGeneral::UpdateRecipes => {
+                        | ServerGeneral::UpdateRecipes
+                        | ServerGeneral::SessionToken(_)
+                        | ServerGeneral::WorldgenDebug(_, _)
+                        | ServerGeneral::OpenBank(_)
+                        | ServerGeneral::OpenTrainer(_)
+                        | ServerGeneral::Directions(_) => {
                             c_type == ClientType::Game && presence.is_some()
                         },
                         // Always possible
@@ -364,6 +544,8 @@ impl ServerMsg {
                         | ServerGeneral::DeleteEntity(_)
                         | ServerGeneral::Disconnect(_)
                         | ServerGeneral::Notification(_)
+                        | ServerGeneral::GuildUpdate(_)
+                        | ServerGeneral::EventCalendar(_)
                         | ServerGeneral::LodZoneUpdate { .. } => true,
                         ServerGeneral::PluginData(_) => true,
                     }
@@ -143,7 +143,9 @@ pub struct SiteInfo {
 #[repr(u8)]
 pub enum SiteKind {
     Town,
-    Dungeon { difficulty: u32 },
+    Dungeon {
+        difficulty: u32,
+    },
     Castle,
     Cave,
     Tree,
@@ -156,6 +158,8 @@ pub enum SiteKind {
     DwarvenMine,
     Cultist,
     Sahagin,
+    /// Only shown on the map once the player discovered it
+    ClimbingRoute,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -183,3 +187,20 @@ pub enum PoiKind {
     Peak(u32),
     Lake(u32),
 }
+
+/// Worldgen data around a player, shown by the debug overlay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldgenDebugOverlay {
+    /// Surface positions and the value of the field there, between 0 and 1.
+    pub samples: Vec<(Vec3<f32>, f32)>,
+    /// Lines to draw, such as the boundaries of plots.
+    pub lines: Vec<[Vec3<f32>; 2]>,
+}
+
+/// Stylized drawing of the land around a buried treasure cache, shown when
+/// reading a treasure map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasureMapSnippet {
+    /// RGBA, with the cache at the centre and north at the top
+    pub rgba: Grid<u32>,
+}
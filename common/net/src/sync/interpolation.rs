@@ -7,10 +7,27 @@ use specs::Component;
 use tracing::warn;
 use vek::ops::{Lerp, Slerp};
 
+/// How quickly the measured update interval and jitter follow changes
+const JITTER_SMOOTHING: f64 = 1.0 / 16.0;
+/// Gaps between updates longer than this (in seconds) are assumed to be the
+/// entity not changing, rather than the connection being unstable
+const MAX_JITTER_INTERVAL: f64 = 1.0;
+/// How many times the jitter is added to the interpolation delay, so that the
+/// next update usually arrives before it's needed
+const JITTER_DELAY_FACTOR: f64 = 2.0;
+const MAX_INTERP_DELAY: f64 = 0.3;
+/// How far (in seconds) to extrapolate past the latest update before the
+/// entity is left where it is, so a dropped update doesn't fling it away
+const MAX_EXTRAPOLATION: f64 = 0.25;
+
 #[derive(Debug)]
 pub struct InterpBuffer<T> {
     pub buf: [(f64, T); 4],
     pub i: usize,
+    /// Smoothed time between updates
+    interval: f64,
+    /// Smoothed deviation of the time between updates from `interval`
+    jitter: f64,
 }
 
 impl<T: Clone> InterpBuffer<T> {
@@ -23,6 +40,8 @@ impl<T: Clone> InterpBuffer<T> {
                 (0.0, x),
             ],
             i: 0,
+            interval: 0.0,
+            jitter: 0.0,
         }
     }
 
@@ -30,12 +49,45 @@ impl<T: Clone> InterpBuffer<T> {
         let InterpBuffer {
             ref mut buf,
             ref mut i,
+            ref mut interval,
+            ref mut jitter,
         } = self;
+        let dt = time - buf[*i].0;
+        if dt > 0.0 && dt < MAX_JITTER_INTERVAL {
+            *jitter += ((dt - *interval).abs() - *jitter) * JITTER_SMOOTHING;
+            *interval += (dt - *interval) * JITTER_SMOOTHING;
+        }
         *i += 1;
         *i %= buf.len();
         buf[*i] = (time, x);
     }
 
+    /// How far behind the latest update the entity is shown, so that there
+    /// usually is a later update to interpolate towards even when updates
+    /// arrive irregularly
+    pub fn delay(&self) -> f64 {
+        (self.interval + JITTER_DELAY_FACTOR * self.jitter).min(MAX_INTERP_DELAY)
+    }
+
+    /// Finds the two updates to interpolate between at `time`, and the point in
+    /// time to interpolate to after accounting for the delay. If no update
+    /// arrived since then, the latest two are returned to extrapolate from.
+    fn bracket(&self, time: f64) -> (&(f64, T), &(f64, T), f64) {
+        let len = self.buf.len();
+        // Counting from the latest update
+        let nth = |n: usize| &self.buf[(self.i + len - n) % len];
+        let time = time - self.delay();
+        let (a, b) = (1..len)
+            .map(|n| (nth(n), nth(n - 1)))
+            .find(|(a, _)| a.0 <= time)
+            .unwrap_or((nth(len - 1), nth(len - 2)));
+        (
+            a,
+            b,
+            time.clamp(nth(len - 1).0, nth(0).0 + MAX_EXTRAPOLATION),
+        )
+    }
+
     fn force_update(&mut self, time: f64, x: T) {
         for i in 0..self.buf.len() {
             self.buf[i] = (time, x.clone());
@@ -73,9 +125,7 @@ impl InterpolatableComponent for Pos {
 
     fn interpolate(self, interp_data: &Self::InterpData, t2: f64, vel: &InterpBuffer<Vel>) -> Self {
         // lerp to test interface, do hermite spline later
-        let InterpBuffer { ref buf, ref i } = interp_data;
-        let (t0, p0) = buf[(i + buf.len() - 1) % buf.len()];
-        let (t1, p1) = buf[i % buf.len()];
+        let (&(t0, p0), &(t1, p1), t_delayed) = interp_data.bracket(t2);
         if (t1 - t0).abs() < f64::EPSILON {
             return self;
         }
@@ -85,9 +135,8 @@ impl InterpolatableComponent for Pos {
             warn!("position delta exceeded sanity check, clamping");
             return p1;
         }
-        let (t0prime, m0) = vel.buf[(i + vel.buf.len() - 1) % vel.buf.len()];
-        let (t1prime, m1) = vel.buf[i % vel.buf.len()];
-        let t = (t2 - t0) / (t1 - t0);
+        let (&(t0prime, m0), &(t1prime, m1), _) = vel.bracket(t2);
+        let t = (t_delayed - t0) / (t1 - t0);
         let mut out = if ENABLE_POSITION_HERMITE
             && ((t0 - t0prime).abs() < f64::EPSILON && (t1 - t1prime).abs() < f64::EPSILON)
         {
@@ -108,7 +157,10 @@ impl InterpolatableComponent for Pos {
         };
 
         if out.map(|x| x.is_nan()).reduce_or() {
-            warn!("interpolation output is nan: {}, {}, {:?}", t2, t, buf);
+            warn!(
+                "interpolation output is nan: {}, {}, {:?}",
+                t2, t, interp_data.buf
+            );
             out = p1.0;
         }
 
@@ -127,9 +179,7 @@ impl InterpolatableComponent for Vel {
     }
 
     fn interpolate(self, interp_data: &Self::InterpData, t2: f64, _: &()) -> Self {
-        let InterpBuffer { ref buf, ref i } = interp_data;
-        let (t0, p0) = buf[(i + buf.len() - 1) % buf.len()];
-        let (t1, p1) = buf[i % buf.len()];
+        let (&(t0, p0), &(t1, p1), t2) = interp_data.bracket(t2);
         if (t1 - t0).abs() < f64::EPSILON {
             return self;
         }
@@ -139,12 +189,12 @@ impl InterpolatableComponent for Vel {
             warn!("velocity delta exceeded sanity check, clamping");
             return p1;
         }
-        let lerp_factor = 1.0 + ((t2 - t1) / (t1 - t0)) as f32;
+        let lerp_factor = ((t2 - t0) / (t1 - t0)) as f32;
         let mut out = Lerp::lerp_unclamped(p0.0, p1.0, lerp_factor);
         if out.map(|x| x.is_nan()).reduce_or() {
             warn!(
                 "interpolation output is nan: {}, {}, {:?}",
-                t2, lerp_factor, buf
+                t2, lerp_factor, interp_data.buf
             );
             out = p1.0;
         }
@@ -164,18 +214,16 @@ impl InterpolatableComponent for Ori {
     }
 
     fn interpolate(self, interp_data: &Self::InterpData, t2: f64, _: &()) -> Self {
-        let InterpBuffer { ref buf, ref i } = interp_data;
-        let (t0, p0) = buf[(i + buf.len() - 1) % buf.len()];
-        let (t1, p1) = buf[i % buf.len()];
+        let (&(t0, p0), &(t1, p1), t2) = interp_data.bracket(t2);
         if (t1 - t0).abs() < f64::EPSILON {
             return self;
         }
-        let lerp_factor = 1.0 + ((t2 - t1) / (t1 - t0)) as f32;
+        let lerp_factor = ((t2 - t0) / (t1 - t0)) as f32;
         let mut out = Slerp::slerp_unclamped(p0.to_quat(), p1.to_quat(), lerp_factor);
         if out.into_vec4().map(|x| x.is_nan()).reduce_or() {
             warn!(
                 "interpolation output is nan: {}, {}, {:?}",
-                t2, lerp_factor, buf
+                t2, lerp_factor, interp_data.buf
             );
             out = p1.to_quat();
         }
@@ -67,6 +67,8 @@ macro_rules! synced_components {
             active_abilities: ActiveAbilities,
             can_build: CanBuild,
             object: Object,
+            unlocked_shrines: UnlockedShrines,
+            titles: Titles,
         }
     };
 }
@@ -271,3 +273,11 @@ impl NetSync for CanBuild {
 impl NetSync for Object {
     const SYNC_FROM: SyncFrom = SyncFrom::AnyEntity;
 }
+
+impl NetSync for UnlockedShrines {
+    const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
+}
+
+impl NetSync for Titles {
+    const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
+}
@@ -183,6 +183,7 @@ fn armor_stats() -> Result<(), Box<dyn Error>> {
                                 precision_power,
                                 stealth,
                                 ground_contact: Default::default(),
+                                resistances: Default::default(),
                             };
                             let armor = comp::item::armor::Armor::new(
                                 kind,
@@ -0,0 +1,32 @@
+use crate::{comp::Content, rtsim::NpcId, trade::SiteId};
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+/// Characters within this distance of a notice board can read it
+pub const NOTICE_BOARD_RANGE: f32 = 5.0;
+/// Characters can't pursue more than this many bounties at once
+pub const MAX_ACTIVE_BOUNTIES: usize = 5;
+
+/// What a bounty asks of the character that accepts it. This also identifies
+/// the bounty: a town only ever posts one bounty per target.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BountyTarget {
+    /// Kill a monster that recently killed someone near the town
+    Hunt(NpcId),
+    /// Recover the goods stolen by the pirates of a hideout
+    Recover(SiteId),
+}
+
+/// A bounty posted on the notice board of a town
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Bounty {
+    pub target: BountyTarget,
+    /// Name of the monster or of the hideout
+    pub target_name: Content,
+    /// Where the monster was last seen, or where the hideout is
+    pub wpos: Vec2<f32>,
+    /// The town paying the reward
+    pub site: SiteId,
+    /// Reward in coins, paid from the funds of the town
+    pub reward: u32,
+}
@@ -1,7 +1,9 @@
+use crate::{comp::Content, resources::Time};
 use chrono::{DateTime, Datelike, Local, TimeZone, Utc};
 use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
+use vek::*;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
 #[repr(u16)]
@@ -57,3 +59,39 @@ impl Calendar {
         this
     }
 }
+
+/// What happens during an event on the server's event calendar
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ScheduledEventKind {
+    /// The boss described by the entity config is spawned at `pos` when the
+    /// event starts
+    WorldBoss {
+        entity_config: String,
+        pos: Vec3<f32>,
+    },
+    /// Experience gained from kills is multiplied
+    BonusExperience { multiplier: f32 },
+    /// Travelling merchants come to the site with the given name, and its
+    /// traders stock more goods
+    MarketDay { site: String },
+    /// The townsfolk of the site with the given name gather in its plazas to
+    /// celebrate. Travelling merchants come too, traders give discounts and
+    /// fireworks are launched at night.
+    Festival { site: String },
+}
+
+/// An ongoing or upcoming occurrence of an event on the server's event
+/// calendar, as shown to players
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalendarEntry {
+    pub name: Content,
+    pub kind: ScheduledEventKind,
+    pub starts_at: Time,
+    pub ends_at: Time,
+}
+
+impl CalendarEntry {
+    pub fn is_active(&self, time: Time) -> bool {
+        (self.starts_at.0..self.ends_at.0).contains(&time.0)
+    }
+}
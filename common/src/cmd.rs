@@ -86,6 +86,25 @@ pub enum AreaKind {
     NoDurability,
 }
 
+/// Worldgen column data that can be shown with the debug overlay
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter, EnumString, AsRefStr, Serialize, Deserialize,
+)]
+pub enum WorldgenDebugField {
+    #[strum(serialize = "humidity")]
+    Humidity,
+    #[strum(serialize = "temperature")]
+    Temperature,
+    #[strum(serialize = "tree_density")]
+    TreeDensity,
+    #[strum(serialize = "path")]
+    Path,
+    #[strum(serialize = "cave")]
+    Cave,
+    #[strum(serialize = "plots")]
+    Plots,
+}
+
 lazy_static! {
     static ref ALIGNMENTS: Vec<String> = ["wild", "enemy", "npc", "pet"]
         .iter()
@@ -135,6 +154,9 @@ lazy_static! {
         souls
     };
     static ref AREA_KINDS: Vec<String> = AreaKind::iter().map(|kind| kind.as_ref().to_string()).collect();
+    static ref WORLDGEN_DEBUG_FIELDS: Vec<String> = WorldgenDebugField::iter()
+        .map(|field| field.as_ref().to_string())
+        .collect();
     static ref OBJECTS: Vec<String> = comp::object::ALL_OBJECTS
         .iter()
         .map(|o| o.to_string().to_string())
@@ -187,6 +209,9 @@ lazy_static! {
             BuffKind::Flame => "flame",
             BuffKind::Frigid => "frigid",
             BuffKind::Lifesteal => "lifesteal",
+            BuffKind::PoisonCoating => "poison_coating",
+            BuffKind::FireCoating => "fire_coating",
+            BuffKind::FrostCoating => "frost_coating",
             // BuffKind::SalamanderAspect => "salamander_aspect",
             BuffKind::ImminentCritical => "imminent_critical",
             BuffKind::Fury => "fury",
@@ -200,6 +225,7 @@ lazy_static! {
             BuffKind::Winded => "winded",
             BuffKind::Concussion => "concussion",
             BuffKind::Staggered => "staggered",
+            BuffKind::Electrified => "electrified",
             BuffKind::Tenacity => "tenacity",
             BuffKind::Resilience => "resilience",
         };
@@ -236,6 +262,26 @@ lazy_static! {
 
     static ref ROLES: Vec<String> = ["admin", "moderator"].iter().copied().map(Into::into).collect();
 
+    static ref SANCTIONS: Vec<String> = ["shadow_mute", "probation"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    static ref REPORT_CATEGORIES: Vec<String> = [
+        "harassment",
+        "cheating",
+        "spam",
+        "griefing",
+        "name",
+        "other",
+    ]
+    .iter()
+    .copied()
+    .map(Into::into)
+    .collect();
+    static ref TITLES: Vec<String> = comp::Title::iter()
+        .map(|title| title.to_key().to_owned())
+        .collect();
+
     /// List of item's asset specifiers. Useful for tab completing.
     /// Doesn't cover all items (like modulars), includes "fake" items like
     /// TagExamples.
@@ -324,6 +370,7 @@ impl FromStr for EntityTarget {
 // Please keep this sorted alphabetically :-)
 #[derive(Copy, Clone, strum::EnumIter)]
 pub enum ServerChatCommand {
+    AccountNote,
     Adminify,
     Airship,
     Alias,
@@ -340,7 +387,9 @@ pub enum ServerChatCommand {
     Campfire,
     ClearPersistedTerrain,
     CreateLocation,
+    Creative,
     DebugColumn,
+    DebugOverlay,
     DebugWays,
     DeleteLocation,
     DestroyTethers,
@@ -351,12 +400,14 @@ pub enum ServerChatCommand {
     Explosion,
     Faction,
     GiveItem,
+    GiveTitle,
     Goto,
     Group,
     GroupInvite,
     GroupKick,
     GroupLeave,
     GroupPromote,
+    Guild,
     Health,
     Help,
     IntoNpc,
@@ -367,23 +418,31 @@ pub enum ServerChatCommand {
     KillNpcs,
     Kit,
     Lantern,
+    LiftSanction,
     Light,
     Lightning,
+    LinkAccount,
     Location,
     MakeBlock,
     MakeNpc,
     MakeSprite,
     MakeVolume,
+    Minigame,
     Motd,
     Mount,
     Object,
     PermitBuild,
     Players,
     Portal,
+    PrefabPlace,
+    PrefabSave,
+    Probation,
+    Race,
     Region,
     ReloadChunks,
     RemoveLights,
     RepairEquipment,
+    Report,
     ResetRecipes,
     Respawn,
     RevokeBuild,
@@ -391,6 +450,7 @@ pub enum ServerChatCommand {
     RtsimChunk,
     RtsimInfo,
     RtsimNpc,
+    RtsimPopulation,
     RtsimPurge,
     RtsimTp,
     Safezone,
@@ -398,6 +458,7 @@ pub enum ServerChatCommand {
     Scale,
     ServerPhysics,
     SetMotd,
+    ShadowMute,
     Ship,
     Site,
     SkillPoint,
@@ -425,6 +486,12 @@ impl ServerChatCommand {
         use Role::*;
         let cmd = ChatCommandData::new;
         match self {
+            ServerChatCommand::AccountNote => cmd(
+                vec![PlayerName(Required), Message(Optional)],
+                "Add a moderator note to an account, or show its notes and sanctions (if no note \
+                 is given)",
+                Some(Moderator),
+            ),
             ServerChatCommand::Adminify => cmd(
                 vec![PlayerName(Required), Enum("role", ROLES.clone(), Optional)],
                 "Temporarily gives a player a restricted admin role or removes the current one \
@@ -557,6 +624,14 @@ impl ServerChatCommand {
                 "Prints some debug information about a column",
                 Some(Admin),
             ),
+            ServerChatCommand::DebugOverlay => cmd(
+                vec![
+                    Enum("field", WORLDGEN_DEBUG_FIELDS.clone(), Required),
+                    Integer("radius", 64, Optional),
+                ],
+                "Shows worldgen data around you, a radius of 0 hides the field again",
+                Some(Admin),
+            ),
             ServerChatCommand::DebugWays => cmd(
                 vec![Integer("x", 15000, Required), Integer("y", 15000, Required)],
                 "Prints some debug information about a column's ways",
@@ -591,6 +666,14 @@ impl ServerChatCommand {
                 "Give yourself some items.\nFor an example or to auto complete use Tab.",
                 Some(Admin),
             ),
+            ServerChatCommand::GiveTitle => cmd(
+                vec![
+                    Enum("title", TITLES.clone(), Required),
+                    PlayerName(Optional),
+                ],
+                "Give a title to yourself or another player, e.g. for taking part in an event",
+                Some(Admin),
+            ),
             ServerChatCommand::Goto => cmd(
                 vec![
                     Float("x", 0.0, Required),
@@ -620,6 +703,9 @@ impl ServerChatCommand {
                 "Promote a player to group leader",
                 None,
             ),
+            ServerChatCommand::Guild => {
+                cmd(vec![Message(Optional)], "Send messages to your guild", None)
+            },
             ServerChatCommand::Health => cmd(
                 vec![Integer("hp", 100, Required)],
                 "Set your current health",
@@ -672,6 +758,14 @@ impl ServerChatCommand {
                 "Change your lantern's strength and color",
                 Some(Admin),
             ),
+            ServerChatCommand::LiftSanction => cmd(
+                vec![
+                    PlayerName(Required),
+                    Enum("sanction", SANCTIONS.clone(), Required),
+                ],
+                "Lift a shadow mute or probation from a player",
+                Some(Moderator),
+            ),
             ServerChatCommand::Light => cmd(
                 vec![
                     Float("r", 1.0, Optional),
@@ -713,6 +807,20 @@ impl ServerChatCommand {
                 "Make a sprite at your location",
                 Some(Admin),
             ),
+            ServerChatCommand::Minigame => cmd(
+                vec![
+                    Enum(
+                        "action",
+                        ["join", "leave", "list", "start", "stop"]
+                            .map(|s| s.to_string())
+                            .to_vec(),
+                        Required,
+                    ),
+                    Any("minigame", Optional),
+                ],
+                "Join, leave or list running minigames. Admins can start and stop them.",
+                None,
+            ),
             ServerChatCommand::Motd => cmd(vec![], "View the server description", None),
             ServerChatCommand::Object => cmd(
                 vec![Enum("object", OBJECTS.clone(), Required)],
@@ -736,6 +844,54 @@ impl ServerChatCommand {
                 "Spawns a portal",
                 Some(Admin),
             ),
+            ServerChatCommand::PrefabPlace => cmd(
+                vec![
+                    Any("name", Required),
+                    Integer("rotation", 0, Optional),
+                    Any("remap", Optional),
+                ],
+                "Places a saved prefab at your position, rotated by quarter turns and with block \
+                 kinds remapped like Rock=Wood,Leaves=GlowingRock",
+                Some(Admin),
+            ),
+            ServerChatCommand::PrefabSave => cmd(
+                vec![
+                    Any("name", Required),
+                    Integer("x0", 0, Required),
+                    Integer("x1", 0, Required),
+                    Integer("y0", 0, Required),
+                    Integer("y1", 0, Required),
+                    Integer("z0", 0, Required),
+                    Integer("z1", 0, Required),
+                ],
+                "Saves the blocks within the given world coordinates as a prefab",
+                Some(Admin),
+            ),
+            ServerChatCommand::Probation => cmd(
+                vec![
+                    PlayerName(Required),
+                    Any("duration", Optional),
+                    Message(Optional),
+                ],
+                "Put a player on probation for a given duration (if provided). Their chat is rate \
+                 limited and they can't trade.",
+                Some(Moderator),
+            ),
+            ServerChatCommand::Race => cmd(
+                vec![
+                    Enum(
+                        "action",
+                        ["checkpoint", "leaderboard", "list", "remove"]
+                            .map(|s| s.to_string())
+                            .to_vec(),
+                        Required,
+                    ),
+                    Any("track", Optional),
+                ],
+                "Show race tracks and their leaderboards. Admins can add checkpoints at their \
+                 position to the end of a track and remove tracks.",
+                None,
+            ),
             ServerChatCommand::ReloadChunks => cmd(
                 vec![Integer("chunk_radius", 6, Optional)],
                 "Reloads chunks loaded on the server",
@@ -785,6 +941,16 @@ impl ServerChatCommand {
                 "Set the server description",
                 Some(Admin),
             ),
+            ServerChatCommand::ShadowMute => cmd(
+                vec![
+                    PlayerName(Required),
+                    Any("duration", Optional),
+                    Message(Optional),
+                ],
+                "Shadow mute a player for a given duration (if provided). Their chat messages are \
+                 only shown to themselves.",
+                Some(Moderator),
+            ),
             ServerChatCommand::Ship => cmd(
                 vec![
                     Enum(
@@ -887,6 +1053,11 @@ impl ServerChatCommand {
                  distance",
                 Some(Admin),
             ),
+            ServerChatCommand::RtsimPopulation => cmd(
+                vec![],
+                "Display the number of rtsim NPCs of each role, and how many of them are loaded",
+                Some(Admin),
+            ),
             ServerChatCommand::RtsimPurge => cmd(
                 vec![Boolean(
                     "whether purging of rtsim data should occur on next startup",
@@ -931,6 +1102,17 @@ impl ServerChatCommand {
             ServerChatCommand::Location => {
                 cmd(vec![Any("name", Required)], "Teleport to a location", None)
             },
+            ServerChatCommand::LinkAccount => cmd(
+                vec![],
+                "Get a one-time code to link your account, for example to the server's Discord",
+                None,
+            ),
+            ServerChatCommand::Creative => cmd(
+                vec![],
+                "Toggles creative mode, which allows flying and building anywhere without taking \
+                 damage",
+                Some(Admin),
+            ),
             ServerChatCommand::CreateLocation => cmd(
                 vec![Any("name", Required)],
                 "Create a location at the current position",
@@ -964,6 +1146,15 @@ impl ServerChatCommand {
             ServerChatCommand::RepairEquipment => {
                 cmd(vec![], "Repairs all equipped items", Some(Admin))
             },
+            ServerChatCommand::Report => cmd(
+                vec![
+                    PlayerName(Required),
+                    Enum("category", REPORT_CATEGORIES.clone(), Required),
+                    Message(Optional),
+                ],
+                "Report a player to the moderators. Your recent chat with them is attached.",
+                None,
+            ),
             ServerChatCommand::Tether => cmd(
                 vec![
                     EntityTarget(Required),
@@ -1005,6 +1196,7 @@ impl ServerChatCommand {
             ServerChatCommand::Campfire => "campfire",
             ServerChatCommand::ClearPersistedTerrain => "clear_persisted_terrain",
             ServerChatCommand::DebugColumn => "debug_column",
+            ServerChatCommand::DebugOverlay => "debug_overlay",
             ServerChatCommand::DebugWays => "debug_ways",
             ServerChatCommand::DisconnectAllPlayers => "disconnect_all_players",
             ServerChatCommand::DropAll => "dropall",
@@ -1012,12 +1204,14 @@ impl ServerChatCommand {
             ServerChatCommand::Explosion => "explosion",
             ServerChatCommand::Faction => "faction",
             ServerChatCommand::GiveItem => "give_item",
+            ServerChatCommand::GiveTitle => "give_title",
             ServerChatCommand::Goto => "goto",
             ServerChatCommand::Group => "group",
             ServerChatCommand::GroupInvite => "group_invite",
             ServerChatCommand::GroupKick => "group_kick",
             ServerChatCommand::GroupLeave => "group_leave",
             ServerChatCommand::GroupPromote => "group_promote",
+            ServerChatCommand::Guild => "guild",
             ServerChatCommand::Health => "health",
             ServerChatCommand::Help => "help",
             ServerChatCommand::IntoNpc => "into_npc",
@@ -1033,11 +1227,15 @@ impl ServerChatCommand {
             ServerChatCommand::MakeBlock => "make_block",
             ServerChatCommand::MakeNpc => "make_npc",
             ServerChatCommand::MakeSprite => "make_sprite",
+            ServerChatCommand::Minigame => "minigame",
             ServerChatCommand::Motd => "motd",
             ServerChatCommand::Object => "object",
             ServerChatCommand::PermitBuild => "permit_build",
             ServerChatCommand::Players => "players",
             ServerChatCommand::Portal => "portal",
+            ServerChatCommand::PrefabPlace => "prefab_place",
+            ServerChatCommand::PrefabSave => "prefab_save",
+            ServerChatCommand::Race => "race",
             ServerChatCommand::ResetRecipes => "reset_recipes",
             ServerChatCommand::Region => "region",
             ServerChatCommand::ReloadChunks => "reload_chunks",
@@ -1048,6 +1246,7 @@ impl ServerChatCommand {
             ServerChatCommand::Say => "say",
             ServerChatCommand::ServerPhysics => "server_physics",
             ServerChatCommand::SetMotd => "set_motd",
+            ServerChatCommand::ShadowMute => "shadow_mute",
             ServerChatCommand::Ship => "ship",
             ServerChatCommand::Site => "site",
             ServerChatCommand::SkillPoint => "skill_point",
@@ -1061,6 +1260,7 @@ impl ServerChatCommand {
             ServerChatCommand::RtsimTp => "rtsim_tp",
             ServerChatCommand::RtsimInfo => "rtsim_info",
             ServerChatCommand::RtsimNpc => "rtsim_npc",
+            ServerChatCommand::RtsimPopulation => "rtsim_population",
             ServerChatCommand::RtsimPurge => "rtsim_purge",
             ServerChatCommand::RtsimChunk => "rtsim_chunk",
             ServerChatCommand::Unban => "unban",
@@ -1071,12 +1271,18 @@ impl ServerChatCommand {
             ServerChatCommand::World => "world",
             ServerChatCommand::MakeVolume => "make_volume",
             ServerChatCommand::Location => "location",
+            ServerChatCommand::LinkAccount => "link_account",
             ServerChatCommand::CreateLocation => "create_location",
+            ServerChatCommand::Creative => "creative",
             ServerChatCommand::DeleteLocation => "delete_location",
             ServerChatCommand::WeatherZone => "weather_zone",
             ServerChatCommand::Lightning => "lightning",
             ServerChatCommand::Scale => "scale",
             ServerChatCommand::RepairEquipment => "repair_equipment",
+            ServerChatCommand::Report => "report",
+            ServerChatCommand::Probation => "probation",
+            ServerChatCommand::LiftSanction => "lift_sanction",
+            ServerChatCommand::AccountNote => "account_note",
             ServerChatCommand::Tether => "tether",
             ServerChatCommand::DestroyTethers => "destroy_tethers",
             ServerChatCommand::Mount => "mount",
@@ -2,7 +2,7 @@ use crate::{
     comp::{
         ability::Capability,
         aura::{AuraKindVariant, EnteredAuras},
-        buff::{Buff, BuffChange, BuffData, BuffKind, BuffSource, DestInfo},
+        buff::{Buff, BuffChange, BuffData, BuffKind, BuffSource, DestInfo, Dispel},
         inventory::{
             item::{
                 armor::Protection,
@@ -28,7 +28,8 @@ use crate::{
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use specs::{Entity as EcsEntity, ReadStorage};
-use std::ops::{Mul, MulAssign};
+use std::ops::{Mul, MulAssign, Sub};
+use strum::EnumIter;
 use vek::*;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -64,6 +65,8 @@ pub const PARRY_BONUS_MULTIPLIER: f32 = 2.0;
 pub const FALLBACK_BLOCK_STRENGTH: f32 = 5.0;
 pub const BEHIND_TARGET_ANGLE: f32 = 45.0;
 pub const BASE_PARRIED_POISE_PUNISHMENT: f32 = 100.0 / 3.5;
+/// How long a successful parry with a block leaves a riposte opportunity open
+pub const PARRY_RIPOSTE_WINDOW: Secs = Secs(1.5);
 
 #[derive(Copy, Clone)]
 pub struct AttackerInfo<'a> {
@@ -583,6 +586,12 @@ impl Attack {
                                 });
                             }
                         },
+                        CombatEffect::Dispel(dispel) => {
+                            emitters.emit(BuffEvent {
+                                entity: target.entity,
+                                buff_change: BuffChange::Dispel(*dispel),
+                            });
+                        },
                         CombatEffect::BuffsVulnerable(damage, buff) => {
                             if target.buffs.map_or(false, |b| b.contains(*buff)) {
                                 let change = {
@@ -816,6 +825,12 @@ impl Attack {
                             });
                         }
                     },
+                    CombatEffect::Dispel(dispel) => {
+                        emitters.emit(BuffEvent {
+                            entity: target.entity,
+                            buff_change: BuffChange::Dispel(dispel),
+                        });
+                    },
                     // Only has an effect when attached to a damage
                     CombatEffect::BuffsVulnerable(_, _) => {},
                     // Only has an effect when attached to a damage
@@ -1026,6 +1041,8 @@ pub enum CombatEffect {
     StunnedVulnerable(f32),
     /// Applies buff to yourself after attack is applied
     SelfBuff(CombatBuff),
+    /// Removes buffs from the target, see [`crate::comp::Buffs::dispel`]
+    Dispel(Dispel),
 }
 
 impl CombatEffect {
@@ -1058,6 +1075,7 @@ impl CombatEffect {
                 CombatEffect::StageVulnerable(v * stats.effect_power, s)
             },
             CombatEffect::RefreshBuff(c, b) => CombatEffect::RefreshBuff(c, b),
+            CombatEffect::Dispel(d) => CombatEffect::Dispel(d),
             CombatEffect::BuffsVulnerable(v, b) => {
                 CombatEffect::BuffsVulnerable(v * stats.effect_power, b)
             },
@@ -1182,6 +1200,114 @@ pub enum DamageKind {
     Energy,
 }
 
+/// Element of damage, which is resisted separately from the protection of
+/// armor
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum DamageElement {
+    #[default]
+    Physical,
+    Fire,
+    Frost,
+    Shock,
+    Poison,
+}
+
+impl DamageElement {
+    /// Element associated with a debuff, used for attacks that don't specify
+    /// their element explicitly
+    pub fn from_buff(kind: BuffKind) -> Option<Self> {
+        match kind {
+            BuffKind::Burning => Some(Self::Fire),
+            BuffKind::Frozen => Some(Self::Frost),
+            BuffKind::Poisoned => Some(Self::Poison),
+            _ => None,
+        }
+    }
+
+    /// Element of an attack with the given effect attached, see
+    /// [`DamageElement::from_buff`]
+    pub fn from_effect(effect: Option<CombatEffect>) -> Self {
+        match effect {
+            Some(CombatEffect::Buff(CombatBuff { kind, .. })) => {
+                Self::from_buff(kind).unwrap_or_default()
+            },
+            _ => Self::Physical,
+        }
+    }
+}
+
+/// Resistances against non-physical damage elements, as the fraction of damage
+/// that is prevented. Negative values are weaknesses.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ElementResistances {
+    pub fire: f32,
+    pub frost: f32,
+    pub shock: f32,
+    pub poison: f32,
+}
+
+impl ElementResistances {
+    pub fn get(&self, element: DamageElement) -> f32 {
+        match element {
+            DamageElement::Physical => 0.0,
+            DamageElement::Fire => self.fire,
+            DamageElement::Frost => self.frost,
+            DamageElement::Shock => self.shock,
+            DamageElement::Poison => self.poison,
+        }
+    }
+
+    pub fn get_mut(&mut self, element: DamageElement) -> Option<&mut f32> {
+        match element {
+            DamageElement::Physical => None,
+            DamageElement::Fire => Some(&mut self.fire),
+            DamageElement::Frost => Some(&mut self.frost),
+            DamageElement::Shock => Some(&mut self.shock),
+            DamageElement::Poison => Some(&mut self.poison),
+        }
+    }
+
+    /// Stacks two sources of resistances multiplicatively, so that they can't
+    /// exceed 100%
+    #[must_use]
+    pub fn combined(self, other: Self) -> Self {
+        let combine = |a: f32, b: f32| 1.0 - (1.0 - a.min(1.0)) * (1.0 - b.min(1.0));
+        Self {
+            fire: combine(self.fire, other.fire),
+            frost: combine(self.frost, other.frost),
+            shock: combine(self.shock, other.shock),
+            poison: combine(self.poison, other.poison),
+        }
+    }
+}
+
+impl Mul<f32> for ElementResistances {
+    type Output = Self;
+
+    fn mul(self, val: f32) -> Self::Output {
+        Self {
+            fire: self.fire * val,
+            frost: self.frost * val,
+            shock: self.shock * val,
+            poison: self.poison * val,
+        }
+    }
+}
+
+impl Sub for ElementResistances {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            fire: self.fire - other.fire,
+            frost: self.frost - other.frost,
+            shock: self.shock - other.shock,
+            poison: self.poison - other.poison,
+        }
+    }
+}
+
 const PIERCING_PENETRATION_FRACTION: f32 = 0.5;
 const SLASHING_ENERGY_FRACTION: f32 = 0.5;
 const CRUSHING_POISE_FRACTION: f32 = 1.0;
@@ -1191,6 +1317,8 @@ const CRUSHING_POISE_FRACTION: f32 = 1.0;
 pub struct Damage {
     pub source: DamageSource,
     pub kind: DamageKind,
+    #[serde(default)]
+    pub element: DamageElement,
     pub value: f32,
 }
 
@@ -1228,11 +1356,17 @@ impl Damage {
         } else {
             0.0
         };
+        // Negative resistances are weaknesses, increasing the damage taken
+        let element_resistance = damage.map_or(0.0, |damage| {
+            compute_element_resistances(inventory, stats, msm)
+                .get(damage.element)
+                .min(1.0)
+        });
         // Return 100% if either DR is at 100% (admin tabard or safezone buff)
         if protection.is_none() || stats_dr >= 1.0 {
             1.0
         } else {
-            1.0 - (1.0 - inventory_dr) * (1.0 - stats_dr)
+            1.0 - (1.0 - inventory_dr) * (1.0 - stats_dr) * (1.0 - element_resistance)
         }
     }
 
@@ -1404,12 +1538,25 @@ impl CombatBuff {
             stats: target_info.stats,
             mass: target_info.mass,
         };
+        let damage_modifier = attacker_info
+            .and_then(|a| a.stats)
+            .map_or(1.0, |s| s.attack_damage_modifier);
+        let mut data = BuffData::new(
+            self.strength.to_strength(damage, strength_modifier),
+            Some(Secs(self.dur_secs as f64)),
+        );
+        // Damage over time is scaled by the attacker's damage modifier when it ticks
+        // instead, so it is taken out of strengths derived from the already modified
+        // damage.
+        if self.kind.dot_scaling().is_some() && damage_modifier > 0.0 {
+            if let CombatBuffStrength::DamageFraction(_) = self.strength {
+                data.strength /= damage_modifier;
+            }
+            data = data.with_source_damage_modifier(damage_modifier);
+        }
         Buff::new(
             self.kind,
-            BuffData::new(
-                self.strength.to_strength(damage, strength_modifier),
-                Some(Secs(self.dur_secs as f64)),
-            ),
+            data,
             Vec::new(),
             source,
             time,
@@ -1717,6 +1864,31 @@ pub fn compute_protection(
     })
 }
 
+/// Computes the total resistances against damage elements from armor, species
+/// and buffs.
+pub fn compute_element_resistances(
+    inventory: Option<&Inventory>,
+    stats: Option<&Stats>,
+    msm: &MaterialStatManifest,
+) -> ElementResistances {
+    let armor = inventory.map_or_else(ElementResistances::default, |inv| {
+        inv.equipped_items()
+            .filter_map(|item| {
+                if let ItemKind::Armor(armor) = &*item.kind() {
+                    Some(
+                        armor
+                            .stats(msm, item.stats_durability_multiplier())
+                            .resistances,
+                    )
+                } else {
+                    None
+                }
+            })
+            .fold(ElementResistances::default(), ElementResistances::combined)
+    });
+    stats.map_or(armor, |stats| armor.combined(stats.element_resistances))
+}
+
 /// Computes the total resilience provided from armor. Is used to determine the
 /// reduction applied to poise damage received by an entity. None indicates that
 /// the armor equipped makes the entity invulnerable to poise damage.
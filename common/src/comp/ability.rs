@@ -39,6 +39,10 @@ use std::{borrow::Cow, time::Duration};
 use super::shockwave::ShockwaveDodgeable;
 
 pub const BASE_ABILITY_LIMIT: usize = 5;
+/// Number of ability loadouts that can be saved for each weapon combination
+pub const ABILITY_LOADOUTS: usize = 3;
+/// Seconds after taking damage before ability loadouts can be switched again
+pub const LOADOUT_SWITCH_COMBAT_LOCKOUT: f64 = 10.0;
 
 // NOTE: different AbilitySpec on same ToolKind share the same key
 /// Descriptor to pick the right (auxiliary) ability set
@@ -56,6 +60,11 @@ pub struct ActiveAbilities {
     pub movement: MovementAbility,
     pub limit: Option<usize>,
     pub auxiliary_sets: HashMap<AuxiliaryKey, Vec<AuxiliaryAbility>>,
+    /// Which loadout of each weapon combination is in `auxiliary_sets`,
+    /// combinations that aren't listed use the first loadout
+    pub active_loadouts: HashMap<AuxiliaryKey, usize>,
+    /// Ability sets of the loadouts that aren't currently in use
+    pub stored_loadouts: HashMap<(AuxiliaryKey, usize), Vec<AuxiliaryAbility>>,
 }
 
 impl Component for ActiveAbilities {
@@ -71,6 +80,8 @@ impl Default for ActiveAbilities {
             movement: MovementAbility::Species,
             limit: None,
             auxiliary_sets: HashMap::new(),
+            active_loadouts: HashMap::new(),
+            stored_loadouts: HashMap::new(),
         }
     }
 }
@@ -110,6 +121,29 @@ impl ActiveAbilities {
         }
     }
 
+    /// Adds the loadouts that aren't in use, discarding those that exceed the
+    /// limit or the number of loadouts
+    pub fn with_loadouts(
+        mut self,
+        active_loadouts: HashMap<AuxiliaryKey, usize>,
+        stored_loadouts: HashMap<(AuxiliaryKey, usize), Vec<AuxiliaryAbility>>,
+    ) -> Self {
+        let limit = self.limit;
+        self.active_loadouts = active_loadouts
+            .into_iter()
+            .filter(|(_, loadout)| *loadout < ABILITY_LOADOUTS)
+            .collect();
+        self.stored_loadouts = stored_loadouts
+            .into_iter()
+            .filter(|((key, loadout), set)| {
+                *loadout < ABILITY_LOADOUTS
+                    && *loadout != self.active_loadout(*key)
+                    && limit.map_or(true, |limit| set.len() == limit)
+            })
+            .collect();
+        self
+    }
+
     pub fn default_limited(limit: usize) -> Self {
         ActiveAbilities {
             limit: Some(limit),
@@ -134,6 +168,57 @@ impl ActiveAbilities {
         }
     }
 
+    /// The loadout of a weapon combination that is currently in use
+    pub fn active_loadout(&self, auxiliary_key: AuxiliaryKey) -> usize {
+        self.active_loadouts
+            .get(&auxiliary_key)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Swaps the ability set of the equipped weapons for another one of their
+    /// loadouts, keeping the current set for later. Loadouts that were never
+    /// used start with the default abilities. Returns false if there's no
+    /// such loadout.
+    pub fn switch_loadout(
+        &mut self,
+        loadout: usize,
+        inventory: Option<&Inventory>,
+        skill_set: Option<&SkillSet>,
+    ) -> bool {
+        if loadout >= ABILITY_LOADOUTS {
+            return false;
+        }
+        let auxiliary_key = Self::active_auxiliary_key(inventory);
+        let current = self.active_loadout(auxiliary_key);
+        if current != loadout {
+            let next = self
+                .stored_loadouts
+                .remove(&(auxiliary_key, loadout))
+                .unwrap_or_else(|| Self::default_ability_set(inventory, skill_set, self.limit));
+            if let Some(previous) = self.auxiliary_sets.insert(auxiliary_key, next) {
+                self.stored_loadouts
+                    .insert((auxiliary_key, current), previous);
+            }
+            self.active_loadouts.insert(auxiliary_key, loadout);
+        }
+        true
+    }
+
+    /// Whether `ability` may be put into the ability set of the given weapon
+    /// combination. Only sets of the equipped weapons can be changed, and only
+    /// to abilities that have been unlocked.
+    pub fn can_select_ability(
+        auxiliary_key: AuxiliaryKey,
+        ability: AuxiliaryAbility,
+        inventory: Option<&Inventory>,
+        skill_set: Option<&SkillSet>,
+    ) -> bool {
+        matches!(ability, AuxiliaryAbility::Empty)
+            || (auxiliary_key == Self::active_auxiliary_key(inventory)
+                && Self::all_available_abilities(inventory, skill_set).contains(&ability))
+    }
+
     pub fn active_auxiliary_key(inv: Option<&Inventory>) -> AuxiliaryKey {
         let tool_kind = |slot| {
             inv.and_then(|inv| inv.equipped(slot))
@@ -720,7 +805,10 @@ impl From<&CharacterState> for CharacterAbilityType {
             | CharacterState::Climb(_)
             | CharacterState::Sit
             | CharacterState::Dance
+            | CharacterState::Emote(_)
             | CharacterState::Pet(_)
+            | CharacterState::Downed(_)
+            | CharacterState::Revive(_)
             | CharacterState::Talk
             | CharacterState::Glide(_)
             | CharacterState::GlideWield(_)
@@ -825,6 +913,8 @@ pub enum CharacterAbility {
         parry_window: basic_block::ParryWindow,
         energy_cost: f32,
         energy_regen: f32,
+        #[serde(default)]
+        energy_drain: f32,
         can_hold: bool,
         blocked_attacks: AttackFilters,
         #[serde(default)]
@@ -1129,6 +1219,7 @@ impl Default for CharacterAbility {
                 angle: 15.0,
                 multi_target: None,
                 damage_effect: None,
+                element: None,
                 attack_effect: None,
                 simultaneous_hits: 1,
                 custom_combo: None,
@@ -1390,6 +1481,7 @@ impl CharacterAbility {
                 parry_window: _,
                 ref mut energy_cost,
                 energy_regen: _,
+                ref mut energy_drain,
                 can_hold: _,
                 blocked_attacks: _,
                 meta: _,
@@ -1397,6 +1489,7 @@ impl CharacterAbility {
                 *buildup_duration /= stats.speed;
                 *recover_duration /= stats.speed;
                 *energy_cost /= stats.energy_efficiency;
+                *energy_drain /= stats.energy_efficiency;
                 *block_strength *= stats.power;
             },
             Roll {
@@ -2365,6 +2458,7 @@ impl From<(&CharacterAbility, AbilityInfo, &JoinData<'_>)> for CharacterState {
                 parry_window,
                 energy_cost,
                 energy_regen,
+                energy_drain,
                 can_hold,
                 blocked_attacks,
                 meta: _,
@@ -2377,6 +2471,7 @@ impl From<(&CharacterAbility, AbilityInfo, &JoinData<'_>)> for CharacterState {
                     parry_window: *parry_window,
                     energy_cost: *energy_cost,
                     energy_regen: *energy_regen,
+                    energy_drain: *energy_drain,
                     can_hold: *can_hold,
                     blocked_attacks: *blocked_attacks,
                     ability_info,
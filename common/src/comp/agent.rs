@@ -1,7 +1,7 @@
 use crate::{
     comp::{
         biped_large, biped_small, bird_medium, humanoid, quadruped_low, quadruped_medium,
-        quadruped_small, ship, Body, UtteranceKind,
+        quadruped_small, serpent, ship, Body, UtteranceKind,
     },
     path::Chaser,
     rtsim::{NpcInput, RtSimController},
@@ -63,6 +63,9 @@ pub enum Alignment {
 pub enum Mark {
     Merchant,
     Guard,
+    Banker,
+    Trainer,
+    Necromancer,
 }
 
 impl Alignment {
@@ -135,6 +138,9 @@ bitflags::bitflags! {
     pub struct BehaviorCapability: u8 {
         const SPEAK = 0b00000001;
         const TRADE = 0b00000010;
+        const BANK  = 0b00000100;
+        const TRAIN = 0b00001000;
+        const REANIMATE = 0b00010000;
     }
 }
 bitflags::bitflags! {
@@ -165,6 +171,27 @@ impl TradingBehavior {
     }
 }
 
+/// Terms a trading agent was talked into by haggling during the current trade
+#[derive(Copy, Clone, Debug)]
+pub struct Haggle {
+    /// How many times the customer has haggled so far
+    pub attempts: u8,
+    /// Multiplier on the value the agent asks in exchange for its goods
+    pub price_factor: f32,
+    /// Proportion of the asking price covered by the last offer, if any
+    pub coverage: Option<f32>,
+}
+
+impl Default for Haggle {
+    fn default() -> Self {
+        Self {
+            attempts: 0,
+            price_factor: 1.0,
+            coverage: None,
+        }
+    }
+}
+
 /// # Behavior Component
 /// This component allow an Entity to register one or more behavior tags.
 /// These tags act as flags of what an Entity can do, or what it is doing.
@@ -413,6 +440,10 @@ impl<'a> From<&'a Body> for Psyche {
                 Body::Dragon(_) => 0.0,
                 Body::Arthropod(_) => 0.0,
                 Body::Crustacean(_) => 0.0,
+                Body::Serpent(serpent) => match serpent.species {
+                    serpent::Species::Viper => 0.2,
+                    serpent::Species::Naga => 0.0,
+                },
             },
             sight_dist: match body {
                 Body::BirdLarge(_) => 250.0,
@@ -620,6 +651,20 @@ impl Timer {
     }
 }
 
+/// A flight that a bird companion has been sent out on by its owner, looping
+/// over an area of the map before returning to their shoulder.
+#[derive(Copy, Clone, Debug)]
+pub struct Scout {
+    /// The centre of the area to loop over
+    pub target: Vec2<f32>,
+    /// When the loop over the target area began, once the bird got there
+    pub circling_since: Option<f64>,
+    /// When the bird last looked around for things to report
+    pub last_scan: f64,
+    /// Whether the bird has finished its loop and is on its way back
+    pub returning: bool,
+}
+
 /// For use with the builder pattern <https://doc.rust-lang.org/1.0.0/style/ownership/builders.html>
 #[derive(Clone, Debug)]
 pub struct Agent {
@@ -642,8 +687,11 @@ pub struct Agent {
     pub flee_from_pos: Option<Pos>,
     pub awareness: Awareness,
     pub stay_pos: Option<Pos>,
+    /// The scouting flight that this pet has been sent out on, if any
+    pub scout: Option<Scout>,
     /// Inputs sent up to rtsim
     pub rtsim_outbox: Option<VecDeque<NpcInput>>,
+    pub haggle: Haggle,
 }
 
 #[derive(Clone, Debug)]
@@ -749,8 +797,10 @@ impl Agent {
             position_pid_controller: None,
             flee_from_pos: None,
             stay_pos: None,
+            scout: None,
             awareness: Awareness::new(0.0),
             rtsim_outbox: None,
+            haggle: Haggle::default(),
         }
     }
 
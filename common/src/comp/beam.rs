@@ -1,4 +1,7 @@
-use crate::{combat::Attack, resources::Secs};
+use crate::{
+    combat::{Attack, DamageElement},
+    resources::Secs,
+};
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use specs::{Component, DerefFlaggedStorage, Entity as EcsEntity};
@@ -46,3 +49,22 @@ pub enum FrontendSpecifier {
     Lightning,
     PhoenixLaser,
 }
+
+impl FrontendSpecifier {
+    /// Element of the damage dealt by beams with this specifier
+    pub fn element(&self) -> DamageElement {
+        match self {
+            Self::Flamethrower | Self::PhoenixLaser => DamageElement::Fire,
+            Self::Frost => DamageElement::Frost,
+            Self::Poison => DamageElement::Poison,
+            Self::Lightning => DamageElement::Shock,
+            Self::LifestealBeam
+            | Self::Cultist
+            | Self::Gravewarden
+            | Self::Bubbles
+            | Self::Steam
+            | Self::WebStrand
+            | Self::Ink => DamageElement::Physical,
+        }
+    }
+}
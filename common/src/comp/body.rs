@@ -14,11 +14,13 @@ pub mod object;
 pub mod quadruped_low;
 pub mod quadruped_medium;
 pub mod quadruped_small;
+pub mod serpent;
 pub mod ship;
 pub mod theropod;
 
 use crate::{
     assets::{self, Asset},
+    combat::ElementResistances,
     consts::{HUMAN_DENSITY, WATER_DENSITY},
     make_case_elim,
     npc::NpcKind,
@@ -29,7 +31,7 @@ use specs::{Component, DerefFlaggedStorage};
 use strum::Display;
 use vek::*;
 
-use super::{BuffKind, Collider, Density, Mass, Scale};
+use super::{pet::Perch, BuffKind, Collider, Density, Mass, Scale};
 
 make_case_elim!(
     body,
@@ -54,6 +56,7 @@ make_case_elim!(
         Arthropod(body: arthropod::Body) = 15,
         ItemDrop(body: item_drop::Body) = 16,
         Crustacean(body: crustacean::Body) = 17,
+        Serpent(body: serpent::Body) = 18,
     }
 );
 
@@ -104,6 +107,7 @@ pub struct AllBodies<BodyMeta, SpeciesMeta> {
     pub ship: BodyData<BodyMeta, ()>,
     pub arthropod: BodyData<BodyMeta, arthropod::AllSpecies<SpeciesMeta>>,
     pub crustacean: BodyData<BodyMeta, crustacean::AllSpecies<SpeciesMeta>>,
+    pub serpent: BodyData<BodyMeta, serpent::AllSpecies<SpeciesMeta>>,
 }
 
 impl<BodyMeta, SpeciesMeta> AllBodies<BodyMeta, SpeciesMeta> {
@@ -128,6 +132,7 @@ impl<BodyMeta, SpeciesMeta> AllBodies<BodyMeta, SpeciesMeta> {
             Body::QuadrupedLow(b) => &self.quadruped_low.species[&b.species],
             Body::Arthropod(b) => &self.arthropod.species[&b.species],
             Body::Crustacean(b) => &self.crustacean.species[&b.species],
+            Body::Serpent(b) => &self.serpent.species[&b.species],
             _ => return None,
         })
     }
@@ -155,6 +160,7 @@ impl<BodyMeta, SpeciesMeta> core::ops::Index<NpcKind> for AllBodies<BodyMeta, Sp
             NpcKind::Crocodile => &self.quadruped_low.body,
             NpcKind::Tarantula => &self.arthropod.body,
             NpcKind::Crab => &self.crustacean.body,
+            NpcKind::Viper => &self.serpent.body,
         }
     }
 }
@@ -184,6 +190,7 @@ impl<'a, BodyMeta, SpeciesMeta> core::ops::Index<&'a Body> for AllBodies<BodyMet
             Body::Arthropod(_) => &self.arthropod.body,
             Body::Ship(_) => &self.ship.body,
             Body::Crustacean(_) => &self.crustacean.body,
+            Body::Serpent(_) => &self.serpent.body,
         }
     }
 }
@@ -278,6 +285,10 @@ impl Body {
                 Body::Crustacean(b2) => b1.species == b2.species,
                 _ => false,
             },
+            Body::Serpent(b1) => match other {
+                Body::Serpent(b2) => b1.species == b2.species,
+                _ => false,
+            },
         }
     }
 
@@ -502,6 +513,10 @@ impl Body {
             Body::Arthropod(_) => 200.0,
             // TODO: mass
             Body::Crustacean(_) => 50.0,
+            Body::Serpent(body) => match body.species {
+                serpent::Species::Viper => 30.0,
+                serpent::Species::Naga => 300.0,
+            },
         };
         Mass(m)
     }
@@ -689,6 +704,10 @@ impl Body {
                 bird_medium::Species::Toucan => Vec3::new(2.1, 1.1, 1.2),
             },
             Body::Crustacean(_) => Vec3::new(1.2, 1.2, 0.7),
+            Body::Serpent(body) => match body.species {
+                serpent::Species::Viper => Vec3::new(1.0, 2.4, 0.6),
+                serpent::Species::Naga => Vec3::new(1.6, 3.6, 2.6),
+            },
         }
     }
 
@@ -1071,6 +1090,12 @@ impl Body {
             },
             Body::Ship(_) => 1000,
             Body::Crustacean(_) => 40,
+            Body::Serpent(body) => match body.species {
+                // T1
+                serpent::Species::Viper => 45,
+                // T3A
+                serpent::Species::Naga => 340,
+            },
         }
     }
 
@@ -1201,10 +1226,39 @@ impl Body {
                 _ => false,
             },
             BuffKind::ProtectingWard => matches!(self, Body::Object(object::Body::BarrelOrgan)),
+            BuffKind::Poisoned => matches!(self, Body::Serpent(_)),
             _ => false,
         }
     }
 
+    /// Innate resistances against elemental damage. Creatures immune to an
+    /// elemental debuff resist its element, and creatures of fire or frost are
+    /// weak to the opposite element.
+    pub fn element_resistances(&self) -> ElementResistances {
+        let fire_immune = self.immune_to(BuffKind::Burning);
+        let frost_immune = self.immune_to(BuffKind::Frozen);
+        let opposed = |immune, opposite_immune| {
+            if immune {
+                0.5
+            } else if opposite_immune {
+                -0.25
+            } else {
+                0.0
+            }
+        };
+        let construct = matches!(self, Body::Object(_) | Body::Golem(_) | Body::Ship(_));
+        ElementResistances {
+            fire: opposed(fire_immune, frost_immune),
+            frost: opposed(frost_immune, fire_immune),
+            shock: if construct { -0.25 } else { 0.0 },
+            poison: if construct || self.immune_to(BuffKind::Poisoned) {
+                0.5
+            } else {
+                0.0
+            },
+        }
+    }
+
     /// Returns a multiplier representing increased difficulty not accounted for
     /// due to AI or not using an actual weapon
     // TODO: Match on species
@@ -1442,6 +1496,7 @@ impl Body {
                 ship::Body::Submarine => [1.0, -2.0, 2.0],
                 ship::Body::Carriage => [1.0, -2.0, 2.0],
                 ship::Body::Cart => [1.0, -2.0, 2.0],
+                ship::Body::HotAirBalloon => [0.0, 0.0, 1.0],
                 ship::Body::Volume => [0.0, 0.0, 0.0],
             },
             _ => [0.0, 0.0, 0.0],
@@ -1449,6 +1504,18 @@ impl Body {
         .into()
     }
 
+    /// Component of the mounting offset specific to the mount, for a rider
+    /// that perches somewhere other than the usual spot on it
+    pub fn mount_offset_for(&self, rider: Option<&Body>) -> Vec3<f32> {
+        match (self, rider.and_then(Perch::of)) {
+            (Body::Humanoid(_), Some(Perch::Head)) => self.dimensions() * Vec3::new(0.0, 0.0, 1.0),
+            (Body::Humanoid(_), Some(Perch::Back)) => {
+                self.dimensions() * Vec3::new(0.0, -0.5, 0.65)
+            },
+            _ => self.mount_offset(),
+        }
+    }
+
     /// Component of the mounting offset specific to the rider
     pub fn rider_offset(&self) -> Vec3<f32> {
         match self {
@@ -126,6 +126,7 @@ make_case_elim!(
         IronPikeBomb = 111,
         Lavathrower = 112,
         Scroll = 113,
+        RaceCheckpoint = 114,
     }
 );
 
@@ -251,6 +252,7 @@ pub const ALL_OBJECTS: [Body; 114] = [
     Body::IronPikeBomb,
     Body::Lavathrower,
     Body::Scroll,
+    Body::RaceCheckpoint,
 ];
 
 impl From<Body> for super::Body {
@@ -374,6 +376,7 @@ impl Body {
             Body::BubbleBomb => "bubble_bomb",
             Body::IronPikeBomb => "iron_pike_bomb",
             Body::Scroll => "recipe",
+            Body::RaceCheckpoint => "race_checkpoint",
         }
     }
 
@@ -517,7 +520,7 @@ impl Body {
             Body::AdletTrap => 10.0,
             Body::Mine => 100.0,
             Body::LightningBolt | Body::SpearIcicle => 20000.0,
-            Body::Portal | Body::PortalActive => 10., // I dont know really
+            Body::Portal | Body::PortalActive | Body::RaceCheckpoint => 10., // I dont know really
         };
 
         Mass(m)
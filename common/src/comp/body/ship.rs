@@ -42,6 +42,7 @@ make_case_elim!(
         Submarine = 6,
         Carriage = 7,
         Cart = 8,
+        HotAirBalloon = 9,
     }
 );
 
@@ -75,7 +76,7 @@ impl Body {
             Body::Submarine => Some("submarine.structure"),
             Body::Carriage => Some("carriage.structure"),
             Body::Cart => Some("cart.structure"),
-            Body::Volume => None,
+            Body::HotAirBalloon | Body::Volume => None,
         }
     }
 
@@ -89,12 +90,13 @@ impl Body {
             Body::Submarine => Vec3::new(2.0, 15.0, 8.0),
             Body::Carriage => Vec3::new(5.0, 12.0, 2.0),
             Body::Cart => Vec3::new(3.0, 6.0, 1.0),
+            Body::HotAirBalloon => Vec3::new(9.0, 9.0, 18.0),
         }
     }
 
     fn balloon_vol(&self) -> f32 {
         match self {
-            Body::DefaultAirship | Body::AirBalloon | Body::Volume => {
+            Body::DefaultAirship | Body::AirBalloon | Body::HotAirBalloon | Body::Volume => {
                 let spheroid_vol = |equat_d: f32, polar_d: f32| -> f32 {
                     (std::f32::consts::PI / 6.0) * equat_d.powi(2) * polar_d
                 };
@@ -120,7 +122,9 @@ impl Body {
 
     pub fn density(&self) -> Density {
         match self {
-            Body::DefaultAirship | Body::AirBalloon | Body::Volume => Density(AIR_DENSITY),
+            Body::DefaultAirship | Body::AirBalloon | Body::HotAirBalloon | Body::Volume => {
+                Density(AIR_DENSITY)
+            },
             Body::Submarine => Density(WATER_DENSITY), // Neutrally buoyant
             Body::Carriage => Density(WATER_DENSITY * 0.5),
             Body::Cart => Density(500.0 / self.dimensions().product()), /* Carts get a constant */
@@ -139,9 +143,16 @@ impl Body {
     }
 
     pub fn can_fly(&self) -> bool {
-        matches!(self, Body::DefaultAirship | Body::AirBalloon | Body::Volume)
+        matches!(
+            self,
+            Body::DefaultAirship | Body::AirBalloon | Body::HotAirBalloon | Body::Volume
+        )
     }
 
+    /// Whether the ship rises and falls by heating the air in its envelope with
+    /// a burner, drifting wherever the wind takes it.
+    pub fn has_burner(&self) -> bool { matches!(self, Body::HotAirBalloon) }
+
     pub fn flying_height(&self) -> f32 { if self.can_fly() { 200.0 } else { 0.0 } }
 
     pub fn has_water_thrust(&self) -> bool {
@@ -155,6 +166,9 @@ impl Body {
             Some(manifest_entry) => Collider::Voxel {
                 id: manifest_entry.to_string(),
             },
+            None if matches!(self, Body::HotAirBalloon) => {
+                Collider::Volume(Arc::new(hot_air_balloon_volume()))
+            },
             None => {
                 use rand::prelude::*;
                 let sz = Vec3::broadcast(11);
@@ -178,11 +192,44 @@ impl Body {
             Body::Galleon => 6.0,
             Body::Skiff => 6.0,
             Body::Submarine => 4.0,
+            Body::HotAirBalloon => 3.0,
             _ => 10.0,
         }
     }
 }
 
+/// Build the volume of a one-person hot air balloon: a wicker basket with a
+/// burner at its centre for the pilot to work, hanging beneath a striped
+/// envelope.
+fn hot_air_balloon_volume() -> figuredata::VoxelCollider {
+    let sz = Vec3::new(9, 9, 18);
+    let wicker = Block::new(BlockKind::Wood, Rgb::new(150, 110, 60));
+    let rope = Block::new(BlockKind::Wood, Rgb::new(90, 70, 45));
+    let envelope_centre = Vec3::new(4.0, 4.0, 12.0);
+    figuredata::VoxelCollider::from_fn(sz, |pos| {
+        let basket = pos.xy().map(|e| (2..=6).contains(&e)).reduce_and();
+        let basket_edge = pos.xy().map(|e| e == 2 || e == 6).reduce_or();
+        let envelope =
+            ((pos.as_::<f32>() - envelope_centre) / Vec3::new(4.5, 4.5, 6.0)).magnitude_squared();
+        if basket && (pos.z == 0 || (basket_edge && pos.z < 3)) {
+            wicker
+        } else if pos.xy() == Vec2::broadcast(4) && pos.z == 1 {
+            Block::air(SpriteKind::Helm)
+        } else if pos.xy().map(|e| e == 2 || e == 6).reduce_and() && (3..7).contains(&pos.z) {
+            rope
+        } else if envelope < 1.0 {
+            // Stripes running down the envelope
+            if (pos.x + pos.y) % 4 < 2 {
+                Block::new(BlockKind::Misc, Rgb::new(200, 50, 40))
+            } else {
+                Block::new(BlockKind::Misc, Rgb::new(230, 190, 60))
+            }
+        } else {
+            Block::air(SpriteKind::Empty)
+        }
+    })
+}
+
 /// Terrain is 11.0 scale relative to small-scale voxels,
 /// airship scale is multiplied by 11 to reach terrain scale.
 pub const AIRSHIP_SCALE: f32 = 11.0;
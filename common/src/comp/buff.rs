@@ -2,7 +2,7 @@
 use crate::{
     combat::{
         AttackEffect, CombatBuff, CombatBuffStrength, CombatEffect, CombatRequirement,
-        DamagedEffect, DeathEffect,
+        DamageElement, DamagedEffect, DeathEffect,
     },
     comp::{aura::AuraKey, Mass, Stats},
     resources::{Secs, Time},
@@ -61,7 +61,8 @@ pub enum BuffKind {
     Invulnerability,
     /// Reduces incoming damage.
     /// Strength scales the damage reduction non-linearly. 0.5 provides 50% DR,
-    /// 1.0 provides 67% DR.
+    /// 1.0 provides 67% DR. Also reduces the amount of buffs that can be
+    /// dispelled by the same fraction.
     ProtectingWard,
     /// Increases movement speed and gives health regeneration.
     /// Strength scales the movement speed linearly. 0.5 is 150% speed, 1.0 is
@@ -97,6 +98,20 @@ pub enum BuffKind {
     /// Your attacks have lifesteal
     /// Strength increases the fraction of damage restored as life
     Lifesteal,
+    /// Your weapon is coated in poison, your attacks cause targets to receive
+    /// the poisoned debuff until the coating runs out of charges
+    /// Strength of poisoned debuff is a fraction of the damage, fraction
+    /// increases linearly with strength
+    PoisonCoating,
+    /// Your weapon is coated in fire oil, your attacks cause targets to
+    /// receive the burning debuff until the coating runs out of charges
+    /// Strength of burning debuff is a fraction of the damage, fraction
+    /// increases linearly with strength
+    FireCoating,
+    /// Your weapon is coated in frost salve, your attacks cause targets to
+    /// receive the frozen debuff until the coating runs out of charges
+    /// Strength of frozen debuff is equal to the strength of this buff
+    FrostCoating,
     /// Your attacks against bleeding targets have lifesteal
     /// Strength increases the fraction of damage restored as life
     Bloodfeast,
@@ -213,6 +228,11 @@ pub enum BuffKind {
     /// Scales linearly with strength, 1.0 leads to 100% more poise damage
     /// received
     Staggered,
+    /// Muscles seizing up after being struck by lightning, slowing movement and
+    /// attack speed.
+    /// Both scale non-linearly with strength, 0.5 leads to a 25% reduction and
+    /// 1.0 to a 33% reduction.
+    Electrified,
     // =================
     //      COMPLEX
     // =================
@@ -259,6 +279,9 @@ impl BuffKind {
             | BuffKind::Flame
             | BuffKind::Frigid
             | BuffKind::Lifesteal
+            | BuffKind::PoisonCoating
+            | BuffKind::FireCoating
+            | BuffKind::FrostCoating
             //| BuffKind::SalamanderAspect
             | BuffKind::ImminentCritical
             | BuffKind::Fury
@@ -283,7 +306,8 @@ impl BuffKind {
             | BuffKind::Rooted
             | BuffKind::Winded
             | BuffKind::Concussion
-            | BuffKind::Staggered => BuffDescriptor::SimpleNegative,
+            | BuffKind::Staggered
+            | BuffKind::Electrified => BuffDescriptor::SimpleNegative,
             BuffKind::Polymorphed => BuffDescriptor::Complex,
         }
     }
@@ -393,12 +417,15 @@ impl BuffKind {
                 kind: ModifierKind::Additive,
             }],
             BuffKind::Invulnerability => vec![BuffEffect::DamageReduction(1.0)],
-            BuffKind::ProtectingWard => vec![BuffEffect::DamageReduction(
-                // Causes non-linearity in effect strength, but necessary
-                // to allow for tool power and other things to affect the
-                // strength. 0.5 also still provides 50% damage reduction.
-                nn_scaling(data.strength),
-            )],
+            BuffKind::ProtectingWard => vec![
+                BuffEffect::DamageReduction(
+                    // Causes non-linearity in effect strength, but necessary
+                    // to allow for tool power and other things to affect the
+                    // strength. 0.5 also still provides 50% damage reduction.
+                    nn_scaling(data.strength),
+                ),
+                BuffEffect::DispelResistance(nn_scaling(data.strength)),
+            ],
             BuffKind::Burning => vec![BuffEffect::HealthChangeOverTime {
                 rate: -data.strength,
                 kind: ModifierKind::Additive,
@@ -463,28 +490,70 @@ impl BuffKind {
                 }
                 effects
             },
-            BuffKind::Flame => vec![BuffEffect::AttackEffect(AttackEffect::new(
-                None,
-                CombatEffect::Buff(CombatBuff {
-                    kind: BuffKind::Burning,
-                    dur_secs: data.secondary_duration.map_or(5.0, |dur| dur.0 as f32),
-                    strength: CombatBuffStrength::DamageFraction(data.strength),
-                    chance: 1.0,
-                }),
-            ))],
-            BuffKind::Frigid => vec![BuffEffect::AttackEffect(AttackEffect::new(
-                None,
-                CombatEffect::Buff(CombatBuff {
-                    kind: BuffKind::Frozen,
-                    dur_secs: data.secondary_duration.map_or(5.0, |dur| dur.0 as f32),
-                    strength: CombatBuffStrength::Value(data.strength),
-                    chance: 1.0,
-                }),
-            ))],
+            BuffKind::Flame => vec![
+                BuffEffect::AttackEffect(AttackEffect::new(
+                    None,
+                    CombatEffect::Buff(CombatBuff {
+                        kind: BuffKind::Burning,
+                        dur_secs: data.secondary_duration.map_or(5.0, |dur| dur.0 as f32),
+                        strength: CombatBuffStrength::DamageFraction(data.strength),
+                        chance: 1.0,
+                    }),
+                )),
+                BuffEffect::ElementResistance(DamageElement::Fire, 0.3),
+            ],
+            BuffKind::Frigid => vec![
+                BuffEffect::AttackEffect(AttackEffect::new(
+                    None,
+                    CombatEffect::Buff(CombatBuff {
+                        kind: BuffKind::Frozen,
+                        dur_secs: data.secondary_duration.map_or(5.0, |dur| dur.0 as f32),
+                        strength: CombatBuffStrength::Value(data.strength),
+                        chance: 1.0,
+                    }),
+                )),
+                BuffEffect::ElementResistance(DamageElement::Frost, 0.3),
+            ],
             BuffKind::Lifesteal => vec![BuffEffect::AttackEffect(AttackEffect::new(
                 None,
                 CombatEffect::Lifesteal(data.strength),
             ))],
+            BuffKind::PoisonCoating => vec![BuffEffect::AttackEffect(
+                AttackEffect::new(
+                    None,
+                    CombatEffect::Buff(CombatBuff {
+                        kind: BuffKind::Poisoned,
+                        dur_secs: data.secondary_duration.map_or(5.0, |dur| dur.0 as f32),
+                        strength: CombatBuffStrength::DamageFraction(data.strength),
+                        chance: 1.0,
+                    }),
+                )
+                .with_requirement(CombatRequirement::AnyDamage),
+            )],
+            BuffKind::FireCoating => vec![BuffEffect::AttackEffect(
+                AttackEffect::new(
+                    None,
+                    CombatEffect::Buff(CombatBuff {
+                        kind: BuffKind::Burning,
+                        dur_secs: data.secondary_duration.map_or(5.0, |dur| dur.0 as f32),
+                        strength: CombatBuffStrength::DamageFraction(data.strength),
+                        chance: 1.0,
+                    }),
+                )
+                .with_requirement(CombatRequirement::AnyDamage),
+            )],
+            BuffKind::FrostCoating => vec![BuffEffect::AttackEffect(
+                AttackEffect::new(
+                    None,
+                    CombatEffect::Buff(CombatBuff {
+                        kind: BuffKind::Frozen,
+                        dur_secs: data.secondary_duration.map_or(5.0, |dur| dur.0 as f32),
+                        strength: CombatBuffStrength::Value(data.strength),
+                        chance: 1.0,
+                    }),
+                )
+                .with_requirement(CombatRequirement::AnyDamage),
+            )],
             /*BuffKind::SalamanderAspect => vec![
                 BuffEffect::BuffImmunity(BuffKind::Burning),
                 BuffEffect::SwimSpeed(1.0 + data.strength),
@@ -536,6 +605,10 @@ impl BuffKind {
             ],
             BuffKind::Concussion => vec![BuffEffect::DisableAuxiliaryAbilities],
             BuffKind::Staggered => vec![BuffEffect::PoiseReduction(-data.strength)],
+            BuffKind::Electrified => vec![
+                BuffEffect::MovementSpeed(1.0 - nn_scaling(data.strength) * 0.5),
+                BuffEffect::AttackSpeed(1.0 - nn_scaling(data.strength) * 0.5),
+            ],
             BuffKind::Tenacity => vec![
                 BuffEffect::DamageReduction(nn_scaling(data.strength)),
                 BuffEffect::MovementSpeed(0.7),
@@ -583,6 +656,41 @@ impl BuffKind {
         data
     }
 
+    /// How damage over time dealt by this buff scales with the damage modifier
+    /// of its source. Returns None if the buff doesn't deal damage over time.
+    pub fn dot_scaling(self) -> Option<DotScaling> {
+        match self {
+            BuffKind::Bleeding | BuffKind::Crippled => Some(DotScaling::Snapshot),
+            // Fire keeps being fed by its source
+            BuffKind::Burning => Some(DotScaling::Dynamic),
+            _ => None,
+        }
+    }
+
+    /// Priority with which buffs of this kind are removed by a dispel, higher
+    /// priorities are removed first. Returns None if the buff can't be
+    /// dispelled at all.
+    pub fn dispel_priority(self) -> Option<u8> {
+        match self {
+            // These only exist to limit other buffs, dispelling them would allow to bypass
+            // that limit
+            BuffKind::PotionSickness | BuffKind::Resilience | BuffKind::Polymorphed => None,
+            // Crowd control and defensive buffs matter the most in a fight
+            BuffKind::Frozen
+            | BuffKind::Rooted
+            | BuffKind::Ensnared
+            | BuffKind::Concussion
+            | BuffKind::Parried
+            | BuffKind::Invulnerability
+            | BuffKind::ProtectingWard => Some(3),
+            // Damage over time
+            BuffKind::Bleeding | BuffKind::Burning | BuffKind::Poisoned | BuffKind::Cursed => {
+                Some(2)
+            },
+            _ => Some(1),
+        }
+    }
+
     /// If a buff kind should also give resilience when applied, return the
     /// strength that resilience should have, otherwise return None
     pub fn resilience_ccr_strength(&self, data: BuffData) -> Option<f32> {
@@ -594,6 +702,16 @@ impl BuffKind {
     }
 }
 
+/// How damage over time follows the attack damage modifier of the entity that
+/// applied it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DotScaling {
+    /// Uses the modifier the source had when the buff was applied
+    Snapshot,
+    /// Follows the current modifier of the source
+    Dynamic,
+}
+
 // Struct used to store data relevant to a buff
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -605,6 +723,12 @@ pub struct BuffData {
     pub secondary_duration: Option<Secs>,
     /// Used to add random data to buffs if needed (e.g. polymorphed)
     pub misc_data: Option<MiscBuffData>,
+    /// Attack damage modifier of the source at the time the buff was applied,
+    /// used to scale damage over time (see [`BuffKind::dot_scaling`])
+    pub source_damage_modifier: Option<f32>,
+    /// How many successful attacks the buff lasts for (e.g. weapon coatings),
+    /// see [`BuffChange::UseCharge`]
+    pub charges: Option<u32>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -620,6 +744,8 @@ impl BuffData {
             delay: None,
             secondary_duration: None,
             misc_data: None,
+            source_damage_modifier: None,
+            charges: None,
         }
     }
 
@@ -637,6 +763,16 @@ impl BuffData {
         self.misc_data = Some(misc_data);
         self
     }
+
+    pub fn with_source_damage_modifier(mut self, modifier: f32) -> Self {
+        self.source_damage_modifier = Some(modifier);
+        self
+    }
+
+    pub fn with_charges(mut self, charges: u32) -> Self {
+        self.charges = Some(charges);
+        self
+    }
 }
 
 /// De/buff category ID.
@@ -737,6 +873,11 @@ pub enum BuffEffect {
     DisableAuxiliaryAbilities,
     /// Reduces duration of crowd control debuffs
     CrowdControlResistance(f32),
+    /// Reduces the amount of positive buffs removed by dispels
+    DispelResistance(f32),
+    /// Resistance against damage of an element, negative values make the
+    /// entity weak to it instead
+    ElementResistance(DamageElement, f32),
 }
 
 /// Actual de/buff.
@@ -782,6 +923,45 @@ pub enum BuffChange {
     },
     /// Refreshes durations of all buffs with this kind.
     Refresh(BuffKind),
+    /// Removes a limited amount of buffs, see [`Buffs::dispel`].
+    Dispel(Dispel),
+    /// Uses up a charge of every buff that has them, removing those that ran
+    /// out of charges.
+    UseCharge,
+}
+
+/// Time during which an aura can't reapply buffs to an entity after they were
+/// dispelled from it.
+pub const DISPEL_AURA_IMMUNITY: Secs = Secs(10.0);
+
+/// Which de/buffs a dispel removes.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DispelTarget {
+    /// Positive buffs, like stripping an enemy of their wards
+    Buffs,
+    /// Negative buffs, like cleansing an ally
+    Debuffs,
+    Kind(BuffKind),
+    Category(BuffCategory),
+}
+
+/// Removes up to `count` de/buffs matching `target`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Dispel {
+    pub target: DispelTarget,
+    pub count: u32,
+}
+
+impl Dispel {
+    fn matches(&self, buff: &Buff) -> bool {
+        match self.target {
+            DispelTarget::Buffs => buff.kind.is_buff(),
+            DispelTarget::Debuffs => !buff.kind.is_buff(),
+            DispelTarget::Kind(kind) => buff.kind == kind,
+            DispelTarget::Category(cat) => buff.cat_ids.contains(&cat),
+        }
+    }
 }
 
 impl Buff {
@@ -821,6 +1001,26 @@ impl Buff {
 
     /// Calculate how much time has elapsed since the buff was applied
     pub fn elapsed(&self, time: Time) -> Secs { Secs(time.0 - self.start_time.0) }
+
+    /// Damage per second dealt by this buff, if it deals damage over time.
+    /// Dynamically scaling buffs are estimated with the damage modifier of
+    /// their source from when they were applied.
+    pub fn dot_damage_rate(&self) -> Option<f32> {
+        self.kind.dot_scaling()?;
+        let rate = self
+            .effects
+            .iter()
+            .filter_map(|effect| match effect {
+                BuffEffect::HealthChangeOverTime {
+                    rate,
+                    kind: ModifierKind::Additive,
+                    ..
+                } if *rate < 0.0 => Some(-rate),
+                _ => None,
+            })
+            .sum::<f32>();
+        Some(rate * self.data.source_damage_modifier.unwrap_or(1.0))
+    }
 }
 
 impl PartialOrd for Buff {
@@ -893,6 +1093,10 @@ pub struct Buffs {
     pub kinds: EnumMap<BuffKind, Option<(Vec<BuffKey>, Time)>>,
     // All buffs currently present on an entity
     pub buffs: SlotMap<BuffKey, Buff>,
+    /// Auras that had their buffs dispelled from this entity, and the time
+    /// until which they are not allowed to reapply them
+    #[serde(default)]
+    pub aura_immunities: Vec<(Uid, AuraKey, Time)>,
 }
 
 impl Buffs {
@@ -1007,6 +1211,70 @@ impl Buffs {
         }
     }
 
+    /// Removes up to `dispel.count` matching buffs, ordered by their
+    /// [`BuffKind::dispel_priority`] and then by strength. Resistance only
+    /// applies to positive buffs and reduces the amount of them that can be
+    /// removed by that fraction.
+    ///
+    /// Dispelled buffs that came from an active aura make the entity immune to
+    /// that aura for [`DISPEL_AURA_IMMUNITY`], otherwise it would reapply them
+    /// on the next tick.
+    pub fn dispel(&mut self, dispel: Dispel, resistance: f32, time: Time) {
+        self.aura_immunities
+            .retain(|(_, _, until)| until.0 > time.0);
+
+        let mut candidates = self
+            .buffs
+            .iter()
+            .filter(|(_, buff)| dispel.matches(buff))
+            .filter_map(|(key, buff)| Some((key, buff.kind.dispel_priority()?, buff)))
+            .collect::<Vec<_>>();
+        candidates.sort_by(|(_, prio_a, a), (_, prio_b, b)| {
+            prio_b
+                .cmp(prio_a)
+                .then_with(|| b.partial_cmp(a).unwrap_or(Ordering::Equal))
+        });
+
+        let max_positive =
+            (dispel.count as f32 * (1.0 - resistance.clamp(0.0, 1.0))).floor() as u32;
+        let mut removed = 0;
+        let mut removed_positive = 0;
+        let mut keys_to_remove = Vec::new();
+        for (key, _, buff) in candidates {
+            if removed >= dispel.count {
+                break;
+            }
+            if buff.kind.is_buff() {
+                if removed_positive >= max_positive {
+                    continue;
+                }
+                removed_positive += 1;
+            }
+            removed += 1;
+            keys_to_remove.push(key);
+            for cat_id in &buff.cat_ids {
+                if let BuffCategory::FromActiveAura(uid, aura_key) = cat_id {
+                    self.aura_immunities.push((
+                        *uid,
+                        *aura_key,
+                        Time(time.0 + DISPEL_AURA_IMMUNITY.0),
+                    ));
+                }
+            }
+        }
+        for key in keys_to_remove {
+            self.remove(key);
+        }
+    }
+
+    /// Checks whether buffs of this aura were recently dispelled from the
+    /// entity, see [`Buffs::dispel`].
+    pub fn is_aura_immune(&self, uid: Uid, aura_key: AuraKey, time: Time) -> bool {
+        self.aura_immunities
+            .iter()
+            .any(|(u, k, until)| *u == uid && *k == aura_key && until.0 > time.0)
+    }
+
     fn delay_queueable_buffs(&mut self, kind: BuffKind, current_time: Time) {
         let mut next_start_time: Option<Time> = None;
         debug_assert!(kind.queues());
@@ -1173,4 +1441,89 @@ pub mod tests {
                 .any(|b| b.end_time.unwrap().0 > 59.99)
         );
     }
+
+    #[test]
+    fn test_dispel_priority_and_aura_immunity() {
+        let time = Time(0.0);
+        let buff = |kind, strength, cat_ids| {
+            Buff::new(
+                kind,
+                BuffData::new(strength, Some(Secs(30.0))),
+                cat_ids,
+                BuffSource::Unknown,
+                time,
+                DestInfo::default(),
+                None,
+            )
+        };
+        let aura_key = slotmap::KeyData::from_ffi(1).into();
+        let aura_cat = BuffCategory::FromActiveAura(Uid(1), aura_key);
+
+        let mut buff_comp: Buffs = Default::default();
+        buff_comp.insert(buff(BuffKind::Bleeding, 1.0, Vec::new()), time);
+        buff_comp.insert(buff(BuffKind::Crippled, 1.0, Vec::new()), time);
+        buff_comp.insert(buff(BuffKind::Frozen, 0.5, vec![aura_cat]), time);
+        buff_comp.insert(buff(BuffKind::PotionSickness, 1.0, Vec::new()), time);
+        buff_comp.insert(buff(BuffKind::Hastened, 1.0, Vec::new()), time);
+
+        buff_comp.dispel(
+            Dispel {
+                target: DispelTarget::Debuffs,
+                count: 2,
+            },
+            0.0,
+            time,
+        );
+        // Crowd control first, then damage over time
+        assert!(!buff_comp.contains(BuffKind::Frozen));
+        assert!(!buff_comp.contains(BuffKind::Bleeding));
+        assert!(buff_comp.contains(BuffKind::Crippled));
+        assert!(buff_comp.contains(BuffKind::Hastened));
+        assert!(buff_comp.is_aura_immune(Uid(1), aura_key, time));
+        assert!(!buff_comp.is_aura_immune(Uid(1), aura_key, Time(DISPEL_AURA_IMMUNITY.0)));
+
+        buff_comp.dispel(
+            Dispel {
+                target: DispelTarget::Debuffs,
+                count: 5,
+            },
+            0.0,
+            time,
+        );
+        // Potion sickness can't be dispelled
+        assert!(!buff_comp.contains(BuffKind::Crippled));
+        assert!(buff_comp.contains(BuffKind::PotionSickness));
+
+        // Full resistance protects positive buffs
+        buff_comp.dispel(
+            Dispel {
+                target: DispelTarget::Buffs,
+                count: 1,
+            },
+            1.0,
+            time,
+        );
+        assert!(buff_comp.contains(BuffKind::Hastened));
+    }
+
+    #[test]
+    fn test_dot_damage_rate_uses_snapshot() {
+        let buff = |kind, data| {
+            Buff::new(
+                kind,
+                data,
+                Vec::new(),
+                BuffSource::Unknown,
+                Time(0.0),
+                DestInfo::default(),
+                None,
+            )
+        };
+        let data = BuffData::new(10.0, Some(Secs(5.0)));
+        let bleeding = buff(BuffKind::Bleeding, data);
+        assert_eq!(bleeding.dot_damage_rate(), Some(10.0));
+        let snapshotted = buff(BuffKind::Bleeding, data.with_source_damage_modifier(1.5));
+        assert_eq!(snapshotted.dot_damage_rate(), Some(15.0));
+        assert_eq!(buff(BuffKind::Regeneration, data).dot_damage_rate(), None);
+    }
 }
@@ -47,12 +47,14 @@ event_emitters! {
         inventory_manip: event::InventoryManipEvent,
         sprite_summon: event::CreateSpriteEvent,
         change_stance: event::ChangeStanceEvent,
+        revive: event::ReviveEvent,
         create_npc: event::CreateNpcEvent,
         energy_change: event::EnergyChangeEvent,
         knockback: event::KnockbackEvent,
         sprite_light: event::ToggleSpriteLightEvent,
         transform: event::TransformEvent,
         create_aura_entity: event::CreateAuraEntityEvent,
+        dig_ground: event::DigGroundEvent,
     }
 }
 
@@ -102,8 +104,14 @@ pub enum CharacterState {
     Climb(climb::Data),
     Sit,
     Dance,
+    /// A short gesture such as waving or bowing
+    Emote(emote::Data),
     Talk,
     Pet(pet::Data),
+    /// Took lethal damage in group play and is waiting to be revived
+    Downed(downed::Data),
+    /// Channeling a revive on a downed teammate
+    Revive(revive::Data),
     Glide(glide::Data),
     GlideWield(glide_wield::Data),
     /// A stunned state
@@ -316,7 +324,9 @@ impl CharacterState {
             CharacterState::Climb(_)
                 | CharacterState::Equipping(_)
                 | CharacterState::Dance
+                | CharacterState::Emote(_)
                 | CharacterState::Pet(_)
+                | CharacterState::Revive(_)
                 | CharacterState::Glide(_)
                 | CharacterState::GlideWield(_)
                 | CharacterState::Talk
@@ -422,15 +432,26 @@ impl CharacterState {
     pub fn is_music(&self) -> bool { matches!(self, CharacterState::Music(_)) }
 
     pub fn attack_immunities(&self) -> Option<AttackFilters> {
-        if let CharacterState::Roll(c) = self {
-            (c.stage_section == StageSection::Movement).then_some(c.static_data.attack_immunities)
-        } else {
-            None
+        match self {
+            CharacterState::Roll(c) => (c.stage_section == StageSection::Movement)
+                .then_some(c.static_data.attack_immunities),
+            // Downed players can only be executed in melee
+            CharacterState::Downed(_) => Some(AttackFilters {
+                melee: false,
+                projectiles: true,
+                beams: true,
+                ground_shockwaves: true,
+                air_shockwaves: true,
+                explosions: true,
+            }),
+            _ => None,
         }
     }
 
     pub fn is_stunned(&self) -> bool { matches!(self, CharacterState::Stunned(_)) }
 
+    pub fn is_downed(&self) -> bool { matches!(self, CharacterState::Downed(_)) }
+
     pub fn is_forced_movement(&self) -> bool {
         matches!(self, CharacterState::ComboMelee2(s) if s.stage_section == StageSection::Action)
             || matches!(self, CharacterState::DashMelee(s) if s.stage_section == StageSection::Charge)
@@ -450,6 +471,7 @@ impl CharacterState {
             CharacterState::Idle(_)
                 | CharacterState::Sit
                 | CharacterState::Dance
+                | CharacterState::Emote(_)
                 | CharacterState::Talk
                 | CharacterState::Stunned(_)
                 | CharacterState::BasicBlock(_)
@@ -507,7 +529,10 @@ impl CharacterState {
             CharacterState::Stunned(data) => data.behavior(j, output_events),
             CharacterState::Sit => sit::Data::behavior(&sit::Data, j, output_events),
             CharacterState::Dance => dance::Data::behavior(&dance::Data, j, output_events),
+            CharacterState::Emote(data) => data.behavior(j, output_events),
             CharacterState::Pet(data) => data.behavior(j, output_events),
+            CharacterState::Downed(data) => data.behavior(j, output_events),
+            CharacterState::Revive(data) => data.behavior(j, output_events),
             CharacterState::BasicBlock(data) => data.behavior(j, output_events),
             CharacterState::Roll(data) => data.behavior(j, output_events),
             CharacterState::Wielding(data) => data.behavior(j, output_events),
@@ -562,7 +587,10 @@ impl CharacterState {
             CharacterState::Dance => {
                 states::dance::Data::handle_event(&dance::Data, j, output_events, action)
             },
+            CharacterState::Emote(data) => data.handle_event(j, output_events, action),
             CharacterState::Pet(data) => data.handle_event(j, output_events, action),
+            CharacterState::Downed(data) => data.handle_event(j, output_events, action),
+            CharacterState::Revive(data) => data.handle_event(j, output_events, action),
             CharacterState::BasicBlock(data) => data.handle_event(j, output_events, action),
             CharacterState::Roll(data) => data.handle_event(j, output_events, action),
             CharacterState::Wielding(data) => data.handle_event(j, output_events, action),
@@ -617,7 +645,10 @@ impl CharacterState {
             CharacterState::Stunned(_) => None,
             CharacterState::Sit => None,
             CharacterState::Dance => None,
+            CharacterState::Emote(_) => None,
             CharacterState::Pet(_) => None,
+            CharacterState::Downed(_) => None,
+            CharacterState::Revive(_) => None,
             CharacterState::BasicBlock(data) => Some(data.static_data.ability_info),
             CharacterState::Roll(data) => Some(data.static_data.ability_info),
             CharacterState::Wielding(_) => None,
@@ -663,7 +694,10 @@ impl CharacterState {
             CharacterState::Stunned(data) => Some(data.stage_section),
             CharacterState::Sit => None,
             CharacterState::Dance => None,
+            CharacterState::Emote(_) => None,
             CharacterState::Pet(_) => None,
+            CharacterState::Downed(_) => None,
+            CharacterState::Revive(_) => None,
             CharacterState::BasicBlock(data) => Some(data.stage_section),
             CharacterState::Roll(data) => Some(data.stage_section),
             CharacterState::Equipping(_) => Some(StageSection::Buildup),
@@ -713,7 +747,10 @@ impl CharacterState {
             }),
             CharacterState::Sit => None,
             CharacterState::Dance => None,
+            CharacterState::Emote(_) => None,
             CharacterState::Pet(_) => None,
+            CharacterState::Downed(_) => None,
+            CharacterState::Revive(_) => None,
             CharacterState::BasicBlock(data) => Some(DurationsInfo {
                 buildup: Some(data.static_data.buildup_duration),
                 recover: Some(data.static_data.recover_duration),
@@ -900,7 +937,10 @@ impl CharacterState {
             CharacterState::Stunned(data) => Some(data.timer),
             CharacterState::Sit => None,
             CharacterState::Dance => None,
+            CharacterState::Emote(data) => Some(data.timer),
             CharacterState::Pet(_) => None,
+            CharacterState::Downed(data) => Some(data.timer),
+            CharacterState::Revive(data) => Some(data.timer),
             CharacterState::BasicBlock(data) => Some(data.timer),
             CharacterState::Roll(data) => Some(data.timer),
             CharacterState::Wielding(_) => None,
@@ -946,7 +986,10 @@ impl CharacterState {
             CharacterState::Stunned(_) => None,
             CharacterState::Sit => None,
             CharacterState::Dance => None,
+            CharacterState::Emote(_) => None,
             CharacterState::Pet(_) => None,
+            CharacterState::Downed(_) => None,
+            CharacterState::Revive(_) => None,
             CharacterState::BasicBlock(_) => None,
             CharacterState::Roll(_) => None,
             CharacterState::Wielding(_) => None,
@@ -21,6 +21,8 @@ pub enum ChatMode {
     Group,
     /// Talk to your faction
     Faction(String),
+    /// Talk to your guild, by its tag
+    Guild(String),
     /// Talk to every player on the server
     World,
 }
@@ -46,6 +48,7 @@ impl ChatMode {
                 group.ok_or(Content::localized("command-message-group-missing"))?,
             ),
             ChatMode::Faction(faction) => ChatType::Faction(from, faction.clone()),
+            ChatMode::Guild(tag) => ChatType::Guild(from, tag.clone()),
             ChatMode::World => ChatType::World(from),
         };
 
@@ -106,6 +109,8 @@ pub enum ChatType<G> {
     GroupMeta(G),
     /// Server notifications to a faction, such as player join/leave
     FactionMeta(String),
+    /// Server notifications to a guild, such as member join/leave
+    GuildMeta(String),
     /// One-on-one chat (from, to)
     Tell(Uid, Uid),
     /// Chat with nearby players
@@ -114,6 +119,8 @@ pub enum ChatType<G> {
     Group(Uid, G),
     /// Factional chat
     Faction(Uid, String),
+    /// Guild chat
+    Guild(Uid, String),
     /// Regional chat
     Region(Uid),
     /// World chat
@@ -151,12 +158,14 @@ impl<G> ChatType<G> {
             ChatType::CommandInfo => None,
             ChatType::CommandError => None,
             ChatType::FactionMeta(_) => None,
+            ChatType::GuildMeta(_) => None,
             ChatType::GroupMeta(_) => None,
             ChatType::Kill(_, _) => None,
             ChatType::Tell(u, _t) => Some(*u),
             ChatType::Say(u) => Some(*u),
             ChatType::Group(u, _s) => Some(*u),
             ChatType::Faction(u, _s) => Some(*u),
+            ChatType::Guild(u, _s) => Some(*u),
             ChatType::Region(u) => Some(*u),
             ChatType::World(u) => Some(*u),
             ChatType::Npc(u) => Some(*u),
@@ -174,13 +183,17 @@ impl<G> ChatType<G> {
             | ChatType::CommandInfo
             | ChatType::CommandError
             | ChatType::FactionMeta(_)
+            | ChatType::GuildMeta(_)
             | ChatType::GroupMeta(_)
             | ChatType::Npc(_)
             | ChatType::NpcSay(_)
             | ChatType::NpcTell(_, _)
             | ChatType::Meta
             | ChatType::Kill(_, _) => None,
-            ChatType::Tell(_, _) | ChatType::Group(_, _) | ChatType::Faction(_, _) => Some(true),
+            ChatType::Tell(_, _)
+            | ChatType::Group(_, _)
+            | ChatType::Faction(_, _)
+            | ChatType::Guild(_, _) => Some(true),
             ChatType::Say(_) | ChatType::Region(_) | ChatType::World(_) => Some(false),
         }
     }
@@ -231,12 +244,14 @@ impl<G> GenericChatMsg<G> {
             ChatType::CommandInfo => ChatType::CommandInfo,
             ChatType::CommandError => ChatType::CommandError,
             ChatType::FactionMeta(a) => ChatType::FactionMeta(a),
+            ChatType::GuildMeta(a) => ChatType::GuildMeta(a),
             ChatType::GroupMeta(g) => ChatType::GroupMeta(f(g)),
             ChatType::Kill(a, b) => ChatType::Kill(a, b),
             ChatType::Tell(a, b) => ChatType::Tell(a, b),
             ChatType::Say(a) => ChatType::Say(a),
             ChatType::Group(a, g) => ChatType::Group(a, f(g)),
             ChatType::Faction(a, b) => ChatType::Faction(a, b),
+            ChatType::Guild(a, b) => ChatType::Guild(a, b),
             ChatType::Region(a) => ChatType::Region(a),
             ChatType::World(a) => ChatType::World(a),
             ChatType::Npc(a) => ChatType::Npc(a),
@@ -271,12 +286,14 @@ impl<G> GenericChatMsg<G> {
             ChatType::CommandInfo => SpeechBubbleType::None,
             ChatType::CommandError => SpeechBubbleType::None,
             ChatType::FactionMeta(_) => SpeechBubbleType::None,
+            ChatType::GuildMeta(_) => SpeechBubbleType::None,
             ChatType::GroupMeta(_) => SpeechBubbleType::None,
             ChatType::Kill(_, _) => SpeechBubbleType::None,
             ChatType::Tell(_u, _) => SpeechBubbleType::Tell,
             ChatType::Say(_u) => SpeechBubbleType::Say,
             ChatType::Group(_u, _s) => SpeechBubbleType::Group,
             ChatType::Faction(_u, _s) => SpeechBubbleType::Faction,
+            ChatType::Guild(_u, _s) => SpeechBubbleType::Guild,
             ChatType::Region(_u) => SpeechBubbleType::Region,
             ChatType::World(_u) => SpeechBubbleType::World,
             ChatType::Npc(_u) => SpeechBubbleType::None,
@@ -318,6 +335,7 @@ pub enum SpeechBubbleType {
     Region,
     Group,
     Faction,
+    Guild,
     World,
     // For NPCs
     Quest, // TODO not implemented
@@ -1,16 +1,23 @@
 use crate::{
+    bounty::BountyTarget,
     comp::{
         ability,
         dialogue::Subject,
+        group::LootMode,
         inventory::{
             item::tool::ToolKind,
             slot::{EquipSlot, InvSlotId, Slot},
         },
         invite::{InviteKind, InviteResponse},
-        BuffKind,
+        skills::Skill,
+        BuffKind, Title,
     },
+    guild::GuildManip,
+    heraldry::Heraldry,
+    loot_roll::{LootRollChoice, LootRollId},
     mounting::VolumePos,
-    trade::{TradeAction, TradeId},
+    states::emote::EmoteKind,
+    trade::{SiteId, TradeAction, TradeId},
     uid::Uid,
     util::Dir,
 };
@@ -34,6 +41,25 @@ pub enum InventoryEvent {
     OverflowMove(usize, InvSlotId),
     OverflowDrop(usize),
     OverflowSplitDrop(usize),
+    BankDeposit {
+        banker: Uid,
+        slot: InvSlotId,
+    },
+    BankWithdraw {
+        banker: Uid,
+        slot: usize,
+    },
+    BankUpgrade {
+        banker: Uid,
+    },
+    LearnSkill {
+        trainer: Uid,
+        skill: Skill,
+    },
+    SetHeraldry {
+        slot: Slot,
+        heraldry: Option<Heraldry>,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -67,6 +93,30 @@ pub enum InventoryManip {
         craft_sprite: Option<VolumePos>,
     },
     SwapEquippedWeapons,
+    /// Move an item from the inventory into the bank kept by `banker`
+    BankDeposit {
+        banker: Uid,
+        slot: InvSlotId,
+    },
+    /// Move an item from a bank slot back into the inventory
+    BankWithdraw {
+        banker: Uid,
+        slot: usize,
+    },
+    /// Buy the next bank capacity upgrade
+    BankUpgrade {
+        banker: Uid,
+    },
+    /// Pay `trainer` to teach the next level of a skill
+    LearnSkill {
+        trainer: Uid,
+        skill: Skill,
+    },
+    /// Decorate a cape or shield with a heraldic design, or remove its design
+    SetHeraldry {
+        slot: Slot,
+        heraldry: Option<Heraldry>,
+    },
 }
 
 impl From<InventoryEvent> for InventoryManip {
@@ -94,6 +144,11 @@ impl From<InventoryEvent> for InventoryManip {
             },
             InventoryEvent::OverflowDrop(o) => Self::Drop(Slot::Overflow(o)),
             InventoryEvent::OverflowSplitDrop(o) => Self::SplitDrop(Slot::Overflow(o)),
+            InventoryEvent::BankDeposit { banker, slot } => Self::BankDeposit { banker, slot },
+            InventoryEvent::BankWithdraw { banker, slot } => Self::BankWithdraw { banker, slot },
+            InventoryEvent::BankUpgrade { banker } => Self::BankUpgrade { banker },
+            InventoryEvent::LearnSkill { trainer, skill } => Self::LearnSkill { trainer, skill },
+            InventoryEvent::SetHeraldry { slot, heraldry } => Self::SetHeraldry { slot, heraldry },
         }
     }
 }
@@ -129,6 +184,8 @@ pub enum GroupManip {
     Leave,
     Kick(Uid),
     AssignLeader(Uid),
+    SetLootMode(LootMode),
+    LootRoll(LootRollId, LootRollChoice),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -158,8 +215,10 @@ pub enum ControlEvent {
     MountVolume(VolumePos),
     Unmount,
     SetPetStay(Uid, bool),
+    SendScout(Uid),
     InventoryEvent(InventoryEvent),
     GroupManip(GroupManip),
+    GuildManip(GuildManip),
     RemoveBuff(BuffKind),
     LeaveStance,
     Respawn,
@@ -169,7 +228,19 @@ pub enum ControlEvent {
         auxiliary_key: ability::AuxiliaryKey,
         new_ability: ability::AuxiliaryAbility,
     },
+    SwitchAbilityLoadout(usize),
     ActivatePortal(Uid),
+    ActivateShrine(Uid),
+    TravelToShrine(SiteId),
+    ReadNoticeBoard(Vec3<i32>),
+    AcceptBounty {
+        board: Vec3<i32>,
+        target: BountyTarget,
+    },
+    RecoverGravestone(Uid),
+    RequestReputation,
+    /// Show one of the unlocked titles under the character's name, or none
+    SelectTitle(Option<Title>),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -181,9 +252,13 @@ pub enum ControlAction {
     Unwield,
     Sit,
     Dance,
+    Emote(EmoteKind),
     Pet {
         target_uid: Uid,
     },
+    Revive {
+        target_uid: Uid,
+    },
     Sneak,
     Stand,
     Talk,
@@ -0,0 +1,30 @@
+use crate::{comp::Body, resources::Time};
+use specs::{Component, VecStorage};
+
+/// How long (in seconds) the remains of a creature stay fresh enough to be
+/// reanimated
+pub const CORPSE_LIFETIME: f64 = 90.0;
+/// How long (in seconds) a reanimated corpse serves its necromancer before
+/// crumbling
+pub const REANIMATED_LIFETIME: f64 = 60.0;
+
+/// Remains of a creature that died recently. Corpses have no body of their
+/// own, they are only tracked so that necromancers can raise them.
+#[derive(Copy, Clone, Debug)]
+pub struct Corpse {
+    pub body: Body,
+    pub decays_at: Time,
+}
+
+impl Corpse {
+    pub fn new(body: Body, time: Time) -> Self {
+        Self {
+            body,
+            decays_at: Time(time.0 + CORPSE_LIFETIME),
+        }
+    }
+}
+
+impl Component for Corpse {
+    type Storage = VecStorage<Self>;
+}
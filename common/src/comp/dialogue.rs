@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use vek::{Vec2, Vec3};
 
 use super::Item;
+use crate::rtsim::Profession;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AskedLocation {
@@ -9,10 +10,47 @@ pub struct AskedLocation {
     pub origin: Vec2<i32>,
 }
 
+/// Characters this close to the site that directions lead to have arrived
+pub const DIRECTIONS_ARRIVAL_RADIUS: f32 = 64.0;
+
+/// Directions to a site given by an NPC, following the roads they would take
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Directions {
+    /// Name of the site the directions lead to
+    pub destination: String,
+    /// Points along the way, the last one being the site itself
+    pub route: Vec<Vec2<f32>>,
+}
+
+impl Directions {
+    /// Drops the points along the route that a character at `pos` has passed
+    /// already. Returns whether they arrived at the site.
+    pub fn follow(&mut self, pos: Vec2<f32>) -> bool {
+        // The character may have taken a shortcut, so skip ahead to wherever the
+        // route is closest
+        if let Some(closest) = self
+            .route
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+            .map(|(i, _)| i)
+        {
+            self.route.drain(..closest);
+        }
+        self.route.last().map_or(true, |site| {
+            site.distance_squared(pos) < DIRECTIONS_ARRIVAL_RADIUS.powi(2)
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PersonType {
     Merchant,
-    Villager { name: String },
+    Villager {
+        name: String,
+    },
+    /// Whoever in the town has this profession, e.g. the blacksmith
+    Profession(Profession),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -26,6 +64,9 @@ impl AskedPerson {
         match &self.person_type {
             PersonType::Merchant => "The Merchant".to_string(),
             PersonType::Villager { name } => name.clone(),
+            PersonType::Profession(profession) => {
+                format!("The {}", <&str>::from(profession))
+            },
         }
     }
 }
@@ -59,6 +100,10 @@ pub enum Subject {
     /// So either it'll tell you something to do, or just say that he got
     /// nothing
     Work,
+    /// Haggling over the prices of an ongoing trade
+    /// NPC will either grant you a discount, or refuse and think less of you
+    /// for asking
+    Haggle,
 }
 
 /// Context of why a NPC has a specific mood (good, neutral, bad, ...)
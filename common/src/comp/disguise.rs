@@ -0,0 +1,48 @@
+use crate::{
+    comp::{item::ItemTag, Inventory},
+    resources::Time,
+};
+use specs::{Component, VecStorage};
+
+/// Item tags of garb that lets its wearer pass as a member of a faction
+pub const DISGUISE_TAGS: [ItemTag; 3] = [ItemTag::Cultist, ItemTag::Gnarling, ItemTag::Pirate];
+
+/// How many pieces of garb have to be worn for a disguise to be convincing
+pub const DISGUISE_PIECES: usize = 3;
+
+/// How long (in seconds) a disguise stays seen through after its wearer acted
+/// suspiciously
+pub const EXPOSURE_DURATION: f64 = 120.0;
+
+/// Returns the tag of the faction garb the inventory's owner is disguised in,
+/// if any
+pub fn disguise(inventory: &Inventory) -> Option<ItemTag> {
+    DISGUISE_TAGS.into_iter().find(|tag| {
+        inventory
+            .equipped_items()
+            .filter(|item| item.tags().contains(tag))
+            .count()
+            >= DISGUISE_PIECES
+    })
+}
+
+/// An entity whose disguise was seen through, it will be treated according to
+/// its true standing until the exposure wears off.
+#[derive(Copy, Clone, Debug)]
+pub struct Exposed {
+    pub until: Time,
+}
+
+impl Exposed {
+    pub fn new(time: Time) -> Self {
+        Self {
+            until: Time(time.0 + EXPOSURE_DURATION),
+        }
+    }
+
+    pub fn is_active(&self, time: Time) -> bool { time.0 < self.until.0 }
+}
+
+impl Component for Exposed {
+    type Storage = VecStorage<Self>;
+}
@@ -0,0 +1,224 @@
+use crate::{
+    assets::{self, AssetExt},
+    comp::{BuffKind, InputKind},
+    resources::{Secs, Time},
+    terrain::SpriteKind,
+};
+use serde::Deserialize;
+use specs::{Component, VecStorage};
+
+/// Data-driven script for a boss encounter.
+///
+/// Scripts are loaded from `common.encounter.*` and referenced by entity
+/// configs through `Meta::Encounter`. They are executed by the server's
+/// encounter system on top of the regular agent behaviour.
+///
+/// Check assets/common/encounter/dwarven_quarry/forgemaster.ron for an example.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EncounterScript {
+    /// Phases of the encounter, ordered by descending health threshold. The
+    /// first phase is entered when the encounter starts.
+    pub phases: Vec<EncounterPhase>,
+    /// Applied once the encounter has lasted longer than `Enrage::after`.
+    #[serde(default)]
+    pub enrage: Option<Enrage>,
+}
+
+impl assets::Asset for EncounterScript {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EncounterPhase {
+    /// Fraction of maximum health at or below which this phase begins.
+    pub health_threshold: f32,
+    /// Inputs triggered in order, looping back to the start once the last one
+    /// was used. Each step waits for its delay after the previous step.
+    #[serde(default)]
+    pub rotation: Vec<RotationStep>,
+    /// Entities spawned around the boss when the phase begins.
+    #[serde(default)]
+    pub adds: Vec<AddSpawn>,
+    /// Sprites periodically created around the boss during this phase.
+    #[serde(default)]
+    pub hazards: Vec<ArenaHazard>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RotationStep {
+    pub input: InputKind,
+    pub delay: Secs,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AddSpawn {
+    /// Entity config asset of the add.
+    pub entity: String,
+    pub amount: u32,
+    /// Radius of the ring around the boss that adds are placed on.
+    pub radius: f32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ArenaHazard {
+    pub sprite: SpriteKind,
+    /// How many sprites are created each time the hazard triggers.
+    pub count: u32,
+    /// Maximum distance from the boss at which sprites are placed.
+    pub radius: f32,
+    pub interval: Secs,
+    /// Time after which the sprites are removed again, see
+    /// `CreateSpriteEvent::del_timeout`.
+    pub lifetime: Option<(f32, f32)>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Enrage {
+    /// Time since the start of the encounter after which the boss enrages.
+    pub after: Secs,
+    /// Buffs applied to the boss when it enrages, with their strength.
+    pub buffs: Vec<(BuffKind, f32)>,
+}
+
+impl EncounterScript {
+    /// Index of the phase that should be active at the given health
+    /// fraction. Phases are never left for an earlier one, so the current
+    /// phase is passed in as lower bound.
+    pub fn phase_for_health(&self, current: usize, health_fraction: f32) -> usize {
+        self.phases
+            .iter()
+            .enumerate()
+            .skip(current + 1)
+            .take_while(|(_, phase)| health_fraction <= phase.health_threshold)
+            .last()
+            .map_or(current, |(i, _)| i)
+    }
+}
+
+/// Runtime state of a scripted encounter attached to a boss entity.
+#[derive(Clone, Debug)]
+pub struct Encounter {
+    /// Asset specifier of the `EncounterScript`.
+    pub script: String,
+    pub state: Option<EncounterState>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EncounterState {
+    pub started: Time,
+    pub phase: usize,
+    /// Index of the next rotation step and the time it should be used at.
+    pub next_rotation: (usize, Time),
+    /// Next trigger time of each hazard of the current phase.
+    pub next_hazards: Vec<Time>,
+    pub enraged: bool,
+}
+
+impl Encounter {
+    pub fn new(script: String) -> Self {
+        Self {
+            script,
+            state: None,
+        }
+    }
+
+    pub fn load_script(&self) -> Option<assets::AssetHandle<EncounterScript>> {
+        EncounterScript::load(&self.script)
+            .inspect_err(|err| {
+                tracing::error!(?err, "Failed to load encounter script {}", self.script)
+            })
+            .ok()
+    }
+}
+
+impl EncounterState {
+    pub fn enter_phase(script: &EncounterScript, phase: usize, started: Time, time: Time) -> Self {
+        let phase_data = script.phases.get(phase);
+        Self {
+            started,
+            phase,
+            next_rotation: (
+                0,
+                Time(
+                    time.0
+                        + phase_data
+                            .and_then(|p| p.rotation.first())
+                            .map_or(0.0, |step| step.delay.0),
+                ),
+            ),
+            next_hazards: phase_data.map_or_else(Vec::new, |p| {
+                p.hazards
+                    .iter()
+                    .map(|hazard| Time(time.0 + hazard.interval.0))
+                    .collect()
+            }),
+            enraged: false,
+        }
+    }
+}
+
+impl Component for Encounter {
+    type Storage = VecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::EntityConfig;
+
+    #[test]
+    fn test_all_encounter_scripts() {
+        let scripts = assets::load_rec_dir::<EncounterScript>("common.encounter")
+            .expect("Failed to access encounter scripts directory");
+        for id in scripts.read().ids() {
+            let script = EncounterScript::load_expect(id).read();
+            assert!(!script.phases.is_empty(), "No phases in {id}");
+            for pair in script.phases.windows(2) {
+                assert!(
+                    pair[0].health_threshold > pair[1].health_threshold,
+                    "Phases must be ordered by descending health threshold in {id}"
+                );
+            }
+            for phase in &script.phases {
+                for add in &phase.adds {
+                    EntityConfig::load_cloned(&add.entity).unwrap_or_else(|_| {
+                        panic!("Invalid add entity \"{}\" in {id}", add.entity)
+                    });
+                }
+                for hazard in &phase.hazards {
+                    assert!(
+                        hazard.interval.0 > 0.0,
+                        "Hazard interval must be positive in {id}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_phase_for_health() {
+        let phase = |health_threshold| EncounterPhase {
+            health_threshold,
+            rotation: Vec::new(),
+            adds: Vec::new(),
+            hazards: Vec::new(),
+        };
+        let script = EncounterScript {
+            phases: vec![phase(1.0), phase(0.6), phase(0.3)],
+            enrage: None,
+        };
+        assert_eq!(script.phase_for_health(0, 0.9), 0);
+        assert_eq!(script.phase_for_health(0, 0.5), 1);
+        assert_eq!(script.phase_for_health(0, 0.1), 2);
+        // Healing doesn't revert a phase
+        assert_eq!(script.phase_for_health(2, 1.0), 2);
+    }
+}
@@ -238,9 +238,10 @@ impl Body {
             | Body::QuadrupedMedium(_)
             | Body::QuadrupedSmall(_)
             | Body::QuadrupedLow(_)
-            | Body::Arthropod(_) => {
+            | Body::Arthropod(_)
+            | Body::Serpent(_) => {
                 let dim = self.dimensions().map(|a| a * 0.5 * scale);
-                let cd: f32 = if matches!(self, Body::QuadrupedLow(_)) {
+                let cd: f32 = if matches!(self, Body::QuadrupedLow(_) | Body::Serpent(_)) {
                     0.7
                 } else {
                     1.0
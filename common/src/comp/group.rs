@@ -37,6 +37,20 @@ pub struct GroupInfo {
     pub num_members: u32,
     // Name of the group
     pub name: String,
+    // How items dropped for the group are handed out
+    pub loot_mode: LootMode,
+}
+
+/// How loot owned by a group is distributed between its members, chosen by
+/// the group leader
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LootMode {
+    /// Any group member can pick up group loot
+    #[default]
+    FreeForAll,
+    /// Boss and chest loot is rolled for, members choosing need, greed or pass
+    /// for each item
+    NeedGreed,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -56,6 +70,8 @@ pub enum ChangeNotification<E> {
     NewGroup { leader: E, members: Vec<(E, Role)> },
     // No longer in a group
     NoGroup,
+    // The leader changed how the group's loot is distributed
+    LootMode(LootMode),
 }
 // Note: now that we are dipping into uids here consider just using
 // ChangeNotification<Uid> everywhere
@@ -78,6 +94,7 @@ impl<E> ChangeNotification<E> {
                 })
             },
             Self::NoGroup => Some(ChangeNotification::NoGroup),
+            Self::LootMode(mode) => Some(ChangeNotification::LootMode(*mode)),
         }
     }
 }
@@ -146,6 +163,7 @@ impl GroupManager {
             leader,
             num_members,
             name: "Group".into(),
+            loot_mode: LootMode::default(),
         }) as u32)
     }
 
@@ -243,6 +261,11 @@ impl GroupManager {
             },
         });
         notifier(new_member, ChangeNotification::NewLeader(leader));
+        if let Some(info) = self.group_info(group)
+            && info.loot_mode != LootMode::default()
+        {
+            notifier(new_member, ChangeNotification::LootMode(info.loot_mode));
+        }
 
         // Add group id for new member and pets
         // Unwrap should not fail since we just found these entities and they should
@@ -531,6 +554,34 @@ impl GroupManager {
             Role::Pet => {},
         });
     }
+
+    // Change how the group's loot is distributed
+    // Does nothing if the entity is not part of a group
+    pub fn set_loot_mode<'a>(
+        &mut self,
+        member: specs::Entity,
+        loot_mode: LootMode,
+        groups: impl GenericReadStorage<Component = Group> + Join<Type = &'a Group> + 'a,
+        entities: &'a specs::Entities,
+        alignments: &'a Alignments,
+        uids: &'a Uids,
+        mut notifier: impl FnMut(specs::Entity, ChangeNotification<specs::Entity>),
+    ) {
+        let group = match groups.get(member) {
+            Some(group) => *group,
+            None => return,
+        };
+
+        match self.group_info_mut(group) {
+            Some(info) => info.loot_mode = loot_mode,
+            None => return,
+        }
+
+        members(group, groups, entities, alignments, uids).for_each(|(e, role)| match role {
+            Role::Member => notifier(e, ChangeNotification::LootMode(loot_mode)),
+            Role::Pet => {},
+        });
+    }
 }
 
 impl Group {
@@ -0,0 +1,39 @@
+use specs::{Component, DenseVecStorage};
+
+/// Extra health given to a scaled enemy for every additional player
+const HEALTH_PER_PLAYER: f32 = 0.6;
+/// Extra damage dealt by a scaled enemy for every additional player
+const DAMAGE_PER_PLAYER: f32 = 0.15;
+/// Extra loot dropped by a scaled enemy for every additional player
+const LOOT_PER_PLAYER: f32 = 0.5;
+/// Groups larger than this don't make enemies any stronger
+const MAX_SCALED_PLAYERS: u32 = 6;
+
+/// Difficulty of a dungeon enemy, decided by the server from the number of
+/// group members present in the dungeon when the enemy first engages.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GroupScaling {
+    players: u32,
+}
+
+impl GroupScaling {
+    pub fn new(players: u32) -> Self {
+        Self {
+            players: players.clamp(1, MAX_SCALED_PLAYERS),
+        }
+    }
+
+    pub fn players(&self) -> u32 { self.players }
+
+    fn extra_players(&self) -> f32 { (self.players - 1) as f32 }
+
+    pub fn health_multiplier(&self) -> f32 { 1.0 + HEALTH_PER_PLAYER * self.extra_players() }
+
+    pub fn damage_multiplier(&self) -> f32 { 1.0 + DAMAGE_PER_PLAYER * self.extra_players() }
+
+    pub fn loot_multiplier(&self) -> f32 { 1.0 + LOOT_PER_PLAYER * self.extra_players() }
+}
+
+impl Component for GroupScaling {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -115,6 +115,15 @@ impl Health {
         self.current = self.current.min(self.maximum);
     }
 
+    /// Like [`Self::update_internal_integer_maximum`], but keeps the current
+    /// fraction of health instead of only clamping it, so that raising the
+    /// maximum also raises the current health.
+    pub fn rescale_internal_integer_maximum(&mut self, maximum: u32) {
+        let fraction = self.current as f64 / self.maximum.max(1) as f64;
+        self.maximum = maximum;
+        self.current = ((maximum as f64 * fraction).round() as u32).min(self.maximum);
+    }
+
     pub fn new(body: comp::Body) -> Self {
         let health = u32::from(body.base_health()) * Self::SCALING_FACTOR_INT;
         Health {
@@ -178,10 +187,23 @@ impl Health {
             .map(|(contrib, (_, time))| (contrib.uid(), *time))
     }
 
+    /// Whether the last health change was damage taken less than `secs`
+    /// seconds ago, used to tell if someone is still in combat
+    pub fn damaged_within(&self, time: Time, secs: f64) -> bool {
+        self.last_change.amount < 0.0 && time.0 - self.last_change.time.0 < secs
+    }
+
     pub fn should_die(&self) -> bool { self.current == 0 }
 
     pub fn kill(&mut self) { self.current = 0; }
 
+    /// Sets current health to a fraction of the maximum and clears the dead
+    /// flag, used when players are downed or revived instead of dying
+    pub fn restore_fraction(&mut self, fraction: f32) {
+        self.current = ((self.maximum as f32 * fraction) as u32).clamp(1, self.maximum.max(1));
+        self.is_dead = false;
+    }
+
     pub fn revive(&mut self) {
         self.current = self.maximum;
         self.is_dead = false;
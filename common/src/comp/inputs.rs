@@ -7,6 +7,9 @@ use vek::geom::Aabb;
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CanBuild {
     pub enabled: bool,
+    /// Creative players can fly, can't be hurt and can build outside of build
+    /// areas
+    pub creative: bool,
     pub build_areas: HashSet<Id<Aabb<i32>>>,
 }
 impl Component for CanBuild {
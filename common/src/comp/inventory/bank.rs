@@ -0,0 +1,122 @@
+use crate::comp::{inventory::InvSlot, Item};
+use serde::{Deserialize, Serialize};
+
+/// Number of bank slots every character starts with
+pub const BASE_BANK_SLOTS: usize = 18;
+/// Number of bank slots added by each capacity upgrade
+pub const BANK_SLOTS_PER_UPGRADE: usize = 9;
+/// Maximum number of capacity upgrades that can be bought
+pub const MAX_BANK_UPGRADES: u16 = 4;
+/// Coin cost of the first capacity upgrade, doubling with every upgrade after
+const BASE_BANK_UPGRADE_COST: u32 = 500;
+
+/// Per-character item storage, only accessible while standing next to a
+/// banker. Items kept here are not part of the regular inventory, so they
+/// can't be used for crafting or offered in trades.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Bank {
+    slots: Vec<InvSlot>,
+    upgrades: u16,
+}
+
+impl Default for Bank {
+    fn default() -> Self {
+        Self {
+            slots: vec![None; BASE_BANK_SLOTS],
+            upgrades: 0,
+        }
+    }
+}
+
+impl Bank {
+    fn capacity_for(upgrades: u16) -> usize {
+        BASE_BANK_SLOTS + BANK_SLOTS_PER_UPGRADE * usize::from(upgrades)
+    }
+
+    /// Total number of slots in the bank
+    pub fn capacity(&self) -> usize { self.slots.len() }
+
+    /// Number of capacity upgrades that have been bought
+    pub fn upgrades(&self) -> u16 { self.upgrades }
+
+    /// Coin cost of the next capacity upgrade, `None` if the bank is already
+    /// fully upgraded
+    pub fn upgrade_cost(&self) -> Option<u32> {
+        (self.upgrades < MAX_BANK_UPGRADES).then(|| BASE_BANK_UPGRADE_COST << self.upgrades)
+    }
+
+    pub fn slots(&self) -> impl ExactSizeIterator<Item = &InvSlot> { self.slots.iter() }
+
+    pub fn get(&self, slot: usize) -> Option<&Item> { self.slots.get(slot)?.as_ref() }
+
+    pub fn free_slots(&self) -> usize { self.slots.iter().filter(|s| s.is_none()).count() }
+
+    /// Adds an item to the bank, stacking it onto an equal item if possible.
+    /// Returns the item again if there was no room for it.
+    pub(super) fn push(&mut self, item: Item) -> Result<(), Item> {
+        if item.is_stackable() {
+            if let Some(existing) = self.slots.iter_mut().flatten().find(|existing| {
+                **existing == item && existing.amount() + item.amount() <= existing.max_amount()
+            }) {
+                return existing.increase_amount(item.amount()).map_err(|_| item);
+            }
+        }
+        match self.slots.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some(item);
+                Ok(())
+            },
+            None => Err(item),
+        }
+    }
+
+    /// Puts an item into a specific slot, returning it if the slot is not
+    /// empty.
+    pub(super) fn insert_at(&mut self, slot: usize, item: Item) -> Result<(), Item> {
+        match self.slots.get_mut(slot) {
+            Some(slot @ None) => {
+                *slot = Some(item);
+                Ok(())
+            },
+            _ => Err(item),
+        }
+    }
+
+    #[must_use = "Returned items will be lost if not used"]
+    pub(super) fn remove(&mut self, slot: usize) -> Option<Item> {
+        self.slots.get_mut(slot)?.take()
+    }
+
+    pub(super) fn upgrade(&mut self) {
+        if self.upgrades < MAX_BANK_UPGRADES {
+            self.upgrades += 1;
+            self.slots.resize(Self::capacity_for(self.upgrades), None);
+        }
+    }
+
+    /// Rebuilds a bank from persisted items and their slot indices. Items
+    /// whose slot does not exist (or is already taken) are returned so that
+    /// they can be put into overflow instead of being lost.
+    pub fn bank_from_persistence(
+        upgrades: u16,
+        items: impl IntoIterator<Item = (usize, Item)>,
+    ) -> (Self, Vec<Item>) {
+        let upgrades = upgrades.min(MAX_BANK_UPGRADES);
+        let mut bank = Self {
+            slots: vec![None; Self::capacity_for(upgrades)],
+            upgrades,
+        };
+        let leftovers = items
+            .into_iter()
+            .filter_map(|(slot, item)| bank.insert_at(slot, item).err())
+            .collect();
+        (bank, leftovers)
+    }
+
+    pub fn persistence_slots_with_index(&self) -> impl Iterator<Item = (usize, &Item)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, slot)| Some((i, slot.as_ref()?)))
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    combat::ElementResistances,
     comp::item::{DurabilityMultiplier, MaterialStatManifest, Rgb},
     terrain::{Block, BlockKind},
 };
@@ -122,6 +123,10 @@ pub struct Stats {
     /// Ground contact type, mostly for shoes
     #[serde(default)]
     pub ground_contact: Friction,
+    /// Resistances against elemental damage are combined multiplicatively
+    /// with those of other equipped items
+    #[serde(default)]
+    pub resistances: ElementResistances,
 }
 
 impl Stats {
@@ -134,6 +139,7 @@ impl Stats {
             precision_power: None,
             stealth: None,
             ground_contact: Friction::Normal,
+            resistances: ElementResistances::default(),
         }
     }
 }
@@ -157,6 +163,7 @@ impl Mul<f32> for Stats {
             stealth: self.stealth.map(|a| a * val),
             // There is nothing to multiply, it is just an enum
             ground_contact: self.ground_contact,
+            resistances: self.resistances * val,
         }
     }
 }
@@ -182,6 +189,7 @@ impl Sub<Stats> for Stats {
                 .map(|(a, b)| a - b),
             stealth: self.stealth.zip(other.stealth).map(|(a, b)| a - b),
             ground_contact: Friction::Normal,
+            resistances: self.resistances - other.resistances,
         }
     }
 }
@@ -292,6 +300,7 @@ impl Armor {
                 precision_power: None,
                 stealth: None,
                 ground_contact: Friction::Normal,
+                resistances: ElementResistances::default(),
             }),
         }
     }
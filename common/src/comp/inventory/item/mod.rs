@@ -9,8 +9,12 @@ pub use tool::{AbilityMap, AbilitySet, AbilitySpec, Hands, Tool, ToolKind};
 
 use crate::{
     assets::{self, AssetExt, BoxedError, Error},
-    comp::inventory::InvSlot,
+    comp::{
+        inventory::InvSlot,
+        skillset::{skills::Skill, SkillGroupKind},
+    },
     effect::Effect,
+    heraldry::Heraldry,
     recipe::RecipeInput,
     resources::ProgramTime,
     terrain::Block,
@@ -55,6 +59,7 @@ pub enum Utility {
     Coins,
     Collar,
     Key,
+    Balloon,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -275,6 +280,7 @@ pub enum ItemTag {
     MaterialKind(MaterialKind),
     Cultist,
     Gnarling,
+    Pirate,
     Potion,
     Food,
     BaseMaterial, // Cloth-scraps, Leather...
@@ -291,6 +297,7 @@ impl TagExampleInfo for ItemTag {
             ItemTag::MaterialKind(material_kind) => material_kind.into(),
             ItemTag::Cultist => "cultist",
             ItemTag::Gnarling => "gnarling",
+            ItemTag::Pirate => "pirate",
             ItemTag::Potion => "potion",
             ItemTag::Food => "food",
             ItemTag::BaseMaterial => "basemat",
@@ -308,6 +315,7 @@ impl TagExampleInfo for ItemTag {
             ItemTag::MaterialKind(_) => None,
             ItemTag::Cultist => Some("common.items.tag_examples.cultist"),
             ItemTag::Gnarling => Some("common.items.tag_examples.gnarling"),
+            ItemTag::Pirate => Some("common.items.tag_examples.pirate"),
             ItemTag::Potion => None,
             ItemTag::Food => None,
             ItemTag::BaseMaterial => None,
@@ -369,6 +377,11 @@ pub enum ItemKind {
     RecipeGroup {
         recipes: Vec<String>,
     },
+    SkillBook {
+        teaches: SkillBook,
+    },
+    /// Shows the way to a buried treasure cache when read
+    TreasureMap,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -380,6 +393,16 @@ pub enum ConsumableKind {
     Recipe,
 }
 
+/// What reading a skill book teaches
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkillBook {
+    /// Grants skill points in a skill group the reader has unlocked
+    SkillPoints { group: SkillGroupKind, amount: u16 },
+    /// Teaches the next level of a skill without spending skill points, the
+    /// prerequisites of the skill still have to be met
+    Skill(Skill),
+}
+
 impl ItemKind {
     pub fn is_equippable(&self) -> bool {
         matches!(
@@ -409,6 +432,8 @@ impl ItemKind {
             ItemKind::Ingredient { descriptor } => format!("Ingredient: {}", descriptor),
             ItemKind::TagExamples { item_ids } => format!("TagExamples: {:?}", item_ids),
             ItemKind::RecipeGroup { .. } => String::from("Recipes:"),
+            ItemKind::SkillBook { .. } => String::from("SkillBook:"),
+            ItemKind::TreasureMap => String::from("TreasureMap:"),
         }
     }
 
@@ -424,7 +449,9 @@ impl ItemKind {
             | ItemKind::Utility { .. }
             | ItemKind::Ingredient { .. }
             | ItemKind::TagExamples { .. }
-            | ItemKind::RecipeGroup { .. } => false,
+            | ItemKind::RecipeGroup { .. }
+            | ItemKind::SkillBook { .. }
+            | ItemKind::TreasureMap => false,
         }
     }
 }
@@ -479,6 +506,9 @@ pub struct Item {
     /// converted into the items durability. Only tracked for tools and armor
     /// currently.
     durability_lost: Option<u32>,
+    /// Design shown on capes and shields, see [`Item::can_bear_heraldry`]
+    #[serde(default)]
+    heraldry: Option<Heraldry>,
 }
 
 /// Newtype around [`Item`] used for frontend events to prevent it accidentally
@@ -975,6 +1005,7 @@ impl Item {
             item_config: None,
             hash: 0,
             durability_lost: None,
+            heraldry: None,
         };
         item.durability_lost = item.has_durability().then_some(0);
         item.update_item_state(ability_map, msm);
@@ -1092,6 +1123,7 @@ impl Item {
                     .map(|old_item| old_item.duplicate(ability_map, msm));
             },
         );
+        new_item.heraldry = self.heraldry;
         new_item
     }
 
@@ -1466,6 +1498,27 @@ impl Item {
         self.update_item_state(ability_map, msm);
     }
 
+    /// Capes and shields can be decorated with a heraldic design
+    pub fn can_bear_heraldry(&self) -> bool {
+        match &*self.kind() {
+            ItemKind::Armor(armor) => matches!(armor.kind, armor::ArmorKind::Back),
+            ItemKind::Tool(tool) => tool.kind == tool::ToolKind::Shield,
+            _ => false,
+        }
+    }
+
+    pub fn heraldry(&self) -> Option<Heraldry> { self.heraldry }
+
+    /// Returns whether the design could be applied
+    pub fn set_heraldry(&mut self, heraldry: Option<Heraldry>) -> bool {
+        if self.can_bear_heraldry() {
+            self.heraldry = heraldry;
+            true
+        } else {
+            false
+        }
+    }
+
     /// If an item is stackable and has an amount greater than 1, creates a new
     /// item with half the amount (rounded down), and decreases the amount of
     /// the original item by the same quantity.
@@ -1516,6 +1569,7 @@ impl Item {
             && self.slots().iter().all(Option::is_none)
             && other.slots().iter().all(Option::is_none)
             && self.durability_lost() == other.durability_lost()
+            && self.heraldry == other.heraldry
     }
 
     /// Checks if this item and another are suitable for grouping into the same
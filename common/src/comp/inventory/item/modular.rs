@@ -633,6 +633,26 @@ pub fn weapon_to_key(mod_weap: impl ItemDesc) -> ModularWeaponKey {
     }
 }
 
+/// This is used as a key to uniquely identify the parts a modular weapon is
+/// built from in voxygen (Main component, material, secondary component, hands)
+pub type ModularWeaponPartsKey = (String, String, String, Hands);
+
+pub fn weapon_to_parts_key(mod_weap: impl ItemDesc) -> ModularWeaponPartsKey {
+    let secondary_id = mod_weap
+        .components()
+        .iter()
+        .find_map(|comp| match &*comp.kind() {
+            ItemKind::ModularComponent(ModularComponent::ToolSecondaryComponent { .. }) => {
+                Some(comp.item_definition_id().itemdef_id()?.to_owned())
+            },
+            _ => None,
+        })
+        .unwrap_or_default();
+    let (primary_id, material_id, hands) = weapon_to_key(mod_weap);
+
+    (primary_id, material_id, secondary_id, hands)
+}
+
 /// This is used as a key to uniquely identify the modular weapon in asset
 /// manifests in voxygen (Main component, material)
 pub type ModularWeaponComponentKey = (String, String);
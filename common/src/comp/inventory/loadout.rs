@@ -7,6 +7,7 @@ use crate::{
         },
         Item,
     },
+    heraldry::Heraldry,
     resources::Time,
 };
 use hashbrown::HashMap;
@@ -504,6 +505,19 @@ impl Loadout {
         }
     }
 
+    /// Returns whether the design could be applied to the item in the slot
+    pub(super) fn set_heraldry_at_slot(
+        &mut self,
+        equip_slot: EquipSlot,
+        heraldry: Option<Heraldry>,
+    ) -> bool {
+        self.slots
+            .iter_mut()
+            .find(|slot| slot.equip_slot == equip_slot)
+            .and_then(|slot| slot.slot.as_mut())
+            .map_or(false, |item| item.set_heraldry(heraldry))
+    }
+
     pub(super) fn cull_recently_unequipped_items(&mut self, time: Time) {
         self.recently_unequipped_items
             .retain(|_def, (unequip_time, count)| {
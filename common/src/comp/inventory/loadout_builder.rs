@@ -8,7 +8,7 @@ use crate::{
             slot::{ArmorSlot, EquipSlot},
         },
         item::{self, Item},
-        object, quadruped_low, quadruped_medium, quadruped_small, theropod, Body,
+        object, quadruped_low, quadruped_medium, quadruped_small, serpent, theropod, Body,
     },
     resources::{Time, TimeOfDay},
     trade::SiteInformation,
@@ -982,6 +982,14 @@ fn default_main_tool(body: &Body) -> Item {
         Body::Crustacean(_) => Some(Item::new_from_asset_expect(
             "common.items.npc_weapons.unique.crab_pincer",
         )),
+        Body::Serpent(body) => match body.species {
+            serpent::Species::Viper => Some(Item::new_from_asset_expect(
+                "common.items.npc_weapons.unique.viper_fangs",
+            )),
+            serpent::Species::Naga => Some(Item::new_from_asset_expect(
+                "common.items.npc_weapons.unique.naga_fangs",
+            )),
+        },
 
         _ => None,
     };
@@ -1,5 +1,6 @@
 use core::ops::Not;
 use hashbrown::HashMap;
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 use specs::{Component, DerefFlaggedStorage};
 use std::{cmp::Ordering, convert::TryFrom, mem, num::NonZeroU32, ops::Range};
@@ -10,6 +11,7 @@ use crate::{
     comp::{
         body::Body,
         inventory::{
+            bank::Bank,
             item::{
                 item_key::ItemKey, tool::AbilityMap, ItemDef, ItemDefinitionIdOwned, ItemKind,
                 MaterialStatManifest, TagExampleInfo,
@@ -22,6 +24,7 @@ use crate::{
         slot::{InvSlotId, SlotId},
         Item,
     },
+    heraldry::Heraldry,
     recipe::{Recipe, RecipeBookManifest},
     resources::Time,
     terrain::SpriteKind,
@@ -31,6 +34,7 @@ use crate::{
 
 use super::FrontendItem;
 
+pub mod bank;
 pub mod item;
 pub mod loadout;
 pub mod loadout_builder;
@@ -58,6 +62,8 @@ pub struct Inventory {
     overflow_items: Vec<Item>,
     /// Recipes that are available for use
     recipe_book: RecipeBook,
+    /// Items stored away at a banker
+    bank: Bank,
 }
 
 /// Errors which the methods on `Inventory` produce
@@ -138,6 +144,7 @@ impl Inventory {
             slots: vec![None; DEFAULT_INVENTORY_SLOTS],
             overflow_items: Vec::new(),
             recipe_book: RecipeBook::default(),
+            bank: Bank::default(),
         }
     }
 
@@ -148,6 +155,7 @@ impl Inventory {
             slots: vec![None; 1],
             overflow_items: Vec::new(),
             recipe_book: RecipeBook::default(),
+            bank: Bank::default(),
         }
     }
 
@@ -156,6 +164,11 @@ impl Inventory {
         self
     }
 
+    pub fn with_bank(mut self, bank: Bank) -> Inventory {
+        self.bank = bank;
+        self
+    }
+
     /// Total number of slots in in the inventory.
     pub fn capacity(&self) -> usize { self.slots().count() }
 
@@ -685,6 +698,23 @@ impl Inventory {
             .filter_map(mem::take)
     }
 
+    /// Takes whole stacks out of randomly chosen inventory slots, as many as
+    /// the given fraction of the occupied slots (rounded up). Equipped items
+    /// are left alone.
+    #[must_use = "Returned items will be lost if not used"]
+    pub fn take_random_fraction(&mut self, fraction: f32, rng: &mut impl Rng) -> Vec<Item> {
+        let occupied = self
+            .slots_with_id()
+            .filter(|(_, slot)| slot.is_some())
+            .map(|(slot_id, _)| slot_id)
+            .collect::<Vec<_>>();
+        let count = (occupied.len() as f32 * fraction.clamp(0.0, 1.0)).ceil() as usize;
+        occupied
+            .choose_multiple(rng, count)
+            .filter_map(|slot_id| self.remove(*slot_id))
+            .collect()
+    }
+
     /// Determine how many of a particular item there is in the inventory.
     pub fn item_count(&self, item_def: &ItemDef) -> u64 {
         self.slots()
@@ -1092,6 +1122,18 @@ impl Inventory {
         }
     }
 
+    /// Decorates the item in `slot` with a heraldic design, or removes its
+    /// design. Returns whether the item can bear one.
+    pub fn set_heraldry(&mut self, slot: Slot, heraldry: Option<Heraldry>) -> bool {
+        match slot {
+            Slot::Inventory(invslot) => self
+                .get_mut(invslot)
+                .map_or(false, |item| item.set_heraldry(heraldry)),
+            Slot::Equip(equip_slot) => self.loadout.set_heraldry_at_slot(equip_slot, heraldry),
+            Slot::Overflow(_) => false,
+        }
+    }
+
     /// When loading a character from the persistence system, pushes any items
     /// to overflow_items that were not able to be loaded into or pushed to the
     /// inventory
@@ -1149,6 +1191,88 @@ impl Inventory {
     pub fn persistence_recipes_iter_with_index(&self) -> impl Iterator<Item = (usize, &Item)> {
         self.recipe_book.persistence_recipes_iter_with_index()
     }
+
+    pub fn bank(&self) -> &Bank { &self.bank }
+
+    /// Moves the item in an inventory slot into the bank. Returns whether
+    /// anything was moved; if the bank is full the item stays where it was.
+    pub fn bank_deposit(&mut self, inv_slot_id: InvSlotId) -> bool {
+        let Some(item) = self.remove(inv_slot_id) else {
+            return false;
+        };
+        match self.bank.push(item) {
+            Ok(()) => true,
+            Err(item) => {
+                // The slot was emptied just above, so this can't fail
+                let _ = self.insert_at(inv_slot_id, item);
+                false
+            },
+        }
+    }
+
+    /// Moves the item in a bank slot into the inventory. Whatever doesn't fit
+    /// into the inventory is put back into the bank slot.
+    pub fn bank_withdraw(&mut self, bank_slot: usize) -> bool {
+        let Some(item) = self.bank.remove(bank_slot) else {
+            return false;
+        };
+        match self.push(item) {
+            Ok(()) => true,
+            Err((item, inserted)) => {
+                // The bank slot was emptied just above, so this can't fail
+                let _ = self.bank.insert_at(bank_slot, item);
+                inserted.is_some()
+            },
+        }
+    }
+
+    /// Buys the next bank capacity upgrade with coins from the inventory.
+    /// Returns whether the upgrade was bought.
+    pub fn bank_upgrade(&mut self, ability_map: &AbilityMap, msm: &MaterialStatManifest) -> bool {
+        let Some(cost) = self.bank.upgrade_cost() else {
+            return false;
+        };
+        if !self.pay_coins(cost, ability_map, msm) {
+            return false;
+        }
+        self.bank.upgrade();
+        true
+    }
+
+    /// Total amount of coins in the inventory
+    pub fn coins(&self) -> u64 {
+        let coins = ItemDefinitionIdOwned::Simple(String::from("common.items.utility.coins"));
+        self.slots()
+            .flatten()
+            .filter(|item| item.item_definition_id() == coins)
+            .map(|item| u64::from(item.amount()))
+            .sum()
+    }
+
+    /// Removes `cost` coins from the inventory. Returns false without taking
+    /// anything if there aren't enough coins.
+    pub fn pay_coins(
+        &mut self,
+        cost: u32,
+        ability_map: &AbilityMap,
+        msm: &MaterialStatManifest,
+    ) -> bool {
+        if self.coins() < u64::from(cost) {
+            return false;
+        }
+        let coins = ItemDefinitionIdOwned::Simple(String::from("common.items.utility.coins"));
+
+        let mut remaining = cost;
+        while let Some(amount) = NonZeroU32::new(remaining)
+            && let Some(slot) = self.get_slot_of_item_by_def_id(&coins)
+        {
+            let taken = self
+                .take_amount(slot, amount, ability_map, msm)
+                .map_or(0, |item| item.amount());
+            remaining = remaining.saturating_sub(taken);
+        }
+        true
+    }
 }
 
 impl Component for Inventory {
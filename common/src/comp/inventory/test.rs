@@ -26,6 +26,7 @@ fn push_full() {
         loadout: LoadoutBuilder::empty().build(),
         overflow_items: vec![],
         recipe_book: RecipeBook::default(),
+        bank: Bank::default(),
     };
     assert_eq!(
         inv.push(TEST_ITEMS[0].duplicate(ability_map, msm))
@@ -48,6 +49,7 @@ fn push_all_full() {
         loadout: LoadoutBuilder::empty().build(),
         overflow_items: vec![],
         recipe_book: RecipeBook::default(),
+        bank: Bank::default(),
     };
     let Error::Full(leftovers) = inv
         .push_all(
@@ -80,6 +82,7 @@ fn push_unique_all_full() {
         loadout: LoadoutBuilder::empty().build(),
         overflow_items: vec![],
         recipe_book: RecipeBook::default(),
+        bank: Bank::default(),
     };
     inv.push_all_unique(
         TEST_ITEMS
@@ -101,6 +104,7 @@ fn push_all_empty() {
         loadout: LoadoutBuilder::empty().build(),
         overflow_items: vec![],
         recipe_book: RecipeBook::default(),
+        bank: Bank::default(),
     };
     inv.push_all(
         TEST_ITEMS
@@ -122,6 +126,7 @@ fn push_all_unique_empty() {
         loadout: LoadoutBuilder::empty().build(),
         overflow_items: vec![],
         recipe_book: RecipeBook::default(),
+        bank: Bank::default(),
     };
     inv.push_all_unique(
         TEST_ITEMS
@@ -528,6 +533,35 @@ fn backpack_crash() {
     );
 }
 
+#[test]
+fn bank_deposit_withdraw_roundtrip() {
+    let mut inv = Inventory::with_empty();
+    let boots = Item::new_from_asset_expect("common.items.testing.test_boots");
+    inv.push(boots).unwrap();
+    let slot = InvSlotId::new(0, 0);
+
+    assert!(inv.bank_deposit(slot));
+    assert!(inv.get(slot).is_none());
+    assert_eq!(1, inv.bank().slots().flatten().count());
+
+    assert!(inv.bank_withdraw(0));
+    assert!(inv.get(slot).is_some());
+    assert!(inv.bank().get(0).is_none());
+}
+
+#[test]
+fn bank_withdraw_into_full_inventory_keeps_item() {
+    let mut inv = Inventory::with_empty();
+    fill_inv_slots(&mut inv, 17);
+    let cape = Item::new_from_asset_expect("common.items.armor.misc.back.admin");
+    inv.push(cape).unwrap();
+    assert!(inv.bank_deposit(InvSlotId::new(0, 17)));
+    fill_inv_slots(&mut inv, 1);
+
+    assert!(!inv.bank_withdraw(0));
+    assert!(inv.bank().get(0).is_some());
+}
+
 fn fill_inv_slots(inv: &mut Inventory, items: u16) {
     let msm = &MaterialStatManifest::load().read();
     let ability_map = &AbilityMap::load().read();
@@ -536,3 +570,32 @@ fn fill_inv_slots(inv: &mut Inventory, items: u16) {
         inv.push(boots.duplicate(ability_map, msm)).unwrap();
     }
 }
+
+/// Taking a fraction of the items should only empty that many slots, rounded
+/// up, and leave the rest of the inventory untouched.
+#[test]
+fn take_random_fraction_rounds_up() {
+    let msm = &MaterialStatManifest::load().read();
+    let ability_map = &AbilityMap::load().read();
+    let mut inv = Inventory {
+        next_sort_order: InventorySortOrder::Name,
+        slots: vec![
+            Some(TEST_ITEMS[0].duplicate(ability_map, msm)),
+            Some(TEST_ITEMS[0].duplicate(ability_map, msm)),
+            Some(TEST_ITEMS[0].duplicate(ability_map, msm)),
+            None,
+        ],
+        loadout: LoadoutBuilder::empty().build(),
+        overflow_items: vec![],
+        recipe_book: RecipeBook::default(),
+        bank: Bank::default(),
+    };
+
+    let taken = inv.take_random_fraction(0.25, &mut rand::thread_rng());
+    assert_eq!(taken.len(), 1);
+    assert_eq!(inv.populated_slots(), 2);
+
+    let taken = inv.take_random_fraction(1.0, &mut rand::thread_rng());
+    assert_eq!(taken.len(), 2);
+    assert_eq!(inv.populated_slots(), 0);
+}
@@ -1,8 +1,15 @@
-use crate::{resources::Time, uid::Uid};
+use crate::{resources::Time, trade::SiteId, uid::Uid};
+use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
-use specs::Component;
+use specs::{Component, DenseVecStorage, DerefFlaggedStorage};
 use vek::*;
 
+/// Distance from a waypoint shrine within which it can be unlocked and used to
+/// fast travel
+pub const SHRINE_RANGE: f32 = 5.0;
+/// Time in seconds after taking damage during which fast travel isn't allowed
+pub const SHRINE_COMBAT_LOCKOUT: f64 = 30.0;
+
 #[derive(Copy, Clone, Debug)]
 pub struct Waypoint {
     pos: Vec3<f32>,
@@ -45,6 +52,38 @@ impl Default for WaypointArea {
     fn default() -> Self { Self(5.0) }
 }
 
+/// Waypoint shrines a character has unlocked, keyed by the site they are in
+/// and storing where travelling to them puts the character
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UnlockedShrines(HashMap<SiteId, Vec3<f32>>);
+
+impl UnlockedShrines {
+    pub fn new(shrines: HashMap<SiteId, Vec3<f32>>) -> Self { Self(shrines) }
+
+    /// Returns false if the shrine was already unlocked
+    pub fn unlock(&mut self, site: SiteId, pos: Vec3<f32>) -> bool {
+        self.0.insert(site, pos).is_none()
+    }
+
+    pub fn get(&self, site: SiteId) -> Option<Vec3<f32>> { self.0.get(&site).copied() }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SiteId, Vec3<f32>)> + '_ {
+        self.0.iter().map(|(site, pos)| (*site, *pos))
+    }
+}
+
+impl Component for UnlockedShrines {
+    type Storage = DerefFlaggedStorage<Self, DenseVecStorage<Self>>;
+}
+
+/// Coins it costs to fast travel between two waypoint shrines, growing with the
+/// distance travelled
+pub fn shrine_travel_cost(from: Vec3<f32>, to: Vec3<f32>) -> u32 {
+    const BASE_COST: u32 = 10;
+    const BLOCKS_PER_COIN: f32 = 250.0;
+    BASE_COST + (from.xy().distance(to.xy()) / BLOCKS_PER_COIN) as u32
+}
+
 /// Marker on the map, used for sharing waypoint with group and
 /// persisting it server side.
 #[derive(Copy, Clone, Debug)]
@@ -60,6 +99,21 @@ pub enum MapMarkerChange {
     Remove,
 }
 
+/// Something that a scouting bird companion spotted on its flight, shown on
+/// its owner's map for a while.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct ScoutPing {
+    pub wpos: Vec2<f32>,
+    pub kind: ScoutPingKind,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScoutPingKind {
+    Creature,
+    Hostile,
+    Site,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MapMarkerUpdate {
     Owned(MapMarkerChange),
@@ -1,8 +1,8 @@
 use crate::{
     combat::{
         Attack, AttackDamage, AttackEffect, CombatBuff, CombatBuffStrength, CombatEffect,
-        CombatRequirement, Damage, DamageKind, DamageSource, FlankMults, GroupTarget, Knockback,
-        KnockbackDir,
+        CombatRequirement, Damage, DamageElement, DamageKind, DamageSource, FlankMults,
+        GroupTarget, Knockback, KnockbackDir,
     },
     comp::{
         buff::BuffKind,
@@ -76,6 +76,9 @@ pub struct MeleeConstructor {
     pub angle: f32,
     pub multi_target: Option<MultiTarget>,
     pub damage_effect: Option<CombatEffect>,
+    /// Element of the damage, inferred from `damage_effect` if not specified
+    #[serde(default)]
+    pub element: Option<DamageElement>,
     pub attack_effect: Option<(CombatEffect, CombatRequirement)>,
     #[serde(default = "default_simultaneous_hits")]
     pub simultaneous_hits: u32,
@@ -102,6 +105,9 @@ impl MeleeConstructor {
             )
         }
         let instance = rand::random();
+        let element = self
+            .element
+            .unwrap_or_else(|| DamageElement::from_effect(self.damage_effect));
         let attack = match self.kind {
             Slash {
                 damage,
@@ -122,6 +128,7 @@ impl MeleeConstructor {
                     Damage {
                         source: DamageSource::Melee,
                         kind: DamageKind::Slashing,
+                        element,
                         value: damage,
                     },
                     Some(GroupTarget::OutOfGroup),
@@ -171,6 +178,7 @@ impl MeleeConstructor {
                     Damage {
                         source: DamageSource::Melee,
                         kind: DamageKind::Piercing,
+                        element,
                         value: damage,
                     },
                     Some(GroupTarget::OutOfGroup),
@@ -213,6 +221,7 @@ impl MeleeConstructor {
                     Damage {
                         source: DamageSource::Melee,
                         kind: DamageKind::Crushing,
+                        element,
                         value: damage,
                     },
                     Some(GroupTarget::OutOfGroup),
@@ -258,6 +267,7 @@ impl MeleeConstructor {
                     Damage {
                         source: DamageSource::Melee,
                         kind: DamageKind::Piercing,
+                        element,
                         value: damage,
                     },
                     Some(GroupTarget::OutOfGroup),
@@ -301,6 +311,7 @@ impl MeleeConstructor {
                     Damage {
                         source: DamageSource::Melee,
                         kind: DamageKind::Energy,
+                        element,
                         value: damage,
                     },
                     None,
@@ -336,6 +347,7 @@ impl MeleeConstructor {
                     Damage {
                         source: DamageSource::Melee,
                         kind: DamageKind::Energy,
+                        element,
                         value: damage,
                     },
                     Some(GroupTarget::OutOfGroup),
@@ -1,6 +1,7 @@
 use super::item::Reagent;
 use crate::{
     resources::{Secs, Time},
+    trade::SiteId,
     uid::Uid,
 };
 use serde::{Deserialize, Serialize};
@@ -29,8 +30,30 @@ pub enum Object {
     SurpriseEgg {
         owner: Option<Uid>,
     },
+    /// Waypoint shrine that characters can unlock and fast travel between
+    Shrine {
+        site: SiteId,
+    },
+    /// Left behind where a player died, holding some of the items they carried
+    /// until it decays
+    Gravestone {
+        owner: Uid,
+        decays_at: Time,
+    },
+    /// Gate of a race track, marking where its checkpoint number `index` is
+    RaceCheckpoint {
+        track: u32,
+        index: u32,
+    },
 }
 
+/// Fraction of the occupied inventory slots of a dying player that are left
+/// behind in their gravestone
+pub const GRAVESTONE_ITEM_FRACTION: f32 = 0.25;
+/// How long a gravestone can be recovered by its owner before it decays and
+/// its items become free for anyone to loot
+pub const GRAVESTONE_LIFETIME: Secs = Secs(1800.0);
+
 impl Component for Object {
     type Storage = DerefFlaggedStorage<Self, specs::VecStorage<Self>>;
 }
@@ -11,10 +11,14 @@ pub mod chat;
 pub mod combo;
 pub mod compass;
 pub mod controller;
+pub mod corpse;
 pub mod dialogue;
+pub mod disguise;
+pub mod encounter;
 mod energy;
 pub mod fluid_dynamics;
 pub mod group;
+mod group_scaling;
 mod health;
 mod inputs;
 pub mod inventory;
@@ -35,6 +39,7 @@ pub mod shockwave;
 pub mod skillset;
 mod stats;
 pub mod teleport;
+mod title;
 pub mod visual;
 
 // Reexports
@@ -54,7 +59,8 @@ pub use self::{
     body::{
         arthropod, biped_large, biped_small, bird_large, bird_medium, crustacean, dragon,
         fish_medium, fish_small, golem, humanoid, item_drop, object, quadruped_low,
-        quadruped_medium, quadruped_small, ship, theropod, AllBodies, Body, BodyData, Gender,
+        quadruped_medium, quadruped_small, serpent, ship, theropod, AllBodies, Body, BodyData,
+        Gender,
     },
     buff::{
         Buff, BuffCategory, BuffChange, BuffData, BuffEffect, BuffKey, BuffKind, BuffSource, Buffs,
@@ -69,9 +75,13 @@ pub use self::{
         Climb, ControlAction, ControlEvent, Controller, ControllerInputs, GroupManip, InputAttr,
         InputKind, InventoryAction, InventoryEvent, InventoryManip, UtteranceKind,
     },
+    corpse::Corpse,
+    disguise::Exposed,
+    encounter::Encounter,
     energy::Energy,
     fluid_dynamics::Fluid,
     group::Group,
+    group_scaling::GroupScaling,
     inputs::CanBuild,
     inventory::{
         item::{
@@ -84,10 +94,13 @@ pub use self::{
         slot, CollectFailedReason, Inventory, InventoryUpdate, InventoryUpdateEvent,
     },
     last::Last,
-    location::{MapMarker, MapMarkerChange, MapMarkerUpdate, Waypoint, WaypointArea},
+    location::{
+        shrine_travel_cost, MapMarker, MapMarkerChange, MapMarkerUpdate, ScoutPing, ScoutPingKind,
+        UnlockedShrines, Waypoint, WaypointArea, SHRINE_COMBAT_LOCKOUT, SHRINE_RANGE,
+    },
     loot_owner::LootOwner,
     melee::{Melee, MeleeConstructor, MeleeConstructorKind},
-    misc::Object,
+    misc::{Object, GRAVESTONE_ITEM_FRACTION, GRAVESTONE_LIFETIME},
     ori::Ori,
     pet::Pet,
     phys::{
@@ -105,6 +118,7 @@ pub use self::{
     },
     stats::{Stats, StatsModifier},
     teleport::Teleporting,
+    title::{Title, TitleSource, Titles, WAYFARER_SHRINES},
     visual::{LightAnimation, LightEmitter},
 };
 pub use common_i18n::{Content, LocalizationArg};
@@ -60,12 +60,44 @@ pub fn is_tameable(body: &Body) -> bool {
     }
 }
 
+/// Determines whether a pet can be sent out by its owner to scout an area of
+/// the map. Only birds small enough to ride on their owner's shoulder can.
+pub fn can_scout(body: &Body) -> bool { matches!(body, Body::BirdMedium(_)) }
+
+/// Where on a humanoid a small companion perches when it rides them
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Perch {
+    Shoulder,
+    Head,
+    Back,
+}
+
+impl Perch {
+    /// Where the given body perches when riding a humanoid, if it's small
+    /// enough to perch on one at all
+    pub fn of(rider: &Body) -> Option<Self> {
+        match rider {
+            Body::BirdMedium(_) => Some(Self::Shoulder),
+            Body::QuadrupedSmall(body) => match body.species {
+                quadruped_small::Species::Squirrel => Some(Self::Shoulder),
+                quadruped_small::Species::Rat | quadruped_small::Species::Frog => Some(Self::Head),
+                quadruped_small::Species::Cat
+                | quadruped_small::Species::Batfox
+                | quadruped_small::Species::Rabbit
+                | quadruped_small::Species::Hare => Some(Self::Back),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
 pub fn is_mountable(mount: &Body, rider: Option<&Body>) -> bool {
     let is_light_enough =
         |rider: Option<&Body>| -> bool { rider.map_or(false, |b| b.mass() <= Mass(500.0)) };
 
     match mount {
-        Body::Humanoid(_) => matches!(rider, Some(Body::BirdMedium(_))),
+        Body::Humanoid(_) => rider.and_then(Perch::of).is_some(),
         Body::BipedLarge(_) => is_light_enough(rider),
         Body::BirdLarge(_) => is_light_enough(rider),
         Body::QuadrupedMedium(body) => match body.species {
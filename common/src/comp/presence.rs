@@ -13,6 +13,9 @@ pub struct Presence {
     /// updated!
     pub kind: PresenceKind,
     pub lossy_terrain_compression: bool,
+    /// Sequence number of the latest controller inputs received from the
+    /// client, which server-authoritative physics updates are based on
+    pub last_input_seq: u64,
 }
 
 impl Presence {
@@ -23,6 +26,7 @@ impl Presence {
             entity_view_distance: ViewDistance::new(view_distances.entity, now),
             kind,
             lossy_terrain_compression: false,
+            last_input_seq: 0,
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::{
     combat::{
         Attack, AttackDamage, AttackEffect, CombatBuff, CombatEffect, CombatRequirement, Damage,
-        DamageKind, DamageSource, GroupTarget, Knockback, KnockbackDir,
+        DamageElement, DamageKind, DamageSource, GroupTarget, Knockback, KnockbackDir,
     },
     comp::item::{tool, Reagent},
     explosion::{ColorPreset, Explosion, RadiusEffect},
@@ -64,6 +64,9 @@ pub struct ProjectileAttack {
     pub knockback: Option<f32>,
     pub energy: f32,
     pub buff: Option<CombatBuff>,
+    /// Element of the damage, inferred from `buff` if not specified
+    #[serde(default)]
+    pub element: Option<DamageElement>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -123,6 +126,9 @@ impl ProjectileConstructor {
                 .with_requirement(CombatRequirement::AnyDamage);
 
             let buff = a.buff.map(CombatEffect::Buff);
+            let element = a
+                .element
+                .unwrap_or_else(|| DamageElement::from_effect(buff));
 
             let (damage_source, damage_kind) = match self.kind {
                 ProjectileConstructorKind::Pointed | ProjectileConstructorKind::Hazard { .. } => {
@@ -145,6 +151,7 @@ impl ProjectileConstructor {
                 Damage {
                     source: damage_source,
                     kind: damage_kind,
+                    element,
                     value: a.damage,
                 },
                 Some(GroupTarget::OutOfGroup),
@@ -1,5 +1,5 @@
 use crate::{
-    combat::{Attack, AttackSource},
+    combat::{Attack, AttackSource, DamageElement},
     uid::Uid,
 };
 use serde::{Deserialize, Serialize};
@@ -67,6 +67,19 @@ pub enum FrontendSpecifier {
     Lightning,
 }
 
+impl FrontendSpecifier {
+    /// Element of the damage dealt by shockwaves with this specifier
+    pub fn element(&self) -> DamageElement {
+        match self {
+            Self::Fire => DamageElement::Fire,
+            Self::Ice | Self::IceSpikes => DamageElement::Frost,
+            Self::Poison => DamageElement::Poison,
+            Self::Lightning => DamageElement::Shock,
+            Self::Ground | Self::Water | Self::Steam | Self::Ink => DamageElement::Physical,
+        }
+    }
+}
+
 impl ShockwaveDodgeable {
     pub fn to_attack_source(&self) -> AttackSource {
         match self {
@@ -62,6 +62,17 @@ impl Asset for SkillCostMap {
     const EXTENSION: &'static str = "ron";
 }
 
+/// Skills that trainer NPCs teach, together with their price in coins per
+/// skill point
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrainerSkills(Vec<(Skill, u32)>);
+
+impl Asset for TrainerSkills {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
 lazy_static! {
     // Determines the skills that comprise each skill group.
     //
@@ -105,6 +116,12 @@ lazy_static! {
             "common.skill_trees.skill_prerequisites",
         ).0
     };
+    // Loads the skills taught by trainers and their prices
+    pub static ref TRAINER_SKILLS: Vec<(Skill, u32)> = {
+        TrainerSkills::load_expect_cloned(
+            "common.skill_trees.trainer_skills",
+        ).0
+    };
     pub static ref SKILL_GROUP_HASHES: HashMap<SkillGroupKind, Vec<u8>> = {
         let map = SkillTreeMap::load_expect_cloned(
             "common.skill_trees.skills_skill-groups_manifest",
@@ -553,6 +570,30 @@ impl SkillSet {
         Self::unlock_skill_cow(self, skill, |x| x)
     }
 
+    /// Grants the skill points needed for the next level of a skill and
+    /// unlocks it, so that the skill can be learned without spending earned
+    /// skill points. The skill set is left unchanged if the skill can't be
+    /// unlocked.
+    pub fn learn_skill(&mut self, skill: Skill) -> Result<(), SkillUnlockError> {
+        let skill_group_kind = skill
+            .skill_group_kind()
+            .ok_or(SkillUnlockError::NoParentSkillTree)?;
+        let mut learned = self.clone();
+        learned.add_skill_points(skill_group_kind, learned.skill_cost(skill));
+        learned.unlock_skill(skill)?;
+        *self = learned;
+        Ok(())
+    }
+
+    /// Price in coins a trainer asks for the next level of a skill, if
+    /// trainers teach it at all
+    pub fn trainer_price(&self, skill: Skill) -> Option<u32> {
+        TRAINER_SKILLS
+            .iter()
+            .find(|(s, _)| *s == skill)
+            .map(|(_, coins_per_sp)| coins_per_sp * u32::from(self.skill_cost(skill)))
+    }
+
     /// Checks if the player has available SP to spend
     pub fn has_available_sp(&self) -> bool {
         self.skill_groups.iter().any(|(kind, sg)| {
@@ -30,3 +30,30 @@ fn check_cyclic_skill_deps() {
 
     assert!(!is_cyclic_directed(&graph));
 }
+
+#[test]
+fn learn_skill_keeps_available_sp() {
+    use crate::comp::{item::tool::ToolKind, skills::MiningSkill};
+
+    let mut skill_set = SkillSet::default();
+    let pick = SkillGroupKind::Weapon(ToolKind::Pick);
+    assert!(
+        skill_set
+            .learn_skill(Skill::Pick(MiningSkill::Speed))
+            .is_ok()
+    );
+    assert_eq!(
+        skill_set.skill_level(Skill::Pick(MiningSkill::Speed)).ok(),
+        Some(1)
+    );
+    assert_eq!(skill_set.available_sp(pick), 0);
+
+    // Skills of locked skill groups can't be learned, and nothing is granted
+    let before = skill_set.clone();
+    assert!(
+        skill_set
+            .learn_skill(Skill::Bow(crate::comp::skills::BowSkill::ProjSpeed))
+            .is_err()
+    );
+    assert_eq!(skill_set, before);
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use specs::{Component, DerefFlaggedStorage};
 use std::{error::Error, fmt};
 
-use crate::combat::{AttackEffect, DamagedEffect, DeathEffect};
+use crate::combat::{AttackEffect, DamagedEffect, DeathEffect, ElementResistances};
 
 use super::Body;
 
@@ -102,6 +102,10 @@ pub struct Stats {
     pub effects_on_death: Vec<DeathEffect>,
     pub disable_auxiliary_abilities: bool,
     pub crowd_control_resistance: f32,
+    pub dispel_resistance: f32,
+    /// Starts out as the innate resistances of the body, buffs are then
+    /// stacked on top of them
+    pub element_resistances: ElementResistances,
 }
 
 impl Stats {
@@ -132,6 +136,8 @@ impl Stats {
             effects_on_death: Vec::new(),
             disable_auxiliary_abilities: false,
             crowd_control_resistance: 0.0,
+            dispel_resistance: 0.0,
+            element_resistances: body.element_resistances(),
         }
     }
 
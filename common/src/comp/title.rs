@@ -0,0 +1,96 @@
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage, DerefFlaggedStorage};
+use strum::{EnumIter, IntoEnumIterator};
+
+/// Number of waypoint shrines a character has to unlock to earn
+/// [`Title::Wayfarer`]
+pub const WAYFARER_SHRINES: usize = 10;
+
+/// A title a character can show under their name
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+pub enum Title {
+    /// Unlocked [`WAYFARER_SHRINES`] waypoint shrines
+    Wayfarer,
+    /// Dug up a cache shown on a treasure map
+    TreasureHunter,
+    /// Completed a bounty from a notice board
+    BountyHunter,
+    /// Granted to the winners of server events
+    Champion,
+    /// Granted to those who took part in server events
+    Reveller,
+}
+
+/// How a title is earned
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TitleSource {
+    Achievement,
+    Quest,
+    Event,
+}
+
+impl Title {
+    pub fn source(self) -> TitleSource {
+        match self {
+            Title::Wayfarer | Title::TreasureHunter => TitleSource::Achievement,
+            Title::BountyHunter => TitleSource::Quest,
+            Title::Champion | Title::Reveller => TitleSource::Event,
+        }
+    }
+
+    /// Stable name of the title, used for persistence, commands and
+    /// localization
+    pub fn to_key(self) -> &'static str {
+        match self {
+            Title::Wayfarer => "wayfarer",
+            Title::TreasureHunter => "treasure_hunter",
+            Title::BountyHunter => "bounty_hunter",
+            Title::Champion => "champion",
+            Title::Reveller => "reveller",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> { Self::iter().find(|title| title.to_key() == key) }
+
+    /// Localization key of the name of the title
+    pub fn i18n_key(self) -> String { format!("hud-title-{}", self.to_key()) }
+}
+
+/// Titles a character earned and the one they chose to show
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Titles {
+    unlocked: HashSet<Title>,
+    selected: Option<Title>,
+}
+
+impl Titles {
+    /// The selected title is dropped if it isn't among the unlocked ones
+    pub fn new(unlocked: HashSet<Title>, selected: Option<Title>) -> Self {
+        let selected = selected.filter(|title| unlocked.contains(title));
+        Self { unlocked, selected }
+    }
+
+    /// Returns false if the title was already unlocked
+    pub fn unlock(&mut self, title: Title) -> bool { self.unlocked.insert(title) }
+
+    /// Returns false if the title isn't unlocked
+    pub fn select(&mut self, title: Option<Title>) -> bool {
+        if title.map_or(true, |title| self.is_unlocked(title)) {
+            self.selected = title;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn selected(&self) -> Option<Title> { self.selected }
+
+    pub fn is_unlocked(&self, title: Title) -> bool { self.unlocked.contains(&title) }
+
+    pub fn unlocked(&self) -> impl Iterator<Item = Title> + '_ { self.unlocked.iter().copied() }
+}
+
+impl Component for Titles {
+    type Storage = DerefFlaggedStorage<Self, DenseVecStorage<Self>>;
+}
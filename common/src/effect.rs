@@ -8,6 +8,7 @@ pub enum Effect {
     Poise(f32),
     Damage(combat::Damage),
     Buff(BuffEffect),
+    Dispel(comp::buff::Dispel),
 }
 
 /// A buff that may be applied to an entity
@@ -26,6 +27,7 @@ impl Effect {
             Effect::Poise(p) => format!("{:+} poise", p),
             Effect::Damage(d) => format!("{:+}", d.value),
             Effect::Buff(e) => format!("{:?} buff", e),
+            Effect::Dispel(d) => format!("dispels {} {:?}", d.count, d.target),
         }
     }
 
@@ -35,6 +37,12 @@ impl Effect {
             Effect::Poise(p) => *p < 0.0,
             Effect::Damage(_) => true,
             Effect::Buff(e) => !e.kind.is_buff(),
+            Effect::Dispel(d) => match d.target {
+                comp::buff::DispelTarget::Buffs => true,
+                comp::buff::DispelTarget::Debuffs => false,
+                comp::buff::DispelTarget::Kind(kind) => kind.is_buff(),
+                comp::buff::DispelTarget::Category(_) => false,
+            },
         }
     }
 
@@ -52,6 +60,8 @@ impl Effect {
             Effect::Buff(effect) => {
                 effect.data.strength *= modifier;
             },
+            // Dispels remove whole buffs, there is nothing to scale
+            Effect::Dispel(_) => {},
         }
     }
 }
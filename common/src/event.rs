@@ -1,4 +1,5 @@
 use crate::{
+    bounty::BountyTarget,
     character::CharacterId,
     combat::AttackSource,
     comp::{
@@ -9,11 +10,12 @@ use crate::{
         DisconnectReason, LootOwner, Ori, Pos, UnresolvedChatMsg, Vel,
     },
     generation::{EntityInfo, SpecialEntity},
+    guild::GuildManip,
     lottery::LootSpec,
     mounting::VolumePos,
     outcome::Outcome,
     resources::Secs,
-    rtsim::RtSimEntity,
+    rtsim::{NpcId, RtSimEntity},
     terrain::SpriteKind,
     trade::{TradeAction, TradeId},
     uid::Uid,
@@ -64,6 +66,7 @@ pub struct NpcBuilder {
     pub pets: Vec<(NpcBuilder, Vec3<f32>)>,
     pub rtsim_entity: Option<RtSimEntity>,
     pub projectile: Option<comp::Projectile>,
+    pub encounter: Option<comp::Encounter>,
 }
 
 impl NpcBuilder {
@@ -82,6 +85,7 @@ impl NpcBuilder {
             loot: LootSpec::Nothing,
             rtsim_entity: None,
             projectile: None,
+            encounter: None,
             pets: Vec::new(),
         }
     }
@@ -116,6 +120,11 @@ impl NpcBuilder {
         self
     }
 
+    pub fn with_encounter(mut self, encounter: impl Into<Option<comp::Encounter>>) -> Self {
+        self.encounter = encounter.into();
+        self
+    }
+
     pub fn with_scale(mut self, scale: comp::Scale) -> Self {
         self.scale = scale;
         self
@@ -147,6 +156,12 @@ pub struct ClientConnectedEvent {
 }
 pub struct ClientDisconnectEvent(pub EcsEntity, pub DisconnectReason);
 pub struct ClientDisconnectWithoutPersistenceEvent(pub EcsEntity);
+/// Moves the client of `entity`, which just registered, to the entity of a
+/// session that lost its connection.
+pub struct ResumeSessionEvent {
+    pub entity: EcsEntity,
+    pub target: EcsEntity,
+}
 
 pub struct ChatEvent(pub UnresolvedChatMsg);
 pub struct CommandEvent(pub EcsEntity, pub String, pub Vec<String>);
@@ -179,6 +194,26 @@ pub struct CreateItemDropEvent {
     pub item: comp::PickupItem,
     pub loot_owner: Option<LootOwner>,
 }
+
+/// Leaves the remains of a creature behind where it died, see
+/// [`comp::Corpse`]
+pub struct CreateCorpseEvent {
+    pub pos: Pos,
+    pub body: comp::Body,
+}
+
+pub struct CreateGravestoneEvent {
+    pub pos: Pos,
+    pub owner: Uid,
+    pub items: Vec<comp::Item>,
+}
+
+pub struct CreateRaceCheckpointEvent {
+    pub pos: Pos,
+    pub track: u32,
+    pub index: u32,
+}
+
 pub struct CreateObjectEvent {
     pub pos: Pos,
     pub vel: Vel,
@@ -189,6 +224,11 @@ pub struct CreateObjectEvent {
     pub stats: Option<comp::Stats>,
 }
 
+/// A lightning bolt hitting the ground around `pos`
+pub struct LightningStrikeEvent {
+    pub pos: Vec3<f32>,
+}
+
 pub struct ExplosionEvent {
     pub pos: Vec3<f32>,
     pub explosion: Explosion,
@@ -222,6 +262,8 @@ pub struct InventoryManipEvent(pub EcsEntity, pub comp::InventoryManip);
 
 pub struct GroupManipEvent(pub EcsEntity, pub comp::GroupManip);
 
+pub struct GuildManipEvent(pub EcsEntity, pub GuildManip);
+
 pub struct RespawnEvent(pub EcsEntity);
 
 pub struct ShootEvent {
@@ -270,6 +312,13 @@ pub struct UnmountEvent(pub EcsEntity);
 
 pub struct SetPetStayEvent(pub EcsEntity, pub EcsEntity, pub bool);
 
+pub struct SendScoutEvent(pub EcsEntity, pub EcsEntity);
+
+pub struct ScoutPingsEvent {
+    pub owner: EcsEntity,
+    pub pings: Vec<comp::ScoutPing>,
+}
+
 pub struct PossessEvent(pub Uid, pub Uid);
 
 pub struct TransformEvent {
@@ -299,6 +348,8 @@ pub struct UpdateCharacterDataEvent {
         Vec<(comp::Pet, comp::Body, comp::Stats)>,
         comp::ActiveAbilities,
         Option<comp::MapMarker>,
+        comp::UnlockedShrines,
+        comp::Titles,
     ),
     pub metadata: UpdateCharacterMetadata,
 }
@@ -340,6 +391,11 @@ pub struct RequestSiteInfoEvent {
     pub id: SiteId,
 }
 
+/// Sends the reputation the character has with each faction to its client
+pub struct RequestReputationEvent {
+    pub entity: EcsEntity,
+}
+
 // Attempt to mine a block, turning it into an item
 pub struct MineBlockEvent {
     pub entity: EcsEntity,
@@ -347,6 +403,12 @@ pub struct MineBlockEvent {
     pub tool: Option<comp::tool::ToolKind>,
 }
 
+/// A character dug into the ground with a shovel, at `pos`
+pub struct DigGroundEvent {
+    pub entity: EcsEntity,
+    pub pos: Vec3<f32>,
+}
+
 pub struct TeleportToEvent {
     pub entity: EcsEntity,
     pub target: Uid,
@@ -385,6 +447,11 @@ pub struct ChangeAbilityEvent {
     pub new_ability: comp::ability::AuxiliaryAbility,
 }
 
+pub struct SwitchAbilityLoadoutEvent {
+    pub entity: EcsEntity,
+    pub loadout: usize,
+}
+
 pub struct UpdateMapMarkerEvent {
     pub entity: EcsEntity,
     pub update: comp::MapMarkerChange,
@@ -402,6 +469,19 @@ pub struct DeleteCharacterEvent {
     pub character_id: CharacterId,
 }
 
+/// Emitted once a revive on a downed teammate has been channeled
+pub struct ReviveEvent {
+    pub entity: EcsEntity,
+    pub target: Uid,
+}
+
+/// Sent when a dead player wants to watch the game from the position of one
+/// of their group members while awaiting respawn
+pub struct GroupSpectateEvent {
+    pub entity: EcsEntity,
+    pub pos: Vec3<f32>,
+}
+
 pub struct ChangeStanceEvent {
     pub entity: EcsEntity,
     pub stance: comp::Stance,
@@ -425,6 +505,77 @@ pub struct StartTeleportingEvent {
     pub entity: EcsEntity,
     pub portal: EcsEntity,
 }
+
+/// Unlocks a waypoint shrine for the character interacting with it
+pub struct ActivateShrineEvent {
+    pub entity: EcsEntity,
+    pub shrine: EcsEntity,
+}
+
+/// Fast travel from the waypoint shrine the character is at to another shrine
+/// they unlocked
+pub struct ShrineTravelEvent {
+    pub entity: EcsEntity,
+    pub site: SiteId,
+}
+
+/// Shows a character the way to a site, as an NPC they asked described it
+pub struct GiveDirectionsEvent {
+    pub entity: EcsEntity,
+    pub directions: comp::dialogue::Directions,
+}
+
+/// Moves the items of a gravestone back into the inventory of its owner
+pub struct RecoverGravestoneEvent {
+    pub entity: EcsEntity,
+    pub gravestone: EcsEntity,
+}
+
+/// Changes the title shown under the name of a character
+pub struct SelectTitleEvent {
+    pub entity: EcsEntity,
+    pub title: Option<comp::Title>,
+}
+
+/// Awards a title to a character, telling them about it unless they already
+/// had it
+pub struct UnlockTitleEvent {
+    pub entity: EcsEntity,
+    pub title: comp::Title,
+}
+
+/// Shows the bounties posted on a notice board to the character reading it
+pub struct ReadNoticeBoardEvent {
+    pub entity: EcsEntity,
+    pub pos: Vec3<i32>,
+}
+
+pub struct AcceptBountyEvent {
+    pub entity: EcsEntity,
+    pub board: Vec3<i32>,
+    pub target: BountyTarget,
+}
+
+/// Something died that may have been the target of a bounty: either an rtsim
+/// NPC, or a hostile entity killed by `killer`
+pub struct BountyKillEvent {
+    pub killer: Option<EcsEntity>,
+    pub victim: Option<NpcId>,
+    pub pos: Vec3<f32>,
+    pub hostile: bool,
+}
+
+/// The disguise of `entity` was seen through, so factions treat it according
+/// to its true standing for a while
+pub struct ExposeDisguiseEvent {
+    pub entity: EcsEntity,
+}
+
+/// Raises `corpse` as a temporary minion of `necromancer`
+pub struct ReanimateEvent {
+    pub necromancer: EcsEntity,
+    pub corpse: EcsEntity,
+}
 pub struct ToggleSpriteLightEvent {
     pub entity: EcsEntity,
     pub pos: Vec3<i32>,
@@ -506,6 +657,7 @@ pub fn register_event_busses(ecs: &mut World) {
     ecs.insert(EventBus::<ClientConnectedEvent>::default());
     ecs.insert(EventBus::<ClientDisconnectEvent>::default());
     ecs.insert(EventBus::<ClientDisconnectWithoutPersistenceEvent>::default());
+    ecs.insert(EventBus::<ResumeSessionEvent>::default());
     ecs.insert(EventBus::<ChatEvent>::default());
     ecs.insert(EventBus::<CommandEvent>::default());
     ecs.insert(EventBus::<CreateSpecialEntityEvent>::default());
@@ -513,7 +665,11 @@ pub fn register_event_busses(ecs: &mut World) {
     ecs.insert(EventBus::<CreateShipEvent>::default());
     ecs.insert(EventBus::<CreateItemDropEvent>::default());
     ecs.insert(EventBus::<CreateObjectEvent>::default());
+    ecs.insert(EventBus::<CreateGravestoneEvent>::default());
+    ecs.insert(EventBus::<CreateCorpseEvent>::default());
+    ecs.insert(EventBus::<CreateRaceCheckpointEvent>::default());
     ecs.insert(EventBus::<ExplosionEvent>::default());
+    ecs.insert(EventBus::<LightningStrikeEvent>::default());
     ecs.insert(EventBus::<BonkEvent>::default());
     ecs.insert(EventBus::<HealthChangeEvent>::default());
     ecs.insert(EventBus::<PoiseChangeEvent>::default());
@@ -521,6 +677,7 @@ pub fn register_event_busses(ecs: &mut World) {
     ecs.insert(EventBus::<DestroyEvent>::default());
     ecs.insert(EventBus::<InventoryManipEvent>::default());
     ecs.insert(EventBus::<GroupManipEvent>::default());
+    ecs.insert(EventBus::<GuildManipEvent>::default());
     ecs.insert(EventBus::<RespawnEvent>::default());
     ecs.insert(EventBus::<ShootEvent>::default());
     ecs.insert(EventBus::<ShockwaveEvent>::default());
@@ -535,6 +692,8 @@ pub fn register_event_busses(ecs: &mut World) {
     ecs.insert(EventBus::<MountVolumeEvent>::default());
     ecs.insert(EventBus::<UnmountEvent>::default());
     ecs.insert(EventBus::<SetPetStayEvent>::default());
+    ecs.insert(EventBus::<SendScoutEvent>::default());
+    ecs.insert(EventBus::<ScoutPingsEvent>::default());
     ecs.insert(EventBus::<PossessEvent>::default());
     ecs.insert(EventBus::<InitializeCharacterEvent>::default());
     ecs.insert(EventBus::<InitializeSpectatorEvent>::default());
@@ -546,7 +705,9 @@ pub fn register_event_busses(ecs: &mut World) {
     ecs.insert(EventBus::<ComboChangeEvent>::default());
     ecs.insert(EventBus::<ParryHookEvent>::default());
     ecs.insert(EventBus::<RequestSiteInfoEvent>::default());
+    ecs.insert(EventBus::<RequestReputationEvent>::default());
     ecs.insert(EventBus::<MineBlockEvent>::default());
+    ecs.insert(EventBus::<DigGroundEvent>::default());
     ecs.insert(EventBus::<TeleportToEvent>::default());
     ecs.insert(EventBus::<CreateSafezoneEvent>::default());
     ecs.insert(EventBus::<SoundEvent>::default());
@@ -554,14 +715,28 @@ pub fn register_event_busses(ecs: &mut World) {
     ecs.insert(EventBus::<TamePetEvent>::default());
     ecs.insert(EventBus::<EntityAttackedHookEvent>::default());
     ecs.insert(EventBus::<ChangeAbilityEvent>::default());
+    ecs.insert(EventBus::<SwitchAbilityLoadoutEvent>::default());
     ecs.insert(EventBus::<UpdateMapMarkerEvent>::default());
     ecs.insert(EventBus::<MakeAdminEvent>::default());
     ecs.insert(EventBus::<DeleteCharacterEvent>::default());
     ecs.insert(EventBus::<ChangeStanceEvent>::default());
+    ecs.insert(EventBus::<ReviveEvent>::default());
+    ecs.insert(EventBus::<GroupSpectateEvent>::default());
     ecs.insert(EventBus::<ChangeBodyEvent>::default());
     ecs.insert(EventBus::<RemoveLightEmitterEvent>::default());
     ecs.insert(EventBus::<TeleportToPositionEvent>::default());
     ecs.insert(EventBus::<StartTeleportingEvent>::default());
+    ecs.insert(EventBus::<ActivateShrineEvent>::default());
+    ecs.insert(EventBus::<ShrineTravelEvent>::default());
+    ecs.insert(EventBus::<ReadNoticeBoardEvent>::default());
+    ecs.insert(EventBus::<AcceptBountyEvent>::default());
+    ecs.insert(EventBus::<BountyKillEvent>::default());
+    ecs.insert(EventBus::<ExposeDisguiseEvent>::default());
+    ecs.insert(EventBus::<ReanimateEvent>::default());
+    ecs.insert(EventBus::<RecoverGravestoneEvent>::default());
+    ecs.insert(EventBus::<GiveDirectionsEvent>::default());
+    ecs.insert(EventBus::<SelectTitleEvent>::default());
+    ecs.insert(EventBus::<UnlockTitleEvent>::default());
     ecs.insert(EventBus::<ToggleSpriteLightEvent>::default());
     ecs.insert(EventBus::<TransformEvent>::default());
     ecs.insert(EventBus::<RequestPluginsEvent>::default());
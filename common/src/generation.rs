@@ -13,7 +13,7 @@ use crate::{
     npc::{self, NPC_NAMES},
     resources::TimeOfDay,
     rtsim,
-    trade::SiteInformation,
+    trade::{SiteId, SiteInformation},
 };
 use enum_map::EnumMap;
 use serde::Deserialize;
@@ -70,6 +70,7 @@ pub struct InventorySpec {
 #[derive(Debug, Deserialize, Clone)]
 pub enum Meta {
     SkillSetAsset(String),
+    Encounter(String),
 }
 
 // FIXME: currently this is used for both base definition
@@ -147,6 +148,7 @@ pub struct EntityConfig {
     /// Meta Info for optional fields
     /// Possible fields:
     /// SkillSetAsset(String) with asset_specifier for skillset
+    /// Encounter(String) with asset_specifier for boss encounter script
     #[serde(default)]
     pub meta: Vec<Meta>,
 }
@@ -181,6 +183,8 @@ pub fn try_all_entity_configs() -> Result<Vec<String>, Error> {
 pub enum SpecialEntity {
     Waypoint,
     Teleporter(PortalData),
+    /// Waypoint shrine of the given site, used for fast travel
+    Shrine(SiteId),
     /// Totem with FriendlyFire and ForcePvP auras
     ArenaTotem {
         range: f32,
@@ -215,6 +219,8 @@ pub struct EntityInfo {
     >,
     // Skills
     pub skillset_asset: Option<String>,
+    // Boss encounter script
+    pub encounter: Option<String>,
 
     pub pets: Vec<EntityInfo>,
 
@@ -247,6 +253,7 @@ impl EntityInfo {
             loadout: LoadoutBuilder::empty(),
             make_loadout: None,
             skillset_asset: None,
+            encounter: None,
             pets: Vec::new(),
             trading_information: None,
             special_entity: None,
@@ -365,6 +372,9 @@ impl EntityInfo {
                 Meta::SkillSetAsset(asset) => {
                     self = self.with_skillset_asset(asset);
                 },
+                Meta::Encounter(asset) => {
+                    self = self.with_encounter(asset);
+                },
             }
         }
 
@@ -502,6 +512,12 @@ impl EntityInfo {
         self
     }
 
+    #[must_use]
+    pub fn with_encounter(mut self, asset: String) -> Self {
+        self.encounter = Some(asset);
+        self
+    }
+
     #[must_use]
     pub fn with_automatic_name(mut self, alias: Option<String>) -> Self {
         let npc_names = NPC_NAMES.read();
@@ -524,6 +540,7 @@ impl EntityInfo {
             Body::BipedLarge(body) => Some(get_npc_name(&npc_names.biped_large, body.species)),
             Body::Arthropod(body) => Some(get_npc_name(&npc_names.arthropod, body.species)),
             Body::Crustacean(body) => Some(get_npc_name(&npc_names.crustacean, body.species)),
+            Body::Serpent(body) => Some(get_npc_name(&npc_names.serpent, body.species)),
             _ => None,
         };
         self.name = name.map(|name| {
@@ -596,12 +613,14 @@ mod tests {
     #[derive(Debug, Eq, Hash, PartialEq)]
     enum MetaId {
         SkillSetAsset,
+        Encounter,
     }
 
     impl Meta {
         fn id(&self) -> MetaId {
             match self {
                 Meta::SkillSetAsset(_) => MetaId::SkillSetAsset,
+                Meta::Encounter(_) => MetaId::Encounter,
             }
         }
     }
@@ -698,6 +717,11 @@ mod tests {
                 Meta::SkillSetAsset(asset) => {
                     drop(SkillSetBuilder::from_asset_expect(&asset));
                 },
+                Meta::Encounter(asset) => {
+                    comp::encounter::EncounterScript::load_cloned(&asset).unwrap_or_else(|_| {
+                        panic!("Encounter asset path invalid: \"{asset}\", in {config_asset}")
+                    });
+                },
             }
         }
         for (meta_id, counter) in meta_counter {
@@ -0,0 +1,275 @@
+use crate::{
+    comp::inventory::{slot::InvSlotId, InvSlot},
+    heraldry::Heraldry,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use vek::*;
+
+/// Coins taken from the inventory of a player that founds a guild
+pub const GUILD_CREATION_COST: u32 = 1000;
+/// Guilds can't have more members than this
+pub const MAX_GUILD_MEMBERS: usize = 50;
+pub const GUILD_NAME_MIN_LEN: usize = 3;
+pub const GUILD_NAME_MAX_LEN: usize = 24;
+pub const GUILD_TAG_MIN_LEN: usize = 2;
+pub const GUILD_TAG_MAX_LEN: usize = 5;
+/// Coins taken from the leader of a guild that claims a guild hall
+pub const GUILD_HALL_COST: u32 = 5000;
+/// Guild members need to be this close to the notice board of their hall to
+/// use its storage
+pub const GUILD_HALL_RANGE: f32 = 40.0;
+pub const GUILD_STORAGE_SLOTS: usize = 27;
+/// Older notices are taken down when more than this many are posted
+pub const MAX_GUILD_NOTICES: usize = 20;
+pub const GUILD_NOTICE_MAX_LEN: usize = 200;
+/// Size of the banner hanging on guild halls, in blocks
+pub const GUILD_BANNER_WIDTH: i32 = 5;
+pub const GUILD_BANNER_HEIGHT: i32 = 8;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GuildId(pub i64);
+
+/// Ranks of guild members, ordered from the lowest to the highest
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GuildRank {
+    Member,
+    Officer,
+    Leader,
+}
+
+impl GuildRank {
+    pub fn can_invite(self) -> bool { self >= GuildRank::Officer }
+
+    /// Officers can kick members, the leader can kick anyone but themselves
+    pub fn can_kick(self, target: GuildRank) -> bool { self >= GuildRank::Officer && target < self }
+
+    /// Only the leader changes ranks. Making someone else the leader hands
+    /// over the leadership.
+    pub fn can_set_rank(self) -> bool { self == GuildRank::Leader }
+
+    pub fn can_disband(self) -> bool { self == GuildRank::Leader }
+
+    /// Only the leader claims halls, and decides who may take from the storage
+    pub fn can_claim_hall(self) -> bool { self == GuildRank::Leader }
+
+    /// Officers and the leader change the banner and take down notices of
+    /// other members
+    pub fn can_manage_hall(self) -> bool { self >= GuildRank::Officer }
+
+    pub fn to_key(self) -> &'static str {
+        match self {
+            GuildRank::Member => "member",
+            GuildRank::Officer => "officer",
+            GuildRank::Leader => "leader",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "member" => Some(GuildRank::Member),
+            "officer" => Some(GuildRank::Officer),
+            "leader" => Some(GuildRank::Leader),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a guild name is acceptable, ignoring whether it is taken
+pub fn valid_guild_name(name: &str) -> bool {
+    let len = name.chars().count();
+    (GUILD_NAME_MIN_LEN..=GUILD_NAME_MAX_LEN).contains(&len)
+        && name.trim() == name
+        && name.chars().all(|c| c.is_alphanumeric() || c == ' ')
+}
+
+/// Whether a guild tag is acceptable, ignoring whether it is taken
+pub fn valid_guild_tag(tag: &str) -> bool {
+    let len = tag.chars().count();
+    (GUILD_TAG_MIN_LEN..=GUILD_TAG_MAX_LEN).contains(&len)
+        && tag.chars().all(|c| c.is_alphanumeric())
+}
+
+/// Where the parts of a guild hall that change with its owner are in the world
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildHallSpot {
+    /// The notice board by the door, which also identifies the hall
+    pub board: Vec3<i32>,
+    /// Top left corner of the banner hanging on the front, seen from outside
+    pub banner_origin: Vec3<i32>,
+    /// Direction along the width of the banner, seen from outside
+    pub banner_right: Vec2<i32>,
+}
+
+impl GuildHallSpot {
+    /// Blocks covered by the banner, along with the point of the design they
+    /// show
+    pub fn banner_cells(&self) -> impl Iterator<Item = (Vec3<i32>, Vec2<f32>)> {
+        let spot = *self;
+        (0..GUILD_BANNER_HEIGHT).flat_map(move |y| {
+            (0..GUILD_BANNER_WIDTH).map(move |x| {
+                let pos = spot.banner_origin + (spot.banner_right * x).with_z(-y);
+                let uv = Vec2::new(
+                    (x as f32 + 0.5) / GUILD_BANNER_WIDTH as f32,
+                    (y as f32 + 0.5) / GUILD_BANNER_HEIGHT as f32,
+                );
+                (pos, uv)
+            })
+        })
+    }
+
+    pub fn in_range(&self, pos: Vec3<f32>) -> bool {
+        pos.distance_squared(self.board.as_::<f32>() + 0.5) < GUILD_HALL_RANGE.powi(2)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildNotice {
+    pub id: i64,
+    pub author: Uuid,
+    pub author_alias: String,
+    pub text: String,
+}
+
+/// The hall a guild claimed, as shown to its members
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GuildHallInfo {
+    pub spot: GuildHallSpot,
+    pub banner: Heraldry,
+    /// Lowest rank allowed to take items out of the storage
+    pub storage_rank: GuildRank,
+    pub storage: Vec<InvSlot>,
+    /// Newest last
+    pub notices: Vec<GuildNotice>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildMemberInfo {
+    pub uuid: Uuid,
+    /// Alias of the player when they joined
+    pub alias: String,
+    pub rank: GuildRank,
+}
+
+/// The guild of a player as shown in their roster
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GuildInfo {
+    pub id: GuildId,
+    pub name: String,
+    pub tag: String,
+    pub members: Vec<GuildMemberInfo>,
+    pub hall: Option<GuildHallInfo>,
+}
+
+impl GuildInfo {
+    pub fn rank_of(&self, uuid: Uuid) -> Option<GuildRank> {
+        self.members
+            .iter()
+            .find(|member| member.uuid == uuid)
+            .map(|member| member.rank)
+    }
+}
+
+/// Why a guild request was refused
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuildError {
+    AlreadyInGuild,
+    NotInGuild,
+    /// The invited player is already in a guild
+    TargetInGuild,
+    /// The target of a kick or rank change isn't in the guild
+    NotAMember,
+    NoPermission,
+    InvalidName,
+    InvalidTag,
+    NameTaken,
+    TagTaken,
+    NotEnoughCoins,
+    GuildFull,
+    /// The guild hasn't claimed a hall
+    NoHall,
+    /// Another guild claimed the hall
+    HallTaken,
+    /// The guild already claimed a hall
+    AlreadyOwnsHall,
+    NotAHall,
+    TooFarFromHall,
+    StorageFull,
+    /// There is no room for items taken out of the storage
+    InventoryFull,
+    InvalidNotice,
+    /// The change couldn't be stored in the database
+    Internal,
+}
+
+/// Requests of a player to manage their guild. Invites go through the usual
+/// invite flow with [`crate::comp::invite::InviteKind::Guild`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuildManip {
+    Create {
+        name: String,
+        tag: String,
+    },
+    Leave,
+    Kick(Uuid),
+    SetRank(Uuid, GuildRank),
+    Disband,
+    /// Buy the hall with the notice board at this position
+    ClaimHall(Vec3<i32>),
+    PostNotice(String),
+    RemoveNotice(i64),
+    SetBanner(Heraldry),
+    SetStorageRank(GuildRank),
+    StorageDeposit(InvSlotId),
+    StorageWithdraw(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_permissions() {
+        assert!(!GuildRank::Member.can_invite());
+        assert!(GuildRank::Officer.can_invite());
+        assert!(GuildRank::Officer.can_kick(GuildRank::Member));
+        assert!(!GuildRank::Officer.can_kick(GuildRank::Officer));
+        assert!(GuildRank::Leader.can_kick(GuildRank::Officer));
+        assert!(!GuildRank::Leader.can_kick(GuildRank::Leader));
+        assert!(!GuildRank::Officer.can_set_rank());
+    }
+
+    #[test]
+    fn names_and_tags() {
+        assert!(valid_guild_name("Iron Wolves"));
+        assert!(!valid_guild_name(" Iron"));
+        assert!(!valid_guild_name("ab"));
+        assert!(valid_guild_tag("IW"));
+        assert!(!valid_guild_tag("I W"));
+        assert!(!valid_guild_tag("TOOLONG"));
+    }
+
+    #[test]
+    fn banner_cells_cover_banner() {
+        let spot = GuildHallSpot {
+            board: Vec3::zero(),
+            banner_origin: Vec3::new(10, 0, 20),
+            banner_right: Vec2::new(0, -1),
+        };
+        let cells = spot.banner_cells().collect::<Vec<_>>();
+        assert_eq!(
+            cells.len(),
+            (GUILD_BANNER_WIDTH * GUILD_BANNER_HEIGHT) as usize
+        );
+        assert_eq!(cells[0].0, Vec3::new(10, 0, 20));
+        assert_eq!(
+            cells.last().unwrap().0,
+            Vec3::new(10, 1 - GUILD_BANNER_WIDTH, 21 - GUILD_BANNER_HEIGHT)
+        );
+        assert!(
+            cells
+                .iter()
+                .all(|(_, uv)| uv.x > 0.0 && uv.x < 1.0 && uv.y > 0.0 && uv.y < 1.0)
+        );
+    }
+}
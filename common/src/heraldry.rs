@@ -0,0 +1,328 @@
+//! Heraldic designs, composed of a pattern over a field and an optional
+//! emblem, that can be shown on capes, shields and guild banners.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use vek::*;
+
+/// Colours a design can be made of
+pub const TINCTURES: [(u8, u8, u8); 10] = [
+    (222, 210, 180),
+    (30, 30, 34),
+    (160, 32, 30),
+    (210, 130, 30),
+    (228, 196, 60),
+    (50, 120, 45),
+    (35, 70, 150),
+    (100, 45, 130),
+    (120, 75, 40),
+    (150, 150, 150),
+];
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    #[default]
+    Plain,
+    /// A vertical stripe
+    Pale,
+    /// A horizontal stripe
+    Fess,
+    /// A diagonal stripe from the top left
+    Bend,
+    Cross,
+    /// A diagonal cross
+    Saltire,
+    Chevron,
+    /// A band across the top
+    Chief,
+    /// Divided into a left and a right half
+    PerPale,
+    Quartered,
+    Bordure,
+}
+
+impl Pattern {
+    pub const ALL: [Pattern; 11] = [
+        Pattern::Plain,
+        Pattern::Pale,
+        Pattern::Fess,
+        Pattern::Bend,
+        Pattern::Cross,
+        Pattern::Saltire,
+        Pattern::Chevron,
+        Pattern::Chief,
+        Pattern::PerPale,
+        Pattern::Quartered,
+        Pattern::Bordure,
+    ];
+
+    /// Whether the pattern covers the point `uv` of the design, from the top
+    /// left at (0, 0) to the bottom right at (1, 1)
+    fn covers(self, uv: Vec2<f32>) -> bool {
+        let centered = (uv - 0.5).map(f32::abs);
+        match self {
+            Pattern::Plain => false,
+            Pattern::Pale => centered.x < 0.2,
+            Pattern::Fess => centered.y < 0.13,
+            Pattern::Bend => (uv.x - uv.y).abs() < 0.15,
+            Pattern::Cross => centered.x < 0.2 || centered.y < 0.13,
+            Pattern::Saltire => (uv.x - uv.y).abs() < 0.12 || (uv.x + uv.y - 1.0).abs() < 0.12,
+            Pattern::Chevron => {
+                let top = 0.55 - centered.x * 0.6;
+                uv.y > top && uv.y < top + 0.2
+            },
+            Pattern::Chief => uv.y < 0.3,
+            Pattern::PerPale => uv.x < 0.5,
+            Pattern::Quartered => (uv.x < 0.5) != (uv.y < 0.5),
+            Pattern::Bordure => centered.x > 0.3 || centered.y > 0.4,
+        }
+    }
+
+    pub fn to_key(self) -> &'static str {
+        match self {
+            Pattern::Plain => "plain",
+            Pattern::Pale => "pale",
+            Pattern::Fess => "fess",
+            Pattern::Bend => "bend",
+            Pattern::Cross => "cross",
+            Pattern::Saltire => "saltire",
+            Pattern::Chevron => "chevron",
+            Pattern::Chief => "chief",
+            Pattern::PerPale => "per_pale",
+            Pattern::Quartered => "quartered",
+            Pattern::Bordure => "bordure",
+        }
+    }
+}
+
+/// A small picture placed in the middle of a design, on top of its pattern
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Emblem {
+    #[default]
+    None,
+    Star,
+    Crown,
+    Sword,
+    Tree,
+    Moon,
+    Skull,
+    Tower,
+    Hammer,
+}
+
+/// Width and height of the emblem bitmaps
+const EMBLEM_SIZE: usize = 7;
+
+impl Emblem {
+    pub const ALL: [Emblem; 9] = [
+        Emblem::None,
+        Emblem::Star,
+        Emblem::Crown,
+        Emblem::Sword,
+        Emblem::Tree,
+        Emblem::Moon,
+        Emblem::Skull,
+        Emblem::Tower,
+        Emblem::Hammer,
+    ];
+
+    /// Rows of the emblem from the top, with the leftmost pixel in the highest
+    /// bit
+    fn bitmap(self) -> [u8; EMBLEM_SIZE] {
+        match self {
+            Emblem::None => [0; EMBLEM_SIZE],
+            Emblem::Star => [
+                0b0001000, 0b0011100, 0b1111111, 0b0111110, 0b0011100, 0b0110110, 0b1100011,
+            ],
+            Emblem::Crown => [
+                0b1001001, 0b1011101, 0b1111111, 0b1111111, 0b0111110, 0b0111110, 0b0000000,
+            ],
+            Emblem::Sword => [
+                0b0001000, 0b0001000, 0b0001000, 0b0001000, 0b0111110, 0b0001000, 0b0001000,
+            ],
+            Emblem::Tree => [
+                0b0001000, 0b0011100, 0b0111110, 0b1111111, 0b0001000, 0b0001000, 0b0011100,
+            ],
+            Emblem::Moon => [
+                0b0011100, 0b0110000, 0b1100000, 0b1100000, 0b1100000, 0b0110000, 0b0011100,
+            ],
+            Emblem::Skull => [
+                0b0111110, 0b1111111, 0b1011101, 0b1111111, 0b0110110, 0b0111110, 0b0101010,
+            ],
+            Emblem::Tower => [
+                0b1010101, 0b1111111, 0b0111110, 0b0110110, 0b0111110, 0b0110110, 0b0111110,
+            ],
+            Emblem::Hammer => [
+                0b1111111, 0b1111111, 0b0001000, 0b0001000, 0b0001000, 0b0001000, 0b0001000,
+            ],
+        }
+    }
+
+    /// Whether the emblem covers the point `uv` of the design, from the top
+    /// left at (0, 0) to the bottom right at (1, 1). Emblems take up the
+    /// middle half of the width and the middle 40% of the height.
+    fn covers(self, uv: Vec2<f32>) -> bool {
+        let local = Vec2::new((uv.x - 0.25) / 0.5, (uv.y - 0.3) / 0.4);
+        if !(0.0..1.0).contains(&local.x) || !(0.0..1.0).contains(&local.y) {
+            return false;
+        }
+        let pixel = local.map(|e| (e * EMBLEM_SIZE as f32) as usize);
+        self.bitmap()[pixel.y] & (1 << (EMBLEM_SIZE - 1 - pixel.x)) != 0
+    }
+
+    pub fn to_key(self) -> &'static str {
+        match self {
+            Emblem::None => "none",
+            Emblem::Star => "star",
+            Emblem::Crown => "crown",
+            Emblem::Sword => "sword",
+            Emblem::Tree => "tree",
+            Emblem::Moon => "moon",
+            Emblem::Skull => "skull",
+            Emblem::Tower => "tower",
+            Emblem::Hammer => "hammer",
+        }
+    }
+}
+
+/// A heraldic design. It is sent and stored packed into a `u32` (see
+/// [`Heraldry::to_bits`]), since it ends up in item and guild data.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "u32", try_from = "u32")]
+pub struct Heraldry {
+    pub pattern: Pattern,
+    /// Index into [`TINCTURES`] of the background colour
+    pub field: u8,
+    /// Index into [`TINCTURES`] of the colour of the pattern
+    pub charge: u8,
+    pub emblem: Emblem,
+    /// Index into [`TINCTURES`] of the colour of the emblem
+    pub emblem_tincture: u8,
+}
+
+#[derive(Debug)]
+pub struct InvalidHeraldry(u32);
+
+impl fmt::Display for InvalidHeraldry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x} is not a valid heraldic design", self.0)
+    }
+}
+
+impl Heraldry {
+    pub fn field_color(&self) -> Rgb<u8> { tincture(self.field) }
+
+    pub fn charge_color(&self) -> Rgb<u8> { tincture(self.charge) }
+
+    pub fn emblem_color(&self) -> Rgb<u8> { tincture(self.emblem_tincture) }
+
+    /// Colour of the design at `uv`, from the top left at (0, 0) to the bottom
+    /// right at (1, 1)
+    pub fn color_at(&self, uv: Vec2<f32>) -> Rgb<u8> {
+        if self.emblem.covers(uv) {
+            self.emblem_color()
+        } else if self.pattern.covers(uv) {
+            self.charge_color()
+        } else {
+            self.field_color()
+        }
+    }
+
+    /// Packs the design into 4 bits for each of its parts, from the pattern in
+    /// the lowest bits to the emblem colour
+    pub fn to_bits(self) -> u32 {
+        let pattern = Pattern::ALL
+            .iter()
+            .position(|p| *p == self.pattern)
+            .unwrap_or(0);
+        let emblem = Emblem::ALL
+            .iter()
+            .position(|e| *e == self.emblem)
+            .unwrap_or(0);
+        pattern as u32
+            | (u32::from(self.field & 0xF) << 4)
+            | (u32::from(self.charge & 0xF) << 8)
+            | ((emblem as u32) << 12)
+            | (u32::from(self.emblem_tincture & 0xF) << 16)
+    }
+
+    pub fn from_bits(bits: u32) -> Option<Self> {
+        let part = |shift: u32| ((bits >> shift) & 0xF) as u8;
+        let tincture = |shift: u32| Some(part(shift)).filter(|t| usize::from(*t) < TINCTURES.len());
+        if bits >> 20 != 0 {
+            return None;
+        }
+        Some(Self {
+            pattern: *Pattern::ALL.get(usize::from(part(0)))?,
+            field: tincture(4)?,
+            charge: tincture(8)?,
+            emblem: *Emblem::ALL.get(usize::from(part(12)))?,
+            emblem_tincture: tincture(16)?,
+        })
+    }
+}
+
+impl From<Heraldry> for u32 {
+    fn from(heraldry: Heraldry) -> Self { heraldry.to_bits() }
+}
+
+impl TryFrom<u32> for Heraldry {
+    type Error = InvalidHeraldry;
+
+    fn try_from(bits: u32) -> Result<Self, Self::Error> {
+        Self::from_bits(bits).ok_or(InvalidHeraldry(bits))
+    }
+}
+
+fn tincture(index: u8) -> Rgb<u8> {
+    let (r, g, b) = TINCTURES[usize::from(index) % TINCTURES.len()];
+    Rgb::new(r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_round_trip() {
+        let heraldry = Heraldry {
+            pattern: Pattern::Bordure,
+            field: 9,
+            charge: 3,
+            emblem: Emblem::Hammer,
+            emblem_tincture: 4,
+        };
+        assert_eq!(Heraldry::from_bits(heraldry.to_bits()), Some(heraldry));
+        assert_eq!(
+            Heraldry::from_bits(Heraldry::default().to_bits()),
+            Some(Heraldry::default())
+        );
+        // Out of range tinctures and unused bits are rejected
+        assert_eq!(Heraldry::from_bits(0xF0), None);
+        assert_eq!(Heraldry::from_bits(1 << 20), None);
+    }
+
+    #[test]
+    fn emblem_drawn_over_pattern() {
+        let heraldry = Heraldry {
+            pattern: Pattern::Pale,
+            field: 0,
+            charge: 1,
+            emblem: Emblem::Sword,
+            emblem_tincture: 2,
+        };
+        // The blade of the sword runs down the middle
+        assert_eq!(
+            heraldry.color_at(Vec2::new(0.5, 0.35)),
+            heraldry.emblem_color()
+        );
+        assert_eq!(
+            heraldry.color_at(Vec2::new(0.45, 0.9)),
+            heraldry.charge_color()
+        );
+        assert_eq!(
+            heraldry.color_at(Vec2::new(0.1, 0.5)),
+            heraldry.field_color()
+        );
+    }
+}
@@ -30,6 +30,7 @@ pub mod shared_server_config;
 pub mod uid;
 
 pub mod astar;
+pub mod bounty;
 pub mod calendar;
 pub mod character;
 pub mod clock;
@@ -41,13 +42,19 @@ pub mod explosion;
 pub mod figure;
 pub mod generation;
 pub mod grid;
+pub mod guild;
+pub mod heraldry;
 pub mod link;
 pub mod lod;
+pub mod loot_roll;
 pub mod lottery;
+pub mod minigame;
 pub mod mounting;
 pub mod npc;
 pub mod outcome;
 pub mod path;
+pub mod prefab;
+pub mod race;
 pub mod ray;
 pub mod recipe;
 pub mod region;
@@ -73,7 +80,7 @@ mod view_distances;
 // within this crate if typed module is declared in macro expansion.
 pub mod typed;
 
-pub use combat::{DamageKind, DamageSource};
+pub use combat::{DamageElement, DamageKind, DamageSource};
 
 pub use cached_spatial_grid::CachedSpatialGrid;
 pub use combat::{Damage, GroupTarget, Knockback, KnockbackDir};
@@ -0,0 +1,201 @@
+use crate::{
+    comp::{
+        group::{self, Group, Role},
+        Alignment, Item, Pos,
+    },
+    resources::Time,
+    uid::Uid,
+};
+use hashbrown::HashMap;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use specs::{Join, ReadStorage};
+use vek::*;
+
+/// Time group members have to make a choice before it defaults to
+/// [`LootRollChoice::Pass`]
+pub const LOOT_ROLL_TIMEOUT_SECS: f64 = 30.0;
+/// Maximum distance from the loot at which group members are allowed to roll
+/// for it
+pub const LOOT_ROLL_RANGE: f32 = 128.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LootRollId(pub u64);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LootRollChoice {
+    Need,
+    Greed,
+    Pass,
+}
+
+/// An item held back by the server while group members decide who gets it
+pub struct LootRoll {
+    pub item: Item,
+    /// Where the item is dropped for the group if nobody wins it
+    pub pos: Vec3<f32>,
+    pub group: Group,
+    pub deadline: Time,
+    /// Members allowed to roll, together with their choice once they made it
+    choices: HashMap<Uid, Option<LootRollChoice>>,
+    announced: bool,
+}
+
+impl LootRoll {
+    pub fn eligible(&self) -> impl Iterator<Item = Uid> + '_ { self.choices.keys().copied() }
+
+    /// Whether every member has chosen or the time to choose ran out
+    pub fn is_decided(&self, time: Time) -> bool {
+        time.0 >= self.deadline.0 || self.choices.values().all(Option::is_some)
+    }
+
+    /// Rolls 1-100 for every member who chose need and returns the highest
+    /// roll, falling back to those who chose greed if nobody needs the item.
+    /// Members that didn't choose in time are treated as having passed.
+    pub fn winner(&self, rng: &mut impl Rng) -> Option<(Uid, LootRollChoice, u32)> {
+        [LootRollChoice::Need, LootRollChoice::Greed]
+            .into_iter()
+            .find_map(|choice| {
+                self.choices
+                    .iter()
+                    .filter(|(_, c)| **c == Some(choice))
+                    .map(|(uid, _)| (*uid, choice, rng.gen_range(1..=100)))
+                    .max_by_key(|(_, _, roll)| *roll)
+            })
+    }
+}
+
+/// Members of `group` close enough to `pos` to roll for loot dropped there
+pub fn eligible_members<'a>(
+    group: Group,
+    pos: Vec3<f32>,
+    groups: impl Join<Type = &'a Group> + 'a,
+    entities: &'a specs::world::EntitiesRes,
+    alignments: &'a ReadStorage<Alignment>,
+    uids: &'a ReadStorage<Uid>,
+    positions: &ReadStorage<Pos>,
+) -> Vec<Uid> {
+    group::members(group, groups, entities, alignments, uids)
+        .filter(|(entity, role)| {
+            matches!(role, Role::Member)
+                && positions.get(*entity).map_or(false, |member_pos| {
+                    member_pos.0.distance_squared(pos) <= LOOT_ROLL_RANGE.powi(2)
+                })
+        })
+        .filter_map(|(entity, _)| uids.get(entity).copied())
+        .collect()
+}
+
+/// Loot rolls currently in progress, owned by the server
+#[derive(Default)]
+pub struct LootRolls {
+    next_id: u64,
+    rolls: HashMap<LootRollId, LootRoll>,
+}
+
+impl LootRolls {
+    pub fn start(
+        &mut self,
+        item: Item,
+        pos: Vec3<f32>,
+        group: Group,
+        eligible: impl IntoIterator<Item = Uid>,
+        time: Time,
+    ) -> LootRollId {
+        let id = LootRollId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.rolls.insert(id, LootRoll {
+            item,
+            pos,
+            group,
+            deadline: Time(time.0 + LOOT_ROLL_TIMEOUT_SECS),
+            choices: eligible.into_iter().map(|uid| (uid, None)).collect(),
+            announced: false,
+        });
+        id
+    }
+
+    /// Returns the rolls started since the last call, so that the eligible
+    /// members can be asked for their choice
+    pub fn take_new(&mut self) -> impl Iterator<Item = (LootRollId, &LootRoll)> {
+        self.rolls
+            .iter_mut()
+            .filter(|(_, roll)| !roll.announced)
+            .map(|(id, roll)| {
+                roll.announced = true;
+                (*id, &*roll)
+            })
+    }
+
+    /// Records the choice of a member, returns false if they are not allowed
+    /// to roll for this item or already made their choice
+    pub fn choose(&mut self, id: LootRollId, uid: Uid, choice: LootRollChoice) -> bool {
+        match self
+            .rolls
+            .get_mut(&id)
+            .and_then(|roll| roll.choices.get_mut(&uid))
+        {
+            Some(slot @ None) => {
+                *slot = Some(choice);
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Removes and returns all rolls that are ready to be handed out
+    pub fn take_decided(&mut self, time: Time) -> Vec<(LootRollId, LootRoll)> {
+        let decided = self
+            .rolls
+            .iter()
+            .filter(|(_, roll)| roll.is_decided(time))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        decided
+            .into_iter()
+            .filter_map(|id| Some((id, self.rolls.remove(&id)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start_roll(rolls: &mut LootRolls, members: &[Uid]) -> LootRollId {
+        rolls.start(
+            Item::new_from_asset_expect("common.items.utility.coins"),
+            Vec3::zero(),
+            crate::comp::group::NPC,
+            members.iter().copied(),
+            Time(0.0),
+        )
+    }
+
+    #[test]
+    fn need_beats_greed() {
+        let mut rolls = LootRolls::default();
+        let id = start_roll(&mut rolls, &[Uid(1), Uid(2), Uid(3)]);
+        assert!(rolls.choose(id, Uid(1), LootRollChoice::Greed));
+        assert!(rolls.choose(id, Uid(2), LootRollChoice::Need));
+        assert!(!rolls.choose(id, Uid(2), LootRollChoice::Greed));
+        assert!(!rolls.choose(id, Uid(4), LootRollChoice::Need));
+        assert!(rolls.take_decided(Time(1.0)).is_empty());
+
+        assert!(rolls.choose(id, Uid(3), LootRollChoice::Pass));
+        let (_, roll) = rolls.take_decided(Time(1.0)).pop().unwrap();
+        let (winner, choice, _) = roll.winner(&mut rand::thread_rng()).unwrap();
+        assert_eq!((winner, choice), (Uid(2), LootRollChoice::Need));
+    }
+
+    #[test]
+    fn timeout_counts_as_pass() {
+        let mut rolls = LootRolls::default();
+        start_roll(&mut rolls, &[Uid(1), Uid(2)]);
+        let (_, roll) = rolls
+            .take_decided(Time(LOOT_ROLL_TIMEOUT_SECS))
+            .pop()
+            .unwrap();
+        assert!(roll.winner(&mut rand::thread_rng()).is_none());
+    }
+}
@@ -42,10 +42,15 @@ pub struct Lottery<T> {
     total: f32,
 }
 
-impl<T: DeserializeOwned + Send + Sync + 'static> assets::Asset for Lottery<T> {
-    type Loader = assets::LoadFrom<Vec<(f32, T)>, assets::RonLoader>;
-
-    const EXTENSION: &'static str = "ron";
+impl<T: DeserializeOwned + Send + Sync + 'static> assets::Compound for Lottery<T> {
+    fn load(
+        cache: assets::AnyCache,
+        id: &assets::SharedString,
+    ) -> Result<Self, assets::BoxedError> {
+        // Plugins can add entries to existing loot tables
+        let items = cache.load_owned::<assets::MultiRon<Vec<(f32, T)>>>(id)?.0;
+        Ok(items.into())
+    }
 }
 
 impl<T> From<Vec<(f32, T)>> for Lottery<T> {
@@ -0,0 +1,132 @@
+use crate::{assets, resources::Secs, uid::Uid};
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+/// Data-driven definition of a timed minigame that players can join.
+///
+/// Definitions are loaded from `common.minigame.*` and started by admins with
+/// `/minigame start`.
+///
+/// Check assets/common/minigame/race_example.ron for an example.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MinigameDef {
+    /// Name shown to players
+    pub name: String,
+    /// Maximum time the minigame runs for
+    pub duration: Secs,
+    #[serde(default)]
+    pub max_players: Option<usize>,
+    /// Where participants are teleported when they join
+    pub start: Vec3<f32>,
+    pub objective: Objective,
+    /// Rewards for the best participants, the first entry goes to the winner.
+    /// Participants without any score don't get rewards.
+    #[serde(default)]
+    pub rewards: Vec<Reward>,
+}
+
+impl assets::Asset for MinigameDef {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub enum Objective {
+    /// Participants score a point for every second they spend within
+    /// `radius` of `pos`, as long as no other participant contests the point
+    CapturePoint { pos: Vec3<f32>, radius: f32 },
+    /// Participants score a point for every checkpoint they pass through, in
+    /// order. The minigame ends once everyone finished.
+    Race {
+        checkpoints: Vec<Vec3<f32>>,
+        radius: f32,
+    },
+    /// Waves of enemies are spawned at `spawn` one after another, every
+    /// participant scores a point for each wave cleared. The minigame ends
+    /// once the last wave was cleared.
+    BossRush {
+        spawn: Vec3<f32>,
+        /// Entity configs of every wave
+        waves: Vec<Vec<String>>,
+        /// Enemies within this distance of `spawn` count towards the current
+        /// wave
+        arena_radius: f32,
+    },
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Reward {
+    pub item: String,
+    #[serde(default = "default_reward_amount")]
+    pub amount: u32,
+}
+
+fn default_reward_amount() -> u32 { 1 }
+
+/// Scores of a running minigame, sent to its participants
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Scoreboard {
+    pub name: String,
+    pub time_left: Secs,
+    /// Scores of all participants, best first
+    pub scores: Vec<(Uid, u32)>,
+}
+
+/// Orders participants by descending score. Ties are broken by who finished
+/// first, e.g. in a race, and participants that didn't finish come last.
+pub fn rank(scores: &mut [(Uid, u32, Option<f64>)]) {
+    scores.sort_by(|(_, a, a_done), (_, b, b_done)| {
+        b.cmp(a).then_with(|| match (a_done, b_done) {
+            (Some(a), Some(b)) => a.total_cmp(b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assets::AssetExt, comp::Item, generation::EntityConfig};
+
+    #[test]
+    fn finishing_first_breaks_ties() {
+        let mut scores = vec![
+            (Uid(1), 3, None),
+            (Uid(2), 3, Some(20.0)),
+            (Uid(3), 4, None),
+            (Uid(4), 3, Some(10.0)),
+        ];
+        rank(&mut scores);
+        let order = scores.iter().map(|(uid, _, _)| *uid).collect::<Vec<_>>();
+        assert_eq!(order, [Uid(3), Uid(4), Uid(2), Uid(1)]);
+    }
+
+    #[test]
+    fn test_all_minigames() {
+        let minigames = assets::load_rec_dir::<MinigameDef>("common.minigame")
+            .expect("Failed to access minigame directory");
+        for id in minigames.read().ids() {
+            let minigame = MinigameDef::load_expect(id).read();
+            assert!(
+                minigame.duration.0 > 0.0,
+                "Duration must be positive in {id}"
+            );
+            for reward in &minigame.rewards {
+                Item::new_from_asset(&reward.item)
+                    .unwrap_or_else(|_| panic!("Invalid reward item \"{}\" in {id}", reward.item));
+            }
+            if let Objective::BossRush { waves, .. } = &minigame.objective {
+                for entity in waves.iter().flatten() {
+                    EntityConfig::load_cloned(entity)
+                        .unwrap_or_else(|_| panic!("Invalid wave entity \"{entity}\" in {id}"));
+                }
+            }
+        }
+    }
+}
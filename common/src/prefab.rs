@@ -0,0 +1,204 @@
+use crate::{
+    terrain::{Block, BlockKind},
+    vol::ReadVol,
+};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+/// Prefabs larger than this many blocks can't be saved, to keep files and
+/// placements reasonably cheap
+pub const MAX_PREFAB_VOLUME: usize = 1 << 24;
+
+/// A saved region of blocks that can be placed elsewhere in the world
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Prefab {
+    size: Vec3<u32>,
+    palette: Vec<Block>,
+    /// Index into the palette for every block, x first then y then z
+    blocks: Vec<u16>,
+}
+
+impl Prefab {
+    /// Copies the blocks within `aabb` (inclusive) out of `vol`. Blocks that
+    /// can't be read are saved as air.
+    pub fn capture(vol: &impl ReadVol<Vox = Block>, aabb: Aabb<i32>) -> Result<Self, String> {
+        let aabb = aabb.made_valid();
+        let size = (aabb.max - aabb.min).map(|e| e as u32 + 1);
+        let volume = size.as_::<u64>().product();
+        if volume > MAX_PREFAB_VOLUME as u64 {
+            return Err(format!(
+                "Prefab would contain {volume} blocks, at most {MAX_PREFAB_VOLUME} are allowed"
+            ));
+        }
+
+        let mut palette = Vec::new();
+        let mut indices = HashMap::new();
+        let mut blocks = Vec::with_capacity(volume as usize);
+        for z in aabb.min.z..=aabb.max.z {
+            for y in aabb.min.y..=aabb.max.y {
+                for x in aabb.min.x..=aabb.max.x {
+                    let block = vol
+                        .get(Vec3::new(x, y, z))
+                        .copied()
+                        .unwrap_or_else(|_| Block::empty());
+                    let index = *indices.entry(block).or_insert_with(|| {
+                        palette.push(block);
+                        palette.len() - 1
+                    });
+                    blocks
+                        .push(u16::try_from(index).map_err(|_| {
+                            "Prefab contains too many different blocks".to_string()
+                        })?);
+                }
+            }
+        }
+
+        Ok(Self {
+            size,
+            palette,
+            blocks,
+        })
+    }
+
+    pub fn size(&self) -> Vec3<u32> { self.size }
+
+    /// The blocks covered by the prefab when placed at `placement`, in world
+    /// coordinates (inclusive)
+    pub fn bounds(&self, placement: &PrefabPlacement) -> Aabb<i32> {
+        let size = self.size.as_::<i32>();
+        let size = if placement.rotation % 2 == 0 {
+            size
+        } else {
+            Vec3::new(size.y, size.x, size.z)
+        };
+        Aabb {
+            min: placement.origin,
+            max: placement.origin + size - 1,
+        }
+    }
+
+    /// The block to place at `wpos` for `placement`, if the prefab covers it
+    pub fn get(&self, placement: &PrefabPlacement, wpos: Vec3<i32>) -> Option<Block> {
+        let size = self.size.as_::<i32>();
+        let rpos = wpos - placement.origin;
+        // Undo the rotation to find the block in the prefab
+        let (x, y) = match placement.rotation % 4 {
+            0 => (rpos.x, rpos.y),
+            1 => (rpos.y, size.y - 1 - rpos.x),
+            2 => (size.x - 1 - rpos.x, size.y - 1 - rpos.y),
+            _ => (size.x - 1 - rpos.y, rpos.x),
+        };
+        let pos = Vec3::new(x, y, rpos.z);
+        if pos.zip(size).iter().any(|(e, s)| *e < 0 || e >= s) {
+            return None;
+        }
+        let index = (pos.x + size.x * (pos.y + size.y * pos.z)) as usize;
+        let block = *self.palette.get(*self.blocks.get(index)? as usize)?;
+        Some(placement.apply(block))
+    }
+}
+
+/// A prefab placed in the world
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PrefabPlacement {
+    pub prefab: String,
+    /// Lowest corner of the placed prefab
+    pub origin: Vec3<i32>,
+    /// Number of quarter turns around the z axis
+    pub rotation: u8,
+    /// Kinds of solid blocks to replace with other kinds, keeping their colour
+    pub remap: Vec<(BlockKind, BlockKind)>,
+}
+
+impl PrefabPlacement {
+    /// Parses a remap in the form `Rock=Wood,Leaves=GlowingRock`
+    pub fn parse_remap(remap: &str) -> Result<Vec<(BlockKind, BlockKind)>, String> {
+        remap
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(|pair| {
+                let (from, to) = pair
+                    .split_once('=')
+                    .ok_or_else(|| format!("Expected 'From=To', got '{pair}'"))?;
+                let parse = |kind: &str| {
+                    kind.trim()
+                        .parse::<BlockKind>()
+                        .ok()
+                        .filter(|kind| kind.is_filled())
+                        .ok_or_else(|| format!("'{kind}' is not a solid block kind"))
+                };
+                Ok((parse(from)?, parse(to)?))
+            })
+            .collect()
+    }
+
+    fn apply(&self, block: Block) -> Block {
+        let block = match self
+            .remap
+            .iter()
+            .find(|(from, _)| *from == block.kind())
+            .zip(block.get_color())
+        {
+            Some(((_, to), color)) => Block::new(*to, color),
+            None => block,
+        };
+        // Sprite orientations are in eighths of a turn
+        match block.get_ori() {
+            Some(ori) => block
+                .with_ori((ori + self.rotation % 4 * 2) % 8)
+                .unwrap_or(block),
+            None => block,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::SpriteKind;
+
+    #[test]
+    fn rotation() {
+        let prefab = Prefab {
+            size: Vec3::new(2, 1, 1),
+            palette: vec![Block::new(BlockKind::Rock, Rgb::zero()), Block::empty()],
+            blocks: vec![0, 1],
+        };
+        let placement = |rotation| PrefabPlacement {
+            prefab: String::new(),
+            origin: Vec3::new(10, 10, 0),
+            rotation,
+            remap: vec![(BlockKind::Rock, BlockKind::Wood)],
+        };
+
+        assert_eq!(prefab.bounds(&placement(1)).max, Vec3::new(10, 11, 0));
+        // Quarter turn counter-clockwise, the block at x = 0 ends up at y = 0
+        let rock = prefab.get(&placement(1), Vec3::new(10, 10, 0)).unwrap();
+        assert_eq!(rock.kind(), BlockKind::Wood);
+        assert!(
+            prefab
+                .get(&placement(1), Vec3::new(10, 11, 0))
+                .unwrap()
+                .is_air()
+        );
+        assert!(prefab.get(&placement(1), Vec3::new(11, 10, 0)).is_none());
+        // Half turn, the block at x = 0 ends up at x = 1
+        assert!(
+            prefab
+                .get(&placement(2), Vec3::new(10, 10, 0))
+                .unwrap()
+                .is_air()
+        );
+        assert_eq!(
+            prefab
+                .get(&placement(2), Vec3::new(11, 10, 0))
+                .unwrap()
+                .kind(),
+            BlockKind::Wood
+        );
+
+        let chair = Block::air(SpriteKind::ChairSingle).with_ori(1).unwrap();
+        assert_eq!(placement(3).apply(chair).get_ori(), Some(7));
+    }
+}
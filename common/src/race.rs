@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+/// Characters within this horizontal distance of a checkpoint pass it
+pub const CHECKPOINT_RADIUS: f32 = 5.0;
+/// Characters gliding or riding this far above or below a checkpoint still pass
+/// it, so that gates can be placed along routes through the air
+pub const CHECKPOINT_HEIGHT: f32 = 20.0;
+
+pub fn passes_checkpoint(pos: Vec3<f32>, checkpoint: Vec3<f32>) -> bool {
+    pos.xy().distance_squared(checkpoint.xy()) <= CHECKPOINT_RADIUS.powi(2)
+        && (pos.z - checkpoint.z).abs() <= CHECKPOINT_HEIGHT
+}
+
+/// Sent to a character when their run on a race track starts or ends
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum RaceProgress {
+    /// The character reached the first checkpoint of `track`, at `start`. The
+    /// time only starts once they leave it.
+    Started { track: String, start: Vec3<f32> },
+    /// The character passed the last checkpoint of `track`
+    Finished {
+        track: String,
+        time: f64,
+        personal_best: bool,
+    },
+    /// The run ended without finishing, e.g. because the character died
+    Aborted,
+}
+
+/// Positions sampled during a run, relative to its start, to play it back as
+/// a ghost
+#[derive(Clone, Debug, Default)]
+pub struct RaceGhost {
+    samples: Vec<(f64, Vec3<f32>)>,
+}
+
+impl RaceGhost {
+    const SAMPLE_INTERVAL: f64 = 0.1;
+
+    pub fn record(&mut self, time: f64, pos: Vec3<f32>) {
+        if self
+            .samples
+            .last()
+            .map_or(true, |(last, _)| time - last >= Self::SAMPLE_INTERVAL)
+        {
+            self.samples.push((time, pos));
+        }
+    }
+
+    /// Where the run was `time` seconds after it started. The ghost waits at
+    /// the end of its path once it finished.
+    pub fn pos_at(&self, time: f64) -> Option<Vec3<f32>> {
+        let next = self.samples.partition_point(|(t, _)| *t <= time);
+        match (
+            next.checked_sub(1).map(|i| self.samples[i]),
+            self.samples.get(next).copied(),
+        ) {
+            (Some((t0, p0)), Some((t1, p1))) => {
+                Some(Lerp::lerp(p0, p1, ((time - t0) / (t1 - t0)) as f32))
+            },
+            (Some((_, p)), None) | (None, Some((_, p))) => Some(p),
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint_is_a_column() {
+        let checkpoint = Vec3::new(100.0, 100.0, 50.0);
+        assert!(passes_checkpoint(Vec3::new(103.0, 100.0, 50.0), checkpoint));
+        assert!(passes_checkpoint(Vec3::new(100.0, 100.0, 65.0), checkpoint));
+        assert!(!passes_checkpoint(
+            Vec3::new(106.0, 100.0, 50.0),
+            checkpoint
+        ));
+        assert!(!passes_checkpoint(
+            Vec3::new(100.0, 100.0, 75.0),
+            checkpoint
+        ));
+    }
+
+    #[test]
+    fn ghost_interpolates_between_samples() {
+        let mut ghost = RaceGhost::default();
+        ghost.record(0.0, Vec3::zero());
+        // Too close to the previous sample
+        ghost.record(0.05, Vec3::broadcast(100.0));
+        ghost.record(1.0, Vec3::new(10.0, 0.0, 0.0));
+        assert_eq!(ghost.pos_at(0.5), Some(Vec3::new(5.0, 0.0, 0.0)));
+        assert_eq!(ghost.pos_at(5.0), Some(Vec3::new(10.0, 0.0, 0.0)));
+    }
+}
@@ -45,6 +45,16 @@ impl Region {
     pub fn entities(&self) -> &BitSet { &self.bitset }
 }
 
+/// A group of neighboring regions that contain entities. Entities in different
+/// shards are at least a region apart, too far to interact with each other
+/// within a tick, so work on them can be split up by shard and done in
+/// parallel.
+#[derive(Default)]
+pub struct RegionShard {
+    pub regions: Vec<Vec2<i32>>,
+    pub entities: BitSet,
+}
+
 /// How far can an entity roam outside its region before it is switched over to
 /// the neighboring one In units of blocks (i.e. world pos)
 /// Used to prevent rapid switching of entities between regions
@@ -91,6 +101,8 @@ pub struct RegionMap {
     // Track the current tick, used to enable not checking everything every tick
     // rate is dependent on the rate the caller calls region_manager.tick()
     tick: u64,
+    // Groups of neighboring regions, updated every tick
+    shards: Vec<RegionShard>,
 }
 impl RegionMap {
     pub fn new() -> Self { Self::default() }
@@ -207,8 +219,45 @@ impl RegionMap {
                 self.remove(key);
             }
         }
+
+        self.update_shards();
+    }
+
+    /// Splits the regions containing entities into groups of neighboring
+    /// regions
+    fn update_shards(&mut self) {
+        span!(_guard, "update_shards", "Region::update_shards");
+        self.shards.clear();
+        let mut visited = vec![false; self.regions.len()];
+        let mut stack = Vec::new();
+        for start in 0..self.regions.len() {
+            if visited[start] || self.regions[start].bitset.is_empty() {
+                continue;
+            }
+            visited[start] = true;
+            stack.push(start);
+            let mut shard = RegionShard::default();
+            while let Some(index) = stack.pop() {
+                let (key, region) = self.regions.get_index(index).unwrap();
+                shard.regions.push(*key);
+                shard.entities |= &region.bitset;
+                for offset in NEIGHBOR_OFFSETS {
+                    if let Some((neighbor, _, region)) = self.regions.get_full(&(key + offset))
+                        && !visited[neighbor]
+                        && !region.bitset.is_empty()
+                    {
+                        visited[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            self.shards.push(shard);
+        }
     }
 
+    /// Groups of neighboring regions containing entities, as of the last tick
+    pub fn shards(&self) -> &[RegionShard] { &self.shards }
+
     /// Must be called immediately after succesfully deleting an entity from the
     /// ecs (i.e. when deleting the entity did not generate a WrongGeneration
     /// error).
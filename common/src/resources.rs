@@ -134,8 +134,9 @@ pub struct PlayerPhysicsSettings {
 ///
 /// May be removed when we will discover better way
 /// to handle duels and murders
-#[derive(PartialEq, Eq, Copy, Clone, Debug, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default, Deserialize, Serialize)]
 pub enum BattleMode {
+    #[default]
     PvP,
     PvE,
 }
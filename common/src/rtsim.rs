@@ -3,13 +3,20 @@
 // `Agent`). When possible, this should be moved to the `rtsim`
 // module in `server`.
 
-use crate::{character::CharacterId, comp::dialogue::Subject, util::Dir};
+use crate::{
+    character::CharacterId,
+    comp::dialogue::{Directions, Subject},
+    states::emote::EmoteKind,
+    terrain::BiomeKind,
+    util::Dir,
+};
 use common_i18n::Content;
+use enum_map::EnumMap;
 use rand::{seq::IteratorRandom, Rng};
 use serde::{Deserialize, Serialize};
 use specs::Component;
-use std::collections::VecDeque;
-use strum::{EnumIter, IntoEnumIterator};
+use std::collections::{HashMap, VecDeque};
+use strum::{EnumIter, IntoEnumIterator, IntoStaticStr};
 use vek::*;
 
 slotmap::new_key_type! { pub struct NpcId; }
@@ -20,6 +27,24 @@ slotmap::new_key_type! { pub struct FactionId; }
 
 slotmap::new_key_type! { pub struct ReportId; }
 
+slotmap::new_key_type! { pub struct HerdId; }
+
+slotmap::new_key_type! { pub struct WreckId; }
+
+impl NpcId {
+    /// Id used by plugins, only valid while the server is running.
+    pub fn to_ffi(self) -> u64 { slotmap::Key::data(&self).as_ffi() }
+
+    pub fn from_ffi(id: u64) -> Self { slotmap::KeyData::from_ffi(id).into() }
+}
+
+impl SiteId {
+    /// Id used by plugins, only valid while the server is running.
+    pub fn to_ffi(self) -> u64 { slotmap::Key::data(&self).as_ffi() }
+
+    pub fn from_ffi(id: u64) -> Self { slotmap::KeyData::from_ffi(id).into() }
+}
+
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub struct RtSimEntity(pub NpcId);
 
@@ -50,6 +75,44 @@ impl From<CharacterId> for Actor {
     fn from(value: CharacterId) -> Self { Actor::Character(value) }
 }
 
+/// How a faction regards an actor, aggregated from the sentiments of its
+/// members.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ReputationTier {
+    Hostile,
+    #[default]
+    Neutral,
+    Friendly,
+    Honored,
+}
+
+impl ReputationTier {
+    /// Multiplier on the prices merchants of the faction charge.
+    pub fn price_factor(&self) -> f32 {
+        match self {
+            Self::Hostile => 1.1,
+            Self::Neutral => 1.0,
+            Self::Friendly => 0.95,
+            Self::Honored => 0.9,
+        }
+    }
+
+    /// Whether merchants of the faction are willing to sell their rare wares.
+    pub fn can_buy_rare_wares(&self) -> bool { *self >= Self::Friendly }
+
+    /// Whether guards of the faction look the other way when the actor
+    /// commits minor crimes, only turning on them for severe ones.
+    pub fn tolerated_by_guards(&self) -> bool { *self >= Self::Friendly }
+}
+
+/// The reputation of a character with a faction, as shown to the player.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FactionReputation {
+    /// Name of the faction's seat
+    pub name: String,
+    pub tier: ReputationTier,
+}
+
 #[derive(EnumIter, Clone, Copy)]
 pub enum PersonalityTrait {
     Open,
@@ -164,6 +227,9 @@ impl Personality {
         }
     }
 
+    /// How cooperative the NPC is, from `0.0` (hostile) to `1.0` (pushover).
+    pub fn agreeableness(&self) -> f32 { self.agreeableness as f32 / Self::MAX as f32 }
+
     pub fn chat_trait(&self, rng: &mut impl Rng) -> Option<PersonalityTrait> {
         PersonalityTrait::iter().filter(|t| self.is(*t)).choose(rng)
     }
@@ -227,6 +293,15 @@ pub struct RtSimController {
     pub actions: VecDeque<NpcAction>,
     pub personality: Personality,
     pub heading_to: Option<String>,
+    /// Sentiment the NPC feels toward the agent's current target, between
+    /// `-1.0` and `1.0`.
+    pub target_sentiment: f32,
+    /// Reputation of the agent's current target with the NPC's faction, if the
+    /// NPC belongs to one.
+    pub target_reputation: Option<ReputationTier>,
+    /// Whether the NPC's home site is celebrating a festival. Traders sell at a
+    /// discount during festivals.
+    pub festival: bool,
     // TODO: Maybe this should allow for looking at a specific entity target?
     pub look_dir: Option<Dir>,
 }
@@ -262,6 +337,12 @@ pub enum NpcAction {
     Say(Option<Actor>, Content),
     /// Attack the given target
     Attack(Actor),
+    /// Perform a short gesture, such as waving
+    Emote(EmoteKind),
+    /// Launch a firework into the sky, to celebrate a festival
+    LaunchFirework,
+    /// Show the given actor the way to a site they asked about
+    GiveDirections(Actor, Directions),
 }
 
 // Represents a message passed back to rtsim from an agent's brain
@@ -269,6 +350,12 @@ pub enum NpcAction {
 pub enum NpcInput {
     Report(ReportId),
     Interaction(Actor, Subject),
+    /// The actor haggled over the prices of a trade, and the NPC either gave in
+    /// or refused.
+    Haggled {
+        by: Actor,
+        accepted: bool,
+    },
 }
 
 // Note: the `serde(name = "...")` is to minimise the length of field
@@ -297,6 +384,89 @@ pub enum ChunkResource {
     Gem, // Amethyst, diamond, etc.
     #[serde(rename = "a")]
     Ore, // Iron, copper, etc.
+    #[serde(rename = "b")]
+    Tree, // Standing trees, felled for timber
+    #[serde(rename = "c")]
+    Game, // Wild animals that can be hunted
+}
+
+/// How worn down the paths through a chunk are by the traffic along them.
+/// Well travelled paths get upgraded, while forgotten ones grass over.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RoadKind {
+    Overgrown,
+    #[default]
+    Dirt,
+    Gravel,
+    Paved,
+}
+
+impl RoadKind {
+    /// Wear of a path that nobody has travelled along yet
+    pub const INITIAL_WEAR: f32 = 1.0;
+
+    pub fn from_wear(wear: f32) -> Self {
+        if wear < 0.5 {
+            Self::Overgrown
+        } else if wear < 2.0 {
+            Self::Dirt
+        } else if wear < 5.0 {
+            Self::Gravel
+        } else {
+            Self::Paved
+        }
+    }
+}
+
+/// The remains of an airship that came down in a chunk.
+#[derive(Copy, Clone, Debug)]
+pub struct ChunkWreck {
+    /// Where the airship came to rest.
+    pub wpos: Vec2<i32>,
+    /// The direction that the airship was travelling in when it came down.
+    pub dir: Vec2<f32>,
+    pub seed: u32,
+    /// How much of the wreck is yet to be salvaged, between 0 and 1.
+    pub remains: f32,
+}
+
+/// What rtsim knows about a chunk that affects how its terrain is generated.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct RtsimChunk {
+    pub resources: EnumMap<ChunkResource, f32>,
+    pub road: RoadKind,
+    pub wreck: Option<ChunkWreck>,
+}
+
+/// The rtsim state of a chunk and the chunks surrounding it, used during
+/// terrain generation. Trees and paths can straddle chunk borders, so how they
+/// get generated depends on the chunk they belong to rather than the chunk
+/// being generated.
+#[derive(Copy, Clone, Debug)]
+pub struct RtsimChunks {
+    chunk_pos: Vec2<i32>,
+    neighbours: [RtsimChunk; 9],
+}
+
+impl RtsimChunks {
+    pub fn new(chunk_pos: Vec2<i32>, mut get: impl FnMut(Vec2<i32>) -> RtsimChunk) -> Self {
+        Self {
+            chunk_pos,
+            neighbours: std::array::from_fn(|i| {
+                get(chunk_pos + Vec2::new(i as i32 % 3 - 1, i as i32 / 3 - 1))
+            }),
+        }
+    }
+
+    /// The state of the chunk being generated.
+    pub fn here(&self) -> &RtsimChunk { &self.neighbours[4] }
+
+    /// The state of the given chunk, if it borders the chunk being generated.
+    pub fn get(&self, chunk_pos: Vec2<i32>) -> Option<&RtsimChunk> {
+        let rpos = chunk_pos - self.chunk_pos + 1;
+        (rpos.x >= 0 && rpos.x < 3 && rpos.y >= 0 && rpos.y < 3)
+            .then(|| &self.neighbours[(rpos.y * 3 + rpos.x) as usize])
+    }
 }
 
 // Note: the `serde(name = "...")` is to minimise the length of field
@@ -315,7 +485,7 @@ pub enum Role {
 
 // Note: the `serde(name = "...")` is to minimise the length of field
 // identifiers for the sake of rtsim persistence
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, IntoStaticStr)]
 pub enum Profession {
     #[serde(rename = "0")]
     Farmer,
@@ -341,17 +511,122 @@ pub enum Profession {
     Herbalist,
     #[serde(rename = "11")]
     Captain,
+    #[serde(rename = "12")]
+    Banker,
+    #[serde(rename = "13")]
+    Trainer,
+    #[serde(rename = "14")]
+    Necromancer,
+    #[serde(rename = "15")]
+    Woodcutter,
+    #[serde(rename = "16")]
+    Miner,
+    #[serde(rename = "17")]
+    Stonecutter,
+    #[serde(rename = "18")]
+    Dockworker,
+    #[serde(rename = "19")]
+    Crier,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WorldSettings {
     pub start_time: f64,
+    #[serde(default)]
+    pub population: PopulationSettings,
 }
 
 impl Default for WorldSettings {
     fn default() -> Self {
         Self {
             start_time: 9.0 * 3600.0, // 9am
+            population: PopulationSettings::default(),
         }
     }
 }
+
+/// Knobs to scale the number of NPCs simulated by the server.
+///
+/// Changes to the rtsim densities and the cap only take effect when rtsim data
+/// is generated, i.e: on a new world or after a purge.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PopulationSettings {
+    /// Multiplier for the number of civilians living in each town.
+    pub civilian_density: f32,
+    /// Fraction of the wildlife spawned alongside terrain chunks that actually
+    /// gets spawned, per biome. Biomes that are not listed use `1.0`.
+    pub wildlife_density: HashMap<BiomeKind, f32>,
+    /// Multiplier for the number of monsters roaming the world.
+    pub monster_density: f32,
+    /// Absolute limit on the number of rtsim NPCs that get generated.
+    pub max_npcs: Option<usize>,
+}
+
+impl Default for PopulationSettings {
+    fn default() -> Self {
+        Self {
+            civilian_density: 1.0,
+            wildlife_density: HashMap::new(),
+            monster_density: 1.0,
+            max_npcs: None,
+        }
+    }
+}
+
+impl PopulationSettings {
+    pub fn wildlife_density(&self, biome: BiomeKind) -> f32 {
+        self.wildlife_density
+            .get(&biome)
+            .copied()
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0)
+    }
+}
+
+/// Version of the rtsim hooks available to plugins. Incremented whenever
+/// [`RtsimHookEvent`] or [`RtsimHookAction`] change in an incompatible way;
+/// plugins built against a different version don't receive any events.
+pub const RTSIM_HOOK_API_VERSION: u32 = 1;
+
+/// Rtsim events that are forwarded to plugins.
+#[derive(Clone, Debug)]
+pub enum RtsimHookEvent {
+    NpcDeath {
+        npc: NpcId,
+        killer: Option<Actor>,
+        wpos: Option<Vec3<f32>>,
+    },
+    /// A resident of a site was killed by someone that doesn't live there.
+    SiteRaided {
+        site: SiteId,
+        victim: NpcId,
+        raider: Option<Actor>,
+    },
+    DialogueStarted {
+        npc: NpcId,
+        target: Actor,
+    },
+}
+
+/// Changes to the rtsim state requested by plugins.
+#[derive(Clone, Debug)]
+pub enum RtsimHookAction {
+    SpawnNpc {
+        wpos: Vec3<f32>,
+        /// RON representation of a [`crate::comp::Body`].
+        body: String,
+        home: Option<SiteId>,
+    },
+    /// Tell the NPCs near `wpos` about the death of `actor`.
+    ReportDeath {
+        actor: Actor,
+        killer: Option<Actor>,
+        wpos: Vec3<f32>,
+    },
+    ModifySentiment {
+        npc: NpcId,
+        target: Actor,
+        change: f32,
+    },
+}
@@ -259,16 +259,32 @@ impl InternalSlowJobPool {
     }
 
     pub fn spawn<F>(&mut self, name: &str, f: F) -> SlowJob
+    where
+        F: FnOnce() + Send + Sync + 'static,
+    {
+        self.enqueue(name, false, f)
+    }
+
+    pub fn spawn_prioritized<F>(&mut self, name: &str, f: F) -> SlowJob
+    where
+        F: FnOnce() + Send + Sync + 'static,
+    {
+        self.enqueue(name, true, f)
+    }
+
+    fn enqueue<F>(&mut self, name: &str, prioritized: bool, f: F) -> SlowJob
     where
         F: FnOnce() + Send + Sync + 'static,
     {
         let id = self.next_id;
         self.next_id += 1;
         let queue = Queue::new(name, id, self.internal.as_ref().expect("internal empty"), f);
-        self.queue
-            .entry(name.to_string())
-            .or_default()
-            .push_back(queue);
+        let deque = self.queue.entry(name.to_string()).or_default();
+        if prioritized {
+            deque.push_front(queue);
+        } else {
+            deque.push_back(queue);
+        }
         debug_assert!(
             self.configs.contains_key(name),
             "Can't spawn unconfigured task!"
@@ -386,6 +402,18 @@ impl SlowJobPool {
             .spawn(name, f)
     }
 
+    /// spawn a new slow job on a certain NAME, ahead of all jobs of that NAME
+    /// that are still queued
+    pub fn spawn_prioritized<F>(&self, name: &str, f: F) -> SlowJob
+    where
+        F: FnOnce() + Send + Sync + 'static,
+    {
+        self.internal
+            .lock()
+            .expect("lock poisoned while spawn_prioritized")
+            .spawn_prioritized(name, f)
+    }
+
     pub fn cancel(&self, job: SlowJob) -> Result<(), SlowJob> {
         let mut lock = self.internal.lock().expect("lock poisoned while cancel");
         if let Some(m) = lock.queue.get_mut(&job.name) {
@@ -687,6 +715,23 @@ mod tests {
         pool.try_run("FOO", func).unwrap_err();
     }
 
+    #[test]
+    fn prioritized_jobs_run_first() {
+        let pool = mock_pool(1, 1, 0, 1, 0, 0);
+        let func = || loop {
+            std::thread::sleep(Duration::from_secs(1))
+        };
+        pool.spawn("FOO", func);
+        let queued = pool.spawn("FOO", func);
+        let prioritized = pool.spawn_prioritized("FOO", func);
+        let internal = pool.internal.lock().unwrap();
+        let ids = internal.queue["FOO"]
+            .iter()
+            .map(|queue| queue.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![prioritized.id, queued.id]);
+    }
+
     #[test]
     fn actually_runs_1() {
         let pool = mock_pool(4, 4, 0, 0, 0, 1);
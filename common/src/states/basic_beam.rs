@@ -120,6 +120,7 @@ impl CharacterBehavior for Data {
                             Damage {
                                 source: DamageSource::Energy,
                                 kind: DamageKind::Energy,
+                                element: self.static_data.specifier.element(),
                                 value: self.static_data.damage,
                             },
                             Some(GroupTarget::OutOfGroup),
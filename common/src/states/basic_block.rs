@@ -14,6 +14,10 @@ use std::time::Duration;
 pub struct ParryWindow {
     pub buildup: bool,
     pub recover: bool,
+    /// How many milliseconds of a held block still count as a parry after
+    /// the buildup
+    #[serde(default)]
+    pub action_ms: u64,
 }
 
 /// Separated out to condense update portions of character state
@@ -36,6 +40,8 @@ pub struct StaticData {
     pub energy_cost: f32,
     /// Energy recovered upon successful parry
     pub energy_regen: f32,
+    /// Energy consumed per second while the block is held
+    pub energy_drain: f32,
     /// Whether block can be held
     pub can_hold: bool,
     /// What kinds of attacks the block applies to
@@ -86,6 +92,10 @@ impl CharacterBehavior for Data {
             StageSection::Action => {
                 if self.static_data.can_hold
                     && input_is_pressed(data, self.static_data.ability_info.input)
+                    && update
+                        .energy
+                        .try_change_by(-self.static_data.energy_drain * data.dt.0)
+                        .is_ok()
                 {
                     // Block
                     update.character = CharacterState::BasicBlock(Data {
@@ -137,6 +147,9 @@ impl Data {
         let could_block = self.static_data.blocked_attacks.applies(attack);
         let timed = match self.stage_section {
             StageSection::Buildup => self.static_data.parry_window.buildup,
+            StageSection::Action => {
+                self.timer < Duration::from_millis(self.static_data.parry_window.action_ms)
+            },
             StageSection::Recover => self.static_data.parry_window.recover,
             _ => false,
         };
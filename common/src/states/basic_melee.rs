@@ -4,7 +4,7 @@ use crate::{
         character_state::OutputEvents, tool::ToolKind, CharacterState, MeleeConstructor,
         StateUpdate,
     },
-    event::LocalEvent,
+    event::{DigGroundEvent, LocalEvent},
     outcome::Outcome,
     states::{
         behavior::{CharacterBehavior, JoinData},
@@ -110,9 +110,14 @@ impl CharacterBehavior for Data {
                     );
                     // Send local event used for frontend shenanigans
                     if self.static_data.ability_info.tool == Some(ToolKind::Shovel) {
+                        let dig_pos = data.pos.0 + *data.ori.look_dir() * (data.body.max_radius());
                         output_events.emit_local(LocalEvent::CreateOutcome(Outcome::GroundDig {
-                            pos: data.pos.0 + *data.ori.look_dir() * (data.body.max_radius()),
+                            pos: dig_pos,
                         }));
+                        output_events.emit_server(DigGroundEvent {
+                            entity: data.entity,
+                            pos: dig_pos,
+                        });
                     }
                 } else if self.timer < self.static_data.swing_duration {
                     // Swings
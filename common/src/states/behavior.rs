@@ -11,6 +11,7 @@ use crate::{
     link::Is,
     mounting::{Rider, VolumeRider},
     resources::{DeltaTime, Time},
+    states::emote::EmoteKind,
     terrain::TerrainGrid,
     uid::{IdMaps, Uid},
 };
@@ -50,6 +51,14 @@ pub trait CharacterBehavior {
     fn dance(&self, data: &JoinData, _output_events: &mut OutputEvents) -> StateUpdate {
         StateUpdate::from(data)
     }
+    fn emote(
+        &self,
+        data: &JoinData,
+        _output_events: &mut OutputEvents,
+        _kind: EmoteKind,
+    ) -> StateUpdate {
+        StateUpdate::from(data)
+    }
     fn pet(
         &self,
         data: &JoinData,
@@ -58,6 +67,14 @@ pub trait CharacterBehavior {
     ) -> StateUpdate {
         StateUpdate::from(data)
     }
+    fn revive(
+        &self,
+        data: &JoinData,
+        _output_events: &mut OutputEvents,
+        _target_uid: Uid,
+    ) -> StateUpdate {
+        StateUpdate::from(data)
+    }
     fn sneak(&self, data: &JoinData, _output_events: &mut OutputEvents) -> StateUpdate {
         StateUpdate::from(data)
     }
@@ -104,7 +121,9 @@ pub trait CharacterBehavior {
             ControlAction::Unwield => self.unwield(data, output_events),
             ControlAction::Sit => self.sit(data, output_events),
             ControlAction::Dance => self.dance(data, output_events),
+            ControlAction::Emote(kind) => self.emote(data, output_events, kind),
             ControlAction::Pet { target_uid } => self.pet(data, output_events, target_uid),
+            ControlAction::Revive { target_uid } => self.revive(data, output_events, target_uid),
             ControlAction::Sneak => {
                 if data.mount_data.is_none() && data.volume_mount_data.is_none() {
                     self.sneak(data, output_events)
@@ -161,6 +180,8 @@ pub struct JoinData<'a> {
     pub id_maps: &'a Read<'a, IdMaps>,
     pub alignments: &'a ReadStorage<'a, Alignment>,
     pub prev_phys_caches: &'a ReadStorage<'a, PreviousPhysCache>,
+    /// Whether the entity is in creative mode, see [`crate::comp::CanBuild`]
+    pub creative: bool,
 }
 
 pub struct JoinStruct<'a> {
@@ -194,6 +215,7 @@ pub struct JoinStruct<'a> {
     pub id_maps: &'a Read<'a, IdMaps>,
     pub alignments: &'a ReadStorage<'a, Alignment>,
     pub prev_phys_caches: &'a ReadStorage<'a, PreviousPhysCache>,
+    pub creative: bool,
 }
 
 impl<'a> JoinData<'a> {
@@ -241,6 +263,7 @@ impl<'a> JoinData<'a> {
             id_maps: j.id_maps,
             alignments: j.alignments,
             prev_phys_caches: j.prev_phys_caches,
+            creative: j.creative,
         }
     }
 }
@@ -1,6 +1,9 @@
 use crate::{
     combat,
-    combat::{Attack, AttackDamage, Damage, DamageKind::Crushing, DamageSource, GroupTarget},
+    combat::{
+        Attack, AttackDamage, Damage, DamageElement, DamageKind::Crushing, DamageSource,
+        GroupTarget,
+    },
     comp::{
         character_state::OutputEvents, item::Reagent, melee::CustomCombo, tool::Stats,
         CharacterState, MeleeConstructor, StateUpdate,
@@ -192,6 +195,7 @@ impl CharacterBehavior for Data {
                             Damage {
                                 source: DamageSource::Explosion,
                                 kind: Crushing,
+                                element: DamageElement::Physical,
                                 value: 10.0,
                             },
                             Some(GroupTarget::OutOfGroup),
@@ -1,4 +1,4 @@
-use super::utils::*;
+use super::{emote::EmoteKind, utils::*};
 use crate::{
     comp::{character_state::OutputEvents, CharacterState, InventoryAction, StateUpdate},
     states::{
@@ -51,6 +51,12 @@ impl CharacterBehavior for Data {
         update
     }
 
+    fn emote(&self, data: &JoinData, _: &mut OutputEvents, kind: EmoteKind) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_emote(data, &mut update, kind);
+        update
+    }
+
     fn pet(&self, data: &JoinData, _: &mut OutputEvents, target_uid: Uid) -> StateUpdate {
         let mut update = StateUpdate::from(data);
         attempt_pet(data, &mut update, target_uid);
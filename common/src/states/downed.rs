@@ -0,0 +1,70 @@
+use super::utils::*;
+use crate::{
+    comp::{character_state::OutputEvents, CharacterState, StateUpdate},
+    states::behavior::{CharacterBehavior, JoinData},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How long a downed player can wait for a revive before they die
+pub const BLEED_OUT_DURATION: Duration = Duration::from_secs(45);
+/// Fraction of their maximum health a downed player is left with, low enough
+/// that any execute finishes them off
+pub const DOWNED_HEALTH_FRACTION: f32 = 0.01;
+/// How fast a downed player can crawl compared to walking
+const CRAWL_EFFICIENCY: f32 = 0.15;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// Time after which the player bleeds out and dies
+    pub bleed_out_duration: Duration,
+}
+
+/// A player in group play that took lethal damage and is waiting for one of
+/// their teammates to revive them. Only melee attacks can hit a downed player,
+/// which execute them.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    pub static_data: StaticData,
+    /// Time spent downed
+    pub timer: Duration,
+}
+
+impl Default for Data {
+    fn default() -> Self {
+        Self {
+            static_data: StaticData {
+                bleed_out_duration: BLEED_OUT_DURATION,
+            },
+            timer: Duration::default(),
+        }
+    }
+}
+
+impl Data {
+    /// Time left before the player bleeds out
+    pub fn remaining(&self) -> Duration {
+        self.static_data
+            .bleed_out_duration
+            .saturating_sub(self.timer)
+    }
+
+    pub fn has_bled_out(&self) -> bool { self.timer >= self.static_data.bleed_out_duration }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData, output_events: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        leave_stance(data, output_events);
+        handle_orientation(data, &mut update, 0.5, None);
+        handle_move(data, &mut update, CRAWL_EFFICIENCY);
+
+        // The server kills the player once they bled out, so just keep counting
+        if let CharacterState::Downed(c) = &mut update.character {
+            c.timer = self.timer + Duration::from_secs_f32(data.dt.0);
+        }
+
+        update
+    }
+}
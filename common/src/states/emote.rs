@@ -0,0 +1,129 @@
+use super::utils::*;
+use crate::{
+    comp::{character_state::OutputEvents, CharacterState, InventoryAction, StateUpdate},
+    states::{
+        behavior::{CharacterBehavior, JoinData},
+        idle,
+    },
+    uid::Uid,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use strum::{EnumIter, IntoStaticStr};
+
+/// A short gesture performed by humanoids. Unlike dancing, emotes end on their
+/// own once their animation has played.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, IntoStaticStr,
+)]
+pub enum EmoteKind {
+    Wave,
+    Point,
+    Bow,
+    Laugh,
+}
+
+impl EmoteKind {
+    /// How long the emote lasts before the character returns to idle.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f32(match self {
+            EmoteKind::Wave => 2.0,
+            EmoteKind::Point => 1.5,
+            EmoteKind::Bow => 2.0,
+            EmoteKind::Laugh => 2.5,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    pub kind: EmoteKind,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    pub static_data: StaticData,
+    /// Time spent performing the emote
+    pub timer: Duration,
+}
+
+impl Data {
+    pub fn new(kind: EmoteKind) -> Self {
+        Self {
+            static_data: StaticData { kind },
+            timer: Duration::default(),
+        }
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData, output_events: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        leave_stance(data, output_events);
+        handle_wield(data, &mut update);
+        handle_jump(data, output_events, &mut update, 1.0);
+
+        if self.timer < self.static_data.kind.duration() {
+            if let CharacterState::Emote(c) = &mut update.character {
+                c.timer = self.timer + Duration::from_secs_f32(data.dt.0);
+            }
+        } else {
+            update.character = CharacterState::Idle(idle::Data::default());
+        }
+
+        // Try to Fall/Stand up/Move
+        if data.physics.on_ground.is_none() || data.inputs.move_dir.magnitude_squared() > 0.0 {
+            update.character = CharacterState::Idle(idle::Data::default());
+        }
+
+        update
+    }
+
+    fn manipulate_loadout(
+        &self,
+        data: &JoinData,
+        output_events: &mut OutputEvents,
+        inv_action: InventoryAction,
+    ) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        handle_manipulate_loadout(data, output_events, &mut update, inv_action);
+        update
+    }
+
+    fn wield(&self, data: &JoinData, _: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_wield(data, &mut update);
+        update
+    }
+
+    fn sit(&self, data: &JoinData, _: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_sit(data, &mut update);
+        update
+    }
+
+    fn dance(&self, data: &JoinData, _: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_dance(data, &mut update);
+        update
+    }
+
+    fn emote(&self, data: &JoinData, _: &mut OutputEvents, kind: EmoteKind) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_emote(data, &mut update, kind);
+        update
+    }
+
+    fn pet(&self, data: &JoinData, _: &mut OutputEvents, target_uid: Uid) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_pet(data, &mut update, target_uid);
+        update
+    }
+
+    fn stand(&self, data: &JoinData, _: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        update.character = CharacterState::Idle(idle::Data::default());
+        update
+    }
+}
@@ -1,4 +1,4 @@
-use super::utils::*;
+use super::{emote::EmoteKind, utils::*};
 use crate::{
     comp::{
         character_state::OutputEvents, controller::InputKind, slot::EquipSlot, CharacterState,
@@ -136,6 +136,12 @@ impl CharacterBehavior for Data {
         update
     }
 
+    fn emote(&self, data: &JoinData, _: &mut OutputEvents, kind: EmoteKind) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_emote(data, &mut update, kind);
+        update
+    }
+
     fn sneak(&self, data: &JoinData, _: &mut OutputEvents) -> StateUpdate {
         let mut update = StateUpdate::from(data);
         attempt_sneak(data, &mut update);
@@ -1,4 +1,4 @@
-use super::utils::*;
+use super::{emote::EmoteKind, utils::*};
 use crate::{
     comp::{
         character_state::OutputEvents, controller::InputKind, inventory::item::armor::Friction,
@@ -97,12 +97,24 @@ impl CharacterBehavior for Data {
         update
     }
 
+    fn emote(&self, data: &JoinData, _: &mut OutputEvents, kind: EmoteKind) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_emote(data, &mut update, kind);
+        update
+    }
+
     fn pet(&self, data: &JoinData, _: &mut OutputEvents, target_uid: Uid) -> StateUpdate {
         let mut update = StateUpdate::from(data);
         attempt_pet(data, &mut update, target_uid);
         update
     }
 
+    fn revive(&self, data: &JoinData, _: &mut OutputEvents, target_uid: Uid) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_revive(data, &mut update, target_uid);
+        update
+    }
+
     fn sneak(&self, data: &JoinData, _: &mut OutputEvents) -> StateUpdate {
         let mut update = StateUpdate::from(data);
         update.character = CharacterState::Idle(Data {
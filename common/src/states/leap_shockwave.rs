@@ -157,6 +157,7 @@ impl CharacterBehavior for Data {
                         Damage {
                             source: DamageSource::Shockwave,
                             kind: self.static_data.damage_kind,
+                            element: self.static_data.specifier.element(),
                             value: self.static_data.damage,
                         },
                         Some(GroupTarget::OutOfGroup),
@@ -194,6 +195,7 @@ impl CharacterBehavior for Data {
                                 Damage {
                                     source: DamageSource::Explosion,
                                     kind: self.static_data.damage_kind,
+                                    element: self.static_data.specifier.element(),
                                     value: self.static_data.damage / 2.,
                                 },
                                 Some(GroupTarget::OutOfGroup),
@@ -14,6 +14,8 @@ pub mod combo_melee2;
 pub mod dance;
 pub mod dash_melee;
 pub mod dive_melee;
+pub mod downed;
+pub mod emote;
 pub mod equipping;
 pub mod finisher_melee;
 pub mod glide;
@@ -25,6 +27,7 @@ pub mod music;
 pub mod pet;
 pub mod rapid_melee;
 pub mod repeater_ranged;
+pub mod revive;
 pub mod riposte_melee;
 pub mod roll;
 pub mod self_buff;
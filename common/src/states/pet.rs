@@ -1,4 +1,4 @@
-use super::utils::*;
+use super::{emote::EmoteKind, utils::*};
 use crate::{
     comp::{character_state::OutputEvents, CharacterState, InventoryAction, StateUpdate},
     states::{
@@ -84,6 +84,12 @@ impl CharacterBehavior for Data {
         update
     }
 
+    fn emote(&self, data: &JoinData, _: &mut OutputEvents, kind: EmoteKind) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        attempt_emote(data, &mut update, kind);
+        update
+    }
+
     fn sit(&self, data: &JoinData, _: &mut OutputEvents) -> StateUpdate {
         let mut update = StateUpdate::from(data);
         attempt_sit(data, &mut update);
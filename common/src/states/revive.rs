@@ -0,0 +1,95 @@
+use super::utils::*;
+use crate::{
+    comp::{character_state::OutputEvents, CharacterState, StateUpdate},
+    event::ReviveEvent,
+    states::{
+        behavior::{CharacterBehavior, JoinData},
+        idle,
+    },
+    uid::Uid,
+    util::Dir,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use vek::Vec3;
+
+/// How long a revive has to be channeled
+pub const REVIVE_DURATION: Duration = Duration::from_secs(5);
+/// Fraction of their maximum health a revived player gets back
+pub const REVIVE_HEALTH_FRACTION: f32 = 0.3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    pub target_uid: Uid,
+    pub revive_duration: Duration,
+}
+
+/// Channels a revive on a downed teammate, the revive is interrupted if the
+/// player moves or leaves the ground
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    pub static_data: StaticData,
+    /// Time spent channeling the revive
+    pub timer: Duration,
+}
+
+impl Data {
+    pub fn new(target_uid: Uid) -> Self {
+        Self {
+            static_data: StaticData {
+                target_uid,
+                revive_duration: REVIVE_DURATION,
+            },
+            timer: Duration::default(),
+        }
+    }
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData, output_events: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        let target_pos = data
+            .id_maps
+            .uid_entity(self.static_data.target_uid)
+            .and_then(|target_entity| data.prev_phys_caches.get(target_entity))
+            .and_then(|prev_phys| prev_phys.pos);
+
+        // Face target if they have a position.
+        if let Some(target_pos) = target_pos {
+            let ori_dir = Dir::from_unnormalized(Vec3::from((target_pos.0 - data.pos.0).xy()));
+            handle_orientation(data, &mut update, 1.0, ori_dir);
+        }
+
+        leave_stance(data, output_events);
+
+        if !target_pos.map_or(false, |target_pos| {
+            can_perform_revive(*data.pos, target_pos)
+        }) {
+            update.character = CharacterState::Idle(idle::Data::default());
+        } else if self.timer < self.static_data.revive_duration {
+            if let CharacterState::Revive(c) = &mut update.character {
+                c.timer = self.timer + Duration::from_secs_f32(data.dt.0);
+            }
+        } else {
+            output_events.emit_server(ReviveEvent {
+                entity: data.entity,
+                target: self.static_data.target_uid,
+            });
+            update.character = CharacterState::Idle(idle::Data::default());
+        }
+
+        // Moving or falling interrupts the revive
+        if data.physics.on_ground.is_none() || data.inputs.move_dir.magnitude_squared() > 0.0 {
+            update.character = CharacterState::Idle(idle::Data::default());
+        }
+
+        update
+    }
+
+    fn stand(&self, data: &JoinData, _: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+        update.character = CharacterState::Idle(idle::Data::default());
+        update
+    }
+}
@@ -221,6 +221,7 @@ impl Data {
             Damage {
                 source: DamageSource::Shockwave,
                 kind: self.static_data.damage_kind,
+                element: self.static_data.specifier.element(),
                 value: self.static_data.damage,
             },
             Some(GroupTarget::OutOfGroup),
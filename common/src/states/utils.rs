@@ -13,7 +13,7 @@ use crate::{
             tool::{self, AbilityContext},
             Hands, ItemKind, ToolKind,
         },
-        quadruped_low, quadruped_medium, quadruped_small, ship,
+        quadruped_low, quadruped_medium, quadruped_small, serpent, ship,
         skills::{Skill, SwimSkill, SKILL_MODIFIERS},
         theropod, Alignment, Body, CharacterState, Density, InputAttr, InputKind, InventoryAction,
         Melee, Pos, StateUpdate,
@@ -40,6 +40,13 @@ use strum::Display;
 use vek::*;
 
 pub const MOVEMENT_THRESHOLD_VEL: f32 = 3.0;
+/// Acceleration of players flying in creative mode
+const CREATIVE_FLY_ACCEL: f32 = 30.0;
+/// Fuel used per second by a balloon's burner at full blast
+const BURNER_FUEL_RATE: f32 = 1.0;
+/// How much denser than the surrounding air the air in a balloon's envelope
+/// gets as it cools without the burner lit, so that the balloon slowly sinks
+const COOLED_ENVELOPE_DENSITY: f32 = 1.1;
 
 impl Body {
     pub fn base_accel(&self) -> f32 {
@@ -186,6 +193,10 @@ impl Body {
                 arthropod::Species::Emberfly => 75.0,
             },
             Body::Crustacean(_) => 80.0,
+            Body::Serpent(body) => match body.species {
+                serpent::Species::Viper => 90.0,
+                serpent::Species::Naga => 100.0,
+            },
         }
     }
 
@@ -253,6 +264,10 @@ impl Body {
             Body::Ship(_) => 6.0 / self.dimensions().y,
             Body::Arthropod(_) => 3.5,
             Body::Crustacean(_) => 3.5,
+            Body::Serpent(body) => match body.species {
+                serpent::Species::Viper => 3.0,
+                serpent::Species::Naga => 2.2,
+            },
         }
     }
 
@@ -298,6 +313,7 @@ impl Body {
                 Body::QuadrupedSmall(_) => 1500.0 * self.mass().0,
                 Body::Arthropod(_) => 500.0 * self.mass().0,
                 Body::Crustacean(_) => 400.0 * self.mass().0,
+                Body::Serpent(_) => 1200.0 * self.mass().0,
             } * front_profile,
         )
     }
@@ -311,6 +327,7 @@ impl Body {
             },
             Body::BirdLarge(_) => Some(GRAVITY * self.mass().0 * 0.5),
             Body::Dragon(_) => Some(200_000.0),
+            Body::Ship(ship) if ship.has_burner() => Some(3_000.0),
             Body::Ship(ship) if ship.can_fly() => Some(300_000.0),
             _ => None,
         }
@@ -413,7 +430,7 @@ pub fn handle_move(data: &JoinData<'_>, update: &mut StateUpdate, efficiency: f3
     if input_is_pressed(data, InputKind::Fly)
         && submersion.map_or(true, |sub| sub < 1.0)
         && (data.physics.on_ground.is_none() || data.body.jump_impulse().is_none())
-        && data.body.fly_thrust().is_some()
+        && (data.body.fly_thrust().is_some() || data.creative)
     {
         fly_move(data, update, efficiency);
     } else if let Some(submersion) = (data.physics.in_liquid().is_some()
@@ -768,6 +785,7 @@ pub fn fly_move(data: &JoinData<'_>, update: &mut StateUpdate, efficiency: f32)
     if let Some(force) = data
         .body
         .fly_thrust()
+        .or_else(|| data.creative.then_some(CREATIVE_FLY_ACCEL * data.mass.0))
         .or_else(|| glider.is_some().then_some(0.0))
     {
         let thrust = efficiency * force;
@@ -782,6 +800,28 @@ pub fn fly_move(data: &JoinData<'_>, update: &mut StateUpdate, efficiency: f32)
                 let anti_grav = GRAVITY * (1.0 + data.inputs.move_z.min(0.0));
                 update.vel.0.z += data.dt.0 * (anti_grav + accel * data.inputs.move_z.max(0.0));
             },
+            // Balloons climb while the burner heats the air in their envelope, using up
+            // fuel, and sink as it cools again
+            Body::Ship(ship) if ship.has_burner() => {
+                let def_density = ship.density().0;
+                let change = if data.inputs.move_z > f32::EPSILON {
+                    if data.energy.current() > 0.0 {
+                        update
+                            .energy
+                            .change_by(-BURNER_FUEL_RATE * data.inputs.move_z * data.dt.0);
+                        -data.inputs.move_z
+                    } else {
+                        0.0
+                    }
+                } else if data.inputs.move_z < -f32::EPSILON {
+                    // Venting hot air to descend quickly
+                    -data.inputs.move_z
+                } else {
+                    (def_density * COOLED_ENVELOPE_DENSITY - data.density.0).clamp(-1.0, 1.0)
+                };
+                update.density.0 = (update.density.0 + data.dt.0 * 0.5 * change)
+                    .clamp(def_density * 0.5, def_density * 1.5);
+            },
             // floaty floaty
             Body::Ship(ship) if ship.can_fly() => {
                 let regulate_density = |min: f32, max: f32, def: f32, rate: f32| -> Density {
@@ -803,6 +843,10 @@ pub fn fly_move(data: &JoinData<'_>, update: &mut StateUpdate, efficiency: f32)
                         regulate_density(def_density * 0.5, def_density * 1.5, def_density, 0.5).0;
                 };
             },
+            // Creative flight hovers in place unless moving up or down
+            _ if data.creative => {
+                update.vel.0.z += data.dt.0 * (GRAVITY + accel * data.inputs.move_z);
+            },
             // oopsie woopsie
             // TODO: refactor to make this state impossible
             _ => {},
@@ -887,6 +931,13 @@ pub fn attempt_dance(data: &JoinData<'_>, update: &mut StateUpdate) {
     }
 }
 
+/// Checks that player can perform an emote and updates `CharacterState` if so
+pub fn attempt_emote(data: &JoinData<'_>, update: &mut StateUpdate, kind: emote::EmoteKind) {
+    if data.physics.on_ground.is_some() && data.body.is_humanoid() {
+        update.character = CharacterState::Emote(emote::Data::new(kind));
+    }
+}
+
 pub fn can_perform_pet(position: Pos, target_position: Pos, target_alignment: Alignment) -> bool {
     let within_distance = position.0.distance_squared(target_position.0) <= MAX_MOUNT_RANGE.powi(2);
     let valid_alignment = matches!(target_alignment, Alignment::Owned(_) | Alignment::Tame);
@@ -915,6 +966,27 @@ pub fn attempt_pet(data: &JoinData<'_>, update: &mut StateUpdate, target_uid: Ui
     }
 }
 
+pub fn can_perform_revive(position: Pos, target_position: Pos) -> bool {
+    position.0.distance_squared(target_position.0) <= MAX_MOUNT_RANGE.powi(2)
+}
+
+/// Starts channeling a revive on a downed teammate, whether the target is
+/// actually downed is checked by the server once the channel completes
+pub fn attempt_revive(data: &JoinData<'_>, update: &mut StateUpdate, target_uid: Uid) {
+    let can_revive = data
+        .id_maps
+        .uid_entity(target_uid)
+        .and_then(|target_entity| data.prev_phys_caches.get(target_entity))
+        .and_then(|prev_phys| prev_phys.pos)
+        .map_or(false, |target_position| {
+            can_perform_revive(*data.pos, target_position)
+        });
+
+    if can_revive && data.physics.on_ground.is_some() && data.body.is_humanoid() {
+        update.character = CharacterState::Revive(revive::Data::new(target_uid));
+    }
+}
+
 pub fn attempt_talk(data: &JoinData<'_>, update: &mut StateUpdate) {
     if data.physics.on_ground.is_some() {
         update.character = CharacterState::Talk;
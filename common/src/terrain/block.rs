@@ -398,7 +398,7 @@ impl Block {
                 | SpriteKind::CliffDecorBlock
                 | SpriteKind::Orb
                 | SpriteKind::Candle => 16,
-                SpriteKind::DiamondLight => 30,
+                SpriteKind::DiamondLight | SpriteKind::LighthouseLamp => 30,
                 SpriteKind::Velorite
                 | SpriteKind::VeloriteFrag
                 | SpriteKind::GrassBlueShort
@@ -520,6 +520,8 @@ impl Block {
                 | SpriteKind::RepairBench
                 | SpriteKind::TanningRack
                 | SpriteKind::Chest
+                | SpriteKind::GuildChest
+                | SpriteKind::GuildBoard
                 | SpriteKind::DungeonChest0
                 | SpriteKind::DungeonChest1
                 | SpriteKind::DungeonChest2
@@ -586,6 +588,18 @@ impl Block {
             .map_or(false, |sprite| sprite.is_controller())
     }
 
+    /// Whether fire can catch on this block, either because it is made of
+    /// plant matter or because it holds a plant sprite
+    #[inline]
+    pub fn is_flammable(&self) -> bool {
+        matches!(
+            self.kind(),
+            BlockKind::Grass | BlockKind::Wood | BlockKind::Leaves | BlockKind::ArtLeaves
+        ) || self.get_sprite().map_or(false, |sprite| {
+            matches!(sprite.category(), sprite::Category::Plant)
+        })
+    }
+
     #[inline]
     pub fn is_bonkable(&self) -> bool {
         match self.get_sprite() {
@@ -123,6 +123,13 @@ use vek::*;
 ///   experience.
 pub const MAX_WORLD_BLOCKS_LG: Vec2<u32> = Vec2 { x: 19, y: 19 };
 
+/// Version of world generation.
+///
+/// This should be incremented whenever a change to world generation causes the
+/// same seed to produce a different world, so that clients know that any data
+/// they cached about a server's world is outdated.
+pub const WORLDGEN_VERSION: u32 = 1;
+
 /// Base two logarithm of a world size, in chunks, per dimension
 /// (each dimension must be a power of 2, so the logarithm is exact).
 ///
@@ -103,6 +103,7 @@ sprites! {
         WardrobeSingleMesa = 0x1B,
         WardrobeDoubleMesa = 0x1C,
         CupboardMesa       = 0x1D,
+        GuildChest         = 0x1E,
         // Crafting
         CraftingBench    = 0x20,
         Forge            = 0x21,
@@ -149,6 +150,8 @@ sprites! {
         BedrollPirate = 0x63,
         Sign          = 0x64,
         Helm          = 0x65,
+        NoticeBoard   = 0x66,
+        GuildBoard    = 0x67,
         // Misc
         Scarecrow      = 0x70,
         FountainArabic = 0x71,
@@ -383,6 +386,7 @@ sprites! {
         SeashellLantern = 3,
         FireBowlGround  = 4,
         MesaLantern     = 5,
+        LighthouseLamp  = 6,
     },
 }
 
@@ -430,6 +434,7 @@ impl SpriteKind {
             SpriteKind::Pumpkin => 0.81,
             SpriteKind::Cabbage => 0.45,
             SpriteKind::Chest => 1.09,
+            SpriteKind::GuildChest => 1.09,
             SpriteKind::CommonLockedChest => 1.09,
             SpriteKind::DungeonChest0 => 1.09,
             SpriteKind::DungeonChest1 => 1.09,
@@ -571,7 +576,7 @@ impl SpriteKind {
             SpriteKind::MagicalBarrier => 3.0,
             SpriteKind::MagicalSeal => 1.0,
             SpriteKind::Helm => 1.7,
-            SpriteKind::Sign => 17.0 / 11.0,
+            SpriteKind::Sign | SpriteKind::NoticeBoard | SpriteKind::GuildBoard => 17.0 / 11.0,
             SpriteKind::SmithingTable => 13.0 / 11.0,
             SpriteKind::Forge0 => 17.0 / 11.0,
             SpriteKind::GearWheel0 => 3.0 / 11.0,
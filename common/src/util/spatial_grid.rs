@@ -49,6 +49,23 @@ impl SpatialGrid {
         }
     }
 
+    /// Moves the entities of `other`, which must use the same cell sizes, into
+    /// this grid
+    pub fn merge(mut self, mut other: Self) -> Self {
+        // Move the entities of the smaller grid
+        if self.grid.len() + self.large_grid.len() < other.grid.len() + other.large_grid.len() {
+            std::mem::swap(&mut self, &mut other);
+        }
+        for (cell, entities) in other.grid {
+            self.grid.entry(cell).or_default().extend(entities);
+        }
+        for (cell, entities) in other.large_grid {
+            self.large_grid.entry(cell).or_default().extend(entities);
+        }
+        self.largest_large_radius = self.largest_large_radius.max(other.largest_large_radius);
+        self
+    }
+
     /// Get an iterator over the entities overlapping the provided axis aligned
     /// bounding region.
     /// NOTE: for best optimization of the iterator use
@@ -6,6 +6,10 @@ pub enum PluginError {
     Toml(toml::de::Error),
     NoConfig,
     NoSuchModule,
+    /// Plugin names may only contain lowercase letters, digits and underscores
+    InvalidName(String),
+    /// Plugin assets below `plugin/` have to be in the directory of the plugin
+    OutsideNamespace(std::path::PathBuf),
     Encoding(Box<ErrorKind>),
     PluginModuleError(String, String, PluginModuleError),
     ProcessExit,
@@ -3,7 +3,13 @@ pub mod memory_manager;
 pub mod module;
 
 use bincode::ErrorKind;
-use common::{assets::ASSETS_PATH, event::PluginHash, uid::Uid};
+use common::{
+    assets::ASSETS_PATH,
+    event::PluginHash,
+    prefab::PrefabPlacement,
+    rtsim::{RtsimHookAction, RtsimHookEvent},
+    uid::Uid,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
@@ -26,6 +32,11 @@ pub struct PluginData {
     name: String,
     modules: HashSet<PathBuf>,
     dependencies: HashSet<String>,
+    /// Item definitions added by the plugin, relative to its namespace. An
+    /// item `sword` of the plugin `example` has the asset id
+    /// `plugin.example.sword`.
+    #[serde(default)]
+    items: HashSet<String>,
 }
 
 fn compute_hash(data: &[u8]) -> PluginHash {
@@ -111,6 +122,24 @@ impl Plugin {
         )
         .map_err(PluginError::Toml)?;
 
+        // Plugins get their own namespace for assets, so that they can't collide
+        // with each other
+        if data.name.is_empty()
+            || !data
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(PluginError::InvalidName(data.name));
+        }
+        let namespace = Path::new("plugin").join(&data.name);
+        if let Some(path) = files
+            .keys()
+            .find(|path| path.starts_with("plugin") && !path.starts_with(&namespace))
+        {
+            return Err(PluginError::OutsideNamespace(path.clone()));
+        }
+
         let modules = data
             .modules
             .iter()
@@ -165,6 +194,28 @@ impl Plugin {
         result
     }
 
+    pub fn rtsim_event(&mut self, ecs: &EcsWorld, event: &RtsimHookEvent) -> Vec<RtsimHookAction> {
+        self.modules
+            .iter_mut()
+            .flat_map(|module| module.rtsim_event(ecs, event))
+            .collect()
+    }
+
+    pub fn take_prefab_placements(&mut self) -> Vec<PrefabPlacement> {
+        self.modules
+            .iter_mut()
+            .flat_map(|module| module.take_prefab_placements())
+            .collect()
+    }
+
+    /// Asset ids of the item definitions added by this plugin.
+    pub fn item_ids(&self) -> impl Iterator<Item = String> + '_ {
+        self.data
+            .items
+            .iter()
+            .map(|item| format!("plugin.{}.{item}", self.data.name))
+    }
+
     /// get the path to the plugin file
     pub fn path(&self) -> &Path { self.path.as_path() }
 
@@ -262,6 +313,20 @@ impl PluginMgr {
         self.plugins.iter().map(|plugin| plugin.hash).collect()
     }
 
+    /// Check that the item definitions added by plugins can be loaded,
+    /// returning the ones that can't.
+    pub fn validate_items(&self) -> Vec<(String, common::assets::Error)> {
+        self.plugins
+            .iter()
+            .flat_map(Plugin::item_ids)
+            .filter_map(|id| {
+                common::comp::Item::new_from_asset(&id)
+                    .err()
+                    .map(|err| (id, err))
+            })
+            .collect()
+    }
+
     /// retrieve a specific plugin
     pub fn find(&self, hash: &PluginHash) -> Option<&Plugin> {
         self.plugins.iter().find(|plugin| &plugin.hash == hash)
@@ -299,6 +364,23 @@ impl PluginMgr {
         });
         result
     }
+
+    /// Forward an rtsim event to all plugins, returning the actions they took
+    /// in response.
+    pub fn rtsim_event(&mut self, ecs: &EcsWorld, event: &RtsimHookEvent) -> Vec<RtsimHookAction> {
+        self.plugins
+            .iter_mut()
+            .flat_map(|plugin| plugin.rtsim_event(ecs, event))
+            .collect()
+    }
+
+    /// Prefabs that plugins asked to place since the last call
+    pub fn take_prefab_placements(&mut self) -> Vec<PrefabPlacement> {
+        self.plugins
+            .iter_mut()
+            .flat_map(|plugin| plugin.take_prefab_placements())
+            .collect()
+    }
 }
 
 /// Error returned by plugin based server commands
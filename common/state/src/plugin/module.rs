@@ -5,6 +5,10 @@ use super::{
     memory_manager::{EcsAccessManager, EcsWorld},
     CommandResults,
 };
+use common::{
+    prefab::PrefabPlacement,
+    rtsim::{Actor, NpcId, RtsimHookAction, RtsimHookEvent, SiteId, RTSIM_HOOK_API_VERSION},
+};
 use hashbrown::HashSet;
 use wasmtime::{
     component::{Component, Linker},
@@ -16,7 +20,7 @@ wasmtime::component::bindgen!({
     path: "../../plugin/wit/veloren.wit",
     async: true,
     with: {
-        "veloren:plugin/information@0.0.1/entity": Entity,
+        "veloren:plugin/information@0.0.2/entity": Entity,
     },
 });
 
@@ -24,7 +28,7 @@ pub struct Entity {
     uid: common::uid::Uid,
 }
 
-use veloren::plugin::{actions, information, types};
+use veloren::plugin::{actions, information, rtsim_actions, rtsim_types, types};
 
 /// This structure represent the WASM State of the plugin.
 pub struct PluginModule {
@@ -33,6 +37,7 @@ pub struct PluginModule {
     store: Mutex<wasmtime::Store<WasiHostCtx>>,
     #[allow(dead_code)]
     name: String,
+    rtsim_api_version: Option<u32>,
 }
 
 struct WasiHostCtx {
@@ -40,6 +45,8 @@ struct WasiHostCtx {
     preview2_table: wasmtime::component::ResourceTable,
     ecs: Arc<EcsAccessManager>,
     registered_commands: HashSet<String>,
+    rtsim_actions: Vec<RtsimHookAction>,
+    prefab_placements: Vec<PrefabPlacement>,
 }
 
 impl wasmtime_wasi::WasiView for WasiHostCtx {
@@ -52,6 +59,8 @@ impl information::Host for WasiHostCtx {}
 
 impl types::Host for WasiHostCtx {}
 
+impl rtsim_types::Host for WasiHostCtx {}
+
 #[wasmtime::component::__internal::async_trait]
 impl actions::Host for WasiHostCtx {
     async fn register_command(&mut self, name: String) -> wasmtime::Result<()> {
@@ -68,6 +77,135 @@ impl actions::Host for WasiHostCtx {
         tracing::info!("Plugin sends message {text} to player {uid:?}");
         Ok(())
     }
+
+    async fn place_prefab(
+        &mut self,
+        name: String,
+        x: i32,
+        y: i32,
+        z: i32,
+        rotation: u8,
+        remap: String,
+    ) -> wasmtime::Result<()> {
+        match PrefabPlacement::parse_remap(&remap) {
+            Ok(remap) => self.prefab_placements.push(PrefabPlacement {
+                prefab: name,
+                origin: vek::Vec3::new(x, y, z),
+                rotation: rotation % 4,
+                remap,
+            }),
+            Err(err) => tracing::warn!("Plugin tried to place prefab {name}: {err}"),
+        }
+        Ok(())
+    }
+}
+
+#[wasmtime::component::__internal::async_trait]
+impl rtsim_actions::Host for WasiHostCtx {
+    async fn spawn_npc(
+        &mut self,
+        wpos: rtsim_types::Position,
+        body: String,
+        home: Option<rtsim_types::SiteId>,
+    ) -> wasmtime::Result<()> {
+        self.rtsim_actions.push(RtsimHookAction::SpawnNpc {
+            wpos: wpos.into(),
+            body,
+            home: home.map(SiteId::from_ffi),
+        });
+        Ok(())
+    }
+
+    async fn report_death(
+        &mut self,
+        actor: rtsim_types::Actor,
+        killer: Option<rtsim_types::Actor>,
+        wpos: rtsim_types::Position,
+    ) -> wasmtime::Result<()> {
+        self.rtsim_actions.push(RtsimHookAction::ReportDeath {
+            actor: actor.into(),
+            killer: killer.map(Into::into),
+            wpos: wpos.into(),
+        });
+        Ok(())
+    }
+
+    async fn modify_sentiment(
+        &mut self,
+        npc: rtsim_types::NpcId,
+        target: rtsim_types::Actor,
+        change: f32,
+    ) -> wasmtime::Result<()> {
+        self.rtsim_actions.push(RtsimHookAction::ModifySentiment {
+            npc: NpcId::from_ffi(npc),
+            target: target.into(),
+            change,
+        });
+        Ok(())
+    }
+}
+
+impl From<rtsim_types::Position> for vek::Vec3<f32> {
+    fn from(pos: rtsim_types::Position) -> Self { vek::Vec3::new(pos.x, pos.y, pos.z) }
+}
+
+impl From<vek::Vec3<f32>> for rtsim_types::Position {
+    fn from(pos: vek::Vec3<f32>) -> Self {
+        Self {
+            x: pos.x,
+            y: pos.y,
+            z: pos.z,
+        }
+    }
+}
+
+impl From<rtsim_types::Actor> for Actor {
+    fn from(actor: rtsim_types::Actor) -> Self {
+        match actor {
+            rtsim_types::Actor::Npc(npc) => Actor::Npc(NpcId::from_ffi(npc)),
+            rtsim_types::Actor::Character(character) => {
+                Actor::Character(common::character::CharacterId(character))
+            },
+        }
+    }
+}
+
+impl From<Actor> for rtsim_types::Actor {
+    fn from(actor: Actor) -> Self {
+        match actor {
+            Actor::Npc(npc) => Self::Npc(npc.to_ffi()),
+            Actor::Character(character) => Self::Character(character.0),
+        }
+    }
+}
+
+impl From<&RtsimHookEvent> for rtsim_types::RtsimEvent {
+    fn from(event: &RtsimHookEvent) -> Self {
+        match *event {
+            RtsimHookEvent::NpcDeath { npc, killer, wpos } => {
+                Self::NpcDeath(rtsim_types::NpcDeath {
+                    npc: npc.to_ffi(),
+                    killer: killer.map(Into::into),
+                    wpos: wpos.map(Into::into),
+                })
+            },
+            RtsimHookEvent::SiteRaided {
+                site,
+                victim,
+                raider,
+            } => Self::SiteRaided(rtsim_types::SiteRaided {
+                site: site.to_ffi(),
+                victim: victim.to_ffi(),
+                raider: raider.map(Into::into),
+            }),
+            RtsimHookEvent::DialogueStarted { npc, target } => {
+                Self::DialogueStarted(rtsim_types::DialogueStarted {
+                    npc: npc.to_ffi(),
+                    target: target.into(),
+                })
+            },
+        }
+    }
 }
 
 #[wasmtime::component::__internal::async_trait]
@@ -209,6 +347,8 @@ impl PluginModule {
             preview2_table: wasmtime_wasi::ResourceTable::new(),
             ecs: Arc::clone(&ecs),
             registered_commands: HashSet::new(),
+            rtsim_actions: Vec::new(),
+            prefab_placements: Vec::new(),
         };
         // the store contains all data of a wasm instance
         let mut store = Store::new(&engine, host_ctx);
@@ -227,11 +367,32 @@ impl PluginModule {
         let (plugin, _instance) =
             futures::executor::block_on(instance_fut).map_err(PluginModuleError::Wasmtime)?;
 
+        let rtsim_api_version = match futures::executor::block_on(
+            plugin
+                .veloren_plugin_rtsim_events()
+                .call_api_version(&mut store),
+        ) {
+            Ok(version) if version == RTSIM_HOOK_API_VERSION => Some(version),
+            Ok(version) => {
+                tracing::warn!(
+                    "Plugin {name} was built against version {version} of the rtsim hooks, but \
+                     the server uses version {RTSIM_HOOK_API_VERSION}. It won't receive rtsim \
+                     events."
+                );
+                Some(version)
+            },
+            Err(err) => {
+                tracing::error!("Plugin {name} failed to report its rtsim hook version: {err:?}");
+                None
+            },
+        };
+
         Ok(Self {
             plugin,
             ecs,
             store: store.into(),
             name,
+            rtsim_api_version,
         })
     }
 
@@ -290,6 +451,29 @@ impl PluginModule {
         })
     }
 
+    /// Returns the rtsim actions the plugin took in response to the event.
+    pub fn rtsim_event(&mut self, ecs: &EcsWorld, event: &RtsimHookEvent) -> Vec<RtsimHookAction> {
+        if self.rtsim_api_version != Some(RTSIM_HOOK_API_VERSION) {
+            return Vec::new();
+        }
+        let event = rtsim_types::RtsimEvent::from(event);
+        self.ecs.execute_with(ecs, || {
+            let future = self
+                .plugin
+                .veloren_plugin_rtsim_events()
+                .call_event(self.store.get_mut().unwrap(), &event);
+            if let Err(err) = futures::executor::block_on(future) {
+                tracing::error!("rtsim_event: {err:?}");
+            }
+        });
+        std::mem::take(&mut self.store.get_mut().unwrap().data_mut().rtsim_actions)
+    }
+
+    /// Prefabs the plugin asked to place since the last call
+    pub fn take_prefab_placements(&mut self) -> Vec<PrefabPlacement> {
+        std::mem::take(&mut self.store.get_mut().unwrap().data_mut().prefab_placements)
+    }
+
     pub fn player_join_event(
         &mut self,
         ecs: &EcsWorld,
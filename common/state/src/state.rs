@@ -10,6 +10,7 @@ use common::{
     comp,
     event::{EventBus, LocalEvent},
     link::Is,
+    loot_roll::LootRolls,
     mounting::{Mount, Rider, VolumeRider, VolumeRiders},
     outcome::Outcome,
     resources::{
@@ -249,6 +250,8 @@ impl State {
         ecs.register::<comp::Health>();
         ecs.register::<comp::Poise>();
         ecs.register::<comp::CanBuild>();
+        ecs.register::<comp::UnlockedShrines>();
+        ecs.register::<comp::Titles>();
         ecs.register::<comp::LightEmitter>();
         ecs.register::<comp::PickupItem>();
         ecs.register::<comp::Scale>();
@@ -266,6 +269,7 @@ impl State {
         ecs.register::<comp::CharacterActivity>();
         ecs.register::<comp::Object>();
         ecs.register::<comp::Group>();
+        ecs.register::<comp::GroupScaling>();
         ecs.register::<comp::Shockwave>();
         ecs.register::<comp::ShockwaveHitEntities>();
         ecs.register::<comp::Beam>();
@@ -274,6 +278,8 @@ impl State {
         ecs.register::<comp::Admin>();
         ecs.register::<comp::Stance>();
         ecs.register::<comp::Teleporting>();
+        ecs.register::<comp::Exposed>();
+        ecs.register::<comp::Corpse>();
 
         // Register components send from clients -> server
         ecs.register::<comp::Controller>();
@@ -351,6 +357,7 @@ impl State {
         ecs.insert(SysMetrics::default());
         ecs.insert(PhysicsMetrics::default());
         ecs.insert(Trades::default());
+        ecs.insert(LootRolls::default());
         ecs.insert(PlayerPhysicsSettings::default());
         ecs.insert(VolumeRiders::default());
 
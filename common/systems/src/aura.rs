@@ -278,13 +278,15 @@ fn activate_aura(
             // and is of at least the same strength
             // and of at least the same duration.
             // If no such buff is present, adds the buff.
-            let emit_buff = !target_buffs.buffs.iter().any(|(_, buff)| {
-                buff.cat_ids
-                    .iter()
-                    .any(|cat_id| matches!(cat_id, BuffCategory::FromActiveAura(uid, aura_key) if *aura_key == key && *uid == applier_uid))
-                    && buff.kind == kind
-                    && buff.data.strength >= data.strength
-            });
+            // Also doesn't reapply buffs that were just dispelled.
+            let emit_buff = !target_buffs.is_aura_immune(applier_uid, key, *read_data.time)
+                && !target_buffs.buffs.iter().any(|(_, buff)| {
+                    buff.cat_ids
+                        .iter()
+                        .any(|cat_id| matches!(cat_id, BuffCategory::FromActiveAura(uid, aura_key) if *aura_key == key && *uid == applier_uid))
+                        && buff.kind == kind
+                        && buff.data.strength >= data.strength
+                });
             if emit_buff {
                 let dest_info = DestInfo {
                     stats: read_data.stats.get(target),
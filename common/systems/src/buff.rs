@@ -6,12 +6,12 @@ use common::{
         body::{object, Body},
         buff::{
             Buff, BuffCategory, BuffChange, BuffData, BuffEffect, BuffKey, BuffKind, BuffSource,
-            Buffs, DestInfo,
+            Buffs, DestInfo, DotScaling,
         },
         fluid_dynamics::{Fluid, LiquidKind},
         item::MaterialStatManifest,
-        Alignment, Energy, Group, Health, HealthChange, Inventory, LightEmitter, Mass,
-        ModifierKind, PhysicsState, Player, Pos, Stats,
+        Alignment, Energy, Group, GroupScaling, Health, HealthChange, Inventory, LightEmitter,
+        Mass, ModifierKind, PhysicsState, Player, Pos, Stats,
     },
     event::{
         BuffEvent, ChangeBodyEvent, CreateSpriteEvent, EmitExt, EnergyChangeEvent,
@@ -28,9 +28,10 @@ use common_base::prof_span;
 use common_ecs::{Job, Origin, ParMode, Phase, System};
 use rayon::iter::ParallelIterator;
 use specs::{
-    shred, Entities, Entity, LendJoin, ParJoin, Read, ReadExpect, ReadStorage, SystemData,
+    shred, Entities, Entity, Join, LendJoin, ParJoin, Read, ReadExpect, ReadStorage, SystemData,
     WriteStorage,
 };
+use std::collections::HashMap;
 use vek::Vec3;
 
 event_emitters! {
@@ -69,6 +70,7 @@ pub struct ReadData<'a> {
     players: ReadStorage<'a, Player>,
     uids: ReadStorage<'a, Uid>,
     masses: ReadStorage<'a, Mass>,
+    group_scalings: ReadStorage<'a, GroupScaling>,
 }
 
 #[derive(Default)]
@@ -145,6 +147,15 @@ impl<'a> System<'a> for Sys {
             }
         }
 
+        // Attack damage modifiers from the previous tick, for damage over time that
+        // dynamically scales with its source. Stats can't be read from other entities
+        // while they are being updated below.
+        let damage_modifiers = (&read_data.uids, &stats)
+            .join()
+            .filter(|(_, stat)| stat.attack_damage_modifier != 1.0)
+            .map(|(uid, stat)| (*uid, stat.attack_damage_modifier))
+            .collect::<HashMap<_, _>>();
+
         let buff_join = (
             &read_data.entities,
             &read_data.buffs,
@@ -483,6 +494,12 @@ impl<'a> System<'a> for Sys {
             // Call to reset stats to base values
             stat.reset_temp_modifiers();
 
+            // Dungeon enemies scaled to the size of the group fighting them
+            if let Some(scaling) = read_data.group_scalings.get(entity) {
+                stat.max_health_modifiers.mult_mod *= scaling.health_multiplier();
+                stat.attack_damage_modifier *= scaling.damage_multiplier();
+            }
+
             let mut body_override = None;
 
             // Iterator over the lists of buffs by kind
@@ -517,6 +534,15 @@ impl<'a> System<'a> for Sys {
                         } else {
                             None
                         };
+                        let dot_modifier =
+                            match (buff.kind.dot_scaling(), buff.data.source_damage_modifier) {
+                                (Some(DotScaling::Snapshot), Some(snapshot)) => snapshot,
+                                (Some(DotScaling::Dynamic), Some(_)) => buff_owner
+                                    .and_then(|owner| damage_modifiers.get(&owner))
+                                    .copied()
+                                    .unwrap_or(1.0),
+                                _ => 1.0,
+                            };
 
                         // Now, execute the buff, based on it's delta
                         for effect in &buff.effects {
@@ -533,6 +559,7 @@ impl<'a> System<'a> for Sys {
                                 energy,
                                 entity,
                                 buff_owner,
+                                dot_modifier,
                                 &mut emitters,
                                 dt,
                                 *read_data.time,
@@ -590,6 +617,7 @@ fn execute_effect(
     energy: &Energy,
     entity: Entity,
     buff_owner: Option<Uid>,
+    dot_modifier: f32,
     server_emitter: &mut (
              impl EmitExt<HealthChangeEvent> + EmitExt<EnergyChangeEvent> + EmitExt<BuffEvent>
          ),
@@ -638,7 +666,13 @@ fn execute_effect(
             tick_dur,
         } => {
             if let Some(num_ticks) = num_ticks(*tick_dur) {
-                let amount = *rate * num_ticks * tick_dur.0 as f32;
+                // Only damage scales with the damage modifier of the source
+                let rate = if *rate < 0.0 {
+                    *rate * dot_modifier
+                } else {
+                    *rate
+                };
+                let amount = rate * num_ticks * tick_dur.0 as f32;
 
                 let (cause, by) = if amount != 0.0 {
                     (Some(DamageSource::Buff(buff_kind)), buff_owner)
@@ -837,5 +871,13 @@ fn execute_effect(
         BuffEffect::CrowdControlResistance(ccr) => {
             stat.crowd_control_resistance += ccr;
         },
+        BuffEffect::DispelResistance(dr) => {
+            stat.dispel_resistance = 1.0 - ((1.0 - stat.dispel_resistance) * (1.0 - *dr));
+        },
+        BuffEffect::ElementResistance(element, res) => {
+            if let Some(resistance) = stat.element_resistances.get_mut(*element) {
+                *resistance = 1.0 - ((1.0 - *resistance) * (1.0 - *res));
+            }
+        },
     };
 }
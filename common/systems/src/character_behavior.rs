@@ -7,9 +7,10 @@ use common::{
         self,
         character_state::{CharacterStateEvents, OutputEvents},
         inventory::item::{tool::AbilityMap, MaterialStatManifest},
-        ActiveAbilities, Beam, Body, CharacterActivity, CharacterState, Combo, Controller, Density,
-        Energy, Health, Inventory, InventoryManip, Mass, Melee, Ori, PhysicsState, Poise, Pos,
-        PreviousPhysCache, Scale, SkillSet, Stance, StateUpdate, Stats, Vel,
+        ActiveAbilities, Beam, Body, CanBuild, CharacterActivity, CharacterState, Combo,
+        Controller, Density, Energy, Health, Inventory, InventoryManip, Mass, Melee, Ori,
+        PhysicsState, Poise, Pos, PreviousPhysCache, Scale, SkillSet, Stance, StateUpdate, Stats,
+        Vel,
     },
     event::{self, EventBus, KnockbackEvent, LocalEvent},
     link::Is,
@@ -54,6 +55,7 @@ pub struct ReadData<'a> {
     inventories: ReadStorage<'a, Inventory>,
     stances: ReadStorage<'a, Stance>,
     prev_phys_caches: ReadStorage<'a, PreviousPhysCache>,
+    can_builds: ReadStorage<'a, CanBuild>,
 }
 
 /// ## Character Behavior System
@@ -165,8 +167,9 @@ impl<'a> System<'a> for Sys {
                 read_data.lazy_update.remove::<Beam>(entity);
             }
 
-            // Enter stunned state if poise damage is enough
-            if let Some(mut poise) = poises.get_mut(entity) {
+            // Enter stunned state if poise damage is enough, downed characters can't be
+            // knocked out of being downed though
+            if let Some(mut poise) = poises.get_mut(entity).filter(|_| !char_state.is_downed()) {
                 let was_wielded = char_state.is_wield();
                 let poise_state = poise.poise_state();
                 let pos = pos.0;
@@ -223,6 +226,10 @@ impl<'a> System<'a> for Sys {
                 id_maps: &id_maps,
                 alignments: &read_data.alignments,
                 prev_phys_caches: &read_data.prev_phys_caches,
+                creative: read_data
+                    .can_builds
+                    .get(entity)
+                    .map_or(false, |can_build| can_build.creative),
             };
 
             for action in actions {
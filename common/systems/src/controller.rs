@@ -18,6 +18,7 @@ event_emitters! {
         mount: event::MountEvent,
         mount_volume: event::MountVolumeEvent,
         set_pet_stay: event::SetPetStayEvent,
+        send_scout: event::SendScoutEvent,
         unmount: event::UnmountEvent,
         lantern: event::SetLanternEvent,
         npc_interact: event::NpcInteractEvent,
@@ -26,11 +27,20 @@ event_emitters! {
         process_trade_action: event::ProcessTradeActionEvent,
         inventory_manip: event::InventoryManipEvent,
         group_manip: event::GroupManipEvent,
+        guild_manip: event::GuildManipEvent,
         respawn: event::RespawnEvent,
         sound: event::SoundEvent,
         change_ability: event::ChangeAbilityEvent,
+        switch_ability_loadout: event::SwitchAbilityLoadoutEvent,
         change_stance: event::ChangeStanceEvent,
         start_teleporting: event::StartTeleportingEvent,
+        activate_shrine: event::ActivateShrineEvent,
+        shrine_travel: event::ShrineTravelEvent,
+        read_notice_board: event::ReadNoticeBoardEvent,
+        accept_bounty: event::AcceptBountyEvent,
+        recover_gravestone: event::RecoverGravestoneEvent,
+        request_reputation: event::RequestReputationEvent,
+        select_title: event::SelectTitleEvent,
         buff: event::BuffEvent,
     }
 }
@@ -88,6 +98,11 @@ impl<'a> System<'a> for Sys {
                             emitters.emit(event::SetPetStayEvent(entity, pet_entity, stay));
                         }
                     },
+                    ControlEvent::SendScout(pet_uid) => {
+                        if let Some(pet_entity) = read_data.id_maps.uid_entity(pet_uid) {
+                            emitters.emit(event::SendScoutEvent(entity, pet_entity));
+                        }
+                    },
                     ControlEvent::RemoveBuff(buff_id) => {
                         emitters.emit(event::BuffEvent {
                             entity,
@@ -121,6 +136,9 @@ impl<'a> System<'a> for Sys {
                     ControlEvent::GroupManip(manip) => {
                         emitters.emit(event::GroupManipEvent(entity, manip))
                     },
+                    ControlEvent::GuildManip(manip) => {
+                        emitters.emit(event::GuildManipEvent(entity, manip))
+                    },
                     ControlEvent::Respawn => emitters.emit(event::RespawnEvent(entity)),
                     ControlEvent::Utterance(kind) => {
                         if let (Some(pos), Some(body), scale) = (
@@ -150,6 +168,9 @@ impl<'a> System<'a> for Sys {
                             new_ability,
                         });
                     },
+                    ControlEvent::SwitchAbilityLoadout(loadout) => {
+                        emitters.emit(event::SwitchAbilityLoadoutEvent { entity, loadout });
+                    },
                     ControlEvent::LeaveStance => {
                         emitters.emit(event::ChangeStanceEvent {
                             entity,
@@ -161,6 +182,35 @@ impl<'a> System<'a> for Sys {
                             emitters.emit(event::StartTeleportingEvent { entity, portal });
                         }
                     },
+                    ControlEvent::ActivateShrine(shrine_uid) => {
+                        if let Some(shrine) = read_data.id_maps.uid_entity(shrine_uid) {
+                            emitters.emit(event::ActivateShrineEvent { entity, shrine });
+                        }
+                    },
+                    ControlEvent::TravelToShrine(site) => {
+                        emitters.emit(event::ShrineTravelEvent { entity, site });
+                    },
+                    ControlEvent::ReadNoticeBoard(pos) => {
+                        emitters.emit(event::ReadNoticeBoardEvent { entity, pos });
+                    },
+                    ControlEvent::AcceptBounty { board, target } => {
+                        emitters.emit(event::AcceptBountyEvent {
+                            entity,
+                            board,
+                            target,
+                        });
+                    },
+                    ControlEvent::RequestReputation => {
+                        emitters.emit(event::RequestReputationEvent { entity });
+                    },
+                    ControlEvent::RecoverGravestone(gravestone_uid) => {
+                        if let Some(gravestone) = read_data.id_maps.uid_entity(gravestone_uid) {
+                            emitters.emit(event::RecoverGravestoneEvent { entity, gravestone });
+                        }
+                    },
+                    ControlEvent::SelectTitle(title) => {
+                        emitters.emit(event::SelectTitleEvent { entity, title });
+                    },
                 }
             }
         }
@@ -1,7 +1,7 @@
 use common::{
     comp::{
-        Body, CharacterActivity, Collider, ControlAction, Controller, InputKind, Ori, Pos, Scale,
-        Vel,
+        Body, CharacterActivity, CharacterState, Collider, ControlAction, ControlEvent, Controller,
+        InputKind, Ori, Pos, Scale, Vel,
     },
     link::Is,
     mounting::{Mount, VolumeRider},
@@ -31,6 +31,7 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Body>,
         ReadStorage<'a, Scale>,
         ReadStorage<'a, Collider>,
+        ReadStorage<'a, CharacterState>,
     );
 
     const NAME: &'static str = "mount";
@@ -53,6 +54,7 @@ impl<'a> System<'a> for Sys {
             bodies,
             scales,
             colliders,
+            character_states,
         ): Self::SystemData,
     ) {
         // For each mount...
@@ -94,7 +96,8 @@ impl<'a> System<'a> for Sys {
             let vel = velocities.get(entity).copied();
             if let (Some(pos), Some(ori), Some(vel)) = (pos, ori, vel) {
                 let mounter_body = bodies.get(rider);
-                let mounting_offset = body.map_or(Vec3::unit_z(), Body::mount_offset)
+                let mounting_offset = body
+                    .map_or(Vec3::unit_z(), |body| body.mount_offset_for(mounter_body))
                     * scales.get(entity).map_or(1.0, |s| s.0)
                     + mounter_body.map_or(Vec3::zero(), Body::rider_offset)
                         * scales.get(rider).map_or(1.0, |s| s.0);
@@ -109,6 +112,13 @@ impl<'a> System<'a> for Sys {
                 controller.inputs = inputs;
                 controller.actions = actions;
             }
+            // ...and throw off small companions perched on a humanoid when it rolls
+            if matches!(body, Some(Body::Humanoid(_)))
+                && matches!(character_states.get(entity), Some(CharacterState::Roll(_)))
+                && let Some(controller) = controllers.get_mut(rider)
+            {
+                controller.push_event(ControlEvent::Unmount);
+            }
         }
 
         // For each volume rider.
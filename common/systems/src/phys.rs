@@ -12,6 +12,7 @@ use common::{
     link::Is,
     mounting::{Rider, VolumeRider},
     outcome::Outcome,
+    region::RegionMap,
     resources::{DeltaTime, GameMode, TimeOfDay},
     states,
     terrain::{Block, BlockKind, CoordinateConversions, SiteKindMeta, TerrainGrid, NEIGHBOR_DELTA},
@@ -23,10 +24,10 @@ use common::{
 use common_base::{prof_span, span};
 use common_ecs::{Job, Origin, ParMode, Phase, PhysicsMetrics, System};
 use itertools::Itertools;
-use rayon::iter::ParallelIterator;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use specs::{
-    shred, Entities, Entity, Join, LendJoin, ParJoin, Read, ReadExpect, ReadStorage, SystemData,
-    Write, WriteExpect, WriteStorage,
+    shred, BitSet, Entities, Entity, Join, LendJoin, ParJoin, Read, ReadExpect, ReadStorage,
+    SystemData, Write, WriteExpect, WriteStorage,
 };
 use std::ops::Range;
 use vek::*;
@@ -117,6 +118,53 @@ fn integrate_forces(
     vel
 }
 
+/// Fastest water current that can push entities around, rivers plunging down
+/// waterfalls are otherwise way too strong to swim out of
+const MAX_WATER_CURRENT: f32 = 15.0;
+
+/// Sets the velocity of the water an entity is in to the flow of the river at
+/// its position, so that rivers push swimmers and boats downstream
+fn apply_water_current(terrain: &TerrainGrid, pos: Vec3<f32>, physics_state: &mut PhysicsState) {
+    if let Some(Fluid::Liquid {
+        kind: LiquidKind::Water,
+        vel,
+        ..
+    }) = &mut physics_state.in_fluid
+    {
+        let current = terrain
+            .get_interpolated(pos.xy().as_(), |c| c.meta().river_velocity())
+            .unwrap_or_default();
+        let magn = current.magnitude_squared().max(0.0001);
+        vel.0 = current * (MAX_WATER_CURRENT.powi(2) / magn).min(1.0).sqrt();
+    }
+}
+
+/// Horizontal radius around a lit campfire in which it creates an updraft
+const CAMPFIRE_THERMAL_RADIUS: f32 = 8.0;
+/// Height above a lit campfire up to which its updraft can be ridden
+const CAMPFIRE_THERMAL_HEIGHT: f32 = 80.0;
+/// Vertical air speed right above a lit campfire
+const CAMPFIRE_THERMAL_LIFT: f32 = 14.0;
+
+/// Updraft created by the hot air rising above lit campfires
+fn campfire_lift(pos: Vec3<f32>, campfires: &[Vec3<f32>]) -> f32 {
+    campfires
+        .iter()
+        .map(|campfire| {
+            let height = pos.z - campfire.z;
+            let dist = pos.xy().distance(campfire.xy());
+            if height < 0.0 || height > CAMPFIRE_THERMAL_HEIGHT || dist > CAMPFIRE_THERMAL_RADIUS {
+                0.0
+            } else {
+                // Narrow column that weakens as the air cools down higher up
+                CAMPFIRE_THERMAL_LIFT
+                    * (1.0 - dist / CAMPFIRE_THERMAL_RADIUS)
+                    * (1.0 - height / CAMPFIRE_THERMAL_HEIGHT)
+            }
+        })
+        .fold(0.0, f32::max)
+}
+
 /// Simulates winds based on weather and terrain data for specific position
 // TODO: Consider exporting it if one wants to build nice visuals
 fn simulated_wind_vel(
@@ -124,6 +172,7 @@ fn simulated_wind_vel(
     weather: &WeatherGrid,
     terrain: &TerrainGrid,
     time_of_day: &TimeOfDay,
+    campfires: &[Vec3<f32>],
 ) -> Result<Vec3<f32>, ()> {
     prof_span!(guard, "Apply Weather INIT");
 
@@ -143,6 +192,9 @@ fn simulated_wind_vel(
     let interp_tree_density = terrain
         .get_interpolated(pos_2d, |c| c.meta().tree_density())
         .unwrap_or(0.);
+    let interp_temp = terrain
+        .get_interpolated(pos_2d, |c| c.meta().temp())
+        .unwrap_or(0.);
     let interp_town = terrain
         .get_interpolated(pos_2d, |c| match c.meta().site() {
             Some(SiteKindMeta::Settlement(_)) => 2.7,
@@ -188,6 +240,9 @@ fn simulated_wind_vel(
     }
     .min(2.0);
 
+    // Hot biomes like deserts and savannas heat the air above them a lot more
+    lift *= 1.0 + interp_temp.clamp(0.0, 1.0) * 0.8;
+
     // TODO: potential source of harsh edges in wind speed.
     //
     // Way more thermals in strong rain as its often caused by strong thermals.
@@ -255,6 +310,9 @@ fn simulated_wind_vel(
     // probably 0. to 1. src: SiteKind::is_suitable_loc comparisons
     wind_vel *= (1.0 - interp_tree_density).max(0.7);
 
+    // Campfires heat the air right above them, regardless of the canopy around
+    wind_vel.z += campfire_lift(pos.0, campfires);
+
     // Clamp magnitude, we never want to throw players around way too fast.
     let magn = wind_vel.magnitude_squared().max(0.0001);
 
@@ -305,6 +363,7 @@ pub struct PhysicsRead<'a> {
     stats: ReadStorage<'a, Stats>,
     weather: Option<Read<'a, WeatherGrid>>,
     time_of_day: Read<'a, TimeOfDay>,
+    region_map: Option<Read<'a, RegionMap>>,
 }
 
 #[derive(SystemData)]
@@ -470,26 +529,64 @@ impl<'a> PhysicsData<'a> {
         // TODO: maintain frame to frame? (requires handling deletion)
         // TODO: if not maintaining frame to frame consider counting entities to
         // preallocate?
-        // TODO: assess parallelizing (overhead might dominate here? would need to merge
-        // the vecs in each hashmap)
         let lg2_cell_size = 5;
         let lg2_large_cell_size = 6;
         let radius_cutoff = 8;
-        let mut spatial_grid = SpatialGrid::new(lg2_cell_size, lg2_large_cell_size, radius_cutoff);
-        for (entity, pos, phys_cache, _, _) in (
+        let new_grid = || SpatialGrid::new(lg2_cell_size, lg2_large_cell_size, radius_cutoff);
+        let insert = |spatial_grid: &mut SpatialGrid,
+                      entity: Entity,
+                      pos: &Pos,
+                      phys_cache: &PreviousPhysCache| {
+            // Note: to not get too fine grained we use a 2D grid for now
+            let radius_2d = phys_cache.scaled_radius.ceil() as u32;
+            let pos_2d = pos.0.xy().map(|e| e as i32);
+            const POS_TRUNCATION_ERROR: u32 = 1;
+            spatial_grid.insert(pos_2d, radius_2d + POS_TRUNCATION_ERROR, entity);
+        };
+
+        // Entities of different region shards are too far apart to end up in the same
+        // cells, so the grid of each shard is constructed in parallel and they are
+        // merged afterwards
+        let shards = read
+            .region_map
+            .as_ref()
+            .map_or(&[][..], |region_map| region_map.shards());
+        let mut spatial_grid = shards
+            .par_iter()
+            .map(|shard| {
+                let mut spatial_grid = new_grid();
+                for (_, entity, pos, phys_cache, _, _) in (
+                    &shard.entities,
+                    &read.entities,
+                    &write.positions,
+                    &write.previous_phys_cache,
+                    write.velocities.mask(),
+                    !&read.projectiles,
+                )
+                    .join()
+                {
+                    insert(&mut spatial_grid, entity, pos, phys_cache);
+                }
+                spatial_grid
+            })
+            .reduce(new_grid, SpatialGrid::merge);
+
+        // Entities that aren't part of any region yet
+        let mut sharded = BitSet::new();
+        for shard in shards {
+            sharded |= &shard.entities;
+        }
+        for (entity, pos, phys_cache, _, _, _) in (
             &read.entities,
             &write.positions,
             &write.previous_phys_cache,
             write.velocities.mask(),
             !&read.projectiles, // Not needed because they are skipped in the inner loop below
+            !&sharded,
         )
             .join()
         {
-            // Note: to not get too fine grained we use a 2D grid for now
-            let radius_2d = phys_cache.scaled_radius.ceil() as u32;
-            let pos_2d = pos.0.xy().map(|e| e as i32);
-            const POS_TRUNCATION_ERROR: u32 = 1;
-            spatial_grid.insert(pos_2d, radius_2d + POS_TRUNCATION_ERROR, entity);
+            insert(&mut spatial_grid, entity, pos, phys_cache);
         }
 
         spatial_grid
@@ -753,11 +850,18 @@ impl<'a> PhysicsData<'a> {
 
         prof_span!(guard, "Apply Weather");
         if let Some(weather) = &read.weather {
-            for (_, state, pos, phys) in (
+            let campfires = (&write.positions, &read.bodies)
+                .join()
+                .filter(|(_, body)| body.is_campfire())
+                .map(|(pos, _)| pos.0)
+                .collect::<Vec<_>>();
+
+            for (_, state, pos, phys, body) in (
                 &read.entities,
                 &read.character_states,
                 &write.positions,
                 &mut write.physics_states,
+                read.bodies.maybe(),
             )
                 .join()
             {
@@ -765,8 +869,11 @@ impl<'a> PhysicsData<'a> {
                 let mut air_vel = Vec3::zero();
 
                 'simulation: {
-                    // Don't simulate for non-gliding, for now
-                    if !state.is_glide() {
+                    // Don't simulate for non-gliding, for now, except for balloons that
+                    // drift wherever the wind takes them
+                    if !state.is_glide()
+                        && !matches!(body, Some(Body::Ship(ship)) if ship.has_burner())
+                    {
                         break 'simulation;
                     }
 
@@ -785,9 +892,13 @@ impl<'a> PhysicsData<'a> {
                     }
 
                     // If couldn't simulate wind for some reason, skip
-                    if let Ok(simulated_vel) =
-                        simulated_wind_vel(pos, weather, &read.terrain, &read.time_of_day)
-                    {
+                    if let Ok(simulated_vel) = simulated_wind_vel(
+                        pos,
+                        weather,
+                        &read.terrain,
+                        &read.time_of_day,
+                        &campfires,
+                    ) {
                         air_vel = simulated_vel
                     };
                 }
@@ -1121,6 +1232,7 @@ impl<'a> PhysicsData<'a> {
                                 &ori,
                                 friction_factor,
                             );
+                            apply_water_current(&read.terrain, cpos.0, physics_state);
                             tgt_pos = cpos.0;
                         },
                         Collider::CapsulePrism {
@@ -1156,6 +1268,7 @@ impl<'a> PhysicsData<'a> {
                                 &ori,
                                 friction_factor,
                             );
+                            apply_water_current(&read.terrain, cpos.0, physics_state);
 
                             // Sticky things shouldn't move when on a surface
                             if physics_state.on_surface().is_some() && sticky.is_some() {
@@ -1261,6 +1374,7 @@ impl<'a> PhysicsData<'a> {
                                     }),
                                     fluid => fluid,
                                 });
+                            apply_water_current(&read.terrain, pos.0, physics_state);
 
                             tgt_pos = pos.0;
                         },
@@ -2040,7 +2154,8 @@ fn box_voxel_collision<T: BaseVol<Vox = Block> + ReadVol>(
                 (old_depth + old_pos.z - pos.0.z).max(depth)
             });
 
-            // TODO: Change this at some point to allow entities to be moved by liquids?
+            // Water currents are applied by the caller, since this is also used for
+            // colliding with the volumes of entities
             let vel = Vel::zero();
 
             if depth > 0.0 {
@@ -1,8 +1,8 @@
 use common::{
     combat,
     comp::{
-        self, item::MaterialStatManifest, CharacterState, Combo, Energy, Health, Inventory, Poise,
-        Pos, Stats, StatsModifier,
+        self, item::MaterialStatManifest, Body, CharacterState, Combo, Energy, Health, Inventory,
+        PhysicsState, Poise, Pos, Stats, StatsModifier,
     },
     event::{DestroyEvent, EmitExt},
     event_emitters,
@@ -16,6 +16,7 @@ use specs::{
 const ENERGY_REGEN_ACCEL: f32 = 1.0;
 const SIT_ENERGY_REGEN_ACCEL: f32 = 2.5;
 const POISE_REGEN_ACCEL: f32 = 2.0;
+const BURNER_REFUEL_ACCEL: f32 = 2.0;
 
 event_emitters! {
     struct Events[Emitters] {
@@ -32,6 +33,8 @@ pub struct ReadData<'a> {
     positions: ReadStorage<'a, Pos>,
     char_states: ReadStorage<'a, CharacterState>,
     inventories: ReadStorage<'a, Inventory>,
+    bodies: ReadStorage<'a, Body>,
+    physics_states: ReadStorage<'a, PhysicsState>,
     msm: ReadExpect<'a, MaterialStatManifest>,
 }
 
@@ -131,7 +134,9 @@ impl<'a> System<'a> for Sys {
                 CharacterState::Idle(_)
                 | CharacterState::Talk
                 | CharacterState::Dance
+                | CharacterState::Emote(_)
                 | CharacterState::Pet(_)
+                | CharacterState::Revive(_)
                 | CharacterState::Skate(_)
                 | CharacterState::Glide(_)
                 | CharacterState::GlideWield(_)
@@ -177,6 +182,7 @@ impl<'a> System<'a> for Sys {
                 CharacterState::Roll(_)
                 | CharacterState::Wallrun(_)
                 | CharacterState::Stunned(_)
+                | CharacterState::Downed(_)
                 | CharacterState::BasicBlock(_)
                 | CharacterState::UseItem(_)
                 | CharacterState::Transform(_)
@@ -184,6 +190,17 @@ impl<'a> System<'a> for Sys {
             }
         });
 
+        // Balloon burners take on fuel while the balloon rests on the ground
+        let join = (&read_data.bodies, &read_data.physics_states, &mut energies).lend_join();
+        join.for_each(|(body, physics, mut energy)| {
+            if matches!(body, Body::Ship(ship) if ship.has_burner())
+                && physics.on_ground.is_some()
+                && energy.needs_regen()
+            {
+                energy.regen(BURNER_REFUEL_ACCEL, dt);
+            }
+        });
+
         // Decay combo
         (&read_data.entities, &mut combos)
             .lend_join()
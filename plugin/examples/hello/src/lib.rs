@@ -3,10 +3,12 @@
 mod bindings;
 
 use bindings::{
-    exports::veloren::plugin::events::Guest,
+    exports::veloren::plugin::{events::Guest, rtsim_events::Guest as RtsimGuest},
     veloren::plugin::{
         actions,
         information::Entity,
+        rtsim_actions,
+        rtsim_types::{Actor, RtsimEvent},
         types::{GameMode, Health, JoinResult, PlayerId, Uid},
     },
 };
@@ -52,3 +54,20 @@ impl Guest for Component {
         )])
     }
 }
+
+impl RtsimGuest for Component {
+    fn api_version() -> u32 { 1 }
+
+    fn event(event: RtsimEvent) {
+        match event {
+            RtsimEvent::SiteRaided(raid) => println!("Site {} was raided", raid.site),
+            // NPCs like players that talk to them
+            RtsimEvent::DialogueStarted(dialogue) => {
+                if let Actor::Character(_) = dialogue.target {
+                    rtsim_actions::modify_sentiment(dialogue.npc, dialogue.target, 0.01);
+                }
+            },
+            RtsimEvent::NpcDeath(_) => {},
+        }
+    }
+}
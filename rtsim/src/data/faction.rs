@@ -1,6 +1,6 @@
-use crate::data::Sentiments;
-use common::rtsim::Actor;
+use crate::data::{sentiment::Target, Sentiment, Sentiments};
 pub use common::rtsim::FactionId;
+use common::rtsim::{Actor, ReputationTier};
 use serde::{Deserialize, Serialize};
 use slotmap::HopSlotMap;
 use std::ops::{Deref, DerefMut};
@@ -11,12 +11,33 @@ pub struct Faction {
     pub seed: u32,
     pub leader: Option<Actor>,
     pub good_or_evil: bool, // TODO: Very stupid, get rid of this
+    /// How advanced the equipment of the faction is, from 0 to 2. Limits the
+    /// equipment that even wealthy sites of the faction can provide.
+    #[serde(default = "default_tech_level")]
+    pub tech_level: u8,
 
     #[serde(default)]
     pub sentiments: Sentiments,
 }
 
+fn default_tech_level() -> u8 { 1 }
+
 impl Faction {
+    /// The reputation the given target has with the faction, see
+    /// [`crate::rule::reputation`].
+    pub fn reputation(&self, target: impl Into<Target>) -> ReputationTier {
+        let sentiment = self.sentiments.toward(target);
+        if sentiment.is(Sentiment::FRIEND) {
+            ReputationTier::Honored
+        } else if sentiment.is(Sentiment::ALLY) {
+            ReputationTier::Friendly
+        } else if sentiment.is(Sentiment::RIVAL) {
+            ReputationTier::Hostile
+        } else {
+            ReputationTier::Neutral
+        }
+    }
+
     pub fn cleanup(&mut self) {
         self.sentiments
             .cleanup(crate::data::sentiment::FACTION_MAX_SENTIMENTS);
@@ -0,0 +1,71 @@
+use common::comp::quadruped_medium;
+pub use common::rtsim::HerdId;
+use serde::{Deserialize, Serialize};
+use slotmap::HopSlotMap;
+use std::ops::{Deref, DerefMut};
+use vek::*;
+
+/// Members of a herd keep within this distance of its centre
+pub const HERD_SPREAD: f32 = 24.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HerdKind {
+    Deer,
+    Moose,
+    Antelope,
+    /// Wolf packs don't graze, they follow the herds they prey on
+    Wolves,
+}
+
+impl HerdKind {
+    pub const GRAZERS: [Self; 3] = [Self::Deer, Self::Moose, Self::Antelope];
+
+    pub fn is_predator(&self) -> bool { matches!(self, Self::Wolves) }
+
+    pub fn species(&self) -> quadruped_medium::Species {
+        match self {
+            Self::Deer => quadruped_medium::Species::Deer,
+            Self::Moose => quadruped_medium::Species::Moose,
+            Self::Antelope => quadruped_medium::Species::Antelope,
+            Self::Wolves => quadruped_medium::Species::Wolf,
+        }
+    }
+
+    /// Temperature of the grazing grounds the herd seeks out in spring and
+    /// autumn, see [`super::nature::Season::warmth`].
+    pub fn preferred_temp(&self) -> f32 {
+        match self {
+            Self::Deer | Self::Wolves => 0.0,
+            Self::Moose => -0.4,
+            Self::Antelope => 0.4,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Herd {
+    pub kind: HerdKind,
+    /// The centre of the herd's members.
+    pub wpos: Vec2<f32>,
+    /// Where the herd is currently migrating to, if anywhere.
+    pub dest: Option<Vec2<f32>>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Herds {
+    pub herds: HopSlotMap<HerdId, Herd>,
+}
+
+impl Herds {
+    pub fn create(&mut self, herd: Herd) -> HerdId { self.herds.insert(herd) }
+}
+
+impl Deref for Herds {
+    type Target = HopSlotMap<HerdId, Herd>;
+
+    fn deref(&self) -> &Self::Target { &self.herds }
+}
+
+impl DerefMut for Herds {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.herds }
+}
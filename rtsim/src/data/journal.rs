@@ -0,0 +1,159 @@
+//! Incremental persistence of rtsim data.
+//!
+//! Serializing all of [`Data`] at once gets expensive for large worlds, mostly
+//! because the whole thing needs to be cloned on the main thread before it can
+//! be handed off to be written. Instead, each save produces a [`JournalEntry`]
+//! holding a slice of the NPCs (plus any that were created or removed since the
+//! last save) and the comparatively small remaining state. Entries are
+//! appended to a journal file next to the data snapshot and replayed on top of
+//! it when loading. Every so often the journal is compacted into a new
+//! snapshot.
+//!
+//! NPCs are matched by their uid rather than their [`NpcId`] because slot map
+//! keys cannot be chosen on insertion, so NPCs created after the last snapshot
+//! may end up with a different id when the journal is replayed.
+
+use super::{Data, Factions, Herds, Nature, Npc, NpcId, Reports, Roads, Sites, Wrecks, WriteError};
+use common::resources::TimeOfDay;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub tick: u64,
+    pub time_of_day: TimeOfDay,
+    pub uid_counter: u64,
+    pub should_purge: bool,
+    /// NPCs persisted by this entry.
+    pub npcs: Vec<Npc>,
+    /// Uids of NPCs that were removed since the previous entry.
+    pub removed_npcs: Vec<u64>,
+    pub sites: Sites,
+    pub factions: Factions,
+    pub reports: Reports,
+    #[serde(default)]
+    pub herds: Herds,
+    #[serde(default)]
+    pub roads: Roads,
+    #[serde(default)]
+    pub wrecks: Wrecks,
+    /// Nature covers every chunk of the world, so it's only included in some
+    /// entries.
+    pub nature: Option<Nature>,
+}
+
+/// Tracks which NPCs need to be included in the next [`JournalEntry`].
+#[derive(Default)]
+pub struct JournalCursor {
+    /// Position of the next slice of NPCs to persist.
+    next_npc: usize,
+    /// Number of entries created so far.
+    entries: u64,
+}
+
+impl Data {
+    /// Create a journal entry containing the next `slices`th of all NPCs, along
+    /// with every NPC created since the previous entry.
+    ///
+    /// Passing `slices = 1` persists all NPCs.
+    pub fn journal_entry(&mut self, cursor: &mut JournalCursor, slices: usize) -> JournalEntry {
+        let slice_len = self.npcs.len().div_ceil(slices.max(1)).max(1);
+        if cursor.next_npc >= self.npcs.len() {
+            cursor.next_npc = 0;
+        }
+
+        let slice = cursor.next_npc..cursor.next_npc + slice_len;
+        let created = std::mem::take(&mut self.npcs.created);
+        let npcs = self
+            .npcs
+            .iter()
+            .enumerate()
+            .filter(|(i, (id, _))| slice.contains(i) || created.contains(id))
+            .map(|(_, (_, npc))| npc.clone())
+            .collect();
+        cursor.next_npc = slice.end;
+
+        let nature =
+            (slices <= 1 || cursor.entries % slices as u64 == 0).then(|| self.nature.clone());
+        cursor.entries += 1;
+
+        JournalEntry {
+            tick: self.tick,
+            time_of_day: self.time_of_day,
+            uid_counter: self.npcs.uid_counter,
+            should_purge: self.should_purge,
+            npcs,
+            removed_npcs: std::mem::take(&mut self.npcs.removed),
+            sites: self.sites.clone(),
+            factions: self.factions.clone(),
+            reports: self.reports.clone(),
+            herds: self.herds.clone(),
+            roads: self.roads.clone(),
+            wrecks: self.wrecks.clone(),
+            nature,
+        }
+    }
+
+    pub fn apply_journal(&mut self, entry: JournalEntry) {
+        let mut by_uid = self
+            .npcs
+            .iter()
+            .map(|(id, npc)| (npc.uid, id))
+            .collect::<HashMap<_, NpcId>>();
+
+        for uid in entry.removed_npcs {
+            if let Some(id) = by_uid.remove(&uid) {
+                self.npcs.remove(id);
+            }
+        }
+        for npc in entry.npcs {
+            match by_uid.get(&npc.uid).and_then(|id| self.npcs.get_mut(*id)) {
+                Some(old) => *old = npc,
+                None => {
+                    let uid = npc.uid;
+                    by_uid.insert(uid, self.npcs.insert(npc));
+                },
+            }
+        }
+
+        self.npcs.uid_counter = self.npcs.uid_counter.max(entry.uid_counter);
+        self.tick = entry.tick;
+        self.time_of_day = entry.time_of_day;
+        self.should_purge = entry.should_purge;
+        self.sites = entry.sites;
+        self.factions = entry.factions;
+        self.reports = entry.reports;
+        self.herds = entry.herds;
+        self.roads = entry.roads;
+        self.wrecks = entry.wrecks;
+        if let Some(nature) = entry.nature {
+            self.nature = nature;
+        }
+    }
+}
+
+impl JournalEntry {
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), WriteError> {
+        rmp_serde::encode::write_named(&mut writer, self)
+    }
+
+    /// Read journal entries in the order they were written.
+    ///
+    /// Reading stops at the first entry that fails to decode, which is
+    /// usually one that was only partially written before a crash.
+    pub fn read_all<R: Read>(mut reader: R) -> impl Iterator<Item = Self> {
+        std::iter::from_fn(move || match rmp_serde::decode::from_read(&mut reader) {
+            Ok(entry) => Some(entry),
+            Err(rmp_serde::decode::Error::InvalidMarkerRead(err))
+                if err.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                None
+            },
+            Err(err) => {
+                tracing::warn!("Stopped reading rtsim journal at invalid entry: {}", err);
+                None
+            },
+        })
+    }
+}
@@ -1,17 +1,25 @@
 pub mod faction;
+pub mod herd;
+pub mod journal;
 pub mod nature;
 pub mod npc;
 pub mod report;
+pub mod road;
 pub mod sentiment;
 pub mod site;
+pub mod wreck;
 
 pub use self::{
     faction::{Faction, FactionId, Factions},
+    herd::{Herd, HerdId, HerdKind, Herds},
+    journal::{JournalCursor, JournalEntry},
     nature::Nature,
     npc::{Npc, NpcId, Npcs},
     report::{Report, ReportId, ReportKind, Reports},
+    road::Roads,
     sentiment::{Sentiment, Sentiments},
     site::{Site, SiteId, Sites},
+    wreck::{Wreck, WreckId, Wrecks},
 };
 
 use common::resources::TimeOfDay;
@@ -46,6 +54,12 @@ pub struct Data {
     pub factions: Factions,
     #[serde(default)]
     pub reports: Reports,
+    #[serde(default)]
+    pub herds: Herds,
+    #[serde(default)]
+    pub roads: Roads,
+    #[serde(default)]
+    pub wrecks: Wrecks,
 
     #[serde(default)]
     pub tick: u64,
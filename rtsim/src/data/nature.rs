@@ -1,4 +1,4 @@
-use common::{grid::Grid, rtsim::ChunkResource};
+use common::{grid::Grid, resources::TimeOfDay, rtsim::ChunkResource};
 use enum_map::EnumMap;
 use serde::{Deserialize, Serialize};
 use vek::*;
@@ -36,6 +36,39 @@ impl Nature {
     }
 }
 
+/// How long each season lasts, in in-game seconds.
+pub const SEASON_LENGTH: f64 = 4.0 * 24.0 * 60.0 * 60.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn from_time_of_day(time_of_day: TimeOfDay) -> Self {
+        match (time_of_day.0 / SEASON_LENGTH).rem_euclid(4.0) as u32 {
+            0 => Self::Spring,
+            1 => Self::Summer,
+            2 => Self::Autumn,
+            _ => Self::Winter,
+        }
+    }
+
+    /// How much warmer the season is than spring and autumn. Wildlife seeks
+    /// out cooler regions in summer and warmer ones in winter to make up for
+    /// it.
+    pub fn warmth(&self) -> f32 {
+        match self {
+            Self::Spring | Self::Autumn => 0.0,
+            Self::Summer => 0.3,
+            Self::Winter => -0.3,
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Chunk {
     /// Represent the 'naturally occurring' resource proportion that exists in
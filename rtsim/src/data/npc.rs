@@ -1,17 +1,18 @@
 use crate::{
     ai::Action,
-    data::{Reports, Sentiments},
+    data::{HerdId, Reports, Sentiments},
     gen::name,
 };
 pub use common::rtsim::{NpcId, Profession};
 use common::{
     character::CharacterId,
-    comp,
+    comp::{self, item::ItemDefinitionIdOwned},
     grid::Grid,
     rtsim::{
         Actor, ChunkResource, FactionId, NpcAction, NpcActivity, NpcInput, Personality, ReportId,
         Role, SiteId,
     },
+    states::emote::EmoteKind,
     store::Id,
     terrain::CoordinateConversions,
     util::Dir,
@@ -87,6 +88,15 @@ impl Controller {
         self.actions.push(NpcAction::Say(target.into(), content));
     }
 
+    pub fn emote(&mut self, kind: EmoteKind) { self.actions.push(NpcAction::Emote(kind)); }
+
+    pub fn launch_firework(&mut self) { self.actions.push(NpcAction::LaunchFirework); }
+
+    pub fn give_directions(&mut self, target: Actor, directions: comp::dialogue::Directions) {
+        self.actions
+            .push(NpcAction::GiveDirections(target, directions));
+    }
+
     pub fn attack(&mut self, target: impl Into<Actor>) {
         self.actions.push(NpcAction::Attack(target.into()));
     }
@@ -98,6 +108,26 @@ pub struct Brain {
     pub action: Box<dyn Action<(), !>>,
 }
 
+/// The wares a trading NPC has for sale.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ShopStock {
+    pub wares: Vec<(ItemDefinitionIdOwned, u32)>,
+    /// Time of day at which the wares were last restocked.
+    pub restocked_at: f64,
+    /// Time of day at which the rare wares were last replaced with new ones.
+    pub rotated_at: f64,
+}
+
+/// Goods carried in the hold of a merchant ship, from one harbor to another.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Cargo {
+    /// The site whose harbor the goods were loaded at.
+    pub from: SiteId,
+    /// What the goods are worth to the site that they're delivered to, in units
+    /// of site wealth.
+    pub value: f32,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Npc {
     pub uid: u64,
@@ -120,6 +150,16 @@ pub struct Npc {
     pub personality: Personality,
     #[serde(default)]
     pub sentiments: Sentiments,
+    /// The wares of NPCs that trade, persisted so that they deplete as players
+    /// buy them.
+    #[serde(default)]
+    pub shop: Option<ShopStock>,
+    /// The herd that wild animals roam and migrate with.
+    #[serde(default)]
+    pub herd: Option<HerdId>,
+    /// The goods in the hold of ships that trade between harbors.
+    #[serde(default)]
+    pub cargo: Option<Cargo>,
 
     // Unpersisted state
     #[serde(skip)]
@@ -158,6 +198,9 @@ impl Clone for Npc {
             body: self.body,
             personality: self.personality,
             sentiments: self.sentiments.clone(),
+            shop: self.shop.clone(),
+            herd: self.herd,
+            cargo: self.cargo.clone(),
             // Not persisted
             chunk_pos: None,
             current_site: Default::default(),
@@ -172,6 +215,7 @@ impl Clone for Npc {
 impl Npc {
     pub const PERM_ENTITY_CONFIG: u32 = 1;
     const PERM_NAME: u32 = 0;
+    pub const PERM_WARES: u32 = 2;
 
     pub fn new(seed: u32, wpos: Vec3<f32>, body: comp::Body, role: Role) -> Self {
         Self {
@@ -188,6 +232,9 @@ impl Npc {
             faction: None,
             is_dead: false,
             known_reports: Default::default(),
+            shop: None,
+            herd: None,
+            cargo: None,
             chunk_pos: None,
             current_site: None,
             controller: Default::default(),
@@ -225,6 +272,12 @@ impl Npc {
         self
     }
 
+    // TODO: have a dedicated `NpcBuilder` type for this.
+    pub fn with_herd(mut self, herd: impl Into<Option<HerdId>>) -> Self {
+        self.herd = herd.into();
+        self
+    }
+
     pub fn rng(&self, perm: u32) -> impl Rng { RandomPerm::new(self.seed.wrapping_add(perm)) }
 
     // TODO: Don't make this depend on deterministic RNG, actually persist names
@@ -238,6 +291,13 @@ impl Npc {
         }
     }
 
+    /// Necromancers and the husks they raise. The dead stay dead once they
+    /// fall a second time.
+    pub fn is_undead(&self) -> bool {
+        matches!(self.profession(), Some(Profession::Necromancer))
+            || matches!(self.body, comp::Body::BipedSmall(body) if body.species == comp::biped_small::Species::Husk)
+    }
+
     pub fn cleanup(&mut self, reports: &Reports) {
         // Clear old or superfluous sentiments
         // TODO: It might be worth giving more important NPCs a higher sentiment
@@ -472,6 +532,13 @@ pub struct Npcs {
     pub npc_grid: Grid<GridCell>,
     #[serde(skip)]
     pub character_map: HashMap<Vec2<i32>, Vec<(CharacterId, Vec3<f32>)>>,
+    /// NPCs created since the last journal entry, see
+    /// [`super::journal::JournalEntry`].
+    #[serde(skip)]
+    pub created: Vec<NpcId>,
+    /// Uids of NPCs removed since the last journal entry.
+    #[serde(skip)]
+    pub removed: Vec<u64>,
 }
 
 impl Default for Npcs {
@@ -482,6 +549,8 @@ impl Default for Npcs {
             mounts: Default::default(),
             npc_grid: construct_npc_grid(),
             character_map: Default::default(),
+            created: Vec::new(),
+            removed: Vec::new(),
         }
     }
 }
@@ -503,7 +572,9 @@ impl Npcs {
     pub fn create_npc(&mut self, mut npc: Npc) -> NpcId {
         npc.uid = self.uid_counter;
         self.uid_counter += 1;
-        self.npcs.insert(npc)
+        let id = self.npcs.insert(npc);
+        self.created.push(id);
+        id
     }
 
     /// Queries nearby npcs, not garantueed to work if radius > 32.0
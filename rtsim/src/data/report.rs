@@ -37,6 +37,9 @@ impl Report {
                     DAYS * 5.0
                 }
             },
+            // Fires don't last long, no point in remembering them
+            ReportKind::Fire { .. } => 60.0 * 60.0,
+            ReportKind::Wreck { .. } => DAYS * 2.0,
         }
     }
 }
@@ -44,6 +47,8 @@ impl Report {
 #[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum ReportKind {
     Death { actor: Actor, killer: Option<Actor> },
+    Fire { wpos: Vec3<f32> },
+    Wreck { vehicle: Actor, wpos: Vec3<f32> },
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -0,0 +1,67 @@
+use common::{rtsim::RoadKind, store::Id};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use vek::*;
+use world::{civ::Track, World};
+
+/// How worn down the tracks between sites are by the traffic along them.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Roads {
+    /// The wear of each track, keyed by the id of the track. Track ids are
+    /// stable for a given world.
+    wear: HashMap<u64, f32>,
+
+    /// The track running through each chunk, if any.
+    #[serde(skip_serializing, skip_deserializing)]
+    chunk_tracks: HashMap<Vec2<i32>, Id<Track>>,
+}
+
+impl Roads {
+    /// Find the tracks running through each chunk, forgetting about the wear
+    /// of tracks that no longer exist and adding any new ones.
+    pub fn link(&mut self, world: &World) {
+        let tracks = &world.civs().tracks;
+        self.chunk_tracks = tracks
+            .iter()
+            .flat_map(|(track_id, track)| {
+                track
+                    .path()
+                    .iter()
+                    .map(move |chunk_pos| (*chunk_pos, track_id))
+            })
+            .collect();
+        self.wear
+            .retain(|track_id, _| tracks.recreate_id(*track_id).is_some());
+        for (track_id, _) in tracks.iter() {
+            self.wear
+                .entry(track_id.id())
+                .or_insert(RoadKind::INITIAL_WEAR);
+        }
+    }
+
+    pub fn track_at(&self, chunk_pos: Vec2<i32>) -> Option<Id<Track>> {
+        self.chunk_tracks.get(&chunk_pos).copied()
+    }
+
+    pub fn wear(&self, track: Id<Track>) -> f32 {
+        self.wear
+            .get(&track.id())
+            .copied()
+            .unwrap_or(RoadKind::INITIAL_WEAR)
+    }
+
+    pub fn wear_mut(&mut self, track: Id<Track>) -> &mut f32 {
+        self.wear
+            .entry(track.id())
+            .or_insert(RoadKind::INITIAL_WEAR)
+    }
+
+    pub fn all_wear_mut(&mut self) -> impl Iterator<Item = &mut f32> + '_ { self.wear.values_mut() }
+
+    pub fn road_at(&self, chunk_pos: Vec2<i32>) -> RoadKind {
+        self.track_at(chunk_pos)
+            .map_or(RoadKind::default(), |track| {
+                RoadKind::from_wear(self.wear(track))
+            })
+    }
+}
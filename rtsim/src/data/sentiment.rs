@@ -65,6 +65,23 @@ impl Sentiments {
         self.map.entry(target.into()).or_default()
     }
 
+    /// Iterate over every target toward which a sentiment is felt.
+    pub fn iter(&self) -> impl Iterator<Item = (Target, &Sentiment)> + '_ {
+        self.map.iter().map(|(tgt, sentiment)| (*tgt, sentiment))
+    }
+
+    /// Replace the sentiment felt toward the given target.
+    pub fn set(&mut self, target: impl Into<Target>, value: f32) {
+        let sentiment = Sentiment {
+            positivity: (value.clamp(-1.0, 1.0) * 126.0).round() as i8,
+        };
+        if sentiment.is_redundant() {
+            self.map.remove(&target.into());
+        } else {
+            self.map.insert(target.into(), sentiment);
+        }
+    }
+
     /// Progressively decay the sentiment back to a neutral sentiment.
     ///
     /// Note that sentiment get decay gets slower the harsher the sentiment is.
@@ -158,7 +175,7 @@ impl Sentiment {
     /// generally try to harm the actor in any way they can.
     pub const VILLAIN: f32 = -0.8;
 
-    fn value(&self) -> f32 { self.positivity as f32 * (1.0 / 126.0) }
+    pub fn value(&self) -> f32 { self.positivity as f32 * (1.0 / 126.0) }
 
     /// Change the sentiment toward the given target by the given amount,
     /// capping out at the given value.
@@ -1,9 +1,10 @@
-use crate::data::{ReportId, Reports};
+use crate::data::{Factions, ReportId, Reports};
 pub use common::rtsim::SiteId;
 use common::{
-    rtsim::{FactionId, NpcId},
+    rtsim::{ChunkResource, FactionId, NpcId},
     store::Id,
 };
+use enum_map::EnumMap;
 use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use slotmap::HopSlotMap;
@@ -17,10 +18,54 @@ pub struct Site {
     pub wpos: Vec2<i32>,
     pub faction: Option<FactionId>,
 
+    /// How prosperous the site currently is, from 0.0 (destitute) to 1.0
+    /// (wealthy). Determines the equipment of the site's defenders and is
+    /// drained when the site's population gets killed by outsiders.
+    #[serde(default = "default_wealth")]
+    pub wealth: f32,
+
     /// The [`Report`]s that the site tracks (you can imagine them being on a
     /// noticeboard or something).
     pub known_reports: HashSet<ReportId>,
 
+    /// Time of day at which a travelling merchant last delivered goods to the
+    /// site, restocking the shops of its traders.
+    #[serde(default)]
+    pub last_delivery: f64,
+
+    /// Time of day at which one of the site's residents was last killed by an
+    /// outsider. Workers stay away from the site's worksites for a while after
+    /// a raid.
+    #[serde(default)]
+    pub last_raid: f64,
+
+    /// Raw materials produced by the site's worksites that are waiting to be
+    /// shipped to its traders.
+    #[serde(default)]
+    #[serde(serialize_with = "crate::data::rugged_ser_enum_map::<_, _, _, 0>")]
+    #[serde(deserialize_with = "crate::data::rugged_de_enum_map::<_, _, _, 0>")]
+    pub stockpile: EnumMap<ChunkResource, f32>,
+
+    /// Travelling merchants that came to the site for its market day or
+    /// festival. They leave once it is over.
+    #[serde(default)]
+    pub market_visitors: Vec<NpcId>,
+
+    /// Whether the site currently holds a market day, as scheduled on the
+    /// server's event calendar.
+    #[serde(skip_serializing, skip_deserializing)]
+    pub market_day: bool,
+
+    /// Whether the site is currently celebrating a festival, as scheduled on
+    /// the server's event calendar.
+    #[serde(skip_serializing, skip_deserializing)]
+    pub festival: bool,
+
+    /// Whether a festival of the site is coming up on the server's event
+    /// calendar. Town criers spread the word.
+    #[serde(skip_serializing, skip_deserializing)]
+    pub festival_upcoming: bool,
+
     /// The site generated during initial worldgen that this site corresponds
     /// to.
     ///
@@ -49,7 +94,38 @@ pub struct Site {
     pub nearby_sites_by_size: Vec<SiteId>,
 }
 
+fn default_wealth() -> f32 { 0.5 }
+
+/// Wealth lost by a site every time one of its residents is killed by an
+/// outsider.
+pub const RAID_WEALTH_LOSS: f32 = 0.04;
+
+/// Fraction of a site's stockpile carried off every time one of its residents
+/// is killed by an outsider.
+pub const RAID_PLUNDER: f32 = 0.1;
+
+/// Wealth lost by a site for every coin it pays out to complete a bounty.
+pub const BOUNTY_WEALTH_PER_COIN: f32 = 0.0001;
+
 impl Site {
+    /// Equipment tier of guards and adventurers living at this site, from 0
+    /// (militia) to 2 (fully armored). Wealthy sites are still limited by the
+    /// tech level of their faction.
+    pub fn equipment_tier(&self, factions: &Factions) -> u8 {
+        let wealth_tier = if self.wealth < 0.3 {
+            0
+        } else if self.wealth < 0.7 {
+            1
+        } else {
+            2
+        };
+        let tech_level = self
+            .faction
+            .and_then(|faction| factions.get(faction))
+            .map_or(1, |faction| faction.tech_level);
+        wealth_tier.min(tech_level + 1)
+    }
+
     pub fn with_faction(mut self, faction: impl Into<Option<FactionId>>) -> Self {
         self.faction = faction.into();
         self
@@ -0,0 +1,53 @@
+pub use common::rtsim::WreckId;
+use common::{comp, resources::TimeOfDay, rtsim::ChunkWreck, terrain::CoordinateConversions};
+use serde::{Deserialize, Serialize};
+use slotmap::HopSlotMap;
+use std::ops::{Deref, DerefMut};
+use vek::*;
+
+/// The remains of an airship that came down, which stay where they fell until
+/// they're salvaged or rot away.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Wreck {
+    pub body: comp::ship::Body,
+    pub wpos: Vec3<f32>,
+    /// The direction that the airship was travelling in when it came down.
+    pub dir: Vec2<f32>,
+    pub seed: u32,
+    pub crashed_at: TimeOfDay,
+    /// How much of the wreck is yet to be salvaged, from 1 (untouched) to 0
+    /// (cleared away).
+    pub remains: f32,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Wrecks {
+    pub wrecks: HopSlotMap<WreckId, Wreck>,
+}
+
+impl Wrecks {
+    pub fn create(&mut self, wreck: Wreck) -> WreckId { self.wrecks.insert(wreck) }
+
+    /// The wreck lying in the given chunk, if any, as needed to generate it.
+    pub fn wreck_at(&self, chunk_pos: Vec2<i32>) -> Option<ChunkWreck> {
+        self.wrecks
+            .values()
+            .find(|wreck| wreck.wpos.xy().as_().wpos_to_cpos() == chunk_pos)
+            .map(|wreck| ChunkWreck {
+                wpos: wreck.wpos.xy().as_(),
+                dir: wreck.dir,
+                seed: wreck.seed,
+                remains: wreck.remains,
+            })
+    }
+}
+
+impl Deref for Wrecks {
+    type Target = HopSlotMap<WreckId, Wreck>;
+
+    fn deref(&self) -> &Self::Target { &self.wrecks }
+}
+
+impl DerefMut for Wrecks {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.wrecks }
+}
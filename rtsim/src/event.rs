@@ -2,13 +2,17 @@ use crate::{RtState, Rule};
 use common::{
     mounting::VolumePos,
     resources::{Time, TimeOfDay},
-    rtsim::{Actor, NpcId},
+    rtsim::{Actor, NpcId, RtsimHookEvent},
 };
 use vek::*;
 use world::{IndexRef, World};
 
 pub trait Event: Clone + 'static {}
 
+/// Events that the server passes on to plugins, taken out every tick.
+#[derive(Default)]
+pub struct HookEvents(pub Vec<RtsimHookEvent>);
+
 pub struct EventCtx<'a, R: Rule, E: Event> {
     pub state: &'a RtState,
     pub rule: &'a mut R,
@@ -38,6 +42,13 @@ pub struct OnDeath {
 }
 impl Event for OnDeath {}
 
+/// A fire started burning around `wpos`
+#[derive(Clone)]
+pub struct OnFire {
+    pub wpos: Vec3<f32>,
+}
+impl Event for OnFire {}
+
 #[derive(Clone)]
 pub struct OnMountVolume {
     pub actor: Actor,
@@ -8,6 +8,7 @@ impl Faction {
             seed: rng.gen(),
             leader: None,
             good_or_evil: rng.gen(),
+            tech_level: rng.gen_range(0..=2),
             sentiments: Default::default(),
         }
     }
@@ -12,13 +12,30 @@ use common::{
     comp::{self, Body},
     resources::TimeOfDay,
     rtsim::{Personality, Role, WorldSettings},
+    spiral::Spiral2d,
     terrain::{BiomeKind, CoordinateConversions, TerrainChunkSize},
     vol::RectVolSize,
 };
 use rand::prelude::*;
 use tracing::info;
 use vek::*;
-use world::{site::SiteKind, site2::PlotKind, IndexRef, World, CONFIG};
+use world::{
+    site::SiteKind,
+    site2::{plot::WorksiteKind, PlotKind},
+    IndexRef, World, CONFIG,
+};
+
+/// Towns need at least this many plots to get a banker
+const MIN_BANKER_SITE_PLOTS: usize = 24;
+/// Towns need at least this many plots to get a trainer
+const MIN_TRAINER_SITE_PLOTS: usize = 16;
+/// How many workers staff each worksite
+const WORKERS_PER_WORKSITE: std::ops::Range<usize> = 2..4;
+/// How many dockworkers work each harbor
+const DOCKWORKERS_PER_HARBOR: std::ops::Range<usize> = 2..4;
+/// How far from their hideout (in chunks) pirates look for open water to
+/// launch their ship from
+const PIRATE_WATER_SEARCH: usize = 9;
 
 impl Data {
     pub fn generate(settings: &WorldSettings, world: &World, index: IndexRef) -> Self {
@@ -27,6 +44,9 @@ impl Data {
             .zip(&mut index.seed.to_le_bytes())
             .for_each(|(dst, src)| *dst = *src);
         let mut rng = SmallRng::from_seed(seed);
+        let population = &settings.population;
+        let max_npcs = population.max_npcs.unwrap_or(usize::MAX);
+        let civilians = |n: usize| (n as f32 * population.civilian_density).round() as usize;
 
         let mut this = Self {
             version: CURRENT_VERSION,
@@ -35,6 +55,9 @@ impl Data {
             sites: Default::default(),
             factions: Default::default(),
             reports: Default::default(),
+            herds: Default::default(),
+            roads: Default::default(),
+            wrecks: Default::default(),
 
             tick: 0,
             time_of_day: TimeOfDay(settings.start_time),
@@ -112,6 +135,7 @@ impl Data {
                     kind,
                     PlotKind::House(_)
                         | PlotKind::Workshop(_)
+                        | PlotKind::GuildHall(_)
                         | PlotKind::AirshipDock(_)
                         | PlotKind::Tavern(_)
                         | PlotKind::Plaza
@@ -127,7 +151,10 @@ impl Data {
             }) as _;
             let matches_plazas = (|kind: &PlotKind| matches!(kind, PlotKind::Plaza)) as _;
             if good_or_evil {
-                for _ in 0..site2.plots().len() {
+                for _ in 0..civilians(site2.plots().len()) {
+                    if this.npcs.len() >= max_npcs {
+                        break;
+                    }
                     this.npcs.create_npc(
                         Npc::new(
                             rng.gen(),
@@ -150,7 +177,10 @@ impl Data {
                     );
                 }
             } else {
-                for _ in 0..15 {
+                for _ in 0..civilians(15) {
+                    if this.npcs.len() >= max_npcs {
+                        break;
+                    }
                     this.npcs.create_npc(
                         Npc::new(
                             rng.gen(),
@@ -166,7 +196,10 @@ impl Data {
             }
             // Merchants
             if good_or_evil {
-                for _ in 0..(site2.plots().len() / 6) + 1 {
+                for _ in 0..civilians(site2.plots().len() / 6 + 1) {
+                    if this.npcs.len() >= max_npcs {
+                        break;
+                    }
                     this.npcs.create_npc(
                         Npc::new(
                             rng.gen(),
@@ -179,12 +212,159 @@ impl Data {
                     );
                 }
             }
+            // Bankers, only in larger towns
+            if good_or_evil
+                && site2.plots().len() >= MIN_BANKER_SITE_PLOTS
+                && this.npcs.len() < max_npcs
+            {
+                this.npcs.create_npc(
+                    Npc::new(
+                        rng.gen(),
+                        rand_wpos(&mut rng, matches_plazas),
+                        random_humanoid(&mut rng),
+                        Role::Civilised(Some(Profession::Banker)),
+                    )
+                    .with_faction(site.faction)
+                    .with_home(site_id)
+                    .with_personality(Personality::random_good(&mut rng)),
+                );
+            }
+            // Trainers, teaching skills for coins
+            if good_or_evil
+                && site2.plots().len() >= MIN_TRAINER_SITE_PLOTS
+                && this.npcs.len() < max_npcs
+            {
+                this.npcs.create_npc(
+                    Npc::new(
+                        rng.gen(),
+                        rand_wpos(&mut rng, matches_plazas),
+                        random_humanoid(&mut rng),
+                        Role::Civilised(Some(Profession::Trainer)),
+                    )
+                    .with_faction(site.faction)
+                    .with_home(site_id)
+                    .with_personality(Personality::random_good(&mut rng)),
+                );
+            }
+            // A town crier, announcing the news in the plazas
+            if good_or_evil && site2.plazas().next().is_some() && this.npcs.len() < max_npcs {
+                this.npcs.create_npc(
+                    Npc::new(
+                        rng.gen(),
+                        rand_wpos(&mut rng, matches_plazas),
+                        random_humanoid(&mut rng),
+                        Role::Civilised(Some(Profession::Crier)),
+                    )
+                    .with_faction(site.faction)
+                    .with_home(site_id)
+                    .with_personality(Personality::random_good(&mut rng)),
+                );
+            }
+
+            // Workers, staffing the site's worksites
+            if good_or_evil {
+                for plot in site2.plots() {
+                    let PlotKind::Worksite(worksite) = plot.kind() else {
+                        continue;
+                    };
+                    let profession = match worksite.kind {
+                        WorksiteKind::Sawmill => Profession::Woodcutter,
+                        WorksiteKind::Mine => Profession::Miner,
+                        WorksiteKind::Quarry => Profession::Stonecutter,
+                    };
+                    let wpos2d = site2.tile_center_wpos(plot.root_tile());
+                    let wpos = wpos2d
+                        .map(|e| e as f32 + 0.5)
+                        .with_z(world.sim().get_alt_approx(wpos2d).unwrap_or(0.0));
+                    for _ in 0..rng.gen_range(WORKERS_PER_WORKSITE) {
+                        if this.npcs.len() >= max_npcs {
+                            break;
+                        }
+                        this.npcs.create_npc(
+                            Npc::new(
+                                rng.gen(),
+                                wpos,
+                                random_humanoid(&mut rng),
+                                Role::Civilised(Some(profession.clone())),
+                            )
+                            .with_faction(site.faction)
+                            .with_home(site_id)
+                            .with_personality(Personality::random(&mut rng)),
+                        );
+                    }
+                }
+            }
+
+            // Harbors, with dockworkers and a merchant ship moored alongside the pier
+            if good_or_evil {
+                for plot in site2.plots() {
+                    let PlotKind::Harbor(harbor) = plot.kind() else {
+                        continue;
+                    };
+                    let wpos = harbor
+                        .start
+                        .map(|e| e as f32 + 0.5)
+                        .with_z(world.sim().get_alt_approx(harbor.start).unwrap_or(0.0));
+                    for _ in 0..rng.gen_range(DOCKWORKERS_PER_HARBOR) {
+                        if this.npcs.len() >= max_npcs {
+                            break;
+                        }
+                        this.npcs.create_npc(
+                            Npc::new(
+                                rng.gen(),
+                                wpos,
+                                random_humanoid(&mut rng),
+                                Role::Civilised(Some(Profession::Dockworker)),
+                            )
+                            .with_faction(site.faction)
+                            .with_home(site_id)
+                            .with_personality(Personality::random(&mut rng)),
+                        );
+                    }
+
+                    // Ships are always spawned together with their captain. They take on
+                    // their first cargo once they find themselves at the mooring.
+                    if this.npcs.len() + 2 > max_npcs {
+                        continue;
+                    }
+                    let wpos = harbor.mooring.as_().with_z(
+                        world
+                            .sim()
+                            .get_interpolated(harbor.mooring, |chunk| chunk.water_alt)
+                            .unwrap_or(0.0),
+                    );
+                    let vehicle_id = this.npcs.create_npc(Npc::new(
+                        rng.gen(),
+                        wpos,
+                        Body::Ship(comp::body::ship::Body::Galleon),
+                        Role::Vehicle,
+                    ));
+                    let npc_id = this.npcs.create_npc(
+                        Npc::new(
+                            rng.gen(),
+                            wpos,
+                            random_humanoid(&mut rng),
+                            Role::Civilised(Some(Profession::Captain)),
+                        )
+                        .with_home(site_id)
+                        .with_personality(Personality::random_good(&mut rng)),
+                    );
+                    this.npcs
+                        .mounts
+                        .steer(vehicle_id, npc_id)
+                        .expect("We just created these npcs");
+                }
+            }
 
             for plot in site2
                 .plots
                 .values()
                 .filter(|plot| matches!(plot.kind(), PlotKind::AirshipDock(_)))
             {
+                // Airships are always spawned together with their captain
+                if this.npcs.len() + 2 > max_npcs {
+                    break;
+                }
                 let wpos = site2.tile_center_wpos(plot.root_tile());
                 let wpos = wpos.as_().with_z(world.sim().get_surface_alt_approx(wpos))
                     + Vec3::unit_z() * 70.0;
@@ -212,6 +392,60 @@ impl Data {
             }
         }
 
+        // Pirate ships, putting out from hideouts to prey on the sea lanes
+        for (site_id, site) in this.sites.iter() {
+            if !site.world_site.map_or(false, |ws| {
+                matches!(index.sites.get(ws).kind, SiteKind::PirateHideout(_))
+            }) {
+                continue;
+            }
+            if this.npcs.len() + 2 > max_npcs {
+                break;
+            }
+            let Some(cpos) = Spiral2d::new()
+                .take(PIRATE_WATER_SEARCH.pow(2))
+                .map(|rpos| site.wpos.wpos_to_cpos() + rpos)
+                .find(|cpos| {
+                    world
+                        .sim()
+                        .get(*cpos)
+                        .map_or(false, |c| c.river.river_kind.is_some())
+                })
+            else {
+                continue;
+            };
+            let species = comp::humanoid::ALL_SPECIES.choose(&mut rng).unwrap();
+            let pirate_body = Body::Humanoid(comp::humanoid::Body::random_with(&mut rng, species));
+            let wpos2d = TerrainChunkSize::center_wpos(cpos);
+            let wpos = wpos2d.as_().with_z(
+                world
+                    .sim()
+                    .get_interpolated(wpos2d, |chunk| chunk.water_alt)
+                    .unwrap_or(0.0),
+            );
+            let vehicle_id = this.npcs.create_npc(Npc::new(
+                rng.gen(),
+                wpos,
+                Body::Ship(comp::body::ship::Body::SailBoat),
+                Role::Vehicle,
+            ));
+            let npc_id = this.npcs.create_npc(
+                Npc::new(
+                    rng.gen(),
+                    wpos,
+                    pirate_body,
+                    Role::Civilised(Some(Profession::Pirate)),
+                )
+                .with_faction(site.faction)
+                .with_home(site_id)
+                .with_personality(Personality::random_evil(&mut rng)),
+            );
+            this.npcs
+                .mounts
+                .steer(vehicle_id, npc_id)
+                .expect("We just created these npcs");
+        }
+
         for (site_id, site) in this.sites.iter() {
             let rand_wpos = |rng: &mut SmallRng| {
                 // don't spawn in buildings
@@ -250,6 +484,9 @@ impl Data {
             .choose(&mut rng) else {
                 continue;
             };
+            if this.npcs.len() >= max_npcs {
+                break;
+            }
 
             this.npcs.create_npc(
                 Npc::new(
@@ -265,7 +502,10 @@ impl Data {
         }
 
         // Spawn monsters into the world
-        for _ in 0..100 {
+        for _ in 0..(100.0 * population.monster_density).round() as usize {
+            if this.npcs.len() >= max_npcs {
+                break;
+            }
             // Try a few times to find a location that's not underwater
             if let Some((wpos, chunk)) = (0..10)
                 .map(|_| world.sim().get_size().map(|sz| rng.gen_range(0..sz as i32)))
@@ -314,18 +554,19 @@ impl Data {
         }
         // Spawn one monster Gigasfrost into the world
         // Try a few times to find a location that's not underwater
-        if let Some((wpos, chunk)) = (0..100)
-            .map(|_| world.sim().get_size().map(|sz| rng.gen_range(0..sz as i32)))
-            .find_map(|pos| Some((pos, world.sim().get(pos).filter(|c| !c.is_underwater())?)))
-            .map(|(pos, chunk)| {
-                let wpos2d = pos.cpos_to_wpos_center();
-                (
-                    wpos2d
-                        .map(|e| e as f32 + 0.5)
-                        .with_z(world.sim().get_alt_approx(wpos2d).unwrap_or(0.0)),
-                    chunk,
-                )
-            })
+        if this.npcs.len() < max_npcs
+            && let Some((wpos, chunk)) = (0..100)
+                .map(|_| world.sim().get_size().map(|sz| rng.gen_range(0..sz as i32)))
+                .find_map(|pos| Some((pos, world.sim().get(pos).filter(|c| !c.is_underwater())?)))
+                .map(|(pos, chunk)| {
+                    let wpos2d = pos.cpos_to_wpos_center();
+                    (
+                        wpos2d
+                            .map(|e| e as f32 + 0.5)
+                            .with_z(world.sim().get_alt_approx(wpos2d).unwrap_or(0.0)),
+                        chunk,
+                    )
+                })
         {
             let species = Some(comp::body::biped_large::Species::Gigasfrost)
                 .filter(|_| chunk.temp < CONFIG.snow_temp)
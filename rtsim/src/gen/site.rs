@@ -8,6 +8,14 @@ use world::{
 };
 
 impl Site {
+    /// Wealth that a site recovers towards when it isn't being raided. Larger
+    /// towns are more prosperous.
+    pub fn base_wealth(world_site: &WorldSite) -> f32 {
+        world_site.site2().map_or(0.3, |site2| {
+            (site2.plots().len() as f32 / 60.0).clamp(0.1, 1.0)
+        })
+    }
+
     pub fn generate(
         world_site_id: Id<WorldSite>,
         _world: &World,
@@ -69,8 +77,16 @@ impl Site {
                     })
                     .map(|(_, faction)| *faction)
             }),
+            wealth: Self::base_wealth(world_site),
             population: Default::default(),
             known_reports: Default::default(),
+            last_delivery: 0.0,
+            last_raid: 0.0,
+            stockpile: Default::default(),
+            market_visitors: Vec::new(),
+            market_day: false,
+            festival: false,
+            festival_upcoming: false,
             nearby_sites_by_size: Vec::new(),
         }
     }
@@ -19,17 +19,21 @@ pub mod rule;
 
 pub use self::{
     data::Data,
-    event::{Event, EventCtx, OnTick},
+    event::{Event, EventCtx, HookEvents, OnTick},
     rule::{Rule, RuleError},
 };
 use anymap2::SendSyncAnyMap;
 use atomic_refcell::AtomicRefCell;
-use common::resources::{Time, TimeOfDay};
+use common::{
+    resources::{Time, TimeOfDay},
+    weather::WeatherGrid,
+};
 use std::{
     any::type_name,
     ops::{Deref, DerefMut},
 };
 use tracing::{error, info};
+use vek::Vec2;
 use world::{IndexRef, World};
 
 pub struct RtState {
@@ -48,7 +52,10 @@ impl RtState {
             rules: SendSyncAnyMap::new(),
             event_handlers: SendSyncAnyMap::new(),
         }
-        .with_resource(data);
+        .with_resource(data)
+        .with_resource(HookEvents::default())
+        // The weather is kept up to date by the server, skies are clear until then
+        .with_resource(WeatherGrid::new(Vec2::zero()));
 
         this.start_default_rules();
 
@@ -68,7 +75,17 @@ impl RtState {
         self.start_rule::<rule::sync_npcs::SyncNpcs>();
         self.start_rule::<rule::simulate_npcs::SimulateNpcs>();
         self.start_rule::<rule::npc_ai::NpcAi>();
+        self.start_rule::<rule::site_wealth::SiteWealth>();
+        self.start_rule::<rule::industry::Industry>();
+        self.start_rule::<rule::sea_trade::SeaTrade>();
+        self.start_rule::<rule::market_day::MarketDay>();
+        self.start_rule::<rule::roads::Roads>();
+        self.start_rule::<rule::reputation::Reputation>();
+        self.start_rule::<rule::necromancy::Necromancy>();
+        self.start_rule::<rule::wildlife::Wildlife>();
+        self.start_rule::<rule::wrecks::Wrecks>();
         self.start_rule::<rule::cleanup::CleanUp>();
+        self.start_rule::<rule::hooks::Hooks>();
     }
 
     pub fn start_rule<R: Rule>(&mut self) {
@@ -1,10 +1,20 @@
 pub mod cleanup;
+pub mod hooks;
+pub mod industry;
+pub mod market_day;
 pub mod migrate;
+pub mod necromancy;
 pub mod npc_ai;
 pub mod replenish_resources;
 pub mod report;
+pub mod reputation;
+pub mod roads;
+pub mod sea_trade;
 pub mod simulate_npcs;
+pub mod site_wealth;
 pub mod sync_npcs;
+pub mod wildlife;
+pub mod wrecks;
 
 use super::RtState;
 use std::fmt;
@@ -32,16 +32,19 @@ impl Rule for CleanUp {
             // Remove dead NPCs
             // TODO: Don't do this every tick, find a sensible way to gradually remove dead NPCs after they've been
             // forgotten
+            let mut removed = Vec::new();
             data.npcs
                 .retain(|npc_id, npc| if npc.is_dead {
                     // Remove NPC from home population
                     if let Some(home) = npc.home.and_then(|home| data.sites.get_mut(home)) {
                         home.population.remove(&npc_id);
                     }
+                    removed.push(npc.uid);
                     false
                 } else {
                     true
                 });
+            data.npcs.removed.extend(removed);
 
             // Clean up entities
             data.npcs
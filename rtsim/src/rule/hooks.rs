@@ -0,0 +1,29 @@
+use crate::{
+    event::{EventCtx, HookEvents, OnDeath},
+    RtState, Rule, RuleError,
+};
+use common::rtsim::RtsimHookEvent;
+
+/// Collects the events that are passed on to plugins.
+pub struct Hooks;
+
+impl Rule for Hooks {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnDeath>(on_death);
+
+        Ok(Self)
+    }
+}
+
+fn on_death(ctx: EventCtx<Hooks, OnDeath>) {
+    if let Some(npc) = ctx.event.actor.npc() {
+        ctx.state
+            .resource_mut::<HookEvents>()
+            .0
+            .push(RtsimHookEvent::NpcDeath {
+                npc,
+                killer: ctx.event.killer,
+                wpos: ctx.event.wpos,
+            });
+    }
+}
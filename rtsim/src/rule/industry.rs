@@ -0,0 +1,75 @@
+//! Workers extract raw materials from the land around their site's worksites
+//! into the site's stockpile. Once enough has been stockpiled, it gets shipped
+//! to the site's traders, restocking their shops and bringing wealth to the
+//! site. Output falls as the land gets worked out, and raids carry off part of
+//! the stockpile (see [`crate::rule::site_wealth`]).
+
+use crate::{
+    event::{EventCtx, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::{
+    rtsim::{ChunkResource, NpcActivity, Profession},
+    terrain::CoordinateConversions,
+};
+
+/// Stockpiles change slowly, so there's no need to update them every tick
+const INDUSTRY_TICK_SKIP: u64 = 30;
+/// How much a worker produces per second when working untouched land
+const PRODUCTION_RATE: f32 = 0.01;
+/// How much of its stockpile a site ships to its traders at once
+const SHIPMENT_SIZE: f32 = 10.0;
+/// Wealth brought to a site by each shipment
+const SHIPMENT_WEALTH: f32 = 0.02;
+
+pub struct Industry;
+
+impl Rule for Industry {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self)
+    }
+}
+
+fn on_tick(ctx: EventCtx<Industry, OnTick>) {
+    if ctx.event.tick % INDUSTRY_TICK_SKIP != 0 {
+        return;
+    }
+    let dt = ctx.event.dt * INDUSTRY_TICK_SKIP as f32;
+    let data = &mut *ctx.state.data_mut();
+
+    for npc in data.npcs.values().filter(|npc| !npc.is_dead) {
+        let Some(NpcActivity::Gather(&[resource])) = npc.controller.activity else {
+            continue;
+        };
+        let is_worker = matches!(
+            (npc.profession(), resource),
+            (Some(Profession::Woodcutter), ChunkResource::Tree)
+                | (Some(Profession::Miner), ChunkResource::Ore)
+                | (Some(Profession::Stonecutter), ChunkResource::Stone)
+        );
+        if !is_worker {
+            continue;
+        }
+        let land = data
+            .nature
+            .get_chunk_resources(npc.wpos.xy().as_().wpos_to_cpos())[resource];
+        if let Some(site) = npc.home.and_then(|home| data.sites.get_mut(home)) {
+            site.stockpile[resource] += PRODUCTION_RATE * land * dt;
+        }
+    }
+
+    let time = ctx.event.time_of_day.0;
+    for site in data.sites.values_mut() {
+        let total = site.stockpile.values().sum::<f32>();
+        if total >= SHIPMENT_SIZE {
+            let remaining = (total - SHIPMENT_SIZE) / total;
+            for (_, amount) in &mut site.stockpile {
+                *amount *= remaining;
+            }
+            site.last_delivery = time;
+            site.wealth = (site.wealth + SHIPMENT_WEALTH).min(1.0);
+        }
+    }
+}
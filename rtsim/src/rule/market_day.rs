@@ -0,0 +1,96 @@
+//! Market days and festivals are scheduled on the server's event calendar.
+//! They draw travelling merchants to the site, who set up in its plazas and
+//! leave again once the occasion is over. The site's own traders stock up for
+//! the occasion too.
+
+use crate::{
+    data::{npc::SimulationMode, Npc},
+    event::{EventCtx, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::{
+    comp::{self, Body},
+    rtsim::{Personality, Profession, Role},
+};
+use rand::prelude::*;
+
+/// Market days last for hours, so there's no need to check on them every tick
+const MARKET_DAY_TICK_SKIP: u64 = 30;
+/// How many travelling merchants come to a site for its market day
+const MARKET_DAY_MERCHANTS: usize = 4;
+
+pub struct MarketDay;
+
+impl Rule for MarketDay {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self)
+    }
+}
+
+fn on_tick(ctx: EventCtx<MarketDay, OnTick>) {
+    if ctx.event.tick % MARKET_DAY_TICK_SKIP != 0 {
+        return;
+    }
+    let data = &mut *ctx.state.data_mut();
+    let mut rng = thread_rng();
+
+    let site_ids = data.sites.keys().collect::<Vec<_>>();
+    for site_id in site_ids {
+        let site = &data.sites[site_id];
+        let occasion = site.market_day || site.festival;
+        if occasion && site.market_visitors.is_empty() {
+            let plazas = site
+                .world_site
+                .and_then(|world_site| ctx.index.sites.get(world_site).site2())
+                .map(|site2| {
+                    site2
+                        .plazas()
+                        .map(|plaza| site2.tile_center_wpos(site2.plot(plaza).root_tile()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let center = site.wpos;
+
+            let visitors = (0..MARKET_DAY_MERCHANTS)
+                .map(|_| {
+                    let wpos2d = plazas
+                        .choose(&mut rng)
+                        .copied()
+                        .unwrap_or(center)
+                        .map(|e| e + rng.gen_range(-5..5));
+                    let wpos = wpos2d
+                        .map(|e| e as f32 + 0.5)
+                        .with_z(ctx.world.sim().get_alt_approx(wpos2d).unwrap_or(0.0));
+                    let species = comp::humanoid::ALL_SPECIES.choose(&mut rng).unwrap();
+                    let body = Body::Humanoid(comp::humanoid::Body::random_with(&mut rng, species));
+                    data.spawn_npc(
+                        Npc::new(
+                            rng.gen(),
+                            wpos,
+                            body,
+                            Role::Civilised(Some(Profession::Merchant)),
+                        )
+                        .with_home(site_id)
+                        .with_personality(Personality::random_good(&mut rng)),
+                    )
+                })
+                .collect();
+            data.sites[site_id].market_visitors = visitors;
+        } else if !occasion && !site.market_visitors.is_empty() {
+            // Merchants only pack up once nobody is around to see them vanish
+            let npcs = &mut data.npcs;
+            data.sites[site_id]
+                .market_visitors
+                .retain(|npc_id| match npcs.get_mut(*npc_id) {
+                    Some(npc) if matches!(npc.mode, SimulationMode::Loaded) => true,
+                    Some(npc) => {
+                        npc.is_dead = true;
+                        false
+                    },
+                    None => false,
+                });
+        }
+    }
+}
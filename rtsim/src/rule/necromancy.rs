@@ -0,0 +1,188 @@
+//! Necromancers are drawn to places where many have died recently. Once one
+//! has settled at a battle site, their lair grows by raising husks from the
+//! creatures and people that die nearby.
+
+use crate::{
+    data::{Data, Faction, Npc},
+    event::{EventCtx, OnDeath, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::{
+    comp::{self, Body},
+    rtsim::{Actor, FactionId, Personality, Profession, Role},
+};
+use rand::prelude::*;
+use vek::*;
+
+/// Battles and lairs change slowly, so there's no need to check them every tick
+const NECROMANCY_TICK_SKIP: u64 = 120;
+/// Deaths closer than this to each other are considered part of the same
+/// battle
+const BATTLE_RADIUS: f32 = 64.0;
+/// Battles are forgotten once nobody has died there for this long (in seconds)
+const BATTLE_MEMORY: f64 = 30.0 * 60.0;
+/// How many deaths a battle needs before a necromancer settles there
+const MIN_BATTLE_DEATHS: u32 = 8;
+/// Lairs harvest the dead within this distance of their necromancer
+const HARVEST_RADIUS: f32 = 256.0;
+/// Chance for a death near a lair to add a husk to it
+const HARVEST_CHANCE: f64 = 0.5;
+/// The most members, necromancer included, that a lair can grow to
+const MAX_LAIR_SIZE: usize = 12;
+/// The most lairs that can exist in the world at once
+const MAX_LAIRS: usize = 4;
+/// Necromancers keep their distance from sites and other lairs
+const MIN_LAIR_DIST: f32 = 512.0;
+
+struct Battle {
+    wpos: Vec3<f32>,
+    deaths: u32,
+    last_death: f64,
+}
+
+#[derive(Default)]
+pub struct Necromancy {
+    battles: Vec<Battle>,
+}
+
+impl Rule for Necromancy {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnDeath>(on_death);
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self::default())
+    }
+}
+
+/// Factions led by a living necromancer, along with the position of the
+/// necromancer
+fn lairs(data: &Data) -> Vec<(FactionId, Vec3<f32>)> {
+    data.factions
+        .iter()
+        .filter_map(|(faction_id, faction)| {
+            let Some(Actor::Npc(leader)) = faction.leader else {
+                return None;
+            };
+            let leader = data.npcs.get(leader)?;
+            (!leader.is_dead && matches!(leader.profession(), Some(Profession::Necromancer)))
+                .then_some((faction_id, leader.wpos))
+        })
+        .collect()
+}
+
+fn on_death(ctx: EventCtx<Necromancy, OnDeath>) {
+    let Some(wpos) = ctx.event.wpos else {
+        return;
+    };
+    let time = ctx.state.data().time_of_day.0;
+
+    match ctx
+        .rule
+        .battles
+        .iter_mut()
+        .find(|battle| battle.wpos.distance_squared(wpos) < BATTLE_RADIUS.powi(2))
+    {
+        Some(battle) => {
+            battle.deaths += 1;
+            battle.last_death = time;
+        },
+        None => ctx.rule.battles.push(Battle {
+            wpos,
+            deaths: 1,
+            last_death: time,
+        }),
+    }
+
+    let data = &mut *ctx.state.data_mut();
+    let victim_faction = ctx
+        .event
+        .actor
+        .npc()
+        .and_then(|npc| data.npcs.get(npc))
+        .and_then(|npc| npc.faction);
+    let mut rng = thread_rng();
+    for (faction, leader_wpos) in lairs(data) {
+        // Husks don't rise from the lair's own dead
+        if victim_faction == Some(faction)
+            || leader_wpos.xy().distance_squared(wpos.xy()) > HARVEST_RADIUS.powi(2)
+            || !rng.gen_bool(HARVEST_CHANCE)
+        {
+            continue;
+        }
+        let size = data
+            .npcs
+            .values()
+            .filter(|npc| !npc.is_dead && npc.faction == Some(faction))
+            .count();
+        if size < MAX_LAIR_SIZE {
+            let husk =
+                comp::biped_small::Body::random_with(&mut rng, &comp::biped_small::Species::Husk);
+            data.spawn_npc(
+                Npc::new(
+                    rng.gen(),
+                    leader_wpos,
+                    Body::BipedSmall(husk),
+                    Role::Civilised(None),
+                )
+                .with_faction(faction),
+            );
+        }
+    }
+}
+
+fn on_tick(ctx: EventCtx<Necromancy, OnTick>) {
+    if ctx.event.tick % NECROMANCY_TICK_SKIP != 0 {
+        return;
+    }
+    let time = ctx.event.time_of_day.0;
+    ctx.rule
+        .battles
+        .retain(|battle| time - battle.last_death < BATTLE_MEMORY);
+
+    let data = &mut *ctx.state.data_mut();
+    let lairs = lairs(data);
+    if lairs.len() >= MAX_LAIRS {
+        return;
+    }
+    let is_secluded = |wpos: Vec3<f32>| {
+        let wpos2d = wpos.xy();
+        data.sites
+            .values()
+            .all(|site| site.wpos.as_().distance_squared(wpos2d) > MIN_LAIR_DIST.powi(2))
+            && lairs
+                .iter()
+                .all(|(_, lair)| lair.xy().distance_squared(wpos2d) > MIN_LAIR_DIST.powi(2))
+    };
+    let Some(idx) = ctx
+        .rule
+        .battles
+        .iter()
+        .position(|battle| battle.deaths >= MIN_BATTLE_DEATHS && is_secluded(battle.wpos))
+    else {
+        return;
+    };
+    let battle = ctx.rule.battles.remove(idx);
+
+    let mut rng = thread_rng();
+    let faction = data.factions.create(Faction {
+        seed: rng.gen(),
+        leader: None,
+        good_or_evil: false,
+        tech_level: 1,
+        sentiments: Default::default(),
+    });
+    let species = comp::humanoid::ALL_SPECIES.choose(&mut rng).unwrap();
+    let necromancer = data.spawn_npc(
+        Npc::new(
+            rng.gen(),
+            battle.wpos,
+            Body::Humanoid(comp::humanoid::Body::random_with(&mut rng, species)),
+            Role::Civilised(Some(Profession::Necromancer)),
+        )
+        .with_personality(Personality::random_evil(&mut rng))
+        .with_faction(faction),
+    );
+    if let Some(faction) = data.factions.get_mut(faction) {
+        faction.leader = Some(Actor::Npc(necromancer));
+    }
+}
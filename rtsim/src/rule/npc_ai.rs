@@ -1,3 +1,5 @@
+mod script;
+
 use std::{collections::VecDeque, hash::BuildHasherDefault};
 
 use crate::{
@@ -7,10 +9,12 @@ use crate::{
         seq, until, Action, NpcCtx, State,
     },
     data::{
+        herd::HERD_SPREAD,
         npc::{Brain, PathData, SimulationMode},
-        ReportKind, Sentiment, Sites,
+        Data, NpcId, ReportKind, Sentiment, Sites,
     },
     event::OnTick,
+    rule::{sea_trade, wrecks},
     RtState, Rule, RuleError,
 };
 use common::{
@@ -18,16 +22,20 @@ use common::{
     comp::{
         self, bird_large,
         compass::{Direction, Distance},
-        dialogue::Subject,
+        dialogue::{AskedLocation, AskedPerson, Directions, PersonType, Subject},
         Content,
     },
     path::Path,
-    rtsim::{Actor, ChunkResource, NpcInput, PersonalityTrait, Profession, Role, SiteId},
+    rtsim::{
+        Actor, ChunkResource, NpcActivity, NpcInput, PersonalityTrait, Profession, Role, SiteId,
+    },
     spiral::Spiral2d,
+    states::emote::EmoteKind,
     store::Id,
     terrain::{CoordinateConversions, TerrainChunkSize},
     time::DayPeriod,
     util::Dir,
+    weather::{WeatherGrid, WeatherKind, CELL_SIZE},
 };
 use fxhash::FxHasher64;
 use itertools::{Either, Itertools};
@@ -77,6 +85,7 @@ fn path_in_site(start: Vec2<i32>, end: Vec2<i32>, site: &site2::Site) -> PathRes
             TileKind::Empty => 3.0,
             TileKind::Hazard(_) => 50.0,
             TileKind::Field => 8.0,
+            TileKind::Canal => 20.0,
             TileKind::Plaza | TileKind::Road { .. } | TileKind::Path | TileKind::Bridge => 1.0,
 
             TileKind::Building
@@ -91,7 +100,7 @@ fn path_in_site(start: Vec2<i32>, end: Vec2<i32>, site: &site2::Site) -> PathRes
         };
         let is_door_tile = |plot: Id<site2::Plot>, tile: Vec2<i32>| match site.plot(plot).kind() {
             site2::PlotKind::House(house) => house.door_tile == tile,
-            site2::PlotKind::Workshop(_) => true,
+            site2::PlotKind::Workshop(_) | site2::PlotKind::Worksite(_) => true,
             _ => false,
         };
         let building = if a_tile.is_building() && b_tile.is_road() {
@@ -258,6 +267,8 @@ impl Rule for NpcAi {
                     .filter(|(_, npc)| matches!(npc.mode, SimulationMode::Loaded) || (npc.seed as u64 + ctx.event.tick) % SIMULATED_TICK_SKIP == 0)
                     .map(|(npc_id, npc)| {
                         let controller = std::mem::take(&mut npc.controller);
+                        // Others can still see what the NPC is doing while it thinks
+                        npc.controller.activity = controller.activity;
                         let inbox = std::mem::take(&mut npc.inbox);
                         let sentiments = std::mem::take(&mut npc.sentiments);
                         let known_reports = std::mem::take(&mut npc.known_reports);
@@ -517,6 +528,198 @@ where
     .debug(|| "traverse points")
 }
 
+/// Where to walk to follow a track through the chunk of one of its nodes
+fn track_node_wpos(world: &World, node: Vec2<i32>) -> Vec2<f32> {
+    // Find the centre of the track node's chunk
+    let node_chunk_wpos = TerrainChunkSize::center_wpos(node);
+
+    // Refine the node position a bit more based on local path information
+    world
+        .sim()
+        .get_nearest_path(node_chunk_wpos)
+        .map_or(node_chunk_wpos, |(_, wpos, _, _)| wpos.as_())
+        .as_::<f32>()
+}
+
+/// Directions to the site that was asked about, along the tracks we would take
+/// from the site we're in. Without a known way there, the directions lead
+/// straight to it.
+fn directions_to(ctx: &NpcCtx, location: &AskedLocation) -> Option<Directions> {
+    let data = ctx.state.data();
+    let world = ctx.world;
+    let (tgt_site, site) = data.sites.iter().find(|(_, site)| {
+        site.world_site
+            .is_some_and(|ws| ctx.index.sites.get(ws).name() == location.name)
+    })?;
+    let mut route = ctx
+        .npc
+        .current_site
+        .and_then(|current_site| path_between_towns(current_site, tgt_site, &data.sites, world))
+        .map(|tracks| {
+            tracks
+                .path
+                .into_iter()
+                .flat_map(move |(track_id, reversed)| {
+                    let nodes = &world.civs().tracks.get(track_id).path().nodes;
+                    // Tracks can be followed backward
+                    if reversed {
+                        Either::Left(nodes.iter().rev())
+                    } else {
+                        Either::Right(nodes.iter())
+                    }
+                    .map(move |node| track_node_wpos(world, *node))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    route.push(site.wpos.as_());
+    Some(Directions {
+        destination: location.name.clone(),
+        route,
+    })
+}
+
+/// How the part of the town that a plot belongs to is called when telling
+/// where someone is
+fn place_name(plot: &PlotKind) -> &'static str {
+    match plot {
+        PlotKind::House(_)
+        | PlotKind::CoastalHouse(_)
+        | PlotKind::SavannahHut(_)
+        | PlotKind::DesertCityMultiPlot(_)
+        | PlotKind::CliffTower(_)
+        | PlotKind::TerracottaHouse(_) => "npc-speech-where_is-place-house",
+        PlotKind::Tavern(_) => "npc-speech-where_is-place-tavern",
+        PlotKind::Workshop(_)
+        | PlotKind::CoastalWorkshop(_)
+        | PlotKind::SavannahWorkshop(_)
+        | PlotKind::Worksite(_) => "npc-speech-where_is-place-workshop",
+        PlotKind::Plaza => "npc-speech-where_is-place-plaza",
+        PlotKind::Harbor(_) | PlotKind::AirshipDock(_) => "npc-speech-where_is-place-docks",
+        PlotKind::GuildHall(_) => "npc-speech-where_is-place-guild_hall",
+        PlotKind::DesertCityArena(_) => "npc-speech-where_is-place-arena",
+        PlotKind::DesertCityTemple(_) | PlotKind::SeaChapel(_) => {
+            "npc-speech-where_is-place-temple"
+        },
+        _ => "npc-speech-where_is-place-streets",
+    }
+}
+
+/// Where the resident of our home town that was asked about is, judging by
+/// what they're doing and which part of town they're in. While they're in
+/// town, we also show the way to them.
+fn whereabouts(ctx: &NpcCtx, person: &AskedPerson) -> (Content, Option<Directions>) {
+    let data = ctx.state.data();
+    let unknown = || {
+        Content::localized_with_args("npc-speech-where_is_unknown", [(
+            "name",
+            Content::Plain(person.name()),
+        )])
+    };
+    let Some((home, town)) = ctx
+        .npc
+        .home
+        .and_then(|home| Some((home, data.sites.get(home)?)))
+    else {
+        return (unknown(), None);
+    };
+    let Some((resident_id, resident)) = town
+        .population
+        .iter()
+        .filter_map(|npc_id| Some((*npc_id, data.npcs.get(*npc_id)?)))
+        .filter(|(_, npc)| {
+            !npc.is_dead
+                && match &person.person_type {
+                    PersonType::Merchant => matches!(npc.profession(), Some(Profession::Merchant)),
+                    PersonType::Villager { name } => npc.get_name().eq_ignore_ascii_case(name),
+                    PersonType::Profession(profession) => npc.profession().is_some_and(|p| {
+                        core::mem::discriminant(&p) == core::mem::discriminant(profession)
+                    }),
+                }
+        })
+        .min_by_key(|(_, npc)| npc.wpos.xy().distance(ctx.npc.wpos.xy()) as i32)
+    else {
+        return (unknown(), None);
+    };
+    if resident_id == ctx.npc_id {
+        return (Content::localized("npc-speech-where_is_me"), None);
+    }
+
+    let name = resident.get_name();
+    if resident.current_site != Some(home) {
+        let comment = match resident
+            .current_site
+            .and_then(|site| data.sites.get(site)?.world_site)
+        {
+            Some(world_site) => Content::localized_with_args("npc-speech-where_is_away", [
+                ("name", Content::Plain(name)),
+                (
+                    "site",
+                    Content::Plain(ctx.index.sites.get(world_site).name().to_string()),
+                ),
+            ]),
+            None => Content::localized_with_args("npc-speech-where_is_travelling", [(
+                "name",
+                Content::Plain(name),
+            )]),
+        };
+        return (comment, None);
+    }
+
+    let wpos = resident.wpos.xy();
+    let (place, in_house) = town
+        .world_site
+        .and_then(|world_site| ctx.index.sites.get(world_site).site2())
+        .map_or(("npc-speech-where_is-place-streets", false), |site2| {
+            let tile = site2.wpos_tile(wpos.as_());
+            match tile.plot.map(|plot| site2.plot(plot).kind()) {
+                Some(plot) => (place_name(plot), matches!(plot, PlotKind::House(_))),
+                None if matches!(tile.kind, TileKind::Empty) => {
+                    ("npc-speech-where_is-place-outskirts", false)
+                },
+                None => ("npc-speech-where_is-place-streets", false),
+            }
+        });
+    let dir = Direction::from_dir(wpos - ctx.npc.wpos.xy()).localize_npc();
+    let dist = Distance::from_length(wpos.distance(ctx.npc.wpos.xy()) as i32).localize_npc();
+    // Those who made it home by night are asleep
+    let comment = if DayPeriod::from(ctx.time_of_day.0).is_dark()
+        && in_house
+        && !matches!(resident.controller.activity, Some(NpcActivity::Goto(..)))
+    {
+        Content::localized_with_args("npc-speech-where_is_asleep", [
+            ("name", Content::Plain(name.clone())),
+            ("dir", dir),
+            ("dist", dist),
+        ])
+    } else {
+        let activity = match resident.controller.activity {
+            Some(NpcActivity::Goto(..)) => "npc-speech-where_is-activity-walking",
+            Some(NpcActivity::Gather(_)) => "npc-speech-where_is-activity-gathering",
+            Some(NpcActivity::HuntAnimals) => "npc-speech-where_is-activity-hunting",
+            Some(NpcActivity::Dance(_) | NpcActivity::Cheer(_)) => {
+                "npc-speech-where_is-activity-celebrating"
+            },
+            Some(NpcActivity::Sit(..)) => "npc-speech-where_is-activity-sitting",
+            None => "npc-speech-where_is-activity-idle",
+        };
+        Content::localized_with_args("npc-speech-where_is", [
+            ("name", Content::Plain(name.clone())),
+            ("activity", Content::localized(activity)),
+            ("place", Content::localized(place)),
+            ("dir", dir),
+            ("dist", dist),
+        ])
+    };
+    (
+        comment,
+        Some(Directions {
+            destination: name,
+            route: vec![wpos],
+        }),
+    )
+}
+
 /// Try to travel to a site. Where practical, paths will be taken.
 fn travel_to_point<S: State>(wpos: Vec2<f32>, speed_factor: f32) -> impl Action<S> {
     now(move |ctx, _| {
@@ -559,18 +762,7 @@ fn travel_to_site<S: State>(tgt_site: SiteId, speed_factor: f32) -> impl Action<
                     Some(node_idx)
                 };
 
-                if let Some(node) = idx.and_then(|idx| nodes.get(idx)) {
-                    // Find the centre of the track node's chunk
-                    let node_chunk_wpos = TerrainChunkSize::center_wpos(*node);
-
-                    // Refine the node position a bit more based on local path information
-                    Some(ctx.world.sim()
-                        .get_nearest_path(node_chunk_wpos)
-                        .map_or(node_chunk_wpos, |(_, wpos, _, _)| wpos.as_())
-                        .as_::<f32>())
-                } else {
-                    None
-                }
+                idx.and_then(|idx| nodes.get(idx)).map(|node| track_node_wpos(ctx.world, *node))
             }, speed_factor)
                 .boxed()
 
@@ -624,7 +816,7 @@ fn travel_to_site<S: State>(tgt_site: SiteId, speed_factor: f32) -> impl Action<
         .map(|_, _| ())
 }
 
-fn talk_to<S: State>(tgt: Actor, _subject: Option<Subject>) -> impl Action<S> + Clone {
+fn talk_to<S: State>(tgt: Actor, subject: Option<Subject>) -> impl Action<S> + Clone {
     now(move |ctx, _| {
         if matches!(tgt, Actor::Npc(_)) && ctx.rng.gen_bool(0.2) {
             // Cut off the conversation sometimes to avoid infinite conversations (but only
@@ -632,8 +824,56 @@ fn talk_to<S: State>(tgt: Actor, _subject: Option<Subject>) -> impl Action<S> +
             // some sort of 'bored of conversation' system
             idle().l()
         } else {
+            let mut directions = None;
+            // Show the way to the site we were asked about
+            let (comment, emote) = if let Some(Subject::Location(location)) = &subject {
+                if let Some(to_site) = directions_to(ctx, location)
+                    && let Some(site_wpos) = to_site.route.last().copied()
+                {
+                    let comment = Content::localized_with_args("npc-speech-directions", [
+                        ("site", Content::Plain(to_site.destination.clone())),
+                        (
+                            "dir",
+                            Direction::from_dir(site_wpos - ctx.npc.wpos.xy()).localize_npc(),
+                        ),
+                        (
+                            "dist",
+                            Distance::from_length(site_wpos.distance(ctx.npc.wpos.xy()) as i32)
+                                .localize_npc(),
+                        ),
+                    ]);
+                    directions = Some(to_site);
+                    (comment, Some(EmoteKind::Point))
+                } else {
+                    let comment =
+                        Content::localized_with_args("npc-speech-directions_unknown", [(
+                            "site",
+                            Content::Plain(location.name.clone()),
+                        )]);
+                    (comment, None)
+                }
+            // Tell where the resident we were asked about is
+            } else if let Some(Subject::Person(person)) = &subject {
+                let (comment, to_resident) = whereabouts(ctx, person);
+                let emote = to_resident.is_some().then_some(EmoteKind::Point);
+                directions = to_resident;
+                (comment, emote)
+            // Talk about the festival that's going on
+            } else if ctx.rng.gen_bool(0.5)
+                && let Some(current_site) = ctx.npc.current_site
+                && let Some(current_site) = ctx.state.data().sites.get(current_site)
+                && current_site.festival
+                && let Some(current_site_name) = current_site
+                    .world_site
+                    .map(|ws| ctx.index.sites.get(ws).name().to_string())
+            {
+                let comment = Content::localized_with_args("npc-speech-festival", [(
+                    "site",
+                    Content::Plain(current_site_name),
+                )]);
+                (comment, Some(EmoteKind::Laugh))
             // Mention nearby sites
-            let comment = if ctx.rng.gen_bool(0.3)
+            } else if ctx.rng.gen_bool(0.3)
                 && let Some(current_site) = ctx.npc.current_site
                 && let Some(current_site) = ctx.state.data().sites.get(current_site)
                 && let Some(mention_site) = current_site.nearby_sites_by_size.choose(&mut ctx.rng)
@@ -642,7 +882,7 @@ fn talk_to<S: State>(tgt: Actor, _subject: Option<Subject>) -> impl Action<S> +
                     .world_site
                     .map(|ws| ctx.index.sites.get(ws).name().to_string())
             {
-                Content::localized_with_args("npc-speech-tell_site", [
+                let comment = Content::localized_with_args("npc-speech-tell_site", [
                     ("site", Content::Plain(mention_site_name)),
                     (
                         "dir",
@@ -656,7 +896,8 @@ fn talk_to<S: State>(tgt: Actor, _subject: Option<Subject>) -> impl Action<S> +
                         )
                         .localize_npc(),
                     ),
-                ])
+                ]);
+                (comment, Some(EmoteKind::Point))
             // Mention current site
             } else if ctx.rng.gen_bool(0.3)
                 && let Some(current_site) = ctx.npc.current_site
@@ -665,10 +906,11 @@ fn talk_to<S: State>(tgt: Actor, _subject: Option<Subject>) -> impl Action<S> +
                     .world_site
                     .map(|ws| ctx.index.sites.get(ws).name().to_string())
             {
-                Content::localized_with_args("npc-speech-site", [(
+                let comment = Content::localized_with_args("npc-speech-site", [(
                     "site",
                     Content::Plain(current_site_name),
-                )])
+                )]);
+                (comment, None)
 
             // Mention nearby monsters
             } else if ctx.rng.gen_bool(0.3)
@@ -680,7 +922,7 @@ fn talk_to<S: State>(tgt: Actor, _subject: Option<Subject>) -> impl Action<S> +
                     .filter(|other| matches!(&other.role, Role::Monster))
                     .min_by_key(|other| other.wpos.xy().distance(ctx.npc.wpos.xy()) as i32)
             {
-                Content::localized_with_args("npc-speech-tell_monster", [
+                let comment = Content::localized_with_args("npc-speech-tell_monster", [
                     ("body", monster.body.localize_npc()),
                     (
                         "dir",
@@ -691,12 +933,17 @@ fn talk_to<S: State>(tgt: Actor, _subject: Option<Subject>) -> impl Action<S> +
                         Distance::from_length(monster.wpos.xy().distance(ctx.npc.wpos.xy()) as i32)
                             .localize_npc(),
                     ),
-                ])
+                ]);
+                (comment, Some(EmoteKind::Point))
             // Specific night dialog
             } else if ctx.rng.gen_bool(0.6) && DayPeriod::from(ctx.time_of_day.0).is_dark() {
-                Content::localized("npc-speech-night")
+                (Content::localized("npc-speech-night"), None)
             } else {
-                ctx.npc.personality.get_generic_comment(&mut ctx.rng)
+                let comment = ctx.npc.personality.get_generic_comment(&mut ctx.rng);
+                // Greet players with a wave now and then
+                let emote = (matches!(tgt, Actor::Character(_)) && ctx.rng.gen_bool(0.3))
+                    .then_some(EmoteKind::Wave);
+                (comment, emote)
             };
             // TODO: Don't special-case players
             let wait = if matches!(tgt, Actor::Character(_)) {
@@ -707,7 +954,15 @@ fn talk_to<S: State>(tgt: Actor, _subject: Option<Subject>) -> impl Action<S> +
             idle()
                 .repeat()
                 .stop_if(timeout(wait))
-                .then(just(move |ctx, _| ctx.controller.say(tgt, comment.clone())))
+                .then(just(move |ctx, _| {
+                    ctx.controller.say(tgt, comment.clone());
+                    if let Some(emote) = emote {
+                        ctx.controller.emote(emote);
+                    }
+                    if let Some(directions) = &directions {
+                        ctx.controller.give_directions(tgt, directions.clone());
+                    }
+                }))
                 .r()
         }
     })
@@ -796,17 +1051,52 @@ fn adventure() -> impl Action<DefaultState> {
     .debug(move || "adventure")
 }
 
+const INGREDIENTS: &[ChunkResource] = &[
+    ChunkResource::Fruit,
+    ChunkResource::Mushroom,
+    ChunkResource::Plant,
+];
+
+/// How many chunks around a site are considered when judging whether the land
+/// can still support the professions living there
+const LAND_CHUNKS: usize = 81;
+/// Villagers move away once the resources they depend on drop below this
+const DEPLETED_LAND: f32 = 0.25;
+/// Villagers only move to sites where the resources they depend on are at least
+/// this abundant
+const ABUNDANT_LAND: f32 = 0.5;
+
+/// Workers only work the land within this many chunks of their worksite
+const WORK_CHUNKS: usize = 81;
+/// Workers move on from land once its resources drop below this
+const WORKED_OUT: f32 = 0.2;
+/// Workers stay away from their worksite for this long after their home has
+/// been raided (in seconds)
+const RAID_FEAR_TIME: f64 = 20.0 * 60.0;
+/// Harbors closer than this are not worth setting sail for
+const MIN_VOYAGE_DIST: f32 = 512.0;
+/// Harbors further than this are too far to sail to in one voyage
+const MAX_VOYAGE_DIST: f32 = 4096.0;
+/// How long a ship may spend at sea before giving up on reaching its harbor
+const MAX_VOYAGE_TIME: f64 = 30.0 * 60.0;
+/// How far away pirates can spot a laden merchant ship
+const PIRATE_SIGHT: f32 = 1024.0;
+/// How long pirates give chase before letting a ship get away
+const PIRATE_CHASE_TIME: f64 = 5.0 * 60.0;
+/// How far pirates stray from their hideout while lying in wait for ships
+const PIRATE_PROWL_DIST: f32 = 768.0;
+/// How long ships and airships wait for a storm to pass before setting off
+/// regardless
+const MAX_STORM_DELAY: f64 = 10.0 * 60.0;
+/// Villagers only go out to salvage wrecks within this distance of their home
+const SALVAGE_RANGE: f32 = 2048.0;
+/// How long town criers wait between their announcements
+const CRIER_ANNOUNCE_INTERVAL: f64 = 45.0;
+/// How long a raid stays news, in in-game seconds
+const NEWS_TIME: f64 = 60.0 * 60.0 * 24.0;
+
 fn gather_ingredients<S: State>() -> impl Action<S> {
-    just(|ctx, _| {
-        ctx.controller.do_gather(
-            &[
-                ChunkResource::Fruit,
-                ChunkResource::Mushroom,
-                ChunkResource::Plant,
-            ][..],
-        )
-    })
-    .debug(|| "gather ingredients")
+    just(|ctx, _| ctx.controller.do_gather(INGREDIENTS)).debug(|| "gather ingredients")
 }
 
 fn hunt_animals<S: State>() -> impl Action<S> {
@@ -828,6 +1118,216 @@ fn find_forest(ctx: &mut NpcCtx) -> Option<Vec2<f32>> {
         .map(|chunk| TerrainChunkSize::center_wpos(chunk).as_())
 }
 
+/// The resource that a worker of the given profession extracts at their
+/// worksite
+fn worker_resources(profession: Profession) -> Option<&'static [ChunkResource]> {
+    match profession {
+        Profession::Woodcutter => Some(&[ChunkResource::Tree]),
+        Profession::Miner => Some(&[ChunkResource::Ore]),
+        Profession::Stonecutter => Some(&[ChunkResource::Stone]),
+        _ => None,
+    }
+}
+
+/// Find a worksite of the worker's home that produces the given resource,
+/// along with the closest land around it that still has some of that resource
+/// left to work. As the land nearest to the worksite gets worked out, workers
+/// have to go ever further afield.
+fn find_worksite(ctx: &mut NpcCtx, resource: ChunkResource) -> Option<(Vec2<f32>, Vec2<f32>)> {
+    let site2 = ctx
+        .state
+        .data()
+        .sites
+        .get(ctx.npc.home?)
+        .and_then(|site| ctx.index.sites.get(site.world_site?).site2())?;
+    let worksite = site2
+        .plots()
+        .filter(|plot| {
+            matches!(plot.kind(), PlotKind::Worksite(worksite) if worksite.kind.resource() == resource)
+        })
+        .choose(&mut ctx.rng)?;
+    let worksite_wpos = site2.tile_center_wpos(worksite.root_tile());
+    let chunk_pos = worksite_wpos.wpos_to_cpos();
+    let data = ctx.state.data();
+    let work_wpos = Spiral2d::new()
+        .take(WORK_CHUNKS)
+        .map(|rpos| chunk_pos + rpos)
+        .find(|cpos| {
+            data.nature.get_chunk_resources(*cpos)[resource] > WORKED_OUT
+                // Timber can only be felled where there are trees to begin with
+                && (resource != ChunkResource::Tree
+                    || ctx.world.sim().get(*cpos).map_or(false, |c| c.tree_density > 0.3))
+        })
+        .map_or(worksite_wpos, TerrainChunkSize::center_wpos);
+    Some((worksite_wpos.as_(), work_wpos.as_()))
+}
+
+/// Find the pier of the npc's home harbor, returning where it meets the shore
+/// and a spot out along it where ships are loaded and unloaded.
+fn find_pier(ctx: &mut NpcCtx) -> Option<(Vec2<f32>, Vec2<f32>)> {
+    let site2 = ctx
+        .state
+        .data()
+        .sites
+        .get(ctx.npc.home?)
+        .and_then(|site| ctx.index.sites.get(site.world_site?).site2())?;
+    let harbor = site2
+        .plots()
+        .filter_map(|plot| match plot.kind() {
+            PlotKind::Harbor(harbor) => Some(harbor),
+            _ => None,
+        })
+        .choose(&mut ctx.rng)?;
+    let quay_wpos = harbor.start.as_::<f32>();
+    let pier_wpos =
+        quay_wpos + (harbor.end - harbor.start).as_::<f32>() * ctx.rng.gen_range(0.4..0.8);
+    Some((quay_wpos, pier_wpos))
+}
+
+/// Find the mooring of another harbor, within sailing distance, for a ship to
+/// head for.
+fn find_harbor(ctx: &mut NpcCtx) -> Option<Vec2<f32>> {
+    let wpos = ctx.npc.wpos.xy();
+    let data = ctx.state.data();
+    data.sites
+        .values()
+        .filter_map(|site| ctx.index.sites.get(site.world_site?).site2())
+        .flat_map(|site2| site2.plots())
+        .filter_map(|plot| match plot.kind() {
+            PlotKind::Harbor(harbor) => Some(harbor.mooring.as_::<f32>()),
+            _ => None,
+        })
+        .filter(|mooring| {
+            (MIN_VOYAGE_DIST.powi(2)..MAX_VOYAGE_DIST.powi(2))
+                .contains(&mooring.distance_squared(wpos))
+        })
+        .choose(&mut ctx.rng)
+}
+
+/// Find a wreck that the npc has heard about, near enough to their home to be
+/// worth salvaging, that hasn't already been cleared away.
+fn find_wreck(ctx: &mut NpcCtx) -> Option<Vec2<f32>> {
+    let data = ctx.state.data();
+    let home_wpos = data.sites.get(ctx.npc.home?)?.wpos.as_::<f32>();
+    ctx.known_reports
+        .iter()
+        .filter_map(|report| match data.reports.get(*report)?.kind {
+            ReportKind::Wreck { wpos, .. } => Some(wpos.xy()),
+            _ => None,
+        })
+        .filter(|wpos| wpos.distance_squared(home_wpos) < SALVAGE_RANGE.powi(2))
+        .filter(|wpos| {
+            data.wrecks
+                .values()
+                .any(|wreck| wreck.wpos.xy().distance_squared(*wpos) < wrecks::WRECK_RADIUS.powi(2))
+        })
+        .choose(&mut ctx.rng)
+}
+
+/// The resources of the land that a profession makes its living from
+fn profession_resources(profession: Profession) -> Option<&'static [ChunkResource]> {
+    match profession {
+        Profession::Herbalist => Some(INGREDIENTS),
+        Profession::Hunter => Some(&[ChunkResource::Game]),
+        _ => None,
+    }
+}
+
+/// The average abundance of the given resources in the land around a position
+fn land_abundance(data: &Data, wpos: Vec2<i32>, resources: &[ChunkResource]) -> f32 {
+    let chunk_pos = wpos.wpos_to_cpos();
+    let total = Spiral2d::new()
+        .take(LAND_CHUNKS)
+        .map(|rpos| {
+            let res = data.nature.get_chunk_resources(chunk_pos + rpos);
+            resources.iter().map(|kind| res[*kind]).sum::<f32>()
+        })
+        .sum::<f32>();
+    total / (LAND_CHUNKS * resources.len().max(1)) as f32
+}
+
+/// A piece of news for a town crier to announce: a raid on the town or one of
+/// its neighbours, a bounty on a monster that killed one of its residents, an
+/// upcoming festival, or word of a site further afield.
+fn world_news(ctx: &mut NpcCtx) -> Option<Content> {
+    let data = ctx.state.data();
+    let index = ctx.index;
+    let home_id = ctx.npc.home?;
+    let home = data.sites.get(home_id)?;
+    let site_name = |site: &crate::data::Site| {
+        site.world_site
+            .map(|ws| Content::Plain(index.sites.get(ws).name().to_string()))
+    };
+    let npc_wpos = ctx.npc.wpos.xy();
+    let locate = |wpos: Vec2<f32>| {
+        [
+            ("dir", Direction::from_dir(wpos - npc_wpos).localize_npc()),
+            (
+                "dist",
+                Distance::from_length(wpos.distance(npc_wpos) as i32).localize_npc(),
+            ),
+        ]
+    };
+
+    let mut news = Vec::new();
+    for site in core::iter::once(home_id)
+        .chain(home.nearby_sites_by_size.iter().copied())
+        .filter_map(|site| data.sites.get(site))
+    {
+        let Some(name) = site_name(site) else {
+            continue;
+        };
+        if site.last_raid > 0.0 && ctx.time_of_day.0 - site.last_raid < NEWS_TIME {
+            news.push(Content::localized_with_args("npc-speech-crier_raid", [(
+                "site",
+                name.clone(),
+            )]));
+        }
+        if site.festival_upcoming {
+            news.push(Content::localized_with_args("npc-speech-crier_festival", [
+                ("site", name.clone()),
+            ]));
+        }
+    }
+    // The town puts a price on the heads of monsters that killed its residents
+    news.extend(
+        home.known_reports
+            .iter()
+            .filter_map(|report| match data.reports.get(*report)?.kind {
+                ReportKind::Death {
+                    killer: Some(Actor::Npc(killer)),
+                    ..
+                } => data.npcs.get(killer),
+                _ => None,
+            })
+            .filter(|monster| matches!(monster.role, Role::Monster))
+            .map(|monster| {
+                let [dir, dist] = locate(monster.wpos.xy());
+                Content::localized_with_args("npc-speech-crier_bounty", [
+                    ("body", monster.body.localize_npc()),
+                    dir,
+                    dist,
+                ])
+            }),
+    );
+    // Travellers bring word of other sites
+    if let Some(site) = home
+        .nearby_sites_by_size
+        .choose(&mut ctx.rng)
+        .and_then(|site| data.sites.get(*site))
+        && let Some(name) = site_name(site)
+    {
+        let [dir, dist] = locate(site.wpos.as_());
+        news.push(Content::localized_with_args("npc-speech-crier_site", [
+            ("site", name),
+            dir,
+            dist,
+        ]));
+    }
+
+    news.into_iter().choose(&mut ctx.rng)
+}
+
 fn choose_plaza(ctx: &mut NpcCtx, site: SiteId) -> Option<Vec2<f32>> {
     ctx.state
         .data()
@@ -848,8 +1348,9 @@ const WALKING_SPEED: f32 = 0.35;
 
 fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
     choose(move |ctx, state: &mut DefaultState| {
+        let consider_moving = state.move_home_timer.should(ctx);
         // Consider moving home if the home site gets too full
-        if state.move_home_timer.should(ctx)
+        if consider_moving
             && let Some(home) = ctx.npc.home
             && Some(home) == ctx.npc.current_site
             && let Some(home_pop_ratio) = ctx.state.data().sites.get(home)
@@ -892,8 +1393,60 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                 .then(travel_to_site(new_home, 0.5))
                 .then(just(move |ctx, _| ctx.controller.set_new_home(new_home))));
         }
+        // Consider moving home if the land around the home site can no longer support our
+        // profession
+        if consider_moving
+            && let Some(home) = ctx.npc.home
+            && Some(home) == ctx.npc.current_site
+            && let Some(resources) = ctx.npc.profession().and_then(profession_resources)
+            && let Some(home_wpos) = ctx.state.data().sites.get(home).map(|site| site.wpos)
+            && land_abundance(&ctx.state.data(), home_wpos, resources) < DEPLETED_LAND
+            && let Some(new_home) = ctx
+                .state
+                .data()
+                .sites
+                .iter()
+                .filter(|(site_id, _)| Some(*site_id) != ctx.npc.home)
+                // Only consider towns as potential homes
+                .filter(|(_, site)| matches!(
+                    site.world_site.map(|ws| &ctx.index.sites.get(ws).kind),
+                    Some(SiteKind::Refactor(_)
+                        | SiteKind::CliffTown(_)
+                        | SiteKind::SavannahPit(_)
+                        | SiteKind::CoastalTown(_)
+                        | SiteKind::DesertCity(_)),
+                ))
+                .filter(|(_, site)| land_abundance(&ctx.state.data(), site.wpos, resources) >= ABUNDANT_LAND)
+                .min_by_key(|(_, site)| site.wpos.as_().distance(ctx.npc.wpos.xy()) as i32)
+                .map(|(site_id, _)| site_id)
+        {
+            let site_name = ctx.state.data().sites[new_home].world_site
+                .map(|ws| ctx.index.sites.get(ws).name().to_string());
+            return important(just(move |ctx, _| {
+                if let Some(site_name) = &site_name {
+                    ctx.controller.say(None, Content::localized_with_args("npc-speech-migrating_depleted", [("site", site_name.clone())]))
+                }
+            })
+                .then(travel_to_site(new_home, 0.5))
+                .then(just(move |ctx, _| ctx.controller.set_new_home(new_home))));
+        }
         let day_period = DayPeriod::from(ctx.time_of_day.0);
         let is_weekend = ctx.time_of_day.day() as u64 % 6 == 0;
+        // Join the celebrations in the plaza while the site holds a festival, day and
+        // night
+        if !matches!(ctx.npc.profession(), Some(Profession::Guard))
+            && is_festival(ctx, visiting_site)
+            && let Some(plaza_wpos) = choose_plaza(ctx, visiting_site)
+        {
+            let wait_time = ctx.rng.gen_range(120.0..300.0);
+            return casual(
+                just(|ctx, _| ctx.controller.say(None, Content::localized("npc-speech-festival_join")))
+                    .then(travel_to_point(plaza_wpos, 0.5))
+                    .debug(|| "walk to festival")
+                    .then(celebrate(visiting_site).stop_if(timeout(wait_time)))
+                    .map(|_, _| ()),
+            );
+        }
         if day_period.is_dark()
             && !matches!(ctx.npc.profession(), Some(Profession::Guard))
         {
@@ -1068,6 +1621,24 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                 return fun_stuff.swap_remove(i);
             }
         }
+        // Word of a wreck nearby draws villagers out to pick over its cargo
+        else if ctx.rng.gen_bool(0.15)
+            && let Some(wreck_wpos) = find_wreck(ctx)
+        {
+            return casual(
+                just(|ctx, _| ctx.controller.say(None, Content::localized("npc-speech-salvage")))
+                    .then(travel_to_point(wreck_wpos, 0.6))
+                    .debug(|| "walk to wreck")
+                    .then({
+                        let wait_time = ctx.rng.gen_range(60.0..120.0);
+                        just(|ctx, _| ctx.controller.do_gather(wrecks::SALVAGE))
+                            .repeat()
+                            .stop_if(timeout(wait_time))
+                    })
+                    .debug(|| "salvage wreck")
+                    .map(|_, _| ()),
+            );
+        }
         // Villagers with roles should perform those roles
         else if matches!(ctx.npc.profession(), Some(Profession::Herbalist)) && ctx.rng.gen_bool(0.8)
         {
@@ -1083,14 +1654,15 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                 );
             }
         } else if matches!(ctx.npc.profession(), Some(Profession::Hunter)) && ctx.rng.gen_bool(0.8) {
-            if let Some(forest_wpos) = find_forest(ctx) {
+            // Hunters follow the herds, falling back to whatever lives in the forest
+            if let Some(hunting_wpos) = find_herd(ctx).or_else(|| find_forest(ctx)) {
                 return casual(
                     just(|ctx, _| {
                         ctx.controller
                             .say(None, Content::localized("npc-speech-start_hunting"))
                     })
-                    .then(travel_to_point(forest_wpos, 0.75))
-                    .debug(|| "walk to forest")
+                    .then(travel_to_point(hunting_wpos, 0.75))
+                    .debug(|| "walk to hunting grounds")
                     .then({
                         let wait_time = ctx.rng.gen_range(30.0..60.0);
                         hunt_animals().repeat().stop_if(timeout(wait_time))
@@ -1098,6 +1670,52 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                     .map(|_, _| ()),
                 );
             }
+        } else if let Some(resources) = ctx.npc.profession().and_then(worker_resources)
+            && ctx.rng.gen_bool(0.8)
+        {
+            // Workers keep away from their worksite for a while after a raid
+            let recently_raided = ctx.npc.home
+                .and_then(|home| ctx.state.data().sites.get(home).map(|site| site.last_raid))
+                .map_or(false, |last_raid| last_raid > 0.0 && ctx.time_of_day.0 - last_raid < RAID_FEAR_TIME);
+            if !recently_raided
+                && let Some((worksite_wpos, work_wpos)) = find_worksite(ctx, resources[0])
+            {
+                return casual(
+                    travel_to_point(worksite_wpos, 0.5)
+                        .debug(|| "walk to worksite")
+                        .then(travel_to_point(work_wpos, 0.5))
+                        .debug(|| "walk to work")
+                        .then({
+                            let wait_time = ctx.rng.gen_range(60.0..120.0);
+                            just(move |ctx, _| ctx.controller.do_gather(resources))
+                                .repeat()
+                                .stop_if(timeout(wait_time))
+                        })
+                        .debug(|| "work")
+                        // Haul what was produced back to the worksite
+                        .then(travel_to_point(worksite_wpos, 0.4))
+                        .map(|_, _| ()),
+                );
+            }
+        } else if matches!(ctx.npc.profession(), Some(Profession::Dockworker))
+            && ctx.rng.gen_bool(0.8)
+        {
+            if let Some((quay_wpos, pier_wpos)) = find_pier(ctx) {
+                return casual(
+                    travel_to_point(quay_wpos, 0.5)
+                        .debug(|| "walk to harbor")
+                        .then(travel_to_point(pier_wpos, 0.4))
+                        .debug(|| "walk along pier")
+                        .then({
+                            let wait_time = ctx.rng.gen_range(60.0..120.0);
+                            idle().repeat().stop_if(timeout(wait_time))
+                        })
+                        .debug(|| "load ships")
+                        // Haul the cargo back to the shore
+                        .then(travel_to_point(quay_wpos, 0.4))
+                        .map(|_, _| ()),
+                );
+            }
         } else if matches!(ctx.npc.profession(), Some(Profession::Guard)) && ctx.rng.gen_bool(0.7) {
             if let Some(plaza_wpos) = choose_plaza(ctx, visiting_site) {
                 return casual(
@@ -1142,6 +1760,25 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                 .debug(|| "sell wares")
                 .map(|_, _| ()),
             );
+        } else if matches!(ctx.npc.profession(), Some(Profession::Crier)) && ctx.rng.gen_bool(0.8) {
+            if let Some(plaza_wpos) = choose_plaza(ctx, visiting_site) {
+                return casual(
+                    travel_to_point(plaza_wpos, 0.4)
+                        .debug(|| "walk to plaza")
+                        .then(
+                            just(|ctx, _| {
+                                let news = world_news(ctx)
+                                    .unwrap_or_else(|| Content::localized("npc-speech-crier_no_news"));
+                                ctx.controller.say(None, news);
+                            })
+                            .then(idle().repeat().stop_if(timeout(CRIER_ANNOUNCE_INTERVAL)))
+                            .repeat()
+                            .stop_if(timeout(ctx.rng.gen_range(180.0..360.0))),
+                        )
+                        .debug(|| "announce news")
+                        .map(|_, _| ()),
+                );
+            }
         }
 
         // If nothing else needs doing, walk between plazas and socialize
@@ -1192,7 +1829,10 @@ fn pilot<S: State>(ship: common::comp::ship::Body) -> impl Action<S> {
     // Travel between different towns in a straight line
     now(move |ctx, _| {
         let data = &*ctx.state.data();
-        let station_wpos = data
+        let weather = ctx.state.resource::<WeatherGrid>();
+        let wpos = ctx.npc.wpos.xy();
+        // Steer clear of routes that would take us through a storm
+        let stations = data
             .sites
             .iter()
             .filter(|(id, _)| Some(*id) != ctx.npc.current_site)
@@ -1202,8 +1842,9 @@ fn pilot<S: State>(ship: common::comp::ship::Body) -> impl Action<S> {
                     .filter(|plot| matches!(plot.kind(), PlotKind::AirshipDock(_)))
                     .map(|plot| site.tile_center_wpos(plot.root_tile()))
             })
-            .choose(&mut ctx.rng);
-        if let Some(station_wpos) = station_wpos {
+            .filter(|station_wpos| !route_is_stormy(&weather, wpos, station_wpos.as_()))
+            .collect::<Vec<_>>();
+        if let Some(station_wpos) = stations.choose(&mut ctx.rng).copied() {
             Either::Right(
                 goto_2d_flying(
                     station_wpos.as_(),
@@ -1220,10 +1861,104 @@ fn pilot<S: State>(ship: common::comp::ship::Body) -> impl Action<S> {
                     32.0,
                     16.0,
                     30.0,
-                )),
+                ))
+                .then(wait_out_storm()),
             )
         } else {
-            Either::Left(finish())
+            // Nowhere to go without flying through a storm, so stay put for now
+            Either::Left(idle().repeat().stop_if(timeout(60.0)).map(|_, _| ()))
+        }
+    })
+    .repeat()
+    .map(|_, _| ())
+}
+
+/// Whether a storm is raging anywhere along the straight route between two
+/// points.
+fn route_is_stormy(weather: &WeatherGrid, from: Vec2<f32>, to: Vec2<f32>) -> bool {
+    let steps = (from.distance(to) / CELL_SIZE as f32).ceil().max(1.0) as usize;
+    (0..=steps).any(|i| {
+        let wpos = from + (to - from) * (i as f32 / steps as f32);
+        weather.get_interpolated(wpos).get_kind() == WeatherKind::Storm
+    })
+}
+
+/// Stay put until any storm has passed, or until we grow tired of waiting.
+fn wait_out_storm<S: State>() -> impl Action<S> {
+    idle()
+        .repeat()
+        .stop_if(|ctx: &mut NpcCtx| {
+            ctx.state
+                .resource::<WeatherGrid>()
+                .get_interpolated(ctx.npc.wpos.xy())
+                .get_kind()
+                != WeatherKind::Storm
+        })
+        .stop_if(timeout(MAX_STORM_DELAY))
+        .debug(|| "wait out storm")
+        .map(|_, _| ())
+}
+
+/// Find a laden merchant ship within sight of a pirate ship, along with its
+/// captain.
+fn find_prey(ctx: &mut NpcCtx) -> Option<(NpcId, Actor)> {
+    let wpos = ctx.npc.wpos.xy();
+    let data = ctx.state.data();
+    data.npcs
+        .iter()
+        .filter(|(_, ship)| {
+            !ship.is_dead
+                && ship.cargo.is_some()
+                && matches!(ship.body, comp::Body::Ship(comp::ship::Body::Galleon))
+                && ship.wpos.xy().distance_squared(wpos) < PIRATE_SIGHT.powi(2)
+        })
+        .filter_map(|(ship_id, _)| {
+            Some((ship_id, data.npcs.mounts.get_steerer_link(ship_id)?.rider))
+        })
+        .choose(&mut ctx.rng)
+}
+
+fn pirate_captain<S: State>() -> impl Action<S> {
+    // Prowl the waters around the hideout, giving chase to any laden merchant ship
+    // that comes into sight and boarding it once alongside
+    now(|ctx, _| {
+        if let Some((prey_ship, prey_captain)) = find_prey(ctx) {
+            let close_to_prey = move |ctx: &NpcCtx, dist: f32| {
+                ctx.state.data().npcs.get(prey_ship).map_or(false, |prey| {
+                    prey.wpos.xy().distance_squared(ctx.npc.wpos.xy()) < dist.powi(2)
+                })
+            };
+            let getaway = ctx.npc.wpos.xy() + ctx.rng.gen::<Vec2<f32>>().map(|e| e - 0.5) * 1024.0;
+            just(move |ctx, _| {
+                if let Some(prey) = ctx.state.data().npcs.get(prey_ship) {
+                    ctx.controller.do_goto(prey.wpos, 1.0);
+                }
+            })
+            .repeat()
+            .stop_if(move |ctx: &mut NpcCtx| close_to_prey(ctx, sea_trade::BOARDING_DIST * 0.5))
+            .stop_if(timeout(PIRATE_CHASE_TIME))
+            .debug(|| "give chase")
+            .then(just(move |ctx, _| {
+                if close_to_prey(ctx, sea_trade::BOARDING_DIST) {
+                    ctx.controller
+                        .say(prey_captain, Content::localized("npc-speech-pirate_board"));
+                    ctx.controller.attack(prey_captain);
+                }
+            }))
+            .debug(|| "board ship")
+            // Make off before anybody comes to help
+            .then(sail_towards(getaway).repeat().stop_if(timeout(60.0)))
+            .map(|_, _| ())
+            .boxed()
+        } else {
+            let hideout = ctx
+                .npc
+                .home
+                .and_then(|home| ctx.state.data().sites.get(home).map(|site| site.wpos.as_()))
+                .unwrap_or(ctx.npc.wpos.xy());
+            let dest =
+                hideout + ctx.rng.gen::<Vec2<f32>>().map(|e| e - 0.5) * PIRATE_PROWL_DIST * 2.0;
+            sail_towards(dest).debug(|| "prowl").boxed()
         }
     })
     .repeat()
@@ -1231,9 +1966,50 @@ fn pilot<S: State>(ship: common::comp::ship::Body) -> impl Action<S> {
 }
 
 fn captain<S: State>() -> impl Action<S> {
-    // For now just randomly travel the sea
+    // Sail between harbors, staying moored at each for a while. Without a harbor
+    // in reach, just randomly travel the sea.
     now(|ctx, _| {
+        if let Some(mooring) = find_harbor(ctx) {
+            let mooring_wpos = mooring.with_z(
+                ctx.world
+                    .sim()
+                    .get_interpolated(mooring.as_(), |chunk| chunk.water_alt)
+                    .unwrap_or(0.0),
+            );
+            sail_towards(mooring)
+                .repeat()
+                .stop_if(move |ctx: &mut NpcCtx| {
+                    ctx.npc.wpos.xy().distance_squared(mooring)
+                        < (TerrainChunkSize::RECT_SIZE.x as f32 * 2.0).powi(2)
+                })
+                .stop_if(timeout(MAX_VOYAGE_TIME))
+                .debug(|| "sail to harbor")
+                .then(goto(mooring_wpos, 0.4, 5.0))
+                .then({
+                    let wait_time = ctx.rng.gen_range(120.0..300.0);
+                    idle().repeat().stop_if(timeout(wait_time))
+                })
+                .debug(|| "moor")
+                // Don't put out to sea in a storm
+                .then(wait_out_storm())
+                .map(|_, _| ())
+                .boxed()
+        } else {
+            sail_towards(ctx.npc.wpos.xy() + ctx.rng.gen::<Vec2<f32>>().map(|e| e - 0.5) * 1024.0)
+                .boxed()
+        }
+    })
+    .repeat()
+    .map(|_, _| ())
+}
+
+/// Sail to a neighbouring stretch of water, favouring the direction of the
+/// given destination.
+fn sail_towards<S: State>(dest: Vec2<f32>) -> impl Action<S> {
+    now(move |ctx, _| {
         let chunk = ctx.npc.wpos.xy().as_().wpos_to_cpos();
+        let dest_chunk = dest.as_().wpos_to_cpos();
+        let weather = ctx.state.resource::<WeatherGrid>();
         if let Some(chunk) = NEIGHBORS
             .into_iter()
             .map(|neighbor| chunk + neighbor)
@@ -1243,7 +2019,15 @@ fn captain<S: State>() -> impl Action<S> {
                     .get(*neighbor)
                     .map_or(false, |c| c.river.river_kind.is_some())
             })
-            .choose(&mut ctx.rng)
+            // Sail around storms rather than through them
+            .filter(|neighbor| {
+                weather
+                    .get_interpolated(TerrainChunkSize::center_wpos(*neighbor).as_())
+                    .get_kind()
+                    != WeatherKind::Storm
+            })
+            // A little randomness helps ships find their way around headlands
+            .min_by_key(|neighbor| neighbor.distance_squared(dest_chunk) + ctx.rng.gen_range(0..4))
         {
             let wpos = TerrainChunkSize::center_wpos(chunk);
             let wpos = wpos.as_().with_z(
@@ -1257,8 +2041,11 @@ fn captain<S: State>() -> impl Action<S> {
             idle().boxed()
         }
     })
-    .repeat()
-    .map(|_, _| ())
+}
+
+/// Say something aloud, perhaps to someone in particular.
+fn exclaim<S: State>(target: Option<Actor>, phrase: &'static str) -> impl Action<S> {
+    just(move |ctx, _| ctx.controller.say(target, Content::localized(phrase)))
 }
 
 fn check_inbox<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S>> {
@@ -1305,19 +2092,60 @@ fn check_inbox<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S>> {
                             "npc-speech-witness_death"
                         };
                         ctx.known_reports.insert(report_id);
+                        break Some(exclaim(killer, phrase).l().l());
+                    },
+                    Some(ReportKind::Death { .. }) => {}, // We don't care about death
+                    Some(ReportKind::Fire { wpos })
+                        if matches!(&ctx.npc.role, Role::Civilised(_)) =>
+                    {
+                        ctx.known_reports.insert(report_id);
+                        // Guards go and deal with the fire, everyone else runs away from it
+                        let (phrase, tgt) =
+                            if matches!(ctx.npc.profession(), Some(Profession::Guard)) {
+                                ("npc-speech-fire_guard", wpos.xy())
+                            } else {
+                                let away = (ctx.npc.wpos - wpos)
+                                    .xy()
+                                    .try_normalized()
+                                    .unwrap_or_else(Vec2::unit_x);
+                                ("npc-speech-fire", ctx.npc.wpos.xy() + away * 32.0)
+                            };
                         break Some(
                             just(move |ctx, _| {
-                                ctx.controller.say(killer, Content::localized(phrase))
+                                ctx.controller.say(None, Content::localized(phrase))
                             })
+                            .then(goto_2d(tgt, 1.0, 4.0))
+                            .r()
                             .l(),
                         );
                     },
-                    Some(ReportKind::Death { .. }) => {}, // We don't care about death
-                    None => {},                           // Stale report, ignore
+                    Some(ReportKind::Fire { .. }) => {},
+                    Some(ReportKind::Wreck { .. })
+                        if matches!(&ctx.npc.role, Role::Civilised(_)) =>
+                    {
+                        ctx.known_reports.insert(report_id);
+                        break Some(exclaim(None, "npc-speech-witness_wreck").l().l());
+                    },
+                    Some(ReportKind::Wreck { .. }) => {},
+                    None => {}, // Stale report, ignore
                 }
             },
             Some(NpcInput::Report(_)) => {}, // Reports we already know of are ignored
             Some(NpcInput::Interaction(by, subject)) => break Some(talk_to(by, Some(subject)).r()),
+            Some(NpcInput::Haggled { by, accepted }) => {
+                // Striking a bargain leaves a fond memory, but pestering us for discounts we
+                // won't give wears our patience thin
+                // TODO: Don't hard-code sentiment change
+                if accepted {
+                    ctx.sentiments
+                        .toward_mut(by)
+                        .change_by(0.02, Sentiment::POSITIVE);
+                } else {
+                    ctx.sentiments
+                        .toward_mut(by)
+                        .change_by(-0.05, Sentiment::RIVAL);
+                }
+            },
             None => break None,
         }
     }
@@ -1330,12 +2158,28 @@ fn check_for_enemies<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S>> {
     // implementing this means accounting for changes in sentiment (that could
     // suddenly make a nearby actor an enemy) as well as variable NPC tick
     // rates!
-    ctx.state
-        .data()
+    let data = ctx.state.data();
+    // Guards look the other way when actors their faction holds in high regard
+    // commit minor crimes, and only turn on them for severe ones
+    let faction = ctx
+        .npc
+        .faction
+        .filter(|_| matches!(ctx.npc.profession(), Some(Profession::Guard)))
+        .and_then(|faction| data.factions.get(faction));
+    let enemy = data
         .npcs
         .nearby(Some(ctx.npc_id), ctx.npc.wpos, 24.0)
-        .find(|actor| ctx.sentiments.toward(*actor).is(Sentiment::ENEMY))
-        .map(|enemy| just(move |ctx, _| ctx.controller.attack(enemy)))
+        .find(|actor| {
+            let tolerated = faction.map_or(false, |faction| {
+                faction.reputation(*actor).tolerated_by_guards()
+            });
+            ctx.sentiments.toward(*actor).is(if tolerated {
+                Sentiment::VILLAIN
+            } else {
+                Sentiment::ENEMY
+            })
+        });
+    enemy.map(|enemy| just(move |ctx, _| ctx.controller.attack(enemy)))
 }
 
 fn react_to_events<S: State>(ctx: &mut NpcCtx, _: &mut S) -> Option<impl Action<S>> {
@@ -1354,6 +2198,11 @@ fn humanoid() -> impl Action<DefaultState> {
                             body @ comp::ship::Body::DefaultAirship
                             | body @ comp::ship::Body::AirBalloon,
                         ) => important(pilot(body)),
+                        comp::Body::Ship(
+                            comp::ship::Body::SailBoat | comp::ship::Body::Galleon,
+                        ) if matches!(ctx.npc.profession(), Some(Profession::Pirate)) => {
+                            important(pirate_captain())
+                        },
                         comp::Body::Ship(
                             comp::ship::Body::SailBoat | comp::ship::Body::Galleon,
                         ) => important(captain()),
@@ -1368,11 +2217,20 @@ fn humanoid() -> impl Action<DefaultState> {
                 )
             }
         } else {
-            let action = if matches!(
+            let action = if let Some(script) = script::find(ctx) {
+                script::compile(&script.behaviour)
+                    // Pick up the new version of the script if it changes
+                    .stop_if(move |_: &mut NpcCtx| script::is_outdated(&script))
+                    .map(|_, _| ())
+                    .l()
+                    .l()
+                    .l()
+            } else if matches!(
                 ctx.npc.profession(),
                 Some(Profession::Adventurer(_) | Profession::Merchant)
-            ) {
-                adventure().l().l()
+            ) && !is_market_visitor(ctx)
+            {
+                adventure().r().l().l()
             } else if let Some(home) = ctx.npc.home {
                 villager(home).r().l()
             } else {
@@ -1384,6 +2242,69 @@ fn humanoid() -> impl Action<DefaultState> {
     })
 }
 
+fn is_festival(ctx: &NpcCtx, site: SiteId) -> bool {
+    ctx.state
+        .data()
+        .sites
+        .get(site)
+        .is_some_and(|site| site.festival)
+}
+
+/// Dance, cheer and chat with the crowd of a festival, and launch fireworks
+/// once it gets dark
+fn celebrate(visiting_site: SiteId) -> impl Action<DefaultState> {
+    choose(|ctx, _| {
+        if DayPeriod::from(ctx.time_of_day.0).is_dark() && ctx.rng.gen_bool(0.2) {
+            casual(
+                just(|ctx, _| ctx.controller.launch_firework())
+                    .then(
+                        just(|ctx, _| ctx.controller.do_cheer(None))
+                            .repeat()
+                            .stop_if(timeout(6.0)),
+                    )
+                    .map(|_, _| ()),
+            )
+        } else if ctx.rng.gen_bool(0.3) {
+            casual(
+                just(|ctx, _| ctx.controller.do_dance(None))
+                    .repeat()
+                    .stop_if(timeout(8.0))
+                    .map(|_, _| ()),
+            )
+        } else if ctx.rng.gen_bool(0.2) {
+            casual(
+                just(|ctx, _| ctx.controller.do_cheer(None))
+                    .repeat()
+                    .stop_if(timeout(4.0))
+                    .map(|_, _| ()),
+            )
+        } else {
+            casual(
+                socialize()
+                    .repeat()
+                    .stop_if(timeout(10.0))
+                    .map_state(|state: &mut DefaultState| &mut state.socialize_timer)
+                    .map(|_, _| ()),
+            )
+        }
+    })
+    .repeat()
+    .stop_if(move |ctx: &mut NpcCtx| !is_festival(ctx, visiting_site))
+    .map(|_, _| ())
+}
+
+/// Merchants that came to a site for its market day stay there until it's over,
+/// rather than travelling on.
+fn is_market_visitor(ctx: &NpcCtx) -> bool {
+    ctx.npc.home.is_some_and(|home| {
+        ctx.state
+            .data()
+            .sites
+            .get(home)
+            .is_some_and(|site| site.market_visitors.contains(&ctx.npc_id))
+    })
+}
+
 fn bird_large() -> impl Action<DefaultState> {
     now(|ctx, bearing: &mut Vec2<f32>| {
         *bearing = bearing
@@ -1543,6 +2464,50 @@ fn monster() -> impl Action<DefaultState> {
     .map(|_, _| ())
 }
 
+/// Wild animals stick with their herd, following it wherever it migrates
+fn follow_herd() -> impl Action<DefaultState> {
+    now(|ctx, _| {
+        let herd = ctx
+            .npc
+            .herd
+            .and_then(|herd| ctx.state.data().herds.get(herd).cloned());
+        let Some(herd) = herd else {
+            return idle().r();
+        };
+        // Stragglers catch up with the rest of the herd before moving on
+        let goal = if ctx.npc.wpos.xy().distance_squared(herd.wpos) > (HERD_SPREAD * 2.0).powi(2) {
+            herd.wpos
+        } else {
+            herd.dest.unwrap_or(herd.wpos)
+        };
+        let offset =
+            Vec2::new(ctx.rng.gen_range(-1.0..1.0), ctx.rng.gen_range(-1.0..1.0)) * HERD_SPREAD;
+        let wait_time = ctx.rng.gen_range(5.0..15.0);
+        goto_2d(goal + offset, 0.5, 8.0)
+            .then(idle().repeat().stop_if(timeout(wait_time)))
+            .map(|_, _| ())
+            .debug(|| "follow herd")
+            .l()
+    })
+}
+
+/// The nearest grazing herd within a day's hunting trip
+fn find_herd(ctx: &mut NpcCtx) -> Option<Vec2<f32>> {
+    const HUNTING_TRIP_RANGE: f32 = 1024.0;
+    let npc_wpos = ctx.npc.wpos.xy();
+    ctx.state
+        .data()
+        .herds
+        .values()
+        .filter(|herd| !herd.kind.is_predator())
+        .map(|herd| herd.wpos)
+        .filter(|wpos| wpos.distance_squared(npc_wpos) < HUNTING_TRIP_RANGE.powi(2))
+        .min_by(|a, b| {
+            a.distance_squared(npc_wpos)
+                .total_cmp(&b.distance_squared(npc_wpos))
+        })
+}
+
 fn think() -> impl Action<DefaultState> {
     now(|ctx, _| match ctx.npc.body {
         common::comp::Body::Humanoid(_) => humanoid().l().l().l(),
@@ -1554,8 +2519,8 @@ fn think() -> impl Action<DefaultState> {
                 .r()
                 .l(),
             Role::Monster => monster().r().r().l(),
-            Role::Wild => idle().r(),
-            Role::Vehicle => idle().r(),
+            Role::Wild => follow_herd().l().r(),
+            Role::Vehicle => idle().r().r(),
         },
     })
 }
@@ -0,0 +1,161 @@
+//! Simple NPC behaviours that are defined in assets rather than in code.
+//!
+//! Scripts are listed in `common.rtsim.brain_scripts` and apply to NPCs
+//! matching their filters. When an NPC picks up a script, its behaviour is
+//! compiled into an [`Action`] built from the same combinators as the rest of
+//! the AI code. Editing the asset makes NPCs drop the old version of their
+//! script and compile the new one.
+
+use super::{idle, travel_to_point, WALKING_SPEED};
+use crate::ai::{finish, just, now, predicate::timeout, Action, NpcCtx, State};
+use common::{
+    assets::{self, AssetExt},
+    comp::Content,
+    states::emote::EmoteKind,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use vek::*;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BrainScript {
+    /// Names of the professions the script applies to, e.g. `"Merchant"`. An
+    /// empty list matches every profession.
+    #[serde(default)]
+    pub professions: Vec<String>,
+    /// Name of the home site of NPCs the script applies to.
+    #[serde(default)]
+    pub site: Option<String>,
+    pub behaviour: Step,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum Step {
+    /// Stand around for the given number of seconds.
+    Idle(f32),
+    /// Walk to a position, relative to the centre of the NPC's home site.
+    Goto((f32, f32)),
+    /// Walk along the given positions, relative to the centre of the NPC's
+    /// home site.
+    Patrol(Vec<(f32, f32)>),
+    /// Say something, given as a localisation key.
+    Say(String),
+    /// Perform an emote, such as `Wave` or `Bow`.
+    Emote(EmoteKind),
+    /// Perform the steps one after the other.
+    Seq(Vec<Step>),
+    /// Perform the step forever.
+    Repeat(Box<Step>),
+    /// Perform the step only between the given hours of the day, waiting
+    /// otherwise. Hours wrap around midnight if `from` is greater than `to`.
+    During { from: f32, to: f32, step: Box<Step> },
+}
+
+pub struct BrainScripts(pub Vec<Arc<BrainScript>>);
+
+impl From<Vec<BrainScript>> for BrainScripts {
+    fn from(scripts: Vec<BrainScript>) -> Self { Self(scripts.into_iter().map(Arc::new).collect()) }
+}
+
+impl assets::Asset for BrainScripts {
+    type Loader = assets::LoadFrom<Vec<BrainScript>, assets::RonLoader>;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+fn scripts() -> assets::AssetHandle<BrainScripts> {
+    BrainScripts::load_expect("common.rtsim.brain_scripts")
+}
+
+/// Find the first script that applies to the NPC.
+pub fn find(ctx: &NpcCtx) -> Option<Arc<BrainScript>> {
+    let profession = ctx.npc.profession();
+    let site_name = ctx
+        .npc
+        .home
+        .and_then(|home| ctx.state.data().sites.get(home)?.world_site)
+        .map(|ws| ctx.index.sites.get(ws).name().to_string());
+
+    let script = scripts()
+        .read()
+        .0
+        .iter()
+        .find(|script| {
+            (script.professions.is_empty()
+                || profession.as_ref().map_or(false, |profession| {
+                    let name: &'static str = profession.into();
+                    script.professions.iter().any(|p| p == name)
+                }))
+                && script
+                    .site
+                    .as_ref()
+                    .map_or(true, |site| site_name.as_ref() == Some(site))
+        })
+        .cloned();
+    script
+}
+
+/// Whether the script was changed since it was loaded.
+pub fn is_outdated(script: &Arc<BrainScript>) -> bool {
+    !scripts().read().0.iter().any(|s| Arc::ptr_eq(s, script))
+}
+
+/// Compile a step into an action.
+pub fn compile<S: State>(step: &Step) -> Box<dyn Action<S>> {
+    match step {
+        Step::Idle(secs) => idle()
+            .repeat()
+            .stop_if(timeout(*secs as f64))
+            .map(|_, _| ())
+            .boxed(),
+        Step::Goto(offset) => goto_offset(Vec2::from(*offset)).boxed(),
+        Step::Patrol(points) => points
+            .iter()
+            .map(|offset| goto_offset(Vec2::from(*offset)).boxed())
+            .reduce(|a, b| a.then(b).boxed())
+            .unwrap_or_else(|| finish().boxed()),
+        Step::Say(key) => {
+            let content = Content::localized(key);
+            just(move |ctx, _| ctx.controller.say(None, content.clone())).boxed()
+        },
+        Step::Emote(kind) => {
+            let kind = *kind;
+            just(move |ctx, _| ctx.controller.emote(kind)).boxed()
+        },
+        Step::Seq(steps) => steps
+            .iter()
+            .map(compile)
+            .reduce(|a, b| a.then(b).boxed())
+            .unwrap_or_else(|| finish().boxed()),
+        Step::Repeat(step) => compile(step).repeat().map(|_, _| ()).boxed(),
+        Step::During { from, to, step } => {
+            let (from, to) = (*from, *to);
+            idle()
+                .repeat()
+                .stop_if(move |ctx: &mut NpcCtx| is_between(ctx, from, to))
+                .then(compile(step).stop_if(move |ctx: &mut NpcCtx| !is_between(ctx, from, to)))
+                .map(|_, _| ())
+                .boxed()
+        },
+    }
+}
+
+fn goto_offset<S: State>(offset: Vec2<f32>) -> impl Action<S> {
+    now(move |ctx, _| {
+        let origin = ctx
+            .npc
+            .home
+            .and_then(|home| ctx.state.data().sites.get(home).map(|site| site.wpos.as_()))
+            .unwrap_or_else(|| ctx.npc.wpos.xy());
+        travel_to_point(origin + offset, WALKING_SPEED)
+    })
+}
+
+fn is_between(ctx: &NpcCtx, from: f32, to: f32) -> bool {
+    let hour = (ctx.time_of_day.0 / 3600.0).rem_euclid(24.0) as f32;
+    if from <= to {
+        (from..to).contains(&hour)
+    } else {
+        hour >= from || hour < to
+    }
+}
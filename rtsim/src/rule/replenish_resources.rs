@@ -1,13 +1,25 @@
 use crate::{event::OnTick, RtState, Rule, RuleError};
+use common::rtsim::ChunkResource;
 use rand::prelude::*;
 
 pub struct ReplenishResources;
 
 /// Take 1 hour to replenish resources entirely. Makes farming unviable, but
 /// probably still poorly balanced.
-// TODO: Different rates for different resources?
 // TODO: Non-renewable resources?
 pub const REPLENISH_TIME: f32 = 60.0 * 60.0;
+
+/// How long it takes for a resource to replenish entirely.
+pub fn replenish_time(res: ChunkResource) -> f32 {
+    match res {
+        // Forests take a long time to grow back after being cleared
+        ChunkResource::Tree => REPLENISH_TIME * 8.0,
+        // Game wanders back in from the surrounding wilderness
+        ChunkResource::Game => REPLENISH_TIME * 0.5,
+        _ => REPLENISH_TIME,
+    }
+}
+
 /// How many chunks should be replenished per tick?
 // TODO: It should be possible to optimise this be remembering the last
 // modification time for each chunk, then lazily projecting forward using a
@@ -23,15 +35,14 @@ impl Rule for ReplenishResources {
 
             // How much should be replenished for each chosen chunk to hit our target
             // replenishment rate?
-            let replenish_amount = world_size.product() as f32
-                * ctx.event.dt
-                * (1.0 / REPLENISH_TIME / REPLENISH_PER_TICK as f32);
+            let replenish_amount =
+                world_size.product() as f32 * ctx.event.dt * (1.0 / REPLENISH_PER_TICK as f32);
             for _ in 0..REPLENISH_PER_TICK {
                 let key = world_size.map(|e| thread_rng().gen_range(0..e as i32));
 
                 let mut res = data.nature.get_chunk_resources(key);
-                for (_, res) in &mut res {
-                    *res = (*res + replenish_amount).clamp(0.0, 1.0);
+                for (kind, res) in &mut res {
+                    *res = (*res + replenish_amount / replenish_time(kind)).clamp(0.0, 1.0);
                 }
                 data.nature.set_chunk_resources(key, res);
             }
@@ -1,6 +1,6 @@
 use crate::{
     data::{report::ReportKind, Report},
-    event::{EventCtx, OnDeath},
+    event::{EventCtx, OnDeath, OnFire},
     RtState, Rule, RuleError,
 };
 use common::rtsim::NpcInput;
@@ -10,6 +10,7 @@ pub struct ReportEvents;
 impl Rule for ReportEvents {
     fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
         rtstate.bind::<Self, OnDeath>(on_death);
+        rtstate.bind::<Self, OnFire>(on_fire);
 
         Ok(Self)
     }
@@ -45,3 +46,28 @@ fn on_death(ctx: EventCtx<ReportEvents, OnDeath>) {
         }
     }
 }
+
+fn on_fire(ctx: EventCtx<ReportEvents, OnFire>) {
+    let data = &mut *ctx.state.data_mut();
+
+    let nearby = data
+        .npcs
+        .nearby(None, ctx.event.wpos, 48.0)
+        .filter_map(|actor| actor.npc())
+        .collect::<Vec<_>>();
+
+    if !nearby.is_empty() {
+        let report = data.reports.create(Report {
+            kind: ReportKind::Fire {
+                wpos: ctx.event.wpos,
+            },
+            at: data.time_of_day,
+        });
+
+        for npc_id in nearby {
+            if let Some(npc) = data.npcs.get_mut(npc_id) {
+                npc.inbox.push_back(NpcInput::Report(report));
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Factions judge characters by what their members think of them: every so
+//! often, the sentiments members feel toward characters are averaged into the
+//! faction's own sentiments, from which [`ReputationTier`]s are derived.
+//!
+//! [`ReputationTier`]: common::rtsim::ReputationTier
+
+use crate::{data::sentiment::Target, event::OnTick, RtState, Rule, RuleError};
+use common::{character::CharacterId, rtsim::FactionId};
+use hashbrown::HashMap;
+
+/// Reputations change slowly, so there's no need to aggregate them every tick
+const REPUTATION_TICK_SKIP: u64 = 60;
+/// How many members of a faction need to hold an opinion of a character before
+/// it's fully reflected in the faction's reputation. Until then, word of mouth
+/// hasn't spread far enough.
+const WORD_OF_MOUTH: f32 = 5.0;
+
+pub struct Reputation;
+
+impl Rule for Reputation {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnTick>(|ctx| {
+            if ctx.event.tick % REPUTATION_TICK_SKIP != 0 {
+                return;
+            }
+            let data = &mut *ctx.state.data_mut();
+
+            let mut opinions = HashMap::<(FactionId, CharacterId), (f32, u32)>::new();
+            for npc in data.npcs.values() {
+                let Some(faction) = npc.faction else {
+                    continue;
+                };
+                for (target, sentiment) in npc.sentiments.iter() {
+                    if let Target::Character(character) = target {
+                        let (sum, count) = opinions.entry((faction, character)).or_default();
+                        *sum += sentiment.value();
+                        *count += 1;
+                    }
+                }
+            }
+
+            for (faction_id, faction) in data.factions.iter_mut() {
+                // Characters that have been forgotten by every member
+                let forgotten = faction
+                    .sentiments
+                    .iter()
+                    .filter_map(|(target, _)| match target {
+                        Target::Character(character)
+                            if !opinions.contains_key(&(faction_id, character)) =>
+                        {
+                            Some(character)
+                        },
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                for character in forgotten {
+                    faction.sentiments.set(character, 0.0);
+                }
+            }
+
+            for ((faction, character), (sum, count)) in opinions {
+                if let Some(faction) = data.factions.get_mut(faction) {
+                    let spread = (count as f32 / WORD_OF_MOUTH).min(1.0);
+                    faction
+                        .sentiments
+                        .set(character, sum / count as f32 * spread);
+                }
+            }
+        });
+
+        Ok(Self)
+    }
+}
@@ -0,0 +1,60 @@
+//! Tracks between sites wear in as people travel along them, upgrading from
+//! dirt to gravel and eventually to paved roads, while tracks that nobody uses
+//! any more slowly grass over.
+
+use crate::{
+    event::{EventCtx, OnSetup, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::{
+    comp::Body,
+    rtsim::{NpcActivity, Role},
+    terrain::CoordinateConversions,
+};
+
+/// Roads change slowly, so there's no need to update them every tick
+const ROAD_TICK_SKIP: u64 = 60;
+/// How long it takes for the wear of a track to mostly fade once nobody
+/// travels along it any more (in seconds)
+const ROAD_DECAY_TIME: f32 = 4.0 * 60.0 * 60.0;
+/// Length (in chunks) of a track that gets worn in to dirt by a single
+/// traveller walking along it at all times. Longer tracks need more travellers
+/// to wear in just as much.
+const REFERENCE_TRACK_LEN: f32 = 16.0;
+
+pub struct Roads;
+
+impl Rule for Roads {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnSetup>(|ctx| ctx.state.data_mut().roads.link(ctx.world));
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self)
+    }
+}
+
+fn on_tick(ctx: EventCtx<Roads, OnTick>) {
+    if ctx.event.tick % ROAD_TICK_SKIP != 0 {
+        return;
+    }
+    let dt = ctx.event.dt * ROAD_TICK_SKIP as f32;
+    let data = &mut *ctx.state.data_mut();
+
+    let decay = (1.0 - dt / ROAD_DECAY_TIME).max(0.0);
+    for wear in data.roads.all_wear_mut() {
+        *wear *= decay;
+    }
+
+    let tracks = &ctx.world.civs().tracks;
+    for npc in data.npcs.values().filter(|npc| {
+        !npc.is_dead
+            && matches!(npc.role, Role::Civilised(_))
+            && !matches!(npc.body, Body::Ship(_))
+            && matches!(npc.controller.activity, Some(NpcActivity::Goto(..)))
+    }) {
+        if let Some(track) = data.roads.track_at(npc.wpos.xy().as_().wpos_to_cpos()) {
+            let track_len = tracks.get(track).path().len().max(1) as f32;
+            *data.roads.wear_mut(track) += dt * REFERENCE_TRACK_LEN / (track_len * ROAD_DECAY_TIME);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Merchant ships carry cargo between the harbors of coastal sites: each time a
+//! ship moors at a harbor it delivers whatever it loaded elsewhere, bringing
+//! wealth to the site, and takes on a new cargo drawn from the site's
+//! stockpile (see [`crate::rule::industry`]). Pirates prey on the sea lanes,
+//! boarding merchant ships that they come alongside and carrying off their
+//! cargo.
+
+use crate::{
+    data::{npc::Cargo, Data, NpcId, Site},
+    event::{EventCtx, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::{
+    comp::{self, Body},
+    rtsim::{Actor, Profession, SiteId},
+};
+use rand::prelude::*;
+use world::site2::PlotKind;
+
+/// Ships move slowly compared to a tick, so there's no need to check for
+/// arrivals every tick
+const SEA_TRADE_TICK_SKIP: u64 = 20;
+/// How close a ship must be to a harbor's mooring to load and unload
+const MOORING_RANGE: f32 = 48.0;
+/// How much of its site's stockpile a ship loads at once
+const CARGO_SIZE: f32 = 5.0;
+/// Wealth brought to a site by a full cargo
+const CARGO_WEALTH: f32 = 0.03;
+/// How close a pirate ship must be to a merchant ship to board it
+pub const BOARDING_DIST: f32 = 12.0;
+/// The chance that a simulated boarding goes the way of the pirates
+const BOARDING_SUCCESS_CHANCE: f64 = 0.6;
+
+pub struct SeaTrade;
+
+impl Rule for SeaTrade {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self)
+    }
+}
+
+fn on_tick(ctx: EventCtx<SeaTrade, OnTick>) {
+    if ctx.event.tick % SEA_TRADE_TICK_SKIP != 0 {
+        return;
+    }
+    let data = &mut *ctx.state.data_mut();
+
+    let moorings = data
+        .sites
+        .iter()
+        .filter_map(|(site_id, site)| {
+            Some((site_id, ctx.index.sites.get(site.world_site?).site2()?))
+        })
+        .flat_map(|(site_id, site2)| {
+            site2.plots().filter_map(move |plot| match plot.kind() {
+                PlotKind::Harbor(harbor) => Some((site_id, harbor.mooring.as_::<f32>())),
+                _ => None,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let ships = data
+        .npcs
+        .iter()
+        .filter(|(_, npc)| {
+            !npc.is_dead && matches!(npc.body, Body::Ship(comp::ship::Body::Galleon))
+        })
+        .filter(|(ship_id, _)| {
+            // Pirate ships don't trade
+            data.npcs
+                .mounts
+                .get_steerer_link(*ship_id)
+                .and_then(|link| link.rider.npc())
+                .and_then(|captain| data.npcs.get(captain))
+                .map_or(false, |captain| {
+                    matches!(captain.profession(), Some(Profession::Captain))
+                })
+        })
+        .filter_map(|(ship_id, ship)| {
+            let (site_id, _) = moorings.iter().find(|(_, mooring)| {
+                mooring.distance_squared(ship.wpos.xy()) < MOORING_RANGE.powi(2)
+            })?;
+            Some((ship_id, *site_id))
+        })
+        .collect::<Vec<_>>();
+
+    for (ship_id, site_id) in ships {
+        let Some(ship) = data.npcs.get_mut(ship_id) else {
+            continue;
+        };
+        if ship
+            .cargo
+            .as_ref()
+            .map_or(false, |cargo| cargo.from == site_id)
+        {
+            continue;
+        }
+        let delivered = ship.cargo.take();
+        let Some(site) = data.sites.get_mut(site_id) else {
+            continue;
+        };
+        if let Some(cargo) = delivered {
+            site.wealth = (site.wealth + cargo.value).min(1.0);
+        }
+        ship.cargo = Some(load_cargo(site_id, site));
+    }
+}
+
+/// Load a ship with a cargo drawn from the site's stockpile. Even sites with
+/// nothing stockpiled have some goods to send abroad.
+pub fn load_cargo(site_id: SiteId, site: &mut Site) -> Cargo {
+    let total = site.stockpile.values().sum::<f32>();
+    let loaded = total.min(CARGO_SIZE);
+    if total > 0.0 {
+        for (_, amount) in &mut site.stockpile {
+            *amount *= 1.0 - loaded / total;
+        }
+    }
+    Cargo {
+        from: site_id,
+        value: CARGO_WEALTH * (0.5 + 0.5 * loaded / CARGO_SIZE),
+    }
+}
+
+/// Resolve a pirate's attempt to board the ship steered by `target` while
+/// neither ship is loaded. If the pirates win the fight, they carry off the
+/// cargo; otherwise they're driven off empty-handed.
+pub fn resolve_boarding(data: &mut Data, rng: &mut impl Rng, pirate: NpcId, target: Actor) {
+    let ship_of = |captain: Actor| {
+        data.npcs
+            .mounts
+            .get_mount_link(captain)
+            .filter(|link| link.is_steering)
+            .map(|link| link.mount)
+    };
+    let (Some(pirate_ship), Some(target_ship)) = (ship_of(Actor::Npc(pirate)), ship_of(target))
+    else {
+        return;
+    };
+    let alongside = data
+        .npcs
+        .get(pirate_ship)
+        .zip(data.npcs.get(target_ship))
+        .map_or(false, |(a, b)| {
+            a.wpos.xy().distance_squared(b.wpos.xy()) < BOARDING_DIST.powi(2)
+        });
+    if alongside && rng.gen_bool(BOARDING_SUCCESS_CHANCE) {
+        let plunder = data
+            .npcs
+            .get_mut(target_ship)
+            .and_then(|ship| ship.cargo.take());
+        if let Some(plunder) = plunder
+            && let Some(ship) = data.npcs.get_mut(pirate_ship)
+        {
+            ship.cargo = Some(plunder);
+        }
+    }
+}
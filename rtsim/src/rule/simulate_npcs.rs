@@ -1,14 +1,16 @@
 use crate::{
     data::{npc::SimulationMode, Npc},
     event::{EventCtx, OnDeath, OnMountVolume, OnTick},
+    rule::{sea_trade, wrecks},
     RtState, Rule, RuleError,
 };
 use common::{
     comp::{self, Body},
     mounting::{Volume, VolumePos},
-    rtsim::{Actor, NpcAction, NpcActivity, Personality},
+    rtsim::{Actor, ChunkResource, NpcAction, NpcActivity, Personality},
     terrain::{CoordinateConversions, TerrainChunkSize},
     vol::RectVolSize,
+    weather::{WeatherGrid, WeatherKind},
 };
 use rand::prelude::*;
 use rand_chacha::ChaChaRng;
@@ -17,6 +19,14 @@ use tracing::{error, warn};
 use vek::{Clamp, Vec2};
 use world::{site::SiteKind, CONFIG};
 
+/// Fraction of a resource in a chunk that a gathering or hunting NPC depletes
+/// per second
+const GATHER_RATE: f32 = 0.0005;
+/// How much of the wind's speed carries ships caught in a storm off course
+const STORM_DRIFT: f32 = 0.25;
+/// The chance per second of an airship caught in a storm being brought down
+const STORM_CRASH_CHANCE: f64 = 0.002;
+
 pub struct SimulateNpcs;
 
 impl Rule for SimulateNpcs {
@@ -53,6 +63,12 @@ fn on_death(ctx: EventCtx<SimulateNpcs, OnDeath>) {
 
     if let Actor::Npc(npc_id) = ctx.event.actor {
         if let Some(npc) = data.npcs.get(npc_id) {
+            // Lairs and herds are replenished by the necromancy and wildlife rules
+            // instead
+            if npc.is_undead() || npc.herd.is_some() {
+                return;
+            }
+
             let mut rng = ChaChaRng::from_seed(thread_rng().gen::<[u8; 32]>());
 
             // Respawn dead NPCs
@@ -201,13 +217,21 @@ fn on_tick(ctx: EventCtx<SimulateNpcs, OnTick>) {
         }
     }
 
+    let weather = ctx.state.resource::<WeatherGrid>();
+    let mut rng = ChaChaRng::from_seed(thread_rng().gen::<[u8; 32]>());
+    let mut boardings = Vec::new();
+    let mut crashes = Vec::new();
     for (npc_id, npc) in data.npcs.npcs.iter_mut().filter(|(_, npc)| !npc.is_dead) {
         if matches!(npc.mode, SimulationMode::Simulated) {
             // Consume NPC actions
             for action in std::mem::take(&mut npc.controller.actions) {
                 match action {
                     NpcAction::Say(_, _) => {}, // Currently, just swallow interactions
-                    NpcAction::Attack(_) => {}, // TODO: Implement simulated combat
+                    // TODO: Implement simulated combat other than boarding ships
+                    NpcAction::Attack(target) => boardings.push((npc_id, target)),
+                    NpcAction::Emote(_)
+                    | NpcAction::LaunchFirework
+                    | NpcAction::GiveDirections(..) => {},
                 }
             }
 
@@ -269,6 +293,40 @@ fn on_tick(ctx: EventCtx<SimulateNpcs, OnTick>) {
                 None => {},
             }
 
+            // Ships caught out in a storm get blown off course, and airships may be
+            // brought down. A downed airship is patched up enough to limp on, but
+            // leaves its wreckage and cargo strewn behind it.
+            if let Body::Ship(ship) = npc.body {
+                let local_weather = weather.get_interpolated(npc.wpos.xy());
+                if local_weather.get_kind() == WeatherKind::Storm {
+                    let new_wpos =
+                        npc.wpos + (local_weather.wind * STORM_DRIFT * ctx.event.dt).with_z(0.0);
+                    match ship {
+                        comp::ship::Body::SailBoat | comp::ship::Body::Galleon => {
+                            // Ships are never blown ashore
+                            if ctx
+                                .world
+                                .sim()
+                                .get(new_wpos.xy().as_().wpos_to_cpos())
+                                .map_or(false, |c| c.river.river_kind.is_some())
+                            {
+                                npc.wpos = new_wpos;
+                            }
+                        },
+                        comp::ship::Body::DefaultAirship | comp::ship::Body::AirBalloon => {
+                            if rng.gen_bool((STORM_CRASH_CHANCE * ctx.event.dt as f64).min(1.0)) {
+                                npc.wpos.z =
+                                    ctx.world.sim().get_surface_alt_approx(npc.wpos.xy().as_());
+                                crashes.push((npc_id, ship, npc.wpos, npc.dir));
+                            } else {
+                                npc.wpos = new_wpos;
+                            }
+                        },
+                        _ => {},
+                    }
+                }
+            }
+
             // Make sure NPCs remain in a valid location
             let clamped_wpos = npc.wpos.xy().clamped(
                 Vec2::zero(),
@@ -306,5 +364,29 @@ fn on_tick(ctx: EventCtx<SimulateNpcs, OnTick>) {
             }
             npc.home = Some(new_home);
         }
+
+        // Gathering and hunting deplete the land around the NPC
+        let gathered: &[ChunkResource] = match npc.controller.activity {
+            Some(NpcActivity::Gather(resources)) => resources,
+            Some(NpcActivity::HuntAnimals) => &[ChunkResource::Game],
+            _ => &[],
+        };
+        if !gathered.is_empty() {
+            let cpos = npc.wpos.xy().as_().wpos_to_cpos();
+            let mut res = data.nature.get_chunk_resources(cpos);
+            for kind in gathered {
+                res[*kind] = (res[*kind] - GATHER_RATE * ctx.event.dt).max(0.0);
+            }
+            data.nature.set_chunk_resources(cpos, res);
+        }
+    }
+
+    // Pirates that have come alongside a ship fight it out with the crew
+    for (pirate, target) in boardings {
+        sea_trade::resolve_boarding(data, &mut rng, pirate, target);
+    }
+
+    for (vehicle, body, wpos, dir) in crashes {
+        wrecks::create_wreck(data, ctx.world, vehicle, body, wpos, dir);
     }
 }
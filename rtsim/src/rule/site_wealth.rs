@@ -0,0 +1,82 @@
+use crate::{
+    data::{
+        site::{RAID_PLUNDER, RAID_WEALTH_LOSS},
+        Site,
+    },
+    event::{EventCtx, HookEvents, OnDeath, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::rtsim::{Actor, RtsimHookEvent};
+
+pub struct SiteWealth;
+
+/// Take 2 hours for a site to recover entirely from being raided to its base
+/// wealth.
+pub const WEALTH_RECOVERY_TIME: f32 = 2.0 * 60.0 * 60.0;
+
+impl Rule for SiteWealth {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnDeath>(on_death);
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self)
+    }
+}
+
+fn on_death(ctx: EventCtx<SiteWealth, OnDeath>) {
+    let data = &mut *ctx.state.data_mut();
+
+    let Some((victim_id, victim)) = ctx
+        .event
+        .actor
+        .npc()
+        .and_then(|npc| Some((npc, data.npcs.get(npc)?)))
+    else {
+        return;
+    };
+    let Some(home) = victim.home else {
+        return;
+    };
+    // Players never belong to a site, so their kills always count as raids
+    let killer_is_resident = match ctx.event.killer {
+        Some(Actor::Npc(killer)) => data.npcs.get(killer).map_or(false, |killer| {
+            killer.home == Some(home)
+                || (killer.faction.is_some() && killer.faction == victim.faction)
+        }),
+        Some(Actor::Character(_)) => false,
+        None => true,
+    };
+
+    if !killer_is_resident && let Some(site) = data.sites.get_mut(home) {
+        site.wealth = (site.wealth - RAID_WEALTH_LOSS).max(0.0);
+        site.last_raid = data.time_of_day.0;
+        for (_, amount) in &mut site.stockpile {
+            *amount *= 1.0 - RAID_PLUNDER;
+        }
+        ctx.state
+            .resource_mut::<HookEvents>()
+            .0
+            .push(RtsimHookEvent::SiteRaided {
+                site: home,
+                victim: victim_id,
+                raider: ctx.event.killer,
+            });
+    }
+}
+
+fn on_tick(ctx: EventCtx<SiteWealth, OnTick>) {
+    let data = &mut *ctx.state.data_mut();
+
+    for site in data.sites.values_mut() {
+        let Some(world_site) = site.world_site else {
+            continue;
+        };
+        let base_wealth = Site::base_wealth(ctx.index.sites.get(world_site));
+        let recovery = ctx.event.dt / WEALTH_RECOVERY_TIME;
+        site.wealth = if site.wealth < base_wealth {
+            (site.wealth + recovery).min(base_wealth)
+        } else {
+            (site.wealth - recovery).max(base_wealth)
+        };
+    }
+}
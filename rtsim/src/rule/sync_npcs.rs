@@ -4,7 +4,7 @@ use crate::{
 };
 use common::{
     grid::Grid,
-    rtsim::{Actor, NpcInput},
+    rtsim::{Actor, NpcInput, Profession},
     terrain::CoordinateConversions,
 };
 
@@ -86,6 +86,7 @@ fn on_tick(ctx: EventCtx<SyncNpcs, OnTick>) {
     let data = &mut *ctx.state.data_mut();
     for (npc_id, npc) in data.npcs.npcs.iter_mut() {
         // Update the NPC's current site, if any
+        let previous_site = npc.current_site;
         npc.current_site = ctx
             .world
             .sim()
@@ -97,6 +98,17 @@ fn on_tick(ctx: EventCtx<SyncNpcs, OnTick>) {
                     .find_map(|site| data.sites.world_site_map.get(site).copied())
             });
 
+        // Travelling merchants deliver goods to the sites they arrive at. NPCs that
+        // don't have a grid cell yet were only just loaded, so they didn't
+        // arrive anywhere.
+        if npc.chunk_pos.is_some()
+            && npc.current_site != previous_site
+            && matches!(npc.profession(), Some(Profession::Merchant))
+            && let Some(site) = npc.current_site.and_then(|site| data.sites.get_mut(site))
+        {
+            site.last_delivery = data.time_of_day.0;
+        }
+
         // Share known reports with current site, if it's our home
         // TODO: Only share new reports
         if let Some(current_site) = npc.current_site
@@ -0,0 +1,267 @@
+//! Wildlife roams the world in herds. Grazing herds migrate between regions
+//! as the seasons change and as they eat their grazing grounds bare, while
+//! wolf packs follow the herds they prey on.
+
+use crate::{
+    data::{
+        herd::HERD_SPREAD, nature::Season, npc::SimulationMode, Data, Herd, HerdId, HerdKind, Npc,
+    },
+    event::{EventCtx, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::{
+    comp::{quadruped_medium, Body},
+    rtsim::{ChunkResource, Role},
+    terrain::CoordinateConversions,
+};
+use hashbrown::HashMap;
+use rand::prelude::*;
+use std::ops::Range;
+use vek::*;
+use world::World;
+
+/// Herds move slowly, so there's no need to update them every tick
+const HERD_TICK_SKIP: u64 = 60;
+/// How many grazing herds roam the world per chunk
+const HERDS_PER_CHUNK: f32 = 1.0 / 16384.0;
+/// There is one wolf pack for this many grazing herds
+const HERDS_PER_PACK: usize = 4;
+const HERD_SIZE: Range<usize> = 3..7;
+const PACK_SIZE: Range<usize> = 2..5;
+/// How far (in chunks) grazing herds look for new grazing grounds
+const MIGRATION_RANGE: i32 = 64;
+/// How many chunks grazing herds consider when looking for new grazing
+/// grounds
+const MIGRATION_CANDIDATES: usize = 24;
+/// How much grazing herds care about the temperature of their grazing grounds
+/// compared to the food that can be found there
+const TEMP_WEIGHT: f32 = 2.0;
+/// Herds closer than this to their destination have arrived
+const ARRIVAL_DIST: f32 = 32.0;
+/// Herds move on once the grass of the chunk they graze drops below this
+const HUNGER_THRESHOLD: f32 = 0.3;
+/// Fraction of the grass of a chunk each member of a herd eats per second
+const GRAZING_RATE: f32 = 0.0002;
+/// How far wolf packs roam in search of prey
+const HUNTING_RANGE: f32 = 4096.0;
+/// Chance per second for a well fed herd that isn't full yet to grow
+const BREEDING_CHANCE: f64 = 0.002;
+
+#[derive(Default)]
+pub struct Wildlife {
+    season: Option<Season>,
+}
+
+impl Rule for Wildlife {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self::default())
+    }
+}
+
+fn preferred_temp(kind: HerdKind, season: Season) -> f32 { kind.preferred_temp() - season.warmth() }
+
+fn grass(data: &Data, wpos: Vec2<f32>) -> f32 {
+    data.nature.get_chunk_resources(wpos.as_().wpos_to_cpos())[ChunkResource::Grass]
+}
+
+/// Choose the most suitable grazing grounds within migration range of the
+/// herd
+fn choose_grazing_grounds(
+    world: &World,
+    data: &Data,
+    herd: &Herd,
+    season: Season,
+    rng: &mut impl Rng,
+) -> Option<Vec2<f32>> {
+    let herd_cpos = herd.wpos.as_().wpos_to_cpos();
+    let temp = preferred_temp(herd.kind, season);
+    (0..MIGRATION_CANDIDATES)
+        .map(|_| {
+            herd_cpos
+                + Vec2::new(
+                    rng.gen_range(-MIGRATION_RANGE..=MIGRATION_RANGE),
+                    rng.gen_range(-MIGRATION_RANGE..=MIGRATION_RANGE),
+                )
+        })
+        .filter_map(|cpos| {
+            let chunk = world.sim().get(cpos).filter(|c| !c.is_underwater())?;
+            let food =
+                data.nature.get_chunk_resources(cpos)[ChunkResource::Grass] * chunk.surface_veg;
+            Some((cpos, food - (chunk.temp - temp).abs() * TEMP_WEIGHT))
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(cpos, _)| cpos.cpos_to_wpos_center().as_())
+}
+
+fn spawn_herd(
+    world: &World,
+    data: &mut Data,
+    kind: HerdKind,
+    wpos: Vec2<f32>,
+    size: Range<usize>,
+    rng: &mut impl Rng,
+) {
+    let herd = data.herds.create(Herd {
+        kind,
+        wpos,
+        dest: None,
+    });
+    for _ in 0..rng.gen_range(size) {
+        spawn_member(world, data, herd, kind, wpos, rng);
+    }
+}
+
+fn spawn_member(
+    world: &World,
+    data: &mut Data,
+    herd: HerdId,
+    kind: HerdKind,
+    wpos: Vec2<f32>,
+    rng: &mut impl Rng,
+) {
+    let wpos = wpos + Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)) * HERD_SPREAD;
+    let body = Body::QuadrupedMedium(quadruped_medium::Body::random_with(rng, &kind.species()));
+    data.spawn_npc(
+        Npc::new(
+            rng.gen(),
+            wpos.with_z(world.sim().get_alt_approx(wpos.as_()).unwrap_or(0.0)),
+            body,
+            Role::Wild,
+        )
+        .with_herd(herd),
+    );
+}
+
+fn on_tick(ctx: EventCtx<Wildlife, OnTick>) {
+    if ctx.event.tick % HERD_TICK_SKIP != 0 {
+        return;
+    }
+    let dt = ctx.event.dt * HERD_TICK_SKIP as f32;
+    let season = Season::from_time_of_day(ctx.event.time_of_day);
+    let season_changed = ctx.rule.season.replace(season) != Some(season);
+    let data = &mut *ctx.state.data_mut();
+    let mut rng = thread_rng();
+
+    // Herds are centred on their living members, herds without any die out
+    let mut members = HashMap::<HerdId, (Vec2<f32>, usize, bool)>::new();
+    for npc in data.npcs.values().filter(|npc| !npc.is_dead) {
+        if let Some(herd) = npc.herd {
+            let (sum, count, loaded) = members.entry(herd).or_default();
+            *sum += npc.wpos.xy();
+            *count += 1;
+            *loaded |= matches!(npc.mode, SimulationMode::Loaded);
+        }
+    }
+    data.herds.retain(|herd_id, herd| {
+        if let Some((sum, count, _)) = members.get(&herd_id) {
+            herd.wpos = *sum / *count as f32;
+            true
+        } else {
+            false
+        }
+    });
+
+    let prey = data
+        .herds
+        .values()
+        .filter(|herd| !herd.kind.is_predator())
+        .map(|herd| herd.wpos)
+        .collect::<Vec<_>>();
+    let herd_ids = data.herds.keys().collect::<Vec<_>>();
+    for herd_id in herd_ids {
+        let herd = &data.herds[herd_id];
+        let (kind, wpos) = (herd.kind, herd.wpos);
+        let (_, count, loaded) = members.get(&herd_id).copied().unwrap_or_default();
+
+        let well_fed = if kind.is_predator() {
+            let hunted = prey
+                .iter()
+                .filter(|prey| prey.distance_squared(wpos) < HUNTING_RANGE.powi(2))
+                .min_by(|a, b| {
+                    a.distance_squared(wpos)
+                        .total_cmp(&b.distance_squared(wpos))
+                })
+                .copied();
+            data.herds[herd_id].dest = hunted;
+            hunted.map_or(false, |hunted| {
+                hunted.distance_squared(wpos) < (HERD_SPREAD * 4.0).powi(2)
+            })
+        } else {
+            let arrived = herd.dest.map_or(true, |dest| {
+                dest.distance_squared(wpos) < ARRIVAL_DIST.powi(2)
+            });
+            let food = grass(data, wpos);
+            if season_changed || (arrived && food < HUNGER_THRESHOLD) {
+                let dest = choose_grazing_grounds(ctx.world, data, herd, season, &mut rng);
+                data.herds[herd_id].dest = dest;
+            } else if arrived {
+                // Graze, leaving the land bare for a while
+                let cpos = wpos.as_().wpos_to_cpos();
+                let mut res = data.nature.get_chunk_resources(cpos);
+                res[ChunkResource::Grass] =
+                    (res[ChunkResource::Grass] - GRAZING_RATE * count as f32 * dt).max(0.0);
+                data.nature.set_chunk_resources(cpos, res);
+            }
+            arrived && food >= HUNGER_THRESHOLD
+        };
+
+        // Herds only grow while nobody is around to see the newcomers appear
+        let max_size = if kind.is_predator() {
+            PACK_SIZE.end
+        } else {
+            HERD_SIZE.end
+        };
+        if well_fed && !loaded && count < max_size && rng.gen_bool(BREEDING_CHANCE * dt as f64) {
+            spawn_member(ctx.world, data, herd_id, kind, wpos, &mut rng);
+        }
+    }
+
+    // Replace herds that died out, one at a time to avoid sudden bursts of
+    // spawning
+    let target_herds =
+        ((ctx.world.sim().get_size().product() as f32 * HERDS_PER_CHUNK).round() as usize).max(1);
+    if prey.len() < target_herds {
+        let land = (0..10)
+            .map(|_| {
+                ctx.world
+                    .sim()
+                    .get_size()
+                    .map(|sz| rng.gen_range(0..sz as i32))
+            })
+            .find_map(|cpos| {
+                Some((
+                    cpos,
+                    ctx.world.sim().get(cpos).filter(|c| !c.is_underwater())?,
+                ))
+            });
+        if let Some((cpos, chunk)) = land {
+            let kind = HerdKind::GRAZERS
+                .into_iter()
+                .min_by(|a, b| {
+                    let temp_diff = |kind| (chunk.temp - preferred_temp(kind, season)).abs();
+                    temp_diff(*a).total_cmp(&temp_diff(*b))
+                })
+                .unwrap_or(HerdKind::Deer);
+            spawn_herd(
+                ctx.world,
+                data,
+                kind,
+                cpos.cpos_to_wpos_center().as_(),
+                HERD_SIZE,
+                &mut rng,
+            );
+        }
+    } else if data
+        .herds
+        .values()
+        .filter(|herd| herd.kind.is_predator())
+        .count()
+        < prey.len() / HERDS_PER_PACK
+        && let Some(hunted) = prey.choose(&mut rng).copied()
+    {
+        let wpos = hunted + Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)) * 256.0;
+        spawn_herd(ctx.world, data, HerdKind::Wolves, wpos, PACK_SIZE, &mut rng);
+    }
+}
@@ -0,0 +1,145 @@
+//! Airships that get destroyed, whether shot down or brought down by a storm,
+//! leave behind a wreck strewn with their cargo. Wrecks are recorded so that
+//! they persist in the world (see [`common::rtsim::ChunkWreck`]), and word of
+//! them spreads through reports, drawing salvage crews out from the towns that
+//! hear of them. Wrecks that nobody gets round to salvaging rot away in time.
+
+use crate::{
+    data::{Data, NpcId, Report, ReportKind, Wreck, WreckId},
+    event::{EventCtx, OnDeath, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::{
+    comp::{self, Body},
+    rtsim::{Actor, ChunkResource, NpcActivity, NpcInput},
+};
+use rand::prelude::*;
+use vek::*;
+use world::World;
+
+/// Wrecks change slowly, so there's no need to update them every tick
+const WRECK_TICK_SKIP: u64 = 30;
+/// How long it takes for a wreck that nobody salvages to rot away (in seconds)
+const WRECK_ROT_TIME: f32 = 4.0 * 60.0 * 60.0;
+/// How much of a wreck each salvager clears away per second
+const SALVAGE_RATE: f32 = 0.001;
+/// At most this many wrecks are kept track of, the oldest being forgotten
+const MAX_WRECKS: usize = 32;
+/// How far the wreckage of an airship is strewn around where it came down
+pub const WRECK_RADIUS: f32 = 16.0;
+/// What salvagers gather from a wreck
+pub const SALVAGE: &[ChunkResource] = &[ChunkResource::Loot];
+
+pub struct Wrecks;
+
+impl Rule for Wrecks {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnDeath>(on_death);
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self)
+    }
+}
+
+fn on_death(ctx: EventCtx<Wrecks, OnDeath>) {
+    let data = &mut *ctx.state.data_mut();
+
+    if let Actor::Npc(npc_id) = ctx.event.actor
+        && let Some(npc) = data.npcs.get(npc_id)
+        && let Body::Ship(body @ (comp::ship::Body::DefaultAirship | comp::ship::Body::AirBalloon)) =
+            npc.body
+    {
+        let wpos = ctx.event.wpos.unwrap_or(npc.wpos);
+        let dir = npc.dir;
+        create_wreck(data, ctx.world, npc_id, body, wpos, dir);
+    }
+}
+
+fn on_tick(ctx: EventCtx<Wrecks, OnTick>) {
+    if ctx.event.tick % WRECK_TICK_SKIP != 0 {
+        return;
+    }
+    let dt = ctx.event.dt * WRECK_TICK_SKIP as f32;
+    let data = &mut *ctx.state.data_mut();
+
+    let salvagers = data
+        .npcs
+        .values()
+        .filter(|npc| {
+            !npc.is_dead
+                && matches!(
+                    npc.controller.activity,
+                    Some(NpcActivity::Gather(&[ChunkResource::Loot]))
+                )
+        })
+        .map(|npc| npc.wpos.xy())
+        .collect::<Vec<_>>();
+    for wreck in data.wrecks.values_mut() {
+        let crew = salvagers
+            .iter()
+            .filter(|wpos| wpos.distance_squared(wreck.wpos.xy()) < WRECK_RADIUS.powi(2))
+            .count();
+        wreck.remains -= (1.0 / WRECK_ROT_TIME + SALVAGE_RATE * crew as f32) * dt;
+    }
+    data.wrecks.retain(|_, wreck| wreck.remains > 0.0);
+}
+
+/// Leave the wreck of an airship where it came down, and let its crew and any
+/// witnesses know about it.
+pub fn create_wreck(
+    data: &mut Data,
+    world: &World,
+    vehicle: NpcId,
+    body: comp::ship::Body,
+    wpos: Vec3<f32>,
+    dir: Vec2<f32>,
+) -> WreckId {
+    let wpos = wpos
+        .xy()
+        .with_z(world.sim().get_surface_alt_approx(wpos.xy().as_()));
+
+    if data.wrecks.len() >= MAX_WRECKS
+        && let Some(oldest) = data
+            .wrecks
+            .iter()
+            .min_by(|(_, a), (_, b)| a.crashed_at.0.total_cmp(&b.crashed_at.0))
+            .map(|(id, _)| id)
+    {
+        data.wrecks.remove(oldest);
+    }
+    let wreck_id = data.wrecks.create(Wreck {
+        body,
+        wpos,
+        dir,
+        seed: thread_rng().gen(),
+        crashed_at: data.time_of_day,
+        remains: 1.0,
+    });
+
+    // Word of a wreck gets around, starting with its crew and any witnesses
+    let report = data.reports.create(Report {
+        kind: ReportKind::Wreck {
+            vehicle: Actor::Npc(vehicle),
+            wpos,
+        },
+        at: data.time_of_day,
+    });
+    let crew = data
+        .npcs
+        .mounts
+        .get_steerer_link(vehicle)
+        .and_then(|link| link.rider.npc());
+    let witnesses = data
+        .npcs
+        .nearby(Some(vehicle), wpos, 48.0)
+        .filter_map(|actor| actor.npc())
+        .chain(crew)
+        .collect::<Vec<_>>();
+    for npc_id in witnesses {
+        if let Some(npc) = data.npcs.get_mut(npc_id) {
+            npc.inbox.push_back(NpcInput::Report(report));
+        }
+    }
+
+    wreck_id
+}
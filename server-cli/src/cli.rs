@@ -3,8 +3,14 @@
 )]
 
 use clap::Parser;
-use common::comp;
-use server::persistence::SqlLogMode;
+use common::comp::{self, Content, LocalizationArg};
+use core::time::Duration;
+use server::{
+    account_link::LinkedAccount,
+    moderation::Ticket,
+    persistence::SqlLogMode,
+    settings::{AccountNote, Announcement, Sanction, SanctionKind, SanctionRecord},
+};
 use std::sync::mpsc::Sender;
 use tracing::error;
 
@@ -79,12 +85,188 @@ pub enum Message {
     SendGlobalMsg {
         msg: String,
     },
+    /// Exports the persisted state of a character to a JSON file in the
+    /// `character_exports` data directory
+    ExportCharacter {
+        /// Database id of the character
+        character_id: i64,
+        /// Name of the export file
+        file: String,
+    },
+    /// Imports a character from a JSON file in the `character_exports` data
+    /// directory
+    ImportCharacter {
+        /// Name of the player that the character is added to
+        username: String,
+        /// Name of the export file
+        file: String,
+    },
+    /// Adds or replaces an announcement that is broadcast to all players at a
+    /// regular interval
+    SetAnnouncement {
+        /// Name of the announcement
+        name: String,
+        /// Localization key of the message
+        key: String,
+        /// Number of seconds between broadcasts
+        interval: u64,
+        /// Arguments of the message, as `name=value`
+        #[arg(value_parser = parse_localization_arg)]
+        args: Vec<(String, String)>,
+    },
+    /// Removes an announcement
+    RemoveAnnouncement {
+        /// Name of the announcement
+        name: String,
+    },
+    /// Lists all announcements
+    ListAnnouncements,
+    /// Lists the reports filed by players
+    ListTickets,
+    /// Marks a report as dealt with, and lets the player that filed it know
+    ResolveTicket {
+        /// Id of the ticket
+        id: u64,
+        /// What was done about it, for other moderators
+        #[arg(default_value = "")]
+        note: String,
+    },
+    /// Shows an announcement as it reads in the given language
+    PreviewAnnouncement {
+        /// Name of the announcement
+        name: String,
+        /// Language identifier, e.g. `en` or `de_DE`
+        #[arg(default_value = i18n::REFERENCE_LANG)]
+        language: String,
+    },
+    /// Lists the accounts with sanctions or moderator notes
+    ListSanctions,
+    /// Shadow mutes a player or puts them on probation
+    SetSanction {
+        /// Name of the player
+        username: String,
+        /// `shadow_mute` or `probation`
+        #[arg(value_parser = parse_sanction_kind)]
+        kind: SanctionKind,
+        /// Reason for the sanction
+        #[arg(default_value = "")]
+        reason: String,
+        /// Number of hours the sanction lasts, until lifted if not given
+        hours: Option<u64>,
+    },
+    /// Lifts a shadow mute or probation from a player
+    LiftSanction {
+        /// Name of the player
+        username: String,
+        /// `shadow_mute` or `probation`
+        #[arg(value_parser = parse_sanction_kind)]
+        kind: SanctionKind,
+    },
+    /// Adds a moderator note to an account
+    AddAccountNote {
+        /// Name of the player
+        username: String,
+        text: String,
+    },
+    /// Lists the accounts linked with a code from `/link_account`
+    ListAccountLinks,
+    /// Redeems a code from `/link_account`, like a bot would
+    RedeemLinkCode {
+        /// The code the player was given
+        code: String,
+        /// Id of the account it is linked to, e.g. a Discord user id
+        external_id: String,
+    },
+    /// Removes the link of an account
+    UnlinkAccount {
+        /// Name of the player
+        username: String,
+    },
+}
+
+/// The name sanctions and notes made from the server console are recorded
+/// under.
+const CONSOLE_AUTHOR: &str = "server console";
+
+fn parse_sanction_kind(kind: &str) -> Result<SanctionKind, String> {
+    kind.parse().map_err(|()| {
+        format!(
+            "Expected one of {:?}, got {:?}",
+            SanctionKind::ALL.map(|kind| kind.as_str()),
+            kind
+        )
+    })
+}
+
+/// Builds a sanction from the arguments of [`Message::SetSanction`].
+pub fn sanction(reason: String, hours: Option<u64>) -> Sanction {
+    let date = chrono::Utc::now();
+    Sanction {
+        reason,
+        performed_by_username: CONSOLE_AUTHOR.to_string(),
+        date,
+        end_date: hours
+            .and_then(|hours| chrono::Duration::try_hours(hours as i64))
+            .and_then(|duration| date.checked_add_signed(duration)),
+    }
+}
+
+/// Builds a note from the arguments of [`Message::AddAccountNote`].
+pub fn account_note(text: String) -> AccountNote {
+    AccountNote {
+        text,
+        author: CONSOLE_AUTHOR.to_string(),
+        date: chrono::Utc::now(),
+    }
+}
+
+fn parse_localization_arg(arg: &str) -> Result<(String, String), String> {
+    arg.split_once('=')
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| {
+            format!(
+                "Expected an argument of the form `name=value`, got {:?}",
+                arg
+            )
+        })
+}
+
+/// Builds an announcement from the arguments of [`Message::SetAnnouncement`].
+/// Arguments that are numbers are passed as such, so that they can be used to
+/// select plural forms.
+pub fn announcement(key: String, interval: u64, args: Vec<(String, String)>) -> Announcement {
+    Announcement {
+        key,
+        args: args
+            .into_iter()
+            .map(|(name, value)| {
+                let arg = match value.parse::<u64>() {
+                    Ok(n) => LocalizationArg::Nat(n),
+                    Err(_) => LocalizationArg::Content(Content::Plain(value)),
+                };
+                (name, arg)
+            })
+            .collect(),
+        interval: Duration::from_secs(interval),
+        enabled: true,
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum MessageReturn {
     Players(Vec<String>),
     Logs(Vec<String>),
+    CharacterTransfer(Result<String, String>),
+    Announcements(Vec<(String, Announcement)>),
+    AnnouncementPreview(Result<String, String>),
+    Tickets(Vec<Ticket>),
+    TicketResolved(Result<(), String>),
+    Sanctions(Vec<(String, SanctionRecord)>),
+    SanctionEdited(Result<(), String>),
+    AccountLinks(Vec<(String, LinkedAccount)>),
+    /// The username of the account that was linked
+    AccountLinked(Result<String, String>),
+    AccountUnlinked(Result<(), String>),
 }
 
 #[derive(Parser)]
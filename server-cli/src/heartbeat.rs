@@ -0,0 +1,119 @@
+//! Opt-in publishing of server metadata, so that community server lists can
+//! show the server without having to scrape it.
+//!
+//! The metadata is refreshed from the server loop, served by the web server at
+//! `/server_info` and, if a master list is configured, periodically posted to
+//! it.
+
+use common_net::msg::WorldFingerprint;
+use serde::{Deserialize, Serialize};
+use server::Server;
+use specs::{Join, WorldExt};
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+
+/// How often the metadata is refreshed from the server.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeartbeatSettings {
+    /// Whether server metadata is collected and served locally.
+    pub enabled: bool,
+    /// URL that the metadata is posted to, if any.
+    pub master_list_url: Option<String>,
+    /// Seconds between two posts to the master list.
+    pub interval_secs: u64,
+}
+
+impl Default for HeartbeatSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            master_list_url: None,
+            interval_secs: 300,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ServerMetadata {
+    pub name: String,
+    pub version: String,
+    pub git_hash: String,
+    pub player_count: u32,
+    pub player_cap: u16,
+    pub uptime_secs: u64,
+    /// Address of the auth server players need an account on, if any.
+    pub auth_server: Option<String>,
+    pub world: WorldFingerprint,
+}
+
+pub type SharedMetadata = Arc<RwLock<Option<ServerMetadata>>>;
+
+pub struct Heartbeat {
+    metadata: SharedMetadata,
+    started: Instant,
+    last_update: Option<Instant>,
+}
+
+impl Heartbeat {
+    pub fn new(metadata: SharedMetadata) -> Self {
+        Self {
+            metadata,
+            started: Instant::now(),
+            last_update: None,
+        }
+    }
+
+    pub fn update(&mut self, server: &Server) {
+        if self
+            .last_update
+            .is_some_and(|last| last.elapsed() < UPDATE_INTERVAL)
+        {
+            return;
+        }
+        self.last_update = Some(Instant::now());
+
+        let info = server.get_server_info();
+        let metadata = ServerMetadata {
+            name: info.name,
+            version: common::util::DISPLAY_VERSION.to_string(),
+            git_hash: info.git_hash,
+            player_count: (&server.state().ecs().read_storage::<common::comp::Player>())
+                .join()
+                .count() as u32,
+            player_cap: server.settings().max_players,
+            uptime_secs: self.started.elapsed().as_secs(),
+            auth_server: info.auth_provider,
+            world: info.world,
+        };
+        // Unwrap is safe, no code that can panic runs while the lock is taken
+        *self.metadata.write().unwrap() = Some(metadata);
+    }
+}
+
+/// Periodically posts the server metadata to the master list.
+pub async fn publish(url: String, interval: Duration, metadata: SharedMetadata) {
+    let client = reqwest::Client::new();
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        let Some(metadata) = metadata.read().unwrap().clone() else {
+            continue;
+        };
+        match client.post(&url).json(&metadata).send().await {
+            Ok(response) if response.status().is_success() => {
+                debug!("Published server metadata to master list")
+            },
+            Ok(response) => warn!(
+                status = ?response.status(),
+                "Master list rejected server metadata"
+            ),
+            Err(e) => warn!(?e, "Failed to publish server metadata to master list"),
+        }
+    }
+}
@@ -12,6 +12,7 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 /// `server-cli` interface commands not to be confused with the commands sent
 /// from the client to the server
 mod cli;
+mod heartbeat;
 mod settings;
 mod shutdown_coordinator;
 mod tui_runner;
@@ -27,20 +28,25 @@ use crate::{
     tuilog::TuiLog,
 };
 use common::{
+    character::CharacterId,
     clock::Clock,
     comp::{ChatType, Player},
     consts::MIN_RECOMMENDED_TOKIO_THREADS,
 };
 use common_base::span;
 use core::sync::atomic::{AtomicUsize, Ordering};
-use server::{persistence::DatabaseSettings, settings::Protocol, Event, Input, Server};
+use server::{
+    persistence::DatabaseSettings,
+    settings::{Protocol, SanctionKind},
+    Event, Input, Server,
+};
 use std::{
     io,
     sync::{atomic::AtomicBool, Arc},
     time::{Duration, Instant},
 };
 use tokio::sync::Notify;
-use tracing::{info, trace};
+use tracing::{error, info, trace, warn};
 
 lazy_static::lazy_static! {
     pub static ref LOG: TuiLog<'static> = TuiLog::default();
@@ -233,6 +239,9 @@ fn main() -> io::Result<()> {
 
     let (web_ui_request_s, web_ui_request_r) = tokio::sync::mpsc::channel(1000);
 
+    let server_metadata = heartbeat::SharedMetadata::default();
+    let server_metadata_clone = Arc::clone(&server_metadata);
+    let web_address = settings.web_address;
     runtime.spawn(async move {
         web::run(
             registry,
@@ -240,12 +249,31 @@ fn main() -> io::Result<()> {
             web_chat_secret,
             ui_api_secret,
             web_ui_request_s,
-            settings.web_address,
+            server_metadata_clone,
+            web_address,
             metrics_shutdown_clone.notified(),
         )
         .await
     });
 
+    if let Some(url) = settings
+        .heartbeat
+        .master_list_url
+        .clone()
+        .filter(|_| settings.heartbeat.enabled)
+    {
+        let interval = Duration::from_secs(settings.heartbeat.interval_secs.max(1));
+        runtime.spawn(heartbeat::publish(
+            url,
+            interval,
+            Arc::clone(&server_metadata),
+        ));
+    }
+    let heartbeat = settings
+        .heartbeat
+        .enabled
+        .then(|| heartbeat::Heartbeat::new(server_metadata));
+
     // Collect addresses that the server is listening to log.
     let gameserver_addresses = protocols_and_addresses
         .into_iter()
@@ -276,6 +304,7 @@ fn main() -> io::Result<()> {
         tui,
         web_ui_request_r,
         shutdown_signal,
+        heartbeat,
     )?;
 
     metrics_shutdown.notify_one();
@@ -283,6 +312,13 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+fn log_character_transfer(result: &Result<String, String>) {
+    match result {
+        Ok(msg) => info!("{}", msg),
+        Err(e) => error!("Character transfer failed: {}", e),
+    }
+}
+
 fn server_loop(
     mut server: Server,
     bench: Option<BenchParams>,
@@ -293,6 +329,7 @@ fn server_loop(
         tokio::sync::oneshot::Sender<MessageReturn>,
     )>,
     shutdown_signal: Arc<AtomicBool>,
+    mut heartbeat: Option<heartbeat::Heartbeat>,
 ) -> io::Result<()> {
     // Set up an fps clock
     let mut clock = Clock::new(Duration::from_secs_f64(1.0 / TPS as f64));
@@ -334,6 +371,10 @@ fn server_loop(
         // Clean up the server after a tick.
         server.cleanup();
 
+        if let Some(heartbeat) = &mut heartbeat {
+            heartbeat.update(&server);
+        }
+
         if tick_no.rem_euclid(1000) == 0 {
             trace!(?tick_no, "keepalive")
         }
@@ -404,6 +445,99 @@ fn server_loop(
                     let msg = ChatType::Meta.into_plain_msg(msg);
                     server.state().send_chat(msg);
                 },
+                Message::ExportCharacter { character_id, file } => {
+                    server.export_character(CharacterId(character_id), &file, move |result| {
+                        log_character_transfer(&result);
+                        let _ = response.send(MessageReturn::CharacterTransfer(result));
+                    });
+                },
+                Message::ImportCharacter { username, file } => {
+                    server.import_character(&username, &file, move |result| {
+                        log_character_transfer(&result);
+                        let _ = response.send(MessageReturn::CharacterTransfer(result));
+                    });
+                },
+                Message::SetAnnouncement {
+                    name,
+                    key,
+                    interval,
+                    args,
+                } => {
+                    server.set_announcement(name, Some(cli::announcement(key, interval, args)));
+                },
+                Message::RemoveAnnouncement { name } => {
+                    server.set_announcement(name, None);
+                },
+                Message::ListAnnouncements => {
+                    let mut announcements: Vec<_> = server
+                        .editable_settings()
+                        .announcements
+                        .iter()
+                        .map(|(name, announcement)| (name.clone(), announcement.clone()))
+                        .collect();
+                    announcements.sort_by(|(a, _), (b, _)| a.cmp(b));
+                    let _ = response.send(MessageReturn::Announcements(announcements));
+                },
+                Message::ListTickets => {
+                    let _ = response.send(MessageReturn::Tickets(server.moderation_tickets()));
+                },
+                Message::ResolveTicket { id, note } => {
+                    let result = server.resolve_moderation_ticket(id, note);
+                    let _ = response.send(MessageReturn::TicketResolved(result));
+                },
+                Message::PreviewAnnouncement { name, language } => {
+                    let content = server
+                        .editable_settings()
+                        .announcements
+                        .get(&name)
+                        .map(|announcement| announcement.content());
+                    let preview = match content {
+                        Some(content) => i18n::LocalizationHandle::load(&language)
+                            .map(|i18n| i18n.read().get_content(&content))
+                            .map_err(|e| format!("Couldn't load language {:?}: {}", language, e)),
+                        None => Err(format!("No announcement named {:?}", name)),
+                    };
+                    let _ = response.send(MessageReturn::AnnouncementPreview(preview));
+                },
+                Message::ListSanctions => {
+                    let mut sanctions: Vec<_> = server
+                        .editable_settings()
+                        .sanctions
+                        .iter()
+                        .map(|(uuid, record)| (uuid.to_string(), record.clone()))
+                        .collect();
+                    sanctions.sort_by(|(_, a), (_, b)| a.username.cmp(&b.username));
+                    let _ = response.send(MessageReturn::Sanctions(sanctions));
+                },
+                Message::SetSanction {
+                    username,
+                    kind,
+                    reason,
+                    hours,
+                } => {
+                    let result =
+                        server.set_sanction(&username, kind, Some(cli::sanction(reason, hours)));
+                    let _ = response.send(MessageReturn::SanctionEdited(result));
+                },
+                Message::LiftSanction { username, kind } => {
+                    let result = server.set_sanction(&username, kind, None);
+                    let _ = response.send(MessageReturn::SanctionEdited(result));
+                },
+                Message::AddAccountNote { username, text } => {
+                    let result = server.add_account_note(&username, cli::account_note(text));
+                    let _ = response.send(MessageReturn::SanctionEdited(result));
+                },
+                Message::ListAccountLinks => {
+                    let _ = response.send(MessageReturn::AccountLinks(server.account_links()));
+                },
+                Message::RedeemLinkCode { code, external_id } => {
+                    let result = server.redeem_account_link(&code, external_id);
+                    let _ = response.send(MessageReturn::AccountLinked(result));
+                },
+                Message::UnlinkAccount { username } => {
+                    let result = server.unlink_account(&username);
+                    let _ = response.send(MessageReturn::AccountUnlinked(result));
+                },
             }
             false
         };
@@ -419,6 +553,75 @@ fn server_loop(
                     match msg_answ {
                         MessageReturn::Players(players) => info!("Players: {:?}", players),
                         MessageReturn::Logs(_) => info!("skipp sending logs to tui"),
+                        // Already logged once the transfer finished
+                        MessageReturn::CharacterTransfer(_) => {},
+                        MessageReturn::Announcements(announcements) => {
+                            for (name, announcement) in announcements {
+                                info!(
+                                    "{}: {} every {}s{}",
+                                    name,
+                                    announcement.key,
+                                    announcement.interval.as_secs(),
+                                    if announcement.enabled {
+                                        ""
+                                    } else {
+                                        " (disabled)"
+                                    }
+                                );
+                            }
+                        },
+                        MessageReturn::AnnouncementPreview(Ok(preview)) => info!("{}", preview),
+                        MessageReturn::AnnouncementPreview(Err(e)) => warn!("{}", e),
+                        MessageReturn::Tickets(tickets) => {
+                            for ticket in tickets.iter().filter(|t| t.resolution.is_none()) {
+                                info!(
+                                    "#{} {}: {} reported {} ({:?}): {}",
+                                    ticket.id,
+                                    ticket.time,
+                                    ticket.reporter.alias,
+                                    ticket.reported.alias,
+                                    ticket.category,
+                                    ticket.comment
+                                );
+                            }
+                        },
+                        MessageReturn::TicketResolved(Ok(())) => info!("Ticket resolved"),
+                        MessageReturn::TicketResolved(Err(e)) => warn!("{}", e),
+                        MessageReturn::Sanctions(sanctions) => {
+                            let now = chrono::Utc::now();
+                            for (uuid, record) in sanctions {
+                                let active: Vec<_> = SanctionKind::ALL
+                                    .into_iter()
+                                    .filter(|kind| {
+                                        record.sanction(*kind).is_some_and(|s| s.is_active(now))
+                                    })
+                                    .map(|kind| kind.as_str())
+                                    .collect();
+                                info!(
+                                    "{} ({}): {:?}, {} notes",
+                                    record.username,
+                                    uuid,
+                                    active,
+                                    record.notes.len()
+                                );
+                            }
+                        },
+                        // Already logged by the server
+                        MessageReturn::SanctionEdited(Ok(())) => {},
+                        MessageReturn::SanctionEdited(Err(e)) => warn!("{}", e),
+                        MessageReturn::AccountLinks(links) => {
+                            for (uuid, linked) in links {
+                                info!(
+                                    "{} ({}): {} since {}",
+                                    linked.username, uuid, linked.external_id, linked.time
+                                );
+                            }
+                        },
+                        // Already logged by the server
+                        MessageReturn::AccountLinked(Ok(_))
+                        | MessageReturn::AccountUnlinked(Ok(())) => {},
+                        MessageReturn::AccountLinked(Err(e))
+                        | MessageReturn::AccountUnlinked(Err(e)) => warn!("{}", e),
                     };
                 }
             }
@@ -1,3 +1,4 @@
+use crate::heartbeat::HeartbeatSettings;
 use serde::{Deserialize, Serialize};
 use std::{
     fs,
@@ -38,6 +39,8 @@ pub struct Settings {
     /// is reachable localhost only (by /ui)
     pub ui_api_secret: Option<String>,
     pub shutdown_signals: Vec<ShutdownSignal>,
+    /// Publishing of server metadata for server lists
+    pub heartbeat: HeartbeatSettings,
 }
 
 impl Default for Settings {
@@ -53,6 +56,7 @@ impl Default for Settings {
             } else {
                 Vec::new()
             },
+            heartbeat: HeartbeatSettings::default(),
         }
     }
 }
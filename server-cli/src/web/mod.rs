@@ -1,5 +1,5 @@
-use crate::web::ui::api::UiRequestSender;
-use axum::{body::Bytes, extract::State, response::IntoResponse, routing::get, Router};
+use crate::{heartbeat::SharedMetadata, web::ui::api::UiRequestSender};
+use axum::{body::Bytes, extract::State, response::IntoResponse, routing::get, Json, Router};
 use core::{future::Future, ops::Deref};
 use http_body_util::Full;
 use hyper::{header, http, StatusCode};
@@ -16,6 +16,7 @@ pub async fn run<S, F, R>(
     chat_secret: Option<String>,
     ui_secret: String,
     web_ui_request_s: UiRequestSender,
+    server_metadata: SharedMetadata,
     addr: S,
     shutdown: F,
 ) -> Result<(), hyper::Error>
@@ -36,7 +37,8 @@ where
         )
         .nest("/ui", ui::router(ui_secret))
         .nest("/metrics", metrics)
-        .route("/health", get(|| async {}));
+        .route("/health", get(|| async {}))
+        .route("/server_info", get(server_info).with_state(server_metadata));
 
     // run it
     let addr = addr.into();
@@ -61,6 +63,19 @@ where
     Ok(())
 }
 
+/// Server metadata for server lists, only available if the heartbeat is
+/// enabled.
+async fn server_info(
+    State(server_metadata): State<SharedMetadata>,
+) -> Result<impl IntoResponse, StatusCode> {
+    server_metadata
+        .read()
+        .unwrap()
+        .clone()
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
 async fn metrics(State(registry): State<Registry>) -> Result<impl IntoResponse, StatusCode> {
     use prometheus::Encoder;
     let mf = registry.gather();
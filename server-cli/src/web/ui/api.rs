@@ -7,8 +7,10 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
+use common::comp::{Content, LocalizationArg};
 use hyper::StatusCode;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use server::settings::SanctionKind;
 use std::{
     collections::HashSet,
     net::{IpAddr, SocketAddr},
@@ -73,6 +75,22 @@ pub fn router(web_ui_request_s: UiRequestSender, secret_token: String) -> Router
         .route("/players", get(players))
         .route("/logs", get(logs))
         .route("/send_global_msg", post(send_global_msg))
+        .route("/export_character", post(export_character))
+        .route("/import_character", post(import_character))
+        .route("/announcements", get(announcements))
+        .route("/set_announcement", post(set_announcement))
+        .route("/remove_announcement", post(remove_announcement))
+        .route("/preview_announcement", post(preview_announcement))
+        .route("/languages", get(languages))
+        .route("/tickets", get(tickets))
+        .route("/resolve_ticket", post(resolve_ticket))
+        .route("/sanctions", get(sanctions))
+        .route("/set_sanction", post(set_sanction))
+        .route("/lift_sanction", post(lift_sanction))
+        .route("/add_note", post(add_note))
+        .route("/account_links", get(account_links))
+        .route("/redeem_link_code", post(redeem_link_code))
+        .route("/unlink_account", post(unlink_account))
         .layer(axum::middleware::from_fn_with_state(ip_addrs, log_users))
         .layer(axum::middleware::from_fn_with_state(token, validate_secret))
         .with_state(web_ui_request_s)
@@ -121,3 +139,391 @@ async fn send_global_msg(
         .await;
     Ok(())
 }
+
+#[derive(Deserialize)]
+struct ExportCharacterBody {
+    character_id: i64,
+    file: String,
+}
+
+async fn export_character(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<ExportCharacterBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    character_transfer(web_ui_request_s, Message::ExportCharacter {
+        character_id: payload.character_id,
+        file: payload.file,
+    })
+    .await
+}
+
+#[derive(Deserialize)]
+struct ImportCharacterBody {
+    username: String,
+    file: String,
+}
+
+async fn import_character(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<ImportCharacterBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    character_transfer(web_ui_request_s, Message::ImportCharacter {
+        username: payload.username,
+        file: payload.file,
+    })
+    .await
+}
+
+async fn character_transfer(
+    web_ui_request_s: UiRequestSender,
+    msg: Message,
+) -> Result<Json<String>, (StatusCode, String)> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s.send((msg, sender)).await;
+    match receiver
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, String::new()))?
+    {
+        MessageReturn::CharacterTransfer(Ok(msg)) => Ok(Json(msg)),
+        MessageReturn::CharacterTransfer(Err(e)) => Err((StatusCode::BAD_REQUEST, e)),
+        _ => Err((StatusCode::INTERNAL_SERVER_ERROR, String::new())),
+    }
+}
+
+/// An announcement, with its arguments as they were entered
+#[derive(Serialize)]
+struct AnnouncementView {
+    name: String,
+    key: String,
+    interval: u64,
+    enabled: bool,
+    args: Vec<(String, String)>,
+}
+
+async fn announcements(
+    State(web_ui_request_s): State<UiRequestSender>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((Message::ListAnnouncements, sender))
+        .await;
+    match receiver
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        MessageReturn::Announcements(announcements) => Ok(Json(
+            announcements
+                .into_iter()
+                .map(|(name, announcement)| AnnouncementView {
+                    name,
+                    key: announcement.key,
+                    interval: announcement.interval.as_secs(),
+                    enabled: announcement.enabled,
+                    args: announcement
+                        .args
+                        .into_iter()
+                        .map(|(name, arg)| {
+                            let value = match arg {
+                                LocalizationArg::Nat(n) => n.to_string(),
+                                LocalizationArg::Content(Content::Plain(text)) => text,
+                                LocalizationArg::Content(content) => format!("{:?}", content),
+                            };
+                            (name, value)
+                        })
+                        .collect(),
+                })
+                .collect::<Vec<_>>(),
+        )),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[derive(Deserialize)]
+struct SetAnnouncementBody {
+    name: String,
+    key: String,
+    interval: u64,
+    #[serde(default)]
+    args: Vec<(String, String)>,
+}
+
+async fn set_announcement(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<SetAnnouncementBody>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (dummy_s, _) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((
+            Message::SetAnnouncement {
+                name: payload.name,
+                key: payload.key,
+                interval: payload.interval,
+                args: payload.args,
+            },
+            dummy_s,
+        ))
+        .await;
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct RemoveAnnouncementBody {
+    name: String,
+}
+
+async fn remove_announcement(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<RemoveAnnouncementBody>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (dummy_s, _) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((Message::RemoveAnnouncement { name: payload.name }, dummy_s))
+        .await;
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct PreviewAnnouncementBody {
+    name: String,
+    language: String,
+}
+
+async fn preview_announcement(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<PreviewAnnouncementBody>,
+) -> Result<Json<String>, (StatusCode, String)> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((
+            Message::PreviewAnnouncement {
+                name: payload.name,
+                language: payload.language,
+            },
+            sender,
+        ))
+        .await;
+    match receiver
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, String::new()))?
+    {
+        MessageReturn::AnnouncementPreview(Ok(preview)) => Ok(Json(preview)),
+        MessageReturn::AnnouncementPreview(Err(e)) => Err((StatusCode::BAD_REQUEST, e)),
+        _ => Err((StatusCode::INTERNAL_SERVER_ERROR, String::new())),
+    }
+}
+
+async fn languages() -> impl IntoResponse { Json(i18n::list_localizations()) }
+
+async fn tickets(
+    State(web_ui_request_s): State<UiRequestSender>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s.send((Message::ListTickets, sender)).await;
+    match receiver
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        MessageReturn::Tickets(tickets) => Ok(Json(tickets)),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[derive(Deserialize)]
+struct ResolveTicketBody {
+    id: u64,
+    #[serde(default)]
+    note: String,
+}
+
+async fn resolve_ticket(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<ResolveTicketBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((
+            Message::ResolveTicket {
+                id: payload.id,
+                note: payload.note,
+            },
+            sender,
+        ))
+        .await;
+    match receiver
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, String::new()))?
+    {
+        MessageReturn::TicketResolved(Ok(())) => Ok(()),
+        MessageReturn::TicketResolved(Err(e)) => Err((StatusCode::BAD_REQUEST, e)),
+        _ => Err((StatusCode::INTERNAL_SERVER_ERROR, String::new())),
+    }
+}
+
+async fn sanctions(
+    State(web_ui_request_s): State<UiRequestSender>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((Message::ListSanctions, sender))
+        .await;
+    match receiver
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        MessageReturn::Sanctions(sanctions) => Ok(Json(sanctions)),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Sends a message that edits sanctions, and turns its reply into a response
+async fn edit_sanctions(
+    web_ui_request_s: UiRequestSender,
+    msg: Message,
+) -> Result<(), (StatusCode, String)> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s.send((msg, sender)).await;
+    match receiver
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, String::new()))?
+    {
+        MessageReturn::SanctionEdited(Ok(())) => Ok(()),
+        MessageReturn::SanctionEdited(Err(e)) => Err((StatusCode::BAD_REQUEST, e)),
+        _ => Err((StatusCode::INTERNAL_SERVER_ERROR, String::new())),
+    }
+}
+
+#[derive(Deserialize)]
+struct SetSanctionBody {
+    username: String,
+    kind: SanctionKind,
+    #[serde(default)]
+    reason: String,
+    hours: Option<u64>,
+}
+
+async fn set_sanction(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<SetSanctionBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    edit_sanctions(web_ui_request_s, Message::SetSanction {
+        username: payload.username,
+        kind: payload.kind,
+        reason: payload.reason,
+        hours: payload.hours,
+    })
+    .await
+}
+
+#[derive(Deserialize)]
+struct LiftSanctionBody {
+    username: String,
+    kind: SanctionKind,
+}
+
+async fn lift_sanction(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<LiftSanctionBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    edit_sanctions(web_ui_request_s, Message::LiftSanction {
+        username: payload.username,
+        kind: payload.kind,
+    })
+    .await
+}
+
+#[derive(Deserialize)]
+struct AddNoteBody {
+    username: String,
+    text: String,
+}
+
+async fn add_note(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<AddNoteBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    edit_sanctions(web_ui_request_s, Message::AddAccountNote {
+        username: payload.username,
+        text: payload.text,
+    })
+    .await
+}
+
+async fn account_links(
+    State(web_ui_request_s): State<UiRequestSender>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((Message::ListAccountLinks, sender))
+        .await;
+    match receiver
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        MessageReturn::AccountLinks(links) => Ok(Json(links)),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[derive(Deserialize)]
+struct RedeemLinkCodeBody {
+    code: String,
+    external_id: String,
+}
+
+#[derive(Serialize)]
+struct RedeemLinkCodeResponse {
+    username: String,
+}
+
+/// Meant for bots that verify players, e.g. on a Discord server
+async fn redeem_link_code(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<RedeemLinkCodeBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((
+            Message::RedeemLinkCode {
+                code: payload.code,
+                external_id: payload.external_id,
+            },
+            sender,
+        ))
+        .await;
+    match receiver
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, String::new()))?
+    {
+        MessageReturn::AccountLinked(Ok(username)) => Ok(Json(RedeemLinkCodeResponse { username })),
+        MessageReturn::AccountLinked(Err(e)) => Err((StatusCode::BAD_REQUEST, e)),
+        _ => Err((StatusCode::INTERNAL_SERVER_ERROR, String::new())),
+    }
+}
+
+#[derive(Deserialize)]
+struct UnlinkAccountBody {
+    username: String,
+}
+
+async fn unlink_account(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(payload): Json<UnlinkAccountBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((
+            Message::UnlinkAccount {
+                username: payload.username,
+            },
+            sender,
+        ))
+        .await;
+    match receiver
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, String::new()))?
+    {
+        MessageReturn::AccountUnlinked(Ok(())) => Ok(()),
+        MessageReturn::AccountUnlinked(Err(e)) => Err((StatusCode::BAD_REQUEST, e)),
+        _ => Err((StatusCode::INTERNAL_SERVER_ERROR, String::new())),
+    }
+}
@@ -91,9 +91,329 @@ async function update_logs() {
     }
 }
 
+async function update_announcements() {
+    const announcements_response = await fetch("/ui_api/v1/announcements");
+    const announcements = await announcements_response.json();
+
+    var announcements_list = document.getElementById("announcements_list");
+    // keep the header row
+    while (announcements_list.rows.length > 1) {
+      announcements_list.deleteRow(1);
+    }
+
+    for (const announcement of announcements) {
+      var row = announcements_list.insertRow();
+      row.insertCell().appendChild(document.createTextNode(announcement.name));
+      row.insertCell().appendChild(document.createTextNode(announcement.key));
+      row.insertCell().appendChild(document.createTextNode(
+        announcement.args.map(([name, value]) => name + "=" + value).join(", ")
+      ));
+      row.insertCell().appendChild(document.createTextNode(announcement.interval));
+
+      var actions = row.insertCell();
+      var preview = document.createElement("input");
+      preview.type = "button";
+      preview.value = "Preview";
+      preview.onclick = () => previewAnnouncement(announcement.name);
+      actions.appendChild(preview);
+      var remove = document.createElement("input");
+      remove.type = "button";
+      remove.value = "Remove";
+      remove.onclick = () => removeAnnouncement(announcement.name);
+      actions.appendChild(remove);
+    }
+}
+
+async function update_languages() {
+    const languages_response = await fetch("/ui_api/v1/languages");
+    const languages = await languages_response.json();
+
+    var preview_language = document.getElementById("preview_language");
+    for (const language of languages) {
+      var option = document.createElement("option");
+      option.value = language.language_identifier;
+      option.text = language.language_name;
+      option.selected = language.language_identifier == "en";
+      preview_language.appendChild(option);
+    }
+}
+
+async function setAnnouncement() {
+    const args = document.getElementById("announcement_args").value
+      .split("\n")
+      .map(line => line.trim())
+      .filter(line => line.includes("="))
+      .map(line => {
+        const i = line.indexOf("=");
+        return [line.slice(0, i).trim(), line.slice(i + 1).trim()];
+      });
+
+    const response = await fetch("/ui_api/v1/set_announcement", {
+        method: "POST",
+        headers: {
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({
+            name: document.getElementById("announcement_name").value,
+            key: document.getElementById("announcement_key").value,
+            interval: parseInt(document.getElementById("announcement_interval").value),
+            args: args
+        })
+    });
+
+    if (response.status == 200) {
+      document.getElementById("announcement_name").value = '';
+      document.getElementById("announcement_key").value = '';
+      document.getElementById("announcement_args").value = '';
+    }
+    await update_announcements();
+}
+
+async function removeAnnouncement(name) {
+    await fetch("/ui_api/v1/remove_announcement", {
+        method: "POST",
+        headers: {
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({
+            name: name
+        })
+    });
+    await update_announcements();
+}
+
+async function previewAnnouncement(name) {
+    const response = await fetch("/ui_api/v1/preview_announcement", {
+        method: "POST",
+        headers: {
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({
+            name: name,
+            language: document.getElementById("preview_language").value
+        })
+    });
+
+    var announcement_preview = document.getElementById("announcement_preview");
+    if (response.status == 200) {
+      announcement_preview.innerText = await response.json();
+    } else {
+      announcement_preview.innerText = await response.text();
+    }
+}
+
+function describeParty(party) {
+    var text = party.alias;
+    if (party.pos) {
+      text += " at " + party.pos.map(Math.round).join(", ");
+    }
+    return text;
+}
+
+function describeChatMessage(msg) {
+    // Only plain messages are shown verbatim, anything else is shown as sent
+    const content = msg.content.Plain !== undefined ? msg.content.Plain : JSON.stringify(msg.content);
+    const [kind, parties] = Object.entries(msg.parties)[0];
+    const first = Array.isArray(parties) ? parties[0] : parties;
+    const sender = first && first.alias ? first.alias : "";
+    return msg.time + " [" + kind + "] " + sender + ": " + content;
+}
+
+async function update_tickets() {
+    const tickets_response = await fetch("/ui_api/v1/tickets");
+    const tickets = await tickets_response.json();
+    const show_resolved = document.getElementById("show_resolved_tickets").checked;
+
+    var tickets_list = document.getElementById("tickets_list");
+    while (tickets_list.lastElementChild) {
+      tickets_list.removeChild(tickets_list.lastElementChild);
+    }
+
+    // newest first
+    for (const ticket of tickets.reverse()) {
+      if (ticket.resolution && !show_resolved) {
+        continue;
+      }
+      var div = document.createElement("div");
+      div.className = "flex-container ticket";
+
+      var summary = document.createElement("p");
+      summary.innerText = "#" + ticket.id + " " + ticket.time + " (" + ticket.category + ")\n"
+        + describeParty(ticket.reporter) + " reported " + describeParty(ticket.reported) + "\n"
+        + ticket.comment;
+      div.appendChild(summary);
+
+      var chat = document.createElement("pre");
+      chat.innerText = ticket.chat_context.map(describeChatMessage).join("\n");
+      div.appendChild(chat);
+
+      if (ticket.resolution) {
+        var resolution = document.createElement("p");
+        resolution.innerText = "Resolved " + ticket.resolution.time + ": " + ticket.resolution.note;
+        div.appendChild(resolution);
+      } else {
+        var note = document.createElement("input");
+        note.type = "text";
+        note.placeholder = "Note";
+        div.appendChild(note);
+        var resolve = document.createElement("input");
+        resolve.type = "button";
+        resolve.value = "Resolve";
+        resolve.onclick = () => resolveTicket(ticket.id, note.value);
+        div.appendChild(resolve);
+      }
+      tickets_list.appendChild(div);
+    }
+}
+
+async function resolveTicket(id, note) {
+    await fetch("/ui_api/v1/resolve_ticket", {
+        method: "POST",
+        headers: {
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({
+            id: id,
+            note: note
+        })
+    });
+    await update_tickets();
+}
+
+const SANCTION_NAMES = { shadow_mute: "Shadow muted", probation: "On probation" };
+
+function describeSanction(name, sanction) {
+    return SANCTION_NAMES[name] + " by " + sanction.performed_by_username + " since " + sanction.date
+      + " until " + (sanction.end_date ? sanction.end_date : "lifted") + ": " + sanction.reason;
+}
+
+async function update_sanctions() {
+    const sanctions_response = await fetch("/ui_api/v1/sanctions");
+    const sanctions = await sanctions_response.json();
+
+    var sanctions_list = document.getElementById("sanctions_list");
+    while (sanctions_list.lastElementChild) {
+      sanctions_list.removeChild(sanctions_list.lastElementChild);
+    }
+
+    for (const [uuid, record] of sanctions) {
+      var div = document.createElement("div");
+      div.className = "flex-container ticket";
+
+      var title = document.createElement("p");
+      title.innerText = record.username + " (" + uuid + ")";
+      div.appendChild(title);
+
+      for (const [name, kind] of [["shadow_mute", "ShadowMute"], ["probation", "Probation"]]) {
+        const sanction = record[name];
+        if (!sanction) {
+          continue;
+        }
+        var line = document.createElement("p");
+        line.innerText = describeSanction(name, sanction);
+        var lift = document.createElement("input");
+        lift.type = "button";
+        lift.value = "Lift";
+        lift.onclick = () => liftSanction(record.username, kind);
+        line.appendChild(lift);
+        div.appendChild(line);
+      }
+
+      if (record.notes.length > 0) {
+        var notes = document.createElement("pre");
+        notes.innerText = record.notes.map(note => note.date + " " + note.author + ": " + note.text).join("\n");
+        div.appendChild(notes);
+      }
+      sanctions_list.appendChild(div);
+    }
+}
+
+async function editSanctions(path, body) {
+    const response = await fetch("/ui_api/v1/" + path, {
+        method: "POST",
+        headers: {
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify(body)
+    });
+
+    document.getElementById("sanction_error").innerText = response.status == 200 ? '' : await response.text();
+    await update_sanctions();
+    return response.status == 200;
+}
+
+async function setSanction() {
+    const hours = parseInt(document.getElementById("sanction_hours").value);
+    if (await editSanctions("set_sanction", {
+        username: document.getElementById("sanction_username").value,
+        kind: document.getElementById("sanction_kind").value,
+        reason: document.getElementById("sanction_reason").value,
+        hours: isNaN(hours) ? null : hours
+    })) {
+      document.getElementById("sanction_reason").value = '';
+      document.getElementById("sanction_hours").value = '';
+    }
+}
+
+async function liftSanction(username, kind) {
+    await editSanctions("lift_sanction", { username: username, kind: kind });
+}
+
+async function addAccountNote() {
+    if (await editSanctions("add_note", {
+        username: document.getElementById("sanction_username").value,
+        text: document.getElementById("sanction_note").value
+    })) {
+      document.getElementById("sanction_note").value = '';
+    }
+}
+
+async function update_account_links() {
+    const links_response = await fetch("/ui_api/v1/account_links");
+    const links = await links_response.json();
+
+    var account_links_list = document.getElementById("account_links_list");
+    // Keep the header row
+    while (account_links_list.rows.length > 1) {
+      account_links_list.deleteRow(1);
+    }
+
+    for (const [uuid, linked] of links) {
+      var row = account_links_list.insertRow();
+      row.insertCell().innerText = linked.username + " (" + uuid + ")";
+      row.insertCell().innerText = linked.external_id;
+      row.insertCell().innerText = linked.time;
+      var unlink = document.createElement("input");
+      unlink.type = "button";
+      unlink.value = "Unlink";
+      unlink.onclick = () => unlinkAccount(linked.username);
+      row.insertCell().appendChild(unlink);
+    }
+}
+
+async function unlinkAccount(username) {
+    await fetch("/ui_api/v1/unlink_account", {
+        method: "POST",
+        headers: {
+          "Content-Type": "application/json",
+        },
+        body: JSON.stringify({
+            username: username
+        })
+    });
+    await update_account_links();
+}
+
 async function loop() {
     await update_players();
     await update_logs();
 }
 
-var loopId = window.setInterval(loop, 1000);
\ No newline at end of file
+var loopId = window.setInterval(loop, 1000);
+window.addEventListener("load", async () => {
+    await update_languages();
+    await update_announcements();
+    await update_tickets();
+    await update_sanctions();
+    await update_account_links();
+});
\ No newline at end of file
@@ -1,7 +1,8 @@
 use crate::{
     consts::{
-        AVG_FOLLOW_DIST, DEFAULT_ATTACK_RANGE, IDLE_HEALING_ITEM_THRESHOLD, MAX_PATROL_DIST,
-        PARTIAL_PATH_DIST, SEPARATION_BIAS, SEPARATION_DIST, STD_AWARENESS_DECAY_RATE,
+        AVG_FOLLOW_DIST, DEFAULT_ATTACK_RANGE, DISGUISE_LINGER_DIST, DISGUISE_PERCEPTION_CHANCE,
+        IDLE_HEALING_ITEM_THRESHOLD, MAX_PATROL_DIST, PARTIAL_PATH_DIST, SEPARATION_BIAS,
+        SEPARATION_DIST, STD_AWARENESS_DECAY_RATE,
     },
     data::{AgentData, AgentEmitters, AttackData, Path, ReadData, Tactic, TargetData},
     util::{
@@ -16,6 +17,7 @@ use common::{
         self,
         ability::BASE_ABILITY_LIMIT,
         agent::{Sound, SoundKind, Target},
+        disguise::disguise,
         inventory::slot::EquipSlot,
         item::{
             tool::{AbilitySpec, ToolKind},
@@ -28,7 +30,7 @@ use common::{
     },
     consts::MAX_MOUNT_RANGE,
     effect::{BuffEffect, Effect},
-    event::{ChatEvent, EmitExt, SoundEvent},
+    event::{ChatEvent, EmitExt, ExposeDisguiseEvent, SoundEvent},
     mounting::VolumePos,
     path::TraversalConfig,
     rtsim::NpcActivity,
@@ -865,6 +867,30 @@ impl<'a> AgentData<'a> {
             .in_circle_aabr(self.pos.0.xy(), agent.psyche.search_dist())
             .collect_vec();
 
+        // Disguised strangers lingering too close might get a second look
+        if let Some(entity) = entities_nearby.iter().copied().find(|entity| {
+            self.is_fooled_by_disguise(*entity, read_data)
+                && read_data.positions.get(*entity).map_or(false, |e_pos| {
+                    e_pos.0.distance_squared(self.pos.0) < DISGUISE_LINGER_DIST.powi(2)
+                        && self.detects_other(
+                            agent,
+                            controller,
+                            entity,
+                            e_pos,
+                            read_data.scales.get(*entity),
+                            read_data,
+                        )
+                })
+        }) {
+            if thread_rng().gen_bool(DISGUISE_PERCEPTION_CHANCE) {
+                self.chat_npc(
+                    Content::localized("npc-speech-disguise_seen_through"),
+                    emitters,
+                );
+                emitters.emit(ExposeDisguiseEvent { entity });
+            }
+        }
+
         let can_ambush = |entity: EcsEntity, read_data: &ReadData| {
             let self_different_from_entity = || {
                 read_data
@@ -979,9 +1005,15 @@ impl<'a> AgentData<'a> {
                 get_pos(entity).map(|pos| (entity, pos, attack_target))
             })
             .filter(|(entity, e_pos, _)| is_detected(entity, e_pos, read_data.scales.get(*entity)))
-            .min_by_key(|(_, e_pos, attack_target)| {
+            .min_by_key(|(entity, e_pos, attack_target)| {
                 (
                     *attack_target,
+                    // Go after those that can still fight back before finishing off downed
+                    // players
+                    read_data
+                        .char_states
+                        .get(*entity)
+                        .map_or(false, |char_state| char_state.is_downed()),
                     (e_pos.0.distance_squared(self.pos.0) * 100.0) as i32,
                 )
             })
@@ -1115,6 +1147,8 @@ impl<'a> AgentData<'a> {
                             "Antlion" => Tactic::ArthropodMelee,
                             "Tarantula" | "Horn Beetle" => Tactic::ArthropodAmbush,
                             "Weevil" | "Black Widow" | "Crawler" => Tactic::ArthropodRanged,
+                            // Serpents
+                            "Viper" | "Naga" => Tactic::Serpent,
                             "Theropod Charge" => Tactic::CircleCharge {
                                 radius: 6,
                                 circle_time: 1,
@@ -1491,6 +1525,9 @@ impl<'a> AgentData<'a> {
                 tgt_data,
                 read_data,
             ),
+            Tactic::Serpent => {
+                self.handle_serpent_attack(agent, controller, &attack_data, tgt_data, read_data)
+            },
             Tactic::ArthropodAmbush => self.handle_arthropod_ambush_attack(
                 agent,
                 controller,
@@ -1960,10 +1997,26 @@ impl<'a> AgentData<'a> {
 
         (entity != *self.entity)
             && !self.passive_towards(entity, read_data)
+            && !self.is_fooled_by_disguise(entity, read_data)
             && (are_our_owners_hostile(self.alignment, other_alignment, read_data)
                 || (is_villager(self.alignment) && is_dressed_as_cultist(entity, read_data)))
     }
 
+    /// Whether `entity` passes as one of our own by wearing the same garb as
+    /// us. Only holds as long as its disguise wasn't seen through.
+    pub fn is_fooled_by_disguise(&self, entity: EcsEntity, read_data: &ReadData) -> bool {
+        matches!(self.alignment, Some(Alignment::Enemy))
+            && read_data
+                .exposed
+                .get(entity)
+                .map_or(true, |exposed| !exposed.is_active(*read_data.time))
+            && read_data
+                .inventories
+                .get(entity)
+                .and_then(disguise)
+                .map_or(false, |tag| disguise(self.inventory) == Some(tag))
+    }
+
     pub fn is_hunting_animal(&self, entity: EcsEntity, read_data: &ReadData) -> bool {
         (entity != *self.entity)
             && !self.friendly_towards(entity, read_data)
@@ -4146,6 +4146,49 @@ impl<'a> AgentData<'a> {
         }
     }
 
+    pub fn handle_serpent_attack(
+        &self,
+        agent: &mut Agent,
+        controller: &mut Controller,
+        attack_data: &AttackData,
+        tgt_data: &TargetData,
+        read_data: &ReadData,
+    ) {
+        const STRIKE_COOLDOWN: f32 = 4.0;
+        const STRIKE_RANGE: f32 = 4.0;
+
+        enum ActionStateTimers {
+            TimerSerpentStrike = 0,
+        }
+
+        agent.combat_state.timers[ActionStateTimers::TimerSerpentStrike as usize] += read_data.dt.0;
+        if attack_data.angle < 60.0 && attack_data.dist_sqrd < attack_data.min_attack_dist.powi(2) {
+            controller.inputs.move_dir = Vec2::zero();
+            controller.push_basic_input(InputKind::Primary);
+        } else if attack_data.angle < 20.0
+            && attack_data.dist_sqrd < (STRIKE_RANGE * attack_data.min_attack_dist).powi(2)
+            && agent.combat_state.timers[ActionStateTimers::TimerSerpentStrike as usize]
+                > STRIKE_COOLDOWN
+        {
+            // Coiled up and lined up with the target, lunge at it
+            controller.inputs.move_dir = Vec2::zero();
+            controller.push_basic_input(InputKind::Secondary);
+            agent.combat_state.timers[ActionStateTimers::TimerSerpentStrike as usize] = 0.0;
+        } else {
+            let path = if attack_data.dist_sqrd < MAX_PATH_DIST.powi(2) {
+                Path::Separate
+            } else {
+                Path::Partial
+            };
+            self.path_toward_target(agent, controller, tgt_data.pos.0, read_data, path, None);
+            // Weave from side to side while closing in so the approach is harder to read
+            if attack_data.dist_sqrd < (2.0 * STRIKE_RANGE * attack_data.min_attack_dist).powi(2) {
+                let weave = (read_data.time.0 as f32 * 2.5).sin() * 0.35 * PI;
+                controller.inputs.move_dir = controller.inputs.move_dir.rotated_z(weave);
+            }
+        }
+    }
+
     pub fn handle_minotaur_attack(
         &self,
         agent: &mut Agent,
@@ -18,3 +18,38 @@ pub const HEALING_ITEM_THRESHOLD: f32 = 0.5;
 pub const IDLE_HEALING_ITEM_THRESHOLD: f32 = 0.999;
 pub const DEFAULT_ATTACK_RANGE: f32 = 2.0;
 pub const STD_AWARENESS_DECAY_RATE: f32 = -0.05;
+/// The largest discount merchants can be haggled into granting
+pub const MAX_HAGGLE_DISCOUNT: f32 = 0.2;
+/// Premium merchants add on when customers they dislike try to haggle
+pub const HAGGLE_PREMIUM: f32 = 0.05;
+pub const MAX_HAGGLE_PREMIUM: f32 = 0.15;
+/// Merchants stop entertaining haggling after this many attempts in a trade
+pub const MAX_HAGGLE_ATTEMPTS: u8 = 3;
+/// Factor applied to the prices of traders whose town celebrates a festival
+pub const FESTIVAL_PRICE_FACTOR: f32 = 0.85;
+/// Disguised entities closer than this to members of the faction they pass
+/// as risk being seen through
+pub const DISGUISE_LINGER_DIST: f32 = 4.0;
+/// Chance for an agent to see through a disguise lingering close to it each
+/// time it looks for targets
+pub const DISGUISE_PERCEPTION_CHANCE: f64 = 0.1;
+/// Birds sent out to scout only fly this far from their owner, so as not to
+/// stray into unloaded terrain
+pub const MAX_SCOUT_RANGE: f32 = 320.0;
+/// How far out a scouting bird loops around the area it was sent to
+pub const SCOUT_LOOP_RADIUS: f32 = 48.0;
+/// How long a scouting bird spends looping around the area it was sent to, in
+/// seconds
+pub const SCOUT_LOOP_DURATION: f64 = 30.0;
+/// How high above the ground a scouting bird flies
+pub const SCOUT_ALTITUDE: f32 = 40.0;
+/// How far a scouting bird can make out creatures below it
+pub const SCOUT_SIGHT_RADIUS: f32 = 64.0;
+/// How often a scouting bird looks around, in seconds
+pub const SCOUT_SCAN_INTERVAL: f64 = 3.0;
+/// The most creatures a scouting bird checks its view of in one look around
+pub const MAX_SCOUT_SIGHTINGS: usize = 16;
+/// Necromancers only raise corpses lying within this distance of them
+pub const REANIMATE_RANGE: f32 = 20.0;
+/// Chance per second for a necromancer in combat to raise a nearby corpse
+pub const REANIMATE_CHANCE: f32 = 0.2;
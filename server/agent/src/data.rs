@@ -12,9 +12,9 @@ use common::{
             },
             slot::EquipSlot,
         },
-        ActiveAbilities, Alignment, Body, CharacterState, Combo, Energy, Health, Inventory,
-        LightEmitter, LootOwner, Ori, PhysicsState, Poise, Pos, Presence, Scale, SkillSet, Stance,
-        Stats, Vel,
+        ActiveAbilities, Alignment, Body, CharacterState, Combo, Corpse, Energy, Exposed, Health,
+        Inventory, LightEmitter, LootOwner, Ori, PhysicsState, Poise, Pos, Presence, Scale,
+        SkillSet, Stance, Stats, Vel,
     },
     consts::GRAVITY,
     event, event_emitters,
@@ -35,6 +35,11 @@ event_emitters! {
         chat: event::ChatEvent,
         sound: event::SoundEvent,
         process_trade_action: event::ProcessTradeActionEvent,
+        expose_disguise: event::ExposeDisguiseEvent,
+        reanimate: event::ReanimateEvent,
+        scout_pings: event::ScoutPingsEvent,
+        create_object: event::CreateObjectEvent,
+        give_directions: event::GiveDirectionsEvent,
     }
 }
 
@@ -229,6 +234,7 @@ pub enum Tactic {
     ArthropodMelee,
     ArthropodRanged,
     ArthropodAmbush,
+    Serpent,
 
     // Specific species tactics
     Mindflayer,
@@ -419,6 +425,8 @@ pub struct ReadData<'a> {
     pub poises: ReadStorage<'a, Poise>,
     pub stances: ReadStorage<'a, Stance>,
     pub presences: ReadStorage<'a, Presence>,
+    pub exposed: ReadStorage<'a, Exposed>,
+    pub corpses: ReadStorage<'a, Corpse>,
     pub ability_map: ReadExpect<'a, AbilityMap>,
 }
 
@@ -492,6 +500,7 @@ pub enum AbilityData {
     },
     BasicBlock {
         energy: f32,
+        energy_drain: f32,
         blocked_attacks: AttackFilters,
         angle: f32,
     },
@@ -656,10 +665,12 @@ impl AbilityData {
             BasicBlock {
                 max_angle,
                 energy_cost,
+                energy_drain,
                 blocked_attacks,
                 ..
             } => Self::BasicBlock {
                 energy: *energy_cost,
+                energy_drain: *energy_drain,
                 angle: *max_angle,
                 blocked_attacks: *blocked_attacks,
             },
@@ -915,11 +926,13 @@ impl AbilityData {
             },
             BasicBlock {
                 energy,
+                energy_drain,
                 angle,
                 blocked_attacks,
             } => {
+                // Keep enough energy to hold the block for a moment
                 melee_check(25.0, *angle, None)
-                    && energy_check(*energy)
+                    && energy_check(*energy + *energy_drain)
                     && attack_kind_check(*blocked_attacks)
                     && tgt_data
                         .char_state
@@ -0,0 +1,153 @@
+//! Linking game accounts to accounts elsewhere, such as on a Discord server,
+//! so that communities can verify and reward their players.
+
+use crate::Settings;
+use atomicwrites::{AtomicFile, OverwriteBehavior};
+use chrono::{DateTime, Utc};
+use common::uuid::Uuid;
+use hashbrown::HashMap;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::Write as _,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::{error, info};
+
+const LINKS_FILE: &str = "account_links.ron";
+/// Codes leave out characters that are easily mistaken for one another
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LENGTH: usize = 8;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LinkedAccount {
+    /// The username of the account when it was linked
+    pub username: String,
+    /// The id of the account on the other side, as given by the bot that
+    /// redeemed the code
+    pub external_id: String,
+    pub time: DateTime<Utc>,
+}
+
+struct PendingCode {
+    uuid: Uuid,
+    username: String,
+    expires: Instant,
+}
+
+/// Linked accounts, persisted in the data directory, and the codes waiting to
+/// be redeemed
+pub struct AccountLinks {
+    path: PathBuf,
+    links: BTreeMap<Uuid, LinkedAccount>,
+    pending: HashMap<String, PendingCode>,
+}
+
+impl AccountLinks {
+    pub fn new(data_dir: PathBuf) -> Self {
+        let path = data_dir.join(LINKS_FILE);
+        let links = match std::fs::read_to_string(&path) {
+            Ok(links) => ron::from_str(&links).unwrap_or_else(|err| {
+                error!(?err, "Failed to parse account links, ignoring them");
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+        info!("Loaded {} account links from {:?}", links.len(), path);
+
+        Self {
+            path,
+            links,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, uuid: &Uuid) -> Option<&LinkedAccount> { self.links.get(uuid) }
+
+    /// Whether the account shows a verified badge to other players
+    pub fn has_badge(&self, uuid: &Uuid, settings: &Settings) -> bool {
+        settings.account_link.grant_badge && self.links.contains_key(uuid)
+    }
+
+    pub fn links(&self) -> impl Iterator<Item = (&Uuid, &LinkedAccount)> { self.links.iter() }
+
+    /// Creates a one-time code that links the account once redeemed, replacing
+    /// any earlier code of the same account
+    pub fn create_code(&mut self, uuid: Uuid, username: String, lifetime: Duration) -> String {
+        let now = Instant::now();
+        self.pending
+            .retain(|_, pending| pending.uuid != uuid && pending.expires > now);
+
+        let mut rng = rand::thread_rng();
+        let code = loop {
+            let code: String = (0..CODE_LENGTH)
+                .map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char)
+                .collect();
+            if !self.pending.contains_key(&code) {
+                break code;
+            }
+        };
+        self.pending.insert(code.clone(), PendingCode {
+            uuid,
+            username,
+            expires: now + lifetime,
+        });
+        code
+    }
+
+    /// Redeems a code on behalf of the given external account, returning the
+    /// account that is now linked to it
+    pub fn redeem(&mut self, code: &str, external_id: String) -> Result<Uuid, String> {
+        let code = code.trim().to_uppercase();
+        let pending = self
+            .pending
+            .get(&code)
+            .filter(|pending| pending.expires > Instant::now())
+            .ok_or_else(|| "Unknown or expired code".to_string())?;
+        if let Some(linked) = self
+            .links
+            .values()
+            .find(|linked| linked.external_id == external_id)
+        {
+            return Err(format!(
+                "{} is already linked to {}",
+                external_id, linked.username
+            ));
+        }
+
+        let pending = self.pending.remove(&code).expect("Checked above");
+        self.links.insert(pending.uuid, LinkedAccount {
+            username: pending.username,
+            external_id,
+            time: Utc::now(),
+        });
+        self.save();
+        Ok(pending.uuid)
+    }
+
+    /// Returns false if the account wasn't linked
+    pub fn unlink(&mut self, uuid: &Uuid) -> bool {
+        let unlinked = self.links.remove(uuid).is_some();
+        if unlinked {
+            self.save();
+        }
+        unlinked
+    }
+
+    fn save(&self) {
+        let links = match ron::ser::to_string_pretty(&self.links, ron::ser::PrettyConfig::default())
+        {
+            Ok(links) => links,
+            Err(err) => {
+                error!(?err, "Failed to serialize account links");
+                return;
+            },
+        };
+        let file = AtomicFile::new(&self.path, OverwriteBehavior::AllowOverwrite);
+        if let Err(err) = file.write(|file| file.write_all(links.as_bytes())) {
+            error!(?err, "Failed to write account links");
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use crate::settings::ModerationSettings;
+use crate::settings::{FilterSeverity, ModerationSettings};
 use authc::Uuid;
 use censor::Censor;
 use common::comp::{AdminRole, ChatType, Group};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use std::{
     fmt,
     sync::Arc,
@@ -14,6 +14,7 @@ pub const MAX_BYTES_CHAT_MSG: usize = 256;
 
 pub enum ActionNote {
     SpamWarn,
+    Censored,
 }
 
 impl fmt::Display for ActionNote {
@@ -24,14 +25,35 @@ impl fmt::Display for ActionNote {
                 "You've sent a lot of messages recently. Make sure to reduce the rate of messages \
                  or you will be automatically muted."
             ),
+            ActionNote::Censored => write!(f, "Parts of your message were censored."),
         }
     }
 }
 
 pub enum ActionErr {
     BannedWord,
+    Link,
     TooLong,
     SpamMuted(Duration),
+    FilterMuted(Duration),
+    Probation(Duration),
+    /// Rejected by a custom filter, with the reason given to the sender
+    Filtered(String),
+}
+
+impl ActionErr {
+    /// The label used to count these in the chat metrics
+    pub fn metric_label(&self) -> &'static str {
+        match self {
+            ActionErr::BannedWord => "blocked",
+            ActionErr::Link => "link",
+            ActionErr::TooLong => "too_long",
+            ActionErr::SpamMuted(_) => "spam",
+            ActionErr::FilterMuted(_) => "muted",
+            ActionErr::Probation(_) => "probation",
+            ActionErr::Filtered(_) => "custom",
+        }
+    }
 }
 
 impl fmt::Display for ActionErr {
@@ -52,18 +74,130 @@ impl fmt::Display for ActionErr {
                 "You have sent too many messages and are muted for {} seconds.",
                 dur.as_secs_f32() as u64
             ),
+            ActionErr::Link => write!(f, "Links are not permitted in chat."),
+            ActionErr::FilterMuted(dur) => write!(
+                f,
+                "Your message contained a banned word and you are muted for {} seconds.",
+                dur.as_secs_f32() as u64
+            ),
+            ActionErr::Probation(dur) => write!(
+                f,
+                "You are on probation and can send your next message in {} seconds.",
+                dur.as_secs_f32().ceil() as u64
+            ),
+            ActionErr::Filtered(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+/// What a [`ChatFilter`] decides should happen to a message.
+pub enum FilterVerdict {
+    Allow,
+    /// Send the given text in place of the message.
+    Censor(String),
+    /// Don't send the message.
+    Block(ActionErr),
+    /// Don't send the message, and mute the sender.
+    Mute,
+}
+
+/// A stage of the chat filter. Filters run in the order they were added, each
+/// seeing the message as censored by the ones before it, until one of them
+/// rejects it.
+///
+/// Besides the filters built from the moderation settings, servers embedding
+/// this crate can add their own with [`AutoMod::add_filter`].
+pub trait ChatFilter: Send + Sync {
+    fn filter(&self, msg: &str) -> FilterVerdict;
+}
+
+/// Filters messages containing any of a list of words.
+pub struct WordFilter {
+    censor: Arc<Censor>,
+    severity: FilterSeverity,
+}
+
+impl WordFilter {
+    pub fn new(censor: Arc<Censor>, severity: FilterSeverity) -> Self { Self { censor, severity } }
+}
+
+impl ChatFilter for WordFilter {
+    fn filter(&self, msg: &str) -> FilterVerdict {
+        if !self.censor.check(msg) {
+            FilterVerdict::Allow
+        } else {
+            match self.severity {
+                FilterSeverity::Censor => FilterVerdict::Censor(self.censor.censor(msg)),
+                FilterSeverity::Block => FilterVerdict::Block(ActionErr::BannedWord),
+                FilterSeverity::Mute => FilterVerdict::Mute,
+            }
+        }
+    }
+}
+
+/// Blocks messages that contain links.
+pub struct UrlFilter;
+
+impl UrlFilter {
+    const TLDS: [&'static str; 12] = [
+        "com", "net", "org", "io", "gg", "xyz", "ru", "de", "co", "me", "tk", "ly",
+    ];
+
+    fn is_link(word: &str) -> bool {
+        let word = word
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        if word.contains("://") || word.starts_with("www.") {
+            return true;
+        }
+        // Bare domains, like `example.com/path`
+        let host = word.split('/').next().unwrap_or_default();
+        let mut labels = host.rsplit('.');
+        match (labels.next(), labels.next()) {
+            (Some(tld), Some(name)) => {
+                !name.is_empty()
+                    && Self::TLDS.contains(&tld)
+                    && host
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '.' || c == '-')
+            },
+            _ => false,
+        }
+    }
+}
+
+impl ChatFilter for UrlFilter {
+    fn filter(&self, msg: &str) -> FilterVerdict {
+        if msg.split_whitespace().any(Self::is_link) {
+            FilterVerdict::Block(ActionErr::Link)
+        } else {
+            FilterVerdict::Allow
         }
     }
 }
 
+/// A message that passed automoderation.
+#[derive(Default)]
+pub struct Approved {
+    /// The text to send instead of the original message, if it was censored
+    pub censored: Option<String>,
+    /// A note to send to the sender
+    pub note: Option<ActionNote>,
+}
+
 pub struct AutoMod {
     settings: ModerationSettings,
-    censor: Arc<Censor>,
+    exempt_players: HashSet<Uuid>,
+    filters: Vec<Box<dyn ChatFilter>>,
     players: HashMap<Uuid, PlayerState>,
 }
 
 impl AutoMod {
-    pub fn new(settings: &ModerationSettings, censor: Arc<Censor>) -> Self {
+    pub fn new(
+        settings: &ModerationSettings,
+        censor: Arc<Censor>,
+        filtered_words: Vec<(FilterSeverity, Vec<String>)>,
+    ) -> Self {
         if settings.automod {
             info!(
                 "Automod enabled, players{} will be subject to automated spam/content filters",
@@ -77,15 +211,31 @@ impl AutoMod {
             info!("Automod disabled");
         }
 
+        let mut filters: Vec<Box<dyn ChatFilter>> =
+            vec![Box::new(WordFilter::new(censor, FilterSeverity::Block))];
+        filters.extend(filtered_words.into_iter().map(|(severity, words)| {
+            Box::new(WordFilter::new(
+                Arc::new(Censor::Custom(words.into_iter().collect())),
+                severity,
+            )) as Box<dyn ChatFilter>
+        }));
+        if settings.block_urls {
+            filters.push(Box::new(UrlFilter));
+        }
+
         Self {
             settings: settings.clone(),
-            censor,
+            exempt_players: settings.exempt_players.iter().copied().collect(),
+            filters,
             players: HashMap::default(),
         }
     }
 
     pub fn enabled(&self) -> bool { self.settings.automod }
 
+    /// Adds a filter that runs after all the existing ones.
+    pub fn add_filter(&mut self, filter: Box<dyn ChatFilter>) { self.filters.push(filter); }
+
     fn player_mut(&mut self, player: Uuid) -> &mut PlayerState {
         self.players.entry(player).or_default()
     }
@@ -97,30 +247,57 @@ impl AutoMod {
         now: Instant,
         chat_type: &ChatType<Group>,
         msg: &str,
-    ) -> Result<Option<ActionNote>, ActionErr> {
+        on_probation: bool,
+    ) -> Result<Approved, ActionErr> {
         // TODO: Consider using grapheme cluster count instead of size in bytes
         if msg.len() > MAX_BYTES_CHAT_MSG {
-            Err(ActionErr::TooLong)
-        } else if !self.settings.automod
+            return Err(ActionErr::TooLong);
+        }
+        // Probation limits the chat of a player whether automod is enabled or not
+        if on_probation {
+            if let Some(wait) = self.player_mut(player).enforce_probation(now) {
+                return Err(ActionErr::Probation(wait));
+            }
+        }
+        if !self.settings.automod
             // Is this a private chat message?
             || chat_type.is_private().unwrap_or(true)
             // Is the user exempt from automoderation?
             || (role.is_some() && self.settings.admins_exempt)
+            || self.exempt_players.contains(&player)
         {
-            Ok(None)
-        } else if self.censor.check(msg) {
-            Err(ActionErr::BannedWord)
-        } else {
-            let volume = self.player_mut(player).enforce_message_volume(now);
-
-            if let Some(until) = self.player_mut(player).muted_until {
-                Err(ActionErr::SpamMuted(until.saturating_duration_since(now)))
-            } else if volume > 0.75 {
-                Ok(Some(ActionNote::SpamWarn))
-            } else {
-                Ok(None)
+            return Ok(Approved::default());
+        }
+
+        let mut censored = None::<String>;
+        for filter in self.filters.iter() {
+            match filter.filter(censored.as_deref().unwrap_or(msg)) {
+                FilterVerdict::Allow => {},
+                FilterVerdict::Censor(text) => censored = Some(text),
+                FilterVerdict::Block(err) => return Err(err),
+                FilterVerdict::Mute => {
+                    let state = self.player_mut(player);
+                    let until = now + SPAM_MUTE_PERIOD;
+                    state.muted_until = Some(state.muted_until.map_or(until, |u| u.max(until)));
+                    return Err(ActionErr::FilterMuted(SPAM_MUTE_PERIOD));
+                },
             }
         }
+
+        let volume = self.player_mut(player).enforce_message_volume(now);
+
+        if let Some(until) = self.player_mut(player).muted_until {
+            Err(ActionErr::SpamMuted(until.saturating_duration_since(now)))
+        } else {
+            Ok(Approved {
+                note: if volume > 0.75 {
+                    Some(ActionNote::SpamWarn)
+                } else {
+                    censored.as_ref().map(|_| ActionNote::Censored)
+                },
+                censored,
+            })
+        }
     }
 }
 
@@ -131,8 +308,10 @@ const CHAT_VOLUME_PERIOD: f32 = 30.0;
 /// period.
 const MAX_AVG_MSG_PER_SECOND: f32 = 1.0 / 5.0; // No more than a message every 5 seconds on average
 /// The period for which a player should be muted when they exceed the message
-/// spam threshold.
+/// spam threshold, or use a word that warrants it.
 const SPAM_MUTE_PERIOD: Duration = Duration::from_secs(180);
+/// The shortest time between two messages of a player on probation.
+const PROBATION_MSG_INTERVAL: Duration = Duration::from_secs(15);
 
 #[derive(Default)]
 pub struct PlayerState {
@@ -140,9 +319,25 @@ pub struct PlayerState {
     /// The average number of messages per second over the last N seconds.
     chat_volume: f32,
     muted_until: Option<Instant>,
+    last_probation_msg_time: Option<Instant>,
 }
 
 impl PlayerState {
+    /// Returns how long the player has to wait if they can't send a message
+    /// yet.
+    pub fn enforce_probation(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(next) = self
+            .last_probation_msg_time
+            .and_then(|last| last.checked_add(PROBATION_MSG_INTERVAL))
+            .filter(|next| *next > now)
+        {
+            Some(next.saturating_duration_since(now))
+        } else {
+            self.last_probation_msg_time = Some(now);
+            None
+        }
+    }
+
     // 0.0 => message is permitted, nothing unusual
     // >=1.0 => message is not permitted, chat volume exceeded
     pub fn enforce_message_volume(&mut self, now: Instant) -> f32 {
@@ -0,0 +1,202 @@
+use atomicwrites::{AtomicFile, OverwriteBehavior};
+use common::{
+    bounty::{Bounty, BountyTarget, MAX_ACTIVE_BOUNTIES},
+    character::CharacterId,
+    comp::Content,
+    rtsim::{Actor, Role},
+    terrain::SiteKindMeta,
+};
+use hashbrown::HashMap;
+use rtsim::data::{Data, ReportKind};
+use std::{io::Write as _, path::PathBuf};
+use tracing::{error, info};
+use vek::*;
+use world::{site::SiteKind, IndexRef};
+
+const BOUNTIES_FILE: &str = "bounties.ron";
+/// Notice boards this close to the center of a town belong to it
+const TOWN_RADIUS: f32 = 200.0;
+/// Towns only care about pirate hideouts this close to them
+const HIDEOUT_RANGE: f32 = 3000.0;
+/// Hostiles killed this close to a hideout recover the goods stolen by its
+/// pirates
+pub const HIDEOUT_RADIUS: f32 = 48.0;
+/// Towns can't afford to post bounties below this wealth
+const MIN_WEALTH: f32 = 0.1;
+/// Most hunts a notice board offers at once
+const MAX_HUNTS: usize = 4;
+const HUNT_REWARD: f32 = 200.0;
+const RECOVER_REWARD: f32 = 300.0;
+
+/// Bounties accepted by characters that they haven't completed yet, persisted
+/// in the data directory
+pub struct Bounties {
+    path: PathBuf,
+    active: HashMap<CharacterId, Vec<Bounty>>,
+}
+
+impl Bounties {
+    pub fn new(data_dir: PathBuf) -> Self {
+        let path = data_dir.join(BOUNTIES_FILE);
+        let active = match std::fs::read_to_string(&path) {
+            Ok(active) => ron::from_str(&active).unwrap_or_else(|err| {
+                error!(?err, "Failed to parse bounties, ignoring them");
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        info!(
+            "Loaded bounties of {} characters from {:?}",
+            active.len(),
+            path
+        );
+
+        Self { path, active }
+    }
+
+    pub fn get(&self, character_id: CharacterId) -> &[Bounty] {
+        self.active.get(&character_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns false if the character already pursues the bounty or as many
+    /// bounties as they can
+    pub fn accept(&mut self, character_id: CharacterId, bounty: Bounty) -> bool {
+        let active = self.active.entry(character_id).or_default();
+        if active.len() >= MAX_ACTIVE_BOUNTIES
+            || active.iter().any(|other| other.target == bounty.target)
+        {
+            return false;
+        }
+        active.push(bounty);
+        self.save();
+        true
+    }
+
+    /// Removes the first bounty of a character that matches, returning it
+    pub fn complete(
+        &mut self,
+        character_id: CharacterId,
+        mut matches: impl FnMut(&Bounty) -> bool,
+    ) -> Option<Bounty> {
+        let active = self.active.get_mut(&character_id)?;
+        let bounty = active.remove(active.iter().position(&mut matches)?);
+        if active.is_empty() {
+            self.active.remove(&character_id);
+        }
+        self.save();
+        Some(bounty)
+    }
+
+    /// Removes the bounties on a target that can't be completed anymore, e.g.
+    /// because somebody else killed the monster. Returns the characters that
+    /// pursued it.
+    pub fn forget(&mut self, target: BountyTarget) -> Vec<CharacterId> {
+        let mut pursuers = Vec::new();
+        self.active.retain(|character_id, active| {
+            let len = active.len();
+            active.retain(|bounty| bounty.target != target);
+            if active.len() != len {
+                pursuers.push(*character_id);
+            }
+            !active.is_empty()
+        });
+        if !pursuers.is_empty() {
+            self.save();
+        }
+        pursuers
+    }
+
+    fn save(&self) {
+        let active =
+            match ron::ser::to_string_pretty(&self.active, ron::ser::PrettyConfig::default()) {
+                Ok(active) => active,
+                Err(err) => {
+                    error!(?err, "Failed to serialize bounties");
+                    return;
+                },
+            };
+        let file = AtomicFile::new(&self.path, OverwriteBehavior::AllowOverwrite);
+        if let Err(err) = file.write(|file| file.write_all(active.as_bytes())) {
+            error!(?err, "Failed to write bounties");
+        }
+    }
+}
+
+/// Bounties posted on the notice board at `pos` by the town it stands in: a
+/// hunt for each monster the town knows killed someone recently, and the
+/// recovery of the goods stolen by the nearest pirate hideout. Rewards grow
+/// with the wealth of the town.
+pub fn board_offers(data: &Data, index: IndexRef, pos: Vec2<f32>) -> Vec<Bounty> {
+    let Some((world_site, town)) = index
+        .sites
+        .iter()
+        .filter(|(_, site)| {
+            matches!(
+                site.kind.convert_to_meta(),
+                Some(SiteKindMeta::Settlement(_))
+            )
+        })
+        .map(|(id, site)| (id, site.get_origin().as_::<f32>()))
+        .filter(|(_, origin)| origin.distance_squared(pos) < TOWN_RADIUS.powi(2))
+        .min_by(|(_, a), (_, b)| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+    else {
+        return Vec::new();
+    };
+    let Some(site) = data
+        .sites
+        .world_site_map
+        .get(&world_site)
+        .and_then(|site| data.sites.get(*site))
+        .filter(|site| site.wealth >= MIN_WEALTH)
+    else {
+        return Vec::new();
+    };
+    let reward = |base: f32| (base * (0.5 + site.wealth)).round() as u32;
+
+    let mut hunted = Vec::new();
+    let hunts = site
+        .known_reports
+        .iter()
+        .filter_map(|report| match data.reports.get(*report)?.kind {
+            ReportKind::Death {
+                killer: Some(Actor::Npc(killer)),
+                ..
+            } => Some(killer),
+            _ => None,
+        })
+        .filter_map(|killer| {
+            let npc = data.npcs.get(killer)?;
+            (matches!(npc.role, Role::Monster) && !hunted.contains(&killer)).then(|| {
+                hunted.push(killer);
+                Bounty {
+                    target: BountyTarget::Hunt(killer),
+                    target_name: npc.body.localize_npc(),
+                    wpos: npc.wpos.xy(),
+                    site: world_site.id(),
+                    reward: reward(HUNT_REWARD),
+                }
+            })
+        })
+        .take(MAX_HUNTS)
+        .collect::<Vec<_>>();
+
+    let recovery = index
+        .sites
+        .iter()
+        .filter(|(_, site)| matches!(site.kind, SiteKind::PirateHideout(_)))
+        .map(|(id, site)| (id, site, site.get_origin().as_::<f32>()))
+        .filter(|(_, _, origin)| origin.distance_squared(town) < HIDEOUT_RANGE.powi(2))
+        .min_by(|(_, _, a), (_, _, b)| {
+            a.distance_squared(town)
+                .total_cmp(&b.distance_squared(town))
+        })
+        .map(|(id, hideout, origin)| Bounty {
+            target: BountyTarget::Recover(id.id()),
+            target_name: Content::Plain(hideout.name().to_string()),
+            wpos: origin,
+            site: world_site.id(),
+            reward: reward(RECOVER_REWARD),
+        });
+
+    hunts.into_iter().chain(recovery).collect()
+}
@@ -82,6 +82,8 @@ pub fn create_character(
         pets: Vec::new(),
         active_abilities: common::comp::ActiveAbilities::default_limited(BASE_ABILITY_LIMIT),
         map_marker,
+        unlocked_shrines: common::comp::UnlockedShrines::default(),
+        titles: common::comp::Titles::default(),
     });
     Ok(())
 }
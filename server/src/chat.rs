@@ -11,7 +11,7 @@ use std::{collections::VecDeque, ops::Sub, sync::Arc, time::Duration};
 use tokio::sync::Mutex;
 use tracing::{info_span, Instrument};
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PlayerInfo {
     uuid: Uuid,
     alias: String,
@@ -20,7 +20,7 @@ pub struct PlayerInfo {
 /// Enum representing death reasons
 ///
 /// All variants should be strictly typed, no string content.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum KillSource {
     Player(PlayerInfo, KillType),
     NonPlayer(String, KillType),
@@ -30,7 +30,7 @@ pub enum KillSource {
     Other,
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 /// partially mapped to common::comp::ChatMsg
 pub enum ChatParties {
     Online(PlayerInfo),
@@ -44,11 +44,13 @@ pub enum ChatParties {
     Say(PlayerInfo),
     FactionMeta(String),
     Faction(PlayerInfo, String),
+    GuildMeta(String),
+    Guild(PlayerInfo, String),
     Region(PlayerInfo),
     World(PlayerInfo),
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub time: DateTime<Utc>,
     pub parties: ChatParties,
@@ -75,6 +77,30 @@ pub struct ChatExporter {
     chat_s: tokio::sync::mpsc::Sender<ChatMessage>,
 }
 
+impl ChatParties {
+    fn involves(&self, uuid: Uuid) -> bool {
+        let is = |info: &PlayerInfo| info.uuid == uuid;
+        match self {
+            ChatParties::Online(info)
+            | ChatParties::Offline(info)
+            | ChatParties::CommandInfo(info)
+            | ChatParties::CommandError(info)
+            | ChatParties::Say(info)
+            | ChatParties::Faction(info, _)
+            | ChatParties::Guild(info, _)
+            | ChatParties::Region(info)
+            | ChatParties::World(info) => is(info),
+            ChatParties::Kill(source, info) => {
+                is(info) || matches!(source, KillSource::Player(killer, _) if is(killer))
+            },
+            ChatParties::GroupMeta(members) => members.iter().any(is),
+            ChatParties::Group(info, members) => is(info) || members.iter().any(is),
+            ChatParties::Tell(from, to) => is(from) || is(to),
+            ChatParties::FactionMeta(_) | ChatParties::GuildMeta(_) => false,
+        }
+    }
+}
+
 impl ChatMessage {
     fn new(chatmsg: &UnresolvedChatMsg, parties: ChatParties) -> Self {
         ChatMessage {
@@ -187,6 +213,20 @@ impl ChatExporter {
                     ));
                 }
             },
+            ChatType::GuildMeta(tag) => {
+                return Some(ChatMessage::new(
+                    chatmsg,
+                    ChatParties::GuildMeta(tag.clone()),
+                ));
+            },
+            ChatType::Guild(from, tag) => {
+                if let Some(player_info) = player_info_from_uid(*from) {
+                    return Some(ChatMessage::new(
+                        chatmsg,
+                        ChatParties::Guild(player_info, tag.clone()),
+                    ));
+                }
+            },
             ChatType::GroupMeta(g) => {
                 let members = group_members_from_group(g);
                 return Some(ChatMessage::new(chatmsg, ChatParties::GroupMeta(members)));
@@ -255,4 +295,19 @@ impl ChatCache {
 
         (Self { messages }, ChatExporter { chat_s })
     }
+
+    /// The cached messages that any of the given players sent or received.
+    /// Empty if the cache is being written to at the moment.
+    pub fn involving(&self, players: &[Uuid]) -> Vec<ChatMessage> {
+        self.messages
+            .try_lock()
+            .map(|messages| {
+                messages
+                    .iter()
+                    .filter(|msg| players.iter().any(|p| msg.parties.involves(*p)))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
@@ -4,16 +4,21 @@ use crate::rtsim::RtSim;
 #[cfg(not(feature = "worldgen"))]
 use crate::test_world::{IndexOwned, World};
 use common::{
-    calendar::Calendar, generation::ChunkSupplement, resources::TimeOfDay, slowjob::SlowJobPool,
-    terrain::TerrainChunk,
+    calendar::Calendar, generation::ChunkSupplement, resources::TimeOfDay, rtsim::RtsimChunks,
+    slowjob::SlowJobPool, terrain::TerrainChunk,
 };
+use enum_map::{Enum, EnumMap};
 use hashbrown::{hash_map::Entry, HashMap};
 use rayon::iter::ParallelIterator;
 use specs::Entity as EcsEntity;
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc,
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
+use strum::{EnumIter, IntoEnumIterator};
 use vek::*;
 #[cfg(feature = "worldgen")]
 use world::{IndexOwned, World};
@@ -23,10 +28,83 @@ type ChunkGenResult = (
     Result<(TerrainChunk, ChunkSupplement), Option<EcsEntity>>,
 );
 
+/// Where a chunk request originated from. Chunks are generated in order of
+/// their source, so that the variants listed first preempt the others.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum, EnumIter)]
+pub enum ChunkSource {
+    /// Chunks requested by connected players
+    Player,
+    /// Chunks regenerated after the world was reloaded
+    Regenerate,
+    /// Chunks kept loaded around bench persisters
+    Persister,
+}
+
+impl ChunkSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Player => "player",
+            Self::Regenerate => "regenerate",
+            Self::Persister => "persister",
+        }
+    }
+}
+
+struct PendingChunk {
+    cancel: Arc<AtomicBool>,
+    source: ChunkSource,
+    /// Whether the chunk was handed to the slow job pool already
+    dispatched: bool,
+}
+
+/// Everything needed to generate a chunk once a worker becomes available.
+struct QueuedChunk {
+    key: Vec2<i32>,
+    entity: Option<EcsEntity>,
+    cancel: Arc<AtomicBool>,
+    world: Arc<World>,
+    index: IndexOwned,
+    rtsim_chunks: Option<RtsimChunks>,
+    time: (TimeOfDay, Calendar),
+}
+
+impl QueuedChunk {
+    fn job(
+        &self,
+        chunk_tx: crossbeam_channel::Sender<ChunkGenResult>,
+    ) -> impl FnOnce() + Send + Sync + 'static {
+        let key = self.key;
+        let entity = self.entity;
+        let cancel = Arc::clone(&self.cancel);
+        let world = Arc::clone(&self.world);
+        let index = self.index.clone();
+        let rtsim_chunks = self.rtsim_chunks;
+        let time = self.time.clone();
+        move || {
+            let index = index.as_index_ref();
+            let payload = world
+                .generate_chunk(index, key, rtsim_chunks, || cancel.load(Ordering::Relaxed), Some(time))
+                // FIXME: Since only the first entity who cancels a chunk is notified, we end up
+                // delaying chunk re-requests for up to 3 seconds for other clients, which isn't
+                // great.  We *could* store all the other requesting clients here, but it could
+                // bloat memory a lot.  Currently, this isn't much of an issue because we rarely
+                // have large numbers of pending chunks, so most of them are likely to be nearby an
+                // actual player most of the time, but that will eventually change.  In the future,
+                // some solution that always pushes chunk updates to players (rather than waiting
+                // for explicit requests) should adequately solve this kind of issue.
+                .map_err(|_| entity);
+            let _ = chunk_tx.send((key, payload));
+        }
+    }
+}
+
 pub struct ChunkGenerator {
     chunk_tx: crossbeam_channel::Sender<ChunkGenResult>,
     chunk_rx: crossbeam_channel::Receiver<ChunkGenResult>,
-    pending_chunks: HashMap<Vec2<i32>, Arc<AtomicBool>>,
+    pending_chunks: HashMap<Vec2<i32>, PendingChunk>,
+    /// Chunks waiting for a free worker, by source. Entries that were cancelled
+    /// or re-queued with a higher priority are skipped when dispatching.
+    queues: EnumMap<ChunkSource, VecDeque<QueuedChunk>>,
     metrics: Arc<ChunkGenMetrics>,
 }
 impl ChunkGenerator {
@@ -36,54 +114,98 @@ impl ChunkGenerator {
             chunk_tx,
             chunk_rx,
             pending_chunks: HashMap::new(),
+            queues: EnumMap::default(),
             metrics: Arc::new(metrics),
         }
     }
 
+    /// Queues a chunk for generation. The chunk is only handed to a worker by
+    /// [`ChunkGenerator::dispatch`], once no chunk of a higher priority source
+    /// is waiting.
     pub fn generate_chunk(
         &mut self,
         entity: Option<EcsEntity>,
+        source: ChunkSource,
         key: Vec2<i32>,
-        slowjob_pool: &SlowJobPool,
         world: Arc<World>,
         #[cfg(feature = "worldgen")] rtsim: &RtSim,
         #[cfg(not(feature = "worldgen"))] _rtsim: &(),
         index: IndexOwned,
         time: (TimeOfDay, Calendar),
     ) {
-        let v = if let Entry::Vacant(v) = self.pending_chunks.entry(key) {
-            v
-        } else {
-            return;
+        let cancel = match self.pending_chunks.entry(key) {
+            Entry::Vacant(v) => {
+                let cancel = Arc::new(AtomicBool::new(false));
+                v.insert(PendingChunk {
+                    cancel: Arc::clone(&cancel),
+                    source,
+                    dispatched: false,
+                });
+                self.metrics.chunks_requested.inc();
+                cancel
+            },
+            // Move chunks that are still waiting to the queue of the more important source
+            Entry::Occupied(mut o) if !o.get().dispatched && source < o.get().source => {
+                o.get_mut().source = source;
+                Arc::clone(&o.get().cancel)
+            },
+            Entry::Occupied(_) => return,
         };
-        let cancel = Arc::new(AtomicBool::new(false));
-        v.insert(Arc::clone(&cancel));
-        let chunk_tx = self.chunk_tx.clone();
-        self.metrics.chunks_requested.inc();
 
         // Get state for this chunk from rtsim
         #[cfg(feature = "worldgen")]
-        let rtsim_resources = Some(rtsim.get_chunk_resources(key));
+        let rtsim_chunks = Some(rtsim.get_chunks(key));
         #[cfg(not(feature = "worldgen"))]
-        let rtsim_resources = None;
+        let rtsim_chunks = None;
 
-        slowjob_pool.spawn("CHUNK_GENERATOR", move || {
-            let index = index.as_index_ref();
-            let payload = world
-                .generate_chunk(index, key, rtsim_resources, || cancel.load(Ordering::Relaxed), Some(time))
-                // FIXME: Since only the first entity who cancels a chunk is notified, we end up
-                // delaying chunk re-requests for up to 3 seconds for other clients, which isn't
-                // great.  We *could* store all the other requesting clients here, but it could
-                // bloat memory a lot.  Currently, this isn't much of an issue because we rarely
-                // have large numbers of pending chunks, so most of them are likely to be nearby an
-                // actual player most of the time, but that will eventually change.  In the future,
-                // some solution that always pushes chunk updates to players (rather than waiting
-                // for explicit requests) should adequately solve this kind of issue.
-                .map_err(|_| entity);
-            let _ = chunk_tx.send((key, payload));
+        self.queues[source].push_back(QueuedChunk {
+            key,
+            entity,
+            cancel,
+            world,
+            index,
+            rtsim_chunks,
+            time,
         });
     }
 
+    /// Hands queued chunks to the slow job pool for as long as it has free
+    /// capacity, starting with the highest priority source.
+    pub fn dispatch(&mut self, slowjob_pool: &SlowJobPool) {
+        'sources: for source in ChunkSource::iter() {
+            let queue = &mut self.queues[source];
+            while let Some(queued) = queue.front() {
+                let is_live = self
+                    .pending_chunks
+                    .get(&queued.key)
+                    .map_or(false, |pending| {
+                        !pending.dispatched
+                            && pending.source == source
+                            && Arc::ptr_eq(&pending.cancel, &queued.cancel)
+                    });
+                if is_live {
+                    if slowjob_pool
+                        .try_run("CHUNK_GENERATOR", queued.job(self.chunk_tx.clone()))
+                        .is_err()
+                    {
+                        break 'sources;
+                    }
+                    if let Some(pending) = self.pending_chunks.get_mut(&queued.key) {
+                        pending.dispatched = true;
+                    }
+                }
+                queue.pop_front();
+            }
+        }
+
+        for source in ChunkSource::iter() {
+            self.metrics
+                .chunks_queued
+                .with_label_values(&[source.label()])
+                .set(self.queues[source].len() as i64);
+        }
+    }
+
     pub fn recv_new_chunk(&mut self) -> Option<ChunkGenResult> {
         // Make sure chunk wasn't cancelled and if it was check to see if there are more
         // chunks to receive
@@ -107,17 +229,18 @@ impl ChunkGenerator {
     }
 
     pub fn cancel_if_pending(&mut self, key: Vec2<i32>) {
-        if let Some(cancel) = self.pending_chunks.remove(&key) {
-            cancel.store(true, Ordering::Relaxed);
+        if let Some(pending) = self.pending_chunks.remove(&key) {
+            pending.cancel.store(true, Ordering::Relaxed);
             self.metrics.chunks_canceled.inc();
         }
     }
 
     pub fn cancel_all(&mut self) {
         let metrics = Arc::clone(&self.metrics);
-        self.pending_chunks.drain().for_each(|(_, cancel)| {
-            cancel.store(true, Ordering::Relaxed);
+        self.pending_chunks.drain().for_each(|(_, pending)| {
+            pending.cancel.store(true, Ordering::Relaxed);
             metrics.chunks_canceled.inc();
         });
+        self.queues.values_mut().for_each(VecDeque::clear);
     }
 }
@@ -1,4 +1,4 @@
-use common_net::msg::{ClientType, ServerGeneral, ServerMsg};
+use common_net::msg::{ClientType, ResumeToken, ServerGeneral, ServerMsg};
 use network::{Message, Participant, Stream, StreamError, StreamParams};
 use serde::{de::DeserializeOwned, Serialize};
 use specs::Component;
@@ -16,6 +16,8 @@ pub struct Client {
     pub last_ping: f64,
     pub login_msg_sent: AtomicBool,
     pub locale: Option<String>,
+    /// Session the client asked to resume when registering.
+    pub resume_token: Option<ResumeToken>,
 
     //TODO: Consider splitting each of these out into their own components so all the message
     //processing systems can run in parallel with each other (though it may turn out not to
@@ -68,6 +70,7 @@ impl Client {
             participant: Some(participant),
             last_ping,
             locale,
+            resume_token: None,
             login_msg_sent: AtomicBool::new(false),
             general_stream,
             ping_stream,
@@ -173,7 +176,8 @@ impl Client {
                     | ServerGeneral::CharacterCreated(_)
                     | ServerGeneral::CharacterEdited(_)
                     | ServerGeneral::CharacterSuccess
-                    | ServerGeneral::SpectatorSuccess(_) => {
+                    | ServerGeneral::SpectatorSuccess(_)
+                    | ServerGeneral::SessionResumed { .. } => {
                         PreparedMsg::new(1, &g, &self.character_screen_stream_params)
                     },
                     // In-game related
@@ -184,6 +188,15 @@ impl Client {
                     | ServerGeneral::ExitInGameSuccess
                     | ServerGeneral::InventoryUpdate(_, _)
                     | ServerGeneral::GroupInventoryUpdate(_, _)
+                    | ServerGeneral::LootRoll { .. }
+                    | ServerGeneral::LootRollEnded { .. }
+                    | ServerGeneral::MinigameScoreboard(_)
+                    | ServerGeneral::MinigameEnded(_)
+                    | ServerGeneral::RaceProgress(_)
+                    | ServerGeneral::TreasureMap(_)
+                    | ServerGeneral::NoticeBoard { .. }
+                    | ServerGeneral::Bounties(_)
+                    | ServerGeneral::Reputation(_)
                     | ServerGeneral::SetViewDistance(_)
                     | ServerGeneral::Outcomes(_)
                     | ServerGeneral::Knockback(_)
@@ -191,10 +204,17 @@ impl Client {
                     | ServerGeneral::UpdatePendingTrade(_, _, _)
                     | ServerGeneral::FinishedTrade(_)
                     | ServerGeneral::MapMarker(_)
+                    | ServerGeneral::ScoutPings(_)
                     | ServerGeneral::WeatherUpdate(_)
                     | ServerGeneral::LocalWindUpdate(_)
+                    | ServerGeneral::PlayerPhysicsAck { .. }
                     | ServerGeneral::SpectatePosition(_)
-                    | ServerGeneral::UpdateRecipes => {
+                    | ServerGeneral::UpdateRecipes
+                    | ServerGeneral::SessionToken(_)
+                    | ServerGeneral::WorldgenDebug(_, _)
+                    | ServerGeneral::OpenBank(_)
+                    | ServerGeneral::OpenTrainer(_)
+                    | ServerGeneral::Directions(_) => {
                         PreparedMsg::new(2, &g, &self.in_game_stream_params)
                     },
                     // Terrain
@@ -215,6 +235,8 @@ impl Client {
                     | ServerGeneral::DeleteEntity(_)
                     | ServerGeneral::Disconnect(_)
                     | ServerGeneral::Notification(_)
+                    | ServerGeneral::GuildUpdate(_)
+                    | ServerGeneral::EventCalendar(_)
                     | ServerGeneral::PluginData(_) => {
                         PreparedMsg::new(3, &g, &self.general_stream_params)
                     },
@@ -4,14 +4,19 @@
 #[cfg(feature = "worldgen")]
 use crate::weather::WeatherJob;
 use crate::{
+    account_link::AccountLinks,
     client::Client,
+    guild::Guilds,
     location::Locations,
     login_provider::LoginProvider,
+    moderation::{ModerationTickets, ReportCategory, ReportParty, MAX_OPEN_REPORTS},
+    prefab::Prefabs,
+    race::RaceTracks,
     settings::{
-        server_description::ServerDescription, Ban, BanAction, BanInfo, EditableSetting,
-        SettingError, WhitelistInfo, WhitelistRecord,
+        server_description::ServerDescription, AccountNote, Ban, BanAction, BanInfo,
+        EditableSetting, Sanction, SanctionKind, SettingError, WhitelistInfo, WhitelistRecord,
     },
-    sys::terrain::SpawnEntityData,
+    sys::{minigame::Minigames, terrain::SpawnEntityData},
     wiring,
     wiring::OutputFormula,
     Server, Settings, StateExt,
@@ -24,8 +29,8 @@ use common::{
     assets,
     calendar::Calendar,
     cmd::{
-        AreaKind, EntityTarget, KitSpec, ServerChatCommand, BUFF_PACK, BUFF_PARSER,
-        KIT_MANIFEST_PATH, PRESET_MANIFEST_PATH,
+        AreaKind, EntityTarget, KitSpec, ServerChatCommand, WorldgenDebugField, BUFF_PACK,
+        BUFF_PARSER, KIT_MANIFEST_PATH, PRESET_MANIFEST_PATH,
     },
     comp::{
         self,
@@ -38,29 +43,29 @@ use common::{
         invite::InviteKind,
         misc::PortalData,
         AdminRole, Aura, AuraKind, BuffCategory, ChatType, Content, Inventory, Item, LightEmitter,
-        WaypointArea,
+        LocalizationArg, WaypointArea,
     },
     depot,
     effect::Effect,
     event::{
         ClientDisconnectEvent, CreateNpcEvent, CreateSpecialEntityEvent, EventBus, ExplosionEvent,
-        GroupManipEvent, InitiateInviteEvent, TamePetEvent,
+        GroupManipEvent, InitiateInviteEvent, LightningStrikeEvent, TamePetEvent, UnlockTitleEvent,
     },
     generation::{EntityConfig, EntityInfo, SpecialEntity},
     link::Is,
     mounting::{Rider, Volume, VolumeRider},
     npc::{self, get_npc_name},
-    outcome::Outcome,
     parse_cmd_args,
+    prefab::{Prefab, PrefabPlacement},
     resources::{BattleMode, PlayerPhysicsSettings, ProgramTime, Secs, Time, TimeOfDay, TimeScale},
     rtsim::{Actor, Role},
     spiral::Spiral2d,
-    terrain::{Block, BlockKind, CoordinateConversions, SpriteKind},
+    terrain::{Block, BlockKind, CoordinateConversions, SpriteKind, TerrainGrid},
     tether::Tethered,
     uid::Uid,
     vol::ReadVol,
-    CachedSpatialGrid, Damage, DamageKind, DamageSource, Explosion, GroupTarget, LoadoutBuilder,
-    RadiusEffect,
+    CachedSpatialGrid, Damage, DamageElement, DamageKind, DamageSource, Explosion, GroupTarget,
+    LoadoutBuilder, RadiusEffect,
 };
 #[cfg(feature = "worldgen")]
 use common::{terrain::TerrainChunkSize, weather};
@@ -68,7 +73,9 @@ use common_net::{
     msg::{DisconnectReason, Notification, PlayerListUpdate, ServerGeneral},
     sync::WorldSyncExt,
 };
-use common_state::{Areas, AreasContainer, BuildArea, NoDurabilityArea, SpecialAreaError, State};
+use common_state::{
+    Areas, AreasContainer, BlockChange, BuildArea, NoDurabilityArea, SpecialAreaError, State,
+};
 use core::{cmp::Ordering, convert::TryFrom};
 use hashbrown::{HashMap, HashSet};
 use humantime::Duration as HumanDuration;
@@ -136,6 +143,7 @@ fn do_command(
     }
 
     let handler: CommandHandler = match cmd {
+        ServerChatCommand::AccountNote => handle_account_note,
         ServerChatCommand::Adminify => handle_adminify,
         ServerChatCommand::Airship => handle_spawn_airship,
         ServerChatCommand::Alias => handle_alias,
@@ -152,6 +160,7 @@ fn do_command(
         ServerChatCommand::Campfire => handle_spawn_campfire,
         ServerChatCommand::ClearPersistedTerrain => handle_clear_persisted_terrain,
         ServerChatCommand::DebugColumn => handle_debug_column,
+        ServerChatCommand::DebugOverlay => handle_debug_overlay,
         ServerChatCommand::DebugWays => handle_debug_ways,
         ServerChatCommand::DisconnectAllPlayers => handle_disconnect_all_players,
         ServerChatCommand::DropAll => handle_drop_all,
@@ -159,12 +168,14 @@ fn do_command(
         ServerChatCommand::Explosion => handle_explosion,
         ServerChatCommand::Faction => handle_faction,
         ServerChatCommand::GiveItem => handle_give_item,
+        ServerChatCommand::GiveTitle => handle_give_title,
         ServerChatCommand::Goto => handle_goto,
         ServerChatCommand::Group => handle_group,
         ServerChatCommand::GroupInvite => handle_group_invite,
         ServerChatCommand::GroupKick => handle_group_kick,
         ServerChatCommand::GroupLeave => handle_group_leave,
         ServerChatCommand::GroupPromote => handle_group_promote,
+        ServerChatCommand::Guild => handle_guild,
         ServerChatCommand::Health => handle_health,
         ServerChatCommand::Help => handle_help,
         ServerChatCommand::IntoNpc => handle_into_npc,
@@ -175,15 +186,21 @@ fn do_command(
         ServerChatCommand::KillNpcs => handle_kill_npcs,
         ServerChatCommand::Kit => handle_kit,
         ServerChatCommand::Lantern => handle_lantern,
+        ServerChatCommand::LiftSanction => handle_lift_sanction,
         ServerChatCommand::Light => handle_light,
         ServerChatCommand::MakeBlock => handle_make_block,
         ServerChatCommand::MakeNpc => handle_make_npc,
         ServerChatCommand::MakeSprite => handle_make_sprite,
+        ServerChatCommand::Minigame => handle_minigame,
         ServerChatCommand::Motd => handle_motd,
         ServerChatCommand::Object => handle_object,
         ServerChatCommand::PermitBuild => handle_permit_build,
         ServerChatCommand::Players => handle_players,
         ServerChatCommand::Portal => handle_spawn_portal,
+        ServerChatCommand::PrefabPlace => handle_prefab_place,
+        ServerChatCommand::PrefabSave => handle_prefab_save,
+        ServerChatCommand::Probation => handle_probation,
+        ServerChatCommand::Race => handle_race,
         ServerChatCommand::ResetRecipes => handle_reset_recipes,
         ServerChatCommand::Region => handle_region,
         ServerChatCommand::ReloadChunks => handle_reload_chunks,
@@ -195,6 +212,7 @@ fn do_command(
         ServerChatCommand::Say => handle_say,
         ServerChatCommand::ServerPhysics => handle_server_physics,
         ServerChatCommand::SetMotd => handle_set_motd,
+        ServerChatCommand::ShadowMute => handle_shadow_mute,
         ServerChatCommand::Ship => handle_spawn_ship,
         ServerChatCommand::Site => handle_site,
         ServerChatCommand::SkillPoint => handle_skill_point,
@@ -208,6 +226,7 @@ fn do_command(
         ServerChatCommand::RtsimTp => handle_rtsim_tp,
         ServerChatCommand::RtsimInfo => handle_rtsim_info,
         ServerChatCommand::RtsimNpc => handle_rtsim_npc,
+        ServerChatCommand::RtsimPopulation => handle_rtsim_population,
         ServerChatCommand::RtsimPurge => handle_rtsim_purge,
         ServerChatCommand::RtsimChunk => handle_rtsim_chunk,
         ServerChatCommand::Unban => handle_unban,
@@ -218,12 +237,15 @@ fn do_command(
         ServerChatCommand::World => handle_world,
         ServerChatCommand::MakeVolume => handle_make_volume,
         ServerChatCommand::Location => handle_location,
+        ServerChatCommand::LinkAccount => handle_link_account,
         ServerChatCommand::CreateLocation => handle_create_location,
+        ServerChatCommand::Creative => handle_creative,
         ServerChatCommand::DeleteLocation => handle_delete_location,
         ServerChatCommand::WeatherZone => handle_weather_zone,
         ServerChatCommand::Lightning => handle_lightning,
         ServerChatCommand::Scale => handle_scale,
         ServerChatCommand::RepairEquipment => handle_repair_equipment,
+        ServerChatCommand::Report => handle_report,
         ServerChatCommand::Tether => handle_tether,
         ServerChatCommand::DestroyTethers => handle_destroy_tethers,
         ServerChatCommand::Mount => handle_mount,
@@ -808,6 +830,176 @@ fn handle_make_sprite(
     }
 }
 
+fn handle_minigame(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (Some(minigame_action), id) = parse_cmd_args!(args, String, String) else {
+        return Err(Content::Plain(action.help_string()));
+    };
+    let id = id.map(|id| {
+        if id.starts_with("common.minigame.") {
+            id
+        } else {
+            format!("common.minigame.{id}")
+        }
+    });
+    let time = *server.state.ecs().read_resource::<Time>();
+
+    let msg = match (minigame_action.as_str(), id) {
+        ("start" | "stop", _) if server.entity_admin_role(client) < Some(AdminRole::Admin) => {
+            return Err("You don't have permission to start or stop minigames.".into());
+        },
+        ("start", Some(id)) => {
+            let name = server
+                .state
+                .ecs()
+                .write_resource::<Minigames>()
+                .start(&id, time)?;
+            Content::localized_with_args("command-minigame-started", [("minigame", name)])
+        },
+        ("stop", Some(id)) => {
+            server.state.ecs().write_resource::<Minigames>().stop(&id)?;
+            Content::localized("command-minigame-stopped")
+        },
+        ("join", Some(id)) => {
+            let uid = uid(server, target, "target")?;
+            let pos = position(server, target, "target")?;
+            let start = server
+                .state
+                .ecs()
+                .write_resource::<Minigames>()
+                .join(&id, uid, pos.0)?;
+            server
+                .state
+                .position_mut(target, true, |current_pos| current_pos.0 = start)?;
+            Content::localized("command-minigame-joined")
+        },
+        ("leave", _) => {
+            let uid = uid(server, target, "target")?;
+            let (scoreboard, return_pos) = server
+                .state
+                .ecs()
+                .write_resource::<Minigames>()
+                .leave(uid, time)
+                .ok_or("You are not taking part in a minigame")?;
+            server.notify_client(target, ServerGeneral::MinigameEnded(scoreboard));
+            server
+                .state
+                .position_mut(target, true, |current_pos| current_pos.0 = return_pos)?;
+            Content::localized("command-minigame-left")
+        },
+        ("list", _) => {
+            let minigames = server.state.ecs().read_resource::<Minigames>();
+            let mut running = minigames
+                .running()
+                .map(|(id, name)| {
+                    format!(
+                        "{name} ({})",
+                        id.strip_prefix("common.minigame.").unwrap_or(id)
+                    )
+                })
+                .collect::<Vec<_>>();
+            running.sort();
+            if running.is_empty() {
+                Content::localized("command-minigame-none-running")
+            } else {
+                Content::localized_with_args("command-minigame-list", [(
+                    "minigames",
+                    running.join(", "),
+                )])
+            }
+        },
+        _ => return Err(Content::Plain(action.help_string())),
+    };
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, msg),
+    );
+    Ok(())
+}
+
+fn handle_race(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (Some(race_action), track) = parse_cmd_args!(args, String, String) else {
+        return Err(Content::Plain(action.help_string()));
+    };
+
+    let msg = match (race_action.as_str(), track) {
+        ("checkpoint" | "remove", _)
+            if server.entity_admin_role(client) < Some(AdminRole::Admin) =>
+        {
+            return Err("You don't have permission to edit race tracks.".into());
+        },
+        ("checkpoint", Some(track)) => {
+            let pos = position(server, target, "target")?;
+            let checkpoints = server
+                .state
+                .ecs()
+                .write_resource::<RaceTracks>()
+                .add_checkpoint(&track, pos.0);
+            Content::localized_with_args("command-race-checkpoint-added", [
+                ("track", track),
+                ("checkpoints", checkpoints.to_string()),
+            ])
+        },
+        ("remove", Some(track)) => {
+            server
+                .state
+                .ecs()
+                .write_resource::<RaceTracks>()
+                .remove(&track)?;
+            Content::localized_with_args("command-race-removed", [("track", track)])
+        },
+        ("leaderboard", Some(track)) => {
+            let race_tracks = server.state.ecs().read_resource::<RaceTracks>();
+            let leaderboard = race_tracks
+                .get(&track)
+                .ok_or_else(|| format!("There is no race track called '{track}'"))?
+                .leaderboard()
+                .iter()
+                .enumerate()
+                .map(|(i, record)| format!("{}. {} {:.2}s", i + 1, record.name, record.time))
+                .collect::<Vec<_>>();
+            if leaderboard.is_empty() {
+                Content::localized_with_args("command-race-no-records", [("track", track)])
+            } else {
+                Content::localized_with_args("command-race-leaderboard", [
+                    ("track", track),
+                    ("records", leaderboard.join("\n")),
+                ])
+            }
+        },
+        ("list", _) => {
+            let race_tracks = server.state.ecs().read_resource::<RaceTracks>();
+            let mut tracks = race_tracks
+                .tracks()
+                .map(|(name, track)| format!("{name} ({})", track.checkpoints.len()))
+                .collect::<Vec<_>>();
+            tracks.sort();
+            if tracks.is_empty() {
+                Content::localized("command-race-no-tracks")
+            } else {
+                Content::localized_with_args("command-race-tracks", [("tracks", tracks.join(", "))])
+            }
+        },
+        _ => return Err(Content::Plain(action.help_string())),
+    };
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, msg),
+    );
+    Ok(())
+}
+
 fn handle_motd(
     server: &mut Server,
     client: EcsEntity,
@@ -1423,7 +1615,8 @@ fn handle_rtsim_info(
     use crate::rtsim::RtSim;
     if let Some(id) = parse_cmd_args!(args, u64) {
         let rtsim = server.state.ecs().read_resource::<RtSim>();
-        let data = rtsim.state().data();
+        let state = rtsim.state();
+        let data = state.data();
         let (id, npc) = data
             .npcs
             .iter()
@@ -1488,7 +1681,8 @@ fn handle_rtsim_npc(
             .collect::<Vec<_>>();
         let npc_names = &*common::npc::NPC_NAMES.read();
         let rtsim = server.state.ecs().read_resource::<RtSim>();
-        let data = rtsim.state().data();
+        let state = rtsim.state();
+        let data = state.data();
         let mut npcs = data
             .npcs
             .values()
@@ -1544,6 +1738,63 @@ fn handle_rtsim_npc(
     }
 }
 
+fn handle_rtsim_population(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    use crate::rtsim::RtSim;
+    use rtsim::data::npc::SimulationMode;
+    let rtsim = server.state.ecs().read_resource::<RtSim>();
+    let state = rtsim.state();
+    let data = state.data();
+
+    // Role -> (total, loaded)
+    let mut counts = std::collections::BTreeMap::<String, (usize, usize)>::new();
+    for npc in data.npcs.values().filter(|npc| !npc.is_dead) {
+        let role = match &npc.role {
+            Role::Civilised(Some(profession)) => format!("{:?}", profession),
+            Role::Civilised(None) => "Civilised".to_string(),
+            Role::Wild => "Wild".to_string(),
+            Role::Monster => "Monster".to_string(),
+            Role::Vehicle => "Vehicle".to_string(),
+        };
+        let count = counts.entry(role).or_default();
+        count.0 += 1;
+        if matches!(npc.mode, SimulationMode::Loaded) {
+            count.1 += 1;
+        }
+    }
+
+    let mut info = String::new();
+    let _ = writeln!(&mut info, "-- Rtsim population --");
+    for (role, (total, loaded)) in &counts {
+        let _ = writeln!(&mut info, "{role}: {total} ({loaded} loaded)");
+    }
+    let (total, loaded) = counts
+        .values()
+        .fold((0, 0), |(t, l), (total, loaded)| (t + total, l + loaded));
+    let max_npcs = server
+        .settings()
+        .world
+        .population
+        .max_npcs
+        .map_or_else(|| "none".to_string(), |max| max.to_string());
+    let _ = writeln!(
+        &mut info,
+        "Total: {total} ({loaded} loaded), generation cap: {max_npcs}"
+    );
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, info),
+    );
+
+    Ok(())
+}
+
 // TODO: Remove this command when rtsim becomes more mature and we're sure we
 // don't need purges to fix broken state.
 fn handle_rtsim_purge(
@@ -1594,9 +1845,10 @@ fn handle_rtsim_chunk(
     let chunk_key = pos.0.xy().as_::<i32>().wpos_to_cpos();
 
     let rtsim = server.state.ecs().read_resource::<RtSim>();
-    let data = rtsim.state().data();
+    let state = rtsim.state();
+    let data = state.data();
 
-    let chunk_states = rtsim.state().resource::<ChunkStates>();
+    let chunk_states = state.resource::<ChunkStates>();
     let chunk_state = match chunk_states.0.get(chunk_key) {
         Some(Some(chunk_state)) => chunk_state,
         Some(None) => {
@@ -1998,6 +2250,85 @@ fn handle_make_volume(
     Ok(())
 }
 
+fn handle_prefab_save(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (Some(name), Some(x0), Some(x1), Some(y0), Some(y1), Some(z0), Some(z1)) =
+        parse_cmd_args!(args, String, i32, i32, i32, i32, i32, i32)
+    else {
+        return Err(Content::Plain(action.help_string()));
+    };
+    let prefab = Prefab::capture(&*server.state.terrain(), Aabb {
+        min: Vec3::new(x0, y0, z0),
+        max: Vec3::new(x1, y1, z1),
+    })
+    .map_err(Content::Plain)?;
+    let size = prefab.size();
+    server
+        .state
+        .ecs()
+        .write_resource::<Prefabs>()
+        .save(&name, prefab)
+        .map_err(Content::Plain)?;
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            Content::Plain(format!(
+                "Saved prefab '{name}' of size {}x{}x{}",
+                size.x, size.y, size.z
+            )),
+        ),
+    );
+    Ok(())
+}
+
+fn handle_prefab_place(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (Some(name), rotation, remap) = parse_cmd_args!(args, String, u8, String) else {
+        return Err(Content::Plain(action.help_string()));
+    };
+    let pos = position(server, target, "target")?;
+    let placement = PrefabPlacement {
+        prefab: name,
+        origin: pos.0.map(|e| e.floor() as i32),
+        rotation: rotation.unwrap_or(0) % 4,
+        remap: PrefabPlacement::parse_remap(remap.as_deref().unwrap_or(""))
+            .map_err(Content::Plain)?,
+    };
+    let ecs = server.state.ecs();
+    let bounds = ecs
+        .write_resource::<Prefabs>()
+        .place(
+            placement,
+            &ecs.read_resource::<TerrainGrid>(),
+            &mut ecs.write_resource::<BlockChange>(),
+        )
+        .map_err(Content::Plain)?;
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            Content::Plain(format!(
+                "Placed prefab from {:?} to {:?}",
+                bounds.min, bounds.max
+            )),
+        ),
+    );
+    Ok(())
+}
+
 fn handle_spawn_campfire(
     server: &mut Server,
     client: EcsEntity,
@@ -2112,6 +2443,7 @@ fn handle_permit_build(
             .map_err(|_| "Cannot find target entity!".to_string())?;
         let mut comp_can_build = entry.or_insert(comp::CanBuild {
             enabled: false,
+            creative: false,
             build_areas: HashSet::new(),
         });
         comp_can_build.build_areas.insert(bb_id);
@@ -2309,6 +2641,38 @@ fn handle_build(
     }
 }
 
+fn handle_creative(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let creative = {
+        let mut can_build = server.state.ecs().write_storage::<comp::CanBuild>();
+        let mut can_build = can_build
+            .entry(target)
+            .map_err(|_| "Cannot find target entity!".to_string())?
+            .or_insert_with(comp::CanBuild::default);
+        can_build.creative ^= true;
+        // Building is what creative mode is for
+        can_build.enabled |= can_build.creative;
+        can_build.creative
+    };
+
+    let msg = Content::localized(if creative {
+        "command-set-creative-mode-on"
+    } else {
+        "command-set-creative-mode-off"
+    });
+    let chat_msg = ServerGeneral::server_msg(ChatType::CommandInfo, msg);
+    if client != target {
+        server.notify_client(target, chat_msg.clone());
+    }
+    server.notify_client(client, chat_msg);
+    Ok(())
+}
+
 fn get_areas_mut<'l>(kind: &str, state: &'l mut State) -> CmdResult<&'l mut Areas> {
     Ok(match AreaKind::from_str(kind).ok() {
         Some(AreaKind::Build) => state
@@ -2561,13 +2925,7 @@ fn handle_kill_npcs(
 
             if should_kill {
                 if let Some(rtsim_entity) = rtsim_entities.get(entity).copied() {
-                    rtsim.hook_rtsim_actor_death(
-                        &ecs.read_resource::<Arc<world::World>>(),
-                        ecs.read_resource::<world::IndexOwned>().as_index_ref(),
-                        Actor::Npc(rtsim_entity.0),
-                        Some(pos.0),
-                        None,
-                    );
+                    rtsim.hook_rtsim_actor_death(Actor::Npc(rtsim_entity.0), Some(pos.0), None);
                 }
                 Some(entity)
             } else {
@@ -2941,6 +3299,7 @@ fn handle_explosion(
                 RadiusEffect::Entity(Effect::Damage(Damage {
                     source: DamageSource::Explosion,
                     kind: DamageKind::Energy,
+                    element: DamageElement::Physical,
                     value: 100.0 * power,
                 })),
                 RadiusEffect::TerrainDestruction(power, Rgb::black()),
@@ -3214,6 +3573,96 @@ fn handle_tell(
     }
 }
 
+fn report_party(server: &Server, entity: EcsEntity, descriptor: &str) -> CmdResult<ReportParty> {
+    let ecs = server.state.ecs();
+    let players = ecs.read_storage::<comp::Player>();
+    let player = players.get(entity).ok_or_else(|| {
+        Content::localized_with_args("command-player-info-unavailable", [("target", descriptor)])
+    })?;
+    Ok(ReportParty {
+        uuid: player.uuid(),
+        alias: player.alias.clone(),
+        pos: ecs
+            .read_storage::<comp::Pos>()
+            .get(entity)
+            .map(|pos| pos.0.into_array()),
+    })
+}
+
+fn handle_report(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+
+    if let (Some(alias), Some(category), comment) =
+        parse_cmd_args!(args, String, String, ..Vec<String>)
+    {
+        let category = category
+            .parse::<ReportCategory>()
+            .map_err(|_| Content::Plain(action.help_string()))?;
+        let reported = find_alias(server.state.ecs(), &alias)?.0;
+        if reported == client {
+            return Err(Content::localized("command-report-self"));
+        }
+        let reporter = report_party(server, client, "client")?;
+        let reported = report_party(server, reported, "player")?;
+        let chat_context = server
+            .chat_cache()
+            .involving(&[reporter.uuid, reported.uuid]);
+        let reported_alias = reported.alias.clone();
+
+        let id = server
+            .state
+            .ecs()
+            .write_resource::<ModerationTickets>()
+            .open(
+                category,
+                comment.join(" "),
+                reporter,
+                reported,
+                chat_context,
+            )
+            .ok_or_else(|| {
+                Content::localized_with_args("command-report-too-many", [(
+                    "max",
+                    MAX_OPEN_REPORTS as u64,
+                )])
+            })?;
+
+        server.notify_client(
+            client,
+            ServerGeneral::server_msg(
+                ChatType::CommandInfo,
+                Content::localized_with_args("command-report-filed", [("id", id)]),
+            ),
+        );
+        // Let moderators that are online know right away
+        let notice = ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            Content::localized_with_args("command-report-notify", [
+                ("id", id.to_string()),
+                ("category", category.as_str().to_string()),
+                ("player", reported_alias),
+            ]),
+        );
+        for (client, _) in (
+            &server.state.ecs().read_storage::<Client>(),
+            &server.state.ecs().read_storage::<comp::Admin>(),
+        )
+            .join()
+        {
+            client.send_fallible(notice.clone());
+        }
+        Ok(())
+    } else {
+        Err(Content::Plain(action.help_string()))
+    }
+}
+
 fn handle_faction(
     server: &mut Server,
     client: EcsEntity,
@@ -3243,6 +3692,46 @@ fn handle_faction(
     }
 }
 
+fn handle_guild(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+
+    let tag = server
+        .state
+        .ecs()
+        .read_storage::<comp::Player>()
+        .get(target)
+        .and_then(|player| {
+            server
+                .state
+                .ecs()
+                .read_resource::<Guilds>()
+                .tag_of(player.uuid())
+                .map(str::to_owned)
+        });
+    if let Some(tag) = tag {
+        let mode = comp::ChatMode::Guild(tag);
+        insert_or_replace_component(server, target, mode.clone(), "target")?;
+        let msg = args.join(" ");
+        if !msg.is_empty() {
+            if let Some(uid) = server.state.ecs().read_storage().get(target) {
+                server
+                    .state
+                    .send_chat(mode.to_msg(*uid, Content::Plain(msg), None)?);
+            }
+        }
+        server.notify_client(target, ServerGeneral::ChatMode(mode));
+        Ok(())
+    } else {
+        Err(Content::localized("command-message-guild-missing"))
+    }
+}
+
 fn handle_group(
     server: &mut Server,
     client: EcsEntity,
@@ -3500,6 +3989,15 @@ fn handle_join_faction(
                     .into_plain_msg(format!("[{}] left faction ({})", alias, faction)),
             );
         }
+        if let Some(uid) = server.state.ecs().uid_from_entity(target) {
+            let faction = match &mode {
+                comp::ChatMode::Faction(faction) => Some(faction.clone()),
+                _ => None,
+            };
+            server.state.notify_players(ServerGeneral::PlayerListUpdate(
+                PlayerListUpdate::Faction(uid, faction),
+            ));
+        }
         server.notify_client(target, ServerGeneral::ChatMode(mode));
         Ok(())
     } else {
@@ -3601,6 +4099,101 @@ cliff_height {:?} "#,
     }
 }
 
+#[cfg(not(feature = "worldgen"))]
+fn handle_debug_overlay(
+    _server: &mut Server,
+    _client: EcsEntity,
+    _target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    Err("Unsupported without worldgen enabled".into())
+}
+
+#[cfg(feature = "worldgen")]
+fn handle_debug_overlay(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    use common_net::msg::world_msg::WorldgenDebugOverlay;
+
+    let (Some(field), radius) = parse_cmd_args!(args, WorldgenDebugField, i32) else {
+        return Err(Content::Plain(action.help_string()));
+    };
+    let radius = radius.unwrap_or(64).clamp(0, 256);
+    if radius == 0 {
+        server.notify_client(client, ServerGeneral::WorldgenDebug(field, None));
+        return Ok(());
+    }
+
+    let center = position(server, target, "target")?.0.xy().map(|e| e as i32);
+    let sim = server.world.sim();
+    let calendar = (*server.state.ecs().read_resource::<Calendar>()).clone();
+    let sampler = server.world.sample_columns();
+    let index = server.index.as_index_ref();
+
+    // Keep the number of samples the same regardless of the radius
+    let step = (radius / 16).max(1);
+    let mut samples = Vec::new();
+    let mut lines = Vec::new();
+    if field == WorldgenDebugField::Plots {
+        for site in index.sites.values().filter_map(|site| site.site2()) {
+            for plot in site.plots() {
+                let bounds = plot.find_bounds();
+                let min = site.tile_wpos(bounds.min);
+                let max = site.tile_wpos(bounds.max + 1);
+                if max.x < center.x - radius
+                    || min.x > center.x + radius
+                    || max.y < center.y - radius
+                    || min.y > center.y + radius
+                {
+                    continue;
+                }
+                let corners =
+                    [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)].map(|corner| {
+                        corner
+                            .as_()
+                            .with_z(sim.get_alt_approx(corner).unwrap_or(0.0) + 1.0)
+                    });
+                for i in 0..corners.len() {
+                    lines.push([corners[i], corners[(i + 1) % corners.len()]]);
+                }
+            }
+        }
+    } else {
+        for y in (-radius..=radius).step_by(step as usize) {
+            for x in (-radius..=radius).step_by(step as usize) {
+                let wpos = center + Vec2::new(x, y);
+                let Some(col) = sampler.get((wpos, index, Some(&calendar))) else {
+                    continue;
+                };
+                let value = match field {
+                    WorldgenDebugField::Humidity => col.humidity,
+                    WorldgenDebugField::Temperature => (col.temp + 1.0) / 2.0,
+                    WorldgenDebugField::TreeDensity => col.tree_density,
+                    // Closeness to the nearest path or cave, fading out over 32 blocks
+                    WorldgenDebugField::Path => col.path.map_or(0.0, |(d, ..)| 1.0 - d / 32.0),
+                    WorldgenDebugField::Cave => col.cave.map_or(0.0, |(d, ..)| 1.0 - d / 32.0),
+                    WorldgenDebugField::Plots => unreachable!(),
+                };
+                samples.push((
+                    wpos.as_().with_z(col.alt.max(col.water_level)),
+                    value.clamp(0.0, 1.0),
+                ));
+            }
+        }
+    }
+
+    server.notify_client(
+        client,
+        ServerGeneral::WorldgenDebug(field, Some(WorldgenDebugOverlay { samples, lines })),
+    );
+    Ok(())
+}
+
 #[cfg(not(feature = "worldgen"))]
 fn handle_debug_ways(
     _server: &mut Server,
@@ -3717,6 +4310,40 @@ fn handle_skill_point(
     }
 }
 
+fn handle_give_title(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    if let (Some(title), alias) = parse_cmd_args!(args, String, String) {
+        let title = comp::Title::from_key(&title).ok_or_else(|| {
+            Content::localized_with_args("command-invalid-title", [("title", title.clone())])
+        })?;
+        let player = match alias {
+            Some(alias) => find_alias(server.state.ecs(), &alias)?.0,
+            None => target,
+        };
+        if server
+            .state
+            .ecs()
+            .read_storage::<comp::Titles>()
+            .contains(player)
+        {
+            server.state.emit_event_now(UnlockTitleEvent {
+                entity: player,
+                title,
+            });
+            Ok(())
+        } else {
+            Err(Content::localized("command-title-no-character"))
+        }
+    } else {
+        Err(Content::Plain(action.help_string()))
+    }
+}
+
 fn parse_skill_tree(skill_tree: &str) -> CmdResult<comp::skillset::SkillGroupKind> {
     use comp::{item::tool::ToolKind, skillset::SkillGroupKind};
     match skill_tree {
@@ -4435,6 +5062,186 @@ fn handle_unban(
     }
 }
 
+/// Sanctions can only be given to players with a lower permanent role.
+fn verify_can_sanction(server: &Server, client_uuid: Uuid, player_uuid: Uuid) -> CmdResult<()> {
+    let admins = &server.editable_settings().admins;
+    if admins.get(&client_uuid).map(|record| record.role)
+        > admins.get(&player_uuid).map(|record| record.role)
+    {
+        Ok(())
+    } else {
+        Err("Cannot sanction players with roles higher than or equal to your own.".into())
+    }
+}
+
+fn handle_sanction(
+    server: &mut Server,
+    client: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+    kind: SanctionKind,
+) -> CmdResult<()> {
+    if let (Some(username), parse_duration, reason_opt) =
+        parse_cmd_args!(args, String, HumanDuration, String)
+    {
+        let reason = reason_opt.unwrap_or_default();
+        let player_uuid = find_username(server, &username)?;
+
+        let client_uuid = uuid(server, client, "client")?;
+        let client_username = uuid_to_username(server, client, client_uuid)?;
+        verify_can_sanction(server, client_uuid, player_uuid)?;
+
+        let now = Utc::now();
+        let end_date = parse_duration
+            .map(|duration| chrono::Duration::from_std(duration.into()))
+            .transpose()
+            .map_err(|err| format!("Error converting to duration: {}", err))?
+            // On overflow, just make the sanction last until it is lifted.
+            .and_then(|duration| now.checked_add_signed(duration));
+
+        let sanction = Sanction {
+            reason: reason.clone(),
+            performed_by_username: client_username,
+            date: now,
+            end_date,
+        };
+
+        let edit = server.editable_settings_mut().sanctions.edit(
+            server.data_dir().as_ref(),
+            |sanctions| {
+                sanctions.set(player_uuid, username.clone(), kind, Some(sanction));
+                Some(format!(
+                    "Applied {} to {} with reason: {}",
+                    kind.as_str(),
+                    username,
+                    reason
+                ))
+            },
+        );
+
+        edit_setting_feedback(server, client, edit, || {
+            unreachable!("edit always succeeds")
+        })
+    } else {
+        Err(Content::Plain(action.help_string()))
+    }
+}
+
+fn handle_shadow_mute(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    handle_sanction(server, client, args, action, SanctionKind::ShadowMute)
+}
+
+fn handle_probation(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    handle_sanction(server, client, args, action, SanctionKind::Probation)
+}
+
+fn handle_lift_sanction(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    if let (Some(username), Some(kind)) = parse_cmd_args!(args, String, String) {
+        let kind = kind
+            .parse::<SanctionKind>()
+            .map_err(|_| Content::Plain(action.help_string()))?;
+        let player_uuid = find_username(server, &username)?;
+
+        let edit = server.editable_settings_mut().sanctions.edit(
+            server.data_dir().as_ref(),
+            |sanctions| {
+                sanctions
+                    .set(player_uuid, username.clone(), kind, None)
+                    .then(|| format!("Lifted {} from {}", kind.as_str(), username))
+            },
+        );
+
+        edit_setting_feedback(server, client, edit, || {
+            format!("{} has no {} to lift", username, kind.as_str())
+        })
+    } else {
+        Err(Content::Plain(action.help_string()))
+    }
+}
+
+fn handle_account_note(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    if let (Some(username), text) = parse_cmd_args!(args, String, String) {
+        let player_uuid = find_username(server, &username)?;
+
+        if let Some(text) = text {
+            let client_uuid = uuid(server, client, "client")?;
+            let note = AccountNote {
+                text,
+                author: uuid_to_username(server, client, client_uuid)?,
+                date: Utc::now(),
+            };
+            let edit = server.editable_settings_mut().sanctions.edit(
+                server.data_dir().as_ref(),
+                |sanctions| {
+                    sanctions.add_note(player_uuid, username.clone(), note);
+                    Some(format!("Added a note to {}", username))
+                },
+            );
+
+            edit_setting_feedback(server, client, edit, || {
+                unreachable!("edit always succeeds")
+            })
+        } else {
+            let now = Utc::now();
+            let mut msg = format!("Sanctions and notes of {}:", username);
+            if let Some(record) = server.editable_settings().sanctions.get(&player_uuid) {
+                for kind in SanctionKind::ALL {
+                    if let Some(sanction) = record.sanction(kind).filter(|s| s.is_active(now)) {
+                        msg.push_str(&format!(
+                            "\n{} by {} until {}: {}",
+                            kind.as_str(),
+                            sanction.performed_by_username,
+                            sanction
+                                .end_date
+                                .map_or_else(|| "lifted".to_string(), |date| date.to_rfc2822()),
+                            sanction.reason,
+                        ));
+                    }
+                }
+                for note in &record.notes {
+                    msg.push_str(&format!(
+                        "\n[{}] {}: {}",
+                        note.date.to_rfc2822(),
+                        note.author,
+                        note.text
+                    ));
+                }
+            }
+            server.notify_client(
+                client,
+                ServerGeneral::server_msg(ChatType::CommandInfo, msg),
+            );
+            Ok(())
+        }
+    } else {
+        Err(Content::Plain(action.help_string()))
+    }
+}
+
 fn handle_server_physics(
     server: &mut Server,
     client: EcsEntity,
@@ -4556,6 +5363,9 @@ fn build_buff(
             | BuffKind::Flame
             | BuffKind::Frigid
             | BuffKind::Lifesteal
+            | BuffKind::PoisonCoating
+            | BuffKind::FireCoating
+            | BuffKind::FrostCoating
             | BuffKind::ImminentCritical
             | BuffKind::Fury
             | BuffKind::Sunderer
@@ -4578,6 +5388,7 @@ fn build_buff(
             | BuffKind::Winded
             | BuffKind::Concussion
             | BuffKind::Staggered
+            | BuffKind::Electrified
             | BuffKind::Tenacity
             | BuffKind::Resilience => {
                 if buff_kind.is_simple() {
@@ -4685,6 +5496,46 @@ fn set_skills(skill_set: &mut comp::SkillSet, preset: &str) -> CmdResult<()> {
     }
 }
 
+fn handle_link_account(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+
+    let settings = server.settings().account_link.clone();
+    if !settings.enabled {
+        return Err(Content::localized("command-link-account-disabled"));
+    }
+    let player_uuid = uuid(server, client, "client")?;
+    let username = uuid_to_username(server, client, player_uuid)?;
+
+    let mut account_links = server.state.ecs().write_resource::<AccountLinks>();
+    if account_links.get(&player_uuid).is_some() {
+        return Err(Content::localized("command-link-account-linked"));
+    }
+    let code = account_links.create_code(player_uuid, username, settings.code_lifetime);
+    drop(account_links);
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            Content::localized_with_args("command-link-account-code", [
+                ("code", LocalizationArg::from(code)),
+                (
+                    "minutes",
+                    LocalizationArg::from(settings.code_lifetime.as_secs() / 60),
+                ),
+                ("instructions", LocalizationArg::from(settings.instructions)),
+            ]),
+        ),
+    );
+    Ok(())
+}
+
 fn handle_location(
     server: &mut Server,
     client: EcsEntity,
@@ -4870,8 +5721,8 @@ fn handle_lightning(
     server
         .state
         .ecs()
-        .read_resource::<EventBus<Outcome>>()
-        .emit_now(Outcome::Lightning { pos });
+        .read_resource::<EventBus<LightningStrikeEvent>>()
+        .emit_now(LightningStrikeEvent { pos });
     Ok(())
 }
 
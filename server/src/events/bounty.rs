@@ -0,0 +1,295 @@
+use crate::client::Client;
+#[cfg(feature = "worldgen")]
+use crate::{
+    bounty::{self, Bounties, HIDEOUT_RADIUS},
+    rtsim::RtSim,
+};
+#[cfg(feature = "worldgen")]
+use common::{
+    assets::AssetExt,
+    bounty::BountyTarget,
+    comp::{
+        item::{tool::AbilityMap, MaterialStatManifest},
+        loot_owner::LootOwnerKind,
+        Content, InventoryUpdate, InventoryUpdateEvent, LocalizationArg, LootOwner, PickupItem,
+        Presence, PresenceKind, Title,
+    },
+    event::{BountyKillEvent, CreateItemDropEvent, EventBus, UnlockTitleEvent},
+    resources::ProgramTime,
+    uid::Uid,
+};
+use common::{
+    bounty::NOTICE_BOARD_RANGE,
+    comp,
+    event::{AcceptBountyEvent, ReadNoticeBoardEvent},
+    terrain::{SpriteKind, TerrainGrid},
+    vol::ReadVol,
+};
+use common_net::msg::ServerGeneral;
+use specs::{DispatcherBuilder, Entity as EcsEntity, ReadExpect, ReadStorage};
+#[cfg(feature = "worldgen")]
+use specs::{Join, Read, WriteExpect, WriteStorage};
+#[cfg(feature = "worldgen")] use tracing::warn;
+use vek::*;
+#[cfg(feature = "worldgen")]
+use world::IndexOwned;
+
+use super::{event_dispatch, ServerEvent};
+
+pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
+    event_dispatch::<ReadNoticeBoardEvent>(builder);
+    event_dispatch::<AcceptBountyEvent>(builder);
+    #[cfg(feature = "worldgen")]
+    event_dispatch::<BountyKillEvent>(builder);
+}
+
+fn at_notice_board(
+    terrain: &TerrainGrid,
+    positions: &ReadStorage<comp::Pos>,
+    entity: EcsEntity,
+    board: Vec3<i32>,
+) -> bool {
+    terrain.get(board).map_or(false, |block| {
+        block.get_sprite() == Some(SpriteKind::NoticeBoard)
+    }) && positions.get(entity).map_or(false, |pos| {
+        pos.0.distance_squared(board.as_() + 0.5) <= NOTICE_BOARD_RANGE.powi(2)
+    })
+}
+
+#[cfg(not(feature = "worldgen"))]
+impl ServerEvent for ReadNoticeBoardEvent {
+    type SystemData<'a> = (
+        ReadExpect<'a, TerrainGrid>,
+        ReadStorage<'a, comp::Pos>,
+        ReadStorage<'a, Client>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (terrain, positions, clients): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            if at_notice_board(&terrain, &positions, ev.entity, ev.pos)
+                && let Some(client) = clients.get(ev.entity)
+            {
+                client.send_fallible(ServerGeneral::NoticeBoard {
+                    pos: ev.pos,
+                    bounties: Vec::new(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(feature = "worldgen")]
+impl ServerEvent for ReadNoticeBoardEvent {
+    type SystemData<'a> = (
+        ReadExpect<'a, IndexOwned>,
+        ReadExpect<'a, RtSim>,
+        ReadExpect<'a, TerrainGrid>,
+        ReadStorage<'a, comp::Pos>,
+        ReadStorage<'a, Client>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (index, rtsim, terrain, positions, clients): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            if at_notice_board(&terrain, &positions, ev.entity, ev.pos)
+                && let Some(client) = clients.get(ev.entity)
+            {
+                let bounties = bounty::board_offers(
+                    rtsim.state().data(),
+                    index.as_index_ref(),
+                    ev.pos.xy().as_(),
+                );
+                client.send_fallible(ServerGeneral::NoticeBoard {
+                    pos: ev.pos,
+                    bounties,
+                });
+            }
+        }
+    }
+}
+
+/// Notice boards don't offer any bounties without worldgen
+#[cfg(not(feature = "worldgen"))]
+impl ServerEvent for AcceptBountyEvent {
+    type SystemData<'a> = ();
+
+    fn handle(_events: impl ExactSizeIterator<Item = Self>, _: Self::SystemData<'_>) {}
+}
+
+#[cfg(feature = "worldgen")]
+impl ServerEvent for AcceptBountyEvent {
+    type SystemData<'a> = (
+        ReadExpect<'a, IndexOwned>,
+        ReadExpect<'a, RtSim>,
+        ReadExpect<'a, TerrainGrid>,
+        WriteExpect<'a, Bounties>,
+        ReadStorage<'a, comp::Pos>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Client>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (index, rtsim, terrain, mut bounties, positions, presences, clients): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            let Some(PresenceKind::Character(character_id)) =
+                presences.get(ev.entity).map(|presence| presence.kind)
+            else {
+                continue;
+            };
+            if !at_notice_board(&terrain, &positions, ev.entity, ev.board) {
+                continue;
+            }
+            let offer = bounty::board_offers(
+                rtsim.state().data(),
+                index.as_index_ref(),
+                ev.board.xy().as_(),
+            )
+            .into_iter()
+            .find(|offer| offer.target == ev.target);
+            if let Some(offer) = offer
+                && bounties.accept(character_id, offer)
+                && let Some(client) = clients.get(ev.entity)
+            {
+                client.send_fallible(ServerGeneral::Bounties(bounties.get(character_id).to_vec()));
+            }
+        }
+    }
+}
+
+#[derive(SystemData)]
+#[cfg(feature = "worldgen")]
+pub struct BountyKillData<'a> {
+    ability_map: ReadExpect<'a, AbilityMap>,
+    msm: ReadExpect<'a, MaterialStatManifest>,
+    program_time: ReadExpect<'a, ProgramTime>,
+    bounties: WriteExpect<'a, Bounties>,
+    rtsim: WriteExpect<'a, RtSim>,
+    create_item_drop: Read<'a, EventBus<CreateItemDropEvent>>,
+    unlock_title: Read<'a, EventBus<UnlockTitleEvent>>,
+    uids: ReadStorage<'a, Uid>,
+    positions: ReadStorage<'a, comp::Pos>,
+    presences: ReadStorage<'a, Presence>,
+    clients: ReadStorage<'a, Client>,
+    inventories: WriteStorage<'a, comp::Inventory>,
+    inventory_updates: WriteStorage<'a, InventoryUpdate>,
+}
+
+/// Pays out the bounty the killer pursued on their victim, if any, and drops
+/// the hunts for rtsim NPCs that died, since nobody can complete them anymore.
+#[cfg(feature = "worldgen")]
+impl ServerEvent for BountyKillEvent {
+    type SystemData<'a> = BountyKillData<'a>;
+
+    fn handle(events: impl ExactSizeIterator<Item = Self>, mut data: Self::SystemData<'_>) {
+        let mut create_item_drop = data.create_item_drop.emitter();
+        let mut unlock_title = data.unlock_title.emitter();
+        for ev in events {
+            let killer = ev
+                .killer
+                .and_then(|killer| match data.presences.get(killer)?.kind {
+                    PresenceKind::Character(character_id) => Some((killer, character_id)),
+                    _ => None,
+                });
+            let completed = killer.and_then(|(killer, character_id)| {
+                let bounty =
+                    data.bounties
+                        .complete(character_id, |bounty| match bounty.target {
+                            BountyTarget::Hunt(npc) => ev.victim == Some(npc),
+                            BountyTarget::Recover(_) => {
+                                ev.hostile
+                                    && bounty.wpos.distance_squared(ev.pos.xy())
+                                        <= HIDEOUT_RADIUS.powi(2)
+                            },
+                        })?;
+                Some((killer, character_id, bounty))
+            });
+
+            if let Some((killer, character_id, bounty)) = completed {
+                data.rtsim.hook_bounty_paid(bounty.site, bounty.reward);
+                unlock_title.emit(UnlockTitleEvent {
+                    entity: killer,
+                    title: Title::BountyHunter,
+                });
+
+                let mut coins = match comp::Item::new_from_asset("common.items.utility.coins") {
+                    Ok(coins) => coins,
+                    Err(err) => {
+                        warn!(?err, "Failed to create the reward of a bounty");
+                        continue;
+                    },
+                };
+                if let Err(err) = coins.set_amount(bounty.reward) {
+                    warn!(?err, "Invalid bounty reward {}", bounty.reward);
+                }
+                let item_msg = coins.frontend_item(&data.ability_map, &data.msm);
+                // Rewards that don't fit into the inventory are dropped at the killer's feet
+                let leftover = match data.inventories.get_mut(killer) {
+                    Some(mut inventory) => inventory.push(coins).err().map(|(coins, _)| coins),
+                    None => Some(coins),
+                };
+                match leftover {
+                    None => {
+                        data.inventory_updates
+                            .entry(killer)
+                            .expect("We know entity exists since we got its inventory.")
+                            .or_insert_with(InventoryUpdate::default)
+                            .push(InventoryUpdateEvent::Collected(item_msg));
+                    },
+                    Some(coins) => {
+                        if let Some((pos, uid)) =
+                            data.positions.get(killer).zip(data.uids.get(killer))
+                        {
+                            create_item_drop.emit(CreateItemDropEvent {
+                                pos: *pos,
+                                vel: comp::Vel(Vec3::zero()),
+                                ori: comp::Ori::default(),
+                                item: PickupItem::new(coins, *data.program_time),
+                                loot_owner: Some(LootOwner::new(
+                                    LootOwnerKind::Player(*uid),
+                                    false,
+                                )),
+                            });
+                        }
+                    },
+                }
+
+                if let Some(client) = data.clients.get(killer) {
+                    client.send_fallible(ServerGeneral::server_msg(
+                        comp::ChatType::Meta,
+                        Content::localized_with_args("hud-bounty-completed", [
+                            ("target", LocalizationArg::from(bounty.target_name)),
+                            ("reward", LocalizationArg::from(u64::from(bounty.reward))),
+                        ]),
+                    ));
+                    client.send_fallible(ServerGeneral::Bounties(
+                        data.bounties.get(character_id).to_vec(),
+                    ));
+                }
+            }
+
+            if let Some(npc) = ev.victim {
+                let pursuers = data.bounties.forget(BountyTarget::Hunt(npc));
+                for (presence, client) in (&data.presences, &data.clients).join() {
+                    if let PresenceKind::Character(character_id) = presence.kind
+                        && pursuers.contains(&character_id)
+                    {
+                        client.send_fallible(ServerGeneral::server_msg(
+                            comp::ChatType::Meta,
+                            Content::localized("hud-bounty-lost"),
+                        ));
+                        client.send_fallible(ServerGeneral::Bounties(
+                            data.bounties.get(character_id).to_vec(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
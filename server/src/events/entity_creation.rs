@@ -1,6 +1,10 @@
 use crate::{
-    client::Client, events::player::handle_exit_ingame, persistence::PersistedComponents,
-    pet::tame_pet, presence::RepositionOnChunkLoad, sys, CharacterUpdater, Server, StateExt,
+    client::Client,
+    events::player::handle_exit_ingame,
+    persistence::PersistedComponents,
+    pet::tame_pet,
+    presence::{RepositionOnChunkLoad, ResumableSession},
+    sys, CharacterUpdater, Server, StateExt,
 };
 use common::{
     comp::{
@@ -12,9 +16,10 @@ use common::{
         WaypointArea,
     },
     event::{
-        CreateAuraEntityEvent, CreateItemDropEvent, CreateNpcEvent, CreateObjectEvent,
-        CreateShipEvent, CreateSpecialEntityEvent, EventBus, InitializeCharacterEvent,
-        InitializeSpectatorEvent, ShockwaveEvent, ShootEvent, UpdateCharacterDataEvent,
+        CreateAuraEntityEvent, CreateCorpseEvent, CreateGravestoneEvent, CreateItemDropEvent,
+        CreateNpcEvent, CreateObjectEvent, CreateRaceCheckpointEvent, CreateShipEvent,
+        CreateSpecialEntityEvent, EventBus, InitializeCharacterEvent, InitializeSpectatorEvent,
+        ShockwaveEvent, ShootEvent, UpdateCharacterDataEvent,
     },
     generation::SpecialEntity,
     mounting::{Mounting, Volume, VolumeMounting, VolumePos},
@@ -23,7 +28,10 @@ use common::{
     uid::{IdMaps, Uid},
     vol::IntoFullVolIterator,
 };
-use common_net::{msg::ServerGeneral, sync::WorldSyncExt};
+use common_net::{
+    msg::{ResumeToken, ServerGeneral},
+    sync::WorldSyncExt,
+};
 use specs::{Builder, Entity as EcsEntity, WorldExt};
 use std::time::Duration;
 use vek::{Rgb, Vec3};
@@ -77,6 +85,8 @@ pub fn handle_loaded_character_data(server: &mut Server, ev: UpdateCharacterData
         pets: ev.components.5,
         active_abilities: ev.components.6,
         map_marker: ev.components.7,
+        unlocked_shrines: ev.components.8,
+        titles: ev.components.9,
     };
     if let Some(marker) = loaded_components.map_marker {
         server.notify_client(
@@ -95,6 +105,17 @@ pub fn handle_loaded_character_data(server: &mut Server, ev: UpdateCharacterData
         ServerGeneral::CharacterDataLoadResult(Err(err))
     } else {
         sys::subscription::initialize_region_subscription(server.state.ecs(), ev.entity);
+        // Allow the client to get back into the game if its connection drops.
+        if !server.settings().session_resume_grace.is_zero() {
+            let token = ResumeToken(rand::random());
+            server
+                .state
+                .write_component_ignore_entity_dead(ev.entity, ResumableSession {
+                    token,
+                    expires: None,
+                });
+            server.notify_client(ev.entity, ServerGeneral::SessionToken(token));
+        }
         // We notify the client with the metadata result from the operation.
         ServerGeneral::CharacterDataLoadResult(Ok(ev.metadata))
     };
@@ -158,6 +179,12 @@ pub fn handle_create_npc(server: &mut Server, mut ev: CreateNpcEvent) -> EcsEnti
         entity
     };
 
+    let entity = if let Some(encounter) = ev.npc.encounter {
+        entity.with(encounter)
+    } else {
+        entity
+    };
+
     let new_entity = entity.build();
 
     if let Some(rtsim_entity) = ev.npc.rtsim_entity {
@@ -441,6 +468,14 @@ pub fn handle_create_special_entity(server: &mut Server, ev: CreateSpecialEntity
                 .create_teleporter(comp::Pos(ev.pos), portal)
                 .build();
         },
+        SpecialEntity::Shrine(site) => {
+            server
+                .state
+                .create_object(Pos(ev.pos), comp::object::Body::TerracottaStatue)
+                .with(comp::Immovable)
+                .with(comp::Object::Shrine { site })
+                .build();
+        },
         SpecialEntity::ArenaTotem { range } => {
             server
                 .state
@@ -491,6 +526,48 @@ pub fn handle_create_object(
         .build();
 }
 
+pub fn handle_create_race_checkpoint(server: &mut Server, ev: CreateRaceCheckpointEvent) {
+    server
+        .state
+        .create_object(ev.pos, comp::object::Body::RaceCheckpoint)
+        .with(comp::Immovable)
+        .with(comp::Object::RaceCheckpoint {
+            track: ev.track,
+            index: ev.index,
+        })
+        .build();
+}
+
+pub fn handle_create_gravestone(server: &mut Server, ev: CreateGravestoneEvent) {
+    let decays_at = Time(server.state.get_time() + comp::GRAVESTONE_LIFETIME.0);
+    server
+        .state
+        .create_object(ev.pos, comp::object::Body::Gravestone)
+        .with(comp::Immovable)
+        .with(comp::Object::Gravestone {
+            owner: ev.owner,
+            decays_at,
+        })
+        .with(ItemDrops(
+            ev.items
+                .into_iter()
+                .map(|item| (item.amount(), item))
+                .collect(),
+        ))
+        .build();
+}
+
+pub fn handle_create_corpse(server: &mut Server, ev: CreateCorpseEvent) {
+    let time = *server.state.ecs().read_resource::<Time>();
+    server
+        .state
+        .ecs_mut()
+        .create_entity()
+        .with(ev.pos)
+        .with(comp::Corpse::new(ev.body, time))
+        .build();
+}
+
 pub fn handle_create_aura_entity(server: &mut Server, ev: CreateAuraEntityEvent) {
     let time = *server.state.ecs().read_resource::<Time>();
     let mut entity = server
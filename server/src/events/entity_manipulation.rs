@@ -12,50 +12,67 @@ use crate::{
     events::entity_creation::handle_create_npc,
     pet::tame_pet,
     state_ext::StateExt,
-    sys::terrain::{NpcData, SpawnEntityData, SAFE_ZONE_RADIUS},
+    sys::{
+        event_calendar::EventCalendarState,
+        terrain::{NpcData, SpawnEntityData, SAFE_ZONE_RADIUS},
+        wildfire::Wildfires,
+    },
     Server, Settings, SpawnPoint,
 };
-#[cfg(feature = "worldgen")]
-use common::rtsim::{Actor, RtSimEntity};
 use common::{
-    combat::{self, AttackSource, DamageContributor, DeathEffect, BASE_PARRIED_POISE_PUNISHMENT},
+    combat::{
+        self, AttackSource, DamageContributor, DeathEffect, BASE_PARRIED_POISE_PUNISHMENT,
+        PARRY_RIPOSTE_WINDOW,
+    },
     comp::{
         self,
         aura::{self, EnteredAuras},
         buff,
         chat::{KillSource, KillType},
+        group::{GroupManager, LootMode},
         inventory::item::{AbilityMap, MaterialStatManifest},
         item::flatten_counted_items,
         loot_owner::LootOwnerKind,
-        Alignment, Auras, Body, CharacterState, Energy, Group, Health, Inventory, Object,
+        Alignment, Auras, Body, CanBuild, CharacterState, Energy, Group, Health, Inventory, Object,
         PickupItem, Player, Poise, PoiseChange, Pos, Presence, PresenceKind, SkillSet, Stats,
         BASE_ABILITY_LIMIT,
     },
     consts::TELEPORTER_RADIUS,
     event::{
         AuraEvent, BonkEvent, BuffEvent, ChangeAbilityEvent, ChangeBodyEvent, ChangeStanceEvent,
-        ChatEvent, ComboChangeEvent, CreateItemDropEvent, CreateNpcEvent, CreateObjectEvent,
-        DeleteEvent, DestroyEvent, EmitExt, Emitter, EnergyChangeEvent, EntityAttackedHookEvent,
-        EventBus, ExplosionEvent, HealthChangeEvent, KnockbackEvent, LandOnGroundEvent,
-        MakeAdminEvent, ParryHookEvent, PoiseChangeEvent, RemoveLightEmitterEvent, RespawnEvent,
-        SoundEvent, StartTeleportingEvent, TeleportToEvent, TeleportToPositionEvent,
-        TransformEvent, UpdateMapMarkerEvent,
+        ChatEvent, ComboChangeEvent, CreateCorpseEvent, CreateGravestoneEvent, CreateItemDropEvent,
+        CreateNpcEvent, CreateObjectEvent, DeleteEvent, DestroyEvent, EmitExt, Emitter,
+        EnergyChangeEvent, EntityAttackedHookEvent, EventBus, ExplosionEvent, ExposeDisguiseEvent,
+        HealthChangeEvent, KnockbackEvent, LandOnGroundEvent, LightningStrikeEvent, MakeAdminEvent,
+        NpcBuilder, ParryHookEvent, PoiseChangeEvent, ReanimateEvent, RemoveLightEmitterEvent,
+        RespawnEvent, ReviveEvent, SoundEvent, StartTeleportingEvent, SwitchAbilityLoadoutEvent,
+        TeleportToEvent, TeleportToPositionEvent, TransformEvent, UpdateMapMarkerEvent,
     },
     event_emitters,
     generation::EntityInfo,
     link::Is,
+    loot_roll::{self, LootRolls},
     lottery::distribute_many,
     mounting::{Rider, VolumeRider},
     outcome::{HealthChangeInfo, Outcome},
     resources::{ProgramTime, Secs, Time},
     spiral::Spiral2d,
-    states::utils::StageSection,
+    states::{
+        downed,
+        revive::REVIVE_HEALTH_FRACTION,
+        utils::{can_perform_revive, StageSection},
+    },
     terrain::{Block, BlockKind, TerrainGrid},
     trade::{TradeResult, Trades},
     uid::{IdMaps, Uid},
     util::Dir,
     vol::ReadVol,
-    CachedSpatialGrid, Damage, DamageKind, DamageSource, GroupTarget, RadiusEffect,
+    CachedSpatialGrid, Damage, DamageElement, DamageKind, DamageSource, GroupTarget, RadiusEffect,
+};
+#[cfg(feature = "worldgen")]
+use common::{
+    event::BountyKillEvent,
+    rtsim::{Actor, RtSimEntity},
 };
 use common_net::{msg::ServerGeneral, sync::WorldSyncExt};
 use common_state::{AreasContainer, BlockChange, NoDurabilityArea};
@@ -67,12 +84,9 @@ use specs::{
     shred, DispatcherBuilder, Entities, Entity as EcsEntity, Entity, Join, LendJoin, Read,
     ReadExpect, ReadStorage, SystemData, WorldExt, Write, WriteStorage,
 };
-#[cfg(feature = "worldgen")] use std::sync::Arc;
 use std::{collections::HashMap, iter, time::Duration};
 use tracing::{debug, warn};
 use vek::{Vec2, Vec3};
-#[cfg(feature = "worldgen")]
-use world::{IndexOwned, World};
 
 use super::{event_dispatch, ServerEvent};
 
@@ -84,6 +98,7 @@ pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
     event_dispatch::<LandOnGroundEvent>(builder);
     event_dispatch::<RespawnEvent>(builder);
     event_dispatch::<ExplosionEvent>(builder);
+    event_dispatch::<LightningStrikeEvent>(builder);
     event_dispatch::<BonkEvent>(builder);
     event_dispatch::<AuraEvent>(builder);
     event_dispatch::<BuffEvent>(builder);
@@ -93,11 +108,15 @@ pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
     event_dispatch::<TeleportToEvent>(builder);
     event_dispatch::<EntityAttackedHookEvent>(builder);
     event_dispatch::<ChangeAbilityEvent>(builder);
+    event_dispatch::<SwitchAbilityLoadoutEvent>(builder);
     event_dispatch::<UpdateMapMarkerEvent>(builder);
     event_dispatch::<MakeAdminEvent>(builder);
     event_dispatch::<ChangeStanceEvent>(builder);
+    event_dispatch::<ReviveEvent>(builder);
     event_dispatch::<ChangeBodyEvent>(builder);
     event_dispatch::<RemoveLightEmitterEvent>(builder);
+    event_dispatch::<ExposeDisguiseEvent>(builder);
+    event_dispatch::<ReanimateEvent>(builder);
     event_dispatch::<TeleportToPositionEvent>(builder);
     event_dispatch::<StartTeleportingEvent>(builder);
 }
@@ -116,6 +135,11 @@ event_emitters! {
         bonk: BonkEvent,
     }
 
+    struct ReadLightningStrikeEvents[LightningStrikeEmitters] {
+        health_change: HealthChangeEvent,
+        buff: BuffEvent,
+    }
+
     struct ReadEntityAttackedHookEvents[EntityAttackedHookEmitters] {
         buff: BuffEvent,
         combo_change: ComboChangeEvent,
@@ -171,14 +195,31 @@ impl ServerEvent for HealthChangeEvent {
         ReadStorage<'a, Uid>,
         WriteStorage<'a, Agent>,
         WriteStorage<'a, Health>,
+        ReadStorage<'a, CanBuild>,
     );
 
     fn handle(
         events: impl ExactSizeIterator<Item = Self>,
-        (entities, outcomes, positions, uids, mut agents, mut healths): Self::SystemData<'_>,
+        (
+            entities,
+            outcomes,
+            positions,
+            uids,
+            mut agents,
+            mut healths,
+            can_builds,
+        ): Self::SystemData<'_>,
     ) {
         let mut outcomes_emitter = outcomes.emitter();
         for ev in events {
+            // Players in creative mode can't be hurt
+            if ev.change.amount < 0.0
+                && can_builds
+                    .get(ev.entity)
+                    .map_or(false, |can_build| can_build.creative)
+            {
+                continue;
+            }
             if let Some((mut health, pos, uid)) = (&mut healths, positions.maybe(), uids.maybe())
                 .lend_join()
                 .get(ev.entity, &entities)
@@ -320,13 +361,15 @@ pub struct DestroyEventData<'a> {
     ability_map: ReadExpect<'a, AbilityMap>,
     time: Read<'a, Time>,
     program_time: ReadExpect<'a, ProgramTime>,
-    #[cfg(feature = "worldgen")]
-    world: ReadExpect<'a, Arc<World>>,
-    #[cfg(feature = "worldgen")]
-    index: ReadExpect<'a, IndexOwned>,
+    group_manager: Read<'a, GroupManager>,
+    loot_rolls: Write<'a, LootRolls>,
+    event_calendar: Read<'a, EventCalendarState>,
     areas_container: Read<'a, AreasContainer<NoDurabilityArea>>,
+    settings: Read<'a, Settings>,
     outcomes: Read<'a, EventBus<Outcome>>,
     create_item_drop: Read<'a, EventBus<CreateItemDropEvent>>,
+    create_gravestone: Read<'a, EventBus<CreateGravestoneEvent>>,
+    create_corpse: Read<'a, EventBus<CreateCorpseEvent>>,
     delete_event: Read<'a, EventBus<DeleteEvent>>,
     chat_events: Read<'a, EventBus<ChatEvent>>,
     melees: WriteStorage<'a, comp::Melee>,
@@ -342,17 +385,21 @@ pub struct DestroyEventData<'a> {
     clients: ReadStorage<'a, Client>,
     uids: ReadStorage<'a, Uid>,
     positions: ReadStorage<'a, Pos>,
-    healths: ReadStorage<'a, Health>,
+    healths: WriteStorage<'a, Health>,
     bodies: ReadStorage<'a, Body>,
     poises: ReadStorage<'a, Poise>,
     groups: ReadStorage<'a, Group>,
     alignments: ReadStorage<'a, Alignment>,
     stats: ReadStorage<'a, Stats>,
     agents: ReadStorage<'a, Agent>,
+    encounters: ReadStorage<'a, comp::Encounter>,
+    group_scalings: ReadStorage<'a, comp::GroupScaling>,
     #[cfg(feature = "worldgen")]
     rtsim_entities: ReadStorage<'a, RtSimEntity>,
     #[cfg(feature = "worldgen")]
     presences: ReadStorage<'a, Presence>,
+    #[cfg(feature = "worldgen")]
+    bounty_kill: Read<'a, EventBus<BountyKillEvent>>,
     buff_events: Read<'a, EventBus<BuffEvent>>,
     masses: ReadStorage<'a, comp::Mass>,
 }
@@ -367,9 +414,13 @@ impl ServerEvent for DestroyEvent {
     fn handle(events: impl ExactSizeIterator<Item = Self>, mut data: Self::SystemData<'_>) {
         let mut chat_emitter = data.chat_events.emitter();
         let mut create_item_drop = data.create_item_drop.emitter();
+        let mut create_gravestone = data.create_gravestone.emitter();
+        let mut create_corpse = data.create_corpse.emitter();
         let mut delete_emitter = data.delete_event.emitter();
         let mut outcomes_emitter = data.outcomes.emitter();
         let mut buff_emitter = data.buff_events.emitter();
+        #[cfg(feature = "worldgen")]
+        let mut bounty_kill_emitter = data.bounty_kill.emitter();
         for ev in events {
             // TODO: Investigate duplicate `Destroy` events (but don't remove this).
             // If the entity was already deleted, it can't be destroyed again.
@@ -382,6 +433,53 @@ impl ServerEvent for DestroyEvent {
             data.melees.remove(ev.entity);
             data.beams.remove(ev.entity);
 
+            // Players in group play are downed instead of dying as long as another member
+            // of their group is still standing to revive them. Downed players that are
+            // executed or bleed out die for real.
+            if data.players.contains(ev.entity)
+                && let Some(group) = data.groups.get(ev.entity).filter(|g| !g.is_special())
+                && !data
+                    .character_states
+                    .get(ev.entity)
+                    .map_or(true, |character_state| character_state.is_downed())
+                && comp::group::members(
+                    *group,
+                    &data.groups,
+                    &data.entities,
+                    &data.alignments,
+                    &data.uids,
+                )
+                .any(|(member, role)| {
+                    member != ev.entity
+                        && matches!(role, comp::group::Role::Member)
+                        && data.players.contains(member)
+                        && data
+                            .healths
+                            .get(member)
+                            .map_or(false, |health| !health.is_dead)
+                        && !data
+                            .character_states
+                            .get(member)
+                            .map_or(false, |character_state| character_state.is_downed())
+                })
+            {
+                if let Some(mut health) = data.healths.get_mut(ev.entity) {
+                    health.restore_fraction(downed::DOWNED_HEALTH_FRACTION);
+                }
+                if let Some(mut character_state) = data.character_states.get_mut(ev.entity) {
+                    *character_state = CharacterState::Downed(downed::Data::default());
+                }
+                // Damage over time would otherwise finish them off right away
+                buff_emitter.emit(BuffEvent {
+                    entity: ev.entity,
+                    buff_change: buff::BuffChange::Dispel(buff::Dispel {
+                        target: buff::DispelTarget::Debuffs,
+                        count: u32::MAX,
+                    }),
+                });
+                continue;
+            }
+
             let get_attacker_name = |cause_of_death: KillType, by: Uid| -> KillSource {
                 // Get attacker entity
                 if let Some(char_entity) = data.id_maps.uid_entity(by) {
@@ -518,7 +616,8 @@ impl ServerEvent for DestroyEvent {
                     entity_skill_set,
                     *entity_body,
                     &data.msm,
-                ) * 20.0;
+                ) * 20.0
+                    * data.event_calendar.exp_multiplier();
 
                 let mut damage_contributors = HashMap::<DamageContrib, (u64, f32)>::new();
                 for (damage_contributor, damage) in entity_health.damage_contributions() {
@@ -679,11 +778,19 @@ impl ServerEvent for DestroyEvent {
                     // Only drop loot if entity has agency (not a player),
                     // and if it is not owned by another entity (not a pet)
                     if !matches!(alignment, Some(Alignment::Owned(_)))
-                        && let Some(items) = data
+                        && let Some(mut items) = data
                             .item_drops
                             .remove(ev.entity)
                             .map(|comp::ItemDrops(item)| item)
                     {
+                        // Dungeon enemies scaled up for a group drop more loot as well
+                        if let Some(scaling) = data.group_scalings.get(ev.entity) {
+                            let loot_multiplier = scaling.loot_multiplier();
+                            for (amount, _) in items.iter_mut() {
+                                *amount = (*amount as f32 * loot_multiplier).round() as u32;
+                            }
+                        }
+
                         // Remove entries where zero exp was awarded - this happens because some
                         // entities like Object bodies don't give EXP.
                         let mut item_receivers = HashMap::new();
@@ -741,6 +848,38 @@ impl ServerEvent for DestroyEvent {
                                 spawn_item(item, None)
                             }
                         } else {
+                            // Boss loot won by a group that rolls for its loot is held back until
+                            // the members close by decided who gets each item
+                            let roll_groups = item_receivers
+                                .keys()
+                                .filter_map(|loot_owner| match loot_owner {
+                                    Some(LootOwnerKind::Group(group))
+                                        if data.encounters.contains(ev.entity)
+                                            && data
+                                                .group_manager
+                                                .group_info(*group)
+                                                .map_or(false, |info| {
+                                                    info.loot_mode == LootMode::NeedGreed
+                                                }) =>
+                                    {
+                                        Some(*group)
+                                    },
+                                    _ => None,
+                                })
+                                .filter_map(|group| {
+                                    let members = loot_roll::eligible_members(
+                                        group,
+                                        pos.0,
+                                        &data.groups,
+                                        &data.entities,
+                                        &data.alignments,
+                                        &data.uids,
+                                        &data.positions,
+                                    );
+                                    (members.len() > 1).then_some((group, members))
+                                })
+                                .collect::<HashMap<_, _>>();
+
                             let mut rng = rand::thread_rng();
                             distribute_many(
                                 item_receivers
@@ -753,7 +892,19 @@ impl ServerEvent for DestroyEvent {
                                     for item in
                                         item.stacked_duplicates(&data.ability_map, &data.msm, count)
                                     {
-                                        spawn_item(item, loot_owner)
+                                        if let Some(LootOwnerKind::Group(group)) = loot_owner
+                                            && let Some(members) = roll_groups.get(&group)
+                                        {
+                                            data.loot_rolls.start(
+                                                item,
+                                                pos.0,
+                                                group,
+                                                members.iter().copied(),
+                                                *data.time,
+                                            );
+                                        } else {
+                                            spawn_item(item, loot_owner)
+                                        }
                                     }
                                 },
                             );
@@ -763,6 +914,7 @@ impl ServerEvent for DestroyEvent {
                 true
             };
             if !should_delete {
+                let death_penalty = data.settings.gameplay.death_penalty;
                 let resists_durability =
                     data.positions
                         .get(ev.entity)
@@ -781,10 +933,32 @@ impl ServerEvent for DestroyEvent {
 
                 // Modify durability on all equipped items
                 if !resists_durability
+                    && death_penalty.damages_items()
                     && let Some(mut inventory) = data.inventories.get_mut(ev.entity)
                 {
                     inventory.damage_items(&data.ability_map, &data.msm, *data.time);
                 }
+
+                // Leave some of the carried items behind for the player to recover
+                if !resists_durability
+                    && death_penalty.leaves_gravestone()
+                    && let Some((mut inventory, pos, uid)) =
+                        (&mut data.inventories, &data.positions, &data.uids)
+                            .lend_join()
+                            .get(ev.entity, &data.entities)
+                {
+                    let items = inventory.take_random_fraction(
+                        comp::GRAVESTONE_ITEM_FRACTION,
+                        &mut rand::thread_rng(),
+                    );
+                    if !items.is_empty() {
+                        create_gravestone.emit(CreateGravestoneEvent {
+                            pos: *pos,
+                            owner: *uid,
+                            items,
+                        });
+                    }
+                }
             }
 
             #[cfg(feature = "worldgen")]
@@ -801,28 +975,55 @@ impl ServerEvent for DestroyEvent {
             };
             #[cfg(feature = "worldgen")]
             let actor = entity_as_actor(ev.entity);
+            #[cfg(feature = "worldgen")]
+            let killer = ev.cause.by.as_ref().and_then(
+                |(DamageContributor::Solo(entity_uid)
+                 | DamageContributor::Group { entity_uid, .. })| {
+                    data.id_maps.uid_entity(*entity_uid)
+                },
+            );
 
             #[cfg(feature = "worldgen")]
             if let Some(actor) = actor {
                 data.rtsim.hook_rtsim_actor_death(
-                    &data.world,
-                    data.index.as_index_ref(),
                     actor,
                     data.positions.get(ev.entity).map(|p| p.0),
-                    ev.cause
-                        .by
-                        .as_ref()
-                        .and_then(
-                            |(DamageContributor::Solo(entity_uid)
-                             | DamageContributor::Group { entity_uid, .. })| {
-                                data.id_maps.uid_entity(*entity_uid)
-                            },
-                        )
-                        .and_then(entity_as_actor),
+                    killer.and_then(entity_as_actor),
                 );
             }
 
+            // Let bounties know about monsters and pirates dying
+            #[cfg(feature = "worldgen")]
+            {
+                let victim = data.rtsim_entities.get(ev.entity).map(|e| e.0);
+                let hostile = matches!(data.alignments.get(ev.entity), Some(Alignment::Enemy));
+                if let Some(pos) = data.positions.get(ev.entity)
+                    && (victim.is_some() || (killer.is_some() && hostile))
+                {
+                    bounty_kill_emitter.emit(BountyKillEvent {
+                        killer,
+                        victim,
+                        pos: pos.0,
+                        hostile,
+                    });
+                }
+            }
+
             if should_delete {
+                // Leave remains behind for necromancers, unless the entity was itself
+                // somebody's minion
+                if let Some((pos, body)) = data
+                    .positions
+                    .get(ev.entity)
+                    .zip(data.bodies.get(ev.entity))
+                    && !matches!(body, Body::Object(_) | Body::ItemDrop(_) | Body::Ship(_))
+                    && !matches!(data.alignments.get(ev.entity), Some(Alignment::Owned(_)))
+                {
+                    create_corpse.emit(CreateCorpseEvent {
+                        pos: *pos,
+                        body: *body,
+                    });
+                }
                 delete_emitter.emit(DeleteEvent(ev.entity));
             }
         }
@@ -895,6 +1096,7 @@ impl ServerEvent for LandOnGroundEvent {
                 let damage = Damage {
                     source: DamageSource::Falling,
                     kind: DamageKind::Crushing,
+                    element: DamageElement::Physical,
                     value: falldmg,
                 };
                 let damage_reduction = Damage::compute_damage_reduction(
@@ -1500,6 +1702,162 @@ pub fn emit_effect_events(
                 )),
             });
         },
+        common::effect::Effect::Dispel(dispel) => emitters.emit(BuffEvent {
+            entity,
+            buff_change: comp::BuffChange::Dispel(dispel),
+        }),
+    }
+}
+
+/// Radius around a lightning strike in which entities get shocked
+const LIGHTNING_STRIKE_RADIUS: f32 = 4.0;
+/// Damage dealt by lightning right where it strikes
+const LIGHTNING_DAMAGE: f32 = 60.0;
+/// Radius around a strike in which lightning can be drawn to metal armor
+const LIGHTNING_ATTRACT_RADIUS: f32 = 32.0;
+/// Chance for each equipped piece of metal armor to draw in a strike
+const LIGHTNING_ATTRACT_CHANCE: f64 = 0.08;
+
+#[derive(SystemData)]
+pub struct LightningStrikeData<'a> {
+    time: Read<'a, Time>,
+    spatial_grid: Read<'a, CachedSpatialGrid>,
+    terrain: ReadExpect<'a, TerrainGrid>,
+    msm: ReadExpect<'a, MaterialStatManifest>,
+    wildfires: Write<'a, Wildfires>,
+    event_busses: ReadLightningStrikeEvents<'a>,
+    outcomes: Read<'a, EventBus<Outcome>>,
+    positions: ReadStorage<'a, Pos>,
+    healths: ReadStorage<'a, Health>,
+    inventories: ReadStorage<'a, Inventory>,
+    stats: ReadStorage<'a, Stats>,
+    masses: ReadStorage<'a, comp::Mass>,
+}
+
+impl ServerEvent for LightningStrikeEvent {
+    type SystemData<'a> = LightningStrikeData<'a>;
+
+    fn handle(events: impl ExactSizeIterator<Item = Self>, mut data: Self::SystemData<'_>) {
+        let mut emitters = data.event_busses.get_emitters();
+        let mut outcome_emitter = data.outcomes.emitter();
+        let mut rng = rand::thread_rng();
+
+        for ev in events {
+            // Strikes far away from any player only need to be seen, not felt
+            if data
+                .terrain
+                .get_key(data.terrain.pos_key(ev.pos.map(|e| e.floor() as i32)))
+                .is_none()
+            {
+                outcome_emitter.emit(Outcome::Lightning { pos: ev.pos });
+                continue;
+            }
+
+            // Lightning is drawn to entities wearing metal armor nearby
+            let pos = data
+                .spatial_grid
+                .0
+                .in_circle_aabr(ev.pos.xy(), LIGHTNING_ATTRACT_RADIUS)
+                .filter(|entity| data.healths.get(*entity).map_or(false, |h| !h.is_dead))
+                .filter_map(|entity| {
+                    let metal_pieces = data
+                        .inventories
+                        .get(entity)?
+                        .equipped_items()
+                        .filter(|item| {
+                            matches!(&*item.kind(), comp::item::ItemKind::Armor(_))
+                                && item.tags().iter().any(|tag| {
+                                    matches!(
+                                        tag,
+                                        comp::item::ItemTag::Material(material)
+                                            if material.material_kind()
+                                                == comp::item::MaterialKind::Metal
+                                    )
+                                })
+                        })
+                        .count();
+                    Some((data.positions.get(entity)?.0, metal_pieces))
+                })
+                .find(|(_, metal_pieces)| {
+                    rng.gen_bool((*metal_pieces as f64 * LIGHTNING_ATTRACT_CHANCE).min(1.0))
+                })
+                .map_or(ev.pos, |(pos, _)| pos);
+
+            outcome_emitter.emit(Outcome::Lightning { pos });
+
+            for entity in data
+                .spatial_grid
+                .0
+                .in_circle_aabr(pos.xy(), LIGHTNING_STRIKE_RADIUS)
+            {
+                let Some(target_pos) = data.positions.get(entity) else {
+                    continue;
+                };
+                let distance = target_pos.0.distance(pos);
+                if distance > LIGHTNING_STRIKE_RADIUS
+                    || data.healths.get(entity).map_or(true, |h| h.is_dead)
+                {
+                    continue;
+                }
+                let strength = 1.0 - 0.5 * distance / LIGHTNING_STRIKE_RADIUS;
+                let inventory = data.inventories.get(entity);
+                let stats = data.stats.get(entity);
+
+                let damage = Damage {
+                    source: DamageSource::Energy,
+                    kind: DamageKind::Energy,
+                    element: DamageElement::Shock,
+                    value: LIGHTNING_DAMAGE * strength,
+                };
+                emitters.emit(HealthChangeEvent {
+                    entity,
+                    change: damage.calculate_health_change(
+                        combat::Damage::compute_damage_reduction(
+                            Some(damage),
+                            inventory,
+                            stats,
+                            &data.msm,
+                        ),
+                        0.0,
+                        None,
+                        None,
+                        0.0,
+                        1.0,
+                        *data.time,
+                        rand::random(),
+                    ),
+                });
+                emitters.emit(BuffEvent {
+                    entity,
+                    buff_change: comp::BuffChange::Add(comp::Buff::new(
+                        BuffKind::Electrified,
+                        buff::BuffData::new(strength, Some(Secs(5.0))),
+                        Vec::new(),
+                        BuffSource::World,
+                        *data.time,
+                        buff::DestInfo {
+                            stats,
+                            mass: data.masses.get(entity),
+                        },
+                        None,
+                    )),
+                });
+            }
+
+            // Set flammable blocks around the strike on fire
+            for _ in 0..4 {
+                let offset = Vec2::new(rng.gen_range(-2..=2), rng.gen_range(-2..=2));
+                let column = pos.map(|e| e.floor() as i32) + offset.with_z(2);
+                let Some(fire_pos) = (0..6).map(|z| column - Vec3::unit_z() * z).find(|pos| {
+                    data.terrain
+                        .get(*pos - Vec3::unit_z())
+                        .map_or(false, |b| b.is_solid())
+                }) else {
+                    continue;
+                };
+                data.wildfires.ignite(fire_pos);
+            }
+        }
     }
 }
 
@@ -1725,6 +2083,24 @@ impl ServerEvent for BuffEvent {
                                 buff.end_time = buff.data.duration.map(|dur| Time(time.0 + dur.0));
                             })
                     },
+                    BuffChange::Dispel(dispel) => {
+                        let resistance = stats.get(ev.entity).map_or(0.0, |s| s.dispel_resistance);
+                        buffs.dispel(dispel, resistance, *time);
+                    },
+                    BuffChange::UseCharge => {
+                        let mut keys_to_remove = Vec::new();
+                        for (key, buff) in buffs.buffs.iter_mut() {
+                            if let Some(charges) = &mut buff.data.charges {
+                                *charges = charges.saturating_sub(1);
+                                if *charges == 0 {
+                                    keys_to_remove.push(key);
+                                }
+                            }
+                        }
+                        for key in keys_to_remove {
+                            buffs.remove(key);
+                        }
+                    },
                 }
             }
         }
@@ -1801,6 +2177,7 @@ impl ServerEvent for ParryHookEvent {
         let mut poise_change_emitter = poise_change_events.emitter();
         let mut buff_emitter = buff_events.emitter();
         for ev in events {
+            let mut riposte_opportunity = false;
             if let Some(mut char_state) = character_states.get_mut(ev.defender) {
                 let return_to_wield = match &mut *char_state {
                     CharacterState::RiposteMelee(c) => {
@@ -1815,6 +2192,7 @@ impl ServerEvent for ParryHookEvent {
                             change: c.static_data.energy_regen,
                         });
                         c.is_parry = true;
+                        riposte_opportunity = true;
                         false
                     },
                     _ => false,
@@ -1855,6 +2233,26 @@ impl ServerEvent for ParryHookEvent {
                     buff_change: buff::BuffChange::Add(buff),
                 });
 
+                // A well-timed block leaves the attacker open, so the next attack of the
+                // defender is a guaranteed precision hit
+                if riposte_opportunity {
+                    buff_emitter.emit(BuffEvent {
+                        entity: ev.defender,
+                        buff_change: buff::BuffChange::Add(buff::Buff::new(
+                            BuffKind::ImminentCritical,
+                            buff::BuffData::new(1.0, Some(PARRY_RIPOSTE_WINDOW)),
+                            vec![buff::BuffCategory::Physical],
+                            BuffSource::Buff,
+                            *time,
+                            buff::DestInfo {
+                                stats: stats.get(ev.defender),
+                                mass: masses.get(ev.defender),
+                            },
+                            None,
+                        )),
+                    });
+                }
+
                 let attacker_poise_change = Poise::apply_poise_reduction(
                     ev.poise_multiplier.clamp(1.0, 2.0) * BASE_PARRIED_POISE_PUNISHMENT,
                     inventories.get(attacker),
@@ -1948,6 +2346,10 @@ impl ServerEvent for EntityAttackedHookEvent {
                         none_required: vec![],
                     },
                 });
+                emitters.emit(BuffEvent {
+                    entity: attacker,
+                    buff_change: buff::BuffChange::UseCharge,
+                });
             }
 
             if let Some((mut char_state, mut poise, pos)) = (
@@ -2060,13 +2462,65 @@ impl ServerEvent for ChangeAbilityEvent {
         (mut active_abilities, inventories, skill_sets): Self::SystemData<'_>,
     ) {
         for ev in events {
+            let inventory = inventories.get(ev.entity);
+            let skill_set = skill_sets.get(ev.entity);
+            if !comp::ActiveAbilities::can_select_ability(
+                ev.auxiliary_key,
+                ev.new_ability,
+                inventory,
+                skill_set,
+            ) {
+                debug!(new_ability = ?ev.new_ability, "Tried to select an ability that isn't available");
+                continue;
+            }
             if let Some(mut active_abilities) = active_abilities.get_mut(ev.entity) {
                 active_abilities.change_ability(
                     ev.slot,
                     ev.auxiliary_key,
                     ev.new_ability,
+                    inventory,
+                    skill_set,
+                );
+            }
+        }
+    }
+}
+
+impl ServerEvent for SwitchAbilityLoadoutEvent {
+    type SystemData<'a> = (
+        Read<'a, Time>,
+        WriteStorage<'a, comp::ActiveAbilities>,
+        ReadStorage<'a, Inventory>,
+        ReadStorage<'a, SkillSet>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, CharacterState>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (time, mut active_abilities, inventories, skill_sets, healths, character_states): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            // Loadouts can only be switched out of combat
+            let in_combat = healths.get(ev.entity).map_or(true, |health| {
+                health.is_dead
+                    || health.damaged_within(*time, comp::ability::LOADOUT_SWITCH_COMBAT_LOCKOUT)
+            }) || character_states
+                .get(ev.entity)
+                .map_or(false, |char_state| char_state.is_attack());
+            if in_combat {
+                continue;
+            }
+            if let Some(mut active_abilities) = active_abilities.get_mut(ev.entity)
+                && !active_abilities.switch_loadout(
+                    ev.loadout,
                     inventories.get(ev.entity),
                     skill_sets.get(ev.entity),
+                )
+            {
+                debug!(
+                    loadout = ev.loadout,
+                    "Tried to switch to an ability loadout that doesn't exist"
                 );
             }
         }
@@ -2141,6 +2595,52 @@ impl ServerEvent for ChangeStanceEvent {
     }
 }
 
+impl ServerEvent for ReviveEvent {
+    type SystemData<'a> = (
+        Read<'a, IdMaps>,
+        ReadStorage<'a, Group>,
+        ReadStorage<'a, Pos>,
+        WriteStorage<'a, Health>,
+        WriteStorage<'a, CharacterState>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (id_maps, groups, positions, mut healths, mut character_states): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            let Some(target) = id_maps.uid_entity(ev.target) else {
+                continue;
+            };
+            // Only teammates close by can revive a downed player
+            let same_group = groups
+                .get(ev.entity)
+                .zip(groups.get(target))
+                .map_or(false, |(group, target_group)| group == target_group);
+            let in_range = positions
+                .get(ev.entity)
+                .zip(positions.get(target))
+                .map_or(false, |(pos, target_pos)| {
+                    can_perform_revive(*pos, *target_pos)
+                });
+            let is_downed = character_states
+                .get(target)
+                .map_or(false, |character_state| character_state.is_downed());
+            let is_alive = healths.get(target).map_or(false, |health| !health.is_dead);
+            if !(same_group && in_range && is_downed && is_alive) {
+                continue;
+            }
+
+            if let Some(mut health) = healths.get_mut(target) {
+                health.restore_fraction(REVIVE_HEALTH_FRACTION);
+            }
+            if let Some(mut character_state) = character_states.get_mut(target) {
+                *character_state = CharacterState::default();
+            }
+        }
+    }
+}
+
 impl ServerEvent for ChangeBodyEvent {
     type SystemData<'a> = WriteStorage<'a, comp::Body>;
 
@@ -2166,6 +2666,88 @@ impl ServerEvent for RemoveLightEmitterEvent {
     }
 }
 
+impl ServerEvent for ExposeDisguiseEvent {
+    type SystemData<'a> = (Read<'a, Time>, WriteStorage<'a, comp::Exposed>);
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (time, mut exposed): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            let _ = exposed.insert(ev.entity, comp::Exposed::new(*time));
+        }
+    }
+}
+
+impl ServerEvent for ReanimateEvent {
+    type SystemData<'a> = (
+        Read<'a, EventBus<CreateNpcEvent>>,
+        Read<'a, EventBus<DeleteEvent>>,
+        Read<'a, EventBus<Outcome>>,
+        WriteStorage<'a, comp::Corpse>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Uid>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (create_npc_events, delete_events, outcomes, mut corpses, positions, uids): Self::SystemData<
+            '_,
+        >,
+    ) {
+        let mut create_npc_emitter = create_npc_events.emitter();
+        let mut delete_emitter = delete_events.emitter();
+        let mut outcome_emitter = outcomes.emitter();
+        for ev in events {
+            let Some(necromancer_uid) = uids.get(ev.necromancer) else {
+                continue;
+            };
+            let Some(pos) = positions.get(ev.corpse).copied() else {
+                continue;
+            };
+            // Removing the corpse first makes sure it can only be raised once
+            let Some(corpse) = corpses.remove(ev.corpse) else {
+                continue;
+            };
+            delete_emitter.emit(DeleteEvent(ev.corpse));
+
+            let body = corpse.body;
+            let loadout = comp::inventory::loadout_builder::LoadoutBuilder::empty()
+                .with_default_maintool(&body)
+                .with_default_equipment(&body)
+                .build();
+            create_npc_emitter.emit(CreateNpcEvent {
+                pos,
+                ori: comp::Ori::default(),
+                npc: NpcBuilder::new(
+                    Stats::new("Risen".to_string(), body),
+                    body,
+                    Alignment::Owned(*necromancer_uid),
+                )
+                .with_skill_set(SkillSet::default())
+                .with_health(Some(Health::new(body)))
+                .with_inventory(Inventory::with_loadout(loadout, body))
+                .with_agent(
+                    Agent::from_body(&body)
+                        .with_behavior(comp::Behavior::from(comp::BehaviorCapability::SPEAK))
+                        .with_no_flee_if(true),
+                )
+                .with_projectile(Some(comp::Projectile {
+                    hit_solid: Vec::new(),
+                    hit_entity: Vec::new(),
+                    time_left: Duration::from_secs_f64(comp::corpse::REANIMATED_LIFETIME),
+                    owner: Some(*necromancer_uid),
+                    ignore_group: true,
+                    is_sticky: false,
+                    is_point: false,
+                })),
+                rider: None,
+            });
+            outcome_emitter.emit(Outcome::SummonedCreature { pos: pos.0, body });
+        }
+    }
+}
+
 impl ServerEvent for TeleportToPositionEvent {
     type SystemData<'a> = (
         Read<'a, IdMaps>,
@@ -2297,6 +2879,7 @@ pub fn transform_entity(
             scale,
             agent,
             loot,
+            encounter,
             alignment: _,
             pos: _,
             pets,
@@ -2393,6 +2976,7 @@ pub fn transform_entity(
             if !is_player {
                 set_or_remove_component(server, entity, agent)?;
                 set_or_remove_component(server, entity, loot.to_items().map(comp::ItemDrops))?;
+                set_or_remove_component(server, entity, encounter)?;
             }
 
             // Spawn pets
@@ -2,22 +2,29 @@ use crate::client::Client;
 use common::{
     comp::{
         self,
-        group::{ChangeNotification, Group, GroupManager},
+        group::{self, ChangeNotification, Group, GroupManager, Role},
         invite::{InviteKind, PendingInvites},
-        ChatType, GroupManip,
+        ChatType, GroupManip, Health, Pos,
     },
-    event::GroupManipEvent,
+    event::{GroupManipEvent, GroupSpectateEvent},
+    loot_roll::LootRolls,
     uid::{IdMaps, Uid},
 };
 use common_net::msg::ServerGeneral;
 use specs::{world::Entity, DispatcherBuilder, Entities, Read, ReadStorage, Write, WriteStorage};
+use tracing::debug;
 
 use super::{event_dispatch, ServerEvent};
 
 pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
     event_dispatch::<GroupManipEvent>(builder);
+    event_dispatch::<GroupSpectateEvent>(builder);
 }
 
+/// How far from a group member a dead player's spectate position may be, to
+/// account for the member having moved since the client last saw them
+const GROUP_SPECTATE_TOLERANCE: f32 = 16.0;
+
 pub fn can_invite(
     clients: &ReadStorage<'_, Client>,
     groups: &ReadStorage<'_, Group>,
@@ -108,7 +115,7 @@ pub fn update_map_markers<'a>(
             }
         },
         // Removed and NoGroup can be inferred by the client, NewLeader does not affect map markers
-        Removed(_) | NoGroup | NewLeader(_) => {},
+        Removed(_) | NoGroup | NewLeader(_) | LootMode(_) => {},
     }
 }
 
@@ -122,11 +129,22 @@ impl ServerEvent for GroupManipEvent {
         ReadStorage<'a, Uid>,
         ReadStorage<'a, comp::Alignment>,
         ReadStorage<'a, comp::MapMarker>,
+        Write<'a, LootRolls>,
     );
 
     fn handle(
         events: impl ExactSizeIterator<Item = Self>,
-        (entities, mut group_manager, id_maps, mut groups, clients, uids, alignments, map_markers): Self::SystemData<'_>,
+        (
+            entities,
+            mut group_manager,
+            id_maps,
+            mut groups,
+            clients,
+            uids,
+            alignments,
+            map_markers,
+            mut loot_rolls,
+        ): Self::SystemData<'_>,
     ) {
         for GroupManipEvent(entity, manip) in events {
             match manip {
@@ -339,6 +357,104 @@ impl ServerEvent for GroupManipEvent {
                         },
                     }
                 },
+                GroupManip::SetLootMode(loot_mode) => {
+                    // Make sure the entity is the group leader
+                    match groups
+                        .get(entity)
+                        .and_then(|group| group_manager.group_info(*group))
+                    {
+                        Some(info) if info.leader == entity => {
+                            group_manager.set_loot_mode(
+                                entity,
+                                loot_mode,
+                                &groups,
+                                &entities,
+                                &alignments,
+                                &uids,
+                                |entity, group_change| {
+                                    if let Some((g, c)) = clients.get(entity).and_then(|c| {
+                                        group_change
+                                            .try_map_ref(|e| uids.get(*e).copied())
+                                            .map(|g| (g, c))
+                                    }) {
+                                        c.send_fallible(ServerGeneral::GroupUpdate(g));
+                                    }
+                                },
+                            );
+                        },
+                        Some(_) => {
+                            if let Some(client) = clients.get(entity) {
+                                client.send_fallible(ServerGeneral::server_msg(
+                                    ChatType::Meta,
+                                    "Only the group leader can change the loot mode.",
+                                ));
+                            }
+                        },
+                        None => {
+                            if let Some(client) = clients.get(entity) {
+                                client.send_fallible(ServerGeneral::server_msg(
+                                    ChatType::Meta,
+                                    "You are not in a group.",
+                                ));
+                            }
+                        },
+                    }
+                },
+                GroupManip::LootRoll(roll_id, choice) => {
+                    // The roll is resolved by the loot system once every member chose or the
+                    // roll timed out
+                    if let Some(uid) = uids.get(entity)
+                        && !loot_rolls.choose(roll_id, *uid, choice)
+                    {
+                        debug!(?roll_id, "Ignoring loot roll choice from ineligible member");
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl ServerEvent for GroupSpectateEvent {
+    type SystemData<'a> = (
+        Entities<'a>,
+        ReadStorage<'a, Group>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, comp::Alignment>,
+        ReadStorage<'a, Health>,
+        WriteStorage<'a, Pos>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (entities, groups, uids, alignments, healths, mut positions): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            let is_dead = healths
+                .get(ev.entity)
+                .map_or(false, |health| health.is_dead);
+            let Some(group) = groups.get(ev.entity).filter(|group| !group.is_special()) else {
+                continue;
+            };
+            // There is no free camera for players, the position has to follow a living
+            // member of their group
+            let near_member = is_dead
+                && group::members(*group, &groups, &entities, &alignments, &uids).any(
+                    |(member, role)| {
+                        member != ev.entity
+                            && matches!(role, Role::Member)
+                            && healths.get(member).map_or(false, |health| !health.is_dead)
+                            && positions.get(member).map_or(false, |pos| {
+                                pos.0.distance_squared(ev.pos) <= GROUP_SPECTATE_TOLERANCE.powi(2)
+                            })
+                    },
+                );
+            if !near_member {
+                debug!(entity = ?ev.entity, "Rejecting group spectate position");
+                continue;
+            }
+
+            if let Some(pos) = positions.get_mut(ev.entity) {
+                pos.0 = ev.pos;
             }
         }
     }
@@ -0,0 +1,304 @@
+use crate::{
+    client::Client,
+    guild::{banner_blocks, Guilds},
+};
+use common::{
+    comp::{
+        self,
+        item::{tool::AbilityMap, MaterialStatManifest},
+        Content, InventoryUpdateEvent, LocalizationArg, Player,
+    },
+    event::{ChatEvent, EventBus, GuildManipEvent},
+    guild::{
+        GuildError, GuildHallSpot, GuildId, GuildManip, GuildRank, GUILD_CREATION_COST,
+        GUILD_HALL_COST,
+    },
+    heraldry::Heraldry,
+    trade::Trades,
+    uid::Uid,
+};
+use common_net::msg::{Notification, ServerGeneral};
+use common_state::BlockChange;
+use specs::{DispatcherBuilder, Join, Read, ReadExpect, ReadStorage, WriteExpect, WriteStorage};
+
+use super::{event_dispatch, ServerEvent};
+
+pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
+    event_dispatch::<GuildManipEvent>(builder);
+}
+
+/// A server notification to the members of the guild with the tag
+pub(super) fn guild_meta<'a>(
+    tag: &str,
+    key: &str,
+    args: impl IntoIterator<Item = (&'a str, LocalizationArg)>,
+) -> ChatEvent {
+    ChatEvent(
+        comp::ChatType::GuildMeta(tag.to_owned()).into_msg(Content::localized_with_args(key, args)),
+    )
+}
+
+pub(super) fn rank_name(rank: GuildRank) -> LocalizationArg {
+    LocalizationArg::from(Content::localized(match rank {
+        GuildRank::Member => "hud-guild-rank-member",
+        GuildRank::Officer => "hud-guild-rank-officer",
+        GuildRank::Leader => "hud-guild-rank-leader",
+    }))
+}
+
+/// Hangs the banner of a guild on its hall
+fn paint_banner(guilds: &Guilds, guild_id: GuildId, block_change: &mut BlockChange) {
+    if let Some(hall) = guilds.get(guild_id).and_then(|guild| guild.hall.as_ref()) {
+        for (pos, block) in banner_blocks(&hall.spot, &hall.banner) {
+            block_change.set(pos, block);
+        }
+    }
+}
+
+/// Puts the banner of an unclaimed hall back up on a hall that was abandoned
+fn unpaint_banner(spot: &GuildHallSpot, block_change: &mut BlockChange) {
+    for (pos, block) in banner_blocks(spot, &Heraldry::default()) {
+        block_change.set(pos, block);
+    }
+}
+
+impl ServerEvent for GuildManipEvent {
+    type SystemData<'a> = (
+        WriteExpect<'a, Guilds>,
+        ReadExpect<'a, AbilityMap>,
+        ReadExpect<'a, MaterialStatManifest>,
+        Read<'a, EventBus<ChatEvent>>,
+        Read<'a, Trades>,
+        WriteExpect<'a, BlockChange>,
+        WriteStorage<'a, comp::Inventory>,
+        WriteStorage<'a, comp::InventoryUpdate>,
+        ReadStorage<'a, comp::Pos>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Client>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (
+            mut guilds,
+            ability_map,
+            msm,
+            chat_events,
+            trades,
+            mut block_change,
+            mut inventories,
+            mut inventory_updates,
+            positions,
+            players,
+            uids,
+            clients,
+        ): Self::SystemData<'_>,
+    ) {
+        let mut chat_emitter = chat_events.emitter();
+        for GuildManipEvent(entity, manip) in events {
+            let Some(player) = players.get(entity) else {
+                continue;
+            };
+            let uuid = player.uuid();
+            let alias = || LocalizationArg::from(player.alias.clone());
+            let pos = positions.get(entity).map(|pos| pos.0);
+            let members_of = |guilds: &Guilds, guild_id| {
+                guilds
+                    .get(guild_id)
+                    .map(|guild| guild.members.iter().map(|member| member.uuid).collect())
+                    .unwrap_or_else(Vec::new)
+            };
+
+            let result = match manip {
+                GuildManip::Create { name, tag } => {
+                    if inventories.get(entity).map_or(false, |inventory| {
+                        inventory.coins() >= u64::from(GUILD_CREATION_COST)
+                    }) {
+                        guilds
+                            .create(uuid, player.alias.clone(), &name, &tag)
+                            .map(|guild_id| {
+                                if let Some(mut inventory) = inventories.get_mut(entity) {
+                                    inventory.pay_coins(GUILD_CREATION_COST, &ability_map, &msm);
+                                }
+                                guilds.notify(Some(guild_id), &[uuid], &players, &uids, &clients);
+                                chat_emitter.emit(guild_meta(&tag, "hud-guild-created", [
+                                    ("name", LocalizationArg::from(name)),
+                                    ("leader", alias()),
+                                ]));
+                            })
+                    } else {
+                        Err(GuildError::NotEnoughCoins)
+                    }
+                },
+                GuildManip::Leave => {
+                    let tag = guilds.tag_of(uuid).map(str::to_owned);
+                    guilds.leave(uuid).map(|left| {
+                        guilds.notify(Some(left.guild_id), &[uuid], &players, &uids, &clients);
+                        if let Some(spot) = left.abandoned_hall {
+                            unpaint_banner(&spot, &mut block_change);
+                        }
+                        if let Some(tag) = tag.filter(|_| !left.disbanded) {
+                            chat_emitter.emit(guild_meta(&tag, "hud-guild-member_left", [(
+                                "name",
+                                alias(),
+                            )]));
+                            if let Some(new_leader) = left.new_leader.and_then(|new_leader| {
+                                guilds.get(left.guild_id)?.member(new_leader)
+                            }) {
+                                chat_emitter.emit(guild_meta(&tag, "hud-guild-rank_changed", [
+                                    ("name", LocalizationArg::from(new_leader.alias.clone())),
+                                    ("rank", rank_name(GuildRank::Leader)),
+                                ]));
+                            }
+                        }
+                    })
+                },
+                GuildManip::Kick(target) => {
+                    let target_alias = guilds
+                        .guild_of(uuid)
+                        .and_then(|guild| guild.member(target))
+                        .map(|member| member.alias.clone());
+                    guilds.kick(uuid, target).map(|guild_id| {
+                        guilds.notify(Some(guild_id), &[target], &players, &uids, &clients);
+                        if let (Some(tag), Some(target_alias)) = (guilds.tag_of(uuid), target_alias)
+                        {
+                            chat_emitter.emit(guild_meta(tag, "hud-guild-member_kicked", [
+                                ("name", LocalizationArg::from(target_alias)),
+                                ("by", alias()),
+                            ]));
+                        }
+                    })
+                },
+                GuildManip::SetRank(target, rank) => {
+                    guilds.set_rank(uuid, target, rank).map(|guild_id| {
+                        guilds.notify(Some(guild_id), &[], &players, &uids, &clients);
+                        if let Some(guild) = guilds.get(guild_id)
+                            && let Some(member) = guild.member(target)
+                        {
+                            chat_emitter.emit(guild_meta(&guild.tag, "hud-guild-rank_changed", [
+                                ("name", LocalizationArg::from(member.alias.clone())),
+                                ("rank", rank_name(rank)),
+                            ]));
+                        }
+                    })
+                },
+                GuildManip::Disband => guilds.disband(uuid).map(|guild| {
+                    let former = guild
+                        .members
+                        .iter()
+                        .map(|member| member.uuid)
+                        .collect::<Vec<_>>();
+                    guilds.notify(None, &former, &players, &uids, &clients);
+                    if let Some(hall) = &guild.hall {
+                        unpaint_banner(&hall.spot, &mut block_change);
+                    }
+                    for (player, client) in (&players, &clients).join() {
+                        if former.contains(&player.uuid()) {
+                            client.send_fallible(ServerGeneral::server_msg(
+                                comp::ChatType::Meta,
+                                Content::localized_with_args("hud-guild-disbanded", [(
+                                    "name",
+                                    LocalizationArg::from(guild.name.clone()),
+                                )]),
+                            ));
+                        }
+                    }
+                }),
+                GuildManip::ClaimHall(board) => {
+                    if inventories.get(entity).map_or(false, |inventory| {
+                        inventory.coins() >= u64::from(GUILD_HALL_COST)
+                    }) {
+                        guilds
+                            .claim_hall(uuid, pos.unwrap_or_default(), board)
+                            .map(|guild_id| {
+                                if let Some(mut inventory) = inventories.get_mut(entity) {
+                                    inventory.pay_coins(GUILD_HALL_COST, &ability_map, &msm);
+                                }
+                                paint_banner(&guilds, guild_id, &mut block_change);
+                                let members = members_of(&guilds, guild_id);
+                                guilds.notify(Some(guild_id), &members, &players, &uids, &clients);
+                                if let Some(tag) = guilds.tag_of(uuid) {
+                                    chat_emitter.emit(guild_meta(tag, "hud-guild-hall_claimed", [
+                                        ("name", alias()),
+                                    ]));
+                                }
+                            })
+                    } else {
+                        Err(GuildError::NotEnoughCoins)
+                    }
+                },
+                GuildManip::SetBanner(banner) => guilds.set_banner(uuid, banner).map(|guild_id| {
+                    paint_banner(&guilds, guild_id, &mut block_change);
+                    let members = members_of(&guilds, guild_id);
+                    guilds.notify(Some(guild_id), &members, &players, &uids, &clients);
+                }),
+                GuildManip::SetStorageRank(rank) => {
+                    guilds.set_storage_rank(uuid, rank).map(|guild_id| {
+                        guilds.notify(Some(guild_id), &[], &players, &uids, &clients);
+                    })
+                },
+                GuildManip::PostNotice(text) => guilds
+                    .post_notice(uuid, player.alias.clone(), &text)
+                    .map(|guild_id| {
+                        guilds.notify(Some(guild_id), &[], &players, &uids, &clients);
+                        if let Some(tag) = guilds.tag_of(uuid) {
+                            chat_emitter.emit(guild_meta(tag, "hud-guild-notice_posted", [(
+                                "name",
+                                alias(),
+                            )]));
+                        }
+                    }),
+                GuildManip::RemoveNotice(notice_id) => {
+                    guilds.remove_notice(uuid, notice_id).map(|guild_id| {
+                        guilds.notify(Some(guild_id), &[], &players, &uids, &clients);
+                    })
+                },
+                GuildManip::StorageDeposit(slot) => {
+                    match (pos, uids.get(entity), inventories.get_mut(entity)) {
+                        (Some(pos), Some(uid), Some(mut inventory))
+                            if !trades.in_mutable_trade(uid) =>
+                        {
+                            guilds.storage_deposit(uuid, pos, &mut inventory, slot).map(
+                                |guild_id| {
+                                    let _ = inventory_updates.insert(
+                                        entity,
+                                        comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                                    );
+                                    guilds.notify(Some(guild_id), &[], &players, &uids, &clients);
+                                },
+                            )
+                        },
+                        _ => Ok(()),
+                    }
+                },
+                GuildManip::StorageWithdraw(index) => {
+                    match (pos, uids.get(entity), inventories.get_mut(entity)) {
+                        (Some(pos), Some(uid), Some(mut inventory))
+                            if !trades.in_mutable_trade(uid) =>
+                        {
+                            guilds
+                                .storage_withdraw(uuid, pos, &mut inventory, index)
+                                .map(|guild_id| {
+                                    let _ = inventory_updates.insert(
+                                        entity,
+                                        comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                                    );
+                                    guilds.notify(Some(guild_id), &[], &players, &uids, &clients);
+                                })
+                        },
+                        _ => Ok(()),
+                    }
+                },
+            };
+
+            if let Err(error) = result
+                && let Some(client) = clients.get(entity)
+            {
+                client.send_fallible(ServerGeneral::Notification(Notification::GuildFailed(
+                    error,
+                )));
+            }
+        }
+    }
+}
@@ -1,5 +1,9 @@
 use crate::client::Client;
-use common::event::RequestSiteInfoEvent;
+#[cfg(feature = "worldgen")]
+use crate::rtsim::RtSim;
+use common::event::{RequestReputationEvent, RequestSiteInfoEvent};
+#[cfg(feature = "worldgen")]
+use common::{comp::Presence, rtsim::FactionReputation};
 use common_net::msg::{world_msg::EconomyInfo, ServerGeneral};
 #[cfg(feature = "plugins")]
 use common_state::plugin::PluginMgr;
@@ -14,6 +18,7 @@ use super::{event_dispatch, ServerEvent};
 
 pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
     event_dispatch::<RequestSiteInfoEvent>(builder);
+    event_dispatch::<RequestReputationEvent>(builder);
     #[cfg(feature = "plugins")]
     event_dispatch::<common::event::RequestPluginsEvent>(builder);
 }
@@ -72,6 +77,67 @@ impl ServerEvent for RequestSiteInfoEvent {
     }
 }
 
+/// Factions only exist with worldgen
+#[cfg(not(feature = "worldgen"))]
+impl ServerEvent for RequestReputationEvent {
+    type SystemData<'a> = ReadStorage<'a, Client>;
+
+    fn handle(events: impl ExactSizeIterator<Item = Self>, clients: Self::SystemData<'_>) {
+        for ev in events {
+            if let Some(client) = clients.get(ev.entity) {
+                client.send_fallible(ServerGeneral::Reputation(Vec::new()));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "worldgen")]
+impl ServerEvent for RequestReputationEvent {
+    type SystemData<'a> = (
+        ReadExpect<'a, IndexOwned>,
+        ReadExpect<'a, RtSim>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Client>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (index, rtsim, presences, clients): Self::SystemData<'_>,
+    ) {
+        let state = rtsim.state();
+        let data = state.data();
+        for ev in events {
+            let Some((client, character_id)) = clients.get(ev.entity).zip(
+                presences
+                    .get(ev.entity)
+                    .and_then(|presence| presence.kind.character_id()),
+            ) else {
+                continue;
+            };
+            let mut reputation = data
+                .factions
+                .iter()
+                // Factions that never heard of the character have no opinion of them
+                .filter(|(_, faction)| faction.sentiments.toward(character_id).value() != 0.0)
+                .filter_map(|(faction_id, faction)| {
+                    // Factions are named after their seat
+                    let name = data
+                        .sites
+                        .values()
+                        .filter(|site| site.faction == Some(faction_id))
+                        .find_map(|site| Some(index.sites.get(site.world_site?).name()))?;
+                    Some(FactionReputation {
+                        name: name.to_string(),
+                        tier: faction.reputation(character_id),
+                    })
+                })
+                .collect::<Vec<_>>();
+            reputation.sort_by(|a, b| b.tier.cmp(&a.tier).then_with(|| a.name.cmp(&b.name)));
+            client.send_fallible(ServerGeneral::Reputation(reputation));
+        }
+    }
+}
+
 /// Send missing plugins to the client
 #[cfg(feature = "plugins")]
 impl ServerEvent for common::event::RequestPluginsEvent {
@@ -1,21 +1,27 @@
 use common_state::{BlockChange, ScheduledBlockChange};
-use specs::{DispatcherBuilder, Join, ReadExpect, ReadStorage, WriteExpect, WriteStorage};
+use specs::{DispatcherBuilder, Join, Read, ReadExpect, ReadStorage, WriteExpect, WriteStorage};
 use vek::*;
 
 use common::{
     assets::{self, Concatenate},
     comp::{
         self,
-        agent::{AgentEvent, SoundKind},
+        agent::{AgentEvent, BehaviorCapability, Scout, SoundKind},
         inventory::slot::EquipSlot,
         item::{flatten_counted_items, MaterialStatManifest},
         loot_owner::LootOwnerKind,
+        pet::can_scout,
         tool::AbilityMap,
     },
-    consts::{MAX_INTERACT_RANGE, MAX_NPCINTERACT_RANGE, SOUND_TRAVEL_DIST_PER_VOLUME},
+    consts::{
+        MAX_INTERACT_RANGE, MAX_NPCINTERACT_RANGE, MAX_PICKUP_RANGE, SOUND_TRAVEL_DIST_PER_VOLUME,
+    },
     event::{
-        CreateItemDropEvent, CreateSpriteEvent, EventBus, MineBlockEvent, NpcInteractEvent,
-        SetLanternEvent, SetPetStayEvent, SoundEvent, TamePetEvent, ToggleSpriteLightEvent,
+        ActivateShrineEvent, CreateItemDropEvent, CreateSpriteEvent, DeleteEvent, DigGroundEvent,
+        EventBus, GiveDirectionsEvent, MineBlockEvent, NpcInteractEvent, RecoverGravestoneEvent,
+        ScoutPingsEvent, SendScoutEvent, SetLanternEvent, SetPetStayEvent, ShrineTravelEvent,
+        SoundEvent, TamePetEvent, TeleportToPositionEvent, ToggleSpriteLightEvent,
+        UnlockTitleEvent,
     },
     link::Is,
     mounting::Mount,
@@ -27,7 +33,10 @@ use common::{
     vol::ReadVol,
 };
 
-use crate::{Server, Time};
+use crate::{
+    client::Client, sys::agent::consts::MAX_SCOUT_RANGE, treasure::TreasureCaches, Server, Time,
+};
+use common_net::msg::{Notification, ServerGeneral, ShrineTravelError};
 
 use crate::pet::tame_pet;
 use hashbrown::{HashMap, HashSet};
@@ -41,10 +50,17 @@ pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
     event_dispatch::<SetLanternEvent>(builder);
     event_dispatch::<NpcInteractEvent>(builder);
     event_dispatch::<SetPetStayEvent>(builder);
+    event_dispatch::<SendScoutEvent>(builder);
+    event_dispatch::<ScoutPingsEvent>(builder);
     event_dispatch::<MineBlockEvent>(builder);
+    event_dispatch::<DigGroundEvent>(builder);
     event_dispatch::<SoundEvent>(builder);
     event_dispatch::<CreateSpriteEvent>(builder);
     event_dispatch::<ToggleSpriteLightEvent>(builder);
+    event_dispatch::<ActivateShrineEvent>(builder);
+    event_dispatch::<ShrineTravelEvent>(builder);
+    event_dispatch::<RecoverGravestoneEvent>(builder);
+    event_dispatch::<GiveDirectionsEvent>(builder);
 }
 
 impl ServerEvent for SetLanternEvent {
@@ -98,11 +114,12 @@ impl ServerEvent for NpcInteractEvent {
         WriteStorage<'a, comp::Agent>,
         ReadStorage<'a, comp::Pos>,
         ReadStorage<'a, Uid>,
+        ReadStorage<'a, Client>,
     );
 
     fn handle(
         events: impl ExactSizeIterator<Item = Self>,
-        (mut agents, positions, uids): Self::SystemData<'_>,
+        (mut agents, positions, uids, clients): Self::SystemData<'_>,
     ) {
         for NpcInteractEvent(interactor, npc_entity, subject) in events {
             let within_range = {
@@ -125,6 +142,28 @@ impl ServerEvent for NpcInteractEvent {
                         .push_back(AgentEvent::Talk(*interactor_uid, subject));
                 }
             }
+
+            // Bankers open the bank even when they're busy talking to someone else
+            if within_range
+                && agents
+                    .get(npc_entity)
+                    .map_or(false, |agent| agent.behavior.can(BehaviorCapability::BANK))
+                && let Some(client) = clients.get(interactor)
+                && let Some(npc_uid) = uids.get(npc_entity)
+            {
+                client.send_fallible(ServerGeneral::OpenBank(*npc_uid));
+            }
+
+            // Trainers show what they teach in the same way
+            if within_range
+                && agents
+                    .get(npc_entity)
+                    .map_or(false, |agent| agent.behavior.can(BehaviorCapability::TRAIN))
+                && let Some(client) = clients.get(interactor)
+                && let Some(npc_uid) = uids.get(npc_entity)
+            {
+                client.send_fallible(ServerGeneral::OpenTrainer(*npc_uid));
+            }
         }
     }
 }
@@ -168,6 +207,71 @@ impl ServerEvent for SetPetStayEvent {
     }
 }
 
+impl ServerEvent for SendScoutEvent {
+    type SystemData<'a> = (
+        WriteStorage<'a, comp::Agent>,
+        ReadStorage<'a, comp::Pos>,
+        ReadStorage<'a, comp::MapMarker>,
+        ReadStorage<'a, comp::Alignment>,
+        ReadStorage<'a, comp::Body>,
+        ReadStorage<'a, Uid>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (mut agents, positions, map_markers, alignments, bodies, uids): Self::SystemData<'_>,
+    ) {
+        for SendScoutEvent(owner, pet) in events {
+            let is_owner = uids.get(owner).map_or(false, |owner_uid| {
+                matches!(
+                    alignments.get(pet),
+                    Some(comp::Alignment::Owned(pet_owner)) if *pet_owner == *owner_uid,
+                )
+            });
+            if !is_owner
+                || !within_mounting_range(positions.get(owner), positions.get(pet))
+                || !bodies.get(pet).map_or(false, can_scout)
+            {
+                continue;
+            }
+            let Some(owner_pos) = positions.get(owner).map(|pos| pos.0.xy()) else {
+                continue;
+            };
+            // The bird scouts around the owner's map marker, or around the owner if
+            // they haven't placed one. It won't stray too far from them, though.
+            let offset = map_markers
+                .get(owner)
+                .map_or(Vec2::zero(), |marker| marker.0.as_() - owner_pos);
+            let target = owner_pos
+                + offset.try_normalized().map_or(Vec2::zero(), |dir| {
+                    dir * offset.magnitude().min(MAX_SCOUT_RANGE)
+                });
+            if let Some(agent) = agents.get_mut(pet)
+                && agent.stay_pos.is_none()
+            {
+                agent.scout = Some(Scout {
+                    target,
+                    circling_since: None,
+                    last_scan: 0.0,
+                    returning: false,
+                });
+            }
+        }
+    }
+}
+
+impl ServerEvent for ScoutPingsEvent {
+    type SystemData<'a> = ReadStorage<'a, Client>;
+
+    fn handle(events: impl ExactSizeIterator<Item = Self>, clients: Self::SystemData<'_>) {
+        for ScoutPingsEvent { owner, pings } in events {
+            if let Some(client) = clients.get(owner) {
+                client.send_fallible(ServerGeneral::ScoutPings(pings));
+            }
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct ResourceExperienceManifest(HashMap<String, u32>);
 
@@ -316,6 +420,71 @@ impl ServerEvent for MineBlockEvent {
     }
 }
 
+impl ServerEvent for DigGroundEvent {
+    type SystemData<'a> = (
+        WriteExpect<'a, BlockChange>,
+        WriteExpect<'a, TreasureCaches>,
+        ReadExpect<'a, TerrainGrid>,
+        ReadExpect<'a, EventBus<Outcome>>,
+        Read<'a, EventBus<UnlockTitleEvent>>,
+        ReadStorage<'a, comp::Presence>,
+        ReadStorage<'a, Client>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (
+            mut block_change,
+            mut treasure_caches,
+            terrain,
+            outcomes,
+            unlock_title_events,
+            presences,
+            clients,
+        ): Self::SystemData<'_>,
+    ) {
+        let mut outcome_emitter = outcomes.emitter();
+        let mut unlock_title_emitter = unlock_title_events.emitter();
+        for ev in events {
+            let Some(character_id) = presences
+                .get(ev.entity)
+                .and_then(|presence| presence.kind.character_id())
+            else {
+                continue;
+            };
+            let Some(cache) = treasure_caches.dig(character_id, ev.pos.xy()) else {
+                continue;
+            };
+            // Unearth the chest on the ground above the cache
+            let surface = (-8..8)
+                .rev()
+                .map(|z| cache.with_z(ev.pos.z.floor() as i32 + z))
+                .find(|pos| terrain.get(*pos).map_or(false, |block| block.is_filled()));
+            let Some(surface) = surface else {
+                continue;
+            };
+            let chest_pos = surface + Vec3::unit_z();
+            block_change.set(chest_pos, Block::air(SpriteKind::ChestBuried));
+            if let Ok(block) = terrain.get(surface) {
+                outcome_emitter.emit(Outcome::BreakBlock {
+                    pos: chest_pos,
+                    color: block.get_color(),
+                });
+            }
+            if let Some(client) = clients.get(ev.entity) {
+                client.send_fallible(ServerGeneral::server_msg(
+                    comp::ChatType::Meta,
+                    comp::Content::localized("hud-treasure_map-found"),
+                ));
+            }
+            unlock_title_emitter.emit(UnlockTitleEvent {
+                entity: ev.entity,
+                title: comp::Title::TreasureHunter,
+            });
+        }
+    }
+}
+
 impl ServerEvent for SoundEvent {
     type SystemData<'a> = (
         ReadExpect<'a, EventBus<Outcome>>,
@@ -435,3 +604,225 @@ pub fn handle_tame_pet(server: &mut Server, ev: TamePetEvent) {
     // showing taming success?
     tame_pet(server.state.ecs(), ev.pet_entity, ev.owner_entity);
 }
+
+impl ServerEvent for ActivateShrineEvent {
+    type SystemData<'a> = (
+        ReadStorage<'a, comp::Pos>,
+        ReadStorage<'a, comp::Object>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, comp::UnlockedShrines>,
+        Read<'a, EventBus<UnlockTitleEvent>>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (positions, objects, clients, mut unlocked_shrines, unlock_title_events): Self::SystemData<
+            '_,
+        >,
+    ) {
+        let mut unlock_title_emitter = unlock_title_events.emitter();
+        for ev in events {
+            let Some((comp::Object::Shrine { site }, shrine_pos)) =
+                objects.get(ev.shrine).zip(positions.get(ev.shrine))
+            else {
+                continue;
+            };
+            let in_range = positions.get(ev.entity).map_or(false, |pos| {
+                pos.0.distance_squared(shrine_pos.0) <= comp::SHRINE_RANGE.powi(2)
+            });
+            let is_locked = unlocked_shrines
+                .get(ev.entity)
+                .map_or(false, |unlocked| unlocked.get(*site).is_none());
+
+            if in_range
+                && is_locked
+                && let Some(mut unlocked) = unlocked_shrines.get_mut(ev.entity)
+            {
+                unlocked.unlock(*site, shrine_pos.0);
+                if let Some(client) = clients.get(ev.entity) {
+                    client.send_fallible(ServerGeneral::Notification(Notification::ShrineUnlocked));
+                }
+                if unlocked.iter().count() >= comp::WAYFARER_SHRINES {
+                    unlock_title_emitter.emit(UnlockTitleEvent {
+                        entity: ev.entity,
+                        title: comp::Title::Wayfarer,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl ServerEvent for ShrineTravelEvent {
+    type SystemData<'a> = (
+        Read<'a, Time>,
+        ReadExpect<'a, AbilityMap>,
+        ReadExpect<'a, MaterialStatManifest>,
+        Read<'a, EventBus<TeleportToPositionEvent>>,
+        ReadStorage<'a, comp::Pos>,
+        ReadStorage<'a, comp::Object>,
+        ReadStorage<'a, comp::Health>,
+        ReadStorage<'a, comp::UnlockedShrines>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, comp::Inventory>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (
+            time,
+            ability_map,
+            msm,
+            teleport_to_position_events,
+            positions,
+            objects,
+            healths,
+            unlocked_shrines,
+            clients,
+            mut inventories,
+        ): Self::SystemData<'_>,
+    ) {
+        let mut teleport_to_position_emitter = teleport_to_position_events.emitter();
+        for ev in events {
+            let Some(health) = healths.get(ev.entity).filter(|health| !health.is_dead) else {
+                continue;
+            };
+            let at_shrine = positions.get(ev.entity).and_then(|pos| {
+                (&positions, &objects)
+                    .join()
+                    .filter(|(_, object)| matches!(object, comp::Object::Shrine { .. }))
+                    .map(|(shrine_pos, _)| shrine_pos.0)
+                    .find(|shrine_pos| {
+                        pos.0.distance_squared(*shrine_pos) <= comp::SHRINE_RANGE.powi(2)
+                    })
+            });
+            let target = unlocked_shrines
+                .get(ev.entity)
+                .and_then(|unlocked| unlocked.get(ev.site));
+            let in_combat = health.damaged_within(*time, comp::SHRINE_COMBAT_LOCKOUT);
+
+            let result = match (at_shrine, target) {
+                (None, _) => Err(ShrineTravelError::NotAtShrine),
+                (_, None) => Err(ShrineTravelError::Locked),
+                _ if in_combat => Err(ShrineTravelError::InCombat),
+                (Some(from), Some(to)) => {
+                    let cost = comp::shrine_travel_cost(from, to);
+                    let paid = inventories
+                        .get_mut(ev.entity)
+                        .map_or(false, |mut inventory| {
+                            inventory.pay_coins(cost, &ability_map, &msm)
+                        });
+                    if paid {
+                        Ok(to)
+                    } else {
+                        Err(ShrineTravelError::NotEnoughCoins)
+                    }
+                },
+            };
+
+            match result {
+                Ok(position) => teleport_to_position_emitter.emit(TeleportToPositionEvent {
+                    entity: ev.entity,
+                    position,
+                }),
+                Err(error) => {
+                    if let Some(client) = clients.get(ev.entity) {
+                        client.send_fallible(ServerGeneral::Notification(
+                            Notification::ShrineTravelFailed(error),
+                        ));
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl ServerEvent for RecoverGravestoneEvent {
+    type SystemData<'a> = (
+        ReadExpect<'a, AbilityMap>,
+        ReadExpect<'a, MaterialStatManifest>,
+        Read<'a, EventBus<DeleteEvent>>,
+        ReadStorage<'a, comp::Pos>,
+        ReadStorage<'a, comp::Object>,
+        ReadStorage<'a, Uid>,
+        WriteStorage<'a, comp::ItemDrops>,
+        WriteStorage<'a, comp::Inventory>,
+        WriteStorage<'a, comp::InventoryUpdate>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (
+            ability_map,
+            msm,
+            delete_events,
+            positions,
+            objects,
+            uids,
+            mut item_drops,
+            mut inventories,
+            mut inventory_updates,
+        ): Self::SystemData<'_>,
+    ) {
+        let mut delete_emitter = delete_events.emitter();
+        for ev in events {
+            let Some(comp::Object::Gravestone { owner, .. }) = objects.get(ev.gravestone) else {
+                continue;
+            };
+            let is_owner = uids.get(ev.entity) == Some(owner);
+            let in_range = positions
+                .get(ev.entity)
+                .zip(positions.get(ev.gravestone))
+                .map_or(false, |(pos, gravestone_pos)| {
+                    pos.0.distance_squared(gravestone_pos.0) <= MAX_PICKUP_RANGE.powi(2)
+                });
+            if !is_owner || !in_range {
+                continue;
+            }
+
+            let (Some(comp::ItemDrops(items)), Some(mut inventory)) = (
+                item_drops.get_mut(ev.gravestone),
+                inventories.get_mut(ev.entity),
+            ) else {
+                continue;
+            };
+
+            // Whatever doesn't fit into the inventory stays in the gravestone
+            let mut collected = Vec::new();
+            let mut leftover = Vec::new();
+            for (amount, item) in items.drain(..) {
+                let item_msg = item.frontend_item(&ability_map, &msm);
+                match inventory.push(item) {
+                    Ok(()) => collected.push(item_msg),
+                    Err((item, _)) => leftover.push((amount, item)),
+                }
+            }
+            *items = leftover;
+            if items.is_empty() {
+                delete_emitter.emit(DeleteEvent(ev.gravestone));
+            }
+
+            if let Some(inventory_update) = inventory_updates
+                .entry(ev.entity)
+                .ok()
+                .map(|entry| entry.or_insert_with(comp::InventoryUpdate::default))
+            {
+                for item_msg in collected {
+                    inventory_update.push(comp::InventoryUpdateEvent::Collected(item_msg));
+                }
+            }
+        }
+    }
+}
+
+impl ServerEvent for GiveDirectionsEvent {
+    type SystemData<'a> = ReadStorage<'a, Client>;
+
+    fn handle(events: impl ExactSizeIterator<Item = Self>, clients: Self::SystemData<'_>) {
+        for ev in events {
+            if let Some(client) = clients.get(ev.entity) {
+                client.send_fallible(ServerGeneral::Directions(ev.directions));
+            }
+        }
+    }
+}
@@ -1,27 +1,35 @@
+#[cfg(not(feature = "worldgen"))]
+use crate::test_world::{IndexOwned, World};
 use hashbrown::HashSet;
 use rand::{seq::IteratorRandom, Rng};
 use specs::{
     join::Join, shred, DispatcherBuilder, Entities, Entity as EcsEntity, Read, ReadExpect,
-    ReadStorage, SystemData, Write, WriteStorage,
+    ReadStorage, SystemData, Write, WriteExpect, WriteStorage,
 };
+use std::sync::Arc;
 use tracing::{debug, error, warn};
 use vek::{Rgb, Vec3};
+#[cfg(feature = "worldgen")]
+use world::{IndexOwned, World};
 
 use common::{
     comp::{
         self,
-        group::members,
+        compass::Direction,
+        group::{members, GroupManager, LootMode},
         item::{self, flatten_counted_items, tool::AbilityMap, MaterialStatManifest},
         loot_owner::LootOwnerKind,
         slot::{self, Slot},
-        InventoryUpdate, LootOwner, PickupItem,
+        ChatType, Content, InventoryUpdate, LocalizationArg, LootOwner, PickupItem,
     },
-    consts::MAX_PICKUP_RANGE,
+    consts::{MAX_NPCINTERACT_RANGE, MAX_PICKUP_RANGE},
     event::{
-        BuffEvent, CreateItemDropEvent, CreateObjectEvent, DeleteEvent, EmitExt, HealthChangeEvent,
-        InventoryManipEvent, PoiseChangeEvent, TamePetEvent,
+        BuffEvent, CreateItemDropEvent, CreateObjectEvent, CreateShipEvent, DeleteEvent, EmitExt,
+        ExposeDisguiseEvent, HealthChangeEvent, InventoryManipEvent, PoiseChangeEvent,
+        TamePetEvent,
     },
     event_emitters,
+    loot_roll::{self, LootRolls},
     mounting::VolumePos,
     recipe::{self, default_component_recipe_book, default_repair_recipe_book, RecipeBookManifest},
     resources::{ProgramTime, Time},
@@ -33,7 +41,7 @@ use common::{
 };
 use comp::LightEmitter;
 
-use crate::client::Client;
+use crate::{client::Client, treasure::TreasureCaches};
 use common::comp::{
     pet::is_tameable, Alignment, Body, CollectFailedReason, Group, InventoryUpdateEvent,
 };
@@ -66,9 +74,11 @@ event_emitters! {
         delete: DeleteEvent,
         create_item_drop: CreateItemDropEvent,
         create_object: CreateObjectEvent,
+        create_ship: CreateShipEvent,
         health_change: HealthChangeEvent,
         poise_change: PoiseChangeEvent,
         buff: BuffEvent,
+        expose_disguise: ExposeDisguiseEvent,
     }
 }
 #[derive(SystemData)]
@@ -77,6 +87,8 @@ pub struct InventoryManipData<'a> {
     events: Events<'a>,
     block_change: Write<'a, common_state::BlockChange>,
     trades: Write<'a, Trades>,
+    loot_rolls: Write<'a, LootRolls>,
+    group_manager: Read<'a, GroupManager>,
     terrain: ReadExpect<'a, common::terrain::TerrainGrid>,
     id_maps: Read<'a, IdMaps>,
     time: Read<'a, Time>,
@@ -84,7 +96,11 @@ pub struct InventoryManipData<'a> {
     ability_map: ReadExpect<'a, AbilityMap>,
     msm: ReadExpect<'a, MaterialStatManifest>,
     rbm: ReadExpect<'a, RecipeBookManifest>,
+    world: ReadExpect<'a, Arc<World>>,
+    index: ReadExpect<'a, IndexOwned>,
+    treasure_caches: WriteExpect<'a, TreasureCaches>,
     inventories: WriteStorage<'a, comp::Inventory>,
+    skill_sets: WriteStorage<'a, comp::SkillSet>,
     items: WriteStorage<'a, comp::PickupItem>,
     inventory_updates: WriteStorage<'a, comp::InventoryUpdate>,
     light_emitters: WriteStorage<'a, comp::LightEmitter>,
@@ -107,6 +123,7 @@ pub struct InventoryManipData<'a> {
     pets: ReadStorage<'a, comp::Pet>,
     velocities: ReadStorage<'a, comp::Vel>,
     masses: ReadStorage<'a, comp::Mass>,
+    presences: ReadStorage<'a, comp::Presence>,
 }
 
 impl ServerEvent for InventoryManipEvent {
@@ -359,9 +376,51 @@ impl ServerEvent for InventoryManipEvent {
                             // If there are items to be reclaimed from the block, add it to the
                             // inventory
                             if let Some(items) = comp::Item::try_reclaim_from_block(block) {
+                                // Chest loot of a group that rolls for its loot is held back until
+                                // the members close by decided who gets each item
+                                let sprite_center =
+                                    sprite_pos.as_::<f32>() + Vec3::new(0.5, 0.5, 0.0);
+                                let roll_members = data
+                                    .groups
+                                    .get(entity)
+                                    .filter(|group| {
+                                        block.get_sprite().map_or(false, |s| s.is_container())
+                                            && data
+                                                .group_manager
+                                                .group_info(**group)
+                                                .map_or(false, |info| {
+                                                    info.loot_mode == LootMode::NeedGreed
+                                                })
+                                    })
+                                    .map(|group| {
+                                        (
+                                            *group,
+                                            loot_roll::eligible_members(
+                                                *group,
+                                                sprite_center,
+                                                &data.groups,
+                                                &data.entities,
+                                                &data.alignments,
+                                                &data.uids,
+                                                &data.positions,
+                                            ),
+                                        )
+                                    })
+                                    .filter(|(_, members)| members.len() > 1);
+
                                 for item in
                                     flatten_counted_items(&items, &data.ability_map, &data.msm)
                                 {
+                                    if let Some((group, members)) = &roll_members {
+                                        data.loot_rolls.start(
+                                            item,
+                                            sprite_center,
+                                            *group,
+                                            members.iter().copied(),
+                                            *data.time,
+                                        );
+                                        continue;
+                                    }
                                     let mut item_msg =
                                         item.frontend_item(&data.ability_map, &data.msm);
                                     let do_announce = match inventory.push(item) {
@@ -404,6 +463,13 @@ impl ServerEvent for InventoryManipEvent {
                             // We made sure earlier the block was not already modified this tick
                             data.block_change.set(sprite_pos, block.into_vacant());
 
+                            // Nobody believes a disguise that comes with sticky fingers
+                            if block.get_sprite().map_or(false, |s| s.is_container())
+                                && comp::disguise::disguise(inventory).is_some()
+                            {
+                                emitters.emit(ExposeDisguiseEvent { entity });
+                            }
+
                             // If the block was a keyhole, remove nearby door blocks
                             // TODO: Abstract this code into a generalised way to do block updates?
                             if let Some(kind_to_destroy) = match block.get_sprite() {
@@ -609,6 +675,37 @@ impl ServerEvent for InventoryManipEvent {
 
                                         Some(InventoryUpdateEvent::Used)
                                     },
+                                    ItemKind::Utility {
+                                        kind: item::Utility::Balloon,
+                                        ..
+                                    } => {
+                                        // Unpack the balloon alongside its owner, ready to board
+                                        if let Some(pos) = data.positions.get(entity) {
+                                            let ori = data
+                                                .orientations
+                                                .get(entity)
+                                                .copied()
+                                                .unwrap_or_default();
+                                            emitters.emit(CreateShipEvent {
+                                                pos: comp::Pos(
+                                                    pos.0
+                                                        + ori.look_vec().with_z(0.0) * 8.0
+                                                        + Vec3::unit_z() * 10.0,
+                                                ),
+                                                ori,
+                                                ship: comp::ship::Body::HotAirBalloon,
+                                                rtsim_entity: None,
+                                                driver: None,
+                                            });
+                                            Some(InventoryUpdateEvent::Used)
+                                        } else {
+                                            inventory.insert_or_stack_at(slot, item).expect(
+                                                "slot was just vacated of item, so it definitely \
+                                                 fits there.",
+                                            );
+                                            None
+                                        }
+                                    },
                                     ItemKind::RecipeGroup { .. } => {
                                         match inventory.push_recipe_group(item) {
                                             Ok(()) => {
@@ -628,6 +725,97 @@ impl ServerEvent for InventoryManipEvent {
                                             },
                                         }
                                     },
+                                    ItemKind::SkillBook { teaches } => {
+                                        let teaches = *teaches;
+                                        let learned = data.skill_sets.get_mut(entity).map_or(
+                                            false,
+                                            |mut skill_set| match teaches {
+                                                item::SkillBook::SkillPoints { group, amount } => {
+                                                    skill_set.skill_group_accessible(group) && {
+                                                        skill_set.add_skill_points(group, amount);
+                                                        true
+                                                    }
+                                                },
+                                                item::SkillBook::Skill(skill) => {
+                                                    skill_set.learn_skill(skill).is_ok()
+                                                },
+                                            },
+                                        );
+                                        if learned {
+                                            Some(InventoryUpdateEvent::Used)
+                                        } else {
+                                            inventory.insert_or_stack_at(slot, item).expect(
+                                                "slot was just vacated of item, so it definitely \
+                                                 fits there.",
+                                            );
+                                            None
+                                        }
+                                    },
+                                    ItemKind::TreasureMap => {
+                                        let index = data.index.as_index_ref();
+                                        let pos = data.positions.get(entity).map(|pos| pos.0);
+                                        let character_id = data
+                                            .presences
+                                            .get(entity)
+                                            .and_then(|presence| presence.kind.character_id());
+                                        // Characters follow one map at a time, reading another
+                                        // one shows the way to the cache they're already
+                                        // looking for without using it up
+                                        let read = pos.zip(character_id).and_then(
+                                            |(pos, character_id)| {
+                                                if let Some(cache) =
+                                                    data.treasure_caches.get(character_id)
+                                                {
+                                                    return Some((pos, cache, false));
+                                                }
+                                                let cache = data.world.find_treasure_spot(
+                                                    index,
+                                                    pos.xy().as_(),
+                                                    &mut rng,
+                                                )?;
+                                                data.treasure_caches.bury(character_id, cache);
+                                                Some((pos, cache, true))
+                                            },
+                                        );
+                                        if let Some((pos, cache, _)) = read
+                                            && let Some(client) = data.clients.get(entity)
+                                        {
+                                            let offset = cache.as_::<f32>() - pos.xy();
+                                            client.send_fallible(ServerGeneral::TreasureMap(
+                                                data.world.treasure_map_snippet(index, cache),
+                                            ));
+                                            client.send_fallible(ServerGeneral::server_msg(
+                                                ChatType::Meta,
+                                                Content::localized_with_args(
+                                                    "hud-treasure_map-read",
+                                                    [
+                                                        (
+                                                            "distance",
+                                                            LocalizationArg::from(
+                                                                offset.magnitude().round() as u64,
+                                                            ),
+                                                        ),
+                                                        (
+                                                            "direction",
+                                                            LocalizationArg::from(
+                                                                Direction::from_dir(offset)
+                                                                    .localize_npc(),
+                                                            ),
+                                                        ),
+                                                    ],
+                                                ),
+                                            ));
+                                        }
+                                        if let Some((_, _, true)) = read {
+                                            Some(InventoryUpdateEvent::Used)
+                                        } else {
+                                            inventory.insert_or_stack_at(slot, item).expect(
+                                                "slot was just vacated of item, so it definitely \
+                                                 fits there.",
+                                            );
+                                            None
+                                        }
+                                    },
                                     _ => {
                                         inventory.insert_or_stack_at(slot, item).expect(
                                             "slot was just vacated of item, so it definitely fits \
@@ -1095,6 +1283,88 @@ impl ServerEvent for InventoryManipEvent {
                 comp::InventoryManip::SwapEquippedWeapons => {
                     inventory.swap_equipped_weapons(*data.time);
                 },
+                comp::InventoryManip::BankDeposit { banker, slot } => {
+                    if can_use_npc_service(
+                        entity,
+                        banker,
+                        comp::BehaviorCapability::BANK,
+                        data.trades.in_mutable_trade(uid),
+                        &data.id_maps,
+                        &data.agents,
+                        &data.positions,
+                    ) && inventory.bank_deposit(slot)
+                    {
+                        let _ = data.inventory_updates.insert(
+                            entity,
+                            comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                        );
+                    }
+                },
+                comp::InventoryManip::BankWithdraw { banker, slot } => {
+                    if can_use_npc_service(
+                        entity,
+                        banker,
+                        comp::BehaviorCapability::BANK,
+                        data.trades.in_mutable_trade(uid),
+                        &data.id_maps,
+                        &data.agents,
+                        &data.positions,
+                    ) && inventory.bank_withdraw(slot)
+                    {
+                        let _ = data.inventory_updates.insert(
+                            entity,
+                            comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                        );
+                    }
+                },
+                comp::InventoryManip::BankUpgrade { banker } => {
+                    if can_use_npc_service(
+                        entity,
+                        banker,
+                        comp::BehaviorCapability::BANK,
+                        data.trades.in_mutable_trade(uid),
+                        &data.id_maps,
+                        &data.agents,
+                        &data.positions,
+                    ) && !inventory.bank_upgrade(&data.ability_map, &data.msm)
+                    {
+                        debug!("Couldn't upgrade bank, not enough coins or fully upgraded");
+                    }
+                },
+                comp::InventoryManip::LearnSkill { trainer, skill } => {
+                    if can_use_npc_service(
+                        entity,
+                        trainer,
+                        comp::BehaviorCapability::TRAIN,
+                        data.trades.in_mutable_trade(uid),
+                        &data.id_maps,
+                        &data.agents,
+                        &data.positions,
+                    ) && let Some(mut skill_set) = data.skill_sets.get_mut(entity)
+                    {
+                        // Learn the skill on a copy first, so that coins are only taken once we
+                        // know the skill can be taught
+                        let mut learned = skill_set.clone();
+                        if let Some(price) = skill_set.trainer_price(skill)
+                            && learned.learn_skill(skill).is_ok()
+                            && inventory.pay_coins(price, &data.ability_map, &data.msm)
+                        {
+                            *skill_set = learned;
+                        } else {
+                            debug!(?skill, "Couldn't learn skill from trainer");
+                        }
+                    }
+                },
+                comp::InventoryManip::SetHeraldry { slot, heraldry } => {
+                    if inventory.set_heraldry(slot, heraldry) {
+                        let _ = data.inventory_updates.insert(
+                            entity,
+                            comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                        );
+                    } else {
+                        debug!(?slot, "Item can't bear a heraldic design");
+                    }
+                },
             }
             if data.trades.in_mutable_trade(uid) {
                 // manipulating the inventory mutated the trade, so reset the accept flags
@@ -1184,6 +1454,34 @@ fn within_pickup_range<S: FindDist<find_dist::Cylinder>>(
         .unwrap_or(false)
 }
 
+/// Services of NPCs like banks and trainers are only available next to an NPC
+/// with the matching capability, and never during a trade so that items can't
+/// be moved out of (or duplicated into) a pending offer.
+fn can_use_npc_service(
+    entity: EcsEntity,
+    npc: Uid,
+    capability: comp::BehaviorCapability,
+    in_trade: bool,
+    id_maps: &IdMaps,
+    agents: &ReadStorage<comp::Agent>,
+    positions: &ReadStorage<comp::Pos>,
+) -> bool {
+    if in_trade {
+        return false;
+    }
+    id_maps.uid_entity(npc).map_or(false, |npc| {
+        agents
+            .get(npc)
+            .map_or(false, |agent| agent.behavior.can(capability))
+            && positions
+                .get(entity)
+                .zip(positions.get(npc))
+                .map_or(false, |(pos, npc_pos)| {
+                    pos.0.distance_squared(npc_pos.0) <= MAX_NPCINTERACT_RANGE.powi(2)
+                })
+    })
+}
+
 fn announce_loot_to_group(
     group_id: &Group,
     entity: EcsEntity,
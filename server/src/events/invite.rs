@@ -1,27 +1,34 @@
 use super::{
     event_dispatch,
     group_manip::{self, update_map_markers},
+    guild::guild_meta,
     ServerEvent,
 };
-use crate::{client::Client, Settings};
+use crate::{
+    client::Client,
+    guild::Guilds,
+    settings::{EditableSettings, SanctionKind},
+    Settings,
+};
+use chrono::Utc;
 use common::{
     comp::{
         self,
         agent::{Agent, AgentEvent},
         group::GroupManager,
         invite::{Invite, InviteKind, InviteResponse, PendingInvites},
-        ChatType, Group, Health, Pos,
+        ChatType, Group, Health, LocalizationArg, Player, Pos,
     },
     consts::MAX_TRADE_RANGE,
-    event::{InitiateInviteEvent, InviteResponseEvent},
+    event::{ChatEvent, EventBus, InitiateInviteEvent, InviteResponseEvent},
+    guild::GuildError,
     trade::{TradeResult, Trades},
     uid::{IdMaps, Uid},
 };
-use common_net::msg::{InviteAnswer, ServerGeneral};
-#[cfg(feature = "worldgen")]
-use specs::ReadExpect;
+use common_net::msg::{InviteAnswer, Notification, ServerGeneral};
 use specs::{
-    shred, DispatcherBuilder, Entities, Entity, Read, ReadStorage, SystemData, Write, WriteStorage,
+    shred, DispatcherBuilder, Entities, Entity, Read, ReadExpect, ReadStorage, SystemData, Write,
+    WriteExpect, WriteStorage,
 };
 use std::time::{Duration, Instant};
 use tracing::{error, warn};
@@ -42,8 +49,10 @@ impl ServerEvent for InitiateInviteEvent {
     type SystemData<'a> = (
         Write<'a, Trades>,
         Read<'a, Settings>,
+        ReadExpect<'a, EditableSettings>,
         Read<'a, IdMaps>,
         Read<'a, GroupManager>,
+        ReadExpect<'a, Guilds>,
         WriteStorage<'a, PendingInvites>,
         WriteStorage<'a, Agent>,
         WriteStorage<'a, Invite>,
@@ -52,6 +61,7 @@ impl ServerEvent for InitiateInviteEvent {
         ReadStorage<'a, Pos>,
         ReadStorage<'a, Group>,
         ReadStorage<'a, Health>,
+        ReadStorage<'a, Player>,
     );
 
     fn handle(
@@ -59,8 +69,10 @@ impl ServerEvent for InitiateInviteEvent {
         (
             mut trades,
             settings,
+            editable_settings,
             id_maps,
             group_manager,
+            guilds,
             mut pending_invites,
             mut agents,
             mut invites,
@@ -69,8 +81,17 @@ impl ServerEvent for InitiateInviteEvent {
             positions,
             groups,
             healths,
+            players,
         ): Self::SystemData<'_>,
     ) {
+        let now = Utc::now();
+        let on_probation = |entity| {
+            players.get(entity).map_or(false, |player: &Player| {
+                editable_settings
+                    .sanctions
+                    .is_active(&player.uuid(), SanctionKind::Probation, now)
+            })
+        };
         for InitiateInviteEvent(inviter, invitee_uid, kind) in events {
             let max_group_size = settings.max_player_group_size;
             let invitee = match id_maps.uid_entity(invitee_uid) {
@@ -104,41 +125,76 @@ impl ServerEvent for InitiateInviteEvent {
                 {
                     continue;
                 }
-            }
 
-            if let InviteKind::Group = kind {
-                if !group_manip::can_invite(
-                    &clients,
-                    &groups,
-                    &group_manager,
-                    &mut pending_invites,
-                    max_group_size,
-                    inviter,
-                    invitee,
-                ) {
+                // Players on probation are not allowed to trade
+                if on_probation(inviter) || on_probation(invitee) {
+                    if let Some(client) = clients.get(inviter) {
+                        client.send_fallible(ServerGeneral::server_msg(
+                            ChatType::Meta,
+                            "Trading is not possible while either player is on probation.",
+                        ));
+                    }
                     continue;
                 }
-            } else {
-                // cancel current trades for inviter before inviting someone else to trade
-                if let Some(inviter_uid) = uids.get(inviter).copied() {
-                    if let Some(active_trade) = trades.entity_trades.get(&inviter_uid).copied() {
-                        trades
-                            .decline_trade(active_trade, inviter_uid)
-                            .and_then(|u| id_maps.uid_entity(u))
-                            .map(|e| {
-                                if let Some(client) = clients.get(e) {
-                                    client.send_fallible(ServerGeneral::FinishedTrade(
-                                        TradeResult::Declined,
-                                    ));
-                                }
-                                if let Some(agent) = agents.get_mut(e) {
-                                    agent.inbox.push_back(AgentEvent::FinishedTrade(
-                                        TradeResult::Declined,
-                                    ));
-                                }
-                            });
+            }
+
+            match kind {
+                InviteKind::Group => {
+                    if !group_manip::can_invite(
+                        &clients,
+                        &groups,
+                        &group_manager,
+                        &mut pending_invites,
+                        max_group_size,
+                        inviter,
+                        invitee,
+                    ) {
+                        continue;
                     }
-                };
+                },
+                InviteKind::Guild => {
+                    // Only players can join guilds
+                    let Some(invitee_player) = players.get(invitee) else {
+                        continue;
+                    };
+                    if let Err(error) =
+                        players
+                            .get(inviter)
+                            .map_or(Err(GuildError::NotInGuild), |inviter_player| {
+                                guilds.can_invite(inviter_player.uuid(), invitee_player.uuid())
+                            })
+                    {
+                        if let Some(client) = clients.get(inviter) {
+                            client.send_fallible(ServerGeneral::Notification(
+                                Notification::GuildFailed(error),
+                            ));
+                        }
+                        continue;
+                    }
+                },
+                InviteKind::Trade => {
+                    // cancel current trades for inviter before inviting someone else to trade
+                    if let Some(inviter_uid) = uids.get(inviter).copied() {
+                        if let Some(active_trade) = trades.entity_trades.get(&inviter_uid).copied()
+                        {
+                            trades
+                                .decline_trade(active_trade, inviter_uid)
+                                .and_then(|u| id_maps.uid_entity(u))
+                                .map(|e| {
+                                    if let Some(client) = clients.get(e) {
+                                        client.send_fallible(ServerGeneral::FinishedTrade(
+                                            TradeResult::Declined,
+                                        ));
+                                    }
+                                    if let Some(agent) = agents.get_mut(e) {
+                                        agent.inbox.push_back(AgentEvent::FinishedTrade(
+                                            TradeResult::Declined,
+                                        ));
+                                    }
+                                });
+                        }
+                    };
+                },
             }
 
             if invites.contains(invitee) {
@@ -225,6 +281,8 @@ pub struct InviteResponseData<'a> {
     entities: Entities<'a>,
     group_manager: Write<'a, GroupManager>,
     trades: Write<'a, Trades>,
+    guilds: WriteExpect<'a, Guilds>,
+    chat_events: Read<'a, EventBus<ChatEvent>>,
     #[cfg(feature = "worldgen")]
     index: ReadExpect<'a, IndexOwned>,
     id_maps: Read<'a, IdMaps>,
@@ -234,6 +292,7 @@ pub struct InviteResponseData<'a> {
     agents: WriteStorage<'a, comp::Agent>,
     uids: ReadStorage<'a, Uid>,
     clients: ReadStorage<'a, Client>,
+    players: ReadStorage<'a, Player>,
     alignments: ReadStorage<'a, comp::Alignment>,
     map_markers: ReadStorage<'a, comp::MapMarker>,
 }
@@ -345,6 +404,42 @@ pub fn handle_invite_accept(data: &mut InviteResponseData, entity: Entity) {
                         .map(|c| c.send(ServerGeneral::UpdatePendingTrade(id, trade, pricing)));
                 }
             },
+            InviteKind::Guild => {
+                let (Some(inviter_player), Some(player)) =
+                    (data.players.get(inviter), data.players.get(entity))
+                else {
+                    return;
+                };
+                // The inviter might have been kicked or their guild filled up since
+                match data
+                    .guilds
+                    .join(inviter_player.uuid(), player.uuid(), player.alias.clone())
+                {
+                    Ok(guild_id) => {
+                        data.guilds.notify(
+                            Some(guild_id),
+                            &[player.uuid()],
+                            &data.players,
+                            &data.uids,
+                            &data.clients,
+                        );
+                        if let Some(tag) = data.guilds.tag_of(player.uuid()) {
+                            data.chat_events.emit_now(guild_meta(
+                                tag,
+                                "hud-guild-member_joined",
+                                [("name", LocalizationArg::from(player.alias.clone()))],
+                            ));
+                        }
+                    },
+                    Err(error) => {
+                        if let Some(client) = data.clients.get(entity) {
+                            client.send_fallible(ServerGeneral::Notification(
+                                Notification::GuildFailed(error),
+                            ));
+                        }
+                    },
+                }
+            },
         }
     }
 }
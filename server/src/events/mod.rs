@@ -13,8 +13,9 @@ use specs::{
 
 use self::{
     entity_creation::{
-        handle_create_aura_entity, handle_create_item_drop, handle_create_npc,
-        handle_create_object, handle_create_ship, handle_create_special_entity,
+        handle_create_aura_entity, handle_create_corpse, handle_create_gravestone,
+        handle_create_item_drop, handle_create_npc, handle_create_object,
+        handle_create_race_checkpoint, handle_create_ship, handle_create_special_entity,
         handle_initialize_character, handle_initialize_spectator, handle_loaded_character_data,
         handle_shockwave, handle_shoot,
     },
@@ -23,19 +24,23 @@ use self::{
     mounting::{handle_mount, handle_mount_volume, handle_unmount},
     player::{
         handle_character_delete, handle_client_disconnect, handle_exit_ingame, handle_possess,
+        handle_resume_session,
     },
     trade::handle_process_trade_action,
 };
 
+mod bounty;
 mod entity_creation;
 mod entity_manipulation;
 mod group_manip;
+mod guild;
 mod information;
 mod interaction;
 mod inventory_manip;
 mod invite;
 mod mounting;
 mod player;
+mod title;
 mod trade;
 
 /// Shared utilities used by other code **in this crate**
@@ -101,7 +106,10 @@ pub fn register_event_systems(builder: &mut DispatcherBuilder) {
     interaction::register_event_systems(builder);
     invite::register_event_systems(builder);
     group_manip::register_event_systems(builder);
+    guild::register_event_systems(builder);
     information::register_event_systems(builder);
+    bounty::register_event_systems(builder);
+    title::register_event_systems(builder);
 }
 
 pub enum Event {
@@ -150,6 +158,9 @@ impl Server {
         self.handle_serial_events(handle_create_special_entity);
         self.handle_serial_events(handle_create_item_drop);
         self.handle_serial_events(handle_create_object);
+        self.handle_serial_events(handle_create_gravestone);
+        self.handle_serial_events(handle_create_corpse);
+        self.handle_serial_events(handle_create_race_checkpoint);
         self.handle_serial_events(handle_create_aura_entity);
         self.handle_serial_events(handle_delete);
 
@@ -171,6 +182,8 @@ impl Server {
                 true,
             ));
         });
+        // After disconnects, so that sessions which expired this tick aren't resumed
+        self.handle_serial_events(handle_resume_session);
         self.handle_serial_events(handle_possess);
         self.handle_serial_events(handle_transform);
         self.handle_serial_events(|this, ev: CommandEvent| {
@@ -1,5 +1,3 @@
-#[cfg(feature = "worldgen")] use std::sync::Arc;
-
 use common::{
     comp::{self, pet::is_mountable},
     consts::{MAX_MOUNT_RANGE, MAX_SPRITE_MOUNT_RANGE},
@@ -131,15 +129,7 @@ pub fn handle_mount_volume(
                     state
                         .ecs()
                         .write_resource::<RtSim>()
-                        .hook_character_mount_volume(
-                            &state.ecs().read_resource::<Arc<world::World>>(),
-                            state
-                                .ecs()
-                                .read_resource::<world::IndexOwned>()
-                                .as_index_ref(),
-                            volume_pos,
-                            rider_actor,
-                        );
+                        .hook_character_mount_volume(volume_pos, rider_actor);
                 }
             }
         }
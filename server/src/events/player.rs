@@ -1,20 +1,26 @@
 use super::Event;
 use crate::{
-    client::Client, metrics::PlayerMetrics, persistence::character_updater::CharacterUpdater,
-    state_ext::StateExt, BattleModeBuffer, Server,
+    account_link::AccountLinks, client::Client, guild::Guilds, metrics::PlayerMetrics,
+    persistence::character_updater::CharacterUpdater, presence::ResumableSession,
+    state_ext::StateExt, sys, BattleModeBuffer, Server, Settings,
 };
 use common::{
     comp,
-    comp::{group, pet::is_tameable, Presence, PresenceKind},
-    event::{DeleteCharacterEvent, PossessEvent},
-    resources::Time,
+    comp::{
+        buff::{Buff, BuffChange, BuffData, BuffKind, BuffSource, DestInfo},
+        group,
+        pet::is_tameable,
+        Presence, PresenceKind,
+    },
+    event::{BuffEvent, DeleteCharacterEvent, PossessEvent, ResumeSessionEvent},
+    resources::{ProgramTime, Secs, Time},
     uid::{IdMaps, Uid},
 };
 use common_base::span;
-use common_net::msg::{PlayerListUpdate, ServerGeneral};
+use common_net::msg::{DisconnectReason, PlayerListUpdate, ServerGeneral};
 use common_state::State;
 use specs::{Builder, Entity as EcsEntity, Join, WorldExt};
-use tracing::{debug, error, trace, warn, Instrument};
+use tracing::{debug, error, info, trace, warn, Instrument};
 
 pub fn handle_character_delete(server: &mut Server, ev: DeleteCharacterEvent) {
     // Can't process a character delete for a player that has an in-game presence,
@@ -215,6 +221,14 @@ pub fn handle_client_disconnect(
         }
     }
 
+    if matches!(
+        reason,
+        comp::DisconnectReason::Timeout | comp::DisconnectReason::NetworkError
+    ) && suspend_session(server, entity)
+    {
+        return Event::ClientDisconnected { entity };
+    }
+
     let state = server.state_mut();
 
     // Tell other clients to remove from player list
@@ -243,6 +257,122 @@ pub fn handle_client_disconnect(
     Event::ClientDisconnected { entity }
 }
 
+/// Keeps the character of a client that lost its connection in the world for
+/// the configured grace period, so that the client can resume its session.
+///
+/// Returns whether the session was suspended.
+fn suspend_session(server: &mut Server, entity: EcsEntity) -> bool {
+    let grace = server.settings().session_resume_grace;
+    let state = server.state_mut();
+    let in_game = state.read_storage::<Client>().contains(entity)
+        && state
+            .read_storage::<Presence>()
+            .get(entity)
+            .is_some_and(|presence| matches!(presence.kind, PresenceKind::Character(_)));
+    if grace.is_zero() || !in_game {
+        return false;
+    }
+
+    let now = state.ecs().read_resource::<ProgramTime>().0;
+    match state
+        .ecs()
+        .write_storage::<ResumableSession>()
+        .get_mut(entity)
+    {
+        Some(session) => session.expires = Some(now + grace.as_secs_f64()),
+        None => return false,
+    }
+
+    state.delete_component::<Client>(entity);
+    // Stop whatever the character was doing when the connection dropped
+    state.write_component_ignore_entity_dead(entity, comp::Controller::default());
+    // Nobody is controlling the character, so keep it from getting killed
+    let time = *state.ecs().read_resource::<Time>();
+    let stats = state.ecs().read_storage::<comp::Stats>();
+    let masses = state.ecs().read_storage::<comp::Mass>();
+    state.emit_event_now(BuffEvent {
+        entity,
+        buff_change: BuffChange::Add(Buff::new(
+            BuffKind::Invulnerability,
+            BuffData::new(1.0, Some(Secs(grace.as_secs_f64()))),
+            Vec::new(),
+            BuffSource::World,
+            time,
+            DestInfo {
+                stats: stats.get(entity),
+                mass: masses.get(entity),
+            },
+            None,
+        )),
+    });
+
+    info!(
+        ?entity,
+        ?grace,
+        "Client lost connection, suspending its session"
+    );
+    true
+}
+
+/// Moves the client of a freshly registered entity to the entity of the
+/// suspended session it resumes.
+pub fn handle_resume_session(server: &mut Server, ev: ResumeSessionEvent) {
+    // The new connection might have been lost again already
+    if !server.state.ecs().is_alive(ev.entity) {
+        return;
+    }
+
+    let state = server.state_mut();
+    let target_suspended = state.ecs().is_alive(ev.target)
+        && !state.read_storage::<Client>().contains(ev.target)
+        && state
+            .read_storage::<ResumableSession>()
+            .get(ev.target)
+            .is_some_and(|session| session.expires.is_some());
+    if !target_suspended {
+        // The session expired in the meantime
+        server.notify_client(
+            ev.entity,
+            ServerGeneral::Disconnect(DisconnectReason::Kicked(String::from(
+                "Your previous session has expired, please log in again.",
+            ))),
+        );
+        handle_client_disconnect(server, ev.entity, comp::DisconnectReason::Kicked, true);
+        return;
+    }
+
+    let Some(client) = state.delete_component::<Client>(ev.entity) else {
+        return;
+    };
+    if let Err(e) = state.ecs().write_storage().insert(ev.target, client) {
+        error!(
+            ?e,
+            "Failed to move client to the entity of its previous session"
+        );
+        return;
+    }
+    if let Some(session) = state
+        .ecs()
+        .write_storage::<ResumableSession>()
+        .get_mut(ev.target)
+    {
+        session.expires = None;
+    }
+    state.emit_event_now(BuffEvent {
+        entity: ev.target,
+        buff_change: BuffChange::RemoveByKind(BuffKind::Invulnerability),
+    });
+
+    // The entity the client registered with is no longer needed
+    if let Err(e) = state.delete_entity_recorded(ev.entity) {
+        error!(?e, entity = ?ev.entity, "Failed to delete entity of resumed client");
+    }
+
+    // The client starts out without knowing about anything around it
+    sys::subscription::initialize_region_subscription(state.ecs(), ev.target);
+    info!(entity = ?ev.target, "Client resumed its session");
+}
+
 /// When a player logs out, their data is queued for persistence in the next
 /// tick of the persistence batch update. The player will be
 /// temporarily unable to log in during this period to avoid
@@ -291,6 +421,18 @@ pub(super) fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity
                     .read_storage::<comp::MapMarker>()
                     .get(entity)
                     .cloned();
+                let unlocked_shrines = state
+                    .ecs()
+                    .read_storage::<comp::UnlockedShrines>()
+                    .get(entity)
+                    .cloned()
+                    .unwrap_or_default();
+                let titles = state
+                    .ecs()
+                    .read_storage::<comp::Titles>()
+                    .get(entity)
+                    .cloned()
+                    .unwrap_or_default();
                 // Store last battle mode change
                 if let Some(change) = player_info.last_battlemode_change {
                     let mode = player_info.battle_mode;
@@ -326,6 +468,8 @@ pub(super) fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity
                     waypoint,
                     active_abilities.clone(),
                     map_marker,
+                    unlocked_shrines,
+                    titles,
                 ));
             },
             PresenceKind::Spectator => { /* Do nothing, spectators do not need persisting */ },
@@ -422,6 +566,7 @@ pub fn handle_possess(
                     // from overwriting original character info with stuff from the new character.
                     kind: PresenceKind::Possessor,
                     lossy_terrain_compression: presence.lossy_terrain_compression,
+                    last_input_seq: presence.last_input_seq,
                 })
             } else {
                 None
@@ -520,11 +665,27 @@ pub fn handle_possess(
                     player_alias: player.alias.clone(),
                     is_online: true,
                     is_moderator: admins.contains(possessee),
+                    verified_badge: ecs
+                        .read_resource::<AccountLinks>()
+                        .has_badge(&player.uuid(), &ecs.read_resource::<Settings>()),
+                    faction: ecs
+                        .read_storage::<comp::Faction>()
+                        .get(possessee)
+                        .map(|comp::Faction(faction)| faction.clone()),
+                    guild_tag: ecs
+                        .read_resource::<Guilds>()
+                        .tag_of(player.uuid())
+                        .map(str::to_owned),
+                    guild_banner: ecs.read_resource::<Guilds>().banner_of(player.uuid()),
                     character: ecs.read_storage::<comp::Stats>().get(possessee).map(|s| {
                         msg::CharacterInfo {
                             name: s.name.clone(),
                             // NOTE: hack, read docs on body::Gender for more
                             gender: s.original_body.humanoid_gender(),
+                            title: ecs
+                                .read_storage::<comp::Titles>()
+                                .get(possessee)
+                                .and_then(|titles| titles.selected()),
                         }
                     }),
                     uuid: player.uuid(),
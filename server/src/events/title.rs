@@ -0,0 +1,70 @@
+use crate::client::Client;
+use common::{
+    comp::{self, Content, LocalizationArg, Player},
+    event::{SelectTitleEvent, UnlockTitleEvent},
+    uid::Uid,
+};
+use common_net::msg::{PlayerListUpdate, ServerGeneral};
+use specs::{DispatcherBuilder, Join, ReadStorage, WriteStorage};
+
+use super::{event_dispatch, ServerEvent};
+
+pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
+    event_dispatch::<SelectTitleEvent>(builder);
+    event_dispatch::<UnlockTitleEvent>(builder);
+}
+
+impl ServerEvent for SelectTitleEvent {
+    type SystemData<'a> = (
+        WriteStorage<'a, comp::Titles>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Client>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (mut all_titles, uids, players, clients): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            let Some(mut titles) = all_titles.get_mut(ev.entity) else {
+                continue;
+            };
+            if titles.selected() == ev.title || !titles.select(ev.title) {
+                continue;
+            }
+            // Everyone sees the title on the nameplate and in the social window
+            if let Some(uid) = uids.get(ev.entity) {
+                for (client, _) in (&clients, &players).join() {
+                    client.send_fallible(ServerGeneral::PlayerListUpdate(PlayerListUpdate::Title(
+                        *uid, ev.title,
+                    )));
+                }
+            }
+        }
+    }
+}
+
+impl ServerEvent for UnlockTitleEvent {
+    type SystemData<'a> = (WriteStorage<'a, comp::Titles>, ReadStorage<'a, Client>);
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (mut titles, clients): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            let unlocked = titles
+                .get_mut(ev.entity)
+                .map_or(false, |mut titles| titles.unlock(ev.title));
+            if unlocked && let Some(client) = clients.get(ev.entity) {
+                client.send_fallible(ServerGeneral::server_msg(
+                    comp::ChatType::Meta,
+                    Content::localized_with_args("hud-title-unlocked", [(
+                        "title",
+                        LocalizationArg::from(Content::localized(ev.title.i18n_key())),
+                    )]),
+                ));
+            }
+        }
+    }
+}
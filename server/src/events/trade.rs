@@ -1,4 +1,8 @@
+#[cfg(feature = "worldgen")]
+use crate::rtsim::RtSim;
 use crate::Server;
+#[cfg(feature = "worldgen")]
+use common::rtsim::RtSimEntity;
 use common::{
     comp::{
         agent::{Agent, AgentEvent},
@@ -55,6 +59,23 @@ fn notify_agent_prices(
     }
 }
 
+/// Persists the wares of a trading rtsim NPC after a trade changed them
+#[cfg(feature = "worldgen")]
+fn update_shop_stock(ecs: &specs::World, entity: EcsEntity) {
+    let Some(rtsim_entity) = ecs.read_storage::<RtSimEntity>().get(entity).copied() else {
+        return;
+    };
+    if let Some(inventory) = ecs.read_storage::<Inventory>().get(entity) {
+        let wares = inventory
+            .slots()
+            .flatten()
+            .map(|item| (item.item_definition_id().to_owned(), item.amount()))
+            .collect();
+        ecs.write_resource::<RtSim>()
+            .hook_shop_trade(rtsim_entity, wares);
+    }
+}
+
 /// Invoked when the trade UI is up, handling item changes, accepts, etc
 pub(super) fn handle_process_trade_action(
     server: &mut Server,
@@ -100,6 +121,10 @@ pub(super) fn handle_process_trade_action(
                                 e,
                                 AgentEvent::FinishedTrade(result.clone()),
                             );
+                            #[cfg(feature = "worldgen")]
+                            if let TradeResult::Completed = result {
+                                update_shop_stock(server.state.ecs(), e);
+                            }
                         }
                         trades.entity_trades.remove_entry(party);
                     }
@@ -0,0 +1,780 @@
+use crate::{
+    client::Client,
+    persistence::{
+        error::PersistenceError, establish_connection, guild as db, ConnectionMode,
+        DatabaseSettings,
+    },
+};
+use common::{
+    comp::{
+        inventory::{slot::InvSlotId, InvSlot},
+        item::{tool::AbilityMap, MaterialStatManifest},
+        Inventory, Item, Player,
+    },
+    guild::{
+        valid_guild_name, valid_guild_tag, GuildError, GuildHallInfo, GuildHallSpot, GuildId,
+        GuildInfo, GuildMemberInfo, GuildNotice, GuildRank, GUILD_NOTICE_MAX_LEN,
+        GUILD_STORAGE_SLOTS, MAX_GUILD_MEMBERS, MAX_GUILD_NOTICES,
+    },
+    heraldry::Heraldry,
+    terrain::{Block, BlockKind, TerrainChunk},
+    uid::Uid,
+    uuid::Uuid,
+    vol::{RectVolSize, WriteVol},
+};
+use common_net::msg::{PlayerListUpdate, ServerGeneral};
+use hashbrown::HashMap;
+use rusqlite::Connection;
+use specs::{Join, ReadStorage};
+use std::sync::{Arc, RwLock};
+use tracing::{error, info, warn};
+use vek::*;
+
+pub struct GuildMember {
+    pub uuid: Uuid,
+    /// Alias of the player when they joined
+    pub alias: String,
+    pub rank: GuildRank,
+}
+
+pub struct Guild {
+    pub id: GuildId,
+    pub name: String,
+    pub tag: String,
+    pub members: Vec<GuildMember>,
+    pub hall: Option<GuildHall>,
+}
+
+/// The hall a guild claimed
+#[derive(Clone)]
+pub struct GuildHall {
+    pub spot: GuildHallSpot,
+    pub banner: Heraldry,
+    /// Lowest rank allowed to take items out of the storage
+    pub storage_rank: GuildRank,
+    pub storage: Vec<InvSlot>,
+    /// Newest last
+    pub notices: Vec<GuildNotice>,
+}
+
+impl GuildHall {
+    fn new(spot: GuildHallSpot) -> Self {
+        Self {
+            spot,
+            banner: Heraldry::default(),
+            storage_rank: GuildRank::Officer,
+            storage: vec![None; GUILD_STORAGE_SLOTS],
+            notices: Vec::new(),
+        }
+    }
+
+    /// Slot an item would be put into, stacking it onto an equal item if
+    /// possible
+    fn slot_for(&self, item: &Item) -> Option<usize> {
+        self.storage
+            .iter()
+            .position(|slot| {
+                slot.as_ref().map_or(false, |existing| {
+                    item.is_stackable()
+                        && existing == item
+                        && existing.amount() + item.amount() <= existing.max_amount()
+                })
+            })
+            .or_else(|| self.storage.iter().position(Option::is_none))
+    }
+
+    pub fn info(&self) -> GuildHallInfo {
+        GuildHallInfo {
+            spot: self.spot,
+            banner: self.banner,
+            storage_rank: self.storage_rank,
+            storage: self.storage.clone(),
+            notices: self.notices.clone(),
+        }
+    }
+}
+
+impl Guild {
+    pub fn is_member(&self, uuid: Uuid) -> bool { self.member(uuid).is_some() }
+
+    pub fn member(&self, uuid: Uuid) -> Option<&GuildMember> {
+        self.members.iter().find(|member| member.uuid == uuid)
+    }
+
+    fn member_mut(&mut self, uuid: Uuid) -> Option<&mut GuildMember> {
+        self.members.iter_mut().find(|member| member.uuid == uuid)
+    }
+
+    pub fn info(&self) -> GuildInfo {
+        GuildInfo {
+            id: self.id,
+            name: self.name.clone(),
+            tag: self.tag.clone(),
+            members: self
+                .members
+                .iter()
+                .map(|member| GuildMemberInfo {
+                    uuid: member.uuid,
+                    alias: member.alias.clone(),
+                    rank: member.rank,
+                })
+                .collect(),
+            hall: self.hall.as_ref().map(GuildHall::info),
+        }
+    }
+
+    fn hall(&self) -> Result<&GuildHall, GuildError> {
+        self.hall.as_ref().ok_or(GuildError::NoHall)
+    }
+
+    /// Checks that the player is a member with at least the given rank
+    fn check_rank(
+        &self,
+        uuid: Uuid,
+        allowed: impl Fn(GuildRank) -> bool,
+    ) -> Result<(), GuildError> {
+        match self.member(uuid) {
+            Some(member) if allowed(member.rank) => Ok(()),
+            Some(_) => Err(GuildError::NoPermission),
+            None => Err(GuildError::NotInGuild),
+        }
+    }
+}
+
+/// What happened to the guild of a player that left it
+pub struct GuildLeft {
+    pub guild_id: GuildId,
+    /// The member that took over when the leader left
+    pub new_leader: Option<Uuid>,
+    /// Whether the guild was deleted because nobody was left
+    pub disbanded: bool,
+    /// The hall the guild had claimed, when it was deleted
+    pub abandoned_hall: Option<GuildHallSpot>,
+}
+
+/// Blocks of the banner hanging on a guild hall
+pub fn banner_blocks(
+    spot: &GuildHallSpot,
+    banner: &Heraldry,
+) -> impl Iterator<Item = (Vec3<i32>, Block)> + '_ {
+    let banner = *banner;
+    spot.banner_cells()
+        .map(move |(pos, uv)| (pos, Block::new(BlockKind::Misc, banner.color_at(uv))))
+}
+
+/// Player guilds, kept in memory and written through to the database on every
+/// change. Guild membership belongs to the account rather than a character.
+pub struct Guilds {
+    database_settings: Arc<RwLock<DatabaseSettings>>,
+    guilds: HashMap<GuildId, Guild>,
+    membership: HashMap<Uuid, GuildId>,
+    /// Halls in the world that guilds can claim
+    hall_spots: Vec<GuildHallSpot>,
+}
+
+impl Guilds {
+    pub fn load(
+        database_settings: Arc<RwLock<DatabaseSettings>>,
+        hall_spots: Vec<GuildHallSpot>,
+        ability_map: &AbilityMap,
+        msm: &MaterialStatManifest,
+    ) -> Self {
+        let (mut guilds, mut halls) = {
+            let settings = database_settings
+                .read()
+                .expect("DatabaseSettings RwLock was poisoned");
+            let connection = establish_connection(&settings, ConnectionMode::ReadOnly);
+            let guilds = db::load_guilds(&connection).unwrap_or_else(|err| {
+                error!(?err, "Failed to load guilds");
+                Vec::new()
+            });
+            let halls = db::load_halls(&connection, ability_map, msm).unwrap_or_else(|err| {
+                error!(?err, "Failed to load guild halls");
+                HashMap::new()
+            });
+            (guilds, halls)
+        };
+        info!("Loaded {} guilds", guilds.len());
+
+        for guild in &mut guilds {
+            let Some(stored) = halls.remove(&guild.id) else {
+                continue;
+            };
+            // Halls are found by their notice board, which moves when the world changes
+            match hall_spots.iter().find(|spot| spot.board == stored.board) {
+                Some(spot) => {
+                    guild.hall = Some(GuildHall {
+                        spot: *spot,
+                        banner: stored.banner,
+                        storage_rank: stored.storage_rank,
+                        storage: stored.storage,
+                        notices: stored.notices,
+                    })
+                },
+                None => warn!(
+                    guild = guild.name,
+                    board = ?stored.board,
+                    "The hall of the guild no longer exists in this world"
+                ),
+            }
+        }
+
+        let membership = guilds
+            .iter()
+            .flat_map(|guild| guild.members.iter().map(|member| (member.uuid, guild.id)))
+            .collect();
+        let guilds = guilds.into_iter().map(|guild| (guild.id, guild)).collect();
+
+        Self {
+            database_settings,
+            guilds,
+            membership,
+            hall_spots,
+        }
+    }
+
+    pub fn get(&self, guild_id: GuildId) -> Option<&Guild> { self.guilds.get(&guild_id) }
+
+    pub fn by_tag(&self, tag: &str) -> Option<&Guild> {
+        self.guilds.values().find(|guild| guild.tag == tag)
+    }
+
+    pub fn guild_of(&self, uuid: Uuid) -> Option<&Guild> {
+        self.membership
+            .get(&uuid)
+            .and_then(|guild_id| self.guilds.get(guild_id))
+    }
+
+    pub fn rank_of(&self, uuid: Uuid) -> Option<GuildRank> {
+        self.guild_of(uuid)
+            .and_then(|guild| guild.member(uuid))
+            .map(|member| member.rank)
+    }
+
+    pub fn tag_of(&self, uuid: Uuid) -> Option<&str> {
+        self.guild_of(uuid).map(|guild| guild.tag.as_str())
+    }
+
+    pub fn info_of(&self, uuid: Uuid) -> Option<GuildInfo> { self.guild_of(uuid).map(Guild::info) }
+
+    /// The banner shown on the cape of a player, for guilds with a hall
+    pub fn banner_of(&self, uuid: Uuid) -> Option<Heraldry> {
+        self.guild_of(uuid)?.hall.as_ref().map(|hall| hall.banner)
+    }
+
+    /// Halls that were claimed, along with the banners of their owners
+    pub fn claimed_halls(&self) -> impl Iterator<Item = (&GuildHallSpot, &Heraldry)> {
+        self.guilds
+            .values()
+            .filter_map(|guild| guild.hall.as_ref())
+            .map(|hall| (&hall.spot, &hall.banner))
+    }
+
+    /// Sends the roster to the online members of `guild_id`, and to the
+    /// `changed` players whose guild changed, whose new guild tags are also
+    /// sent to everyone along with their banners
+    pub fn notify(
+        &self,
+        guild_id: Option<GuildId>,
+        changed: &[Uuid],
+        players: &ReadStorage<Player>,
+        uids: &ReadStorage<Uid>,
+        clients: &ReadStorage<Client>,
+    ) {
+        let mut tags = Vec::new();
+        for (player, uid, client) in (players, uids, clients).join() {
+            let uuid = player.uuid();
+            let is_changed = changed.contains(&uuid);
+            if is_changed {
+                tags.push((
+                    *uid,
+                    self.tag_of(uuid).map(str::to_owned),
+                    self.banner_of(uuid),
+                ));
+            }
+            if is_changed || guild_id.is_some() && self.membership.get(&uuid) == guild_id.as_ref() {
+                client.send_fallible(ServerGeneral::GuildUpdate(self.info_of(uuid)));
+            }
+        }
+
+        if !tags.is_empty() {
+            for client in clients.join() {
+                for (uid, tag, banner) in &tags {
+                    client.send_fallible(ServerGeneral::PlayerListUpdate(
+                        PlayerListUpdate::GuildTag(*uid, tag.clone()),
+                    ));
+                    client.send_fallible(ServerGeneral::PlayerListUpdate(
+                        PlayerListUpdate::GuildBanner(*uid, *banner),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Runs a write against the database, the in-memory state should only be
+    /// changed when this succeeds
+    fn write<T>(
+        &self,
+        f: impl FnOnce(&Connection) -> Result<T, PersistenceError>,
+    ) -> Result<T, GuildError> {
+        let settings = self
+            .database_settings
+            .read()
+            .expect("DatabaseSettings RwLock was poisoned");
+        let connection = establish_connection(&settings, ConnectionMode::ReadWrite);
+        f(&connection).map_err(|err| {
+            error!(?err, "Failed to write guild changes to the database");
+            GuildError::Internal
+        })
+    }
+
+    /// Founds a new guild led by the player. Paying for it is up to the caller.
+    pub fn create(
+        &mut self,
+        uuid: Uuid,
+        alias: String,
+        name: &str,
+        tag: &str,
+    ) -> Result<GuildId, GuildError> {
+        if self.membership.contains_key(&uuid) {
+            return Err(GuildError::AlreadyInGuild);
+        }
+        if !valid_guild_name(name) {
+            return Err(GuildError::InvalidName);
+        }
+        if !valid_guild_tag(tag) {
+            return Err(GuildError::InvalidTag);
+        }
+        if self
+            .guilds
+            .values()
+            .any(|guild| guild.name.eq_ignore_ascii_case(name))
+        {
+            return Err(GuildError::NameTaken);
+        }
+        if self
+            .guilds
+            .values()
+            .any(|guild| guild.tag.eq_ignore_ascii_case(tag))
+        {
+            return Err(GuildError::TagTaken);
+        }
+
+        let leader = GuildMember {
+            uuid,
+            alias,
+            rank: GuildRank::Leader,
+        };
+        let guild_id = self.write(|connection| db::create_guild(connection, name, tag, &leader))?;
+        self.guilds.insert(guild_id, Guild {
+            id: guild_id,
+            name: name.to_owned(),
+            tag: tag.to_owned(),
+            members: vec![leader],
+            hall: None,
+        });
+        self.membership.insert(uuid, guild_id);
+
+        Ok(guild_id)
+    }
+
+    /// Checks whether `inviter` may invite `invitee` into their guild
+    pub fn can_invite(&self, inviter: Uuid, invitee: Uuid) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(inviter).ok_or(GuildError::NotInGuild)?;
+        if !guild
+            .member(inviter)
+            .map_or(false, |member| member.rank.can_invite())
+        {
+            return Err(GuildError::NoPermission);
+        }
+        if self.membership.contains_key(&invitee) {
+            return Err(GuildError::TargetInGuild);
+        }
+        if guild.members.len() >= MAX_GUILD_MEMBERS {
+            return Err(GuildError::GuildFull);
+        }
+
+        Ok(guild.id)
+    }
+
+    /// Adds a player that accepted an invite to the guild of the inviter
+    pub fn join(
+        &mut self,
+        inviter: Uuid,
+        uuid: Uuid,
+        alias: String,
+    ) -> Result<GuildId, GuildError> {
+        let guild_id = self.can_invite(inviter, uuid)?;
+        let member = GuildMember {
+            uuid,
+            alias,
+            rank: GuildRank::Member,
+        };
+        self.write(|connection| db::insert_member(connection, guild_id, &member))?;
+        if let Some(guild) = self.guilds.get_mut(&guild_id) {
+            guild.members.push(member);
+        }
+        self.membership.insert(uuid, guild_id);
+
+        Ok(guild_id)
+    }
+
+    /// Removes a player from their guild. When the leader leaves, the highest
+    /// ranking member that joined first takes over, and guilds without members
+    /// are deleted.
+    pub fn leave(&mut self, uuid: Uuid) -> Result<GuildLeft, GuildError> {
+        let guild = self.guild_of(uuid).ok_or(GuildError::NotInGuild)?;
+        let guild_id = guild.id;
+        let was_leader = guild
+            .member(uuid)
+            .map_or(false, |member| member.rank == GuildRank::Leader);
+        let new_leader = was_leader
+            .then(|| {
+                guild
+                    .members
+                    .iter()
+                    .rev()
+                    .filter(|member| member.uuid != uuid)
+                    .max_by_key(|member| member.rank)
+                    .map(|member| member.uuid)
+            })
+            .flatten();
+        let disbanded = guild.members.len() <= 1;
+        let abandoned_hall = guild
+            .hall
+            .as_ref()
+            .filter(|_| disbanded)
+            .map(|hall| hall.spot);
+
+        if disbanded {
+            self.write(|connection| db::delete_guild(connection, guild_id))?;
+            self.guilds.remove(&guild_id);
+            self.membership.remove(&uuid);
+        } else {
+            self.write(|connection| {
+                db::delete_member(connection, uuid)?;
+                if let Some(new_leader) = new_leader {
+                    db::update_rank(connection, new_leader, GuildRank::Leader)?;
+                }
+                Ok(())
+            })?;
+            if let Some(guild) = self.guilds.get_mut(&guild_id) {
+                guild.members.retain(|member| member.uuid != uuid);
+                if let Some(member) = new_leader.and_then(|new_leader| guild.member_mut(new_leader))
+                {
+                    member.rank = GuildRank::Leader;
+                }
+            }
+            self.membership.remove(&uuid);
+        }
+
+        Ok(GuildLeft {
+            guild_id,
+            new_leader,
+            disbanded,
+            abandoned_hall,
+        })
+    }
+
+    pub fn kick(&mut self, by: Uuid, target: Uuid) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        let (Some(by_member), Some(target_member)) = (guild.member(by), guild.member(target))
+        else {
+            return Err(GuildError::NotAMember);
+        };
+        if !by_member.rank.can_kick(target_member.rank) {
+            return Err(GuildError::NoPermission);
+        }
+
+        self.leave(target).map(|left| left.guild_id)
+    }
+
+    /// Changes the rank of a member. Making another member the leader hands
+    /// over the leadership, and the former leader becomes an officer.
+    pub fn set_rank(
+        &mut self,
+        by: Uuid,
+        target: Uuid,
+        rank: GuildRank,
+    ) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        let guild_id = guild.id;
+        if !guild
+            .member(by)
+            .map_or(false, |member| member.rank.can_set_rank())
+        {
+            return Err(GuildError::NoPermission);
+        }
+        if by == target || guild.member(target).is_none() {
+            return Err(GuildError::NotAMember);
+        }
+
+        let hand_over = rank == GuildRank::Leader;
+        self.write(|connection| {
+            db::update_rank(connection, target, rank)?;
+            if hand_over {
+                db::update_rank(connection, by, GuildRank::Officer)?;
+            }
+            Ok(())
+        })?;
+        if let Some(guild) = self.guilds.get_mut(&guild_id) {
+            if let Some(member) = guild.member_mut(target) {
+                member.rank = rank;
+            }
+            if hand_over && let Some(member) = guild.member_mut(by) {
+                member.rank = GuildRank::Officer;
+            }
+        }
+
+        Ok(guild_id)
+    }
+
+    /// Deletes the guild of its leader, returning it so that the former members
+    /// can be notified
+    pub fn disband(&mut self, by: Uuid) -> Result<Guild, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        let guild_id = guild.id;
+        if !guild
+            .member(by)
+            .map_or(false, |member| member.rank.can_disband())
+        {
+            return Err(GuildError::NoPermission);
+        }
+
+        self.write(|connection| db::delete_guild(connection, guild_id))?;
+        let guild = self
+            .guilds
+            .remove(&guild_id)
+            .ok_or(GuildError::NotInGuild)?;
+        for member in &guild.members {
+            self.membership.remove(&member.uuid);
+        }
+
+        Ok(guild)
+    }
+
+    /// Paints the banners of claimed halls into a newly generated chunk, which
+    /// has the banner the hall had before it was claimed
+    pub fn paint_banners(&self, key: Vec2<i32>, chunk: &mut TerrainChunk) {
+        let chunk_min = key * TerrainChunk::RECT_SIZE.as_::<i32>();
+        let chunk_max = chunk_min + TerrainChunk::RECT_SIZE.as_::<i32>();
+        for (spot, banner) in self.claimed_halls() {
+            for (wpos, block) in banner_blocks(spot, banner) {
+                if wpos.xy().map2(chunk_min, |e, min| e >= min).reduce_and()
+                    && wpos.xy().map2(chunk_max, |e, max| e < max).reduce_and()
+                {
+                    let _ = chunk.set(wpos - chunk_min.with_z(0), block);
+                }
+            }
+        }
+    }
+
+    /// Replaces the hall of a guild, once the change was written
+    fn save_hall(&mut self, guild_id: GuildId, hall: GuildHall) -> Result<(), GuildError> {
+        self.write(|connection| db::save_hall(connection, guild_id, &hall))?;
+        if let Some(guild) = self.guilds.get_mut(&guild_id) {
+            guild.hall = Some(hall);
+        }
+
+        Ok(())
+    }
+
+    /// Claims the hall with the notice board at `board` for the guild of its
+    /// leader standing at `pos`. Paying for it is up to the caller.
+    pub fn claim_hall(
+        &mut self,
+        by: Uuid,
+        pos: Vec3<f32>,
+        board: Vec3<i32>,
+    ) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        let guild_id = guild.id;
+        guild.check_rank(by, GuildRank::can_claim_hall)?;
+        if guild.hall.is_some() {
+            return Err(GuildError::AlreadyOwnsHall);
+        }
+        let spot = *self
+            .hall_spots
+            .iter()
+            .find(|spot| spot.board == board)
+            .ok_or(GuildError::NotAHall)?;
+        if !spot.in_range(pos) {
+            return Err(GuildError::TooFarFromHall);
+        }
+        if self
+            .claimed_halls()
+            .any(|(claimed, _)| claimed.board == board)
+        {
+            return Err(GuildError::HallTaken);
+        }
+
+        self.save_hall(guild_id, GuildHall::new(spot))?;
+
+        Ok(guild_id)
+    }
+
+    pub fn set_banner(&mut self, by: Uuid, banner: Heraldry) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        guild.check_rank(by, GuildRank::can_manage_hall)?;
+        let guild_id = guild.id;
+        let hall = GuildHall {
+            banner,
+            ..guild.hall()?.clone()
+        };
+        self.save_hall(guild_id, hall)?;
+
+        Ok(guild_id)
+    }
+
+    pub fn set_storage_rank(&mut self, by: Uuid, rank: GuildRank) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        guild.check_rank(by, GuildRank::can_claim_hall)?;
+        let guild_id = guild.id;
+        let hall = GuildHall {
+            storage_rank: rank,
+            ..guild.hall()?.clone()
+        };
+        self.save_hall(guild_id, hall)?;
+
+        Ok(guild_id)
+    }
+
+    /// Pins a notice to the board of the hall, taking down the oldest ones when
+    /// there are too many
+    pub fn post_notice(
+        &mut self,
+        by: Uuid,
+        alias: String,
+        text: &str,
+    ) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        let guild_id = guild.id;
+        let hall = guild.hall()?;
+        let text = text.trim();
+        if text.is_empty() || text.chars().count() > GUILD_NOTICE_MAX_LEN {
+            return Err(GuildError::InvalidNotice);
+        }
+
+        let overflow = (hall.notices.len() + 1).saturating_sub(MAX_GUILD_NOTICES);
+        let taken_down = hall
+            .notices
+            .iter()
+            .take(overflow)
+            .map(|notice| notice.id)
+            .collect::<Vec<_>>();
+        let id = self.write(|connection| {
+            let id = db::insert_notice(connection, guild_id, by, &alias, text)?;
+            db::delete_notices(connection, &taken_down)?;
+            Ok(id)
+        })?;
+        if let Some(hall) = self
+            .guilds
+            .get_mut(&guild_id)
+            .and_then(|guild| guild.hall.as_mut())
+        {
+            hall.notices.drain(..overflow);
+            hall.notices.push(GuildNotice {
+                id,
+                author: by,
+                author_alias: alias,
+                text: text.to_owned(),
+            });
+        }
+
+        Ok(guild_id)
+    }
+
+    /// Takes down a notice, members can only take down their own ones
+    pub fn remove_notice(&mut self, by: Uuid, notice_id: i64) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        let guild_id = guild.id;
+        let notice = guild
+            .hall()?
+            .notices
+            .iter()
+            .find(|notice| notice.id == notice_id)
+            .ok_or(GuildError::InvalidNotice)?;
+        if notice.author != by {
+            guild.check_rank(by, GuildRank::can_manage_hall)?;
+        }
+
+        self.write(|connection| db::delete_notices(connection, &[notice_id]))?;
+        if let Some(hall) = self
+            .guilds
+            .get_mut(&guild_id)
+            .and_then(|guild| guild.hall.as_mut())
+        {
+            hall.notices.retain(|notice| notice.id != notice_id);
+        }
+
+        Ok(guild_id)
+    }
+
+    /// Moves an item from the inventory of a member standing at `pos` into the
+    /// storage of their hall
+    pub fn storage_deposit(
+        &mut self,
+        by: Uuid,
+        pos: Vec3<f32>,
+        inventory: &mut Inventory,
+        slot: InvSlotId,
+    ) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        let guild_id = guild.id;
+        let hall = guild.hall()?;
+        if !hall.spot.in_range(pos) {
+            return Err(GuildError::TooFarFromHall);
+        }
+        let Some(item) = inventory.get(slot) else {
+            return Ok(guild_id);
+        };
+        let index = hall.slot_for(item).ok_or(GuildError::StorageFull)?;
+
+        let mut hall = hall.clone();
+        match &mut hall.storage[index] {
+            Some(existing) => existing
+                .increase_amount(item.amount())
+                .map_err(|_| GuildError::StorageFull)?,
+            empty => *empty = Some(item.clone()),
+        }
+        self.save_hall(guild_id, hall)?;
+        inventory.remove(slot);
+
+        Ok(guild_id)
+    }
+
+    /// Moves an item from the storage of the hall into the inventory of a
+    /// member standing at `pos`, if their rank allows it
+    pub fn storage_withdraw(
+        &mut self,
+        by: Uuid,
+        pos: Vec3<f32>,
+        inventory: &mut Inventory,
+        index: usize,
+    ) -> Result<GuildId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        let guild_id = guild.id;
+        let hall = guild.hall()?;
+        guild.check_rank(by, |rank| rank >= hall.storage_rank)?;
+        if !hall.spot.in_range(pos) {
+            return Err(GuildError::TooFarFromHall);
+        }
+        if inventory.free_slots() == 0 {
+            return Err(GuildError::InventoryFull);
+        }
+
+        let mut hall = hall.clone();
+        let Some(item) = hall.storage.get_mut(index).and_then(Option::take) else {
+            return Ok(guild_id);
+        };
+        self.save_hall(guild_id, hall)?;
+        if let Err((item, _)) = inventory.push(item) {
+            error!(
+                ?item,
+                "Item taken from guild storage didn't fit into the inventory"
+            );
+        }
+
+        Ok(guild_id)
+    }
+}
@@ -14,7 +14,9 @@
     const_type_name
 )]
 
+pub mod account_link;
 pub mod automod;
+pub mod bounty;
 mod character_creator;
 pub mod chat;
 pub mod chunk_generator;
@@ -25,14 +27,19 @@ pub mod connection_handler;
 mod data_dir;
 pub mod error;
 pub mod events;
+pub mod guild;
 pub mod input;
 pub mod location;
 pub mod lod;
 pub mod login_provider;
+pub mod login_queue;
 pub mod metrics;
+pub mod moderation;
 pub mod persistence;
 mod pet;
+pub mod prefab;
 pub mod presence;
+pub mod race;
 pub mod rtsim;
 pub mod settings;
 pub mod state_ext;
@@ -40,6 +47,7 @@ pub mod sys;
 #[cfg(feature = "persistent_world")]
 pub mod terrain_persistence;
 #[cfg(not(feature = "worldgen"))] mod test_world;
+pub mod treasure;
 
 #[cfg(feature = "worldgen")] mod weather;
 
@@ -51,22 +59,25 @@ pub use crate::{
     error::Error,
     events::Event,
     input::Input,
-    settings::{CalendarMode, EditableSettings, Settings},
+    settings::{
+        AccountNote, Announcement, CalendarMode, EditableSettings, Sanction, SanctionKind, Settings,
+    },
 };
 
 #[cfg(feature = "persistent_world")]
 use crate::terrain_persistence::TerrainPersistence;
 use crate::{
     automod::AutoMod,
-    chunk_generator::ChunkGenerator,
+    chunk_generator::{ChunkGenerator, ChunkSource},
     client::Client,
     cmd::ChatCommandExt,
     connection_handler::ConnectionHandler,
     data_dir::DataDir,
     location::Locations,
     login_provider::LoginProvider,
+    login_queue::LoginQueue,
     persistence::PersistedComponents,
-    presence::{RegionSubscription, RepositionOnChunkLoad},
+    presence::{RegionSubscription, RepositionOnChunkLoad, ResumableSession},
     state_ext::StateExt,
     sys::sentinel::DeletedEntities,
 };
@@ -93,13 +104,17 @@ use common::{
     shared_server_config::ServerConstants,
     slowjob::SlowJobPool,
     terrain::TerrainChunk,
+    uid::Uid,
     util::GIT_DATE_TIMESTAMP,
     vol::RectRasterableVol,
 };
 use common_base::prof_span;
 use common_ecs::run_now;
 use common_net::{
-    msg::{ClientType, DisconnectReason, ServerGeneral, ServerInfo, ServerMsg},
+    msg::{
+        ClientType, DisconnectReason, PlayerListUpdate, ServerGeneral, ServerInfo, ServerMsg,
+        WorldFingerprint,
+    },
     sync::WorldSyncExt,
 };
 use common_state::{AreasContainer, BlockDiff, BuildArea, State};
@@ -276,7 +291,9 @@ impl Server {
         let tick_metrics = TickMetrics::new(&registry).unwrap();
         let physics_metrics = PhysicsMetrics::new(&registry).unwrap();
         let server_event_metrics = metrics::ServerEventMetrics::new(&registry).unwrap();
+        let chat_metrics = metrics::ChatMetrics::new(&registry).unwrap();
         let query_server_metrics = metrics::QueryServerMetrics::new(&registry).unwrap();
+        let persistence_metrics = metrics::PersistenceMetrics::new(&registry).unwrap();
 
         let battlemode_buffer = BattleModeBuffer::default();
 
@@ -285,6 +302,14 @@ impl Server {
         // Load plugins before generating the world.
         #[cfg(feature = "plugins")]
         let plugin_mgr = PluginMgr::from_asset_or_default();
+        #[cfg(feature = "plugins")]
+        for (item, err) in plugin_mgr.validate_items() {
+            error!(
+                ?err,
+                ?item,
+                "Failed to load item definition added by plugin"
+            );
+        }
 
         #[cfg(feature = "worldgen")]
         let (world, index) = World::generate(
@@ -350,6 +375,12 @@ impl Server {
         );
         register_event_busses(state.ecs_mut());
         state.ecs_mut().insert(battlemode_buffer);
+        state
+            .ecs_mut()
+            .insert(sys::announcement::AnnouncementSchedule::default());
+        state
+            .ecs_mut()
+            .insert(sys::event_calendar::EventCalendarState::default());
         state.ecs_mut().insert(settings.clone());
         state.ecs_mut().insert(editable_settings);
         state.ecs_mut().insert(DataDir {
@@ -366,6 +397,7 @@ impl Server {
             settings.auth_server_address.clone(),
             Arc::clone(&runtime),
         ));
+        state.ecs_mut().insert(LoginQueue::default());
         state.ecs_mut().insert(HwStats {
             hardware_threads: num_cpus::get() as u32,
             rayon_threads: num_cpus::get() as u32,
@@ -379,7 +411,28 @@ impl Server {
         state.ecs_mut().insert(tick_metrics);
         state.ecs_mut().insert(physics_metrics);
         state.ecs_mut().insert(server_event_metrics);
+        state.ecs_mut().insert(chat_metrics);
         state.ecs_mut().insert(query_server_metrics);
+        state.ecs_mut().insert(persistence_metrics);
+        state
+            .ecs_mut()
+            .insert(prefab::Prefabs::new(data_dir.to_owned()));
+        state
+            .ecs_mut()
+            .insert(race::RaceTracks::new(data_dir.to_owned()));
+        state
+            .ecs_mut()
+            .insert(treasure::TreasureCaches::new(data_dir.to_owned()));
+        state
+            .ecs_mut()
+            .insert(bounty::Bounties::new(data_dir.to_owned()));
+        state
+            .ecs_mut()
+            .insert(moderation::ModerationTickets::new(data_dir.to_owned()));
+        state
+            .ecs_mut()
+            .insert(account_link::AccountLinks::new(data_dir.to_owned()));
+        state.ecs_mut().insert(sys::minigame::Minigames::default());
         if settings.experimental_terrain_persistence {
             #[cfg(feature = "persistent_world")]
             {
@@ -436,6 +489,16 @@ impl Server {
             Arc::<RwLock<DatabaseSettings>>::clone(&database_settings),
         )?);
 
+        let guilds = guild::Guilds::load(
+            Arc::<RwLock<DatabaseSettings>>::clone(&database_settings),
+            world.guild_halls(index.as_index_ref()),
+            &state.ecs().read_resource::<comp::item::tool::AbilityMap>(),
+            &state
+                .ecs()
+                .read_resource::<comp::inventory::item::MaterialStatManifest>(),
+        );
+        state.ecs_mut().insert(guilds);
+
         // System schedulers to control execution of systems
         state
             .ecs_mut()
@@ -446,11 +509,13 @@ impl Server {
 
         // Server-only components
         state.ecs_mut().register::<RegionSubscription>();
+        state.ecs_mut().register::<ResumableSession>();
         state.ecs_mut().register::<Client>();
         state.ecs_mut().register::<comp::Presence>();
         state.ecs_mut().register::<wiring::WiringElement>();
         state.ecs_mut().register::<wiring::Circuit>();
         state.ecs_mut().register::<Anchor>();
+        state.ecs_mut().register::<comp::Encounter>();
         state.ecs_mut().register::<comp::Pet>();
         state.ecs_mut().register::<login_provider::PendingLogin>();
         state.ecs_mut().register::<RepositionOnChunkLoad>();
@@ -462,9 +527,10 @@ impl Server {
         state.ecs_mut().insert(Arc::clone(&censor));
 
         // Init automod
+        let filtered_words = settings.moderation.load_filtered_words(data_dir);
         state
             .ecs_mut()
-            .insert(AutoMod::new(&settings.moderation, censor));
+            .insert(AutoMod::new(&settings.moderation, censor, filtered_words));
 
         state.ecs_mut().insert(map);
 
@@ -708,6 +774,29 @@ impl Server {
             git_hash: common::util::GIT_HASH.to_string(),
             git_date: common::util::GIT_DATE.to_string(),
             auth_provider: settings.auth_server_address.clone(),
+            world: self.world_fingerprint(&settings),
+        }
+    }
+
+    fn world_fingerprint(&self, settings: &Settings) -> WorldFingerprint {
+        #[cfg(feature = "worldgen")]
+        let map_size_lg = self.world.sim().map_size_lg();
+        #[cfg(not(feature = "worldgen"))]
+        let map_size_lg = self.world.map_size_lg();
+
+        WorldFingerprint {
+            seed_hash: WorldFingerprint::hash_seed(settings.world_seed),
+            worldgen_version: common::terrain::map::WORLDGEN_VERSION,
+            map_size_lg: map_size_lg.vec(),
+            calendar_events: self
+                .state
+                .ecs()
+                .read_resource::<Calendar>()
+                .events()
+                .copied()
+                .collect(),
+            battle_mode: settings.gameplay.battle_mode.default_mode(),
+            battle_mode_choosable: settings.gameplay.battle_mode.allow_choosing(),
         }
     }
 
@@ -825,11 +914,8 @@ impl Server {
                 .iter()
                 .any(|c| c.old.get_rtsim_resource() != c.new.get_rtsim_resource())
             {
-                ecs.write_resource::<rtsim::RtSim>().hook_block_update(
-                    &ecs.read_resource::<Arc<world::World>>(),
-                    ecs.read_resource::<world::IndexOwned>().as_index_ref(),
-                    changes,
-                );
+                ecs.write_resource::<rtsim::RtSim>()
+                    .hook_block_update(changes);
             }
         }
 
@@ -897,6 +983,12 @@ impl Server {
             }
         }
 
+        #[cfg(feature = "worldgen")]
+        self.process_rtsim_hooks();
+
+        #[cfg(feature = "plugins")]
+        self.process_plugin_prefab_placements();
+
         // Prevent anchor entity chains which are not currently supported due to:
         // * potential cycles?
         // * unloading a chain could occur across an unbounded number of ticks with the
@@ -1091,6 +1183,8 @@ impl Server {
                                         pets,
                                         active_abilities,
                                         map_marker,
+                                        unlocked_shrines,
+                                        titles,
                                     } = character_data;
                                     let character_data = (
                                         body,
@@ -1101,6 +1195,8 @@ impl Server {
                                         pets,
                                         active_abilities,
                                         map_marker,
+                                        unlocked_shrines,
+                                        titles,
                                     );
                                     // TODO: Does this need to be a server event? E.g. we could
                                     // just handle it here.
@@ -1143,7 +1239,6 @@ impl Server {
             let index = &mut self.index;
             let world = &mut self.world;
             let ecs = self.state.ecs_mut();
-            let slow_jobs = ecs.write_resource::<SlowJobPool>();
 
             index.reload_if_changed(|index| {
                 let mut chunk_generator = ecs.write_resource::<ChunkGenerator>();
@@ -1165,8 +1260,8 @@ impl Server {
                     terrain.iter().for_each(|(pos, _)| {
                         chunk_generator.generate_chunk(
                             None,
+                            ChunkSource::Regenerate,
                             pos,
-                            &slow_jobs,
                             Arc::clone(world),
                             &rtsim,
                             index.clone(),
@@ -1343,15 +1438,14 @@ impl Server {
 
     pub fn generate_chunk(&mut self, entity: EcsEntity, key: Vec2<i32>) {
         let ecs = self.state.ecs();
-        let slow_jobs = ecs.read_resource::<SlowJobPool>();
         #[cfg(feature = "worldgen")]
         let rtsim = ecs.read_resource::<rtsim::RtSim>();
         #[cfg(not(feature = "worldgen"))]
         let rtsim = ();
         ecs.write_resource::<ChunkGenerator>().generate_chunk(
             Some(entity),
+            ChunkSource::Player,
             key,
-            &slow_jobs,
             Arc::clone(&self.world),
             &rtsim,
             self.index.clone(),
@@ -1362,6 +1456,60 @@ impl Server {
         );
     }
 
+    /// Pass rtsim events on to plugins and apply the actions they take in
+    /// response.
+    #[cfg(feature = "worldgen")]
+    fn process_rtsim_hooks(&mut self) {
+        let events = self
+            .state
+            .ecs()
+            .write_resource::<rtsim::RtSim>()
+            .take_hook_events();
+
+        #[cfg(feature = "plugins")]
+        if !events.is_empty() {
+            let actions = {
+                let mut plugin_manager = self.state.ecs().write_resource::<PluginMgr>();
+                let ecs_world = EcsWorld {
+                    entities: &self.state.ecs().entities(),
+                    health: self.state.ecs().read_component().into(),
+                    uid: self.state.ecs().read_component().into(),
+                    id_maps: &self.state.ecs().read_resource::<IdMaps>().into(),
+                    player: self.state.ecs().read_component().into(),
+                };
+                events
+                    .iter()
+                    .flat_map(|event| plugin_manager.rtsim_event(&ecs_world, event))
+                    .collect::<Vec<_>>()
+            };
+            self.state
+                .ecs()
+                .write_resource::<rtsim::RtSim>()
+                .hook_plugin_actions(actions);
+        }
+        #[cfg(not(feature = "plugins"))]
+        drop(events);
+    }
+
+    /// Place the prefabs plugins asked for during this tick.
+    #[cfg(feature = "plugins")]
+    fn process_plugin_prefab_placements(&mut self) {
+        let ecs = self.state.ecs();
+        let placements = ecs.write_resource::<PluginMgr>().take_prefab_placements();
+        if placements.is_empty() {
+            return;
+        }
+        let mut prefabs = ecs.write_resource::<prefab::Prefabs>();
+        let terrain = ecs.read_resource::<common::terrain::TerrainGrid>();
+        let mut block_change = ecs.write_resource::<common_state::BlockChange>();
+        for placement in placements {
+            let name = placement.prefab.clone();
+            if let Err(err) = prefabs.place(placement, &terrain, &mut block_change) {
+                warn!("Plugin failed to place prefab {name}: {err}");
+            }
+        }
+    }
+
     fn process_command(&mut self, entity: EcsEntity, name: String, args: Vec<String>) {
         // Find the command object and run its handler.
         if let Ok(command) = name.parse::<ServerChatCommand>() {
@@ -1508,6 +1656,191 @@ impl Server {
         };
     }
 
+    /// All moderation tickets, oldest first
+    pub fn moderation_tickets(&self) -> Vec<moderation::Ticket> {
+        self.state
+            .ecs()
+            .read_resource::<moderation::ModerationTickets>()
+            .tickets()
+            .cloned()
+            .collect()
+    }
+
+    /// Marks a moderation ticket as dealt with, and lets the player that filed
+    /// it know if they are online.
+    pub fn resolve_moderation_ticket(&self, id: u64, note: String) -> Result<(), String> {
+        let reporter = self
+            .state
+            .ecs()
+            .write_resource::<moderation::ModerationTickets>()
+            .resolve(id, note)?;
+        if let Some((client, _)) = (
+            &self.state.ecs().read_storage::<Client>(),
+            &self.state.ecs().read_storage::<comp::Player>(),
+        )
+            .join()
+            .find(|(_, player)| player.uuid() == reporter)
+        {
+            client.send_fallible(ServerGeneral::server_msg(
+                comp::ChatType::CommandInfo,
+                comp::Content::localized_with_args("command-report-resolved", [("id", id)]),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Adds or replaces the announcement with the given name, or removes it if
+    /// `announcement` is `None`.
+    pub fn set_announcement(&self, name: String, announcement: Option<Announcement>) {
+        let data_dir = self.data_dir();
+        let edit =
+            self.editable_settings_mut()
+                .announcements
+                .edit(data_dir.as_ref(), |announcements| {
+                    Some(match announcement {
+                        Some(announcement) => {
+                            let info = format!("Announcement {:?} set", name);
+                            announcements.insert(name, announcement);
+                            info
+                        },
+                        None => {
+                            announcements.remove(&name)?;
+                            format!("Announcement {:?} removed", name)
+                        },
+                    })
+                });
+        handle_edit((), edit);
+    }
+
+    /// Applies, replaces or (if `sanction` is `None`) lifts a sanction on the
+    /// account with the given username.
+    ///
+    /// NOTE: Do *not* allow this to be called from any command that doesn't go
+    /// through the CLI!
+    pub fn set_sanction(
+        &self,
+        username: &str,
+        kind: SanctionKind,
+        sanction: Option<Sanction>,
+    ) -> Result<(), String> {
+        let uuid = self.username_to_uuid(username)?;
+        let data_dir = self.data_dir();
+        let edit = self
+            .editable_settings_mut()
+            .sanctions
+            .edit(data_dir.as_ref(), |sanctions| {
+                let info = match &sanction {
+                    Some(_) => format!("Applied {} to {}", kind.as_str(), username),
+                    None => format!("Lifted {} from {}", kind.as_str(), username),
+                };
+                sanctions
+                    .set(uuid, username.into(), kind, sanction)
+                    .then_some(info)
+            });
+        handle_edit((), edit)
+            .ok_or_else(|| format!("{} has no {} to lift", username, kind.as_str()))
+    }
+
+    /// NOTE: Do *not* allow this to be called from any command that doesn't go
+    /// through the CLI!
+    pub fn add_account_note(&self, username: &str, note: AccountNote) -> Result<(), String> {
+        let uuid = self.username_to_uuid(username)?;
+        let data_dir = self.data_dir();
+        let edit = self
+            .editable_settings_mut()
+            .sanctions
+            .edit(data_dir.as_ref(), |sanctions| {
+                sanctions.add_note(uuid, username.into(), note);
+                Some(format!("Added a note to {}", username))
+            });
+        handle_edit((), edit).ok_or_else(|| "Failed to add the note".to_string())
+    }
+
+    pub fn account_links(&self) -> Vec<(String, account_link::LinkedAccount)> {
+        self.state
+            .ecs()
+            .read_resource::<account_link::AccountLinks>()
+            .links()
+            .map(|(uuid, linked)| (uuid.to_string(), linked.clone()))
+            .collect()
+    }
+
+    /// Redeems an account link code on behalf of an external account (such as
+    /// a Discord bot would), returning the username of the linked account.
+    pub fn redeem_account_link(&self, code: &str, external_id: String) -> Result<String, String> {
+        let uuid = self
+            .state
+            .ecs()
+            .write_resource::<account_link::AccountLinks>()
+            .redeem(code, external_id)?;
+        let username = self
+            .state
+            .ecs()
+            .read_resource::<account_link::AccountLinks>()
+            .get(&uuid)
+            .map(|linked| linked.username.clone())
+            .unwrap_or_default();
+        info!("Linked the account of {}", username);
+
+        if let Some((client, _)) = (
+            &self.state.ecs().read_storage::<Client>(),
+            &self.state.ecs().read_storage::<comp::Player>(),
+        )
+            .join()
+            .find(|(_, player)| player.uuid() == uuid)
+        {
+            client.send_fallible(ServerGeneral::server_msg(
+                comp::ChatType::CommandInfo,
+                comp::Content::localized("command-link-account-done"),
+            ));
+        }
+        self.notify_verified_badge(uuid);
+        Ok(username)
+    }
+
+    pub fn unlink_account(&self, username: &str) -> Result<(), String> {
+        let uuid = self.username_to_uuid(username)?;
+        if !self
+            .state
+            .ecs()
+            .write_resource::<account_link::AccountLinks>()
+            .unlink(&uuid)
+        {
+            return Err(format!("{} has no linked account", username));
+        }
+        info!("Unlinked the account of {}", username);
+        self.notify_verified_badge(uuid);
+        Ok(())
+    }
+
+    /// Updates the badge of the player with the given UUID in the player list
+    /// of all clients, if they are online.
+    fn notify_verified_badge(&self, uuid: common::uuid::Uuid) {
+        let ecs = self.state.ecs();
+        let verified = ecs
+            .read_resource::<account_link::AccountLinks>()
+            .has_badge(&uuid, &self.settings());
+        if let Some((uid, _)) = (
+            &ecs.read_storage::<Uid>(),
+            &ecs.read_storage::<comp::Player>(),
+        )
+            .join()
+            .find(|(_, player)| player.uuid() == uuid)
+        {
+            self.state.notify_players(ServerGeneral::PlayerListUpdate(
+                PlayerListUpdate::VerifiedBadge(*uid, verified),
+            ));
+        }
+    }
+
+    fn username_to_uuid(&self, username: &str) -> Result<common::uuid::Uuid, String> {
+        self.state
+            .ecs()
+            .fetch::<LoginProvider>()
+            .username_to_uuid(username)
+            .map_err(|err| format!("Unable to determine UUID for {}: {:?}", username, err))
+    }
+
     /// Useful for testing without a client
     /// view_distance: distance in chunks that are persisted, this acts like the
     /// player view distance so it is actually a bit farther due to a buffer
@@ -1557,6 +1890,104 @@ impl Server {
         info!("Disconnecting all clients due to local console command");
         self.disconnect_all_clients_requested = true;
     }
+
+    /// Resolves the name of a character export file, which are all kept in the
+    /// `character_exports` directory of the server data.
+    fn character_export_path(&self, file: &str) -> Result<std::path::PathBuf, String> {
+        let dir = self.data_dir().path.join("character_exports");
+        if std::path::Path::new(file).file_name() != Some(file.as_ref()) {
+            return Err(format!("Invalid character export file name: {}", file));
+        }
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        Ok(dir.join(file))
+    }
+
+    /// Exports the full persisted state of a character to a JSON file in the
+    /// `character_exports` data directory.
+    ///
+    /// The export is done on a separate thread, `callback` is invoked with
+    /// the outcome once it is finished.
+    ///
+    /// Note that the export reflects the last time the character was
+    /// persisted, which may lag behind for characters that are currently
+    /// logged in.
+    pub fn export_character(
+        &self,
+        character_id: CharacterId,
+        file: &str,
+        callback: impl FnOnce(Result<String, String>) + Send + 'static,
+    ) {
+        let path = match self.character_export_path(file) {
+            Ok(path) => path,
+            Err(e) => return callback(Err(e)),
+        };
+        // Unwrap is safe, see `set_sql_log_mode`
+        let database_settings = self.database_settings.read().unwrap().clone();
+        std::thread::Builder::new()
+            .name("character_export".into())
+            .spawn(move || {
+                let result = persistence::export_character(&database_settings, character_id)
+                    .map_err(|e| e.to_string())
+                    .and_then(|export| {
+                        let file = std::fs::File::create(&path).map_err(|e| e.to_string())?;
+                        serde_json::to_writer_pretty(std::io::BufWriter::new(file), &export)
+                            .map_err(|e| e.to_string())?;
+                        Ok(format!(
+                            "Exported character {} to {}",
+                            character_id.0,
+                            path.display()
+                        ))
+                    });
+                callback(result);
+            })
+            .expect("Failed to spawn character export thread");
+    }
+
+    /// Imports a character from a JSON file in the `character_exports` data
+    /// directory, adding it to the characters of the player with the given
+    /// username. See [`Self::export_character`].
+    pub fn import_character(
+        &self,
+        username: &str,
+        file: &str,
+        callback: impl FnOnce(Result<String, String>) + Send + 'static,
+    ) {
+        let export = self.character_export_path(file).and_then(|path| {
+            let file = std::fs::File::open(&path).map_err(|e| e.to_string())?;
+            serde_json::from_reader::<_, persistence::CharacterExport>(std::io::BufReader::new(
+                file,
+            ))
+            .map_err(|e| e.to_string())
+        });
+        let uuid = self
+            .state
+            .ecs()
+            .fetch::<LoginProvider>()
+            .username_to_uuid(username)
+            .map_err(|e| format!("Could not find uuid for {}: {:?}", username, e));
+
+        match export.and_then(|export| Ok((export, uuid?))) {
+            Ok((export, uuid)) => {
+                let username = username.to_owned();
+                self.state
+                    .ecs()
+                    .write_resource::<CharacterUpdater>()
+                    .import_character(uuid.to_string(), export, move |result| {
+                        callback(
+                            result
+                                .map(|character_id| {
+                                    format!(
+                                        "Imported character {} for {}",
+                                        character_id.0, username
+                                    )
+                                })
+                                .map_err(|e| e.to_string()),
+                        )
+                    });
+            },
+            Err(e) => callback(Err(e)),
+        }
+    }
 }
 
 impl Drop for Server {
@@ -129,9 +129,10 @@ impl LoginProvider {
                     return Some(Err(RegisterError::NotOnWhitelist));
                 }
 
-                // non-admins can only join if the player count has not been exceeded.
+                // Whether admins can join a full server is up to the caller, depending on
+                // the login queue settings.
                 let (player_count_exceeded, res) = player_count_exceeded(username, uuid);
-                if admin.is_none() && player_count_exceeded {
+                if player_count_exceeded {
                     return Some(Err(RegisterError::TooManyPlayers));
                 }
 
@@ -0,0 +1,208 @@
+//! Queue of clients waiting to log in while the server is full.
+//!
+//! Queued clients keep their pending login, which is retried every tick until
+//! a slot is free for them. The queue itself is rebuilt from the logins that
+//! were queued during the tick, so clients that disconnect drop out of it on
+//! their own.
+
+use crate::settings::LoginQueueSettings;
+use specs::Entity;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Number of recent admissions from the queue used to estimate waiting times.
+const ADMISSION_HISTORY: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// The client can log in now.
+    Admit,
+    /// The client has to wait at the given position, starting at 1.
+    Queue(u32),
+    /// Both the server and its queue are full.
+    Reject,
+}
+
+pub struct QueuedLogin {
+    entity: Entity,
+    /// Admins and moderators are queued ahead of other players.
+    priority: bool,
+    joined: Instant,
+    /// Position that was last sent to the client, and when.
+    last_update: Option<(u32, Instant)>,
+}
+
+#[derive(Default)]
+pub struct LoginQueue {
+    /// Ordered by priority first, then by time of arrival.
+    queue: Vec<QueuedLogin>,
+    admissions: VecDeque<Instant>,
+}
+
+impl LoginQueue {
+    /// Decide whether the client of `entity` can log in.
+    ///
+    /// `players_before_tick` is the player count at the start of the tick,
+    /// which the free slots are taken from in queue order, while
+    /// `player_count` also includes players that logged in during this tick.
+    pub fn admission(
+        &self,
+        entity: Entity,
+        priority: bool,
+        players_before_tick: usize,
+        player_count: usize,
+        max_players: usize,
+        settings: &LoginQueueSettings,
+    ) -> Admission {
+        let capacity = if priority {
+            match settings.priority_slots {
+                Some(slots) => max_players + usize::from(slots),
+                None => return Admission::Admit,
+            }
+        } else {
+            max_players
+        };
+
+        let queued = self.queue.iter().any(|q| q.entity == entity);
+        // Priority logins only wait for each other
+        let ahead = self
+            .queue
+            .iter()
+            .take_while(|q| q.entity != entity)
+            .filter(|q| !priority || q.priority)
+            .count();
+
+        if ahead < capacity.saturating_sub(players_before_tick) && player_count < capacity {
+            Admission::Admit
+        } else if !settings.enabled
+            || (!queued && !priority && self.queue.len() >= settings.max_length as usize)
+        {
+            Admission::Reject
+        } else {
+            Admission::Queue(ahead as u32 + 1)
+        }
+    }
+
+    /// Create the entry of `entity` for the queue of the next tick.
+    ///
+    /// Returns whether the client should be sent its position, which is the
+    /// case if it changed or `update_interval` passed since the last update.
+    pub fn requeue(
+        &self,
+        entity: Entity,
+        priority: bool,
+        position: u32,
+        update_interval: Duration,
+    ) -> (QueuedLogin, bool) {
+        let now = Instant::now();
+        let old = self.queue.iter().find(|q| q.entity == entity);
+        let send_update = old
+            .and_then(|q| q.last_update)
+            .map_or(true, |(old_position, time)| {
+                old_position != position || now.duration_since(time) >= update_interval
+            });
+
+        (
+            QueuedLogin {
+                entity,
+                priority,
+                joined: old.map_or(now, |q| q.joined),
+                last_update: if send_update {
+                    Some((position, now))
+                } else {
+                    old.and_then(|q| q.last_update)
+                },
+            },
+            send_update,
+        )
+    }
+
+    /// Estimated time until the client at `position` can log in, based on how
+    /// quickly the queue moved recently.
+    pub fn estimated_wait(&self, position: u32) -> Option<Duration> {
+        let first = self.admissions.front()?;
+        let last = self.admissions.back()?;
+        let admissions = self.admissions.len() as u32 - 1;
+        (admissions > 0).then(|| last.duration_since(*first) / admissions * position)
+    }
+
+    /// Replace the queue by the logins that were queued during this tick.
+    ///
+    /// `admitted` are the clients that logged in during this tick, which is
+    /// used to keep track of how quickly the queue moves.
+    pub fn update(&mut self, mut queued: Vec<QueuedLogin>, admitted: &[Entity]) {
+        let now = Instant::now();
+        let admitted_from_queue = self
+            .queue
+            .iter()
+            .filter(|q| admitted.contains(&q.entity))
+            .count();
+        for _ in 0..admitted_from_queue {
+            if self.admissions.len() >= ADMISSION_HISTORY {
+                self.admissions.pop_front();
+            }
+            self.admissions.push_back(now);
+        }
+
+        queued.sort_by_key(|q| (!q.priority, q.joined));
+        self.queue = queued;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::{Builder, World, WorldExt};
+
+    #[test]
+    fn queue_order() {
+        let mut world = World::new();
+        let [a, b, admin] = [(); 3].map(|_| world.create_entity().build());
+        let settings = LoginQueueSettings {
+            priority_slots: Some(1),
+            ..Default::default()
+        };
+
+        let mut queue = LoginQueue::default();
+        assert_eq!(
+            queue.admission(a, false, 9, 9, 10, &settings),
+            Admission::Admit
+        );
+        assert_eq!(
+            queue.admission(a, false, 10, 10, 10, &settings),
+            Admission::Queue(1)
+        );
+
+        let queued = [(a, false, 1), (b, false, 2), (admin, true, 1)]
+            .map(|(entity, priority, position)| {
+                queue.requeue(entity, priority, position, Duration::MAX).0
+            })
+            .into();
+        queue.update(queued, &[]);
+
+        // The admin is queued ahead, but can use the priority slot
+        assert_eq!(
+            queue.admission(b, false, 10, 10, 10, &settings),
+            Admission::Queue(3)
+        );
+        assert_eq!(
+            queue.admission(admin, true, 10, 10, 10, &settings),
+            Admission::Admit
+        );
+        // Queued admins take up free slots as well
+        assert_eq!(
+            queue.admission(b, false, 9, 9, 10, &settings),
+            Admission::Queue(3)
+        );
+        assert_eq!(
+            queue.admission(a, false, 9, 9, 10, &settings),
+            Admission::Queue(2)
+        );
+        assert_eq!(
+            queue.admission(a, false, 8, 8, 10, &settings),
+            Admission::Admit
+        );
+    }
+}
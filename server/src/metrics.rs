@@ -18,6 +18,9 @@ pub struct EcsSystemMetrics {
     pub system_start_time: IntGaugeVec,
     pub system_length_time: IntGaugeVec,
     pub system_thread_avg: GaugeVec,
+    pub system_wait_time: IntGaugeVec,
+    pub system_slack_time: IntGaugeVec,
+    pub system_critical_path: IntGaugeVec,
     // Counter will only give us granularity on pool speed (2s?) for actual spike detection we
     // need the Historgram
     pub system_length_hist: HistogramVec,
@@ -44,6 +47,11 @@ pub struct ChunkGenMetrics {
     pub chunks_requested: IntCounter,
     pub chunks_served: IntCounter,
     pub chunks_canceled: IntCounter,
+    pub chunks_queued: IntGaugeVec,
+}
+
+pub struct PersistenceMetrics {
+    pub queue_depth: IntGaugeVec,
 }
 
 pub struct JobMetrics {
@@ -70,6 +78,12 @@ pub struct ServerEventMetrics {
     pub event_count: IntCounterVec,
 }
 
+pub struct ChatMetrics {
+    /// Messages rejected or censored by automoderation, by what happened to
+    /// them
+    pub filtered_messages: IntCounterVec,
+}
+
 pub struct QueryServerMetrics {
     pub received_packets: IntCounter,
     pub dropped_packets: IntCounter,
@@ -153,10 +167,35 @@ impl EcsSystemMetrics {
             ),
             &["system"],
         )?;
+        let system_wait_time = IntGaugeVec::new(
+            Opts::new(
+                "system_wait_time",
+                "time in ns an ECS system waited to start after its dependencies finished",
+            ),
+            &["system"],
+        )?;
+        let system_slack_time = IntGaugeVec::new(
+            Opts::new(
+                "system_slack_time",
+                "time in ns an ECS system could have taken longer without delaying the tick",
+            ),
+            &["system"],
+        )?;
+        let system_critical_path = IntGaugeVec::new(
+            Opts::new(
+                "system_critical_path",
+                "1 if the ECS system is on the chain of dependencies that determined the tick \
+                 length",
+            ),
+            &["system"],
+        )?;
 
         registry.register(Box::new(system_start_time.clone()))?;
         registry.register(Box::new(system_length_time.clone()))?;
         registry.register(Box::new(system_thread_avg.clone()))?;
+        registry.register(Box::new(system_wait_time.clone()))?;
+        registry.register(Box::new(system_slack_time.clone()))?;
+        registry.register(Box::new(system_critical_path.clone()))?;
         registry.register(Box::new(system_length_hist.clone()))?;
         registry.register(Box::new(system_length_count.clone()))?;
 
@@ -164,6 +203,9 @@ impl EcsSystemMetrics {
             system_start_time,
             system_length_time,
             system_thread_avg,
+            system_wait_time,
+            system_slack_time,
+            system_critical_path,
             system_length_hist,
             system_length_count,
         })
@@ -267,18 +309,44 @@ impl ChunkGenMetrics {
             "number of all canceled chunks on the server",
         ))?;
 
+        let chunks_queued = IntGaugeVec::new(
+            Opts::new(
+                "chunks_queued",
+                "number of chunks waiting for a chunk generation worker, per request source",
+            ),
+            &["source"],
+        )?;
+
         registry.register(Box::new(chunks_requested.clone()))?;
         registry.register(Box::new(chunks_served.clone()))?;
         registry.register(Box::new(chunks_canceled.clone()))?;
+        registry.register(Box::new(chunks_queued.clone()))?;
 
         Ok(Self {
             chunks_requested,
             chunks_served,
             chunks_canceled,
+            chunks_queued,
         })
     }
 }
 
+impl PersistenceMetrics {
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let queue_depth = IntGaugeVec::new(
+            Opts::new(
+                "persistence_queue_depth",
+                "number of database requests waiting to be processed, per queue",
+            ),
+            &["queue"],
+        )?;
+
+        registry.register(Box::new(queue_depth.clone()))?;
+
+        Ok(Self { queue_depth })
+    }
+}
+
 impl JobMetrics {
     pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
         let bucket = vec![
@@ -439,6 +507,21 @@ impl ServerEventMetrics {
     }
 }
 
+impl ChatMetrics {
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let filtered_messages = IntCounterVec::new(
+            Opts::new(
+                "chat_filtered_messages",
+                "number of chat messages censored or rejected by automoderation",
+            ),
+            &["action"],
+        )?;
+        registry.register(Box::new(filtered_messages.clone()))?;
+
+        Ok(Self { filtered_messages })
+    }
+}
+
 impl QueryServerMetrics {
     pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
         let received_packets = IntCounter::with_opts(Opts::new(
@@ -0,0 +1,35 @@
+-- Number of bank capacity upgrades bought by each character
+ALTER TABLE character ADD COLUMN bank_upgrades INT NOT NULL DEFAULT 0;
+
+CREATE TEMP TABLE _temp_character_bank_pairings
+(
+    temp_bank_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    character_id INT NOT NULL,
+    bank_id INT
+);
+
+INSERT
+INTO _temp_character_bank_pairings
+SELECT	NULL,
+        i.item_id,
+        NULL
+FROM item i
+WHERE i.item_definition_id = 'veloren.core.pseudo_containers.character';
+
+UPDATE _temp_character_bank_pairings
+SET bank_id = ((SELECT MAX(entity_id) FROM entity) + temp_bank_id);
+
+INSERT
+INTO entity
+SELECT t.bank_id
+FROM _temp_character_bank_pairings t;
+
+INSERT
+INTO item
+SELECT	t.bank_id,
+        t.character_id,
+        'veloren.core.pseudo_containers.bank',
+        1,
+        'bank',
+        ''
+FROM _temp_character_bank_pairings t;
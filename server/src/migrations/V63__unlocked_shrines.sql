@@ -0,0 +1,3 @@
+-- Waypoint shrines unlocked by each character for fast travel, stored as a
+-- JSON list of site ids and travel positions
+ALTER TABLE character ADD COLUMN unlocked_shrines TEXT NOT NULL DEFAULT '[]';
@@ -0,0 +1,19 @@
+-- Player guilds, membership is tied to the account rather than a character
+CREATE TABLE guild
+(
+    guild_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    tag TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE guild_member
+(
+    player_uuid TEXT PRIMARY KEY NOT NULL,
+    guild_id INT NOT NULL,
+    rank TEXT NOT NULL,
+    alias TEXT NOT NULL,
+    FOREIGN KEY(guild_id) REFERENCES guild(guild_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_guild_member_guild_id ON guild_member(guild_id);
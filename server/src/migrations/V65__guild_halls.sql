@@ -0,0 +1,26 @@
+-- Halls claimed by guilds, identified by the position of their notice board.
+-- The banner and the items in the storage are stored as JSON.
+CREATE TABLE guild_hall
+(
+    guild_id INTEGER PRIMARY KEY NOT NULL,
+    board_x INT NOT NULL,
+    board_y INT NOT NULL,
+    board_z INT NOT NULL,
+    banner TEXT NOT NULL,
+    storage_rank TEXT NOT NULL,
+    storage TEXT NOT NULL,
+    FOREIGN KEY(guild_id) REFERENCES guild(guild_id) ON DELETE CASCADE
+);
+
+CREATE TABLE guild_notice
+(
+    notice_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    guild_id INT NOT NULL,
+    author_uuid TEXT NOT NULL,
+    author_alias TEXT NOT NULL,
+    text TEXT NOT NULL,
+    posted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY(guild_id) REFERENCES guild(guild_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_guild_notice_guild_id ON guild_notice(guild_id);
@@ -0,0 +1,3 @@
+-- Titles earned by each character and the one they show under their name,
+-- stored as JSON with the keys of the titles
+ALTER TABLE character ADD COLUMN titles TEXT NOT NULL DEFAULT '{"unlocked":[],"selected":null}';
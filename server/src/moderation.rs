@@ -0,0 +1,177 @@
+use crate::chat::ChatMessage;
+use atomicwrites::{AtomicFile, OverwriteBehavior};
+use chrono::{DateTime, Utc};
+use common::uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io::Write as _, path::PathBuf, str::FromStr};
+use tracing::{error, info};
+
+const TICKETS_FILE: &str = "moderation_tickets.ron";
+/// Players can't have more reports than this waiting for a moderator, so that
+/// the report command can't be used to flood the ticket list
+pub const MAX_OPEN_REPORTS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportCategory {
+    Harassment,
+    Cheating,
+    Spam,
+    Griefing,
+    Name,
+    Other,
+}
+
+impl ReportCategory {
+    pub const ALL: [Self; 6] = [
+        Self::Harassment,
+        Self::Cheating,
+        Self::Spam,
+        Self::Griefing,
+        Self::Name,
+        Self::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Harassment => "harassment",
+            Self::Cheating => "cheating",
+            Self::Spam => "spam",
+            Self::Griefing => "griefing",
+            Self::Name => "name",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl FromStr for ReportCategory {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == s)
+            .ok_or(())
+    }
+}
+
+/// A player involved in a report, as they were when it was made
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReportParty {
+    pub uuid: Uuid,
+    pub alias: String,
+    pub pos: Option<[f32; 3]>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Resolution {
+    pub time: DateTime<Utc>,
+    pub note: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Ticket {
+    pub id: u64,
+    pub time: DateTime<Utc>,
+    pub category: ReportCategory,
+    pub comment: String,
+    pub reporter: ReportParty,
+    pub reported: ReportParty,
+    /// Recent chat messages sent or received by either party
+    pub chat_context: Vec<ChatMessage>,
+    pub resolution: Option<Resolution>,
+}
+
+/// Player reports for moderators to look into, persisted in the data directory
+pub struct ModerationTickets {
+    path: PathBuf,
+    tickets: BTreeMap<u64, Ticket>,
+}
+
+impl ModerationTickets {
+    pub fn new(data_dir: PathBuf) -> Self {
+        let path = data_dir.join(TICKETS_FILE);
+        let tickets = match std::fs::read_to_string(&path) {
+            Ok(tickets) => ron::from_str(&tickets).unwrap_or_else(|err| {
+                error!(?err, "Failed to parse moderation tickets, ignoring them");
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+        info!(
+            "Loaded {} moderation tickets from {:?}",
+            tickets.len(),
+            path
+        );
+
+        Self { path, tickets }
+    }
+
+    pub fn tickets(&self) -> impl Iterator<Item = &Ticket> { self.tickets.values() }
+
+    /// Files a new report, returning the id of its ticket, or `None` if the
+    /// reporter has too many open reports already
+    pub fn open(
+        &mut self,
+        category: ReportCategory,
+        comment: String,
+        reporter: ReportParty,
+        reported: ReportParty,
+        chat_context: Vec<ChatMessage>,
+    ) -> Option<u64> {
+        let open_reports = self
+            .tickets
+            .values()
+            .filter(|ticket| ticket.resolution.is_none() && ticket.reporter.uuid == reporter.uuid)
+            .count();
+        if open_reports >= MAX_OPEN_REPORTS {
+            return None;
+        }
+
+        let id = self.tickets.last_key_value().map_or(1, |(id, _)| id + 1);
+        self.tickets.insert(id, Ticket {
+            id,
+            time: Utc::now(),
+            category,
+            comment,
+            reporter,
+            reported,
+            chat_context,
+            resolution: None,
+        });
+        self.save();
+        Some(id)
+    }
+
+    /// Marks a ticket as dealt with, returning the player that filed it
+    pub fn resolve(&mut self, id: u64, note: String) -> Result<Uuid, String> {
+        let ticket = self
+            .tickets
+            .get_mut(&id)
+            .ok_or_else(|| format!("No ticket with id {}", id))?;
+        if ticket.resolution.is_some() {
+            return Err(format!("Ticket {} is already resolved", id));
+        }
+        ticket.resolution = Some(Resolution {
+            time: Utc::now(),
+            note,
+        });
+        let reporter = ticket.reporter.uuid;
+        self.save();
+        Ok(reporter)
+    }
+
+    fn save(&self) {
+        let tickets =
+            match ron::ser::to_string_pretty(&self.tickets, ron::ser::PrettyConfig::default()) {
+                Ok(tickets) => tickets,
+                Err(err) => {
+                    error!(?err, "Failed to serialize moderation tickets");
+                    return;
+                },
+            };
+        let file = AtomicFile::new(&self.path, OverwriteBehavior::AllowOverwrite);
+        if let Err(err) = file.write(|file| file.write_all(tickets.as_bytes())) {
+            error!(?err, "Failed to write moderation tickets");
+        }
+    }
+}
@@ -17,6 +17,8 @@ use crate::{
             convert_items_to_database_items, convert_loadout_from_database_items,
             convert_recipe_book_from_database_items, convert_skill_groups_to_database,
             convert_skill_set_from_database, convert_stats_from_database,
+            convert_titles_from_database_json, convert_titles_to_database_json,
+            convert_unlocked_shrines_from_database_json, convert_unlocked_shrines_to_database_json,
             convert_waypoint_from_database_json, convert_waypoint_to_database_json,
         },
         character_loader::{CharacterCreationResult, CharacterDataResult, CharacterListResult},
@@ -38,6 +40,7 @@ use tracing::{debug, error, trace, warn};
 /// general, these have many invariants that need to be maintained when they're
 /// called--do not assume it's safe to make these public!
 mod conversions;
+pub mod transfer;
 
 pub(crate) type EntityId = i64;
 
@@ -49,10 +52,12 @@ const LOADOUT_PSEUDO_CONTAINER_DEF_ID: &str = "veloren.core.pseudo_containers.lo
 const OVERFLOW_ITEMS_PSEUDO_CONTAINER_DEF_ID: &str =
     "veloren.core.pseudo_containers.overflow_items";
 const RECIPE_BOOK_PSEUDO_CONTAINER_DEF_ID: &str = "veloren.core.pseudo_containers.recipe_book";
+const BANK_PSEUDO_CONTAINER_DEF_ID: &str = "veloren.core.pseudo_containers.bank";
 const INVENTORY_PSEUDO_CONTAINER_POSITION: &str = "inventory";
 const LOADOUT_PSEUDO_CONTAINER_POSITION: &str = "loadout";
 const OVERFLOW_ITEMS_PSEUDO_CONTAINER_POSITION: &str = "overflow_items";
 const RECIPE_BOOK_PSEUDO_CONTAINER_POSITION: &str = "recipe_book";
+const BANK_PSEUDO_CONTAINER_POSITION: &str = "bank";
 const WORLD_PSEUDO_CONTAINER_ID: EntityId = 1;
 
 #[derive(Clone, Copy)]
@@ -61,6 +66,7 @@ struct CharacterContainers {
     loadout_container_id: EntityId,
     overflow_items_container_id: EntityId,
     recipe_book_container_id: EntityId,
+    bank_container_id: EntityId,
 }
 
 /// Load the inventory/loadout
@@ -136,6 +142,7 @@ pub fn load_character_data(
     let overflow_items_items =
         load_items(connection, character_containers.overflow_items_container_id)?;
     let recipe_book_items = load_items(connection, character_containers.recipe_book_container_id)?;
+    let bank_items = load_items(connection, character_containers.bank_container_id)?;
 
     let mut stmt = connection.prepare_cached(
         "
@@ -143,14 +150,17 @@ pub fn load_character_data(
                 c.alias,
                 c.waypoint,
                 b.variant,
-                b.body_data
+                b.body_data,
+                c.bank_upgrades,
+                c.unlocked_shrines,
+                c.titles
         FROM    character c
         JOIN    body b ON (c.character_id = b.body_id)
         WHERE   c.player_uuid = ?1
         AND     c.character_id = ?2",
     )?;
 
-    let (body_data, character_data) = stmt.query_row(
+    let (body_data, character_data, bank_upgrades, unlocked_shrines, titles) = stmt.query_row(
         [requesting_player_uuid.clone(), char_id.0.to_string()],
         |row| {
             let character_data = Character {
@@ -166,7 +176,17 @@ pub fn load_character_data(
                 body_data: row.get(4)?,
             };
 
-            Ok((body_data, character_data))
+            let bank_upgrades: i64 = row.get(5)?;
+            let unlocked_shrines: String = row.get(6)?;
+            let titles: String = row.get(7)?;
+
+            Ok((
+                body_data,
+                character_data,
+                bank_upgrades,
+                unlocked_shrines,
+                titles,
+            ))
         },
     )?;
 
@@ -187,6 +207,23 @@ pub fn load_character_data(
         None => (None, None),
     };
 
+    let unlocked_shrines = convert_unlocked_shrines_from_database_json(&unlocked_shrines)
+        .unwrap_or_else(|e| {
+            warn!(
+                "Error reading unlocked shrines from database for character ID {}, error: {}",
+                char_id.0, e
+            );
+            comp::UnlockedShrines::default()
+        });
+
+    let titles = convert_titles_from_database_json(&titles).unwrap_or_else(|e| {
+        warn!(
+            "Error reading titles from database for character ID {}, error: {}",
+            char_id.0, e
+        );
+        comp::Titles::default()
+    });
+
     let mut stmt = connection.prepare_cached(
         "
         SELECT  skill_group_kind,
@@ -289,11 +326,16 @@ pub fn load_character_data(
                 character_containers.overflow_items_container_id,
                 &overflow_items_items,
                 &recipe_book_items,
+                character_containers.bank_container_id,
+                &bank_items,
+                bank_upgrades,
             )?,
             waypoint: char_waypoint,
             pets,
             active_abilities: convert_active_abilities_from_database(&ability_set_data),
             map_marker: char_map_marker,
+            unlocked_shrines,
+            titles,
         },
         UpdateCharacterMetadata {
             skill_set_persistence_load_error,
@@ -405,11 +447,13 @@ pub fn create_character(
         pets: _,
         active_abilities,
         map_marker,
+        unlocked_shrines,
+        titles,
     } = persisted_components;
 
-    // Fetch new entity IDs for character, inventory, loadout, overflow items, and
-    // recipe book
-    let mut new_entity_ids = get_new_entity_ids(transaction, |next_id| next_id + 5)?;
+    // Fetch new entity IDs for character, inventory, loadout, overflow items,
+    // recipe book, and bank
+    let mut new_entity_ids = get_new_entity_ids(transaction, |next_id| next_id + 6)?;
 
     // Create pseudo-container items for character
     let character_id = new_entity_ids.next().unwrap();
@@ -417,6 +461,7 @@ pub fn create_character(
     let loadout_container_id = new_entity_ids.next().unwrap();
     let overflow_items_container_id = new_entity_ids.next().unwrap();
     let recipe_book_container_id = new_entity_ids.next().unwrap();
+    let bank_container_id = new_entity_ids.next().unwrap();
 
     let pseudo_containers = vec![
         Item {
@@ -459,6 +504,14 @@ pub fn create_character(
             position: RECIPE_BOOK_PSEUDO_CONTAINER_POSITION.to_owned(),
             properties: String::new(),
         },
+        Item {
+            stack_size: 1,
+            item_id: bank_container_id,
+            parent_container_item_id: character_id,
+            item_definition_id: BANK_PSEUDO_CONTAINER_DEF_ID.to_owned(),
+            position: BANK_PSEUDO_CONTAINER_POSITION.to_owned(),
+            properties: String::new(),
+        },
     ];
 
     let mut stmt = transaction.prepare_cached(
@@ -505,8 +558,10 @@ pub fn create_character(
         INSERT INTO character (character_id,
                                player_uuid,
                                alias,
-                               waypoint)
-        VALUES (?1, ?2, ?3, ?4)",
+                               waypoint,
+                               unlocked_shrines,
+                               titles)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
     )?;
 
     stmt.execute([
@@ -514,6 +569,8 @@ pub fn create_character(
         &uuid,
         &character_alias,
         &convert_waypoint_to_database_json(waypoint, map_marker),
+        &convert_unlocked_shrines_to_database_json(&unlocked_shrines),
+        &convert_titles_to_database_json(&titles),
     ])?;
     drop(stmt);
 
@@ -569,6 +626,7 @@ pub fn create_character(
             inventory_container_id,
             overflow_items_container_id,
             recipe_book_container_id,
+            bank_container_id,
             &mut next_id,
         );
         inserts = inserts_;
@@ -862,6 +920,11 @@ fn get_pseudo_containers(
             character_id,
             RECIPE_BOOK_PSEUDO_CONTAINER_POSITION,
         )?,
+        bank_container_id: get_pseudo_container_id(
+            connection,
+            character_id,
+            BANK_PSEUDO_CONTAINER_POSITION,
+        )?,
     };
 
     Ok(character_containers)
@@ -1042,6 +1105,8 @@ pub fn update(
     char_waypoint: Option<comp::Waypoint>,
     active_abilities: comp::ability::ActiveAbilities,
     map_marker: Option<comp::MapMarker>,
+    unlocked_shrines: comp::UnlockedShrines,
+    titles: comp::Titles,
     transaction: &mut Transaction,
 ) -> Result<(), PersistenceError> {
     // Run pet persistence
@@ -1058,6 +1123,7 @@ pub fn update(
             pseudo_containers.inventory_container_id,
             pseudo_containers.overflow_items_container_id,
             pseudo_containers.recipe_book_container_id,
+            pseudo_containers.bank_container_id,
             &mut next_id,
         );
         upserts = upserts_;
@@ -1071,6 +1137,7 @@ pub fn update(
         Value::from(pseudo_containers.loadout_container_id),
         Value::from(pseudo_containers.overflow_items_container_id),
         Value::from(pseudo_containers.recipe_book_container_id),
+        Value::from(pseudo_containers.bank_container_id),
     ];
     for it in load_items(transaction, pseudo_containers.inventory_container_id)? {
         existing_item_ids.push(Value::from(it.item_id));
@@ -1084,6 +1151,9 @@ pub fn update(
     for it in load_items(transaction, pseudo_containers.recipe_book_container_id)? {
         existing_item_ids.push(Value::from(it.item_id));
     }
+    for it in load_items(transaction, pseudo_containers.bank_container_id)? {
+        existing_item_ids.push(Value::from(it.item_id));
+    }
 
     let non_upserted_items = upserts
         .iter()
@@ -1183,12 +1253,21 @@ pub fn update(
     let mut stmt = transaction.prepare_cached(
         "
         UPDATE  character
-        SET     waypoint = ?1
-        WHERE   character_id = ?2
+        SET     waypoint = ?1,
+                bank_upgrades = ?2,
+                unlocked_shrines = ?3,
+                titles = ?4
+        WHERE   character_id = ?5
     ",
     )?;
 
-    let waypoint_count = stmt.execute([&db_waypoint as &dyn ToSql, &char_id.0])?;
+    let waypoint_count = stmt.execute([
+        &db_waypoint as &dyn ToSql,
+        &i64::from(inventory.bank().upgrades()),
+        &convert_unlocked_shrines_to_database_json(&unlocked_shrines),
+        &convert_titles_to_database_json(&titles),
+        &char_id.0,
+    ])?;
 
     if waypoint_count != 1 {
         return Err(PersistenceError::OtherError(format!(
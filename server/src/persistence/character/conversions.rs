@@ -2,8 +2,8 @@ use crate::persistence::{
     character::EntityId,
     error::PersistenceError,
     json_models::{
-        self, CharacterPosition, DatabaseAbilitySet, DatabaseItemProperties, GenericBody,
-        HumanoidBody,
+        self, CharacterPosition, CharacterTitles, DatabaseAbilitySet, DatabaseItemProperties,
+        GenericBody, HumanoidBody, UnlockedShrine,
     },
     models::{AbilitySets, Character, Item, SkillGroup},
 };
@@ -12,6 +12,7 @@ use common::{
     comp::{
         body,
         inventory::{
+            bank::Bank,
             item::{tool::AbilityMap, Item as VelorenItem, MaterialStatManifest},
             loadout::{Loadout, LoadoutError},
             loadout_builder::LoadoutBuilder,
@@ -20,7 +21,8 @@ use common::{
         },
         item,
         skillset::{self, skills::Skill, SkillGroupKind, SkillSet},
-        ActiveAbilities, Body as CompBody, Inventory, MapMarker, Stats, Waypoint,
+        ActiveAbilities, Body as CompBody, Inventory, MapMarker, Stats, Title, Titles,
+        UnlockedShrines, Waypoint,
     },
     resources::Time,
 };
@@ -59,6 +61,7 @@ pub fn convert_items_to_database_items(
     inventory_container_id: EntityId,
     overflow_items_container_id: EntityId,
     recipe_book_container_id: EntityId,
+    bank_container_id: EntityId,
     next_id: &mut i64,
 ) -> Vec<ItemModelPair> {
     let loadout = inventory
@@ -83,6 +86,16 @@ pub fn convert_items_to_database_items(
                 recipe_book_container_id,
             )
         });
+    let bank = inventory
+        .bank()
+        .persistence_slots_with_index()
+        .map(|(i, item)| {
+            (
+                serde_json::to_string(&i).expect("failed to serialize index of bank slot"),
+                Some(item),
+                bank_container_id,
+            )
+        });
     // Inventory slots.
     let inventory = inventory.slots_with_id().map(|(pos, item)| {
         (
@@ -98,6 +111,7 @@ pub fn convert_items_to_database_items(
         .chain(loadout)
         .chain(overflow_items)
         .chain(recipe_book)
+        .chain(bank)
         .collect();
     let mut upserts = Vec::new();
     let mut depth = HashMap::new();
@@ -105,6 +119,7 @@ pub fn convert_items_to_database_items(
     depth.insert(loadout_container_id, 0);
     depth.insert(overflow_items_container_id, 0);
     depth.insert(recipe_book_container_id, 0);
+    depth.insert(bank_container_id, 0);
     while let Some((position, item, parent_container_item_id)) = bfs_queue.pop_front() {
         // Construct new items.
         if let Some(item) = item {
@@ -299,6 +314,66 @@ pub fn convert_waypoint_from_database_json(
     ))
 }
 
+pub fn convert_unlocked_shrines_to_database_json(unlocked_shrines: &UnlockedShrines) -> String {
+    let shrines = unlocked_shrines
+        .iter()
+        .map(|(site, pos)| UnlockedShrine { site, pos })
+        .collect::<Vec<_>>();
+    serde_json::to_string(&shrines).unwrap_or_else(|err| {
+        warn!("Error encoding unlocked shrines: {:?}", err);
+        String::from("[]")
+    })
+}
+
+pub fn convert_unlocked_shrines_from_database_json(
+    shrines: &str,
+) -> Result<UnlockedShrines, PersistenceError> {
+    let shrines = serde_json::de::from_str::<Vec<UnlockedShrine>>(shrines).map_err(|err| {
+        PersistenceError::ConversionError(format!(
+            "Error de-serializing unlocked shrines: {} err: {}",
+            shrines, err
+        ))
+    })?;
+    Ok(UnlockedShrines::new(
+        shrines
+            .into_iter()
+            .map(|UnlockedShrine { site, pos }| (site, pos))
+            .collect(),
+    ))
+}
+
+pub fn convert_titles_to_database_json(titles: &Titles) -> String {
+    let titles = CharacterTitles {
+        unlocked: titles
+            .unlocked()
+            .map(|title| title.to_key().to_owned())
+            .collect(),
+        selected: titles.selected().map(|title| title.to_key().to_owned()),
+    };
+    serde_json::to_string(&titles).unwrap_or_else(|err| {
+        warn!("Error encoding titles: {:?}", err);
+        String::from("{\"unlocked\":[],\"selected\":null}")
+    })
+}
+
+/// Titles that no longer exist are skipped
+pub fn convert_titles_from_database_json(titles: &str) -> Result<Titles, PersistenceError> {
+    let CharacterTitles { unlocked, selected } =
+        serde_json::de::from_str(titles).map_err(|err| {
+            PersistenceError::ConversionError(format!(
+                "Error de-serializing titles: {} err: {}",
+                titles, err
+            ))
+        })?;
+    Ok(Titles::new(
+        unlocked
+            .iter()
+            .filter_map(|key| Title::from_key(key))
+            .collect(),
+        selected.as_deref().and_then(Title::from_key),
+    ))
+}
+
 // Used to handle cases of modular items that are composed of components.
 // When called with the index of a component's parent item, it can get a mutable
 // reference to that parent item so that the component can be added to the
@@ -382,6 +457,9 @@ pub fn convert_inventory_from_database_items(
     overflow_items_container_id: i64,
     overflow_items: &[Item],
     recipe_book_items: &[Item],
+    bank_container_id: i64,
+    bank_items: &[Item],
+    bank_upgrades: i64,
 ) -> Result<Inventory, PersistenceError> {
     // Loadout items must be loaded before inventory items since loadout items
     // provide inventory slots. Since items stored inside loadout items actually
@@ -393,7 +471,11 @@ pub fn convert_inventory_from_database_items(
     let overflow_items =
         convert_overflow_items_from_database_items(overflow_items_container_id, overflow_items)?;
     let recipe_book = convert_recipe_book_from_database_items(recipe_book_items)?;
-    let mut inventory = Inventory::with_loadout_humanoid(loadout).with_recipe_book(recipe_book);
+    let (bank, misplaced_bank_items) =
+        convert_bank_from_database_items(bank_container_id, bank_items, bank_upgrades)?;
+    let mut inventory = Inventory::with_loadout_humanoid(loadout)
+        .with_recipe_book(recipe_book)
+        .with_bank(bank);
     let mut item_indices = HashMap::new();
 
     let mut failed_inserts = HashMap::new();
@@ -506,7 +588,8 @@ pub fn convert_inventory_from_database_items(
     if let Err(inv_error) = inventory.push_all(
         overflow_items
             .into_iter()
-            .chain(failed_inserts.into_values()),
+            .chain(failed_inserts.into_values())
+            .chain(misplaced_bank_items),
     ) {
         inventory.persistence_push_overflow_items(inv_error.returned_items());
     }
@@ -582,7 +665,48 @@ pub fn convert_overflow_items_from_database_items(
     overflow_items_container_id: i64,
     database_items: &[Item],
 ) -> Result<Vec<VelorenItem>, PersistenceError> {
-    let mut overflow_items_with_database_position = HashMap::new();
+    let overflow_items =
+        convert_positioned_items_from_database_items(overflow_items_container_id, database_items)?
+            .into_values()
+            .collect::<Vec<_>>();
+
+    Ok(overflow_items)
+}
+
+/// Loads the bank, returning any items that no longer fit into it (e.g. if
+/// the number of bank slots was rebalanced) separately.
+pub fn convert_bank_from_database_items(
+    bank_container_id: i64,
+    database_items: &[Item],
+    bank_upgrades: i64,
+) -> Result<(Bank, Vec<VelorenItem>), PersistenceError> {
+    let upgrades = u16::try_from(bank_upgrades).map_err(|_| {
+        PersistenceError::ConversionError(format!("Invalid bank upgrades: {}", bank_upgrades))
+    })?;
+    let mut misplaced_items = Vec::new();
+    let mut bank_items = Vec::new();
+    for (position, item) in
+        convert_positioned_items_from_database_items(bank_container_id, database_items)?
+    {
+        match serde_json::from_str::<usize>(&position) {
+            Ok(slot) => bank_items.push((slot, item)),
+            Err(_) => misplaced_items.push(item),
+        }
+    }
+
+    let (bank, leftovers) = Bank::bank_from_persistence(upgrades, bank_items);
+    misplaced_items.extend(leftovers);
+
+    Ok((bank, misplaced_items))
+}
+
+/// Loads the items of a pseudo-container whose items are only identified by
+/// their database position, such as overflow items or the bank.
+fn convert_positioned_items_from_database_items(
+    container_id: i64,
+    database_items: &[Item],
+) -> Result<HashMap<String, VelorenItem>, PersistenceError> {
+    let mut items_with_database_position = HashMap::new();
     let mut item_indices = HashMap::new();
 
     // In order to items with components to properly load, it is important that this
@@ -621,8 +745,8 @@ pub fn convert_overflow_items_from_database_items(
             })?;
         }
 
-        if db_item.parent_container_item_id == overflow_items_container_id {
-            match overflow_items_with_database_position.insert(db_item.position.clone(), item) {
+        if db_item.parent_container_item_id == container_id {
+            match items_with_database_position.insert(db_item.position.clone(), item) {
                 None => {
                     // Insert successful
                 },
@@ -630,7 +754,7 @@ pub fn convert_overflow_items_from_database_items(
                     // If insert returns a value, database had two items stored with the same
                     // position which is an error.
                     return Err(PersistenceError::ConversionError(
-                        "Inserted an item into the same overflow slot twice".to_string(),
+                        "Inserted an item into the same slot twice".to_string(),
                     ));
                 },
             }
@@ -639,23 +763,19 @@ pub fn convert_overflow_items_from_database_items(
                 j,
                 database_items,
                 &item_indices,
-                &mut overflow_items_with_database_position,
+                &mut items_with_database_position,
                 &|o_i, s| o_i.get_mut(s),
             )?
             .persistence_access_add_component(item);
         } else {
             return Err(PersistenceError::ConversionError(format!(
-                "Couldn't find parent item {} before item {} in overflow items",
-                db_item.parent_container_item_id, db_item.item_id
+                "Couldn't find parent item {} before item {} in container {}",
+                db_item.parent_container_item_id, db_item.item_id, container_id
             )));
         }
     }
 
-    let overflow_items = overflow_items_with_database_position
-        .into_values()
-        .collect::<Vec<_>>();
-
-    Ok(overflow_items)
+    Ok(items_with_database_position)
 }
 
 fn get_item_from_asset(item_definition_id: &str) -> Result<common::comp::Item, PersistenceError> {
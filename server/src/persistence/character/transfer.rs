@@ -0,0 +1,330 @@
+//! Export and import of the full persisted state of a character, used to move
+//! characters between servers or players.
+//!
+//! Exports contain the raw database rows of a character. Entity IDs are only
+//! meaningful within a single database, so they are remapped to newly
+//! allocated IDs on import.
+
+use super::{
+    check_character_limit, get_new_entity_ids, load_character_data, load_items, EntityId,
+    CHARACTER_PSEUDO_CONTAINER_DEF_ID, WORLD_PSEUDO_CONTAINER_ID,
+};
+use crate::persistence::error::PersistenceError;
+use common::character::CharacterId;
+use hashbrown::HashMap;
+use rusqlite::{Connection, ToSql, Transaction};
+use serde::{Deserialize, Serialize};
+
+/// Incremented whenever the format of [`CharacterExport`] changes in a way
+/// that older exports can't be imported anymore.
+pub const CHARACTER_EXPORT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct CharacterExport {
+    pub version: u32,
+    pub character_id: EntityId,
+    pub alias: String,
+    pub waypoint: Option<String>,
+    pub body: ExportedBody,
+    pub skill_groups: Vec<ExportedSkillGroup>,
+    pub ability_sets: String,
+    pub pets: Vec<ExportedPet>,
+    /// Every item below the character's pseudo container, ordered so that
+    /// containers come before their contents.
+    pub items: Vec<ExportedItem>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedBody {
+    pub variant: String,
+    pub body_data: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedSkillGroup {
+    pub skill_group_kind: String,
+    pub earned_exp: i64,
+    pub spent_exp: i64,
+    pub skills: String,
+    pub hash_val: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedPet {
+    pub name: String,
+    pub body: ExportedBody,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedItem {
+    pub item_id: EntityId,
+    pub parent_container_item_id: EntityId,
+    pub item_definition_id: String,
+    pub stack_size: i64,
+    pub position: String,
+    pub properties: String,
+}
+
+pub fn export_character(
+    char_id: CharacterId,
+    connection: &Connection,
+) -> Result<CharacterExport, PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  c.alias,
+                c.waypoint,
+                b.variant,
+                b.body_data
+        FROM    character c
+        JOIN    body b ON (c.character_id = b.body_id)
+        WHERE   c.character_id = ?1",
+    )?;
+    let (alias, waypoint, body) = stmt.query_row([char_id.0], |row| {
+        Ok((row.get(0)?, row.get(1)?, ExportedBody {
+            variant: row.get(2)?,
+            body_data: row.get(3)?,
+        }))
+    })?;
+    drop(stmt);
+
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  skill_group_kind,
+                earned_exp,
+                spent_exp,
+                skills,
+                hash_val
+        FROM    skill_group
+        WHERE   entity_id = ?1",
+    )?;
+    let skill_groups = stmt
+        .query_map([char_id.0], |row| {
+            Ok(ExportedSkillGroup {
+                skill_group_kind: row.get(0)?,
+                earned_exp: row.get(1)?,
+                spent_exp: row.get(2)?,
+                skills: row.get(3)?,
+                hash_val: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    drop(stmt);
+
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  ability_sets
+        FROM    ability_set
+        WHERE   entity_id = ?1",
+    )?;
+    let ability_sets = stmt.query_row([char_id.0], |row| row.get(0))?;
+    drop(stmt);
+
+    #[rustfmt::skip]
+    let mut stmt = connection.prepare_cached("
+        SELECT  p.name,
+                b.variant,
+                b.body_data
+        FROM    pet p
+        JOIN    body b ON (p.pet_id = b.body_id)
+        WHERE   p.character_id = ?1",
+    )?;
+    let pets = stmt
+        .query_map([char_id.0], |row| {
+            Ok(ExportedPet {
+                name: row.get(0)?,
+                body: ExportedBody {
+                    variant: row.get(1)?,
+                    body_data: row.get(2)?,
+                },
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    drop(stmt);
+
+    let items = load_items(connection, char_id.0)?
+        .into_iter()
+        .map(|item| ExportedItem {
+            item_id: item.item_id,
+            parent_container_item_id: item.parent_container_item_id,
+            item_definition_id: item.item_definition_id,
+            stack_size: item.stack_size,
+            position: item.position,
+            properties: item.properties,
+        })
+        .collect();
+
+    Ok(CharacterExport {
+        version: CHARACTER_EXPORT_VERSION,
+        character_id: char_id.0,
+        alias,
+        waypoint,
+        body,
+        skill_groups,
+        ability_sets,
+        pets,
+        items,
+    })
+}
+
+/// Creates a new character for the player identified by `uuid` from an
+/// export.
+///
+/// The imported character is loaded again before the transaction is
+/// committed, which validates it the same way as any other character.
+pub fn import_character(
+    uuid: &str,
+    export: CharacterExport,
+    transaction: &mut Transaction,
+) -> Result<CharacterId, PersistenceError> {
+    if export.version != CHARACTER_EXPORT_VERSION {
+        return Err(PersistenceError::ConversionError(format!(
+            "Unsupported character export version {}, expected {}",
+            export.version, CHARACTER_EXPORT_VERSION
+        )));
+    }
+
+    check_character_limit(uuid, transaction)?;
+
+    let new_id_count = 1 + export.items.len() + export.pets.len();
+    let mut new_entity_ids =
+        get_new_entity_ids(transaction, |next_id| next_id + new_id_count as i64)?;
+
+    let character_id = new_entity_ids.next().unwrap();
+    let mut id_map = HashMap::from([(export.character_id, character_id)]);
+    for item in &export.items {
+        id_map.insert(item.item_id, new_entity_ids.next().unwrap());
+    }
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO item (item_id,
+                          parent_container_item_id,
+                          item_definition_id,
+                          stack_size,
+                          position,
+                          properties)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+
+    stmt.execute([
+        &character_id as &dyn ToSql,
+        &WORLD_PSEUDO_CONTAINER_ID,
+        &CHARACTER_PSEUDO_CONTAINER_DEF_ID,
+        &1,
+        &character_id.to_string(),
+        &"",
+    ])?;
+
+    for item in &export.items {
+        let parent_id = id_map.get(&item.parent_container_item_id).ok_or_else(|| {
+            PersistenceError::ConversionError(format!(
+                "Item {} is inside of unknown container {}",
+                item.item_id, item.parent_container_item_id
+            ))
+        })?;
+        stmt.execute([
+            &id_map[&item.item_id] as &dyn ToSql,
+            parent_id,
+            &item.item_definition_id,
+            &item.stack_size,
+            &item.position,
+            &item.properties,
+        ])?;
+    }
+    drop(stmt);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO body (body_id,
+                          variant,
+                          body_data)
+        VALUES (?1, ?2, ?3)",
+    )?;
+    stmt.execute([
+        &character_id as &dyn ToSql,
+        &export.body.variant,
+        &export.body.body_data,
+    ])?;
+    drop(stmt);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO character (character_id,
+                               player_uuid,
+                               alias,
+                               waypoint)
+        VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    stmt.execute([
+        &character_id as &dyn ToSql,
+        &uuid,
+        &export.alias,
+        &export.waypoint,
+    ])?;
+    drop(stmt);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO skill_group (entity_id,
+                                 skill_group_kind,
+                                 earned_exp,
+                                 spent_exp,
+                                 skills,
+                                 hash_val)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    for skill_group in &export.skill_groups {
+        stmt.execute([
+            &character_id as &dyn ToSql,
+            &skill_group.skill_group_kind,
+            &skill_group.earned_exp,
+            &skill_group.spent_exp,
+            &skill_group.skills,
+            &skill_group.hash_val,
+        ])?;
+    }
+    drop(stmt);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO ability_set (entity_id,
+                                 ability_sets)
+        VALUES (?1, ?2)",
+    )?;
+    stmt.execute([&character_id as &dyn ToSql, &export.ability_sets])?;
+    drop(stmt);
+
+    for pet in &export.pets {
+        let pet_id = new_entity_ids.next().unwrap();
+
+        #[rustfmt::skip]
+        let mut stmt = transaction.prepare_cached("
+            INSERT
+            INTO    body (
+                    body_id,
+                    variant,
+                    body_data)
+            VALUES  (?1, ?2, ?3)"
+        )?;
+        stmt.execute([
+            &pet_id as &dyn ToSql,
+            &pet.body.variant,
+            &pet.body.body_data,
+        ])?;
+
+        #[rustfmt::skip]
+        let mut stmt = transaction.prepare_cached("
+            INSERT
+            INTO    pet (
+                    pet_id,
+                    character_id,
+                    name)
+            VALUES  (?1, ?2, ?3)",
+        )?;
+        stmt.execute([&pet_id as &dyn ToSql, &character_id, &pet.name])?;
+    }
+
+    load_character_data(uuid.to_owned(), CharacterId(character_id), transaction)?;
+
+    Ok(CharacterId(character_id))
+}
@@ -12,6 +12,11 @@ use rusqlite::Connection;
 use std::sync::{Arc, RwLock};
 use tracing::{debug, error};
 
+/// Number of read-only connections used to serve character loader requests.
+/// SQLite in WAL mode allows readers to run concurrently with each other and
+/// with the writer, so a burst of logins doesn't have to be served one by one.
+const LOADER_CONNECTIONS: usize = 4;
+
 pub(crate) type CharacterListResult = Result<Vec<CharacterItem>, PersistenceError>;
 pub(crate) type CharacterCreationResult =
     Result<(CharacterId, Vec<CharacterItem>), PersistenceError>;
@@ -86,29 +91,36 @@ impl CharacterLoader {
         let (update_tx, internal_rx) = crossbeam_channel::unbounded::<CharacterLoaderRequest>();
         let (internal_tx, update_rx) = crossbeam_channel::unbounded::<CharacterUpdaterMessage>();
 
-        let builder = std::thread::Builder::new().name("persistence_loader".into());
-        builder
-            .spawn(move || {
-                // Unwrap here is safe as there is no code that can panic when the write lock is
-                // taken that could cause the RwLock to become poisoned.
-                //
-                // This connection -must- remain read-only to avoid lock contention with the
-                // CharacterUpdater thread.
-                let mut conn =
-                    establish_connection(&settings.read().unwrap(), ConnectionMode::ReadOnly);
-
-                for request in internal_rx {
-                    conn.update_log_mode(&settings);
-
-                    let response = CharacterLoader::process_request(request, &conn);
-                    debug!("Processing complete");
-                    if let Err(e) = internal_tx.send(response) {
-                        error!(?e, "Could not send character loader response");
+        for i in 0..LOADER_CONNECTIONS {
+            let (settings, internal_rx, internal_tx) = (
+                Arc::clone(&settings),
+                internal_rx.clone(),
+                internal_tx.clone(),
+            );
+            let builder = std::thread::Builder::new().name(format!("persistence_loader_{}", i));
+            builder
+                .spawn(move || {
+                    // Unwrap here is safe as there is no code that can panic when the write lock
+                    // is taken that could cause the RwLock to become poisoned.
+                    //
+                    // This connection -must- remain read-only to avoid lock contention with the
+                    // CharacterUpdater thread.
+                    let mut conn =
+                        establish_connection(&settings.read().unwrap(), ConnectionMode::ReadOnly);
+
+                    for request in internal_rx {
+                        conn.update_log_mode(&settings);
+
+                        let response = CharacterLoader::process_request(request, &conn);
+                        debug!("Processing complete");
+                        if let Err(e) = internal_tx.send(response) {
+                            error!(?e, "Could not send character loader response");
+                        }
+                        debug!("Sent character loader response");
                     }
-                    debug!("Sent character loader response");
-                }
-            })
-            .unwrap();
+                })
+                .unwrap();
+        }
 
         Ok(Self {
             update_rx,
@@ -187,4 +199,7 @@ impl CharacterLoader {
 
     /// Returns a non-blocking iterator over CharacterLoaderResponse messages
     pub fn messages(&self) -> TryIter<CharacterUpdaterMessage> { self.update_rx.try_iter() }
+
+    /// Number of requests waiting for a free connection
+    pub fn queue_len(&self) -> usize { self.update_tx.len() }
 }
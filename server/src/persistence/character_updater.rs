@@ -2,18 +2,19 @@ use crate::comp;
 use common::character::CharacterId;
 
 use crate::persistence::{
+    character::transfer::import_character,
     character_loader::{
         CharacterScreenResponse, CharacterScreenResponseKind, CharacterUpdaterMessage,
     },
     error::PersistenceError,
-    establish_connection, ConnectionMode, DatabaseSettings, EditableComponents,
+    establish_connection, CharacterExport, ConnectionMode, DatabaseSettings, EditableComponents,
     PersistedComponents, VelorenConnection,
 };
 use crossbeam_channel::TryIter;
 use rusqlite::DropBehavior;
 use specs::Entity;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc, RwLock,
@@ -29,6 +30,8 @@ pub type CharacterUpdateData = (
     Option<comp::Waypoint>,
     comp::ability::ActiveAbilities,
     Option<comp::MapMarker>,
+    comp::UnlockedShrines,
+    comp::Titles,
 );
 
 pub type PetPersistenceData = (comp::Pet, comp::Body, comp::Stats);
@@ -52,9 +55,16 @@ enum CharacterUpdaterAction {
         character_alias: String,
         editable_components: EditableComponents,
     },
+    ImportCharacter {
+        player_uuid: String,
+        export: Box<CharacterExport>,
+        callback: ImportCallback,
+    },
     DisconnectedSuccess,
 }
 
+type ImportCallback = Box<dyn FnOnce(Result<CharacterId, PersistenceError>) + Send>;
+
 #[derive(Clone)]
 enum DatabaseAction {
     New(DatabaseActionKind),
@@ -115,9 +125,14 @@ impl CharacterUpdater {
                 // taken that could cause the RwLock to become poisoned.
                 let mut conn =
                     establish_connection(&settings.read().unwrap(), ConnectionMode::ReadWrite);
-                while let Ok(action) = update_rx.recv() {
+                // Actions received while merging batches, which are handled afterwards
+                let mut deferred = VecDeque::new();
+                while let Some(action) = deferred.pop_front().or_else(|| update_rx.recv().ok()) {
                     match action {
-                        CharacterUpdaterAction::BatchUpdate { batch_id, updates } => {
+                        CharacterUpdaterAction::BatchUpdate {
+                            batch_id,
+                            mut updates,
+                        } => {
                             if disconnect_all_clients_requested_clone.load(Ordering::Relaxed) {
                                 debug!(
                                     "Skipping persistence due to pending disconnection of all \
@@ -127,7 +142,28 @@ impl CharacterUpdater {
                             }
                             conn.update_log_mode(&settings);
 
-                            if let Err(e) = execute_batch_update(updates.into_iter(), &mut conn) {
+                            // If batches queued up while the previous one was being written,
+                            // write them all in a single transaction.
+                            let mut batch_ids = vec![batch_id];
+                            for action in update_rx.try_iter() {
+                                match action {
+                                    CharacterUpdaterAction::BatchUpdate {
+                                        batch_id,
+                                        updates: more_updates,
+                                    } => {
+                                        batch_ids.push(batch_id);
+                                        updates.extend(more_updates);
+                                    },
+                                    action => deferred.push_back(action),
+                                }
+                            }
+                            if batch_ids.len() > 1 {
+                                debug!("Merged {} queued persistence batches", batch_ids.len());
+                            }
+
+                            if let Err(e) =
+                                execute_batch_update(dedup_updates(updates).into_iter(), &mut conn)
+                            {
                                 error!(
                                     ?e,
                                     "Error during character batch update, disconnecting all \
@@ -137,15 +173,17 @@ impl CharacterUpdater {
                                     .store(true, Ordering::Relaxed);
                             };
 
-                            if let Err(e) = response_tx
-                                .send(CharacterUpdaterMessage::DatabaseBatchCompletion(batch_id))
-                            {
-                                error!(?e, "Could not send DatabaseBatchCompletion message");
-                            } else {
-                                debug!(
-                                    "Submitted DatabaseBatchCompletion - Batch ID: {}",
-                                    batch_id
-                                );
+                            for batch_id in batch_ids {
+                                if let Err(e) = response_tx.send(
+                                    CharacterUpdaterMessage::DatabaseBatchCompletion(batch_id),
+                                ) {
+                                    error!(?e, "Could not send DatabaseBatchCompletion message");
+                                } else {
+                                    debug!(
+                                        "Submitted DatabaseBatchCompletion - Batch ID: {}",
+                                        batch_id
+                                    );
+                                }
                             }
                         },
                         CharacterUpdaterAction::CreateCharacter {
@@ -208,6 +246,20 @@ impl CharacterUpdater {
                                 ),
                             }
                         },
+                        CharacterUpdaterAction::ImportCharacter {
+                            player_uuid,
+                            export,
+                            callback,
+                        } => {
+                            let result = execute_character_import(&player_uuid, *export, &mut conn);
+                            if let Err(e) = &result {
+                                error!(
+                                    "Error importing character for player {}, error: {:?}",
+                                    player_uuid, e
+                                );
+                            }
+                            callback(result);
+                        },
                         CharacterUpdaterAction::DisconnectedSuccess => {
                             info!(
                                 "CharacterUpdater received DisconnectedSuccess event, resuming \
@@ -331,6 +383,29 @@ impl CharacterUpdater {
         }
     }
 
+    /// Creates a new character for the player from a [`CharacterExport`].
+    /// `callback` is invoked from the persistence thread once the import is
+    /// done.
+    pub fn import_character(
+        &mut self,
+        requesting_player_uuid: String,
+        export: CharacterExport,
+        callback: impl FnOnce(Result<CharacterId, PersistenceError>) + Send + 'static,
+    ) {
+        if let Err(e) =
+            self.update_tx
+                .as_ref()
+                .unwrap()
+                .send(CharacterUpdaterAction::ImportCharacter {
+                    player_uuid: requesting_player_uuid,
+                    export: Box::new(export),
+                    callback: Box::new(callback),
+                })
+        {
+            error!(?e, "Could not send character import request");
+        }
+    }
+
     fn next_pending_database_event_id(&mut self) -> u64 {
         self.last_pending_database_event_id += 1;
         self.last_pending_database_event_id
@@ -407,6 +482,29 @@ impl CharacterUpdater {
 
     /// Returns a non-blocking iterator over CharacterLoaderResponse messages
     pub fn messages(&self) -> TryIter<CharacterUpdaterMessage> { self.response_rx.try_iter() }
+
+    /// Number of actions sent to the persistence thread that it hasn't started
+    /// processing yet
+    pub fn queue_len(&self) -> usize { self.update_tx.as_ref().map_or(0, |tx| tx.len()) }
+
+    /// Number of logouts and deletions waiting to be written to the database
+    pub fn pending_action_count(&self) -> usize { self.pending_database_actions.len() }
+}
+
+/// Removes character updates that are superseded by a later update of the same
+/// character, which happens when several batches are merged.
+fn dedup_updates(updates: Vec<DatabaseActionKind>) -> Vec<DatabaseActionKind> {
+    let mut updated = HashSet::new();
+    let mut updates = updates
+        .into_iter()
+        .rev()
+        .filter(|action| match action {
+            DatabaseActionKind::UpdateCharacter(update) => updated.insert(update.0),
+            DatabaseActionKind::DeleteCharacter { .. } => true,
+        })
+        .collect::<Vec<_>>();
+    updates.reverse();
+    updates
 }
 
 fn execute_batch_update(
@@ -425,6 +523,8 @@ fn execute_batch_update(
             waypoint,
             active_abilities,
             map_marker,
+            unlocked_shrines,
+            titles,
         )) => super::character::update(
             character_id,
             stats,
@@ -433,6 +533,8 @@ fn execute_batch_update(
             waypoint,
             active_abilities,
             map_marker,
+            unlocked_shrines,
+            titles,
             &mut transaction,
         ),
         DatabaseActionKind::DeleteCharacter {
@@ -509,6 +611,17 @@ fn execute_character_edit(
     Ok(CharacterUpdaterMessage::CharacterScreenResponse(response))
 }
 
+fn execute_character_import(
+    requesting_player_uuid: &str,
+    export: CharacterExport,
+    connection: &mut VelorenConnection,
+) -> Result<CharacterId, PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    let character_id = import_character(requesting_player_uuid, export, &mut transaction)?;
+    transaction.commit()?;
+    Ok(character_id)
+}
+
 impl Drop for CharacterUpdater {
     fn drop(&mut self) {
         drop(self.update_tx.take());
@@ -0,0 +1,359 @@
+//! Database operations for player guilds, see [`crate::guild::Guilds`]
+
+use crate::{
+    guild::{Guild, GuildHall, GuildMember},
+    persistence::error::PersistenceError,
+};
+use common::{
+    comp::{
+        inventory::InvSlot,
+        item::{tool::AbilityMap, Item, ItemDefinitionIdOwned, MaterialStatManifest},
+    },
+    guild::{GuildId, GuildNotice, GuildRank, GUILD_STORAGE_SLOTS},
+    heraldry::Heraldry,
+    uuid::Uuid,
+};
+use hashbrown::HashMap;
+use rusqlite::{Connection, ToSql};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use vek::*;
+
+/// An item in the storage of a guild hall, as it is kept in the database
+#[derive(Serialize, Deserialize)]
+struct StoredItem {
+    id: ItemDefinitionIdOwned,
+    amount: u32,
+}
+
+/// A guild hall as it is kept in the database, before it is matched up with
+/// the hall in the world by the position of its notice board
+pub struct StoredHall {
+    pub board: Vec3<i32>,
+    pub banner: Heraldry,
+    pub storage_rank: GuildRank,
+    pub storage: Vec<InvSlot>,
+    pub notices: Vec<GuildNotice>,
+}
+
+pub fn load_guilds(connection: &Connection) -> Result<Vec<Guild>, PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  guild_id,
+                name,
+                tag
+        FROM    guild",
+    )?;
+
+    let mut guilds = stmt
+        .query_map([], |row| {
+            Ok(Guild {
+                id: GuildId(row.get(0)?),
+                name: row.get(1)?,
+                tag: row.get(2)?,
+                members: Vec::new(),
+                hall: None,
+            })
+        })?
+        .map(|guild| guild.map(|guild| (guild.id, guild)))
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  player_uuid,
+                guild_id,
+                rank,
+                alias
+        FROM    guild_member",
+    )?;
+
+    let members = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            GuildId(row.get(1)?),
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+
+    for member in members {
+        let (uuid, guild_id, rank, alias) = member?;
+        let (Ok(uuid), Some(rank)) = (Uuid::parse_str(&uuid), GuildRank::from_key(&rank)) else {
+            warn!(?uuid, ?rank, "Ignoring invalid guild member");
+            continue;
+        };
+        if let Some(guild) = guilds.get_mut(&guild_id) {
+            guild.members.push(GuildMember { uuid, alias, rank });
+        }
+    }
+
+    Ok(guilds.into_values().collect())
+}
+
+pub fn load_halls(
+    connection: &Connection,
+    ability_map: &AbilityMap,
+    msm: &MaterialStatManifest,
+) -> Result<HashMap<GuildId, StoredHall>, PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  guild_id,
+                board_x,
+                board_y,
+                board_z,
+                banner,
+                storage_rank,
+                storage
+        FROM    guild_hall",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            GuildId(row.get(0)?),
+            Vec3::new(row.get(1)?, row.get(2)?, row.get(3)?),
+            row.get::<_, String>(4)?,
+            row.get::<_, String>(5)?,
+            row.get::<_, String>(6)?,
+        ))
+    })?;
+
+    let mut halls = HashMap::new();
+    for row in rows {
+        let (guild_id, board, banner, storage_rank, storage) = row?;
+        let banner = serde_json::from_str(&banner).unwrap_or_else(|err| {
+            warn!(
+                ?err,
+                ?guild_id,
+                "Invalid guild banner, using the default one"
+            );
+            Heraldry::default()
+        });
+        let storage_rank = GuildRank::from_key(&storage_rank).unwrap_or(GuildRank::Officer);
+        let stored_items = serde_json::from_str::<Vec<Option<StoredItem>>>(&storage)?;
+        let mut storage = stored_items
+            .into_iter()
+            .map(|stored| {
+                stored.and_then(|stored| {
+                    let mut item =
+                        Item::new_from_item_definition_id(stored.id.as_ref(), ability_map, msm)
+                            .map_err(|err| {
+                                warn!(?err, ?guild_id, "Dropping invalid guild storage item")
+                            })
+                            .ok()?;
+                    item.set_amount(stored.amount).ok()?;
+                    Some(item)
+                })
+            })
+            .collect::<Vec<_>>();
+        storage.resize(GUILD_STORAGE_SLOTS, None);
+
+        halls.insert(guild_id, StoredHall {
+            board,
+            banner,
+            storage_rank,
+            storage,
+            notices: Vec::new(),
+        });
+    }
+
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  notice_id,
+                guild_id,
+                author_uuid,
+                author_alias,
+                text
+        FROM    guild_notice
+        ORDER BY notice_id",
+    )?;
+
+    let notices = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            GuildId(row.get(1)?),
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+        ))
+    })?;
+
+    for notice in notices {
+        let (id, guild_id, author, author_alias, text) = notice?;
+        let Ok(author) = Uuid::parse_str(&author) else {
+            warn!(?author, "Ignoring guild notice with an invalid author");
+            continue;
+        };
+        if let Some(hall) = halls.get_mut(&guild_id) {
+            hall.notices.push(GuildNotice {
+                id,
+                author,
+                author_alias,
+                text,
+            });
+        }
+    }
+
+    Ok(halls)
+}
+
+/// Inserts a new guild with its founder as the leader, returning its id
+pub fn create_guild(
+    connection: &Connection,
+    name: &str,
+    tag: &str,
+    leader: &GuildMember,
+) -> Result<GuildId, PersistenceError> {
+    let transaction = connection.unchecked_transaction()?;
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO guild (name, tag)
+        VALUES (?1, ?2)",
+    )?;
+    stmt.execute([name, tag])?;
+    drop(stmt);
+    let guild_id = GuildId(transaction.last_insert_rowid());
+
+    insert_member(&transaction, guild_id, leader)?;
+    transaction.commit()?;
+
+    Ok(guild_id)
+}
+
+pub fn insert_member(
+    connection: &Connection,
+    guild_id: GuildId,
+    member: &GuildMember,
+) -> Result<(), PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        INSERT INTO guild_member (player_uuid, guild_id, rank, alias)
+        VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    stmt.execute([
+        &member.uuid.to_string() as &dyn ToSql,
+        &guild_id.0,
+        &member.rank.to_key(),
+        &member.alias,
+    ])?;
+
+    Ok(())
+}
+
+pub fn delete_member(connection: &Connection, uuid: Uuid) -> Result<(), PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        DELETE
+        FROM    guild_member
+        WHERE   player_uuid = ?1",
+    )?;
+    stmt.execute([uuid.to_string()])?;
+
+    Ok(())
+}
+
+pub fn update_rank(
+    connection: &Connection,
+    uuid: Uuid,
+    rank: GuildRank,
+) -> Result<(), PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        UPDATE  guild_member
+        SET     rank = ?1
+        WHERE   player_uuid = ?2",
+    )?;
+    stmt.execute([rank.to_key(), &uuid.to_string()])?;
+
+    Ok(())
+}
+
+/// Deletes a guild, its members are removed by the foreign key cascade
+pub fn delete_guild(connection: &Connection, guild_id: GuildId) -> Result<(), PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        DELETE
+        FROM    guild
+        WHERE   guild_id = ?1",
+    )?;
+    stmt.execute([guild_id.0])?;
+
+    Ok(())
+}
+
+/// Writes the hall of a guild, replacing what was stored for it before.
+/// Notices are written separately.
+pub fn save_hall(
+    connection: &Connection,
+    guild_id: GuildId,
+    hall: &GuildHall,
+) -> Result<(), PersistenceError> {
+    let banner = serde_json::to_string(&hall.banner)?;
+    let storage = serde_json::to_string(
+        &hall
+            .storage
+            .iter()
+            .map(|slot| {
+                slot.as_ref().map(|item| StoredItem {
+                    id: item.item_definition_id().to_owned(),
+                    amount: item.amount(),
+                })
+            })
+            .collect::<Vec<_>>(),
+    )?;
+
+    let mut stmt = connection.prepare_cached(
+        "
+        REPLACE INTO guild_hall (guild_id, board_x, board_y, board_z, banner, storage_rank, \
+         storage)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+    stmt.execute([
+        &guild_id.0 as &dyn ToSql,
+        &hall.spot.board.x,
+        &hall.spot.board.y,
+        &hall.spot.board.z,
+        &banner,
+        &hall.storage_rank.to_key(),
+        &storage,
+    ])?;
+
+    Ok(())
+}
+
+/// Inserts a notice, returning its id
+pub fn insert_notice(
+    connection: &Connection,
+    guild_id: GuildId,
+    author: Uuid,
+    author_alias: &str,
+    text: &str,
+) -> Result<i64, PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        INSERT INTO guild_notice (guild_id, author_uuid, author_alias, text)
+        VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    stmt.execute([
+        &guild_id.0 as &dyn ToSql,
+        &author.to_string(),
+        &author_alias,
+        &text,
+    ])?;
+
+    Ok(connection.last_insert_rowid())
+}
+
+pub fn delete_notices(connection: &Connection, notice_ids: &[i64]) -> Result<(), PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        DELETE
+        FROM    guild_notice
+        WHERE   notice_id = ?1",
+    )?;
+    for notice_id in notice_ids {
+        stmt.execute([notice_id])?;
+    }
+
+    Ok(())
+}
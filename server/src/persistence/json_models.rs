@@ -1,4 +1,4 @@
-use common::comp;
+use common::{comp, heraldry::Heraldry};
 use common_base::dev_panic;
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
@@ -68,6 +68,20 @@ pub struct CharacterPosition {
     pub map_marker: Option<Vec2<i32>>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct UnlockedShrine {
+    pub site: u64,
+    pub pos: Vec3<f32>,
+}
+
+/// Titles are stored by their keys, so that renaming a variant doesn't lose
+/// them
+#[derive(Serialize, Deserialize)]
+pub struct CharacterTitles {
+    pub unlocked: Vec<String>,
+    pub selected: Option<String>,
+}
+
 pub fn skill_group_to_db_string(skill_group: comp::skillset::SkillGroupKind) -> String {
     use comp::{item::tool::ToolKind, skillset::SkillGroupKind::*};
     let skill_group_string = match skill_group {
@@ -120,6 +134,12 @@ pub struct DatabaseAbilitySet {
     mainhand: String,
     offhand: String,
     abilities: Vec<String>,
+    /// Which loadout of the weapon combination this set belongs to
+    #[serde(default)]
+    loadout: usize,
+    /// Whether the loadout isn't the one currently in use
+    #[serde(default)]
+    stored: bool,
 }
 
 fn aux_ability_to_string(ability: comp::ability::AuxiliaryAbility) -> String {
@@ -268,50 +288,76 @@ fn tool_kind_from_string(tool: String) -> Option<comp::item::tool::ToolKind> {
 pub fn active_abilities_to_db_model(
     active_abilities: &comp::ability::ActiveAbilities,
 ) -> Vec<DatabaseAbilitySet> {
-    active_abilities
-        .auxiliary_sets
-        .iter()
-        .map(|((mainhand, offhand), abilities)| DatabaseAbilitySet {
-            mainhand: tool_kind_to_string(*mainhand),
-            offhand: tool_kind_to_string(*offhand),
+    let to_db_model = |(mainhand, offhand): comp::ability::AuxiliaryKey,
+                       loadout: usize,
+                       stored: bool,
+                       abilities: &[comp::ability::AuxiliaryAbility]| {
+        DatabaseAbilitySet {
+            mainhand: tool_kind_to_string(mainhand),
+            offhand: tool_kind_to_string(offhand),
             abilities: abilities
                 .iter()
                 .map(|ability| aux_ability_to_string(*ability))
                 .collect(),
+            loadout,
+            stored,
+        }
+    };
+    active_abilities
+        .auxiliary_sets
+        .iter()
+        .map(|(key, abilities)| {
+            to_db_model(
+                *key,
+                active_abilities.active_loadout(*key),
+                false,
+                abilities,
+            )
         })
+        .chain(
+            active_abilities
+                .stored_loadouts
+                .iter()
+                .map(|((key, loadout), abilities)| to_db_model(*key, *loadout, true, abilities)),
+        )
         .collect::<Vec<_>>()
 }
 
 pub fn active_abilities_from_db_model(
     ability_sets: Vec<DatabaseAbilitySet>,
 ) -> comp::ability::ActiveAbilities {
-    let ability_sets = ability_sets
-        .into_iter()
-        .map(
-            |DatabaseAbilitySet {
-                 mainhand,
-                 offhand,
-                 abilities,
-             }| {
-                let mut auxiliary_abilities =
-                    vec![comp::ability::AuxiliaryAbility::Empty; comp::ability::BASE_ABILITY_LIMIT];
-                for (empty, ability) in auxiliary_abilities.iter_mut().zip(abilities.into_iter()) {
-                    *empty = aux_ability_from_string(&ability);
-                }
-                (
-                    (
-                        tool_kind_from_string(mainhand),
-                        tool_kind_from_string(offhand),
-                    ),
-                    auxiliary_abilities,
-                )
-            },
-        )
-        .collect::<HashMap<_, _>>();
+    let mut active_sets = HashMap::new();
+    let mut active_loadouts = HashMap::new();
+    let mut stored_loadouts = HashMap::new();
+    for DatabaseAbilitySet {
+        mainhand,
+        offhand,
+        abilities,
+        loadout,
+        stored,
+    } in ability_sets
+    {
+        let mut auxiliary_abilities =
+            vec![comp::ability::AuxiliaryAbility::Empty; comp::ability::BASE_ABILITY_LIMIT];
+        for (empty, ability) in auxiliary_abilities.iter_mut().zip(abilities.into_iter()) {
+            *empty = aux_ability_from_string(&ability);
+        }
+        let key = (
+            tool_kind_from_string(mainhand),
+            tool_kind_from_string(offhand),
+        );
+        if stored {
+            stored_loadouts.insert((key, loadout), auxiliary_abilities);
+        } else {
+            active_loadouts.insert(key, loadout);
+            active_sets.insert(key, auxiliary_abilities);
+        }
+    }
     comp::ability::ActiveAbilities::from_auxiliary(
-        ability_sets,
+        active_sets,
         Some(comp::ability::BASE_ABILITY_LIMIT),
     )
+    .with_loadouts(active_loadouts, stored_loadouts)
 }
 
 /// Struct containing item properties in the format that they get persisted to
@@ -321,17 +367,24 @@ pub fn active_abilities_from_db_model(
 pub struct DatabaseItemProperties {
     #[serde(skip_serializing_if = "Option::is_none")]
     durability: Option<NonZeroU32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heraldry: Option<Heraldry>,
 }
 
 pub fn item_properties_to_db_model(item: &comp::Item) -> DatabaseItemProperties {
     DatabaseItemProperties {
         durability: item.persistence_durability(),
+        heraldry: item.heraldry(),
     }
 }
 
 pub fn apply_db_item_properties(item: &mut comp::Item, properties: &DatabaseItemProperties) {
-    let DatabaseItemProperties { durability } = properties;
+    let DatabaseItemProperties {
+        durability,
+        heraldry,
+    } = properties;
     item.persistence_set_durability(*durability);
+    item.set_heraldry(*heraldry);
 }
 
 #[cfg(test)]
@@ -345,4 +398,34 @@ pub mod tests {
              forward compatible with migration V50.",
         );
     }
+
+    #[test]
+    fn test_ability_loadouts() {
+        use super::{active_abilities_from_db_model, active_abilities_to_db_model};
+        use common::comp::{ability::AuxiliaryAbility, tool::ToolKind};
+
+        // Sets saved before loadouts existed belong to the first loadout
+        const OLD_ABILITY_SETS: &str =
+            r#"[{"mainhand":"Sword","offhand":"None","abilities":["Main Weapon:index:1"]}]"#;
+        let sword = (Some(ToolKind::Sword), None);
+        let mut active_abilities = active_abilities_from_db_model(
+            serde_json::from_str(OLD_ABILITY_SETS).expect("Old ability sets should still load"),
+        );
+        assert_eq!(active_abilities.active_loadout(sword), 0);
+        assert_eq!(
+            active_abilities.auxiliary_sets[&sword][0],
+            AuxiliaryAbility::MainWeapon(1)
+        );
+
+        // Stored loadouts survive a round trip
+        active_abilities.active_loadouts.insert(sword, 1);
+        let first_set = active_abilities.auxiliary_sets[&sword].clone();
+        active_abilities
+            .stored_loadouts
+            .insert((sword, 0), first_set.clone());
+        let loaded =
+            active_abilities_from_db_model(active_abilities_to_db_model(&active_abilities));
+        assert_eq!(loaded.active_loadout(sword), 1);
+        assert_eq!(loaded.stored_loadouts.get(&(sword, 0)), Some(&first_set));
+    }
 }
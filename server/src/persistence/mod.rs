@@ -9,11 +9,12 @@ pub mod character_loader;
 pub mod character_updater;
 mod diesel_to_rusqlite;
 pub mod error;
+pub mod guild;
 mod json_models;
 mod models;
 
-use crate::persistence::character_updater::PetPersistenceData;
-use common::comp;
+use crate::persistence::{character_updater::PetPersistenceData, error::PersistenceError};
+use common::{character::CharacterId, comp};
 use refinery::Report;
 use rusqlite::{Connection, OpenFlags};
 use std::{
@@ -27,6 +28,7 @@ use tracing::info;
 
 // re-export waypoint parser for use to look up location names in character list
 pub(crate) use character::parse_waypoint;
+pub use character::transfer::CharacterExport;
 
 /// A struct of the components that are persisted to the DB for each character
 #[derive(Debug)]
@@ -39,6 +41,8 @@ pub struct PersistedComponents {
     pub pets: Vec<PetPersistenceData>,
     pub active_abilities: comp::ActiveAbilities,
     pub map_marker: Option<comp::MapMarker>,
+    pub unlocked_shrines: comp::UnlockedShrines,
+    pub titles: comp::Titles,
 }
 
 pub type EditableComponents = (comp::Body,);
@@ -191,6 +195,20 @@ pub fn vacuum_database(settings: &DatabaseSettings) {
     info!("Database vacuumed");
 }
 
+/// Loads the full persisted state of a character so that it can be imported
+/// on another server, see
+/// [`character_updater::CharacterUpdater::import_character`].
+///
+/// This opens its own read-only connection so that it can be called from any
+/// thread.
+pub fn export_character(
+    settings: &DatabaseSettings,
+    character_id: CharacterId,
+) -> Result<CharacterExport, PersistenceError> {
+    let conn = establish_connection(settings, ConnectionMode::ReadOnly);
+    character::transfer::export_character(character_id, &conn)
+}
+
 // These callbacks use info logging because they are never enabled by default,
 // only when explicitly turned on via CLI arguments or interactive CLI commands.
 // Setting them to anything other than info would remove the ability to get SQL
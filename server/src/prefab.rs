@@ -0,0 +1,176 @@
+use atomicwrites::{AtomicFile, OverwriteBehavior};
+use common::{
+    prefab::{Prefab, PrefabPlacement},
+    terrain::{TerrainChunk, TerrainGrid},
+    vol::{RectRasterableVol, WriteVol},
+};
+use common_state::BlockChange;
+use hashbrown::HashMap;
+use std::{io::Write as _, path::PathBuf, sync::Arc};
+use tracing::{error, info, warn};
+use vek::*;
+
+/// Bumped whenever the format of prefab files changes in an incompatible way
+const PREFAB_VERSION: u16 = 0;
+const PLACEMENTS_FILE: &str = "placements.ron";
+
+/// Prefabs saved by admins, and where they were placed in the world. Placements
+/// are applied again to every chunk that gets generated, so they survive
+/// chunks being regenerated.
+pub struct Prefabs {
+    path: PathBuf,
+    loaded: HashMap<String, Arc<Prefab>>,
+    placements: Vec<PrefabPlacement>,
+}
+
+impl Prefabs {
+    pub fn new(mut data_dir: PathBuf) -> Self {
+        data_dir.push("prefabs");
+        let path = data_dir;
+        if let Err(err) = std::fs::create_dir_all(&path) {
+            error!(?err, ?path, "Failed to create prefab directory");
+        }
+
+        let placements = match std::fs::read_to_string(path.join(PLACEMENTS_FILE)) {
+            Ok(placements) => ron::from_str(&placements).unwrap_or_else(|err| {
+                error!(?err, "Failed to parse prefab placements, ignoring them");
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        info!(
+            "Using {:?} as the prefab path, {} prefabs are placed",
+            path,
+            placements.len()
+        );
+
+        Self {
+            path,
+            loaded: HashMap::new(),
+            placements,
+        }
+    }
+
+    fn path_for(&self, name: &str) -> Result<PathBuf, String> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!(
+                "'{name}' is not a valid prefab name, only letters, digits, '_' and '-' are \
+                 allowed"
+            ));
+        }
+        Ok(self.path.join(format!("{name}.prefab")))
+    }
+
+    pub fn save(&mut self, name: &str, prefab: Prefab) -> Result<(), String> {
+        let path = self.path_for(name)?;
+        let bytes = bincode::serialize(&(PREFAB_VERSION, &prefab))
+            .map_err(|err| format!("Failed to serialize prefab: {err}"))?;
+        AtomicFile::new(path, OverwriteBehavior::AllowOverwrite)
+            .write(|file| file.write_all(&bytes))
+            .map_err(|err| format!("Failed to write prefab: {err}"))?;
+        self.loaded.insert(name.to_string(), Arc::new(prefab));
+        Ok(())
+    }
+
+    pub fn get(&mut self, name: &str) -> Result<Arc<Prefab>, String> {
+        if let Some(prefab) = self.loaded.get(name) {
+            return Ok(Arc::clone(prefab));
+        }
+        let bytes = std::fs::read(self.path_for(name)?)
+            .map_err(|err| format!("Failed to read prefab '{name}': {err}"))?;
+        let prefab = match bincode::deserialize::<(u16, Prefab)>(&bytes) {
+            Ok((PREFAB_VERSION, prefab)) => Arc::new(prefab),
+            Ok((version, _)) => {
+                return Err(format!("Prefab '{name}' has unsupported version {version}"));
+            },
+            Err(err) => return Err(format!("Failed to load prefab '{name}': {err}")),
+        };
+        self.loaded.insert(name.to_string(), Arc::clone(&prefab));
+        Ok(prefab)
+    }
+
+    /// Places a prefab into the loaded terrain and remembers the placement, so
+    /// that it is applied to chunks that aren't loaded yet too
+    pub fn place(
+        &mut self,
+        placement: PrefabPlacement,
+        terrain: &TerrainGrid,
+        block_change: &mut BlockChange,
+    ) -> Result<Aabb<i32>, String> {
+        let prefab = self.get(&placement.prefab)?;
+        let bounds = prefab.bounds(&placement);
+        for z in bounds.min.z..=bounds.max.z {
+            for y in bounds.min.y..=bounds.max.y {
+                for x in bounds.min.x..=bounds.max.x {
+                    let wpos = Vec3::new(x, y, z);
+                    if terrain.contains_key_real(terrain.pos_key(wpos))
+                        && let Some(block) = prefab.get(&placement, wpos)
+                    {
+                        block_change.set(wpos, block);
+                    }
+                }
+            }
+        }
+
+        self.placements.push(placement);
+        self.save_placements();
+        Ok(bounds)
+    }
+
+    /// Applies all placements overlapping the chunk at `key` to it, for newly
+    /// generated chunks
+    pub fn apply_to_chunk(&mut self, key: Vec2<i32>, chunk: &mut TerrainChunk) {
+        let chunk_min = key * TerrainChunk::RECT_SIZE.as_::<i32>();
+        let chunk_max = chunk_min + TerrainChunk::RECT_SIZE.as_::<i32>() - 1;
+        for i in 0..self.placements.len() {
+            let name = self.placements[i].prefab.clone();
+            let prefab = match self.get(&name) {
+                Ok(prefab) => prefab,
+                Err(err) => {
+                    warn!(?err, "Skipping placement of prefab that failed to load");
+                    continue;
+                },
+            };
+            let placement = &self.placements[i];
+            let bounds = prefab.bounds(placement);
+            let min = bounds.min.xy().map2(chunk_min, i32::max);
+            let max = bounds.max.xy().map2(chunk_max, i32::min);
+            if min.x > max.x || min.y > max.y {
+                continue;
+            }
+            for z in bounds.min.z..=bounds.max.z {
+                for y in min.y..=max.y {
+                    for x in min.x..=max.x {
+                        let wpos = Vec3::new(x, y, z);
+                        if let Some(block) = prefab.get(placement, wpos) {
+                            let rpos = wpos - chunk_min.with_z(0);
+                            let _ = chunk.set(rpos, block);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn save_placements(&self) {
+        let placements =
+            match ron::ser::to_string_pretty(&self.placements, ron::ser::PrettyConfig::default()) {
+                Ok(placements) => placements,
+                Err(err) => {
+                    error!(?err, "Failed to serialize prefab placements");
+                    return;
+                },
+            };
+        let file = AtomicFile::new(
+            self.path.join(PLACEMENTS_FILE),
+            OverwriteBehavior::AllowOverwrite,
+        );
+        if let Err(err) = file.write(|file| file.write_all(placements.as_bytes())) {
+            error!(?err, "Failed to write prefab placements");
+        }
+    }
+}
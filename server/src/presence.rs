@@ -1,3 +1,4 @@
+use common_net::msg::ResumeToken;
 use hashbrown::HashSet;
 use serde::{Deserialize, Serialize};
 use specs::{Component, VecStorage};
@@ -27,3 +28,17 @@ pub struct RepositionOnChunkLoad {
 impl Component for RepositionOnChunkLoad {
     type Storage = VecStorage<Self>;
 }
+
+/// Allows a client that lost its connection while in-game to reattach to its
+/// entity.
+#[derive(Clone, Debug)]
+pub struct ResumableSession {
+    pub token: ResumeToken,
+    /// While the client is disconnected, the program time at which the entity
+    /// is removed if no client reattached.
+    pub expires: Option<f64>,
+}
+
+impl Component for ResumableSession {
+    type Storage = specs::DenseVecStorage<Self>;
+}
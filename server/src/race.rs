@@ -0,0 +1,153 @@
+use atomicwrites::{AtomicFile, OverwriteBehavior};
+use common::{character::CharacterId, uid::Uid};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use std::{io::Write as _, path::PathBuf};
+use tracing::{error, info};
+use vek::*;
+
+const TRACKS_FILE: &str = "race_tracks.ron";
+/// Number of records shown on the leaderboard of a track
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// Best run of a character on a track
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RaceRecord {
+    pub character_id: CharacterId,
+    pub name: String,
+    pub time: f64,
+    /// Time at which every checkpoint after the first one was passed
+    pub splits: Vec<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RaceTrack {
+    /// Stable id used to tell apart the gates of different tracks
+    pub id: u32,
+    pub checkpoints: Vec<Vec3<f32>>,
+    /// Best run of every character that finished the track, fastest first
+    records: Vec<RaceRecord>,
+}
+
+impl RaceTrack {
+    pub fn leaderboard(&self) -> &[RaceRecord] {
+        &self.records[..self.records.len().min(LEADERBOARD_SIZE)]
+    }
+
+    pub fn record(&self, character_id: CharacterId) -> Option<&RaceRecord> {
+        self.records
+            .iter()
+            .find(|record| record.character_id == character_id)
+    }
+}
+
+/// A run of a character on a track that is in progress
+pub struct RaceRun {
+    pub track: String,
+    /// Index of the next checkpoint to pass
+    pub next: usize,
+    pub started_at: f64,
+    pub splits: Vec<f64>,
+}
+
+/// Race tracks placed by admins with their leaderboards, persisted in the data
+/// directory, and the runs currently in progress.
+pub struct RaceTracks {
+    path: PathBuf,
+    tracks: HashMap<String, RaceTrack>,
+    pub runs: HashMap<Uid, RaceRun>,
+}
+
+impl RaceTracks {
+    pub fn new(data_dir: PathBuf) -> Self {
+        let path = data_dir.join(TRACKS_FILE);
+        let tracks = match std::fs::read_to_string(&path) {
+            Ok(tracks) => ron::from_str(&tracks).unwrap_or_else(|err| {
+                error!(?err, "Failed to parse race tracks, ignoring them");
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        info!("Loaded {} race tracks from {:?}", tracks.len(), path);
+
+        Self {
+            path,
+            tracks,
+            runs: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RaceTrack> { self.tracks.get(name) }
+
+    pub fn tracks(&self) -> impl Iterator<Item = (&String, &RaceTrack)> { self.tracks.iter() }
+
+    /// Adds a checkpoint to the end of a track, creating the track if it
+    /// doesn't exist yet. Returns the number of checkpoints of the track.
+    ///
+    /// Changing the course of a track invalidates its leaderboard.
+    pub fn add_checkpoint(&mut self, name: &str, pos: Vec3<f32>) -> usize {
+        let next_id = self.tracks.values().map(|track| track.id + 1).max();
+        let track = self
+            .tracks
+            .entry(name.to_string())
+            .or_insert_with(|| RaceTrack {
+                id: next_id.unwrap_or(0),
+                checkpoints: Vec::new(),
+                records: Vec::new(),
+            });
+        track.checkpoints.push(pos);
+        track.records.clear();
+        let len = track.checkpoints.len();
+        self.runs.retain(|_, run| run.track != name);
+        self.save();
+        len
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), String> {
+        self.tracks
+            .remove(name)
+            .ok_or_else(|| format!("There is no race track called '{name}'"))?;
+        self.runs.retain(|_, run| run.track != name);
+        self.save();
+        Ok(())
+    }
+
+    /// Records a finished run, returning whether it is the character's new
+    /// best time on the track
+    pub fn finish(&mut self, name: &str, record: RaceRecord) -> bool {
+        let Some(track) = self.tracks.get_mut(name) else {
+            return false;
+        };
+        if let Some(previous) = track
+            .records
+            .iter()
+            .position(|previous| previous.character_id == record.character_id)
+        {
+            if track.records[previous].time <= record.time {
+                return false;
+            }
+            track.records.remove(previous);
+        }
+        let rank = track
+            .records
+            .partition_point(|other| other.time <= record.time);
+        track.records.insert(rank, record);
+        self.save();
+        true
+    }
+
+    fn save(&self) {
+        let tracks =
+            match ron::ser::to_string_pretty(&self.tracks, ron::ser::PrettyConfig::default()) {
+                Ok(tracks) => tracks,
+                Err(err) => {
+                    error!(?err, "Failed to serialize race tracks");
+                    return;
+                },
+            };
+        let file = AtomicFile::new(&self.path, OverwriteBehavior::AllowOverwrite);
+        if let Err(err) = file.write(|file| file.write_all(tracks.as_bytes())) {
+            error!(?err, "Failed to write race tracks");
+        }
+    }
+}
@@ -4,36 +4,92 @@ pub mod tick;
 
 use atomicwrites::{AtomicFile, OverwriteBehavior};
 use common::{
+    comp::item::ItemDefinitionIdOwned,
     grid::Grid,
     mounting::VolumePos,
-    rtsim::{Actor, ChunkResource, NpcId, RtSimEntity, WorldSettings},
+    resources::{Time, TimeOfDay},
+    rtsim::{
+        Actor, ChunkResource, NpcId, NpcInput, Role, RtSimEntity, RtsimChunk, RtsimChunks,
+        RtsimHookAction, RtsimHookEvent, WorldSettings,
+    },
 };
 use common_ecs::{dispatch, System};
 use common_state::BlockDiff;
-use crossbeam_channel::{unbounded, Receiver, Sender};
+use crossbeam_channel::{bounded, unbounded, Receiver, Sender, TryRecvError};
 use enum_map::EnumMap;
+use parking_lot::{Mutex, MutexGuard};
 use rtsim::{
-    data::{npc::SimulationMode, Data, ReadError},
-    event::{OnDeath, OnMountVolume, OnSetup},
+    data::{
+        npc::SimulationMode, site::BOUNTY_WEALTH_PER_COIN, Data, JournalCursor, JournalEntry, Npc,
+        ReadError, Report, ReportKind,
+    },
+    event::{HookEvents, OnDeath, OnFire, OnMountVolume, OnSetup},
     RtState,
 };
 use specs::DispatcherBuilder;
 use std::{
-    fs::{self, File},
-    io,
-    path::PathBuf,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
     thread::{self, JoinHandle},
     time::Instant,
 };
 use tracing::{debug, error, info, trace, warn};
 use vek::*;
-use world::{IndexRef, World};
+use world::{IndexOwned, IndexRef, World};
+
+/// Each save persists this fraction of all NPCs, so every NPC is saved at least
+/// once every `JOURNAL_SLICES` saves.
+const JOURNAL_SLICES: usize = 10;
+/// Number of journal entries after which the journal is compacted into a new
+/// snapshot of the rtsim data.
+const JOURNAL_COMPACT_ENTRIES: usize = 30;
+/// Minimum time, in seconds, between two rtsim ticks. Rtsim doesn't need to run
+/// at the rate of the server tick, and ticking it less often leaves more time
+/// for everything else.
+const TICK_INTERVAL: f32 = 0.1;
+
+/// A change to the rtsim state that has to wait until the tick thread is idle.
+type PendingHook = Box<dyn FnOnce(&mut RtState, &World, IndexRef) + Send + Sync>;
 
 pub struct RtSim {
     file_path: PathBuf,
     last_saved: Option<Instant>,
-    state: RtState,
-    save_thread: Option<(Sender<Data>, JoinHandle<()>)>,
+    /// Only locked by the main thread while the tick thread is idle, except for
+    /// the few operations that can't wait (see [`RtSim::state`]).
+    state: Arc<Mutex<RtState>>,
+    /// Hooks invoked since the tick thread last became idle. These are applied
+    /// before the next tick is started, so the main thread never waits for the
+    /// tick thread to pass on events.
+    pending: Vec<PendingHook>,
+    /// Time accumulated since the last tick was started.
+    tick_dt: f32,
+    /// If the tick thread couldn't be started or died, rtsim is ticked on the
+    /// main thread instead.
+    tick_thread: Option<TickThread>,
+    journal_cursor: JournalCursor,
+    save_thread: Option<(Sender<SaveMsg>, JoinHandle<()>)>,
+}
+
+struct TickThread {
+    tx: Sender<TickMsg>,
+    done: Receiver<()>,
+    busy: bool,
+}
+
+struct TickMsg {
+    world: Arc<World>,
+    index: IndexOwned,
+    time_of_day: TimeOfDay,
+    time: Time,
+    dt: f32,
+}
+
+enum SaveMsg {
+    Append(Box<JournalEntry>),
+    /// Write a new snapshot and clear the journal.
+    Compact,
 }
 
 impl RtSim {
@@ -61,7 +117,8 @@ impl RtSim {
                                      rtsim data will be purged"
                                 );
                             },
-                            Ok(data) | Err(ReadError::VersionMismatch(data)) => {
+                            Ok(mut data) | Err(ReadError::VersionMismatch(mut data)) => {
+                                replay_journal(&mut data, &Self::get_journal_path(&file_path));
                                 info!("Rtsim data loaded.");
                                 if data.should_purge {
                                     warn!(
@@ -119,21 +176,56 @@ impl RtSim {
             data
         };
 
-        let mut this = Self {
-            last_saved: None,
-            state: RtState::new(data).with_resource(ChunkStates(Grid::populate_from(
-                world.sim().get_size().as_(),
-                |_| None,
-            ))),
-            file_path,
-            save_thread: None,
+        // The save thread keeps its own copy of the data that journal entries are
+        // applied to, so that it can compact the journal without involving the main
+        // thread. It starts by writing a snapshot, which also discards the journal of
+        // any data that was purged or failed to load.
+        let save_thread = {
+            let (tx, rx) = unbounded();
+            let (file_path, persisted) = (file_path.clone(), data.clone());
+            let _ = tx.send(SaveMsg::Compact);
+            (
+                tx,
+                thread::spawn(move || save_thread(file_path, persisted, rx)),
+            )
         };
 
-        rule::start_rules(&mut this.state);
+        let mut state = RtState::new(data).with_resource(ChunkStates(Grid::populate_from(
+            world.sim().get_size().as_(),
+            |_| None,
+        )));
 
-        this.state.emit(OnSetup, world, index);
+        rule::start_rules(&mut state);
 
-        Ok(this)
+        state.emit(OnSetup, world, index);
+
+        let state = Arc::new(Mutex::new(state));
+        let tick_thread = {
+            let (tx, rx) = unbounded();
+            let (done_tx, done) = bounded(1);
+            let state = Arc::clone(&state);
+            thread::Builder::new()
+                .name("rtsim-tick".to_string())
+                .spawn(move || tick_thread(state, rx, done_tx))
+                .map_err(|e| error!("Failed to start rtsim tick thread: {}", e))
+                .ok()
+                .map(|_| TickThread {
+                    tx,
+                    done,
+                    busy: false,
+                })
+        };
+
+        Ok(Self {
+            last_saved: None,
+            state,
+            pending: Vec::new(),
+            tick_dt: 0.0,
+            tick_thread,
+            file_path,
+            journal_cursor: JournalCursor::default(),
+            save_thread: Some(save_thread),
+        })
     }
 
     fn get_file_path(mut data_dir: PathBuf) -> PathBuf {
@@ -147,63 +239,175 @@ impl RtSim {
         path
     }
 
-    pub fn hook_character_mount_volume(
-        &mut self,
-        world: &World,
-        index: IndexRef,
-        pos: VolumePos<NpcId>,
-        actor: Actor,
-    ) {
-        self.state.emit(OnMountVolume { actor, pos }, world, index)
+    fn get_journal_path(file_path: &Path) -> PathBuf { file_path.with_extension("journal") }
+
+    fn defer(&mut self, hook: impl FnOnce(&mut RtState, &World, IndexRef) + Send + Sync + 'static) {
+        self.pending.push(Box::new(hook));
+    }
+
+    pub fn hook_character_mount_volume(&mut self, pos: VolumePos<NpcId>, actor: Actor) {
+        self.defer(move |state, world, index| {
+            state.emit(OnMountVolume { actor, pos }, world, index)
+        });
     }
 
     pub fn hook_load_chunk(&mut self, key: Vec2<i32>, max_res: EnumMap<ChunkResource, usize>) {
-        if let Some(chunk_state) = self.state.get_resource_mut::<ChunkStates>().0.get_mut(key) {
-            *chunk_state = Some(LoadedChunkState { max_res });
-        }
+        self.defer(move |state, _, _| {
+            if let Some(chunk_state) = state.get_resource_mut::<ChunkStates>().0.get_mut(key) {
+                *chunk_state = Some(LoadedChunkState { max_res });
+            }
+        });
     }
 
     pub fn hook_unload_chunk(&mut self, key: Vec2<i32>) {
-        if let Some(chunk_state) = self.state.get_resource_mut::<ChunkStates>().0.get_mut(key) {
-            *chunk_state = None;
-        }
+        self.defer(move |state, _, _| {
+            if let Some(chunk_state) = state.get_resource_mut::<ChunkStates>().0.get_mut(key) {
+                *chunk_state = None;
+            }
+        });
     }
 
     // Note that this hook only needs to be invoked if the block change results in a
     // change to the rtsim resource produced by [`Block::get_rtsim_resource`].
-    pub fn hook_block_update(&mut self, world: &World, index: IndexRef, changes: Vec<BlockDiff>) {
-        self.state
-            .emit(event::OnBlockChange { changes }, world, index);
+    pub fn hook_block_update(&mut self, changes: Vec<BlockDiff>) {
+        self.defer(move |state, world, index| {
+            state.emit(event::OnBlockChange { changes }, world, index)
+        });
     }
 
     pub fn hook_rtsim_entity_unload(&mut self, entity: RtSimEntity) {
-        let data = self.state.get_data_mut();
+        self.defer(move |state, _, _| {
+            let data = state.get_data_mut();
 
-        if let Some(npc) = data.npcs.get_mut(entity.0) {
-            if matches!(npc.mode, SimulationMode::Simulated) {
-                error!("Unloaded already unloaded entity");
+            if let Some(npc) = data.npcs.get_mut(entity.0) {
+                if matches!(npc.mode, SimulationMode::Simulated) {
+                    error!("Unloaded already unloaded entity");
+                }
+                npc.mode = SimulationMode::Simulated;
             }
-            npc.mode = SimulationMode::Simulated;
-        }
+        });
     }
 
     pub fn hook_rtsim_actor_death(
         &mut self,
-        world: &World,
-        index: IndexRef,
         actor: Actor,
         wpos: Option<Vec3<f32>>,
         killer: Option<Actor>,
     ) {
-        self.state.emit(
-            OnDeath {
-                wpos,
-                actor,
-                killer,
-            },
-            world,
-            index,
-        );
+        self.defer(move |state, world, index| {
+            state.emit(
+                OnDeath {
+                    wpos,
+                    actor,
+                    killer,
+                },
+                world,
+                index,
+            )
+        });
+    }
+
+    /// Takes the reward of a completed bounty out of the funds of the town
+    /// that posted it.
+    pub fn hook_bounty_paid(&mut self, world_site: common::trade::SiteId, reward: u32) {
+        self.defer(move |state, _, _| {
+            let data = state.get_data_mut();
+            if let Some(site) = data
+                .sites
+                .values_mut()
+                .find(|site| site.world_site.map(|id| id.id()) == Some(world_site))
+            {
+                site.wealth = (site.wealth - reward as f32 * BOUNTY_WEALTH_PER_COIN).max(0.0);
+            }
+        });
+    }
+
+    /// Starts or ends the market day of a town, scheduled by the server's event
+    /// calendar.
+    pub fn hook_market_day(&mut self, site_name: String, active: bool) {
+        self.defer(move |state, _, index| {
+            let data = state.get_data_mut();
+            let time = data.time_of_day.0;
+            if let Some(site) = find_site_by_name(data, index, &site_name)
+                && site.market_day != active
+            {
+                site.market_day = active;
+                if active {
+                    // Traders restock for the occasion
+                    site.last_delivery = time;
+                }
+            }
+        });
+    }
+
+    /// Starts or ends the festival of a town, scheduled by the server's event
+    /// calendar.
+    pub fn hook_festival(&mut self, site_name: String, active: bool) {
+        self.defer(move |state, _, index| {
+            let data = state.get_data_mut();
+            let time = data.time_of_day.0;
+            if let Some(site) = find_site_by_name(data, index, &site_name)
+                && site.festival != active
+            {
+                site.festival = active;
+                if active {
+                    site.last_delivery = time;
+                }
+            }
+        });
+    }
+
+    /// Marks the towns whose festival is coming up on the server's event
+    /// calendar, so that word of it gets around.
+    pub fn hook_upcoming_festivals(&mut self, site_names: Vec<String>) {
+        self.defer(move |state, _, index| {
+            let data = state.get_data_mut();
+            for site in data.sites.values_mut() {
+                site.festival_upcoming = false;
+            }
+            for site_name in &site_names {
+                if let Some(site) = find_site_by_name(data, index, site_name) {
+                    site.festival_upcoming = true;
+                }
+            }
+        });
+    }
+
+    /// Persists the wares of a trading NPC after players bought or sold some.
+    pub fn hook_shop_trade(
+        &mut self,
+        entity: RtSimEntity,
+        wares: Vec<(ItemDefinitionIdOwned, u32)>,
+    ) {
+        self.defer(move |state, _, _| {
+            if let Some(shop) = state
+                .get_data_mut()
+                .npcs
+                .get_mut(entity.0)
+                .and_then(|npc| npc.shop.as_mut())
+            {
+                shop.wares = wares;
+            }
+        });
+    }
+
+    pub fn hook_fire(&mut self, wpos: Vec3<f32>) {
+        self.defer(move |state, world, index| state.emit(OnFire { wpos }, world, index));
+    }
+
+    /// Take the events that happened since the last call, to be passed on to
+    /// plugins. While the tick thread is busy, the events are left for a later
+    /// call instead.
+    pub fn take_hook_events(&mut self) -> Vec<RtsimHookEvent> {
+        match self.state.try_lock() {
+            Some(mut state) => std::mem::take(&mut state.get_resource_mut::<HookEvents>().0),
+            None => Vec::new(),
+        }
+    }
+
+    /// Apply actions that plugins took in response to rtsim events.
+    pub fn hook_plugin_actions(&mut self, actions: Vec<RtsimHookAction>) {
+        self.defer(move |state, _, _| apply_plugin_actions(state.get_data_mut(), actions));
     }
 
     pub fn save(&mut self, wait_until_finished: bool) {
@@ -217,14 +421,32 @@ impl RtSim {
         let (tx, _) = self.save_thread.get_or_insert_with(|| {
             trace!("Starting rtsim data save thread...");
             let (tx, rx) = unbounded();
-            let file_path = self.file_path.clone();
-            (tx, thread::spawn(move || save_thread(file_path, rx)))
+            let (file_path, persisted) = (self.file_path.clone(), self.state.lock().data().clone());
+            (
+                tx,
+                thread::spawn(move || save_thread(file_path, persisted, rx)),
+            )
         });
 
-        // Send rtsim data to the save thread
-        if let Err(err) = tx.send(self.state.data().clone()) {
+        // Only send a slice of the NPCs to the save thread to avoid cloning all of
+        // the rtsim data at once. When waiting for the save to finish everything is
+        // persisted and written to a fresh snapshot instead.
+        let slices = if wait_until_finished {
+            1
+        } else {
+            JOURNAL_SLICES
+        };
+        let entry = self
+            .state
+            .lock()
+            .data_mut()
+            .journal_entry(&mut self.journal_cursor, slices);
+        if let Err(err) = tx.send(SaveMsg::Append(Box::new(entry))) {
             error!("Failed to perform rtsim save: {}", err);
         }
+        if wait_until_finished {
+            let _ = tx.send(SaveMsg::Compact);
+        }
 
         // If we need to wait until the save thread has done its work (due to, for
         // example, server shutdown) then do that.
@@ -241,28 +463,249 @@ impl RtSim {
     }
 
     // TODO: Clean up this API a bit
+    // NOTE: This waits for the tick thread if it is busy
     pub fn get_chunk_resources(&self, key: Vec2<i32>) -> EnumMap<ChunkResource, f32> {
-        self.state.data().nature.get_chunk_resources(key)
+        self.state.lock().data().nature.get_chunk_resources(key)
+    }
+
+    /// The rtsim state of a chunk and its neighbours, used to generate it.
+    // NOTE: This waits for the tick thread if it is busy
+    pub fn get_chunks(&self, key: Vec2<i32>) -> RtsimChunks {
+        let state = self.state.lock();
+        let data = state.data();
+        RtsimChunks::new(key, |key| RtsimChunk {
+            resources: data.nature.get_chunk_resources(key),
+            road: data.roads.road_at(key),
+            wreck: data.wrecks.wreck_at(key),
+        })
     }
 
-    pub fn state(&self) -> &RtState { &self.state }
+    /// Locks the rtsim state, waiting for the tick thread to finish its current
+    /// tick if necessary. Avoid holding on to it for long.
+    pub fn state(&self) -> MutexGuard<'_, RtState> { self.state.lock() }
 
     pub fn set_should_purge(&mut self, should_purge: bool) {
-        self.state.data_mut().should_purge = should_purge;
+        self.state.lock().data_mut().should_purge = should_purge;
+    }
+
+    /// Returns whether the tick thread is done with the last tick, in which
+    /// case the state can be locked without waiting.
+    fn poll_tick_thread(&mut self) -> bool {
+        let Some(tick_thread) = &mut self.tick_thread else {
+            return true;
+        };
+        if tick_thread.busy {
+            match tick_thread.done.try_recv() {
+                Ok(()) => tick_thread.busy = false,
+                Err(TryRecvError::Empty) => return false,
+                Err(TryRecvError::Disconnected) => {
+                    error!("Rtsim tick thread died, ticking rtsim on the main thread instead");
+                    self.tick_thread = None;
+                },
+            }
+        }
+        true
+    }
+
+    /// Applies the hooks invoked since the last call. Must only be called while
+    /// the tick thread is idle.
+    fn apply_pending(&mut self, world: &World, index: IndexRef) {
+        let mut state = self.state.lock();
+        for hook in self.pending.drain(..) {
+            hook(&mut state, world, index);
+        }
+    }
+
+    /// Starts the next tick once enough time passed since the previous one.
+    /// Must only be called while the tick thread is idle.
+    fn start_tick(
+        &mut self,
+        world: &Arc<World>,
+        index: &IndexOwned,
+        time_of_day: TimeOfDay,
+        time: Time,
+    ) {
+        if self.tick_dt < TICK_INTERVAL {
+            return;
+        }
+        let dt = std::mem::take(&mut self.tick_dt);
+
+        if let Some(tick_thread) = &mut self.tick_thread {
+            let msg = TickMsg {
+                world: Arc::clone(world),
+                index: index.clone(),
+                time_of_day,
+                time,
+                dt,
+            };
+            if tick_thread.tx.send(msg).is_ok() {
+                tick_thread.busy = true;
+                return;
+            }
+            error!("Rtsim tick thread died, ticking rtsim on the main thread instead");
+            self.tick_thread = None;
+        }
+        self.state
+            .lock()
+            .tick(world, index.as_index_ref(), time_of_day, time, dt);
     }
 }
 
-fn save_thread(file_path: PathBuf, rx: Receiver<Data>) {
+/// The rtsim site linked to the world site with the given name, used by events
+/// of the server's event calendar.
+fn find_site_by_name<'a>(
+    data: &'a mut Data,
+    index: IndexRef,
+    site_name: &str,
+) -> Option<&'a mut rtsim::data::Site> {
+    let Some((world_site, _)) = index
+        .sites
+        .iter()
+        .find(|(_, site)| site.name() == site_name)
+    else {
+        warn!("Event scheduled for unknown site {site_name:?}");
+        return None;
+    };
+    data.sites
+        .values_mut()
+        .find(|site| site.world_site == Some(world_site))
+}
+
+fn tick_thread(state: Arc<Mutex<RtState>>, rx: Receiver<TickMsg>, done: Sender<()>) {
+    while let Ok(msg) = rx.recv() {
+        common_base::prof_span!("rtsim tick");
+        state.lock().tick(
+            &msg.world,
+            msg.index.as_index_ref(),
+            msg.time_of_day,
+            msg.time,
+            msg.dt,
+        );
+        if done.send(()).is_err() {
+            break;
+        }
+    }
+}
+
+/// Apply actions that plugins took in response to rtsim events.
+fn apply_plugin_actions(data: &mut Data, actions: Vec<RtsimHookAction>) {
+    for action in actions {
+        match action {
+            RtsimHookAction::SpawnNpc { wpos, body, home } => {
+                match ron::from_str::<common::comp::Body>(&body) {
+                    Ok(body) => {
+                        let home = home.filter(|home| data.sites.contains_key(*home));
+                        let role = if home.is_some() {
+                            Role::Civilised(None)
+                        } else {
+                            Role::Wild
+                        };
+                        data.spawn_npc(Npc::new(rand::random(), wpos, body, role).with_home(home));
+                    },
+                    Err(e) => warn!(?e, "Plugin tried to spawn an NPC with an invalid body"),
+                }
+            },
+            RtsimHookAction::ReportDeath {
+                actor,
+                killer,
+                wpos,
+            } => {
+                let nearby = data
+                    .npcs
+                    .nearby(None, wpos, 32.0)
+                    .filter_map(|actor| actor.npc())
+                    .collect::<Vec<_>>();
+                let report = data.reports.create(Report {
+                    kind: ReportKind::Death { actor, killer },
+                    at: data.time_of_day,
+                });
+                for npc_id in nearby {
+                    if let Some(npc) = data.npcs.get_mut(npc_id) {
+                        npc.inbox.push_back(NpcInput::Report(report));
+                    }
+                }
+            },
+            RtsimHookAction::ModifySentiment {
+                npc,
+                target,
+                change,
+            } => {
+                if let Some(npc) = data.npcs.get_mut(npc) {
+                    npc.sentiments.toward_mut(target).change_by(change, 1.0);
+                }
+            },
+        }
+    }
+}
+
+fn replay_journal(data: &mut Data, journal_path: &Path) {
+    match File::open(journal_path) {
+        Ok(file) => {
+            let mut entries = 0;
+            for entry in JournalEntry::read_all(io::BufReader::new(file)) {
+                data.apply_journal(entry);
+                entries += 1;
+            }
+            info!("Replayed {} rtsim journal entries.", entries);
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+        Err(e) => error!("Failed to open rtsim journal: {}", e),
+    }
+}
+
+fn save_thread(file_path: PathBuf, mut persisted: Data, rx: Receiver<SaveMsg>) {
     if let Some(dir) = file_path.parent() {
         let _ = fs::create_dir_all(dir);
     }
 
+    let journal_path = RtSim::get_journal_path(&file_path);
     let atomic_file = AtomicFile::new(file_path, OverwriteBehavior::AllowOverwrite);
-    while let Ok(data) = rx.recv() {
-        debug!("Writing rtsim data to file...");
-        match atomic_file.write(move |file| data.write_to(io::BufWriter::new(file))) {
-            Ok(_) => debug!("Rtsim data saved."),
-            Err(e) => error!("Saving rtsim data failed: {}", e),
+    let mut journal_entries = 0;
+    while let Ok(msg) = rx.recv() {
+        let compact = match msg {
+            SaveMsg::Append(entry) => {
+                debug!("Appending rtsim data to journal...");
+                let res = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&journal_path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|file| {
+                        let mut writer = io::BufWriter::new(file);
+                        entry.write_to(&mut writer).map_err(|e| e.to_string())?;
+                        writer.flush().map_err(|e| e.to_string())
+                    });
+                persisted.apply_journal(*entry);
+                match res {
+                    Ok(()) => {
+                        debug!("Rtsim data saved.");
+                        journal_entries += 1;
+                        journal_entries >= JOURNAL_COMPACT_ENTRIES
+                    },
+                    Err(e) => {
+                        // The entry is still part of the persisted data, so compacting
+                        // recovers from the failure.
+                        error!("Saving rtsim journal failed: {}", e);
+                        true
+                    },
+                }
+            },
+            SaveMsg::Compact => true,
+        };
+
+        if compact {
+            debug!("Writing rtsim data to file...");
+            match atomic_file.write(|file| persisted.write_to(io::BufWriter::new(file))) {
+                Ok(_) => {
+                    // The snapshot contains everything in the journal
+                    if let Err(e) = File::create(&journal_path) {
+                        error!("Failed to clear rtsim journal: {}", e);
+                    }
+                    journal_entries = 0;
+                    debug!("Rtsim data compacted.");
+                },
+                Err(e) => error!("Saving rtsim data failed: {}", e),
+            }
         }
     }
 }
@@ -3,54 +3,84 @@
 use super::*;
 use crate::sys::terrain::SpawnEntityData;
 use common::{
+    assets::AssetExt,
     calendar::Calendar,
-    comp::{self, Body, Presence, PresenceKind},
+    comp::{
+        self,
+        item::{tool::AbilityMap, ItemDefinitionIdOwned, MaterialStatManifest, Quality},
+        Body, Presence, PresenceKind,
+    },
     event::{CreateNpcEvent, CreateShipEvent, DeleteEvent, EventBus, NpcBuilder},
     generation::{BodyBuilder, EntityConfig, EntityInfo},
     resources::{DeltaTime, Time, TimeOfDay},
-    rtsim::{Actor, NpcId, RtSimEntity},
+    rtsim::{Actor, NpcId, NpcInput, ReputationTier, RtSimEntity, RtsimHookEvent},
     slowjob::SlowJobPool,
     terrain::CoordinateConversions,
     trade::{Good, SiteInformation},
     util::Dir,
+    weather::WeatherGrid,
     LoadoutBuilder,
 };
 use common_ecs::{Job, Origin, Phase, System};
-use rtsim::data::{
-    npc::{Profession, SimulationMode},
-    Npc, Sites,
+use rand::Rng;
+use rtsim::{
+    data::{
+        npc::{Profession, ShopStock, SimulationMode},
+        Factions, Npc, Sites,
+    },
+    event::HookEvents,
 };
 use specs::{Entities, Join, LendJoin, Read, ReadExpect, ReadStorage, WriteExpect, WriteStorage};
 use std::{sync::Arc, time::Duration};
 use tracing::error;
-use world::site::settlement::trader_loadout;
+use world::site::settlement::{trader_capacity, trader_loadout, trader_wares};
 
-fn humanoid_config(profession: &Profession) -> &'static str {
+/// `equipment_tier` is provided by the home site of the NPC, see
+/// [`rtsim::data::Site::equipment_tier`].
+fn humanoid_config(profession: &Profession, equipment_tier: u8) -> &'static str {
     match profession {
         Profession::Farmer => "common.entity.village.farmer",
         Profession::Hunter => "common.entity.village.hunter",
         Profession::Herbalist => "common.entity.village.herbalist",
         Profession::Captain => "common.entity.village.captain",
         Profession::Merchant => "common.entity.village.merchant",
-        Profession::Guard => "common.entity.village.guard",
-        Profession::Adventurer(rank) => match rank {
-            0 => "common.entity.world.traveler0",
-            1 => "common.entity.world.traveler1",
-            2 => "common.entity.world.traveler2",
-            3 => "common.entity.world.traveler3",
-            _ => {
+        Profession::Banker => "common.entity.village.banker",
+        Profession::Trainer => "common.entity.village.trainer",
+        Profession::Guard => match equipment_tier {
+            0 => "common.entity.village.militia",
+            1 => "common.entity.village.guard",
+            _ => "common.entity.village.guard_elite",
+        },
+        Profession::Adventurer(rank) => {
+            let rank = if *rank > 3 {
                 error!(
                     "Tried to get configuration for invalid adventurer rank {}",
                     rank
                 );
-                "common.entity.world.traveler3"
-            },
+                3
+            } else {
+                *rank
+            };
+            // Adventurers of poor sites can't afford the gear of their rank, while
+            // wealthy sites equip them one rank better
+            match (rank + u32::from(equipment_tier)).saturating_sub(1).min(3) {
+                0 => "common.entity.world.traveler0",
+                1 => "common.entity.world.traveler1",
+                2 => "common.entity.world.traveler2",
+                _ => "common.entity.world.traveler3",
+            }
         },
         Profession::Blacksmith => "common.entity.village.blacksmith",
         Profession::Chef => "common.entity.village.chef",
         Profession::Alchemist => "common.entity.village.alchemist",
         Profession::Pirate => "common.entity.spot.pirate",
         Profession::Cultist => "common.entity.dungeon.cultist.cultist",
+        Profession::Necromancer => "common.entity.wild.aggressive.necromancer",
+        Profession::Woodcutter => "common.entity.village.woodcutter",
+        Profession::Miner => "common.entity.village.miner",
+        Profession::Stonecutter => "common.entity.village.stonecutter",
+        Profession::Dockworker => "common.entity.village.dockworker",
+        Profession::Crier => "common.entity.village.crier",
     }
 }
 
@@ -62,75 +92,120 @@ fn loadout_default(
     loadout
 }
 
-fn merchant_loadout(
+/// Equips traders with the bags their wares are carried in. The wares
+/// themselves are persisted by rtsim, see [`stock_shop`].
+fn shop_loadout(
     loadout_builder: LoadoutBuilder,
-    economy: Option<&SiteInformation>,
+    _economy: Option<&SiteInformation>,
     _time: Option<&(TimeOfDay, Calendar)>,
 ) -> LoadoutBuilder {
-    trader_loadout(loadout_builder, economy, |_| true)
+    trader_loadout(loadout_builder, None, |_| false)
 }
 
-fn farmer_loadout(
+fn treasure_map_seller_loadout(
     loadout_builder: LoadoutBuilder,
-    economy: Option<&SiteInformation>,
+    _economy: Option<&SiteInformation>,
     _time: Option<&(TimeOfDay, Calendar)>,
 ) -> LoadoutBuilder {
-    trader_loadout(loadout_builder, economy, |good| matches!(good, Good::Food))
+    let mut backpack = comp::Item::new_from_asset_expect("common.items.armor.misc.back.backpack");
+    if let Some(slot) = backpack.slots_mut().first_mut() {
+        *slot = Some(comp::Item::new_from_asset_expect(
+            "common.items.utility.treasure_map",
+        ));
+    }
+    loadout_builder.back(Some(backpack))
 }
 
-fn herbalist_loadout(
-    loadout_builder: LoadoutBuilder,
-    economy: Option<&SiteInformation>,
-    _time: Option<&(TimeOfDay, Calendar)>,
-) -> LoadoutBuilder {
-    trader_loadout(loadout_builder, economy, |good| {
-        matches!(good, Good::Ingredients)
-    })
-}
+/// Chance for an adventurer to have picked up a treasure map on their travels
+/// that they're willing to sell
+const TREASURE_MAP_SELLER_CHANCE: f32 = 0.05;
 
-fn chef_loadout(
-    loadout_builder: LoadoutBuilder,
-    economy: Option<&SiteInformation>,
-    _time: Option<&(TimeOfDay, Calendar)>,
-) -> LoadoutBuilder {
-    trader_loadout(loadout_builder, economy, |good| matches!(good, Good::Food))
+/// Goods sold by NPCs of a profession, if they trade at all
+fn shop_goods(profession: &Profession) -> Option<fn(Good) -> bool> {
+    match profession {
+        Profession::Merchant => Some(|_| true),
+        Profession::Farmer | Profession::Chef => Some(|good| matches!(good, Good::Food)),
+        Profession::Herbalist => Some(|good| matches!(good, Good::Ingredients)),
+        Profession::Blacksmith => Some(|good| matches!(good, Good::Tools | Good::Armor)),
+        Profession::Alchemist => Some(|good| matches!(good, Good::Potions)),
+        _ => None,
+    }
 }
 
-fn blacksmith_loadout(
-    loadout_builder: LoadoutBuilder,
-    economy: Option<&SiteInformation>,
-    _time: Option<&(TimeOfDay, Calendar)>,
-) -> LoadoutBuilder {
-    trader_loadout(loadout_builder, economy, |good| {
-        matches!(good, Good::Tools | Good::Armor)
-    })
-}
+/// Time of day after which traders replace their rare wares with new ones
+const SHOP_ROTATION_PERIOD: f64 = 3.0 * 24.0 * 60.0 * 60.0;
 
-fn alchemist_loadout(
-    loadout_builder: LoadoutBuilder,
-    economy: Option<&SiteInformation>,
-    _time: Option<&(TimeOfDay, Calendar)>,
-) -> LoadoutBuilder {
-    trader_loadout(loadout_builder, economy, |good| {
-        matches!(good, Good::Potions)
-    })
+/// How much more of each ware traders stock on their site's market days and
+/// festivals
+const MARKET_DAY_STOCK_MULTIPLIER: u32 = 2;
+
+fn is_rare_ware(ware: &ItemDefinitionIdOwned) -> bool {
+    comp::Item::new_from_item_definition_id(
+        ware.as_ref(),
+        &AbilityMap::load().read(),
+        &MaterialStatManifest::load().read(),
+    )
+    .map_or(false, |item| item.quality() >= Quality::High)
 }
 
-fn profession_extra_loadout(
-    profession: Option<&Profession>,
-) -> fn(
-    LoadoutBuilder,
-    Option<&SiteInformation>,
-    time: Option<&(TimeOfDay, Calendar)>,
-) -> LoadoutBuilder {
-    match profession {
-        Some(Profession::Merchant) => merchant_loadout,
-        Some(Profession::Farmer) => farmer_loadout,
-        Some(Profession::Herbalist) => herbalist_loadout,
-        Some(Profession::Chef) => chef_loadout,
-        Some(Profession::Blacksmith) => blacksmith_loadout,
-        Some(Profession::Alchemist) => alchemist_loadout,
-        _ => loadout_default,
+/// Gives traders their wares before they are loaded. Wares are only picked
+/// from the economy of the trader's site the first time; afterwards they only
+/// deplete as players buy them, until a travelling merchant delivers goods to
+/// the site. Rare wares are rotated every few days.
+fn stock_shop(npc: &mut Npc, sites: &Sites, index: IndexRef, time_of_day: f64) {
+    let Some(goods) = npc.profession().as_ref().and_then(shop_goods) else {
+        return;
+    };
+    let home = npc.home.and_then(|home| sites.get(home));
+    let economy = home.and_then(|site| {
+        let world_site = site.world_site?;
+        index
+            .sites
+            .get(world_site)
+            .trade_information(world_site.id())
+    });
+    // Traders bring out extra stock for market days and festivals
+    let stock_multiplier = if home.map_or(false, |site| site.market_day || site.festival) {
+        MARKET_DAY_STOCK_MULTIPLIER
+    } else {
+        1
+    };
+    let fresh_wares = || {
+        let mut wares = trader_wares(economy.as_ref(), goods);
+        for (_, amount) in &mut wares {
+            *amount *= stock_multiplier;
+        }
+        wares
+    };
+    let capacity = trader_capacity();
+
+    let shop = npc.shop.get_or_insert_with(|| ShopStock {
+        wares: fresh_wares(),
+        restocked_at: time_of_day,
+        rotated_at: time_of_day,
+    });
+
+    if home.map_or(false, |site| site.last_delivery > shop.restocked_at) {
+        for (ware, amount) in fresh_wares() {
+            match shop.wares.iter_mut().find(|(other, _)| *other == ware) {
+                Some((_, stock)) => *stock = (*stock).max(amount),
+                None if shop.wares.len() < capacity => shop.wares.push((ware, amount)),
+                None => {},
+            }
+        }
+        shop.restocked_at = time_of_day;
+    }
+
+    if time_of_day - shop.rotated_at >= SHOP_ROTATION_PERIOD {
+        shop.wares.retain(|(ware, _)| !is_rare_ware(ware));
+        let room = capacity.saturating_sub(shop.wares.len());
+        shop.wares.extend(
+            fresh_wares()
+                .into_iter()
+                .filter(|(ware, _)| is_rare_ware(ware))
+                .take(room),
+        );
+        shop.rotated_at = time_of_day;
     }
 }
 
@@ -145,6 +220,9 @@ fn profession_agent_mark(profession: Option<&Profession>) -> Option<comp::agent:
             | Profession::Alchemist,
         ) => Some(comp::agent::Mark::Merchant),
         Some(Profession::Guard) => Some(comp::agent::Mark::Guard),
+        Some(Profession::Banker) => Some(comp::agent::Mark::Banker),
+        Some(Profession::Trainer) => Some(comp::agent::Mark::Trainer),
+        Some(Profession::Necromancer) => Some(comp::agent::Mark::Necromancer),
         _ => None,
     }
 }
@@ -152,6 +230,7 @@ fn profession_agent_mark(profession: Option<&Profession>) -> Option<comp::agent:
 fn get_npc_entity_info(
     npc: &Npc,
     sites: &Sites,
+    factions: &Factions,
     index: IndexRef,
     time: Option<&(TimeOfDay, Calendar)>,
 ) -> EntityInfo {
@@ -164,21 +243,57 @@ fn get_npc_entity_info(
             index.sites.get(site).trade_information(site.id())
         });
 
-        let config_asset = humanoid_config(&profession);
+        let equipment_tier = npc
+            .home
+            .and_then(|home| sites.get(home))
+            .map_or(1, |site| site.equipment_tier(factions));
+        let config_asset = humanoid_config(&profession, equipment_tier);
+        let sells_treasure_map = matches!(profession, Profession::Adventurer(_))
+            && npc
+                .rng(Npc::PERM_WARES)
+                .gen_bool(TREASURE_MAP_SELLER_CHANCE as f64);
 
         let entity_config = EntityConfig::from_asset_expect_owned(config_asset)
             .with_body(BodyBuilder::Exact(npc.body));
-        EntityInfo::at(pos.0)
+        let mut entity_info = EntityInfo::at(pos.0)
             .with_entity_config(entity_config, Some(config_asset), &mut rng, time)
-            .with_alignment(if matches!(profession, Profession::Cultist) {
-                comp::Alignment::Enemy
+            .with_alignment(
+                if matches!(profession, Profession::Cultist | Profession::Necromancer) {
+                    comp::Alignment::Enemy
+                } else {
+                    comp::Alignment::Npc
+                },
+            )
+            .with_economy(economy.as_ref())
+            .with_lazy_loadout(if sells_treasure_map {
+                treasure_map_seller_loadout
+            } else if npc.shop.is_some() {
+                shop_loadout
             } else {
-                comp::Alignment::Npc
+                loadout_default
             })
-            .with_economy(economy.as_ref())
-            .with_lazy_loadout(profession_extra_loadout(Some(&profession)))
             .with_alias(npc.get_name())
-            .with_agent_mark(profession_agent_mark(Some(&profession)))
+            .with_agent_mark(if sells_treasure_map {
+                Some(comp::agent::Mark::Merchant)
+            } else {
+                profession_agent_mark(Some(&profession))
+            });
+        // Traders carry their wares in the bags of their loadout
+        if let Some(shop) = &npc.shop {
+            let ability_map = &AbilityMap::load().read();
+            let msm = &MaterialStatManifest::load().read();
+            entity_info.inventory = shop
+                .wares
+                .iter()
+                .filter_map(|(ware, amount)| {
+                    let item =
+                        comp::Item::new_from_item_definition_id(ware.as_ref(), ability_map, msm)
+                            .ok()?;
+                    Some((*amount, item))
+                })
+                .collect();
+        }
+        entity_info
     } else {
         let config_asset = match npc.body {
             Body::BirdLarge(body) => match body.species {
@@ -219,6 +334,17 @@ fn get_npc_entity_info(
                 },
                 species => unimplemented!("rtsim spawning for {:?}", species),
             },
+            Body::BipedSmall(body) => match body.species {
+                comp::biped_small::Species::Husk => "common.entity.dungeon.cultist.husk",
+                species => unimplemented!("rtsim spawning for {:?}", species),
+            },
+            Body::QuadrupedMedium(body) => match body.species {
+                comp::quadruped_medium::Species::Deer => "common.entity.wild.peaceful.deer",
+                comp::quadruped_medium::Species::Moose => "common.entity.wild.peaceful.moose",
+                comp::quadruped_medium::Species::Antelope => "common.entity.wild.peaceful.antelope",
+                comp::quadruped_medium::Species::Wolf => "common.entity.wild.aggressive.wolf",
+                species => unimplemented!("rtsim spawning for {:?}", species),
+            },
             body => unimplemented!("rtsim spawning for {:?}", body),
         };
         let entity_config = EntityConfig::from_asset_expect_owned(config_asset)
@@ -248,6 +374,7 @@ impl<'a> System<'a> for Sys {
         WriteStorage<'a, comp::Agent>,
         ReadStorage<'a, Presence>,
         ReadExpect<'a, Calendar>,
+        Option<Read<'a, WeatherGrid>>,
     );
 
     const NAME: &'static str = "rtsim::tick";
@@ -273,6 +400,7 @@ impl<'a> System<'a> for Sys {
             mut agents,
             presences,
             calendar,
+            weather_grid,
         ): Self::SystemData,
     ) {
         let mut create_ship_emitter = create_ship_events.emitter();
@@ -281,9 +409,24 @@ impl<'a> System<'a> for Sys {
         let rtsim = &mut *rtsim;
         let calendar_data = (*time_of_day, (*calendar).clone());
 
+        // The results of the tick in progress aren't available yet, try again on the
+        // next server tick
+        rtsim.tick_dt += dt.0;
+        if !rtsim.poll_tick_thread() {
+            return;
+        }
+
+        rtsim.apply_pending(&world, index.as_index_ref());
+        let state = rtsim.state.lock();
+
         // Set up rtsim inputs
         {
-            let mut data = rtsim.state.data_mut();
+            // Update the weather, so that NPCs can plan around it
+            if let Some(weather_grid) = weather_grid.as_deref() {
+                *state.resource_mut::<WeatherGrid>() = weather_grid.clone();
+            }
+
+            let mut data = state.data_mut();
 
             // Update time of day
             data.time_of_day = *time_of_day;
@@ -303,83 +446,75 @@ impl<'a> System<'a> for Sys {
             }
         }
 
-        // Tick rtsim
-        rtsim
-            .state
-            .tick(&world, index.as_index_ref(), *time_of_day, *time, dt.0);
-
-        // Perform a save if required
-        if rtsim
-            .last_saved
-            .map_or(true, |ls| ls.elapsed() > Duration::from_secs(60))
+        // Load and synchronise NPCs with the results of the previous tick
         {
-            // TODO: Use slow jobs
-            let _ = slow_jobs;
-            rtsim.save(/* &slow_jobs, */ false);
-        }
-
-        let chunk_states = rtsim.state.resource::<ChunkStates>();
-        let data = &mut *rtsim.state.data_mut();
-
-        let mut create_event = |id: NpcId, npc: &Npc, steering: Option<NpcBuilder>| match npc.body {
-            Body::Ship(body) => {
-                create_ship_emitter.emit(CreateShipEvent {
-                    pos: comp::Pos(npc.wpos),
-                    ori: comp::Ori::from(Dir::new(npc.dir.with_z(0.0))),
-                    ship: body,
-                    rtsim_entity: Some(RtSimEntity(id)),
-                    driver: steering,
-                });
-            },
-            _ => {
-                let entity_info = get_npc_entity_info(
-                    npc,
-                    &data.sites,
-                    index.as_index_ref(),
-                    Some(&calendar_data),
-                );
-
-                let (mut npc_builder, pos) = SpawnEntityData::from_entity_info(entity_info)
-                    .into_npc_data_inner()
-                    .expect("Entity loaded from assets cannot be special")
-                    .to_npc_builder();
-
-                if let Some(agent) = &mut npc_builder.agent {
-                    agent.rtsim_outbox = Some(Default::default());
-                }
-
-                create_npc_emitter.emit(CreateNpcEvent {
-                    pos,
-                    ori: comp::Ori::from(Dir::new(npc.dir.with_z(0.0))),
-                    npc: npc_builder.with_rtsim(RtSimEntity(id)),
-                    rider: steering,
-                });
-            },
-        };
-
-        // Load in mounted npcs and their riders
-        for mount in data.npcs.mounts.iter_mounts() {
-            let mount_npc = data.npcs.npcs.get_mut(mount).expect("This should exist");
-            let chunk = mount_npc.wpos.xy().as_::<i32>().wpos_to_cpos();
-
-            if matches!(mount_npc.mode, SimulationMode::Simulated)
-                && chunk_states.0.get(chunk).map_or(false, |c| c.is_some())
-            {
-                mount_npc.mode = SimulationMode::Loaded;
-
-                let mut actor_info = |actor: Actor| {
-                    let npc_id = actor.npc()?;
-                    let npc = data.npcs.npcs.get_mut(npc_id)?;
-                    if matches!(npc.mode, SimulationMode::Simulated) {
-                        npc.mode = SimulationMode::Loaded;
+            let chunk_states = state.resource::<ChunkStates>();
+            let mut hook_events = state.resource_mut::<HookEvents>();
+            let data = &mut *state.data_mut();
+
+            let mut create_event =
+                |id: NpcId, npc: &Npc, steering: Option<NpcBuilder>| match npc.body {
+                    Body::Ship(body) => {
+                        create_ship_emitter.emit(CreateShipEvent {
+                            pos: comp::Pos(npc.wpos),
+                            ori: comp::Ori::from(Dir::new(npc.dir.with_z(0.0))),
+                            ship: body,
+                            rtsim_entity: Some(RtSimEntity(id)),
+                            driver: steering,
+                        });
+                    },
+                    _ => {
                         let entity_info = get_npc_entity_info(
                             npc,
                             &data.sites,
+                            &data.factions,
                             index.as_index_ref(),
                             Some(&calendar_data),
                         );
 
-                        let mut npc_builder = SpawnEntityData::from_entity_info(entity_info)
+                        let (mut npc_builder, pos) = SpawnEntityData::from_entity_info(entity_info)
+                            .into_npc_data_inner()
+                            .expect("Entity loaded from assets cannot be special")
+                            .to_npc_builder();
+
+                        if let Some(agent) = &mut npc_builder.agent {
+                            agent.rtsim_outbox = Some(Default::default());
+                        }
+
+                        create_npc_emitter.emit(CreateNpcEvent {
+                            pos,
+                            ori: comp::Ori::from(Dir::new(npc.dir.with_z(0.0))),
+                            npc: npc_builder.with_rtsim(RtSimEntity(id)),
+                            rider: steering,
+                        });
+                    },
+                };
+
+            // Load in mounted npcs and their riders
+            for mount in data.npcs.mounts.iter_mounts() {
+                let mount_npc = data.npcs.npcs.get_mut(mount).expect("This should exist");
+                let chunk = mount_npc.wpos.xy().as_::<i32>().wpos_to_cpos();
+
+                if matches!(mount_npc.mode, SimulationMode::Simulated)
+                    && chunk_states.0.get(chunk).map_or(false, |c| c.is_some())
+                {
+                    mount_npc.mode = SimulationMode::Loaded;
+
+                    let mut actor_info = |actor: Actor| {
+                        let npc_id = actor.npc()?;
+                        let npc = data.npcs.npcs.get_mut(npc_id)?;
+                        if matches!(npc.mode, SimulationMode::Simulated) {
+                            npc.mode = SimulationMode::Loaded;
+                            stock_shop(npc, &data.sites, index.as_index_ref(), data.time_of_day.0);
+                            let entity_info = get_npc_entity_info(
+                                npc,
+                                &data.sites,
+                                &data.factions,
+                                index.as_index_ref(),
+                                Some(&calendar_data),
+                            );
+
+                            let mut npc_builder = SpawnEntityData::from_entity_info(entity_info)
                             .into_npc_data_inner()
                             // EntityConfig can't represent Waypoints at all
                             // as of now, and if someone will try to spawn
@@ -389,78 +524,130 @@ impl<'a> System<'a> for Sys {
                             .0
                             .with_rtsim(RtSimEntity(npc_id));
 
-                        if let Some(agent) = &mut npc_builder.agent {
-                            agent.rtsim_outbox = Some(Default::default());
-                        }
+                            if let Some(agent) = &mut npc_builder.agent {
+                                agent.rtsim_outbox = Some(Default::default());
+                            }
 
-                        Some(npc_builder)
-                    } else {
-                        error!("Npc is loaded but vehicle is unloaded");
-                        None
-                    }
-                };
+                            Some(npc_builder)
+                        } else {
+                            error!("Npc is loaded but vehicle is unloaded");
+                            None
+                        }
+                    };
 
-                let steerer = data
-                    .npcs
-                    .mounts
-                    .get_steerer_link(mount)
-                    .and_then(|link| actor_info(link.rider));
+                    let steerer = data
+                        .npcs
+                        .mounts
+                        .get_steerer_link(mount)
+                        .and_then(|link| actor_info(link.rider));
 
-                let mount_npc = data.npcs.npcs.get(mount).expect("This should exist");
-                create_event(mount, mount_npc, steerer);
+                    let mount_npc = data.npcs.npcs.get(mount).expect("This should exist");
+                    create_event(mount, mount_npc, steerer);
+                }
             }
-        }
 
-        // Load in NPCs
-        for (npc_id, npc) in data.npcs.npcs.iter_mut() {
-            let chunk = npc.wpos.xy().as_::<i32>().wpos_to_cpos();
+            // Load in NPCs
+            for (npc_id, npc) in data.npcs.npcs.iter_mut() {
+                let chunk = npc.wpos.xy().as_::<i32>().wpos_to_cpos();
 
-            // Load the NPC into the world if it's in a loaded chunk and is not already
-            // loaded
-            if matches!(npc.mode, SimulationMode::Simulated)
+                // Load the NPC into the world if it's in a loaded chunk and is not already
+                // loaded
+                if matches!(npc.mode, SimulationMode::Simulated)
                 && chunk_states.0.get(chunk).map_or(false, |c| c.is_some())
                 // Riding npcs will be spawned by the vehicle.
                 && data.npcs.mounts.get_mount_link(npc_id).is_none()
-            {
-                npc.mode = SimulationMode::Loaded;
-                create_event(npc_id, npc, None);
+                {
+                    npc.mode = SimulationMode::Loaded;
+                    stock_shop(npc, &data.sites, index.as_index_ref(), data.time_of_day.0);
+                    create_event(npc_id, npc, None);
+                }
             }
-        }
 
-        // Synchronise rtsim NPC with entity data
-        for (entity, pos, rtsim_entity, agent) in (
-            &entities,
-            &positions,
-            &rtsim_entities,
-            (&mut agents).maybe(),
-        )
-            .join()
-        {
-            if let Some(npc) = data.npcs.get_mut(rtsim_entity.0) {
-                match npc.mode {
-                    SimulationMode::Loaded => {
-                        // Update rtsim NPC state
-                        npc.wpos = pos.0;
-
-                        // Update entity state
-                        if let Some(agent) = agent {
-                            agent.rtsim_controller.personality = npc.personality;
-                            agent.rtsim_controller.look_dir = npc.controller.look_dir;
-                            agent.rtsim_controller.activity = npc.controller.activity;
-                            agent
-                                .rtsim_controller
-                                .actions
-                                .extend(std::mem::take(&mut npc.controller.actions));
-                            if let Some(rtsim_outbox) = &mut agent.rtsim_outbox {
-                                npc.inbox.append(rtsim_outbox);
+            // Synchronise rtsim NPC with entity data
+            for (entity, pos, rtsim_entity, agent) in (
+                &entities,
+                &positions,
+                &rtsim_entities,
+                (&mut agents).maybe(),
+            )
+                .join()
+            {
+                if let Some(npc) = data.npcs.get_mut(rtsim_entity.0) {
+                    match npc.mode {
+                        SimulationMode::Loaded => {
+                            // Update rtsim NPC state
+                            npc.wpos = pos.0;
+
+                            // Update entity state
+                            if let Some(agent) = agent {
+                                agent.rtsim_controller.personality = npc.personality;
+                                let target_actor = agent.target.and_then(|target| {
+                                    presences
+                                        .get(target.target)
+                                        .and_then(|p| p.kind.character_id())
+                                        .map(Actor::Character)
+                                        .or_else(|| {
+                                            Some(Actor::Npc(rtsim_entities.get(target.target)?.0))
+                                        })
+                                });
+                                agent.rtsim_controller.target_sentiment = target_actor
+                                    .map_or(0.0, |actor| npc.sentiments.toward(actor).value());
+                                agent.rtsim_controller.target_reputation = npc
+                                    .faction
+                                    .and_then(|faction| data.factions.get(faction))
+                                    .map(|faction| {
+                                        target_actor.map_or(ReputationTier::Neutral, |actor| {
+                                            faction.reputation(actor)
+                                        })
+                                    });
+                                agent.rtsim_controller.festival = npc
+                                    .home
+                                    .and_then(|home| data.sites.get(home))
+                                    .map_or(false, |site| site.festival);
+                                agent.rtsim_controller.look_dir = npc.controller.look_dir;
+                                agent.rtsim_controller.activity = npc.controller.activity;
+                                agent
+                                    .rtsim_controller
+                                    .actions
+                                    .extend(std::mem::take(&mut npc.controller.actions));
+                                if let Some(rtsim_outbox) = &mut agent.rtsim_outbox {
+                                    hook_events
+                                        .0
+                                        .extend(rtsim_outbox.iter().filter_map(
+                                            |input| match input {
+                                                NpcInput::Interaction(target, _) => {
+                                                    Some(RtsimHookEvent::DialogueStarted {
+                                                        npc: rtsim_entity.0,
+                                                        target: *target,
+                                                    })
+                                                },
+                                                _ => None,
+                                            },
+                                        ));
+                                    npc.inbox.append(rtsim_outbox);
+                                }
                             }
-                        }
-                    },
-                    SimulationMode::Simulated => {
-                        delete_emitter.emit(DeleteEvent(entity));
-                    },
+                        },
+                        SimulationMode::Simulated => {
+                            delete_emitter.emit(DeleteEvent(entity));
+                        },
+                    }
                 }
             }
         }
+        drop(state);
+
+        // Perform a save if required
+        if rtsim
+            .last_saved
+            .map_or(true, |ls| ls.elapsed() > Duration::from_secs(60))
+        {
+            // TODO: Use slow jobs
+            let _ = slow_jobs;
+            rtsim.save(/* &slow_jobs, */ false);
+        }
+
+        // Tick rtsim on the tick thread, its results are picked up once it is done
+        rtsim.start_tick(&world, &index, *time_of_day, *time);
     }
 }
@@ -1,15 +1,21 @@
 pub mod admin;
+pub mod announcements;
 pub mod banlist;
 mod editable;
+pub mod event_calendar;
+pub mod sanctions;
 pub mod server_description;
 pub mod whitelist;
 
 pub use editable::{EditableSetting, Error as SettingError};
 
 pub use admin::{AdminRecord, Admins};
+pub use announcements::{Announcement, Announcements};
 pub use banlist::{
     Ban, BanAction, BanEntry, BanError, BanErrorKind, BanInfo, BanKind, BanRecord, Banlist,
 };
+pub use event_calendar::{EventCalendar, ScheduledEvent};
+pub use sanctions::{AccountNote, Sanction, SanctionKind, SanctionRecord, Sanctions};
 pub use server_description::ServerDescriptions;
 pub use whitelist::{Whitelist, WhitelistInfo, WhitelistRecord};
 
@@ -40,6 +46,9 @@ const WHITELIST_FILENAME: &str = "whitelist.ron";
 const BANLIST_FILENAME: &str = "banlist.ron";
 const SERVER_DESCRIPTION_FILENAME: &str = "description.ron";
 const ADMINS_FILENAME: &str = "admins.ron";
+const ANNOUNCEMENTS_FILENAME: &str = "announcements.ron";
+const SANCTIONS_FILENAME: &str = "sanctions.ron";
+const EVENT_CALENDAR_FILENAME: &str = "event_calendar.ron";
 
 #[derive(Copy, Clone, Debug, Deserialize, Serialize)]
 pub enum ServerBattleMode {
@@ -93,6 +102,29 @@ pub enum Protocol {
     },
 }
 
+/// What players lose when they die
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
+pub enum DeathPenalty {
+    /// Equipped items lose durability
+    #[default]
+    Durability,
+    /// Some of the carried items are left behind in a gravestone that the
+    /// player has to return to before it decays
+    Gravestone,
+    /// Both of the above
+    DurabilityAndGravestone,
+}
+
+impl DeathPenalty {
+    pub fn damages_items(&self) -> bool {
+        matches!(self, Self::Durability | Self::DurabilityAndGravestone)
+    }
+
+    pub fn leaves_gravestone(&self) -> bool {
+        matches!(self, Self::Gravestone | Self::DurabilityAndGravestone)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GameplaySettings {
     #[serde(default)]
@@ -100,6 +132,10 @@ pub struct GameplaySettings {
     #[serde(default)]
     // explosion_burn_marks by players
     pub explosion_burn_marks: bool,
+    #[serde(default)]
+    pub death_penalty: DeathPenalty,
+    #[serde(default)]
+    pub wildfire: WildfireSettings,
 }
 
 impl Default for GameplaySettings {
@@ -107,35 +143,106 @@ impl Default for GameplaySettings {
         Self {
             battle_mode: ServerBattleMode::default(),
             explosion_burn_marks: true,
+            death_penalty: DeathPenalty::default(),
+            wildfire: WildfireSettings::default(),
         }
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WildfireSettings {
+    /// Whether fires spread to nearby flammable blocks
+    pub enabled: bool,
+    /// Chance, each second, for a fire to spread to each block next to it
+    pub spread_chance: f32,
+    /// How long, in seconds, a block burns before it is gone
+    pub burn_duration: f32,
+    /// Upper limit of blocks that can burn at the same time in the whole world
+    pub max_burning_blocks: usize,
+}
+
+impl Default for WildfireSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            spread_chance: 0.04,
+            burn_duration: 12.0,
+            max_burning_blocks: 512,
+        }
+    }
+}
+
+/// What the chat filter does with a message containing a filtered word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilterSeverity {
+    /// The word is replaced with asterisks, but the message is still sent.
+    Censor,
+    /// The message is not sent.
+    Block,
+    /// The message is not sent, and the sender is muted for a while.
+    Mute,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WordList {
+    pub file: PathBuf,
+    pub severity: FilterSeverity,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ModerationSettings {
+    /// Words in these files block messages, and can't be used in character
+    /// names.
     #[serde(default)]
     pub banned_words_files: Vec<PathBuf>,
+    /// Additional word lists, each handled according to its severity.
+    #[serde(default)]
+    pub filtered_words: Vec<WordList>,
+    /// Whether messages containing links are blocked.
+    #[serde(default)]
+    pub block_urls: bool,
     #[serde(default)]
     pub automod: bool,
     #[serde(default)]
     pub admins_exempt: bool,
+    /// Players that are never subject to automoderation, regardless of their
+    /// role.
+    #[serde(default)]
+    pub exempt_players: Vec<authc::Uuid>,
 }
 
 impl ModerationSettings {
-    pub fn load_banned_words(&self, data_dir: &Path) -> Vec<String> {
-        let mut banned_words = Vec::new();
-        for fname in self.banned_words_files.iter() {
-            let mut path = with_config_dir(data_dir);
-            path.push(fname);
-            match std::fs::File::open(&path) {
-                Ok(file) => match ron::de::from_reader(&file) {
-                    Ok(mut words) => banned_words.append(&mut words),
-                    Err(error) => error!(?error, ?file, "Couldn't read banned words file"),
+    fn load_words(data_dir: &Path, fname: &Path) -> Vec<String> {
+        let mut path = with_config_dir(data_dir);
+        path.push(fname);
+        match std::fs::File::open(&path) {
+            Ok(file) => match ron::de::from_reader(&file) {
+                Ok(words) => words,
+                Err(error) => {
+                    error!(?error, ?file, "Couldn't read banned words file");
+                    Vec::new()
                 },
-                Err(error) => error!(?error, ?path, "Couldn't open banned words file"),
-            }
+            },
+            Err(error) => {
+                error!(?error, ?path, "Couldn't open banned words file");
+                Vec::new()
+            },
         }
-        banned_words
+    }
+
+    pub fn load_banned_words(&self, data_dir: &Path) -> Vec<String> {
+        self.banned_words_files
+            .iter()
+            .flat_map(|fname| Self::load_words(data_dir, fname))
+            .collect()
+    }
+
+    pub fn load_filtered_words(&self, data_dir: &Path) -> Vec<(FilterSeverity, Vec<String>)> {
+        self.filtered_words
+            .iter()
+            .map(|list| (list.severity, Self::load_words(data_dir, &list.file)))
+            .collect()
     }
 }
 
@@ -143,8 +250,66 @@ impl Default for ModerationSettings {
     fn default() -> Self {
         Self {
             banned_words_files: Vec::new(),
+            filtered_words: Vec::new(),
+            block_urls: false,
             automod: false,
             admins_exempt: true,
+            exempt_players: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoginQueueSettings {
+    /// Whether clients wait in a queue when the server is full, instead of
+    /// being rejected.
+    pub enabled: bool,
+    /// Clients that try to log in while this many others are waiting are
+    /// rejected.
+    pub max_length: u32,
+    /// Slots on top of `max_players` that only admins and moderators can use.
+    /// When these are full, they are queued ahead of other players. If `None`,
+    /// admins and moderators can always log in.
+    pub priority_slots: Option<u16>,
+    /// How often waiting clients are sent their position, even if it didn't
+    /// change.
+    pub update_interval: Duration,
+}
+
+impl Default for LoginQueueSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_length: 100,
+            priority_slots: None,
+            update_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccountLinkSettings {
+    /// Whether players can request codes to link their account, e.g. with a
+    /// Discord bot.
+    pub enabled: bool,
+    /// Shown to players along with their code, to tell them where to redeem
+    /// it (for example, which command to send to the bot).
+    pub instructions: String,
+    /// How long a code can be redeemed for.
+    pub code_lifetime: Duration,
+    /// Whether linked accounts get a badge in the social window.
+    pub grant_badge: bool,
+}
+
+impl Default for AccountLinkSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            instructions: String::new(),
+            code_lifetime: Duration::from_secs(600),
+            grant_badge: true,
         }
     }
 }
@@ -190,6 +355,10 @@ pub struct Settings {
     pub max_view_distance: Option<u32>,
     pub max_player_group_size: u32,
     pub client_timeout: Duration,
+    /// How long the character of a player that lost their connection stays in
+    /// the world, so that they can resume their session. Zero disables
+    /// resuming sessions.
+    pub session_resume_grace: Duration,
     pub max_player_for_kill_broadcast: Option<usize>,
     pub calendar_mode: CalendarMode,
 
@@ -202,6 +371,10 @@ pub struct Settings {
     pub gameplay: GameplaySettings,
     #[serde(default)]
     pub moderation: ModerationSettings,
+    #[serde(default)]
+    pub login_queue: LoginQueueSettings,
+    #[serde(default)]
+    pub account_link: AccountLinkSettings,
 
     #[serde(default)]
     pub world: WorldSettings,
@@ -230,10 +403,13 @@ impl Default for Settings {
             max_player_group_size: 6,
             calendar_mode: CalendarMode::Auto,
             client_timeout: Duration::from_secs(40),
+            session_resume_grace: Duration::from_secs(60),
             max_player_for_kill_broadcast: None,
             experimental_terrain_persistence: false,
             gameplay: GameplaySettings::default(),
             moderation: ModerationSettings::default(),
+            login_queue: LoginQueueSettings::default(),
+            account_link: AccountLinkSettings::default(),
             world: WorldSettings::default(),
         }
     }
@@ -312,6 +488,7 @@ impl Settings {
             max_players: 100,
             max_view_distance: None,
             client_timeout: Duration::from_secs(180),
+            session_resume_grace: Duration::ZERO,
             ..load // Fill in remaining fields from server_settings.ron.
         }
     }
@@ -381,6 +558,9 @@ pub struct EditableSettings {
     pub banlist: Banlist,
     pub server_description: ServerDescriptions,
     pub admins: Admins,
+    pub announcements: Announcements,
+    pub sanctions: Sanctions,
+    pub event_calendar: EventCalendar,
 }
 
 impl EditableSettings {
@@ -390,6 +570,9 @@ impl EditableSettings {
             banlist: Banlist::load(data_dir),
             server_description: ServerDescriptions::load(data_dir),
             admins: Admins::load(data_dir),
+            announcements: Announcements::load(data_dir),
+            sanctions: Sanctions::load(data_dir),
+            event_calendar: EventCalendar::load(data_dir),
         }
     }
 
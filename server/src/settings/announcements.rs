@@ -0,0 +1,153 @@
+//! Versioned announcement settings files.
+
+use super::ANNOUNCEMENTS_FILENAME as FILENAME;
+use crate::settings::editable::{EditableSetting, Version};
+use core::convert::{Infallible, TryFrom};
+use serde::{Deserialize, Serialize};
+
+/// NOTE: Always replace this with the latest announcements version. Then update
+/// the AnnouncementsRaw, the TryFrom<AnnouncementsRaw> for Announcements, the
+/// previously most recent module, and add a new module for the latest version!
+/// Please respect the migration upgrade guarantee found in the parent module
+/// with any upgrade.
+pub use self::v0::*;
+
+/// Versioned settings files, one per version.
+#[derive(Deserialize, Serialize)]
+pub enum AnnouncementsRaw {
+    V0(Announcements),
+}
+
+impl From<Announcements> for AnnouncementsRaw {
+    fn from(value: Announcements) -> Self {
+        // Replace variant with that of current latest version.
+        Self::V0(value)
+    }
+}
+
+impl TryFrom<AnnouncementsRaw> for (Version, Announcements) {
+    type Error = <Announcements as EditableSetting>::Error;
+
+    fn try_from(
+        value: AnnouncementsRaw,
+    ) -> Result<Self, <Announcements as EditableSetting>::Error> {
+        use AnnouncementsRaw::*;
+        Ok(match value {
+            // Latest version (move to old section using the pattern of other old version when it
+            // is no longer latest).
+            V0(mut value) => (value.validate()?, value),
+        })
+    }
+}
+
+type Final = Announcements;
+
+impl EditableSetting for Announcements {
+    type Error = Infallible;
+    type Legacy = legacy::Announcements;
+    type Setting = AnnouncementsRaw;
+
+    const FILENAME: &'static str = FILENAME;
+}
+
+mod legacy {
+    use super::{v0 as next, Final};
+    use hashbrown::HashMap;
+    use serde::{Deserialize, Serialize};
+
+    /// Announcements have always been versioned, but a hand written file that
+    /// lists them without the version tag is accepted too.
+    #[derive(Deserialize, Serialize, Default)]
+    #[serde(transparent)]
+    pub struct Announcements(pub(super) HashMap<String, next::Announcement>);
+
+    impl From<Announcements> for Final {
+        /// Legacy files are always valid, which is why we implement From
+        /// rather than TryFrom.
+        fn from(value: Announcements) -> Self { next::Announcements::migrate(value) }
+    }
+}
+
+mod v0 {
+    use super::{legacy as prev, Final};
+    use crate::settings::editable::{EditableSetting, Version};
+    use common::comp::{Content, LocalizationArg};
+    use core::time::Duration;
+    use hashbrown::HashMap;
+    use serde::{Deserialize, Serialize};
+
+    /// The shortest interval announcements can be repeated at, so that a typo
+    /// can't flood the chat.
+    pub const MIN_INTERVAL: Duration = Duration::from_secs(60);
+
+    /// Recurring messages broadcast to every player, by name.
+    #[derive(Clone, Default, Deserialize, Serialize)]
+    #[serde(transparent)]
+    pub struct Announcements(pub HashMap<String, Announcement>);
+
+    /// An announcement is a localization key rather than a message, so that
+    /// every client shows it in their own language.
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct Announcement {
+        /// The localization key of the message.
+        pub key: String,
+        /// The arguments of the message.
+        #[serde(default)]
+        pub args: HashMap<String, LocalizationArg>,
+        /// How long to wait between broadcasts.
+        pub interval: Duration,
+        /// Disabled announcements are kept, but not broadcast.
+        #[serde(default = "default_enabled")]
+        pub enabled: bool,
+    }
+
+    fn default_enabled() -> bool { true }
+
+    impl Announcement {
+        pub fn content(&self) -> Content {
+            Content::Localized {
+                key: self.key.clone(),
+                seed: rand::random(),
+                args: self.args.clone(),
+            }
+        }
+    }
+
+    impl core::ops::Deref for Announcements {
+        type Target = HashMap<String, Announcement>;
+
+        fn deref(&self) -> &Self::Target { &self.0 }
+    }
+
+    impl core::ops::DerefMut for Announcements {
+        fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+    }
+
+    impl Announcements {
+        /// One-off migration from the previous version.  This must be
+        /// guaranteed to produce a valid settings file as long as it is
+        /// called with a valid settings file from the previous version.
+        pub(super) fn migrate(prev: prev::Announcements) -> Self {
+            let mut announcements = Announcements(prev.0);
+            let _ = announcements.validate();
+            announcements
+        }
+
+        /// Perform any needed validation on these announcements that can't be
+        /// done using parsing.
+        ///
+        /// The returned version being "Old" indicates the loaded setting has
+        /// been modified during validation (this is why validate takes
+        /// `&mut self`).
+        pub(super) fn validate(&mut self) -> Result<Version, <Final as EditableSetting>::Error> {
+            let mut version = Version::Latest;
+            for announcement in self.0.values_mut() {
+                if announcement.interval < MIN_INTERVAL {
+                    announcement.interval = MIN_INTERVAL;
+                    version = Version::Old;
+                }
+            }
+            Ok(version)
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Versioned event calendar settings files.
+
+use super::EVENT_CALENDAR_FILENAME as FILENAME;
+use crate::settings::editable::{EditableSetting, Version};
+use core::convert::{Infallible, TryFrom};
+use serde::{Deserialize, Serialize};
+
+/// NOTE: Always replace this with the latest event calendar version. Then
+/// update the EventCalendarRaw, the TryFrom<EventCalendarRaw> for
+/// EventCalendar, the previously most recent module, and add a new module for
+/// the latest version! Please respect the migration upgrade guarantee found in
+/// the parent module with any upgrade.
+pub use self::v0::*;
+
+/// Versioned settings files, one per version.
+#[derive(Deserialize, Serialize)]
+pub enum EventCalendarRaw {
+    V0(EventCalendar),
+}
+
+impl From<EventCalendar> for EventCalendarRaw {
+    fn from(value: EventCalendar) -> Self {
+        // Replace variant with that of current latest version.
+        Self::V0(value)
+    }
+}
+
+impl TryFrom<EventCalendarRaw> for (Version, EventCalendar) {
+    type Error = <EventCalendar as EditableSetting>::Error;
+
+    fn try_from(
+        value: EventCalendarRaw,
+    ) -> Result<Self, <EventCalendar as EditableSetting>::Error> {
+        use EventCalendarRaw::*;
+        Ok(match value {
+            // Latest version (move to old section using the pattern of other old version when it
+            // is no longer latest).
+            V0(mut value) => (value.validate()?, value),
+        })
+    }
+}
+
+type Final = EventCalendar;
+
+impl EditableSetting for EventCalendar {
+    type Error = Infallible;
+    type Legacy = legacy::EventCalendar;
+    type Setting = EventCalendarRaw;
+
+    const FILENAME: &'static str = FILENAME;
+}
+
+mod legacy {
+    use super::{v0 as next, Final};
+    use hashbrown::HashMap;
+    use serde::{Deserialize, Serialize};
+
+    /// The event calendar has always been versioned, but a hand written file
+    /// that lists the events without the version tag is accepted too.
+    #[derive(Deserialize, Serialize, Default)]
+    #[serde(transparent)]
+    pub struct EventCalendar(pub(super) HashMap<String, next::ScheduledEvent>);
+
+    impl From<EventCalendar> for Final {
+        /// Legacy files are always valid, which is why we implement From
+        /// rather than TryFrom.
+        fn from(value: EventCalendar) -> Self { next::EventCalendar::migrate(value) }
+    }
+}
+
+mod v0 {
+    use super::{legacy as prev, Final};
+    use crate::settings::editable::{EditableSetting, Version};
+    use chrono::{DateTime, Utc};
+    use common::{calendar::ScheduledEventKind, comp::Content};
+    use core::time::Duration;
+    use hashbrown::HashMap;
+    use serde::{Deserialize, Serialize};
+
+    /// Events scheduled by the server, by name.
+    #[derive(Clone, Default, Deserialize, Serialize)]
+    #[serde(transparent)]
+    pub struct EventCalendar(pub HashMap<String, ScheduledEvent>);
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct ScheduledEvent {
+        /// The name shown to players, usually a localization key so that every
+        /// client shows it in their own language.
+        pub name: Content,
+        pub kind: ScheduledEventKind,
+        /// When the event first starts.
+        pub start: DateTime<Utc>,
+        /// How long each occurrence of the event lasts.
+        pub duration: Duration,
+        /// If set, the event starts again this long after each start, e.g.
+        /// every week.
+        #[serde(default)]
+        pub repeat: Option<Duration>,
+        /// Disabled events are kept, but neither shown nor run.
+        #[serde(default = "default_enabled")]
+        pub enabled: bool,
+    }
+
+    fn default_enabled() -> bool { true }
+
+    impl ScheduledEvent {
+        /// Start of the occurrence that is ongoing at `now`, or else of the
+        /// next one. `None` if the event is over for good.
+        pub fn occurrence(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+            let duration = chrono::Duration::from_std(self.duration).ok()?;
+            if now < self.start + duration {
+                return Some(self.start);
+            }
+            let repeat = chrono::Duration::from_std(self.repeat?).ok()?;
+            let period = repeat.num_seconds().max(1);
+            let elapsed = (now - self.start).num_seconds();
+            // The last occurrence that started before now
+            let last = self.start + chrono::Duration::seconds(elapsed / period * period);
+            Some(if now < last + duration {
+                last
+            } else {
+                last + repeat
+            })
+        }
+    }
+
+    impl core::ops::Deref for EventCalendar {
+        type Target = HashMap<String, ScheduledEvent>;
+
+        fn deref(&self) -> &Self::Target { &self.0 }
+    }
+
+    impl core::ops::DerefMut for EventCalendar {
+        fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+    }
+
+    impl EventCalendar {
+        /// One-off migration from the previous version.  This must be
+        /// guaranteed to produce a valid settings file as long as it is
+        /// called with a valid settings file from the previous version.
+        pub(super) fn migrate(prev: prev::EventCalendar) -> Self {
+            let mut calendar = EventCalendar(prev.0);
+            let _ = calendar.validate();
+            calendar
+        }
+
+        /// Perform any needed validation on this calendar that can't be done
+        /// using parsing.
+        ///
+        /// The returned version being "Old" indicates the loaded setting has
+        /// been modified during validation (this is why validate takes
+        /// `&mut self`).
+        pub(super) fn validate(&mut self) -> Result<Version, <Final as EditableSetting>::Error> {
+            let mut version = Version::Latest;
+            for event in self.0.values_mut() {
+                // Occurrences of an event may not overlap
+                if let Some(repeat) = &mut event.repeat
+                    && *repeat < event.duration
+                {
+                    *repeat = event.duration;
+                    version = Version::Old;
+                }
+            }
+            Ok(version)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use common::{calendar::ScheduledEventKind, comp::Content};
+    use core::time::Duration;
+
+    const HOUR: u64 = 60 * 60;
+    const DAY: u64 = 24 * HOUR;
+
+    #[test]
+    fn weekly_event_occurrences() {
+        let start = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let event = ScheduledEvent {
+            name: Content::Plain("Double Experience Weekend".to_owned()),
+            kind: ScheduledEventKind::BonusExperience { multiplier: 2.0 },
+            start,
+            duration: Duration::from_secs(2 * DAY),
+            repeat: Some(Duration::from_secs(7 * DAY)),
+            enabled: true,
+        };
+        let at = |secs: u64| start + chrono::Duration::seconds(secs as i64);
+
+        // Before the first occurrence, and during it
+        assert_eq!(
+            event.occurrence(at(0) - chrono::Duration::days(3)),
+            Some(at(0))
+        );
+        assert_eq!(event.occurrence(at(DAY)), Some(at(0)));
+        // Between occurrences, the next one is shown
+        assert_eq!(event.occurrence(at(3 * DAY)), Some(at(7 * DAY)));
+        // During a later occurrence
+        assert_eq!(event.occurrence(at(15 * DAY + HOUR)), Some(at(14 * DAY)));
+
+        let once = ScheduledEvent {
+            repeat: None,
+            ..event
+        };
+        assert_eq!(once.occurrence(at(3 * DAY)), None);
+    }
+}
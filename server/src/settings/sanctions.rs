@@ -0,0 +1,237 @@
+//! Versioned sanctions settings files.
+
+use super::SANCTIONS_FILENAME as FILENAME;
+use crate::settings::editable::{EditableSetting, Version};
+use core::convert::{Infallible, TryFrom};
+use serde::{Deserialize, Serialize};
+
+/// NOTE: Always replace this with the latest sanctions version. Then update the
+/// SanctionsRaw, the TryFrom<SanctionsRaw> for Sanctions, the previously most
+/// recent module, and add a new module for the latest version!  Please respect
+/// the migration upgrade guarantee found in the parent module with any upgrade.
+pub use self::v0::*;
+
+/// Versioned settings files, one per version.
+#[derive(Deserialize, Serialize)]
+pub enum SanctionsRaw {
+    V0(Sanctions),
+}
+
+impl From<Sanctions> for SanctionsRaw {
+    fn from(value: Sanctions) -> Self {
+        // Replace variant with that of current latest version.
+        Self::V0(value)
+    }
+}
+
+impl TryFrom<SanctionsRaw> for (Version, Sanctions) {
+    type Error = <Sanctions as EditableSetting>::Error;
+
+    fn try_from(value: SanctionsRaw) -> Result<Self, <Sanctions as EditableSetting>::Error> {
+        use SanctionsRaw::*;
+        Ok(match value {
+            // Latest version (move to old section using the pattern of other old version when it
+            // is no longer latest).
+            V0(mut value) => (value.validate()?, value),
+        })
+    }
+}
+
+type Final = Sanctions;
+
+impl EditableSetting for Sanctions {
+    type Error = Infallible;
+    type Legacy = legacy::Sanctions;
+    type Setting = SanctionsRaw;
+
+    const FILENAME: &'static str = FILENAME;
+}
+
+mod legacy {
+    use super::{v0 as next, Final};
+    use authc::Uuid;
+    use hashbrown::HashMap;
+    use serde::{Deserialize, Serialize};
+
+    /// Sanctions have always been versioned, but a hand written file that lists
+    /// them without the version tag is accepted too.
+    #[derive(Deserialize, Serialize, Default)]
+    #[serde(transparent)]
+    pub struct Sanctions(pub(super) HashMap<Uuid, next::SanctionRecord>);
+
+    impl From<Sanctions> for Final {
+        /// Legacy files are always valid, which is why we implement From
+        /// rather than TryFrom.
+        fn from(value: Sanctions) -> Self { next::Sanctions::migrate(value) }
+    }
+}
+
+mod v0 {
+    use super::{legacy as prev, Final};
+    use crate::settings::editable::{EditableSetting, Version};
+    use authc::Uuid;
+    use chrono::{prelude::*, Utc};
+    use hashbrown::HashMap;
+    use serde::{Deserialize, Serialize};
+
+    /// Moderation states short of a ban.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+    pub enum SanctionKind {
+        /// Chat messages are only shown to their sender.
+        ShadowMute,
+        /// Chat messages are rate limited, and trading is not allowed.
+        Probation,
+    }
+
+    impl SanctionKind {
+        pub const ALL: [Self; 2] = [Self::ShadowMute, Self::Probation];
+
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                Self::ShadowMute => "shadow_mute",
+                Self::Probation => "probation",
+            }
+        }
+    }
+
+    impl core::str::FromStr for SanctionKind {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Self::ALL
+                .into_iter()
+                .find(|kind| kind.as_str() == s)
+                .ok_or(())
+        }
+    }
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct Sanction {
+        pub reason: String,
+        pub performed_by_username: String,
+        pub date: DateTime<Utc>,
+        /// If `None`, the sanction lasts until it is lifted.
+        pub end_date: Option<DateTime<Utc>>,
+    }
+
+    impl Sanction {
+        pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+            self.end_date.map_or(true, |end_date| end_date > now)
+        }
+    }
+
+    /// A note moderators keep about an account, e.g. about past warnings.
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct AccountNote {
+        pub text: String,
+        pub author: String,
+        pub date: DateTime<Utc>,
+    }
+
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    pub struct SanctionRecord {
+        /// The username of the account when the record was last changed.
+        pub username: String,
+        #[serde(default)]
+        pub shadow_mute: Option<Sanction>,
+        #[serde(default)]
+        pub probation: Option<Sanction>,
+        #[serde(default)]
+        pub notes: Vec<AccountNote>,
+    }
+
+    impl SanctionRecord {
+        pub fn sanction(&self, kind: SanctionKind) -> Option<&Sanction> {
+            match kind {
+                SanctionKind::ShadowMute => self.shadow_mute.as_ref(),
+                SanctionKind::Probation => self.probation.as_ref(),
+            }
+        }
+
+        pub fn sanction_mut(&mut self, kind: SanctionKind) -> &mut Option<Sanction> {
+            match kind {
+                SanctionKind::ShadowMute => &mut self.shadow_mute,
+                SanctionKind::Probation => &mut self.probation,
+            }
+        }
+
+        fn is_empty(&self) -> bool {
+            self.shadow_mute.is_none() && self.probation.is_none() && self.notes.is_empty()
+        }
+    }
+
+    /// Sanctions and notes of accounts, by UUID.
+    #[derive(Clone, Default, Deserialize, Serialize)]
+    #[serde(transparent)]
+    pub struct Sanctions(pub HashMap<Uuid, SanctionRecord>);
+
+    impl core::ops::Deref for Sanctions {
+        type Target = HashMap<Uuid, SanctionRecord>;
+
+        fn deref(&self) -> &Self::Target { &self.0 }
+    }
+
+    impl core::ops::DerefMut for Sanctions {
+        fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+    }
+
+    impl Sanctions {
+        /// Whether the account is currently under the given sanction.
+        pub fn is_active(&self, uuid: &Uuid, kind: SanctionKind, now: DateTime<Utc>) -> bool {
+            self.0
+                .get(uuid)
+                .and_then(|record| record.sanction(kind))
+                .map_or(false, |sanction| sanction.is_active(now))
+        }
+
+        /// Applies, replaces or (if `sanction` is `None`) lifts a sanction.
+        /// Returns false if there was nothing to lift.
+        pub fn set(
+            &mut self,
+            uuid: Uuid,
+            username: String,
+            kind: SanctionKind,
+            sanction: Option<Sanction>,
+        ) -> bool {
+            let record = self.0.entry(uuid).or_default();
+            record.username = username;
+            let lifted = sanction.is_none();
+            let old = core::mem::replace(record.sanction_mut(kind), sanction);
+            if record.is_empty() {
+                self.0.remove(&uuid);
+            }
+            !lifted || old.is_some()
+        }
+
+        pub fn add_note(&mut self, uuid: Uuid, username: String, note: AccountNote) {
+            let record = self.0.entry(uuid).or_default();
+            record.username = username;
+            record.notes.push(note);
+        }
+
+        /// One-off migration from the previous version.  This must be
+        /// guaranteed to produce a valid settings file as long as it is
+        /// called with a valid settings file from the previous version.
+        pub(super) fn migrate(prev: prev::Sanctions) -> Self {
+            let mut sanctions = Sanctions(prev.0);
+            let _ = sanctions.validate();
+            sanctions
+        }
+
+        /// Perform any needed validation on these sanctions that can't be
+        /// done using parsing.
+        ///
+        /// The returned version being "Old" indicates the loaded setting has
+        /// been modified during validation (this is why validate takes
+        /// `&mut self`).
+        pub(super) fn validate(&mut self) -> Result<Version, <Final as EditableSetting>::Error> {
+            let len = self.0.len();
+            self.0.retain(|_, record| !record.is_empty());
+            Ok(if self.0.len() == len {
+                Version::Latest
+            } else {
+                Version::Old
+            })
+        }
+    }
+}
@@ -2,18 +2,22 @@
 use crate::rtsim::RtSim;
 use crate::{
     automod::AutoMod,
+    bounty::Bounties,
     chat::ChatExporter,
     client::Client,
     events::{self, shared::update_map_markers},
+    guild::Guilds,
+    metrics::ChatMetrics,
     persistence::PersistedComponents,
     pet::restore_pet,
     presence::RepositionOnChunkLoad,
-    settings::Settings,
+    settings::{EditableSettings, SanctionKind, Settings},
     sys::sentinel::DeletedEntities,
     wiring, BattleModeBuffer, SpawnPoint,
 };
+use chrono::Utc;
 #[cfg(feature = "worldgen")]
-use common::{calendar::Calendar, resources::TimeOfDay, slowjob::SlowJobPool};
+use common::{calendar::Calendar, resources::TimeOfDay};
 use common::{
     character::CharacterId,
     comp::{
@@ -133,12 +137,9 @@ pub trait StateExt {
         components: PersistedComponents,
     ) -> Result<(), String>;
     /// Iterates over registered clients and send each `ServerMsg`
-    fn validate_chat_msg(
-        &self,
-        player: EcsEntity,
-        chat_type: &comp::ChatType<comp::Group>,
-        msg: &str,
-    ) -> bool;
+    /// Runs the message through automoderation, censoring it in place.
+    /// Returns whether it may be sent.
+    fn validate_chat_msg(&self, player: EcsEntity, msg: &mut comp::UnresolvedChatMsg) -> bool;
     fn send_chat(&self, msg: comp::UnresolvedChatMsg);
     fn notify_players(&self, msg: ServerGeneral);
     fn notify_in_game_clients(&self, msg: ServerGeneral);
@@ -450,7 +451,6 @@ impl StateExt for State {
         // Request chunks
         {
             let ecs = self.ecs();
-            let slow_jobs = ecs.write_resource::<SlowJobPool>();
             let rtsim = ecs.read_resource::<RtSim>();
             let mut chunk_generator =
                 ecs.write_resource::<crate::chunk_generator::ChunkGenerator>();
@@ -471,7 +471,7 @@ impl StateExt for State {
             .for_each(|chunk_key| {
                 {
                     let time = (*ecs.read_resource::<TimeOfDay>(), (*ecs.read_resource::<Calendar>()).clone());
-                    chunk_generator.generate_chunk(None, chunk_key, &slow_jobs, Arc::clone(world), &rtsim, index.clone(), time);
+                    chunk_generator.generate_chunk(None, crate::chunk_generator::ChunkSource::Persister, chunk_key, Arc::clone(world), &rtsim, index.clone(), time);
                 }
             });
         }
@@ -538,6 +538,12 @@ impl StateExt for State {
             // Tell the client its request was successful.
             if let Some(client) = self.ecs().read_storage::<Client>().get(entity) {
                 client.send_fallible(ServerGeneral::CharacterSuccess);
+                client.send_fallible(ServerGeneral::Bounties(
+                    self.ecs()
+                        .read_resource::<Bounties>()
+                        .get(character_id)
+                        .to_vec(),
+                ));
             }
         }
     }
@@ -582,6 +588,8 @@ impl StateExt for State {
             pets,
             active_abilities,
             map_marker,
+            unlocked_shrines,
+            titles,
         } = components;
 
         if let Some(player_uid) = self.read_component_copied::<Uid>(entity) {
@@ -612,6 +620,7 @@ impl StateExt for State {
                     name: String::from(&stats.name),
                     // NOTE: hack, read docs on body::Gender for more
                     gender: stats.original_body.humanoid_gender(),
+                    title: titles.selected(),
                 }),
             ));
 
@@ -630,6 +639,8 @@ impl StateExt for State {
             self.write_component_ignore_entity_dead(entity, active_abilities);
             self.write_component_ignore_entity_dead(entity, skill_set);
             self.write_component_ignore_entity_dead(entity, inventory);
+            self.write_component_ignore_entity_dead(entity, unlocked_shrines);
+            self.write_component_ignore_entity_dead(entity, titles);
             self.write_component_ignore_entity_dead(
                 entity,
                 comp::InventoryUpdate::new(comp::InventoryUpdateEvent::default()),
@@ -724,21 +735,22 @@ impl StateExt for State {
         Ok(())
     }
 
-    fn validate_chat_msg(
-        &self,
-        entity: EcsEntity,
-        chat_type: &comp::ChatType<comp::Group>,
-        msg: &str,
-    ) -> bool {
+    fn validate_chat_msg(&self, entity: EcsEntity, msg: &mut comp::UnresolvedChatMsg) -> bool {
         let mut automod = self.ecs().write_resource::<AutoMod>();
         let client = self.ecs().read_storage::<Client>();
         let player = self.ecs().read_storage::<Player>();
+        let metrics = self.ecs().read_resource::<ChatMetrics>();
         let Some(client) = client.get(entity) else {
             return true;
         };
         let Some(player) = player.get(entity) else {
             return true;
         };
+        let on_probation = self
+            .ecs()
+            .read_resource::<EditableSettings>()
+            .sanctions
+            .is_active(&player.uuid(), SanctionKind::Probation, Utc::now());
 
         match automod.validate_chat_msg(
             player.uuid(),
@@ -747,11 +759,19 @@ impl StateExt for State {
                 .get(entity)
                 .map(|a| a.0),
             Instant::now(),
-            chat_type,
-            msg,
+            &msg.chat_type,
+            msg.content().as_plain().unwrap_or_default(),
+            on_probation,
         ) {
-            Ok(note) => {
-                if let Some(note) = note {
+            Ok(approved) => {
+                if let Some(censored) = approved.censored {
+                    metrics
+                        .filtered_messages
+                        .with_label_values(&["censored"])
+                        .inc();
+                    msg.set_content(Content::Plain(censored));
+                }
+                if let Some(note) = approved.note {
                     let _ = client.send(ServerGeneral::server_msg(
                         ChatType::CommandInfo,
                         format!("{}", note),
@@ -760,6 +780,10 @@ impl StateExt for State {
                 true
             },
             Err(err) => {
+                metrics
+                    .filtered_messages
+                    .with_label_values(&[err.metric_label()])
+                    .inc();
                 let _ = client.send(ServerGeneral::server_msg(
                     ChatType::CommandError,
                     format!("{}", err),
@@ -771,7 +795,7 @@ impl StateExt for State {
 
     /// Send the chat message to the proper players. Say and region are limited
     /// by location. Faction and group are limited by component.
-    fn send_chat(&self, msg: comp::UnresolvedChatMsg) {
+    fn send_chat(&self, mut msg: comp::UnresolvedChatMsg) {
         let ecs = self.ecs();
         let is_within =
             |target, a: &comp::Pos, b: &comp::Pos| a.0.distance_squared(b.0) < target * target;
@@ -785,22 +809,39 @@ impl StateExt for State {
             chat_exporter.send(exported_message);
         }
 
+        let id_maps = ecs.read_resource::<IdMaps>();
+        let entity_from_uid = |uid| id_maps.uid_entity(uid);
+
+        let permitted = msg.chat_type.uid().map_or(true, |sender| {
+            entity_from_uid(sender).map_or(false, |e| self.validate_chat_msg(e, &mut msg))
+        });
+
         let resolved_msg = msg
             .clone()
             .map_group(|_| group_info.map_or_else(|| "???".to_string(), |i| i.name.clone()));
 
-        let id_maps = ecs.read_resource::<IdMaps>();
-        let entity_from_uid = |uid| id_maps.uid_entity(uid);
+        // Messages of shadow muted players are only sent back to themselves
+        let shadow_muted_sender = msg
+            .chat_type
+            .uid()
+            .and_then(entity_from_uid)
+            .filter(|sender| {
+                ecs.read_storage::<Player>()
+                    .get(*sender)
+                    .map_or(false, |player| {
+                        ecs.read_resource::<EditableSettings>().sanctions.is_active(
+                            &player.uuid(),
+                            SanctionKind::ShadowMute,
+                            Utc::now(),
+                        )
+                    })
+            });
 
-        if msg.chat_type.uid().map_or(true, |sender| {
-            entity_from_uid(sender).map_or(false, |e| {
-                self.validate_chat_msg(
-                    e,
-                    &msg.chat_type,
-                    msg.content().as_plain().unwrap_or_default(),
-                )
-            })
-        }) {
+        if let Some(sender) = shadow_muted_sender.filter(|_| permitted) {
+            if let Some(client) = ecs.read_storage::<Client>().get(sender) {
+                client.send_fallible(ServerGeneral::ChatMsg(resolved_msg));
+            }
+        } else if permitted {
             match &msg.chat_type {
                 comp::ChatType::Offline(_)
                 | comp::ChatType::CommandInfo
@@ -942,6 +983,45 @@ impl StateExt for State {
                         }
                     }
                 },
+                comp::ChatType::Guild(from, tag) => {
+                    let guilds = ecs.read_resource::<Guilds>();
+                    let players = ecs.read_storage::<Player>();
+                    let guild = guilds.by_tag(tag).filter(|guild| {
+                        entity_from_uid(*from)
+                            .and_then(|sender| players.get(sender))
+                            .map_or(false, |player| guild.is_member(player.uuid()))
+                    });
+                    if let Some(guild) = guild {
+                        for (client, player) in (&ecs.read_storage::<Client>(), &players).join() {
+                            if guild.is_member(player.uuid()) {
+                                client.send_fallible(ServerGeneral::ChatMsg(resolved_msg.clone()));
+                            }
+                        }
+                    } else {
+                        // The sender left their guild since they switched to guild chat
+                        let reply = comp::ChatType::CommandError
+                            .into_msg(Content::localized("command-message-guild-missing"));
+
+                        let clients = ecs.read_storage::<Client>();
+                        if let Some(client) =
+                            entity_from_uid(*from).and_then(|entity| clients.get(entity))
+                        {
+                            client.send_fallible(ServerGeneral::ChatMsg(reply));
+                        }
+                    }
+                },
+                comp::ChatType::GuildMeta(tag) => {
+                    let guilds = ecs.read_resource::<Guilds>();
+                    if let Some(guild) = guilds.by_tag(tag) {
+                        for (client, player) in
+                            (&ecs.read_storage::<Client>(), &ecs.read_storage::<Player>()).join()
+                        {
+                            if guild.is_member(player.uuid()) {
+                                client.send_fallible(ServerGeneral::ChatMsg(resolved_msg.clone()));
+                            }
+                        }
+                    }
+                },
                 comp::ChatType::Group(from, g) => {
                     if group_info.is_none() {
                         // Group not found, reply with command error
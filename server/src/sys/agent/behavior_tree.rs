@@ -5,31 +5,49 @@ use common::{
             TRADE_INTERACTION_TIME,
         },
         dialogue::Subject,
-        Agent, Alignment, BehaviorCapability, BehaviorState, Body, BuffKind, CharacterState,
-        ControlAction, ControlEvent, Controller, InputKind, InventoryEvent, Pos, UtteranceKind,
+        item::Reagent,
+        object, Agent, Alignment, BehaviorCapability, BehaviorState, Body, BuffKind,
+        CharacterState, Content, ControlAction, ControlEvent, Controller, InputKind,
+        InventoryEvent, LightEmitter, Object, Pos, ScoutPing, ScoutPingKind, UtteranceKind, Vel,
+    },
+    consts::MAX_MOUNT_RANGE,
+    event::{
+        CreateObjectEvent, EmitExt, ExposeDisguiseEvent, GiveDirectionsEvent, ReanimateEvent,
+        ScoutPingsEvent,
     },
     path::TraversalConfig,
     rtsim::{NpcAction, RtSimEntity},
+    terrain::{Block, CoordinateConversions},
+    uid::Uid,
+};
+use rand::{
+    prelude::{SliceRandom, ThreadRng},
+    thread_rng, Rng,
 };
-use rand::{prelude::ThreadRng, thread_rng, Rng};
 use server_agent::data::AgentEmitters;
-use specs::Entity as EcsEntity;
-use vek::{Vec2, Vec3};
+use specs::{Entity as EcsEntity, Join};
+use vek::{Rgb, Vec2, Vec3};
 
 use self::interaction::{
-    handle_inbox_cancel_interactions, handle_inbox_finished_trade, handle_inbox_talk,
-    handle_inbox_trade_accepted, handle_inbox_trade_invite, handle_inbox_update_pending_trade,
-    increment_timer_deltatime, process_inbox_interaction, process_inbox_sound_and_hurt,
+    handle_inbox_cancel_interactions, handle_inbox_finished_trade, handle_inbox_haggle,
+    handle_inbox_talk, handle_inbox_trade_accepted, handle_inbox_trade_invite,
+    handle_inbox_update_pending_trade, increment_timer_deltatime, process_inbox_interaction,
+    process_inbox_sound_and_hurt,
 };
 
 use super::{
     consts::{
         DAMAGE_MEMORY_DURATION, FLEE_DURATION, HEALING_ITEM_THRESHOLD, MAX_PATROL_DIST,
-        MAX_STAY_DISTANCE, NORMAL_FLEE_DIR_DIST, NPC_PICKUP_RANGE, RETARGETING_THRESHOLD_SECONDS,
+        MAX_SCOUT_SIGHTINGS, MAX_STAY_DISTANCE, NORMAL_FLEE_DIR_DIST, NPC_PICKUP_RANGE,
+        REANIMATE_CHANCE, REANIMATE_RANGE, RETARGETING_THRESHOLD_SECONDS, SCOUT_ALTITUDE,
+        SCOUT_LOOP_DURATION, SCOUT_LOOP_RADIUS, SCOUT_SCAN_INTERVAL, SCOUT_SIGHT_RADIUS,
         STD_AWARENESS_DECAY_RATE,
     },
     data::{AgentData, ReadData, TargetData},
-    util::{get_entity_by_id, is_dead, is_dead_or_invulnerable, is_invulnerable, stop_pursuing},
+    util::{
+        entities_have_line_of_sight, get_entity_by_id, is_dead, is_dead_or_invulnerable,
+        is_invulnerable, stop_pursuing,
+    },
 };
 
 mod interaction;
@@ -82,6 +100,7 @@ impl BehaviorTree {
                 maintain_if_gliding,
                 react_on_dangerous_fall,
                 react_if_on_fire,
+                scout_if_sent,
                 target_if_attacked,
                 process_inbox_sound_and_hurt,
                 process_inbox_interaction,
@@ -129,7 +148,7 @@ impl BehaviorTree {
             && (agent.behavior.can(BehaviorCapability::SPEAK)
                 || agent.behavior.can(BehaviorCapability::TRADE))
         {
-            let mut tree: Vec<BehaviorFn> = vec![increment_timer_deltatime];
+            let mut tree: Vec<BehaviorFn> = vec![increment_timer_deltatime, handle_inbox_haggle];
             if agent.behavior.can(BehaviorCapability::SPEAK) {
                 tree.push(handle_inbox_talk);
             }
@@ -152,7 +171,12 @@ impl BehaviorTree {
     /// Attack the target, and heal self if applicable
     pub fn hostile() -> Self {
         Self {
-            tree: vec![heal_self_if_hurt, hurt_utterance, do_combat],
+            tree: vec![
+                heal_self_if_hurt,
+                hurt_utterance,
+                reanimate_corpses,
+                do_combat,
+            ],
         }
     }
 
@@ -280,6 +304,16 @@ fn target_if_attacked(bdata: &mut BehaviorData) -> bool {
                     if is_dead_or_invulnerable(attacker, bdata.read_data) {
                         bdata.agent.target = None;
                     } else {
+                        // No disguise holds up to turning on the ones you pretend to be
+                        if bdata
+                            .agent_data
+                            .is_fooled_by_disguise(attacker, bdata.read_data)
+                        {
+                            bdata
+                                .emitters
+                                .emit(ExposeDisguiseEvent { entity: attacker });
+                        }
+
                         if bdata.agent.target.is_none() {
                             bdata
                                 .controller
@@ -380,6 +414,159 @@ fn do_hostile_tree_if_hostile_and_aware(bdata: &mut BehaviorData) -> bool {
     false
 }
 
+/// If sent out to scout by the owner, fly a loop over the area they picked,
+/// reporting what can be seen from the air, then return to their shoulder
+fn scout_if_sent(bdata: &mut BehaviorData) -> bool {
+    let Some(mut scout) = bdata.agent.scout else {
+        return false;
+    };
+    let read_data = bdata.read_data;
+    let owner = match bdata.agent_data.alignment {
+        Some(Alignment::Owned(owner_uid)) => get_entity_by_id(*owner_uid, read_data)
+            .filter(|owner| !is_dead(*owner, read_data))
+            .and_then(|owner| Some((owner, *owner_uid, read_data.positions.get(owner)?))),
+        _ => None,
+    };
+    let Some((owner, owner_uid, owner_pos)) = owner else {
+        bdata.agent.scout = None;
+        return false;
+    };
+    let pos = bdata.agent_data.pos.0;
+    let time = read_data.time.0;
+
+    let dest = if scout.returning {
+        if pos.distance_squared(owner_pos.0) < MAX_MOUNT_RANGE.powi(2) {
+            // Back on the owner's shoulder
+            bdata.controller.push_event(ControlEvent::Mount(owner_uid));
+            bdata.agent.scout = None;
+            return true;
+        }
+        owner_pos.0
+    } else {
+        if read_data.is_riders.contains(*bdata.agent_data.entity) {
+            bdata.controller.push_event(ControlEvent::Unmount);
+            return true;
+        }
+        let from_target = pos.xy() - scout.target;
+        match scout.circling_since {
+            None if from_target.magnitude_squared() < SCOUT_LOOP_RADIUS.powi(2) => {
+                scout.circling_since = Some(time);
+            },
+            Some(since) if time - since > SCOUT_LOOP_DURATION => scout.returning = true,
+            _ => {},
+        }
+        let dest = if scout.circling_since.is_some() {
+            // Aim a little ahead along the loop, so that the bird keeps circling
+            let (sin, cos) = 0.5_f32.sin_cos();
+            let dir = from_target.try_normalized().unwrap_or_else(Vec2::unit_x);
+            scout.target
+                + Vec2::new(dir.x * cos - dir.y * sin, dir.x * sin + dir.y * cos)
+                    * SCOUT_LOOP_RADIUS
+        } else {
+            scout.target
+        };
+        // Fly high over the area, to get a good view of it
+        let ground_dist = read_data
+            .terrain
+            .ray(pos, pos - Vec3::unit_z() * SCOUT_ALTITUDE * 2.0)
+            .until(Block::is_solid)
+            .cast()
+            .0;
+        dest.with_z(pos.z - ground_dist + SCOUT_ALTITUDE)
+    };
+
+    if !scout.returning && time - scout.last_scan > SCOUT_SCAN_INTERVAL {
+        scout.last_scan = time;
+        let pings = spot_from_above(&bdata.agent_data, read_data, owner_uid);
+        if !pings.is_empty() {
+            bdata.emitters.emit(ScoutPingsEvent { owner, pings });
+        }
+    }
+
+    let offset = dest - pos;
+    bdata.controller.push_basic_input(InputKind::Fly);
+    if bdata.agent_data.physics_state.on_ground.is_some() {
+        bdata.controller.push_basic_input(InputKind::Jump);
+    }
+    bdata.controller.inputs.move_dir = offset.xy().try_normalized().unwrap_or_else(Vec2::zero);
+    bdata.controller.inputs.move_z = (offset.z / 10.0).clamp(-1.0, 1.0);
+
+    bdata.agent.scout = Some(scout);
+    true
+}
+
+/// What a scouting bird can make out below it: creatures in plain view, and any
+/// sites nearby. Only the nearest few creatures are checked for a clear line of
+/// sight, to keep the cost of each look around down.
+fn spot_from_above(agent_data: &AgentData, read_data: &ReadData, owner_uid: Uid) -> Vec<ScoutPing> {
+    let pos = agent_data.pos;
+    let owner_alignment = Alignment::Owned(owner_uid);
+
+    let mut sightings = read_data
+        .cached_spatial_grid
+        .0
+        .in_circle_aabr(pos.0.xy(), SCOUT_SIGHT_RADIUS)
+        .filter(|entity| entity != agent_data.entity && !is_dead(*entity, read_data))
+        .filter_map(|entity| {
+            let other_pos = read_data.positions.get(entity)?;
+            let body = read_data.bodies.get(entity)?;
+            let alignment = read_data.alignments.get(entity).copied();
+            // The owner and their companions needn't be reported
+            if matches!(body, Body::Object(_) | Body::ItemDrop(_) | Body::Ship(_))
+                || alignment == Some(owner_alignment)
+            {
+                return None;
+            }
+            let dist_sqr = other_pos.0.xy().distance_squared(pos.0.xy());
+            (dist_sqr < SCOUT_SIGHT_RADIUS.powi(2))
+                .then_some((entity, other_pos, body, alignment, dist_sqr))
+        })
+        .collect::<Vec<_>>();
+    sightings.sort_by(|a, b| a.4.total_cmp(&b.4));
+
+    #[cfg_attr(not(feature = "worldgen"), allow(unused_mut))]
+    let mut pings = sightings
+        .into_iter()
+        .take(MAX_SCOUT_SIGHTINGS)
+        .filter(|(entity, other_pos, body, _, _)| {
+            entities_have_line_of_sight(
+                pos,
+                agent_data.body,
+                agent_data.scale,
+                other_pos,
+                Some(body),
+                read_data.scales.get(*entity),
+                read_data,
+            )
+        })
+        .map(|(_, other_pos, _, alignment, _)| ScoutPing {
+            wpos: other_pos.0.xy(),
+            kind: if alignment.map_or(false, |a| a.hostile_towards(owner_alignment)) {
+                ScoutPingKind::Hostile
+            } else {
+                ScoutPingKind::Creature
+            },
+        })
+        .collect::<Vec<_>>();
+
+    // Sites are big enough to be made out from much further away
+    #[cfg(feature = "worldgen")]
+    pings.extend(
+        read_data
+            .world
+            .civs()
+            .sites()
+            .map(|site| site.center.cpos_to_wpos().as_::<f32>())
+            .filter(|wpos| wpos.distance_squared(pos.0.xy()) < (SCOUT_SIGHT_RADIUS * 4.0).powi(2))
+            .map(|wpos| ScoutPing {
+                wpos,
+                kind: ScoutPingKind::Site,
+            }),
+    );
+
+    pings
+}
+
 /// if owned, do the pet tree and stop the current BehaviorTree
 fn do_pet_tree_if_owned(bdata: &mut BehaviorData) -> bool {
     if let (Some(Target { target, .. }), Some(Alignment::Owned(uid))) =
@@ -552,6 +739,51 @@ fn handle_rtsim_actions(bdata: &mut BehaviorData) -> bool {
                     bdata.agent_data.chat_npc(msg, bdata.emitters);
                 }
             },
+            NpcAction::Emote(kind) => {
+                bdata.controller.push_action(ControlAction::Emote(kind));
+            },
+            NpcAction::LaunchFirework => {
+                let rng = &mut thread_rng();
+                let reagent = *[
+                    Reagent::Blue,
+                    Reagent::Green,
+                    Reagent::Purple,
+                    Reagent::Red,
+                    Reagent::White,
+                    Reagent::Yellow,
+                ]
+                .choose(rng)
+                .expect("Reagents are not empty");
+                // Launched like a thrown firework, straight up into the sky
+                bdata.emitters.emit(CreateObjectEvent {
+                    pos: Pos(bdata.agent_data.pos.0 + Vec3::unit_z() * 0.25),
+                    vel: Vel(Vec3::new(
+                        rng.gen_range(-15.0..15.0),
+                        rng.gen_range(-15.0..15.0),
+                        rng.gen_range(80.0..110.0),
+                    )),
+                    body: object::Body::for_firework(reagent),
+                    object: Some(Object::Firework {
+                        owner: Some(*bdata.agent_data.uid),
+                        reagent,
+                    }),
+                    item: None,
+                    light_emitter: Some(LightEmitter {
+                        animated: true,
+                        flicker: 2.0,
+                        strength: 2.0,
+                        col: Rgb::new(1.0, 1.0, 0.0),
+                    }),
+                    stats: None,
+                });
+            },
+            NpcAction::GiveDirections(target, directions) => {
+                if let Some(entity) = bdata.read_data.lookup_actor(target) {
+                    bdata
+                        .emitters
+                        .emit(GiveDirectionsEvent { entity, directions });
+                }
+            },
             NpcAction::Attack(target) => {
                 if let Some(target) = bdata.read_data.lookup_actor(target) {
                     bdata.agent.target = Some(Target::new(
@@ -690,6 +922,45 @@ fn hurt_utterance(bdata: &mut BehaviorData) -> bool {
     false
 }
 
+/// Necromancers occasionally raise the freshest corpse near them to fight by
+/// their side
+fn reanimate_corpses(bdata: &mut BehaviorData) -> bool {
+    let BehaviorData {
+        agent,
+        agent_data,
+        read_data,
+        emitters,
+        rng,
+        ..
+    } = bdata;
+
+    if agent.behavior.can(BehaviorCapability::REANIMATE)
+        && rng.gen::<f32>() < REANIMATE_CHANCE * read_data.dt.0
+        && let Some(corpse) = (
+            &read_data.entities,
+            &read_data.corpses,
+            &read_data.positions,
+        )
+            .join()
+            .filter(|(_, _, pos)| {
+                pos.0.distance_squared(agent_data.pos.0) < REANIMATE_RANGE.powi(2)
+            })
+            .max_by(|(_, a, _), (_, b, _)| a.decays_at.0.total_cmp(&b.decays_at.0))
+            .map(|(entity, _, _)| entity)
+    {
+        emitters.emit(ReanimateEvent {
+            necromancer: *agent_data.entity,
+            corpse,
+        });
+        agent_data.chat_npc_if_allowed_to_speak(
+            Content::localized("npc-speech-necromancer_reanimate"),
+            agent,
+            emitters,
+        );
+    }
+    false
+}
+
 fn update_target_awareness(bdata: &mut BehaviorData) -> bool {
     let BehaviorData {
         agent,
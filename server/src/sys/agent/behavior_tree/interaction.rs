@@ -1,9 +1,9 @@
 use common::{
     comp::{
-        agent::{AgentEvent, Target, TimerAction},
+        agent::{AgentEvent, Haggle, Target, TimerAction},
         compass::{Direction, Distance},
         dialogue::Subject,
-        inventory::item::{ItemTag, MaterialStatManifest},
+        inventory::item::{ItemTag, MaterialStatManifest, Quality},
         invite::{InviteKind, InviteResponse},
         tool::AbilityMap,
         BehaviorState, Content, ControlAction, Item, TradingBehavior, UnresolvedChatMsg,
@@ -11,11 +11,17 @@ use common::{
     },
     event::{ChatEvent, EmitExt, ProcessTradeActionEvent},
     rtsim::{Actor, NpcInput, PersonalityTrait},
-    trade::{TradeAction, TradePhase, TradeResult},
+    trade::{PendingTrade, ReducedInventory, TradeAction, TradePhase, TradeResult},
 };
 use rand::{thread_rng, Rng};
 
-use crate::sys::agent::util::get_entity_by_id;
+use crate::sys::agent::{
+    consts::{
+        FESTIVAL_PRICE_FACTOR, HAGGLE_PREMIUM, MAX_HAGGLE_ATTEMPTS, MAX_HAGGLE_DISCOUNT,
+        MAX_HAGGLE_PREMIUM,
+    },
+    util::get_entity_by_id,
+};
 
 use super::{BehaviorData, BehaviorTree};
 
@@ -89,7 +95,11 @@ pub fn handle_inbox_talk(bdata: &mut BehaviorData) -> bool {
         let by_entity = get_entity_by_id(by, read_data);
 
         if let Some(rtsim_outbox) = &mut agent.rtsim_outbox {
-            if let Subject::Regular | Subject::Mood | Subject::Work = subject
+            if let Subject::Regular
+            | Subject::Mood
+            | Subject::Work
+            | Subject::Location(_)
+            | Subject::Person(_) = subject
                 && let Some(by_entity) = by_entity
                 && let Some(actor) = read_data
                     .presences
@@ -262,7 +272,8 @@ pub fn handle_inbox_talk(bdata: &mut BehaviorData) -> bool {
                             agent_data.chat_npc(Content::Plain(msg), emitters);
                         }
                     },
-                    Subject::Work => {},
+                    // Handled by `handle_inbox_haggle`
+                    Subject::Work | Subject::Haggle => {},
                 }
             }
         }
@@ -270,6 +281,108 @@ pub fn handle_inbox_talk(bdata: &mut BehaviorData) -> bool {
     true
 }
 
+/// Handles a Talk event about haggling if the front of the agent's inbox
+/// contains one
+///
+/// Whether the agent gives in depends on how agreeable they are, how they feel
+/// about the customer and how close the current offer already is to their
+/// asking price.
+pub fn handle_inbox_haggle(bdata: &mut BehaviorData) -> bool {
+    let BehaviorData {
+        agent,
+        agent_data,
+        read_data,
+        emitters,
+        rng,
+        ..
+    } = bdata;
+
+    if !matches!(
+        agent.inbox.front(),
+        Some(AgentEvent::Talk(_, Subject::Haggle))
+    ) {
+        return false;
+    }
+
+    if let Some(AgentEvent::Talk(by, _)) = agent.inbox.pop_front()
+        && agent.behavior.is(BehaviorState::TRADING)
+        && agent.behavior.trade_site().is_some()
+        && let Some(by_entity) = get_entity_by_id(by, read_data)
+        && agent
+            .target
+            .map_or(false, |target| target.target == by_entity)
+    {
+        let haggle = &mut agent.haggle;
+        let personality = &agent.rtsim_controller.personality;
+        let sentiment = agent.rtsim_controller.target_sentiment;
+
+        let (accepted, content) = if haggle.attempts >= MAX_HAGGLE_ATTEMPTS {
+            (
+                false,
+                Content::localized("npc-speech-merchant_haggle_exhausted"),
+            )
+        } else {
+            haggle.attempts += 1;
+            // Lowball offers leave no room for negotiation
+            let lowball = haggle
+                .coverage
+                .map_or(false, |coverage| coverage < 1.0 - MAX_HAGGLE_DISCOUNT);
+            let willingness =
+                personality.agreeableness() + sentiment - 0.15 * f32::from(haggle.attempts);
+            if !lowball && rng.gen::<f32>() < willingness.clamp(0.05, 0.9) {
+                let discount =
+                    rng.gen_range(0.02..=MAX_HAGGLE_DISCOUNT * willingness.clamp(0.1, 1.0));
+                haggle.price_factor =
+                    (haggle.price_factor - discount).max(1.0 - MAX_HAGGLE_DISCOUNT);
+                (
+                    true,
+                    Content::localized_with_args("npc-speech-merchant_haggle_accept", [(
+                        "discount",
+                        ((1.0 - haggle.price_factor) * 100.0).round().max(0.0) as u64,
+                    )]),
+                )
+            } else if sentiment < 0.0 || personality.is(PersonalityTrait::Disagreeable) {
+                haggle.price_factor =
+                    (haggle.price_factor + HAGGLE_PREMIUM).min(1.0 + MAX_HAGGLE_PREMIUM);
+                (
+                    false,
+                    Content::localized("npc-speech-merchant_haggle_insulted"),
+                )
+            } else if lowball {
+                (
+                    false,
+                    Content::localized("npc-speech-merchant_haggle_lowball"),
+                )
+            } else {
+                (
+                    false,
+                    Content::localized("npc-speech-merchant_haggle_decline"),
+                )
+            }
+        };
+
+        emitters.emit(ChatEvent(UnresolvedChatMsg::npc_tell(
+            *agent_data.uid,
+            by,
+            content,
+        )));
+
+        if let Some(rtsim_outbox) = &mut agent.rtsim_outbox
+            && let Some(actor) = read_data
+                .presences
+                .get(by_entity)
+                .and_then(|p| p.kind.character_id().map(Actor::Character))
+                .or_else(|| Some(Actor::Npc(read_data.rtsim_entities.get(by_entity)?.0)))
+        {
+            rtsim_outbox.push_back(NpcInput::Haggled {
+                by: actor,
+                accepted,
+            });
+        }
+    }
+    true
+}
+
 /// Handles TradeInvite event if the front of the agent's inbox contains one
 pub fn handle_inbox_trade_invite(bdata: &mut BehaviorData) -> bool {
     let BehaviorData {
@@ -393,6 +506,7 @@ pub fn handle_inbox_finished_trade(bdata: &mut BehaviorData) -> bool {
             agent.behavior.unset(BehaviorState::TRADING);
             agent.target = None;
         }
+        agent.haggle = Haggle::default();
     }
     true
 }
@@ -436,9 +550,16 @@ pub fn handle_inbox_update_pending_trade(bdata: &mut BehaviorData) -> bool {
             };
             match agent.behavior.trading_behavior {
                 TradingBehavior::RequireBalanced { .. } => {
+                    let reputation = agent.rtsim_controller.target_reputation;
+                    let rare_refused = reputation
+                        .map_or(false, |reputation| !reputation.can_buy_rare_wares())
+                        && offers_rare_wares(&pending, &inventories, who);
                     let balance0 = prices.balance(&pending.offers, &inventories, 1 - who, true);
                     let balance1 = prices.balance(&pending.offers, &inventories, who, false);
                     match (balance0, balance1) {
+                        _ if rare_refused => {
+                            message(Content::localized("npc-speech-merchant_reputation_rare"))
+                        },
                         // TODO: Localise
                         (_, None) => message(Content::Plain(
                             "I'm not willing to sell that item".to_string(),
@@ -448,6 +569,18 @@ pub fn handle_inbox_update_pending_trade(bdata: &mut BehaviorData) -> bool {
                             "I'm not willing to buy that item".to_string(),
                         )),
                         (Some(balance0), Some(balance1)) => {
+                            // Haggling and the customer's reputation may have earned them a
+                            // discount or premium, and festivals bring discounts for everyone
+                            let festival_factor = if agent.rtsim_controller.festival {
+                                FESTIVAL_PRICE_FACTOR
+                            } else {
+                                1.0
+                            };
+                            let balance1 = balance1
+                                * agent.haggle.price_factor
+                                * reputation.map_or(1.0, |reputation| reputation.price_factor())
+                                * festival_factor;
+                            agent.haggle.coverage = (balance1 > 0.0).then(|| balance0 / balance1);
                             if balance0 >= balance1 {
                                 // If the trade is favourable to us, only send an accept message if
                                 // we're not already accepting
@@ -539,6 +672,27 @@ pub fn handle_inbox_update_pending_trade(bdata: &mut BehaviorData) -> bool {
     true
 }
 
+/// Whether we offer any ware rare enough to be kept for customers our faction
+/// holds in good standing
+fn offers_rare_wares(
+    pending: &PendingTrade,
+    inventories: &[Option<ReducedInventory>; 2],
+    who: usize,
+) -> bool {
+    let ability_map = AbilityMap::load().read();
+    let msm = MaterialStatManifest::load().read();
+    inventories[who].as_ref().map_or(false, |ri| {
+        pending.offers[who].keys().any(|slot| {
+            ri.inventory
+                .get(slot)
+                .and_then(|item| {
+                    Item::new_from_item_definition_id(item.name.as_ref(), &ability_map, &msm).ok()
+                })
+                .map_or(false, |item| item.quality() >= Quality::High)
+        })
+    })
+}
+
 /// Deny any received interaction:
 /// - `AgentEvent::Talk` and `AgentEvent::TradeAccepted` are cut short by an
 ///   "I'm busy" message
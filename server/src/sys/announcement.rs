@@ -0,0 +1,59 @@
+use crate::{client::Client, settings::EditableSettings};
+use common::{
+    comp::{ChatType, Presence},
+    resources::Time,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use hashbrown::HashMap;
+use specs::{Join, Read, ReadExpect, ReadStorage, Write};
+
+/// When each announcement is next due, by name
+#[derive(Default)]
+pub struct AnnouncementSchedule(HashMap<String, f64>);
+
+/// This system broadcasts the recurring announcements of the server. They are
+/// sent as localization keys, so each client shows them in its own language.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, Time>,
+        ReadExpect<'a, EditableSettings>,
+        Write<'a, AnnouncementSchedule>,
+        ReadStorage<'a, Client>,
+        ReadStorage<'a, Presence>,
+    );
+
+    const NAME: &'static str = "announcement";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (time, editable_settings, mut schedule, clients, presences): Self::SystemData,
+    ) {
+        let announcements = &editable_settings.announcements;
+        // Forget removed announcements, so that they start over if added again
+        schedule
+            .0
+            .retain(|name, _| announcements.contains_key(name));
+
+        for (name, announcement) in announcements.iter().filter(|(_, a)| a.enabled) {
+            let interval = announcement.interval.as_secs_f64();
+            let next = schedule
+                .0
+                .entry_ref(name.as_str())
+                .or_insert(time.0 + interval);
+            if *next > time.0 {
+                continue;
+            }
+            *next = time.0 + interval;
+
+            let msg = ServerGeneral::ChatMsg(ChatType::Meta.into_msg(announcement.content()));
+            for (client, _) in (&clients, &presences).join() {
+                client.send_fallible(msg.clone());
+            }
+        }
+    }
+}
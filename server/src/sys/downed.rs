@@ -0,0 +1,36 @@
+use common::comp::{CharacterState, Health};
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{Entities, Join, ReadStorage, WriteStorage};
+
+/// This system kills downed players that weren't revived in time, the stats
+/// system then handles their death as usual
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        ReadStorage<'a, CharacterState>,
+        WriteStorage<'a, Health>,
+    );
+
+    const NAME: &'static str = "downed";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(_job: &mut Job<Self>, (entities, character_states, mut healths): Self::SystemData) {
+        let bled_out = (&entities, &character_states, &healths)
+            .join()
+            .filter(|(_, character_state, health)| {
+                matches!(character_state, CharacterState::Downed(data) if data.has_bled_out())
+                    && !health.should_die()
+            })
+            .map(|(entity, _, _)| entity)
+            .collect::<Vec<_>>();
+
+        for entity in bled_out {
+            if let Some(mut health) = healths.get_mut(entity) {
+                health.kill();
+            }
+        }
+    }
+}
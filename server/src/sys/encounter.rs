@@ -0,0 +1,220 @@
+use crate::sys::terrain::SpawnEntityData;
+use common::{
+    comp::{
+        self,
+        buff::{Buff, BuffCategory, BuffChange, BuffData, BuffSource, DestInfo},
+        encounter::{EncounterScript, EncounterState},
+        Agent, Controller, Encounter, Health, Mass, Pos, Stats,
+    },
+    event::{BuffEvent, CreateNpcEvent, CreateSpriteEvent, EmitExt},
+    event_emitters,
+    generation::EntityInfo,
+    resources::Time,
+    terrain::TerrainGrid,
+    util::Dir,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use rand::Rng;
+use specs::{Entities, Entity as EcsEntity, Join, Read, ReadExpect, ReadStorage, WriteStorage};
+use std::f32::consts::TAU;
+use vek::*;
+
+event_emitters! {
+    struct Events[Emitters] {
+        create_npc: CreateNpcEvent,
+        create_sprite: CreateSpriteEvent,
+        buff: BuffEvent,
+    }
+}
+
+/// This system runs boss encounter scripts: phase transitions, ability
+/// rotations, add spawns, arena hazards and enrage timers.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Events<'a>,
+        Read<'a, Time>,
+        ReadExpect<'a, TerrainGrid>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Agent>,
+        ReadStorage<'a, Stats>,
+        ReadStorage<'a, Mass>,
+        WriteStorage<'a, Encounter>,
+        WriteStorage<'a, Controller>,
+    );
+
+    const NAME: &'static str = "encounter";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            events,
+            time,
+            terrain,
+            positions,
+            healths,
+            agents,
+            stats,
+            masses,
+            mut encounters,
+            mut controllers,
+        ): Self::SystemData,
+    ) {
+        let mut emitters = events.get_emitters();
+        let mut rng = rand::thread_rng();
+
+        for (entity, encounter, pos, health, agent, controller) in (
+            &entities,
+            &mut encounters,
+            &positions,
+            &healths,
+            &agents,
+            &mut controllers,
+        )
+            .join()
+        {
+            if health.is_dead {
+                continue;
+            }
+
+            let engaged = agent.target.map_or(false, |target| target.aggro_on);
+            if !engaged {
+                // Reset the encounter once the boss loses its target, so that enrage timers
+                // and rotations start over on the next pull.
+                if encounter.state.is_some() {
+                    encounter.state = None;
+                }
+                continue;
+            }
+
+            let Some(script) = encounter.load_script() else {
+                continue;
+            };
+            let script = script.read();
+
+            let state = encounter.state.get_or_insert_with(|| {
+                spawn_adds(&script, 0, entity, pos, &mut rng, &mut emitters);
+                EncounterState::enter_phase(&script, 0, *time, *time)
+            });
+
+            // Phase transitions
+            let prev_phase = state.phase;
+            let next_phase = script.phase_for_health(prev_phase, health.fraction());
+            if next_phase != prev_phase {
+                let enraged = state.enraged;
+                *state = EncounterState::enter_phase(&script, next_phase, state.started, *time);
+                state.enraged = enraged;
+                // Adds of every phase that was passed through are spawned, even if the boss
+                // skipped it entirely with a single hit.
+                for phase in prev_phase + 1..=next_phase {
+                    spawn_adds(&script, phase, entity, pos, &mut rng, &mut emitters);
+                }
+            }
+
+            let Some(phase) = script.phases.get(state.phase) else {
+                continue;
+            };
+
+            // Ability rotation
+            let (step_idx, next_time) = state.next_rotation;
+            if !phase.rotation.is_empty() && time.0 >= next_time.0 {
+                if let Some(step) = phase.rotation.get(step_idx) {
+                    controller.push_basic_input(step.input);
+                }
+                let next_idx = (step_idx + 1) % phase.rotation.len();
+                let delay = phase.rotation[next_idx].delay;
+                state.next_rotation = (next_idx, Time(time.0 + delay.0));
+            }
+
+            // Arena hazards
+            for (hazard, next_time) in phase.hazards.iter().zip(state.next_hazards.iter_mut()) {
+                if time.0 < next_time.0 {
+                    continue;
+                }
+                *next_time = Time(time.0 + hazard.interval.0);
+                for _ in 0..hazard.count {
+                    let offset = Vec2::new(
+                        rng.gen_range(-hazard.radius..=hazard.radius),
+                        rng.gen_range(-hazard.radius..=hazard.radius),
+                    );
+                    let hazard_pos = (pos.0.xy() + offset).with_z(pos.0.z).as_::<i32>();
+                    if let Some(ground) = terrain.try_find_ground(hazard_pos) {
+                        emitters.emit(CreateSpriteEvent {
+                            pos: ground,
+                            sprite: hazard.sprite,
+                            del_timeout: hazard.lifetime,
+                        });
+                    }
+                }
+            }
+
+            // Enrage timer
+            if let Some(enrage) = &script.enrage
+                && !state.enraged
+                && time.0 - state.started.0 >= enrage.after.0
+            {
+                state.enraged = true;
+                let dest_info = DestInfo {
+                    stats: stats.get(entity),
+                    mass: masses.get(entity),
+                };
+                for (kind, strength) in &enrage.buffs {
+                    emitters.emit(BuffEvent {
+                        entity,
+                        buff_change: BuffChange::Add(Buff::new(
+                            *kind,
+                            BuffData::new(*strength, None),
+                            vec![BuffCategory::PersistOnDeath],
+                            BuffSource::World,
+                            *time,
+                            dest_info,
+                            None,
+                        )),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn spawn_adds(
+    script: &EncounterScript,
+    phase: usize,
+    boss: EcsEntity,
+    pos: &Pos,
+    rng: &mut impl Rng,
+    emitters: &mut Emitters,
+) {
+    let Some(phase) = script.phases.get(phase) else {
+        return;
+    };
+
+    for add in &phase.adds {
+        for i in 0..add.amount {
+            let offset =
+                Vec2::unit_x().rotated_z(TAU * (i as f32 / add.amount as f32)) * add.radius;
+            let add_pos = pos.0 + offset.with_z(0.0);
+            let entity_info = EntityInfo::at(add_pos).with_asset_expect(&add.entity, rng, None);
+            match SpawnEntityData::from_entity_info(entity_info) {
+                SpawnEntityData::Npc(data) => {
+                    let (npc_builder, pos) = data.to_npc_builder();
+                    emitters.emit(CreateNpcEvent {
+                        pos,
+                        ori: comp::Ori::from(Dir::random_2d(rng)),
+                        npc: npc_builder.with_anchor(comp::Anchor::Entity(boss)),
+                        rider: None,
+                    });
+                },
+                SpawnEntityData::Special(_, _) => {
+                    tracing::warn!("Encounter adds must be npcs, found: {}", add.entity);
+                },
+            }
+        }
+    }
+}
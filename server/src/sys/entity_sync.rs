@@ -8,7 +8,7 @@ use common::{
     mounting::Rider,
     outcome::Outcome,
     region::{Event as RegionEvent, RegionMap},
-    resources::{PlayerPhysicsSettings, Time, TimeOfDay, TimeScale},
+    resources::{PlayerPhysicsSetting, PlayerPhysicsSettings, Time, TimeOfDay, TimeScale},
     terrain::TerrainChunkSize,
     uid::Uid,
     vol::RectVolSize,
@@ -249,13 +249,7 @@ impl<'a> System<'a> for Sys {
                     {
                         // Decide how regularly to send physics updates.
                         let send_now = if client_entity == &entity {
-                            should_sync_client_physics(
-                                entity,
-                                &player_physics_settings,
-                                &players,
-                                &force_updates,
-                                is_rider,
-                            )
+                            should_sync_client_physics(entity, &force_updates, is_rider)
                         } else if matches!(collider, Some(Collider::Voxel { .. })) {
                             // Things with a voxel collider (airships, etc.) need to have very
                             // stable physics so we always send updated
@@ -312,13 +306,14 @@ impl<'a> System<'a> for Sys {
         job.cpu_stats.measure(common_ecs::ParMode::Single);
 
         // Sync components that are only synced for the client's own entity.
-        for (entity, client, &uid, (maybe_pos, last_pos), vel, ori) in (
+        for (entity, client, &uid, (maybe_pos, last_pos), vel, ori, presence) in (
             &entities,
             &clients,
             uids,
             (positions.maybe(), last_pos.mask().maybe()),
             (&velocities, last_vel.mask().maybe()).maybe(),
             (&orientations, last_vel.mask().maybe()).maybe(),
+            presences.maybe(),
         )
             .join()
         {
@@ -334,13 +329,7 @@ impl<'a> System<'a> for Sys {
             );
 
             if include_all_comps && let Some(&pos) = maybe_pos {
-                let send_now = should_sync_client_physics(
-                    entity,
-                    &player_physics_settings,
-                    &players,
-                    &force_updates,
-                    is_rider,
-                );
+                let send_now = should_sync_client_physics(entity, &force_updates, is_rider);
                 add_physics_components(
                     send_now,
                     &mut comp_sync_package,
@@ -358,6 +347,21 @@ impl<'a> System<'a> for Sys {
                     force_updates.get(entity).map_or(0, |f| f.counter()),
                 ));
             }
+
+            // Clients with server-authoritative physics predict their own movement, so
+            // rather than overwriting it we tell them which of their inputs the
+            // current state is based on and let them reconcile
+            if let (Some(presence), Some(&pos), Some((&vel, _))) = (presence, maybe_pos, vel)
+                && player_physics_setting(entity, &player_physics_settings, &players)
+                    .server_authoritative()
+                && !should_sync_client_physics(entity, &force_updates, is_rider)
+            {
+                client.send_fallible(ServerGeneral::PlayerPhysicsAck {
+                    input_seq: presence.last_input_seq,
+                    pos,
+                    vel,
+                });
+            }
         }
 
         // Update the last physics components for each entity
@@ -460,25 +464,28 @@ impl<'a> System<'a> for Sys {
     }
 }
 
+fn player_physics_setting(
+    entity: specs::Entity,
+    player_physics_settings: &PlayerPhysicsSettings,
+    players: &ReadStorage<'_, Player>,
+) -> PlayerPhysicsSetting {
+    players
+        .get(entity)
+        .and_then(|p| player_physics_settings.settings.get(&p.uuid()).copied())
+        .unwrap_or_default()
+}
+
 /// Determines whether a client should receive an update about its own physics
 /// components.
 fn should_sync_client_physics(
     entity: specs::Entity,
-    player_physics_settings: &PlayerPhysicsSettings,
-    players: &ReadStorage<'_, Player>,
     force_updates: &WriteStorage<'_, ForceUpdate>,
     is_rider: &ReadStorage<'_, Is<Rider>>,
 ) -> bool {
-    let player_physics_setting = players
-        .get(entity)
-        .and_then(|p| player_physics_settings.settings.get(&p.uuid()).copied())
-        .unwrap_or_default();
     // Don't send client physics updates about itself unless force update is
-    // set or the client is subject to
-    // server-authoritative physics
-    force_updates.get(entity).map_or(false, |f| f.is_forced())
-        || player_physics_setting.server_authoritative()
-        || is_rider.contains(entity)
+    // set or the client is riding something. Clients subject to
+    // server-authoritative physics are sent `PlayerPhysicsAck` instead.
+    force_updates.get(entity).map_or(false, |f| f.is_forced()) || is_rider.contains(entity)
 }
 
 /// Adds physics components if `send_now` is true or `Option<Last<T>>` is
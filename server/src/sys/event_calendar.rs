@@ -0,0 +1,227 @@
+#[cfg(feature = "worldgen")]
+use crate::rtsim::RtSim;
+use crate::{client::Client, settings::EditableSettings, sys::terrain::SpawnEntityData};
+use chrono::{DateTime, Utc};
+use common::{
+    calendar::{CalendarEntry, ScheduledEventKind},
+    comp::{self, ChatType, Content, Presence},
+    event::{CreateNpcEvent, EmitExt},
+    event_emitters,
+    generation::EntityInfo,
+    resources::Time,
+    util::Dir,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use hashbrown::HashMap;
+#[cfg(feature = "worldgen")]
+use specs::WriteExpect;
+use specs::{Join, Read, ReadExpect, ReadStorage, Write};
+use tracing::warn;
+
+/// How often, in seconds, the calendar is checked for events starting or
+/// ending
+const CALENDAR_UPDATE_INTERVAL: f64 = 10.0;
+/// How far ahead upcoming events are shown to players
+const CALENDAR_LOOKAHEAD_DAYS: i64 = 14;
+
+#[cfg(feature = "worldgen")]
+type RtSimData<'a> = WriteExpect<'a, RtSim>;
+#[cfg(not(feature = "worldgen"))]
+type RtSimData<'a> = ();
+
+/// The ongoing and upcoming occurrences of the events in the server's event
+/// calendar
+#[derive(Default)]
+pub struct EventCalendarState {
+    entries: Vec<CalendarEntry>,
+    /// Occurrences shown to players, by event name and start. Players are only
+    /// sent the calendar again when these change.
+    shown: Vec<(String, DateTime<Utc>)>,
+    /// Start of the currently running occurrence of each event, by name
+    active: HashMap<String, (DateTime<Utc>, ScheduledEventKind)>,
+    next_update: f64,
+}
+
+impl EventCalendarState {
+    /// Ongoing and upcoming events, soonest first
+    pub fn entries(&self) -> &[CalendarEntry] { &self.entries }
+
+    /// Factor applied to all experience rewards by the bonus experience events
+    /// that are currently running
+    pub fn exp_multiplier(&self) -> f32 {
+        self.active
+            .values()
+            .filter_map(|(_, kind)| match kind {
+                ScheduledEventKind::BonusExperience { multiplier } => Some(*multiplier),
+                _ => None,
+            })
+            .product()
+    }
+}
+
+event_emitters! {
+    struct Events[Emitters] {
+        create_npc: CreateNpcEvent,
+    }
+}
+
+/// This system runs the events of the server's event calendar as they start
+/// and end, and keeps players informed about upcoming ones.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Events<'a>,
+        Read<'a, Time>,
+        ReadExpect<'a, EditableSettings>,
+        Write<'a, EventCalendarState>,
+        ReadStorage<'a, Client>,
+        ReadStorage<'a, Presence>,
+        RtSimData<'a>,
+    );
+
+    const NAME: &'static str = "event_calendar";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (events, time, editable_settings, mut state, clients, presences, rtsim): Self::SystemData,
+    ) {
+        if state.next_update > time.0 {
+            return;
+        }
+        state.next_update = time.0 + CALENDAR_UPDATE_INTERVAL;
+
+        let mut emitters = events.get_emitters();
+        #[cfg(feature = "worldgen")]
+        let mut rtsim = rtsim;
+        #[cfg(not(feature = "worldgen"))]
+        let _ = rtsim;
+
+        let now = Utc::now();
+        let horizon = now + chrono::Duration::days(CALENDAR_LOOKAHEAD_DAYS);
+        // Server time of the given point in time, the server clock doesn't follow
+        // the wall clock
+        let to_time =
+            |at: DateTime<Utc>| Time(time.0 + (at - now).num_milliseconds() as f64 / 1000.0);
+
+        let mut upcoming = Vec::new();
+        let mut running = HashMap::new();
+        for (name, event) in editable_settings
+            .event_calendar
+            .iter()
+            .filter(|(_, event)| event.enabled)
+        {
+            let Some(start) = event.occurrence(now).filter(|start| *start < horizon) else {
+                continue;
+            };
+            if start <= now {
+                running.insert(name.clone(), (start, event.kind.clone()));
+            }
+            let ends_at = chrono::Duration::from_std(event.duration)
+                .map_or(start, |duration| start + duration);
+            upcoming.push((name.clone(), start, CalendarEntry {
+                name: event.name.clone(),
+                kind: event.kind.clone(),
+                starts_at: to_time(start),
+                ends_at: to_time(ends_at),
+            }));
+        }
+        upcoming.sort_by_key(|(name, start, _)| (*start, name.clone()));
+
+        let notify = |content: Content| {
+            let msg = ServerGeneral::ChatMsg(ChatType::Meta.into_msg(content));
+            for (client, _) in (&clients, &presences).join() {
+                client.send_fallible(msg.clone());
+            }
+        };
+
+        // Events that ended, or were disabled or removed while running
+        let ended = state
+            .active
+            .iter()
+            .filter(|(name, (start, _))| running.get(*name).map(|(s, _)| s) != Some(start))
+            .map(|(name, (_, kind))| (name.clone(), kind.clone()))
+            .collect::<Vec<_>>();
+        for (name, kind) in ended {
+            state.active.remove(&name);
+            #[cfg(feature = "worldgen")]
+            match kind {
+                ScheduledEventKind::MarketDay { site } => rtsim.hook_market_day(site, false),
+                ScheduledEventKind::Festival { site } => rtsim.hook_festival(site, false),
+                ScheduledEventKind::WorldBoss { .. }
+                | ScheduledEventKind::BonusExperience { .. } => {},
+            }
+            #[cfg(not(feature = "worldgen"))]
+            let _ = kind;
+        }
+
+        for (name, (start, kind)) in running {
+            if state.active.contains_key(&name) {
+                continue;
+            }
+            match &kind {
+                ScheduledEventKind::WorldBoss { entity_config, pos } => {
+                    let rng = &mut rand::thread_rng();
+                    let entity_info =
+                        EntityInfo::at(*pos).with_asset_expect(entity_config, rng, None);
+                    match SpawnEntityData::from_entity_info(entity_info) {
+                        SpawnEntityData::Npc(data) => {
+                            let (npc_builder, pos) = data.to_npc_builder();
+                            emitters.emit(CreateNpcEvent {
+                                pos,
+                                ori: comp::Ori::from(Dir::random_2d(rng)),
+                                npc: npc_builder,
+                                rider: None,
+                            });
+                        },
+                        SpawnEntityData::Special(_, _) => {
+                            warn!("World bosses must be npcs, found: {entity_config}");
+                        },
+                    }
+                },
+                ScheduledEventKind::BonusExperience { .. } => {},
+                #[cfg(feature = "worldgen")]
+                ScheduledEventKind::MarketDay { site } => rtsim.hook_market_day(site.clone(), true),
+                #[cfg(feature = "worldgen")]
+                ScheduledEventKind::Festival { site } => rtsim.hook_festival(site.clone(), true),
+                #[cfg(not(feature = "worldgen"))]
+                ScheduledEventKind::MarketDay { .. } | ScheduledEventKind::Festival { .. } => {},
+            }
+            if let Some(event) = editable_settings.event_calendar.get(&name) {
+                notify(Content::localized_with_args("calendar-event-started", [(
+                    "event",
+                    event.name.clone(),
+                )]));
+            }
+            state.active.insert(name, (start, kind));
+        }
+
+        let shown = upcoming
+            .iter()
+            .map(|(name, start, _)| (name.clone(), *start))
+            .collect::<Vec<_>>();
+        // Town criers announce festivals that haven't started yet
+        #[cfg(feature = "worldgen")]
+        let festivals = upcoming
+            .iter()
+            .filter(|(_, start, _)| *start > now)
+            .filter_map(|(_, _, entry)| match &entry.kind {
+                ScheduledEventKind::Festival { site } => Some(site.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        state.entries = upcoming.into_iter().map(|(_, _, entry)| entry).collect();
+        if shown != state.shown {
+            state.shown = shown;
+            #[cfg(feature = "worldgen")]
+            rtsim.hook_upcoming_festivals(festivals);
+            let msg = ServerGeneral::EventCalendar(state.entries.clone());
+            for (client, _) in (&clients, &presences).join() {
+                client.send_fallible(msg.clone());
+            }
+        }
+    }
+}
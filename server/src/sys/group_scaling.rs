@@ -0,0 +1,112 @@
+use common::{
+    comp::{
+        group::{self, Role},
+        Agent, Alignment, Group, GroupScaling, Health, Player, Pos, Stats,
+    },
+    terrain::{CoordinateConversions, SiteKindMeta, TerrainGrid},
+    uid::Uid,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{Entities, Join, ReadExpect, ReadStorage, WriteStorage};
+use vek::*;
+
+/// Group members further away from the enemy than this aren't considered to
+/// be taking part in the fight
+const SCALING_RANGE: f32 = 150.0;
+
+fn in_dungeon(terrain: &TerrainGrid, pos: Vec3<f32>) -> bool {
+    terrain
+        .get_key(pos.xy().as_::<i32>().wpos_to_cpos())
+        .map_or(false, |chunk| {
+            matches!(chunk.meta().site(), Some(SiteKindMeta::Dungeon(_)))
+        })
+}
+
+/// This system scales dungeon enemies to the number of group members present
+/// in the dungeon at the moment the enemy first engages a player.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, TerrainGrid>,
+        ReadStorage<'a, Agent>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Alignment>,
+        ReadStorage<'a, Group>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Stats>,
+        WriteStorage<'a, Health>,
+        WriteStorage<'a, GroupScaling>,
+    );
+
+    const NAME: &'static str = "group_scaling";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            terrain,
+            agents,
+            positions,
+            alignments,
+            groups,
+            players,
+            uids,
+            stats,
+            mut healths,
+            mut group_scalings,
+        ): Self::SystemData,
+    ) {
+        let activated = (
+            &entities,
+            &agents,
+            &positions,
+            &alignments,
+            !&group_scalings,
+        )
+            .join()
+            .filter(|(_, _, _, alignment, _)| matches!(alignment, Alignment::Enemy))
+            .filter_map(|(entity, agent, pos, _, _)| {
+                let target = agent
+                    .target
+                    .filter(|target| target.hostile && target.aggro_on)?;
+                Some((entity, target.target, pos.0))
+            })
+            .filter(|(_, target, _)| players.contains(*target))
+            .filter(|(_, _, pos)| in_dungeon(&terrain, *pos))
+            .collect::<Vec<_>>();
+
+        for (entity, target, pos) in activated {
+            let present_players = match groups.get(target).filter(|group| !group.is_special()) {
+                Some(group) => group::members(*group, &groups, &entities, &alignments, &uids)
+                    .filter(|(member, role)| {
+                        matches!(role, Role::Member)
+                            && players.contains(*member)
+                            && positions.get(*member).map_or(false, |member_pos| {
+                                member_pos.0.distance_squared(pos) <= SCALING_RANGE.powi(2)
+                                    && in_dungeon(&terrain, member_pos.0)
+                            })
+                    })
+                    .count() as u32,
+                None => 1,
+            };
+            let scaling = GroupScaling::new(present_players);
+
+            // The buff system keeps the maximum health scaled from now on, but
+            // the enemy should also start the fight at full (scaled) health
+            if let (Some(health), Some(stats)) = (healths.get_mut(entity), stats.get(entity)) {
+                let mut modifiers = stats.max_health_modifiers;
+                modifiers.mult_mod *= scaling.health_multiplier();
+                if let Some(maximum) = health.needs_maximum_update(modifiers) {
+                    health.rescale_internal_integer_maximum(maximum);
+                }
+            }
+
+            let _ = group_scalings.insert(entity, scaling);
+        }
+    }
+}
@@ -1,10 +1,23 @@
+use crate::client::Client;
 use common::{
-    comp::{group::GroupManager, loot_owner::LootOwnerKind, LootOwner},
+    comp::{
+        self,
+        group::GroupManager,
+        item::{tool::AbilityMap, MaterialStatManifest},
+        loot_owner::LootOwnerKind,
+        Inventory, InventoryUpdate, InventoryUpdateEvent, LootOwner, PickupItem,
+    },
+    event::{CreateItemDropEvent, EventBus},
+    loot_roll::LootRolls,
+    resources::{ProgramTime, Time},
     uid::IdMaps,
 };
 use common_ecs::{Job, Origin, Phase, System};
-use specs::{Entities, Entity, Join, Read, WriteStorage};
+use common_net::msg::ServerGeneral;
+use specs::{Entities, Entity, Join, Read, ReadExpect, ReadStorage, Write, WriteStorage};
+use std::time::Duration;
 use tracing::debug;
+use vek::*;
 
 // This system manages loot that exists in the world
 #[derive(Default)]
@@ -15,6 +28,15 @@ impl<'a> System<'a> for Sys {
         WriteStorage<'a, LootOwner>,
         Read<'a, IdMaps>,
         Read<'a, GroupManager>,
+        Write<'a, LootRolls>,
+        Read<'a, Time>,
+        ReadExpect<'a, ProgramTime>,
+        ReadExpect<'a, AbilityMap>,
+        ReadExpect<'a, MaterialStatManifest>,
+        Read<'a, EventBus<CreateItemDropEvent>>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, Inventory>,
+        WriteStorage<'a, InventoryUpdate>,
     );
 
     const NAME: &'static str = "loot";
@@ -23,7 +45,21 @@ impl<'a> System<'a> for Sys {
 
     fn run(
         _job: &mut Job<Self>,
-        (entities, mut loot_owners, id_maps, group_manager): Self::SystemData,
+        (
+            entities,
+            mut loot_owners,
+            id_maps,
+            group_manager,
+            mut loot_rolls,
+            time,
+            program_time,
+            ability_map,
+            msm,
+            create_item_drop,
+            clients,
+            mut inventories,
+            mut inventory_updates,
+        ): Self::SystemData,
     ) {
         // Find and remove expired loot ownership. Loot ownership is expired when either
         // the expiry time has passed, or the owner no longer exists
@@ -52,5 +88,81 @@ impl<'a> System<'a> for Sys {
         for entity in expired {
             loot_owners.remove(entity);
         }
+
+        // Ask eligible group members to roll for newly held back items
+        for (id, roll) in loot_rolls.take_new() {
+            let item = roll.item.frontend_item(&ability_map, &msm);
+            let timeout = Duration::from_secs_f64((roll.deadline.0 - time.0).max(0.0));
+            for uid in roll.eligible() {
+                if let Some(client) = id_maps.uid_entity(uid).and_then(|e| clients.get(e)) {
+                    client.send_fallible(ServerGeneral::LootRoll {
+                        id,
+                        item: item.duplicate(&ability_map, &msm),
+                        timeout,
+                    });
+                }
+            }
+        }
+
+        // Hand out the items of every decided roll
+        let mut create_item_drop = create_item_drop.emitter();
+        let mut rng = rand::thread_rng();
+        for (id, roll) in loot_rolls.take_decided(*time) {
+            let winner = roll.winner(&mut rng);
+            for uid in roll.eligible() {
+                if let Some(client) = id_maps.uid_entity(uid).and_then(|e| clients.get(e)) {
+                    client.send_fallible(ServerGeneral::LootRollEnded { id, winner });
+                }
+            }
+
+            let winner = winner.and_then(|(uid, _, _)| {
+                id_maps
+                    .uid_entity(uid)
+                    .filter(|entity| entities.is_alive(*entity))
+                    .map(|entity| (uid, entity))
+            });
+            // If nobody won the item it becomes free-for-all, if the winner can't carry it
+            // it is dropped for them instead
+            let leftover = match winner
+                .and_then(|(uid, entity)| Some((uid, entity, inventories.get_mut(entity)?)))
+            {
+                Some((uid, entity, inventory)) => {
+                    let mut item_msg = roll.item.frontend_item(&ability_map, &msm);
+                    let (leftover, collected) = match inventory.push(roll.item) {
+                        Ok(()) => (None, true),
+                        Err((item, inserted)) => {
+                            if let Some(inserted) = inserted {
+                                item_msg
+                                    .set_amount(inserted.get())
+                                    .expect("Inserted must be > 0 and <= item.max_amount()");
+                            }
+                            (
+                                Some((item, Some(LootOwnerKind::Player(uid)))),
+                                inserted.is_some(),
+                            )
+                        },
+                    };
+                    if collected {
+                        inventory_updates
+                            .entry(entity)
+                            .expect("We know entity exists since we got its inventory.")
+                            .or_insert_with(InventoryUpdate::default)
+                            .push(InventoryUpdateEvent::Collected(item_msg));
+                    }
+                    leftover
+                },
+                None => Some((roll.item, None)),
+            };
+
+            if let Some((item, loot_owner)) = leftover {
+                create_item_drop.emit(CreateItemDropEvent {
+                    pos: comp::Pos(roll.pos),
+                    vel: comp::Vel(Vec3::zero()),
+                    ori: comp::Ori::default(),
+                    item: PickupItem::new(item, *program_time),
+                    loot_owner: loot_owner.map(|owner| LootOwner::new(owner, false)),
+                });
+            }
+        }
     }
 }
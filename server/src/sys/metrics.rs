@@ -10,6 +10,7 @@ use std::{
     sync::{Arc, Mutex},
     time::Instant,
 };
+use tracing::debug;
 use veloren_query_server::server::Metrics as RawQueryServerMetrics;
 
 /// This system exports metrics
@@ -97,6 +98,35 @@ impl<'a> System<'a> for Sys {
                 .observe(len as f64 / NANOSEC_PER_SEC);
         }
 
+        // Report how the systems were scheduled, to find the ones that serialize the
+        // tick
+        let schedule = common_ecs::analyze_schedule(
+            &state,
+            &sys_metrics.systems.lock().unwrap(),
+            tick_start.0,
+        );
+        for (name, system) in &schedule.systems {
+            export_ecs
+                .system_wait_time
+                .with_label_values(&[name])
+                .set(system.wait_ns as i64);
+            export_ecs
+                .system_slack_time
+                .with_label_values(&[name])
+                .set(system.slack_ns as i64);
+            export_ecs
+                .system_critical_path
+                .with_label_values(&[name])
+                .set(schedule.critical_path.contains(name) as i64);
+        }
+        if tick.0.rem_euclid(1000) == 0 {
+            debug!(
+                critical_path = ?schedule.critical_path,
+                suggested_order = ?schedule.suggested_order,
+                "ECS system schedule"
+            );
+        }
+
         // Report other info
         export_tick.time_of_day.set(time_of_day.0);
         if tick.0.rem_euclid(100) == 0 {
@@ -0,0 +1,442 @@
+use crate::{client::Client, sys::terrain::SpawnEntityData};
+use common::{
+    assets::AssetExt,
+    comp::{
+        self,
+        item::{tool::AbilityMap, MaterialStatManifest},
+        loot_owner::LootOwnerKind,
+        Agent, Alignment, Health, Inventory, InventoryUpdate, InventoryUpdateEvent, Item,
+        LootOwner, PickupItem, Pos, Title,
+    },
+    event::{
+        CreateItemDropEvent, CreateNpcEvent, EmitExt, TeleportToPositionEvent, UnlockTitleEvent,
+    },
+    event_emitters,
+    generation::EntityInfo,
+    minigame::{self, MinigameDef, Objective, Scoreboard},
+    resources::{DeltaTime, ProgramTime, Secs, Time},
+    uid::{IdMaps, Uid},
+    util::Dir,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use hashbrown::HashMap;
+use specs::{Entities, Join, Read, ReadExpect, ReadStorage, Write, WriteStorage};
+use std::f32::consts::TAU;
+use tracing::warn;
+use vek::*;
+
+/// How often, in seconds, participants are sent the current scores
+const SCOREBOARD_INTERVAL: f64 = 1.0;
+/// Time enemies of a boss rush wave get to spawn before the wave can count as
+/// cleared
+const WAVE_SPAWN_GRACE: f64 = 3.0;
+
+/// Minigames currently running on the server
+#[derive(Default)]
+pub struct Minigames {
+    running: HashMap<String, RunningMinigame>,
+}
+
+struct RunningMinigame {
+    def: MinigameDef,
+    ends_at: f64,
+    next_scoreboard: f64,
+    participants: HashMap<Uid, Participant>,
+    /// Number of boss rush waves spawned so far, and when the last one was
+    /// spawned
+    waves: (usize, f64),
+    /// Whether the objective was completed, ending the minigame early
+    completed: bool,
+}
+
+#[derive(Default)]
+struct Participant {
+    score: u32,
+    /// Time spent on a capture point, or the index of the next checkpoint of a
+    /// race
+    progress: f64,
+    finished_at: Option<f64>,
+    /// Where the participant was before joining, they are sent back there
+    /// afterwards
+    return_pos: Vec3<f32>,
+}
+
+impl RunningMinigame {
+    fn scoreboard(&self, time: Time) -> (Scoreboard, Vec<Uid>) {
+        let mut scores = self
+            .participants
+            .iter()
+            .map(|(uid, p)| (*uid, p.score, p.finished_at))
+            .collect::<Vec<_>>();
+        minigame::rank(&mut scores);
+        let scoreboard = Scoreboard {
+            name: self.def.name.clone(),
+            time_left: Secs((self.ends_at - time.0).max(0.0)),
+            scores: scores
+                .iter()
+                .map(|(uid, score, _)| (*uid, *score))
+                .collect(),
+        };
+        let ranking = scores.into_iter().map(|(uid, _, _)| uid).collect();
+        (scoreboard, ranking)
+    }
+}
+
+impl Minigames {
+    /// Starts the minigame defined by the asset `id`, returning its name
+    pub fn start(&mut self, id: &str, time: Time) -> Result<String, String> {
+        if self.running.contains_key(id) {
+            return Err(format!("Minigame {id} is already running"));
+        }
+        let def = MinigameDef::load_cloned(id)
+            .map_err(|err| format!("Failed to load minigame {id}: {err}"))?;
+        let name = def.name.clone();
+        self.running.insert(id.to_string(), RunningMinigame {
+            ends_at: time.0 + def.duration.0,
+            next_scoreboard: time.0,
+            participants: HashMap::new(),
+            waves: (0, time.0),
+            completed: false,
+            def,
+        });
+        Ok(name)
+    }
+
+    /// Ends the minigame during the next update, handing out rewards as usual
+    pub fn stop(&mut self, id: &str) -> Result<(), String> {
+        let minigame = self
+            .running
+            .get_mut(id)
+            .ok_or_else(|| format!("Minigame {id} is not running"))?;
+        minigame.completed = true;
+        Ok(())
+    }
+
+    /// Adds a player to a running minigame, returning where they should be
+    /// teleported to
+    pub fn join(&mut self, id: &str, uid: Uid, pos: Vec3<f32>) -> Result<Vec3<f32>, String> {
+        if self.participating(uid).is_some() {
+            return Err("You are already taking part in a minigame".to_string());
+        }
+        let minigame = self
+            .running
+            .get_mut(id)
+            .ok_or_else(|| format!("Minigame {id} is not running"))?;
+        if minigame
+            .def
+            .max_players
+            .map_or(false, |max| minigame.participants.len() >= max)
+        {
+            return Err(format!("{} is full", minigame.def.name));
+        }
+        minigame.participants.insert(uid, Participant {
+            return_pos: pos,
+            ..Default::default()
+        });
+        Ok(minigame.def.start)
+    }
+
+    /// Removes a player from their minigame, returning its scores and where
+    /// the player should be teleported back to
+    pub fn leave(&mut self, uid: Uid, time: Time) -> Option<(Scoreboard, Vec3<f32>)> {
+        let minigame = self
+            .running
+            .values_mut()
+            .find(|minigame| minigame.participants.contains_key(&uid))?;
+        let (scoreboard, _) = minigame.scoreboard(time);
+        let participant = minigame.participants.remove(&uid)?;
+        Some((scoreboard, participant.return_pos))
+    }
+
+    /// Name of the minigame the player takes part in
+    pub fn participating(&self, uid: Uid) -> Option<&str> {
+        self.running
+            .values()
+            .find(|minigame| minigame.participants.contains_key(&uid))
+            .map(|minigame| minigame.def.name.as_str())
+    }
+
+    /// Asset ids and names of the running minigames
+    pub fn running(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.running
+            .iter()
+            .map(|(id, minigame)| (id.as_str(), minigame.def.name.as_str()))
+    }
+}
+
+event_emitters! {
+    struct Events[Emitters] {
+        create_npc: CreateNpcEvent,
+        create_item_drop: CreateItemDropEvent,
+        teleport: TeleportToPositionEvent,
+        unlock_title: UnlockTitleEvent,
+    }
+}
+
+/// This system updates the objectives of running minigames, sends scoreboards
+/// to their participants and hands out rewards once they end
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Events<'a>,
+        Read<'a, Time>,
+        Read<'a, DeltaTime>,
+        ReadExpect<'a, ProgramTime>,
+        Read<'a, IdMaps>,
+        Write<'a, Minigames>,
+        ReadExpect<'a, AbilityMap>,
+        ReadExpect<'a, MaterialStatManifest>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Agent>,
+        ReadStorage<'a, Alignment>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, Inventory>,
+        WriteStorage<'a, InventoryUpdate>,
+    );
+
+    const NAME: &'static str = "minigame";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            events,
+            time,
+            dt,
+            program_time,
+            id_maps,
+            mut minigames,
+            ability_map,
+            msm,
+            positions,
+            healths,
+            agents,
+            alignments,
+            clients,
+            mut inventories,
+            mut inventory_updates,
+        ): Self::SystemData,
+    ) {
+        if minigames.running.is_empty() {
+            return;
+        }
+        let mut emitters = events.get_emitters();
+        let mut rng = rand::thread_rng();
+
+        let mut ended = Vec::new();
+        for (id, minigame) in minigames.running.iter_mut() {
+            // Players that logged off are no longer taking part
+            minigame
+                .participants
+                .retain(|uid, _| id_maps.uid_entity(*uid).is_some());
+
+            // Positions of the participants that are still alive
+            let alive = minigame
+                .participants
+                .keys()
+                .filter_map(|uid| {
+                    let entity = id_maps.uid_entity(*uid)?;
+                    healths
+                        .get(entity)
+                        .map_or(true, |health| !health.is_dead)
+                        .then_some((*uid, positions.get(entity)?.0))
+                })
+                .collect::<Vec<_>>();
+
+            match &minigame.def.objective {
+                Objective::CapturePoint { pos, radius } => {
+                    let mut capturing = alive
+                        .iter()
+                        .filter(|(_, p)| p.distance_squared(*pos) <= radius.powi(2));
+                    // Contested points don't score
+                    if let (Some((uid, _)), None) = (capturing.next(), capturing.next())
+                        && let Some(participant) = minigame.participants.get_mut(uid)
+                    {
+                        participant.progress += dt.0 as f64;
+                        participant.score = participant.progress as u32;
+                    }
+                },
+                Objective::Race {
+                    checkpoints,
+                    radius,
+                } => {
+                    for (uid, pos) in &alive {
+                        let Some(participant) = minigame.participants.get_mut(uid) else {
+                            continue;
+                        };
+                        if let Some(checkpoint) = checkpoints.get(participant.progress as usize)
+                            && pos.distance_squared(*checkpoint) <= radius.powi(2)
+                        {
+                            participant.progress += 1.0;
+                            participant.score += 1;
+                            if participant.progress as usize == checkpoints.len() {
+                                participant.finished_at = Some(time.0);
+                            }
+                        }
+                    }
+                    minigame.completed |= !minigame.participants.is_empty()
+                        && minigame
+                            .participants
+                            .values()
+                            .all(|participant| participant.finished_at.is_some());
+                },
+                Objective::BossRush {
+                    spawn,
+                    waves,
+                    arena_radius,
+                } => {
+                    let (spawned, spawned_at) = minigame.waves;
+                    let cleared = spawned > 0
+                        && time.0 - spawned_at > WAVE_SPAWN_GRACE
+                        && !(&entities, &positions, &healths, &alignments, &agents)
+                            .join()
+                            .any(|(_, pos, health, alignment, _)| {
+                                matches!(alignment, Alignment::Enemy)
+                                    && !health.is_dead
+                                    && pos.0.distance_squared(*spawn) <= arena_radius.powi(2)
+                            });
+                    if cleared {
+                        for participant in minigame.participants.values_mut() {
+                            participant.score += 1;
+                        }
+                    }
+                    if spawned == waves.len() {
+                        minigame.completed |= cleared;
+                    } else if (spawned == 0 && !alive.is_empty()) || cleared {
+                        spawn_wave(&waves[spawned], *spawn, &mut rng, &mut emitters);
+                        minigame.waves = (spawned + 1, time.0);
+                    }
+                },
+            }
+
+            if minigame.completed || time.0 >= minigame.ends_at {
+                ended.push(id.clone());
+            } else if time.0 >= minigame.next_scoreboard {
+                minigame.next_scoreboard = time.0 + SCOREBOARD_INTERVAL;
+                let (scoreboard, _) = minigame.scoreboard(*time);
+                for uid in minigame.participants.keys() {
+                    if let Some(client) = id_maps.uid_entity(*uid).and_then(|e| clients.get(e)) {
+                        client.send_fallible(ServerGeneral::MinigameScoreboard(scoreboard.clone()));
+                    }
+                }
+            }
+        }
+
+        for id in ended {
+            let Some(minigame) = minigames.running.remove(&id) else {
+                continue;
+            };
+            let (scoreboard, ranking) = minigame.scoreboard(*time);
+            let rewarded = ranking
+                .iter()
+                .filter(|uid| {
+                    minigame
+                        .participants
+                        .get(*uid)
+                        .map_or(false, |p| p.score > 0)
+                })
+                .zip(&minigame.def.rewards);
+            for (uid, reward) in rewarded {
+                let Some(entity) = id_maps.uid_entity(*uid) else {
+                    continue;
+                };
+                let mut item = match Item::new_from_asset(&reward.item) {
+                    Ok(item) => item,
+                    Err(err) => {
+                        warn!(?err, "Invalid reward {} in minigame {id}", reward.item);
+                        continue;
+                    },
+                };
+                if let Err(err) = item.set_amount(reward.amount) {
+                    warn!(?err, "Invalid reward amount in minigame {id}");
+                }
+                let item_msg = item.frontend_item(&ability_map, &msm);
+                // Rewards that don't fit into the inventory are dropped at the player's feet
+                let leftover = match inventories.get_mut(entity) {
+                    Some(inventory) => inventory.push(item).err().map(|(item, _)| item),
+                    None => Some(item),
+                };
+                match leftover {
+                    None => {
+                        inventory_updates
+                            .entry(entity)
+                            .expect("We know entity exists since we got its inventory.")
+                            .or_insert_with(InventoryUpdate::default)
+                            .push(InventoryUpdateEvent::Collected(item_msg));
+                    },
+                    Some(item) => {
+                        if let Some(pos) = positions.get(entity) {
+                            emitters.emit(CreateItemDropEvent {
+                                pos: *pos,
+                                vel: comp::Vel(Vec3::zero()),
+                                ori: comp::Ori::default(),
+                                item: PickupItem::new(item, *program_time),
+                                loot_owner: Some(LootOwner::new(
+                                    LootOwnerKind::Player(*uid),
+                                    false,
+                                )),
+                            });
+                        }
+                    },
+                }
+            }
+
+            for (uid, participant) in &minigame.participants {
+                let Some(entity) = id_maps.uid_entity(*uid) else {
+                    continue;
+                };
+                // Only those who scored count as having taken part
+                if participant.score > 0 {
+                    let title = if ranking.first() == Some(uid) {
+                        Title::Champion
+                    } else {
+                        Title::Reveller
+                    };
+                    emitters.emit(UnlockTitleEvent { entity, title });
+                }
+                if let Some(client) = clients.get(entity) {
+                    client.send_fallible(ServerGeneral::MinigameEnded(scoreboard.clone()));
+                }
+                emitters.emit(TeleportToPositionEvent {
+                    entity,
+                    position: participant.return_pos,
+                });
+            }
+        }
+    }
+}
+
+fn spawn_wave(
+    wave: &[String],
+    center: Vec3<f32>,
+    rng: &mut impl rand::Rng,
+    emitters: &mut Emitters,
+) {
+    for (i, entity) in wave.iter().enumerate() {
+        // Spread the enemies of a wave out a little, so they don't spawn inside of
+        // each other
+        let offset = Vec2::unit_x().rotated_z(TAU * (i as f32 / wave.len() as f32)) * 3.0;
+        let entity_info =
+            EntityInfo::at(center + offset.with_z(0.0)).with_asset_expect(entity, rng, None);
+        match SpawnEntityData::from_entity_info(entity_info) {
+            SpawnEntityData::Npc(data) => {
+                let (npc_builder, pos) = data.to_npc_builder();
+                emitters.emit(CreateNpcEvent {
+                    pos,
+                    ori: comp::Ori::from(Dir::random_2d(rng)),
+                    npc: npc_builder,
+                    rider: None,
+                });
+            },
+            SpawnEntityData::Special(_, _) => {
+                warn!("Minigame waves must be npcs, found: {entity}");
+            },
+        }
+    }
+}
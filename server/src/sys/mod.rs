@@ -1,22 +1,31 @@
 pub mod agent;
+pub mod announcement;
 pub mod chunk_send;
 pub mod chunk_serialize;
+pub mod downed;
+pub mod encounter;
 pub mod entity_sync;
+pub mod event_calendar;
+pub mod group_scaling;
 pub mod invite_timeout;
 pub mod item;
 pub mod loot;
 pub mod metrics;
+pub mod minigame;
 pub mod msg;
 pub mod object;
 pub mod persistence;
 pub mod pets;
+pub mod race;
 pub mod sentinel;
 pub mod server_info;
+pub mod session_timeout;
 pub mod subscription;
 pub mod teleporter;
 pub mod terrain;
 pub mod terrain_sync;
 pub mod waypoint;
+pub mod wildfire;
 pub mod wiring;
 
 use common_ecs::{dispatch, run_now, System};
@@ -33,19 +42,30 @@ pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch::<melee::Sys>(dispatch_builder, &[&projectile::Sys::sys_name()]);
     //Note: server should not depend on interpolation system
     dispatch::<agent::Sys>(dispatch_builder, &[]);
+    // Encounter scripts push inputs on top of what the agent decided this tick
+    dispatch::<encounter::Sys>(dispatch_builder, &[&agent::Sys::sys_name()]);
+    // Enemies are scaled once the agent system gave them their first target
+    dispatch::<group_scaling::Sys>(dispatch_builder, &[&agent::Sys::sys_name()]);
     dispatch::<terrain::Sys>(dispatch_builder, &[&msg::terrain::Sys::sys_name()]);
     dispatch::<waypoint::Sys>(dispatch_builder, &[]);
     dispatch::<teleporter::Sys>(dispatch_builder, &[]);
     dispatch::<invite_timeout::Sys>(dispatch_builder, &[]);
+    dispatch::<downed::Sys>(dispatch_builder, &[]);
+    dispatch::<session_timeout::Sys>(dispatch_builder, &[]);
     dispatch::<persistence::Sys>(dispatch_builder, &[]);
     dispatch::<object::Sys>(dispatch_builder, &[]);
     dispatch::<wiring::Sys>(dispatch_builder, &[]);
+    dispatch::<wildfire::Sys>(dispatch_builder, &[]);
+    dispatch::<minigame::Sys>(dispatch_builder, &[]);
+    dispatch::<race::Sys>(dispatch_builder, &[]);
     // no dependency, as we only work once per sec anyway.
     dispatch::<chunk_serialize::Sys>(dispatch_builder, &[]);
     // don't depend on chunk_serialize, as we assume everything is done in a SlowJow
     dispatch::<chunk_send::Sys>(dispatch_builder, &[]);
     dispatch::<item::Sys>(dispatch_builder, &[]);
     dispatch::<server_info::Sys>(dispatch_builder, &[]);
+    dispatch::<announcement::Sys>(dispatch_builder, &[]);
+    dispatch::<event_calendar::Sys>(dispatch_builder, &[]);
 }
 
 pub fn run_sync_systems(ecs: &mut specs::World) {
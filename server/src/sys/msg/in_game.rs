@@ -49,9 +49,28 @@ event_emitters! {
         request_site_info: event::RequestSiteInfoEvent,
         update_map_marker: event::UpdateMapMarkerEvent,
         client_disconnect: event::ClientDisconnectEvent,
+        group_spectate: event::GroupSpectateEvent,
     }
 }
 
+/// Whether a player may change the block at `pos`, either because they are in
+/// creative mode or because one of their build areas contains it
+fn can_build_at(
+    can_build: &CanBuild,
+    build_areas: &AreasContainer<BuildArea>,
+    pos: Vec3<i32>,
+) -> bool {
+    can_build.creative
+        || can_build.build_areas.iter().any(|area| {
+            build_areas
+                .areas()
+                .get(*area)
+                // TODO: Make this an exclusive check on the upper bound of the AABB
+                // Vek defaults to inclusive which is not optimal
+                .map_or(false, |aabb| aabb.contains_point(pos))
+        })
+}
+
 impl Sys {
     #[allow(clippy::too_many_arguments)]
     fn handle_client_in_game_msg(
@@ -107,11 +126,12 @@ impl Sys {
                     client.send(ServerGeneral::SetViewDistance(clamped_vds.terrain))?;
                 }
             },
-            ClientGeneral::ControllerInputs(inputs) => {
+            ClientGeneral::ControllerInputs { inputs, input_seq } => {
                 if presence.kind.controlling_char() {
                     if let Some(controller) = controller {
                         controller.inputs.update_with_new(*inputs);
                     }
+                    presence.last_input_seq = input_seq;
                 }
             },
             ClientGeneral::ControlEvent(event) => {
@@ -154,60 +174,35 @@ impl Sys {
                 }
             },
             ClientGeneral::BreakBlock(pos) => {
-                if let Some(comp_can_build) = can_build.get(entity) {
-                    if comp_can_build.enabled {
-                        for area in comp_can_build.build_areas.iter() {
-                            if let Some(old_block) = build_areas
-                                .areas()
-                                .get(*area)
-                                // TODO: Make this an exclusive check on the upper bound of the AABB
-                                // Vek defaults to inclusive which is not optimal
-                                .filter(|aabb| aabb.contains_point(pos))
-                                .and_then(|_| terrain.get(pos).ok())
-                            {
-                                let new_block = old_block.into_vacant();
-                                // Take the rare writes lock as briefly as possible.
-                                let mut guard = rare_writes.lock();
-                                let _was_set =
-                                    guard.block_changes.try_set(pos, new_block).is_some();
-                                #[cfg(feature = "persistent_world")]
-                                if _was_set {
-                                    if let Some(terrain_persistence) =
-                                        guard._terrain_persistence.as_mut()
-                                    {
-                                        terrain_persistence.set_block(pos, new_block);
-                                    }
-                                }
-                            }
+                if let Some(comp_can_build) = can_build.get(entity)
+                    && comp_can_build.enabled
+                    && can_build_at(comp_can_build, build_areas, pos)
+                    && let Ok(old_block) = terrain.get(pos)
+                {
+                    let new_block = old_block.into_vacant();
+                    // Take the rare writes lock as briefly as possible.
+                    let mut guard = rare_writes.lock();
+                    let _was_set = guard.block_changes.try_set(pos, new_block).is_some();
+                    #[cfg(feature = "persistent_world")]
+                    if _was_set {
+                        if let Some(terrain_persistence) = guard._terrain_persistence.as_mut() {
+                            terrain_persistence.set_block(pos, new_block);
                         }
                     }
                 }
             },
             ClientGeneral::PlaceBlock(pos, new_block) => {
-                if let Some(comp_can_build) = can_build.get(entity) {
-                    if comp_can_build.enabled {
-                        for area in comp_can_build.build_areas.iter() {
-                            if build_areas
-                                .areas()
-                                .get(*area)
-                                // TODO: Make this an exclusive check on the upper bound of the AABB
-                                // Vek defaults to inclusive which is not optimal
-                                .filter(|aabb| aabb.contains_point(pos))
-                                .is_some()
-                            {
-                                // Take the rare writes lock as briefly as possible.
-                                let mut guard = rare_writes.lock();
-                                let _was_set =
-                                    guard.block_changes.try_set(pos, new_block).is_some();
-                                #[cfg(feature = "persistent_world")]
-                                if _was_set {
-                                    if let Some(terrain_persistence) =
-                                        guard._terrain_persistence.as_mut()
-                                    {
-                                        terrain_persistence.set_block(pos, new_block);
-                                    }
-                                }
-                            }
+                if let Some(comp_can_build) = can_build.get(entity)
+                    && comp_can_build.enabled
+                    && can_build_at(comp_can_build, build_areas, pos)
+                {
+                    // Take the rare writes lock as briefly as possible.
+                    let mut guard = rare_writes.lock();
+                    let _was_set = guard.block_changes.try_set(pos, new_block).is_some();
+                    #[cfg(feature = "persistent_world")]
+                    if _was_set {
+                        if let Some(terrain_persistence) = guard._terrain_persistence.as_mut() {
+                            terrain_persistence.set_block(pos, new_block);
                         }
                     }
                 }
@@ -247,6 +242,12 @@ impl Sys {
                     if let Some(position) = position {
                         position.0 = pos;
                     }
+                } else if presence.kind.controlling_char()
+                    && healths.get(entity).map_or(false, |health| health.is_dead)
+                {
+                    // Dead players may only watch their group, which is validated once the
+                    // positions of the other members are available
+                    emitters.emit(event::GroupSpectateEvent { entity, pos });
                 }
             },
             ClientGeneral::RequestCharacterList
@@ -1,30 +1,35 @@
 use crate::{
+    account_link::AccountLinks,
     client::Client,
+    guild::Guilds,
     login_provider::{LoginProvider, PendingLogin},
+    login_queue::{Admission, LoginQueue},
     metrics::PlayerMetrics,
-    sys::sentinel::TrackedStorages,
+    presence::ResumableSession,
+    sys::{event_calendar::EventCalendarState, sentinel::TrackedStorages},
     EditableSettings, Settings,
 };
 use common::{
-    comp::{self, Admin, Player, Stats},
-    event::{ClientDisconnectEvent, EventBus, MakeAdminEvent},
+    comp::{self, Admin, Player, Presence, Stats},
+    event::{ClientDisconnectEvent, Emitter, EventBus, MakeAdminEvent, ResumeSessionEvent},
     recipe::{default_component_recipe_book, default_repair_recipe_book},
     resources::TimeOfDay,
     shared_server_config::ServerConstants,
     uid::Uid,
+    ViewDistances,
 };
 use common_base::prof_span;
 use common_ecs::{Job, Origin, Phase, System};
 use common_net::msg::{
     server::ServerDescription, CharacterInfo, ClientRegister, DisconnectReason, PlayerInfo,
-    PlayerListUpdate, RegisterError, ServerGeneral, ServerInit, WorldMapMsg,
+    PlayerListUpdate, RegisterError, RegisterStatus, ServerGeneral, ServerInit, WorldMapMsg,
 };
 use hashbrown::{hash_map, HashMap};
 use itertools::Either;
 use rayon::prelude::*;
 use specs::{
     shred, Entities, Join, LendJoin, ParJoin, Read, ReadExpect, ReadStorage, SystemData,
-    WriteStorage,
+    WriteExpect, WriteStorage,
 };
 use tracing::{debug, info, trace, warn};
 
@@ -40,13 +45,21 @@ type ReadPlugin<'a> = Option<Read<'a, ()>>;
 pub struct ReadData<'a> {
     entities: Entities<'a>,
     stats: ReadStorage<'a, Stats>,
+    factions: ReadStorage<'a, comp::Faction>,
+    titles: ReadStorage<'a, comp::Titles>,
     uids: ReadStorage<'a, Uid>,
+    presences: ReadStorage<'a, Presence>,
+    resumable_sessions: ReadStorage<'a, ResumableSession>,
     client_disconnect_events: Read<'a, EventBus<ClientDisconnectEvent>>,
+    resume_session_events: Read<'a, EventBus<ResumeSessionEvent>>,
     make_admin_events: Read<'a, EventBus<MakeAdminEvent>>,
     login_provider: ReadExpect<'a, LoginProvider>,
     player_metrics: ReadExpect<'a, PlayerMetrics>,
     settings: ReadExpect<'a, Settings>,
     editable_settings: ReadExpect<'a, EditableSettings>,
+    account_links: ReadExpect<'a, AccountLinks>,
+    guilds: ReadExpect<'a, Guilds>,
+    event_calendar: Read<'a, EventCalendarState>,
     time_of_day: Read<'a, TimeOfDay>,
     material_stats: ReadExpect<'a, comp::item::MaterialStatManifest>,
     ability_map: ReadExpect<'a, comp::item::tool::AbilityMap>,
@@ -66,6 +79,7 @@ impl<'a> System<'a> for Sys {
         WriteStorage<'a, Client>,
         WriteStorage<'a, Player>,
         WriteStorage<'a, PendingLogin>,
+        WriteExpect<'a, LoginQueue>,
     );
 
     const NAME: &'static str = "msg::register";
@@ -74,7 +88,7 @@ impl<'a> System<'a> for Sys {
 
     fn run(
         _job: &mut Job<Self>,
-        (read_data, mut clients, mut players, mut pending_logins): Self::SystemData,
+        (read_data, mut clients, mut players, mut pending_logins, mut login_queue): Self::SystemData,
     ) {
         let mut make_admin_emitter = read_data.make_admin_events.emitter();
         // Player list to send new players, and lookup from UUID to entity (so we don't
@@ -89,9 +103,11 @@ impl<'a> System<'a> for Sys {
             &players,
             read_data.stats.maybe(),
             read_data.trackers.admin.maybe(),
+            read_data.factions.maybe(),
+            read_data.titles.maybe(),
         )
             .join()
-            .map(|(entity, uid, player, stats, admin)| {
+            .map(|(entity, uid, player, stats, admin, faction, titles)| {
                 (
                     (*uid, PlayerInfo {
                         is_online: true,
@@ -101,8 +117,15 @@ impl<'a> System<'a> for Sys {
                             name: stats.name.clone(),
                             // NOTE: hack, read docs for body::Gender for more
                             gender: stats.original_body.humanoid_gender(),
+                            title: titles.and_then(|titles| titles.selected()),
                         }),
                         uuid: player.uuid(),
+                        verified_badge: read_data
+                            .account_links
+                            .has_badge(&player.uuid(), &read_data.settings),
+                        faction: faction.map(|comp::Faction(faction)| faction.clone()),
+                        guild_tag: read_data.guilds.tag_of(player.uuid()).map(str::to_owned),
+                        guild_banner: read_data.guilds.banner_of(player.uuid()),
                     }),
                     (player.uuid(), entity),
                 )
@@ -117,9 +140,9 @@ impl<'a> System<'a> for Sys {
         //
         // Big enough that we hopefully won't have to reallocate.
         //
-        // Also includes a list of logins to retry and finished_pending, since we
-        // happen to update those around the same time that we update the new
-        // players list.
+        // Also includes a list of logins to retry, finished_pending and the logins
+        // that were queued, since we happen to update those around the same time
+        // that we update the new players list.
         //
         // NOTE: stdlib mutex is more than good enough on Linux and (probably) Windows,
         // but not Mac.
@@ -127,16 +150,21 @@ impl<'a> System<'a> for Sys {
             HashMap::<_, (_, _, _, _)>::with_capacity(capacity),
             Vec::with_capacity(capacity),
             Vec::with_capacity(capacity),
+            Vec::new(),
         ));
+        let queue_settings = &read_data.settings.login_queue;
+        let queue = &*login_queue;
 
         // defer auth lockup
         for (entity, client) in (&read_data.entities, &mut clients).join() {
             let mut locale = None;
+            let mut resume_token = None;
 
             let _ = super::try_recv_all(client, 0, |_, msg: ClientRegister| {
                 trace!(?msg.token_or_username, "defer auth lockup");
                 let pending = read_data.login_provider.verify(&msg.token_or_username);
                 locale = msg.locale;
+                resume_token = msg.resume_token;
                 let _ = pending_logins.insert(entity, pending);
                 Ok(())
             });
@@ -145,6 +173,9 @@ impl<'a> System<'a> for Sys {
             if let Some(locale) = locale {
                 client.locale = Some(locale);
             }
+            if let Some(resume_token) = resume_token {
+                client.resume_token = Some(resume_token);
+            }
         }
 
         let old_player_count = player_list.len();
@@ -165,8 +196,13 @@ impl<'a> System<'a> for Sys {
             // NOTE: Required because Specs has very poor work splitting for sparse joins.
             .par_bridge()
             .for_each_init(
-                || read_data.client_disconnect_events.emitter(),
-                |client_disconnect_emitter, (entity, uid, client, _, pending)| {
+                || {
+                    (
+                        read_data.client_disconnect_events.emitter(),
+                        read_data.resume_session_events.emitter(),
+                    )
+                },
+                |(client_disconnect_emitter, resume_session_emitter), (entity, uid, client, _, pending)| {
                     prof_span!("msg::register login");
                     if let Err(e) = || -> Result<(), crate::error::Error> {
                         let extra_checks = |username: String, uuid: authc::Uuid| {
@@ -183,6 +219,12 @@ impl<'a> System<'a> for Sys {
                                     is_moderator: admin.is_some(),
                                     character: None, // new players will be on character select.
                                     uuid: player.uuid(),
+                                    verified_badge: read_data
+                                        .account_links
+                                        .has_badge(&uuid, &read_data.settings),
+                                    faction: None,
+                                    guild_tag: read_data.guilds.tag_of(uuid).map(str::to_owned),
+                                    guild_banner: read_data.guilds.banner_of(uuid),
                                 })
                                 .map(|player_info| {
                                     // Prepare the player list update to be sent to all clients.
@@ -211,17 +253,25 @@ impl<'a> System<'a> for Sys {
                             // there's a race between when we read it and when we (potentially)
                             // write to it.
                             let guard = new_players.lock();
+                            let admission = queue.admission(
+                                entity,
+                                admin.is_some(),
+                                old_player_count,
+                                old_player_count + guard.0.len(),
+                                max_players,
+                                queue_settings,
+                            );
                             // Guard comes first in the tuple so it's dropped before the other
                             // stuff if login returns an error.
                             (
-                                old_player_count + guard.0.len() >= max_players,
-                                (guard, (pending_login, player, admin, msg, old_player)),
+                                admission == Admission::Reject,
+                                (guard, (pending_login, player, admin, msg, old_player, admission)),
                             )
                         };
                         // Destructure new_players_guard last so it gets dropped before the other
                         // three.
                         let (
-                            (pending_login, player, admin, msg, old_player),
+                            (pending_login, player, admin, msg, old_player, admission),
                             mut new_players_guard,
                         ) = match LoginProvider::login(
                             pending,
@@ -252,7 +302,8 @@ impl<'a> System<'a> for Sys {
                             },
                         };
 
-                        let (new_players_by_uuid, retries, finished_pending) = &mut *new_players_guard;
+                        let (new_players_by_uuid, retries, finished_pending, queued) =
+                            &mut *new_players_guard;
                         finished_pending.push(entity);
                         // Check if the user logged in before us during this tick (this is why we
                         // need the lock held).
@@ -268,7 +319,30 @@ impl<'a> System<'a> for Sys {
                         );
                         let vacant_player = match old_player {
                             Either::Left((old_entity, old_client)) => {
-                                if matches!(old_client, None | Some(Some(_))) {
+                                // The old player might be waiting for its client to reconnect
+                                let suspended_session = read_data
+                                    .resumable_sessions
+                                    .get(old_entity)
+                                    .filter(|session| {
+                                        session.expires.is_some() && matches!(old_client, Some(None))
+                                    });
+                                if let Some(session) = suspended_session {
+                                    if client.resume_token == Some(session.token) {
+                                        drop(new_players_guard);
+                                        return resume_session(
+                                            &read_data,
+                                            client,
+                                            entity,
+                                            old_entity,
+                                            &player_list,
+                                            resume_session_emitter,
+                                        );
+                                    }
+                                    // Logging in without resuming replaces the old session, which
+                                    // is removed later in the tick.
+                                    retries.push((entity, pending_login));
+                                    drop(new_players_guard);
+                                } else if matches!(old_client, None | Some(Some(_))) {
                                     // We can't login the new client right now as the
                                     // removal of the old client and player occurs later in
                                     // the tick, so we instead setup the new login to be
@@ -311,6 +385,26 @@ impl<'a> System<'a> for Sys {
                             Either::Right(v) => v,
                         };
 
+                        // The server is full, retry the login next tick
+                        if let Admission::Queue(position) = admission {
+                            let (queued_login, send_update) = queue.requeue(
+                                entity,
+                                admin.is_some(),
+                                position,
+                                queue_settings.update_interval,
+                            );
+                            queued.push(queued_login);
+                            retries.push((entity, pending_login));
+                            drop(new_players_guard);
+                            if send_update {
+                                client.send(Ok(RegisterStatus::Queued {
+                                    position,
+                                    estimated_wait: queue.estimated_wait(position),
+                                }))?;
+                            }
+                            return Ok(());
+                        }
+
                         let Some(msg) = msg else {
                             drop(new_players_guard);
                             // Invalid player
@@ -323,53 +417,30 @@ impl<'a> System<'a> for Sys {
                         // adding a new player.
 
                         // Add to list to notify all clients of the new player
+                        let uuid = player.uuid();
                         vacant_player.insert((entity, player, admin, msg));
                         drop(new_players_guard);
                         read_data.player_metrics.players_connected.inc();
 
                         // Tell the client its request was successful.
-                        client.send(Ok(()))?;
-
-                        #[cfg(feature = "plugins")]
-                        let active_plugins = read_data.plugin_mgr.plugin_list();
-                        #[cfg(not(feature = "plugins"))]
-                        let active_plugins = Vec::default();
-
-                        let server_descriptions = &read_data.editable_settings.server_description;
-                        let description = ServerDescription {
-                            motd: server_descriptions.get(client.locale.as_deref()).map(|d| d.motd.clone()).unwrap_or_default(),
-                            rules: server_descriptions.get_rules(client.locale.as_deref()).map(str::to_string),
-                        };
+                        client.send(Ok(RegisterStatus::Registered))?;
 
                         // Send client all the tracked components currently attached to its entity
                         // as well as synced resources (currently only `TimeOfDay`)
                         debug!("Starting initial sync with client.");
-                        client.send(ServerInit::GameSync {
-                            // Send client their entity
-                            entity_package: read_data
-                                .trackers
-                                .create_entity_package_with_uid(entity, *uid, None, None, None),
-                            time_of_day: *read_data.time_of_day,
-                            max_group_size: read_data.settings.max_player_group_size,
-                            client_timeout: read_data.settings.client_timeout,
-                            world_map: (*read_data.map).clone(),
-                            recipe_book: (*read_data.recipe_book).clone(),
-                            component_recipe_book: default_component_recipe_book().cloned(),
-                            repair_recipe_book: default_repair_recipe_book().cloned(),
-                            material_stats: (*read_data.material_stats).clone(),
-                            ability_map: (*read_data.ability_map).clone(),
-                            server_constants: ServerConstants {
-                                day_cycle_coefficient: read_data.settings.day_cycle_coefficient()
-                            },
-                            description,
-                            active_plugins,
-                        })?;
+                        client.send(game_sync(&read_data, client, entity, *uid))?;
                         debug!("Done initial sync with client.");
 
                         // Send initial player list
                         client.send(ServerGeneral::PlayerListUpdate(PlayerListUpdate::Init(
                             player_list.clone(),
                         )))?;
+                        client.send(ServerGeneral::GuildUpdate(
+                            read_data.guilds.info_of(uuid),
+                        ))?;
+                        client.send(ServerGeneral::EventCalendar(
+                            read_data.event_calendar.entries().to_vec(),
+                        ))?;
 
                         Ok(())
                     }() {
@@ -377,7 +448,12 @@ impl<'a> System<'a> for Sys {
                     }
                 },
             );
-        let (new_players, retries, finished_pending) = new_players.into_inner();
+        let (new_players, retries, finished_pending, queued) = new_players.into_inner();
+        let admitted = new_players
+            .values()
+            .map(|(entity, ..)| *entity)
+            .collect::<Vec<_>>();
+        login_queue.update(queued, &admitted);
         finished_pending.into_iter().for_each(|e| {
             // Remove all entities in finished_pending from pending_logins.
             pending_logins.remove(e);
@@ -436,3 +512,85 @@ impl<'a> System<'a> for Sys {
             });
     }
 }
+
+/// Initial data package for a client that registered, controlling `entity`.
+fn game_sync(read_data: &ReadData, client: &Client, entity: specs::Entity, uid: Uid) -> ServerInit {
+    #[cfg(feature = "plugins")]
+    let active_plugins = read_data.plugin_mgr.plugin_list();
+    #[cfg(not(feature = "plugins"))]
+    let active_plugins = Vec::default();
+
+    let server_descriptions = &read_data.editable_settings.server_description;
+    let description = ServerDescription {
+        motd: server_descriptions
+            .get(client.locale.as_deref())
+            .map(|d| d.motd.clone())
+            .unwrap_or_default(),
+        rules: server_descriptions
+            .get_rules(client.locale.as_deref())
+            .map(str::to_string),
+    };
+
+    ServerInit::GameSync {
+        // Send client their entity
+        entity_package: read_data
+            .trackers
+            .create_entity_package_with_uid(entity, uid, None, None, None),
+        time_of_day: *read_data.time_of_day,
+        max_group_size: read_data.settings.max_player_group_size,
+        client_timeout: read_data.settings.client_timeout,
+        world_map: (*read_data.map).clone(),
+        recipe_book: (*read_data.recipe_book).clone(),
+        component_recipe_book: default_component_recipe_book().cloned(),
+        repair_recipe_book: default_repair_recipe_book().cloned(),
+        material_stats: (*read_data.material_stats).clone(),
+        ability_map: (*read_data.ability_map).clone(),
+        server_constants: ServerConstants {
+            day_cycle_coefficient: read_data.settings.day_cycle_coefficient(),
+        },
+        description,
+        active_plugins,
+    }
+}
+
+/// Sends a client everything it needs to pick up the session of `old_entity`,
+/// whose client lost its connection. The client itself is moved over to that
+/// entity when the emitted [`ResumeSessionEvent`] is handled.
+fn resume_session(
+    read_data: &ReadData,
+    client: &Client,
+    entity: specs::Entity,
+    old_entity: specs::Entity,
+    player_list: &HashMap<Uid, PlayerInfo>,
+    resume_session_emitter: &mut Emitter<ResumeSessionEvent>,
+) -> Result<(), crate::error::Error> {
+    let (Some(uid), Some(presence)) = (
+        read_data.uids.get(old_entity),
+        read_data.presences.get(old_entity),
+    ) else {
+        return Ok(());
+    };
+
+    info!(?old_entity, "Resuming session");
+    client.send(Ok(RegisterStatus::Registered))?;
+    client.send(game_sync(read_data, client, old_entity, *uid))?;
+    client.send(ServerGeneral::PlayerListUpdate(PlayerListUpdate::Init(
+        player_list.clone(),
+    )))?;
+    client.send(ServerGeneral::EventCalendar(
+        read_data.event_calendar.entries().to_vec(),
+    ))?;
+    client.send(ServerGeneral::SessionResumed {
+        presence: presence.kind,
+        view_distances: ViewDistances {
+            terrain: presence.terrain_view_distance.current(),
+            entity: presence.entity_view_distance.current(),
+        },
+    })?;
+
+    resume_session_emitter.emit(ResumeSessionEvent {
+        entity,
+        target: old_entity,
+    });
+    Ok(())
+}
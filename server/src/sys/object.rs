@@ -1,15 +1,21 @@
 use common::{
-    comp::{object, Body, Object, PhysicsState, Pos, Teleporting, Vel},
+    comp::{
+        object, Body, Corpse, ItemDrops, Object, Ori, PhysicsState, PickupItem, Pos, Teleporting,
+        Vel,
+    },
     consts::TELEPORTER_RADIUS,
     effect::Effect,
-    event::{ChangeBodyEvent, DeleteEvent, EmitExt, EventBus, ExplosionEvent, ShootEvent},
+    event::{
+        ChangeBodyEvent, CreateItemDropEvent, DeleteEvent, EmitExt, EventBus, ExplosionEvent,
+        ShootEvent,
+    },
     event_emitters,
     outcome::Outcome,
-    resources::{DeltaTime, Time},
-    CachedSpatialGrid, Damage, DamageKind, DamageSource, Explosion, RadiusEffect,
+    resources::{DeltaTime, ProgramTime, Time},
+    CachedSpatialGrid, Damage, DamageElement, DamageKind, DamageSource, Explosion, RadiusEffect,
 };
 use common_ecs::{Job, Origin, Phase, System};
-use specs::{Entities, Join, LendJoin, Read, ReadStorage};
+use specs::{Entities, Join, LendJoin, Read, ReadExpect, ReadStorage, WriteStorage};
 use vek::Rgb;
 
 event_emitters! {
@@ -18,6 +24,7 @@ event_emitters! {
         explosion: ExplosionEvent,
         shoot: ShootEvent,
         change_body: ChangeBodyEvent,
+        create_item_drop: CreateItemDropEvent,
     }
 }
 
@@ -30,6 +37,7 @@ impl<'a> System<'a> for Sys {
         Events<'a>,
         Read<'a, DeltaTime>,
         Read<'a, Time>,
+        ReadExpect<'a, ProgramTime>,
         Read<'a, EventBus<Outcome>>,
         Read<'a, CachedSpatialGrid>,
         ReadStorage<'a, Pos>,
@@ -38,6 +46,8 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Object>,
         ReadStorage<'a, Body>,
         ReadStorage<'a, Teleporting>,
+        ReadStorage<'a, Corpse>,
+        WriteStorage<'a, ItemDrops>,
     );
 
     const NAME: &'static str = "object";
@@ -51,6 +61,7 @@ impl<'a> System<'a> for Sys {
             events,
             _dt,
             time,
+            program_time,
             outcome_bus,
             spatial_grid,
             positions,
@@ -59,6 +70,8 @@ impl<'a> System<'a> for Sys {
             objects,
             bodies,
             teleporting,
+            corpses,
+            mut item_drops,
         ): Self::SystemData,
     ) {
         let mut emitters = events.get_emitters();
@@ -85,6 +98,7 @@ impl<'a> System<'a> for Sys {
                                     RadiusEffect::Entity(Effect::Damage(Damage {
                                         source: DamageSource::Explosion,
                                         kind: DamageKind::Energy,
+                                        element: DamageElement::Physical,
                                         value: 40.0,
                                     })),
                                     RadiusEffect::Entity(Effect::Poise(-100.0)),
@@ -184,6 +198,7 @@ impl<'a> System<'a> for Sys {
                                     RadiusEffect::Entity(Effect::Damage(Damage {
                                         source: DamageSource::Explosion,
                                         kind: DamageKind::Energy,
+                                        element: DamageElement::Physical,
                                         value: 5.0,
                                     })),
                                     RadiusEffect::Entity(Effect::Poise(-40.0)),
@@ -233,6 +248,32 @@ impl<'a> System<'a> for Sys {
                         });
                     }
                 },
+                // Race gates are kept in sync with their tracks by the race system
+                Object::Shrine { .. } | Object::RaceCheckpoint { .. } => {},
+                Object::Gravestone { decays_at, .. } => {
+                    if time.0 >= decays_at.0 {
+                        emitters.emit(DeleteEvent(entity));
+                        // The owner didn't come back in time, leave the items for anyone to loot
+                        if let Some(ItemDrops(items)) = item_drops.remove(entity) {
+                            for (_, item) in items {
+                                emitters.emit(CreateItemDropEvent {
+                                    pos: *pos,
+                                    vel: Vel::zero(),
+                                    ori: Ori::default(),
+                                    item: PickupItem::new(item, *program_time),
+                                    loot_owner: None,
+                                });
+                            }
+                        }
+                    }
+                },
+            }
+        }
+
+        // Corpses that nobody reanimated in time rot away
+        for (entity, corpse) in (&entities, &corpses).join() {
+            if time.0 >= corpse.decays_at.0 {
+                emitters.emit(DeleteEvent(entity));
             }
         }
     }
@@ -1,14 +1,18 @@
-use crate::{persistence::character_updater, sys::SysScheduler};
+use crate::{
+    metrics::PersistenceMetrics,
+    persistence::{character_loader::CharacterLoader, character_updater},
+    sys::SysScheduler,
+};
 use common::{
     comp::{
         pet::{is_tameable, Pet},
         ActiveAbilities, Alignment, Body, Inventory, MapMarker, Presence, PresenceKind, SkillSet,
-        Stats, Waypoint,
+        Stats, Titles, UnlockedShrines, Waypoint,
     },
     uid::Uid,
 };
 use common_ecs::{Job, Origin, Phase, System};
-use specs::{Join, LendJoin, ReadStorage, Write, WriteExpect};
+use specs::{Join, LendJoin, ReadExpect, ReadStorage, Write, WriteExpect};
 use tracing::error;
 
 #[derive(Default)]
@@ -24,10 +28,14 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Uid>,
         ReadStorage<'a, Waypoint>,
         ReadStorage<'a, MapMarker>,
+        ReadStorage<'a, UnlockedShrines>,
+        ReadStorage<'a, Titles>,
         ReadStorage<'a, Pet>,
         ReadStorage<'a, Stats>,
         ReadStorage<'a, ActiveAbilities>,
         WriteExpect<'a, character_updater::CharacterUpdater>,
+        ReadExpect<'a, CharacterLoader>,
+        ReadExpect<'a, PersistenceMetrics>,
         Write<'a, SysScheduler<Self>>,
     );
 
@@ -46,13 +54,30 @@ impl<'a> System<'a> for Sys {
             uids,
             player_waypoints,
             map_markers,
+            unlocked_shrines,
+            titles,
             pets,
             stats,
             active_abilities,
             mut updater,
+            character_loader,
+            metrics,
             mut scheduler,
         ): Self::SystemData,
     ) {
+        metrics
+            .queue_depth
+            .with_label_values(&["loader"])
+            .set(character_loader.queue_len() as i64);
+        metrics
+            .queue_depth
+            .with_label_values(&["updater"])
+            .set(updater.queue_len() as i64);
+        metrics
+            .queue_depth
+            .with_label_values(&["pending_actions"])
+            .set(updater.pending_action_count() as i64);
+
         if scheduler.should_run() {
             updater.batch_update(
                 (
@@ -63,6 +88,8 @@ impl<'a> System<'a> for Sys {
                     player_waypoints.maybe(),
                     &active_abilities,
                     map_markers.maybe(),
+                    unlocked_shrines.maybe(),
+                    titles.maybe(),
                 )
                     .join()
                     .filter_map(
@@ -74,6 +101,8 @@ impl<'a> System<'a> for Sys {
                             waypoint,
                             active_abilities,
                             map_marker,
+                            unlocked_shrines,
+                            titles,
                         )| match presence.kind {
                             PresenceKind::LoadingCharacter(_char_id) => {
                                 error!(
@@ -107,6 +136,8 @@ impl<'a> System<'a> for Sys {
                                     waypoint.cloned(),
                                     active_abilities.clone(),
                                     map_marker.cloned(),
+                                    unlocked_shrines.cloned().unwrap_or_default(),
+                                    titles.cloned().unwrap_or_default(),
                                 ))
                             },
                             PresenceKind::Spectator | PresenceKind::Possessor => None,
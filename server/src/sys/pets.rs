@@ -58,9 +58,12 @@ impl<'a> System<'a> for Sys {
             .collect();
 
         for (pet_entity, owner_pos) in lost_pets.iter() {
-            let stay = agn.get(*pet_entity).and_then(|x| x.stay_pos).is_some();
+            // Pets told to stay or sent out to scout are meant to be away
+            let away = agn
+                .get(*pet_entity)
+                .map_or(false, |x| x.stay_pos.is_some() || x.scout.is_some());
             if let Some(pet_pos) = positions.get_mut(*pet_entity)
-                && !stay
+                && !away
             {
                 // Move the pets to their owner's position
                 // TODO: Create a teleportation event to handle this instead of
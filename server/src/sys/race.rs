@@ -0,0 +1,216 @@
+use crate::{
+    client::Client,
+    race::{RaceRecord, RaceRun, RaceTracks},
+};
+use common::{
+    comp::{ChatType, Content, Health, Object, Pos, Presence, Stats},
+    event::{CreateRaceCheckpointEvent, DeleteEvent, EmitExt},
+    event_emitters,
+    race::{passes_checkpoint, RaceProgress, CHECKPOINT_RADIUS},
+    resources::Time,
+    terrain::TerrainGrid,
+    uid::{IdMaps, Uid},
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use hashbrown::HashSet;
+use specs::{Entities, Join, Read, ReadExpect, ReadStorage, WriteExpect};
+
+/// Runs taking longer than this, in seconds, are given up on
+const MAX_RUN_DURATION: f64 = 3600.0;
+
+event_emitters! {
+    struct Events[Emitters] {
+        create_race_checkpoint: CreateRaceCheckpointEvent,
+        delete: DeleteEvent,
+    }
+}
+
+fn format_time(time: f64) -> String { format!("{}:{:05.2}", (time / 60.0) as u32, time % 60.0) }
+
+/// This system times characters running race tracks and keeps the gates of
+/// the tracks spawned in loaded chunks
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Events<'a>,
+        Read<'a, Time>,
+        Read<'a, IdMaps>,
+        WriteExpect<'a, RaceTracks>,
+        ReadExpect<'a, TerrainGrid>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Object>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Stats>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Client>,
+    );
+
+    const NAME: &'static str = "race";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            events,
+            time,
+            id_maps,
+            mut race_tracks,
+            terrain,
+            uids,
+            positions,
+            objects,
+            presences,
+            stats,
+            healths,
+            clients,
+        ): Self::SystemData,
+    ) {
+        let mut emitters = events.get_emitters();
+
+        // Remove gates of tracks that changed, and spawn the missing ones
+        let mut gates = HashSet::new();
+        for (entity, object, pos) in (&entities, &objects, &positions).join() {
+            if let Object::RaceCheckpoint { track, index } = object {
+                let checkpoint = race_tracks
+                    .tracks()
+                    .find(|(_, t)| t.id == *track)
+                    .and_then(|(_, t)| t.checkpoints.get(*index as usize));
+                if checkpoint.map_or(true, |checkpoint| {
+                    pos.0.xy().distance_squared(checkpoint.xy()) > CHECKPOINT_RADIUS.powi(2)
+                }) || !gates.insert((*track, *index))
+                {
+                    emitters.emit(DeleteEvent(entity));
+                }
+            }
+        }
+        for (_, track) in race_tracks.tracks() {
+            for (index, checkpoint) in track.checkpoints.iter().enumerate() {
+                if !gates.contains(&(track.id, index as u32))
+                    && terrain
+                        .contains_key_real(terrain.pos_key(checkpoint.map(|e| e.floor() as i32)))
+                {
+                    emitters.emit(CreateRaceCheckpointEvent {
+                        pos: Pos(*checkpoint),
+                        track: track.id,
+                        index: index as u32,
+                    });
+                }
+            }
+        }
+
+        let mut runs = std::mem::take(&mut race_tracks.runs);
+        for (uid, pos, presence, stats, health, client) in (
+            &uids,
+            &positions,
+            &presences,
+            &stats,
+            healths.maybe(),
+            &clients,
+        )
+            .join()
+        {
+            let Some(character_id) = presence.kind.character_id() else {
+                continue;
+            };
+            let notify = |content: Content| {
+                client.send_fallible(ServerGeneral::server_msg(ChatType::Meta, content))
+            };
+
+            if let Some(run) = runs.get_mut(uid) {
+                let checkpoints = race_tracks
+                    .get(&run.track)
+                    .map(|track| track.checkpoints.as_slice())
+                    .unwrap_or_default();
+                if health.map_or(false, |health| health.is_dead)
+                    || time.0 - run.started_at > MAX_RUN_DURATION
+                    || checkpoints.is_empty()
+                {
+                    client.send_fallible(ServerGeneral::RaceProgress(RaceProgress::Aborted));
+                    runs.remove(uid);
+                    continue;
+                }
+                if let Some(checkpoint) = checkpoints.get(run.next)
+                    && passes_checkpoint(pos.0, *checkpoint)
+                {
+                    let split = time.0 - run.started_at;
+                    run.splits.push(split);
+                    run.next += 1;
+                    if run.next == checkpoints.len() {
+                        let track = run.track.clone();
+                        let record = RaceRecord {
+                            character_id,
+                            name: stats.name.clone(),
+                            time: split,
+                            splits: std::mem::take(&mut run.splits),
+                        };
+                        runs.remove(uid);
+                        let personal_best = race_tracks.finish(&track, record);
+                        notify(Content::localized_with_args(
+                            if personal_best {
+                                "hud-race-personal-best"
+                            } else {
+                                "hud-race-finished"
+                            },
+                            [("track", track.clone()), ("time", format_time(split))],
+                        ));
+                        client.send_fallible(ServerGeneral::RaceProgress(RaceProgress::Finished {
+                            track,
+                            time: split,
+                            personal_best,
+                        }));
+                        continue;
+                    } else {
+                        // Compare against the split of the best run so far
+                        let best = race_tracks
+                            .get(&run.track)
+                            .and_then(|track| track.record(character_id))
+                            .and_then(|record| record.splits.get(run.splits.len() - 1));
+                        let diff = best
+                            .map_or_else(String::new, |best| format!(" ({:+.2})", split - best));
+                        notify(Content::localized_with_args("hud-race-split", [
+                            ("checkpoint", run.next.to_string()),
+                            ("checkpoints", checkpoints.len().to_string()),
+                            ("time", format!("{}{diff}", format_time(split))),
+                        ]));
+                    }
+                }
+            }
+
+            // Characters that haven't left the start of their track yet keep restarting
+            // their run, so the time starts when they leave it
+            if runs.get(uid).map_or(true, |run| run.next <= 1) {
+                let start = race_tracks.tracks().find(|(_, track)| {
+                    track.checkpoints.len() > 1 && passes_checkpoint(pos.0, track.checkpoints[0])
+                });
+                if let Some((name, track)) = start {
+                    let restarted = runs.get(uid).map_or(false, |run| &run.track == name);
+                    if !restarted {
+                        client.send_fallible(ServerGeneral::RaceProgress(RaceProgress::Started {
+                            track: name.clone(),
+                            start: track.checkpoints[0],
+                        }));
+                    }
+                    runs.insert(*uid, RaceRun {
+                        track: name.clone(),
+                        next: 1,
+                        started_at: time.0,
+                        splits: Vec::new(),
+                    });
+                }
+            }
+        }
+        // Characters that logged off or were deleted aren't running anymore
+        runs.retain(|uid, _| {
+            id_maps
+                .uid_entity(*uid)
+                .map_or(false, |entity| clients.contains(entity))
+        });
+        race_tracks.runs = runs;
+    }
+}
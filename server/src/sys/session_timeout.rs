@@ -0,0 +1,41 @@
+use crate::presence::ResumableSession;
+use common::{
+    comp::DisconnectReason,
+    event::{ClientDisconnectEvent, EventBus},
+    resources::ProgramTime,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{Entities, Join, Read, ReadStorage};
+
+/// This system removes the characters of clients that lost their connection
+/// and did not resume their session in time
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, ProgramTime>,
+        Read<'a, EventBus<ClientDisconnectEvent>>,
+        ReadStorage<'a, ResumableSession>,
+    );
+
+    const NAME: &'static str = "session_timeout";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, program_time, client_disconnect_events, sessions): Self::SystemData,
+    ) {
+        let mut client_disconnect_emitter = client_disconnect_events.emitter();
+        for (entity, session) in (&entities, &sessions).join() {
+            if session
+                .expires
+                .is_some_and(|expires| program_time.0 >= expires)
+            {
+                client_disconnect_emitter
+                    .emit(ClientDisconnectEvent(entity, DisconnectReason::Timeout));
+            }
+        }
+    }
+}
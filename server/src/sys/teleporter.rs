@@ -104,6 +104,7 @@ impl<'a> System<'a> for Sys {
                         | CharacterState::Wielding(_)
                         | CharacterState::Sit
                         | CharacterState::Dance
+                        | CharacterState::Emote(_)
                 )
             {
                 cancel_teleporting.push(entity);
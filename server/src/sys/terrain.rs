@@ -8,8 +8,14 @@ use world::{IndexOwned, World};
 
 #[cfg(feature = "worldgen")] use crate::rtsim;
 use crate::{
-    chunk_generator::ChunkGenerator, chunk_serialize::ChunkSendEntry, client::Client,
-    presence::RepositionOnChunkLoad, settings::Settings, ChunkRequest, Tick,
+    chunk_generator::{ChunkGenerator, ChunkSource},
+    chunk_serialize::ChunkSendEntry,
+    client::Client,
+    guild::Guilds,
+    prefab::Prefabs,
+    presence::RepositionOnChunkLoad,
+    settings::Settings,
+    ChunkRequest, Tick,
 };
 use common::{
     calendar::Calendar,
@@ -34,6 +40,7 @@ use common_state::TerrainChanges;
 use comp::Behavior;
 use core::cmp::Reverse;
 use itertools::Itertools;
+use rand::Rng;
 use rayon::{iter::Either, prelude::*};
 use specs::{
     shred, storage::GenericReadStorage, Entities, Entity, Join, LendJoin, ParJoin, Read,
@@ -79,6 +86,8 @@ pub struct Data<'a> {
     rtsim: RtSimData<'a>,
     #[cfg(feature = "persistent_world")]
     terrain_persistence: TerrainPersistenceData<'a>,
+    prefabs: WriteExpect<'a, Prefabs>,
+    guilds: ReadExpect<'a, Guilds>,
     positions: WriteStorage<'a, Pos>,
     presences: ReadStorage<'a, Presence>,
     clients: ReadStorage<'a, Client>,
@@ -116,21 +125,21 @@ impl<'a> System<'a> for Sys {
         data.chunk_requests.drain(..).for_each(|request| {
             data.chunk_generator.generate_chunk(
                 Some(request.entity),
+                ChunkSource::Player,
                 request.key,
-                &data.slow_jobs,
                 Arc::clone(&data.world),
                 &data.rtsim,
                 data.index.clone(),
                 (*data.time_of_day, data.calendar.clone()),
             )
         });
+        data.chunk_generator.dispatch(&data.slow_jobs);
 
         let mut rng = rand::thread_rng();
         // Fetch any generated `TerrainChunk`s and insert them into the terrain.
         // Also, send the chunk data to anybody that is close by.
         let mut new_chunks = Vec::new();
         'insert_terrain_chunks: while let Some((key, res)) = data.chunk_generator.recv_new_chunk() {
-            #[allow(unused_mut)]
             let (mut chunk, supplement) = match res {
                 Ok((chunk, supplement)) => (chunk, supplement),
                 Err(Some(entity)) => {
@@ -147,6 +156,13 @@ impl<'a> System<'a> for Sys {
                 },
             };
 
+            // Prefabs placed in the world come back when chunks are regenerated, changes
+            // made to them since then are restored by terrain persistence
+            data.prefabs.apply_to_chunk(key, &mut chunk);
+
+            // Guild halls show the banners of the guilds that claimed them
+            data.guilds.paint_banners(key, &mut chunk);
+
             // Apply changes from terrain persistence to this chunk
             #[cfg(feature = "persistent_world")]
             if let Some(terrain_persistence) = data.terrain_persistence.as_mut() {
@@ -170,8 +186,25 @@ impl<'a> System<'a> for Sys {
                     .hook_load_chunk(key, supplement.rtsim_max_resources);
             }
 
+            // Servers can spawn less wildlife to lighten their load
+            #[cfg(feature = "worldgen")]
+            let wildlife_density = data.world.sim().get(key).map_or(1.0, |chunk| {
+                data.server_settings
+                    .world
+                    .population
+                    .wildlife_density(chunk.get_biome())
+            });
+            #[cfg(not(feature = "worldgen"))]
+            let wildlife_density = 1.0;
+
             // Handle chunk supplement
             for entity in supplement.entities {
+                if matches!(entity.alignment, comp::Alignment::Wild)
+                    && !rng.gen_bool(f64::from(wildlife_density))
+                {
+                    continue;
+                }
+
                 // Check this because it's a common source of weird bugs
                 assert!(
                     data.terrain
@@ -389,6 +422,7 @@ pub struct NpcData {
     pub alignment: comp::Alignment,
     pub scale: comp::Scale,
     pub loot: LootSpec<String>,
+    pub encounter: Option<comp::Encounter>,
     pub pets: Vec<(NpcData, Vec3<f32>)>,
 }
 
@@ -422,6 +456,7 @@ impl SpawnEntityData {
             loot,
             // tools and skills
             skillset_asset,
+            encounter,
             loadout: mut loadout_builder,
             inventory: items,
             make_loadout,
@@ -498,6 +533,18 @@ impl SpawnEntityData {
                 Behavior::default()
                     .maybe_with_capabilities(can_speak.then_some(BehaviorCapability::SPEAK))
                     .maybe_with_capabilities(trade_for_site.map(|_| BehaviorCapability::TRADE))
+                    .maybe_with_capabilities(
+                        matches!(agent_mark, Some(agent::Mark::Banker))
+                            .then_some(BehaviorCapability::BANK),
+                    )
+                    .maybe_with_capabilities(
+                        matches!(agent_mark, Some(agent::Mark::Trainer))
+                            .then_some(BehaviorCapability::TRAIN),
+                    )
+                    .maybe_with_capabilities(
+                        matches!(agent_mark, Some(agent::Mark::Necromancer))
+                            .then_some(BehaviorCapability::REANIMATE),
+                    )
                     .with_trade_site(trade_for_site),
             );
 
@@ -532,6 +579,7 @@ impl SpawnEntityData {
             alignment,
             scale: comp::Scale(scale),
             loot,
+            encounter: encounter.map(comp::Encounter::new),
             pets: {
                 let pet_count = pets.len() as f32;
                 pets.into_iter()
@@ -577,6 +625,7 @@ impl NpcData {
             alignment,
             scale,
             loot,
+            encounter,
             pets,
         } = self;
 
@@ -589,6 +638,7 @@ impl NpcData {
                 .with_agent(agent)
                 .with_scale(scale)
                 .with_loot(loot)
+                .with_encounter(encounter)
                 .with_pets(
                     pets.into_iter()
                         .map(|(pet, offset)| (pet.to_npc_builder().0, offset))
@@ -0,0 +1,186 @@
+#[cfg(feature = "worldgen")]
+use crate::rtsim::RtSim;
+use crate::settings::Settings;
+use common::{
+    resources::Time,
+    terrain::{Block, CoordinateConversions, SpriteKind, TerrainGrid},
+    vol::ReadVol,
+    weather::WeatherGrid,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_state::BlockChange;
+use hashbrown::HashMap;
+use rand::Rng;
+#[cfg(feature = "worldgen")]
+use specs::WriteExpect;
+use specs::{Read, ReadExpect, Write};
+use vek::*;
+
+/// How often, in seconds, fires spread and burn out
+const WILDFIRE_DT: f64 = 1.0;
+/// Rain above this intensity puts out fires
+const RAIN_EXTINGUISH_THRESHOLD: f32 = 0.25;
+
+#[cfg(feature = "worldgen")]
+type RtSimData<'a> = WriteExpect<'a, RtSim>;
+#[cfg(not(feature = "worldgen"))]
+type RtSimData<'a> = ();
+
+/// Blocks currently on fire, kept bounded by the server settings so that fires
+/// can't spread across the whole world
+#[derive(Default)]
+pub struct Wildfires {
+    /// Burning blocks and the time at which they burn out
+    burning: HashMap<Vec3<i32>, f64>,
+    /// Blocks that were set on fire since the last update
+    pending: Vec<Vec3<i32>>,
+    last_update: f64,
+}
+
+impl Wildfires {
+    /// Sets the block at `pos` on fire during the next update, if it can burn
+    pub fn ignite(&mut self, pos: Vec3<i32>) { self.pending.push(pos); }
+
+    pub fn is_burning(&self, pos: Vec3<i32>) -> bool { self.burning.contains_key(&pos) }
+}
+
+/// Whether a fire can be started at `pos`, either on a plant sprite or in the
+/// empty space on top of flammable ground
+fn can_burn(terrain: &TerrainGrid, pos: Vec3<i32>) -> bool {
+    terrain.get(pos).map_or(false, |b| {
+        b.is_air()
+            && (b.is_flammable()
+                || (b.get_sprite() == Some(SpriteKind::Empty)
+                    && terrain
+                        .get(pos - Vec3::unit_z())
+                        .map_or(false, |b| b.is_flammable())))
+    })
+}
+
+fn is_near_water(terrain: &TerrainGrid, pos: Vec3<i32>) -> bool {
+    [
+        Vec3::unit_x(),
+        -Vec3::unit_x(),
+        Vec3::unit_y(),
+        -Vec3::unit_y(),
+        Vec3::unit_z(),
+        -Vec3::unit_z(),
+    ]
+    .into_iter()
+    .any(|dir| terrain.get(pos + dir).map_or(false, |b| b.is_liquid()))
+}
+
+/// This system spreads fires to nearby flammable blocks and puts them out once
+/// they burnt down, or when rain or water reaches them
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, Time>,
+        Read<'a, Settings>,
+        Write<'a, Wildfires>,
+        Write<'a, BlockChange>,
+        ReadExpect<'a, TerrainGrid>,
+        Option<Read<'a, WeatherGrid>>,
+        RtSimData<'a>,
+    );
+
+    const NAME: &'static str = "wildfire";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (time, settings, mut wildfires, mut block_change, terrain, weather, rtsim): Self::SystemData,
+    ) {
+        if time.0 - wildfires.last_update < WILDFIRE_DT {
+            return;
+        }
+        wildfires.last_update = time.0;
+        let settings = &settings.gameplay.wildfire;
+        let mut rng = rand::thread_rng();
+
+        // Put out fires that burnt down, got rained on, or were replaced by something
+        // else in the meantime
+        let is_raining = |pos: Vec3<i32>| {
+            weather.as_ref().map_or(false, |weather| {
+                weather.get_interpolated(pos.xy().as_()).rain > RAIN_EXTINGUISH_THRESHOLD
+            })
+        };
+        let extinguished = wildfires
+            .burning
+            .iter()
+            .filter(|(pos, burns_out)| {
+                time.0 >= **burns_out
+                    || is_raining(**pos)
+                    || is_near_water(&terrain, **pos)
+                    || terrain
+                        .get(**pos)
+                        .map_or(true, |b| b.get_sprite() != Some(SpriteKind::FireBlock))
+            })
+            .map(|(pos, _)| *pos)
+            .collect::<Vec<_>>();
+        for pos in extinguished {
+            wildfires.burning.remove(&pos);
+            if terrain
+                .get(pos)
+                .map_or(false, |b| b.get_sprite() == Some(SpriteKind::FireBlock))
+            {
+                // Whatever was burning is gone now
+                block_change.try_set(pos, Block::air(SpriteKind::Empty));
+            }
+        }
+
+        // Spread existing fires to the blocks around them, and light new ones
+        let mut to_ignite = std::mem::take(&mut wildfires.pending);
+        if settings.enabled {
+            for pos in wildfires.burning.keys() {
+                for offset in (-1..=1)
+                    .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| (x, y, z))))
+                    .map(Vec3::from)
+                    .filter(|offset: &Vec3<i32>| *offset != Vec3::zero())
+                {
+                    if rng.gen_bool(settings.spread_chance.clamp(0.0, 1.0) as f64) {
+                        to_ignite.push(*pos + offset);
+                    }
+                }
+            }
+        } else {
+            to_ignite.clear();
+        }
+
+        // One of the new fires in each chunk that caught fire
+        let mut ignited_chunks = HashMap::new();
+        for pos in to_ignite {
+            if wildfires.burning.len() >= settings.max_burning_blocks {
+                break;
+            }
+            if wildfires.is_burning(pos)
+                || !can_burn(&terrain, pos)
+                || is_raining(pos)
+                || is_near_water(&terrain, pos)
+            {
+                continue;
+            }
+            if block_change
+                .try_set(pos, Block::air(SpriteKind::FireBlock))
+                .is_some()
+            {
+                let burn_time = settings.burn_duration * rng.gen_range(0.75..1.25);
+                wildfires.burning.insert(pos, time.0 + burn_time as f64);
+                ignited_chunks.entry(pos.xy().wpos_to_cpos()).or_insert(pos);
+            }
+        }
+
+        // Let nearby NPCs know about the fire, once per chunk that caught fire
+        #[cfg(feature = "worldgen")]
+        {
+            let mut rtsim = rtsim;
+            for fire in ignited_chunks.into_values() {
+                rtsim.hook_fire(fire.as_());
+            }
+        }
+        #[cfg(not(feature = "worldgen"))]
+        let _ = (rtsim, ignited_chunks);
+    }
+}
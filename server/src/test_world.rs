@@ -1,14 +1,16 @@
 use common::{
     calendar::Calendar,
     generation::ChunkSupplement,
+    grid::Grid,
+    guild::GuildHallSpot,
     resources::TimeOfDay,
-    rtsim::ChunkResource,
+    rtsim::RtsimChunks,
     terrain::{
         Block, BlockKind, MapSizeLg, SpriteKind, TerrainChunk, TerrainChunkMeta, TerrainChunkSize,
     },
     vol::RectVolSize,
 };
-use enum_map::EnumMap;
+use common_net::msg::world_msg::TreasureMapSnippet;
 use rand::{prelude::*, rngs::SmallRng};
 use std::time::Duration;
 use vek::*;
@@ -51,7 +53,7 @@ impl World {
         &self,
         _index: IndexRef,
         chunk_pos: Vec2<i32>,
-        _rtsim_resources: Option<EnumMap<ChunkResource, f32>>,
+        _rtsim_chunks: Option<RtsimChunks>,
         // TODO: misleading name
         mut _should_continue: impl FnMut() -> bool,
         _time: Option<(TimeOfDay, Calendar)>,
@@ -87,4 +89,25 @@ impl World {
         // Test world has no locations
         None
     }
+
+    pub fn find_treasure_spot(
+        &self,
+        _index: IndexRef,
+        _origin: Vec2<i32>,
+        _rng: &mut impl Rng,
+    ) -> Option<Vec2<i32>> {
+        // Test world has nowhere to bury treasure
+        None
+    }
+
+    pub fn treasure_map_snippet(&self, _index: IndexRef, _cache: Vec2<i32>) -> TreasureMapSnippet {
+        TreasureMapSnippet {
+            rgba: Grid::new(Vec2::broadcast(1), 0),
+        }
+    }
+
+    pub fn guild_halls(&self, _index: IndexRef) -> Vec<GuildHallSpot> {
+        // Test world has no towns to put guild halls in
+        Vec::new()
+    }
 }
@@ -0,0 +1,71 @@
+use atomicwrites::{AtomicFile, OverwriteBehavior};
+use common::character::CharacterId;
+use hashbrown::HashMap;
+use std::{io::Write as _, path::PathBuf};
+use tracing::{error, info};
+use vek::*;
+
+const CACHES_FILE: &str = "treasure_caches.ron";
+/// Characters digging with a shovel this close to their cache, horizontally,
+/// unearth it
+pub const DIG_RADIUS: f32 = 3.0;
+
+/// Treasure caches buried for the characters that read a treasure map and
+/// haven't dug them up yet, persisted in the data directory. A character
+/// follows one map at a time.
+pub struct TreasureCaches {
+    path: PathBuf,
+    caches: HashMap<CharacterId, Vec2<i32>>,
+}
+
+impl TreasureCaches {
+    pub fn new(data_dir: PathBuf) -> Self {
+        let path = data_dir.join(CACHES_FILE);
+        let caches = match std::fs::read_to_string(&path) {
+            Ok(caches) => ron::from_str(&caches).unwrap_or_else(|err| {
+                error!(?err, "Failed to parse treasure caches, ignoring them");
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        info!("Loaded {} treasure caches from {:?}", caches.len(), path);
+
+        Self { path, caches }
+    }
+
+    pub fn get(&self, character_id: CharacterId) -> Option<Vec2<i32>> {
+        self.caches.get(&character_id).copied()
+    }
+
+    pub fn bury(&mut self, character_id: CharacterId, pos: Vec2<i32>) {
+        self.caches.insert(character_id, pos);
+        self.save();
+    }
+
+    /// Removes the cache of a character if `pos` is close enough to it,
+    /// returning where it was
+    pub fn dig(&mut self, character_id: CharacterId, pos: Vec2<f32>) -> Option<Vec2<i32>> {
+        let cache = self.get(character_id)?;
+        if cache.as_::<f32>().distance_squared(pos) > DIG_RADIUS.powi(2) {
+            return None;
+        }
+        self.caches.remove(&character_id);
+        self.save();
+        Some(cache)
+    }
+
+    fn save(&self) {
+        let caches =
+            match ron::ser::to_string_pretty(&self.caches, ron::ser::PrettyConfig::default()) {
+                Ok(caches) => caches,
+                Err(err) => {
+                    error!(?err, "Failed to serialize treasure caches");
+                    return;
+                },
+            };
+        let file = AtomicFile::new(&self.path, OverwriteBehavior::AllowOverwrite);
+        if let Err(err) = file.write(|file| file.write_all(caches.as_bytes())) {
+            error!(?err, "Failed to write treasure caches");
+        }
+    }
+}
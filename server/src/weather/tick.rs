@@ -1,7 +1,6 @@
 use common::{
     comp,
-    event::EventBus,
-    outcome::Outcome,
+    event::{EventBus, LightningStrikeEvent},
     resources::{DeltaTime, ProgramTime, TimeOfDay},
     slowjob::{SlowJob, SlowJobPool},
     weather::{SharedWeatherGrid, Weather, WeatherGrid},
@@ -56,7 +55,7 @@ impl<'a> System<'a> for Sys {
         Write<'a, Option<WeatherJob>>,
         WriteExpect<'a, WeatherGrid>,
         WriteExpect<'a, SlowJobPool>,
-        Read<'a, EventBus<Outcome>>,
+        Read<'a, EventBus<LightningStrikeEvent>>,
         ReadExpect<'a, Arc<World>>,
         ReadStorage<'a, Client>,
         ReadStorage<'a, comp::Pos>,
@@ -78,7 +77,7 @@ impl<'a> System<'a> for Sys {
             mut weather_job,
             mut grid,
             slow_job_pool,
-            outcomes,
+            lightning_strikes,
             world,
             clients,
             positions,
@@ -150,7 +149,7 @@ impl<'a> System<'a> for Sys {
 
         // Chance to emit lightning every frame from one or more of the cells that
         // currently has the correct weather conditions.
-        let mut outcome_emitter = outcomes.emitter();
+        let mut lightning_emitter = lightning_strikes.emitter();
         let mut rng = thread_rng();
         let num_cells = lightning_cells.cells.len() as f64 * 0.0015 * delta_time.0 as f64;
         let num_cells = num_cells.floor() as u32 + rng.gen_bool(num_cells.fract()) as u32;
@@ -162,7 +161,7 @@ impl<'a> System<'a> for Sys {
             );
             let wpos = cell_pos
                 .map(|e| (e as f32 + rng.gen_range(0.0..1.0)) * common::weather::CELL_SIZE as f32);
-            outcome_emitter.emit(Outcome::Lightning {
+            lightning_emitter.emit(LightningStrikeEvent {
                 pos: wpos.with_z(world.sim().get_alt_approx(wpos.as_()).unwrap_or(0.0)),
             });
         }
@@ -115,6 +115,7 @@ impl Skeleton for ArthropodSkeleton {
                 orientation: mount_orientation,
                 scale: Vec3::one(),
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -154,6 +154,7 @@ impl Skeleton for BipedLargeSkeleton {
                 orientation: mount_orientation,
                 scale: Vec3::one(),
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -101,6 +101,7 @@ impl Skeleton for BipedSmallSkeleton {
                     .into(),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -118,6 +118,7 @@ impl Skeleton for BirdLargeSkeleton {
                 orientation: mount_orientation,
                 scale: Vec3::one(),
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
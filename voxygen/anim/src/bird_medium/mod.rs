@@ -4,6 +4,7 @@ pub mod dash;
 pub mod feed;
 pub mod fly;
 pub mod idle;
+pub mod perch;
 pub mod run;
 pub mod shockwave;
 pub mod shoot;
@@ -14,8 +15,9 @@ pub mod swim;
 // Reexports
 pub use self::{
     alpha::AlphaAnimation, breathe::BreatheAnimation, dash::DashAnimation, feed::FeedAnimation,
-    fly::FlyAnimation, idle::IdleAnimation, run::RunAnimation, shockwave::ShockwaveAnimation,
-    shoot::ShootAnimation, stunned::StunnedAnimation, summon::SummonAnimation, swim::SwimAnimation,
+    fly::FlyAnimation, idle::IdleAnimation, perch::PerchAnimation, run::RunAnimation,
+    shockwave::ShockwaveAnimation, shoot::ShootAnimation, stunned::StunnedAnimation,
+    summon::SummonAnimation, swim::SwimAnimation,
 };
 
 use super::{make_bone, vek::*, FigureBoneData, Offsets, Skeleton};
@@ -90,6 +92,7 @@ impl Skeleton for BirdMediumSkeleton {
                     .into(),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
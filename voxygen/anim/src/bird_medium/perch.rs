@@ -0,0 +1,79 @@
+use super::{
+    super::{vek::*, Animation},
+    BirdMediumSkeleton, SkeletonAttr,
+};
+use std::{f32::consts::PI, ops::Mul};
+
+/// Perched on whoever it's riding, wings folded and keeping a lookout
+pub struct PerchAnimation;
+
+impl Animation for PerchAnimation {
+    type Dependency<'a> = f32;
+    type Skeleton = BirdMediumSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"bird_medium_perch\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "bird_medium_perch")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        global_time: Self::Dependency<'_>,
+        anim_time: f32,
+        _rate: &mut f32,
+        s_a: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        // Birds keep a closer watch than most, so look about more often
+        let head_look = Vec2::new(
+            (global_time + anim_time / 3.0).floor().mul(7331.0).sin() * 0.8,
+            (global_time + anim_time / 3.0).floor().mul(1337.0).sin() * 0.2,
+        );
+        let breathe = (anim_time * 3.0).sin();
+        // Every so often the bird ruffles its wings to keep its balance
+        let ruffle = (anim_time * 0.8).sin().max(0.0).powi(12);
+
+        next.head.scale = Vec3::one() * 0.99;
+        next.leg_l.scale = Vec3::one() * s_a.scaler * 0.99;
+        next.leg_r.scale = Vec3::one() * s_a.scaler * 0.99;
+        next.chest.scale = Vec3::one() * s_a.scaler * 0.99;
+        next.tail.scale = Vec3::one() * 1.01;
+        next.wing_in_l.scale = Vec3::one() * s_a.scaler * 0.99;
+        next.wing_in_r.scale = Vec3::one() * s_a.scaler * 0.99;
+
+        // Crouched down, gripping on with its feet
+        next.leg_l.position = Vec3::new(-s_a.leg.0, s_a.leg.1, s_a.leg.2 + 0.5);
+        next.leg_l.orientation = Quaternion::rotation_x(0.3);
+        next.leg_r.position = Vec3::new(s_a.leg.0, s_a.leg.1, s_a.leg.2 + 0.5);
+        next.leg_r.orientation = Quaternion::rotation_x(0.3);
+
+        next.chest.position =
+            Vec3::new(0.0, s_a.chest.0, s_a.chest.1 + breathe * 0.05 + 0.5) * s_a.scaler;
+        next.chest.orientation = Quaternion::rotation_x(-0.1);
+
+        next.head.position = Vec3::new(0.0, s_a.head.0, s_a.head.1);
+        next.head.orientation =
+            Quaternion::rotation_z(head_look.x) * Quaternion::rotation_x(head_look.y + 0.1);
+
+        next.tail.position = Vec3::new(0.0, s_a.tail.0, s_a.tail.1);
+        next.tail.orientation = Quaternion::rotation_x(0.3 + ruffle * 0.3);
+
+        next.wing_in_l.position = Vec3::new(-s_a.wing_in.0, s_a.wing_in.1, s_a.wing_in.2);
+        next.wing_in_r.position = Vec3::new(s_a.wing_in.0, s_a.wing_in.1, s_a.wing_in.2);
+        next.wing_in_l.orientation = Quaternion::rotation_x(-PI / 2.0)
+            * Quaternion::rotation_y(-PI / 2.5 + ruffle * 0.6)
+            * Quaternion::rotation_z(-PI / 4.0);
+        next.wing_in_r.orientation = Quaternion::rotation_x(-PI / 2.0)
+            * Quaternion::rotation_y(PI / 2.5 - ruffle * 0.6)
+            * Quaternion::rotation_z(PI / 4.0);
+
+        next.wing_out_l.position =
+            Vec3::new(-s_a.wing_out.0 / 2.5, s_a.wing_out.1, s_a.wing_out.2 + 1.0);
+        next.wing_out_r.position =
+            Vec3::new(s_a.wing_out.0 / 2.5, s_a.wing_out.1, s_a.wing_out.2 + 1.0);
+        next.wing_out_l.orientation = Quaternion::rotation_y(0.1) * Quaternion::rotation_z(0.2);
+        next.wing_out_r.orientation = Quaternion::rotation_y(-0.1) * Quaternion::rotation_z(-0.2);
+
+        next
+    }
+}
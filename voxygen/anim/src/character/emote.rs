@@ -0,0 +1,105 @@
+use super::{
+    super::{vek::*, Animation},
+    CharacterSkeleton, SkeletonAttr,
+};
+use common::states::emote::EmoteKind;
+use core::f32::consts::PI;
+
+pub struct EmoteAnimation;
+
+impl Animation for EmoteAnimation {
+    type Dependency<'a> = EmoteKind;
+    type Skeleton = CharacterSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"character_emote\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "character_emote")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        kind: Self::Dependency<'_>,
+        anim_time: f32,
+        rate: &mut f32,
+        s_a: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        *rate = 1.0;
+        let mut next = (*skeleton).clone();
+
+        // Ease in over the first quarter of a second and out over the last
+        let duration = kind.duration().as_secs_f32();
+        let blend = (anim_time * 4.0)
+            .min((duration - anim_time) * 4.0)
+            .clamp(0.0, 1.0);
+
+        match kind {
+            EmoteKind::Wave => {
+                let swing = (anim_time * 10.0).sin();
+
+                next.head.orientation = Quaternion::rotation_x(0.1 * blend)
+                    * Quaternion::rotation_z(swing * 0.05 * blend);
+                next.hand_r.position = Vec3::new(
+                    s_a.hand.0 + 2.0 * blend,
+                    s_a.hand.1 + 2.0 * blend,
+                    s_a.hand.2 + 12.0 * blend,
+                );
+                next.hand_r.orientation = Quaternion::rotation_x(PI * 0.9 * blend)
+                    * Quaternion::rotation_y((-0.4 + swing * 0.5) * blend);
+            },
+            EmoteKind::Point => {
+                next.chest.orientation = Quaternion::rotation_z(-0.2 * blend);
+                next.head.orientation = Quaternion::rotation_z(0.2 * blend);
+                next.hand_r.position = Vec3::new(
+                    s_a.hand.0 - 1.0 * blend,
+                    s_a.hand.1 + 6.0 * blend,
+                    s_a.hand.2 + 6.0 * blend,
+                );
+                next.hand_r.orientation =
+                    Quaternion::rotation_x(PI * 0.5 * blend) * Quaternion::rotation_y(-0.2 * blend);
+            },
+            EmoteKind::Bow => {
+                let bend = (anim_time * PI / duration).sin().max(0.0) * blend;
+
+                next.chest.position = Vec3::new(0.0, s_a.chest.0, s_a.chest.1 - bend);
+                next.chest.orientation = Quaternion::rotation_x(-0.6 * bend);
+                next.head.orientation = Quaternion::rotation_x(-0.3 * bend);
+                next.belt.orientation = Quaternion::rotation_x(0.2 * bend);
+                next.shorts.orientation = Quaternion::rotation_x(0.3 * bend);
+                next.hand_l.position = Vec3::new(
+                    -s_a.hand.0 + 3.0 * bend,
+                    s_a.hand.1 + 3.0 * bend,
+                    s_a.hand.2 + 2.0 * bend,
+                );
+                next.hand_l.orientation = Quaternion::rotation_x(1.2 * bend);
+                next.hand_r.position = Vec3::new(
+                    s_a.hand.0 + 1.0 * bend,
+                    s_a.hand.1 - 2.0 * bend,
+                    s_a.hand.2 + 1.0 * bend,
+                );
+                next.hand_r.orientation = Quaternion::rotation_x(-0.4 * bend);
+            },
+            EmoteKind::Laugh => {
+                let shake = (anim_time * 18.0).sin() * blend;
+                let lean = blend * 0.25;
+
+                next.head.position = Vec3::new(0.0, s_a.head.0, s_a.head.1 + shake.abs() * 0.5);
+                next.head.orientation = Quaternion::rotation_x(lean + shake * 0.08);
+                next.chest.position = Vec3::new(0.0, s_a.chest.0, s_a.chest.1 + shake.abs() * 0.75);
+                next.chest.orientation = Quaternion::rotation_x(lean * 0.5);
+                next.hand_l.position = Vec3::new(
+                    -s_a.hand.0 + 2.0 * blend,
+                    s_a.hand.1 + 3.0 * blend,
+                    s_a.hand.2 + 3.0 * blend + shake,
+                );
+                next.hand_l.orientation = Quaternion::rotation_x(1.0 * blend);
+                next.hand_r.position = Vec3::new(
+                    s_a.hand.0 - 2.0 * blend,
+                    s_a.hand.1 + 3.0 * blend,
+                    s_a.hand.2 + 3.0 * blend + shake,
+                );
+                next.hand_r.orientation = Quaternion::rotation_x(1.0 * blend);
+            },
+        }
+
+        next
+    }
+}
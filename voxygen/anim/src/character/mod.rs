@@ -4,6 +4,7 @@ pub mod climb;
 pub mod collect;
 pub mod consume;
 pub mod dance;
+pub mod emote;
 pub mod equip;
 pub mod glidewield;
 pub mod gliding;
@@ -38,6 +39,7 @@ pub use self::{
     collect::CollectAnimation,
     consume::ConsumeAnimation,
     dance::DanceAnimation,
+    emote::EmoteAnimation,
     equip::EquipAnimation,
     glidewield::GlideWieldAnimation,
     gliding::GlidingAnimation,
@@ -64,7 +66,7 @@ pub use self::{
     wallrun::WallrunAnimation,
     wield::WieldAnimation,
 };
-use super::{make_bone, vek::*, FigureBoneData, Offsets, Skeleton, TrailSource};
+use super::{make_bone, vek::*, FigureBoneData, Offsets, Perches, Skeleton, TrailSource};
 use common::comp::{
     self,
     tool::{Hands, ToolKind},
@@ -102,6 +104,8 @@ skeleton_impls!(struct CharacterSkeleton {
     off_weapon_trail: bool,
     // Cannot exist at same time as weapon trails. Since gliding and attacking are mutually exclusive, should never be a concern.
     glider_trails: bool,
+    // Swing of cloth worn on the back, driven by the cloth simulation rather than the animations
+    back_sway: Quaternion<f32>,
 });
 
 impl CharacterSkeleton {
@@ -112,6 +116,10 @@ impl CharacterSkeleton {
             ..Self::default()
         }
     }
+
+    /// Swings cloth worn on the back, such as a cape, about the point it hangs
+    /// from.
+    pub fn sway_back(&mut self, sway: Quaternion<f32>) { self.back_sway = sway; }
 }
 
 impl Skeleton for CharacterSkeleton {
@@ -158,7 +166,7 @@ impl Skeleton for CharacterSkeleton {
             make_bone(head_mat),
             make_bone(chest_mat),
             make_bone(chest_mat * Mat4::<f32>::from(self.belt)),
-            make_bone(chest_mat * Mat4::<f32>::from(self.back)),
+            make_bone(chest_mat * Mat4::<f32>::from(self.back) * Mat4::<f32>::from(self.back_sway)),
             make_bone(shorts_mat),
             make_bone(control_l_mat * hand_l_mat),
             make_bone(hand_r_mat),
@@ -174,15 +182,35 @@ impl Skeleton for CharacterSkeleton {
             make_bone(control_mat * hand_l_mat * Mat4::<f32>::from(self.hold)),
         ];
 
-        // Offset from the mounted bone's origin.
+        // Riders perch on top of the right shoulder, following it as it moves but
+        // not as it turns, so that they aren't flung about by swings and the like.
         // Note: This could be its own bone if we need to animate it independently.
-        let mount_position = (chest_mat * Vec4::from_point(Vec3::new(5.5, 0.0, 6.5)))
-            .homogenized()
-            .xyz();
+        let mount_position = (chest_mat
+            * Vec4::from_point(self.shoulder_r.position + Vec3::new(0.5, 0.0, 1.5)))
+        .homogenized()
+        .xyz();
         // NOTE: We apply the ori from base_mat externally so we don't need to worry
         // about it here for now.
         let mount_orientation =
             self.torso.orientation * self.chest.orientation * Quaternion::rotation_y(0.4);
+        // Smaller companions might rather curl up on top of the head, or ride along
+        // on the back
+        let head_perch = Transform {
+            position: (head_mat * Vec4::from_point(Vec3::new(0.0, 0.0, 9.0)))
+                .homogenized()
+                .xyz(),
+            orientation: self.torso.orientation * self.chest.orientation * self.head.orientation,
+            scale: Vec3::one(),
+        };
+        let back_perch = Transform {
+            position: (chest_mat
+                * Mat4::<f32>::from(self.back)
+                * Vec4::from_point(Vec3::new(0.0, -3.0, 3.0)))
+            .homogenized()
+            .xyz(),
+            orientation: self.torso.orientation * self.chest.orientation,
+            scale: Vec3::one(),
+        };
 
         let weapon_trails = self.main_weapon_trail || self.off_weapon_trail;
         Offsets {
@@ -193,6 +221,10 @@ impl Skeleton for CharacterSkeleton {
                 orientation: mount_orientation,
                 scale: Vec3::one(),
             },
+            perches: Some(Perches {
+                head: head_perch,
+                back: back_perch,
+            }),
             primary_trail_mat: if weapon_trails {
                 self.main_weapon_trail
                     .then_some((main_mat, TrailSource::Weapon))
@@ -108,6 +108,7 @@ impl Skeleton for CrustaceanSkeleton {
                 orientation: mount_orientation,
                 scale: Vec3::one(),
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -79,6 +79,7 @@ impl Skeleton for DragonSkeleton {
                 position: comp::Body::Dragon(body).mount_offset().into_tuple().into(),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
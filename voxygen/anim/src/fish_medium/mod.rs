@@ -61,6 +61,7 @@ impl Skeleton for FishMediumSkeleton {
                     .into(),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
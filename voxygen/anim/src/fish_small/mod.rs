@@ -52,6 +52,7 @@ impl Skeleton for FishSmallSkeleton {
                     .into(),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -38,6 +38,7 @@ impl Skeleton for FixtureSkeleton {
             lantern: None,
             viewpoint: None,
             mount_bone: Transform::default(),
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
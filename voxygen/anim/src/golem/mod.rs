@@ -82,6 +82,7 @@ impl Skeleton for GolemSkeleton {
                 position: comp::Body::Golem(body).mount_offset().into_tuple().into(),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
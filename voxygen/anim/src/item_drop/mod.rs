@@ -44,6 +44,7 @@ impl Skeleton for ItemDropSkeleton {
                     .into(),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
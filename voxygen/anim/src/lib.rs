@@ -64,6 +64,7 @@ pub mod object;
 pub mod quadruped_low;
 pub mod quadruped_medium;
 pub mod quadruped_small;
+pub mod serpent;
 pub mod ship;
 pub mod theropod;
 pub mod util;
@@ -107,10 +108,20 @@ pub struct Offsets {
     pub lantern: Option<Vec3<f32>>,
     pub viewpoint: Option<Vec3<f32>>,
     pub mount_bone: Transform<f32, f32, f32>,
+    /// Other spots that small companions can perch on, besides the mount bone
+    pub perches: Option<Perches>,
     pub primary_trail_mat: Option<(Mat4<f32>, TrailSource)>,
     pub secondary_trail_mat: Option<(Mat4<f32>, TrailSource)>,
 }
 
+/// Where small companions riding on a skeleton perch when they don't perch on
+/// its mount bone
+#[derive(Clone, Copy)]
+pub struct Perches {
+    pub head: Transform<f32, f32, f32>,
+    pub back: Transform<f32, f32, f32>,
+}
+
 #[derive(Clone, Copy)]
 pub enum TrailSource {
     Weapon,
@@ -47,6 +47,7 @@ impl Skeleton for ObjectSkeleton {
                 position: comp::Body::Object(body).mount_offset().into_tuple().into(),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -106,6 +106,7 @@ impl Skeleton for QuadrupedLowSkeleton {
                 orientation: mount_orientation,
                 scale: Vec3::one(),
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -136,6 +136,7 @@ impl Skeleton for QuadrupedMediumSkeleton {
                 orientation: mount_orientation,
                 scale: Vec3::one(),
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -3,6 +3,7 @@ pub mod combomelee;
 pub mod feed;
 pub mod idle;
 pub mod jump;
+pub mod perch;
 pub mod run;
 pub mod shockwave;
 pub mod stunned;
@@ -10,7 +11,7 @@ pub mod stunned;
 // Reexports
 pub use self::{
     alpha::AlphaAnimation, combomelee::ComboAnimation, feed::FeedAnimation, idle::IdleAnimation,
-    jump::JumpAnimation, run::RunAnimation, shockwave::ShockwaveAnimation,
+    jump::JumpAnimation, perch::PerchAnimation, run::RunAnimation, shockwave::ShockwaveAnimation,
     stunned::StunnedAnimation,
 };
 
@@ -74,6 +75,7 @@ impl Skeleton for QuadrupedSmallSkeleton {
                 orientation: mount_orientation,
                 scale: Vec3::one(),
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
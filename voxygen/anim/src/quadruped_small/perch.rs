@@ -0,0 +1,67 @@
+use super::{
+    super::{vek::*, Animation},
+    QuadrupedSmallSkeleton, SkeletonAttr,
+};
+use std::ops::Mul;
+
+/// Curled up on top of whoever it's riding, watching the world go by
+pub struct PerchAnimation;
+
+impl Animation for PerchAnimation {
+    type Dependency<'a> = f32;
+    type Skeleton = QuadrupedSmallSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"quadruped_small_perch\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "quadruped_small_perch")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        global_time: Self::Dependency<'_>,
+        anim_time: f32,
+        _rate: &mut f32,
+        s_a: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        let breathe = (anim_time * 2.0).sin();
+
+        let head_look = Vec2::new(
+            (global_time / 3.0 + anim_time / 6.0)
+                .floor()
+                .mul(7331.0)
+                .sin()
+                * 0.6,
+            (global_time / 3.0 + anim_time / 6.0)
+                .floor()
+                .mul(1337.0)
+                .sin()
+                * 0.2,
+        );
+
+        next.head.position = Vec3::new(0.0, s_a.head.0, s_a.head.1 - 0.5);
+        next.head.orientation =
+            Quaternion::rotation_z(head_look.x) * Quaternion::rotation_x(head_look.y - 0.1);
+
+        // Lying low, so as not to fall off
+        next.chest.position = Vec3::new(0.0, s_a.chest.0, s_a.chest.1 - 1.5 + breathe * 0.1);
+        next.chest.orientation = Quaternion::rotation_y(breathe * 0.02);
+
+        // Legs tucked in under the body
+        next.leg_fl.position = Vec3::new(-s_a.feet_f.0, s_a.feet_f.1, s_a.feet_f.2 + 1.0);
+        next.leg_fl.orientation = Quaternion::rotation_x(-1.2);
+        next.leg_fr.position = Vec3::new(s_a.feet_f.0, s_a.feet_f.1, s_a.feet_f.2 + 1.0);
+        next.leg_fr.orientation = Quaternion::rotation_x(-1.2);
+        next.leg_bl.position = Vec3::new(-s_a.feet_b.0, s_a.feet_b.1, s_a.feet_b.2 + 1.0);
+        next.leg_bl.orientation = Quaternion::rotation_x(1.2);
+        next.leg_br.position = Vec3::new(s_a.feet_b.0, s_a.feet_b.1, s_a.feet_b.2 + 1.0);
+        next.leg_br.orientation = Quaternion::rotation_x(1.2);
+
+        // Tail curled around to one side, swishing now and then
+        next.tail.position = Vec3::new(0.0, s_a.tail.0, s_a.tail.1 - 0.5);
+        next.tail.orientation = Quaternion::rotation_z(0.8 + (anim_time * 0.7).sin().powi(9) * 0.4)
+            * Quaternion::rotation_x(-0.3);
+
+        next
+    }
+}
@@ -0,0 +1,59 @@
+use super::{
+    super::{vek::*, Animation},
+    SerpentSkeleton, SkeletonAttr,
+};
+use common::states::utils::StageSection;
+
+pub struct AlphaAnimation;
+
+impl Animation for AlphaAnimation {
+    type Dependency<'a> = Option<StageSection>;
+    type Skeleton = SerpentSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"serpent_alpha\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "serpent_alpha")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        stage_section: Self::Dependency<'_>,
+        anim_time: f32,
+        _rate: &mut f32,
+        s_a: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+
+        let (movement1, movement2, movement3) = match stage_section {
+            Some(StageSection::Buildup) => (anim_time.sqrt(), 0.0, 0.0),
+            Some(StageSection::Action) => (1.0, anim_time.powf(0.25), 0.0),
+            Some(StageSection::Recover) => (1.0, 1.0, anim_time.powi(2)),
+            _ => (0.0, 0.0, 0.0),
+        };
+        let pullback = 1.0 - movement3;
+        // Drawn back into an S-curve during the buildup, then thrown straight
+        let coil = movement1 * (1.0 - movement2) * pullback;
+        let lunge = movement2 * pullback;
+
+        next.chest.orientation = Quaternion::rotation_z(coil * 0.4);
+        next.spine_0.orientation = Quaternion::rotation_z(-coil * 0.9);
+        next.spine_1.orientation = Quaternion::rotation_z(coil * 0.9);
+        next.tail_f.orientation = Quaternion::rotation_z(-coil * 0.7);
+        next.tail_b.orientation = Quaternion::rotation_z(coil * 0.5);
+
+        next.neck.position = Vec3::new(
+            0.0,
+            s_a.neck.0 - coil * s_a.segment * 0.3 + lunge * s_a.segment * 0.5,
+            s_a.neck.1,
+        );
+        next.neck.orientation = Quaternion::rotation_x(s_a.rear + coil * 0.5 - lunge * 0.8)
+            * Quaternion::rotation_z(-coil * 0.4);
+        next.head.orientation = Quaternion::rotation_x(-s_a.rear - coil * 0.3 + lunge * 0.5)
+            * Quaternion::rotation_z(coil * 0.2);
+        // The jaw gapes wide on the way in and snaps shut on contact
+        next.jaw.orientation = Quaternion::rotation_x(
+            -(movement1 * 0.4 + movement2 * 0.5) * (1.0 - movement2.powi(4)) * pullback,
+        );
+
+        next
+    }
+}
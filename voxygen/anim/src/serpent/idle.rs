@@ -0,0 +1,47 @@
+use super::{
+    super::{vek::*, Animation},
+    SerpentSkeleton, SkeletonAttr,
+};
+
+pub struct IdleAnimation;
+
+impl Animation for IdleAnimation {
+    type Dependency<'a> = f32;
+    type Skeleton = SerpentSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"serpent_idle\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "serpent_idle")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        global_time: Self::Dependency<'_>,
+        anim_time: f32,
+        _rate: &mut f32,
+        s_a: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+        s_a.rest(&mut next);
+
+        let breathe = (anim_time * 0.8 * s_a.tempo).sin();
+        let sway = (anim_time * 0.35 * s_a.tempo + global_time * 0.05).sin();
+        // Short, sharp flicks of the jaw every few seconds
+        let flick = (((anim_time * 0.3).fract() * 2.0 - 1.0) * (anim_time * 9.0).sin()).powi(8);
+
+        // Coil the body into a loose spiral, tighter towards the tip of the tail
+        next.chest.orientation = Quaternion::rotation_z(0.2 + sway * 0.05);
+        next.spine_0.orientation = Quaternion::rotation_z(0.9 + breathe * 0.03);
+        next.spine_1.orientation = Quaternion::rotation_z(1.0 + breathe * 0.04);
+        next.tail_f.orientation = Quaternion::rotation_z(1.1 - breathe * 0.05);
+        next.tail_b.orientation = Quaternion::rotation_z(1.3 + sway * 0.2);
+
+        // Rise out of the coil and keep the head level while it sways
+        next.neck.orientation =
+            Quaternion::rotation_x(s_a.rear + breathe * 0.03) * Quaternion::rotation_z(sway * 0.25);
+        next.head.orientation = Quaternion::rotation_x(-s_a.rear - breathe * 0.03)
+            * Quaternion::rotation_z(-sway * 0.15);
+        next.jaw.orientation = Quaternion::rotation_x(-flick * 0.15);
+
+        next
+    }
+}
@@ -0,0 +1,148 @@
+mod alpha;
+mod idle;
+mod run;
+
+// Reexports
+pub use self::{alpha::AlphaAnimation, idle::IdleAnimation, run::RunAnimation};
+
+use common::comp::{self};
+
+use super::{make_bone, vek::*, FigureBoneData, Offsets, Skeleton};
+
+pub type Body = comp::serpent::Body;
+
+skeleton_impls!(struct SerpentSkeleton {
+    + head,
+    + jaw,
+    + neck,
+    + chest,
+    + spine_0,
+    + spine_1,
+    + tail_f,
+    + tail_b,
+});
+
+impl Skeleton for SerpentSkeleton {
+    type Attr = SkeletonAttr;
+    type Body = Body;
+
+    const BONE_COUNT: usize = 8;
+    #[cfg(feature = "use-dyn-lib")]
+    const COMPUTE_FN: &'static [u8] = b"serpent_compute_s\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "serpent_compute_s")]
+
+    fn compute_matrices_inner(
+        &self,
+        base_mat: Mat4<f32>,
+        buf: &mut [FigureBoneData; super::MAX_BONE_COUNT],
+        body: Self::Body,
+    ) -> Offsets {
+        let base_mat = base_mat * Mat4::scaling_3d(SkeletonAttr::from(&body).scaler / 11.0);
+
+        // The body is a single chain running out from the chest in both directions,
+        // so every segment inherits the bend of the segments before it.
+        let chest_mat = base_mat * Mat4::<f32>::from(self.chest);
+        let neck_mat = chest_mat * Mat4::<f32>::from(self.neck);
+        let head_mat = neck_mat * Mat4::<f32>::from(self.head);
+        let jaw_mat = head_mat * Mat4::<f32>::from(self.jaw);
+        let spine_0_mat = chest_mat * Mat4::<f32>::from(self.spine_0);
+        let spine_1_mat = spine_0_mat * Mat4::<f32>::from(self.spine_1);
+        let tail_f_mat = spine_1_mat * Mat4::<f32>::from(self.tail_f);
+        let tail_b_mat = tail_f_mat * Mat4::<f32>::from(self.tail_b);
+
+        *(<&mut [_; Self::BONE_COUNT]>::try_from(&mut buf[0..Self::BONE_COUNT]).unwrap()) = [
+            make_bone(head_mat),
+            make_bone(jaw_mat),
+            make_bone(neck_mat),
+            make_bone(chest_mat),
+            make_bone(spine_0_mat),
+            make_bone(spine_1_mat),
+            make_bone(tail_f_mat),
+            make_bone(tail_b_mat),
+        ];
+
+        let mount_position = (chest_mat * Vec4::from_point(mount_point(&body)))
+            .homogenized()
+            .xyz();
+
+        Offsets {
+            lantern: None,
+            viewpoint: Some((head_mat * Vec4::new(0.0, 3.0, 2.0, 1.0)).xyz()),
+            mount_bone: Transform {
+                position: mount_position,
+                orientation: self.chest.orientation,
+                scale: Vec3::one(),
+            },
+            perches: None,
+            primary_trail_mat: None,
+            secondary_trail_mat: None,
+        }
+    }
+}
+
+pub struct SkeletonAttr {
+    head: (f32, f32),
+    jaw: (f32, f32),
+    neck: (f32, f32),
+    chest: (f32, f32),
+    segment: f32,
+    /// How far the neck is raised off the ground when at rest
+    rear: f32,
+    scaler: f32,
+    tempo: f32,
+}
+
+impl From<&Body> for SkeletonAttr {
+    fn from(body: &Body) -> Self {
+        use comp::serpent::Species::*;
+        match body.species {
+            Viper => Self {
+                head: (6.0, 0.0),
+                jaw: (0.5, 0.0),
+                neck: (3.0, 0.0),
+                chest: (0.0, 0.0),
+                segment: 6.0,
+                rear: 0.6,
+                scaler: 0.6,
+                tempo: 1.2,
+            },
+            // The naga's neck bone is its upright torso, so the head sits on top of it
+            // rather than in front
+            Naga => Self {
+                head: (0.0, 12.0),
+                jaw: (2.0, 0.0),
+                neck: (2.0, 3.0),
+                chest: (0.0, 0.0),
+                segment: 8.0,
+                rear: 0.0,
+                scaler: 1.0,
+                tempo: 0.9,
+            },
+        }
+    }
+}
+
+impl SkeletonAttr {
+    /// Lays the spine out flat behind the chest, ready for an animation to
+    /// bend it.
+    fn rest(&self, next: &mut SerpentSkeleton) {
+        next.chest.position = Vec3::new(0.0, self.chest.0, self.chest.1);
+        next.neck.position = Vec3::new(0.0, self.neck.0, self.neck.1);
+        next.head.position = Vec3::new(0.0, self.head.0, self.head.1);
+        next.jaw.position = Vec3::new(0.0, self.jaw.0, self.jaw.1);
+        next.spine_0.position = Vec3::new(0.0, -self.segment * 0.5, 0.0);
+        next.spine_1.position = Vec3::new(0.0, -self.segment, 0.0);
+        next.tail_f.position = Vec3::new(0.0, -self.segment, 0.0);
+        next.tail_b.position = Vec3::new(0.0, -self.segment, 0.0);
+    }
+}
+
+fn mount_point(body: &Body) -> Vec3<f32> {
+    use comp::serpent::Species::*;
+    match body.species {
+        Viper => (0.0, 0.0, 3.0),
+        Naga => (0.0, 0.0, 6.0),
+    }
+    .into()
+}
@@ -0,0 +1,69 @@
+use super::{
+    super::{vek::*, Animation},
+    SerpentSkeleton, SkeletonAttr,
+};
+use std::f32::consts::PI;
+
+pub struct RunAnimation;
+
+impl Animation for RunAnimation {
+    type Dependency<'a> = (Vec3<f32>, Vec3<f32>, Vec3<f32>, f32, Vec3<f32>, f32);
+    type Skeleton = SerpentSkeleton;
+
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"serpent_run\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "serpent_run")]
+    fn update_skeleton_inner(
+        skeleton: &Self::Skeleton,
+        (velocity, orientation, last_ori, _global_time, avg_vel, acc_vel): Self::Dependency<'_>,
+        anim_time: f32,
+        rate: &mut f32,
+        s_a: &SkeletonAttr,
+    ) -> Self::Skeleton {
+        let mut next = (*skeleton).clone();
+        s_a.rest(&mut next);
+
+        let speed = Vec2::<f32>::from(velocity).magnitude().min(12.0);
+        *rate = 1.0;
+        let speednorm = speed / 12.0;
+
+        // The body is driven by a single wave travelling from head to tail, so that
+        // each segment follows the path of the one in front of it
+        let phase = (acc_vel * 0.9 + anim_time * 2.0) * s_a.tempo;
+        let amplitude = 0.35 + speednorm * 0.15;
+        let wave = |segment: f32| (phase - segment * PI * 0.45).sin() * amplitude;
+
+        let ori: Vec2<f32> = Vec2::from(orientation);
+        let last_ori = Vec2::from(last_ori);
+        let tilt = if vek::Vec2::new(ori, last_ori)
+            .map(|o| o.magnitude_squared())
+            .map(|m| m > 0.001 && m.is_finite())
+            .reduce_and()
+            && ori.angle_between(last_ori).is_finite()
+        {
+            ori.angle_between(last_ori).min(0.2)
+                * last_ori.determine_side(Vec2::zero(), ori).signum()
+        } else {
+            0.0
+        };
+        // Bend the whole body away from the turn, as the tail lags behind the head
+        let turn = tilt * 2.0;
+
+        let x_tilt = avg_vel.z.atan2(avg_vel.xy().magnitude()) * speednorm;
+
+        next.chest.orientation = Quaternion::rotation_x(x_tilt) * Quaternion::rotation_z(wave(1.0));
+        next.spine_0.orientation = Quaternion::rotation_z(wave(2.0) - wave(1.0) + turn);
+        next.spine_1.orientation = Quaternion::rotation_z(wave(3.0) - wave(2.0) + turn);
+        next.tail_f.orientation = Quaternion::rotation_z(wave(4.0) - wave(3.0) + turn);
+        next.tail_b.orientation = Quaternion::rotation_z(wave(5.0) - wave(4.0) + turn * 1.5);
+
+        // The neck rides the wave but the head holds its line to the target
+        next.neck.orientation =
+            Quaternion::rotation_x(s_a.rear * 0.5) * Quaternion::rotation_z(wave(0.0) - wave(1.0));
+        next.head.orientation =
+            Quaternion::rotation_x(-s_a.rear * 0.5) * Quaternion::rotation_z(-wave(0.0) - tilt);
+
+        next
+    }
+}
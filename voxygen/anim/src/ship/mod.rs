@@ -55,6 +55,7 @@ impl Skeleton for ShipSkeleton {
                     .mul_point(comp::Body::Ship(body).mount_offset().into_tuple().into()),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: attr
                 .bone1_prop_trail_offset
                 .map(|offset| (bone1_mat, TrailSource::Propeller(offset))),
@@ -117,6 +118,7 @@ impl<'a> From<&'a Body> for SkeletonAttr {
                 Submarine => (0.0, 0.0, 0.0),
                 Carriage => (0.0, 0.0, 0.0),
                 Cart => (0.0, 0.0, 0.0),
+                HotAirBalloon => (0.0, 0.0, 0.0),
                 Volume => (0.0, 0.0, 0.0),
             },
             bone1: match body {
@@ -128,6 +130,7 @@ impl<'a> From<&'a Body> for SkeletonAttr {
                 Submarine => (0.0, -15.0, 3.5),
                 Carriage => (0.0, 3.0, 2.0),
                 Cart => (0.0, 1.0, 1.0),
+                HotAirBalloon => (0.0, 0.0, 0.0),
                 Volume => (0.0, 0.0, 0.0),
             },
             bone2: match body {
@@ -139,6 +142,7 @@ impl<'a> From<&'a Body> for SkeletonAttr {
                 Submarine => (0.0, 0.0, 0.0),
                 Carriage => (0.0, -3.0, 2.0),
                 Cart => (0.0, -2.5, 1.0),
+                HotAirBalloon => (0.0, 0.0, 0.0),
                 Volume => (0.0, 0.0, 0.0),
             },
             bone3: match body {
@@ -150,6 +154,7 @@ impl<'a> From<&'a Body> for SkeletonAttr {
                 Submarine => (0.0, -18.0, 3.5),
                 Carriage => (0.0, 0.0, 0.0),
                 Cart => (0.0, 0.0, 0.0),
+                HotAirBalloon => (0.0, 0.0, 0.0),
                 Volume => (0.0, 0.0, 0.0),
             },
             bone1_ori: match body {
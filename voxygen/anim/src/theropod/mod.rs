@@ -84,6 +84,7 @@ impl Skeleton for TheropodSkeleton {
                     .into(),
                 ..Default::default()
             },
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -19,6 +19,7 @@ pub fn draw_char_state_group(
         CharacterState::ChargedMelee(data) => charged_melee_grid(ui, data),
         // Character states with no associated data to display
         CharacterState::Dance
+        | CharacterState::Emote(_)
         | CharacterState::Idle(_)
         | CharacterState::Sit
         | CharacterState::GlideWield(_)
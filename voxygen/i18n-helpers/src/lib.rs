@@ -198,6 +198,7 @@ pub fn localize_chat_message(
         | ChatType::CommandInfo
         | ChatType::Meta
         | ChatType::FactionMeta(_)
+        | ChatType::GuildMeta(_)
         | ChatType::GroupMeta(_) => localization.get_content(msg.content()),
         ChatType::Tell(from, to) => {
             // If `from` is you, it means you're writing to someone
@@ -222,9 +223,9 @@ pub fn localize_chat_message(
         ChatType::Say(uid) | ChatType::Region(uid) | ChatType::World(uid) => {
             message_format(uid, msg.content(), None)
         },
-        ChatType::Group(uid, descriptor) | ChatType::Faction(uid, descriptor) => {
-            message_format(uid, msg.content(), Some(descriptor))
-        },
+        ChatType::Group(uid, descriptor)
+        | ChatType::Faction(uid, descriptor)
+        | ChatType::Guild(uid, descriptor) => message_format(uid, msg.content(), Some(descriptor)),
         ChatType::Npc(uid) | ChatType::NpcSay(uid) => message_format(uid, msg.content(), None),
         ChatType::NpcTell(from, to) => {
             // If `from` is you, it means you're writing to someone
@@ -389,6 +390,9 @@ fn get_buff_ident(buff: BuffKind) -> &'static str {
         | BuffKind::Flame
         | BuffKind::Frigid
         | BuffKind::Lifesteal
+        | BuffKind::PoisonCoating
+        | BuffKind::FireCoating
+        | BuffKind::FrostCoating
         // | BuffKind::SalamanderAspect
         | BuffKind::ImminentCritical
         | BuffKind::Fury
@@ -412,7 +416,8 @@ fn get_buff_ident(buff: BuffKind) -> &'static str {
         | BuffKind::Rooted
         | BuffKind::Winded
         | BuffKind::Concussion
-        | BuffKind::Staggered => {
+        | BuffKind::Staggered
+        | BuffKind::Electrified => {
             tracing::error!("Player was killed by a debuff that doesn't do damage!");
             "mysterious"
         },
@@ -86,6 +86,7 @@ fn maps_basic_melee() {
                     range: 3.5,
                     angle: 15.0,
                     damage_effect: None,
+                    element: None,
                     attack_effect: None,
                     multi_target: None,
                     simultaneous_hits: 1,
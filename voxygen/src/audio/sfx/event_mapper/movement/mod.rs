@@ -1,9 +1,9 @@
 /// EventMapper::Movement watches the movement states of surrounding entities,
-/// and triggers sfx related to running, climbing and gliding, at a volume
-/// proportionate to the extity's size
+/// and triggers sfx related to running, landing, climbing and gliding, at a
+/// volume proportionate to the extity's size
 use super::EventMapper;
 use crate::{
-    audio::sfx::{SfxEvent, SfxTriggerItem, SfxTriggers, SFX_DIST_LIMIT_SQR},
+    audio::sfx::{block_material, SfxEvent, SfxTriggerItem, SfxTriggers, SFX_DIST_LIMIT_SQR},
     scene::{Camera, Terrain},
     AudioFrontend,
 };
@@ -20,6 +20,17 @@ use specs::{Entity as EcsEntity, Join, LendJoin, WorldExt};
 use std::time::{Duration, Instant};
 use vek::*;
 
+/// Footsteps are quiet, so they are only played for entities much closer than
+/// other sfx, which also keeps crowds from flooding the mixer.
+const FOOTSTEP_DIST_LIMIT_SQR: f32 = 2500.0;
+/// The most footsteps and landings that are started in a single tick. Entities
+/// that miss out keep counting their steps and get their turn later on.
+const MAX_FOOTSTEPS_PER_TICK: usize = 8;
+/// Water shallower than this is waded through, rather than swum in.
+const WADING_DEPTH: f32 = 0.8;
+/// Landings slower than this are too soft to be heard.
+const MIN_LANDING_SPEED: f32 = 6.0;
+
 #[derive(Clone)]
 struct PreviousEntityState {
     event: SfxEvent,
@@ -43,6 +54,9 @@ impl Default for PreviousEntityState {
 
 pub struct MovementEventMapper {
     event_history: HashMap<EcsEntity, PreviousEntityState>,
+    /// How fast airborne entities were last falling, so that landings can be
+    /// heard in proportion to how hard they hit the ground.
+    fall_speeds: HashMap<EcsEntity, f32>,
 }
 
 impl EventMapper for MovementEventMapper {
@@ -60,6 +74,7 @@ impl EventMapper for MovementEventMapper {
 
         let focus_off = camera.get_focus_pos().map(f32::trunc);
         let cam_pos = camera.dependents().cam_pos + focus_off;
+        let mut footsteps = 0;
 
         for (entity, pos, vel, body, scale, physics, character) in (
             &ecs.entities(),
@@ -82,6 +97,9 @@ impl EventMapper for MovementEventMapper {
                     Some(block) => block.kind(),
                     None => BlockKind::Air,
                 };
+                let is_footstep_audible = entity == player_entity
+                    || (pos.0.distance_squared(cam_pos) < FOOTSTEP_DIST_LIMIT_SQR
+                        && footsteps < MAX_FOOTSTEPS_PER_TICK);
 
                 let mapped_event = match body {
                     Body::Humanoid(_) => Self::map_movement_event(
@@ -91,32 +109,69 @@ impl EventMapper for MovementEventMapper {
                         vel.0,
                         underfoot_block_kind,
                     ),
-                    Body::QuadrupedMedium(_) | Body::QuadrupedSmall(_) | Body::QuadrupedLow(_) => {
+                    Body::QuadrupedMedium(_)
+                    | Body::QuadrupedSmall(_)
+                    | Body::QuadrupedLow(_)
+                    | Body::Arthropod(_)
+                    | Body::Crustacean(_) => {
                         Self::map_quadruped_movement_event(physics, vel.0, underfoot_block_kind)
                     },
-                    Body::BirdMedium(_) | Body::BirdLarge(_) | Body::BipedLarge(_) => {
+                    Body::BirdMedium(_)
+                    | Body::BirdLarge(_)
+                    | Body::BipedLarge(_)
+                    | Body::BipedSmall(_)
+                    | Body::Theropod(_)
+                    | Body::Golem(_) => {
                         Self::map_non_humanoid_movement_event(physics, vel.0, underfoot_block_kind)
                     },
                     _ => SfxEvent::Idle, // Ignore fish, etc...
                 };
+                let is_footstep = matches!(mapped_event, SfxEvent::Run(_) | SfxEvent::QuadRun(_));
 
-                // Check for SFX config entry for this movement
-                if Self::should_emit(internal_state, triggers.get_key_value(&mapped_event)) {
-                    let underwater = state
+                let underwater = || {
+                    state
                         .terrain()
                         .get(cam_pos.map(|e| e.floor() as i32))
                         .map(|b| b.is_liquid())
-                        .unwrap_or(false);
+                        .unwrap_or(false)
+                };
 
+                // Check for SFX config entry for this movement
+                if (!is_footstep || is_footstep_audible)
+                    && Self::should_emit(internal_state, triggers.get_key_value(&mapped_event))
+                {
                     let sfx_trigger_item = triggers.get_key_value(&mapped_event);
                     audio.emit_sfx(
                         sfx_trigger_item,
                         pos.0,
                         Some(Self::get_volume_for_body_type(body)),
-                        underwater,
+                        underwater(),
                     );
                     internal_state.time = Instant::now();
                     internal_state.steps_taken = 0.0;
+                    if is_footstep {
+                        footsteps += 1;
+                    }
+                }
+
+                if physics.on_ground.is_none() {
+                    self.fall_speeds.insert(entity, (-vel.0.z).max(0.0));
+                } else if let Some(fall_speed) = self.fall_speeds.remove(&entity) {
+                    if let Some(material) = Self::underfoot_material(physics, underfoot_block_kind)
+                        .filter(|_| is_footstep_audible && fall_speed > MIN_LANDING_SPEED)
+                    {
+                        let sfx_trigger_item = triggers.get_key_value(&SfxEvent::Land(material));
+                        audio.emit_sfx(
+                            sfx_trigger_item,
+                            pos.0,
+                            Some(
+                                Self::get_volume_for_body_type(body)
+                                    * (fall_speed / MIN_LANDING_SPEED).min(2.0),
+                            ),
+                            underwater(),
+                        );
+                        footsteps += 1;
+                    }
                 }
 
                 // update state to determine the next event. We only record the time (above) if
@@ -138,6 +193,7 @@ impl MovementEventMapper {
     pub fn new() -> Self {
         Self {
             event_history: HashMap::new(),
+            fall_speeds: HashMap::new(),
         }
     }
 
@@ -154,6 +210,30 @@ impl MovementEventMapper {
             now.duration_since(event.time) < Duration::from_secs(TRACKING_TIMEOUT)
                 || entity.id() == player.id()
         });
+        let event_history = &self.event_history;
+        self.fall_speeds
+            .retain(|entity, _| event_history.contains_key(entity));
+    }
+
+    /// Whether the entity is standing in water shallow enough to walk through
+    fn is_wading(physics_state: &PhysicsState) -> bool {
+        physics_state.on_ground.is_some()
+            && physics_state
+                .in_liquid()
+                .map_or(false, |depth| depth < WADING_DEPTH)
+    }
+
+    /// The material underfoot, taking water that is being waded through into
+    /// account
+    fn underfoot_material(
+        physics_state: &PhysicsState,
+        underfoot_block_kind: BlockKind,
+    ) -> Option<BlockKind> {
+        if Self::is_wading(physics_state) {
+            Some(BlockKind::Water)
+        } else {
+            block_material(underfoot_block_kind)
+        }
     }
 
     /// When specific entity movements are detected, the associated sound (if
@@ -195,9 +275,8 @@ impl MovementEventMapper {
         underfoot_block_kind: BlockKind,
     ) -> SfxEvent {
         // Match run / roll / swim state
-        if physics_state.in_liquid().is_some() && vel.magnitude() > 2.0
-            || !previous_state.in_water && physics_state.in_liquid().is_some()
-        {
+        let swimming = physics_state.in_liquid().is_some() && !Self::is_wading(physics_state);
+        if swimming && vel.magnitude() > 2.0 || !previous_state.in_water && swimming {
             return SfxEvent::Swim;
         } else if physics_state.on_ground.is_some() && vel.magnitude() > 0.1
             || !previous_state.on_ground && physics_state.on_ground.is_some()
@@ -207,18 +286,8 @@ impl MovementEventMapper {
             } else if character_state.is_stealthy() {
                 SfxEvent::Sneak
             } else {
-                match underfoot_block_kind {
-                    BlockKind::Snow | BlockKind::ArtSnow => SfxEvent::Run(BlockKind::Snow),
-                    BlockKind::Rock
-                    | BlockKind::WeakRock
-                    | BlockKind::GlowingRock
-                    | BlockKind::GlowingWeakRock
-                    | BlockKind::Ice => SfxEvent::Run(BlockKind::Rock),
-                    BlockKind::Earth => SfxEvent::Run(BlockKind::Earth),
-                    // BlockKind::Sand => SfxEvent::Run(BlockKind::Sand),
-                    BlockKind::Air => SfxEvent::Idle,
-                    _ => SfxEvent::Run(BlockKind::Grass),
-                }
+                Self::underfoot_material(physics_state, underfoot_block_kind)
+                    .map_or(SfxEvent::Idle, SfxEvent::Run)
             };
         }
 
@@ -236,21 +305,14 @@ impl MovementEventMapper {
         vel: Vec3<f32>,
         underfoot_block_kind: BlockKind,
     ) -> SfxEvent {
-        if physics_state.in_liquid().is_some() && vel.magnitude() > 2.0 {
+        if physics_state.in_liquid().is_some()
+            && !Self::is_wading(physics_state)
+            && vel.magnitude() > 2.0
+        {
             SfxEvent::Swim
         } else if physics_state.on_ground.is_some() && vel.magnitude() > 0.1 {
-            match underfoot_block_kind {
-                BlockKind::Snow | BlockKind::ArtSnow => SfxEvent::Run(BlockKind::Snow),
-                BlockKind::Rock
-                | BlockKind::WeakRock
-                | BlockKind::GlowingRock
-                | BlockKind::GlowingWeakRock
-                | BlockKind::Ice => SfxEvent::Run(BlockKind::Rock),
-                // BlockKind::Sand => SfxEvent::Run(BlockKind::Sand),
-                BlockKind::Earth => SfxEvent::Run(BlockKind::Earth),
-                BlockKind::Air => SfxEvent::Idle,
-                _ => SfxEvent::Run(BlockKind::Grass),
-            }
+            Self::underfoot_material(physics_state, underfoot_block_kind)
+                .map_or(SfxEvent::Idle, SfxEvent::Run)
         } else {
             SfxEvent::Idle
         }
@@ -262,21 +324,14 @@ impl MovementEventMapper {
         vel: Vec3<f32>,
         underfoot_block_kind: BlockKind,
     ) -> SfxEvent {
-        if physics_state.in_liquid().is_some() && vel.magnitude() > 2.0 {
+        if physics_state.in_liquid().is_some()
+            && !Self::is_wading(physics_state)
+            && vel.magnitude() > 2.0
+        {
             SfxEvent::Swim
         } else if physics_state.on_ground.is_some() && vel.magnitude() > 0.1 {
-            match underfoot_block_kind {
-                BlockKind::Snow | BlockKind::ArtSnow => SfxEvent::QuadRun(BlockKind::Snow),
-                BlockKind::Rock
-                | BlockKind::WeakRock
-                | BlockKind::GlowingRock
-                | BlockKind::GlowingWeakRock
-                | BlockKind::Ice => SfxEvent::QuadRun(BlockKind::Rock),
-                // BlockKind::Sand => SfxEvent::QuadRun(BlockKind::Sand),
-                BlockKind::Earth => SfxEvent::QuadRun(BlockKind::Earth),
-                BlockKind::Air => SfxEvent::Idle,
-                _ => SfxEvent::QuadRun(BlockKind::Grass),
-            }
+            Self::underfoot_material(physics_state, underfoot_block_kind)
+                .map_or(SfxEvent::Idle, SfxEvent::QuadRun)
         } else {
             SfxEvent::Idle
         }
@@ -293,6 +348,11 @@ impl MovementEventMapper {
             Body::BirdMedium(_) => 0.3,
             Body::BirdLarge(_) => 0.2,
             Body::BipedLarge(_) => 1.0,
+            Body::BipedSmall(_) => 0.5,
+            Body::Theropod(_) => 1.0,
+            Body::Golem(_) => 1.0,
+            Body::Arthropod(_) => 0.3,
+            Body::Crustacean(_) => 0.3,
             _ => 0.9,
         }
     }
@@ -96,6 +96,7 @@ use common::{
     outcome::Outcome,
     terrain::{BlockKind, SpriteKind, TerrainChunk},
     uid::Uid,
+    vol::ReadVol,
     DamageSource,
 };
 use common_state::State;
@@ -113,6 +114,49 @@ use vek::*;
 /// player.
 const SFX_DIST_LIMIT_SQR: f32 = 20000.0;
 
+/// Groups block kinds by the sound they make underfoot or when struck, each
+/// group being represented by one of its kinds. Blocks that make no sound at
+/// all give `None`.
+pub fn block_material(kind: BlockKind) -> Option<BlockKind> {
+    match kind {
+        BlockKind::Air => None,
+        BlockKind::Water => Some(BlockKind::Water),
+        BlockKind::Snow | BlockKind::ArtSnow => Some(BlockKind::Snow),
+        BlockKind::Rock
+        | BlockKind::WeakRock
+        | BlockKind::GlowingRock
+        | BlockKind::GlowingWeakRock
+        | BlockKind::Lava
+        | BlockKind::Ice => Some(BlockKind::Rock),
+        BlockKind::Earth | BlockKind::Sand => Some(BlockKind::Earth),
+        BlockKind::Wood => Some(BlockKind::Wood),
+        _ => Some(BlockKind::Grass),
+    }
+}
+
+/// Finds the material a projectile that came to rest at `pos` struck, looking
+/// at the block it ended up in and then at those around it.
+fn struck_material(client: &Client, pos: Vec3<f32>) -> Option<BlockKind> {
+    let terrain = client.state().terrain();
+    let pos = pos.map(|e| e.floor() as i32);
+    [
+        Vec3::zero(),
+        -Vec3::unit_z(),
+        Vec3::unit_x(),
+        -Vec3::unit_x(),
+        Vec3::unit_y(),
+        -Vec3::unit_y(),
+        Vec3::unit_z(),
+    ]
+    .into_iter()
+    .find_map(|offset| {
+        terrain
+            .get(pos + offset)
+            .ok()
+            .and_then(|block| block_material(block.kind()))
+    })
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Hash, Eq)]
 pub enum SfxEvent {
     Campfire,
@@ -131,6 +175,8 @@ pub enum SfxEvent {
     Swim,
     Run(BlockKind),
     QuadRun(BlockKind),
+    Land(BlockKind),
+    Impact(BlockKind),
     Roll,
     Sneak,
     Climb,
@@ -643,6 +689,12 @@ impl SfxMgr {
                     if target.is_none() {
                         let sfx_trigger_item = triggers.get_key_value(&SfxEvent::ArrowMiss);
                         audio.emit_sfx(sfx_trigger_item, *pos, Some(2.0), underwater);
+
+                        if let Some(material) = struck_material(client, *pos) {
+                            let sfx_trigger_item =
+                                triggers.get_key_value(&SfxEvent::Impact(material));
+                            audio.emit_sfx(sfx_trigger_item, *pos, Some(1.5), underwater);
+                        }
                     } else if *source == client.uid() {
                         let sfx_trigger_item = triggers.get_key_value(&SfxEvent::ArrowHit);
                         audio.emit_sfx(
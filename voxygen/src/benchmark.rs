@@ -0,0 +1,330 @@
+//! The built-in graphics benchmark, started with `--benchmark`.
+//!
+//! It starts a singleplayer server with the default world, spectates it and
+//! flies the camera along the same paths every time, so that the frame rates
+//! of different settings, machines and versions can be compared. The scenes
+//! are described in `voxygen.benchmark`.
+
+use crate::{
+    scene::{camera::Camera, CameraMode},
+    settings::GraphicsSettings,
+    telemetry::SystemInfo,
+};
+use chrono::{DateTime, Utc};
+use client::Client;
+use common::{
+    assets::{self, AssetExt},
+    terrain::TerrainChunkSize,
+    vol::RectVolSize,
+};
+use common_net::msg::world_msg::SiteKind;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    mem::discriminant,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::{info, warn};
+use vek::*;
+
+const SCENES_ASSET: &str = "voxygen.benchmark";
+/// Time given to the terrain around a scene to be meshed after it was loaded
+const MIN_WARMUP: f32 = 5.0;
+/// Scenes are started anyway if their terrain takes longer than this to load
+const MAX_WARMUP: f32 = 60.0;
+/// Time and weather are only changed once the server moved the spectator to
+/// the scene, since weather zones are placed around it.
+const SETUP_DELAY: f32 = 1.0;
+
+/// Where the path of a scene is placed in the world
+#[derive(Clone, Debug, Deserialize)]
+pub enum Anchor {
+    /// The site of this kind with the lowest id
+    Site(SiteKind),
+    /// A fixed position in the world
+    Pos(i32, i32),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Waypoint {
+    /// Offset from the anchor, with `z` relative to the ground there
+    pub pos: (f32, f32, f32),
+    /// Yaw and pitch of the camera in degrees, with positive pitch looking
+    /// down
+    pub look: (f32, f32),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BenchmarkScene {
+    pub name: String,
+    pub anchor: Anchor,
+    /// Time of day, as accepted by `/time`
+    pub time: String,
+    /// Weather around the anchor, as accepted by `/weather_zone`
+    pub weather: String,
+    /// Seconds it takes the camera to fly along the path
+    pub duration: f32,
+    pub path: Vec<Waypoint>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BenchmarkScenes {
+    pub scenes: Vec<BenchmarkScene>,
+}
+
+impl assets::Asset for BenchmarkScenes {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SceneResult {
+    pub name: String,
+    pub frames: usize,
+    pub average_fps: f32,
+    /// Frame rate of the slowest 1% of frames
+    pub low_1_percent_fps: f32,
+}
+
+impl SceneResult {
+    fn new(name: String, frame_times: &mut [f32]) -> Self {
+        frame_times.sort_unstable_by(|a, b| b.total_cmp(a));
+        let slowest = &frame_times[..frame_times.len().div_ceil(100)];
+        let fps = |frame_times: &[f32]| {
+            frame_times.len() as f32 / frame_times.iter().sum::<f32>().max(f32::EPSILON)
+        };
+        Self {
+            name,
+            frames: frame_times.len(),
+            average_fps: fps(frame_times),
+            low_1_percent_fps: fps(slowest),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct BenchmarkReport<'a> {
+    time: DateTime<Utc>,
+    system: SystemInfo,
+    graphics: &'a GraphicsSettings,
+    scenes: &'a [SceneResult],
+}
+
+enum Stage {
+    /// Waiting for the server to start
+    Starting,
+    /// Waiting for the terrain around the scene to load
+    Loading {
+        elapsed: f32,
+        /// Position of the anchor, with the height of the ground once it's
+        /// loaded
+        anchor: Option<Vec3<f32>>,
+        ground_loaded: bool,
+    },
+    Recording {
+        elapsed: f32,
+        anchor: Vec3<f32>,
+        frame_times: Vec<f32>,
+    },
+}
+
+pub struct Benchmark {
+    scenes: Vec<BenchmarkScene>,
+    /// Index of the current scene
+    scene: usize,
+    stage: Stage,
+    results: Vec<SceneResult>,
+}
+
+impl Benchmark {
+    pub fn new() -> Self {
+        Self {
+            scenes: BenchmarkScenes::load_expect_cloned(SCENES_ASSET).scenes,
+            scene: 0,
+            stage: Stage::Starting,
+            results: Vec::new(),
+        }
+    }
+
+    /// Whether the benchmark world was started already
+    pub fn is_started(&self) -> bool { !matches!(self.stage, Stage::Starting) }
+
+    pub fn start(&mut self) {
+        info!("Starting the benchmark with {} scenes", self.scenes.len());
+        self.stage = Self::loading();
+    }
+
+    fn loading() -> Stage {
+        Stage::Loading {
+            elapsed: 0.0,
+            anchor: None,
+            ground_loaded: false,
+        }
+    }
+
+    /// Moves the camera along the current scene and records the frame time.
+    /// Returns true once all scenes are done.
+    pub fn update(&mut self, client: &mut Client, camera: &mut Camera, dt: Duration) -> bool {
+        let Some(scene) = self.scenes.get(self.scene) else {
+            return true;
+        };
+        let dt = dt.as_secs_f32();
+        camera.set_mode(CameraMode::Freefly);
+
+        match &mut self.stage {
+            Stage::Starting => {},
+            Stage::Loading {
+                elapsed,
+                anchor,
+                ground_loaded,
+            } => {
+                let anchor = anchor.get_or_insert_with(|| {
+                    let anchor = anchor_pos(client, &scene.anchor).unwrap_or_else(|| {
+                        warn!(
+                            anchor = ?scene.anchor,
+                            "Benchmark anchor not found, using the world center"
+                        );
+                        (client.world_data().chunk_size().as_::<i32>()
+                            * TerrainChunkSize::RECT_SIZE.as_::<i32>())
+                            / 2
+                    });
+                    anchor.as_::<f32>().with_z(camera.get_focus_pos().z)
+                });
+                if !*ground_loaded && let Some(alt) = ground_alt(client, anchor.xy()) {
+                    anchor.z = alt;
+                    *ground_loaded = true;
+                }
+                let anchor = *anchor;
+
+                let was_set_up = *elapsed >= SETUP_DELAY;
+                *elapsed += dt;
+                if !was_set_up && *elapsed >= SETUP_DELAY {
+                    client.send_command("time".to_string(), vec![scene.time.clone()]);
+                    client.send_command("weather_zone".to_string(), vec![
+                        scene.weather.clone(),
+                        "1000".to_string(),
+                        (MAX_WARMUP + scene.duration).to_string(),
+                    ]);
+                }
+
+                place_camera(camera, anchor, scene, 0.0);
+
+                let view_distance = client.view_distance().unwrap_or(1) as f32
+                    * TerrainChunkSize::RECT_SIZE.x as f32;
+                let loaded = *ground_loaded && client.loaded_distance() >= view_distance * 0.9;
+                if (*elapsed >= MIN_WARMUP && loaded) || *elapsed >= MAX_WARMUP {
+                    info!(scene = %scene.name, "Recording benchmark scene");
+                    self.stage = Stage::Recording {
+                        elapsed: 0.0,
+                        anchor,
+                        frame_times: Vec::new(),
+                    };
+                }
+            },
+            Stage::Recording {
+                elapsed,
+                anchor,
+                frame_times,
+            } => {
+                *elapsed += dt;
+                frame_times.push(dt);
+                place_camera(camera, *anchor, scene, *elapsed / scene.duration);
+
+                if *elapsed >= scene.duration {
+                    let result = SceneResult::new(scene.name.clone(), frame_times);
+                    info!(?result, "Finished benchmark scene");
+                    self.results.push(result);
+                    self.scene += 1;
+                    self.stage = Self::loading();
+                }
+            },
+        }
+
+        self.scene >= self.scenes.len()
+    }
+
+    /// Prints the results, and writes them to the `results` directory.
+    pub fn report(&self, dir: &Path, system: SystemInfo, graphics: &GraphicsSettings) {
+        println!(
+            "{:<16} {:>8} {:>12} {:>12}",
+            "Scene", "Frames", "Average FPS", "1% low FPS"
+        );
+        for result in &self.results {
+            println!(
+                "{:<16} {:>8} {:>12.1} {:>12.1}",
+                result.name, result.frames, result.average_fps, result.low_1_percent_fps
+            );
+        }
+
+        let report = BenchmarkReport {
+            time: Utc::now(),
+            system,
+            graphics,
+            scenes: &self.results,
+        };
+        let dir = dir.join("results");
+        let path = dir.join(format!("{}.json", report.time.format("%Y-%m-%d_%H-%M-%S")));
+        let result = fs::create_dir_all(&dir)
+            .and_then(|()| serde_json::to_vec_pretty(&report).map_err(std::io::Error::from))
+            .and_then(|json| fs::write(&path, json));
+        match result {
+            Ok(()) => info!("Wrote benchmark results to {}", path.display()),
+            Err(error) => warn!(?error, ?path, "Couldn't write benchmark results"),
+        }
+    }
+}
+
+impl Default for Benchmark {
+    fn default() -> Self { Self::new() }
+}
+
+/// The directory the benchmark world and results are kept in
+pub fn benchmark_dir(userdata_dir: &Path) -> PathBuf { userdata_dir.join("benchmark") }
+
+fn anchor_pos(client: &Client, anchor: &Anchor) -> Option<Vec2<i32>> {
+    match anchor {
+        Anchor::Site(kind) => client
+            .sites()
+            .values()
+            .filter(|site| discriminant(&site.site.kind) == discriminant(kind))
+            .min_by_key(|site| site.site.id)
+            .map(|site| site.site.wpos),
+        Anchor::Pos(x, y) => Some(Vec2::new(*x, *y)),
+    }
+}
+
+/// Height of the ground at the given position, if its chunk is loaded
+fn ground_alt(client: &Client, wpos: Vec2<f32>) -> Option<f32> {
+    let terrain = client.state().terrain();
+    let chunk_key = terrain.pos_key(wpos.as_::<i32>().with_z(0));
+    terrain.get_key(chunk_key).map(|chunk| chunk.meta().alt())
+}
+
+/// Places the camera at the given progress (from 0 to 1) along the path
+fn place_camera(camera: &mut Camera, anchor: Vec3<f32>, scene: &BenchmarkScene, progress: f32) {
+    let Some(last) = scene.path.len().checked_sub(1) else {
+        return;
+    };
+    let along = progress.clamp(0.0, 1.0) * last as f32;
+    let from = &scene.path[(along.floor() as usize).min(last)];
+    let to = &scene.path[(along.ceil() as usize).min(last)];
+    let t = along.fract();
+
+    let pos = Vec3::<f32>::from(from.pos).lerp(Vec3::from(to.pos), t);
+    let look = Vec2::<f32>::from(from.look).lerp(Vec2::from(to.look), t);
+    camera.force_focus_pos(anchor + pos);
+    camera.set_orientation_instant(Vec3::new(look.x.to_radians(), look.y.to_radians(), 0.0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_scenes() {
+        let scenes = BenchmarkScenes::load_expect_cloned(SCENES_ASSET).scenes;
+        assert!(scenes.iter().all(|scene| !scene.path.is_empty()));
+    }
+}
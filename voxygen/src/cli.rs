@@ -20,6 +20,14 @@ pub struct Args {
     #[clap(short, long)]
     pub server: Option<String>,
 
+    /// Run the graphics benchmark with the current settings, then quit.
+    ///
+    /// The results are printed, and written to the `benchmark` directory in
+    /// the userdata directory.
+    #[cfg(feature = "singleplayer")]
+    #[clap(long)]
+    pub benchmark: bool,
+
     #[clap(subcommand)]
     pub command: Option<Commands>,
 }
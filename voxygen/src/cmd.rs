@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use crate::{
+    hud::map_annotation,
     render::ExperimentalShader,
     session::{settings_change::change_render_mode, SessionState},
     GlobalState,
@@ -8,18 +9,22 @@ use crate::{
 use client::Client;
 use common::{
     cmd::*,
-    comp::Admin,
+    comp::{
+        dialogue::{AskedPerson, PersonType, Subject},
+        Admin, Alignment,
+    },
     link::Is,
     mounting::{Mount, Rider, VolumeRider},
     parse_cmd_args,
     resources::PlayerEntity,
+    rtsim::Profession,
     uid::Uid,
     uuid::Uuid,
 };
 use common_net::sync::WorldSyncExt;
 use itertools::Itertools;
 use levenshtein::levenshtein;
-use specs::{Join, WorldExt};
+use specs::{Entity as EcsEntity, Join, WorldExt};
 use strum::{EnumIter, IntoEnumIterator};
 
 // Please keep this sorted alphabetically, same as with server commands :-)
@@ -27,8 +32,10 @@ use strum::{EnumIter, IntoEnumIterator};
 pub enum ClientChatCommand {
     ExperimentalShader,
     Help,
+    MapAnnotations,
     Mute,
     Unmute,
+    WhereIs,
 }
 
 impl ClientChatCommand {
@@ -53,6 +60,23 @@ impl ClientChatCommand {
                 "Display information about commands",
                 None,
             ),
+            ClientChatCommand::MapAnnotations => cmd(
+                vec![
+                    Enum(
+                        "action",
+                        ["clear", "export", "import", "share"]
+                            .iter()
+                            .map(|s| s.to_string())
+                            .collect(),
+                        Required,
+                    ),
+                    Any("file name", Optional),
+                ],
+                "Manage the annotations drawn on your map: clear them, export them to or import \
+                 them from a file in the map_annotations folder of your config directory, or \
+                 share them with your group.",
+                None,
+            ),
             ClientChatCommand::Mute => cmd(
                 vec![PlayerName(Required)],
                 "Mutes chat messages from a player.",
@@ -63,6 +87,12 @@ impl ClientChatCommand {
                 "Unmutes a player muted with the 'mute' command.",
                 None,
             ),
+            ClientChatCommand::WhereIs => cmd(
+                vec![Message(Required)],
+                "Asks the townsperson you're looking at where to find a resident, by name or by \
+                 profession (e.g. 'blacksmith').",
+                None,
+            ),
         }
     }
 
@@ -70,8 +100,10 @@ impl ClientChatCommand {
         match self {
             ClientChatCommand::ExperimentalShader => "experimental_shader",
             ClientChatCommand::Help => "help",
+            ClientChatCommand::MapAnnotations => "map_annotations",
             ClientChatCommand::Mute => "mute",
             ClientChatCommand::Unmute => "unmute",
+            ClientChatCommand::WhereIs => "whereis",
         }
     }
 
@@ -301,6 +333,7 @@ pub fn run_command(
 
     preproccess_command(session_state, &command, &mut args)?;
 
+    let target_entity = session_state.target_entity;
     let client = &mut session_state.client.borrow_mut();
 
     match command {
@@ -308,9 +341,13 @@ pub fn run_command(
             client.send_command(cmd.keyword().into(), args);
             Ok(None) // The server will provide a response when the command is run
         },
-        ChatCommandKind::Client(cmd) => {
-            Ok(Some(run_client_command(client, global_state, cmd, args)?))
-        },
+        ChatCommandKind::Client(cmd) => Ok(Some(run_client_command(
+            client,
+            global_state,
+            target_entity,
+            cmd,
+            args,
+        )?)),
     }
 }
 
@@ -346,14 +383,21 @@ fn invalid_command_message(client: &Client, user_entered_invalid_command: String
 fn run_client_command(
     client: &mut Client,
     global_state: &mut GlobalState,
+    target_entity: Option<EcsEntity>,
     command: ClientChatCommand,
     args: Vec<String>,
 ) -> Result<String, String> {
     let command = match command {
         ClientChatCommand::ExperimentalShader => handle_experimental_shader,
         ClientChatCommand::Help => handle_help,
+        // Sharing annotations sends messages, so this needs to modify the client
+        ClientChatCommand::MapAnnotations => {
+            return handle_map_annotations(client, global_state, args);
+        },
         ClientChatCommand::Mute => handle_mute,
         ClientChatCommand::Unmute => handle_unmute,
+        // Asks the NPC that is being looked at
+        ClientChatCommand::WhereIs => return handle_where_is(client, target_entity, args),
     };
 
     command(client, global_state, args)
@@ -396,6 +440,144 @@ fn handle_help(
     }
 }
 
+fn handle_map_annotations(
+    client: &mut Client,
+    global_state: &mut GlobalState,
+    args: Vec<String>,
+) -> Result<String, String> {
+    let server = client.server_info().name.clone();
+    let file_path = |name: Option<String>| {
+        let name = name.ok_or("You must specify a file name.")?;
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!(
+                "{name} is not a valid file name, only use letters, numbers, '_' and '-'."
+            ));
+        }
+        Ok(global_state
+            .config_dir
+            .join("map_annotations")
+            .join(format!("{name}.ron")))
+    };
+    let result = match parse_cmd_args!(args, String, String) {
+        (Some(action), _) if action == "clear" => {
+            let annotations = global_state.profile.map_annotations_mut(&server);
+            let count = annotations.len();
+            annotations.clear();
+            Ok(format!("Removed {count} map annotations."))
+        },
+        (Some(action), name) if action == "export" => {
+            let path = file_path(name)?;
+            let exported =
+                map_annotation::export(global_state.profile.get_map_annotations(&server))?;
+            std::fs::create_dir_all(path.parent().unwrap_or(&path))
+                .and_then(|_| std::fs::write(&path, exported))
+                .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+            return Ok(format!("Exported map annotations to {}.", path.display()));
+        },
+        (Some(action), name) if action == "import" => {
+            let path = file_path(name)?;
+            let exported = std::fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+            let imported = map_annotation::import(&exported)?;
+            let count = imported.len();
+            global_state
+                .profile
+                .map_annotations_mut(&server)
+                .extend(imported);
+            Ok(format!("Imported {count} map annotations."))
+        },
+        (Some(action), _) if action == "share" => {
+            if client.group_info().is_none() {
+                return Err("You must be in a group to share map annotations.".to_string());
+            }
+            let annotations = global_state.profile.get_map_annotations(&server);
+            for msg in annotations.iter().flat_map(map_annotation::encode_shared) {
+                client.send_command("group".to_string(), vec![msg]);
+            }
+            return Ok(format!(
+                "Shared {} map annotations with your group.",
+                annotations.len()
+            ));
+        },
+        _ => {
+            return Err(
+                "You must specify whether to clear, export, import or share map annotations."
+                    .to_string(),
+            );
+        },
+    };
+    global_state
+        .profile
+        .save_to_file_warn(&global_state.config_dir);
+    result
+}
+
+/// Professions of townspeople that can be asked about instead of their name
+const ASKABLE_PROFESSIONS: &[Profession] = &[
+    Profession::Farmer,
+    Profession::Hunter,
+    Profession::Merchant,
+    Profession::Guard,
+    Profession::Blacksmith,
+    Profession::Chef,
+    Profession::Alchemist,
+    Profession::Herbalist,
+    Profession::Captain,
+    Profession::Banker,
+    Profession::Trainer,
+    Profession::Woodcutter,
+    Profession::Miner,
+    Profession::Stonecutter,
+    Profession::Dockworker,
+    Profession::Crier,
+];
+
+fn handle_where_is(
+    client: &mut Client,
+    target_entity: Option<EcsEntity>,
+    args: Vec<String>,
+) -> Result<String, String> {
+    let query = args.join(" ");
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("You must specify who you are looking for.".to_string());
+    }
+    let npc = target_entity
+        .filter(|entity| {
+            matches!(
+                client.state().read_storage::<Alignment>().get(*entity),
+                Some(Alignment::Npc)
+            )
+        })
+        .ok_or("You must be looking at a townsperson to ask them.")?;
+
+    let profession = query
+        .strip_prefix("the ")
+        .or_else(|| query.strip_prefix("The "))
+        .unwrap_or(query);
+    let person_type = ASKABLE_PROFESSIONS
+        .iter()
+        .find(|p| <&str>::from(*p).eq_ignore_ascii_case(profession))
+        .map_or_else(
+            || PersonType::Villager {
+                name: query.to_string(),
+            },
+            |p| PersonType::Profession(p.clone()),
+        );
+    client.npc_interact(
+        npc,
+        Subject::Person(AskedPerson {
+            person_type,
+            origin: None,
+        }),
+    );
+    Ok(format!("You ask where to find {query}."))
+}
+
 fn handle_mute(
     client: &Client,
     global_state: &mut GlobalState,
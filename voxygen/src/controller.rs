@@ -136,6 +136,15 @@ impl From<&crate::settings::GamepadSettings> for ControllerSettings {
                 map.entry(settings.game_buttons.dance)
                     .or_default()
                     .push(GameInput::Dance);
+                map.entry(settings.game_buttons.emote_wheel)
+                    .or_default()
+                    .push(GameInput::EmoteWheel);
+                map.entry(settings.game_buttons.quick_action_wheel)
+                    .or_default()
+                    .push(GameInput::QuickActionWheel);
+                map.entry(settings.game_buttons.show_interactables)
+                    .or_default()
+                    .push(GameInput::ShowInteractables);
                 map.entry(settings.game_buttons.glide)
                     .or_default()
                     .push(GameInput::Glide);
@@ -371,6 +380,15 @@ impl From<&crate::settings::GamepadSettings> for ControllerSettings {
                 map.entry(settings.game_layer_buttons.dance)
                     .or_default()
                     .push(GameInput::Dance);
+                map.entry(settings.game_layer_buttons.emote_wheel)
+                    .or_default()
+                    .push(GameInput::EmoteWheel);
+                map.entry(settings.game_layer_buttons.quick_action_wheel)
+                    .or_default()
+                    .push(GameInput::QuickActionWheel);
+                map.entry(settings.game_layer_buttons.show_interactables)
+                    .or_default()
+                    .push(GameInput::ShowInteractables);
                 map.entry(settings.game_layer_buttons.glide)
                     .or_default()
                     .push(GameInput::Glide);
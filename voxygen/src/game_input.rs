@@ -61,6 +61,12 @@ pub enum GameInput {
     Sit,
     #[strum(serialize = "gameinput-dance")]
     Dance,
+    #[strum(serialize = "gameinput-emotewheel")]
+    EmoteWheel,
+    #[strum(serialize = "gameinput-quickactionwheel")]
+    QuickActionWheel,
+    #[strum(serialize = "gameinput-showinteractables")]
+    ShowInteractables,
     #[strum(serialize = "gameinput-greet")]
     Greet,
     #[strum(serialize = "gameinput-glide")]
@@ -83,6 +89,8 @@ pub enum GameInput {
     Mount,
     #[strum(serialize = "gameinput-stayfollow")]
     StayFollow,
+    #[strum(serialize = "gameinput-scout")]
+    Scout,
     #[strum(serialize = "gameinput-chat")]
     Chat,
     #[strum(serialize = "gameinput-command")]
@@ -95,8 +103,14 @@ pub enum GameInput {
     Bag,
     #[strum(serialize = "gameinput-trade")]
     Trade,
+    #[strum(serialize = "gameinput-ask_directions")]
+    AskDirections,
     #[strum(serialize = "gameinput-social")]
     Social,
+    #[strum(serialize = "gameinput-questlog")]
+    QuestLog,
+    #[strum(serialize = "gameinput-calendar")]
+    Calendar,
     #[strum(serialize = "gameinput-crafting")]
     Crafting,
     #[strum(serialize = "gameinput-spellbook")]
@@ -172,6 +186,8 @@ pub enum GameInput {
     MuteAmbience,
     #[strum(serialize = "gameinput-togglewalk")]
     ToggleWalk,
+    #[strum(serialize = "gameinput-cyclebuildpalette")]
+    CycleBuildPalette,
 }
 
 impl GameInput {
@@ -18,7 +18,10 @@ use crate::{
 use client::Client;
 use common::{
     assets::AssetExt,
-    combat::{combat_rating, perception_dist_multiplier_from_stealth, Damage},
+    combat::{
+        combat_rating, compute_element_resistances, perception_dist_multiplier_from_stealth,
+        Damage, DamageElement,
+    },
     comp::{
         inventory::{slot::Slot, InventorySortOrder},
         item::{ItemDef, ItemDesc, ItemI18n, MaterialStatManifest, Quality},
@@ -28,10 +31,11 @@ use common::{
 use conrod_core::{
     color,
     widget::{self, Button, Image, Rectangle, Scrollbar, State as ConrodState, Text},
-    widget_ids, Color, Colorable, Positionable, Sizeable, UiCell, Widget, WidgetCommon,
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, UiCell, Widget, WidgetCommon,
 };
 use i18n::Localization;
 use std::borrow::Cow;
+use strum::IntoEnumIterator;
 
 use crate::hud::slots::SlotKind;
 use specs::Entity as EcsEntity;
@@ -636,6 +640,7 @@ widget_ids! {
         feet_slot,
         back_slot,
         tabard_slot,
+        heraldry_btn,
         glider_slot,
         active_mainhand_slot,
         active_offhand_slot,
@@ -750,6 +755,7 @@ pub enum Event {
     SortInventory,
     SwapEquippedWeapons,
     SetDetailsMode(bool),
+    OpenHeraldry,
 }
 
 impl<'a> Widget for Bag<'a> {
@@ -1050,6 +1056,29 @@ impl<'a> Widget for Bag<'a> {
             )
             .min(999.9);
             let indicator_col = cr_color(combat_rating);
+            let element_resistances =
+                compute_element_resistances(Some(inventory), Some(self.stats), self.msm);
+            let protection_desc = DamageElement::iter()
+                .filter_map(|element| {
+                    let key = match element {
+                        DamageElement::Physical => return None,
+                        DamageElement::Fire => "hud-bag-element-fire",
+                        DamageElement::Frost => "hud-bag-element-frost",
+                        DamageElement::Shock => "hud-bag-element-shock",
+                        DamageElement::Poison => "hud-bag-element-poison",
+                    };
+                    let resistance = (100.0 * element_resistances.get(element)).round() as i32;
+                    (resistance != 0).then(|| {
+                        i18n.get_msg_ctx("hud-bag-element_resistance", &i18n::fluent_args! {
+                            "element" => i18n.get_msg(key),
+                            "resistance" => resistance,
+                        })
+                    })
+                })
+                .fold(
+                    i18n.get_msg("hud-bag-protection_desc").into_owned(),
+                    |desc, line| format!("{desc}\n{line}"),
+                );
             for i in STATS.iter().copied().enumerate() {
                 let btn = Button::image(match i.1 {
                     "Health" => self.imgs.health_ico,
@@ -1115,7 +1144,7 @@ impl<'a> Widget for Bag<'a> {
                 };
                 let tooltip_txt = match i.1 {
                     "Combat Rating" => i18n.get_msg("hud-bag-combat_rating_desc"),
-                    "Protection" => i18n.get_msg("hud-bag-protection_desc"),
+                    "Protection" => Cow::Borrowed(protection_desc.as_str()),
                     "Stun Resilience" => i18n.get_msg("hud-bag-stun_res_desc"),
                     _ => Cow::Borrowed(""),
                 };
@@ -1299,6 +1328,22 @@ impl<'a> Widget for Bag<'a> {
             let slot_id = state.ids.tabard_slot;
             set_tooltip!(slot, slot_id, item_slot, "hud-bag-tabard");
 
+            // Heraldry designer
+            if Button::image(self.imgs.button)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .w_h(45.0, 18.0)
+                .down_from(state.ids.tabard_slot, 5.0)
+                .label(&i18n.get_msg("hud-bag-heraldry"))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_font_size(self.fonts.cyri.scale(10))
+                .label_color(TEXT_COLOR)
+                .set(state.ids.heraldry_btn, ui)
+                .was_clicked()
+            {
+                event = Some(Event::OpenHeraldry);
+            }
+
             // Active Mainhand/Left-Slot
             let item_slot = EquipSlot::ActiveMainhand;
             let slot = slot_maker
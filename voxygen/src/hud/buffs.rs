@@ -16,8 +16,8 @@ use common::{
 use conrod_core::{
     color,
     image::Id,
-    widget::{self, Button, Image, Rectangle, Text},
-    widget_ids, Color, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+    widget::{self, primitive::line::Style as LineStyle, Button, Image, Rectangle, Text},
+    widget_ids, Color, Colorable, Positionable, Sizeable, UiCell, Widget, WidgetCommon,
 };
 
 widget_ids! {
@@ -34,6 +34,16 @@ widget_ids! {
         buff_txts[],
         buff_multiplicities[],
         debuff_multiplicities[],
+        buff_durations[],
+        debuff_durations[],
+        buff_warnings[],
+        debuff_warnings[],
+        pinned_align,
+        pinned[],
+        pinned_timers[],
+        pinned_txts[],
+        pinned_multiplicities[],
+        pinned_warnings[],
     }
 }
 
@@ -95,10 +105,12 @@ pub struct State {
 pub enum Event {
     RemoveBuff(BuffKind),
     LeaveStance,
+    TogglePin(BuffKind),
 }
 
 const MULTIPLICITY_COLOR: Color = TEXT_COLOR;
 const MULTIPLICITY_FONT_SIZE: u32 = 20;
+const PINNED_SIZE: f64 = 56.0;
 
 impl<'a> Widget for BuffsBar<'a> {
     type Event = Vec<Event>;
@@ -139,7 +151,15 @@ impl<'a> Widget for BuffsBar<'a> {
         .desc_font_size(self.fonts.cyri.scale(12))
         .font_id(self.fonts.cyri.conrod_id)
         .desc_text_color(TEXT_COLOR);
-        let buff_icons = BuffIcon::icons_vec(self.buffs, self.stance);
+        // Pinned buffs are shown apart from the others
+        let pinned_kinds = &self.global_state.settings.interface.pinned_buffs;
+        let (pinned_icons, buff_icons): (Vec<_>, Vec<_>) =
+            BuffIcon::icons_vec(self.buffs, self.stance)
+                .into_iter()
+                .partition(|icon| {
+                    icon.buff_kind()
+                        .map_or(false, |kind| pinned_kinds.contains(&kind))
+                });
         if let BuffPosition::Bar = buff_position {
             let decayed_health = 1.0 - self.health.maximum() / self.health.base_max();
             let show_health = self.global_state.settings.interface.always_show_bars
@@ -205,6 +225,18 @@ impl<'a> Widget for BuffsBar<'a> {
                         .resize(2 * debuff_count, gen)
                 });
             };
+            if state.ids.buff_durations.len() < buff_count {
+                state.update(|state| {
+                    state.ids.buff_durations.resize(buff_count, gen);
+                    state.ids.buff_warnings.resize(buff_count, gen);
+                });
+            };
+            if state.ids.debuff_durations.len() < debuff_count {
+                state.update(|state| {
+                    state.ids.debuff_durations.resize(debuff_count, gen);
+                    state.ids.debuff_warnings.resize(debuff_count, gen);
+                });
+            };
 
             // Create Buff Widgets
             let mut buff_vec = state
@@ -262,12 +294,20 @@ impl<'a> Widget for BuffsBar<'a> {
                             .color(MULTIPLICITY_COLOR)
                             .set(mult_id[1], ui);
                     }
+                    self.duration_text(buff, *id, state.ids.buff_durations[i], ui);
+                    self.warning(buff, *id, state.ids.buff_warnings[i], buff_ani, ui);
                     // Create Buff tooltip
                     let (title, desc_txt) = buff.kind.title_description(localized_strings);
                     let remaining_time = buff.get_buff_time(*self.time);
                     let click_to_remove =
                         format!("<{}>", &localized_strings.get_msg("buff-remove"));
-                    let desc = format!("{}\n\n{}\n\n{}", desc_txt, remaining_time, click_to_remove);
+                    let desc = format!(
+                        "{}\n\n{}\n\n{}{}",
+                        desc_txt,
+                        remaining_time,
+                        click_to_remove,
+                        self.pin_hint(buff, false)
+                    );
                     // Timer overlay
                     if Button::image(self.get_duration_image(duration_percentage))
                         .w_h(40.0, 40.0)
@@ -287,6 +327,7 @@ impl<'a> Widget for BuffsBar<'a> {
                             BuffIconKind::Stance(_) => event.push(Event::LeaveStance),
                         }
                     };
+                    event.extend(Self::pin_toggle(buff, *timer_id, ui));
                 });
 
             // Create Debuff Widgets
@@ -344,10 +385,21 @@ impl<'a> Widget for BuffsBar<'a> {
                             .color(MULTIPLICITY_COLOR)
                             .set(mult_id[1], ui);
                     }
+                    self.duration_text(debuff, *id, state.ids.debuff_durations[i], ui);
+                    self.warning(debuff, *id, state.ids.debuff_warnings[i], buff_ani, ui);
                     // Create Debuff tooltip
                     let (title, desc_txt) = debuff.kind.title_description(localized_strings);
                     let remaining_time = debuff.get_buff_time(*self.time);
-                    let desc = format!("{}\n\n{}", desc_txt, remaining_time);
+                    let mut desc = if let Some(damage) = debuff.get_remaining_damage(*self.time) {
+                        let remaining_damage = localized_strings.get_msg_ctx(
+                            "buff-text-remaining_damage",
+                            &i18n::fluent_args! { "damage" => format!("{:.0}", damage) },
+                        );
+                        format!("{}\n\n{}\n{}", desc_txt, remaining_damage, remaining_time)
+                    } else {
+                        format!("{}\n\n{}", desc_txt, remaining_time)
+                    };
+                    desc.push_str(&self.pin_hint(debuff, false));
                     Image::new(self.get_duration_image(duration_percentage))
                         .w_h(40.0, 40.0)
                         .middle_of(*id)
@@ -359,6 +411,7 @@ impl<'a> Widget for BuffsBar<'a> {
                             DEBUFF_COLOR,
                         )
                         .set(*timer_id, ui);
+                    event.extend(Self::pin_toggle(debuff, *timer_id, ui));
                 });
         }
 
@@ -386,6 +439,9 @@ impl<'a> Widget for BuffsBar<'a> {
             if state.ids.buff_multiplicities.len() < 2 * buff_count {
                 state.update(|state| state.ids.buff_multiplicities.resize(2 * buff_count, gen));
             };
+            if state.ids.buff_warnings.len() < buff_count {
+                state.update(|state| state.ids.buff_warnings.resize(buff_count, gen));
+            };
 
             // Create Buff Widgets
 
@@ -442,16 +498,24 @@ impl<'a> Widget for BuffsBar<'a> {
                             .color(MULTIPLICITY_COLOR)
                             .set(mult_id[1], ui);
                     }
+                    self.warning(buff, *id, state.ids.buff_warnings[i], buff_ani, ui);
                     // Create Buff tooltip
                     let (title, desc_txt) = buff.kind.title_description(localized_strings);
                     let remaining_time = buff.get_buff_time(*self.time);
                     let click_to_remove =
                         format!("<{}>", &localized_strings.get_msg("buff-remove"));
-                    let desc = if buff.is_buff {
+                    let mut desc = if buff.is_buff {
                         format!("{}\n\n{}", desc_txt, click_to_remove)
+                    } else if let Some(damage) = buff.get_remaining_damage(*self.time) {
+                        let remaining_damage = localized_strings.get_msg_ctx(
+                            "buff-text-remaining_damage",
+                            &i18n::fluent_args! { "damage" => format!("{:.0}", damage) },
+                        );
+                        format!("{}\n\n{}", desc_txt, remaining_damage)
                     } else {
                         desc_txt.to_string()
                     };
+                    desc.push_str(&self.pin_hint(buff, false));
                     // Timer overlay
                     if Button::image(self.get_duration_image(duration_percentage))
                         .w_h(40.0, 40.0)
@@ -475,6 +539,7 @@ impl<'a> Widget for BuffsBar<'a> {
                             BuffIconKind::Stance(_) => event.push(Event::LeaveStance),
                         }
                     }
+                    event.extend(Self::pin_toggle(buff, *timer_id, ui));
                     Text::new(&remaining_time)
                         .down_from(*timer_id, 1.0)
                         .font_size(self.fonts.cyri.scale(10))
@@ -485,11 +550,156 @@ impl<'a> Widget for BuffsBar<'a> {
                 },
             );
         }
+
+        // Pinned buffs, shown larger above the buff bar or below the buffs next to
+        // the minimap
+        let pinned_count = pinned_icons.len();
+        let gen = &mut ui.widget_id_generator();
+        if state.ids.pinned.len() < pinned_count {
+            state.update(|state| {
+                state.ids.pinned.resize(pinned_count, gen);
+                state.ids.pinned_timers.resize(pinned_count, gen);
+                state.ids.pinned_txts.resize(pinned_count, gen);
+                state.ids.pinned_warnings.resize(pinned_count, gen);
+                state
+                    .ids
+                    .pinned_multiplicities
+                    .resize(2 * pinned_count, gen);
+            });
+        };
+        let pinned_align = Rectangle::fill_with([484.0, PINNED_SIZE + 14.0], color::TRANSPARENT);
+        match buff_position {
+            BuffPosition::Bar => pinned_align.up_from(state.ids.align, 4.0),
+            BuffPosition::Map => pinned_align.down_from(state.ids.align, 4.0),
+        }
+        .set(state.ids.pinned_align, ui);
+        for (i, buff) in pinned_icons.iter().enumerate() {
+            let id = state.ids.pinned[i];
+            let timer_id = state.ids.pinned_timers[i];
+            let max_duration = buff.kind.max_duration();
+            let current_duration = buff.end_time.map(|end| end - self.time.0);
+            let duration_percentage = current_duration.map_or(1000.0, |cur| {
+                max_duration.map_or(1000.0, |max| cur / max.0 * 1000.0)
+            }) as u32;
+            // Centered in a single row
+            let x = (i as f64 - (pinned_count as f64 - 1.0) / 2.0) * (PINNED_SIZE + 4.0);
+            Image::new(buff.kind.image(self.imgs))
+                .w_h(PINNED_SIZE, PINNED_SIZE)
+                .x_y_relative_to(state.ids.pinned_align, x, 7.0)
+                .color(if current_duration.map_or(false, |cur| cur < 10.0) {
+                    Some(pulsating_col)
+                } else {
+                    Some(norm_col)
+                })
+                .set(id, ui);
+            if buff.multiplicity() > 1 {
+                let mult_id = &state.ids.pinned_multiplicities[2 * i..2 * i + 2];
+                Rectangle::fill_with([0.0, 0.0], MULTIPLICITY_COLOR.plain_contrast())
+                    .bottom_right_with_margins_on(id, 1.0, 1.0)
+                    .wh_of(mult_id[1])
+                    .graphics_for(id)
+                    .set(mult_id[0], ui);
+                Text::new(&format!("{}", buff.multiplicity()))
+                    .middle_of(mult_id[0])
+                    .graphics_for(id)
+                    .font_size(self.fonts.cyri.scale(MULTIPLICITY_FONT_SIZE))
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .color(MULTIPLICITY_COLOR)
+                    .set(mult_id[1], ui);
+            }
+            self.warning(buff, id, state.ids.pinned_warnings[i], buff_ani, ui);
+            let (title, desc) = buff.kind.title_description(localized_strings);
+            let mut desc = desc.into_owned();
+            if let Some(damage) = buff.get_remaining_damage(*self.time) {
+                desc += "\n\n";
+                desc += &localized_strings.get_msg_ctx(
+                    "buff-text-remaining_damage",
+                    &i18n::fluent_args! { "damage" => format!("{:.0}", damage) },
+                );
+            }
+            if buff.is_buff {
+                desc += &format!("\n\n<{}>", &localized_strings.get_msg("buff-remove"));
+            }
+            desc += &self.pin_hint(buff, true);
+            if Button::image(self.get_duration_image(duration_percentage))
+                .w_h(PINNED_SIZE, PINNED_SIZE)
+                .middle_of(id)
+                .with_tooltip(
+                    self.tooltip_manager,
+                    &title,
+                    &desc,
+                    &buffs_tooltip,
+                    if buff.is_buff {
+                        BUFF_COLOR
+                    } else {
+                        DEBUFF_COLOR
+                    },
+                )
+                .set(timer_id, ui)
+                .was_clicked()
+                && buff.is_buff
+                && let Some(kind) = buff.buff_kind()
+            {
+                event.push(Event::RemoveBuff(kind));
+            }
+            event.extend(Self::pin_toggle(buff, timer_id, ui));
+            Text::new(&buff.get_buff_time(*self.time))
+                .down_from(timer_id, 1.0)
+                .font_size(self.fonts.cyri.scale(12))
+                .font_id(self.fonts.cyri.conrod_id)
+                .graphics_for(timer_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.pinned_txts[i], ui);
+        }
+
         event
     }
 }
 
 impl<'a> BuffsBar<'a> {
+    /// Remaining seconds, shown on the icon
+    fn duration_text(&self, buff: &BuffIcon, id: Id, txt_id: Id, ui: &mut UiCell) {
+        if let Some(end) = buff.end_time {
+            Text::new(&format!("{:.0}", (end - self.time.0).max(0.0)))
+                .top_left_with_margins_on(id, 1.0, 2.0)
+                .font_size(self.fonts.cyri.scale(12))
+                .font_id(self.fonts.cyri.conrod_id)
+                .graphics_for(id)
+                .color(TEXT_COLOR)
+                .set(txt_id, ui);
+        }
+    }
+
+    /// A flashing frame around dangerous debuffs
+    fn warning(&self, buff: &BuffIcon, id: Id, warning_id: Id, flash: f32, ui: &mut UiCell) {
+        if buff.is_dangerous(*self.time, self.health) {
+            Rectangle::outline_styled([0.0, 0.0], LineStyle::new().thickness(3.0))
+                .wh_of(id)
+                .middle_of(id)
+                .color(Color::Rgba(1.0, 0.1, 0.1, flash))
+                .graphics_for(id)
+                .set(warning_id, ui);
+        }
+    }
+
+    fn pin_hint(&self, buff: &BuffIcon, pinned: bool) -> String {
+        if buff.buff_kind().is_none() {
+            return String::new();
+        }
+        let key = if pinned { "buff-unpin" } else { "buff-pin" };
+        format!("\n<{}>", self.localized_strings.get_msg(key))
+    }
+
+    /// Right-clicking a buff pins or unpins it
+    fn pin_toggle(buff: &BuffIcon, id: Id, ui: &mut UiCell) -> Option<Event> {
+        let kind = buff.buff_kind()?;
+        ui.widget_input(id)
+            .clicks()
+            .right()
+            .next()
+            .map(|_| Event::TogglePin(kind))
+    }
+
     fn get_duration_image(&self, duration_percentage: u32) -> Id {
         match duration_percentage as u64 {
             875..=1000 => self.imgs.nothing, // 8/8
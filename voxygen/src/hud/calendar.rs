@@ -0,0 +1,246 @@
+use client::Client;
+use common::{
+    calendar::{CalendarEntry, ScheduledEventKind},
+    resources::Time,
+};
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Scrollbar, Text},
+    widget_ids, Color, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+
+use crate::ui::fonts::Fonts;
+
+use super::{img_ids::Imgs, TEXT_COLOR, TEXT_COLOR_GREY, TEXT_VELORITE, UI_HIGHLIGHT_0, UI_MAIN};
+
+pub struct State {
+    ids: Ids,
+}
+
+widget_ids! {
+    pub struct Ids {
+        bg,
+        frame,
+        icon,
+        close,
+        title_align,
+        title,
+        content_align,
+        scrollbar,
+        empty_txt,
+        name_txts[],
+        info_txts[],
+    }
+}
+
+#[derive(WidgetCommon)]
+pub struct Calendar<'a> {
+    client: &'a Client,
+    time: &'a Time,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Calendar<'a> {
+    pub fn new(
+        client: &'a Client,
+        time: &'a Time,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+    ) -> Self {
+        Self {
+            client,
+            time,
+            imgs,
+            fonts,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+
+    /// What the event is about, and how long until it starts or ends
+    fn info_text(&self, entry: &CalendarEntry) -> String {
+        let i18n = self.localized_strings;
+        let kind = match &entry.kind {
+            ScheduledEventKind::WorldBoss { .. } => i18n.get_msg("hud-calendar-world_boss"),
+            ScheduledEventKind::BonusExperience { multiplier } => {
+                i18n.get_msg_ctx("hud-calendar-bonus_exp", &i18n::fluent_args! {
+                    "multiplier" => *multiplier,
+                })
+            },
+            ScheduledEventKind::MarketDay { site } => {
+                i18n.get_msg_ctx("hud-calendar-market_day", &i18n::fluent_args! {
+                    "site" => site.as_str(),
+                })
+            },
+            ScheduledEventKind::Festival { site } => {
+                i18n.get_msg_ctx("hud-calendar-festival", &i18n::fluent_args! {
+                    "site" => site.as_str(),
+                })
+            },
+        };
+        let (key, until) = if entry.is_active(*self.time) {
+            ("hud-calendar-ends_in", entry.ends_at.0 - self.time.0)
+        } else {
+            ("hud-calendar-starts_in", entry.starts_at.0 - self.time.0)
+        };
+        let countdown = i18n.get_msg_ctx(key, &i18n::fluent_args! {
+            "time" => self.countdown(until),
+        });
+        format!("{kind}\n{countdown}")
+    }
+
+    fn countdown(&self, secs: f64) -> String {
+        let minutes = (secs.max(0.0) / 60.0).ceil() as u64;
+        let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+        let (key, args) = if days > 0 {
+            ("hud-calendar-days", i18n::fluent_args! {
+                "days" => days,
+                "hours" => hours,
+            })
+        } else if hours > 0 {
+            ("hud-calendar-hours", i18n::fluent_args! {
+                "hours" => hours,
+                "minutes" => minutes,
+            })
+        } else {
+            ("hud-calendar-minutes", i18n::fluent_args! {
+                "minutes" => minutes,
+            })
+        };
+        self.localized_strings.get_msg_ctx(key, &args).into_owned()
+    }
+}
+
+pub enum Event {
+    Close,
+}
+
+impl<'a> Widget for Calendar<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        Self::State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Calendar::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut event = None;
+
+        // Window BG, next to the quest log
+        Image::new(self.imgs.quest_bg)
+            .bottom_left_with_margins_on(ui.window, 308.0, 215.0)
+            .color(Some(UI_MAIN))
+            .w_h(280.0, 460.0)
+            .set(state.ids.bg, ui);
+        // Window frame
+        Image::new(self.imgs.quest_frame)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .w_h(280.0, 460.0)
+            .set(state.ids.frame, ui);
+
+        // Icon
+        Image::new(self.imgs.quest_ico)
+            .w_h(30.0, 30.0)
+            .top_left_with_margins_on(state.ids.frame, 6.0, 6.0)
+            .set(state.ids.icon, ui);
+        // X-Button
+        if Button::image(self.imgs.close_button)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.ids.frame, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        // Title
+        Rectangle::fill_with([212.0, 42.0], color::TRANSPARENT)
+            .top_left_with_margins_on(state.ids.frame, 2.0, 44.0)
+            .set(state.ids.title_align, ui);
+        Text::new(&self.localized_strings.get_msg("hud-calendar"))
+            .middle_of(state.ids.title_align)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // Content Alignment
+        Rectangle::fill_with([270.0, 395.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.ids.frame, 55.0)
+            .scroll_kids_vertically()
+            .set(state.ids.content_align, ui);
+        Scrollbar::y_axis(state.ids.content_align)
+            .thickness(4.0)
+            .color(Color::Rgba(0.79, 1.09, 1.09, 0.0))
+            .set(state.ids.scrollbar, ui);
+
+        let entries = self.client.event_calendar();
+        if state.ids.name_txts.len() < entries.len() {
+            state.update(|s| {
+                s.ids
+                    .name_txts
+                    .resize(entries.len(), &mut ui.widget_id_generator());
+                s.ids
+                    .info_txts
+                    .resize(entries.len(), &mut ui.widget_id_generator());
+            });
+        }
+
+        if entries.is_empty() {
+            Text::new(&self.localized_strings.get_msg("hud-calendar-empty"))
+                .top_left_with_margins_on(state.ids.content_align, 0.0, 2.0)
+                .w(260.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(16))
+                .color(TEXT_COLOR)
+                .set(state.ids.empty_txt, ui);
+        }
+
+        for (i, entry) in entries.iter().enumerate() {
+            // Ongoing events are highlighted
+            let color = if entry.is_active(*self.time) {
+                TEXT_VELORITE
+            } else {
+                TEXT_COLOR
+            };
+            let name_txt = Text::new(&self.localized_strings.get_content(&entry.name))
+                .w(260.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(18))
+                .color(color);
+            if i == 0 {
+                name_txt.top_left_with_margins_on(state.ids.content_align, 0.0, 2.0)
+            } else {
+                name_txt.down_from(state.ids.info_txts[i - 1], 15.0)
+            }
+            .set(state.ids.name_txts[i], ui);
+
+            Text::new(&self.info_text(entry))
+                .down_from(state.ids.name_txts[i], 5.0)
+                .w(260.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR_GREY)
+                .set(state.ids.info_txts[i], ui);
+        }
+
+        event
+    }
+}
@@ -2,7 +2,7 @@ use super::{
     img_ids::{Imgs, ImgsRot},
     item_imgs::{animate_by_pulse, ItemImgs},
     Position, PositionSpecifier, Show, BLACK, CRITICAL_HP_COLOR, HP_COLOR, TEXT_COLOR,
-    UI_HIGHLIGHT_0, UI_MAIN, XP_COLOR,
+    TEXT_GRAY_COLOR, UI_HIGHLIGHT_0, UI_MAIN, XP_COLOR,
 };
 use crate::{
     game_input::GameInput,
@@ -23,7 +23,9 @@ use common::{
     combat,
     comp::{
         self,
-        ability::{Ability, ActiveAbilities, AuxiliaryAbility, BASE_ABILITY_LIMIT},
+        ability::{
+            Ability, ActiveAbilities, AuxiliaryAbility, ABILITY_LOADOUTS, BASE_ABILITY_LIMIT,
+        },
         inventory::{
             item::{
                 item_key::ItemKey,
@@ -37,7 +39,7 @@ use common::{
             StaffSkill, SwimSkill, SwordSkill, SKILL_MODIFIERS,
         },
         skillset::{SkillGroupKind, SkillSet},
-        Body, CharacterState, Energy, Health, Inventory, Poise, Stats,
+        Body, CharacterState, Energy, Health, Inventory, Poise, Stats, Title, TitleSource,
     },
 };
 use conrod_core::{
@@ -47,6 +49,7 @@ use conrod_core::{
 };
 use i18n::Localization;
 use std::borrow::Cow;
+use strum::IntoEnumIterator;
 use vek::*;
 const ART_SIZE: [f64; 2] = [320.0, 320.0];
 
@@ -173,6 +176,7 @@ widget_ids! {
         ability_page_right,
         active_abilities[],
         active_abilities_keys[],
+        ability_loadouts[],
         main_weap_select,
         off_weap_select,
         abilities[],
@@ -184,13 +188,18 @@ widget_ids! {
         // Stats
         stat_names[],
         stat_values[],
+        // Titles
+        title_names[],
+        title_descs[],
+        title_btns[],
+        hide_title_btn,
     }
 }
 
 #[derive(WidgetCommon)]
 pub struct Diary<'a> {
     show: &'a Show,
-    _client: &'a Client,
+    client: &'a Client,
     global_state: &'a GlobalState,
     skill_set: &'a SkillSet,
     active_abilities: &'a ActiveAbilities,
@@ -238,7 +247,7 @@ impl Default for DiaryShow {
 impl<'a> Diary<'a> {
     pub fn new(
         show: &'a Show,
-        _client: &'a Client,
+        client: &'a Client,
         global_state: &'a GlobalState,
         skill_set: &'a SkillSet,
         active_abilities: &'a ActiveAbilities,
@@ -262,7 +271,7 @@ impl<'a> Diary<'a> {
     ) -> Self {
         Self {
             show,
-            _client,
+            client,
             global_state,
             skill_set,
             active_abilities,
@@ -308,7 +317,7 @@ const TREES: [&str; 8] = [
 
 // Possible future sections: Bestiary ("Pokedex" of fought enemies), Weapon and
 // armour catalogue, Achievements...
-const SECTIONS: [&str; 3] = ["Skill-Trees", "Abilities", "Stats"];
+const SECTIONS: [&str; 4] = ["Skill-Trees", "Abilities", "Stats", "Titles"];
 
 pub enum Event {
     Close,
@@ -316,6 +325,8 @@ pub enum Event {
     UnlockSkill(Skill),
     ChangeSection(DiarySection),
     SelectExpBar(Option<SkillGroupKind>),
+    SwitchAbilityLoadout(usize),
+    SelectTitle(Option<Title>),
 }
 
 #[derive(PartialEq, Eq)]
@@ -323,6 +334,7 @@ pub enum DiarySection {
     SkillTrees,
     AbilitySelection,
     Stats,
+    Titles,
 }
 
 pub struct DiaryState {
@@ -442,6 +454,7 @@ impl<'a> Widget for Diary<'a> {
                 "Abilities" => "List of your currently available abilities.",
                 "Skill-Trees" => "",
                 "Stats" => "",
+                "Titles" => "Titles you earned, one of which can be shown under your name.",
                 _ => "",
             };
             let btn_img = {
@@ -449,6 +462,7 @@ impl<'a> Widget for Diary<'a> {
                     "Abilities" => self.imgs.spellbook_ico,
                     "Skill-Trees" => self.imgs.skilltree_ico,
                     "Stats" => self.imgs.stats_ico,
+                    "Titles" => self.imgs.quest_ico,
                     _ => self.imgs.nothing,
                 };
                 if i == 0 {
@@ -909,6 +923,54 @@ impl<'a> Widget for Diary<'a> {
                         .set(state.ids.active_abilities_keys[i], ui);
                 }
 
+                // Loadouts of the equipped weapons, next to the active abilities
+                state.update(|s| {
+                    s.ids
+                        .ability_loadouts
+                        .resize(ABILITY_LOADOUTS, &mut ui.widget_id_generator())
+                });
+                let active_loadout = self
+                    .active_abilities
+                    .active_loadout(ActiveAbilities::active_auxiliary_key(Some(self.inventory)));
+                for i in 0..ABILITY_LOADOUTS {
+                    let loadout_btn = Button::image(if i == active_loadout {
+                        self.imgs.button_press
+                    } else {
+                        self.imgs.button
+                    })
+                    .w_h(30.0, 24.0)
+                    .hover_image(self.imgs.button_hover)
+                    .press_image(self.imgs.button_press)
+                    .label(&(i + 1).to_string())
+                    .label_font_id(self.fonts.cyri.conrod_id)
+                    .label_font_size(self.fonts.cyri.scale(14))
+                    .label_color(TEXT_COLOR);
+                    let loadout_btn = if i == 0 {
+                        loadout_btn
+                            .right_from(state.ids.spellbook_skills_bg, 8.0)
+                            .align_top_of(state.ids.spellbook_skills_bg)
+                    } else {
+                        loadout_btn.down_from(state.ids.ability_loadouts[i - 1], 4.0)
+                    };
+                    if loadout_btn
+                        .with_tooltip(
+                            self.tooltip_manager,
+                            &self.localized_strings.get_msg_ctx(
+                                "hud-ability_loadout",
+                                &i18n::fluent_args! { "number" => i + 1 },
+                            ),
+                            &self.localized_strings.get_msg("hud-ability_loadout-desc"),
+                            &diary_tooltip,
+                            TEXT_COLOR,
+                        )
+                        .set(state.ids.ability_loadouts[i], ui)
+                        .was_clicked()
+                        && i != active_loadout
+                    {
+                        events.push(Event::SwitchAbilityLoadout(i));
+                    }
+                }
+
                 let abilities: Vec<_> = ActiveAbilities::all_available_abilities(
                     Some(self.inventory),
                     Some(self.skill_set),
@@ -1295,6 +1357,115 @@ impl<'a> Widget for Diary<'a> {
                     number.set(state.ids.stat_values[i], ui);
                 }
 
+                events
+            },
+            DiarySection::Titles => {
+                // Background Art
+                Image::new(self.imgs.book_bg)
+                    .w_h(299.0 * 4.0, 184.0 * 4.0)
+                    .mid_top_with_margin_on(state.ids.content_align, 4.0)
+                    .set(state.ids.spellbook_art, ui);
+
+                let titles = self
+                    .client
+                    .state()
+                    .ecs()
+                    .read_storage::<comp::Titles>()
+                    .get(self.client.entity())
+                    .cloned()
+                    .unwrap_or_default();
+                let all_titles = Title::iter().collect::<Vec<_>>();
+                if state.ids.title_names.len() < all_titles.len() {
+                    state.update(|s| {
+                        let gen = &mut ui.widget_id_generator();
+                        s.ids.title_names.resize(all_titles.len(), gen);
+                        s.ids.title_descs.resize(all_titles.len(), gen);
+                        s.ids.title_btns.resize(all_titles.len(), gen);
+                    });
+                }
+
+                let button = |label: &str, enabled: bool| {
+                    Button::image(self.imgs.button)
+                        .hover_image(if enabled {
+                            self.imgs.button_hover
+                        } else {
+                            self.imgs.button
+                        })
+                        .press_image(if enabled {
+                            self.imgs.button_press
+                        } else {
+                            self.imgs.button
+                        })
+                        .w_h(120.0, 30.0)
+                        .label(label)
+                        .label_font_id(self.fonts.cyri.conrod_id)
+                        .label_font_size(self.fonts.cyri.scale(16))
+                        .label_color(if enabled { TEXT_COLOR } else { TEXT_GRAY_COLOR })
+                        .image_color(if enabled { TEXT_COLOR } else { TEXT_GRAY_COLOR })
+                };
+
+                for (i, title) in all_titles.into_iter().enumerate() {
+                    let unlocked = titles.is_unlocked(title);
+                    let mut name = Text::new(&self.localized_strings.get_msg(title.i18n_key()))
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(29))
+                        .color(if unlocked { BLACK } else { TEXT_GRAY_COLOR });
+                    name = if i == 0 {
+                        name.top_left_with_margins_on(state.ids.spellbook_art, 20.0, 20.0)
+                    } else {
+                        name.down_from(state.ids.title_names[i - 1], 42.0)
+                    };
+                    name.set(state.ids.title_names[i], ui);
+
+                    // How the title is earned
+                    let source = match title.source() {
+                        TitleSource::Achievement => "hud-title-source-achievement",
+                        TitleSource::Quest => "hud-title-source-quest",
+                        TitleSource::Event => "hud-title-source-event",
+                    };
+                    Text::new(&self.localized_strings.get_msg_ctx(
+                        &format!("{}-desc", title.i18n_key()),
+                        &i18n::fluent_args! {
+                            "source" => self.localized_strings.get_msg(source),
+                            "shrines" => comp::WAYFARER_SHRINES,
+                        },
+                    ))
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(18))
+                    .color(BLACK)
+                    .down_from(state.ids.title_names[i], 6.0)
+                    .set(state.ids.title_descs[i], ui);
+
+                    let shown = titles.selected() == Some(title);
+                    let label = if shown {
+                        "hud-title-shown"
+                    } else if unlocked {
+                        "hud-title-show"
+                    } else {
+                        "hud-title-locked"
+                    };
+                    if button(&self.localized_strings.get_msg(label), unlocked && !shown)
+                        .align_middle_y_of(state.ids.title_names[i])
+                        .x_relative_to(state.ids.spellbook_art, -100.0)
+                        .set(state.ids.title_btns[i], ui)
+                        .was_clicked()
+                        && unlocked
+                        && !shown
+                    {
+                        events.push(Event::SelectTitle(Some(title)));
+                    }
+                }
+
+                let any_shown = titles.selected().is_some();
+                if button(&self.localized_strings.get_msg("hud-title-hide"), any_shown)
+                    .bottom_left_with_margins_on(state.ids.spellbook_art, 30.0, 20.0)
+                    .set(state.ids.hide_title_btn, ui)
+                    .was_clicked()
+                    && any_shown
+                {
+                    events.push(Event::SelectTitle(None));
+                }
+
                 events
             },
         }
@@ -1320,6 +1491,7 @@ fn section_from_str(string: &str) -> Option<DiarySection> {
         "Abilities" => Some(DiarySection::AbilitySelection),
         "Skill-Trees" => Some(DiarySection::SkillTrees),
         "Stats" => Some(DiarySection::Stats),
+        "Titles" => Some(DiarySection::Titles),
         _ => None,
     }
 }
@@ -3184,6 +3356,15 @@ impl<'a> SkillStrings<'a> {
     }
 }
 
+/// Localized title of a skill, as shown in the skill trees
+pub(super) fn skill_title<'loc>(
+    i18n: &'loc Localization,
+    skill_set: &SkillSet,
+    skill: Skill,
+) -> Cow<'loc, str> {
+    skill_strings(skill).localize(i18n, skill_set, skill).0
+}
+
 fn sp<'loc>(i18n: &'loc Localization, skill_set: &SkillSet, skill: Skill) -> Cow<'loc, str> {
     let current_level = skill_set.skill_level(skill);
     if matches!(current_level, Ok(level) if level == skill.max_level()) {
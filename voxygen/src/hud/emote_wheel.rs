@@ -0,0 +1,151 @@
+use super::{img_ids::Imgs, slots, TEXT_COLOR};
+use crate::ui::{
+    fonts::Fonts,
+    slot::{ContentSize, SlotMaker},
+};
+use common::states::emote::EmoteKind;
+use conrod_core::{
+    widget::{self, Button},
+    widget_ids, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use std::f64::consts::PI;
+use strum::IntoEnumIterator;
+use vek::*;
+
+/// Distance from the centre of the screen to the centre of each button
+const WHEEL_RADIUS: f64 = 120.0;
+
+widget_ids! {
+    struct Ids {
+        emote_buttons[],
+        emote_slots[],
+    }
+}
+
+/// Localization key of the name of an emote
+pub fn emote_label(kind: EmoteKind) -> String {
+    format!("hud-emote-{}", <&str>::from(kind).to_lowercase())
+}
+
+#[derive(WidgetCommon)]
+pub struct EmoteWheel<'a> {
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    slot_manager: &'a mut slots::SlotManager,
+    pulse: f32,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> EmoteWheel<'a> {
+    pub fn new(
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        slot_manager: &'a mut slots::SlotManager,
+        pulse: f32,
+    ) -> Self {
+        Self {
+            imgs,
+            fonts,
+            localized_strings,
+            slot_manager,
+            pulse,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    Emote(EmoteKind),
+}
+
+impl<'a> Widget for EmoteWheel<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("EmoteWheel::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        let count = EmoteKind::iter().count();
+        if state.ids.emote_buttons.len() < count {
+            state.update(|s| {
+                s.ids
+                    .emote_buttons
+                    .resize(count, &mut ui.widget_id_generator());
+                s.ids
+                    .emote_slots
+                    .resize(count, &mut ui.widget_id_generator());
+            });
+        }
+
+        // Emotes can be dragged from their slots onto the quick bar
+        let mut slot_maker = SlotMaker {
+            empty_slot: self.imgs.skillbar_slot,
+            filled_slot: self.imgs.skillbar_slot,
+            selected_slot: self.imgs.inv_slot_sel,
+            background_color: None,
+            content_size: ContentSize {
+                width_height_ratio: 1.0,
+                max_fraction: 0.8,
+            },
+            selected_content_scale: 1.0,
+            amount_font: self.fonts.cyri.conrod_id,
+            amount_margins: Vec2::new(1.0, 1.0),
+            amount_font_size: self.fonts.cyri.scale(12),
+            amount_text_color: TEXT_COLOR,
+            content_source: &(),
+            image_source: self.imgs,
+            slot_manager: Some(self.slot_manager),
+            pulse: self.pulse,
+        };
+
+        let mut event = None;
+        for (i, kind) in EmoteKind::iter().enumerate() {
+            // Lay the emotes out clockwise, starting at the top
+            let angle = PI / 2.0 - i as f64 * 2.0 * PI / count as f64;
+            if Button::image(self.imgs.button)
+                .x_y_relative_to(
+                    ui.window,
+                    angle.cos() * WHEEL_RADIUS,
+                    angle.sin() * WHEEL_RADIUS,
+                )
+                .w_h(120.0, 40.0)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .label(&self.localized_strings.get_msg(&emote_label(kind)))
+                .label_y(conrod_core::position::Relative::Scalar(2.0))
+                .label_color(TEXT_COLOR)
+                .label_font_size(self.fonts.cyri.scale(16))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .set(state.ids.emote_buttons[i], ui)
+                .was_clicked()
+            {
+                event = Some(Event::Emote(kind));
+            }
+            slot_maker
+                .fabricate(kind, [40.0; 2])
+                .left_from(state.ids.emote_buttons[i], 2.0)
+                .set(state.ids.emote_slots[i], ui);
+        }
+
+        event
+    }
+}
@@ -16,7 +16,12 @@ use crate::{
 use client::{self, Client};
 use common::{
     combat,
-    comp::{group::Role, inventory::item::MaterialStatManifest, invite::InviteKind, Stats},
+    comp::{
+        group::{LootMode, Role},
+        inventory::item::MaterialStatManifest,
+        invite::InviteKind,
+        Stats,
+    },
     resources::Time,
     uid::{IdMaps, Uid},
 };
@@ -39,7 +44,7 @@ widget_ids! {
         btn_bg,
         btn_friend,
         btn_leader,
-        btn_link,
+        btn_loot_mode,
         btn_kick,
         btn_leave,
         scroll_area,
@@ -129,6 +134,7 @@ pub enum Event {
     Kick(Uid),
     LeaveGroup,
     AssignLeader(Uid),
+    SetLootMode(LootMode),
 }
 
 impl<'a> Widget for Group<'a> {
@@ -377,6 +383,9 @@ impl<'a> Widget for Group<'a> {
             let bodies = client_state.ecs().read_storage::<common::comp::Body>();
             let poises = client_state.ecs().read_storage::<common::comp::Poise>();
             let stances = client_state.ecs().read_storage::<common::comp::Stance>();
+            let char_states = client_state
+                .ecs()
+                .read_storage::<common::comp::CharacterState>();
 
             // Keep track of the total number of widget ids we are using for buffs
             let mut total_buff_count = 0;
@@ -393,6 +402,9 @@ impl<'a> Widget for Group<'a> {
                 let body = entity.and_then(|entity| bodies.get(entity));
                 let poise = entity.and_then(|entity| poises.get(entity));
                 let stance = entity.and_then(|entity| stances.get(entity));
+                let is_downed = entity
+                    .and_then(|entity| char_states.get(entity))
+                    .map_or(false, |char_state| char_state.is_downed());
 
                 if let (
                     Some(stats),
@@ -457,6 +469,14 @@ impl<'a> Widget for Group<'a> {
                             .font_id(self.fonts.cyri.conrod_id)
                             .color(KILL_COLOR)
                             .set(state.ids.dead_txt[i], ui);
+                    } else if is_downed {
+                        // Downed Text
+                        Text::new(&self.localized_strings.get_msg("hud-group-downed"))
+                            .mid_top_with_margin_on(state.ids.member_panels_bg[i], 1.0)
+                            .font_size(20)
+                            .font_id(self.fonts.cyri.conrod_id)
+                            .color(KILL_COLOR)
+                            .set(state.ids.dead_txt[i], ui);
                     } else {
                         // Health Text
                         let txt = format!(
@@ -694,25 +714,31 @@ impl<'a> Widget for Group<'a> {
                             });
                         }
                     };
+                    let loot_mode = self.client.group_loot_mode();
                     if Button::image(self.imgs.button)
                         .w_h(90.0, 22.0)
                         .mid_bottom_with_margin_on(state.ids.btn_leader, -27.0)
-                        .hover_image(self.imgs.button)
-                        .press_image(self.imgs.button)
-                        .label(&self.localized_strings.get_msg("hud-group-link_group"))
-                        .hover_image(self.imgs.button)
-                        .press_image(self.imgs.button)
-                        .label_color(TEXT_COLOR_GREY)
-                        .image_color(TEXT_COLOR_GREY)
+                        .hover_image(self.imgs.button_hover)
+                        .press_image(self.imgs.button_press)
+                        .label(&self.localized_strings.get_msg(match loot_mode {
+                            LootMode::FreeForAll => "hud-group-loot_mode-free_for_all",
+                            LootMode::NeedGreed => "hud-group-loot_mode-need_greed",
+                        }))
+                        .label_color(TEXT_COLOR)
                         .label_font_id(self.fonts.cyri.conrod_id)
                         .label_font_size(self.fonts.cyri.scale(10))
-                        .set(state.ids.btn_link, ui)
+                        .set(state.ids.btn_loot_mode, ui)
                         .was_clicked()
-                    {};
+                    {
+                        events.push(Event::SetLootMode(match loot_mode {
+                            LootMode::FreeForAll => LootMode::NeedGreed,
+                            LootMode::NeedGreed => LootMode::FreeForAll,
+                        }));
+                    };
                     if Button::image(self.imgs.button)
                         .w_h(90.0, 22.0)
-                        .mid_bottom_with_margin_on(state.ids.btn_link, -27.0)
-                        .down_from(state.ids.btn_link, 5.0)
+                        .mid_bottom_with_margin_on(state.ids.btn_loot_mode, -27.0)
+                        .down_from(state.ids.btn_loot_mode, 5.0)
                         .hover_image(self.imgs.button_hover)
                         .press_image(self.imgs.button_press)
                         .label(&self.localized_strings.get_msg("hud-group-kick"))
@@ -821,6 +847,18 @@ impl<'a> Widget for Group<'a> {
                         "name" => &name,
                     },
                 ),
+                InviteKind::Guild => self.localized_strings.get_msg_ctx(
+                    "hud-group-invite_to_guild",
+                    &i18n::fluent_args! {
+                        "name" => &name,
+                        "tag" => self
+                            .client
+                            .player_list()
+                            .get(&invite_uid)
+                            .and_then(|info| info.guild_tag.clone())
+                            .unwrap_or_default(),
+                    },
+                ),
             };
             Text::new(&invite_text)
                 .mid_top_with_margin_on(state.ids.bg, 5.0)
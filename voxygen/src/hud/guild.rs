@@ -0,0 +1,448 @@
+use client::Client;
+use common::guild::{
+    valid_guild_name, valid_guild_tag, GuildManip, GuildRank, GUILD_CREATION_COST,
+    GUILD_NAME_MAX_LEN, GUILD_TAG_MAX_LEN,
+};
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Scrollbar, Text, TextEdit},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use itertools::Itertools;
+
+use crate::ui::fonts::Fonts;
+
+use super::{
+    img_ids::Imgs, CRITICAL_HP_COLOR, GUILD_COLOR, TEXT_COLOR, TEXT_COLOR_3, UI_HIGHLIGHT_0,
+    UI_MAIN,
+};
+
+pub struct State {
+    ids: Ids,
+    name_input: String,
+    tag_input: String,
+}
+
+widget_ids! {
+    pub struct Ids {
+        bg,
+        frame,
+        icon,
+        close,
+        title_align,
+        title,
+        content_align,
+        scrollbar,
+        name_txt,
+        members_txt,
+        member_txts[],
+        promote_btns[],
+        demote_btns[],
+        kick_btns[],
+        leave_btn,
+        disband_btn,
+        create_txt,
+        name_label,
+        name_bg,
+        name_input,
+        tag_label,
+        tag_bg,
+        tag_input,
+        cost_txt,
+        create_btn,
+    }
+}
+
+#[derive(WidgetCommon)]
+pub struct Guild<'a> {
+    client: &'a Client,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Guild<'a> {
+    pub fn new(
+        client: &'a Client,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+    ) -> Self {
+        Self {
+            client,
+            imgs,
+            fonts,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+
+    fn rank_name(&self, rank: GuildRank) -> String {
+        self.localized_strings.get_msg(rank_key(rank)).into_owned()
+    }
+
+    fn button(&self, label: &str, enabled: bool) -> Button<'a, widget::button::Image> {
+        Button::image(self.imgs.button)
+            .hover_image(if enabled {
+                self.imgs.button_hover
+            } else {
+                self.imgs.button
+            })
+            .press_image(if enabled {
+                self.imgs.button_press
+            } else {
+                self.imgs.button
+            })
+            .label(label)
+            .label_y(conrod_core::position::Relative::Scalar(2.0))
+            .label_color(if enabled { TEXT_COLOR } else { TEXT_COLOR_3 })
+            .image_color(if enabled { TEXT_COLOR } else { TEXT_COLOR_3 })
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_font_id(self.fonts.cyri.conrod_id)
+    }
+}
+
+pub enum Event {
+    Close,
+    Manip(GuildManip),
+}
+
+pub(super) fn rank_key(rank: GuildRank) -> &'static str {
+    match rank {
+        GuildRank::Member => "hud-guild-rank-member",
+        GuildRank::Officer => "hud-guild-rank-officer",
+        GuildRank::Leader => "hud-guild-rank-leader",
+    }
+}
+
+impl<'a> Widget for Guild<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        Self::State {
+            ids: Ids::new(id_gen),
+            name_input: String::new(),
+            tag_input: String::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Guild::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut event = None;
+
+        // Window BG
+        Image::new(self.imgs.social_bg_on)
+            .bottom_left_with_margins_on(ui.window, 308.0, 315.0)
+            .color(Some(UI_MAIN))
+            .w_h(280.0, 460.0)
+            .set(state.ids.bg, ui);
+        // Window frame
+        Image::new(self.imgs.social_frame_on)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .w_h(280.0, 460.0)
+            .set(state.ids.frame, ui);
+
+        // Icon
+        Image::new(self.imgs.social)
+            .w_h(30.0, 30.0)
+            .top_left_with_margins_on(state.ids.frame, 6.0, 6.0)
+            .set(state.ids.icon, ui);
+        // X-Button
+        if Button::image(self.imgs.close_button)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.ids.frame, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        // Title
+        Rectangle::fill_with([212.0, 42.0], color::TRANSPARENT)
+            .top_left_with_margins_on(state.ids.frame, 2.0, 44.0)
+            .set(state.ids.title_align, ui);
+        Text::new(&self.localized_strings.get_msg("hud-guild"))
+            .middle_of(state.ids.title_align)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // Content Alignment
+        Rectangle::fill_with([270.0, 350.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.ids.frame, 55.0)
+            .scroll_kids_vertically()
+            .set(state.ids.content_align, ui);
+        Scrollbar::y_axis(state.ids.content_align)
+            .thickness(4.0)
+            .color(Color::Rgba(0.79, 1.09, 1.09, 0.0))
+            .set(state.ids.scrollbar, ui);
+
+        let player_list = self.client.player_list();
+        let my_uuid = self
+            .client
+            .uid()
+            .and_then(|uid| player_list.get(&uid))
+            .map(|player| player.uuid);
+
+        let Some(guild) = self.client.guild() else {
+            // Not in a guild, offer to found one
+            Text::new(&self.localized_strings.get_msg("hud-guild-create"))
+                .top_left_with_margins_on(state.ids.content_align, 0.0, 2.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(20))
+                .color(TEXT_COLOR)
+                .set(state.ids.create_txt, ui);
+
+            Text::new(&self.localized_strings.get_msg("hud-guild-create-name"))
+                .down_from(state.ids.create_txt, 15.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR)
+                .set(state.ids.name_label, ui);
+            Rectangle::fill([260.0, 22.0])
+                .down_from(state.ids.name_label, 5.0)
+                .hsla(0.0, 0.0, 0.0, 0.7)
+                .set(state.ids.name_bg, ui);
+            if let Some(name) = TextEdit::new(&state.name_input)
+                .top_left_with_margins_on(state.ids.name_bg, 1.0, 4.0)
+                .w_h(252.0, 20.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR)
+                .set(state.ids.name_input, ui)
+            {
+                if name.chars().count() <= GUILD_NAME_MAX_LEN {
+                    state.update(|s| s.name_input = name);
+                }
+            }
+
+            Text::new(&self.localized_strings.get_msg("hud-guild-create-tag"))
+                .down_from(state.ids.name_bg, 10.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR)
+                .set(state.ids.tag_label, ui);
+            Rectangle::fill([260.0, 22.0])
+                .down_from(state.ids.tag_label, 5.0)
+                .hsla(0.0, 0.0, 0.0, 0.7)
+                .set(state.ids.tag_bg, ui);
+            if let Some(tag) = TextEdit::new(&state.tag_input)
+                .top_left_with_margins_on(state.ids.tag_bg, 1.0, 4.0)
+                .w_h(252.0, 20.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR)
+                .set(state.ids.tag_input, ui)
+            {
+                if tag.chars().count() <= GUILD_TAG_MAX_LEN {
+                    state.update(|s| s.tag_input = tag);
+                }
+            }
+
+            Text::new(&self.localized_strings.get_msg_ctx(
+                "hud-guild-create-cost",
+                &i18n::fluent_args! {
+                    "cost" => GUILD_CREATION_COST,
+                },
+            ))
+            .down_from(state.ids.tag_bg, 10.0)
+            .w(260.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.cost_txt, ui);
+
+            let can_create =
+                valid_guild_name(&state.name_input) && valid_guild_tag(&state.tag_input);
+            if self
+                .button(
+                    &self.localized_strings.get_msg("hud-guild-create-confirm"),
+                    can_create,
+                )
+                .w_h(106.0, 26.0)
+                .down_from(state.ids.cost_txt, 10.0)
+                .set(state.ids.create_btn, ui)
+                .was_clicked()
+                && can_create
+            {
+                event = Some(Event::Manip(GuildManip::Create {
+                    name: state.name_input.clone(),
+                    tag: state.tag_input.clone(),
+                }));
+                state.update(|s| {
+                    s.name_input.clear();
+                    s.tag_input.clear();
+                });
+            }
+
+            return event;
+        };
+
+        let my_rank = my_uuid.and_then(|uuid| guild.rank_of(uuid));
+
+        Text::new(&format!("[{}] {}", guild.tag, guild.name))
+            .top_left_with_margins_on(state.ids.content_align, 0.0, 2.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(GUILD_COLOR)
+            .set(state.ids.name_txt, ui);
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg_ctx("hud-guild-members", &i18n::fluent_args! {
+                    "count" => guild.members.len(),
+                }),
+        )
+        .down_from(state.ids.name_txt, 10.0)
+        .font_id(self.fonts.cyri.conrod_id)
+        .font_size(self.fonts.cyri.scale(16))
+        .color(TEXT_COLOR)
+        .set(state.ids.members_txt, ui);
+
+        let members = guild
+            .members
+            .iter()
+            .sorted_by(|a, b| {
+                b.rank
+                    .cmp(&a.rank)
+                    .then_with(|| a.alias.to_lowercase().cmp(&b.alias.to_lowercase()))
+            })
+            .collect_vec();
+        if state.ids.member_txts.len() < members.len() {
+            state.update(|s| {
+                let gen = &mut ui.widget_id_generator();
+                s.ids.member_txts.resize(members.len(), gen);
+                s.ids.promote_btns.resize(members.len(), gen);
+                s.ids.demote_btns.resize(members.len(), gen);
+                s.ids.kick_btns.resize(members.len(), gen);
+            });
+        }
+
+        let mut last = state.ids.members_txt;
+        for (i, member) in members.into_iter().enumerate() {
+            let online = player_list
+                .values()
+                .any(|player| player.uuid == member.uuid && player.is_online);
+            let status = self.localized_strings.get_msg(if online {
+                "hud-guild-online"
+            } else {
+                "hud-guild-offline"
+            });
+            Text::new(&format!(
+                "{} - {} ({})",
+                member.alias,
+                self.rank_name(member.rank),
+                status
+            ))
+            .down_from(last, 8.0)
+            .w(260.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(if online { TEXT_COLOR } else { TEXT_COLOR_3 })
+            .set(state.ids.member_txts[i], ui);
+            last = state.ids.member_txts[i];
+
+            // Management of other members, depending on our rank
+            let Some(my_rank) = my_rank.filter(|_| Some(member.uuid) != my_uuid) else {
+                continue;
+            };
+            let promotion = match member.rank {
+                GuildRank::Member => Some(GuildRank::Officer),
+                GuildRank::Officer => Some(GuildRank::Leader),
+                GuildRank::Leader => None,
+            }
+            .filter(|_| my_rank.can_set_rank());
+            let demotion = (member.rank == GuildRank::Officer && my_rank.can_set_rank())
+                .then_some(GuildRank::Member);
+            let can_kick = my_rank.can_kick(member.rank);
+
+            let mut last_btn = None;
+            if let Some(rank) = promotion {
+                if self
+                    .button(&self.localized_strings.get_msg("hud-guild-promote"), true)
+                    .w_h(80.0, 22.0)
+                    .down_from(state.ids.member_txts[i], 4.0)
+                    .set(state.ids.promote_btns[i], ui)
+                    .was_clicked()
+                {
+                    event = Some(Event::Manip(GuildManip::SetRank(member.uuid, rank)));
+                }
+                last_btn = Some(state.ids.promote_btns[i]);
+            }
+            if let Some(rank) = demotion {
+                let button = self
+                    .button(&self.localized_strings.get_msg("hud-guild-demote"), true)
+                    .w_h(80.0, 22.0);
+                if match last_btn {
+                    Some(last_btn) => button.right_from(last_btn, 5.0),
+                    None => button.down_from(state.ids.member_txts[i], 4.0),
+                }
+                .set(state.ids.demote_btns[i], ui)
+                .was_clicked()
+                {
+                    event = Some(Event::Manip(GuildManip::SetRank(member.uuid, rank)));
+                }
+                last_btn = Some(state.ids.demote_btns[i]);
+            }
+            if can_kick {
+                let button = self
+                    .button(&self.localized_strings.get_msg("hud-guild-kick"), true)
+                    .w_h(80.0, 22.0)
+                    .image_color(CRITICAL_HP_COLOR);
+                if match last_btn {
+                    Some(last_btn) => button.right_from(last_btn, 5.0),
+                    None => button.down_from(state.ids.member_txts[i], 4.0),
+                }
+                .set(state.ids.kick_btns[i], ui)
+                .was_clicked()
+                {
+                    event = Some(Event::Manip(GuildManip::Kick(member.uuid)));
+                }
+                last_btn = Some(state.ids.kick_btns[i]);
+            }
+            if let Some(last_btn) = last_btn {
+                last = last_btn;
+            }
+        }
+
+        // Leave and disband
+        if self
+            .button(&self.localized_strings.get_msg("hud-guild-leave"), true)
+            .w_h(106.0, 26.0)
+            .bottom_left_with_margins_on(state.ids.frame, 9.0, 7.0)
+            .set(state.ids.leave_btn, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Manip(GuildManip::Leave));
+        }
+        let can_disband = my_rank.map_or(false, |rank| rank.can_disband());
+        if self
+            .button(
+                &self.localized_strings.get_msg("hud-guild-disband"),
+                can_disband,
+            )
+            .w_h(106.0, 26.0)
+            .bottom_right_with_margins_on(state.ids.frame, 9.0, 7.0)
+            .set(state.ids.disband_btn, ui)
+            .was_clicked()
+            && can_disband
+        {
+            event = Some(Event::Manip(GuildManip::Disband));
+        }
+
+        event
+    }
+}
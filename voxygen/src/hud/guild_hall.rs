@@ -0,0 +1,428 @@
+use client::Client;
+use common::guild::{
+    GuildManip, GuildRank, GUILD_BANNER_HEIGHT, GUILD_BANNER_WIDTH, GUILD_HALL_COST,
+    GUILD_NOTICE_MAX_LEN,
+};
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Scrollbar, Text, TextEdit},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use vek::*;
+
+use crate::ui::fonts::Fonts;
+
+use super::{
+    guild::rank_key, img_ids::Imgs, CRITICAL_HP_COLOR, GUILD_COLOR, TEXT_COLOR, TEXT_COLOR_3,
+    UI_HIGHLIGHT_0, UI_MAIN,
+};
+
+/// Size of a block of the banner in the preview
+const BANNER_CELL_SIZE: f64 = 10.0;
+
+pub struct State {
+    ids: Ids,
+    notice_input: String,
+}
+
+widget_ids! {
+    pub struct Ids {
+        bg,
+        frame,
+        icon,
+        close,
+        title_align,
+        title,
+        content_align,
+        scrollbar,
+        info_txt,
+        claim_btn,
+        banner_txt,
+        banner_cells[],
+        design_btn,
+        storage_rank_txt,
+        storage_rank_btn,
+        notices_txt,
+        notice_txts[],
+        remove_notice_btns[],
+        notice_bg,
+        notice_input,
+        post_btn,
+    }
+}
+
+pub enum Event {
+    Close,
+    Manip(GuildManip),
+    /// Open the heraldry designer on the banner of the hall
+    DesignBanner,
+}
+
+/// The notice board of a guild hall, where guilds claim the hall and its
+/// members manage it
+#[derive(WidgetCommon)]
+pub struct GuildHall<'a> {
+    client: &'a Client,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    /// Position of the notice board, which identifies the hall
+    board: Vec3<i32>,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> GuildHall<'a> {
+    pub fn new(
+        client: &'a Client,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        board: Vec3<i32>,
+    ) -> Self {
+        Self {
+            client,
+            imgs,
+            fonts,
+            localized_strings,
+            board,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+
+    fn button(&self, label: &str, enabled: bool) -> Button<'a, widget::button::Image> {
+        Button::image(self.imgs.button)
+            .hover_image(if enabled {
+                self.imgs.button_hover
+            } else {
+                self.imgs.button
+            })
+            .press_image(if enabled {
+                self.imgs.button_press
+            } else {
+                self.imgs.button
+            })
+            .label(label)
+            .label_y(conrod_core::position::Relative::Scalar(2.0))
+            .label_color(if enabled { TEXT_COLOR } else { TEXT_COLOR_3 })
+            .image_color(if enabled { TEXT_COLOR } else { TEXT_COLOR_3 })
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_font_id(self.fonts.cyri.conrod_id)
+    }
+
+    fn text<'b>(&self, text: &'b str, size: u32) -> Text<'b> {
+        Text::new(text)
+            .w(260.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(size))
+            .color(TEXT_COLOR)
+    }
+}
+
+fn next_rank(rank: GuildRank) -> GuildRank {
+    match rank {
+        GuildRank::Member => GuildRank::Officer,
+        GuildRank::Officer => GuildRank::Leader,
+        GuildRank::Leader => GuildRank::Member,
+    }
+}
+
+impl<'a> Widget for GuildHall<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        Self::State {
+            ids: Ids::new(id_gen),
+            notice_input: String::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("GuildHall::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut event = None;
+
+        // Window BG
+        Image::new(self.imgs.social_bg_on)
+            .bottom_left_with_margins_on(ui.window, 308.0, 315.0)
+            .color(Some(UI_MAIN))
+            .w_h(280.0, 460.0)
+            .set(state.ids.bg, ui);
+        // Window frame
+        Image::new(self.imgs.social_frame_on)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .w_h(280.0, 460.0)
+            .set(state.ids.frame, ui);
+
+        // Icon
+        Image::new(self.imgs.social)
+            .w_h(30.0, 30.0)
+            .top_left_with_margins_on(state.ids.frame, 6.0, 6.0)
+            .set(state.ids.icon, ui);
+        // X-Button
+        if Button::image(self.imgs.close_button)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.ids.frame, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        // Title
+        Rectangle::fill_with([212.0, 42.0], color::TRANSPARENT)
+            .top_left_with_margins_on(state.ids.frame, 2.0, 44.0)
+            .set(state.ids.title_align, ui);
+        Text::new(&self.localized_strings.get_msg("hud-guild-hall"))
+            .middle_of(state.ids.title_align)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // Content Alignment
+        Rectangle::fill_with([270.0, 390.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.ids.frame, 55.0)
+            .scroll_kids_vertically()
+            .set(state.ids.content_align, ui);
+        Scrollbar::y_axis(state.ids.content_align)
+            .thickness(4.0)
+            .color(Color::Rgba(0.79, 1.09, 1.09, 0.0))
+            .set(state.ids.scrollbar, ui);
+
+        let player_list = self.client.player_list();
+        let my_uuid = self
+            .client
+            .uid()
+            .and_then(|uid| player_list.get(&uid))
+            .map(|player| player.uuid);
+        let guild = self.client.guild();
+        let my_rank = guild
+            .zip(my_uuid)
+            .and_then(|(guild, uuid)| guild.rank_of(uuid));
+
+        let hall = match guild.map(|guild| guild.hall.as_ref()) {
+            None => {
+                self.text(
+                    &self.localized_strings.get_msg("hud-guild-hall-no_guild"),
+                    14,
+                )
+                .top_left_with_margins_on(state.ids.content_align, 0.0, 2.0)
+                .set(state.ids.info_txt, ui);
+                return event;
+            },
+            Some(None) => {
+                // Our guild has no hall yet, the leader can buy this one
+                self.text(
+                    &self.localized_strings.get_msg_ctx(
+                        "hud-guild-hall-claim-cost",
+                        &i18n::fluent_args! {
+                            "cost" => GUILD_HALL_COST,
+                        },
+                    ),
+                    14,
+                )
+                .top_left_with_margins_on(state.ids.content_align, 0.0, 2.0)
+                .set(state.ids.info_txt, ui);
+
+                let can_claim = my_rank.map_or(false, |rank| rank.can_claim_hall());
+                if self
+                    .button(
+                        &self.localized_strings.get_msg("hud-guild-hall-claim"),
+                        can_claim,
+                    )
+                    .w_h(106.0, 26.0)
+                    .down_from(state.ids.info_txt, 10.0)
+                    .set(state.ids.claim_btn, ui)
+                    .was_clicked()
+                    && can_claim
+                {
+                    event = Some(Event::Manip(GuildManip::ClaimHall(self.board)));
+                }
+                return event;
+            },
+            Some(Some(hall)) if hall.spot.board != self.board => {
+                self.text(
+                    &self.localized_strings.get_msg("hud-guild-hall-not_ours"),
+                    14,
+                )
+                .top_left_with_margins_on(state.ids.content_align, 0.0, 2.0)
+                .set(state.ids.info_txt, ui);
+                return event;
+            },
+            Some(Some(hall)) => hall,
+        };
+
+        // Banner, with a preview and a button to open the designer
+        self.text(&self.localized_strings.get_msg("hud-guild-hall-banner"), 16)
+            .top_left_with_margins_on(state.ids.content_align, 0.0, 2.0)
+            .color(GUILD_COLOR)
+            .set(state.ids.banner_txt, ui);
+
+        let cell_count = (GUILD_BANNER_WIDTH * GUILD_BANNER_HEIGHT) as usize;
+        if state.ids.banner_cells.len() < cell_count {
+            state.update(|s| {
+                s.ids
+                    .banner_cells
+                    .resize(cell_count, &mut ui.widget_id_generator())
+            });
+        }
+        for y in 0..GUILD_BANNER_HEIGHT {
+            for x in 0..GUILD_BANNER_WIDTH {
+                let uv = Vec2::new(
+                    (x as f32 + 0.5) / GUILD_BANNER_WIDTH as f32,
+                    (y as f32 + 0.5) / GUILD_BANNER_HEIGHT as f32,
+                );
+                let col = hall.banner.color_at(uv);
+                Rectangle::fill([BANNER_CELL_SIZE; 2])
+                    .rgb(
+                        col.r as f32 / 255.0,
+                        col.g as f32 / 255.0,
+                        col.b as f32 / 255.0,
+                    )
+                    .down_from(state.ids.banner_txt, 8.0 + y as f64 * BANNER_CELL_SIZE)
+                    .x_relative_to(
+                        state.ids.banner_txt,
+                        -130.0 + 10.0 + x as f64 * BANNER_CELL_SIZE,
+                    )
+                    .set(
+                        state.ids.banner_cells[(y * GUILD_BANNER_WIDTH + x) as usize],
+                        ui,
+                    );
+            }
+        }
+
+        let can_manage = my_rank.map_or(false, |rank| rank.can_manage_hall());
+        if self
+            .button(
+                &self.localized_strings.get_msg("hud-guild-hall-design"),
+                can_manage,
+            )
+            .w_h(106.0, 22.0)
+            .down_from(state.ids.banner_txt, 8.0)
+            .x_relative_to(state.ids.banner_txt, 50.0)
+            .set(state.ids.design_btn, ui)
+            .was_clicked()
+            && can_manage
+        {
+            event = Some(Event::DesignBanner);
+        }
+
+        // Who may take items out of the storage
+        let banner_bottom = 16.0 + GUILD_BANNER_HEIGHT as f64 * BANNER_CELL_SIZE;
+        self.text(
+            &self
+                .localized_strings
+                .get_msg_ctx("hud-guild-storage-access", &i18n::fluent_args! {
+                    "rank" => self.localized_strings.get_msg(rank_key(hall.storage_rank)),
+                }),
+            14,
+        )
+        .down_from(state.ids.banner_txt, banner_bottom)
+        .set(state.ids.storage_rank_txt, ui);
+        let can_set_storage_rank = my_rank.map_or(false, |rank| rank.can_claim_hall());
+        if self
+            .button(
+                &self
+                    .localized_strings
+                    .get_msg("hud-guild-hall-storage_rank"),
+                can_set_storage_rank,
+            )
+            .w_h(106.0, 22.0)
+            .down_from(state.ids.storage_rank_txt, 4.0)
+            .set(state.ids.storage_rank_btn, ui)
+            .was_clicked()
+            && can_set_storage_rank
+        {
+            event = Some(Event::Manip(GuildManip::SetStorageRank(next_rank(
+                hall.storage_rank,
+            ))));
+        }
+
+        // Notices, newest first
+        self.text(
+            &self.localized_strings.get_msg("hud-guild-hall-notices"),
+            16,
+        )
+        .down_from(state.ids.storage_rank_btn, 12.0)
+        .color(GUILD_COLOR)
+        .set(state.ids.notices_txt, ui);
+
+        Rectangle::fill([260.0, 22.0])
+            .down_from(state.ids.notices_txt, 5.0)
+            .hsla(0.0, 0.0, 0.0, 0.7)
+            .set(state.ids.notice_bg, ui);
+        if let Some(text) = TextEdit::new(&state.notice_input)
+            .top_left_with_margins_on(state.ids.notice_bg, 1.0, 4.0)
+            .w_h(252.0, 20.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.notice_input, ui)
+        {
+            if text.chars().count() <= GUILD_NOTICE_MAX_LEN {
+                state.update(|s| s.notice_input = text);
+            }
+        }
+        let can_post = !state.notice_input.trim().is_empty();
+        if self
+            .button(
+                &self.localized_strings.get_msg("hud-guild-hall-post"),
+                can_post,
+            )
+            .w_h(80.0, 22.0)
+            .down_from(state.ids.notice_bg, 4.0)
+            .set(state.ids.post_btn, ui)
+            .was_clicked()
+            && can_post
+        {
+            event = Some(Event::Manip(GuildManip::PostNotice(
+                state.notice_input.clone(),
+            )));
+            state.update(|s| s.notice_input.clear());
+        }
+
+        if state.ids.notice_txts.len() < hall.notices.len() {
+            state.update(|s| {
+                let gen = &mut ui.widget_id_generator();
+                s.ids.notice_txts.resize(hall.notices.len(), gen);
+                s.ids.remove_notice_btns.resize(hall.notices.len(), gen);
+            });
+        }
+        let mut last = state.ids.post_btn;
+        for (i, notice) in hall.notices.iter().rev().enumerate() {
+            self.text(&format!("{}: {}", notice.author_alias, notice.text), 14)
+                .down_from(last, 8.0)
+                .set(state.ids.notice_txts[i], ui);
+            last = state.ids.notice_txts[i];
+
+            if can_manage || Some(notice.author) == my_uuid {
+                if self
+                    .button(
+                        &self.localized_strings.get_msg("hud-guild-hall-remove"),
+                        true,
+                    )
+                    .w_h(80.0, 22.0)
+                    .image_color(CRITICAL_HP_COLOR)
+                    .down_from(state.ids.notice_txts[i], 4.0)
+                    .set(state.ids.remove_notice_btns[i], ui)
+                    .was_clicked()
+                {
+                    event = Some(Event::Manip(GuildManip::RemoveNotice(notice.id)));
+                }
+                last = state.ids.remove_notice_btns[i];
+            }
+        }
+
+        event
+    }
+}
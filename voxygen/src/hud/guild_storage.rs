@@ -0,0 +1,301 @@
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Text},
+    widget_ids, Color, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use vek::*;
+
+use client::Client;
+use common::{
+    comp::inventory::item::{ItemDesc, ItemI18n, MaterialStatManifest, Quality},
+    guild::GUILD_STORAGE_SLOTS,
+};
+use i18n::Localization;
+
+use crate::ui::{
+    fonts::Fonts,
+    slot::{ContentSize, SlotMaker},
+    ImageFrame, ItemTooltip, ItemTooltipManager, ItemTooltipable,
+};
+
+use super::{
+    guild::rank_key,
+    img_ids::{Imgs, ImgsRot},
+    item_imgs::ItemImgs,
+    slots::{GuildStorageSlot, SlotManager},
+    HudInfo, TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
+};
+
+/// Number of storage slots per row
+const STORAGE_COLUMNS: usize = 9;
+const SLOT_SIZE: f64 = 40.0;
+
+widget_ids! {
+    pub struct Ids {
+        bg,
+        bg_frame,
+        close,
+        title_bg,
+        title,
+        slot_alignment,
+        slots[],
+        capacity_text,
+        access_text,
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    Close,
+}
+
+/// The storage in the hall of our guild, shared by its members
+#[derive(WidgetCommon)]
+pub struct GuildStorage<'a> {
+    client: &'a Client,
+    info: &'a HudInfo,
+    imgs: &'a Imgs,
+    item_imgs: &'a ItemImgs,
+    fonts: &'a Fonts,
+    rot_imgs: &'a ImgsRot,
+    item_tooltip_manager: &'a mut ItemTooltipManager,
+    slot_manager: &'a mut SlotManager,
+    localized_strings: &'a Localization,
+    item_i18n: &'a ItemI18n,
+    msm: &'a MaterialStatManifest,
+    pulse: f32,
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> GuildStorage<'a> {
+    pub fn new(
+        client: &'a Client,
+        info: &'a HudInfo,
+        imgs: &'a Imgs,
+        item_imgs: &'a ItemImgs,
+        fonts: &'a Fonts,
+        rot_imgs: &'a ImgsRot,
+        item_tooltip_manager: &'a mut ItemTooltipManager,
+        slot_manager: &'a mut SlotManager,
+        localized_strings: &'a Localization,
+        item_i18n: &'a ItemI18n,
+        msm: &'a MaterialStatManifest,
+        pulse: f32,
+    ) -> Self {
+        Self {
+            client,
+            info,
+            imgs,
+            item_imgs,
+            fonts,
+            rot_imgs,
+            item_tooltip_manager,
+            slot_manager,
+            localized_strings,
+            item_i18n,
+            msm,
+            pulse,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+impl<'a> Widget for GuildStorage<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("GuildStorage::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        let Some(hall) = self.client.guild().and_then(|guild| guild.hall.as_ref()) else {
+            return Some(Event::Close);
+        };
+        let mut event = None;
+
+        // Background
+        Image::new(self.imgs.inv_middle_bg_bag)
+            .w_h(424.0, 482.0)
+            .color(Some(UI_MAIN))
+            .mid_bottom_with_margin_on(ui.window, 295.0)
+            .set(state.ids.bg, ui);
+        Image::new(self.imgs.inv_middle_frame)
+            .w_h(424.0, 482.0)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.bg_frame, ui);
+
+        // Title
+        let title = self.localized_strings.get_msg("hud-guild-storage");
+        Text::new(&title)
+            .mid_top_with_margin_on(state.ids.bg_frame, 9.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(Color::Rgba(0.0, 0.0, 0.0, 1.0))
+            .set(state.ids.title_bg, ui);
+        Text::new(&title)
+            .top_left_with_margins_on(state.ids.title_bg, 2.0, 2.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // Close button
+        if Button::image(self.imgs.close_btn)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_btn_hover)
+            .press_image(self.imgs.close_btn_press)
+            .top_right_with_margins_on(state.ids.bg, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        // Slots
+        let item_tooltip = ItemTooltip::new(
+            {
+                // Edge images [t, b, r, l]
+                // Corner images [tr, tl, br, bl]
+                let edge = &self.rot_imgs.tt_side;
+                let corner = &self.rot_imgs.tt_corner;
+                ImageFrame::new(
+                    [edge.cw180, edge.none, edge.cw270, edge.cw90],
+                    [corner.none, corner.cw270, corner.cw90, corner.cw180],
+                    Color::Rgba(0.08, 0.07, 0.04, 1.0),
+                    5.0,
+                )
+            },
+            self.client,
+            self.info,
+            self.imgs,
+            self.item_imgs,
+            self.pulse,
+            self.msm,
+            self.localized_strings,
+            self.item_i18n,
+        )
+        .title_font_size(self.fonts.cyri.scale(20))
+        .parent(ui.window)
+        .desc_font_size(self.fonts.cyri.scale(12))
+        .font_id(self.fonts.cyri.conrod_id)
+        .desc_text_color(TEXT_COLOR);
+
+        // Alignment for Grid
+        Rectangle::fill_with(
+            [STORAGE_COLUMNS as f64 * SLOT_SIZE, 6.0 * SLOT_SIZE],
+            color::TRANSPARENT,
+        )
+        .mid_top_with_margin_on(state.ids.bg_frame, 70.0)
+        .scroll_kids_vertically()
+        .set(state.ids.slot_alignment, ui);
+
+        if state.ids.slots.len() < GUILD_STORAGE_SLOTS {
+            state.update(|s| {
+                s.ids
+                    .slots
+                    .resize(GUILD_STORAGE_SLOTS, &mut ui.widget_id_generator());
+            });
+        }
+
+        let mut slot_maker = SlotMaker {
+            empty_slot: self.imgs.inv_slot,
+            filled_slot: self.imgs.inv_slot,
+            selected_slot: self.imgs.inv_slot_sel,
+            background_color: Some(UI_MAIN),
+            content_size: ContentSize {
+                width_height_ratio: 1.0,
+                max_fraction: 0.75,
+            },
+            selected_content_scale: 1.067,
+            amount_font: self.fonts.cyri.conrod_id,
+            amount_margins: Vec2::new(-4.0, 0.0),
+            amount_font_size: self.fonts.cyri.scale(12),
+            amount_text_color: TEXT_COLOR,
+            content_source: hall,
+            image_source: self.item_imgs,
+            slot_manager: Some(self.slot_manager),
+            pulse: self.pulse,
+        };
+
+        // Stored items aren't part of any trade, so they're shown without prices
+        let prices_info = None;
+        for (i, item) in hall.storage.iter().enumerate().take(GUILD_STORAGE_SLOTS) {
+            let x = i % STORAGE_COLUMNS;
+            let y = i / STORAGE_COLUMNS;
+            let slot_widget = slot_maker
+                .fabricate(GuildStorageSlot { index: i }, [SLOT_SIZE as f32; 2])
+                .top_left_with_margins_on(
+                    state.ids.slot_alignment,
+                    y as f64 * SLOT_SIZE,
+                    x as f64 * SLOT_SIZE,
+                );
+            if let Some(item) = item {
+                let quality_col_img = match item.quality() {
+                    Quality::Low => self.imgs.inv_slot_grey,
+                    Quality::Common => self.imgs.inv_slot_common,
+                    Quality::Moderate => self.imgs.inv_slot_green,
+                    Quality::High => self.imgs.inv_slot_blue,
+                    Quality::Epic => self.imgs.inv_slot_purple,
+                    Quality::Legendary => self.imgs.inv_slot_gold,
+                    Quality::Artifact => self.imgs.inv_slot_orange,
+                    _ => self.imgs.inv_slot_red,
+                };
+                slot_widget
+                    .filled_slot(quality_col_img)
+                    .with_item_tooltip(
+                        self.item_tooltip_manager,
+                        core::iter::once(item as &dyn ItemDesc),
+                        &prices_info,
+                        &item_tooltip,
+                    )
+                    .set(state.ids.slots[i], ui);
+            } else {
+                slot_widget.set(state.ids.slots[i], ui);
+            }
+        }
+
+        // Capacity and who may take items out
+        let used = hall.storage.iter().filter(|slot| slot.is_some()).count();
+        Text::new(&self.localized_strings.get_msg_ctx(
+            "hud-guild-storage-capacity",
+            &i18n::fluent_args! {
+                "used" => used,
+                "capacity" => GUILD_STORAGE_SLOTS,
+            },
+        ))
+        .bottom_left_with_margins_on(state.ids.bg, 95.0, 32.0)
+        .font_id(self.fonts.cyri.conrod_id)
+        .font_size(self.fonts.cyri.scale(14))
+        .color(TEXT_COLOR)
+        .set(state.ids.capacity_text, ui);
+
+        let rank = self.localized_strings.get_msg(rank_key(hall.storage_rank));
+        Text::new(&self.localized_strings.get_msg_ctx(
+            "hud-guild-storage-access",
+            &i18n::fluent_args! {
+                "rank" => rank,
+            },
+        ))
+        .bottom_right_with_margins_on(state.ids.bg, 95.0, 32.0)
+        .font_id(self.fonts.cyri.conrod_id)
+        .font_size(self.fonts.cyri.scale(14))
+        .color(TEXT_COLOR)
+        .set(state.ids.access_text, ui);
+
+        event
+    }
+}
@@ -0,0 +1,484 @@
+use client::Client;
+use common::{
+    comp::{
+        inventory::slot::{ArmorSlot, EquipSlot, Slot},
+        Inventory,
+    },
+    guild::GuildManip,
+    heraldry::{Emblem, Heraldry, Pattern, TINCTURES},
+};
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Text},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use image::{Rgba, RgbaImage};
+use vek::*;
+
+use crate::ui::fonts::Fonts;
+
+use super::{img_ids::Imgs, TEXT_COLOR, TEXT_COLOR_3, UI_HIGHLIGHT_0, UI_MAIN};
+
+/// Size of the texture the design is composed into for the preview, in the
+/// proportions of a guild banner
+const PREVIEW_WIDTH: u32 = 40;
+const PREVIEW_HEIGHT: u32 = 64;
+
+/// What the designer applies its design to
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HeraldryTarget {
+    Cape,
+    Shield,
+    GuildBanner,
+}
+
+impl HeraldryTarget {
+    const ALL: [HeraldryTarget; 3] = [
+        HeraldryTarget::Cape,
+        HeraldryTarget::Shield,
+        HeraldryTarget::GuildBanner,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            HeraldryTarget::Cape => "hud-heraldry-target-cape",
+            HeraldryTarget::Shield => "hud-heraldry-target-shield",
+            HeraldryTarget::GuildBanner => "hud-heraldry-target-guild_banner",
+        }
+    }
+}
+
+/// The equipped item a design for `target` is applied to, if it can bear one
+fn target_slot(inventory: &Inventory, target: HeraldryTarget) -> Option<EquipSlot> {
+    let slots: &[EquipSlot] = match target {
+        HeraldryTarget::Cape => &[EquipSlot::Armor(ArmorSlot::Back)],
+        HeraldryTarget::Shield => &[EquipSlot::ActiveOffhand, EquipSlot::ActiveMainhand],
+        HeraldryTarget::GuildBanner => &[],
+    };
+    slots.iter().copied().find(|slot| {
+        inventory
+            .equipped(*slot)
+            .map_or(false, |item| item.can_bear_heraldry())
+    })
+}
+
+/// The design `target` currently shows, to start the designer from
+pub fn current_design(client: &Client, target: HeraldryTarget) -> Option<Heraldry> {
+    match target {
+        HeraldryTarget::Cape | HeraldryTarget::Shield => {
+            let inventories = client.inventories();
+            let inventory = inventories.get(client.entity())?;
+            inventory
+                .equipped(target_slot(inventory, target)?)
+                .and_then(|item| item.heraldry())
+        },
+        HeraldryTarget::GuildBanner => client
+            .guild()
+            .and_then(|guild| guild.hall.as_ref())
+            .map(|hall| hall.banner),
+    }
+}
+
+/// Composes the design into a texture for the preview
+pub fn compose(heraldry: Heraldry) -> RgbaImage {
+    RgbaImage::from_fn(PREVIEW_WIDTH, PREVIEW_HEIGHT, |x, y| {
+        let uv = Vec2::new(
+            (x as f32 + 0.5) / PREVIEW_WIDTH as f32,
+            (y as f32 + 0.5) / PREVIEW_HEIGHT as f32,
+        );
+        let color = heraldry.color_at(uv);
+        Rgba([color.r, color.g, color.b, 255])
+    })
+}
+
+fn next<T: Copy + PartialEq>(all: &[T], current: T) -> T {
+    let index = all.iter().position(|t| *t == current).unwrap_or(0);
+    all[(index + 1) % all.len()]
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+widget_ids! {
+    pub struct Ids {
+        bg,
+        frame,
+        icon,
+        close,
+        title_align,
+        title,
+        target_btns[],
+        preview_frame,
+        preview,
+        pattern_txt,
+        pattern_btn,
+        emblem_txt,
+        emblem_btn,
+        field_txt,
+        field_swatches[],
+        charge_txt,
+        charge_swatches[],
+        emblem_color_txt,
+        emblem_swatches[],
+        info_txt,
+        apply_btn,
+        clear_btn,
+    }
+}
+
+pub enum Event {
+    Close,
+    /// The design in the designer changed
+    Change(Heraldry),
+    SetTarget(HeraldryTarget),
+    SetItemHeraldry(Slot, Option<Heraldry>),
+    Manip(GuildManip),
+}
+
+/// Composes heraldic designs and applies them to capes, shields or the banner
+/// of the player's guild
+#[derive(WidgetCommon)]
+pub struct HeraldryDesigner<'a> {
+    client: &'a Client,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    draft: Heraldry,
+    target: HeraldryTarget,
+    /// The draft composed into a texture, once it is ready
+    preview: Option<conrod_core::image::Id>,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> HeraldryDesigner<'a> {
+    pub fn new(
+        client: &'a Client,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        draft: Heraldry,
+        target: HeraldryTarget,
+        preview: Option<conrod_core::image::Id>,
+    ) -> Self {
+        Self {
+            client,
+            imgs,
+            fonts,
+            localized_strings,
+            draft,
+            target,
+            preview,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+
+    fn button(&self, label: &str, enabled: bool) -> Button<'a, widget::button::Image> {
+        Button::image(self.imgs.button)
+            .hover_image(if enabled {
+                self.imgs.button_hover
+            } else {
+                self.imgs.button
+            })
+            .press_image(if enabled {
+                self.imgs.button_press
+            } else {
+                self.imgs.button
+            })
+            .label(label)
+            .label_y(conrod_core::position::Relative::Scalar(2.0))
+            .label_color(if enabled { TEXT_COLOR } else { TEXT_COLOR_3 })
+            .image_color(if enabled { TEXT_COLOR } else { TEXT_COLOR_3 })
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_font_id(self.fonts.cyri.conrod_id)
+    }
+
+    fn text<'b>(&self, text: &'b str) -> Text<'b> {
+        Text::new(text)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+    }
+
+    /// A row of swatches for each tincture, returning the one that was clicked
+    fn swatches(
+        &self,
+        ids: &[widget::Id],
+        below: widget::Id,
+        selected: u8,
+        ui: &mut conrod_core::UiCell,
+    ) -> Option<u8> {
+        let mut clicked = None;
+        for (i, (r, g, b)) in TINCTURES.into_iter().enumerate() {
+            let size = if usize::from(selected) == i {
+                20.0
+            } else {
+                16.0
+            };
+            let button = Button::new().w_h(size, size).color(Color::Rgba(
+                r as f32 / 255.0,
+                g as f32 / 255.0,
+                b as f32 / 255.0,
+                1.0,
+            ));
+            let button = if i == 0 {
+                button.down_from(below, 6.0)
+            } else {
+                button.right_from(ids[i - 1], 6.0)
+            };
+            if button.set(ids[i], ui).was_clicked() {
+                clicked = Some(i as u8);
+            }
+        }
+        clicked
+    }
+}
+
+impl<'a> Widget for HeraldryDesigner<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        Self::State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("HeraldryDesigner::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut event = None;
+        let i18n = self.localized_strings;
+
+        if state.ids.target_btns.len() < HeraldryTarget::ALL.len()
+            || state.ids.field_swatches.len() < TINCTURES.len()
+        {
+            state.update(|s| {
+                let mut id_gen = ui.widget_id_generator();
+                s.ids
+                    .target_btns
+                    .resize(HeraldryTarget::ALL.len(), &mut id_gen);
+                s.ids.field_swatches.resize(TINCTURES.len(), &mut id_gen);
+                s.ids.charge_swatches.resize(TINCTURES.len(), &mut id_gen);
+                s.ids.emblem_swatches.resize(TINCTURES.len(), &mut id_gen);
+            });
+        }
+
+        // Window BG
+        Image::new(self.imgs.social_bg_on)
+            .bottom_left_with_margins_on(ui.window, 308.0, 315.0)
+            .color(Some(UI_MAIN))
+            .w_h(280.0, 460.0)
+            .set(state.ids.bg, ui);
+        // Window frame
+        Image::new(self.imgs.social_frame_on)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .w_h(280.0, 460.0)
+            .set(state.ids.frame, ui);
+
+        // Icon
+        Image::new(self.imgs.social)
+            .w_h(30.0, 30.0)
+            .top_left_with_margins_on(state.ids.frame, 6.0, 6.0)
+            .set(state.ids.icon, ui);
+        // X-Button
+        if Button::image(self.imgs.close_button)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.ids.frame, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        // Title
+        Rectangle::fill_with([212.0, 42.0], color::TRANSPARENT)
+            .top_left_with_margins_on(state.ids.frame, 2.0, 44.0)
+            .set(state.ids.title_align, ui);
+        Text::new(&i18n.get_msg("hud-heraldry"))
+            .middle_of(state.ids.title_align)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // What the design is for
+        for (i, target) in HeraldryTarget::ALL.into_iter().enumerate() {
+            let button = self
+                .button(&i18n.get_msg(target.key()), target == self.target)
+                .w_h(82.0, 24.0);
+            let button = if i == 0 {
+                button.top_left_with_margins_on(state.ids.frame, 52.0, 8.0)
+            } else {
+                button.right_from(state.ids.target_btns[i - 1], 4.0)
+            };
+            if button.set(state.ids.target_btns[i], ui).was_clicked() && target != self.target {
+                event = Some(Event::SetTarget(target));
+            }
+        }
+
+        // Preview
+        Rectangle::fill([
+            PREVIEW_WIDTH as f64 * 2.0 + 4.0,
+            PREVIEW_HEIGHT as f64 * 2.0 + 4.0,
+        ])
+        .color(Color::Rgba(0.0, 0.0, 0.0, 0.6))
+        .down_from(state.ids.target_btns[0], 10.0)
+        .set(state.ids.preview_frame, ui);
+        if let Some(preview) = self.preview {
+            Image::new(preview)
+                .w_h(PREVIEW_WIDTH as f64 * 2.0, PREVIEW_HEIGHT as f64 * 2.0)
+                .middle_of(state.ids.preview_frame)
+                .set(state.ids.preview, ui);
+        }
+
+        // Pattern and emblem, cycled through
+        let draft = self.draft;
+        self.text(&i18n.get_msg("hud-heraldry-pattern"))
+            .right_from(state.ids.preview_frame, 12.0)
+            .align_top_of(state.ids.preview_frame)
+            .set(state.ids.pattern_txt, ui);
+        if self
+            .button(
+                &i18n.get_msg(&format!("hud-heraldry-pattern-{}", draft.pattern.to_key())),
+                true,
+            )
+            .w_h(140.0, 24.0)
+            .down_from(state.ids.pattern_txt, 4.0)
+            .set(state.ids.pattern_btn, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Change(Heraldry {
+                pattern: next(&Pattern::ALL, draft.pattern),
+                ..draft
+            }));
+        }
+        self.text(&i18n.get_msg("hud-heraldry-emblem"))
+            .down_from(state.ids.pattern_btn, 10.0)
+            .set(state.ids.emblem_txt, ui);
+        if self
+            .button(
+                &i18n.get_msg(&format!("hud-heraldry-emblem-{}", draft.emblem.to_key())),
+                true,
+            )
+            .w_h(140.0, 24.0)
+            .down_from(state.ids.emblem_txt, 4.0)
+            .set(state.ids.emblem_btn, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Change(Heraldry {
+                emblem: next(&Emblem::ALL, draft.emblem),
+                ..draft
+            }));
+        }
+
+        // Tinctures of each part
+        self.text(&i18n.get_msg("hud-heraldry-field"))
+            .down_from(state.ids.preview_frame, 10.0)
+            .set(state.ids.field_txt, ui);
+        if let Some(field) = self.swatches(
+            &state.ids.field_swatches,
+            state.ids.field_txt,
+            draft.field,
+            ui,
+        ) {
+            event = Some(Event::Change(Heraldry { field, ..draft }));
+        }
+        self.text(&i18n.get_msg("hud-heraldry-charge"))
+            .down_from(state.ids.field_swatches[0], 8.0)
+            .set(state.ids.charge_txt, ui);
+        if let Some(charge) = self.swatches(
+            &state.ids.charge_swatches,
+            state.ids.charge_txt,
+            draft.charge,
+            ui,
+        ) {
+            event = Some(Event::Change(Heraldry { charge, ..draft }));
+        }
+        self.text(&i18n.get_msg("hud-heraldry-emblem_color"))
+            .down_from(state.ids.charge_swatches[0], 8.0)
+            .set(state.ids.emblem_color_txt, ui);
+        if let Some(emblem_tincture) = self.swatches(
+            &state.ids.emblem_swatches,
+            state.ids.emblem_color_txt,
+            draft.emblem_tincture,
+            ui,
+        ) {
+            event = Some(Event::Change(Heraldry {
+                emblem_tincture,
+                ..draft
+            }));
+        }
+
+        // Applying the design, where possible
+        let inventories = self.client.inventories();
+        let slot = inventories
+            .get(self.client.entity())
+            .and_then(|inventory| target_slot(inventory, self.target));
+        let can_apply = match self.target {
+            HeraldryTarget::Cape | HeraldryTarget::Shield => slot.is_some(),
+            HeraldryTarget::GuildBanner => {
+                let player_list = self.client.player_list();
+                let my_uuid = self
+                    .client
+                    .uid()
+                    .and_then(|uid| player_list.get(&uid))
+                    .map(|player| player.uuid);
+                self.client.guild().map_or(false, |guild| {
+                    guild.hall.is_some()
+                        && my_uuid
+                            .and_then(|uuid| guild.rank_of(uuid))
+                            .map_or(false, |rank| rank.can_manage_hall())
+                })
+            },
+        };
+        if !can_apply {
+            let info = match self.target {
+                HeraldryTarget::Cape => "hud-heraldry-no_cape",
+                HeraldryTarget::Shield => "hud-heraldry-no_shield",
+                HeraldryTarget::GuildBanner => "hud-heraldry-no_banner",
+            };
+            self.text(&i18n.get_msg(info))
+                .w(260.0)
+                .color(TEXT_COLOR_3)
+                .down_from(state.ids.emblem_swatches[0], 10.0)
+                .set(state.ids.info_txt, ui);
+        }
+        if self
+            .button(&i18n.get_msg("hud-heraldry-apply"), can_apply)
+            .w_h(106.0, 26.0)
+            .bottom_left_with_margins_on(state.ids.frame, 14.0, 20.0)
+            .set(state.ids.apply_btn, ui)
+            .was_clicked()
+            && can_apply
+        {
+            event = Some(match slot {
+                Some(slot) => Event::SetItemHeraldry(Slot::Equip(slot), Some(draft)),
+                None => Event::Manip(GuildManip::SetBanner(draft)),
+            });
+        }
+        // Guild banners always show a design, items can go without
+        if let Some(slot) = slot {
+            if self
+                .button(&i18n.get_msg("hud-heraldry-clear"), true)
+                .w_h(106.0, 26.0)
+                .bottom_right_with_margins_on(state.ids.frame, 14.0, 20.0)
+                .set(state.ids.clear_btn, ui)
+                .was_clicked()
+            {
+                event = Some(Event::SetItemHeraldry(Slot::Equip(slot), None));
+            }
+        }
+
+        event
+    }
+}
@@ -837,6 +837,7 @@ image_ids! {
         debuff_winded: "voxygen.element.de_buffs.debuff_winded",
         debuff_concussion: "voxygen.element.de_buffs.debuff_concussion",
         debuff_staggered: "voxygen.element.de_buffs.debuff_staggered",
+        debuff_electrified: "voxygen.element.skills.hammer.thunderclap",
 
         // Animation Frames
         // Buff Frame
@@ -0,0 +1,168 @@
+use super::{get_quality_col, img_ids::Imgs, util, TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN};
+use crate::ui::fonts::Fonts;
+use client::Client;
+use common::{
+    comp::inventory::item::ItemI18n,
+    loot_roll::{LootRollChoice, LootRollId},
+};
+use conrod_core::{
+    widget::{self, Button, Image, Rectangle, Text},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+
+widget_ids! {
+    pub struct Ids {
+        bg,
+        title,
+        item_name,
+        timeout_bg,
+        timeout,
+        btn_need,
+        btn_greed,
+        btn_pass,
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    Choose(LootRollId, LootRollChoice),
+}
+
+/// Asks the player to roll for the oldest item dropped for their group that
+/// they haven't made a choice for yet
+#[derive(WidgetCommon)]
+pub struct LootRoll<'a> {
+    client: &'a Client,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    item_i18n: &'a ItemI18n,
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> LootRoll<'a> {
+    pub fn new(
+        client: &'a Client,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        item_i18n: &'a ItemI18n,
+    ) -> Self {
+        Self {
+            client,
+            imgs,
+            fonts,
+            localized_strings,
+            item_i18n,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+impl<'a> Widget for LootRoll<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("LootRoll::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        // Rolls that timed out are passed by the server, so don't offer them anymore
+        let roll = self
+            .client
+            .loot_rolls()
+            .find(|roll| roll.received.elapsed() < roll.timeout)?;
+        let mut event = None;
+
+        // Frame, right above the group window
+        Rectangle::fill_with([220.0, 110.0], Color::Rgba(0.0, 0.0, 0.0, 0.8))
+            .bottom_left_with_margins_on(ui.window, 258.0, 490.0)
+            .set(state.ids.bg, ui);
+        Text::new(&self.localized_strings.get_msg("hud-loot_roll-title"))
+            .mid_top_with_margin_on(state.ids.bg, 5.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+        Text::new(&util::describe(
+            &roll.item,
+            self.localized_strings,
+            self.item_i18n,
+        ))
+        .mid_top_with_margin_on(state.ids.bg, 25.0)
+        .font_size(self.fonts.cyri.scale(12))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(get_quality_col(&roll.item))
+        .w(200.0)
+        .center_justify()
+        .set(state.ids.item_name, ui);
+
+        // Timeout bar
+        let timeout_progress =
+            1.0 - roll.received.elapsed().as_secs_f32() / roll.timeout.as_secs_f32();
+        Image::new(self.imgs.progress_frame)
+            .w_h(100.0, 10.0)
+            .mid_top_with_margin_on(state.ids.bg, 55.0)
+            .color(Some(UI_MAIN))
+            .set(state.ids.timeout_bg, ui);
+        Image::new(self.imgs.progress)
+            .w_h(98.0 * timeout_progress.max(0.0) as f64, 8.0)
+            .top_left_with_margins_on(state.ids.timeout_bg, 1.0, 1.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.timeout, ui);
+
+        // Choices
+        for (choice, key, id, x) in [
+            (
+                LootRollChoice::Need,
+                "hud-loot_roll-need",
+                state.ids.btn_need,
+                -70.0,
+            ),
+            (
+                LootRollChoice::Greed,
+                "hud-loot_roll-greed",
+                state.ids.btn_greed,
+                0.0,
+            ),
+            (
+                LootRollChoice::Pass,
+                "hud-loot_roll-pass",
+                state.ids.btn_pass,
+                70.0,
+            ),
+        ] {
+            if Button::image(self.imgs.button)
+                .w_h(65.0, 22.0)
+                .mid_bottom_with_margin_on(state.ids.bg, 10.0)
+                .x_relative_to(state.ids.bg, x)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .label(&self.localized_strings.get_msg(key))
+                .label_color(TEXT_COLOR)
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_font_size(self.fonts.cyri.scale(12))
+                .set(id, ui)
+                .was_clicked()
+            {
+                event = Some(Event::Choose(roll.id, choice));
+            }
+        }
+
+        event
+    }
+}
@@ -1,7 +1,10 @@
 use super::{
     img_ids::{Imgs, ImgsRot},
-    MapMarkers, QUALITY_COMMON, QUALITY_EPIC, QUALITY_HIGH, QUALITY_LOW, QUALITY_MODERATE, TEXT_BG,
-    TEXT_BLUE_COLOR, TEXT_COLOR, TEXT_GRAY_COLOR, TEXT_VELORITE, UI_HIGHLIGHT_0, UI_MAIN,
+    is_site_discovered,
+    map_annotation::{AnnotationIcon, MapAnnotation, STROKE_COLORS},
+    scout_ping_color, MapMarkers, QUALITY_COMMON, QUALITY_EPIC, QUALITY_HIGH, QUALITY_LOW,
+    QUALITY_MODERATE, TEXT_BG, TEXT_BLUE_COLOR, TEXT_COLOR, TEXT_GRAY_COLOR, TEXT_VELORITE,
+    UI_HIGHLIGHT_0, UI_MAIN,
 };
 use crate::{
     game_input::GameInput,
@@ -27,8 +30,9 @@ use conrod_core::{
     widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, UiCell, Widget, WidgetCommon,
 };
 use i18n::Localization;
-use specs::WorldExt;
+use specs::{Join, WorldExt};
 use std::borrow::Cow;
+use strum::IntoEnumIterator;
 use vek::*;
 use winit::event::MouseButton;
 
@@ -49,6 +53,7 @@ widget_ids! {
         qlog_title,
         zoom_slider,
         mmap_site_icons[],
+        shrine_icons[],
         mmap_poi_icons[],
         mmap_poi_title_bgs[],
         mmap_poi_titles[],
@@ -59,6 +64,7 @@ widget_ids! {
         member_height_indicators[],
         location_marker,
         location_marker_group[],
+        scout_pings[],
         map_settings_align,
         show_towns_img,
         show_towns_box,
@@ -104,7 +110,31 @@ widget_ids! {
         map_mode_overlay,
         minimap_mode_btn,
         minimap_mode_overlay,
+        annotation_title,
+        annotation_colors[],
+        annotation_icons[],
+        annotation_undo,
+        annotation_strokes[],
+        annotation_stamps[],
+        directions_route[],
+        directions_hint,
+    }
+}
 
+/// What clicking and dragging on the map does while annotating it
+#[derive(Clone, Copy, PartialEq)]
+enum AnnotationTool {
+    Draw([u8; 3]),
+    Stamp(AnnotationIcon),
+}
+
+fn annotation_icon_img(imgs: &Imgs, icon: AnnotationIcon) -> conrod_core::image::Id {
+    match icon {
+        AnnotationIcon::Flag => imgs.location_marker,
+        AnnotationIcon::Danger => imgs.skull,
+        AnnotationIcon::Camp => imgs.mmap_site_town,
+        AnnotationIcon::Cave => imgs.mmap_site_cave,
+        AnnotationIcon::Unknown => imgs.map_dif_unknown,
     }
 }
 
@@ -125,6 +155,8 @@ pub struct Map<'a> {
     tooltip_manager: &'a mut TooltipManager,
     location_markers: &'a MapMarkers,
     map_drag: Vec2<f64>,
+    /// Whether an NPC is asked the way to the town that gets clicked
+    asking_directions: bool,
 }
 impl<'a> Map<'a> {
     pub fn new(
@@ -139,6 +171,7 @@ impl<'a> Map<'a> {
         tooltip_manager: &'a mut TooltipManager,
         location_markers: &'a MapMarkers,
         map_drag: Vec2<f64>,
+        asking_directions: bool,
     ) -> Self {
         Self {
             imgs,
@@ -153,12 +186,16 @@ impl<'a> Map<'a> {
             tooltip_manager,
             location_markers,
             map_drag,
+            asking_directions,
         }
     }
 }
 
 pub struct State {
     ids: Ids,
+    annotation_tool: Option<AnnotationTool>,
+    /// Stroke that is currently being drawn
+    stroke: Vec<Vec2<i32>>,
 }
 
 pub enum Event {
@@ -168,6 +205,10 @@ pub enum Event {
     SetLocationMarker(Vec2<i32>),
     MapDrag(Vec2<f64>),
     RemoveMarker,
+    TravelToShrine(SiteId),
+    AskDirections(SiteId),
+    AddAnnotation(MapAnnotation),
+    UndoAnnotation,
 }
 
 fn get_site_economy(site_rich: &SiteInfoRich) -> String {
@@ -227,6 +268,8 @@ impl<'a> Widget for Map<'a> {
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             ids: Ids::new(id_gen),
+            annotation_tool: None,
+            stroke: Vec::new(),
         }
     }
 
@@ -369,6 +412,9 @@ impl<'a> Widget for Map<'a> {
         let max_drag = player_pos_chunks;
         let drag = self.map_drag.clamped(min_drag, max_drag);
 
+        // Dragging draws on the map instead of moving it while annotating
+        let annotating = state.annotation_tool.is_some();
+
         enum MarkerChange {
             Pos(Vec2<f32>),
             ClickPos,
@@ -427,6 +473,7 @@ impl<'a> Widget for Map<'a> {
                     .widget_input(widget)
                     .drags()
                     .left()
+                    .filter(|_| !annotating)
                     .map(|drag| Vec2::<f64>::from(drag.delta_xy))
                     .sum();
                 // Drag represents offset of view from the player_pos in chunk coords
@@ -444,6 +491,64 @@ impl<'a> Widget for Map<'a> {
             state.ids.map_layers[0],
         );
 
+        // Annotating
+        let rel_to_wpos = |xy: [f64; 2]| {
+            let cpos = Vec2::<f64>::from(xy) / zoom - drag;
+            (cpos.as_::<f32>().cpos_to_wpos() + player_pos.xy()).as_::<i32>()
+        };
+        match state.annotation_tool {
+            Some(AnnotationTool::Draw(color)) => {
+                let input = ui.widget_input(state.ids.map_layers[0]);
+                let drags = input.drags().left().collect::<Vec<_>>();
+                // A new stroke starts where the mouse was pressed
+                let dragged = drags
+                    .first()
+                    .filter(|_| state.stroke.is_empty())
+                    .map(|first| first.origin)
+                    .into_iter()
+                    .chain(drags.iter().map(|d| d.to))
+                    .map(rel_to_wpos)
+                    .collect::<Vec<_>>();
+                let released = input.releases().left().next().is_some();
+                // Skip points that are too close to show up as separate on the map
+                let min_dist = (2.0 / zoom * TerrainChunkSize::RECT_SIZE.x as f64) as i32;
+                if !dragged.is_empty() {
+                    state.update(|s| {
+                        for point in dragged {
+                            if s.stroke.last().map_or(true, |last| {
+                                last.distance_squared(point) >= min_dist.pow(2).max(1)
+                            }) {
+                                s.stroke.push(point);
+                            }
+                        }
+                    });
+                }
+                if released && !state.stroke.is_empty() {
+                    if state.stroke.len() > 1 {
+                        events.push(Event::AddAnnotation(MapAnnotation::Stroke {
+                            color,
+                            points: state.stroke.clone(),
+                        }));
+                    }
+                    state.update(|s| s.stroke.clear());
+                }
+            },
+            Some(AnnotationTool::Stamp(icon)) => {
+                if let Some(click) = ui
+                    .widget_input(state.ids.map_layers[0])
+                    .clicks()
+                    .left()
+                    .next()
+                {
+                    events.push(Event::AddAnnotation(MapAnnotation::Stamp {
+                        icon,
+                        pos: rel_to_wpos(click.xy),
+                    }));
+                }
+            },
+            None => {},
+        }
+
         let rect_src = position::Rect::from_xy_dim(
             [
                 (player_pos.x as f64 / TerrainChunkSize::RECT_SIZE.x as f64) - drag.x,
@@ -834,6 +939,84 @@ impl<'a> Widget for Map<'a> {
                 .color(TEXT_COLOR)
                 .set(state.ids.show_voxel_map_text, ui);
         }
+        // Annotation tools
+        Text::new(&i18n.get_msg("hud-map-annotate"))
+            .down_from(state.ids.show_peaks_img, 15.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.annotation_title, ui);
+        let icons = AnnotationIcon::iter().collect::<Vec<_>>();
+        if state.ids.annotation_colors.len() < STROKE_COLORS.len()
+            || state.ids.annotation_icons.len() < icons.len()
+        {
+            state.update(|s| {
+                s.ids
+                    .annotation_colors
+                    .resize(STROKE_COLORS.len(), &mut ui.widget_id_generator());
+                s.ids
+                    .annotation_icons
+                    .resize(icons.len(), &mut ui.widget_id_generator());
+            });
+        }
+        let mut selected_tool = None;
+        for (i, color) in STROKE_COLORS.into_iter().enumerate() {
+            let selected = state.annotation_tool == Some(AnnotationTool::Draw(color));
+            let size = if selected { 18.0 } else { 14.0 };
+            let button = Button::new().w_h(size, size).color(Color::Rgba(
+                color[0] as f32 / 255.0,
+                color[1] as f32 / 255.0,
+                color[2] as f32 / 255.0,
+                1.0,
+            ));
+            let button = if i == 0 {
+                button.down_from(state.ids.annotation_title, 8.0)
+            } else {
+                button.right_from(state.ids.annotation_colors[i - 1], 8.0)
+            };
+            if button.set(state.ids.annotation_colors[i], ui).was_clicked() {
+                selected_tool = Some((!selected).then_some(AnnotationTool::Draw(color)));
+            }
+        }
+        for (i, icon) in icons.into_iter().enumerate() {
+            let selected = state.annotation_tool == Some(AnnotationTool::Stamp(icon));
+            let button = Button::image(annotation_icon_img(self.imgs, icon))
+                .w_h(20.0, 20.0)
+                .image_color(if selected {
+                    UI_HIGHLIGHT_0
+                } else {
+                    TEXT_GRAY_COLOR
+                });
+            let button = if i == 0 {
+                button.down_from(state.ids.annotation_colors[0], 8.0)
+            } else {
+                button.right_from(state.ids.annotation_icons[i - 1], 6.0)
+            };
+            if button.set(state.ids.annotation_icons[i], ui).was_clicked() {
+                selected_tool = Some((!selected).then_some(AnnotationTool::Stamp(icon)));
+            }
+        }
+        if let Some(tool) = selected_tool {
+            state.update(|s| {
+                s.annotation_tool = tool;
+                s.stroke.clear();
+            });
+        }
+        if Button::image(self.imgs.button)
+            .w_h(92.0, 24.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .down_from(state.ids.annotation_icons[0], 8.0)
+            .label(&i18n.get_msg("hud-map-annotation-undo"))
+            .label_y(position::Relative::Scalar(1.0))
+            .label_color(TEXT_COLOR)
+            .label_font_size(self.fonts.cyri.scale(12))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .set(state.ids.annotation_undo, ui)
+            .was_clicked()
+        {
+            events.push(Event::UndoAnnotation);
+        }
         // Map icons
         if state.ids.mmap_poi_icons.len() < self.client.pois().len() {
             state.update(|state| {
@@ -898,8 +1081,138 @@ impl<'a> Widget for Map<'a> {
                 }
             };
 
+        // Annotations drawn by the player, including the stroke that is being drawn,
+        // and the ones shared by the group
+        let current_stroke = match state.annotation_tool {
+            Some(AnnotationTool::Draw(color)) if !state.stroke.is_empty() => {
+                Some(MapAnnotation::Stroke {
+                    color,
+                    points: state.stroke.clone(),
+                })
+            },
+            _ => None,
+        };
+        let annotations = self
+            .global_state
+            .profile
+            .get_map_annotations(&self.client.server_info().name)
+            .iter()
+            .chain(current_stroke.iter())
+            .map(|annotation| (annotation, 1.0))
+            .chain(
+                self.location_markers
+                    .group_annotations
+                    .values()
+                    .flatten()
+                    .map(|annotation| (annotation, 0.6)),
+            );
+        let map_center = Vec2::from(ui.xy_of(state.ids.map_layers[0]).unwrap_or([0.0; 2]));
+        let mut strokes = Vec::new();
+        let mut stamps = Vec::new();
+        for (annotation, alpha) in annotations {
+            match annotation {
+                MapAnnotation::Stroke { color, points } => {
+                    let color = Color::Rgba(
+                        color[0] as f32 / 255.0,
+                        color[1] as f32 / 255.0,
+                        color[2] as f32 / 255.0,
+                        alpha,
+                    );
+                    // Only draw the parts of the stroke that are on the map
+                    let mut part = Vec::new();
+                    for point in points {
+                        if let Some((rpos, _)) = wpos_to_rpos_fade(point.as_(), Vec2::zero(), 1.0) {
+                            part.push((map_center + rpos.as_::<f64>()).into_array());
+                        } else if part.len() > 1 {
+                            strokes.push((std::mem::take(&mut part), color));
+                        } else {
+                            part.clear();
+                        }
+                    }
+                    if part.len() > 1 {
+                        strokes.push((part, color));
+                    }
+                },
+                MapAnnotation::Stamp { icon, pos } => {
+                    if let Some((rpos, fade)) =
+                        wpos_to_rpos_fade(pos.as_(), Vec2::broadcast(10.0), 10.0)
+                    {
+                        stamps.push((rpos, *icon, fade * alpha));
+                    }
+                },
+            }
+        }
+        if state.ids.annotation_strokes.len() < strokes.len()
+            || state.ids.annotation_stamps.len() < stamps.len()
+        {
+            state.update(|s| {
+                let strokes_len = s.ids.annotation_strokes.len().max(strokes.len());
+                s.ids
+                    .annotation_strokes
+                    .resize(strokes_len, &mut ui.widget_id_generator());
+                let stamps_len = s.ids.annotation_stamps.len().max(stamps.len());
+                s.ids
+                    .annotation_stamps
+                    .resize(stamps_len, &mut ui.widget_id_generator());
+            });
+        }
+        for (i, (points, color)) in strokes.into_iter().enumerate() {
+            widget::PointPath::abs(points)
+                .color(color)
+                .thickness(2.0)
+                .graphics_for(state.ids.map_layers[0])
+                .set(state.ids.annotation_strokes[i], ui);
+        }
+        for (i, (rpos, icon, fade)) in stamps.into_iter().enumerate() {
+            Image::new(annotation_icon_img(self.imgs, icon))
+                .x_y_position_relative_to(
+                    state.ids.map_layers[0],
+                    position::Relative::Scalar(rpos.x as f64),
+                    position::Relative::Scalar(rpos.y as f64),
+                )
+                .w_h(20.0, 20.0)
+                .color(Some(UI_HIGHLIGHT_0.alpha(fade)))
+                .graphics_for(state.ids.map_layers[0])
+                .set(state.ids.annotation_stamps[i], ui);
+        }
+
+        // The rest of the way to the site an NPC gave directions to
+        let mut route_parts = Vec::new();
+        if let Some(directions) = self.client.directions() {
+            let mut part = Vec::new();
+            for point in core::iter::once(player_pos.xy()).chain(directions.route.iter().copied()) {
+                if let Some((rpos, _)) = wpos_to_rpos_fade(point, Vec2::zero(), 1.0) {
+                    part.push((map_center + rpos.as_::<f64>()).into_array());
+                } else if part.len() > 1 {
+                    route_parts.push(std::mem::take(&mut part));
+                } else {
+                    part.clear();
+                }
+            }
+            if part.len() > 1 {
+                route_parts.push(part);
+            }
+        }
+        if state.ids.directions_route.len() < route_parts.len() {
+            state.update(|s| {
+                s.ids
+                    .directions_route
+                    .resize(route_parts.len(), &mut ui.widget_id_generator())
+            });
+        }
+        for (i, points) in route_parts.into_iter().enumerate() {
+            widget::PointPath::abs(points)
+                .color(TEXT_VELORITE)
+                .thickness(3.0)
+                .graphics_for(state.ids.map_layers[0])
+                .set(state.ids.directions_route[i], ui);
+        }
+
         for (i, site_rich) in self.client.sites().values().enumerate() {
             let site = &site_rich.site;
+            if !is_site_discovered(self.client, self.global_state, site) {
+                continue;
+            }
 
             let rside = zoom as f32 * 8.0 * 1.2;
 
@@ -931,6 +1244,7 @@ impl<'a> Widget for Map<'a> {
                         SiteKind::Cultist => i18n.get_msg("hud-map-cultist"),
                         SiteKind::Sahagin => i18n.get_msg("hud-map-sahagin"),
                         SiteKind::DwarvenMine => i18n.get_msg("hud-map-df_mine"),
+                        SiteKind::ClimbingRoute => i18n.get_msg("hud-map-climbing_route"),
                     });
             let (difficulty, desc) = match &site.kind {
                 SiteKind::Town => (None, i18n.get_msg("hud-map-town")),
@@ -963,6 +1277,7 @@ impl<'a> Widget for Map<'a> {
                 SiteKind::Cultist => (Some(5), i18n.get_msg("hud-map-cultist")),
                 SiteKind::Sahagin => (Some(2), i18n.get_msg("hud-map-sahagin")),
                 SiteKind::DwarvenMine => (Some(5), i18n.get_msg("hud-map-df_mine")),
+                SiteKind::ClimbingRoute => (None, i18n.get_msg("hud-map-climbing_route")),
             };
             let desc = desc.into_owned() + &get_site_economy(site_rich);
             let site_btn = Button::image(match &site.kind {
@@ -984,6 +1299,7 @@ impl<'a> Widget for Map<'a> {
                     _ => self.imgs.mmap_site_dungeon,
                 },
                 SiteKind::Bridge => self.imgs.mmap_site_bridge,
+                SiteKind::ClimbingRoute => self.imgs.mmap_poi_peak,
             })
             .x_y_position_relative_to(
                 state.ids.map_layers[0],
@@ -1010,6 +1326,7 @@ impl<'a> Widget for Map<'a> {
                     _ => self.imgs.mmap_site_dungeon_hover,
                 },
                 SiteKind::Bridge => self.imgs.mmap_site_bridge_hover,
+                SiteKind::ClimbingRoute => self.imgs.mmap_poi_peak_hover,
             })
             .image_color(UI_HIGHLIGHT_0.alpha(fade))
             .with_tooltip(
@@ -1059,12 +1376,19 @@ impl<'a> Widget for Map<'a> {
                 | SiteKind::Terracotta
                 | SiteKind::Adlet => show_dungeons,
                 SiteKind::Castle => show_castles,
-                SiteKind::Cave => show_caves,
+                SiteKind::Cave | SiteKind::ClimbingRoute => show_caves,
                 SiteKind::Tree => show_trees,
                 SiteKind::Bridge => show_bridges,
             };
             if show_site {
-                let tooltip_visible = site_btn.set_ext(state.ids.mmap_site_icons[i], ui).1;
+                let (clicked, tooltip_visible) = site_btn.set_ext(state.ids.mmap_site_icons[i], ui);
+
+                if self.asking_directions
+                    && matches!(site.kind, SiteKind::Town)
+                    && clicked.was_clicked()
+                {
+                    events.push(Event::AskDirections(site.id));
+                }
 
                 if SHOW_ECONOMY && tooltip_visible && site_rich.economy.is_none() {
                     events.push(Event::RequestSiteInfo(site.id));
@@ -1136,7 +1460,7 @@ impl<'a> Widget for Map<'a> {
                             dif_img.set(state.ids.site_difs[i], ui)
                         }
                     },
-                    SiteKind::Cave => {
+                    SiteKind::Cave | SiteKind::ClimbingRoute => {
                         if show_caves {
                             dif_img.set(state.ids.site_difs[i], ui)
                         }
@@ -1261,8 +1585,86 @@ impl<'a> Widget for Map<'a> {
                 },
             }
         }
-        // Group member indicators
+        // Waypoint shrines the character unlocked, they can be travelled to while
+        // standing at one of them
         let client_state = self.client.state();
+        let unlocked_shrines = client_state
+            .ecs()
+            .read_storage::<comp::UnlockedShrines>()
+            .get(self.client.entity())
+            .cloned()
+            .unwrap_or_default();
+        let current_shrine = {
+            let positions = client_state.ecs().read_storage::<comp::Pos>();
+            let objects = client_state.ecs().read_storage::<comp::Object>();
+            (&positions, &objects)
+                .join()
+                .find_map(|(pos, object)| match object {
+                    comp::Object::Shrine { site }
+                        if pos.0.distance_squared(player_pos) <= comp::SHRINE_RANGE.powi(2) =>
+                    {
+                        Some((*site, pos.0))
+                    },
+                    _ => None,
+                })
+        };
+        let shrines = unlocked_shrines.iter().collect::<Vec<_>>();
+        if state.ids.shrine_icons.len() < shrines.len() {
+            state.update(|s| {
+                s.ids
+                    .shrine_icons
+                    .resize(shrines.len(), &mut ui.widget_id_generator())
+            });
+        }
+        for (i, (site, shrine_pos)) in shrines.into_iter().enumerate() {
+            let side_length = zoom as f32 * 5.0;
+            let (rpos, fade) = match wpos_to_rpos_fade(
+                shrine_pos.xy(),
+                Vec2::from(side_length / 2.0),
+                side_length / 2.0,
+            ) {
+                Some(x) => x,
+                None => continue,
+            };
+            let title = self
+                .client
+                .sites()
+                .get(&site)
+                .and_then(|site_rich| site_rich.site.name.clone())
+                .map_or_else(|| i18n.get_msg("hud-map-shrine"), Cow::Owned);
+            let desc = match current_shrine {
+                Some((current, _)) if current == site => i18n.get_msg("hud-map-shrine-here"),
+                Some((_, current_pos)) => {
+                    i18n.get_msg_ctx("hud-map-shrine-travel", &i18n::fluent_args! {
+                        "cost" => comp::shrine_travel_cost(current_pos, shrine_pos)
+                    })
+                },
+                None => i18n.get_msg("hud-map-shrine-visit"),
+            };
+            let clicked = Button::image(self.imgs.mmap_site_excl)
+                .x_y_position_relative_to(
+                    state.ids.map_layers[0],
+                    position::Relative::Scalar(rpos.x as f64),
+                    position::Relative::Scalar(rpos.y as f64),
+                )
+                .w_h(side_length as f64, side_length as f64)
+                .image_color(TEXT_VELORITE.alpha(fade))
+                .floating(true)
+                .with_tooltip(
+                    self.tooltip_manager,
+                    &title,
+                    &desc,
+                    &site_tooltip,
+                    TEXT_VELORITE,
+                )
+                .set(state.ids.shrine_icons[i], ui)
+                .was_clicked();
+            if clicked && current_shrine.map_or(false, |(current, _)| current != site) {
+                events.push(Event::TravelToShrine(site));
+            }
+        }
+
+        // Group member indicators
         let stats = client_state.ecs().read_storage::<comp::Stats>();
         let member_pos = client_state.ecs().read_storage::<comp::Pos>();
         let group_members = self
@@ -1404,6 +1806,34 @@ impl<'a> Widget for Map<'a> {
                 );
             }
         }
+        // What a scouting bird companion spotted
+        let scout_pings = self.location_markers.scout_pings().collect::<Vec<_>>();
+        if state.ids.scout_pings.len() < scout_pings.len() {
+            state.update(|s| {
+                s.ids
+                    .scout_pings
+                    .resize(scout_pings.len(), &mut ui.widget_id_generator())
+            })
+        };
+        for (i, (ping, age_fade)) in scout_pings.into_iter().enumerate() {
+            let side_length = 12.0 * factor;
+            let Some((rpos, fade)) =
+                wpos_to_rpos_fade(ping.wpos, Vec2::from(side_length / 2.0), side_length / 2.0)
+            else {
+                continue;
+            };
+            Image::new(self.imgs.indicator_group)
+                .x_y_position_relative_to(
+                    state.ids.map_layers[0],
+                    position::Relative::Scalar(rpos.x as f64),
+                    position::Relative::Scalar(rpos.y as f64),
+                )
+                .w_h(side_length as f64, side_length as f64)
+                .image_color(scout_ping_color(ping.kind).alpha(fade * age_fade))
+                .floating(true)
+                .set(state.ids.scout_pings[i], ui);
+        }
+
         // Location marker
         if let Some((lm, (rpos, fade))) = self.location_markers.owned.and_then(|lm| {
             let lm = lm.as_();
@@ -1558,6 +1988,16 @@ impl<'a> Widget for Map<'a> {
             .color(TEXT_COLOR)
             .set(state.ids.waypoint_txt, ui);
 
+        if self.asking_directions {
+            Text::new(&i18n.get_msg("hud-map-ask_directions"))
+                .mid_top_with_margin_on(state.ids.map_layers[0], 10.0)
+                .font_size(self.fonts.cyri.scale(18))
+                .font_id(self.fonts.cyri.conrod_id)
+                .graphics_for(state.ids.map_layers[0])
+                .color(TEXT_VELORITE)
+                .set(state.ids.directions_hint, ui);
+        }
+
         // Show topographic map
         if Button::image(self.imgs.button)
             .w_h(92.0, icon_size.y)
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+use vek::*;
+
+/// Prefix of group chat messages that carry a shared annotation rather than
+/// text meant to be read
+pub const SHARED_ANNOTATION_PREFIX: &str = "#map-annotation ";
+/// Group chat messages can't be longer than this, longer strokes are split
+/// into several messages
+const MAX_SHARED_LEN: usize = 256;
+
+/// Icons that can be stamped on the map
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum AnnotationIcon {
+    Flag,
+    Danger,
+    Camp,
+    Cave,
+    Unknown,
+}
+
+impl AnnotationIcon {
+    fn keyword(&self) -> &'static str {
+        match self {
+            AnnotationIcon::Flag => "flag",
+            AnnotationIcon::Danger => "danger",
+            AnnotationIcon::Camp => "camp",
+            AnnotationIcon::Cave => "cave",
+            AnnotationIcon::Unknown => "unknown",
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        Self::iter().find(|icon| icon.keyword() == keyword)
+    }
+}
+
+/// Something the player drew on their world map, in world coordinates
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MapAnnotation {
+    /// Free-drawn line through `points`
+    Stroke {
+        color: [u8; 3],
+        points: Vec<Vec2<i32>>,
+    },
+    Stamp {
+        icon: AnnotationIcon,
+        pos: Vec2<i32>,
+    },
+}
+
+/// Colours strokes can be drawn with
+pub const STROKE_COLORS: [[u8; 3]; 4] = [[200, 40, 40], [40, 80, 200], [40, 160, 60], [30, 30, 30]];
+
+/// Serializes annotations to share them with others, e.g. as a file
+pub fn export(annotations: &[MapAnnotation]) -> Result<String, String> {
+    ron::ser::to_string_pretty(annotations, ron::ser::PrettyConfig::default())
+        .map_err(|err| format!("Failed to export map annotations: {err}"))
+}
+
+pub fn import(exported: &str) -> Result<Vec<MapAnnotation>, String> {
+    ron::from_str(exported).map_err(|err| format!("Failed to import map annotations: {err}"))
+}
+
+/// Encodes an annotation as group chat messages, strokes that are too long to
+/// fit in one message are split into several connected strokes
+pub fn encode_shared(annotation: &MapAnnotation) -> Vec<String> {
+    match annotation {
+        MapAnnotation::Stamp { icon, pos } => vec![format!(
+            "{SHARED_ANNOTATION_PREFIX}I {} {},{}",
+            icon.keyword(),
+            pos.x,
+            pos.y
+        )],
+        MapAnnotation::Stroke { color, points } => {
+            let header = format!(
+                "{SHARED_ANNOTATION_PREFIX}S {:02x}{:02x}{:02x}",
+                color[0], color[1], color[2]
+            );
+            let mut messages = Vec::new();
+            let mut msg = header.clone();
+            let mut last = None;
+            for point in points {
+                let encoded = format!(" {},{}", point.x, point.y);
+                if msg.len() + encoded.len() > MAX_SHARED_LEN {
+                    messages.push(std::mem::replace(&mut msg, header.clone()));
+                    // Continue from the previous point so that the parts stay connected
+                    if let Some(last) = &last {
+                        msg.push_str(last);
+                    }
+                }
+                msg.push_str(&encoded);
+                last = Some(encoded);
+            }
+            messages.push(msg);
+            messages
+        },
+    }
+}
+
+/// Decodes a group chat message sent by [`encode_shared`], returns `None` if
+/// the message isn't a shared annotation
+pub fn decode_shared(msg: &str) -> Option<MapAnnotation> {
+    let mut parts = msg.strip_prefix(SHARED_ANNOTATION_PREFIX)?.split(' ');
+    let parse_pos = |pos: &str| {
+        let (x, y) = pos.split_once(',')?;
+        Some(Vec2::new(x.parse().ok()?, y.parse().ok()?))
+    };
+    match parts.next()? {
+        "I" => Some(MapAnnotation::Stamp {
+            icon: AnnotationIcon::from_keyword(parts.next()?)?,
+            pos: parse_pos(parts.next()?)?,
+        }),
+        "S" => {
+            let color = u32::from_str_radix(parts.next()?, 16).ok()?;
+            Some(MapAnnotation::Stroke {
+                color: [(color >> 16) as u8, (color >> 8) as u8, color as u8],
+                points: parts.map(parse_pos).collect::<Option<_>>()?,
+            })
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_annotations_roundtrip() {
+        let stamp = MapAnnotation::Stamp {
+            icon: AnnotationIcon::Danger,
+            pos: Vec2::new(1200, -5),
+        };
+        assert_eq!(
+            encode_shared(&stamp)
+                .iter()
+                .map(|msg| decode_shared(msg))
+                .collect::<Vec<_>>(),
+            [Some(stamp)]
+        );
+
+        let points = (0..100)
+            .map(|i| Vec2::new(i * 10, 30000 - i))
+            .collect::<Vec<_>>();
+        let stroke = MapAnnotation::Stroke {
+            color: STROKE_COLORS[1],
+            points: points.clone(),
+        };
+        let messages = encode_shared(&stroke);
+        assert!(messages.len() > 1);
+        assert!(messages.iter().all(|msg| msg.len() <= MAX_SHARED_LEN));
+        let mut decoded = Vec::new();
+        for msg in &messages {
+            let Some(MapAnnotation::Stroke { color, points }) = decode_shared(msg) else {
+                panic!("Failed to decode {msg}");
+            };
+            assert_eq!(color, STROKE_COLORS[1]);
+            // Every part starts where the previous one ended
+            if let Some(last) = decoded.last().copied() {
+                assert_eq!(points.first(), Some(&last));
+                decoded.extend_from_slice(&points[1..]);
+            } else {
+                decoded = points;
+            }
+        }
+        assert_eq!(decoded, points);
+    }
+
+    #[test]
+    fn chat_is_not_an_annotation() {
+        assert_eq!(decode_shared("hello"), None);
+        assert_eq!(
+            decode_shared(&format!("{SHARED_ANNOTATION_PREFIX}S zz 1,2")),
+            None
+        );
+    }
+}
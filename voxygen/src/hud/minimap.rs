@@ -1,7 +1,8 @@
 use super::{
     img_ids::{Imgs, ImgsRot},
-    MapMarkers, QUALITY_COMMON, QUALITY_DEBUG, QUALITY_EPIC, QUALITY_HIGH, QUALITY_LOW,
-    QUALITY_MODERATE, TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
+    is_site_discovered, scout_ping_color, MapMarkers, QUALITY_COMMON, QUALITY_DEBUG, QUALITY_EPIC,
+    QUALITY_HIGH, QUALITY_LOW, QUALITY_MODERATE, TEXT_COLOR, TEXT_VELORITE, UI_HIGHLIGHT_0,
+    UI_MAIN,
 };
 use crate::{
     hud::{Graphic, Ui},
@@ -32,11 +33,21 @@ use specs::WorldExt;
 use std::sync::Arc;
 use vek::*;
 
+#[derive(Clone, Copy)]
+struct MinimapPixel {
+    color: Rgba<u8>,
+    /// Whether the block at the z-level of the layer is solid
+    filled: bool,
+    /// How far below the z-level of the layer the visible surface is, or
+    /// `u8::MAX` if there is none
+    depth: u8,
+}
+
 struct MinimapColumn {
     /// Coordinate of lowest z-slice
     zlo: i32,
     /// Z-slices of colors and filled-ness
-    layers: Vec<Grid<(Rgba<u8>, bool)>>,
+    layers: Vec<Grid<MinimapPixel>>,
     /// Color and filledness above the highest layer
     above: (Rgba<u8>, bool),
     /// Color and filledness below the lowest layer
@@ -49,10 +60,14 @@ pub struct VoxelMinimap {
     image_id: img_ids::Rotations,
     last_pos: Vec3<i32>,
     last_ceiling: i32,
+    last_cave_mode: bool,
     keyed_jobs: KeyedJobs<Vec2<i32>, MinimapColumn>,
 }
 
 const VOXEL_MINIMAP_SIDELENGTH: u32 = 256;
+/// How far below the surface the player has to be for the minimap to switch to
+/// the cave view.
+const CAVE_MODE_DEPTH: f32 = 8.0;
 
 impl VoxelMinimap {
     pub fn new(ui: &mut Ui) -> Self {
@@ -70,6 +85,7 @@ impl VoxelMinimap {
             composited,
             last_pos: Vec3::zero(),
             last_ceiling: 0,
+            last_cave_mode: false,
             keyed_jobs: KeyedJobs::new("IMAGE_PROCESSING"),
         }
     }
@@ -84,7 +100,7 @@ impl VoxelMinimap {
     }
 
     /// Each layer is a slice of the terrain near that z-level
-    fn composite_layer_slice(chunk: &TerrainChunk, layers: &mut Vec<Grid<(Rgba<u8>, bool)>>) {
+    fn composite_layer_slice(chunk: &TerrainChunk, layers: &mut Vec<Grid<MinimapPixel>>) {
         for z in chunk.get_min_z()..=chunk.get_max_z() {
             let grid = Grid::populate_from(Vec2::new(32, 32), |v| {
                 let mut rgba = Rgba::<f32>::zero();
@@ -98,17 +114,22 @@ impl VoxelMinimap {
                     rgba += color.as_() * *weight as f32;
                 }
                 let rgba: Rgba<u8> = (rgba / weights.iter().map(|x| *x as f32).sum::<f32>()).as_();
-                (rgba, true)
+                MinimapPixel {
+                    color: rgba,
+                    filled: true,
+                    depth: 0,
+                }
             });
             layers.push(grid);
         }
     }
 
     /// Each layer is the overhead as if its z-level were the ceiling
-    fn composite_layer_overhead(chunk: &TerrainChunk, layers: &mut Vec<Grid<(Rgba<u8>, bool)>>) {
+    fn composite_layer_overhead(chunk: &TerrainChunk, layers: &mut Vec<Grid<MinimapPixel>>) {
         for z in chunk.get_min_z()..=chunk.get_max_z() {
             let grid = Grid::populate_from(Vec2::new(32, 32), |v| {
                 let mut rgba = None;
+                let mut depth = u8::MAX;
 
                 let mut seen_solids: u32 = 0;
                 let mut seen_air: u32 = 0;
@@ -120,6 +141,7 @@ impl VoxelMinimap {
                     {
                         if seen_air > 0 {
                             rgba = Some(color);
+                            depth = (dz - chunk.get_min_z()).clamp(0, u8::MAX as i32) as u8;
                             break;
                         }
                         seen_solids += 1;
@@ -144,12 +166,39 @@ impl VoxelMinimap {
                         )
                 });
                 let rgba = rgba.unwrap_or_else(|| Rgba::new(0, 0, 0, 255));
-                (rgba, is_filled)
+                MinimapPixel {
+                    color: rgba,
+                    filled: is_filled,
+                    depth,
+                }
             });
-            layers.push(grid);
+            layers.push(Self::shade_slopes(grid));
         }
     }
 
+    /// Shade slopes as if lit from the north-west, so that the shape of the
+    /// terrain can be made out.
+    fn shade_slopes(grid: Grid<MinimapPixel>) -> Grid<MinimapPixel> {
+        Grid::populate_from(grid.size(), |v| {
+            let pixel = grid.get(v).copied().unwrap();
+            let neighbor = grid
+                .get(v + Vec2::new(-1, 1))
+                .filter(|n| n.depth != u8::MAX)
+                .map_or(pixel.depth, |n| n.depth);
+            if pixel.depth == u8::MAX {
+                return pixel;
+            }
+            let shade = (1.0 - (pixel.depth as f32 - neighbor as f32) * 0.1).clamp(0.6, 1.3);
+            MinimapPixel {
+                color: (pixel.color.rgb().as_::<f32>() * shade)
+                    .map(|e| e.min(255.0))
+                    .as_::<u8>()
+                    .with_a(pixel.color.a),
+                ..pixel
+            }
+        })
+    }
+
     fn add_chunks_near(
         &mut self,
         pool: &SlowJobPool,
@@ -215,6 +264,23 @@ impl VoxelMinimap {
         });
     }
 
+    /// Shade a pixel by the height of its surface relative to the player.
+    fn shade_height(pixel: MinimapPixel, rel_z: i32, cave_mode: bool) -> Rgba<u8> {
+        if cave_mode && pixel.filled {
+            // Walls of the cave
+            return Rgba::new(48, 48, 48, 255);
+        } else if pixel.depth == u8::MAX {
+            return pixel.color;
+        }
+        // Caves are shaded more strongly, so that drops and ledges stand out
+        let range = if cave_mode { 16.0 } else { 96.0 };
+        let shade = 1.0 + (rel_z as f32 / range).clamp(-1.0, 1.0) * 0.3;
+        (pixel.color.rgb().as_::<f32>() * shade)
+            .map(|e| e.min(255.0))
+            .as_::<u8>()
+            .with_a(pixel.color.a)
+    }
+
     pub fn maintain(&mut self, client: &Client, ui: &mut Ui) {
         let player = client.entity();
         let pos = if let Some(pos) = client.state().ecs().read_storage::<comp::Pos>().get(player) {
@@ -261,7 +327,7 @@ impl VoxelMinimap {
                                 layers
                                     .get((pos.z as i32 - zlo + dz) as usize)
                                     .and_then(|grid| grid.get(cmod))
-                                    .map_or(false, |(_, b)| *b)
+                                    .map_or(false, |p| p.filled)
                             })
                             .unwrap_or_else(||
                                 // if the `find` returned None, there's no solid blocks above the
@@ -282,13 +348,23 @@ impl VoxelMinimap {
                 )
                 .unwrap_or(0)
         };
+        // Underground, show a slice of the terrain at the player's height instead,
+        // since the overhead view of caves is mostly made up of their ceilings
+        let cave_mode = ceiling_offset != i32::MAX
+            && client
+                .current_chunk()
+                .map_or(false, |chunk| pos.z < chunk.meta().alt() - CAVE_MODE_DEPTH);
         if self.last_pos.xy() != cpos
             || self.last_pos.z != pos.z as i32
             || self.last_ceiling != ceiling_offset
+            || self.last_cave_mode != cave_mode
             || new_chunks
         {
             self.last_pos = cpos.with_z(pos.z as i32);
             self.last_ceiling = ceiling_offset;
+            self.last_cave_mode = cave_mode;
+            // The z-level to show, relative to the player
+            let view_offset = if cave_mode { 1 } else { ceiling_offset };
             for y in 0..VOXEL_MINIMAP_SIDELENGTH {
                 for x in 0..VOXEL_MINIMAP_SIDELENGTH {
                     let voff = Vec2::new(x as f32, y as f32);
@@ -307,7 +383,7 @@ impl VoxelMinimap {
                                 above,
                                 below,
                             } = column;
-                            if (pos.z as i32).saturating_add(ceiling_offset) < *zlo {
+                            if (pos.z as i32).saturating_add(view_offset) < *zlo {
                                 // If the ceiling is below the bottom of a chunk, color it black,
                                 // so that the middles of caves/dungeons don't show the forests
                                 // around them.
@@ -317,15 +393,22 @@ impl VoxelMinimap {
                                 // the ceiling's height (using the top slice of the chunk if the
                                 // ceiling is above the chunk, (e.g. so that forests with
                                 // differently-tall trees are handled properly)
+                                let layer = (((pos.z as i32 - zlo).saturating_add(view_offset))
+                                    as usize)
+                                    .min(layers.len().saturating_sub(1));
                                 // TODO: evaluate clippy, toolchain upgrade 2021-12-19
                                 #[allow(clippy::unnecessary_lazy_evaluations)]
                                 layers
-                                    .get(
-                                        (((pos.z as i32 - zlo).saturating_add(ceiling_offset))
-                                            as usize)
-                                            .min(layers.len().saturating_sub(1)),
-                                    )
-                                    .and_then(|grid| grid.get(cmod).map(|c| c.0.as_()))
+                                    .get(layer)
+                                    .and_then(|grid| grid.get(cmod))
+                                    .map(|pixel| {
+                                        let surface_z = zlo + layer as i32 - pixel.depth as i32;
+                                        Self::shade_height(
+                                            *pixel,
+                                            surface_z - pos.z as i32,
+                                            cave_mode,
+                                        )
+                                    })
                                     .or_else(|| {
                                         Some(if pos.z as i32 > *zlo {
                                             above.0
@@ -376,6 +459,8 @@ widget_ids! {
         member_indicators[],
         location_marker,
         location_marker_group[],
+        scout_pings[],
+        route_breadcrumbs[],
         voxel_minimap,
     }
 }
@@ -691,6 +776,9 @@ impl<'a> Widget for MiniMap<'a> {
 
             for (i, site_rich) in self.client.sites().values().enumerate() {
                 let site = &site_rich.site;
+                if !is_site_discovered(self.client, self.global_state, site) {
+                    continue;
+                }
 
                 let rpos = match wpos_to_rpos(site.wpos.map(|e| e as f32), false) {
                     Some(rpos) => rpos,
@@ -711,6 +799,7 @@ impl<'a> Widget for MiniMap<'a> {
                     SiteKind::Haniwa => Some(3),
                     SiteKind::Cultist => Some(5),
                     SiteKind::DwarvenMine => Some(5),
+                    SiteKind::ClimbingRoute => None,
                 };
 
                 Image::new(match &site.kind {
@@ -728,6 +817,7 @@ impl<'a> Widget for MiniMap<'a> {
                     SiteKind::Cultist => self.imgs.mmap_site_cultist_bg,
                     SiteKind::Sahagin => self.imgs.mmap_site_sahagin_bg,
                     SiteKind::DwarvenMine => self.imgs.mmap_site_mine_bg,
+                    SiteKind::ClimbingRoute => self.imgs.mmap_poi_peak,
                 })
                 .x_y_position_relative_to(
                     state.ids.map_layers[0],
@@ -760,6 +850,7 @@ impl<'a> Widget for MiniMap<'a> {
                     SiteKind::Cultist => self.imgs.mmap_site_cultist,
                     SiteKind::Sahagin => self.imgs.mmap_site_sahagin,
                     SiteKind::DwarvenMine => self.imgs.mmap_site_mine,
+                    SiteKind::ClimbingRoute => self.imgs.mmap_poi_peak,
                 })
                 .middle_of(state.ids.mmap_site_icons_bgs[i])
                 .w_h(20.0, 20.0)
@@ -850,6 +941,58 @@ impl<'a> Widget for MiniMap<'a> {
                 }
             }
 
+            // What a scouting bird companion spotted
+            let scout_pings = self.location_markers.scout_pings().collect::<Vec<_>>();
+            if state.ids.scout_pings.len() < scout_pings.len() {
+                state.update(|s| {
+                    s.ids
+                        .scout_pings
+                        .resize(scout_pings.len(), &mut ui.widget_id_generator())
+                })
+            };
+            for (i, (ping, fade)) in scout_pings.into_iter().enumerate() {
+                if let Some(rpos) = wpos_to_rpos(ping.wpos, false) {
+                    Image::new(self.imgs.indicator_group)
+                        .x_y_position_relative_to(
+                            state.ids.map_layers[0],
+                            position::Relative::Scalar(rpos.x as f64),
+                            position::Relative::Scalar(rpos.y as f64),
+                        )
+                        .w_h(10.0, 10.0)
+                        .image_color(scout_ping_color(ping.kind).alpha(fade))
+                        .parent(ui.window)
+                        .set(state.ids.scout_pings[i], ui)
+                }
+            }
+
+            // Breadcrumbs along the way an NPC gave directions to, the next one is
+            // always shown at the edge of the minimap
+            let route = self
+                .client
+                .directions()
+                .map_or(&[][..], |directions| &directions.route);
+            if state.ids.route_breadcrumbs.len() < route.len() {
+                state.update(|s| {
+                    s.ids
+                        .route_breadcrumbs
+                        .resize(route.len(), &mut ui.widget_id_generator())
+                })
+            };
+            for (i, point) in route.iter().enumerate() {
+                if let Some(rpos) = wpos_to_rpos(*point, i == 0) {
+                    Image::new(self.imgs.indicator_group)
+                        .x_y_position_relative_to(
+                            state.ids.map_layers[0],
+                            position::Relative::Scalar(rpos.x as f64),
+                            position::Relative::Scalar(rpos.y as f64),
+                        )
+                        .w_h(8.0, 8.0)
+                        .image_color(TEXT_VELORITE)
+                        .parent(ui.window)
+                        .set(state.ids.route_breadcrumbs[i], ui)
+                }
+            }
+
             // Location marker
             if let Some(rpos) = self
                 .location_markers
@@ -1,15 +1,23 @@
 #![allow(non_local_definitions)] // because of WidgetCommon derive
 mod animation;
 mod bag;
+mod bank;
 mod buffs;
 mod buttons;
+mod calendar;
 mod change_notification;
 mod chat;
 mod crafting;
 mod diary;
+mod emote_wheel;
 mod esc_menu;
 mod group;
+mod guild;
+mod guild_hall;
+mod guild_storage;
+mod heraldry;
 mod hotbar;
+mod loot_roll;
 mod loot_scroller;
 mod map;
 mod minimap;
@@ -18,48 +26,65 @@ mod overitem;
 mod popup;
 mod prompt_dialog;
 mod quest;
+mod quick_bar;
+mod quick_wheel;
 mod settings_window;
 mod skillbar;
 mod slots;
 mod social;
 mod subtitles;
 mod trade;
+mod trainer;
 
 pub mod img_ids;
 pub mod item_imgs;
+pub mod map_annotation;
 pub mod util;
 
 pub use crafting::CraftingTab;
 pub use hotbar::{SlotContents as HotbarSlotContents, State as HotbarState};
 pub use item_imgs::animate_by_pulse;
 pub use loot_scroller::LootMessage;
+pub use map_annotation::MapAnnotation;
+pub use quick_bar::{SlotContents as QuickBarSlotContents, State as QuickBarState, QUICK_SLOTS};
 pub use settings_window::ScaleChange;
 pub use subtitles::Subtitle;
 
 use bag::Bag;
+use bank::Bank;
 use buffs::BuffsBar;
 use buttons::Buttons;
+use calendar::Calendar;
 use change_notification::{ChangeNotification, NotificationReason};
 use chat::Chat;
 use chrono::NaiveTime;
 use crafting::Crafting;
 use diary::{Diary, SelectedSkillTree};
+use emote_wheel::EmoteWheel;
 use esc_menu::EscMenu;
 use group::Group;
+use guild::Guild;
+use guild_hall::GuildHall;
+use guild_storage::GuildStorage;
+use heraldry::{HeraldryDesigner, HeraldryTarget};
 use img_ids::Imgs;
 use item_imgs::ItemImgs;
+use loot_roll::LootRoll;
 use loot_scroller::LootScroller;
 use map::Map;
 use minimap::{MiniMap, VoxelMinimap};
 use popup::Popup;
 use prompt_dialog::PromptDialog;
 use quest::Quest;
+use quick_bar::QuickBar;
+use quick_wheel::QuickWheel;
 use serde::{Deserialize, Serialize};
 use settings_window::{SettingsTab, SettingsWindow};
 use skillbar::Skillbar;
 use social::Social;
 use subtitles::Subtitles;
 use trade::Trade;
+use trainer::Trainer;
 
 use crate::{
     cmd::get_player_uuid,
@@ -69,8 +94,12 @@ use crate::{
     },
     game_input::GameInput,
     hud::{img_ids::ImgsRot, prompt_dialog::DialogOutcomeEvent},
+    profile::TradeRecord,
     render::UiDrawer,
-    scene::camera::{self, Camera},
+    scene::{
+        camera::{self, Camera},
+        terrain::TerrainMemoryStats,
+    },
     session::{
         interactable::{BlockInteraction, Interactable},
         settings_change::{
@@ -86,11 +115,13 @@ use crate::{
 };
 use client::Client;
 use common::{
+    bounty::BountyTarget,
     combat,
     comp::{
         self,
         ability::{AuxiliaryAbility, Stance},
         fluid_dynamics,
+        group::LootMode,
         inventory::{
             slot::{InvSlotId, Slot},
             trade_pricing::TradePricing,
@@ -101,26 +132,35 @@ use common::{
             ItemDefinitionIdOwned, ItemDesc, ItemI18n, MaterialStatManifest, Quality,
         },
         loot_owner::LootOwnerKind,
-        pet::is_mountable,
+        pet::{can_scout, is_mountable},
         skillset::{skills::Skill, SkillGroupKind, SkillsPersistenceError},
-        BuffData, BuffKind, Content, Health, Item, MapMarkerChange, PickupItem, PresenceKind,
+        BuffData, BuffKind, Content, Health, Item, LocalizationArg, MapMarkerChange, PickupItem,
+        PresenceKind,
     },
-    consts::MAX_PICKUP_RANGE,
+    consts::{MAX_NPCINTERACT_RANGE, MAX_PICKUP_RANGE},
+    guild::{GuildManip, GUILD_HALL_RANGE},
+    heraldry::Heraldry,
     link::Is,
+    loot_roll::{LootRollChoice, LootRollId},
+    minigame::Scoreboard,
     mounting::{Mount, Rider, VolumePos},
     outcome::Outcome,
     recipe::RecipeBookManifest,
     resources::{ProgramTime, Secs, Time},
     slowjob::SlowJobPool,
+    states::emote::EmoteKind,
     terrain::{SpriteKind, TerrainChunk, UnlockKind},
-    trade::{ReducedInventory, TradeAction},
+    trade::{ReducedInventory, TradeAction, TradePhase},
     uid::Uid,
     util::{srgba_to_linear, Dir},
     vol::RectRasterableVol,
 };
 use common_base::{prof_span, span};
 use common_net::{
-    msg::{world_msg::SiteId, Notification},
+    msg::{
+        world_msg::{SiteId, SiteInfo, SiteKind, TreasureMapSnippet},
+        Notification,
+    },
     sync::WorldSyncExt,
 };
 use conrod_core::{
@@ -130,6 +170,7 @@ use conrod_core::{
 };
 use hashbrown::{HashMap, HashSet};
 use i18n::Localization;
+use image::{DynamicImage, RgbaImage};
 use rand::Rng;
 use specs::{Entity as EcsEntity, Join, LendJoin, WorldExt};
 use std::{
@@ -195,6 +236,8 @@ const SAY_COLOR: Color = Color::Rgba(1.0, 0.8, 0.8, 1.0);
 const GROUP_COLOR: Color = Color::Rgba(0.47, 0.84, 1.0, 1.0);
 /// Color for factional chat
 const FACTION_COLOR: Color = Color::Rgba(0.24, 1.0, 0.48, 1.0);
+/// Color for guild chat
+const GUILD_COLOR: Color = Color::Rgba(1.0, 0.84, 0.35, 1.0);
 /// Color for regional chat
 const REGION_COLOR: Color = Color::Rgba(0.8, 1.0, 0.8, 1.0);
 /// Color for death messagesw
@@ -228,6 +271,17 @@ const NAMETAG_DMG_RANGE: f32 = 120.0;
 const SPEECH_BUBBLE_RANGE: f32 = NAMETAG_RANGE;
 const EXP_FLOATER_LIFETIME: f32 = 2.0;
 const EXP_ACCUMULATION_DURATION: f32 = 0.5;
+/// How long what a scouting bird companion spotted stays on the map
+const SCOUT_PING_DURATION: Duration = Duration::from_secs(90);
+
+/// The colour of what a scouting bird companion spotted, on the map
+fn scout_ping_color(kind: comp::ScoutPingKind) -> Color {
+    match kind {
+        comp::ScoutPingKind::Creature => Color::Rgba(0.9, 0.9, 0.6, 1.0),
+        comp::ScoutPingKind::Hostile => ENEMY_HP_COLOR,
+        comp::ScoutPingKind::Site => TEXT_VELORITE,
+    }
+}
 
 // TODO: Don't hard code this
 pub fn default_water_color() -> Rgba<f32> { srgba_to_linear(Rgba::new(0.0, 0.18, 0.37, 1.0)) }
@@ -255,6 +309,9 @@ widget_ids! {
         sct_lvl,
         hurt_bg,
         death_bg,
+        downed_txt,
+        downed_timer_txt,
+        vario_txt,
         sct_bgs[],
         scts[],
 
@@ -279,6 +336,7 @@ widget_ids! {
         time,
         entity_count,
         num_chunks,
+        terrain_memory,
         num_lights,
         num_figures,
         num_particles,
@@ -320,6 +378,8 @@ widget_ids! {
         prompt_dialog,
         bag,
         trade,
+        bank,
+        trainer,
         social,
         quest,
         diary,
@@ -327,12 +387,21 @@ widget_ids! {
         buttons,
         buffs,
         esc_menu,
+        emote_wheel,
+        quick_bar,
+        quick_wheel,
         small_window,
         social_window,
+        guild_window,
+        guild_hall_window,
+        guild_storage,
+        heraldry_window,
         quest_window,
+        calendar_window,
         crafting_window,
         settings_window,
         group_window,
+        loot_roll,
         item_info,
         subtitles,
 
@@ -367,6 +436,10 @@ widget_ids! {
         tut_arrow,
         tut_arrow_txt_bg,
         tut_arrow_txt,
+
+        // Treasure map
+        treasure_map,
+        treasure_map_close,
     }
 }
 
@@ -566,6 +639,8 @@ pub struct BuffIcon {
     kind: BuffIconKind,
     is_buff: bool,
     end_time: Option<f64>,
+    /// Damage per second, if the buff deals damage over time
+    dot_rate: Option<f32>,
 }
 
 impl BuffIcon {
@@ -584,6 +659,40 @@ impl BuffIcon {
         }
     }
 
+    /// Total damage the buff will still deal until it runs out
+    pub fn get_remaining_damage(&self, time: Time) -> Option<f32> {
+        let remaining = self.end_time? - time.0;
+        Some(self.dot_rate? * remaining.max(0.0) as f32)
+    }
+
+    /// Whether the debuff is dangerous enough to warn about, like being frozen
+    /// or bleeding away a large part of the remaining health
+    pub fn is_dangerous(&self, time: Time, health: &comp::Health) -> bool {
+        /// Fraction of the current health that a debuff must still deal
+        const DANGEROUS_DAMAGE: f32 = 0.25;
+
+        match self.kind {
+            BuffIconKind::Buff {
+                kind: BuffKind::Frozen,
+                ..
+            } => true,
+            _ => {
+                !self.is_buff
+                    && self.get_remaining_damage(time).map_or(false, |damage| {
+                        damage >= health.current() * DANGEROUS_DAMAGE
+                    })
+            },
+        }
+    }
+
+    /// The pinnable kind of the buff, stances can't be pinned
+    pub fn buff_kind(&self) -> Option<BuffKind> {
+        match self.kind {
+            BuffIconKind::Buff { kind, .. } => Some(kind),
+            BuffIconKind::Stance(_) => None,
+        }
+    }
+
     pub fn icons_vec(buffs: &comp::Buffs, stance: Option<&comp::Stance>) -> Vec<Self> {
         buffs
             .iter_active()
@@ -602,6 +711,7 @@ impl BuffIcon {
             kind: BuffIconKind::Stance(*stance),
             is_buff: true,
             end_time: None,
+            dot_rate: None,
         })
     }
 
@@ -618,6 +728,7 @@ impl BuffIcon {
             },
             is_buff: buff.kind.is_buff(),
             end_time: buff.end_time.map(|end| end.0),
+            dot_rate: buff.dot_damage_rate(),
         })
     }
 }
@@ -661,6 +772,7 @@ pub struct DebugInfo {
     pub num_lights: u32,
     pub num_visible_chunks: u32,
     pub num_shadow_chunks: u32,
+    pub terrain_memory: TerrainMemoryStats,
     pub num_figures: u32,
     pub num_figures_visible: u32,
     pub num_particles: u32,
@@ -705,7 +817,9 @@ pub enum Event {
     SplitDropSlot(comp::slot::Slot),
     SortInventory,
     ChangeHotbarState(Box<HotbarState>),
+    ChangeQuickBarState(Box<QuickBarState>),
     TradeAction(TradeAction),
+    Haggle,
     Ability(usize, bool),
     Logout,
     Quit,
@@ -735,22 +849,50 @@ pub enum Event {
         sprite_pos: VolumePos,
     },
     InviteMember(Uid),
+    InviteToGuild(Uid),
+    GuildManip(GuildManip),
+    SetHeraldry(Slot, Option<Heraldry>),
+    SelectTitle(Option<comp::Title>),
     AcceptInvite,
     DeclineInvite,
     KickMember(Uid),
     LeaveGroup,
     AssignLeader(Uid),
+    SetGroupLootMode(LootMode),
+    LootRoll(LootRollId, LootRollChoice),
     RemoveBuff(BuffKind),
     LeaveStance,
+    Emote(EmoteKind),
+    BankDeposit {
+        banker: Uid,
+        slot: InvSlotId,
+    },
+    BankWithdraw {
+        banker: Uid,
+        slot: usize,
+    },
+    BankUpgrade(Uid),
+    LearnSkill {
+        trainer: Uid,
+        skill: Skill,
+    },
     UnlockSkill(Skill),
     SelectExpBar(Option<SkillGroupKind>),
 
     RequestSiteInfo(SiteId),
+    RequestReputation,
+    TravelToShrine(SiteId),
+    AskDirections(EcsEntity, SiteId),
+    AcceptBounty(BountyTarget),
+    CloseNoticeBoard,
     ChangeAbility(usize, AuxiliaryAbility),
+    SwitchAbilityLoadout(usize),
 
     SettingsChange(SettingsChange),
     AcknowledgePersistenceLoadError,
     MapMarkerEvent(MapMarkerChange),
+    AddMapAnnotation(MapAnnotation),
+    UndoMapAnnotation,
 }
 
 // TODO: Are these the possible layouts we want?
@@ -857,6 +999,20 @@ impl PressBehavior {
 pub struct MapMarkers {
     owned: Option<Vec2<i32>>,
     group: HashMap<Uid, Vec2<i32>>,
+    /// Map annotations shared by group members
+    group_annotations: HashMap<Uid, Vec<MapAnnotation>>,
+    /// What a bird companion spotted while out scouting, and when
+    scout_pings: Vec<(comp::ScoutPing, Instant)>,
+}
+
+impl MapMarkers {
+    /// Scout pings that have yet to fade, along with how faded they are
+    pub fn scout_pings(&self) -> impl Iterator<Item = (&comp::ScoutPing, f32)> + '_ {
+        self.scout_pings.iter().filter_map(|(ping, spotted)| {
+            let age = spotted.elapsed().as_secs_f32() / SCOUT_PING_DURATION.as_secs_f32();
+            (age < 1.0).then_some((ping, 1.0 - age))
+        })
+    }
 }
 
 /// (target slot, input value, inventory quantity, is our inventory, error,
@@ -897,14 +1053,31 @@ pub struct Show {
     bag_details: bool,
     trade: bool,
     trade_details: bool,
+    trade_history: bool,
+    /// The banker whose bank is open, if any
+    bank: Option<Uid>,
+    /// The trainer whose skills are listed, if any
+    trainer: Option<Uid>,
     social: bool,
     diary: bool,
     group: bool,
+    guild: bool,
+    /// The notice board of the guild hall that is being read, if any
+    guild_hall: Option<Vec3<i32>>,
+    guild_storage: bool,
+    /// What the heraldry designer applies its design to, while it is open
+    heraldry: Option<HeraldryTarget>,
+    heraldry_draft: Heraldry,
     quest: bool,
+    calendar: bool,
     group_menu: bool,
     esc_menu: bool,
+    emote_wheel: bool,
+    quick_wheel: bool,
     open_windows: Windows,
     map: bool,
+    /// The NPC that is asked the way to the site picked on the map, if any
+    asking_directions: Option<EcsEntity>,
     ingame: bool,
     chat_tab_settings_index: Option<usize>,
     settings_tab: SettingsTab,
@@ -930,6 +1103,8 @@ impl Show {
 
             if !open {
                 self.crafting = false;
+                self.bank = None;
+                self.guild_storage = false;
             }
 
             self.want_grab = !self.any_window_requires_cursor();
@@ -945,14 +1120,61 @@ impl Show {
         }
     }
 
-    fn map(&mut self, open: bool) {
+    fn bank(&mut self, banker: Option<Uid>) {
+        if !self.esc_menu {
+            self.bag = banker.is_some();
+            self.bank = banker;
+            self.map = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn guild_storage(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.bag = open;
+            self.guild_storage = open;
+            self.map = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn heraldry(&mut self, target: Option<HeraldryTarget>, draft: Option<Heraldry>) {
+        if !self.esc_menu {
+            self.heraldry = target;
+            if let Some(draft) = draft {
+                self.heraldry_draft = draft;
+            }
+            self.map = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn guild_hall(&mut self, board: Option<Vec3<i32>>) {
+        if !self.esc_menu {
+            self.guild_hall = board;
+            self.map = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn trainer(&mut self, trainer: Option<Uid>) {
+        if !self.esc_menu {
+            self.trainer = trainer;
+            self.map = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    pub fn map(&mut self, open: bool) {
         if !self.esc_menu {
             self.map = open;
             self.bag = false;
             self.crafting = false;
             self.crafting_fields.salvage = false;
             self.social = false;
+            self.guild = false;
             self.quest = false;
+            self.calendar = false;
             self.diary = false;
             self.want_grab = !self.any_window_requires_cursor();
         }
@@ -970,9 +1192,31 @@ impl Show {
         }
     }
 
+    fn guild(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.guild = open;
+            self.quest = false;
+            self.calendar = false;
+            self.diary = false;
+            self.map = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
     fn quest(&mut self, open: bool) {
         if !self.esc_menu {
             self.quest = open;
+            self.guild = false;
+            self.diary = false;
+            self.map = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn calendar(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.calendar = open;
+            self.guild = false;
             self.diary = false;
             self.map = false;
             self.want_grab = !self.any_window_requires_cursor();
@@ -1015,7 +1259,9 @@ impl Show {
     fn diary(&mut self, open: bool) {
         if !self.esc_menu {
             self.social = false;
+            self.guild = false;
             self.quest = false;
+            self.calendar = false;
             self.crafting = false;
             self.crafting_fields.salvage = false;
             self.bag = false;
@@ -1026,6 +1272,20 @@ impl Show {
         }
     }
 
+    fn emote_wheel(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.emote_wheel = open;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn quick_wheel(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.quick_wheel = open;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
     fn settings(&mut self, open: bool) {
         if !self.esc_menu {
             self.open_windows = if open {
@@ -1035,7 +1295,9 @@ impl Show {
             };
             self.bag = false;
             self.social = false;
+            self.guild = false;
             self.quest = false;
+            self.calendar = false;
             self.crafting = false;
             self.crafting_fields.salvage = false;
             self.diary = false;
@@ -1047,6 +1309,10 @@ impl Show {
 
     fn toggle_map(&mut self) { self.map(!self.map) }
 
+    fn toggle_emote_wheel(&mut self) { self.emote_wheel(!self.emote_wheel) }
+
+    fn toggle_quick_wheel(&mut self) { self.quick_wheel(!self.quick_wheel) }
+
     fn toggle_social(&mut self) { self.social(!self.social); }
 
     fn toggle_crafting(&mut self) { self.crafting(!self.crafting) }
@@ -1080,14 +1346,23 @@ impl Show {
     fn any_window_requires_cursor(&self) -> bool {
         self.bag
             || self.trade
+            || self.bank.is_some()
+            || self.guild_storage
+            || self.guild_hall.is_some()
+            || self.heraldry.is_some()
+            || self.trainer.is_some()
             || self.esc_menu
             || self.map
             || self.social
+            || self.guild
             || self.crafting
             || self.diary
             || self.help
             || self.intro
             || self.quest
+            || self.calendar
+            || self.emote_wheel
+            || self.quick_wheel
             || !matches!(self.open_windows, Windows::None)
     }
 
@@ -1095,14 +1370,23 @@ impl Show {
         if self.any_window_requires_cursor() {
             self.bag = false;
             self.trade = false;
+            self.bank = None;
+            self.guild_storage = false;
+            self.guild_hall = None;
+            self.heraldry = None;
+            self.trainer = None;
             self.esc_menu = false;
             self.help = false;
             self.intro = false;
             self.map = false;
             self.social = false;
+            self.guild = false;
             self.quest = false;
+            self.calendar = false;
             self.diary = false;
             self.crafting = false;
+            self.emote_wheel = false;
+            self.quick_wheel = false;
             self.open_windows = Windows::None;
             self.want_grab = true;
 
@@ -1162,9 +1446,31 @@ impl Show {
             },
             comp::MapMarkerUpdate::ClearGroup => {
                 self.location_markers.group.clear();
+                self.location_markers.group_annotations.clear();
             },
         }
     }
+
+    pub fn add_scout_pings(&mut self, pings: Vec<comp::ScoutPing>) {
+        let now = Instant::now();
+        let scout_pings = &mut self.location_markers.scout_pings;
+        // Fresh sightings replace older ones of the same thing
+        scout_pings.retain(|(old, spotted)| {
+            now.duration_since(*spotted) < SCOUT_PING_DURATION
+                && !pings.iter().any(|new| {
+                    new.kind == old.kind && new.wpos.distance_squared(old.wpos) < 16.0_f32.powi(2)
+                })
+        });
+        scout_pings.extend(pings.into_iter().map(|ping| (ping, now)));
+    }
+
+    pub fn add_group_annotation(&mut self, user: Uid, annotation: MapAnnotation) {
+        self.location_markers
+            .group_annotations
+            .entry(user)
+            .or_default()
+            .push(annotation);
+    }
 }
 
 pub struct PromptDialogSettings {
@@ -1292,12 +1598,20 @@ pub struct Hud {
     hp_pulse: f32,
     slot_manager: slots::SlotManager,
     hotbar: hotbar::State,
+    quick_bar: quick_bar::State,
     events: Vec<Event>,
     crosshair_opacity: f32,
+    /// Smoothed vertical speed shown while gliding
+    vario: f32,
     floaters: Floaters,
     voxel_minimap: VoxelMinimap,
     map_drag: Vec2<f64>,
     force_chat: bool,
+    trade_summary: Option<TradeRecord>,
+    /// The last treasure map read and whether it's still open
+    treasure_map: Option<(conrod_core::image::Id, bool)>,
+    /// The draft of the heraldry designer, composed into a texture
+    heraldry_preview: Option<(conrod_core::image::Id, Heraldry)>,
 }
 
 impl Hud {
@@ -1346,6 +1660,11 @@ impl Hud {
         // Create a new HotbarState from the persisted slots.
         let hotbar_state =
             HotbarState::new(global_state.profile.get_hotbar_slots(server, character_id));
+        let quick_bar_state = QuickBarState::new(
+            global_state
+                .profile
+                .get_quick_bar_slots(server, character_id),
+        );
 
         let slot_manager = slots::SlotManager::new(
             ui.id_generator(),
@@ -1388,7 +1707,12 @@ impl Hud {
                 bag_details: false,
                 trade: false,
                 trade_details: false,
+                trade_history: false,
+                bank: None,
+                trainer: None,
                 esc_menu: false,
+                emote_wheel: false,
+                quick_wheel: false,
                 open_windows: Windows::None,
                 map: false,
                 crafting: false,
@@ -1396,9 +1720,16 @@ impl Hud {
                 social: false,
                 diary: false,
                 group: false,
+                guild: false,
+                guild_hall: None,
+                guild_storage: false,
+                heraldry: None,
+                heraldry_draft: Heraldry::default(),
                 // Change this before implementation!
                 quest: false,
+                calendar: false,
                 group_menu: false,
+                asking_directions: None,
                 chat_tab_settings_index: None,
                 settings_tab: SettingsTab::Interface,
                 diary_fields: diary::DiaryShow::default(),
@@ -1425,8 +1756,10 @@ impl Hud {
             hp_pulse: 0.0,
             slot_manager,
             hotbar: hotbar_state,
+            quick_bar: quick_bar_state,
             events: Vec::new(),
             crosshair_opacity: 0.0,
+            vario: 0.0,
             floaters: Floaters {
                 exp_floaters: Vec::new(),
                 skill_point_displays: Vec::new(),
@@ -1435,6 +1768,9 @@ impl Hud {
             },
             map_drag: Vec2::zero(),
             force_chat: false,
+            trade_summary: None,
+            treasure_map: None,
+            heraldry_preview: None,
         }
     }
 
@@ -1508,6 +1844,8 @@ impl Hud {
             let is_riders = ecs.read_storage::<Is<Rider>>();
             let stances = ecs.read_storage::<comp::Stance>();
             let char_activities = ecs.read_storage::<comp::CharacterActivity>();
+            let char_states = ecs.read_storage::<comp::CharacterState>();
+            let vels = ecs.read_storage::<comp::Vel>();
             let time = ecs.read_resource::<Time>();
 
             // Check if there was a persistence load error of the skillset, and if so
@@ -1587,7 +1925,51 @@ impl Hud {
                         .graphics_for(ui_widgets.window)
                         .color(Some(Color::Rgba(0.0, 0.0, 0.0, 1.0)))
                         .set(self.ids.death_bg, ui_widgets);
-                } // Crosshair
+                }
+                // Downed Frame
+                if let Some(comp::CharacterState::Downed(downed)) = char_states.get(me) {
+                    Text::new(&i18n.get_msg("hud-downed"))
+                        .mid_top_with_margin_on(ui_widgets.window, 200.0)
+                        .font_size(self.fonts.cyri.scale(40))
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .color(CRITICAL_HP_COLOR)
+                        .set(self.ids.downed_txt, ui_widgets);
+                    Text::new(
+                        &i18n.get_msg_ctx("hud-downed-bleed_out", &i18n::fluent_args! {
+                            "seconds" => downed.remaining().as_secs()
+                        }),
+                    )
+                    .mid_bottom_with_margin_on(self.ids.downed_txt, -30.0)
+                    .font_size(self.fonts.cyri.scale(20))
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .color(TEXT_COLOR)
+                    .set(self.ids.downed_timer_txt, ui_widgets);
+                }
+                // Vario, tells gliders whether they are climbing in a thermal or sinking
+                if let (Some(comp::CharacterState::Glide(_)), Some(vel)) =
+                    (char_states.get(me), vels.get(me))
+                {
+                    self.vario = Lerp::lerp(self.vario, vel.0.z, 3.0 * dt.as_secs_f32());
+                    let color = if self.vario > 0.5 {
+                        HP_COLOR
+                    } else if self.vario < -5.0 {
+                        CRITICAL_HP_COLOR
+                    } else {
+                        TEXT_COLOR
+                    };
+                    Text::new(&i18n.get_msg_ctx("hud-vario", &i18n::fluent_args! {
+                        "rate" => format!("{:+.1}", self.vario),
+                    }))
+                    .mid_bottom_with_margin_on(ui_widgets.window, 280.0)
+                    .x_relative_to(ui_widgets.window, 80.0)
+                    .font_size(self.fonts.cyri.scale(16))
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .color(color)
+                    .set(self.ids.vario_txt, ui_widgets);
+                } else {
+                    self.vario = 0.0;
+                }
+                // Crosshair
                 let show_crosshair = (info.is_aiming || info.is_first_person) && !health.is_dead;
                 self.crosshair_opacity = Lerp::lerp(
                     self.crosshair_opacity,
@@ -2158,10 +2540,16 @@ impl Hud {
                         };
                         vec![(Some(GameInput::Mount), i18n.get_msg(key).to_string())]
                     },
-                    BlockInteraction::Read(_) => vec![(
+                    BlockInteraction::Read(_)
+                    | BlockInteraction::NoticeBoard
+                    | BlockInteraction::GuildBoard => vec![(
                         Some(GameInput::Interact),
                         i18n.get_msg("hud-read").to_string(),
                     )],
+                    BlockInteraction::GuildStorage => vec![(
+                        Some(GameInput::Interact),
+                        i18n.get_msg("hud-open").to_string(),
+                    )],
                     // TODO: change to turn on/turn off?
                     BlockInteraction::LightToggle(enable) => vec![(
                         Some(GameInput::Interact),
@@ -2234,13 +2622,21 @@ impl Hud {
                     .set(overitem_id, ui_widgets);
                 }
             } else if let Some(Interactable::Entity(entity)) = interactable {
-                // show hud for campfires and portals
+                // show hud for campfires, portals, waypoint shrines and gravestones
+                let object = client
+                    .state()
+                    .ecs()
+                    .read_storage::<comp::Object>()
+                    .get(*entity)
+                    .copied();
+                let is_shrine = matches!(object, Some(comp::Object::Shrine { .. }));
+                let is_gravestone = matches!(object, Some(comp::Object::Gravestone { .. }));
                 if let Some(body) = client
                     .state()
                     .ecs()
                     .read_storage::<comp::Body>()
                     .get(*entity)
-                    .filter(|b| b.is_campfire() || b.is_portal())
+                    .filter(|b| b.is_campfire() || b.is_portal() || is_shrine || is_gravestone)
                 {
                     let overitem_id = overitem_walker.next(
                         &mut self.ids.overitems,
@@ -2264,6 +2660,10 @@ impl Hud {
                             "hud-crafting-campfire"
                         } else if body.is_portal() {
                             "hud-portal"
+                        } else if is_shrine {
+                            "hud-shrine"
+                        } else if is_gravestone {
+                            "hud-gravestone"
                         } else {
                             "hud-use"
                         }),
@@ -2279,8 +2679,10 @@ impl Hud {
                             Some(GameInput::Interact),
                             i18n.get_msg(if body.is_campfire() {
                                 "hud-sit"
-                            } else if body.is_portal() {
+                            } else if body.is_portal() || is_shrine {
                                 "hud-activate"
+                            } else if is_gravestone {
+                                "hud-recover"
                             } else {
                                 "hud-use"
                             })
@@ -2370,29 +2772,52 @@ impl Hud {
                         // group
                         // Note: even if this passes the healthbar can
                         // be hidden in some cases if it is at maximum
+                        let is_focused = info.target_entity.map_or(false, |e| e == entity)
+                            || info.selected_entity.map_or(false, |s| s.0 == entity);
+                        let nametag_range = if in_group {
+                            NAMETAG_GROUP_RANGE
+                        } else if hpfl
+                            .time_since_last_dmg_by_me
+                            .map_or(false, |t| t < NAMETAG_DMG_TIME)
+                        {
+                            NAMETAG_DMG_RANGE
+                        } else {
+                            NAMETAG_RANGE
+                        };
                         let display_overhead_info = !is_me
                             && (is_mount.is_none()
                                 || health.map_or(true, overhead::should_show_healthbar))
                             && is_rider
                                 .map_or(true, |is_rider| Some(&is_rider.mount) != uids.get(me))
-                            && (info.target_entity.map_or(false, |e| e == entity)
-                                || info.selected_entity.map_or(false, |s| s.0 == entity)
+                            && (is_focused
                                 || health.map_or(true, overhead::should_show_healthbar)
                                 || in_group)
-                            && dist_sqr
-                                < (if in_group {
-                                    NAMETAG_GROUP_RANGE
-                                } else if hpfl
-                                    .time_since_last_dmg_by_me
-                                    .map_or(false, |t| t < NAMETAG_DMG_TIME)
-                                {
-                                    NAMETAG_DMG_RANGE
-                                } else {
-                                    NAMETAG_RANGE
-                                })
-                                .powi(2);
+                            && dist_sqr < nametag_range.powi(2);
+
+                        // Then apply the player's nameplate settings
+                        let display = display_overhead_info
+                            .then(|| {
+                                overhead::nameplate_display(
+                                    &global_state.settings.interface,
+                                    &overhead::NameplateSubject {
+                                        health,
+                                        is_hostile: alignment.zip(client.uid()).map_or(
+                                            false,
+                                            |(alignment, my_uid)| {
+                                                alignment
+                                                    .hostile_towards(comp::Alignment::Owned(my_uid))
+                                            },
+                                        ),
+                                        in_group,
+                                        is_focused,
+                                        dist_sqr,
+                                        range: nametag_range,
+                                    },
+                                )
+                            })
+                            .flatten();
 
-                        let info = display_overhead_info.then(|| overhead::Info {
+                        let info = display.map(|display| overhead::Info {
                             name: Some(&stats.name),
                             health,
                             buffs: Some(buffs),
@@ -2407,6 +2832,24 @@ impl Hud {
                                 None
                             },
                             stance,
+                            tag: global_state
+                                .settings
+                                .interface
+                                .nameplate_show_tags
+                                .then(|| client.player_list().get(uid))
+                                .flatten()
+                                .and_then(|player_info| {
+                                    player_info
+                                        .guild_tag
+                                        .as_deref()
+                                        .or(player_info.faction.as_deref())
+                                }),
+                            title: client
+                                .player_list()
+                                .get(uid)
+                                .and_then(|player_info| player_info.character.as_ref())
+                                .and_then(|character| character.title),
+                            display,
                         });
                         // Only render bubble if nearby or if its me and setting is on
                         let bubble = if (dist_sqr < SPEECH_BUBBLE_RANGE.powi(2) && !is_me)
@@ -2460,6 +2903,15 @@ impl Hud {
                     &self.fonts,
                     &global_state.window.key_layout,
                     match alignment {
+                        // Downed teammates can be revived
+                        _ if in_group
+                            && dist_sqr < common::consts::MAX_MOUNT_RANGE.powi(2)
+                            && char_states
+                                .get(entity)
+                                .map_or(false, |char_state| char_state.is_downed()) =>
+                        {
+                            vec![(GameInput::Interact, i18n.get_msg("hud-revive").to_string())]
+                        },
                         // TODO: Don't use `MAX_MOUNT_RANGE` here, add dedicated interaction range
                         Some(comp::Alignment::Npc)
                             if dist_sqr < common::consts::MAX_MOUNT_RANGE.powi(2)
@@ -2468,6 +2920,10 @@ impl Hud {
                             vec![
                                 (GameInput::Interact, i18n.get_msg("hud-talk").to_string()),
                                 (GameInput::Trade, i18n.get_msg("hud-trade").to_string()),
+                                (
+                                    GameInput::AskDirections,
+                                    i18n.get_msg("hud-ask_directions").to_string(),
+                                ),
                             ]
                         },
                         Some(comp::Alignment::Owned(owner))
@@ -2501,6 +2957,13 @@ impl Hud {
                                         })
                                         .to_string(),
                                     ));
+
+                                    if can_scout(body) && !is_staying {
+                                        options.push((
+                                            GameInput::Scout,
+                                            i18n.get_msg("hud-scout").to_string(),
+                                        ));
+                                    }
                                 }
 
                                 // Anyone can pet a tamed animal
@@ -2872,10 +3335,25 @@ impl Hud {
             .font_size(self.fonts.cyri.scale(14))
             .set(self.ids.num_chunks, ui_widgets);
 
+            // GPU memory used by terrain meshes
+            let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+            Text::new(&format!(
+                "Terrain meshes: {:.1} / {:.0} MiB ({:.1} MiB pooled, {} evicted)",
+                mib(debug_info.terrain_memory.mesh_bytes),
+                mib(debug_info.terrain_memory.budget_bytes),
+                mib(debug_info.terrain_memory.pooled_bytes),
+                debug_info.terrain_memory.evicted_chunks,
+            ))
+            .color(TEXT_COLOR)
+            .down_from(self.ids.num_chunks, V_PAD)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .set(self.ids.terrain_memory, ui_widgets);
+
             // Type of biome
             Text::new(&format!("Biome: {:?}", client.current_biome()))
                 .color(TEXT_COLOR)
-                .down_from(self.ids.num_chunks, V_PAD)
+                .down_from(self.ids.terrain_memory, V_PAD)
                 .font_id(self.fonts.cyri.conrod_id)
                 .font_size(self.fonts.cyri.scale(14))
                 .set(self.ids.current_biome, ui_widgets);
@@ -3075,8 +3553,20 @@ impl Hud {
                 group::Event::Kick(uid) => events.push(Event::KickMember(uid)),
                 group::Event::LeaveGroup => events.push(Event::LeaveGroup),
                 group::Event::AssignLeader(uid) => events.push(Event::AssignLeader(uid)),
+                group::Event::SetLootMode(loot_mode) => {
+                    events.push(Event::SetGroupLootMode(loot_mode))
+                },
             }
         }
+
+        // Loot rolls
+        if let Some(loot_roll::Event::Choose(id, choice)) =
+            LootRoll::new(client, &self.imgs, &self.fonts, i18n, &self.item_i18n)
+                .set(self.ids.loot_roll, ui_widgets)
+        {
+            events.push(Event::LootRoll(id, choice));
+        }
+
         // Popup (waypoint saved and similar notifications)
         Popup::new(
             i18n,
@@ -3219,6 +3709,18 @@ impl Hud {
                 Some(skillbar::Event::OpenBag) => self.show.bag = !self.show.bag,
                 None => {},
             }
+
+            QuickBar::new(
+                &self.imgs,
+                &self.item_imgs,
+                &self.fonts,
+                i18n,
+                inventory,
+                &self.quick_bar,
+                &mut self.slot_manager,
+                self.pulse,
+            )
+            .set(self.ids.quick_bar, ui_widgets);
         }
         // Bag contents
         if self.show.bag {
@@ -3278,12 +3780,31 @@ impl Hud {
                     Some(bag::Event::SwapEquippedWeapons) => {
                         self.events.push(Event::SwapEquippedWeapons)
                     },
+                    Some(bag::Event::OpenHeraldry) => self.show.heraldry(
+                        Some(HeraldryTarget::Cape),
+                        heraldry::current_design(client, HeraldryTarget::Cape),
+                    ),
                     None => {},
                 }
             }
         }
         // Trade window
         if self.show.trade {
+            if let Some((_, pending, prices)) = client.pending_trade() {
+                // Remember what the trade was about once it can no longer change, as
+                // items have already changed hands when we learn it completed.
+                if !matches!(pending.phase(), TradePhase::Mutate) {
+                    self.trade_summary =
+                        trade::summarize(client, pending, prices, i18n, &self.item_i18n);
+                }
+            }
+            let character_id = match client.presence() {
+                Some(PresenceKind::Character(id) | PresenceKind::LoadingCharacter(id)) => Some(id),
+                _ => None,
+            };
+            let trade_history = global_state
+                .profile
+                .get_trade_history(&client.server_info().name, character_id);
             if let Some(action) = Trade::new(
                 client,
                 &info,
@@ -3299,6 +3820,7 @@ impl Hud {
                 &msm,
                 self.pulse,
                 &mut self.show,
+                trade_history,
             )
             .set(self.ids.trade, ui_widgets)
             {
@@ -3328,6 +3850,10 @@ impl Hud {
                         }
                         events.push(Event::TradeAction(action));
                     },
+                    trade::TradeEvent::Haggle => events.push(Event::Haggle),
+                    trade::TradeEvent::SetHistoryMode(mode) => {
+                        self.show.trade_history = mode;
+                    },
                     trade::TradeEvent::SetDetailsMode(mode) => {
                         self.show.trade_details = mode;
                     },
@@ -3338,6 +3864,113 @@ impl Hud {
             }
         }
 
+        // Bank window
+        if let Some(banker) = self.show.bank {
+            // Close the bank once we walk away from the banker
+            let positions = ecs.read_storage::<comp::Pos>();
+            let within_range = ecs
+                .entity_from_uid(banker)
+                .and_then(|banker| positions.get(banker))
+                .zip(positions.get(info.viewpoint_entity))
+                .map_or(false, |(banker_pos, pos)| {
+                    banker_pos.0.distance_squared(pos.0) <= MAX_NPCINTERACT_RANGE.powi(2)
+                });
+            let event = if within_range {
+                Bank::new(
+                    client,
+                    &info,
+                    &self.imgs,
+                    &self.item_imgs,
+                    &self.fonts,
+                    &self.rot_imgs,
+                    item_tooltip_manager,
+                    &mut self.slot_manager,
+                    i18n,
+                    &self.item_i18n,
+                    &msm,
+                    self.pulse,
+                )
+                .set(self.ids.bank, ui_widgets)
+            } else {
+                Some(bank::Event::Close)
+            };
+            match event {
+                Some(bank::Event::Close) => {
+                    self.show.bank(None);
+                    self.slot_manager.idle();
+                },
+                Some(bank::Event::Upgrade) => events.push(Event::BankUpgrade(banker)),
+                None => {},
+            }
+        }
+
+        // Guild storage window
+        if self.show.guild_storage {
+            // Close the storage once we walk away from the hall
+            let positions = ecs.read_storage::<comp::Pos>();
+            let within_range = client
+                .guild()
+                .and_then(|guild| guild.hall.as_ref())
+                .zip(positions.get(info.viewpoint_entity))
+                .map_or(false, |(hall, pos)| hall.spot.in_range(pos.0));
+            let event = if within_range {
+                GuildStorage::new(
+                    client,
+                    &info,
+                    &self.imgs,
+                    &self.item_imgs,
+                    &self.fonts,
+                    &self.rot_imgs,
+                    item_tooltip_manager,
+                    &mut self.slot_manager,
+                    i18n,
+                    &self.item_i18n,
+                    &msm,
+                    self.pulse,
+                )
+                .set(self.ids.guild_storage, ui_widgets)
+            } else {
+                Some(guild_storage::Event::Close)
+            };
+            match event {
+                Some(guild_storage::Event::Close) => {
+                    self.show.guild_storage(false);
+                    self.slot_manager.idle();
+                },
+                None => {},
+            }
+        }
+
+        // Trainer window
+        if let Some(trainer) = self.show.trainer {
+            // Close the window once we walk away from the trainer
+            let positions = ecs.read_storage::<comp::Pos>();
+            let within_range = ecs
+                .entity_from_uid(trainer)
+                .and_then(|trainer| positions.get(trainer))
+                .zip(positions.get(info.viewpoint_entity))
+                .map_or(false, |(trainer_pos, pos)| {
+                    trainer_pos.0.distance_squared(pos.0) <= MAX_NPCINTERACT_RANGE.powi(2)
+                });
+            let event = match (
+                skill_sets.get(info.viewpoint_entity),
+                inventories.get(info.viewpoint_entity),
+            ) {
+                (Some(skill_set), Some(inventory)) if within_range => {
+                    Trainer::new(skill_set, inventory, &self.imgs, &self.fonts, i18n)
+                        .set(self.ids.trainer, ui_widgets)
+                },
+                _ => Some(trainer::Event::Close),
+            };
+            match event {
+                Some(trainer::Event::Close) => self.show.trainer(None),
+                Some(trainer::Event::Learn(skill)) => {
+                    events.push(Event::LearnSkill { trainer, skill })
+                },
+                None => {},
+            }
+        }
+
         // Buffs
         if let (Some(player_buffs), Some(health), Some(energy)) = (
             buffs.get(info.viewpoint_entity),
@@ -3363,6 +3996,9 @@ impl Hud {
                 match event {
                     buffs::Event::RemoveBuff(buff_id) => events.push(Event::RemoveBuff(buff_id)),
                     buffs::Event::LeaveStance => events.push(Event::LeaveStance),
+                    buffs::Event::TogglePin(kind) => events.push(Event::SettingsChange(
+                        InterfaceChange::ToggleBuffPin(kind).into(),
+                    )),
                 }
             }
         }
@@ -3628,34 +4264,50 @@ impl Hud {
             }
         }
         // Quest Window
-        let stats = client.state().ecs().read_storage::<comp::Stats>();
         if self.show.quest {
-            if let Some(stats) = stats.get(entity) {
-                match Quest::new(
-                    &self.show,
-                    client,
-                    &self.imgs,
-                    &self.fonts,
-                    i18n,
-                    &self.rot_imgs,
-                    tooltip_manager,
-                    stats,
-                    &self.item_imgs,
-                    self.pulse,
-                )
-                .set(self.ids.quest_window, ui_widgets)
-                {
-                    Some(quest::Event::Close) => {
-                        self.show.quest(false);
-                        if !self.show.bag {
-                            self.show.want_grab = true;
-                            self.force_ungrab = false;
-                        } else {
-                            self.force_ungrab = true
-                        };
-                    },
-                    None => {},
-                }
+            match Quest::new(
+                &self.show,
+                client,
+                &self.imgs,
+                &self.fonts,
+                i18n,
+                &self.rot_imgs,
+                tooltip_manager,
+            )
+            .set(self.ids.quest_window, ui_widgets)
+            {
+                Some(quest::Event::Close) => {
+                    self.show.quest(false);
+                    events.push(Event::CloseNoticeBoard);
+                    if !self.show.bag {
+                        self.show.want_grab = true;
+                        self.force_ungrab = false;
+                    } else {
+                        self.force_ungrab = true
+                    };
+                },
+                Some(quest::Event::AcceptBounty(target)) => {
+                    events.push(Event::AcceptBounty(target));
+                },
+                Some(quest::Event::ToggleCalendar) => self.show.calendar(!self.show.calendar),
+                None => {},
+            }
+        }
+
+        // Event calendar window
+        if self.show.calendar {
+            let time = client.state().ecs().read_resource::<Time>();
+            if let Some(calendar::Event::Close) =
+                Calendar::new(client, &time, &self.imgs, &self.fonts, i18n)
+                    .set(self.ids.calendar_window, ui_widgets)
+            {
+                self.show.calendar(false);
+                if !self.show.bag {
+                    self.show.want_grab = true;
+                    self.force_ungrab = false;
+                } else {
+                    self.force_ungrab = true
+                };
             }
         }
 
@@ -3689,6 +4341,8 @@ impl Hud {
                         self.to_focus = Some(Some(widget_id));
                     },
                     social::Event::Invite(uid) => events.push(Event::InviteMember(uid)),
+                    social::Event::InviteToGuild(uid) => events.push(Event::InviteToGuild(uid)),
+                    social::Event::ToggleGuild => self.show.guild(!self.show.guild),
                     social::Event::SearchPlayers(search_key) => {
                         self.show.search_social_players(search_key)
                     },
@@ -3696,6 +4350,93 @@ impl Hud {
             }
         }
 
+        // Guild Window
+        if self.show.guild {
+            match Guild::new(client, &self.imgs, &self.fonts, i18n)
+                .set(self.ids.guild_window, ui_widgets)
+            {
+                Some(guild::Event::Close) => {
+                    self.show.guild(false);
+                    if !self.show.bag {
+                        self.show.want_grab = true;
+                        self.force_ungrab = false;
+                    } else {
+                        self.force_ungrab = true
+                    };
+                },
+                Some(guild::Event::Manip(manip)) => events.push(Event::GuildManip(manip)),
+                None => {},
+            }
+        }
+
+        // Guild hall window
+        if let Some(board) = self.show.guild_hall {
+            // Close the board once we walk away from it
+            let within_range = ecs
+                .read_storage::<comp::Pos>()
+                .get(info.viewpoint_entity)
+                .map_or(false, |pos| {
+                    pos.0.distance_squared(board.as_::<f32>() + 0.5) < GUILD_HALL_RANGE.powi(2)
+                });
+            let event = if within_range {
+                GuildHall::new(client, &self.imgs, &self.fonts, i18n, board)
+                    .set(self.ids.guild_hall_window, ui_widgets)
+            } else {
+                Some(guild_hall::Event::Close)
+            };
+            match event {
+                Some(guild_hall::Event::Close) => {
+                    self.show.guild_hall(None);
+                    if !self.show.bag {
+                        self.show.want_grab = true;
+                        self.force_ungrab = false;
+                    } else {
+                        self.force_ungrab = true
+                    };
+                },
+                Some(guild_hall::Event::Manip(manip)) => events.push(Event::GuildManip(manip)),
+                Some(guild_hall::Event::DesignBanner) => self.show.heraldry(
+                    Some(HeraldryTarget::GuildBanner),
+                    heraldry::current_design(client, HeraldryTarget::GuildBanner),
+                ),
+                None => {},
+            }
+        }
+
+        // Heraldry designer
+        if let Some(target) = self.show.heraldry {
+            match HeraldryDesigner::new(
+                client,
+                &self.imgs,
+                &self.fonts,
+                i18n,
+                self.show.heraldry_draft,
+                target,
+                self.heraldry_preview.map(|(id, _)| id),
+            )
+            .set(self.ids.heraldry_window, ui_widgets)
+            {
+                Some(heraldry::Event::Close) => {
+                    self.show.heraldry(None, None);
+                    if !self.show.bag {
+                        self.show.want_grab = true;
+                        self.force_ungrab = false;
+                    } else {
+                        self.force_ungrab = true
+                    };
+                },
+                Some(heraldry::Event::Change(draft)) => self.show.heraldry_draft = draft,
+                Some(heraldry::Event::SetTarget(target)) => self
+                    .show
+                    .heraldry(Some(target), heraldry::current_design(client, target)),
+                Some(heraldry::Event::SetItemHeraldry(slot, heraldry)) => {
+                    events.push(Event::SetHeraldry(slot, heraldry))
+                },
+                Some(heraldry::Event::Manip(manip)) => events.push(Event::GuildManip(manip)),
+                None => {},
+            }
+        }
+
         // Diary
         if self.show.diary {
             let entity = info.viewpoint_entity;
@@ -3757,9 +4498,13 @@ impl Hud {
                         diary::Event::ChangeSection(section) => {
                             self.show.diary_fields.section = section;
                         },
+                        diary::Event::SwitchAbilityLoadout(loadout) => {
+                            events.push(Event::SwitchAbilityLoadout(loadout))
+                        },
                         diary::Event::SelectExpBar(xp_bar) => {
                             events.push(Event::SelectExpBar(xp_bar))
                         },
+                        diary::Event::SelectTitle(title) => events.push(Event::SelectTitle(title)),
                     }
                 }
             }
@@ -3778,6 +4523,7 @@ impl Hud {
                 tooltip_manager,
                 &self.show.location_markers,
                 self.map_drag,
+                self.show.asking_directions.is_some(),
             )
             .set(self.ids.map, ui_widgets)
             {
@@ -3804,11 +4550,61 @@ impl Hud {
                         self.show.location_markers.owned = None;
                         events.push(Event::MapMarkerEvent(MapMarkerChange::Remove));
                     },
+                    map::Event::TravelToShrine(site) => {
+                        self.show.map(false);
+                        events.push(Event::TravelToShrine(site));
+                    },
+                    map::Event::AskDirections(site) => {
+                        if let Some(npc) = self.show.asking_directions.take() {
+                            events.push(Event::AskDirections(npc, site));
+                        }
+                        self.show.map(false);
+                    },
+                    map::Event::AddAnnotation(annotation) => {
+                        events.push(Event::AddMapAnnotation(annotation));
+                    },
+                    map::Event::UndoAnnotation => events.push(Event::UndoMapAnnotation),
                 }
             }
         } else {
-            // Reset the map position when it's not showing
+            // Reset the map position and stop asking for directions when it's not
+            // showing
             self.map_drag = Vec2::zero();
+            self.show.asking_directions = None;
+        }
+
+        if self.show.emote_wheel {
+            if let Some(emote_wheel::Event::Emote(kind)) = EmoteWheel::new(
+                &self.imgs,
+                &self.fonts,
+                i18n,
+                &mut self.slot_manager,
+                self.pulse,
+            )
+            .set(self.ids.emote_wheel, ui_widgets)
+            {
+                events.push(Event::Emote(kind));
+                self.show.emote_wheel(false);
+            }
+        }
+
+        if self.show.quick_wheel {
+            if let Some(quick_wheel::Event::Use(slot)) = QuickWheel::new(
+                &self.imgs,
+                &self.item_imgs,
+                &self.fonts,
+                i18n,
+                &self.quick_bar,
+                self.pulse,
+            )
+            .set(self.ids.quick_wheel, ui_widgets)
+            {
+                events.extend(
+                    self.quick_bar
+                        .use_slot(slot, inventories.get(info.viewpoint_entity)),
+                );
+                self.show.quick_wheel(false);
+            }
         }
 
         if self.show.esc_menu {
@@ -3959,8 +4755,18 @@ impl Hud {
                 Equip(e) => Some(Slot::Equip(e)),
                 Hotbar(_) => None,
                 Trade(_) => None,
+                Bank(_) => None,
+                GuildStorage(_) => None,
                 Ability(_) => None,
                 Crafting(_) => None,
+                QuickBar(_) => None,
+                Emote(_) => None,
+            };
+            let link_to_quick_bar = |quick_bar: &mut quick_bar::State, slot: InvSlotId, q| {
+                inventories
+                    .get(info.viewpoint_entity)
+                    .and_then(|inv| inv.get(slot))
+                    .map_or(false, |item| quick_bar.add_inventory_link(q, item))
             };
             match event {
                 slot::Event::Dragged(a, b) => {
@@ -3992,6 +4798,30 @@ impl Hud {
                     } else if let (Hotbar(a), Hotbar(b)) = (a, b) {
                         self.hotbar.swap(a, b);
                         events.push(Event::ChangeHotbarState(Box::new(self.hotbar.to_owned())));
+                    } else if let (
+                        Inventory(InventorySlot {
+                            slot: Slot::Inventory(slot),
+                            ours: true,
+                            ..
+                        }),
+                        QuickBar(q),
+                    ) = (a, b)
+                    {
+                        if link_to_quick_bar(&mut self.quick_bar, slot, q) {
+                            events.push(Event::ChangeQuickBarState(Box::new(
+                                self.quick_bar.to_owned(),
+                            )));
+                        }
+                    } else if let (QuickBar(a), QuickBar(b)) = (a, b) {
+                        self.quick_bar.swap(a, b);
+                        events.push(Event::ChangeQuickBarState(Box::new(
+                            self.quick_bar.to_owned(),
+                        )));
+                    } else if let (Emote(kind), QuickBar(q)) = (a, b) {
+                        self.quick_bar.add_emote(q, kind);
+                        events.push(Event::ChangeQuickBarState(Box::new(
+                            self.quick_bar.to_owned(),
+                        )));
                     } else if let (Inventory(i), Trade(t)) = (a, b) {
                         if i.ours == t.ours {
                             if let (Some(inventory), Slot::Inventory(slot)) =
@@ -4016,6 +4846,39 @@ impl Hud {
                                 }
                             }
                         }
+                    } else if let (
+                        Inventory(InventorySlot {
+                            slot: Slot::Inventory(slot),
+                            ours: true,
+                            ..
+                        }),
+                        Bank(_),
+                    ) = (a, b)
+                    {
+                        if let Some(banker) = self.show.bank {
+                            events.push(Event::BankDeposit { banker, slot });
+                        }
+                    } else if let (Bank(b), Inventory(InventorySlot { ours: true, .. })) = (a, b) {
+                        if let Some(banker) = self.show.bank {
+                            events.push(Event::BankWithdraw {
+                                banker,
+                                slot: b.index,
+                            });
+                        }
+                    } else if let (
+                        Inventory(InventorySlot {
+                            slot: Slot::Inventory(slot),
+                            ours: true,
+                            ..
+                        }),
+                        GuildStorage(_),
+                    ) = (a, b)
+                    {
+                        events.push(Event::GuildManip(GuildManip::StorageDeposit(slot)));
+                    } else if let (GuildStorage(g), Inventory(InventorySlot { ours: true, .. })) =
+                        (a, b)
+                    {
+                        events.push(Event::GuildManip(GuildManip::StorageWithdraw(g.index)));
                     } else if let (Ability(a), Ability(b)) = (a, b) {
                         match (a, b) {
                             (AbilitySlot::Ability(ability), AbilitySlot::Slot(index)) => {
@@ -4091,6 +4954,11 @@ impl Hud {
                     } else if let Hotbar(h) = from {
                         self.hotbar.clear_slot(h);
                         events.push(Event::ChangeHotbarState(Box::new(self.hotbar.to_owned())));
+                    } else if let QuickBar(q) = from {
+                        self.quick_bar.clear_slot(q);
+                        events.push(Event::ChangeQuickBarState(Box::new(
+                            self.quick_bar.to_owned(),
+                        )));
                     } else if let Trade(t) = from {
                         if let Some(inventory) = inventories.get(t.entity) {
                             if let Some(invslot) = t.invslot {
@@ -4115,6 +4983,11 @@ impl Hud {
                     } else if let Hotbar(h) = from {
                         self.hotbar.clear_slot(h);
                         events.push(Event::ChangeHotbarState(Box::new(self.hotbar.to_owned())));
+                    } else if let QuickBar(q) = from {
+                        self.quick_bar.clear_slot(q);
+                        events.push(Event::ChangeQuickBarState(Box::new(
+                            self.quick_bar.to_owned(),
+                        )));
                     } else if let Ability(AbilitySlot::Slot(index)) = from {
                         events.push(Event::ChangeAbility(index, AuxiliaryAbility::Empty));
                     }
@@ -4142,6 +5015,30 @@ impl Hud {
                     } else if let (Hotbar(a), Hotbar(b)) = (a, b) {
                         self.hotbar.swap(a, b);
                         events.push(Event::ChangeHotbarState(Box::new(self.hotbar.to_owned())));
+                    } else if let (
+                        Inventory(InventorySlot {
+                            slot: Slot::Inventory(slot),
+                            ours: true,
+                            ..
+                        }),
+                        QuickBar(q),
+                    ) = (a, b)
+                    {
+                        if link_to_quick_bar(&mut self.quick_bar, slot, q) {
+                            events.push(Event::ChangeQuickBarState(Box::new(
+                                self.quick_bar.to_owned(),
+                            )));
+                        }
+                    } else if let (QuickBar(a), QuickBar(b)) = (a, b) {
+                        self.quick_bar.swap(a, b);
+                        events.push(Event::ChangeQuickBarState(Box::new(
+                            self.quick_bar.to_owned(),
+                        )));
+                    } else if let (Emote(kind), QuickBar(q)) = (a, b) {
+                        self.quick_bar.add_emote(q, kind);
+                        events.push(Event::ChangeQuickBarState(Box::new(
+                            self.quick_bar.to_owned(),
+                        )));
                     } else if let (Inventory(i), Trade(t)) = (a, b) {
                         if i.ours == t.ours {
                             if let (Some(inventory), Slot::Inventory(slot)) =
@@ -4197,7 +5094,24 @@ impl Hud {
                 },
                 slot::Event::Used(from) => {
                     // Item used (selected and then clicked again)
-                    if let Some(from) = to_slot(from) {
+                    if let (Some(banker), Bank(b)) = (self.show.bank, from) {
+                        events.push(Event::BankWithdraw {
+                            banker,
+                            slot: b.index,
+                        });
+                    } else if let (Some(banker), Some(Slot::Inventory(slot))) =
+                        (self.show.bank, to_slot(from))
+                    {
+                        // While the bank is open, using an item deposits it instead
+                        events.push(Event::BankDeposit { banker, slot });
+                    } else if let GuildStorage(g) = from {
+                        events.push(Event::GuildManip(GuildManip::StorageWithdraw(g.index)));
+                    } else if let (true, Some(Slot::Inventory(slot))) =
+                        (self.show.guild_storage, to_slot(from))
+                    {
+                        // The same goes for the guild storage
+                        events.push(Event::GuildManip(GuildManip::StorageDeposit(slot)));
+                    } else if let Some(from) = to_slot(from) {
                         if self.show.crafting_fields.salvage
                             && matches!(
                                 self.show.crafting_fields.crafting_tab,
@@ -4239,6 +5153,14 @@ impl Hud {
                             },
                             hotbar::SlotContents::Ability(_) => {},
                         });
+                    } else if let QuickBar(q) = from {
+                        events.extend(
+                            self.quick_bar
+                                .use_slot(q, inventories.get(info.viewpoint_entity)),
+                        );
+                    } else if let Emote(kind) = from {
+                        events.push(Event::Emote(kind));
+                        self.show.emote_wheel(false);
                     } else if let Ability(AbilitySlot::Slot(index)) = from {
                         events.push(Event::ChangeAbility(index, AuxiliaryAbility::Empty));
                     } else if let Crafting(c) = from {
@@ -4443,6 +5365,32 @@ impl Hud {
                 },
             }
         }
+        // Treasure map
+        if let Some((treasure_map, open)) = &mut self.treasure_map
+            && *open
+            && !self.show.esc_menu
+        {
+            Image::new(*treasure_map)
+                .w_h(384.0, 384.0)
+                .middle_of(ui_widgets.window)
+                .set(self.ids.treasure_map, ui_widgets);
+            if Button::image(self.imgs.button)
+                .w_h(150.0, 40.0)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .mid_bottom_with_margin_on(self.ids.treasure_map, -50.0)
+                .label(&i18n.get_msg("common-close"))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_font_size(self.fonts.cyri.scale(18))
+                .label_color(TEXT_COLOR)
+                .label_y(conrod_core::position::Relative::Scalar(2.0))
+                .set(self.ids.treasure_map_close, ui_widgets)
+                .was_clicked()
+            {
+                *open = false;
+                self.show.want_grab = true;
+            }
+        }
         // TODO: Add event/stat based tutorial system
         if self.show.intro && !self.show.esc_menu {
             prof_span!("intro show");
@@ -4563,6 +5511,102 @@ impl Hud {
 
     pub fn new_message(&mut self, msg: comp::ChatMsg) { self.new_messages.push_back(msg); }
 
+    pub fn new_loot_roll_result(
+        &mut self,
+        client: &Client,
+        item: &comp::FrontendItem,
+        winner: Option<(Uid, LootRollChoice, u32)>,
+    ) {
+        let (item_name, _) = item.i18n(&self.item_i18n);
+        let content = match winner {
+            Some((uid, choice, roll)) => {
+                let name = client.player_list().get(&uid).map_or_else(
+                    || format!("<uid {}>", uid),
+                    |info| info.player_alias.clone(),
+                );
+                Content::localized_with_args("hud-loot_roll-won", [
+                    ("player", LocalizationArg::from(name)),
+                    ("item", LocalizationArg::from(item_name)),
+                    (
+                        "choice",
+                        LocalizationArg::from(Content::localized(match choice {
+                            LootRollChoice::Need => "hud-loot_roll-need",
+                            LootRollChoice::Greed => "hud-loot_roll-greed",
+                            LootRollChoice::Pass => "hud-loot_roll-pass",
+                        })),
+                    ),
+                    ("roll", LocalizationArg::from(u64::from(roll))),
+                ])
+            },
+            None => Content::localized_with_args("hud-loot_roll-nobody", [(
+                "item",
+                LocalizationArg::from(item_name),
+            )]),
+        };
+        self.new_message(comp::ChatType::GroupMeta("Group".into()).into_msg(content));
+    }
+
+    /// Opens the quest log on the bounties offered by the notice board the
+    /// character just read
+    pub fn show_notice_board(&mut self) {
+        self.show.quest(true);
+        self.events.push(Event::RequestReputation);
+    }
+
+    pub fn show_treasure_map(&mut self, snippet: &TreasureMapSnippet) {
+        let size = snippet.rgba.size().map(|e| e as u32);
+        let Some(img) = RgbaImage::from_raw(
+            size.x,
+            size.y,
+            snippet
+                .rgba
+                .raw()
+                .iter()
+                .flat_map(|rgba| rgba.to_le_bytes())
+                .collect(),
+        ) else {
+            warn!("Received a malformed treasure map");
+            return;
+        };
+        let graphic = Graphic::Image(Arc::new(DynamicImage::ImageRgba8(img)), None);
+        let id = match self.treasure_map {
+            Some((id, _)) => {
+                self.ui.replace_graphic(id, graphic);
+                id
+            },
+            None => self.ui.add_graphic(graphic),
+        };
+        self.treasure_map = Some((id, true));
+        self.show.want_grab = false;
+    }
+
+    pub fn new_minigame_result(&mut self, client: &Client, scoreboard: &Scoreboard) {
+        let scores = scoreboard
+            .scores
+            .iter()
+            .enumerate()
+            .map(|(i, (uid, score))| {
+                let name = client.player_list().get(uid).map_or_else(
+                    || format!("<uid {}>", uid),
+                    |info| info.player_alias.clone(),
+                );
+                format!("{}. {name} ({score})", i + 1)
+            })
+            .collect::<Vec<_>>();
+        let content = if scores.is_empty() {
+            Content::localized_with_args("hud-minigame-nobody", [(
+                "minigame",
+                LocalizationArg::from(scoreboard.name.clone()),
+            )])
+        } else {
+            Content::localized_with_args("hud-minigame-ended", [
+                ("minigame", LocalizationArg::from(scoreboard.name.clone())),
+                ("scores", LocalizationArg::from(scores.join(", "))),
+            ])
+        };
+        self.new_message(comp::ChatType::Meta.into_msg(content));
+    }
+
     pub fn new_notification(&mut self, msg: Notification) { self.new_notifications.push_back(msg); }
 
     pub fn set_scaling_mode(&mut self, scale_mode: ScaleMode) {
@@ -4775,6 +5819,14 @@ impl Hud {
                         self.show.toggle_map();
                         true
                     },
+                    GameInput::EmoteWheel if state => {
+                        self.show.toggle_emote_wheel();
+                        true
+                    },
+                    GameInput::QuickActionWheel if state => {
+                        self.show.toggle_quick_wheel();
+                        true
+                    },
                     GameInput::Bag if state => {
                         let state = !self.show.bag;
                         Self::show_bag(&mut self.slot_manager, &mut self.show, state);
@@ -4784,6 +5836,17 @@ impl Hud {
                         self.show.toggle_social();
                         true
                     },
+                    GameInput::QuestLog if state => {
+                        self.show.quest(!self.show.quest);
+                        if self.show.quest {
+                            self.events.push(Event::RequestReputation);
+                        }
+                        true
+                    },
+                    GameInput::Calendar if state => {
+                        self.show.calendar(!self.show.calendar);
+                        true
+                    },
                     GameInput::Crafting if state => {
                         self.show.toggle_crafting();
                         true
@@ -4958,6 +6021,26 @@ impl Hud {
         if let Some(maybe_id) = self.to_focus.take() {
             self.ui.focus_widget(maybe_id);
         }
+        // Compose the draft of the heraldry designer into a texture whenever it changes
+        let draft = self.show.heraldry_draft;
+        if self.show.heraldry.is_some()
+            && self
+                .heraldry_preview
+                .map_or(true, |(_, composed)| composed != draft)
+        {
+            let graphic = Graphic::Image(
+                Arc::new(DynamicImage::ImageRgba8(heraldry::compose(draft))),
+                None,
+            );
+            let id = match self.heraldry_preview {
+                Some((id, _)) => {
+                    self.ui.replace_graphic(id, graphic);
+                    id
+                },
+                None => self.ui.add_graphic(graphic),
+            };
+            self.heraldry_preview = Some((id, draft));
+        }
         let events = self.update_layout(
             client,
             global_state,
@@ -4991,6 +6074,23 @@ impl Hud {
     #[inline]
     pub fn clear_cursor(&mut self) { self.slot_manager.idle(); }
 
+    /// Summary of the last trade, taken once the trade has finished.
+    pub fn take_trade_summary(&mut self) -> Option<TradeRecord> { self.trade_summary.take() }
+
+    pub fn open_bank(&mut self, banker: Uid) { self.show.bank(Some(banker)); }
+
+    pub fn open_guild_hall(&mut self, board: Vec3<i32>) { self.show.guild_hall(Some(board)); }
+
+    pub fn open_guild_storage(&mut self) { self.show.guild_storage(true); }
+
+    pub fn open_trainer(&mut self, trainer: Uid) { self.show.trainer(Some(trainer)); }
+
+    /// Open the map to pick the site the NPC is asked the way to
+    pub fn ask_directions(&mut self, npc: EcsEntity) {
+        self.show.map(true);
+        self.show.asking_directions = Some(npc);
+    }
+
     pub fn render<'a>(&'a self, drawer: &mut UiDrawer<'_, 'a>) {
         span!(_guard, "render", "Hud::render");
         // Don't show anything if the UI is toggled off.
@@ -5190,6 +6290,22 @@ impl Hud {
         }
     }
 }
+/// Whether the site should be shown on the map, climbing routes only show up
+/// once the character discovered them
+pub fn is_site_discovered(client: &Client, global_state: &GlobalState, site: &SiteInfo) -> bool {
+    if !matches!(site.kind, SiteKind::ClimbingRoute) {
+        return true;
+    }
+    let character_id = match client.presence() {
+        Some(PresenceKind::Character(id) | PresenceKind::LoadingCharacter(id)) => Some(id),
+        _ => None,
+    };
+    global_state
+        .profile
+        .get_discovered_sites(&client.server_info().name, character_id)
+        .map_or(false, |sites| sites.contains(&site.id))
+}
+
 // Get item qualities of equipped items and assign a tooltip title/frame color
 pub fn get_quality_col<I: ItemDesc + ?Sized>(item: &I) -> Color {
     match item.quality() {
@@ -5263,6 +6379,10 @@ pub fn get_buff_image(buff: BuffKind, imgs: &Imgs) -> conrod_core::image::Id {
         BuffKind::Flame => imgs.buff_flame,
         BuffKind::Frigid => imgs.buff_frigid,
         BuffKind::Lifesteal => imgs.buff_lifesteal,
+        // TODO: Get images for coatings
+        BuffKind::PoisonCoating => imgs.debuff_poisoned_0,
+        BuffKind::FireCoating => imgs.buff_flame,
+        BuffKind::FrostCoating => imgs.buff_frigid,
         BuffKind::Resilience => imgs.buff_resilience,
         // TODO: Get image
         // BuffKind::SalamanderAspect => imgs.debuff_burning_0,
@@ -5291,6 +6411,7 @@ pub fn get_buff_image(buff: BuffKind, imgs: &Imgs) -> conrod_core::image::Id {
         BuffKind::Winded => imgs.debuff_winded,
         BuffKind::Concussion => imgs.debuff_concussion,
         BuffKind::Staggered => imgs.debuff_staggered,
+        BuffKind::Electrified => imgs.debuff_electrified,
     }
 }
 
@@ -5323,6 +6444,9 @@ pub fn get_sprite_desc(sprite: SpriteKind, localized_strings: &Localization) ->
         SpriteKind::ChairSingle | SpriteKind::ChairDouble => "common-sprite-chair",
         SpriteKind::Crate => "common-sprite-crate",
         SpriteKind::HangingSign => "common-sprite-signboard",
+        SpriteKind::NoticeBoard => "common-sprite-notice_board",
+        SpriteKind::GuildBoard => "common-sprite-guild_board",
+        SpriteKind::GuildChest => "common-sprite-guild_chest",
         SpriteKind::StreetLamp => "common-sprite-street_lamp",
         SpriteKind::Lantern => "common-sprite-lantern",
         SpriteKind::SeashellLantern => "common-sprite-seashell_lantern",
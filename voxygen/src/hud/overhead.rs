@@ -1,7 +1,7 @@
 use super::{
     cr_color, img_ids::Imgs, DEFAULT_NPC, ENEMY_HP_COLOR, FACTION_COLOR, GROUP_COLOR, GROUP_MEMBER,
-    HP_COLOR, LOW_HP_COLOR, QUALITY_EPIC, REGION_COLOR, SAY_COLOR, STAMINA_COLOR, TELL_COLOR,
-    TEXT_BG, TEXT_COLOR,
+    GUILD_COLOR, HP_COLOR, LOW_HP_COLOR, QUALITY_EPIC, REGION_COLOR, SAY_COLOR, STAMINA_COLOR,
+    TELL_COLOR, TEXT_BG, TEXT_COLOR,
 };
 use crate::{
     game_input::GameInput,
@@ -10,7 +10,7 @@ use crate::{
     ui::{fonts::Fonts, Ingameable},
 };
 use common::{
-    comp::{Buffs, Energy, Health, SpeechBubble, SpeechBubbleType, Stance},
+    comp::{Buffs, Energy, Health, SpeechBubble, SpeechBubbleType, Stance, Title},
     resources::Time,
 };
 use conrod_core::{
@@ -23,6 +23,7 @@ use i18n::Localization;
 use keyboard_keynames::key_layout::KeyLayout;
 
 const MAX_BUBBLE_WIDTH: f64 = 250.0;
+const TITLE_COLOR: Color = Color::Rgba(0.93, 0.82, 0.55, 1.0);
 widget_ids! {
     struct Ids {
         // Speech bubble
@@ -43,6 +44,8 @@ widget_ids! {
         // Name
         name_bg,
         name,
+        title_bg,
+        title,
 
         // HP
         level,
@@ -73,6 +76,11 @@ pub struct Info<'a> {
     pub energy: Option<&'a Energy>,
     pub combat_rating: Option<f32>,
     pub stance: Option<&'a Stance>,
+    /// Faction tag shown in front of the name
+    pub tag: Option<&'a str>,
+    /// Title shown under the name
+    pub title: Option<Title>,
+    pub display: NameplateDisplay,
 }
 
 /// Determines whether to show the healthbar
@@ -84,6 +92,60 @@ pub fn should_show_healthbar(health: &Health) -> bool {
 pub fn decayed_health_displayed(health: &Health) -> bool {
     (1.0 - health.maximum() / health.base_max()) > 0.0
 }
+
+/// What is known about an entity when deciding how to show its nameplate
+pub struct NameplateSubject<'a> {
+    pub health: Option<&'a Health>,
+    pub is_hostile: bool,
+    pub in_group: bool,
+    /// Targeted or selected by the player
+    pub is_focused: bool,
+    pub dist_sqr: f32,
+    /// Distance beyond which the nameplate is not shown at all
+    pub range: f32,
+}
+
+#[derive(Clone, Copy)]
+pub struct NameplateDisplay {
+    pub healthbar: bool,
+    /// Scale of the name and bars, 1.0 up close
+    pub scale: f64,
+}
+
+/// Smallest scale nameplates shrink to at the edge of their range
+const MIN_NAMEPLATE_SCALE: f32 = 0.6;
+
+/// Evaluates the nameplate settings for an entity whose nameplate would be
+/// shown, returning `None` if one of them hides it
+pub fn nameplate_display(
+    settings: &InterfaceSettings,
+    subject: &NameplateSubject,
+) -> Option<NameplateDisplay> {
+    // Group members and whatever the player is looking at are always shown in full
+    let important = subject.in_group || subject.is_focused;
+
+    if settings.nameplate_hostiles_only && !subject.is_hostile && !important {
+        return None;
+    }
+
+    let healthbar = subject.health.map_or(false, |health| {
+        !settings.nameplate_hide_full_health
+            || should_show_healthbar(health)
+            || decayed_health_displayed(health)
+    });
+
+    let scale = if settings.nameplate_scale_by_distance && !important {
+        let dist_frac = (subject.dist_sqr.sqrt() / subject.range).clamp(0.0, 1.0);
+        1.0 - (1.0 - MIN_NAMEPLATE_SCALE) * dist_frac
+    } else {
+        1.0
+    };
+
+    Some(NameplateDisplay {
+        healthbar,
+        scale: f64::from(scale),
+    })
+}
 /// ui widget containing everything that goes over a character's head
 /// (Speech bubble, Name, Level, HP/energy bars, etc.)
 #[derive(WidgetCommon)]
@@ -148,6 +210,7 @@ impl<'a> Ingameable for Overhead<'a> {
         // Number of conrod primitives contained in the overhead display. TODO maybe
         // this could be done automatically?
         // - 2 Text::new for name
+        // - 2 Text::new for title, if any
         //
         // If HP Info is shown:
         // - 1 for level: either Text or Image <-- Not used currently, will be replaced
@@ -167,6 +230,7 @@ impl<'a> Ingameable for Overhead<'a> {
         // - 10 Image::new for speech bubble (9-slice + tail)
         self.info.map_or(0, |info| {
             2 + 1
+                + if info.title.is_some() { 2 } else { 0 }
                 + if self.bubble.is_none() {
                     2 * info
                         .buffs
@@ -176,12 +240,12 @@ impl<'a> Ingameable for Overhead<'a> {
                 } else {
                     0
                 }
-                + if info.health.map_or(false, should_show_healthbar) {
+                + if info.display.healthbar {
                     5 + usize::from(info.energy.is_some())
+                        + usize::from(info.health.map_or(false, decayed_health_displayed))
                 } else {
                     0
                 }
-                + usize::from(info.health.map_or(false, decayed_health_displayed))
                 + (!self.interaction_options.is_empty()) as usize * 2
         }) + if self.bubble.is_some() { 13 } else { 0 }
     }
@@ -202,9 +266,6 @@ impl<'a> Widget for Overhead<'a> {
 
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, ui, .. } = args;
-        const BARSIZE: f64 = 2.0; // Scaling
-        const MANA_BAR_HEIGHT: f64 = BARSIZE * 1.5;
-        const MANA_BAR_Y: f64 = MANA_BAR_HEIGHT / 2.0;
         if let Some(Info {
             name,
             health,
@@ -212,8 +273,15 @@ impl<'a> Widget for Overhead<'a> {
             energy,
             combat_rating,
             stance,
+            tag,
+            title,
+            display,
         }) = self.info
         {
+            let scale = display.scale;
+            let bar_size = 2.0 * scale; // Scaling
+            let mana_bar_height = bar_size * 1.5;
+            let mana_bar_y = mana_bar_height / 2.0;
             // Used to set healthbar colours based on hp_percentage
             let hp_percentage = health.map_or(100.0, |h| {
                 f64::from(h.current() / h.base_max().max(h.maximum()) * 100.0)
@@ -221,12 +289,27 @@ impl<'a> Widget for Overhead<'a> {
             // Compare levels to decide if a skull is shown
             let health_current = health.map_or(1.0, |h| f64::from(h.current()));
             let health_max = health.map_or(1.0, |h| f64::from(h.maximum()));
-            let name_y = if (health_current - health_max).abs() < 1e-6 {
-                MANA_BAR_Y + 20.0
+            let title_y = if (health_current - health_max).abs() < 1e-6 {
+                mana_bar_y + 20.0 * scale
+            } else {
+                mana_bar_y + 32.0 * scale
+            };
+            // The title takes the place of the name, which moves up above it
+            let name_y = if title.is_some() {
+                title_y + 16.0 * scale
             } else {
-                MANA_BAR_Y + 32.0
+                title_y
+            };
+            let font_size = if hp_percentage.abs() > 99.9 {
+                24.0
+            } else {
+                20.0
+            };
+            let font_size = (font_size * scale) as u32;
+            let name = match (tag, name) {
+                (Some(tag), Some(name)) => format!("[{}] {}", tag, name),
+                (_, name) => name.unwrap_or("").to_owned(),
             };
-            let font_size = if hp_percentage.abs() > 99.9 { 24 } else { 20 };
             // Show K for numbers above 10^3 and truncate them
             // Show M for numbers above 10^6 and truncate them
             let health_cur_txt = match health_current as u32 {
@@ -247,7 +330,7 @@ impl<'a> Widget for Overhead<'a> {
                 .unwrap_or_default();
             let buff_count = buff_icons.len().min(11);
             Rectangle::fill_with([168.0, 100.0], color::TRANSPARENT)
-                .x_y(-1.0, name_y + 60.0)
+                .x_y(-1.0, name_y + 60.0 * scale)
                 .parent(id)
                 .set(state.ids.buffs_align, ui);
 
@@ -314,7 +397,7 @@ impl<'a> Widget for Overhead<'a> {
                     });
             }
             // Name
-            Text::new(name.unwrap_or(""))
+            Text::new(&name)
                 //Text::new(&format!("{} [{:?}]", name, combat_rating)) // <- Uncomment to debug combat ratings
                 .font_id(self.fonts.cyri.conrod_id)
                 .font_size(font_size)
@@ -322,7 +405,7 @@ impl<'a> Widget for Overhead<'a> {
                 .x_y(-1.0, name_y)
                 .parent(id)
                 .set(state.ids.name_bg, ui);
-            Text::new(name.unwrap_or(""))
+            Text::new(&name)
                 //Text::new(&format!("{} [{:?}]", name, combat_rating)) // <- Uncomment to debug combat ratings
                 .font_id(self.fonts.cyri.conrod_id)
                 .font_size(font_size)
@@ -337,38 +420,55 @@ impl<'a> Widget for Overhead<'a> {
                 .parent(id)
                 .set(state.ids.name, ui);
 
+            if let Some(title) = title {
+                let title = self.i18n.get_msg(title.i18n_key());
+                let title_font_size = (14.0 * scale) as u32;
+                Text::new(&title)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(title_font_size)
+                    .color(Color::Rgba(0.0, 0.0, 0.0, 1.0))
+                    .x_y(-1.0, title_y - 2.0 * scale)
+                    .parent(id)
+                    .set(state.ids.title_bg, ui);
+                Text::new(&title)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(title_font_size)
+                    .color(TITLE_COLOR)
+                    .x_y(0.0, title_y - 2.0 * scale + 1.0)
+                    .parent(id)
+                    .set(state.ids.title, ui);
+            }
+
             match health {
-                Some(health)
-                    if should_show_healthbar(health) || decayed_health_displayed(health) =>
-                {
+                Some(health) if display.healthbar => {
                     // Show HP Bar
                     let hp_ani = (self.pulse * 4.0/* speed factor */).cos() * 0.5 + 1.0; //Animation timer
                     let crit_hp_color: Color = Color::Rgba(0.93, 0.59, 0.03, hp_ani);
                     let decayed_health = f64::from(1.0 - health.maximum() / health.base_max());
                     // Background
                     Image::new(if self.in_group {self.imgs.health_bar_group_bg} else {self.imgs.enemy_health_bg})
-                        .w_h(84.0 * BARSIZE, 10.0 * BARSIZE)
-                        .x_y(0.0, MANA_BAR_Y + 6.5) //-25.5)
+                        .w_h(84.0 * bar_size, 10.0 * bar_size)
+                        .x_y(0.0, mana_bar_y + 6.5 * scale) //-25.5)
                         .color(Some(Color::Rgba(0.1, 0.1, 0.1, 0.8)))
                         .parent(id)
                         .set(state.ids.health_bar_bg, ui);
 
                     // % HP Filling
-                    let size_factor = (hp_percentage / 100.0) * BARSIZE;
+                    let size_factor = (hp_percentage / 100.0) * bar_size;
                     let w = if self.in_group {
                         82.0 * size_factor
                     } else {
                         73.0 * size_factor
                     };
-                    let h = 6.0 * BARSIZE;
+                    let h = 6.0 * bar_size;
                     let x = if self.in_group {
-                        (0.0 + (hp_percentage / 100.0 * 41.0 - 41.0)) * BARSIZE
+                        (0.0 + (hp_percentage / 100.0 * 41.0 - 41.0)) * bar_size
                     } else {
-                        (4.5 + (hp_percentage / 100.0 * 36.45 - 36.45)) * BARSIZE
+                        (4.5 + (hp_percentage / 100.0 * 36.45 - 36.45)) * bar_size
                     };
                     Image::new(self.imgs.enemy_bar)
                         .w_h(w, h)
-                        .x_y(x, MANA_BAR_Y + 8.0)
+                        .x_y(x, mana_bar_y + 8.0 * scale)
                         .color(if self.in_group {
                             // Different HP bar colors only for group members
                             Some(match hp_percentage {
@@ -384,20 +484,20 @@ impl<'a> Widget for Overhead<'a> {
 
                     if decayed_health > 0.0 {
                         let x_decayed = if self.in_group {
-                            (0.0 - (decayed_health * 41.0 - 41.0)) * BARSIZE
+                            (0.0 - (decayed_health * 41.0 - 41.0)) * bar_size
                         } else {
-                            (4.5 - (decayed_health * 36.45 - 36.45)) * BARSIZE
+                            (4.5 - (decayed_health * 36.45 - 36.45)) * bar_size
                         };
 
                         let decay_bar_len = decayed_health
                             * if self.in_group {
-                                82.0 * BARSIZE
+                                82.0 * bar_size
                             } else {
-                                73.0 * BARSIZE
+                                73.0 * bar_size
                             };
                         Image::new(self.imgs.enemy_bar)
                             .w_h(decay_bar_len, h)
-                            .x_y(x_decayed, MANA_BAR_Y + 8.0)
+                            .x_y(x_decayed, mana_bar_y + 8.0 * scale)
                             .color(Some(QUALITY_EPIC))
                             .parent(id)
                             .set(state.ids.decay_bar, ui);
@@ -408,7 +508,7 @@ impl<'a> Widget for Overhead<'a> {
                     };
                     Text::new(&txt)
                         .mid_top_with_margin_on(state.ids.health_bar_bg, 2.0)
-                        .font_size(10)
+                        .font_size((10.0 * scale) as u32)
                         .font_id(self.fonts.cyri.conrod_id)
                         .color(TEXT_COLOR)
                         .parent(id)
@@ -417,20 +517,20 @@ impl<'a> Widget for Overhead<'a> {
                     // % Mana Filling
                     if let Some(energy) = energy {
                         let energy_factor = f64::from(energy.current() / energy.maximum());
-                        let size_factor = energy_factor * BARSIZE;
+                        let size_factor = energy_factor * bar_size;
                         let w = if self.in_group {
                             80.0 * size_factor
                         } else {
                             72.0 * size_factor
                         };
                         let x = if self.in_group {
-                            ((0.0 + (energy_factor * 40.0)) - 40.0) * BARSIZE
+                            ((0.0 + (energy_factor * 40.0)) - 40.0) * bar_size
                         } else {
-                            ((3.5 + (energy_factor * 36.5)) - 36.45) * BARSIZE
+                            ((3.5 + (energy_factor * 36.5)) - 36.45) * bar_size
                         };
-                        Rectangle::fill_with([w, MANA_BAR_HEIGHT], STAMINA_COLOR)
+                        Rectangle::fill_with([w, mana_bar_height], STAMINA_COLOR)
                             .x_y(
-                                x, MANA_BAR_Y, //-32.0,
+                                x, mana_bar_y, //-32.0,
                             )
                             .parent(id)
                             .set(state.ids.mana_bar, ui);
@@ -438,8 +538,8 @@ impl<'a> Widget for Overhead<'a> {
 
                     // Foreground
                     Image::new(if self.in_group {self.imgs.health_bar_group} else {self.imgs.enemy_health})
-                .w_h(84.0 * BARSIZE, 10.0 * BARSIZE)
-                .x_y(0.0, MANA_BAR_Y + 6.5) //-25.5)
+                .w_h(84.0 * bar_size, 10.0 * bar_size)
+                .x_y(0.0, mana_bar_y + 6.5 * scale) //-25.5)
                 .color(Some(Color::Rgba(1.0, 1.0, 1.0, 0.99)))
                 .parent(id)
                 .set(state.ids.health_bar_fg, ui);
@@ -456,8 +556,8 @@ impl<'a> Widget for Overhead<'a> {
                             } else {
                                 self.imgs.skull
                             })
-                            .w_h(18.0 * BARSIZE, 18.0 * BARSIZE)
-                            .x_y(-39.0 * BARSIZE, MANA_BAR_Y + 7.0)
+                            .w_h(18.0 * bar_size, 18.0 * bar_size)
+                            .x_y(-39.0 * bar_size, mana_bar_y + 7.0 * scale)
                             .color(Some(Color::Rgba(1.0, 1.0, 1.0, 1.0)))
                             .parent(id)
                             .set(state.ids.level_skull, ui);
@@ -467,8 +567,8 @@ impl<'a> Widget for Overhead<'a> {
                             } else {
                                 self.imgs.combat_rating_ico
                             })
-                            .w_h(7.0 * BARSIZE, 7.0 * BARSIZE)
-                            .x_y(-37.0 * BARSIZE, MANA_BAR_Y + 6.0)
+                            .w_h(7.0 * bar_size, 7.0 * bar_size)
+                            .x_y(-37.0 * bar_size, mana_bar_y + 6.0 * scale)
                             .color(Some(indicator_col))
                             .parent(id)
                             .set(state.ids.level, ui);
@@ -509,7 +609,7 @@ impl<'a> Widget for Overhead<'a> {
                     .parent(id)
                     .down_from(
                         self.info.map_or(state.ids.name, |info| {
-                            if info.health.map_or(false, should_show_healthbar) {
+                            if info.display.healthbar {
                                 if info.energy.is_some() {
                                     state.ids.mana_bar
                                 } else {
@@ -696,6 +796,7 @@ fn bubble_color(bubble: &SpeechBubble, dark_mode: bool) -> (Color, Color) {
         SpeechBubbleType::Region => REGION_COLOR,
         SpeechBubbleType::Group => GROUP_COLOR,
         SpeechBubbleType::Faction => FACTION_COLOR,
+        SpeechBubbleType::Guild => GUILD_COLOR,
         SpeechBubbleType::World
         | SpeechBubbleType::Quest
         | SpeechBubbleType::Trade
@@ -715,7 +816,7 @@ fn bubble_icon(sb: &SpeechBubble, imgs: &Imgs) -> conrod_core::image::Id {
         SpeechBubbleType::Say => imgs.chat_say_small,
         SpeechBubbleType::Region => imgs.chat_region_small,
         SpeechBubbleType::Group => imgs.chat_group_small,
-        SpeechBubbleType::Faction => imgs.chat_faction_small,
+        SpeechBubbleType::Faction | SpeechBubbleType::Guild => imgs.chat_faction_small,
         SpeechBubbleType::World => imgs.chat_world_small,
         SpeechBubbleType::Quest => imgs.nothing, // TODO not implemented
         SpeechBubbleType::Trade => imgs.nothing, // TODO not implemented
@@ -1,7 +1,8 @@
 use super::Show;
 use crate::ui::fonts::Fonts;
 use client::{self, Client};
-use common_net::msg::Notification;
+use common::guild::GuildError;
+use common_net::msg::{Notification, ShrineTravelError};
 use conrod_core::{
     widget::{self, Text},
     widget_ids, Color, Colorable, Positionable, Widget, WidgetCommon,
@@ -131,6 +132,61 @@ impl<'a> Widget for Popup<'a> {
                         s.infos.push_back(text.to_string());
                     });
                 },
+                Notification::ShrineUnlocked => {
+                    state.update(|s| {
+                        if s.infos.is_empty() {
+                            s.last_info_update = Instant::now();
+                        }
+                        let text = self.i18n.get_msg("hud-shrine_unlocked");
+                        s.infos.push_back(text.to_string());
+                    });
+                },
+                Notification::ShrineTravelFailed(error) => {
+                    state.update(|s| {
+                        if s.errors.is_empty() {
+                            s.last_error_update = Instant::now();
+                        }
+                        let text = self.i18n.get_msg(match error {
+                            ShrineTravelError::NotAtShrine => "hud-shrine_travel-not_at_shrine",
+                            ShrineTravelError::Locked => "hud-shrine_travel-locked",
+                            ShrineTravelError::InCombat => "hud-shrine_travel-in_combat",
+                            ShrineTravelError::NotEnoughCoins => {
+                                "hud-shrine_travel-not_enough_coins"
+                            },
+                        });
+                        s.errors.push_back(text.to_string());
+                    });
+                },
+                Notification::GuildFailed(error) => {
+                    state.update(|s| {
+                        if s.errors.is_empty() {
+                            s.last_error_update = Instant::now();
+                        }
+                        let text = self.i18n.get_msg(match error {
+                            GuildError::AlreadyInGuild => "hud-guild-error-already_in_guild",
+                            GuildError::NotInGuild => "hud-guild-error-not_in_guild",
+                            GuildError::TargetInGuild => "hud-guild-error-target_in_guild",
+                            GuildError::NotAMember => "hud-guild-error-not_a_member",
+                            GuildError::NoPermission => "hud-guild-error-no_permission",
+                            GuildError::InvalidName => "hud-guild-error-invalid_name",
+                            GuildError::InvalidTag => "hud-guild-error-invalid_tag",
+                            GuildError::NameTaken => "hud-guild-error-name_taken",
+                            GuildError::TagTaken => "hud-guild-error-tag_taken",
+                            GuildError::NotEnoughCoins => "hud-guild-error-not_enough_coins",
+                            GuildError::GuildFull => "hud-guild-error-guild_full",
+                            GuildError::NoHall => "hud-guild-error-no_hall",
+                            GuildError::HallTaken => "hud-guild-error-hall_taken",
+                            GuildError::AlreadyOwnsHall => "hud-guild-error-already_owns_hall",
+                            GuildError::NotAHall => "hud-guild-error-not_a_hall",
+                            GuildError::TooFarFromHall => "hud-guild-error-too_far_from_hall",
+                            GuildError::StorageFull => "hud-guild-error-storage_full",
+                            GuildError::InventoryFull => "hud-guild-error-inventory_full",
+                            GuildError::InvalidNotice => "hud-guild-error-invalid_notice",
+                            GuildError::Internal => "hud-guild-error-internal",
+                        });
+                        s.errors.push_back(text.to_string());
+                    });
+                },
             }
         }
 
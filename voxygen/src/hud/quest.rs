@@ -1,5 +1,8 @@
 use client::Client;
-use common::comp::{inventory::item::item_key::ItemKey, Stats};
+use common::{
+    bounty::{Bounty, BountyTarget, MAX_ACTIVE_BOUNTIES},
+    rtsim::ReputationTier,
+};
 use conrod_core::{
     color,
     widget::{self, Button, Image, Rectangle, Scrollbar, Text},
@@ -12,8 +15,7 @@ use inline_tweak::*;
 
 use super::{
     img_ids::{Imgs, ImgsRot},
-    item_imgs::{animate_by_pulse, ItemImgs},
-    Show, HP_COLOR, TEXT_COLOR, TEXT_DULL_RED_COLOR, TEXT_VELORITE, UI_HIGHLIGHT_0, UI_MAIN,
+    Show, CRITICAL_HP_COLOR, HP_COLOR, TEXT_COLOR, TEXT_VELORITE, UI_HIGHLIGHT_0, UI_MAIN,
 };
 
 pub struct State {
@@ -31,31 +33,29 @@ widget_ids! {
         title,
         content_align,
         scrollbar,
-        intro_txt,
-        desc_txt_0,
-        quest_objectives[],
-        quest_reward_txt,
-        objective_text,
-        quest_rewards_frames[],
-        quest_rewards_icons[],
-        quest_rewards_txts[],
-        accept_btn,
-        decline_btn,
+        board_txt,
+        board_empty_txt,
+        offer_txts[],
+        offer_btns[],
+        active_txt,
+        active_empty_txt,
+        active_txts[],
+        reputation_txt,
+        reputation_empty_txt,
+        reputation_txts[],
+        calendar_btn,
     }
 }
 
 #[derive(WidgetCommon)]
 pub struct Quest<'a> {
     _show: &'a Show,
-    _client: &'a Client,
+    client: &'a Client,
     imgs: &'a Imgs,
     fonts: &'a Fonts,
     localized_strings: &'a Localization,
     _rot_imgs: &'a ImgsRot,
     _tooltip_manager: &'a mut TooltipManager,
-    stats: &'a Stats,
-    item_imgs: &'a ItemImgs,
-    pulse: f32,
 
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
@@ -64,27 +64,21 @@ pub struct Quest<'a> {
 impl<'a> Quest<'a> {
     pub fn new(
         _show: &'a Show,
-        _client: &'a Client,
+        client: &'a Client,
         imgs: &'a Imgs,
         fonts: &'a Fonts,
         localized_strings: &'a Localization,
         _rot_imgs: &'a ImgsRot,
         _tooltip_manager: &'a mut TooltipManager,
-        stats: &'a Stats,
-        item_imgs: &'a ItemImgs,
-        pulse: f32,
     ) -> Self {
         Self {
             _show,
-            _client,
+            client,
             imgs,
             _rot_imgs,
             fonts,
             localized_strings,
             _tooltip_manager,
-            stats,
-            item_imgs,
-            pulse,
             common: widget::CommonBuilder::default(),
         }
     }
@@ -92,6 +86,8 @@ impl<'a> Quest<'a> {
 
 pub enum Event {
     Close,
+    AcceptBounty(BountyTarget),
+    ToggleCalendar,
 }
 
 impl<'a> Widget for Quest<'a> {
@@ -153,7 +149,7 @@ impl<'a> Widget for Quest<'a> {
             .set(state.ids.title, ui);
 
         // Content Alignment
-        Rectangle::fill_with([tweak!(270.0), tweak!(395.0)], color::TRANSPARENT)
+        Rectangle::fill_with([tweak!(270.0), tweak!(355.0)], color::TRANSPARENT)
             .mid_top_with_margin_on(state.ids.frame, tweak!(55.0))
             .scroll_kids_vertically()
             .set(state.ids.content_align, ui);
@@ -162,175 +158,209 @@ impl<'a> Widget for Quest<'a> {
             .color(Color::Rgba(0.79, 1.09, 1.09, 0.0))
             .set(state.ids.scrollbar, ui);
 
-        // Quest Text
-
-        // Introduction
-
-        Text::new(
-            &self
-                .localized_strings
-                .get_msg_ctx("hud-quest-intro", &i18n::fluent_args! {
-                    "playername" => self.stats.name.to_string(),
-                }),
-        )
-        .top_left_with_margins_on(state.ids.content_align, tweak!(0.0), tweak!(2.0))
-        .font_id(self.fonts.cyri.conrod_id)
-        .font_size(self.fonts.cyri.scale(tweak!(20)))
-        .color(TEXT_COLOR)
-        .set(state.ids.intro_txt, ui);
-
-        enum QuestType {
-            FetchQuest,
-            // KillQuest,
+        // Event calendar of the server
+        if Button::image(self.imgs.button)
+            .mid_bottom_with_margin_on(state.ids.frame, 10.0)
+            .w_h(140.0, 30.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .label(&self.localized_strings.get_msg("hud-calendar"))
+            .label_y(conrod_core::position::Relative::Scalar(2.0))
+            .label_color(TEXT_COLOR)
+            .label_font_size(self.fonts.cyri.scale(16))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .set(state.ids.calendar_btn, ui)
+            .was_clicked()
+        {
+            event = Some(Event::ToggleCalendar);
         }
 
-        // Define type of quest to change introduction text
-
-        let quest_type = QuestType::FetchQuest;
-
-        let q_desc0 = match quest_type {
-            QuestType::FetchQuest => "hud-quest-desc-fetch",
-            // QuestType::KillQuest => "hud-quest-desc-kill",
+        let bounty_text = |bounty: &Bounty| {
+            let key = match bounty.target {
+                BountyTarget::Hunt(_) => "hud-bounty-hunt",
+                BountyTarget::Recover(_) => "hud-bounty-recover",
+            };
+            let distance = self
+                .client
+                .position()
+                .map_or(0.0, |pos| pos.xy().distance(bounty.wpos));
+            self.localized_strings
+                .get_msg_ctx(key, &i18n::fluent_args! {
+                    "target" => self.localized_strings.get_content(&bounty.target_name),
+                    "distance" => distance.round() as u32,
+                    "reward" => bounty.reward,
+                })
+                .into_owned()
         };
 
-        Text::new(&self.localized_strings.get_msg(q_desc0))
-            .top_left_with_margins_on(state.ids.intro_txt, tweak!(40.0), tweak!(0.0))
-            .font_id(self.fonts.cyri.conrod_id)
-            .font_size(self.fonts.cyri.scale(tweak!(20)))
-            .color(TEXT_COLOR)
-            .set(state.ids.desc_txt_0, ui);
-
-        // Objective(s)
-        let objective_amount = 20.0;
-        let objective_name = "Flower";
-        let objective_txt = format!("{}x {}", objective_amount, objective_name);
-
-        Text::new(&objective_txt)
-            .down_from(state.ids.desc_txt_0, tweak!(10.0))
-            .font_id(self.fonts.cyri.conrod_id)
-            .font_size(self.fonts.cyri.scale(tweak!(20)))
-            .color(TEXT_VELORITE)
-            .set(state.ids.objective_text, ui);
-
-        Text::new(&self.localized_strings.get_msg("hud-quest-reward"))
-            .down_from(state.ids.objective_text, tweak!(30.0))
-            .font_id(self.fonts.cyri.conrod_id)
-            .font_size(self.fonts.cyri.scale(tweak!(20)))
-            .color(TEXT_COLOR)
-            .set(state.ids.quest_reward_txt, ui);
-
-        // insert reward item data here
-        // [amount, item_desc]
+        // Bounties offered by the notice board the character is reading, if any
+        let offers = self.client.notice_board().unwrap_or(&[]);
+        let active = self.client.bounties();
+        let can_accept = active.len() < MAX_ACTIVE_BOUNTIES;
 
-        //("common.items.weapons.sword.caladbolg");
-        let rewards = [
-            (1, "common.items.weapons.dagger.starter_dagger", "Dagger"),
-            (4, "common.items.crafting_ing.seashells", "Seashell"),
-            (
-                8,
-                "common.items.crafting_ing.animal_misc.raptor_feather",
-                "Raptor Feather",
-            ),
-        ];
-        let rewards_amount = rewards.len();
-
-        if state.ids.quest_rewards_frames.len() < rewards_amount {
+        if state.ids.offer_txts.len() < offers.len() {
             state.update(|s| {
                 s.ids
-                    .quest_rewards_frames
-                    .resize(rewards.len(), &mut ui.widget_id_generator())
-            })
-        };
-        if state.ids.quest_rewards_icons.len() < rewards_amount {
-            state.update(|s| {
+                    .offer_txts
+                    .resize(offers.len(), &mut ui.widget_id_generator());
                 s.ids
-                    .quest_rewards_icons
-                    .resize(rewards.len(), &mut ui.widget_id_generator())
-            })
-        };
-        if state.ids.quest_rewards_txts.len() < rewards_amount {
+                    .offer_btns
+                    .resize(offers.len(), &mut ui.widget_id_generator());
+            });
+        }
+        if state.ids.active_txts.len() < active.len() {
             state.update(|s| {
                 s.ids
-                    .quest_rewards_txts
-                    .resize(rewards.len(), &mut ui.widget_id_generator())
-            })
-        };
+                    .active_txts
+                    .resize(active.len(), &mut ui.widget_id_generator())
+            });
+        }
 
-        for (i, item) in rewards.iter().enumerate() {
-            // Slot BG
-            let mut frame_img = Image::new(self.imgs.skillbar_slot)
-                .w_h(40.0, 40.0)
-                .color(Some(Color::Rgba(1.0, 1.0, 1.0, 1.0)));
+        let mut last = None;
+        if self.client.notice_board().is_some() {
+            Text::new(&self.localized_strings.get_msg("hud-bounty-board"))
+                .top_left_with_margins_on(state.ids.content_align, 0.0, 2.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(20))
+                .color(TEXT_COLOR)
+                .set(state.ids.board_txt, ui);
+            last = Some(state.ids.board_txt);
+
+            if offers.is_empty() {
+                Text::new(&self.localized_strings.get_msg("hud-bounty-board-empty"))
+                    .down_from(state.ids.board_txt, 10.0)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(16))
+                    .color(TEXT_COLOR)
+                    .set(state.ids.board_empty_txt, ui);
+                last = Some(state.ids.board_empty_txt);
+            }
 
-            if i == 0 {
-                frame_img = frame_img.down_from(state.ids.quest_reward_txt, tweak!(10.0))
-            } else {
-                frame_img = frame_img.down_from(state.ids.quest_rewards_frames[i - 1], tweak!(5.0))
+            for (i, offer) in offers.iter().enumerate() {
+                let accepted = active.iter().any(|bounty| bounty.target == offer.target);
+                Text::new(&bounty_text(offer))
+                    .down_from(last.unwrap_or(state.ids.board_txt), 10.0)
+                    .w(260.0)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(16))
+                    .color(if accepted { TEXT_VELORITE } else { TEXT_COLOR })
+                    .set(state.ids.offer_txts[i], ui);
+                last = Some(state.ids.offer_txts[i]);
+
+                if !accepted && can_accept {
+                    if Button::image(self.imgs.button)
+                        .down_from(state.ids.offer_txts[i], 5.0)
+                        .w_h(100.0, 30.0)
+                        .hover_image(self.imgs.button_hover)
+                        .press_image(self.imgs.button_press)
+                        .label(&self.localized_strings.get_msg("hud-quest-accept"))
+                        .label_y(conrod_core::position::Relative::Scalar(2.0))
+                        .label_color(TEXT_COLOR)
+                        .label_font_size(self.fonts.cyri.scale(16))
+                        .label_font_id(self.fonts.cyri.conrod_id)
+                        .image_color(HP_COLOR)
+                        .set(state.ids.offer_btns[i], ui)
+                        .was_clicked()
+                    {
+                        event = Some(Event::AcceptBounty(offer.target));
+                    }
+                    last = Some(state.ids.offer_btns[i]);
+                }
             }
-            frame_img.set(state.ids.quest_rewards_frames[i], ui);
+        }
 
-            // Item amount and text
-            let item_txt = if item.0 == 1 {
-                item.2.to_string()
-            } else {
-                format!("{}x {}", item.0, item.2)
-            };
-            //INPUT QUALITY HERE TO CHANGE COLOR
-            let item_quality = TEXT_VELORITE;
-            Text::new(&item_txt)
-                .right_from(state.ids.quest_rewards_frames[i], tweak!(10.0))
+        // Quest log
+        let log_txt = Text::new(&self.localized_strings.get_msg("hud-bounty-active"))
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR);
+        match last {
+            Some(last) => log_txt.down_from(last, 20.0),
+            None => log_txt.top_left_with_margins_on(state.ids.content_align, 0.0, 2.0),
+        }
+        .set(state.ids.active_txt, ui);
+
+        if active.is_empty() {
+            Text::new(&self.localized_strings.get_msg("hud-bounty-active-empty"))
+                .down_from(state.ids.active_txt, 10.0)
                 .font_id(self.fonts.cyri.conrod_id)
-                .font_size(self.fonts.cyri.scale(tweak!(18)))
-                .color(item_quality)
-                .set(state.ids.quest_rewards_txts[i], ui);
-            // Item image
-            Image::new(animate_by_pulse(
-                &self
-                    .item_imgs
-                    .img_ids_or_not_found_img(ItemKey::Simple(item.1.to_string())),
-                self.pulse,
-            ))
-            .w_h(38.0, 38.0)
-            .middle_of(state.ids.quest_rewards_frames[i])
-            .color(Some(Color::Rgba(1.0, 1.0, 1.0, 1.0)))
-            .set(state.ids.quest_rewards_icons[i], ui);
+                .font_size(self.fonts.cyri.scale(16))
+                .color(TEXT_COLOR)
+                .set(state.ids.active_empty_txt, ui);
         }
 
-        // Accept/Decline Buttons
+        let mut last = state.ids.active_empty_txt;
+        for (i, bounty) in active.iter().enumerate() {
+            Text::new(&bounty_text(bounty))
+                .down_from(
+                    if i == 0 {
+                        state.ids.active_txt
+                    } else {
+                        state.ids.active_txts[i - 1]
+                    },
+                    10.0,
+                )
+                .w(260.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(16))
+                .color(TEXT_VELORITE)
+                .set(state.ids.active_txts[i], ui);
+            last = state.ids.active_txts[i];
+        }
 
-        if Button::image(self.imgs.button)
-            .bottom_left_with_margins_on(state.ids.content_align, tweak!(5.0), tweak!(5.0))
-            .w_h(tweak!(120.0), tweak!(50.0))
-            .hover_image(self.imgs.button_hover)
-            .press_image(self.imgs.button_press)
-            .label(&self.localized_strings.get_msg("hud-quest-accept"))
-            .label_y(conrod_core::position::Relative::Scalar(3.0))
-            .label_color(TEXT_COLOR)
-            .label_font_size(self.fonts.cyri.scale(20))
-            .label_font_id(self.fonts.cyri.conrod_id)
-            .image_color(HP_COLOR)
-            .set(state.ids.accept_btn, ui)
-            .was_clicked()
-        {
-            event = Some(Event::Close);
-        };
+        // Reputation with factions
+        let reputation = self.client.reputation();
+        if state.ids.reputation_txts.len() < reputation.len() {
+            state.update(|s| {
+                s.ids
+                    .reputation_txts
+                    .resize(reputation.len(), &mut ui.widget_id_generator())
+            });
+        }
+        Text::new(&self.localized_strings.get_msg("hud-reputation"))
+            .down_from(last, 20.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.reputation_txt, ui);
 
-        if Button::image(self.imgs.button)
-            .bottom_right_with_margins_on(state.ids.content_align, tweak!(5.0), tweak!(5.0))
-            .w_h(tweak!(120.0), tweak!(50.0))
-            .hover_image(self.imgs.button_hover)
-            .press_image(self.imgs.button_press)
-            .label(&self.localized_strings.get_msg("hud-quest-decline"))
-            .label_y(conrod_core::position::Relative::Scalar(3.0))
-            .label_color(TEXT_COLOR)
-            .label_font_size(self.fonts.cyri.scale(20))
-            .label_font_id(self.fonts.cyri.conrod_id)
-            .image_color(TEXT_DULL_RED_COLOR)
-            .set(state.ids.decline_btn, ui)
-            .was_clicked()
-        {
-            event = Some(Event::Close);
-        };
+        if reputation.is_empty() {
+            Text::new(&self.localized_strings.get_msg("hud-reputation-empty"))
+                .down_from(state.ids.reputation_txt, 10.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(16))
+                .color(TEXT_COLOR)
+                .set(state.ids.reputation_empty_txt, ui);
+        }
+
+        for (i, faction) in reputation.iter().enumerate() {
+            let (key, color) = match faction.tier {
+                ReputationTier::Hostile => ("hud-reputation-hostile", CRITICAL_HP_COLOR),
+                ReputationTier::Neutral => ("hud-reputation-neutral", TEXT_COLOR),
+                ReputationTier::Friendly => ("hud-reputation-friendly", HP_COLOR),
+                ReputationTier::Honored => ("hud-reputation-honored", TEXT_VELORITE),
+            };
+            Text::new(
+                &self
+                    .localized_strings
+                    .get_msg_ctx(key, &i18n::fluent_args! {
+                        "faction" => faction.name.as_str(),
+                    }),
+            )
+            .down_from(
+                if i == 0 {
+                    state.ids.reputation_txt
+                } else {
+                    state.ids.reputation_txts[i - 1]
+                },
+                10.0,
+            )
+            .w(260.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(16))
+            .color(color)
+            .set(state.ids.reputation_txts[i], ui);
+        }
 
         event
     }
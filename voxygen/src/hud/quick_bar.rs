@@ -0,0 +1,224 @@
+//! A secondary bar for consumables, tools and emotes, so that they can be used
+//! mid-fight without opening the bag.
+//!
+//! Every slot does exactly one thing per use: items are used through the same
+//! inventory events as using them from the bag, and are validated by the
+//! server the same way.
+
+use super::{
+    emote_wheel::emote_label, img_ids::Imgs, item_imgs::ItemImgs, slots, Event, TEXT_COLOR,
+};
+use crate::ui::{
+    fonts::Fonts,
+    slot::{ContentSize, SlotMaker},
+};
+use common::{
+    comp::{
+        inventory::item::{item_key::ItemKey, Item, ItemKind},
+        slot::Slot as InvSlot,
+        Inventory,
+    },
+    states::emote::EmoteKind,
+};
+use conrod_core::{
+    widget::{self, Text},
+    widget_ids, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+pub const QUICK_SLOTS: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Slot(pub usize);
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum SlotContents {
+    Inventory(u64, ItemKey),
+    Emote(EmoteKind),
+}
+
+#[derive(Clone, Default)]
+pub struct State {
+    pub slots: [Option<SlotContents>; QUICK_SLOTS],
+}
+
+impl State {
+    pub fn new(slots: [Option<SlotContents>; QUICK_SLOTS]) -> Self { Self { slots } }
+
+    pub fn get(&self, slot: Slot) -> Option<&SlotContents> {
+        self.slots.get(slot.0).and_then(Option::as_ref)
+    }
+
+    pub fn swap(&mut self, a: Slot, b: Slot) { self.slots.swap(a.0, b.0); }
+
+    pub fn clear_slot(&mut self, slot: Slot) { self.slots[slot.0] = None; }
+
+    /// Whether an item can be put in the quick bar
+    pub fn accepts(item: &Item) -> bool {
+        matches!(
+            &*item.kind(),
+            ItemKind::Consumable { .. }
+                | ItemKind::Throwable { .. }
+                | ItemKind::Utility { .. }
+                | ItemKind::Tool(_)
+                | ItemKind::Lantern(_)
+        )
+    }
+
+    /// Returns false if the item can't be put in the quick bar
+    pub fn add_inventory_link(&mut self, slot: Slot, item: &Item) -> bool {
+        let accepted = Self::accepts(item);
+        if accepted {
+            self.slots[slot.0] = Some(SlotContents::Inventory(
+                item.item_hash(),
+                ItemKey::from(item),
+            ));
+        }
+        accepted
+    }
+
+    pub fn add_emote(&mut self, slot: Slot, kind: EmoteKind) {
+        self.slots[slot.0] = Some(SlotContents::Emote(kind));
+    }
+
+    /// The event using the given slot, if it holds anything usable
+    pub fn use_slot(&self, slot: Slot, inventory: Option<&Inventory>) -> Option<Event> {
+        match self.get(slot)? {
+            SlotContents::Inventory(item_hash, _) => inventory
+                .and_then(|inv| inv.get_slot_from_hash(*item_hash))
+                .map(|slot| Event::UseSlot {
+                    slot: InvSlot::Inventory(slot),
+                    bypass_dialog: false,
+                }),
+            SlotContents::Emote(kind) => Some(Event::Emote(*kind)),
+        }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        slots[],
+        emote_labels[],
+    }
+}
+
+#[derive(WidgetCommon)]
+pub struct QuickBar<'a> {
+    imgs: &'a Imgs,
+    item_imgs: &'a ItemImgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    inventory: &'a Inventory,
+    quick_bar: &'a State,
+    slot_manager: &'a mut slots::SlotManager,
+    pulse: f32,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> QuickBar<'a> {
+    pub fn new(
+        imgs: &'a Imgs,
+        item_imgs: &'a ItemImgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        inventory: &'a Inventory,
+        quick_bar: &'a State,
+        slot_manager: &'a mut slots::SlotManager,
+        pulse: f32,
+    ) -> Self {
+        Self {
+            imgs,
+            item_imgs,
+            fonts,
+            localized_strings,
+            inventory,
+            quick_bar,
+            slot_manager,
+            pulse,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct QuickBarState {
+    ids: Ids,
+}
+
+impl<'a> Widget for QuickBar<'a> {
+    type Event = ();
+    type State = QuickBarState;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        QuickBarState {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("QuickBar::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        if state.ids.slots.len() < QUICK_SLOTS {
+            state.update(|s| {
+                s.ids
+                    .slots
+                    .resize(QUICK_SLOTS, &mut ui.widget_id_generator());
+                s.ids
+                    .emote_labels
+                    .resize(QUICK_SLOTS, &mut ui.widget_id_generator());
+            });
+        }
+
+        let content_source = (self.quick_bar, self.inventory);
+        let image_source = (self.item_imgs, self.imgs);
+        let mut slot_maker = SlotMaker {
+            empty_slot: self.imgs.skillbar_slot,
+            filled_slot: self.imgs.skillbar_slot,
+            selected_slot: self.imgs.inv_slot_sel,
+            background_color: None,
+            content_size: ContentSize {
+                width_height_ratio: 1.0,
+                max_fraction: 0.9,
+            },
+            selected_content_scale: 1.0,
+            amount_font: self.fonts.cyri.conrod_id,
+            amount_margins: Vec2::new(1.0, 1.0),
+            amount_font_size: self.fonts.cyri.scale(12),
+            amount_text_color: TEXT_COLOR,
+            content_source: &content_source,
+            image_source: &image_source,
+            slot_manager: Some(self.slot_manager),
+            pulse: self.pulse,
+        };
+
+        // A column along the right edge of the screen, from the top
+        for i in 0..QUICK_SLOTS {
+            let slot = slot_maker.fabricate(Slot(i), [40.0; 2]);
+            let slot = if i == 0 {
+                slot.mid_right_with_margin_on(ui.window, 10.0)
+                    .y_relative_to(ui.window, (QUICK_SLOTS - 1) as f64 * 21.0)
+            } else {
+                slot.down_from(state.ids.slots[i - 1], 2.0)
+            };
+            slot.set(state.ids.slots[i], ui);
+
+            // Emotes have no icons of their own, so they are named
+            if let Some(SlotContents::Emote(kind)) = self.quick_bar.get(Slot(i)) {
+                Text::new(&self.localized_strings.get_msg(&emote_label(*kind)))
+                    .mid_bottom_with_margin_on(state.ids.slots[i], 2.0)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(10))
+                    .color(TEXT_COLOR)
+                    .graphics_for(state.ids.slots[i])
+                    .set(state.ids.emote_labels[i], ui);
+            }
+        }
+    }
+}
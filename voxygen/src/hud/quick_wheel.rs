@@ -0,0 +1,128 @@
+use super::{
+    emote_wheel::emote_label,
+    img_ids::Imgs,
+    item_imgs::{animate_by_pulse, ItemImgs},
+    quick_bar::{self, SlotContents, QUICK_SLOTS},
+    TEXT_COLOR,
+};
+use crate::ui::fonts::Fonts;
+use conrod_core::{
+    widget::{self, Button},
+    widget_ids, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use std::f64::consts::PI;
+
+/// Distance from the centre of the screen to the centre of each slot
+const WHEEL_RADIUS: f64 = 120.0;
+
+widget_ids! {
+    struct Ids {
+        slot_buttons[],
+    }
+}
+
+/// The quick bar laid out around the centre of the screen, so that it can be
+/// reached with a controller
+#[derive(WidgetCommon)]
+pub struct QuickWheel<'a> {
+    imgs: &'a Imgs,
+    item_imgs: &'a ItemImgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    quick_bar: &'a quick_bar::State,
+    pulse: f32,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> QuickWheel<'a> {
+    pub fn new(
+        imgs: &'a Imgs,
+        item_imgs: &'a ItemImgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        quick_bar: &'a quick_bar::State,
+        pulse: f32,
+    ) -> Self {
+        Self {
+            imgs,
+            item_imgs,
+            fonts,
+            localized_strings,
+            quick_bar,
+            pulse,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    Use(quick_bar::Slot),
+}
+
+impl<'a> Widget for QuickWheel<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("QuickWheel::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        if state.ids.slot_buttons.len() < QUICK_SLOTS {
+            state.update(|s| {
+                s.ids
+                    .slot_buttons
+                    .resize(QUICK_SLOTS, &mut ui.widget_id_generator())
+            });
+        }
+
+        let mut event = None;
+        for i in 0..QUICK_SLOTS {
+            // Same layout as the emote wheel: clockwise, starting at the top
+            let angle = PI / 2.0 - i as f64 * 2.0 * PI / QUICK_SLOTS as f64;
+            let (x, y) = (angle.cos() * WHEEL_RADIUS, angle.sin() * WHEEL_RADIUS);
+            let button = match self.quick_bar.get(quick_bar::Slot(i)) {
+                Some(SlotContents::Inventory(_, item_key)) => Button::image(animate_by_pulse(
+                    &self.item_imgs.img_ids_or_not_found_img(item_key.clone()),
+                    self.pulse,
+                ))
+                .w_h(50.0, 50.0),
+                Some(SlotContents::Emote(kind)) => Button::image(self.imgs.button)
+                    .w_h(120.0, 40.0)
+                    .hover_image(self.imgs.button_hover)
+                    .press_image(self.imgs.button_press)
+                    .label(&self.localized_strings.get_msg(&emote_label(*kind)))
+                    .label_y(conrod_core::position::Relative::Scalar(2.0))
+                    .label_color(TEXT_COLOR)
+                    .label_font_size(self.fonts.cyri.scale(16))
+                    .label_font_id(self.fonts.cyri.conrod_id),
+                None => Button::image(self.imgs.skillbar_slot).w_h(50.0, 50.0),
+            };
+            if button
+                .x_y_relative_to(ui.window, x, y)
+                .set(state.ids.slot_buttons[i], ui)
+                .was_clicked()
+                && self.quick_bar.get(quick_bar::Slot(i)).is_some()
+            {
+                event = Some(Event::Use(quick_bar::Slot(i)));
+            }
+        }
+
+        event
+    }
+}
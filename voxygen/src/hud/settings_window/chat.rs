@@ -56,6 +56,9 @@ widget_ids! {
         btn_messages_faction,
         text_messages_faction,
         icon_messages_faction,
+        btn_messages_guild,
+        text_messages_guild,
+        icon_messages_guild,
         btn_messages_group,
         text_messages_group,
         icon_messages_group,
@@ -489,10 +492,29 @@ impl<'a> Widget for Chat<'a> {
                 .right_from(state.ids.text_messages_faction, 5.0)
                 .set(state.ids.icon_messages_faction, ui);
 
+            //Messages - guild
+            if chat_tab.filter.message_guild
+                != create_toggle(chat_tab.filter.message_guild, !chat_tab.filter.message_all)
+                    .down_from(state.ids.btn_messages_faction, 10.0)
+                    .set(state.ids.btn_messages_guild, ui)
+                && !chat_tab.filter.message_all
+            {
+                updated_chat_tab.filter.message_guild = !chat_tab.filter.message_guild;
+            }
+
+            let guild_text = self.localized_strings.get_msg("hud-settings-guild");
+            create_toggle_text(&guild_text, !chat_tab.filter.message_all)
+                .right_from(state.ids.btn_messages_guild, 5.0)
+                .set(state.ids.text_messages_guild, ui);
+
+            create_toggle_icon(self.imgs.chat_faction_small, !chat_tab.filter.message_all)
+                .right_from(state.ids.text_messages_guild, 5.0)
+                .set(state.ids.icon_messages_guild, ui);
+
             //Messages - world
             if chat_tab.filter.message_world
                 != create_toggle(chat_tab.filter.message_world, !chat_tab.filter.message_all)
-                    .down_from(state.ids.btn_messages_faction, 10.0)
+                    .down_from(state.ids.btn_messages_guild, 10.0)
                     .set(state.ids.btn_messages_world, ui)
                 && !chat_tab.filter.message_all
             {
@@ -65,6 +65,9 @@ widget_ids! {
         aim_offset_y_slider,
         aim_offset_y_label,
         aim_offset_y_value,
+        interact_highlight_slider,
+        interact_highlight_label,
+        interact_highlight_value,
     }
 }
 
@@ -734,12 +737,49 @@ impl<'a> Widget for Gameplay<'a> {
             .color(TEXT_COLOR)
             .set(state.ids.aim_offset_y_value, ui);
 
+        // Interactable highlight
+        let display_interact_highlight = self.global_state.settings.gameplay.interact_highlight;
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-interact_highlight"),
+        )
+        .down_from(state.ids.aim_offset_y_slider, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(TEXT_COLOR)
+        .set(state.ids.interact_highlight_label, ui);
+
+        if let Some(new_val) = ImageSlider::continuous(
+            display_interact_highlight,
+            0.0,
+            1.0,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(550.0, 22.0)
+        .down_from(state.ids.interact_highlight_label, 10.0)
+        .track_breadth(30.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.interact_highlight_slider, ui)
+        {
+            events.push(AdjustInteractHighlight(new_val));
+        }
+
+        Text::new(&format!("{:.0}%", display_interact_highlight * 100.0))
+            .right_from(state.ids.interact_highlight_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.interact_highlight_value, ui);
+
         // Reset the gameplay settings to the default settings
         if Button::image(self.imgs.button)
             .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
             .hover_image(self.imgs.button_hover)
             .press_image(self.imgs.button_press)
-            .down_from(state.ids.aim_offset_y_slider, 12.0)
+            .down_from(state.ids.interact_highlight_slider, 12.0)
             .label(
                 &self
                     .localized_strings
@@ -110,6 +110,16 @@ widget_ids! {
         experience_numbers_title,
         accum_experience_text,
         accum_experience_button,
+        //
+        nameplates_title,
+        nameplate_hostiles_only_button,
+        nameplate_hostiles_only_text,
+        nameplate_hide_full_health_button,
+        nameplate_hide_full_health_text,
+        nameplate_scale_by_distance_button,
+        nameplate_scale_by_distance_text,
+        nameplate_show_tags_button,
+        nameplate_show_tags_text,
     }
 }
 
@@ -1327,6 +1337,67 @@ impl<'a> Widget for Interface<'a> {
         .color(TEXT_COLOR)
         .set(state.ids.accum_experience_text, ui);
 
+        // Nameplates
+        Text::new(&self.localized_strings.get_msg("hud-settings-nameplates"))
+            .down_from(state.ids.accum_experience_button, 20.0)
+            .font_size(self.fonts.cyri.scale(18))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.nameplates_title, ui);
+
+        let interface = &self.global_state.settings.interface;
+        let nameplate_toggles: [(bool, _, _, _, fn(bool) -> InterfaceChange); 4] = [
+            (
+                interface.nameplate_hostiles_only,
+                state.ids.nameplate_hostiles_only_button,
+                state.ids.nameplate_hostiles_only_text,
+                "hud-settings-nameplate_hostiles_only",
+                NameplateHostilesOnly,
+            ),
+            (
+                interface.nameplate_hide_full_health,
+                state.ids.nameplate_hide_full_health_button,
+                state.ids.nameplate_hide_full_health_text,
+                "hud-settings-nameplate_hide_full_health",
+                NameplateHideFullHealth,
+            ),
+            (
+                interface.nameplate_scale_by_distance,
+                state.ids.nameplate_scale_by_distance_button,
+                state.ids.nameplate_scale_by_distance_text,
+                "hud-settings-nameplate_scale_by_distance",
+                NameplateScaleByDistance,
+            ),
+            (
+                interface.nameplate_show_tags,
+                state.ids.nameplate_show_tags_button,
+                state.ids.nameplate_show_tags_text,
+                "hud-settings-nameplate_show_tags",
+                NameplateShowTags,
+            ),
+        ];
+        let mut above = state.ids.nameplates_title;
+        for (enabled, button_id, text_id, key, event) in nameplate_toggles {
+            let toggled =
+                ToggleButton::new(enabled, self.imgs.checkbox, self.imgs.checkbox_checked)
+                    .w_h(18.0, 18.0)
+                    .down_from(above, 8.0)
+                    .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+                    .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+                    .set(button_id, ui);
+            if toggled != enabled {
+                events.push(event(toggled));
+            }
+            Text::new(&self.localized_strings.get_msg(key))
+                .right_from(button_id, 10.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .graphics_for(button_id)
+                .color(TEXT_COLOR)
+                .set(text_id, ui);
+            above = button_id;
+        }
+
         // Reset the interface settings to the default settings
         if Button::image(self.imgs.button)
             .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
@@ -25,6 +25,8 @@ widget_ids! {
         player_physics_behavior_list,
         lossy_terrain_compression_button,
         lossy_terrain_compression_label,
+        telemetry_text,
+        telemetry_button,
         third_party_integrations_title,
         enable_discord_integration_text,
         enable_discord_integration_button
@@ -256,6 +258,30 @@ impl<'a> Widget for Networking<'a> {
             ));
         }
 
+        // Crash and performance reports
+        let telemetry = ToggleButton::new(
+            self.global_state.settings.telemetry.enabled,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.player_physics_behavior_list, 16.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.telemetry_button, ui);
+
+        if self.global_state.settings.telemetry.enabled != telemetry {
+            events.push(NetworkingChange::ToggleTelemetry(telemetry));
+        }
+
+        Text::new(&self.localized_strings.get_msg("hud-settings-telemetry"))
+            .right_from(state.ids.telemetry_button, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .graphics_for(state.ids.telemetry_button)
+            .color(TEXT_COLOR)
+            .set(state.ids.telemetry_text, ui);
+
         #[cfg(feature = "discord")]
         {
             // Third party integrations
@@ -264,7 +290,7 @@ impl<'a> Widget for Networking<'a> {
                     .localized_strings
                     .get_msg("hud-settings-third_party_integrations"),
             )
-            .down_from(state.ids.player_physics_behavior_list, 16.0)
+            .down_from(state.ids.telemetry_button, 16.0)
             .font_size(self.fonts.cyri.scale(18))
             .font_id(self.fonts.cyri.conrod_id)
             .color(TEXT_COLOR)
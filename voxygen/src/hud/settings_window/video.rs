@@ -8,6 +8,7 @@ use crate::{
     render::{
         AaMode, BloomConfig, BloomFactor, BloomMode, CloudMode, FluidMode, LightingMode,
         PresentMode, ReflectionMode, RenderMode, ShadowMapMode, ShadowMode, UpscaleMode,
+        MAX_POINT_SHADOWS,
     },
     session::settings_change::Graphics as GraphicsChange,
     settings::{Fps, GraphicsSettings},
@@ -114,6 +115,8 @@ widget_ids! {
         particles_label,
         weapon_trails_button,
         weapon_trails_label,
+        cloth_physics_button,
+        cloth_physics_label,
         flashing_lights_button,
         flashing_lights_label,
         flashing_lights_info_label,
@@ -127,6 +130,9 @@ widget_ids! {
         shadow_mode_map_resolution_text,
         shadow_mode_map_resolution_slider,
         shadow_mode_map_resolution_value,
+        point_shadows_text,
+        point_shadows_slider,
+        point_shadows_value,
         rain_map_resolution_text,
         rain_map_resolution_slider,
         rain_map_resolution_value,
@@ -1322,6 +1328,45 @@ impl<'a> Widget for Video<'a> {
                 .font_id(self.fonts.cyri.conrod_id)
                 .color(TEXT_COLOR)
                 .set(state.ids.shadow_mode_map_resolution_value, ui);
+
+            // Number of point lights casting shadows
+            Text::new(
+                &self
+                    .localized_strings
+                    .get_msg("hud-settings-shadow_rendering_mode-map-point_shadows"),
+            )
+            .down_from(state.ids.shadow_mode_list, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.point_shadows_text, ui);
+
+            if let Some(new_val) = ImageSlider::discrete(
+                render_mode.point_shadows.min(MAX_POINT_SHADOWS),
+                0,
+                MAX_POINT_SHADOWS,
+                self.imgs.slider_indicator,
+                self.imgs.slider,
+            )
+            .w_h(104.0, 22.0)
+            .right_from(state.ids.point_shadows_text, 8.0)
+            .track_breadth(12.0)
+            .slider_length(10.0)
+            .pad_track((5.0, 5.0))
+            .set(state.ids.point_shadows_slider, ui)
+            {
+                events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
+                    point_shadows: new_val,
+                    ..render_mode.clone()
+                })));
+            }
+
+            Text::new(&format!("{}", render_mode.point_shadows))
+                .right_from(state.ids.point_shadows_slider, 8.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.point_shadows_value, ui);
         }
 
         // Rain occlusion texture size
@@ -1331,7 +1376,14 @@ impl<'a> Widget for Video<'a> {
                 .localized_strings
                 .get_msg("hud-settings-rain_occlusion-resolution"),
         )
-        .down_from(state.ids.shadow_mode_list, 10.0)
+        .down_from(
+            if shadow_map_mode.is_some() {
+                state.ids.point_shadows_text
+            } else {
+                state.ids.shadow_mode_list
+            },
+            10.0,
+        )
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .color(TEXT_COLOR)
@@ -1439,6 +1491,31 @@ impl<'a> Widget for Video<'a> {
             ));
         }
 
+        // Cloth physics
+        Text::new(&self.localized_strings.get_msg("hud-settings-cloth_physics"))
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .right_from(state.ids.weapon_trails_label, 64.0)
+            .color(TEXT_COLOR)
+            .set(state.ids.cloth_physics_label, ui);
+
+        let cloth_physics_enabled = ToggleButton::new(
+            self.global_state.settings.graphics.cloth_physics_enabled,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .right_from(state.ids.cloth_physics_label, 10.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.cloth_physics_button, ui);
+
+        if self.global_state.settings.graphics.cloth_physics_enabled != cloth_physics_enabled {
+            events.push(GraphicsChange::ToggleClothPhysicsEnabled(
+                cloth_physics_enabled,
+            ));
+        }
+
         // Disable flashing lights
         Text::new(
             &self
@@ -48,6 +48,8 @@ widget_ids! {
         death_message_2_bg,
         death_message_3,
         death_message_3_bg,
+        death_message_4,
+        death_message_4_bg,
         death_bg,
         // Level up message
         level_up,
@@ -468,6 +470,34 @@ impl<'a> Skillbar<'a> {
                 .color(CRITICAL_HP_COLOR)
                 .set(state.ids.death_message_3, ui);
         }
+
+        // Group members can be watched until the player respawns
+        if !self.client.group_members().is_empty()
+            && let Some(key) = self
+                .global_state
+                .settings
+                .controls
+                .get_binding(GameInput::SpectateViewpoint)
+        {
+            let spectate_msg = localized_strings.get_msg_ctx(
+                "hud-press_key_to_spectate_group",
+                &i18n::fluent_args! {
+                    "key" => key.display_string(key_layout)
+                },
+            );
+            Text::new(&spectate_msg)
+                .mid_bottom_with_margin_on(ui.window, 120.0)
+                .font_size(self.fonts.cyri.scale(20))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(Color::Rgba(0.0, 0.0, 0.0, 1.0))
+                .set(state.ids.death_message_4_bg, ui);
+            Text::new(&spectate_msg)
+                .bottom_left_with_margins_on(state.ids.death_message_4_bg, 2.0, 2.0)
+                .font_size(self.fonts.cyri.scale(20))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.death_message_4, ui);
+        }
     }
 
     fn show_stat_bars(&self, state: &State, ui: &mut UiCell) -> Option<Event> {
@@ -2,6 +2,7 @@ use super::{
     hotbar::{self, Slot as HotbarSlot},
     img_ids,
     item_imgs::ItemImgs,
+    quick_bar::{self, Slot as QuickBarSlot},
     util,
 };
 use crate::ui::slot::{self, SlotKey, SumSlot};
@@ -13,7 +14,9 @@ use common::{
         ActiveAbilities, Body, CharacterState, Combo, Energy, Inventory, Item, ItemKey, SkillSet,
         Stance, Stats,
     },
+    guild::GuildHallInfo,
     recipe::ComponentRecipeBook,
+    states::emote::EmoteKind,
 };
 use conrod_core::{image, Color};
 use specs::Entity as EcsEntity;
@@ -27,8 +30,12 @@ pub enum SlotKind {
     Equip(EquipSlot),
     Hotbar(HotbarSlot),
     Trade(TradeSlot),
+    Bank(BankSlot),
+    GuildStorage(GuildStorageSlot),
     Ability(AbilitySlot),
     Crafting(CraftSlot),
+    QuickBar(QuickBarSlot),
+    Emote(EmoteKind),
     /* Spellbook(SpellbookSlot), TODO */
 }
 
@@ -238,6 +245,72 @@ impl<'a> SlotKey<HotbarSource<'a>, HotbarImageSource<'a>> for HotbarSlot {
     }
 }
 
+#[derive(Clone, PartialEq, Eq)]
+pub enum QuickBarImage {
+    Item(ItemKey),
+    Emote,
+}
+
+type QuickBarSource<'a> = (&'a quick_bar::State, &'a Inventory);
+type QuickBarImageSource<'a> = (&'a ItemImgs, &'a img_ids::Imgs);
+
+impl<'a> SlotKey<QuickBarSource<'a>, QuickBarImageSource<'a>> for QuickBarSlot {
+    type ImageKey = QuickBarImage;
+
+    fn image_key(
+        &self,
+        (quick_bar, inventory): &QuickBarSource<'a>,
+    ) -> Option<(Self::ImageKey, Option<Color>)> {
+        const GREYED_OUT: Color = Color::Rgba(0.3, 0.3, 0.3, 0.8);
+        quick_bar.get(*self).map(|contents| match contents {
+            quick_bar::SlotContents::Inventory(item_hash, item_key) => {
+                match inventory.get_by_hash(*item_hash) {
+                    Some(item) => (QuickBarImage::Item(item.into()), None),
+                    None => (QuickBarImage::Item(item_key.clone()), Some(GREYED_OUT)),
+                }
+            },
+            quick_bar::SlotContents::Emote(_) => (QuickBarImage::Emote, None),
+        })
+    }
+
+    fn amount(&self, (quick_bar, inventory): &QuickBarSource<'a>) -> Option<u32> {
+        quick_bar
+            .get(*self)
+            .and_then(|content| match content {
+                quick_bar::SlotContents::Inventory(item_hash, _) => {
+                    inventory.get_by_hash(*item_hash)
+                },
+                quick_bar::SlotContents::Emote(_) => None,
+            })
+            .map(|item| item.amount())
+            .filter(|amount| *amount > 1)
+    }
+
+    fn image_ids(
+        key: &Self::ImageKey,
+        (item_imgs, imgs): &QuickBarImageSource<'a>,
+    ) -> Vec<image::Id> {
+        match key {
+            QuickBarImage::Item(key) => item_imgs.img_ids_or_not_found_img(key.clone()),
+            QuickBarImage::Emote => vec![imgs.social],
+        }
+    }
+}
+
+impl SlotKey<(), img_ids::Imgs> for EmoteKind {
+    type ImageKey = ();
+
+    fn image_key(&self, _source: &()) -> Option<(Self::ImageKey, Option<Color>)> {
+        Some(((), None))
+    }
+
+    fn amount(&self, _source: &()) -> Option<u32> { None }
+
+    fn image_ids(_key: &Self::ImageKey, imgs: &img_ids::Imgs) -> Vec<image::Id> {
+        vec![imgs.social]
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AbilitySlot {
     Slot(usize),
@@ -287,6 +360,64 @@ impl<'a> SlotKey<AbilitiesSource<'a>, img_ids::Imgs> for AbilitySlot {
     }
 }
 
+/// A slot of the bank kept by a banker, indexing into `Inventory::bank`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BankSlot {
+    pub index: usize,
+}
+
+impl SlotKey<Inventory, ItemImgs> for BankSlot {
+    type ImageKey = ItemKey;
+
+    fn image_key(&self, source: &Inventory) -> Option<(Self::ImageKey, Option<Color>)> {
+        source.bank().get(self.index).map(|i| (i.into(), None))
+    }
+
+    fn amount(&self, source: &Inventory) -> Option<u32> {
+        source
+            .bank()
+            .get(self.index)
+            .map(|item| item.amount())
+            .filter(|amount| *amount > 1)
+    }
+
+    fn image_ids(key: &Self::ImageKey, source: &ItemImgs) -> Vec<image::Id> {
+        source.img_ids_or_not_found_img(key.clone())
+    }
+}
+
+/// A slot of the storage in the hall of our guild, indexing into
+/// `GuildHallInfo::storage`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GuildStorageSlot {
+    pub index: usize,
+}
+
+impl SlotKey<GuildHallInfo, ItemImgs> for GuildStorageSlot {
+    type ImageKey = ItemKey;
+
+    fn image_key(&self, source: &GuildHallInfo) -> Option<(Self::ImageKey, Option<Color>)> {
+        source
+            .storage
+            .get(self.index)?
+            .as_ref()
+            .map(|i| (i.into(), None))
+    }
+
+    fn amount(&self, source: &GuildHallInfo) -> Option<u32> {
+        source
+            .storage
+            .get(self.index)?
+            .as_ref()
+            .map(|item| item.amount())
+            .filter(|amount| *amount > 1)
+    }
+
+    fn image_ids(key: &Self::ImageKey, source: &ItemImgs) -> Vec<image::Id> {
+        source.img_ids_or_not_found_img(key.clone())
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct CraftSlot {
     pub index: u32,
@@ -359,6 +490,14 @@ impl From<TradeSlot> for SlotKind {
     fn from(trade: TradeSlot) -> Self { Self::Trade(trade) }
 }
 
+impl From<BankSlot> for SlotKind {
+    fn from(bank: BankSlot) -> Self { Self::Bank(bank) }
+}
+
+impl From<GuildStorageSlot> for SlotKind {
+    fn from(guild_storage: GuildStorageSlot) -> Self { Self::GuildStorage(guild_storage) }
+}
+
 impl From<AbilitySlot> for SlotKind {
     fn from(ability: AbilitySlot) -> Self { Self::Ability(ability) }
 }
@@ -367,6 +506,14 @@ impl From<CraftSlot> for SlotKind {
     fn from(craft: CraftSlot) -> Self { Self::Crafting(craft) }
 }
 
+impl From<QuickBarSlot> for SlotKind {
+    fn from(quick_bar: QuickBarSlot) -> Self { Self::QuickBar(quick_bar) }
+}
+
+impl From<EmoteKind> for SlotKind {
+    fn from(emote: EmoteKind) -> Self { Self::Emote(emote) }
+}
+
 impl SumSlot for SlotKind {
     fn drag_size(&self) -> Option<[f64; 2]> {
         Some(match self {
@@ -25,9 +25,12 @@ widget_ids! {
         scrollbar,
         online_align,
         player_names[],
+        verified_badges[],
         online_txt,
         online_no,
         invite_button,
+        guild_button,
+        guild_invite_button,
         player_search_icon,
         player_search_input,
         player_search_input_bg,
@@ -85,6 +88,8 @@ impl<'a> Social<'a> {
 pub enum Event {
     Close,
     Invite(Uid),
+    InviteToGuild(Uid),
+    ToggleGuild,
     Focus(widget::Id),
     SearchPlayers(Option<String>),
 }
@@ -174,7 +179,7 @@ impl<'a> Widget for Social<'a> {
         let player_count = players.clone().count();
 
         // Content Alignment
-        Rectangle::fill_with([270.0, 346.0], color::TRANSPARENT)
+        Rectangle::fill_with([270.0, 316.0], color::TRANSPARENT)
             .mid_top_with_margin_on(state.ids.frame, 74.0)
             .scroll_kids_vertically()
             .set(state.ids.online_align, ui);
@@ -201,7 +206,10 @@ impl<'a> Widget for Social<'a> {
             state.update(|s| {
                 s.ids
                     .player_names
-                    .resize(player_count, &mut ui.widget_id_generator())
+                    .resize(player_count, &mut ui.widget_id_generator());
+                s.ids
+                    .verified_badges
+                    .resize(player_count, &mut ui.widget_id_generator());
             })
         };
 
@@ -244,7 +252,7 @@ impl<'a> Widget for Social<'a> {
             let hide_username = true;
             let selected = state.selected_uid.map_or(false, |u| u.0 == uid);
             let alias = &player_info.player_alias;
-            let name_text = match &player_info.character {
+            let mut name_text = match &player_info.character {
                 Some(character) => {
                     if hide_username {
                         character.name.to_string()
@@ -258,11 +266,31 @@ impl<'a> Widget for Social<'a> {
                     self.localized_strings.get_msg("hud-group-in_menu")
                 ), // character select or spectating
             };
-            let acc_name_txt = format!(
+            if let Some(tag) = &player_info.guild_tag {
+                name_text = format!("[{}] {}", tag, name_text);
+            }
+            if let Some(title) = player_info
+                .character
+                .as_ref()
+                .and_then(|character| character.title)
+            {
+                name_text = format!(
+                    "{}, {}",
+                    name_text,
+                    self.localized_strings.get_msg(title.i18n_key())
+                );
+            }
+            let mut acc_name_txt = format!(
                 "{}: {}",
                 &self.localized_strings.get_msg("hud-social-account"),
                 alias
             );
+            if player_info.verified_badge {
+                acc_name_txt.push_str(&format!(
+                    "\n{}",
+                    self.localized_strings.get_msg("hud-social-verified")
+                ));
+            }
             // Player name widget
             let button = Button::image(if !selected {
                 self.imgs.nothing
@@ -304,6 +332,12 @@ impl<'a> Widget for Social<'a> {
             {
                 state.update(|s| s.selected_uid = Some((uid, Instant::now())));
             }
+            if player_info.verified_badge {
+                Image::new(self.imgs.check_checked)
+                    .w_h(12.0, 12.0)
+                    .mid_right_with_margin_on(state.ids.player_names[i], 4.0)
+                    .set(state.ids.verified_badges[i], ui);
+            }
         }
 
         // Invite Button
@@ -405,6 +439,88 @@ impl<'a> Widget for Social<'a> {
             }
         }
 
+        // Guild
+        let guild_text = self.localized_strings.get_msg("hud-guild");
+        if Button::image(self.imgs.button)
+            .w_h(106.0, 26.0)
+            .bottom_left_with_margins_on(state.ids.frame, 38.0, 7.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .label(&guild_text)
+            .label_y(conrod_core::position::Relative::Scalar(3.0))
+            .label_color(TEXT_COLOR)
+            .image_color(TEXT_COLOR)
+            .label_font_size(self.fonts.cyri.scale(15))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .set(state.ids.guild_button, ui)
+            .was_clicked()
+        {
+            events.push(Event::ToggleGuild);
+        }
+
+        // Only officers and the leader can invite, and only players without a guild
+        let can_guild_invite = self.client.guild().map_or(false, |guild| {
+            self.client
+                .uid()
+                .and_then(|uid| self.client.player_list().get(&uid))
+                .and_then(|player| guild.rank_of(player.uuid))
+                .map_or(false, |rank| rank.can_invite())
+        });
+        let selected_to_guild_invite = can_guild_invite
+            .then(|| {
+                state
+                    .selected_uid
+                    .as_ref()
+                    .map(|(s, _)| *s)
+                    .filter(|selected| {
+                        self.client
+                            .player_list()
+                            .get(selected)
+                            .map_or(false, |selected_player| {
+                                selected_player.is_online && selected_player.guild_tag.is_none()
+                            })
+                    })
+            })
+            .flatten();
+        let guild_invite_text = self.localized_strings.get_msg("hud-guild-invite");
+        if Button::image(self.imgs.button)
+            .w_h(106.0, 26.0)
+            .bottom_right_with_margins_on(state.ids.frame, 38.0, 7.0)
+            .hover_image(if selected_to_guild_invite.is_some() {
+                self.imgs.button_hover
+            } else {
+                self.imgs.button
+            })
+            .press_image(if selected_to_guild_invite.is_some() {
+                self.imgs.button_press
+            } else {
+                self.imgs.button
+            })
+            .label(&guild_invite_text)
+            .label_y(conrod_core::position::Relative::Scalar(3.0))
+            .label_color(if selected_to_guild_invite.is_some() {
+                TEXT_COLOR
+            } else {
+                TEXT_COLOR_3
+            })
+            .image_color(if selected_to_guild_invite.is_some() {
+                TEXT_COLOR
+            } else {
+                TEXT_COLOR_3
+            })
+            .label_font_size(self.fonts.cyri.scale(15))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .set(state.ids.guild_invite_button, ui)
+            .was_clicked()
+        {
+            if let Some(uid) = selected_to_guild_invite {
+                events.push(Event::InviteToGuild(uid));
+                state.update(|s| {
+                    s.selected_uid = None;
+                });
+            }
+        }
+
         // Player Search
         if Button::image(self.imgs.search_btn)
             .top_left_with_margins_on(state.ids.frame, 54.0, 9.0)
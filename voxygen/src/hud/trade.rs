@@ -13,7 +13,8 @@ use common::{
         inventory::item::{ItemDesc, ItemI18n, MaterialStatManifest, Quality},
         Inventory, Stats,
     },
-    trade::{PendingTrade, SitePrices, TradeAction, TradePhase},
+    trade::{Good, PendingTrade, ReducedInventory, SitePrices, TradeAction, TradePhase},
+    uid::Uid,
 };
 use common_net::sync::WorldSyncExt;
 use i18n::Localization;
@@ -23,6 +24,7 @@ use crate::{
         bag::{BackgroundIds, InventoryScroller},
         Event as HudEvent, PromptDialogSettings,
     },
+    profile::TradeRecord,
     ui::{
         fonts::Fonts,
         slot::{ContentSize, SlotMaker},
@@ -38,11 +40,13 @@ use super::{
     util, Hud, HudInfo, Show, TradeAmountInput, TEXT_COLOR, TEXT_GRAY_COLOR, UI_HIGHLIGHT_0,
     UI_MAIN,
 };
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::VecDeque};
 
 pub enum TradeEvent {
     TradeAction(TradeAction),
+    Haggle,
     SetDetailsMode(bool),
+    SetHistoryMode(bool),
     HudUpdate(HudUpdate),
     ShowPrompt(PromptDialogSettings),
 }
@@ -73,6 +77,7 @@ widget_ids! {
         phase_indicator,
         accept_button,
         decline_button,
+        haggle_button,
         inventory_scroller,
         amount_bg,
         amount_notice,
@@ -82,6 +87,11 @@ widget_ids! {
         amount_input,
         amount_btn,
         trade_details_btn,
+        balance_text,
+        history_btn,
+        history_bg,
+        history_title,
+        history_text,
     }
 }
 
@@ -103,6 +113,7 @@ pub struct Trade<'a> {
     msm: &'a MaterialStatManifest,
     pulse: f32,
     show: &'a mut Show,
+    trade_history: Option<&'a VecDeque<TradeRecord>>,
     needs_thirdconfirm: bool,
 }
 
@@ -122,6 +133,7 @@ impl<'a> Trade<'a> {
         msm: &'a MaterialStatManifest,
         pulse: f32,
         show: &'a mut Show,
+        trade_history: Option<&'a VecDeque<TradeRecord>>,
     ) -> Self {
         Self {
             client,
@@ -139,6 +151,7 @@ impl<'a> Trade<'a> {
             msm,
             pulse,
             show,
+            trade_history,
             needs_thirdconfirm: false,
         }
     }
@@ -146,6 +159,78 @@ impl<'a> Trade<'a> {
 
 const MAX_TRADE_SLOTS: usize = 16;
 
+/// Name of a party of the trade, as shown in the trade window.
+fn party_name(client: &Client, uid: Uid) -> Option<String> {
+    client
+        .player_list()
+        .get(&uid)
+        .map(|info| info.player_alias.clone())
+        .or_else(|| {
+            let entity = client.state().ecs().entity_from_uid(uid)?;
+            client
+                .state()
+                .read_storage::<Stats>()
+                .get(entity)
+                .map(|e| e.name.to_owned())
+        })
+}
+
+/// Approximate value in coins of what we offer and of what the other party
+/// offers, in that order. Returns `None` if the trade isn't taking place at a
+/// site with known prices, and `None` for either offer if it contains items
+/// that can't be priced.
+pub fn offer_values(
+    client: &Client,
+    trade: &PendingTrade,
+    prices: &Option<SitePrices>,
+) -> Option<(Option<f32>, Option<f32>)> {
+    let prices = prices.as_ref()?;
+    let inventories = client.inventories();
+    let mut r_inventories = [None, None];
+    for (i, party) in trade.parties.iter().enumerate() {
+        let entity = client.state().ecs().entity_from_uid(*party)?;
+        r_inventories[i] = Some(ReducedInventory::from(inventories.get(entity)?));
+    }
+    let who = trade.which_party(client.uid()?)?;
+    let coin_price = prices.values.get(&Good::Coin).copied().unwrap_or(1.0);
+    let ours = prices.balance(&trade.offers, &r_inventories, who, true);
+    let theirs = prices.balance(&trade.offers, &r_inventories, 1 - who, false);
+    Some((ours.map(|v| v / coin_price), theirs.map(|v| v / coin_price)))
+}
+
+/// Summarise a trade that can no longer be changed, so that it can be
+/// remembered once it completes.
+pub fn summarize(
+    client: &Client,
+    trade: &PendingTrade,
+    prices: &Option<SitePrices>,
+    i18n: &Localization,
+    item_i18n: &ItemI18n,
+) -> Option<TradeRecord> {
+    let who = trade.which_party(client.uid()?)?;
+    let inventories = client.inventories();
+    let items = |who: usize| -> Option<Vec<(String, u32)>> {
+        let entity = client.state().ecs().entity_from_uid(trade.parties[who])?;
+        let inventory = inventories.get(entity)?;
+        let mut items: Vec<_> = trade.offers[who]
+            .iter()
+            .filter_map(|(slot, amount)| {
+                let (name, _) = util::item_text(inventory.get(*slot)?, i18n, item_i18n);
+                Some((name, *amount))
+            })
+            .collect();
+        items.sort();
+        Some(items)
+    };
+
+    Some(TradeRecord {
+        partner: party_name(client, trade.parties[1 - who]).unwrap_or_default(),
+        given: items(who)?,
+        received: items(1 - who)?,
+        value: offer_values(client, trade, prices).and_then(|(ours, theirs)| ours.zip(theirs)),
+    })
+}
+
 impl<'a> Trade<'a> {
     fn background(&mut self, state: &mut ConrodState<'_, State>, ui: &mut UiCell<'_>) {
         Image::new(self.imgs.inv_middle_bg_bag)
@@ -235,19 +320,7 @@ impl<'a> Trade<'a> {
             .scroll_kids_vertically()
             .set(state.ids.inv_alignment[who], ui);
 
-        let name = self
-            .client
-            .player_list()
-            .get(&uid)
-            .map(|info| info.player_alias.clone())
-            .or_else(|| {
-                self.client
-                    .state()
-                    .read_storage::<Stats>()
-                    .get(entity)
-                    .map(|e| e.name.to_owned())
-            })
-            .unwrap_or_else(|| format!("Player {}", who));
+        let name = party_name(self.client, uid).unwrap_or_else(|| format!("Player {}", who));
 
         let offer_header = if ours {
             self.localized_strings.get_msg("hud-trade-your_offer")
@@ -591,6 +664,136 @@ impl<'a> Trade<'a> {
         }
     }
 
+    fn balance_indicator(
+        &mut self,
+        state: &mut ConrodState<'_, State>,
+        ui: &mut UiCell<'_>,
+        trade: &'a PendingTrade,
+        prices: &'a Option<SitePrices>,
+    ) {
+        let Some((ours, theirs)) = offer_values(self.client, trade, prices) else {
+            return;
+        };
+        let format_value =
+            |value: Option<f32>| value.map_or_else(|| "?".to_string(), |v| format!("{:0.1}", v));
+        let balance =
+            self.localized_strings
+                .get_msg_ctx("hud-trade-balance", &i18n::fluent_args! {
+                    "ours" => format_value(ours),
+                    "theirs" => format_value(theirs),
+                });
+        // Green if we offer at least as much as we ask for, since merchants won't
+        // accept anything less
+        let color = match ours.zip(theirs) {
+            Some((ours, theirs)) if ours >= theirs => Color::Rgba(0.4, 1.0, 0.4, 1.0),
+            Some(_) => Color::Rgba(1.0, 0.6, 0.3, 1.0),
+            None => TEXT_GRAY_COLOR,
+        };
+        Text::new(&balance)
+            .mid_bottom_with_margin_on(state.ids.bg, 116.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(color)
+            .set(state.ids.balance_text, ui);
+    }
+
+    fn history(
+        &mut self,
+        state: &mut ConrodState<'_, State>,
+        ui: &mut UiCell<'_>,
+    ) -> Option<TradeEvent> {
+        let mut event = None;
+        if Button::image(self.imgs.button)
+            .w_h(80.0, 20.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .top_left_with_margins_on(state.ids.bg, 8.0, 12.0)
+            .label(&self.localized_strings.get_msg("hud-trade-history"))
+            .label_font_size(self.fonts.cyri.scale(12))
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_y(Relative::Scalar(1.0))
+            .set(state.ids.history_btn, ui)
+            .was_clicked()
+        {
+            event = Some(TradeEvent::SetHistoryMode(!self.show.trade_history));
+        }
+
+        if !self.show.trade_history {
+            return event;
+        }
+
+        Rectangle::fill([280.0, 482.0])
+            .right_from(state.ids.bg, 4.0)
+            .color(Color::Rgba(0.0, 0.0, 0.0, 0.8))
+            .scroll_kids_vertically()
+            .set(state.ids.history_bg, ui);
+        Text::new(&self.localized_strings.get_msg("hud-trade-history"))
+            .mid_top_with_margin_on(state.ids.history_bg, 10.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(18))
+            .color(TEXT_COLOR)
+            .set(state.ids.history_title, ui);
+
+        let format_items = |items: &[(String, u32)]| {
+            if items.is_empty() {
+                self.localized_strings
+                    .get_msg("hud-trade-nothing")
+                    .into_owned()
+            } else {
+                items
+                    .iter()
+                    .map(|(name, amount)| format!("{}x {}", amount, name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        };
+        let entries = self
+            .trade_history
+            .filter(|history| !history.is_empty())
+            .map(|history| {
+                history
+                    .iter()
+                    .map(|record| {
+                        let mut entry = self.localized_strings.get_msg_ctx(
+                            "hud-trade-history-entry",
+                            &i18n::fluent_args! {
+                                "partner" => record.partner.as_str(),
+                                "given" => format_items(&record.given),
+                                "received" => format_items(&record.received),
+                            },
+                        );
+                        if let Some((given, received)) = record.value {
+                            entry.to_mut().push_str(&self.localized_strings.get_msg_ctx(
+                                "hud-trade-history-value",
+                                &i18n::fluent_args! {
+                                    "given" => format!("{:0.1}", given),
+                                    "received" => format!("{:0.1}", received),
+                                },
+                            ));
+                        }
+                        entry.into_owned()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+            })
+            .unwrap_or_else(|| {
+                self.localized_strings
+                    .get_msg("hud-trade-history-empty")
+                    .into_owned()
+            });
+        Text::new(&entries)
+            .mid_top_with_margin_on(state.ids.history_bg, 40.0)
+            .w(260.0)
+            .wrap_by_word()
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(12))
+            .color(TEXT_COLOR)
+            .set(state.ids.history_text, ui);
+
+        event
+    }
+
     fn accept_decline_buttons(
         &mut self,
         state: &mut ConrodState<'_, State>,
@@ -657,6 +860,32 @@ impl<'a> Trade<'a> {
         event
     }
 
+    fn haggle_button(
+        &mut self,
+        state: &mut ConrodState<'_, State>,
+        ui: &mut UiCell<'_>,
+        trade: &'a PendingTrade,
+        prices: &'a Option<SitePrices>,
+    ) -> Option<TradeEvent> {
+        // Only merchants, who know the prices of their goods, can be haggled with
+        if prices.is_none() || trade.phase != TradePhase::Mutate {
+            return None;
+        }
+        Button::image(self.imgs.button)
+            .w_h(31.0 * 5.0, 12.0 * 2.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .down_from(state.ids.accept_button, 6.0)
+            .label(&self.localized_strings.get_msg("hud-trade-haggle"))
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_y(Relative::Scalar(2.0))
+            .set(state.ids.haggle_button, ui)
+            .was_clicked()
+            .then_some(TradeEvent::Haggle)
+    }
+
     fn input_item_amount(
         &mut self,
         state: &mut ConrodState<'_, State>,
@@ -856,7 +1085,10 @@ impl<'a> Widget for Trade<'a> {
 
         event = self.item_pane(state, ui, trade, prices, false).or(event);
         event = self.item_pane(state, ui, trade, prices, true).or(event);
+        self.balance_indicator(state, ui, trade, prices);
+        event = self.history(state, ui).or(event);
         event = self.accept_decline_buttons(state, ui, trade).or(event);
+        event = self.haggle_button(state, ui, trade, prices).or(event);
         event = self.close_button(state, ui).or(event);
         self.input_item_amount(state, ui, trade).or(event)
     }
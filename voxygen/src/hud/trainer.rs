@@ -0,0 +1,222 @@
+use conrod_core::{
+    color,
+    position::Relative,
+    widget::{self, Button, Image, Rectangle, Text},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+
+use common::comp::{
+    skillset::{skills::Skill, SkillSet, TRAINER_SKILLS},
+    Inventory,
+};
+use i18n::Localization;
+
+use crate::ui::fonts::Fonts;
+
+use super::{diary, img_ids::Imgs, TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN};
+
+const ROW_HEIGHT: f64 = 30.0;
+
+widget_ids! {
+    pub struct Ids {
+        bg,
+        bg_frame,
+        close,
+        title_bg,
+        title,
+        list_align,
+        names[],
+        prices[],
+        learn_btns[],
+        nothing_to_learn,
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    Close,
+    Learn(Skill),
+}
+
+/// Lists the skills a trainer can teach the player next, with their price
+#[derive(WidgetCommon)]
+pub struct Trainer<'a> {
+    skill_set: &'a SkillSet,
+    inventory: &'a Inventory,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Trainer<'a> {
+    pub fn new(
+        skill_set: &'a SkillSet,
+        inventory: &'a Inventory,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+    ) -> Self {
+        Self {
+            skill_set,
+            inventory,
+            imgs,
+            fonts,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+impl<'a> Widget for Trainer<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Trainer::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut event = None;
+
+        // Background
+        Image::new(self.imgs.inv_middle_bg_bag)
+            .w_h(424.0, 482.0)
+            .color(Some(UI_MAIN))
+            .mid_bottom_with_margin_on(ui.window, 295.0)
+            .set(state.ids.bg, ui);
+        Image::new(self.imgs.inv_middle_frame)
+            .w_h(424.0, 482.0)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.bg_frame, ui);
+
+        // Title
+        let title = self.localized_strings.get_msg("hud-trainer");
+        Text::new(&title)
+            .mid_top_with_margin_on(state.ids.bg_frame, 9.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(Color::Rgba(0.0, 0.0, 0.0, 1.0))
+            .set(state.ids.title_bg, ui);
+        Text::new(&title)
+            .top_left_with_margins_on(state.ids.title_bg, 2.0, 2.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // Close button
+        if Button::image(self.imgs.close_btn)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_btn_hover)
+            .press_image(self.imgs.close_btn_press)
+            .top_right_with_margins_on(state.ids.bg, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        // Only offer skills the player can still learn, the trainer refuses
+        // skills of weapons they haven't unlocked yet
+        let skills = TRAINER_SKILLS
+            .iter()
+            .map(|(skill, _)| *skill)
+            .filter(|skill| {
+                skill
+                    .skill_group_kind()
+                    .map_or(false, |group| self.skill_set.skill_group_accessible(group))
+                    && !self.skill_set.is_at_max_level(*skill)
+            })
+            .filter_map(|skill| Some((skill, self.skill_set.trainer_price(skill)?)))
+            .collect::<Vec<_>>();
+
+        Rectangle::fill_with([360.0, 370.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.ids.bg_frame, 60.0)
+            .scroll_kids_vertically()
+            .set(state.ids.list_align, ui);
+
+        if skills.is_empty() {
+            Text::new(&self.localized_strings.get_msg("hud-trainer-nothing"))
+                .mid_top_with_margin_on(state.ids.list_align, 10.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR)
+                .set(state.ids.nothing_to_learn, ui);
+        }
+
+        if state.ids.names.len() < skills.len() {
+            state.update(|s| {
+                let gen = &mut ui.widget_id_generator();
+                s.ids.names.resize(skills.len(), gen);
+                s.ids.prices.resize(skills.len(), gen);
+                s.ids.learn_btns.resize(skills.len(), gen);
+            });
+        }
+
+        let coins = self.inventory.coins();
+        for (i, (skill, price)) in skills.into_iter().enumerate() {
+            let top = i as f64 * ROW_HEIGHT;
+            Text::new(&diary::skill_title(
+                self.localized_strings,
+                self.skill_set,
+                skill,
+            ))
+            .top_left_with_margins_on(state.ids.list_align, top + 5.0, 5.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.names[i], ui);
+
+            let affordable = coins >= u64::from(price);
+            let can_learn = self.skill_set.prerequisites_met(skill) && affordable;
+            Text::new(&self.localized_strings.get_msg_ctx(
+                "hud-trainer-price",
+                &i18n::fluent_args! {
+                    "cost" => price,
+                },
+            ))
+            .top_right_with_margins_on(state.ids.list_align, top + 5.0, 95.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(if affordable { TEXT_COLOR } else { color::GREY })
+            .set(state.ids.prices[i], ui);
+
+            if Button::image(self.imgs.button)
+                .w_h(80.0, 24.0)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .image_color(if can_learn {
+                    Color::Rgba(1.0, 1.0, 1.0, 1.0)
+                } else {
+                    color::GREY
+                })
+                .top_right_with_margins_on(state.ids.list_align, top, 5.0)
+                .label(&self.localized_strings.get_msg("hud-trainer-learn"))
+                .label_font_size(self.fonts.cyri.scale(14))
+                .label_color(TEXT_COLOR)
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_y(Relative::Scalar(2.0))
+                .set(state.ids.learn_btns[i], ui)
+                .was_clicked()
+                && can_learn
+            {
+                event = Some(Event::Learn(skill));
+            }
+        }
+
+        event
+    }
+}
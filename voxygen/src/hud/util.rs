@@ -1,6 +1,7 @@
 use super::img_ids;
 use common::{
     comp::{
+        buff::DispelTarget,
         inventory::trade_pricing::TradePricing,
         item::{
             armor::{Armor, ArmorKind, Protection},
@@ -113,6 +114,8 @@ pub fn kind_text<'a>(kind: &ItemKind, i18n: &'a Localization) -> Cow<'a, str> {
         ItemKind::Lantern { .. } => i18n.get_msg("common-kind-lantern"),
         ItemKind::TagExamples { .. } => Cow::Borrowed(""),
         ItemKind::RecipeGroup { .. } => i18n.get_msg("common-kind-recipegroup"),
+        ItemKind::SkillBook { .. } => i18n.get_msg("common-kind-skillbook"),
+        ItemKind::TreasureMap => i18n.get_msg("common-kind-treasure_map"),
     }
 }
 
@@ -192,6 +195,9 @@ fn buff_key(buff: BuffKind) -> &'static str {
         BuffKind::Flame => "buff-burn",
         BuffKind::Frigid => "buff-frigid",
         BuffKind::Lifesteal => "buff-lifesteal",
+        BuffKind::PoisonCoating => "buff-poison_coating",
+        BuffKind::FireCoating => "buff-fire_coating",
+        BuffKind::FrostCoating => "buff-frost_coating",
         BuffKind::ImminentCritical => "buff-imminentcritical",
         BuffKind::Fury => "buff-fury",
         BuffKind::Sunderer => "buff-sunderer",
@@ -217,6 +223,7 @@ fn buff_key(buff: BuffKind) -> &'static str {
         BuffKind::Winded => "buff-winded",
         BuffKind::Concussion => "buff-concussion",
         BuffKind::Staggered => "buff-staggered",
+        BuffKind::Electrified => "buff-electrified",
         // Neutral
         BuffKind::Polymorphed => "buff-polymorphed",
     }
@@ -299,6 +306,15 @@ pub fn consumable_desc(effects: &Effects, i18n: &Localization) -> Vec<String> {
                             let key = buff_key(buff.kind);
                             i18n.get_attr(key, "stat")
                         },
+                        // Show how many attacks they last for
+                        BuffKind::PoisonCoating
+                        | BuffKind::FireCoating
+                        | BuffKind::FrostCoating => {
+                            let key = buff_key(buff.kind);
+                            i18n.get_attr_ctx(key, "stat", &i18n::fluent_args! {
+                                "charges" => buff.data.charges.unwrap_or(1),
+                            })
+                        },
                         // Have no stat description
                         BuffKind::Bleeding
                         | BuffKind::Burning
@@ -332,6 +348,7 @@ pub fn consumable_desc(effects: &Effects, i18n: &Localization) -> Vec<String> {
                         | BuffKind::Winded
                         | BuffKind::Concussion
                         | BuffKind::Staggered
+                        | BuffKind::Electrified
                         | BuffKind::Tenacity
                         | BuffKind::Resilience => Cow::Borrowed(""),
                     };
@@ -376,6 +393,9 @@ pub fn consumable_desc(effects: &Effects, i18n: &Localization) -> Vec<String> {
                             | BuffKind::Flame
                             | BuffKind::Frigid
                             | BuffKind::Lifesteal
+                            | BuffKind::PoisonCoating
+                            | BuffKind::FireCoating
+                            | BuffKind::FrostCoating
                             // | BuffKind::SalamanderAspect
                             | BuffKind::ImminentCritical
                             | BuffKind::Fury
@@ -389,6 +409,7 @@ pub fn consumable_desc(effects: &Effects, i18n: &Localization) -> Vec<String> {
                             | BuffKind::Winded
                             | BuffKind::Concussion
                             | BuffKind::Staggered
+                            | BuffKind::Electrified
                             | BuffKind::Tenacity
                             | BuffKind::Resilience => Cow::Borrowed(""),
                         }
@@ -402,6 +423,16 @@ pub fn consumable_desc(effects: &Effects, i18n: &Localization) -> Vec<String> {
                     };
 
                     write!(&mut description, " {}", dur_desc).unwrap();
+                } else if let Effect::Dispel(dispel) = effect {
+                    let key = match dispel.target {
+                        DispelTarget::Debuffs => "buff-text-dispel_debuffs",
+                        DispelTarget::Buffs => "buff-text-dispel_buffs",
+                        DispelTarget::Kind(_) | DispelTarget::Category(_) => "buff-text-dispel",
+                    };
+                    let dispel_desc = i18n.get_msg_ctx(key, &i18n::fluent_args! {
+                        "count" => dispel.count
+                    });
+                    write!(&mut description, "{}", dispel_desc).unwrap();
                 }
                 descriptions.push(description);
             }
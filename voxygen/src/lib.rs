@@ -23,6 +23,7 @@
 #[macro_use]
 pub mod ui;
 pub mod audio;
+pub mod benchmark;
 pub mod cmd;
 pub mod controller;
 mod credits;
@@ -43,6 +44,7 @@ pub mod session;
 pub mod settings;
 #[cfg(feature = "singleplayer")]
 pub mod singleplayer;
+pub mod telemetry;
 pub mod window;
 
 #[cfg(feature = "singleplayer")]
@@ -60,6 +62,7 @@ use crate::{
 };
 use common::clock::Clock;
 use common_base::span;
+use common_net::msg::ResumeToken;
 use i18n::LocalizationHandle;
 use std::path::PathBuf;
 
@@ -79,6 +82,8 @@ pub struct GlobalState {
     pub lazy_init: scene::terrain::SpriteRenderContextLazy,
     pub audio: AudioFrontend,
     pub info_message: Option<String>,
+    /// Session that was lost to a connection error and may still be resumed.
+    pub resume_session: Option<ResumeSession>,
     pub clock: Clock,
     #[cfg(feature = "singleplayer")]
     pub singleplayer: SingleplayerState,
@@ -87,11 +92,21 @@ pub struct GlobalState {
     pub clipboard: iced_winit::Clipboard,
     // Used to clear the shadow textures when entering a PlayState that doesn't utilise shadows
     pub clear_shadows_next_frame: bool,
+    /// Crash and performance reports, if the player opted in
+    pub telemetry: Option<telemetry::Telemetry>,
+    /// The graphics benchmark, if the game was started to run it
+    pub benchmark: Option<benchmark::Benchmark>,
     /// A channel that sends Discord activity updates to a background task
     #[cfg(feature = "discord")]
     pub discord: crate::discord::Discord,
 }
 
+pub struct ResumeSession {
+    pub server_address: String,
+    pub username: String,
+    pub token: ResumeToken,
+}
+
 impl GlobalState {
     /// Called after a change in play state has occurred (usually used to
     /// reverse any temporary effects a state may have made).
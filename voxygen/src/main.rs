@@ -20,6 +20,8 @@ static GLOBAL: common_base::tracy_client::ProfiledAllocator<std::alloc::System>
 
 use i18n::{self, LocalizationHandle};
 #[cfg(feature = "singleplayer")]
+use veloren_voxygen::benchmark::Benchmark;
+#[cfg(feature = "singleplayer")]
 use veloren_voxygen::singleplayer::SingleplayerState;
 use veloren_voxygen::{
     audio::AudioFrontend,
@@ -28,6 +30,7 @@ use veloren_voxygen::{
     run,
     scene::terrain::SpriteRenderContext,
     settings::{get_fps, AudioOutput, Settings},
+    telemetry::Telemetry,
     window::Window,
     GlobalState,
 };
@@ -213,6 +216,13 @@ fn main() {
         Err(error) => panic!("Failed to create window!: {:?}", error),
     };
 
+    let telemetry = Telemetry::new(
+        &settings.telemetry,
+        &config_dir,
+        Some(window.renderer().adapter_info().into()),
+        &tokio_runtime,
+    );
+
     let clipboard = iced_winit::Clipboard::connect(window.window());
 
     let lazy_init = SpriteRenderContext::new(window.renderer_mut());
@@ -242,11 +252,17 @@ fn main() {
         )),
         settings,
         info_message: None,
+        resume_session: None,
         #[cfg(feature = "singleplayer")]
         singleplayer: SingleplayerState::None,
         i18n,
         clipboard,
         clear_shadows_next_frame: false,
+        telemetry,
+        #[cfg(feature = "singleplayer")]
+        benchmark: args.benchmark.then(Benchmark::new),
+        #[cfg(not(feature = "singleplayer"))]
+        benchmark: None,
         #[cfg(feature = "discord")]
         discord,
     };
@@ -66,6 +66,22 @@ impl CharSelectionState {
     }
 
     pub fn client(&self) -> &RefCell<Client> { &self.client }
+
+    fn spectate(&self, global_state: &mut GlobalState) -> PlayStateResult {
+        {
+            let mut c = self.client.borrow_mut();
+            let graphics = &global_state.settings.graphics;
+            c.request_spectate(common::ViewDistances {
+                terrain: graphics.terrain_view_distance,
+                entity: graphics.entity_view_distance,
+            });
+        }
+        PlayStateResult::Switch(Box::new(SessionState::new(
+            global_state,
+            UpdateCharacterMetadata::default(),
+            Rc::clone(&self.client),
+        )))
+    }
 }
 
 impl PlayState for CharSelectionState {
@@ -95,6 +111,11 @@ impl PlayState for CharSelectionState {
             client.registered()
         };
         if client_registered {
+            // The benchmark flies the camera around instead of playing a character
+            if global_state.benchmark.is_some() {
+                return self.spectate(global_state);
+            }
+
             // Handle window events
             for event in events {
                 if self.char_selection_ui.handle_event(event.clone()) {
@@ -152,21 +173,7 @@ impl PlayState for CharSelectionState {
                             entity: graphics.entity_view_distance,
                         });
                     },
-                    ui::Event::Spectate => {
-                        {
-                            let mut c = self.client.borrow_mut();
-                            let graphics = &global_state.settings.graphics;
-                            c.request_spectate(common::ViewDistances {
-                                terrain: graphics.terrain_view_distance,
-                                entity: graphics.entity_view_distance,
-                            });
-                        }
-                        return PlayStateResult::Switch(Box::new(SessionState::new(
-                            global_state,
-                            UpdateCharacterMetadata::default(),
-                            Rc::clone(&self.client),
-                        )));
-                    },
+                    ui::Event::Spectate => return self.spectate(global_state),
                     ui::Event::ShowRules => {
                         let client = self.client.borrow();
 
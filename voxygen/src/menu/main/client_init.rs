@@ -3,6 +3,7 @@ use client::{
     error::{Error as ClientError, NetworkConnectError, NetworkError},
     Client, ClientInitStage, ServerInfo,
 };
+use common_net::msg::ResumeToken;
 use crossbeam_channel::{unbounded, Receiver, Sender, TryRecvError};
 use std::{
     path::Path,
@@ -51,6 +52,7 @@ impl ClientInit {
         password: String,
         runtime: Arc<runtime::Runtime>,
         locale: Option<String>,
+        resume_token: Option<ResumeToken>,
         config_dir: &Path,
     ) -> Self {
         let (tx, rx) = unbounded();
@@ -87,6 +89,7 @@ impl ClientInit {
                     &username,
                     &password,
                     locale.clone(),
+                    resume_token,
                     trust_fn,
                     &|stage| {
                         let _ = init_stage_tx.send(stage);
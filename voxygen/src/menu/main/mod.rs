@@ -1,4 +1,5 @@
 mod client_init;
+mod server_status;
 mod ui;
 
 use super::{char_selection::CharSelectionState, dummy_scene::Scene, server_info::ServerInfoState};
@@ -18,6 +19,7 @@ use client::{
 use client_init::{ClientInit, Error as InitError, Msg as InitMsg};
 use common::comp;
 use common_base::span;
+use common_net::msg::{ResumeToken, WorldFingerprint};
 #[cfg(feature = "plugins")]
 use common_state::plugin::PluginMgr;
 use i18n::LocalizationHandle;
@@ -26,7 +28,7 @@ use server::ServerInitStage;
 use specs::WorldExt;
 use std::{path::Path, sync::Arc};
 use tokio::runtime;
-use tracing::error;
+use tracing::{error, warn};
 use ui::{Event as MainMenuEvent, MainMenuUi};
 
 pub use ui::rand_bg_image_spec;
@@ -88,6 +90,22 @@ impl PlayState for MainMenuState {
         #[cfg(feature = "singleplayer")]
         {
             global_state.singleplayer = SingleplayerState::None;
+
+            // The benchmark starts its own world right away, and is given up on if it
+            // ends up back here
+            match &mut global_state.benchmark {
+                Some(benchmark) if !benchmark.is_started() => {
+                    benchmark.start();
+                    self.main_menu_ui.show_singleplayer_loading();
+                    global_state.singleplayer = SingleplayerState::benchmark();
+                    global_state.singleplayer.run(&global_state.tokio_runtime);
+                },
+                Some(_) => {
+                    warn!("The benchmark was interrupted");
+                    global_state.benchmark = None;
+                },
+                None => {},
+            }
         }
 
         // Updated localization in case the selected language was changed
@@ -131,6 +149,7 @@ impl PlayState for MainMenuState {
                             global_state.settings.language.send_to_server.then_some(
                                 global_state.settings.language.selected_language.clone(),
                             ),
+                            None,
                             &global_state.i18n,
                             &global_state.config_dir,
                         );
@@ -138,6 +157,7 @@ impl PlayState for MainMenuState {
                     Ok(Err(e)) => {
                         error!(?e, "Could not start server");
                         global_state.singleplayer = SingleplayerState::None;
+                        global_state.benchmark = None;
                         self.init = InitState::None;
                         self.main_menu_ui.cancel_connection();
                         let server_err = match e {
@@ -234,6 +254,7 @@ impl PlayState for MainMenuState {
                 }
                 // Register voxygen components / resources
                 crate::ecs::init(client.state_mut().ecs_mut());
+                check_world_fingerprint(global_state, &client.server_info().world);
                 self.init = InitState::Pipeline(Box::new(client));
             },
             Some(InitMsg::Done(Err(e))) => {
@@ -385,6 +406,15 @@ impl PlayState for MainMenuState {
                         .settings
                         .save_to_file_warn(&global_state.config_dir);
 
+                    // Only resume a lost session when logging back into the same account
+                    let resume_token = global_state
+                        .resume_session
+                        .take()
+                        .filter(|session| {
+                            session.server_address == server_address && session.username == username
+                        })
+                        .map(|session| session.token);
+
                     let connection_args = if use_srv {
                         ConnectionArgs::Srv {
                             hostname: server_address,
@@ -416,6 +446,7 @@ impl PlayState for MainMenuState {
                             .language
                             .send_to_server
                             .then_some(global_state.settings.language.selected_language.clone()),
+                        resume_token,
                         &global_state.i18n,
                         &global_state.config_dir,
                     );
@@ -492,9 +523,28 @@ impl PlayState for MainMenuState {
                         .client()
                         .map(|init| init.auth_trust(auth_server, trust));
                 },
+                MainMenuEvent::ToggleFavoriteServer { server_index } => {
+                    let net_settings = &mut global_state.settings.networking;
+                    if let Some(server) = net_settings.servers.get(server_index) {
+                        if !net_settings.favorite_servers.remove(server) {
+                            net_settings.favorite_servers.insert(server.clone());
+                        }
+                    }
+
+                    global_state
+                        .settings
+                        .save_to_file_warn(&global_state.config_dir);
+                },
+                MainMenuEvent::RefreshServerStatus => {
+                    self.main_menu_ui.refresh_server_status(
+                        &global_state.tokio_runtime,
+                        &global_state.settings.networking.servers,
+                    );
+                },
                 MainMenuEvent::DeleteServer { server_index } => {
                     let net_settings = &mut global_state.settings.networking;
-                    net_settings.servers.remove(server_index);
+                    let server = net_settings.servers.remove(server_index);
+                    net_settings.favorite_servers.remove(&server);
 
                     global_state
                         .settings
@@ -658,6 +708,7 @@ fn attempt_login(
     init: &mut InitState,
     runtime: &Arc<runtime::Runtime>,
     locale: Option<String>,
+    resume_token: Option<ResumeToken>,
     localized_strings: &LocalizationHandle,
     config_dir: &Path,
 ) {
@@ -692,7 +743,30 @@ fn attempt_login(
             password,
             Arc::clone(runtime),
             locale,
+            resume_token,
             config_dir,
         ));
     }
 }
+
+/// Remembers the world fingerprint of the server that was just joined, warning
+/// if its world changed since the last time it was joined.
+fn check_world_fingerprint(global_state: &mut GlobalState, fingerprint: &WorldFingerprint) {
+    let networking = &mut global_state.settings.networking;
+    let server = networking.default_server.clone();
+    match networking.world_fingerprints.get(&server) {
+        Some(known) if known == fingerprint => return,
+        Some(known) if !known.same_terrain(fingerprint) => warn!(
+            ?server,
+            "The world of this server changed since it was last joined, data cached for it is \
+             outdated"
+        ),
+        _ => {},
+    }
+    networking
+        .world_fingerprints
+        .insert(server, fingerprint.clone());
+    global_state
+        .settings
+        .save_to_file_warn(&global_state.config_dir);
+}
@@ -0,0 +1,100 @@
+//! Status of the saved servers shown in the server list, obtained from their
+//! query servers.
+
+use hashbrown::HashMap;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::runtime;
+use tracing::debug;
+use veloren_query_server::{client::QueryClient, proto::ServerInfo};
+
+/// Port the query server listens on unless configured otherwise.
+const DEFAULT_QUERY_PORT: u16 = 14006;
+
+#[derive(Clone, Copy)]
+pub enum ServerStatus {
+    Querying,
+    Online { info: ServerInfo, ping: Duration },
+    Unreachable,
+}
+
+impl ServerStatus {
+    /// Whether the server runs the same version as this client, if known.
+    pub fn compatible(&self) -> Option<bool> {
+        match self {
+            Self::Online { info, .. } => Some(
+                u32::from_str_radix(&common::util::GIT_HASH[..8], 16)
+                    .is_ok_and(|hash| hash == info.git_hash),
+            ),
+            Self::Querying | Self::Unreachable => None,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ServerStatuses(Arc<Mutex<HashMap<String, ServerStatus>>>);
+
+impl ServerStatuses {
+    /// Query all given servers concurrently, replacing any previous results.
+    pub fn refresh(&self, runtime: &runtime::Runtime, servers: &[String]) {
+        let mut statuses = self.0.lock().unwrap();
+        statuses.clear();
+        for server in servers {
+            statuses.insert(server.clone(), ServerStatus::Querying);
+
+            let statuses = Arc::clone(&self.0);
+            let server = server.clone();
+            runtime.spawn(async move {
+                let status = query(&server).await;
+                // Results of queries from before the last refresh are dropped
+                if let Some(old) = statuses.lock().unwrap().get_mut(&server) {
+                    *old = status;
+                }
+            });
+        }
+    }
+
+    pub fn get(&self, server: &str) -> Option<ServerStatus> {
+        self.0.lock().unwrap().get(server).copied()
+    }
+}
+
+async fn query(server: &str) -> ServerStatus {
+    let addr = match tokio::net::lookup_host((query_host(server), DEFAULT_QUERY_PORT)).await {
+        Ok(mut addrs) => addrs.next(),
+        Err(e) => {
+            debug!(?e, ?server, "Failed to resolve server address");
+            None
+        },
+    };
+    let Some(addr) = addr else {
+        return ServerStatus::Unreachable;
+    };
+
+    match QueryClient::new(addr).server_info().await {
+        Ok((info, ping)) => ServerStatus::Online { info, ping },
+        Err(e) => {
+            debug!(?e, ?server, "Failed to query server");
+            ServerStatus::Unreachable
+        },
+    }
+}
+
+/// Strip the game port from a server address, since the query server uses a
+/// different one.
+fn query_host(server: &str) -> &str {
+    let server = server.trim();
+    if let Some(ipv6) = server
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+    {
+        return ipv6.0;
+    }
+    match server.rsplit_once(':') {
+        // Bare IPv6 addresses contain multiple colons and no port
+        Some((host, port)) if !host.contains(':') && port.parse::<u16>().is_ok() => host,
+        _ => server,
+    }
+}
@@ -201,6 +201,22 @@ impl Screen {
                             ClientInitStage::StartingClient => {
                                 i18n.get_msg("hud-init-stage-client-starting-client")
                             },
+                            ClientInitStage::Queued {
+                                position,
+                                estimated_wait,
+                            } => match estimated_wait {
+                                Some(wait) => i18n.get_msg_ctx(
+                                    "hud-init-stage-client-queued-eta",
+                                    &i18n::fluent_args! {
+                                        "position" => position,
+                                        "minutes" => wait.as_secs().div_ceil(60).max(1),
+                                    },
+                                ),
+                                None => i18n.get_msg_ctx(
+                                    "hud-init-stage-client-queued",
+                                    &i18n::fluent_args! { "position" => position },
+                                ),
+                            },
                         },
                         DetailedInitializationStage::CreatingRenderPipeline(done, total) => i18n
                             .get_msg_ctx(
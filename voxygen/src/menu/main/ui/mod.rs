@@ -29,7 +29,7 @@ use rand::{seq::SliceRandom, thread_rng};
 use std::time::Duration;
 use tracing::warn;
 
-use super::DetailedInitializationStage;
+use super::{server_status::ServerStatuses, DetailedInitializationStage};
 
 // TODO: what is this? (showed up in rebase)
 //const COL1: Color = Color::Rgba(0.07, 0.1, 0.1, 0.9);
@@ -151,6 +151,10 @@ pub enum Event {
     DeleteServer {
         server_index: usize,
     },
+    ToggleFavoriteServer {
+        server_index: usize,
+    },
+    RefreshServerStatus,
 }
 
 pub struct LoginInfo {
@@ -223,6 +227,7 @@ pub struct Controls {
     // field).
     server_field_locked: bool,
     selected_server_index: Option<usize>,
+    server_statuses: ServerStatuses,
     login_info: LoginInfo,
 
     show: Showing,
@@ -263,6 +268,8 @@ enum Message {
     TrustPromptCancel,
     CloseError,
     DeleteServer,
+    ToggleFavoriteServer(usize),
+    RefreshServers,
     /* Note: Keeping in case we re-add the disclaimer
      *AcceptDisclaimer, */
 }
@@ -321,6 +328,7 @@ impl Controls {
 
             server_field_locked,
             selected_server_index,
+            server_statuses: ServerStatuses::default(),
             login_info,
 
             show: Showing::Login,
@@ -403,6 +411,8 @@ impl Controls {
                 &self.fonts,
                 &self.imgs,
                 &settings.networking.servers,
+                &settings.networking.favorite_servers,
+                &self.server_statuses,
                 self.selected_server_index,
                 &self.i18n.read(),
                 button_style,
@@ -468,6 +478,7 @@ impl Controls {
                     self.screen = Screen::Servers {
                         screen: servers::Screen::new(),
                     };
+                    events.push(Event::RefreshServerStatus);
                 }
             },
             Message::ShowCredits => {
@@ -585,6 +596,10 @@ impl Controls {
                     events.push(Event::DeleteServer { server_index });
                 }
             },
+            Message::ToggleFavoriteServer(server_index) => {
+                events.push(Event::ToggleFavoriteServer { server_index });
+            },
+            Message::RefreshServers => events.push(Event::RefreshServerStatus),
             /* Note: Keeping in case we re-add the disclaimer */
             /*Message::AcceptDisclaimer => {
                 if let Screen::Disclaimer { .. } = &self.screen {
@@ -741,6 +756,21 @@ impl MainMenuUi {
         self.controls.update_init_stage(stage);
     }
 
+    pub fn refresh_server_status(&self, runtime: &tokio::runtime::Runtime, servers: &[String]) {
+        self.controls.server_statuses.refresh(runtime, servers);
+    }
+
+    /// Shows the loading screen of a singleplayer world that was started
+    /// without going through the world selector.
+    #[cfg(feature = "singleplayer")]
+    pub fn show_singleplayer_loading(&mut self) {
+        self.controls.screen = Screen::Connecting {
+            screen: connecting::Screen::new(&mut self.ui),
+            connection_state: ConnectionState::InProgress,
+            init_stage: DetailedInitializationStage::Singleplayer,
+        };
+    }
+
     pub fn connected(&mut self) { self.controls.exit_connect_screen(); }
 
     pub fn cancel_connection(&mut self) { self.controls.exit_connect_screen(); }
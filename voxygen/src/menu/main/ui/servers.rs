@@ -1,8 +1,12 @@
-use super::{Imgs, Message, FILL_FRAC_ONE};
+use super::{
+    super::server_status::{ServerStatus, ServerStatuses},
+    Imgs, Message, FILL_FRAC_ONE,
+};
 use crate::ui::{
     fonts::IcedFonts as Fonts,
     ice::{component::neat_button, style, Element},
 };
+use hashbrown::HashSet;
 use i18n::Localization;
 use iced::{
     button, scrollable, Align, Button, Column, Container, Length, Row, Scrollable, Space, Text,
@@ -11,7 +15,9 @@ use iced::{
 pub struct Screen {
     back_button: button::State,
     delete_button: button::State,
+    refresh_button: button::State,
     server_buttons: Vec<button::State>,
+    favorite_buttons: Vec<button::State>,
     servers_list: scrollable::State,
 }
 
@@ -20,7 +26,9 @@ impl Screen {
         Self {
             back_button: Default::default(),
             delete_button: Default::default(),
+            refresh_button: Default::default(),
             server_buttons: vec![],
+            favorite_buttons: vec![],
             servers_list: Default::default(),
         }
     }
@@ -29,7 +37,9 @@ impl Screen {
         &mut self,
         fonts: &Fonts,
         imgs: &Imgs,
-        servers: &[impl AsRef<str>],
+        servers: &[String],
+        favorite_servers: &HashSet<String>,
+        server_statuses: &ServerStatuses,
         selected_server_index: Option<usize>,
         i18n: &Localization,
         button_style: style::button::Style,
@@ -65,6 +75,19 @@ impl Screen {
         .width(Length::Fill)
         .align_x(Align::Center);
 
+        let refresh_button = Container::new(
+            Container::new(neat_button(
+                &mut self.refresh_button,
+                i18n.get_msg("main-servers-refresh"),
+                FILL_FRAC_ONE,
+                button_style,
+                Some(Message::RefreshServers),
+            ))
+            .max_width(200),
+        )
+        .width(Length::Fill)
+        .align_x(Align::Center);
+
         let mut list = Scrollable::new(&mut self.servers_list)
             .spacing(8)
             .align_items(Align::Start)
@@ -74,44 +97,76 @@ impl Screen {
         // Reset button states if servers were added / removed
         if self.server_buttons.len() != servers.len() {
             self.server_buttons = vec![Default::default(); servers.len()];
+            self.favorite_buttons = vec![Default::default(); servers.len()];
         }
 
-        let list_items =
-            self.server_buttons
-                .iter_mut()
-                .zip(servers)
-                .enumerate()
-                .map(|(i, (state, server))| {
-                    let color = if Some(i) == selected_server_index {
-                        (97, 255, 18)
-                    } else {
-                        (97, 97, 25)
-                    };
-                    let button = Button::new(
-                        state,
-                        Row::with_children(vec![
-                            Space::new(Length::FillPortion(5), Length::Units(0)).into(),
-                            Text::new(server.as_ref())
-                                .size(fonts.cyri.scale(30))
-                                .width(Length::FillPortion(95))
-                                .vertical_alignment(iced::VerticalAlignment::Center)
-                                .into(),
-                        ]),
-                    )
-                    .style(
-                        style::button::Style::new(imgs.selection)
-                            .hover_image(imgs.selection_hover)
-                            .press_image(imgs.selection_press)
-                            .image_color(vek::Rgba::new(color.0, color.1, color.2, 255)),
-                    )
-                    .min_height(100)
-                    .on_press(Message::ServerChanged(i));
+        let mut items = self
+            .server_buttons
+            .iter_mut()
+            .zip(self.favorite_buttons.iter_mut())
+            .zip(servers)
+            .enumerate()
+            .collect::<Vec<_>>();
+        // Favorites are listed first, otherwise servers keep the order they were
+        // added in
+        items.sort_by_key(|(_, (_, server))| !favorite_servers.contains(*server));
+
+        let list_items = items
+            .into_iter()
+            .map(|(i, ((state, favorite_state), server))| {
+                let is_favorite = favorite_servers.contains(server);
+
+                let color = if Some(i) == selected_server_index {
+                    (97, 255, 18)
+                } else {
+                    (97, 97, 25)
+                };
+                let button = Button::new(
+                    state,
                     Row::with_children(vec![
-                        Space::new(Length::FillPortion(3), Length::Units(0)).into(),
-                        button.width(Length::FillPortion(92)).into(),
                         Space::new(Length::FillPortion(5), Length::Units(0)).into(),
+                        Column::with_children(vec![
+                            Text::new(server.as_str()).size(fonts.cyri.scale(30)).into(),
+                            status_row(fonts, i18n, server_statuses.get(server)),
+                        ])
+                        .width(Length::FillPortion(95))
+                        .spacing(2)
+                        .into(),
                     ])
-                });
+                    .align_items(Align::Center),
+                )
+                .style(
+                    style::button::Style::new(imgs.selection)
+                        .hover_image(imgs.selection_hover)
+                        .press_image(imgs.selection_press)
+                        .image_color(vek::Rgba::new(color.0, color.1, color.2, 255)),
+                )
+                .min_height(100)
+                .on_press(Message::ServerChanged(i));
+
+                let favorite_button = neat_button(
+                    favorite_state,
+                    if is_favorite {
+                        i18n.get_msg("main-servers-unfavorite")
+                    } else {
+                        i18n.get_msg("main-servers-favorite")
+                    },
+                    FILL_FRAC_ONE,
+                    button_style,
+                    Some(Message::ToggleFavoriteServer(i)),
+                );
+
+                Row::with_children(vec![
+                    Space::new(Length::FillPortion(3), Length::Units(0)).into(),
+                    button.width(Length::FillPortion(72)).into(),
+                    Container::new(favorite_button)
+                        .width(Length::FillPortion(20))
+                        .padding(4)
+                        .into(),
+                    Space::new(Length::FillPortion(5), Length::Units(0)).into(),
+                ])
+                .align_items(Align::Center)
+            });
 
         for item in list_items {
             list = list.push(item);
@@ -122,9 +177,13 @@ impl Screen {
                 Column::with_children(vec![
                     title.into(),
                     list.into(),
-                    Row::with_children(vec![delete_button.into(), back_button.into()])
-                        .width(Length::Fill)
-                        .into(),
+                    Row::with_children(vec![
+                        delete_button.into(),
+                        refresh_button.into(),
+                        back_button.into(),
+                    ])
+                    .width(Length::Fill)
+                    .into(),
                 ])
                 .width(Length::Fill)
                 .height(Length::Fill)
@@ -138,7 +197,7 @@ impl Screen {
                     (54, 46, 38, 255).into(),
                 ),
             )
-            .max_width(500),
+            .max_width(600),
         )
         .width(Length::Fill)
         .align_x(Align::Center)
@@ -146,3 +205,59 @@ impl Screen {
         .into()
     }
 }
+
+/// Population, latency and version compatibility of a server.
+fn status_row<'a>(
+    fonts: &Fonts,
+    i18n: &Localization,
+    status: Option<ServerStatus>,
+) -> Element<'a, Message> {
+    const GOOD_COLOR: [f32; 3] = [0.38, 1.0, 0.07];
+    const BAD_COLOR: [f32; 3] = [1.0, 0.35, 0.25];
+    const NEUTRAL_COLOR: [f32; 3] = [0.7, 0.7, 0.7];
+
+    let text = |text: String, color: [f32; 3]| {
+        Text::new(text)
+            .size(fonts.cyri.scale(18))
+            .color(color)
+            .into()
+    };
+
+    let children = match status {
+        None | Some(ServerStatus::Querying) => vec![text(
+            i18n.get_msg("main-servers-querying").into_owned(),
+            NEUTRAL_COLOR,
+        )],
+        Some(ServerStatus::Unreachable) => vec![text(
+            i18n.get_msg("main-servers-unreachable").into_owned(),
+            BAD_COLOR,
+        )],
+        Some(status @ ServerStatus::Online { info, ping }) => {
+            let (badge, badge_color) = if status.compatible().unwrap_or(false) {
+                (i18n.get_msg("main-servers-compatible"), GOOD_COLOR)
+            } else {
+                (i18n.get_msg("main-servers-incompatible"), BAD_COLOR)
+            };
+            vec![
+                text(
+                    i18n.get_msg_ctx("main-servers-population", &i18n::fluent_args! {
+                        "players" => info.players_count,
+                        "cap" => info.player_cap,
+                    })
+                    .into_owned(),
+                    NEUTRAL_COLOR,
+                ),
+                text(
+                    i18n.get_msg_ctx("main-servers-ping", &i18n::fluent_args! {
+                        "ping" => ping.as_millis() as u64,
+                    })
+                    .into_owned(),
+                    NEUTRAL_COLOR,
+                ),
+                text(badge.into_owned(), badge_color),
+            ]
+        },
+    };
+
+    Row::with_children(children).spacing(15).into()
+}
@@ -1,3 +1,4 @@
+use crate::telemetry;
 use std::{panic, panic::PanicInfo, path::PathBuf};
 use tracing::error;
 
@@ -67,10 +68,15 @@ pub fn set_panic_hook(log_filename: String, logs_dir: PathBuf) {
             .as_str(),
         );
 
+        let backtrace = backtrace::Backtrace::new();
         error!(
             "VOXYGEN HAS PANICKED\n\n{}\n\nBacktrace:\n{:?}",
-            dialog_message,
-            backtrace::Backtrace::new(),
+            dialog_message, backtrace,
+        );
+        telemetry::report_crash(
+            reason,
+            panic_info.location().map(|location| location.to_string()),
+            &backtrace,
         );
 
         #[cfg(feature = "native-dialog")]
@@ -1,20 +1,46 @@
-use crate::hud;
+use crate::hud::{self, map_annotation::MapAnnotation};
 use common::{character::CharacterId, uuid::Uuid};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::VecDeque,
     fs,
     io::Write,
     path::{Path, PathBuf},
 };
 use tracing::warn;
 
+/// How many completed trades are remembered per character
+const MAX_TRADE_HISTORY: usize = 20;
+
+/// Summary of a completed trade, kept so players can look back at what they
+/// paid for things.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TradeRecord {
+    /// Name of the other party
+    pub partner: String,
+    /// Names and amounts of the items we gave away
+    pub given: Vec<(String, u32)>,
+    /// Names and amounts of the items we received
+    pub received: Vec<(String, u32)>,
+    /// Approximate value in coins of what we gave and what we received, if
+    /// the trade took place at a site with known prices
+    pub value: Option<(f32, f32)>,
+}
+
 /// Represents a character in the profile.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(default)]
 pub struct CharacterProfile {
     /// Array representing a character's hotbar.
     pub hotbar_slots: [Option<hud::HotbarSlotContents>; 10],
+    /// Most recent completed trades, newest first.
+    pub trade_history: VecDeque<TradeRecord>,
+    /// Ids of the sites that are only shown on the map once discovered, such
+    /// as climbing routes, that the character found.
+    pub discovered_sites: HashSet<u64>,
+    /// Array representing a character's quick-action bar.
+    pub quick_bar_slots: [Option<hud::QuickBarSlotContents>; hud::QUICK_SLOTS],
 }
 
 const fn default_slots() -> [Option<hud::HotbarSlotContents>; 10] {
@@ -25,6 +51,9 @@ impl Default for CharacterProfile {
     fn default() -> Self {
         CharacterProfile {
             hotbar_slots: default_slots(),
+            trade_history: VecDeque::new(),
+            discovered_sites: HashSet::new(),
+            quick_bar_slots: Default::default(),
         }
     }
 }
@@ -41,6 +70,8 @@ pub struct ServerProfile {
     pub spectate_position: Option<vek::Vec3<f32>>,
     /// Hash of left-accepted server rules
     pub accepted_rules: Option<u64>,
+    /// Lines and icons drawn on the world map of this server
+    pub map_annotations: Vec<MapAnnotation>,
 }
 
 impl Default for ServerProfile {
@@ -50,6 +81,7 @@ impl Default for ServerProfile {
             selected_character: None,
             spectate_position: None,
             accepted_rules: None,
+            map_annotations: Vec::new(),
         }
     }
 }
@@ -163,6 +195,160 @@ impl Profile {
         .hotbar_slots = slots;
     }
 
+    /// Get the quick_bar_slots for the requested character_id, which are
+    /// empty if the server or character does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    pub fn get_quick_bar_slots(
+        &self,
+        server: &str,
+        character_id: Option<CharacterId>,
+    ) -> [Option<hud::QuickBarSlotContents>; hud::QUICK_SLOTS] {
+        match character_id {
+            Some(character_id) => self
+                .servers
+                .get(server)
+                .and_then(|s| s.characters.get(&character_id)),
+            None => self.transient_character.as_ref(),
+        }
+        .map(|c| c.quick_bar_slots.clone())
+        .unwrap_or_default()
+    }
+
+    /// Set the quick_bar_slots for the requested character_id.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    /// * slots - array of quick_bar_slots to save.
+    pub fn set_quick_bar_slots(
+        &mut self,
+        server: &str,
+        character_id: Option<CharacterId>,
+        slots: [Option<hud::QuickBarSlotContents>; hud::QUICK_SLOTS],
+    ) {
+        match character_id {
+            Some(character_id) => self
+                .servers
+                .entry(server.to_string())
+                .or_default()
+                .characters
+                .entry(character_id)
+                .or_default(),
+            None => self.transient_character.get_or_insert_default(),
+        }
+        .quick_bar_slots = slots;
+    }
+
+    /// Get the trade history for the requested character_id.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    pub fn get_trade_history(
+        &self,
+        server: &str,
+        character_id: Option<CharacterId>,
+    ) -> Option<&VecDeque<TradeRecord>> {
+        match character_id {
+            Some(character_id) => self
+                .servers
+                .get(server)
+                .and_then(|s| s.characters.get(&character_id)),
+            None => self.transient_character.as_ref(),
+        }
+        .map(|c| &c.trade_history)
+    }
+
+    /// Record a completed trade for the requested character_id, forgetting
+    /// the oldest one if the history is full.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    /// * record - summary of the trade.
+    pub fn push_trade_record(
+        &mut self,
+        server: &str,
+        character_id: Option<CharacterId>,
+        record: TradeRecord,
+    ) {
+        let history = &mut match character_id {
+            Some(character_id) => self
+                .servers
+                .entry(server.to_string())
+                .or_default()
+                .characters
+                .entry(character_id)
+                .or_default(),
+            None => self.transient_character.get_or_insert_default(),
+        }
+        .trade_history;
+        history.push_front(record);
+        history.truncate(MAX_TRADE_HISTORY);
+    }
+
+    /// Get the ids of the sites discovered by the requested character_id.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    pub fn get_discovered_sites(
+        &self,
+        server: &str,
+        character_id: Option<CharacterId>,
+    ) -> Option<&HashSet<u64>> {
+        match character_id {
+            Some(character_id) => self
+                .servers
+                .get(server)
+                .and_then(|s| s.characters.get(&character_id)),
+            None => self.transient_character.as_ref(),
+        }
+        .map(|c| &c.discovered_sites)
+    }
+
+    /// Remember that the requested character_id discovered a site, returning
+    /// whether it wasn't discovered before.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    /// * site - id of the discovered site.
+    pub fn discover_site(
+        &mut self,
+        server: &str,
+        character_id: Option<CharacterId>,
+        site: u64,
+    ) -> bool {
+        match character_id {
+            Some(character_id) => self
+                .servers
+                .entry(server.to_string())
+                .or_default()
+                .characters
+                .entry(character_id)
+                .or_default(),
+            None => self.transient_character.get_or_insert_default(),
+        }
+        .discovered_sites
+        .insert(site)
+    }
+
     /// Get the selected_character for the provided server.
     ///
     /// if the server does not exist then the default selected_character (None)
@@ -233,6 +419,34 @@ impl Profile {
             .spectate_position = spectate_position;
     }
 
+    /// Get the annotations drawn on the world map of the provided server.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the player is on.
+    pub fn get_map_annotations(&self, server: &str) -> &[MapAnnotation] {
+        self.servers
+            .get(server)
+            .map_or(&[], |s| s.map_annotations.as_slice())
+    }
+
+    /// Get the annotations drawn on the world map of the provided server to
+    /// modify them.
+    ///
+    /// If the server does not exist then the appropriate fields will be
+    /// initialised.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the player is on.
+    pub fn map_annotations_mut(&mut self, server: &str) -> &mut Vec<MapAnnotation> {
+        &mut self
+            .servers
+            .entry(server.to_string())
+            .or_default()
+            .map_annotations
+    }
+
     /// Save the current profile to disk.
     fn save_to_file(&self, config_dir: &Path) -> std::io::Result<()> {
         let path = Profile::get_path(config_dir);
@@ -266,4 +480,25 @@ mod tests {
         let slots = [(); 10].map(|()| None);
         profile.set_hotbar_slots("TestServer", Some(CharacterId(12345)), slots);
     }
+
+    #[test]
+    fn test_trade_history_is_capped() {
+        let mut profile = Profile::default();
+        for i in 0..MAX_TRADE_HISTORY + 5 {
+            profile.push_trade_record("TestServer", Some(CharacterId(12345)), TradeRecord {
+                partner: format!("Trader {}", i),
+                given: Vec::new(),
+                received: Vec::new(),
+                value: None,
+            });
+        }
+        let history = profile
+            .get_trade_history("TestServer", Some(CharacterId(12345)))
+            .unwrap();
+        assert_eq!(history.len(), MAX_TRADE_HISTORY);
+        assert_eq!(
+            history[0].partner,
+            format!("Trader {}", MAX_TRADE_HISTORY + 4)
+        );
+    }
 }
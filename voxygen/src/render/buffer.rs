@@ -24,6 +24,19 @@ impl<T: Copy + Pod> Buffer<T> {
         }
     }
 
+    /// Fills an existing buffer instead of allocating a new one. The buffer
+    /// must have been created with [`wgpu::BufferUsages::COPY_DST`] and be
+    /// large enough to hold `data`.
+    pub(super) fn reuse(queue: &wgpu::Queue, buf: wgpu::Buffer, data: &[T]) -> Self {
+        queue.write_buffer(&buf, 0, bytemuck::cast_slice(data));
+
+        Self {
+            buf,
+            len: data.len(),
+            phantom_data: std::marker::PhantomData,
+        }
+    }
+
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize { self.len }
 }
@@ -17,7 +17,7 @@ pub use self::{
     error::RenderError,
     instances::Instances,
     mesh::{Mesh, Quad, Tri},
-    model::{DynamicModel, Model, SubModel},
+    model::{DynamicModel, Model, ModelPool, SubModel},
     pipelines::{
         clouds::Locals as CloudsLocals,
         debug::{DebugLayout, DebugPipeline, Locals as DebugLocals, Vertex as DebugVertex},
@@ -224,6 +224,9 @@ pub enum LightingMode {
     BlinnPhong,
 }
 
+/// The maximum number of point lights that can cast shadows at the same time.
+pub const MAX_POINT_SHADOWS: u32 = 8;
+
 /// Shadow map settings.
 #[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct ShadowMapMode {
@@ -399,6 +402,9 @@ pub struct RenderMode {
     pub bloom: BloomMode,
     /// 0.0..1.0
     pub point_glow: f32,
+    /// How many of the nearest point lights cast shadows when rendering with
+    /// shadow maps, up to [`MAX_POINT_SHADOWS`].
+    pub point_shadows: u32,
 
     pub flashing_lights_enabled: bool,
 
@@ -421,6 +427,7 @@ impl Default for RenderMode {
             rain_occlusion: ShadowMapMode::default(),
             bloom: BloomMode::default(),
             point_glow: 0.35,
+            point_shadows: 2,
             flashing_lights_enabled: true,
             experimental_shaders: HashSet::default(),
             upscale_mode: UpscaleMode::default(),
@@ -443,6 +450,7 @@ impl RenderMode {
                 rain_occlusion: self.rain_occlusion,
                 bloom: self.bloom,
                 point_glow: self.point_glow,
+                point_shadows: self.point_shadows.min(MAX_POINT_SHADOWS),
                 flashing_lights_enabled: self.flashing_lights_enabled,
                 experimental_shaders: self.experimental_shaders,
             },
@@ -468,6 +476,7 @@ pub struct PipelineModes {
     pub rain_occlusion: ShadowMapMode,
     bloom: BloomMode,
     point_glow: f32,
+    pub point_shadows: u32,
     flashing_lights_enabled: bool,
     experimental_shaders: HashSet<ExperimentalShader>,
 }
@@ -3,8 +3,13 @@ use super::{
     mesh::Mesh,
     Vertex,
 };
+use hashbrown::HashMap;
 use std::ops::Range;
 
+/// Smallest buffer handed out by a [`ModelPool`], so that tiny meshes don't
+/// end up spread over lots of differently sized buckets
+const MIN_POOLED_BUFFER_SIZE: wgpu::BufferAddress = 16 * 1024;
+
 /// Represents a mesh that has been sent to the GPU.
 pub struct SubModel<'a, V: Vertex> {
     pub vertex_range: Range<u32>,
@@ -40,6 +45,27 @@ impl<V: Vertex> Model<V> {
         })
     }
 
+    /// Like [`Model::new`], but reuses a vertex buffer from `pool` if one of
+    /// a fitting size is available
+    pub fn new_pooled(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        mesh: &Mesh<V>,
+        pool: &mut ModelPool,
+    ) -> Option<Self> {
+        if mesh.vertices().is_empty() {
+            return None;
+        }
+
+        let buf = pool.take(
+            device,
+            mesh.vertices().len() as wgpu::BufferAddress * V::STRIDE,
+        );
+        Some(Self {
+            vbuf: Buffer::reuse(queue, buf, mesh.vertices()),
+        })
+    }
+
     /// Create a model with a slice of a portion of this model to send to the
     /// renderer.
     pub fn submodel(&self, vertex_range: Range<u32>) -> SubModel<V> {
@@ -54,6 +80,79 @@ impl<V: Vertex> Model<V> {
 
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize { self.vbuf.len() }
+
+    /// Size of the GPU memory backing this model, which may be larger than
+    /// its vertices need if it came from a [`ModelPool`]
+    pub fn size_bytes(&self) -> u64 { self.vbuf.buf.size() }
+}
+
+/// Keeps the vertex buffers of models that are no longer needed around, so
+/// that new models of a similar size can reuse them rather than allocating
+/// fresh GPU memory every time.
+///
+/// Buffers are bucketed by their size, rounded up to the next power of two.
+#[derive(Default)]
+pub struct ModelPool {
+    free: HashMap<wgpu::BufferAddress, Vec<wgpu::Buffer>>,
+    pooled_bytes: u64,
+    max_pooled_bytes: u64,
+}
+
+impl ModelPool {
+    /// Total size of the buffers waiting to be reused
+    pub fn pooled_bytes(&self) -> u64 { self.pooled_bytes }
+
+    /// Changes how much memory the pool may hold on to, freeing buffers if it
+    /// currently holds more than that
+    pub fn set_max_pooled_bytes(&mut self, max_pooled_bytes: u64) {
+        self.max_pooled_bytes = max_pooled_bytes;
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        // Free the largest buffers first, they are the least likely to be reused
+        while self.pooled_bytes > self.max_pooled_bytes {
+            let Some(size) = self.free.keys().max().copied() else {
+                break;
+            };
+            let bufs = self.free.get_mut(&size).expect("Key was just found");
+            bufs.pop();
+            if bufs.is_empty() {
+                self.free.remove(&size);
+            }
+            self.pooled_bytes -= size;
+        }
+    }
+
+    fn take(&mut self, device: &wgpu::Device, size: wgpu::BufferAddress) -> wgpu::Buffer {
+        let size = size.max(MIN_POOLED_BUFFER_SIZE).next_power_of_two();
+        if let Some(buf) = self.free.get_mut(&size).and_then(Vec::pop) {
+            self.pooled_bytes -= size;
+            return buf;
+        }
+
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Pooled vertex buffer"),
+            size,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Hands the vertex buffer of a model back to the pool. Models that
+    /// weren't created by [`Model::new_pooled`] are simply dropped.
+    pub fn recycle<V: Vertex>(&mut self, model: Model<V>) {
+        let buf = model.vbuf.buf;
+        let size = buf.size();
+        if size >= MIN_POOLED_BUFFER_SIZE
+            && size.is_power_of_two()
+            && buf.usage().contains(wgpu::BufferUsages::COPY_DST)
+        {
+            self.free.entry(size).or_default().push(buf);
+            self.pooled_bytes += size;
+            self.trim();
+        }
+    }
 }
 
 /// Represents a mesh that has been sent to the GPU.
@@ -73,8 +73,10 @@ pub struct Globals {
     ambiance: f32,
     cam_mode: u32,
     sprite_render_distance: f32,
+    interact_highlight: f32,
+    interact_reveal: f32,
     // To keep 16-byte-aligned.
-    globals_dummy: [f32; 3],
+    globals_dummy: [f32; 1],
 }
 /// Make sure Globals is 16-byte-aligned.
 const _: () = assert!(core::mem::size_of::<Globals>() % 16 == 0);
@@ -113,6 +115,7 @@ impl Globals {
         light_count: usize,
         shadow_count: usize,
         directed_light_count: usize,
+        point_shadow_count: usize,
         medium: BlockKind,
         select_pos: Option<Vec3<i32>>,
         gamma: f32,
@@ -122,6 +125,8 @@ impl Globals {
         ambiance: f32,
         cam_mode: CameraMode,
         sprite_render_distance: f32,
+        interact_highlight: f32,
+        interact_reveal: f32,
     ) -> Self {
         Self {
             view_mat: view_mat.into_col_arrays(),
@@ -164,7 +169,7 @@ impl Globals {
                 usize::min(light_count, MAX_POINT_LIGHT_COUNT) as u32,
                 usize::min(shadow_count, MAX_FIGURE_SHADOW_COUNT) as u32,
                 usize::min(directed_light_count, MAX_DIRECTED_LIGHT_COUNT) as u32,
-                0,
+                usize::min(point_shadow_count, light_count) as u32,
             ],
             shadow_proj_factors: [
                 shadow_planes.y / (shadow_planes.y - shadow_planes.x),
@@ -192,7 +197,9 @@ impl Globals {
             ambiance: ambiance.clamped(0.0, 1.0),
             cam_mode: cam_mode as u32,
             sprite_render_distance,
-            globals_dummy: [0.0; 3],
+            interact_highlight,
+            interact_reveal: interact_reveal.clamped(0.0, 1.0),
+            globals_dummy: [0.0; 1],
         }
     }
 }
@@ -560,7 +567,7 @@ impl GlobalsLayouts {
                     visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
                     ty: wgpu::BindingType::Texture {
                         sample_type: wgpu::TextureSampleType::Depth,
-                        view_dimension: wgpu::TextureViewDimension::Cube,
+                        view_dimension: wgpu::TextureViewDimension::CubeArray,
                         multisampled: false,
                     },
                     count: None,
@@ -114,6 +114,8 @@ pub enum ParticleMode {
     Spore = 60,
     SurpriseEgg = 61,
     FlameTornado = 62,
+    SnowFootprint = 63,
+    SandFootprint = 64,
 }
 
 impl ParticleMode {
@@ -15,8 +15,10 @@ pub struct Locals {
     /// A value to offset the rain, to make it move over time.
     integrated_rain_vel: f32,
     rain_density: f32,
+    /// How much snow settled on the ground, between 0 and 1.
+    snow_cover: f32,
     // To keep 16-byte-aligned.
-    occlusion_dummy: [f32; 2],
+    occlusion_dummy: f32,
 }
 /// Make sure Locals is 16-byte-aligned.
 const _: () = assert!(core::mem::size_of::<Locals>() % 16 == 0);
@@ -28,6 +30,7 @@ impl Locals {
         rain_dir_mat: Mat4<f32>,
         rain_density: f32,
         integrated_rain_vel: f32,
+        snow_cover: f32,
     ) -> Self {
         Self {
             rain_occlusion_matrices: rain_occlusion_matrices.into_col_arrays(),
@@ -35,7 +38,16 @@ impl Locals {
             rain_dir_mat: rain_dir_mat.into_col_arrays(),
             integrated_rain_vel,
             rain_density,
-            occlusion_dummy: [0.0; 2],
+            snow_cover,
+            occlusion_dummy: 0.0,
+        }
+    }
+
+    /// Locals without any falling rain, only the snow that settled.
+    pub fn with_snow_cover(snow_cover: f32) -> Self {
+        Self {
+            snow_cover,
+            ..Self::default()
         }
     }
 }
@@ -99,6 +99,7 @@ pub struct Instance {
     inst_mat2: [f32; 4],
     inst_mat3: [f32; 4],
     pos_ori_door: u32,
+    /// The highest bit is set for sprites that can be interacted with
     inst_vert_page: u32,
     inst_light: f32,
     inst_glow: f32,
@@ -117,6 +118,7 @@ impl Instance {
         glow: f32,
         vert_page: u32,
         is_door: bool,
+        is_interactable: bool,
     ) -> Self {
         const EXTRA_NEG_Z: i32 = 32768;
 
@@ -131,7 +133,7 @@ impl Instance {
                 | (((pos.z + EXTRA_NEG_Z).clamp(0, 1 << 16) as u32) & 0xFFFF) << 12
                 | (u32::from(ori_bits) & 0x7) << 29
                 | (u32::from(is_door) & 1) << 28,
-            inst_vert_page: vert_page,
+            inst_vert_page: (vert_page & 0x7FFF_FFFF) | u32::from(is_interactable) << 31,
             inst_light: light,
             inst_glow: glow,
             model_wind_sway: wind_sway,
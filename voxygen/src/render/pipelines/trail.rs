@@ -9,11 +9,13 @@ use std::{
 #[derive(Copy, Clone, Debug, Zeroable, Pod, PartialEq)]
 pub struct Vertex {
     pub pos: [f32; 3],
+    pub color: [f32; 4],
 }
 
 impl Vertex {
     fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
-        const ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Float32x3];
+        const ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+            wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x4];
         wgpu::VertexBufferLayout {
             array_stride: Self::STRIDE,
             step_mode: wgpu::VertexStepMode::Vertex,
@@ -21,7 +23,12 @@ impl Vertex {
         }
     }
 
-    pub fn zero() -> Self { Self { pos: [0.0; 3] } }
+    pub fn zero() -> Self {
+        Self {
+            pos: [0.0; 3],
+            color: [0.0; 4],
+        }
+    }
 }
 
 impl Mul<f32> for Vertex {
@@ -30,6 +37,7 @@ impl Mul<f32> for Vertex {
     fn mul(self, val: f32) -> Self::Output {
         Self {
             pos: self.pos.map(|a| a * val),
+            color: self.color.map(|a| a * val),
         }
     }
 }
@@ -44,6 +52,12 @@ impl Add<Vertex> for Vertex {
                 self.pos[1] + other.pos[1],
                 self.pos[2] + other.pos[2],
             ],
+            color: [
+                self.color[0] + other.color[0],
+                self.color[1] + other.color[1],
+                self.color[2] + other.color[2],
+                self.color[3] + other.color[3],
+            ],
         }
     }
 }
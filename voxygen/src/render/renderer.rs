@@ -22,7 +22,7 @@ use super::{
     consts::Consts,
     instances::Instances,
     mesh::Mesh,
-    model::{DynamicModel, Model},
+    model::{DynamicModel, Model, ModelPool},
     pipelines::{
         blit, bloom, clouds, debug, figure, postprocess, rain_occlusion, rope, shadow, sprite,
         terrain, ui, GlobalsBindGroup, GlobalsLayouts, ShadowTexturesBindGroup,
@@ -181,6 +181,8 @@ pub struct Renderer {
 
     // To remember the backend info after initialization for debug purposes
     graphics_backend: String,
+    /// The selected graphics device, reported by telemetry
+    adapter_info: wgpu::AdapterInfo,
 
     /// The texture format used for the intermediate rendering passes
     intermediate_format: wgpu::TextureFormat,
@@ -413,6 +415,7 @@ impl Renderer {
             &device,
             (dims.width, dims.height),
             &ShadowMapMode::try_from(pipeline_modes.shadow).unwrap_or_default(),
+            pipeline_modes.point_shadows,
             max_texture_size,
         )
         .map_err(|err| {
@@ -620,6 +623,7 @@ impl Renderer {
             is_minimized: false,
 
             graphics_backend,
+            adapter_info: info,
 
             intermediate_format,
 
@@ -631,6 +635,8 @@ impl Renderer {
     /// Get the graphics backend being used
     pub fn graphics_backend(&self) -> &str { &self.graphics_backend }
 
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo { &self.adapter_info }
+
     /// Check the status of the intial pipeline creation
     /// Returns `None` if complete
     /// Returns `Some((total, complete))` if in progress
@@ -822,6 +828,7 @@ impl Renderer {
                     &self.device,
                     (dims.x, dims.y),
                     &mode,
+                    self.pipeline_modes.point_shadows,
                     self.max_texture_size,
                 ) {
                     Ok((new_point_depth, new_directed_depth)) => {
@@ -1424,6 +1431,17 @@ impl Renderer {
         Model::new(&self.device, mesh)
     }
 
+    /// Create a new model from the provided mesh, reusing GPU memory from
+    /// `pool` where possible.
+    pub fn create_model_pooled<V: Vertex>(
+        &mut self,
+        mesh: &Mesh<V>,
+        pool: &mut ModelPool,
+    ) -> Option<Model<V>> {
+        self.ensure_sufficient_index_length::<V>(mesh.vertices().len());
+        Model::new_pooled(&self.device, &self.queue, mesh, pool)
+    }
+
     /// Create a new dynamic model with the specified size.
     pub fn create_dynamic_model<V: Vertex>(&mut self, size: usize) -> DynamicModel<V> {
         self.ensure_sufficient_index_length::<V>(size);
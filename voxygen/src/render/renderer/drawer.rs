@@ -598,9 +598,13 @@ impl<'frame> Drawer<'frame> {
 
     /// Does nothing if the shadow pipelines are not available or shadow map
     /// rendering is disabled
+    /// Renders the terrain into the cube maps of the `light_count` nearest
+    /// point lights, the matrices of which follow the 6 reserved for the
+    /// directed lights
     pub fn draw_point_shadows<'data>(
         &mut self,
         matrices: &[shadow::PointLightMatrix; 126],
+        light_count: usize,
         chunks: impl Clone
         + Iterator<Item = (&'data Model<terrain::Vertex>, &'data terrain::BoundLocals)>,
     ) {
@@ -618,7 +622,9 @@ impl<'frame> Drawer<'frame> {
             const STRIDE: usize = std::mem::size_of::<shadow::PointLightMatrix>();
             let data = bytemuck::cast_slice(matrices);
 
-            for face in 0..6 {
+            for (point_light, face) in
+                (0..light_count).flat_map(|light| (0..6).map(move |face| (light, face)))
+            {
                 // TODO: view creation cost?
                 let view =
                     shadow_renderer
@@ -631,11 +637,11 @@ impl<'frame> Drawer<'frame> {
                             aspect: wgpu::TextureAspect::DepthOnly,
                             base_mip_level: 0,
                             mip_level_count: None,
-                            base_array_layer: face,
+                            base_array_layer: point_light as u32 * 6 + face,
                             array_layer_count: Some(1),
                         });
 
-                let label = format!("point shadow face-{} pass", face);
+                let label = format!("point shadow {} face-{} pass", point_light, face);
                 let mut render_pass =
                     encoder.scoped_render_pass(&label, device, &wgpu::RenderPassDescriptor {
                         label: Some(&label),
@@ -656,18 +662,16 @@ impl<'frame> Drawer<'frame> {
                 set_quad_index_buffer::<terrain::Vertex>(&mut render_pass, &self.borrow);
                 render_pass.set_bind_group(0, &self.globals.bind_group, &[]);
 
-                (0../*20*/1).for_each(|point_light| {
-                    render_pass.set_push_constants(
-                        wgpu::ShaderStages::VERTEX_FRAGMENT,
-                        0,
-                        &data[(6 * (point_light + 1) * STRIDE + face as usize * STRIDE)
-                            ..(6 * (point_light + 1) * STRIDE + (face + 1) as usize * STRIDE)],
-                    );
-                    chunks.clone().for_each(|(model, locals)| {
-                        render_pass.set_bind_group(1, &locals.bind_group, &[]);
-                        render_pass.set_vertex_buffer(0, model.buf().slice(..));
-                        render_pass.draw_indexed(0..model.len() as u32 / 4 * 6, 0, 0..1);
-                    });
+                render_pass.set_push_constants(
+                    wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    0,
+                    &data[(6 * (point_light + 1) * STRIDE + face as usize * STRIDE)
+                        ..(6 * (point_light + 1) * STRIDE + (face + 1) as usize * STRIDE)],
+                );
+                chunks.clone().for_each(|(model, locals)| {
+                    render_pass.set_bind_group(1, &locals.bind_group, &[]);
+                    render_pass.set_vertex_buffer(0, model.buf().slice(..));
+                    render_pass.draw_indexed(0..model.len() as u32 / 4 * 6, 0, 0..1);
                 });
             }
         }
@@ -706,7 +710,7 @@ impl<'frame> Drawer<'frame> {
                 },
             );
 
-            for face in 0..6 {
+            for layer in 0..shadow_renderer.point_depth.get_dimensions().z {
                 // TODO: view creation cost?
                 let view =
                     shadow_renderer
@@ -719,11 +723,11 @@ impl<'frame> Drawer<'frame> {
                             aspect: wgpu::TextureAspect::DepthOnly,
                             base_mip_level: 0,
                             mip_level_count: None,
-                            base_array_layer: face,
+                            base_array_layer: layer,
                             array_layer_count: Some(1),
                         });
 
-                let label = format!("clear point shadow face-{} pass", face);
+                let label = format!("clear point shadow layer-{} pass", layer);
                 let _ = encoder.scoped_render_pass(&label, device, &wgpu::RenderPassDescriptor {
                     label: Some(&label),
                     color_attachments: &[],
@@ -1,4 +1,6 @@
-use super::super::{pipelines::shadow, texture::Texture, RenderError, ShadowMapMode};
+use super::super::{
+    pipelines::shadow, texture::Texture, RenderError, ShadowMapMode, MAX_POINT_SHADOWS,
+};
 use vek::*;
 
 /// A type that holds shadow map data.  Since shadow mapping may not be
@@ -17,7 +19,7 @@ pub struct ShadowMapRenderer {
 pub enum ShadowMap {
     Enabled(ShadowMapRenderer),
     Disabled {
-        dummy_point: Texture, // Cube texture
+        dummy_point: Texture, // Cube array texture
         dummy_directed: Texture,
     },
 }
@@ -104,7 +106,7 @@ impl ShadowMap {
             Texture::new_raw(device, &tex, &view, &sampler_info)
         };
 
-        let cube_tex = make_tex(wgpu::TextureViewDimension::Cube, 6);
+        let cube_tex = make_tex(wgpu::TextureViewDimension::CubeArray, 6);
         let tex = make_tex(wgpu::TextureViewDimension::D2, 1);
 
         // Clear to 1.0
@@ -136,12 +138,14 @@ impl ShadowMap {
         (cube_tex, tex)
     }
 
-    /// Create textures and views for shadow maps.
+    /// Create textures and views for shadow maps, with one cube map for each
+    /// of the `point_shadows` lights casting shadows.
     /// Returns (point, directed)
     pub(super) fn create_shadow_views(
         device: &wgpu::Device,
         size: (u32, u32),
         mode: &ShadowMapMode,
+        point_shadows: u32,
         max_texture_size: u32,
     ) -> Result<(Texture, Texture), RenderError> {
         // (Attempt to) apply resolution factor to shadow map resolution.
@@ -198,7 +202,8 @@ impl ShadowMap {
             size: wgpu::Extent3d {
                 width: diag_two_size / 4,
                 height: diag_two_size / 4,
-                depth_or_array_layers: 6,
+                // Always allocate at least one cube map so that the view stays valid.
+                depth_or_array_layers: 6 * point_shadows.clamp(1, MAX_POINT_SHADOWS),
             },
             mip_level_count: levels,
             sample_count: 1,
@@ -211,7 +216,7 @@ impl ShadowMap {
         let point_shadow_view = wgpu::TextureViewDescriptor {
             label: None,
             format: Some(wgpu::TextureFormat::Depth24Plus),
-            dimension: Some(wgpu::TextureViewDimension::Cube),
+            dimension: Some(wgpu::TextureViewDimension::CubeArray),
             aspect: wgpu::TextureAspect::DepthOnly,
             base_mip_level: 0,
             mip_level_count: None,
@@ -118,6 +118,9 @@ pub fn run(mut global_state: GlobalState, event_loop: EventLoop, server: Option<
                 global_state
                     .profile
                     .save_to_file_warn(&global_state.config_dir);
+                if let Some(telemetry) = &mut global_state.telemetry {
+                    telemetry.report_frame_times();
+                }
             },
             _ => {},
         }
@@ -270,6 +273,9 @@ fn handle_main_events_cleared(
             .clock
             .set_target_dt(Duration::from_secs_f64(1.0 / target_fps as f64));
         global_state.clock.tick();
+        if let Some(telemetry) = &mut global_state.telemetry {
+            telemetry.record_frame(global_state.clock.dt());
+        }
         drop(guard);
         #[cfg(feature = "tracy")]
         common_base::tracy_client::frame_mark();
@@ -28,6 +28,7 @@ use common::{
         CharacterState,
     },
     figure::{Segment, TerrainSegment},
+    heraldry::Heraldry,
     slowjob::SlowJobPool,
     vol::{BaseVol, IntoVolIterator, ReadVol},
 };
@@ -157,6 +158,10 @@ pub struct FigureKey<Body> {
 pub enum ToolKey {
     Tool(String),
     Modular(modular::ModularWeaponKey),
+    /// A modular weapon that is assembled from the models of its components,
+    /// falling back to the model of the whole weapon when they're missing.
+    #[serde(skip)]
+    ModularParts(modular::ModularWeaponPartsKey),
 }
 
 /// Character data that should be visible when tools are visible (i.e. in third
@@ -165,6 +170,9 @@ pub enum ToolKey {
 pub(super) struct CharacterToolKey {
     pub active: Option<ToolKey>,
     pub second: Option<ToolKey>,
+    /// Designs painted onto shields
+    pub active_heraldry: Option<Heraldry>,
+    pub second_heraldry: Option<Heraldry>,
 }
 
 /// Character data that exists in third person only.
@@ -175,6 +183,8 @@ pub(super) struct CharacterThirdPersonKey {
     pub chest: Option<String>,
     pub belt: Option<String>,
     pub back: Option<String>,
+    /// Design painted onto the cape
+    pub back_heraldry: Option<Heraldry>,
     pub pants: Option<String>,
 }
 
@@ -203,7 +213,12 @@ pub(super) struct CharacterCacheKey {
 }
 
 impl CharacterCacheKey {
-    fn from(cs: Option<&CharacterState>, camera_mode: CameraMode, inventory: &Inventory) -> Self {
+    fn from(
+        cs: Option<&CharacterState>,
+        camera_mode: CameraMode,
+        inventory: &Inventory,
+        banner: Option<Heraldry>,
+    ) -> Self {
         let is_first_person = match camera_mode {
             CameraMode::FirstPerson => true,
             CameraMode::ThirdPerson | CameraMode::Freefly => false,
@@ -245,6 +260,10 @@ impl CharacterCacheKey {
                     chest: key_from_slot(EquipSlot::Armor(ArmorSlot::Chest)),
                     belt: key_from_slot(EquipSlot::Armor(ArmorSlot::Belt)),
                     back: key_from_slot(EquipSlot::Armor(ArmorSlot::Back)),
+                    // A design chosen for the cape itself is shown instead of the guild banner
+                    back_heraldry: inventory
+                        .equipped(EquipSlot::Armor(ArmorSlot::Back))
+                        .and_then(|item| item.heraldry().or(banner)),
                     pants: key_from_slot(EquipSlot::Armor(ArmorSlot::Legs)),
                 })
             },
@@ -252,7 +271,7 @@ impl CharacterCacheKey {
                 let tool_key_from_item = |item: &Item| match item.item_definition_id() {
                     ItemDefinitionId::Simple(id) => ToolKey::Tool(String::from(id)),
                     ItemDefinitionId::Modular { .. } => {
-                        ToolKey::Modular(modular::weapon_to_key(item))
+                        ToolKey::ModularParts(modular::weapon_to_parts_key(item))
                     },
                     ItemDefinitionId::Compound { simple_base, .. } => {
                         ToolKey::Tool(String::from(simple_base))
@@ -265,6 +284,12 @@ impl CharacterCacheKey {
                     second: inventory
                         .equipped(EquipSlot::ActiveOffhand)
                         .map(tool_key_from_item),
+                    active_heraldry: inventory
+                        .equipped(EquipSlot::ActiveMainhand)
+                        .and_then(Item::heraldry),
+                    second_heraldry: inventory
+                        .equipped(EquipSlot::ActiveOffhand)
+                        .and_then(Item::heraldry),
                 })
             } else {
                 None
@@ -328,6 +353,7 @@ where
         _atlas: &super::FigureAtlas,
         body: Skel::Body,
         inventory: Option<&Inventory>,
+        banner: Option<Heraldry>,
         // TODO: Consider updating the tick by putting it in a Cell.
         _tick: u64,
         camera_mode: CameraMode,
@@ -347,6 +373,7 @@ where
                     character_state,
                     camera_mode,
                     inventory,
+                    banner,
                 ))
             }),
         };
@@ -354,10 +381,27 @@ where
         if let Some(model) = self.models.get(&key).and_then(|d| d.0.0.get_done()) {
             Some(model)
         } else {
-            None
+            self.placeholder(&key)
         }
     }
 
+    /// While the model for `key` is still being meshed, an already meshed
+    /// model of the same body (e.g. with the gear it had before) is shown
+    /// instead, rather than having the figure disappear for a few frames.
+    fn placeholder<'b>(
+        &'b self,
+        key: &FigureKey<Skel::Body>,
+    ) -> Option<
+        &'b <<Skel::Body as BodySpec>::ModelEntryFuture<LOD_COUNT> as ModelEntryFuture<
+            LOD_COUNT,
+        >>::ModelEntry,
+    > {
+        self.models
+            .iter()
+            .filter(|(k, _)| k.body == key.body && k.item_key == key.item_key)
+            .find_map(|(_, ((model, _), _))| model.get_done())
+    }
+
     pub fn clear_models(&mut self) { self.models.clear(); }
 
     pub fn clean(&mut self, atlas: &mut super::FigureAtlas, tick: u64)
@@ -396,11 +440,15 @@ where
         atlas: &mut super::FigureAtlas,
         body: Skel::Body,
         inventory: Option<&Inventory>,
+        banner: Option<Heraldry>,
         extra: <Skel::Body as BodySpec>::Extra,
         tick: u64,
         camera_mode: CameraMode,
         character_state: Option<&CharacterState>,
         slow_jobs: &SlowJobPool,
+        // Whether meshing this model, if needed, should go ahead of the models that are
+        // already queued (e.g. for figures close to the camera)
+        prioritized: bool,
         item_key: Option<ItemKey>,
     ) -> (FigureModelEntryLod<'c>, &'c Skel::Attr)
     where
@@ -417,48 +465,38 @@ where
                     character_state,
                     camera_mode,
                     inventory,
+                    banner,
                 ))
             }),
         };
 
+        // NOTE: The entry is looked up again below, since the borrow checker doesn't
+        // let us fall back to a placeholder while holding on to the entry.
+        let lookup_key = key.clone();
         // TODO: Use raw entries to avoid significant performance overhead.
         match self.models.entry(key) {
             Entry::Occupied(o) => {
-                let ((model, skel), last_used) = o.into_mut();
+                let ((model, _), last_used) = o.into_mut();
                 *last_used = tick;
-                (
-                    match model {
-                        FigureModelEntryFuture::Pending(recv) => {
-                            if let Some(MeshWorkerResponse {
-                                atlas_texture_data,
-                                atlas_size,
-                                opaque,
-                                bounds,
-                                vertex_range,
-                            }) = Arc::get_mut(recv).take().and_then(|cell| cell.take())
-                            {
-                                let model_entry = atlas.create_figure(
-                                    renderer,
-                                    atlas_texture_data,
-                                    atlas_size,
-                                    (opaque, bounds),
-                                    vertex_range,
-                                );
-                                *model = FigureModelEntryFuture::Done(model_entry);
-                                // NOTE: Borrow checker isn't smart enough to figure this out.
-                                if let FigureModelEntryFuture::Done(model) = model {
-                                    Some(model)
-                                } else {
-                                    unreachable!();
-                                }
-                            } else {
-                                None
-                            }
-                        },
-                        FigureModelEntryFuture::Done(model) => Some(model),
-                    },
-                    skel,
-                )
+                if let FigureModelEntryFuture::Pending(recv) = model {
+                    if let Some(MeshWorkerResponse {
+                        atlas_texture_data,
+                        atlas_size,
+                        opaque,
+                        bounds,
+                        vertex_range,
+                    }) = Arc::get_mut(recv).take().and_then(|cell| cell.take())
+                    {
+                        let model_entry = atlas.create_figure(
+                            renderer,
+                            atlas_texture_data,
+                            atlas_size,
+                            (opaque, bounds),
+                            vertex_range,
+                        );
+                        *model = FigureModelEntryFuture::Done(model_entry);
+                    }
+                }
             },
             Entry::Vacant(v) => {
                 let key = v.key().clone();
@@ -466,10 +504,9 @@ where
                 let manifests = self.manifests.clone();
                 let slot_ = Arc::clone(&slot);
 
-                slow_jobs.spawn("FIGURE_MESHING", move || {
+                let job = move || {
                     // First, load all the base vertex data.
-                    let meshes =
-                        <Skel::Body as BodySpec>::bone_meshes(&key, &manifests, extra);
+                    let meshes = <Skel::Body as BodySpec>::bone_meshes(&key, &manifests, extra);
 
                     // Then, set up meshing context.
                     let mut greedy = FigureModel::make_greedy();
@@ -601,15 +638,22 @@ where
                         bounds: figure_bounds,
                         vertex_range: models,
                     }));
-                });
+                };
+                if prioritized {
+                    slow_jobs.spawn_prioritized("FIGURE_MESHING", job);
+                } else {
+                    slow_jobs.spawn("FIGURE_MESHING", job);
+                }
 
-                let skel = &(v
-                    .insert(((FigureModelEntryFuture::Pending(slot), skeleton_attr), tick))
-                    .0)
-                    .1;
-                (None, skel)
+                v.insert(((FigureModelEntryFuture::Pending(slot), skeleton_attr), tick));
             },
         }
+
+        let ((model, skel), _) = &self.models[&lookup_key];
+        (
+            model.get_done().or_else(|| self.placeholder(&lookup_key)),
+            skel,
+        )
     }
 }
 
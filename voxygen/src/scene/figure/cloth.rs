@@ -0,0 +1,71 @@
+use super::{FigureData, FigureUpdateCommonParameters};
+use crate::render::Renderer;
+use anim::vek::*;
+use common::heraldry::Heraldry;
+
+/// How strongly the air flowing past the wearer lifts the cloth.
+const DRAG: f32 = 0.015;
+/// How quickly the cloth returns to where the air pushes it.
+const STIFFNESS: f32 = 60.0;
+const DAMPING: f32 = 8.0;
+/// Larger time steps than this are split, to keep the spring stable when the
+/// frame rate drops.
+const MAX_STEP: f32 = 1.0 / 30.0;
+
+/// A damped spring that lets cloth hanging from the back, like capes and
+/// scarves, trail behind its wearer and flap in the wind.
+///
+/// Back items are a single bone, so the whole item swings about the point it
+/// hangs from.
+#[derive(Default)]
+pub struct BackCloth {
+    /// Lift behind the wearer, and swing of the hem towards the wearer's right,
+    /// in radians.
+    angle: Vec2<f32>,
+    ang_vel: Vec2<f32>,
+    /// Guild banner painted onto the cape, kept so that the model can be
+    /// looked up again when rendering.
+    pub banner: Option<Heraldry>,
+}
+
+impl BackCloth {
+    /// Advances the simulation given the velocity of the wearer relative to
+    /// the wind, returning the rotation to apply to the back bone.
+    pub fn step(
+        &mut self,
+        air_vel: Vec3<f32>,
+        ori: Quaternion<f32>,
+        gliding: bool,
+        dt: f32,
+    ) -> Quaternion<f32> {
+        // The air flowing past the wearer, in the wearer's own frame
+        let flow = ori.inverse() * -air_vel;
+        let drag = |v: f32| v * v.abs() * DRAG;
+        // The cloth is caught under the glider, so it is lifted further and held
+        // flatter against the flow
+        let (max_lift, lift_scale) = if gliding { (1.4, 1.5) } else { (1.1, 1.0) };
+        let target = Vec2::new(
+            (-drag(flow.y) * lift_scale).atan().clamp(-0.2, max_lift),
+            drag(flow.x).atan().clamp(-0.5, 0.5),
+        );
+
+        let mut remaining = dt.max(0.0);
+        while remaining > 0.0 {
+            let dt = remaining.min(MAX_STEP);
+            remaining -= dt;
+            let acc = (target - self.angle) * STIFFNESS - self.ang_vel * DAMPING;
+            self.ang_vel += acc * dt;
+            self.angle += self.ang_vel * dt;
+        }
+
+        Quaternion::rotation_x(-self.angle.x) * Quaternion::rotation_y(-self.angle.y)
+    }
+}
+
+/// Stepped by the humanoid update rather than here, since it needs to know the
+/// state of the wearer.
+impl FigureData for BackCloth {
+    fn new(_renderer: &mut Renderer) -> Self { Self::default() }
+
+    fn update(&mut self, _renderer: &mut Renderer, _parameters: &FigureUpdateCommonParameters) {}
+}
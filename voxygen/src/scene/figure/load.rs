@@ -20,6 +20,7 @@ use common::{
         quadruped_low::{self, BodyType as QLBodyType, Species as QLSpecies},
         quadruped_medium::{self, BodyType as QMBodyType, Species as QMSpecies},
         quadruped_small::{self, BodyType as QSBodyType, Species as QSSpecies},
+        serpent::{self, BodyType as SEBodyType, Species as SESpecies},
         ship::{
             self,
             figuredata::{ShipSpec, VoxelCollider},
@@ -27,8 +28,9 @@ use common::{
         theropod::{self, BodyType as TBodyType, Species as TSpecies},
     },
     figure::{Cell, DynaUnionizer, MatCell, MatSegment, Material, Segment},
+    heraldry::Heraldry,
     terrain::Block,
-    vol::{IntoFullPosIterator, ReadVol},
+    vol::{IntoFullPosIterator, ReadVol, SizedVol, WriteVol},
     volumes::dyna::Dyna,
 };
 use hashbrown::HashMap;
@@ -106,6 +108,33 @@ fn recolor_grey(rgb: Rgb<u8>, color: Rgb<u8>) -> Rgb<u8> {
     }
 }
 
+/// Paints a heraldic design, if any, across the x and z axes of a segment,
+/// keeping the shading of the original voxels.
+fn paint_heraldry(mut segment: Segment, heraldry: Option<Heraldry>) -> Segment {
+    let Some(heraldry) = heraldry else {
+        return segment;
+    };
+    let size = segment.size().map(|e| e.max(1) as f32);
+    for pos in segment.full_pos_iter() {
+        let Some(cell) = segment.get(pos).ok().copied() else {
+            continue;
+        };
+        if let Some(rgb) = cell.get_color() {
+            let uv = Vec2::new(
+                (pos.x as f32 + 0.5) / size.x,
+                1.0 - (pos.z as f32 + 0.5) / size.z,
+            );
+            let grey = ((u16::from(rgb.r) + u16::from(rgb.g) + u16::from(rgb.b)) / 3) as u8;
+            let color = recolor_grey(Rgb::broadcast(grey), heraldry.color_at(uv));
+            let _ = segment.set(
+                pos,
+                Cell::new(color, cell.is_glowy(), cell.is_shiny(), cell.is_hollow()),
+            );
+        }
+    }
+    segment
+}
+
 /// A set of reloadable specifications for a Body.
 pub trait BodySpec: Sized {
     type Spec;
@@ -205,12 +234,21 @@ struct ArmorVoxSpec {
 }
 
 #[derive(Deserialize)]
-#[allow(dead_code)]
 enum ModularComponentSpec {
-    /// item id, offset from origin to mount point
-    Damage((String, [i32; 3])),
-    /// item id, offset from origin to hand, offset from origin to mount point
-    Held((String, [f32; 3], [i32; 3])),
+    /// Damage components have a model per material, found at
+    /// `<vox>.<material>`, and are mounted by the centre of their base.
+    Damage { vox: String },
+    /// Held components are a single model with the position of the hand and
+    /// the point the damage component mounts to, both relative to its origin.
+    Held {
+        vox: String,
+        hand: [f32; 3],
+        mount: [i32; 3],
+        /// Whether the model lies along x with the mount end first, as the
+        /// item icons do, and needs to be stood upright before use.
+        #[serde(default)]
+        lying: bool,
+    },
 }
 
 // For use by armor with a left and right component
@@ -451,6 +489,7 @@ make_vox_spec!(
         armor_pants: HumArmorPantsSpec = "voxygen.voxel.humanoid_armor_pants_manifest",
         armor_foot: HumArmorFootSpec = "voxygen.voxel.humanoid_armor_foot_manifest",
         main_weapon: HumMainWeaponSpec = "voxygen.voxel.biped_weapon_manifest",
+        modular_component: HumModularComponentSpec = "voxygen.voxel.biped_weapon_component_manifest",
         armor_lantern: HumArmorLanternSpec = "voxygen.voxel.humanoid_lantern_manifest",
         armor_glider: HumArmorGliderSpec = "voxygen.voxel.humanoid_glider_manifest",
         armor_head: HumArmorHeadSpec = "voxygen.voxel.humanoid_armor_head_manifest",
@@ -509,6 +548,7 @@ make_vox_spec!(
                     body,
                     color,
                     loadout.back.as_deref(),
+                    loadout.back_heraldry,
                 )
             }),
             third_person.map(|loadout| {
@@ -557,17 +597,21 @@ make_vox_spec!(
                 color,
                 glider,
             )),
-            tool.and_then(|tool| tool.active.as_ref()).map(|tool| {
-                spec.main_weapon.read().0.mesh_main_weapon(
+            tool.and_then(|tool| Some((tool.active.as_ref()?, tool.active_heraldry))).map(|(tool, heraldry)| {
+                let (segment, offset) = spec.main_weapon.read().0.mesh_main_weapon(
+                    &spec.modular_component.read().0,
                     tool,
                     false,
-                )
+                );
+                (paint_heraldry(segment, heraldry), offset)
             }),
-            tool.and_then(|tool| tool.second.as_ref()).map(|tool| {
-                spec.main_weapon.read().0.mesh_main_weapon(
+            tool.and_then(|tool| Some((tool.second.as_ref()?, tool.second_heraldry))).map(|(tool, heraldry)| {
+                let (segment, offset) = spec.main_weapon.read().0.mesh_main_weapon(
+                    &spec.modular_component.read().0,
                     tool,
                     true,
-                )
+                );
+                (paint_heraldry(segment, heraldry), offset)
             }),
             Some(spec.armor_lantern.read().0.mesh_lantern(
                 body,
@@ -800,7 +844,13 @@ impl HumArmorBeltSpec {
 }
 // Cape
 impl HumArmorBackSpec {
-    fn mesh_back(&self, body: &Body, color_spec: &HumColorSpec, back: Option<&str>) -> BoneMeshes {
+    fn mesh_back(
+        &self,
+        body: &Body,
+        color_spec: &HumColorSpec,
+        back: Option<&str>,
+        heraldry: Option<Heraldry>,
+    ) -> BoneMeshes {
         let spec = if let Some(back) = back {
             match self.0.map.get(back) {
                 Some(spec) => spec,
@@ -823,8 +873,10 @@ impl HumArmorBackSpec {
             let back_color = Vec3::from(color);
             back_segment = back_segment.map_rgb(|rgb| recolor_grey(rgb, Rgb::from(back_color)));
         }
-
-        (back_segment, Vec3::from(spec.vox_spec.1))
+        (
+            paint_heraldry(back_segment, heraldry),
+            Vec3::from(spec.vox_spec.1),
+        )
     }
 }
 // Legs
@@ -934,7 +986,24 @@ impl HumArmorFootSpec {
 }
 
 impl HumMainWeaponSpec {
-    fn mesh_main_weapon(&self, tool: &ToolKey, flipped: bool) -> BoneMeshes {
+    fn mesh_main_weapon(
+        &self,
+        components: &HumModularComponentSpec,
+        tool: &ToolKey,
+        flipped: bool,
+    ) -> BoneMeshes {
+        if let ToolKey::ModularParts((primary, material, secondary, hands)) = tool {
+            return components
+                .mesh_modular_weapon(primary, material, secondary, flipped)
+                .unwrap_or_else(|| {
+                    self.mesh_main_weapon(
+                        components,
+                        &ToolKey::Modular((primary.clone(), material.clone(), *hands)),
+                        flipped,
+                    )
+                });
+        }
+
         let not_found = |tool: &ToolKey| {
             error!(?tool, "No tool/weapon specification exists");
             load_mesh("not_found", Vec3::new(-1.5, -1.5, -7.0))
@@ -957,6 +1026,78 @@ impl HumMainWeaponSpec {
     }
 }
 
+impl HumModularComponentSpec {
+    /// Assembles a modular weapon out of the models of its components, or
+    /// returns `None` if either component has no specification.
+    fn mesh_modular_weapon(
+        &self,
+        primary: &str,
+        material: &str,
+        secondary: &str,
+        flipped: bool,
+    ) -> Option<BoneMeshes> {
+        let damage_vox = match self.0.get(primary) {
+            Some(ModularComponentSpec::Damage { vox }) => vox,
+            _ => return None,
+        };
+        let (held_vox, hand, mount, lying) = match self.0.get(secondary) {
+            Some(ModularComponentSpec::Held {
+                vox,
+                hand,
+                mount,
+                lying,
+            }) => (vox, hand, mount, *lying),
+            _ => return None,
+        };
+
+        let material = material.rsplit('.').next().unwrap_or(material);
+        let damage =
+            graceful_load_segment_flipped(&format!("{}.{}", damage_vox, material), flipped, 0);
+        let held = if lying {
+            stand_upright(graceful_load_segment(held_vox, 0), flipped)
+        } else {
+            graceful_load_segment_flipped(held_vox, flipped, 0)
+        };
+
+        let mirror = |x: i32, width: u32| if flipped { width as i32 - 1 - x } else { x };
+        let held_mount = Vec3::new(mirror(mount[0], held.sz.x), mount[1], mount[2]);
+        let damage_mount = Vec3::new(
+            mirror(damage.sz.x as i32 / 2, damage.sz.x),
+            damage.sz.y as i32 / 2,
+            0,
+        );
+        let mut hand = Vec3::from(*hand);
+        if flipped {
+            hand.x = held.sz.x as f32 - hand.x;
+        }
+
+        let (weapon, origin_offset) = DynaUnionizer::new()
+            .add(held, Vec3::zero())
+            .add(damage, held_mount - damage_mount)
+            .unify();
+
+        Some((weapon, -hand - origin_offset.map(|e| e as f32)))
+    }
+}
+
+/// Stands a model lying along x upright, such that its first column ends up on
+/// top.
+fn stand_upright(segment: Segment, flipped: bool) -> Segment {
+    let sz = segment.sz;
+    let mut upright = Segment::filled(Vec3::new(sz.z, sz.y, sz.x), Cell::Empty, ());
+    for pos in segment.full_pos_iter() {
+        if let Ok(cell) = segment.get(pos) {
+            let x = if flipped {
+                sz.z as i32 - 1 - pos.z
+            } else {
+                pos.z
+            };
+            let _ = upright.set(Vec3::new(x, pos.y, sz.x as i32 - 1 - pos.x), *cell);
+        }
+    }
+    upright
+}
+
 // Lantern
 impl HumArmorLanternSpec {
     fn mesh_lantern(
@@ -3069,6 +3210,88 @@ impl CrustaceanLateralSpec {
     }
 }
 
+//////
+#[derive(Deserialize)]
+struct SerpentCentralSpec(HashMap<(SESpecies, SEBodyType), SerpentCentralVoxSpec>);
+impl_concatenate_for_wrapper!(SerpentCentralSpec);
+
+#[derive(Deserialize)]
+struct SerpentCentralVoxSpec {
+    head: SerpentCentralSubSpec,
+    jaw: SerpentCentralSubSpec,
+    neck: SerpentCentralSubSpec,
+    chest: SerpentCentralSubSpec,
+    spine_0: SerpentCentralSubSpec,
+    spine_1: SerpentCentralSubSpec,
+    tail_f: SerpentCentralSubSpec,
+    tail_b: SerpentCentralSubSpec,
+}
+#[derive(Deserialize)]
+struct SerpentCentralSubSpec {
+    offset: [f32; 3], // Should be relative to initial origin
+    central: VoxSimple,
+    #[serde(default)]
+    model_index: u32,
+}
+make_vox_spec!(
+    serpent::Body,
+    struct SerpentSpec {
+        central: SerpentCentralSpec = "voxygen.voxel.serpent_central_manifest",
+    },
+    |FigureKey { body, extra, .. }, spec| {
+        let third_person = extra.as_ref().and_then(|loadout| loadout.third_person.as_ref());
+        let central = spec.central.read();
+        let mesh = |name, bone: fn(&SerpentCentralVoxSpec) -> &SerpentCentralSubSpec| {
+            central.0.mesh_bone(body.species, body.body_type, name, bone)
+        };
+
+        [
+            third_person.map(|_| mesh("head", |spec| &spec.head)),
+            third_person.map(|_| mesh("jaw", |spec| &spec.jaw)),
+            Some(mesh("neck", |spec| &spec.neck)),
+            Some(mesh("chest", |spec| &spec.chest)),
+            Some(mesh("spine_0", |spec| &spec.spine_0)),
+            Some(mesh("spine_1", |spec| &spec.spine_1)),
+            Some(mesh("tail_f", |spec| &spec.tail_f)),
+            Some(mesh("tail_b", |spec| &spec.tail_b)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]
+    },
+);
+
+impl SerpentCentralSpec {
+    /// Every bone of a serpent is a plain central segment, so they all share
+    /// one lookup rather than a mesh function each.
+    fn mesh_bone(
+        &self,
+        species: SESpecies,
+        body_type: SEBodyType,
+        name: &str,
+        bone: fn(&SerpentCentralVoxSpec) -> &SerpentCentralSubSpec,
+    ) -> BoneMeshes {
+        let spec = match self.0.get(&(species, body_type)) {
+            Some(spec) => bone(spec),
+            None => {
+                error!(
+                    "No {} specification exists for the combination of {:?} and {:?}",
+                    name, species, body_type
+                );
+                return load_mesh("not_found", Vec3::new(-5.0, -5.0, -2.5));
+            },
+        };
+        let central = graceful_load_segment(&spec.central.0, spec.model_index);
+
+        (central, Vec3::from(spec.offset))
+    }
+}
+
 #[derive(Deserialize)]
 struct FishMediumCentralSpec(HashMap<(FMSpecies, FMBodyType), SidedFMCentralVoxSpec>);
 impl_concatenate_for_wrapper!(FishMediumCentralSpec);
@@ -1,4 +1,5 @@
 mod cache;
+mod cloth;
 pub mod load;
 mod volume;
 
@@ -6,6 +7,8 @@ pub use cache::FigureModelCache;
 pub use load::load_mesh; // TODO: Don't make this public.
 pub use volume::VolumeKey;
 
+use cloth::BackCloth;
+
 use crate::{
     ecs::comp::Interpolated,
     render::{
@@ -22,7 +25,8 @@ use crate::{
         camera::{Camera, CameraMode, Dependents},
         math,
         terrain::Terrain,
-        SceneData, TrailMgr, RAIN_THRESHOLD,
+        trail::{AbilityVfxManifest, TrailStyle},
+        SceneData, TrailMgr, INTERACT_REVEAL_RANGE, RAIN_THRESHOLD,
     },
 };
 use anim::{
@@ -32,12 +36,13 @@ use anim::{
     fish_small::FishSmallSkeleton, golem::GolemSkeleton, item_drop::ItemDropSkeleton,
     object::ObjectSkeleton, quadruped_low::QuadrupedLowSkeleton,
     quadruped_medium::QuadrupedMediumSkeleton, quadruped_small::QuadrupedSmallSkeleton,
-    ship::ShipSkeleton, theropod::TheropodSkeleton, Animation, Skeleton,
+    serpent::SerpentSkeleton, ship::ShipSkeleton, theropod::TheropodSkeleton, Animation, Skeleton,
 };
 use common::{
     comp::{
         inventory::slot::EquipSlot,
         item::{armor::ArmorKind, Hands, ItemKind, ToolKind},
+        pet::Perch,
         ship::{self, figuredata::VOXEL_COLLIDER_MANIFEST},
         slot::ArmorSlot,
         Body, CharacterActivity, CharacterState, Collider, Controller, Health, Inventory, ItemKey,
@@ -47,9 +52,9 @@ use common::{
     link::Is,
     mounting::{Rider, VolumeRider},
     resources::{DeltaTime, Time},
-    states::{equipping, idle, utils::StageSection, wielding},
+    states::{equipping, idle, pet, revive, utils::StageSection, wielding},
     terrain::{Block, SpriteKind, TerrainChunk, TerrainGrid},
-    uid::IdMaps,
+    uid::{IdMaps, Uid},
     util::Dir,
     vol::{ReadVol, RectRasterableVol},
 };
@@ -73,6 +78,10 @@ use super::terrain::{BlocksOfInterest, SPRITE_LOD_LEVELS};
 const DAMAGE_FADE_COEFFICIENT: f64 = 15.0;
 const MOVING_THRESHOLD: f32 = 0.2;
 const MOVING_THRESHOLD_SQR: f32 = MOVING_THRESHOLD * MOVING_THRESHOLD;
+/// Models of figures within this distance from the camera focus are meshed
+/// before the others, so that the figures right in front of the player don't
+/// wait behind a crowd of distant ones.
+const PRIORITIZED_MESHING_DIST: f32 = 48.0;
 
 /// camera data, figure LOD render distance.
 pub type CameraData<'a> = (&'a Camera, f32);
@@ -195,7 +204,7 @@ impl<'a, const N: usize> ModelEntryRef<'a, N> {
 }
 
 struct FigureMgrStates {
-    character_states: HashMap<EcsEntity, FigureState<CharacterSkeleton>>,
+    character_states: HashMap<EcsEntity, FigureState<CharacterSkeleton, BackCloth>>,
     quadruped_small_states: HashMap<EcsEntity, FigureState<QuadrupedSmallSkeleton>>,
     quadruped_medium_states: HashMap<EcsEntity, FigureState<QuadrupedMediumSkeleton>>,
     quadruped_low_states: HashMap<EcsEntity, FigureState<QuadrupedLowSkeleton>>,
@@ -214,6 +223,7 @@ struct FigureMgrStates {
     volume_states: HashMap<EcsEntity, FigureState<VolumeKey, BoundTerrainLocals>>,
     arthropod_states: HashMap<EcsEntity, FigureState<ArthropodSkeleton>>,
     crustacean_states: HashMap<EcsEntity, FigureState<CrustaceanSkeleton>>,
+    serpent_states: HashMap<EcsEntity, FigureState<SerpentSkeleton>>,
 }
 
 impl FigureMgrStates {
@@ -238,6 +248,7 @@ impl FigureMgrStates {
             volume_states: HashMap::new(),
             arthropod_states: HashMap::new(),
             crustacean_states: HashMap::new(),
+            serpent_states: HashMap::new(),
         }
     }
 
@@ -313,6 +324,7 @@ impl FigureMgrStates {
                 .crustacean_states
                 .get_mut(entity)
                 .map(DerefMut::deref_mut),
+            Body::Serpent(_) => self.serpent_states.get_mut(entity).map(DerefMut::deref_mut),
         }
     }
 
@@ -338,7 +350,7 @@ impl FigureMgrStates {
             Body::Object(_) => self.object_states.remove(entity).map(|e| e.meta),
             Body::ItemDrop(_) => self.item_drop_states.remove(entity).map(|e| e.meta),
             Body::Ship(ship) => {
-                if matches!(ship, ship::Body::Volume) {
+                if matches!(ship, ship::Body::Volume | ship::Body::HotAirBalloon) {
                     self.volume_states.remove(entity).map(|e| e.meta)
                 } else if ship.manifest_entry().is_some() {
                     self.ship_states.remove(entity).map(|e| e.meta)
@@ -348,6 +360,7 @@ impl FigureMgrStates {
             },
             Body::Arthropod(_) => self.arthropod_states.remove(entity).map(|e| e.meta),
             Body::Crustacean(_) => self.crustacean_states.remove(entity).map(|e| e.meta),
+            Body::Serpent(_) => self.serpent_states.remove(entity).map(|e| e.meta),
         }
     }
 
@@ -372,6 +385,7 @@ impl FigureMgrStates {
         self.volume_states.retain(|k, v| f(k, &mut *v));
         self.arthropod_states.retain(|k, v| f(k, &mut *v));
         self.crustacean_states.retain(|k, v| f(k, &mut *v));
+        self.serpent_states.retain(|k, v| f(k, &mut *v));
     }
 
     fn count(&self) -> usize {
@@ -395,6 +409,7 @@ impl FigureMgrStates {
             + self.volume_states.len()
             + self.arthropod_states.len()
             + self.crustacean_states.len()
+            + self.serpent_states.len()
     }
 
     fn count_visible(&self) -> usize {
@@ -482,6 +497,11 @@ impl FigureMgrStates {
                 .iter()
                 .filter(|(_, c)| c.visible())
                 .count()
+            + self
+                .serpent_states
+                .iter()
+                .filter(|(_, c)| c.visible())
+                .count()
             + self.ship_states.iter().filter(|(_, c)| c.visible()).count()
             + self
                 .volume_states
@@ -501,7 +521,7 @@ impl FigureMgrStates {
     {
         match body {
             Body::Ship(body) => {
-                if matches!(body, ship::Body::Volume) {
+                if matches!(body, ship::Body::Volume | ship::Body::HotAirBalloon) {
                     self.volume_states.get(entity).map(|state| &state.extra)
                 } else if body.manifest_entry().is_some() {
                     self.ship_states.get(entity).map(|state| &state.extra)
@@ -535,6 +555,7 @@ pub struct FigureMgr {
     volume_model_cache: FigureModelCache<VolumeKey>,
     arthropod_model_cache: FigureModelCache<ArthropodSkeleton>,
     crustacean_model_cache: FigureModelCache<CrustaceanSkeleton>,
+    serpent_model_cache: FigureModelCache<SerpentSkeleton>,
     states: FigureMgrStates,
 }
 
@@ -561,6 +582,7 @@ impl FigureMgr {
             volume_model_cache: FigureModelCache::new(),
             arthropod_model_cache: FigureModelCache::new(),
             crustacean_model_cache: FigureModelCache::new(),
+            serpent_model_cache: FigureModelCache::new(),
             states: FigureMgrStates::default(),
         }
     }
@@ -587,6 +609,7 @@ impl FigureMgr {
             || self.volume_model_cache.watcher_reloaded()
             || self.arthropod_model_cache.watcher_reloaded()
             || self.crustacean_model_cache.watcher_reloaded()
+            || self.serpent_model_cache.watcher_reloaded()
     }
 
     pub fn clean(&mut self, tick: u64) {
@@ -614,6 +637,7 @@ impl FigureMgr {
             self.volume_model_cache.clear_models();
             self.arthropod_model_cache.clear_models();
             self.crustacean_model_cache.clear_models();
+            self.serpent_model_cache.clear_models();
         }
 
         self.model_cache.clean(&mut self.atlas, tick);
@@ -637,6 +661,7 @@ impl FigureMgr {
         self.volume_model_cache.clean(&mut self.atlas, tick);
         self.arthropod_model_cache.clean(&mut self.atlas, tick);
         self.crustacean_model_cache.clean(&mut self.atlas, tick);
+        self.serpent_model_cache.clean(&mut self.atlas, tick);
     }
 
     pub fn update_lighting(&mut self, scene_data: &SceneData) {
@@ -851,6 +876,11 @@ impl FigureMgr {
 
         let terrain_grid = ecs.read_resource::<TerrainGrid>();
 
+        let wind = scene_data.client.weather_at_player().wind_vel();
+
+        let ability_vfx = AbilityVfxManifest::load().read();
+        let uids = ecs.read_storage::<Uid>();
+
         for (
             i,
             (
@@ -915,6 +945,16 @@ impl FigureMgr {
             };
             let viewpoint_character_state = if is_viewpoint { character_state } else { None };
 
+            // A dead player watching their group is moved along with the camera, so their
+            // body is hidden until they respawn
+            if !is_viewpoint
+                && entity == scene_data.client.entity()
+                && health.map_or(false, |health| health.is_dead)
+            {
+                self.states.remove(body, &entity);
+                continue;
+            }
+
             let (pos, ori) = interpolated
                 .map(|i| {
                     (
@@ -1023,15 +1063,25 @@ impl FigureMgr {
                         })
                 })
                 .unwrap_or_else(|| Rgba::broadcast(1.0))
-            // Highlight targeted collectible entities
-            * if item.is_some() && scene_data.target_entity.map_or(false, |e| e == entity) {
-                Rgba::new(1.5, 1.5, 1.5, 1.0)
-            } else {
-                Rgba::one()
+            // Highlight targeted collectible entities, and nearby ones while they are revealed
+            * {
+                let highlight = if item.is_none() {
+                    0.0
+                } else if scene_data.target_entity.map_or(false, |e| e == entity) {
+                    scene_data.interact_highlight
+                } else if pos.0.distance_squared(focus_pos) < INTERACT_REVEAL_RANGE.powi(2) {
+                    0.5 * scene_data.interact_reveal
+                } else {
+                    0.0
+                };
+                Rgba::new(1.0 + highlight, 1.0 + highlight, 1.0 + highlight, 1.0)
             };
 
             let scale = scale.map(|s| s.0).unwrap_or(1.0);
 
+            let prioritize_meshing = is_viewpoint
+                || pos.0.distance_squared(focus_pos) < PRIORITIZED_MESHING_DIST.powi(2);
+
             let mut state_animation_rate = 1.0;
 
             let tool_info = |equip_slot| {
@@ -1072,9 +1122,14 @@ impl FigureMgr {
                 if let Some(is_rider) = is_rider {
                     let mount = is_rider.mount;
                     let mount = id_maps.uid_entity(mount)?;
-                    let body = *bodies.get(mount)?;
-                    let meta = self.states.get_mut(&body, &mount)?;
-                    Some((meta.mount_transform, meta.mount_world_pos))
+                    let mount_body = *bodies.get(mount)?;
+                    let meta = self.states.get_mut(&mount_body, &mount)?;
+                    let transform = match (Perch::of(body), meta.mount_perches) {
+                        (Some(Perch::Head), Some(perches)) => perches.head,
+                        (Some(Perch::Back), Some(perches)) => perches.back,
+                        _ => meta.mount_transform,
+                    };
+                    Some((transform, meta.mount_world_pos))
                 } else {
                     None
                 }
@@ -1093,6 +1148,9 @@ impl FigureMgr {
                 mount_transform_pos,
                 body: Some(body),
                 tools: (active_tool_kind, second_tool_kind),
+                trail: character.map_or_else(TrailStyle::default, |cs| {
+                    ability_vfx.get(cs, inventory).trail
+                }),
                 col,
                 dt,
                 _lpindex: lpindex,
@@ -1105,16 +1163,23 @@ impl FigureMgr {
 
             match body {
                 Body::Humanoid(body) => {
+                    // Members of a guild wear its banner on their capes
+                    let banner = uids
+                        .get(entity)
+                        .and_then(|uid| scene_data.client.player_list().get(uid))
+                        .and_then(|player| player.guild_banner);
                     let (model, skeleton_attr) = self.model_cache.get_or_create_model(
                         renderer,
                         &mut self.atlas,
                         body,
                         inventory,
+                        banner,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -1145,6 +1210,16 @@ impl FigureMgr {
                         })
                         .unwrap_or(0.0);
 
+                    let wears_cloth = scene_data.cloth_physics_enabled
+                        && inventory
+                            .and_then(|i| i.equipped(EquipSlot::Armor(ArmorSlot::Back)))
+                            .map_or(false, |i| {
+                                matches!(
+                                    i.kind().as_ref(),
+                                    ItemKind::Armor(armor) if matches!(armor.kind, ArmorKind::Back)
+                                )
+                            });
+
                     let state = self
                         .states
                         .character_states
@@ -1704,7 +1779,7 @@ impl FigureMgr {
                                 skeleton_attr,
                             )
                         },
-                        CharacterState::Sit { .. } => {
+                        CharacterState::Sit { .. } | CharacterState::Downed(_) => {
                             anim::character::SitAnimation::update_skeleton(
                                 &target_base,
                                 (active_tool_kind, second_tool_kind, time),
@@ -1748,8 +1823,23 @@ impl FigureMgr {
                                 skeleton_attr,
                             )
                         },
-                        CharacterState::Pet(s) => {
-                            let target_entity = id_maps.uid_entity(s.static_data.target_uid);
+                        CharacterState::Emote(s) => {
+                            anim::character::EmoteAnimation::update_skeleton(
+                                &target_base,
+                                s.static_data.kind,
+                                s.timer.as_secs_f32(),
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
+                        CharacterState::Pet(pet::Data {
+                            static_data: pet::StaticData { target_uid },
+                        })
+                        | CharacterState::Revive(revive::Data {
+                            static_data: revive::StaticData { target_uid, .. },
+                            ..
+                        }) => {
+                            let target_entity = id_maps.uid_entity(*target_uid);
                             let target_pos = target_entity.and_then(|target_entity| {
                                 ecs.read_component::<Pos>()
                                     .get(target_entity)
@@ -1826,6 +1916,16 @@ impl FigureMgr {
                     };
 
                     state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.extra.banner = banner;
+                    if wears_cloth {
+                        let sway = state.extra.step(
+                            anim::vek::Vec3::<f32>::from(vel.0 - Vec3::from(wind)),
+                            ori,
+                            matches!(character, CharacterState::Glide(_)),
+                            dt,
+                        );
+                        state.skeleton.sway_back(sway);
+                    }
                     state.update(
                         renderer,
                         trail_mgr,
@@ -1843,11 +1943,13 @@ impl FigureMgr {
                             &mut self.atlas,
                             body,
                             inventory,
+                            None,
                             (),
                             tick,
                             viewpoint_camera_mode,
                             viewpoint_character_state,
                             &slow_jobs,
+                            prioritize_meshing,
                             None,
                         );
 
@@ -1875,9 +1977,18 @@ impl FigureMgr {
                         physics.on_ground.is_some(),
                         rel_vel.magnitude_squared() > MOVING_THRESHOLD_SQR, // Moving
                         physics.in_liquid().is_some(),                      // In water
+                        is_rider.is_some(),
                     ) {
+                        // Perched on someone
+                        (_, _, _, true) => anim::quadruped_small::PerchAnimation::update_skeleton(
+                            &QuadrupedSmallSkeleton::default(),
+                            time,
+                            state.state_time,
+                            &mut state_animation_rate,
+                            skeleton_attr,
+                        ),
                         // Standing
-                        (true, false, false) => {
+                        (true, false, false, _) => {
                             anim::quadruped_small::IdleAnimation::update_skeleton(
                                 &QuadrupedSmallSkeleton::default(),
                                 time,
@@ -1887,7 +1998,7 @@ impl FigureMgr {
                             )
                         },
                         // Running
-                        (true, true, false) => {
+                        (true, true, false, _) => {
                             anim::quadruped_small::RunAnimation::update_skeleton(
                                 &QuadrupedSmallSkeleton::default(),
                                 (
@@ -1905,7 +2016,7 @@ impl FigureMgr {
                             )
                         },
                         // Swimming
-                        (_, _, true) => anim::quadruped_small::RunAnimation::update_skeleton(
+                        (_, _, true, _) => anim::quadruped_small::RunAnimation::update_skeleton(
                             &QuadrupedSmallSkeleton::default(),
                             (
                                 rel_vel.magnitude(),
@@ -1921,21 +2032,23 @@ impl FigureMgr {
                             skeleton_attr,
                         ),
                         // In air
-                        (false, _, false) => anim::quadruped_small::RunAnimation::update_skeleton(
-                            &QuadrupedSmallSkeleton::default(),
-                            (
-                                rel_vel.magnitude(),
-                                // TODO: Update to use the quaternion.
-                                ori * anim::vek::Vec3::<f32>::unit_y(),
-                                state.last_ori * anim::vek::Vec3::<f32>::unit_y(),
-                                time,
-                                rel_avg_vel,
-                                state.acc_vel,
-                            ),
-                            state.state_time,
-                            &mut state_animation_rate,
-                            skeleton_attr,
-                        ),
+                        (false, _, false, _) => {
+                            anim::quadruped_small::RunAnimation::update_skeleton(
+                                &QuadrupedSmallSkeleton::default(),
+                                (
+                                    rel_vel.magnitude(),
+                                    // TODO: Update to use the quaternion.
+                                    ori * anim::vek::Vec3::<f32>::unit_y(),
+                                    state.last_ori * anim::vek::Vec3::<f32>::unit_y(),
+                                    time,
+                                    rel_avg_vel,
+                                    state.acc_vel,
+                                ),
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
                     };
                     let target_bones = match &character {
                         CharacterState::BasicMelee(s) => {
@@ -2085,11 +2198,13 @@ impl FigureMgr {
                             &mut self.atlas,
                             body,
                             inventory,
+                            None,
                             (),
                             tick,
                             viewpoint_camera_mode,
                             viewpoint_character_state,
                             &slow_jobs,
+                            prioritize_meshing,
                             None,
                         );
 
@@ -2396,11 +2511,13 @@ impl FigureMgr {
                             &mut self.atlas,
                             body,
                             inventory,
+                            None,
                             (),
                             tick,
                             viewpoint_camera_mode,
                             viewpoint_character_state,
                             &slow_jobs,
+                            prioritize_meshing,
                             None,
                         );
 
@@ -2801,11 +2918,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -2835,6 +2954,16 @@ impl FigureMgr {
                         physics.in_liquid().is_some(),                      // In water
                         is_rider.is_some() || is_volume_rider.is_some(),
                     ) {
+                        // Perched on someone
+                        (_, _, _, true) if is_rider.is_some() => {
+                            anim::bird_medium::PerchAnimation::update_skeleton(
+                                &BirdMediumSkeleton::default(),
+                                time,
+                                state.state_time,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
                         // Standing
                         (true, false, false, _) => {
                             anim::bird_medium::IdleAnimation::update_skeleton(
@@ -3124,11 +3253,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -3207,11 +3338,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -3816,11 +3949,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -3903,11 +4038,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -4066,11 +4203,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -4374,11 +4513,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -4544,17 +4685,121 @@ impl FigureMgr {
                         body,
                     );
                 },
+                Body::Serpent(body) => {
+                    let (model, skeleton_attr) = self.serpent_model_cache.get_or_create_model(
+                        renderer,
+                        &mut self.atlas,
+                        body,
+                        inventory,
+                        None,
+                        (),
+                        tick,
+                        viewpoint_camera_mode,
+                        viewpoint_character_state,
+                        &slow_jobs,
+                        prioritize_meshing,
+                        None,
+                    );
+
+                    let state = self.states.serpent_states.entry(entity).or_insert_with(|| {
+                        FigureState::new(renderer, SerpentSkeleton::default(), body)
+                    });
+
+                    // Average velocity relative to the current ground
+                    let rel_avg_vel = state.avg_vel - physics.ground_vel;
+
+                    let (character, last_character) = match (character, last_character) {
+                        (Some(c), Some(l)) => (c, l),
+                        _ => continue,
+                    };
+
+                    if !character.same_variant(&last_character.0) {
+                        state.state_time = 0.0;
+                    }
+
+                    let target_base = match (
+                        physics.on_ground.is_some() || physics.in_liquid().is_some(),
+                        rel_vel.magnitude_squared() > MOVING_THRESHOLD_SQR, // Moving
+                    ) {
+                        // Coiled up
+                        (true, false) => anim::serpent::IdleAnimation::update_skeleton(
+                            &SerpentSkeleton::default(),
+                            time,
+                            state.state_time,
+                            &mut state_animation_rate,
+                            skeleton_attr,
+                        ),
+                        // Slithering, on land and in water alike
+                        _ => anim::serpent::RunAnimation::update_skeleton(
+                            &SerpentSkeleton::default(),
+                            (
+                                rel_vel,
+                                // TODO: Update to use the quaternion.
+                                ori * anim::vek::Vec3::<f32>::unit_y(),
+                                state.last_ori * anim::vek::Vec3::<f32>::unit_y(),
+                                time,
+                                rel_avg_vel,
+                                state.acc_vel,
+                            ),
+                            state.state_time,
+                            &mut state_animation_rate,
+                            skeleton_attr,
+                        ),
+                    };
+                    let target_bones = match &character {
+                        CharacterState::ComboMelee2(s) => {
+                            let timer = s.timer.as_secs_f32();
+                            let current_strike = s.completed_strikes % s.static_data.strikes.len();
+                            let strike_data = s.static_data.strikes[current_strike];
+                            let progress = match s.stage_section {
+                                StageSection::Buildup => {
+                                    timer / strike_data.buildup_duration.as_secs_f32()
+                                },
+                                StageSection::Action => {
+                                    timer / strike_data.swing_duration.as_secs_f32()
+                                },
+                                StageSection::Recover => {
+                                    timer / strike_data.recover_duration.as_secs_f32()
+                                },
+                                _ => 0.0,
+                            };
+
+                            anim::serpent::AlphaAnimation::update_skeleton(
+                                &target_base,
+                                Some(s.stage_section),
+                                progress,
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
+                        // TODO!
+                        _ => target_base,
+                    };
+
+                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.update(
+                        renderer,
+                        trail_mgr,
+                        &mut update_buf,
+                        &common_params,
+                        state_animation_rate,
+                        model,
+                        body,
+                    );
+                },
                 Body::BirdLarge(body) => {
                     let (model, skeleton_attr) = self.bird_large_model_cache.get_or_create_model(
                         renderer,
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -4946,11 +5191,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -5029,11 +5276,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -5666,11 +5915,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -5896,11 +6147,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         None,
                     );
 
@@ -6018,11 +6271,13 @@ impl FigureMgr {
                         &mut self.atlas,
                         body,
                         inventory,
+                        None,
                         (),
                         tick,
                         viewpoint_camera_mode,
                         viewpoint_character_state,
                         &slow_jobs,
+                        prioritize_meshing,
                         item_key,
                     );
 
@@ -6542,6 +6797,7 @@ impl FigureMgr {
             volume_model_cache,
             arthropod_model_cache,
             crustacean_model_cache,
+            serpent_model_cache,
             states:
                 FigureMgrStates {
                     character_states,
@@ -6563,6 +6819,7 @@ impl FigureMgr {
                     volume_states,
                     arthropod_states,
                     crustacean_states,
+                    serpent_states,
                 },
         } = self;
         let atlas = atlas_;
@@ -6578,6 +6835,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                state.extra.banner,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6597,6 +6855,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6616,6 +6875,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6635,6 +6895,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6654,6 +6915,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6673,6 +6935,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6692,6 +6955,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6711,6 +6975,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6730,6 +6995,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6749,6 +7015,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6768,6 +7035,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6787,6 +7055,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6806,6 +7075,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6825,6 +7095,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6844,6 +7115,27 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
+                                tick,
+                                viewpoint_camera_mode,
+                                character_state,
+                                None,
+                            )
+                            .map(ModelEntryRef::Figure),
+                    )
+                }),
+            Body::Serpent(body) => serpent_states
+                .get(&entity)
+                .filter(|state| filter_state(state))
+                .map(move |state| {
+                    (
+                        state.bound(),
+                        serpent_model_cache
+                            .get_model(
+                                atlas,
+                                body,
+                                inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6863,6 +7155,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6882,6 +7175,7 @@ impl FigureMgr {
                                 atlas,
                                 body,
                                 inventory,
+                                None,
                                 tick,
                                 viewpoint_camera_mode,
                                 character_state,
@@ -6891,7 +7185,7 @@ impl FigureMgr {
                     )
                 }),
             Body::Ship(body) => {
-                if matches!(body, ship::Body::Volume) {
+                if matches!(body, ship::Body::Volume | ship::Body::HotAirBalloon) {
                     volume_states
                         .get(&entity)
                         .filter(|state| filter_state(state))
@@ -6903,6 +7197,7 @@ impl FigureMgr {
                                         atlas,
                                         VolumeKey { entity, mut_count },
                                         None,
+                                        None,
                                         tick,
                                         CameraMode::default(),
                                         None,
@@ -6923,6 +7218,7 @@ impl FigureMgr {
                                         atlas,
                                         body,
                                         None,
+                                        None,
                                         tick,
                                         CameraMode::default(),
                                         None,
@@ -7113,6 +7409,11 @@ impl FigureMgr {
                     .crustacean_states
                     .get(&entity)
                     .and_then(|state| state.viewpoint_offset),
+                Body::Serpent(_) => self
+                    .states
+                    .serpent_states
+                    .get(&entity)
+                    .and_then(|state| state.viewpoint_offset),
             })
             .map(|viewpoint| viewpoint.into())
             .unwrap_or_else(Vec3::zero)
@@ -7296,6 +7597,8 @@ pub struct FigureStateMeta {
     off_abs_trail_points: Option<(anim::vek::Vec3<f32>, anim::vek::Vec3<f32>)>,
     // Animation to be applied to rider of this entity
     mount_transform: anim::vek::Transform<f32, f32, f32>,
+    // Animations to be applied to small companions perched elsewhere on this entity
+    mount_perches: Option<anim::Perches>,
     // Contains the position of this figure or if it is a rider it will contain the mount's
     // mount_world_pos
     // Unlike the interpolated position stored in the ecs this will be propagated along
@@ -7356,6 +7659,7 @@ pub struct FigureUpdateCommonParameters<'a> {
     pub mount_transform_pos: Option<(anim::vek::Transform<f32, f32, f32>, anim::vek::Vec3<f32>)>,
     pub body: Option<Body>,
     pub tools: (Option<ToolKind>, Option<ToolKind>),
+    pub trail: TrailStyle,
     pub col: Rgba<f32>,
     pub dt: f32,
     // TODO: evaluate unused variable
@@ -7414,6 +7718,7 @@ impl<S: Skeleton, D: FigureData> FigureState<S, D> {
                 main_abs_trail_points: None,
                 off_abs_trail_points: None,
                 mount_transform: offsets.mount_bone,
+                mount_perches: offsets.perches,
                 mount_world_pos: anim::vek::Vec3::zero(),
                 state_time: 0.0,
                 last_ori: Ori::default().into(),
@@ -7446,6 +7751,7 @@ impl<S: Skeleton, D: FigureData> FigureState<S, D> {
             mount_transform_pos,
             body,
             tools,
+            trail: trail_style,
             col,
             dt,
             _lpindex,
@@ -7602,9 +7908,12 @@ impl<S: Skeleton, D: FigureData> FigureState<S, D> {
             is_main_weapon: bool,
             pos: anim::vek::Vec3<f32>,
             tool: Option<ToolKind>,
+            style: &TrailStyle,
         ) {
             let weapon_offsets = new_weapon_trail_mat.map(|(mat, trail)| {
                 let (trail_start, trail_end) = trail.relative_offsets(tool);
+                // Shorter trails are drawn from the tip of the weapon inwards
+                let trail_start = Lerp::lerp(trail_end, trail_start, style.length);
                 ((mat * trail_start).xyz(), (mat * trail_end).xyz())
             });
             let new_abs_trail_points = weapon_offsets.map(|(a, b)| (a + pos, b + pos));
@@ -7614,6 +7923,7 @@ impl<S: Skeleton, D: FigureData> FigureState<S, D> {
                 let quad_mesh = trail_mgr.entity_mesh_or_insert(entity, is_main_weapon);
                 let vertex = |p: anim::vek::Vec3<f32>| trail::Vertex {
                     pos: p.into_array(),
+                    color: style.color,
                 };
                 let quad = Quad::new(vertex(*p1), vertex(*p2), vertex(p3), vertex(p4));
                 quad_mesh.replace_quad(trail_mgr_offset * 4, quad);
@@ -7630,6 +7940,7 @@ impl<S: Skeleton, D: FigureData> FigureState<S, D> {
                 true,
                 pos_with_mount_offset,
                 tools.0,
+                trail_style,
             );
             handle_weapon_trails(
                 trail_mgr,
@@ -7639,11 +7950,13 @@ impl<S: Skeleton, D: FigureData> FigureState<S, D> {
                 false,
                 pos_with_mount_offset,
                 tools.1,
+                trail_style,
             );
         }
 
         // TODO: compute the mount bone only when it is needed
         self.mount_transform = offsets.mount_bone;
+        self.mount_perches = offsets.perches;
         self.mount_world_pos = pos_with_mount_offset;
 
         let smoothing = (5.0 * dt).min(1.0);
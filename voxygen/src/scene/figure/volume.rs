@@ -54,6 +54,7 @@ impl anim::Skeleton for VolumeKey {
             lantern: None,
             viewpoint: None,
             mount_bone: anim::vek::Transform::default(),
+            perches: None,
             primary_trail_mat: None,
             secondary_trail_mat: None,
         }
@@ -34,6 +34,7 @@ use crate::{
 use client::Client;
 use common::{
     calendar::Calendar,
+    cmd::WorldgenDebugField,
     comp::{
         self, item::ItemDesc, ship::figuredata::VOXEL_COLLIDER_MANIFEST, slot::EquipSlot,
         tool::ToolKind,
@@ -45,6 +46,7 @@ use common::{
     weather::WeatherGrid,
 };
 use common_base::{prof_span, span};
+use common_net::msg::world_msg::WorldgenDebugOverlay;
 use common_state::State;
 use comp::item::Reagent;
 use hashbrown::HashMap;
@@ -64,7 +66,13 @@ pub(crate) const MAX_POINT_LIGHT_MATRICES_COUNT: usize = MAX_LIGHT_COUNT * 6 + 6
 const NUM_DIRECTED_LIGHTS: usize = 1;
 const LIGHT_DIST_RADIUS: f32 = 64.0; // The distance beyond which lights may not emit light from their origin
 const SHADOW_DIST_RADIUS: f32 = 8.0;
+const LIGHTHOUSE_SPIN_SPEED: f32 = 0.5; // How quickly lighthouse beams rotate, in radians per second
+const LIGHTHOUSE_BEAM_LIGHTS: usize = 4; // The number of lights making up each lighthouse beam
+const LIGHTHOUSE_BEAM_SPACING: f32 = 12.0; // The distance between the lights of a lighthouse beam
 const SHADOW_MAX_DIST: f32 = 96.0; // The distance beyond which shadows may not be visible
+/// Interactables closer than this to the focus are revealed, keep in sync with
+/// `INTERACT_REVEAL_RANGE` in globals.glsl
+pub const INTERACT_REVEAL_RANGE: f32 = 16.0;
 /// The minimum sin γ we will use before switching to uniform mapping.
 const EPSILON_UPSILON: f64 = -1.0;
 
@@ -77,6 +85,12 @@ const RUNNING_THRESHOLD: f32 = 0.7;
 
 /// The threashold for starting calculations with rain.
 const RAIN_THRESHOLD: f32 = 0.0;
+/// Below this temperature rain falls as snow and settles on the ground.
+const SNOWFALL_TEMP: f32 = -0.4;
+/// How fast snow settles during heavy snowfall, per second.
+const SNOW_ACCUMULATION_RATE: f32 = 0.01;
+/// How fast settled snow melts once it stops snowing, per second.
+const SNOW_MELT_RATE: f32 = 0.004;
 
 /// is_daylight, array of active lights.
 pub type LightData<'a> = (bool, &'a [Light]);
@@ -109,6 +123,8 @@ pub struct Scene {
     map_bounds: Vec2<f32>,
     select_pos: Option<Vec3<i32>>,
     light_data: Vec<Light>,
+    /// How many of the nearest lights cast shadows this frame
+    point_shadow_count: usize,
 
     particle_mgr: ParticleMgr,
     trail_mgr: TrailMgr,
@@ -119,6 +135,8 @@ pub struct Scene {
     ambient_mgr: AmbientMgr,
 
     integrated_rain_vel: f32,
+    /// How much snow settled around the player, between 0 and 1
+    snow_cover: f32,
     wind_vel: Vec2<f32>,
     pub interpolated_time_of_day: Option<f64>,
     last_lightning: Option<(Vec3<f32>, f64)>,
@@ -144,10 +162,17 @@ pub struct SceneData<'a> {
     pub sprite_render_distance: f32,
     pub particles_enabled: bool,
     pub weapon_trails_enabled: bool,
+    pub cloth_physics_enabled: bool,
     pub flashing_lights_enabled: bool,
     pub figure_lod_render_distance: f32,
+    /// GPU memory terrain meshes may use, in bytes
+    pub terrain_memory_budget: u64,
     pub is_aiming: bool,
     pub interpolated_time_of_day: Option<f64>,
+    /// Brightness of the highlight on the targeted interactable
+    pub interact_highlight: f32,
+    /// How strongly nearby interactables are revealed, from 0 to 1
+    pub interact_reveal: f32,
 }
 
 impl<'a> SceneData<'a> {
@@ -350,6 +375,7 @@ impl Scene {
             ),
             select_pos: None,
             light_data: Vec::new(),
+            point_shadow_count: 0,
             particle_mgr: ParticleMgr::new(renderer),
             trail_mgr: TrailMgr::default(),
             figure_mgr: FigureMgr::new(renderer),
@@ -360,6 +386,7 @@ impl Scene {
                 ambience: ambient::load_ambience_items(),
             },
             integrated_rain_vel: 0.0,
+            snow_cover: 0.0,
             wind_vel: Vec2::zero(),
             interpolated_time_of_day: None,
             last_lightning: None,
@@ -766,6 +793,26 @@ impl Scene {
                         .map(|el| el.light.with_strength((el.fadeout)(el.timeout))),
                 ),
         );
+        // Lighthouses sweep a rotating beam of light across the water at night
+        if scene_data.state.get_day_period().is_dark() {
+            let angle = scene_data.state.get_time() as f32 * LIGHTHOUSE_SPIN_SPEED;
+            let sweep = Vec3::new(angle.cos(), angle.sin(), 0.0);
+            lights.extend(
+                self.terrain
+                    .lighthouses()
+                    .map(|lpos| lpos.as_::<f32>() + 0.5)
+                    .filter(|lpos| lpos.distance_squared(viewpoint_pos) < max_light_dist)
+                    .flat_map(|lpos| {
+                        (1..=LIGHTHOUSE_BEAM_LIGHTS).map(move |i| {
+                            Light::new(
+                                lpos + sweep * (i as f32 * LIGHTHOUSE_BEAM_SPACING),
+                                Rgb::new(1.0, 0.9, 0.6),
+                                8.0 / i as f32,
+                            )
+                        })
+                    }),
+            );
+        }
         let voxel_colliders_manifest = VOXEL_COLLIDER_MANIFEST.read();
         let figure_mgr = &self.figure_mgr;
         lights.extend(
@@ -883,6 +930,16 @@ impl Scene {
         let focus_pos = self.camera.get_focus_pos();
         let focus_off = focus_pos.map(|e| e.trunc());
 
+        // Only the nearest lights cast shadows, since each of them needs its own cube
+        // map to be rendered
+        self.point_shadow_count = if renderer.pipeline_modes().shadow.is_map() {
+            lights
+                .len()
+                .min(renderer.pipeline_modes().point_shadows as usize)
+        } else {
+            0
+        };
+
         // Update global constants.
         renderer.update_consts(&mut self.data.globals, &[Globals::new(
             view_mat,
@@ -900,6 +957,7 @@ impl Scene {
             lights.len(),
             shadows.len(),
             NUM_DIRECTED_LIGHTS,
+            self.point_shadow_count,
             scene_data
                 .state
                 .terrain()
@@ -917,6 +975,8 @@ impl Scene {
             scene_data.ambiance,
             self.camera.get_mode(),
             scene_data.sprite_render_distance - 20.0,
+            scene_data.interact_highlight,
+            scene_data.interact_reveal,
         )]);
         renderer.update_clouds_locals(CloudsLocals::new(proj_mat_inv, view_mat_inv));
         renderer.update_postprocess_locals(PostProcessLocals::new(proj_mat_inv, view_mat_inv));
@@ -1236,6 +1296,24 @@ impl Scene {
             .state()
             .max_weather_near(focus_off.xy() + cam_pos.xy());
         self.wind_vel = weather.wind_vel();
+
+        // Snow settles while it's cold and precipitating, and slowly melts away
+        // otherwise
+        let temp = client
+            .state()
+            .terrain()
+            .get_interpolated((focus_off.xy() + cam_pos.xy()).as_(), |chunk| {
+                chunk.meta().temp()
+            })
+            .unwrap_or(0.0);
+        let old_snow_cover = self.snow_cover;
+        self.snow_cover = if temp < SNOWFALL_TEMP && weather.rain > RAIN_THRESHOLD {
+            self.snow_cover + weather.rain * SNOW_ACCUMULATION_RATE * dt
+        } else {
+            self.snow_cover - SNOW_MELT_RATE * dt
+        }
+        .clamp(0.0, 1.0);
+
         if weather.rain > RAIN_THRESHOLD {
             let weather = client.weather_at_player();
             let rain_vel = weather.rain_vel();
@@ -1253,13 +1331,14 @@ impl Scene {
                 rain_dir_mat,
                 weather.rain,
                 self.integrated_rain_vel,
+                self.snow_cover,
             );
 
             renderer.update_consts(&mut self.data.rain_occlusion_mats, &[rain_occlusion_locals]);
-        } else if self.integrated_rain_vel > 0.0 {
+        } else if self.integrated_rain_vel > 0.0 || self.snow_cover != old_snow_cover {
             self.integrated_rain_vel = 0.0;
-            // Need to set rain to zero
-            let rain_occlusion_locals = RainOcclusionLocals::default();
+            // Need to set rain to zero, while keeping the settled snow
+            let rain_occlusion_locals = RainOcclusionLocals::with_snow_cover(self.snow_cover);
             renderer.update_consts(&mut self.data.rain_occlusion_mats, &[rain_occlusion_locals]);
         }
 
@@ -1420,6 +1499,7 @@ impl Scene {
                 prof_span!("point shadows");
                 drawer.draw_point_shadows(
                     &self.data.point_light_matrices,
+                    self.point_shadow_count,
                     self.terrain.chunks_for_point_shadows(focus_pos),
                 )
             }
@@ -1644,6 +1724,71 @@ impl Scene {
         });
     }
 
+    /// Replace the shapes shown for a field of the worldgen debug overlay.
+    pub fn set_worldgen_debug_overlay(
+        &mut self,
+        field: WorldgenDebugField,
+        overlay: Option<WorldgenDebugOverlay>,
+        overlays: &mut HashMap<WorldgenDebugField, Vec<DebugShapeId>>,
+    ) {
+        for shape in overlays.remove(&field).into_iter().flatten() {
+            self.debug.remove_shape(shape);
+        }
+        let Some(overlay) = overlay else {
+            return;
+        };
+
+        let mut shapes = Vec::new();
+        for (pos, value) in overlay.samples {
+            let value = value.clamp(0.0, 1.0);
+            let shape = DebugShape::Line([pos, pos + Vec3::unit_z() * (1.0 + value * 8.0)], 0.15);
+            let id = self.debug.add_shape(shape);
+            // Blue for low values, red for high values
+            let color = Rgb::new(0.1, 0.3, 1.0)
+                .map2(Rgb::new(1.0, 0.2, 0.1), |lo, hi| Lerp::lerp(lo, hi, value));
+            self.debug
+                .set_context(id, [0.0; 4], color.with_a(0.8).into_array(), [
+                    0.0, 0.0, 0.0, 1.0,
+                ]);
+            shapes.push(id);
+        }
+        for line in overlay.lines {
+            let id = self.debug.add_shape(DebugShape::Line(line, 0.1));
+            self.debug
+                .set_context(id, [0.0; 4], [1.0, 1.0, 0.2, 0.8], [0.0, 0.0, 0.0, 1.0]);
+            shapes.push(id);
+        }
+        overlays.insert(field, shapes);
+    }
+
+    /// Show the best run on the race track the player is running as a
+    /// translucent figure racing alongside them
+    pub fn maintain_race_ghost(&mut self, client: &Client, ghost: &mut Option<DebugShapeId>) {
+        match client.race_ghost_pos() {
+            Some(pos) => {
+                let shape_id = *ghost.get_or_insert_with(|| {
+                    self.debug.add_shape(DebugShape::CapsulePrism {
+                        p0: Vec2::zero(),
+                        p1: Vec2::zero(),
+                        radius: 0.4,
+                        height: 1.75,
+                    })
+                });
+                self.debug.set_context(
+                    shape_id,
+                    pos.with_w(0.0).into_array(),
+                    [0.6, 0.8, 1.0, 0.4],
+                    [0.0, 0.0, 0.0, 1.0],
+                );
+            },
+            None => {
+                if let Some(shape_id) = ghost.take() {
+                    self.debug.remove_shape(shape_id);
+                }
+            },
+        }
+    }
+
     pub fn maintain_debug_vectors(&mut self, client: &Client, lines: &mut PlayerDebugLines) {
         lines
             .chunk_normal
@@ -1,4 +1,8 @@
-use super::{terrain::BlocksOfInterest, FigureMgr, SceneData, Terrain};
+use super::{
+    terrain::BlocksOfInterest,
+    trail::{AbilityVfxManifest, ImpactKind, ImpactVfx},
+    FigureMgr, SceneData, Terrain,
+};
 use crate::{
     ecs::comp::Interpolated,
     mesh::{greedy::GreedyMesh, segment::generate_mesh_base_vol_particle},
@@ -10,27 +14,28 @@ use crate::{
 };
 use common::{
     assets::{AssetExt, DotVoxAsset},
+    combat::DamageSource,
     comp::{
         self, aura, beam, body, buff,
         item::Reagent,
         object,
         shockwave::{self, ShockwaveDodgeable},
-        Beam, Body, CharacterActivity, CharacterState, Fluid, Ori, PhysicsState, Pos, Scale,
-        Shockwave, Vel,
+        Beam, Body, CharacterActivity, CharacterState, Fluid, Inventory, Ori, PhysicsState, Pos,
+        Scale, Shockwave, Vel,
     },
     figure::Segment,
     outcome::Outcome,
     resources::{DeltaTime, Time},
     spiral::Spiral2d,
     states::{self, utils::StageSection},
-    terrain::{Block, SpriteKind, TerrainChunk, TerrainGrid},
+    terrain::{Block, BlockKind, SpriteKind, TerrainChunk, TerrainGrid},
     uid::IdMaps,
     vol::{ReadVol, RectRasterableVol, SizedVol},
 };
 use common_base::span;
 use hashbrown::HashMap;
 use rand::prelude::*;
-use specs::{Join, LendJoin, WorldExt};
+use specs::{Entity as EcsEntity, Join, LendJoin, WorldExt};
 use std::{
     f32::consts::{PI, TAU},
     time::Duration,
@@ -49,6 +54,10 @@ pub struct ParticleMgr {
 
     /// GPU Vertex Buffers
     model_cache: HashMap<&'static str, Model<ParticleVertex>>,
+
+    /// Where each entity left its last footprint, and whether the next one is
+    /// left by its left foot
+    footprints: HashMap<EcsEntity, (Vec3<f32>, bool)>,
 }
 
 impl ParticleMgr {
@@ -58,6 +67,7 @@ impl ParticleMgr {
             scheduler: HeartbeatScheduler::new(),
             instances: default_instances(renderer),
             model_cache: default_cache(renderer),
+            footprints: HashMap::new(),
         }
     }
 
@@ -329,6 +339,46 @@ impl ParticleMgr {
                     };
                 };
             },
+            Outcome::HealthChange { pos, info } => {
+                // Melee hits show the impact effect of the ability that landed them
+                if info.amount < 0.0 && matches!(info.cause, Some(DamageSource::Melee)) {
+                    let ecs = scene_data.state.ecs();
+                    let id_maps = ecs.read_resource::<IdMaps>();
+                    let impact = info
+                        .by
+                        .and_then(|by| id_maps.uid_entity(by.uid()))
+                        .and_then(|attacker| {
+                            let char_state = ecs.read_storage::<CharacterState>();
+                            let inventories = ecs.read_storage::<Inventory>();
+                            let vfx = AbilityVfxManifest::load()
+                                .read()
+                                .get(char_state.get(attacker)?, inventories.get(attacker));
+                            vfx.impact
+                        });
+                    if let Some(ImpactVfx { kind, count }) = impact {
+                        let height = id_maps
+                            .uid_entity(info.target)
+                            .and_then(|target| ecs.read_storage::<Body>().get(target).copied())
+                            .map_or(1.0, |body| body.height() * 0.6);
+                        let (mode, lifespan) = match kind {
+                            ImpactKind::Sparks => (ParticleMode::GunPowderSpark, 200),
+                            ImpactKind::Blood => (ParticleMode::Blood, 250),
+                            ImpactKind::Embers => (ParticleMode::FieryBurstSparks, 300),
+                            ImpactKind::Frost => (ParticleMode::Ice, 300),
+                            ImpactKind::Energy => (ParticleMode::EnergyBuffing, 300),
+                        };
+                        self.particles
+                            .resize_with(self.particles.len() + count, || {
+                                Particle::new(
+                                    Duration::from_millis(lifespan),
+                                    time,
+                                    mode,
+                                    *pos + Vec3::unit_z() * height,
+                                )
+                            });
+                    }
+                }
+            },
             Outcome::Block { pos, parry, .. } => {
                 if *parry {
                     self.particles.resize_with(self.particles.len() + 10, || {
@@ -468,7 +518,6 @@ impl ParticleMgr {
             | Outcome::ExpChange { .. }
             | Outcome::SkillPointGain { .. }
             | Outcome::ComboChange { .. }
-            | Outcome::HealthChange { .. }
             | Outcome::PoiseChange { .. }
             | Outcome::Utterance { .. }
             | Outcome::IceSpikes { .. }
@@ -512,6 +561,7 @@ impl ParticleMgr {
             self.maintain_shockwave_particles(scene_data);
             self.maintain_aura_particles(scene_data);
             self.maintain_buff_particles(scene_data);
+            self.maintain_footprint_particles(scene_data);
 
             self.upload_particles(renderer);
         } else {
@@ -523,6 +573,7 @@ impl ParticleMgr {
 
             // remove all timings
             self.scheduler.clear();
+            self.footprints.clear();
         }
     }
 
@@ -581,7 +632,7 @@ impl ParticleMgr {
                 Body::Object(object::Body::PortalActive) => {
                     self.maintain_active_portal_particles(scene_data, interpolated.pos)
                 },
-                Body::Object(object::Body::Portal) => {
+                Body::Object(object::Body::Portal | object::Body::RaceCheckpoint) => {
                     self.maintain_portal_particles(scene_data, interpolated.pos)
                 },
                 _ => {},
@@ -589,6 +640,71 @@ impl ParticleMgr {
         }
     }
 
+    /// Entities walking over snow or sand leave a trail of footprints that
+    /// fades away over time
+    fn maintain_footprint_particles(&mut self, scene_data: &SceneData) {
+        span!(
+            _guard,
+            "footprint_particles",
+            "ParticleMgr::maintain_footprint_particles"
+        );
+        const FOOTPRINT_LIFESPAN: Duration = Duration::from_secs(30);
+        const FOOTPRINT_STRIDE: f32 = 0.7;
+        const FOOTPRINT_SPACING: f32 = 0.15;
+
+        let ecs = scene_data.state.ecs();
+        let time = scene_data.state.get_time();
+        let entities = ecs.entities();
+        self.footprints
+            .retain(|entity, _| entities.is_alive(*entity));
+
+        for (entity, body, interpolated, physics, scale) in (
+            &entities,
+            &ecs.read_storage::<Body>(),
+            &ecs.read_storage::<Interpolated>(),
+            &ecs.read_storage::<PhysicsState>(),
+            ecs.read_storage::<Scale>().maybe(),
+        )
+            .join()
+        {
+            let ground = physics
+                .on_ground
+                .filter(|_| {
+                    !matches!(body, Body::Object(_) | Body::ItemDrop(_) | Body::Ship(_))
+                        && physics.in_liquid().is_none()
+                })
+                .map(|block| block.kind());
+            let mode = match ground {
+                Some(BlockKind::Snow | BlockKind::ArtSnow) => ParticleMode::SnowFootprint,
+                Some(BlockKind::Sand) => ParticleMode::SandFootprint,
+                _ => {
+                    self.footprints.remove(&entity);
+                    continue;
+                },
+            };
+
+            let scale = scale.map_or(1.0, |s| s.0);
+            let pos = interpolated.pos;
+            let (last_pos, left) = *self.footprints.entry(entity).or_insert((pos, false));
+            let step = pos.xy() - last_pos.xy();
+            if step.magnitude_squared() < (FOOTPRINT_STRIDE * scale).powi(2) {
+                continue;
+            }
+
+            let dir = step.normalized();
+            let side = if left { 1.0 } else { -1.0 };
+            let print_pos = pos + Vec3::new(-dir.y, dir.x, 0.0) * side * FOOTPRINT_SPACING * scale;
+            self.particles.push(Particle::new_directed(
+                FOOTPRINT_LIFESPAN,
+                time,
+                mode,
+                print_pos,
+                print_pos + Vec3::from(dir),
+            ));
+            self.footprints.insert(entity, (pos, !left));
+        }
+    }
+
     fn maintain_campfirelit_particles(
         &mut self,
         scene_data: &SceneData,
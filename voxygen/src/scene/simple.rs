@@ -9,6 +9,7 @@ use crate::{
         camera::{self, Camera, CameraMode},
         figure::{FigureAtlas, FigureModelCache, FigureState, FigureUpdateCommonParameters},
         terrain::{SpriteRenderContext, SpriteRenderState},
+        trail::TrailStyle,
         CloudsLocals, CullingMode, Lod, PostProcessLocals,
     },
     window::{Event, PressState},
@@ -245,6 +246,7 @@ impl Scene {
             0,
             0,
             0,
+            0,
             BlockKind::Air,
             None,
             scene_data.gamma,
@@ -254,6 +256,8 @@ impl Scene {
             scene_data.ambiance,
             self.camera.get_mode(),
             250.0,
+            0.0,
+            0.0,
         )]);
         renderer.update_clouds_locals(CloudsLocals::new(proj_mat_inv, view_mat_inv));
         renderer.update_postprocess_locals(PostProcessLocals::new(proj_mat_inv, view_mat_inv));
@@ -294,6 +298,7 @@ impl Scene {
                 mount_transform_pos: None,
                 body: None,
                 tools: (None, None),
+                trail: TrailStyle::default(),
                 col: Rgba::broadcast(1.0),
                 dt,
                 _lpindex: 0,
@@ -330,11 +335,13 @@ impl Scene {
                 &mut self.figure_atlas,
                 body,
                 inventory,
+                None,
                 (),
                 scene_data.tick,
                 CameraMode::default(),
                 None,
                 scene_data.slow_job_pool,
+                true,
                 None,
             );
             char_state.update(
@@ -405,6 +412,7 @@ impl Scene {
                 &self.figure_atlas,
                 body,
                 inventory,
+                None,
                 tick,
                 CameraMode::default(),
                 None,
@@ -426,6 +434,7 @@ impl Scene {
             &self.figure_atlas,
             ship::Body::DefaultAirship,
             Default::default(),
+            None,
             tick,
             CameraMode::default(),
             None,
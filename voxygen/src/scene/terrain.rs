@@ -13,7 +13,7 @@ use crate::{
     render::{
         pipelines::{self, AtlasData, AtlasTextures},
         AltIndices, CullingMode, FigureSpriteAtlasData, FirstPassDrawer, FluidVertex, GlobalModel,
-        Instances, LodData, Mesh, Model, RenderError, Renderer, SpriteDrawer,
+        Instances, LodData, Mesh, Model, ModelPool, RenderError, Renderer, SpriteDrawer,
         SpriteGlobalsBindGroup, SpriteInstance, SpriteVertex, SpriteVerts, TerrainAtlasData,
         TerrainLocals, TerrainShadowDrawer, TerrainVertex, SPRITE_VERT_PAGE_SIZE,
     },
@@ -48,6 +48,15 @@ use vek::*;
 
 const SPRITE_SCALE: Vec3<f32> = Vec3::new(1.0 / 11.0, 1.0 / 11.0, 1.0 / 11.0);
 pub const SPRITE_LOD_LEVELS: usize = 5;
+/// Fraction of the foliage (sprites swaying in the wind, like grass and crops)
+/// that is kept at each LOD level. Distant foliage is thinned out since it is
+/// barely noticeable, while being the bulk of the sprites in plains and
+/// forests.
+const FOLIAGE_LOD_DENSITY: [f32; SPRITE_LOD_LEVELS] = [1.0, 1.0, 0.75, 0.5, 0.3];
+
+/// Share of the terrain memory budget that may be taken up by unused mesh
+/// buffers kept around for reuse
+const MESH_POOL_BUDGET_DIVISOR: u64 = 8;
 
 // For rain occlusion we only need to render the closest chunks.
 /// How many chunks are maximally rendered for rain occlusion.
@@ -104,6 +113,22 @@ pub struct TerrainChunkData {
     frustum_last_plane_index: u8,
 
     alt_indices: AltIndices,
+
+    /// Time at which the chunk was last visible, used to pick which meshes to
+    /// evict when over the memory budget
+    last_visible: f64,
+    /// Whether the meshes of this chunk were freed to stay within the memory
+    /// budget. They get rebuilt once the chunk comes back into view.
+    evicted: bool,
+}
+
+/// GPU memory used by terrain meshes, shown in the debug overlay
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TerrainMemoryStats {
+    pub mesh_bytes: u64,
+    pub pooled_bytes: u64,
+    pub budget_bytes: u64,
+    pub evicted_chunks: usize,
 }
 
 /// The depth at which the intermediate zone between underground and surface
@@ -200,8 +225,20 @@ pub fn get_sprite_instances<'a, I: 'a>(
             // NOTE: Safe because 0 ≤ ori < 8
             let light = light_map(wpos);
             let glow = glow_map(wpos);
-
-            for (lod_level, sprite_data) in lod_levels.iter_mut().zip(&sprite_data[&key]) {
+            let is_interactable = Interaction::of_block(&block).next().is_some();
+            // Foliage that is left out at one LOD level is left out at all coarser ones
+            // too, so that it doesn't pop in and out while moving around
+            let foliage_roll =
+                (seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 40) as f32 / (1u64 << 24) as f32;
+
+            for ((lod_level, sprite_data), density) in lod_levels
+                .iter_mut()
+                .zip(&sprite_data[&key])
+                .zip(FOLIAGE_LOD_DENSITY)
+            {
+                if cfg.wind_sway > 0.0 && foliage_roll >= density {
+                    continue;
+                }
                 let mat = Mat4::identity()
                     // Scaling for different LOD resolutions
                     .scaled_3d(sprite_data.scale)
@@ -226,6 +263,7 @@ pub fn get_sprite_instances<'a, I: 'a>(
                         glow,
                         page,
                         sprite.is_door(),
+                        is_interactable,
                     );
                     set_instance(lod_level, instance, wpos);
                 }
@@ -431,12 +469,33 @@ pub struct Terrain<V: RectRasterableVol = TerrainChunk> {
     /// for any particular chunk; look at the `texture` field in
     /// `TerrainChunkData` for that.
     atlas_textures: Arc<AtlasTextures<pipelines::terrain::Locals, TerrainAtlasData>>,
+    /// Vertex buffers of replaced or evicted chunk meshes, reused for new ones
+    mesh_pool: ModelPool,
+    /// How much GPU memory chunk meshes may use before the least recently seen
+    /// ones get evicted
+    mesh_budget: u64,
 
     phantom: PhantomData<V>,
 }
 
 impl TerrainChunkData {
     pub fn can_shadow_sun(&self) -> bool { self.visible.is_visible() || self.can_shadow_sun }
+
+    /// GPU memory used by the meshes of this chunk
+    fn mesh_bytes(&self) -> u64 {
+        self.opaque_model.as_ref().map_or(0, Model::size_bytes)
+            + self.fluid_model.as_ref().map_or(0, Model::size_bytes)
+    }
+
+    /// Hands the meshes of this chunk to `pool`, so that they can be reused
+    fn recycle_meshes(&mut self, pool: &mut ModelPool) {
+        if let Some(model) = self.opaque_model.take() {
+            pool.recycle(model);
+        }
+        if let Some(model) = self.fluid_model.take() {
+            pool.recycle(model);
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -668,6 +727,8 @@ impl<V: RectRasterableVol> Terrain<V> {
                 &sprite_render_context.sprite_verts_buffer,
             ),
             atlas_textures: Arc::new(atlas_textures),
+            mesh_pool: ModelPool::default(),
+            mesh_budget: u64::MAX,
             phantom: PhantomData,
         }
     }
@@ -745,8 +806,9 @@ impl<V: RectRasterableVol> Terrain<V> {
     }
 
     fn insert_chunk(&mut self, pos: Vec2<i32>, chunk: TerrainChunkData) {
-        if let Some(old) = self.chunks.insert(pos, chunk) {
+        if let Some(mut old) = self.chunks.insert(pos, chunk) {
             self.remove_chunk_meta(pos, &old);
+            old.recycle_meshes(&mut self.mesh_pool);
         }
         /* let (zmin, zmax) = chunk.z_bounds;
         self.z_index_up.insert(Vec3::from(zmin, pos.x, pos.y));
@@ -813,6 +875,18 @@ impl<V: RectRasterableVol> Terrain<V> {
             .unwrap_or(0.0)
     }
 
+    /// The positions of the lit lighthouse lamps in all loaded chunks
+    pub fn lighthouses(&self) -> impl Iterator<Item = Vec3<i32>> + '_ {
+        self.chunks.iter().flat_map(|(chunk_pos, chunk)| {
+            let chunk_wpos = Vec3::from(chunk_pos * TerrainChunk::RECT_SIZE.map(|e| e as i32));
+            chunk
+                .blocks_of_interest
+                .lighthouses
+                .iter()
+                .map(move |lpos| chunk_wpos + *lpos)
+        })
+    }
+
     pub fn glow_normal_at_wpos(&self, wpos: Vec3<f32>) -> (Vec3<f32>, f32) {
         let wpos_chunk = wpos.xy().map2(TerrainChunk::RECT_SIZE, |e: f32, sz| {
             (e as i32).div_euclid(sz as i32)
@@ -1260,10 +1334,14 @@ impl<V: RectRasterableVol> Terrain<V> {
                             &mesh.atlas_texture_data.kinds,
                         );
 
+                        let opaque_model =
+                            renderer.create_model_pooled(&mesh.opaque_mesh, &mut self.mesh_pool);
+                        let fluid_model =
+                            renderer.create_model_pooled(&mesh.fluid_mesh, &mut self.mesh_pool);
                         self.insert_chunk(response.pos, TerrainChunkData {
                             load_time,
-                            opaque_model: renderer.create_model(&mesh.opaque_mesh),
-                            fluid_model: renderer.create_model(&mesh.fluid_mesh),
+                            opaque_model,
+                            fluid_model,
                             atlas_alloc: Some(allocation.id),
                             atlas_textures: Arc::clone(&self.atlas_textures),
                             light_map: mesh.light_map,
@@ -1290,6 +1368,8 @@ impl<V: RectRasterableVol> Terrain<V> {
                             sun_occluder_z_bounds: mesh.sun_occluder_z_bounds,
                             frustum_last_plane_index: 0,
                             alt_indices: mesh.alt_indices,
+                            last_visible: current_time,
+                            evicted: false,
                         });
                     } else if let Some(chunk) = self.chunks.get_mut(&response.pos) {
                         // There was an update that didn't require a remesh (probably related to
@@ -1347,6 +1427,24 @@ impl<V: RectRasterableVol> Terrain<V> {
 
             chunk.frustum_last_plane_index = last_plane_index;
             chunk.visible.in_frustum = in_frustum;
+
+            if chunk.visible.is_visible() {
+                chunk.last_visible = current_time;
+                // Rebuild the meshes of evicted chunks that came back into view
+                if chunk.evicted && !self.mesh_todo.contains_key(pos) {
+                    let neighbours = (-1..2)
+                        .flat_map(|i| (-1..2).map(move |j| Vec2::new(i, j)))
+                        .all(|rpos| scene_data.state.terrain().contains_key_real(pos + rpos));
+                    if neighbours {
+                        self.mesh_todo.insert(*pos, ChunkMeshState {
+                            pos: *pos,
+                            started_tick: current_tick,
+                            is_worker_active: false,
+                            skip_remesh: false,
+                        });
+                    }
+                }
+            }
             let chunk_area = Aabr {
                 min: chunk_pos,
                 max: chunk_pos + chunk_sz,
@@ -1465,20 +1563,35 @@ impl<V: RectRasterableVol> Terrain<V> {
             // Otherwise we may end up with multiple instances of the chunk trying to cast
             // shadows at the same time.
             let chunks = &self.chunks;
-            self.shadow_chunks
-                .retain(|(pos, chunk)| !chunks.contains_key(pos) && can_shadow_sun(*pos, chunk));
+            let (retained, dropped): (Vec<_>, Vec<_>) = std::mem::take(&mut self.shadow_chunks)
+                .into_iter()
+                .partition(|(pos, chunk)| !chunks.contains_key(pos) && can_shadow_sun(*pos, chunk));
+            self.shadow_chunks = retained;
+            for (_, mut chunk) in dropped {
+                chunk.recycle_meshes(&mut self.mesh_pool);
+            }
 
             (visible_light_volume, visible_bounds)
         } else {
             // There's no daylight or no shadows, so there's no reason to keep any
             // shadow chunks around.
-            self.shadow_chunks.clear();
+            for (_, mut chunk) in self.shadow_chunks.drain(..) {
+                chunk.recycle_meshes(&mut self.mesh_pool);
+            }
             (Vec::new(), math::Aabr {
                 min: math::Vec2::zero(),
                 max: math::Vec2::zero(),
             })
         };
         drop(guard);
+
+        span!(guard, "Evict chunk meshes");
+        self.mesh_budget = scene_data.terrain_memory_budget;
+        self.mesh_pool
+            .set_max_pooled_bytes(self.mesh_budget / MESH_POOL_BUDGET_DIVISOR);
+        self.evict_meshes(focus_pos);
+        drop(guard);
+
         span!(guard, "Rain occlusion magic");
         // Check if there is rain near the camera
         let max_weather = scene_data
@@ -1531,6 +1644,55 @@ impl<V: RectRasterableVol> Terrain<V> {
         )
     }
 
+    /// Frees the meshes of the chunks that haven't been seen for the longest
+    /// time, furthest away first, until the terrain fits into its memory
+    /// budget again. Chunks that are visible or cast shadows are kept.
+    fn evict_meshes(&mut self, focus_pos: Vec3<f32>) {
+        let mut mesh_bytes = self
+            .chunks
+            .values()
+            .chain(self.shadow_chunks.iter().map(|(_, chunk)| chunk))
+            .map(TerrainChunkData::mesh_bytes)
+            .sum::<u64>();
+        if mesh_bytes <= self.mesh_budget {
+            return;
+        }
+
+        let chunk_sz = V::RECT_SIZE.x as f32;
+        let mut candidates = self
+            .chunks
+            .iter()
+            .filter(|(_, chunk)| {
+                !chunk.evicted && !chunk.can_shadow_sun() && !chunk.can_shadow_point
+            })
+            .map(|(pos, chunk)| {
+                let center = (pos.as_::<f32>() + 0.5) * chunk_sz;
+                (
+                    *pos,
+                    chunk.last_visible,
+                    center.distance_squared(focus_pos.xy()),
+                )
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_unstable_by(|(_, a_seen, a_dist), (_, b_seen, b_dist)| {
+            a_seen.total_cmp(b_seen).then(b_dist.total_cmp(a_dist))
+        });
+
+        for (pos, _, _) in candidates {
+            if mesh_bytes <= self.mesh_budget {
+                break;
+            }
+            if let Some(chunk) = self.chunks.get_mut(&pos) {
+                mesh_bytes -= chunk.mesh_bytes();
+                chunk.recycle_meshes(&mut self.mesh_pool);
+                if let Some(atlas_alloc) = chunk.atlas_alloc.take() {
+                    self.atlas.deallocate(atlas_alloc);
+                }
+                chunk.evicted = true;
+            }
+        }
+    }
+
     pub fn get(&self, chunk_key: Vec2<i32>) -> Option<&TerrainChunkData> {
         self.chunks.get(&chunk_key)
     }
@@ -1546,6 +1708,20 @@ impl<V: RectRasterableVol> Terrain<V> {
 
     pub fn shadow_chunk_count(&self) -> usize { self.shadow_chunks.len() }
 
+    pub fn memory_stats(&self) -> TerrainMemoryStats {
+        TerrainMemoryStats {
+            mesh_bytes: self
+                .chunks
+                .values()
+                .chain(self.shadow_chunks.iter().map(|(_, chunk)| chunk))
+                .map(TerrainChunkData::mesh_bytes)
+                .sum(),
+            pooled_bytes: self.mesh_pool.pooled_bytes(),
+            budget_bytes: self.mesh_budget,
+            evicted_chunks: self.chunks.values().filter(|chunk| chunk.evicted).count(),
+        }
+    }
+
     pub fn render_shadows<'a>(
         &'a self,
         drawer: &mut TerrainShadowDrawer<'_, 'a>,
@@ -17,6 +17,42 @@ pub enum Interaction {
     Mount,
     Read,
     LightToggle(bool),
+    NoticeBoard,
+    GuildBoard,
+    GuildStorage,
+}
+
+impl Interaction {
+    /// The ways a block can be interacted with
+    pub fn of_block(block: &Block) -> impl Iterator<Item = Self> {
+        let light_toggle = block
+            .get_sprite()
+            .filter(|sprite| sprite.category() == sprite::Category::Lamp)
+            .and_then(|_| block.get_attr().ok())
+            .map(|sprite::LightEnabled(enabled)| Self::LightToggle(!enabled));
+        let mount = block.is_mountable().then_some(Self::Mount);
+        let sprite = block.get_sprite().and_then(|sprite| match sprite {
+            SpriteKind::CraftingBench
+            | SpriteKind::SpinningWheel
+            | SpriteKind::Loom
+            | SpriteKind::RepairBench => Some(Self::Craft(CraftingTab::All)),
+            SpriteKind::Forge | SpriteKind::TanningRack => {
+                Some(Self::Craft(CraftingTab::ProcessedMaterial))
+            },
+            SpriteKind::Cauldron => Some(Self::Craft(CraftingTab::Potion)),
+            SpriteKind::Anvil => Some(Self::Craft(CraftingTab::Weapon)),
+            SpriteKind::CookingPot => Some(Self::Craft(CraftingTab::Food)),
+            SpriteKind::DismantlingBench => Some(Self::Craft(CraftingTab::Dismantle)),
+            SpriteKind::Sign | SpriteKind::HangingSign => Some(Self::Read),
+            SpriteKind::NoticeBoard => Some(Self::NoticeBoard),
+            SpriteKind::GuildBoard => Some(Self::GuildBoard),
+            SpriteKind::GuildChest => Some(Self::GuildStorage),
+            _ => None,
+        });
+        let collect = block.collectible_id().is_some().then_some(Self::Collect);
+
+        [light_toggle, mount, sprite, collect].into_iter().flatten()
+    }
 }
 
 pub enum FireplaceType {
@@ -48,6 +84,7 @@ pub struct BlocksOfInterest {
     pub fireflies: Vec<Vec3<i32>>,
     pub flowers: Vec<Vec3<i32>>,
     pub fire_bowls: Vec<Vec3<i32>>,
+    pub lighthouses: Vec<Vec3<i32>>,
     pub snow: Vec<Vec3<i32>>,
     pub spores: Vec<Vec3<i32>>,
     //This is so crickets stay in place and don't randomly change sounds
@@ -92,6 +129,7 @@ impl BlocksOfInterest {
         // some of them
         let mut minor_lights = Vec::new();
         let mut fire_bowls = Vec::new();
+        let mut lighthouses = Vec::new();
         let mut snow = Vec::new();
         let mut cricket1 = Vec::new();
         let mut cricket2 = Vec::new();
@@ -144,16 +182,6 @@ impl BlocksOfInterest {
                 BlockKind::Snow | BlockKind::Ice if rng.gen_range(0..16) == 0 => snow.push(pos),
                 _ => {
                     if let Some(sprite) = block.get_sprite() {
-                        if sprite.category() == sprite::Category::Lamp {
-                            if let Ok(sprite::LightEnabled(enabled)) = block.get_attr() {
-                                interactables.push((pos, Interaction::LightToggle(!enabled)));
-                            }
-                        }
-
-                        if block.is_mountable() {
-                            interactables.push((pos, Interaction::Mount));
-                        }
-
                         match sprite {
                             SpriteKind::Ember => {
                                 fires.push(pos);
@@ -168,6 +196,11 @@ impl BlocksOfInterest {
                             SpriteKind::FireBowlGround => fire_bowls.push(pos + Vec3::unit_z()),
                             SpriteKind::StreetLampTall => fire_bowls.push(pos + Vec3::unit_z() * 4),
                             SpriteKind::WallSconce => fire_bowls.push(pos + Vec3::unit_z()),
+                            SpriteKind::LighthouseLamp
+                                if block.get_attr().map_or(true, |sprite::LightEnabled(e)| e) =>
+                            {
+                                lighthouses.push(pos)
+                            },
                             SpriteKind::Beehive => beehives.push(pos),
                             SpriteKind::Reed => {
                                 reeds.push(pos);
@@ -183,41 +216,12 @@ impl BlocksOfInterest {
                             SpriteKind::WhiteFlower => flowers.push(pos),
                             SpriteKind::YellowFlower => flowers.push(pos),
                             SpriteKind::Sunflower => flowers.push(pos),
-                            SpriteKind::CraftingBench => {
-                                interactables.push((pos, Interaction::Craft(CraftingTab::All)))
-                            },
                             SpriteKind::SmokeDummy => {
                                 smokers.push(SmokerProperties::new(pos, FireplaceType::Workshop));
                             },
-                            SpriteKind::Forge => interactables
-                                .push((pos, Interaction::Craft(CraftingTab::ProcessedMaterial))),
-                            SpriteKind::TanningRack => interactables
-                                .push((pos, Interaction::Craft(CraftingTab::ProcessedMaterial))),
-                            SpriteKind::SpinningWheel => {
-                                interactables.push((pos, Interaction::Craft(CraftingTab::All)))
-                            },
-                            SpriteKind::Loom => {
-                                interactables.push((pos, Interaction::Craft(CraftingTab::All)))
-                            },
-                            SpriteKind::Cauldron => {
-                                fires.push(pos);
-                                interactables.push((pos, Interaction::Craft(CraftingTab::Potion)))
-                            },
-                            SpriteKind::Anvil => {
-                                interactables.push((pos, Interaction::Craft(CraftingTab::Weapon)))
-                            },
-                            SpriteKind::CookingPot => {
-                                fires.push(pos);
-                                interactables.push((pos, Interaction::Craft(CraftingTab::Food)))
-                            },
-                            SpriteKind::DismantlingBench => {
-                                fires.push(pos);
-                                interactables
-                                    .push((pos, Interaction::Craft(CraftingTab::Dismantle)))
-                            },
-                            SpriteKind::RepairBench => {
-                                interactables.push((pos, Interaction::Craft(CraftingTab::All)))
-                            },
+                            SpriteKind::Cauldron
+                            | SpriteKind::CookingPot
+                            | SpriteKind::DismantlingBench => fires.push(pos),
                             SpriteKind::OneWayWall => one_way_walls.push((
                                 pos,
                                 Vec2::unit_y()
@@ -228,9 +232,6 @@ impl BlocksOfInterest {
                                     )
                                     .with_z(0.0),
                             )),
-                            SpriteKind::Sign | SpriteKind::HangingSign => {
-                                interactables.push((pos, Interaction::Read))
-                            },
                             SpriteKind::MycelBlue => spores.push(pos),
                             SpriteKind::Mold => spores.push(pos),
                             _ => {},
@@ -238,9 +239,8 @@ impl BlocksOfInterest {
                     }
                 },
             }
-            if block.collectible_id().is_some() {
-                interactables.push((pos, Interaction::Collect));
-            }
+            interactables
+                .extend(Interaction::of_block(&block).map(|interaction| (pos, interaction)));
             if let Some(glow) = block.get_glow() {
                 // Currently, we count filled blocks as 'minor' lights, and sprites as
                 // non-minor.
@@ -275,6 +275,7 @@ impl BlocksOfInterest {
             fireflies,
             flowers,
             fire_bowls,
+            lighthouses,
             snow,
             spores,
             cricket1,
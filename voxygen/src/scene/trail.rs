@@ -1,11 +1,88 @@
 use super::SceneData;
 use crate::render::{DynamicModel, Mesh, Quad, Renderer, TrailDrawer, TrailVertex};
-use common::comp::{object, Body, Pos, Vel};
+use common::{
+    assets::{self, AssetExt, AssetHandle},
+    comp::{object, Body, CharacterState, Inventory, Pos, Vel},
+};
 use common_base::span;
+use serde::Deserialize;
 use specs::{Entity as EcsEntity, Join, WorldExt};
 use std::collections::HashMap;
 use vek::*;
 
+/// How the trail left by a weapon looks.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct TrailStyle {
+    pub color: [f32; 4],
+    /// The fraction of the weapon, measured from its tip, that leaves a trail.
+    #[serde(default = "TrailStyle::full_length")]
+    pub length: f32,
+}
+
+impl TrailStyle {
+    fn full_length() -> f32 { 1.0 }
+}
+
+impl Default for TrailStyle {
+    fn default() -> Self {
+        Self {
+            color: [0.55, 0.92, 1.0, 0.05],
+            length: Self::full_length(),
+        }
+    }
+}
+
+/// The kinds of particles an ability can throw up from whatever it hits.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum ImpactKind {
+    Sparks,
+    Blood,
+    Embers,
+    Frost,
+    Energy,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ImpactVfx {
+    pub kind: ImpactKind,
+    pub count: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub struct AbilityVfx {
+    #[serde(default)]
+    pub trail: TrailStyle,
+    #[serde(default)]
+    pub impact: Option<ImpactVfx>,
+}
+
+/// The trails and hit effects of abilities, keyed by ability id. Abilities
+/// without an entry use the default trail and have no hit effects of their
+/// own.
+#[derive(Deserialize)]
+pub struct AbilityVfxManifest(HashMap<String, AbilityVfx>);
+
+impl assets::Asset for AbilityVfxManifest {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+impl AbilityVfxManifest {
+    pub fn load() -> AssetHandle<Self> { Self::load_expect("voxygen.ability_vfx_manifest") }
+
+    /// Finds how the ability a character is currently using looks.
+    pub fn get(&self, char_state: &CharacterState, inventory: Option<&Inventory>) -> AbilityVfx {
+        char_state
+            .ability_info()
+            .and_then(|info| info.ability)
+            .and_then(|ability| ability.ability_id(Some(char_state), inventory))
+            .and_then(|id| self.0.get(id))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
 struct MeshKey {
     entity: EcsEntity,
@@ -72,6 +149,7 @@ impl TrailMgr {
                     let p3 = p4 + Vec3::unit_z() * THICKNESS;
                     let vertex = |p: Vec3<f32>| TrailVertex {
                         pos: p.into_array(),
+                        color: TrailStyle::default().color,
                     };
                     let quad = Quad::new(vertex(p1), vertex(p2), vertex(p3), vertex(p4));
                     quad_mesh.replace_quad(offset * 4, quad);
@@ -186,3 +264,11 @@ impl TrailMgr {
 
     pub fn offset(&self) -> usize { self.offset }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_ability_vfx_manifest() { let _ = AbilityVfxManifest::load(); }
+}
@@ -8,9 +8,11 @@ use super::{
 };
 use client::Client;
 use common::{
+    bounty::NOTICE_BOARD_RANGE,
     comp,
     comp::{ship::figuredata::VOXEL_COLLIDER_MANIFEST, tool::ToolKind, Collider, Content},
     consts::{MAX_INTERACT_RANGE, MAX_PICKUP_RANGE, MAX_SPRITE_MOUNT_RANGE, TELEPORTER_RADIUS},
+    guild::GUILD_HALL_RANGE,
     link::Is,
     mounting::{Mount, Rider, VolumePos, VolumeRider},
     terrain::{Block, TerrainGrid, UnlockKind},
@@ -37,6 +39,9 @@ pub enum BlockInteraction {
     Mount,
     Read(Content),
     LightToggle(bool),
+    NoticeBoard,
+    GuildBoard,
+    GuildStorage,
 }
 
 #[derive(Clone, Debug)]
@@ -107,6 +112,19 @@ impl Interactable {
             Interaction::Craft(tab) => BlockInteraction::Craft(tab),
             Interaction::Mount => BlockInteraction::Mount,
             Interaction::LightToggle(enable) => BlockInteraction::LightToggle(enable),
+            Interaction::NoticeBoard => match volume_pos.kind {
+                common::mounting::Volume::Terrain => BlockInteraction::NoticeBoard,
+                // Notice boards on volume entities are not currently supported
+                common::mounting::Volume::Entity(_) => return None,
+            },
+            Interaction::GuildBoard => match volume_pos.kind {
+                common::mounting::Volume::Terrain => BlockInteraction::GuildBoard,
+                common::mounting::Volume::Entity(_) => return None,
+            },
+            Interaction::GuildStorage => match volume_pos.kind {
+                common::mounting::Volume::Terrain => BlockInteraction::GuildStorage,
+                common::mounting::Volume::Entity(_) => return None,
+            },
         };
         Some(Self::Block(block, volume_pos, block_interaction))
     }
@@ -209,6 +227,7 @@ pub(super) fn select_interactable(
         let bodies = ecs.read_storage::<comp::Body>();
         let items = ecs.read_storage::<comp::PickupItem>();
         let stats = ecs.read_storage::<comp::Stats>();
+        let objects = ecs.read_storage::<comp::Object>();
 
         let player_char_state = char_states.get(player_entity);
         let player_cylinder = Cylinder::from_components(
@@ -231,13 +250,14 @@ pub(super) fn select_interactable(
             !&is_mounts,
             is_riders.maybe(),
             (stats.mask() | items.mask()).maybe(),
+            objects.maybe(),
         )
             .lend_join();
 
         let closest_interactable_entity = spacial_grid.0.in_circle_aabr(player_pos.xy(), MAX_PICKUP_RANGE)
             .filter(|&e| e != player_entity) // skip the player's entity
             .filter_map(|e| entity_data.get(e, &entities))
-            .filter_map(|(e, p, b, s, c, cs, _, is_rider, has_stats_or_item)| {
+            .filter_map(|(e, p, b, s, c, cs, _, is_rider, has_stats_or_item, object)| {
                 // Note, if this becomes expensive to compute do it after the distance check!
                 //
                 // The entities that can be interacted with:
@@ -248,10 +268,12 @@ pub(super) fn select_interactable(
                 //   some false positives here as long as it doesn't frequently prevent us from
                 //   interacting with actual interactable entities that are closer by)
                 // * Dropped items that can be picked up (Item component)
+                // * Waypoint shrines (Object::Shrine)
+                // * Own gravestones (Object::Gravestone)
                 // * Are not riding the player
                 let not_riding_player = is_rider
                     .map_or(true, |is_rider| Some(&is_rider.mount) != uids.get(viewpoint_entity));
-                let is_interactable = (b.is_campfire() || (b.is_portal() && (p.0.distance_squared(player_pos) <= TELEPORTER_RADIUS.powi(2))) || has_stats_or_item.is_some()) && not_riding_player;
+                let is_interactable = (b.is_campfire() || (b.is_portal() && (p.0.distance_squared(player_pos) <= TELEPORTER_RADIUS.powi(2))) || has_stats_or_item.is_some() || matches!(object, Some(comp::Object::Shrine { .. })) || matches!(object, Some(comp::Object::Gravestone { owner, .. }) if Some(owner) == uids.get(player_entity))) && not_riding_player;
                 if !is_interactable {
                     return None;
                 };
@@ -347,7 +369,9 @@ pub(super) fn select_interactable(
                     Interaction::Mount => !is_volume_rider.contains(player_entity)
                         && wpos.distance_squared(player_pos) < MAX_SPRITE_MOUNT_RANGE.powi(2)
                         && !is_volume_rider.join().any(|is_volume_rider| is_volume_rider.pos == *volume_pos),
-                    Interaction::LightToggle(_) => wpos.distance_squared(player_pos) < MAX_INTERACT_RANGE.powi(2),
+                    Interaction::LightToggle(_) | Interaction::GuildStorage => wpos.distance_squared(player_pos) < MAX_INTERACT_RANGE.powi(2),
+                    Interaction::NoticeBoard => wpos.distance_squared(player_pos) < NOTICE_BOARD_RANGE.powi(2),
+                    Interaction::GuildBoard => wpos.distance_squared(player_pos) < GUILD_HALL_RANGE.powi(2),
                     _ => true,
                 }
             })
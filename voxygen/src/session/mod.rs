@@ -13,6 +13,7 @@ use vek::*;
 
 use client::{self, Client};
 use common::{
+    cmd::WorldgenDebugField,
     comp::{
         self,
         dialogue::Subject,
@@ -36,16 +37,20 @@ use common::{
     CachedSpatialGrid,
 };
 use common_base::{prof_span, span};
-use common_net::{msg::server::InviteAnswer, sync::WorldSyncExt};
+use common_net::{
+    msg::{server::InviteAnswer, world_msg::SiteKind},
+    sync::WorldSyncExt,
+};
 
 use crate::{
     audio::sfx::SfxEvent,
+    benchmark::benchmark_dir,
     cmd::run_command,
     error::Error,
     game_input::GameInput,
     hud::{
-        AutoPressBehavior, DebugInfo, Event as HudEvent, Hud, HudCollectFailedReason, HudInfo,
-        LootMessage, PromptDialogSettings,
+        map_annotation::decode_shared, AutoPressBehavior, DebugInfo, Event as HudEvent, Hud,
+        HudCollectFailedReason, HudInfo, LootMessage, PromptDialogSettings,
     },
     key_state::KeyState,
     menu::char_selection::CharSelectionState,
@@ -53,8 +58,9 @@ use crate::{
     scene::{camera, CameraMode, DebugShapeId, Scene, SceneData},
     session::target::ray_entities,
     settings::Settings,
+    telemetry::SystemInfo,
     window::{AnalogGameInput, Event},
-    Direction, GlobalState, PlayState, PlayStateResult,
+    Direction, GlobalState, PlayState, PlayStateResult, ResumeSession,
 };
 use hashbrown::HashMap;
 use interactable::{select_interactable, BlockInteraction, Interactable};
@@ -81,6 +87,26 @@ use voxygen_egui::EguiDebugInfo;
     is not being detected at a low enough scroll speed).
 */
 const ZOOM_LOCK_SCROLL_DELTA_INTENT: f32 = 14.0;
+/// Distance within which sites that are hidden on the map until discovered,
+/// such as climbing routes, get discovered
+const SITE_DISCOVERY_RANGE: f32 = 64.0;
+/// How long nearby interactables stay revealed after pressing the key, in
+/// seconds
+const REVEAL_INTERACTABLES_DURATION: f32 = 4.0;
+/// Blocks that can be selected for placing while building, on top of picking
+/// existing blocks
+const BUILD_PALETTE: [(BlockKind, [u8; 3]); 10] = [
+    (BlockKind::Rock, [110, 110, 110]),
+    (BlockKind::Rock, [170, 160, 140]),
+    (BlockKind::WeakRock, [90, 70, 60]),
+    (BlockKind::Earth, [100, 70, 40]),
+    (BlockKind::Grass, [70, 130, 40]),
+    (BlockKind::Sand, [220, 200, 140]),
+    (BlockKind::Wood, [120, 80, 40]),
+    (BlockKind::Wood, [190, 150, 100]),
+    (BlockKind::Leaves, [50, 110, 30]),
+    (BlockKind::GlowingRock, [230, 200, 120]),
+];
 
 /// The action to perform after a tick
 enum TickAction {
@@ -98,6 +124,37 @@ pub struct PlayerDebugLines {
     pub vel: Option<DebugShapeId>,
 }
 
+/// Remember the sites that only show up on the map once discovered when
+/// the player gets close to them.
+fn discover_sites(client: &Client, hud: &mut Hud, global_state: &mut GlobalState) {
+    let Some(pos) = client.position() else {
+        return;
+    };
+    let character_id = match client.presence() {
+        Some(PresenceKind::Character(id) | PresenceKind::LoadingCharacter(id)) => Some(id),
+        _ => None,
+    };
+    let mut discovered = false;
+    for site in client.sites().values().map(|site| &site.site) {
+        if matches!(site.kind, SiteKind::ClimbingRoute)
+            && site.wpos.as_::<f32>().distance_squared(pos.xy()) < SITE_DISCOVERY_RANGE.powi(2)
+            && global_state
+                .profile
+                .discover_site(&client.server_info().name, character_id, site.id)
+        {
+            hud.new_message(
+                ChatType::Meta.into_msg(Content::localized("hud-climbing_route-discovered")),
+            );
+            discovered = true;
+        }
+    }
+    if discovered {
+        global_state
+            .profile
+            .save_to_file_warn(&global_state.config_dir);
+    }
+}
+
 pub struct SessionState {
     scene: Scene,
     pub(crate) client: Rc<RefCell<Client>>,
@@ -107,6 +164,7 @@ pub struct SessionState {
     inputs: comp::ControllerInputs,
     inputs_state: HashSet<GameInput>,
     selected_block: Block,
+    build_palette_index: usize,
     walk_forward_dir: Vec2<f32>,
     walk_right_dir: Vec2<f32>,
     free_look: bool,
@@ -119,11 +177,15 @@ pub struct SessionState {
     pub(crate) selected_entity: Option<(specs::Entity, std::time::Instant)>,
     pub(crate) viewpoint_entity: Option<specs::Entity>,
     interactable: Option<Interactable>,
+    /// Seconds left to reveal all nearby interactables for
+    interactables_reveal: f32,
     #[cfg(not(target_os = "macos"))]
     mumble_link: SharedLink,
     hitboxes: HashMap<specs::Entity, DebugShapeId>,
     lines: PlayerDebugLines,
     tracks: HashMap<Vec2<i32>, Vec<DebugShapeId>>,
+    race_ghost: Option<DebugShapeId>,
+    worldgen_debug: HashMap<WorldgenDebugField, Vec<DebugShapeId>>,
 }
 
 /// Represents an active game session (i.e., the one being played).
@@ -179,6 +241,7 @@ impl SessionState {
             inputs_state: HashSet::new(),
             hud,
             selected_block: Block::new(BlockKind::Misc, Rgb::broadcast(255)),
+            build_palette_index: 0,
             walk_forward_dir,
             walk_right_dir,
             free_look: false,
@@ -191,11 +254,14 @@ impl SessionState {
             selected_entity: None,
             viewpoint_entity: None,
             interactable: None,
+            interactables_reveal: 0.0,
             #[cfg(not(target_os = "macos"))]
             mumble_link,
             hitboxes: HashMap::new(),
             metadata,
             tracks: HashMap::new(),
+            race_ghost: None,
+            worldgen_debug: HashMap::new(),
             lines: Default::default(),
         }
     }
@@ -232,6 +298,17 @@ impl SessionState {
             .unwrap_or_else(|| (self.client.borrow().entity(), true))
     }
 
+    fn is_spectator(&self) -> bool {
+        self.client.borrow().presence() == Some(PresenceKind::Spectator)
+    }
+
+    /// Whether the player is dead and can watch their group members until
+    /// they respawn.
+    fn is_group_spectating(&self) -> bool {
+        let client = self.client.borrow();
+        !self.is_spectator() && client.is_dead() && !client.group_members().is_empty()
+    }
+
     /// Tick the session (and the client attached to it).
     fn tick(
         &mut self,
@@ -249,6 +326,9 @@ impl SessionState {
             &mut self.tracks,
         );
         self.scene.maintain_debug_vectors(&client, &mut self.lines);
+        self.scene
+            .maintain_race_ghost(&client, &mut self.race_ghost);
+        discover_sites(&client, &mut self.hud, global_state);
 
         // All this camera code is just to determine if it's underwater for the sfx
         // filter
@@ -288,7 +368,17 @@ impl SessionState {
         for event in client.tick(self.inputs.clone(), dt)? {
             match event {
                 client::Event::Chat(m) => {
-                    self.hud.new_message(m);
+                    // Annotations shared by group members are added to the map rather than
+                    // shown in the chat
+                    if let comp::ChatType::Group(from, _) = &m.chat_type
+                        && let Some(annotation) = m.content().as_plain().and_then(decode_shared)
+                    {
+                        if client.uid() != Some(*from) {
+                            self.hud.show.add_group_annotation(*from, annotation);
+                        }
+                    } else {
+                        self.hud.new_message(m);
+                    }
                 },
                 client::Event::GroupInventoryUpdate(item, uid) => {
                     self.hud.new_loot_message(LootMessage {
@@ -307,6 +397,7 @@ impl SessionState {
                     let kind_str = match kind {
                         InviteKind::Group => "Group",
                         InviteKind::Trade => "Trade",
+                        InviteKind::Guild => "Guild",
                     };
                     let target_name = match client.player_list().get(&target) {
                         Some(info) => info.player_alias.clone(),
@@ -331,6 +422,23 @@ impl SessionState {
                 },
                 client::Event::TradeComplete { result, trade: _ } => {
                     self.hud.clear_cursor();
+                    let summary = self.hud.take_trade_summary();
+                    if let (TradeResult::Completed, Some(record)) = (&result, summary) {
+                        let character_id = match client.presence() {
+                            Some(
+                                PresenceKind::Character(id) | PresenceKind::LoadingCharacter(id),
+                            ) => Some(id),
+                            _ => None,
+                        };
+                        global_state.profile.push_trade_record(
+                            &client.server_info().name,
+                            character_id,
+                            record,
+                        );
+                        global_state
+                            .profile
+                            .save_to_file_warn(&global_state.config_dir);
+                    }
                     self.hud
                         .new_message(ChatType::Meta.into_msg(Content::localized(match result {
                             TradeResult::Completed => "hud-trade-result-completed",
@@ -429,6 +537,9 @@ impl SessionState {
                 client::Event::MapMarker(event) => {
                     self.hud.show.update_map_markers(event);
                 },
+                client::Event::ScoutPings(pings) => {
+                    self.hud.show.add_scout_pings(pings);
+                },
                 client::Event::StartSpectate(spawn_point) => {
                     let server_name = &client.server_info().name;
                     let spawn_point = global_state
@@ -451,6 +562,22 @@ impl SessionState {
                 client::Event::PluginDataReceived(data) => {
                     tracing::warn!("Received plugin data at wrong time {}", data.len());
                 },
+                client::Event::WorldgenDebug(field, overlay) => {
+                    self.scene
+                        .set_worldgen_debug_overlay(field, overlay, &mut self.worldgen_debug);
+                },
+                client::Event::OpenBank(banker) => self.hud.open_bank(banker),
+                client::Event::OpenTrainer(trainer) => self.hud.open_trainer(trainer),
+                client::Event::LootRollEnded { item, winner } => {
+                    self.hud.new_loot_roll_result(&client, &item, winner);
+                },
+                client::Event::TreasureMap(snippet) => {
+                    self.hud.show_treasure_map(&snippet);
+                },
+                client::Event::OpenNoticeBoard => self.hud.show_notice_board(),
+                client::Event::MinigameEnded(scoreboard) => {
+                    self.hud.new_minigame_result(&client, &scoreboard);
+                },
             }
         }
 
@@ -657,13 +784,22 @@ impl PlayState for SessionState {
 
             if presence == PresenceKind::Spectator {
                 let mut client = self.client.borrow_mut();
-                if client.spectate_position(cam_pos) {
+                if client.spectate_position(cam_pos) && global_state.benchmark.is_none() {
                     let server_name = &client.server_info().name;
                     global_state.profile.set_spectate_position(
                         server_name,
                         Some(self.scene.camera().get_focus_pos()),
                     );
                 }
+            } else if let Some(viewpoint_entity) = self.viewpoint_entity {
+                // Follow the watched group member so that their surroundings stay loaded
+                let mut client = self.client.borrow_mut();
+                let member_pos = client
+                    .state()
+                    .read_component_copied::<Pos>(viewpoint_entity);
+                if let Some(member_pos) = member_pos {
+                    client.spectate_position(member_pos.0);
+                }
             }
 
             // Nearest block to consider with GameInput primary or secondary key.
@@ -844,6 +980,11 @@ impl PlayState for SessionState {
                                     self.client.borrow_mut().utter(UtteranceKind::Greeting);
                                 }
                             },
+                            GameInput::ShowInteractables => {
+                                if state {
+                                    self.interactables_reveal = REVEAL_INTERACTABLES_DURATION;
+                                }
+                            },
                             GameInput::Sneak => {
                                 let is_trading = self.client.borrow().is_trading();
                                 if state && !is_trading {
@@ -1019,6 +1160,39 @@ impl PlayState for SessionState {
                                     client.set_pet_stay(pet_entity, !is_staying);
                                 }
                             },
+                            GameInput::Scout if state => {
+                                let mut client = self.client.borrow_mut();
+                                let player_pos = client
+                                    .state()
+                                    .read_storage::<Pos>()
+                                    .get(client.entity())
+                                    .copied();
+
+                                // Birds riding on the player's shoulder can be sent out too
+                                let scout = player_pos.and_then(|player_pos| {
+                                    let ecs = client.state().ecs();
+                                    let positions = ecs.read_storage::<Pos>();
+                                    let alignments = ecs.read_storage::<comp::Alignment>();
+                                    let bodies = ecs.read_storage::<comp::Body>();
+                                    ecs.read_resource::<CachedSpatialGrid>()
+                                        .0
+                                        .in_circle_aabr(player_pos.0.xy(), MAX_MOUNT_RANGE)
+                                        .filter(|e| {
+                                            matches!(alignments.get(*e),
+                                                Some(comp::Alignment::Owned(owner)) if Some(*owner) == client.uid())
+                                                && bodies.get(*e).map_or(false, comp::pet::can_scout)
+                                        })
+                                        .min_by_key(|e| {
+                                            OrderedFloat(positions.get(*e).map_or(
+                                                MAX_MOUNT_RANGE.powi(2),
+                                                |pos| player_pos.0.distance_squared(pos.0),
+                                            ))
+                                        })
+                                });
+                                if let Some(pet_entity) = scout {
+                                    client.send_scout(pet_entity);
+                                }
+                            },
                             GameInput::Interact => {
                                 if state {
                                     let mut client = self.client.borrow_mut();
@@ -1068,6 +1242,19 @@ impl PlayState for SessionState {
                                                     BlockInteraction::LightToggle(enable) => {
                                                         client.toggle_sprite_light(*pos, *enable);
                                                     },
+                                                    BlockInteraction::NoticeBoard => {
+                                                        if let common::mounting::Volume::Terrain =
+                                                            pos.kind
+                                                        {
+                                                            client.read_notice_board(pos.pos);
+                                                        }
+                                                    },
+                                                    BlockInteraction::GuildBoard => {
+                                                        self.hud.open_guild_hall(pos.pos)
+                                                    },
+                                                    BlockInteraction::GuildStorage => {
+                                                        self.hud.open_guild_storage()
+                                                    },
                                                 }
                                             },
                                             Interactable::Entity(entity) => {
@@ -1114,6 +1301,50 @@ impl PlayState for SessionState {
                                                     .flatten()
                                                 {
                                                     client.activate_portal(portal_uid);
+                                                } else if let Some(shrine_uid) = client
+                                                    .state()
+                                                    .read_storage::<comp::Object>()
+                                                    .get(*entity)
+                                                    .filter(|object| {
+                                                        matches!(
+                                                            object,
+                                                            comp::Object::Shrine { .. }
+                                                        )
+                                                    })
+                                                    .and_then(|_| {
+                                                        client
+                                                            .state()
+                                                            .ecs()
+                                                            .uid_from_entity(*entity)
+                                                    })
+                                                {
+                                                    client.activate_shrine(shrine_uid);
+                                                    self.hud.show.map(true);
+                                                } else if let Some(gravestone_uid) = client
+                                                    .state()
+                                                    .read_storage::<comp::Object>()
+                                                    .get(*entity)
+                                                    .filter(|object| {
+                                                        matches!(
+                                                            object,
+                                                            comp::Object::Gravestone { .. }
+                                                        )
+                                                    })
+                                                    .and_then(|_| {
+                                                        client
+                                                            .state()
+                                                            .ecs()
+                                                            .uid_from_entity(*entity)
+                                                    })
+                                                {
+                                                    client.recover_gravestone(gravestone_uid);
+                                                } else if client
+                                                    .state()
+                                                    .read_storage::<comp::CharacterState>()
+                                                    .get(*entity)
+                                                    .map_or(false, |cs| cs.is_downed())
+                                                {
+                                                    client.revive(*entity);
                                                 } else if pettable {
                                                     client.do_pet(*entity);
                                                 } else {
@@ -1160,6 +1391,20 @@ impl PlayState for SessionState {
                                     }
                                 }
                             },
+                            GameInput::AskDirections if state => {
+                                if let Some(Interactable::Entity(entity)) = &self.interactable
+                                    && matches!(
+                                        self.client
+                                            .borrow()
+                                            .state()
+                                            .read_storage::<comp::Alignment>()
+                                            .get(*entity),
+                                        Some(comp::Alignment::Npc)
+                                    )
+                                {
+                                    self.hud.ask_directions(*entity);
+                                }
+                            },
                             GameInput::FreeLook => {
                                 let hud = &mut self.hud;
                                 global_state.settings.gameplay.free_look_behavior.update(
@@ -1253,6 +1498,14 @@ impl PlayState for SessionState {
                                     client.decline_invite();
                                 }
                             },
+                            GameInput::SpectateViewpoint if state && self.is_group_spectating() => {
+                                // Dead players cycle through their group members and end up back
+                                // at their own body, there is no free camera for them
+                                let client = self.client.borrow();
+                                self.viewpoint_entity =
+                                    client.next_group_spectate_target(self.viewpoint_entity);
+                                self.scene.camera_mut().set_mode(CameraMode::ThirdPerson);
+                            },
                             GameInput::SpectateViewpoint if state => {
                                 if self.viewpoint_entity.is_some() {
                                     self.viewpoint_entity = None;
@@ -1276,6 +1529,16 @@ impl PlayState for SessionState {
                                     }
                                 }
                             },
+                            GameInput::CycleBuildPalette if state && can_build => {
+                                let (kind, color) = BUILD_PALETTE[self.build_palette_index];
+                                self.build_palette_index =
+                                    (self.build_palette_index + 1) % BUILD_PALETTE.len();
+                                self.selected_block = Block::new(kind, Rgb::from(color));
+                                self.hud.new_message(
+                                    ChatType::CommandInfo
+                                        .into_plain_msg(format!("Building with {kind:?}")),
+                                );
+                            },
                             GameInput::ToggleWalk if state => {
                                 global_state
                                     .settings
@@ -1330,7 +1593,31 @@ impl PlayState for SessionState {
                     .contains(entity)
             }) {
                 self.viewpoint_entity = None;
-                self.scene.camera_mut().set_mode(CameraMode::Freefly);
+                let camera_mode = if self.is_spectator() {
+                    CameraMode::Freefly
+                } else {
+                    CameraMode::ThirdPerson
+                };
+                self.scene.camera_mut().set_mode(camera_mode);
+            }
+
+            // Stop watching the group once respawned, or move on when the watched member
+            // died
+            if let Some(entity) = self.viewpoint_entity
+                && !self.is_spectator()
+            {
+                let client = self.client.borrow();
+                if !client.is_dead() {
+                    self.viewpoint_entity = None;
+                } else if client
+                    .state()
+                    .ecs()
+                    .read_storage::<comp::Health>()
+                    .get(entity)
+                    .map_or(true, |health| health.is_dead)
+                {
+                    self.viewpoint_entity = client.next_group_spectate_target(Some(entity));
+                }
             }
 
             let (viewpoint_entity, mutable_viewpoint) = self.viewpoint_entity();
@@ -1339,6 +1626,7 @@ impl PlayState for SessionState {
             let input_vec = self.key_state.dir_vec();
             let (axis_right, axis_up) = (input_vec[0], input_vec[1]);
             let dt = global_state.clock.get_stable_dt().as_secs_f32();
+            self.interactables_reveal = (self.interactables_reveal - dt).max(0.0);
 
             if mutable_viewpoint {
                 // If auto-gliding, point camera into the wind
@@ -1524,6 +1812,21 @@ impl PlayState for SessionState {
                 },
             };
 
+            if let Some(benchmark) = &mut global_state.benchmark
+                && benchmark.update(
+                    &mut self.client.borrow_mut(),
+                    self.scene.camera_mut(),
+                    global_state.clock.dt(),
+                )
+            {
+                benchmark.report(
+                    &benchmark_dir(&global_state.userdata_dir),
+                    SystemInfo::new(Some(global_state.window.renderer().adapter_info().into())),
+                    &global_state.settings.graphics,
+                );
+                return PlayStateResult::Shutdown;
+            }
+
             let mut outcomes = Vec::new();
 
             // Runs if either in a multiplayer server or the singleplayer server is unpaused
@@ -1546,6 +1849,19 @@ impl PlayState for SessionState {
                         );
                         error!("[session] Failed to tick the scene: {:?}", err);
 
+                        // Logging back in shortly after allows continuing where we left off
+                        let client = self.client.borrow();
+                        global_state.resume_session =
+                            client.resume_token().map(|token| ResumeSession {
+                                server_address: global_state
+                                    .settings
+                                    .networking
+                                    .default_server
+                                    .clone(),
+                                username: global_state.settings.networking.username.clone(),
+                                token,
+                            });
+
                         return PlayStateResult::Pop;
                     },
                 }
@@ -1608,6 +1924,7 @@ impl PlayState for SessionState {
                     num_lights: self.scene.lights().len() as u32,
                     num_visible_chunks: self.scene.terrain().visible_chunk_count() as u32,
                     num_shadow_chunks: self.scene.terrain().shadow_chunk_count() as u32,
+                    terrain_memory: self.scene.terrain().memory_stats(),
                     num_figures: self.scene.figure_mgr().figure_count() as u32,
                     num_figures_visible: self.scene.figure_mgr().figure_count_visible() as u32,
                     num_particles: self.scene.particle_mgr().particle_count() as u32,
@@ -1713,6 +2030,22 @@ impl PlayState for SessionState {
                         self.client.borrow_mut().remove_buff(buff_id);
                     },
                     HudEvent::LeaveStance => self.client.borrow_mut().leave_stance(),
+                    HudEvent::Emote(kind) => {
+                        self.stop_auto_walk();
+                        self.client.borrow_mut().emote(kind);
+                    },
+                    HudEvent::BankDeposit { banker, slot } => {
+                        self.client.borrow_mut().bank_deposit(banker, slot);
+                    },
+                    HudEvent::BankWithdraw { banker, slot } => {
+                        self.client.borrow_mut().bank_withdraw(banker, slot);
+                    },
+                    HudEvent::BankUpgrade(banker) => {
+                        self.client.borrow_mut().bank_upgrade(banker);
+                    },
+                    HudEvent::LearnSkill { trainer, skill } => {
+                        self.client.borrow_mut().learn_skill(trainer, skill);
+                    },
                     HudEvent::UnlockSkill(skill) => {
                         self.client.borrow_mut().unlock_skill(skill);
                     },
@@ -1955,9 +2288,33 @@ impl PlayState for SessionState {
 
                         info!("Event! -> ChangedHotbarState")
                     },
+                    HudEvent::ChangeQuickBarState(state) => {
+                        let client = self.client.borrow();
+
+                        let server_name = &client.server_info().name;
+                        let character_id = match client.presence().unwrap() {
+                            PresenceKind::Character(id) => Some(id),
+                            PresenceKind::LoadingCharacter(id) => Some(id),
+                            PresenceKind::Spectator => {
+                                unreachable!("HUD adaption in Spectator mode!")
+                            },
+                            PresenceKind::Possessor => None,
+                        };
+
+                        global_state.profile.set_quick_bar_slots(
+                            server_name,
+                            character_id,
+                            state.slots,
+                        );
+
+                        global_state
+                            .profile
+                            .save_to_file_warn(&global_state.config_dir);
+                    },
                     HudEvent::TradeAction(action) => {
                         self.client.borrow_mut().perform_trade_action(action);
                     },
+                    HudEvent::Haggle => self.client.borrow_mut().haggle(),
                     HudEvent::Ability(i, state) => {
                         self.client.borrow_mut().handle_input(
                             InputKind::Ability(i),
@@ -1970,6 +2327,21 @@ impl PlayState for SessionState {
                     HudEvent::RequestSiteInfo(id) => {
                         self.client.borrow_mut().request_site_economy(id);
                     },
+                    HudEvent::RequestReputation => {
+                        self.client.borrow_mut().request_reputation();
+                    },
+                    HudEvent::TravelToShrine(site) => {
+                        self.client.borrow_mut().travel_to_shrine(site);
+                    },
+                    HudEvent::AskDirections(npc, site) => {
+                        self.client.borrow_mut().ask_directions(npc, site);
+                    },
+                    HudEvent::AcceptBounty(target) => {
+                        self.client.borrow_mut().accept_bounty(target);
+                    },
+                    HudEvent::CloseNoticeBoard => {
+                        self.client.borrow_mut().close_notice_board();
+                    },
 
                     HudEvent::CraftRecipe {
                         recipe_name: recipe,
@@ -2100,6 +2472,18 @@ impl PlayState for SessionState {
                     HudEvent::InviteMember(uid) => {
                         self.client.borrow_mut().send_invite(uid, InviteKind::Group);
                     },
+                    HudEvent::InviteToGuild(uid) => {
+                        self.client.borrow_mut().send_invite(uid, InviteKind::Guild);
+                    },
+                    HudEvent::GuildManip(manip) => {
+                        self.client.borrow_mut().guild_manip(manip);
+                    },
+                    HudEvent::SetHeraldry(slot, heraldry) => {
+                        self.client.borrow_mut().set_heraldry(slot, heraldry);
+                    },
+                    HudEvent::SelectTitle(title) => {
+                        self.client.borrow_mut().select_title(title);
+                    },
                     HudEvent::AcceptInvite => {
                         self.client.borrow_mut().accept_invite();
                     },
@@ -2115,9 +2499,18 @@ impl PlayState for SessionState {
                     HudEvent::AssignLeader(uid) => {
                         self.client.borrow_mut().assign_group_leader(uid);
                     },
+                    HudEvent::SetGroupLootMode(loot_mode) => {
+                        self.client.borrow_mut().set_group_loot_mode(loot_mode);
+                    },
+                    HudEvent::LootRoll(id, choice) => {
+                        self.client.borrow_mut().choose_loot_roll(id, choice);
+                    },
                     HudEvent::ChangeAbility(slot, new_ability) => {
                         self.client.borrow_mut().change_ability(slot, new_ability);
                     },
+                    HudEvent::SwitchAbilityLoadout(loadout) => {
+                        self.client.borrow_mut().switch_ability_loadout(loadout);
+                    },
                     HudEvent::SettingsChange(settings_change) => {
                         settings_change.process(global_state, self);
                     },
@@ -2127,6 +2520,26 @@ impl PlayState for SessionState {
                     HudEvent::MapMarkerEvent(event) => {
                         self.client.borrow_mut().map_marker_event(event);
                     },
+                    HudEvent::AddMapAnnotation(annotation) => {
+                        let client = self.client.borrow();
+                        global_state
+                            .profile
+                            .map_annotations_mut(&client.server_info().name)
+                            .push(annotation);
+                        global_state
+                            .profile
+                            .save_to_file_warn(&global_state.config_dir);
+                    },
+                    HudEvent::UndoMapAnnotation => {
+                        let client = self.client.borrow();
+                        global_state
+                            .profile
+                            .map_annotations_mut(&client.server_info().name)
+                            .pop();
+                        global_state
+                            .profile
+                            .save_to_file_warn(&global_state.config_dir);
+                    },
                 }
             }
 
@@ -2154,6 +2567,7 @@ impl PlayState for SessionState {
                         as f32,
                     particles_enabled: global_state.settings.graphics.particles_enabled,
                     weapon_trails_enabled: global_state.settings.graphics.weapon_trails_enabled,
+                    cloth_physics_enabled: global_state.settings.graphics.cloth_physics_enabled,
                     flashing_lights_enabled: global_state
                         .settings
                         .graphics
@@ -2164,8 +2578,13 @@ impl PlayState for SessionState {
                         .graphics
                         .figure_lod_render_distance
                         as f32,
+                    terrain_memory_budget: u64::from(
+                        global_state.settings.graphics.terrain_memory_budget,
+                    ) << 20,
                     is_aiming,
                     interpolated_time_of_day: self.scene.interpolated_time_of_day,
+                    interact_highlight: global_state.settings.gameplay.interact_highlight,
+                    interact_reveal: self.interactables_reveal.min(1.0),
                 };
 
                 // Runs if either in a multiplayer server or the singleplayer server is unpaused
@@ -2244,11 +2663,15 @@ impl PlayState for SessionState {
             mouse_smoothing: settings.gameplay.smooth_pan_enable,
             sprite_render_distance: settings.graphics.sprite_render_distance as f32,
             figure_lod_render_distance: settings.graphics.figure_lod_render_distance as f32,
+            terrain_memory_budget: u64::from(settings.graphics.terrain_memory_budget) << 20,
             particles_enabled: settings.graphics.particles_enabled,
             weapon_trails_enabled: settings.graphics.weapon_trails_enabled,
+            cloth_physics_enabled: settings.graphics.cloth_physics_enabled,
             flashing_lights_enabled: settings.graphics.render_mode.flashing_lights_enabled,
             is_aiming: self.is_aiming,
             interpolated_time_of_day: self.scene.interpolated_time_of_day,
+            interact_highlight: settings.gameplay.interact_highlight,
+            interact_reveal: self.interactables_reveal.min(1.0),
         };
 
         // Render world
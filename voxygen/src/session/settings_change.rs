@@ -11,9 +11,11 @@ use crate::{
         audio::AudioVolume, AudioSettings, ChatSettings, ControlSettings, Fps, GamepadSettings,
         GameplaySettings, GraphicsSettings, InterfaceSettings,
     },
+    telemetry::Telemetry,
     window::{FullScreenSettings, Window},
     GlobalState,
 };
+use common::comp::BuffKind;
 use i18n::{LanguageMetadata, LocalizationHandle};
 use std::rc::Rc;
 
@@ -78,6 +80,7 @@ pub enum Gameplay {
 
     AdjustAimOffsetX(f32),
     AdjustAimOffsetY(f32),
+    AdjustInteractHighlight(f32),
 
     ResetGameplaySettings,
 }
@@ -103,6 +106,7 @@ pub enum Graphics {
     ChangeFullscreenMode(FullScreenSettings),
     ToggleParticlesEnabled(bool),
     ToggleWeaponTrailsEnabled(bool),
+    ToggleClothPhysicsEnabled(bool),
     AdjustWindowSize([u16; 2]),
 
     ResetGraphicsSettings,
@@ -118,6 +122,10 @@ pub enum Interface {
     SpeechBubbleSelf(bool),
     SpeechBubbleDarkMode(bool),
     SpeechBubbleIcon(bool),
+    NameplateHostilesOnly(bool),
+    NameplateHideFullHealth(bool),
+    NameplateScaleByDistance(bool),
+    NameplateShowTags(bool),
     ToggleHelp(bool),
     ToggleDebug(bool),
     ToggleHitboxes(bool),
@@ -134,6 +142,7 @@ pub enum Interface {
     TogglePoiseBar(bool),
     ToggleShortcutNumbers(ShortcutNumbers),
     BuffPosition(BuffPosition),
+    ToggleBuffPin(BuffKind),
 
     UiScale(ScaleChange),
     //Minimap
@@ -174,6 +183,7 @@ pub enum Networking {
         server_authoritative: bool,
     },
     ToggleLossyTerrainCompression(bool),
+    ToggleTelemetry(bool),
 
     #[cfg(feature = "discord")]
     ToggleDiscordIntegration(bool),
@@ -432,6 +442,9 @@ impl SettingsChange {
                     Gameplay::AdjustAimOffsetY(offset) => {
                         settings.gameplay.aim_offset_y = offset;
                     },
+                    Gameplay::AdjustInteractHighlight(strength) => {
+                        settings.gameplay.interact_highlight = strength;
+                    },
                     Gameplay::ResetGameplaySettings => {
                         // Reset Gameplay Settings
                         settings.gameplay = GameplaySettings::default();
@@ -516,6 +529,9 @@ impl SettingsChange {
                     Graphics::ToggleWeaponTrailsEnabled(weapon_trails_enabled) => {
                         settings.graphics.weapon_trails_enabled = weapon_trails_enabled;
                     },
+                    Graphics::ToggleClothPhysicsEnabled(cloth_physics_enabled) => {
+                        settings.graphics.cloth_physics_enabled = cloth_physics_enabled;
+                    },
                     Graphics::AdjustWindowSize(new_size) => {
                         global_state.window.set_size(new_size.into());
                         settings.graphics.window_size = new_size;
@@ -589,6 +605,18 @@ impl SettingsChange {
                     Interface::SpeechBubbleIcon(sbi) => {
                         settings.interface.speech_bubble_icon = sbi;
                     },
+                    Interface::NameplateHostilesOnly(hostiles_only) => {
+                        settings.interface.nameplate_hostiles_only = hostiles_only;
+                    },
+                    Interface::NameplateHideFullHealth(hide_full_health) => {
+                        settings.interface.nameplate_hide_full_health = hide_full_health;
+                    },
+                    Interface::NameplateScaleByDistance(scale_by_distance) => {
+                        settings.interface.nameplate_scale_by_distance = scale_by_distance;
+                    },
+                    Interface::NameplateShowTags(show_tags) => {
+                        settings.interface.nameplate_show_tags = show_tags;
+                    },
                     Interface::ToggleHelp(_) => {
                         // implemented in hud
                     },
@@ -634,6 +662,14 @@ impl SettingsChange {
                     Interface::BuffPosition(buff_position) => {
                         settings.interface.buff_position = buff_position;
                     },
+                    Interface::ToggleBuffPin(kind) => {
+                        let pinned = &mut settings.interface.pinned_buffs;
+                        if let Some(i) = pinned.iter().position(|pinned| *pinned == kind) {
+                            pinned.remove(i);
+                        } else {
+                            pinned.push(kind);
+                        }
+                    },
                     Interface::UiScale(scale_change) => {
                         settings.interface.ui_scale = session_state.hud.scale_change(scale_change);
                     },
@@ -750,6 +786,15 @@ impl SettingsChange {
                         .borrow_mut()
                         .request_lossy_terrain_compression(lossy_terrain_compression);
                 },
+                Networking::ToggleTelemetry(enabled) => {
+                    settings.telemetry.enabled = enabled;
+                    global_state.telemetry = Telemetry::new(
+                        &settings.telemetry,
+                        &global_state.config_dir,
+                        Some(global_state.window.renderer().adapter_info().into()),
+                        &global_state.tokio_runtime,
+                    );
+                },
                 #[cfg(feature = "discord")]
                 Networking::ToggleDiscordIntegration(enabled) => {
                     use crate::discord::Discord;
@@ -11,6 +11,7 @@ pub const DEFAULT_CHAT_BOX_WIDTH: f64 = 470.0;
 pub const DEFAULT_CHAT_BOX_HEIGHT: f64 = 150.0;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct ChatFilter {
     //messages
     pub message_all: bool,
@@ -19,6 +20,7 @@ pub struct ChatFilter {
     pub message_say: bool,
     pub message_group: bool,
     pub message_faction: bool,
+    pub message_guild: bool,
     //activity (login/logout)
     pub activity_all: bool,
     pub activity_group: bool,
@@ -36,10 +38,12 @@ impl ChatFilter {
             ChatType::Kill(_, u) => self.death_all || self.death_group && group_members.contains(u),
             ChatType::GroupMeta(_) => true,   //todo
             ChatType::FactionMeta(_) => true, //todo
+            ChatType::GuildMeta(_) => true,
             ChatType::Tell(..) => true,
             ChatType::Say(_) => self.message_all || self.message_say,
             ChatType::Group(..) => self.message_all || self.message_group,
             ChatType::Faction(..) => self.message_all || self.message_faction,
+            ChatType::Guild(..) => self.message_all || self.message_guild,
             ChatType::Region(_) => self.message_all || self.message_region,
             ChatType::World(_) => self.message_all || self.message_world,
             ChatType::Npc(..) => true,
@@ -58,6 +62,7 @@ impl Default for ChatFilter {
             message_say: true,
             message_group: true,
             message_faction: true,
+            message_guild: true,
 
             activity_all: false,
             activity_group: true,
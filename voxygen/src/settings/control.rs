@@ -138,6 +138,9 @@ impl ControlSettings {
             GameInput::Jump => Some(KeyMouse::Key(VirtualKeyCode::Space)),
             GameInput::Sit => Some(KeyMouse::Key(VirtualKeyCode::K)),
             GameInput::Dance => Some(KeyMouse::Key(VirtualKeyCode::J)),
+            GameInput::EmoteWheel => Some(KeyMouse::Key(VirtualKeyCode::U)),
+            GameInput::QuickActionWheel => None,
+            GameInput::ShowInteractables => None,
             GameInput::Greet => Some(KeyMouse::Key(VirtualKeyCode::H)),
             GameInput::Glide => Some(KeyMouse::Key(VirtualKeyCode::LControl)),
             GameInput::Climb => Some(KeyMouse::Key(VirtualKeyCode::Space)),
@@ -149,10 +152,14 @@ impl ControlSettings {
             GameInput::ToggleLantern => Some(KeyMouse::Key(VirtualKeyCode::G)),
             GameInput::Mount => Some(KeyMouse::Key(VirtualKeyCode::F)),
             GameInput::StayFollow => Some(KeyMouse::Key(VirtualKeyCode::V)),
+            GameInput::Scout => None,
             GameInput::Map => Some(KeyMouse::Key(VirtualKeyCode::M)),
             GameInput::Bag => Some(KeyMouse::Key(VirtualKeyCode::B)),
             GameInput::Trade => Some(KeyMouse::Key(VirtualKeyCode::T)),
+            GameInput::AskDirections => Some(KeyMouse::Key(VirtualKeyCode::Backslash)),
             GameInput::Social => Some(KeyMouse::Key(VirtualKeyCode::O)),
+            GameInput::QuestLog => Some(KeyMouse::Key(VirtualKeyCode::Semicolon)),
+            GameInput::Calendar => Some(KeyMouse::Key(VirtualKeyCode::F9)),
             GameInput::Crafting => Some(KeyMouse::Key(VirtualKeyCode::C)),
             GameInput::Spellbook => Some(KeyMouse::Key(VirtualKeyCode::P)),
             GameInput::Settings => Some(KeyMouse::Key(VirtualKeyCode::F10)),
@@ -201,6 +208,7 @@ impl ControlSettings {
             GameInput::MuteSfx => None,
             GameInput::MuteAmbience => None,
             GameInput::ToggleWalk => Some(KeyMouse::Key(VirtualKeyCode::I)),
+            GameInput::CycleBuildPalette => Some(KeyMouse::Key(VirtualKeyCode::Z)),
         }
     }
 }
@@ -96,6 +96,9 @@ pub mod con_settings {
         pub jump: LayerEntry,
         pub sit: LayerEntry,
         pub dance: LayerEntry,
+        pub emote_wheel: LayerEntry,
+        pub quick_action_wheel: LayerEntry,
+        pub show_interactables: LayerEntry,
         pub glide: LayerEntry,
         pub climb: LayerEntry,
         pub climb_down: LayerEntry,
@@ -211,6 +214,21 @@ pub mod con_settings {
                     mod1: Button::Simple(GilButton::LeftTrigger),
                     mod2: Button::Simple(GilButton::Unknown),
                 },
+                emote_wheel: LayerEntry {
+                    button: Button::Simple(GilButton::DPadDown),
+                    mod1: Button::Simple(GilButton::LeftTrigger),
+                    mod2: Button::Simple(GilButton::Unknown),
+                },
+                quick_action_wheel: LayerEntry {
+                    button: Button::Simple(GilButton::North),
+                    mod1: Button::Simple(GilButton::LeftTrigger),
+                    mod2: Button::Simple(GilButton::Unknown),
+                },
+                show_interactables: LayerEntry {
+                    button: Button::Simple(GilButton::Unknown),
+                    mod1: Button::Simple(GilButton::Unknown),
+                    mod2: Button::Simple(GilButton::Unknown),
+                },
                 glide: LayerEntry {
                     button: Button::Simple(GilButton::DPadUp),
                     mod1: Button::Simple(GilButton::Unknown),
@@ -312,6 +330,9 @@ pub mod con_settings {
         pub jump: Button,
         pub sit: Button,
         pub dance: Button,
+        pub emote_wheel: Button,
+        pub quick_action_wheel: Button,
+        pub show_interactables: Button,
         pub glide: Button,
         pub climb: Button,
         pub climb_down: Button,
@@ -417,6 +438,9 @@ pub mod con_settings {
                 jump: Button::Simple(GilButton::South),
                 sit: Button::Simple(GilButton::Unknown),
                 dance: Button::Simple(GilButton::Unknown),
+                emote_wheel: Button::Simple(GilButton::Unknown),
+                quick_action_wheel: Button::Simple(GilButton::Unknown),
+                show_interactables: Button::Simple(GilButton::Unknown),
                 glide: Button::Simple(GilButton::Unknown),
                 climb: Button::Simple(GilButton::South),
                 climb_down: Button::Simple(GilButton::West),
@@ -23,6 +23,8 @@ pub struct GameplaySettings {
     pub zoom_lock: bool,
     pub aim_offset_x: f32,
     pub aim_offset_y: f32,
+    /// Brightness of the highlight on the targeted interactable, 0 disables it
+    pub interact_highlight: f32,
 }
 
 impl Default for GameplaySettings {
@@ -46,6 +48,7 @@ impl Default for GameplaySettings {
             zoom_lock: false,
             aim_offset_x: 0.6,
             aim_offset_y: 0.0,
+            interact_highlight: 0.5,
         }
     }
 }
@@ -35,6 +35,7 @@ pub struct GraphicsSettings {
     pub sprite_render_distance: u32,
     pub particles_enabled: bool,
     pub weapon_trails_enabled: bool,
+    pub cloth_physics_enabled: bool,
     pub figure_lod_render_distance: u32,
     pub max_fps: Fps,
     pub max_background_fps: Fps,
@@ -46,6 +47,9 @@ pub struct GraphicsSettings {
     pub window_size: [u16; 2],
     pub fullscreen: FullScreenSettings,
     pub lod_detail: u32,
+    /// GPU memory terrain meshes may use before far away chunks are evicted,
+    /// in MiB
+    pub terrain_memory_budget: u32,
 }
 
 impl Default for GraphicsSettings {
@@ -57,6 +61,7 @@ impl Default for GraphicsSettings {
             sprite_render_distance: 100,
             particles_enabled: true,
             weapon_trails_enabled: true,
+            cloth_physics_enabled: true,
             figure_lod_render_distance: 300,
             max_fps: Fps::Max(60),
             max_background_fps: Fps::Max(30),
@@ -68,6 +73,7 @@ impl Default for GraphicsSettings {
             window_size: [1280, 720],
             fullscreen: FullScreenSettings::default(),
             lod_detail: 250,
+            terrain_memory_budget: 1536,
         }
     }
 }
@@ -81,6 +87,7 @@ impl GraphicsSettings {
             lod_distance: 0,
             sprite_render_distance: 80,
             figure_lod_render_distance: 100,
+            cloth_physics_enabled: false,
             lod_detail: 80,
             render_mode: RenderMode {
                 aa: AaMode::FxUpscale,
@@ -107,6 +114,7 @@ impl GraphicsSettings {
             lod_distance: 75,
             sprite_render_distance: 125,
             figure_lod_render_distance: 200,
+            cloth_physics_enabled: true,
             lod_detail: 200,
             render_mode: RenderMode {
                 aa: AaMode::FxUpscale,
@@ -133,6 +141,7 @@ impl GraphicsSettings {
             lod_distance: 150,
             sprite_render_distance: 250,
             figure_lod_render_distance: 350,
+            cloth_physics_enabled: true,
             lod_detail: 300,
             render_mode: RenderMode {
                 aa: AaMode::Fxaa,
@@ -141,6 +150,7 @@ impl GraphicsSettings {
                 fluid: FluidMode::Medium,
                 lighting: LightingMode::BlinnPhong,
                 shadow: ShadowMode::Map(ShadowMapMode { resolution: 0.75 }),
+                point_shadows: 1,
                 rain_occlusion: ShadowMapMode { resolution: 0.25 },
                 bloom: BloomMode::On(BloomConfig {
                     factor: BloomFactor::Medium,
@@ -162,6 +172,7 @@ impl GraphicsSettings {
             lod_distance: 200,
             sprite_render_distance: 350,
             figure_lod_render_distance: 450,
+            cloth_physics_enabled: true,
             lod_detail: 375,
             render_mode: RenderMode {
                 aa: AaMode::Fxaa,
@@ -170,6 +181,7 @@ impl GraphicsSettings {
                 fluid: FluidMode::Medium,
                 lighting: LightingMode::Ashikhmin,
                 shadow: ShadowMode::Map(ShadowMapMode { resolution: 1.0 }),
+                point_shadows: 2,
                 rain_occlusion: ShadowMapMode { resolution: 0.5 },
                 bloom: BloomMode::On(BloomConfig {
                     factor: BloomFactor::Medium,
@@ -191,6 +203,7 @@ impl GraphicsSettings {
             lod_distance: 450,
             sprite_render_distance: 800,
             figure_lod_render_distance: 600,
+            cloth_physics_enabled: true,
             lod_detail: 500,
             render_mode: RenderMode {
                 aa: AaMode::Fxaa,
@@ -199,6 +212,7 @@ impl GraphicsSettings {
                 fluid: FluidMode::High,
                 lighting: LightingMode::Ashikhmin,
                 shadow: ShadowMode::Map(ShadowMapMode { resolution: 1.75 }),
+                point_shadows: 4,
                 rain_occlusion: ShadowMapMode { resolution: 0.5 },
                 bloom: BloomMode::On(BloomConfig {
                     factor: BloomFactor::Medium,
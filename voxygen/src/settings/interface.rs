@@ -2,7 +2,7 @@ use crate::{
     hud::{BarNumbers, BuffPosition, CrosshairType, Intro, ShortcutNumbers, XpBar},
     ui::ScaleMode,
 };
-use common::comp::skillset::SkillGroupKind;
+use common::comp::{skillset::SkillGroupKind, BuffKind};
 use serde::{Deserialize, Serialize};
 
 /// `InterfaceSettings` contains UI, HUD and Map options.
@@ -24,12 +24,22 @@ pub struct InterfaceSettings {
     pub speech_bubble_self: bool,
     pub speech_bubble_dark_mode: bool,
     pub speech_bubble_icon: bool,
+    /// Only show nameplates of hostile entities, besides targeted ones and
+    /// group members
+    pub nameplate_hostiles_only: bool,
+    pub nameplate_hide_full_health: bool,
+    /// Shrink nameplates of distant entities
+    pub nameplate_scale_by_distance: bool,
+    /// Show the faction tags of players on their nameplates
+    pub nameplate_show_tags: bool,
     pub crosshair_opacity: f32,
     pub crosshair_type: CrosshairType,
     pub intro_show: Intro,
     pub xp_bar: XpBar,
     pub shortcut_numbers: ShortcutNumbers,
     pub buff_position: BuffPosition,
+    /// Buffs shown larger and apart from the others
+    pub pinned_buffs: Vec<BuffKind>,
     pub bar_numbers: BarNumbers,
     pub always_show_bars: bool,
     pub enable_poise_bar: bool,
@@ -72,12 +82,17 @@ impl Default for InterfaceSettings {
             speech_bubble_self: true,
             speech_bubble_dark_mode: false,
             speech_bubble_icon: true,
+            nameplate_hostiles_only: false,
+            nameplate_hide_full_health: true,
+            nameplate_scale_by_distance: false,
+            nameplate_show_tags: true,
             crosshair_opacity: 0.6,
             crosshair_type: CrosshairType::Round,
             intro_show: Intro::Show,
             xp_bar: XpBar::Always,
             shortcut_numbers: ShortcutNumbers::On,
             buff_position: BuffPosition::Bar,
+            pinned_buffs: Vec::new(),
             bar_numbers: BarNumbers::Values,
             always_show_bars: false,
             enable_poise_bar: false,
@@ -15,6 +15,7 @@ pub mod graphics;
 pub mod interface;
 pub mod language;
 pub mod networking;
+pub mod telemetry;
 
 pub use audio::{AudioOutput, AudioSettings};
 pub use chat::ChatSettings;
@@ -25,6 +26,7 @@ pub use graphics::{get_fps, Fps, GraphicsSettings};
 pub use interface::InterfaceSettings;
 pub use language::LanguageSettings;
 pub use networking::NetworkingSettings;
+pub use telemetry::TelemetrySettings;
 
 /// `Settings` contains everything that can be configured in the settings.ron
 /// file.
@@ -45,6 +47,7 @@ pub struct Settings {
     pub language: LanguageSettings,
     pub screenshots_path: PathBuf,
     pub controller: GamepadSettings,
+    pub telemetry: TelemetrySettings,
 }
 
 impl Default for Settings {
@@ -80,6 +83,7 @@ impl Default for Settings {
             language: LanguageSettings::default(),
             screenshots_path,
             controller: GamepadSettings::default(),
+            telemetry: TelemetrySettings::default(),
         }
     }
 }
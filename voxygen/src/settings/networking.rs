@@ -1,4 +1,5 @@
-use hashbrown::HashSet;
+use common_net::msg::WorldFingerprint;
+use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
 /// `NetworkingSettings` stores server and networking settings.
@@ -15,6 +16,11 @@ pub struct NetworkingSettings {
     pub player_physics_behavior: bool,
     pub lossy_terrain_compression: bool,
     pub enable_discord_integration: bool,
+    /// Fingerprint of the world of each server when it was last joined, used
+    /// to detect when a server's world changed.
+    pub world_fingerprints: HashMap<String, WorldFingerprint>,
+    /// Servers that are pinned to the top of the server list.
+    pub favorite_servers: HashSet<String>,
 }
 
 impl Default for NetworkingSettings {
@@ -33,6 +39,8 @@ impl Default for NetworkingSettings {
             player_physics_behavior: false,
             lossy_terrain_compression: false,
             enable_discord_integration: true,
+            world_fingerprints: HashMap::new(),
+            favorite_servers: HashSet::new(),
         }
     }
 }
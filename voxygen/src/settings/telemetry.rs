@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// `TelemetrySettings` controls the opt-in crash and performance reports.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    /// Whether crash and performance reports are written to the telemetry
+    /// directory.
+    pub enabled: bool,
+    /// Where reports are uploaded to when the game starts. If not set, they
+    /// are only kept locally.
+    pub upload_endpoint: Option<String>,
+}
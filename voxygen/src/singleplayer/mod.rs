@@ -62,6 +62,15 @@ impl SingleplayerState {
         Self::Init(SingleplayerWorlds::load(&dir))
     }
 
+    /// Prepares the default world in the benchmark directory.
+    pub fn benchmark() -> Self {
+        let dir = common_base::userdata_dir_workspace!();
+
+        Self::Init(SingleplayerWorlds::default_world(
+            crate::benchmark::benchmark_dir(&dir).join("world"),
+        ))
+    }
+
     pub fn run(&mut self, runtime: &Arc<Runtime>) {
         if let Self::Init(worlds) = self {
             let Some(world) = worlds.current() else {
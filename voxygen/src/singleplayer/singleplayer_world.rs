@@ -173,6 +173,31 @@ impl SingleplayerWorlds {
         }
     }
 
+    /// A copy of the default world in the given folder, used by the benchmark
+    /// so that it doesn't show up among the player's worlds.
+    pub fn default_world(folder: PathBuf) -> SingleplayerWorlds {
+        if let Err(e) = fs::create_dir_all(&folder) {
+            error!("Failed to create default world folder: {e}");
+        }
+
+        let world = SingleplayerWorld {
+            name: "Default World".to_string(),
+            gen_opts: None,
+            day_length: DAY_LENGTH_DEFAULT,
+            seed: DEFAULT_WORLD_SEED,
+            // Copied again on every start, so that it matches this version
+            is_generated: false,
+            map_path: folder.join("map.bin"),
+            path: folder.clone(),
+        };
+
+        SingleplayerWorlds {
+            worlds: vec![world],
+            current: Some(0),
+            worlds_folder: folder,
+        }
+    }
+
     pub fn delete_map_file(&mut self, map: usize) {
         let w = &mut self.worlds[map];
         if w.is_generated {
@@ -0,0 +1,314 @@
+//! Opt-in crash and performance reports, which help to find out on which
+//! platforms crashes and performance problems happen the most.
+//!
+//! Reports are written to the `telemetry` directory in the config directory,
+//! and uploaded to the configured endpoint (if any) the next time the game
+//! starts. They never contain the username, server addresses or chat, and
+//! paths in backtraces are made relative to the home directory.
+
+use crate::settings::TelemetrySettings;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+use tokio::runtime::Runtime;
+use tracing::{debug, info, warn};
+
+/// Frame times are summarised in a report after this many frames (about ten
+/// minutes at 60 fps), and when the game is closed.
+const FRAMES_PER_REPORT: usize = 36_000;
+/// Only the newest reports are kept, in each of the telemetry directories.
+const MAX_KEPT_REPORTS: usize = 32;
+const UPLOADED_DIR: &str = "uploaded";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub name: String,
+    pub vendor: u32,
+    pub device: u32,
+    pub device_type: String,
+    pub backend: String,
+    pub driver: String,
+    pub driver_info: String,
+}
+
+impl From<&wgpu::AdapterInfo> for GpuInfo {
+    fn from(info: &wgpu::AdapterInfo) -> Self {
+        Self {
+            name: info.name.clone(),
+            vendor: info.vendor,
+            device: info.device,
+            device_type: format!("{:?}", info.device_type),
+            backend: format!("{:?}", info.backend),
+            driver: info.driver.clone(),
+            driver_info: info.driver_info.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SystemInfo {
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    pub cpu_cores: usize,
+    pub gpu: Option<GpuInfo>,
+}
+
+impl SystemInfo {
+    pub fn new(gpu: Option<GpuInfo>) -> Self {
+        Self {
+            version: common::util::DISPLAY_VERSION_LONG.clone(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpu_cores: num_cpus::get(),
+            gpu,
+        }
+    }
+}
+
+/// Frame time percentiles, in milliseconds
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FrameTimes {
+    pub frames: usize,
+    pub p50: f32,
+    pub p90: f32,
+    pub p99: f32,
+    pub max: f32,
+}
+
+impl FrameTimes {
+    fn summarise(frame_times: &mut [f32]) -> Option<Self> {
+        if frame_times.is_empty() {
+            return None;
+        }
+        frame_times.sort_unstable_by(f32::total_cmp);
+        let percentile = |p: f32| {
+            frame_times
+                .get(((frame_times.len() - 1) as f32 * p).round() as usize)
+                .copied()
+        };
+        Some(Self {
+            frames: frame_times.len(),
+            p50: percentile(0.5)?,
+            p90: percentile(0.9)?,
+            p99: percentile(0.99)?,
+            max: *frame_times.last()?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ReportKind {
+    Crash {
+        reason: String,
+        location: Option<String>,
+        backtrace: String,
+    },
+    Performance(FrameTimes),
+}
+
+impl ReportKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Crash { .. } => "crash",
+            Self::Performance(_) => "performance",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Report {
+    pub time: DateTime<Utc>,
+    pub system: SystemInfo,
+    pub kind: ReportKind,
+}
+
+/// What the panic hook needs to write a crash report, set once the player
+/// opted in.
+struct CrashContext {
+    dir: PathBuf,
+    system: SystemInfo,
+}
+
+static CRASH_CONTEXT: Mutex<Option<CrashContext>> = Mutex::new(None);
+
+pub struct Telemetry {
+    dir: PathBuf,
+    system: SystemInfo,
+    frame_times: Vec<f32>,
+}
+
+impl Telemetry {
+    /// Returns `None` if the player didn't opt in.
+    pub fn new(
+        settings: &TelemetrySettings,
+        config_dir: &Path,
+        gpu: Option<GpuInfo>,
+        runtime: &Runtime,
+    ) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+        let dir = config_dir.join("telemetry");
+        if let Err(error) = fs::create_dir_all(dir.join(UPLOADED_DIR)) {
+            warn!(?error, ?dir, "Couldn't create the telemetry directory");
+            return None;
+        }
+        info!("Writing telemetry reports to {}", dir.display());
+
+        let system = SystemInfo::new(gpu);
+        if let Ok(mut context) = CRASH_CONTEXT.lock() {
+            *context = Some(CrashContext {
+                dir: dir.clone(),
+                system: system.clone(),
+            });
+        }
+        if let Some(endpoint) = settings.upload_endpoint.clone() {
+            runtime.spawn(upload_reports(dir.clone(), endpoint));
+        }
+
+        Some(Self {
+            dir,
+            system,
+            frame_times: Vec::with_capacity(FRAMES_PER_REPORT),
+        })
+    }
+
+    pub fn record_frame(&mut self, dt: Duration) {
+        self.frame_times.push(dt.as_secs_f32() * 1000.0);
+        if self.frame_times.len() >= FRAMES_PER_REPORT {
+            self.report_frame_times();
+        }
+    }
+
+    /// Writes a report of the frames recorded since the last one.
+    ///
+    /// This also happens when telemetry is turned off.
+    pub fn report_frame_times(&mut self) {
+        if let Some(frame_times) = FrameTimes::summarise(&mut self.frame_times) {
+            write_report(&self.dir, &Report {
+                time: Utc::now(),
+                system: self.system.clone(),
+                kind: ReportKind::Performance(frame_times),
+            });
+        }
+        self.frame_times.clear();
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        self.report_frame_times();
+        if let Ok(mut context) = CRASH_CONTEXT.lock() {
+            *context = None;
+        }
+    }
+}
+
+/// Writes a crash report, if the player opted in. Called by the panic hook.
+pub fn report_crash(reason: &str, location: Option<String>, backtrace: &backtrace::Backtrace) {
+    // The panic might have happened while the context was being set
+    let Ok(context) = CRASH_CONTEXT.try_lock() else {
+        return;
+    };
+    if let Some(context) = &*context {
+        write_report(&context.dir, &Report {
+            time: Utc::now(),
+            system: context.system.clone(),
+            kind: ReportKind::Crash {
+                reason: anonymise(reason),
+                location: location.map(|location| anonymise(&location)),
+                backtrace: anonymise(&format!("{:?}", backtrace)),
+            },
+        });
+    }
+}
+
+/// Removes the home directory, which often contains the user's name, from
+/// paths.
+fn anonymise(text: &str) -> String {
+    match directories_next::UserDirs::new() {
+        Some(dirs) => text.replace(&*dirs.home_dir().to_string_lossy(), "~"),
+        None => text.to_string(),
+    }
+}
+
+fn write_report(dir: &Path, report: &Report) {
+    let path = dir.join(format!(
+        "{}_{}.json",
+        report.time.format("%Y-%m-%d_%H-%M-%S%.3f"),
+        report.kind.name()
+    ));
+    let result = serde_json::to_vec_pretty(report)
+        .map_err(std::io::Error::from)
+        .and_then(|json| fs::write(&path, json));
+    match result {
+        Ok(()) => debug!(?path, "Wrote telemetry report"),
+        Err(error) => warn!(?error, ?path, "Couldn't write telemetry report"),
+    }
+    prune_reports(dir);
+}
+
+/// Report paths in the directory, oldest first
+fn reports(dir: &Path) -> Vec<PathBuf> {
+    let mut reports: Vec<_> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    // The file names start with the time
+    reports.sort();
+    reports
+}
+
+fn prune_reports(dir: &Path) {
+    let reports = reports(dir);
+    for path in reports
+        .iter()
+        .take(reports.len().saturating_sub(MAX_KEPT_REPORTS))
+    {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Uploads the reports that weren't uploaded yet, and moves them to the
+/// `uploaded` directory.
+async fn upload_reports(dir: PathBuf, endpoint: String) {
+    let client = reqwest::Client::new();
+    let uploaded_dir = dir.join(UPLOADED_DIR);
+    let mut uploaded = 0;
+    for path in reports(&dir) {
+        let Ok(report) = fs::read(&path) else {
+            continue;
+        };
+        let result = client
+            .post(&endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(report)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(error) = result {
+            warn!(
+                ?error,
+                "Couldn't upload telemetry report, trying again next time"
+            );
+            break;
+        }
+        if let Some(name) = path.file_name() {
+            let _ = fs::rename(&path, uploaded_dir.join(name));
+        }
+        uploaded += 1;
+    }
+    if uploaded > 0 {
+        info!("Uploaded {} telemetry reports", uploaded);
+        prune_reports(&uploaded_dir);
+    }
+}
@@ -18,9 +18,11 @@ fn main() {
     let mut zoom = 1.0;
     let colors = &*index.colors();
     let features = &*index.features();
+    let layer_tuning = &*index.layer_tuning();
     let index = IndexRef {
         colors,
         features,
+        layer_tuning,
         index,
     };
 
@@ -567,6 +567,7 @@ impl Civs {
                 let index_ref = IndexRef {
                     colors: &index.colors(),
                     features: &index.features(),
+                    layer_tuning: &index.layer_tuning(),
                     index,
                 };
                 match &sim_site.kind {
@@ -755,8 +756,9 @@ impl Civs {
                 .min_by_key(|(_id, s)| s.get_origin().map(|e| e as i64).distance_squared(wpos));
             if let Some((_id, s)) = closest_site {
                 let distance_squared = s.get_origin().map(|e| e as i64).distance_squared(wpos);
+                let irrigated = s.is_irrigated();
                 s.economy
-                    .add_chunk(ctx.sim.get(chpos).unwrap(), distance_squared);
+                    .add_chunk(ctx.sim.get(chpos).unwrap(), distance_squared, irrigated);
             }
         });
         drop(guard);
@@ -1719,6 +1721,9 @@ fn find_path(
 /// Return tuple: (final location, cost)
 ///
 /// For efficiency, this computes for all 8 directions at once.
+/// The longest distance (in chunks) that a bridge may span
+const MAX_BRIDGE_LEN: i32 = 9;
+
 fn walk_in_all_dirs(
     sim: &WorldSim,
     bridge: Option<Vec2<i32>>,
@@ -1758,10 +1763,16 @@ fn walk_in_all_dirs(
     for (i, &dir) in NEIGHBORS.iter().enumerate() {
         let is_cardinal_dir = dir.x == 0 || dir.y == 0;
         if is_cardinal_dir && potential[i].is_none() {
-            // if we can skip over unsuitable area with a bridge
-            potential[i] = (4..=5).find_map(|i| {
-                loc_suitable_for_walking(sim, a + dir * i)
-                    .then(|| (a + dir * i, 120.0 + (i - 4) as f32 * 10.0))
+            // if we can skip over unsuitable area with a bridge. Longer bridges
+            // are much more expensive to build, so they only get built over
+            // wide rivers when there's no other way around.
+            potential[i] = (4..=MAX_BRIDGE_LEN).find_map(|i| {
+                loc_suitable_for_walking(sim, a + dir * i).then(|| {
+                    (
+                        a + dir * i,
+                        120.0 + (i - 4) as f32 * 10.0 + (i - 5).max(0).pow(2) as f32 * 40.0,
+                    )
+                })
             });
         }
     }
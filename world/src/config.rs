@@ -1,5 +1,6 @@
 use common::assets;
 use serde::Deserialize;
+use std::ops::Range;
 use vek::*;
 
 pub struct Config {
@@ -101,3 +102,25 @@ impl assets::Asset for Features {
 
     const EXTENSION: &'static str = "ron";
 }
+
+/// Tunable constants of the world layers. Like [`Features`], changes to these
+/// are picked up while the server runs when hot-reloading is enabled, which
+/// regenerates all loaded chunks.
+#[derive(Deserialize)]
+pub struct LayerTuning {
+    /// Depths of water that coral grows in.
+    pub coral_depth: Range<f32>,
+    /// Maximum height of coral above the seabed.
+    pub coral_height: f32,
+    /// Chance of a creature spawning at a suitable spot in caves.
+    pub cave_spawn_chance: f32,
+    /// Multiplier for the tree density of columns when deciding whether a
+    /// tree grows there.
+    pub tree_density_factor: f32,
+}
+
+impl assets::Asset for LayerTuning {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
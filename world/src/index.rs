@@ -1,7 +1,7 @@
 use crate::{
     layer::wildlife::{self, DensityFn, SpawnEntry},
     site::{economy::TradeInformation, Site},
-    Colors, Features,
+    Colors, Features, LayerTuning,
 };
 use common::{
     assets::{AssetExt, AssetHandle},
@@ -14,6 +14,7 @@ use std::sync::Arc;
 
 const WORLD_COLORS_MANIFEST: &str = "world.style.colors";
 const WORLD_FEATURES_MANIFEST: &str = "world.features";
+const WORLD_LAYER_TUNING_MANIFEST: &str = "world.layer_tuning";
 
 pub struct Index {
     pub seed: u32,
@@ -24,6 +25,7 @@ pub struct Index {
     pub wildlife_spawns: Vec<(AssetHandle<SpawnEntry>, DensityFn)>,
     colors: AssetHandle<Arc<Colors>>,
     features: AssetHandle<Arc<Features>>,
+    layer_tuning: AssetHandle<Arc<LayerTuning>>,
 }
 
 /// An owned reference to indexed data.
@@ -35,6 +37,7 @@ pub struct Index {
 pub struct IndexOwned {
     colors: Arc<Colors>,
     features: Arc<Features>,
+    layer_tuning: Arc<LayerTuning>,
     index: Arc<Index>,
 }
 
@@ -51,6 +54,7 @@ impl Deref for IndexOwned {
 pub struct IndexRef<'a> {
     pub colors: &'a Colors,
     pub features: &'a Features,
+    pub layer_tuning: &'a LayerTuning,
     pub index: &'a Index,
 }
 
@@ -65,6 +69,7 @@ impl Index {
     pub fn new(seed: u32) -> Self {
         let colors = Arc::<Colors>::load_expect(WORLD_COLORS_MANIFEST);
         let features = Arc::<Features>::load_expect(WORLD_FEATURES_MANIFEST);
+        let layer_tuning = Arc::<LayerTuning>::load_expect(WORLD_LAYER_TUNING_MANIFEST);
         let wildlife_spawns = wildlife::spawn_manifest()
             .into_iter()
             .map(|(e, f)| (SpawnEntry::load_expect(e), f))
@@ -79,6 +84,7 @@ impl Index {
             wildlife_spawns,
             colors,
             features,
+            layer_tuning,
         }
     }
 
@@ -86,6 +92,10 @@ impl Index {
 
     pub fn features(&self) -> impl Deref<Target = Arc<Features>> + '_ { self.features.read() }
 
+    pub fn layer_tuning(&self) -> impl Deref<Target = Arc<LayerTuning>> + '_ {
+        self.layer_tuning.read()
+    }
+
     pub fn get_site_prices(&self, site_id: SiteId) -> Option<SitePrices> {
         self.sites
             .recreate_id(site_id)
@@ -98,11 +108,13 @@ impl IndexOwned {
     pub fn new(index: Index) -> Self {
         let colors = index.colors.cloned();
         let features = index.features.cloned();
+        let layer_tuning = index.layer_tuning.cloned();
 
         Self {
             index: Arc::new(index),
             colors,
             features,
+            layer_tuning,
         }
     }
 
@@ -114,11 +126,14 @@ impl IndexOwned {
     ///
     /// Ideally, this should be called about once per tick.
     pub fn reload_if_changed<R>(&mut self, reload: impl FnOnce(&mut Self) -> R) -> Option<R> {
-        let reloaded = self.index.colors.reloaded_global() || self.index.features.reloaded_global();
+        let reloaded = self.index.colors.reloaded_global()
+            || self.index.features.reloaded_global()
+            || self.index.layer_tuning.reloaded_global();
         reloaded.then(move || {
             // Reload the fields from the asset handle, which is updated automatically
             self.colors = self.index.colors.cloned();
             self.features = self.index.features.cloned();
+            self.layer_tuning = self.index.layer_tuning.cloned();
             // Update wildlife spawns which is based on base_density in features
             reload(self)
         })
@@ -128,6 +143,7 @@ impl IndexOwned {
         IndexRef {
             colors: &self.colors,
             features: &self.features,
+            layer_tuning: &self.layer_tuning,
             index: &self.index,
         }
     }
@@ -1599,7 +1599,8 @@ fn write_column<R: Rng>(
 
 // #[inline_tweak::tweak_fn]
 fn apply_entity_spawns<R: Rng>(canvas: &mut Canvas, wpos: Vec3<i32>, biome: &Biome, rng: &mut R) {
-    if RandomField::new(canvas.info().index().seed).chance(wpos, 0.035) {
+    let index = canvas.info().index();
+    if RandomField::new(index.seed).chance(wpos, index.layer_tuning.cave_spawn_chance) {
         if let Some(entity_asset) = [
             // Mushroom biome
             (
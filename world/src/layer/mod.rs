@@ -5,10 +5,11 @@ pub mod shrub;
 pub mod spot;
 pub mod tree;
 pub mod wildlife;
+pub mod wreck;
 
 pub use self::{
     cave::apply_caves_to as apply_caves2_to, rock::apply_rocks_to, scatter::apply_scatter_to,
-    shrub::apply_shrubs_to, spot::apply_spots_to, tree::apply_trees_to,
+    shrub::apply_shrubs_to, spot::apply_spots_to, tree::apply_trees_to, wreck::apply_wrecks_to,
 };
 
 use crate::{
@@ -22,7 +23,8 @@ use common::{
     assets::AssetExt,
     generation::{ChunkSupplement, EntityInfo},
     lottery::Lottery,
-    terrain::{Block, BlockKind, SpriteKind},
+    rtsim::{RoadKind, RtsimChunks},
+    terrain::{Block, BlockKind, CoordinateConversions, SpriteKind},
     vol::{ReadVol, RectSizedVol, WriteVol},
 };
 use hashbrown::HashMap;
@@ -31,7 +33,7 @@ use rand::prelude::*;
 use serde::Deserialize;
 use std::{
     f32,
-    ops::{Add, Mul, Range, Sub},
+    ops::{Add, Mul, Sub},
 };
 use vek::*;
 
@@ -95,7 +97,7 @@ impl PathLocals {
     }
 }
 
-pub fn apply_paths_to(canvas: &mut Canvas) {
+pub fn apply_paths_to(canvas: &mut Canvas, rtsim_chunks: Option<&RtsimChunks>) {
     let info = canvas.info();
     canvas.foreach_col(|canvas, wpos2d, col| {
         let surface_z = col.riverless_alt.floor() as i32;
@@ -123,18 +125,54 @@ pub fn apply_paths_to(canvas: &mut Canvas) {
             } = PathLocals::new(&canvas.info(), col, path_nearest);
             let surface_z = (riverless_alt + bridge_offset).floor() as i32;
 
+            // Paths are worn in by the traffic along the track they belong to
+            let road = rtsim_chunks
+                .and_then(|chunks| chunks.get(path_nearest.as_::<i32>().wpos_to_cpos()))
+                .map_or(RoadKind::default(), |chunk| chunk.road);
+
             for z in inset - depth..inset {
+                let bridge_color: Rgb<u8> = info.index().colors.layer.bridge.into();
                 canvas.set(
                     Vec3::new(wpos2d.x, wpos2d.y, surface_z + z),
                     if bridge_offset >= 2.0 && path_dist >= 3.0 || z < inset - 1 {
-                        Block::new(
-                            BlockKind::Rock,
-                            noisy_color(info.index().colors.layer.bridge.into(), 8),
-                        )
+                        Block::new(BlockKind::Rock, noisy_color(bridge_color, 8))
                     } else {
                         let path_color =
                             path.surface_color(col.sub_surface_color.map(|e| (e * 255.0) as u8));
-                        Block::new(BlockKind::Earth, noisy_color(path_color, 8))
+                        match road {
+                            // Only a narrow trail remains of paths that have grassed over
+                            RoadKind::Overgrown if path_dist >= path.width * 0.4 => Block::new(
+                                BlockKind::Grass,
+                                noisy_color(col.surface_color.map(|e| (e * 255.0) as u8), 8),
+                            ),
+                            RoadKind::Overgrown | RoadKind::Dirt => {
+                                Block::new(BlockKind::Earth, noisy_color(path_color, 8))
+                            },
+                            RoadKind::Gravel => Block::new(
+                                BlockKind::Earth,
+                                noisy_color(
+                                    Rgb::lerp(
+                                        path_color.map(|e| e as f32),
+                                        bridge_color.map(|e| e as f32),
+                                        0.6,
+                                    )
+                                    .map(|e| e as u8),
+                                    20,
+                                ),
+                            ),
+                            RoadKind::Paved => {
+                                // Mortar between the cobbles
+                                let is_joint = wpos2d.x.rem_euclid(3) == 0
+                                    || (wpos2d.y + wpos2d.x.div_euclid(3)).rem_euclid(3) == 0;
+                                Block::new(
+                                    BlockKind::Rock,
+                                    noisy_color(
+                                        bridge_color.map(|e| if is_joint { e / 2 } else { e }),
+                                        8,
+                                    ),
+                                )
+                            },
+                        }
                     },
                 );
             }
@@ -687,33 +725,35 @@ pub fn apply_coral_to(canvas: &mut Canvas) {
         return; // Don't bother with coral for a chunk nowhere near water
     }
 
+    let tuning = info.index().layer_tuning;
+    let coral_depth = tuning.coral_depth.clone();
+    let coral_height = tuning.coral_height;
+
     canvas.foreach_col(|canvas, wpos2d, col| {
-        const CORAL_DEPTH: Range<f32> = 14.0..32.0;
-        const CORAL_HEIGHT: f32 = 14.0;
         const CORAL_DEPTH_FADEOUT: f32 = 5.0;
         const CORAL_SCALE: f32 = 10.0;
 
         let water_depth = col.water_level - col.alt;
 
-        if !CORAL_DEPTH.contains(&water_depth) {
+        if !coral_depth.contains(&water_depth) {
             return; // Avoid coral entirely for this column if we're outside coral depths
         }
 
-        for z in col.alt.floor() as i32..(col.alt + CORAL_HEIGHT) as i32 {
+        for z in col.alt.floor() as i32..(col.alt + coral_height) as i32 {
             let wpos = Vec3::new(wpos2d.x, wpos2d.y, z);
 
             let coral_factor = Lerp::lerp(
                 1.0,
                 0.0,
                 // Fade coral out due to incorrect depth
-                ((water_depth.clamped(CORAL_DEPTH.start, CORAL_DEPTH.end) - water_depth).abs()
+                ((water_depth.clamped(coral_depth.start, coral_depth.end) - water_depth).abs()
                     / CORAL_DEPTH_FADEOUT)
                     .min(1.0),
             ) * Lerp::lerp(
                 1.0,
                 0.0,
                 // Fade coral out due to incorrect altitude above the seabed
-                ((z as f32 - col.alt) / CORAL_HEIGHT).powi(2),
+                ((z as f32 - col.alt) / coral_height).powi(2),
             ) * FastNoise::new(info.index.seed + 7)
                 .get(wpos.map(|e| e as f64) / 32.0)
                 .sub(0.2)
@@ -6,7 +6,7 @@ use crate::{
 use common::{
     assets::{Asset, AssetCombined, AssetHandle, Concatenate, RonLoader},
     generation::EntityInfo,
-    terrain::{BiomeKind, Structure, TerrainChunkSize},
+    terrain::{BiomeKind, Block, BlockKind, SpriteKind, Structure, TerrainChunkSize},
     vol::RectVolSize,
 };
 use lazy_static::lazy_static;
@@ -64,6 +64,9 @@ pub enum Spot {
     JungleTemple,
     SaurokTotem,
     JungleOutpost,
+    /// Ledges, vines and anchors leading up a cliff face to a treasure cache at
+    /// the top. Unlike other spots, these show up on the map once discovered.
+    ClimbingRoute,
     RonFile(&'static SpotProperties),
 }
 
@@ -357,6 +360,20 @@ impl Spot {
             },
             false,
         );
+        Self::generate_spots(
+            Spot::ClimbingRoute,
+            world,
+            2.0,
+            |g, c| {
+                g > 0.5
+                    && c.near_cliffs()
+                    && !c.river.near_water()
+                    && !c.path.0.is_way()
+                    && c.sites.is_empty()
+                    && matches!(c.get_biome(), Mountain | Snowland | Taiga)
+            },
+            false,
+        );
 
         // Missing:
         /*
@@ -575,6 +592,10 @@ pub fn apply_spots_to(canvas: &mut Canvas, _dynamic_rng: &mut impl Rng) {
                 entity_radius: 2.0,
                 entities: &[],
             },
+            Spot::ClimbingRoute => {
+                apply_climbing_route(canvas, spot_wpos2d, &mut rng);
+                SpotConfig::default()
+            },
             Spot::RonFile(properties) => SpotConfig {
                 base_structures: Some(&properties.base_structures),
                 entity_radius: 1.0,
@@ -626,6 +647,118 @@ pub fn apply_spots_to(canvas: &mut Canvas, _dynamic_rng: &mut impl Rng) {
     }
 }
 
+/// Distance from the centre of a climbing route within which the foot and top
+/// of its cliff are searched, so that it stays within the neighbouring chunks
+const CLIMBING_ROUTE_RADIUS: i32 = 24;
+/// Vertical distance between the ledges of a climbing route
+const CLIMBING_LEDGE_SPACING: i32 = 6;
+
+/// Positions of all climbing routes in the world, for showing them on the map
+pub fn climbing_routes(sim: &WorldSim) -> impl Iterator<Item = Vec2<i32>> + '_ {
+    let size = sim.get_size().as_::<i32>();
+    (0..size.x)
+        .flat_map(move |x| (0..size.y).map(move |y| Vec2::new(x, y)))
+        .filter(|cpos| {
+            sim.get(*cpos).map_or(false, |chunk| {
+                matches!(chunk.spot, Some(Spot::ClimbingRoute))
+            })
+        })
+        .map(|cpos| {
+            cpos.map2(TerrainChunkSize::RECT_SIZE, |e, sz| {
+                e * sz as i32 + sz as i32 / 2
+            })
+        })
+}
+
+/// Generates the part of the climbing route around `center` that lies within
+/// the canvas. Only uses world data, so the route turns out the same for every
+/// chunk it crosses.
+fn apply_climbing_route(canvas: &mut Canvas, center: Vec2<i32>, rng: &mut impl Rng) {
+    let alt_at = |wpos: Vec2<i32>| canvas.col_or_gen(wpos).map(|col| col.alt as i32);
+    let candidates = (-CLIMBING_ROUTE_RADIUS..=CLIMBING_ROUTE_RADIUS)
+        .step_by(4)
+        .flat_map(|x| {
+            (-CLIMBING_ROUTE_RADIUS..=CLIMBING_ROUTE_RADIUS)
+                .step_by(4)
+                .map(move |y| center + Vec2::new(x, y))
+        })
+        .filter_map(|wpos| Some((wpos, alt_at(wpos)?)))
+        .collect::<Vec<_>>();
+    let (Some((foot, _)), Some((top, _))) = (
+        candidates.iter().min_by_key(|(_, alt)| *alt),
+        candidates.iter().max_by_key(|(_, alt)| *alt),
+    ) else {
+        return;
+    };
+
+    // Height of the terrain along the route, from its foot to the top
+    let len = foot.as_::<f32>().distance(top.as_::<f32>()).ceil() as i32;
+    let profile = (0..=len)
+        .filter_map(|i| {
+            let wpos = Lerp::lerp(foot.as_::<f32>(), top.as_::<f32>(), i as f32 / len as f32)
+                .map(|e| e.round() as i32);
+            Some((wpos, alt_at(wpos)?))
+        })
+        .collect::<Vec<_>>();
+    let (Some((_, foot_alt)), Some((top_pos, top_alt))) = (profile.first(), profile.last()) else {
+        return;
+    };
+    if top_alt - foot_alt < CLIMBING_LEDGE_SPACING * 2 {
+        return;
+    }
+    // Ledges stick out sideways from the route so that they don't block it
+    let side = (top - foot).map(|e| e.signum()).yx() * Vec2::new(-1, 1);
+    let stone = canvas
+        .col_or_gen(*top_pos)
+        .map_or(Rgb::broadcast(128), |col| col.stone_col);
+
+    let mut last_ledge = *foot_alt;
+    for z in
+        (foot_alt + CLIMBING_LEDGE_SPACING..top_alt - 2).step_by(CLIMBING_LEDGE_SPACING as usize)
+    {
+        // The ledge sits in front of the first column of the cliff face that is at
+        // least as high
+        let Some(face) = profile.iter().position(|(_, alt)| *alt >= z) else {
+            continue;
+        };
+        let (ledge, _) = profile[face.saturating_sub(1)];
+        for offset in [Vec2::zero(), side] {
+            canvas.set(
+                (ledge + offset).with_z(z - 1),
+                Block::new(BlockKind::Rock, stone),
+            );
+        }
+        // Anchors on some of the ledges to rest at, vines hanging down to the one below
+        if rng.gen_bool(0.5) {
+            canvas.map(ledge.with_z(z), |block| {
+                if block.is_air() {
+                    block.with_sprite(SpriteKind::Rope)
+                } else {
+                    block
+                }
+            });
+        }
+        for vine_z in last_ledge + 1..z - 1 {
+            canvas.map((ledge + side).with_z(vine_z), |block| {
+                if block.is_air() {
+                    block.with_sprite(SpriteKind::Liana)
+                } else {
+                    block
+                }
+            });
+        }
+        last_ledge = z;
+    }
+
+    canvas.map(top_pos.with_z(top_alt + 1), |block| {
+        if block.is_air() {
+            block.with_sprite(SpriteKind::Chest)
+        } else {
+            block
+        }
+    });
+}
+
 #[derive(serde::Deserialize, Clone, Debug)]
 enum SpotCondition {
     MaxGradient(f32),
@@ -4,14 +4,15 @@ use crate::{
     column::ColumnGen,
     layer::cave::tunnel_bounds_at,
     util::{gen_cache::StructureGenCache, RandomPerm, Sampler, UnitChooser},
-    Canvas, CanvasInfo, ColumnSample,
+    Canvas, CanvasInfo, ColumnSample, LayerTuning,
 };
 use common::{
     assets::AssetHandle,
     calendar::{Calendar, CalendarEvent},
+    rtsim::{ChunkResource, RtsimChunks},
     terrain::{
         structure::{Structure, StructureBlock, StructuresGroup},
-        Block, BlockKind, SpriteKind,
+        Block, BlockKind, CoordinateConversions, SpriteKind,
     },
     vol::ReadVol,
 };
@@ -39,6 +40,7 @@ pub fn tree_valid_at(
     wpos: Vec2<i32>,
     col: &ColumnSample,
     info: Option<CanvasInfo<'_>>,
+    tuning: &LayerTuning,
     seed: u32,
 ) -> bool {
     if col.alt < col.water_level
@@ -53,7 +55,9 @@ pub fn tree_valid_at(
         return false;
     }
 
-    if ((seed.wrapping_mul(13)) & 0xFF) as f32 / 256.0 > col.tree_density {
+    if ((seed.wrapping_mul(13)) & 0xFF) as f32 / 256.0
+        > col.tree_density * tuning.tree_density_factor
+    {
         return false;
     }
 
@@ -64,6 +68,7 @@ pub fn apply_trees_to(
     canvas: &mut Canvas,
     dynamic_rng: &mut impl Rng,
     calendar: Option<&Calendar>,
+    rtsim_chunks: Option<&RtsimChunks>,
 ) {
     // TODO: Get rid of this
     #[allow(clippy::large_enum_variant)]
@@ -98,7 +103,14 @@ pub fn apply_trees_to(
 
             let crowding = col.tree_density;
 
-            if !tree_valid_at(wpos, &col, Some(info), seed) {
+            if !tree_valid_at(wpos, &col, Some(info), info.index().layer_tuning, seed) {
+                return None;
+            }
+
+            // Trees that have been felled leave clearings behind until the forest regrows
+            if let Some(chunk) = rtsim_chunks.and_then(|chunks| chunks.get(wpos.wpos_to_cpos()))
+                && !QUIRKY_RAND.chance(seed + 3, chunk.resources[ChunkResource::Tree])
+            {
                 return None;
             }
 
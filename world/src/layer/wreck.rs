@@ -0,0 +1,133 @@
+use crate::{
+    util::{RandomField, Sampler, LOCALITY},
+    Canvas,
+};
+use common::{
+    generation::EntityInfo,
+    rtsim::{ChunkWreck, RtsimChunks},
+    terrain::{Block, BlockKind, CoordinateConversions, SpriteKind},
+};
+use rand::prelude::*;
+use vek::*;
+
+/// Half the length of the hull of a wrecked airship
+const HULL_HALF_LEN: f32 = 14.0;
+/// Half the width of the hull of a wrecked airship
+const HULL_HALF_WIDTH: f32 = 5.0;
+/// How deep the hull is buried in the ground it came down on
+const HULL_BURIAL: i32 = 2;
+/// How far the cargo of a wreck is strewn around it
+const DEBRIS_RADIUS: f32 = 16.0;
+/// The chance of there being a piece of cargo in a given column around a wreck
+/// that has yet to be picked over
+const DEBRIS_CHANCE: f32 = 0.025;
+/// Wrecks that have been picked over more than this no longer draw scavengers
+const SCAVENGED_REMAINS: f32 = 0.5;
+
+/// Draw the wrecks of airships that have come down around this chunk, as
+/// recorded by rtsim, along with their scattered cargo. Wrecks that have yet
+/// to be picked over attract scavengers.
+pub fn apply_wrecks_to(
+    canvas: &mut Canvas,
+    dynamic_rng: &mut impl Rng,
+    rtsim_chunks: Option<&RtsimChunks>,
+) {
+    let Some(rtsim_chunks) = rtsim_chunks else {
+        return;
+    };
+    let chunk_pos = canvas.wpos().wpos_to_cpos();
+    let wrecks = LOCALITY
+        .into_iter()
+        .filter_map(|rpos| rtsim_chunks.get(chunk_pos + rpos)?.wreck)
+        .collect::<Vec<_>>();
+    if wrecks.is_empty() {
+        return;
+    }
+
+    let wood = Block::new(BlockKind::Wood, Rgb::new(85, 60, 40));
+    let charred = Block::new(BlockKind::Wood, Rgb::new(40, 32, 28));
+    let cloth = Block::new(BlockKind::Misc, Rgb::new(170, 150, 120));
+
+    canvas.foreach_col(|canvas, wpos2d, col| {
+        // Whatever came down in the water has long since sunk
+        if col.alt < col.water_level {
+            return;
+        }
+        let surface_z = col.alt.floor() as i32;
+
+        for wreck in &wrecks {
+            let rpos = (wpos2d - wreck.wpos).as_::<f32>();
+            let along = rpos.dot(wreck.dir);
+            let across = rpos.dot(wreck.dir.yx() * Vec2::new(-1.0, 1.0));
+            let rng = RandomField::new(wreck.seed);
+
+            // The hull snapped in two where it hit the ground
+            let snap = (wreck.seed % 9) as f32 - 4.0;
+            let hull = (along / HULL_HALF_LEN).powi(2) + (across / HULL_HALF_WIDTH).powi(2);
+            if hull < 1.0 && (along - snap).abs() > 1.5 {
+                let height = (HULL_HALF_WIDTH * (1.0 - hull).sqrt()) as i32 - HULL_BURIAL;
+                for z in surface_z - HULL_BURIAL..=surface_z + height {
+                    let wpos = wpos2d.with_z(z);
+                    if hull > 0.6 || z < surface_z {
+                        // Planks near the snap were scorched when it came down
+                        canvas.set(
+                            wpos,
+                            if (along - snap).abs() < 4.0 && rng.chance(wpos, 0.5) {
+                                charred
+                            } else {
+                                wood
+                            },
+                        );
+                    } else {
+                        canvas.set(wpos, Block::empty());
+                    }
+                }
+                continue;
+            }
+
+            // The torn envelope is draped over the ground alongside the hull
+            if (HULL_HALF_WIDTH + 2.0..HULL_HALF_WIDTH + 9.0).contains(&across)
+                && along.abs() < HULL_HALF_LEN * 0.8
+                && !rng.chance(wpos2d.with_z(surface_z), 0.2)
+            {
+                canvas.set(wpos2d.with_z(surface_z + 1), cloth);
+                continue;
+            }
+
+            // Cargo strewn about the wreck, what remains of it
+            if rpos.magnitude_squared() < DEBRIS_RADIUS.powi(2)
+                && rng.chance(wpos2d.with_z(surface_z + 1), DEBRIS_CHANCE * wreck.remains)
+            {
+                let sprite = match rng.get(wpos2d.with_z(surface_z)) % 16 {
+                    0 => SpriteKind::Chest,
+                    1..=8 => SpriteKind::Crate,
+                    _ => SpriteKind::Barrel,
+                };
+                canvas.map(wpos2d.with_z(surface_z + 1), |block| {
+                    if block.is_air() {
+                        block.with_sprite(sprite)
+                    } else {
+                        block
+                    }
+                });
+            }
+        }
+    });
+
+    // Scavengers pick over the wreck before anybody else gets to it
+    if let Some(ChunkWreck { wpos, remains, .. }) = rtsim_chunks.here().wreck
+        && remains > SCAVENGED_REMAINS
+    {
+        for _ in 0..dynamic_rng.gen_range(1..=3) {
+            let wpos2d =
+                wpos + Vec2::new(dynamic_rng.gen_range(-8..=8), dynamic_rng.gen_range(-8..=8));
+            let Some(alt) = canvas.info().col(wpos2d).map(|col| col.alt) else {
+                continue;
+            };
+            canvas.spawn(
+                EntityInfo::at(wpos2d.as_::<f32>().with_z(alt) + Vec3::new(0.5, 0.5, 1.0))
+                    .with_asset_expect("common.entity.spot.grim_salvager", dynamic_rng, None),
+            );
+        }
+    }
+}
@@ -24,12 +24,13 @@ pub mod sim;
 pub mod sim2;
 pub mod site;
 pub mod site2;
+mod treasure;
 pub mod util;
 
 // Reexports
 pub use crate::{
     canvas::{Canvas, CanvasInfo},
-    config::{Features, CONFIG},
+    config::{Features, LayerTuning, CONFIG},
     land::Land,
     layer::PathLocals,
 };
@@ -54,15 +55,15 @@ use common::{
     generation::{ChunkSupplement, EntityInfo, SpecialEntity},
     lod,
     resources::TimeOfDay,
-    rtsim::ChunkResource,
+    rtsim::RtsimChunks,
     terrain::{
-        Block, BlockKind, SpriteKind, TerrainChunk, TerrainChunkMeta, TerrainChunkSize, TerrainGrid,
+        Block, BlockKind, SiteKindMeta, SpriteKind, TerrainChunk, TerrainChunkMeta,
+        TerrainChunkSize, TerrainGrid,
     },
     vol::{ReadVol, RectVolSize, WriteVol},
 };
 use common_base::prof_span;
 use common_net::msg::{world_msg, WorldMapMsg};
-use enum_map::EnumMap;
 use rand::{prelude::*, Rng};
 use rand_chacha::ChaCha8Rng;
 use serde::Deserialize;
@@ -250,6 +251,15 @@ impl World {
                             kind: world_msg::SiteKind::Cave,
                             wpos,
                         }))
+                    .chain(layer::spot::climbing_routes(self.sim())
+                        .map(|wpos| world_msg::SiteInfo {
+                            // Derived from the position, so that it stays the same across
+                            // restarts for remembering discovered routes
+                            id: 1 << 32 | (wpos.x as u64) << 16 | wpos.y as u64,
+                            name: None,
+                            kind: world_msg::SiteKind::ClimbingRoute,
+                            wpos,
+                        }))
                     .collect(),
                 possible_starting_sites: {
                     const STARTING_SITE_COUNT: usize = 5;
@@ -359,7 +369,7 @@ impl World {
         &self,
         index: IndexRef,
         chunk_pos: Vec2<i32>,
-        rtsim_resources: Option<EnumMap<ChunkResource, f32>>,
+        rtsim_chunks: Option<RtsimChunks>,
         // TODO: misleading name
         mut should_continue: impl FnMut() -> bool,
         time: Option<(TimeOfDay, Calendar)>,
@@ -512,14 +522,20 @@ impl World {
             layer::apply_shrubs_to(&mut canvas, &mut dynamic_rng);
         }
         if index.features.trees {
-            layer::apply_trees_to(&mut canvas, &mut dynamic_rng, calendar);
+            layer::apply_trees_to(
+                &mut canvas,
+                &mut dynamic_rng,
+                calendar,
+                rtsim_chunks.as_ref(),
+            );
         }
         if index.features.scatter {
             layer::apply_scatter_to(&mut canvas, &mut dynamic_rng, calendar);
         }
         if index.features.paths {
-            layer::apply_paths_to(&mut canvas);
+            layer::apply_paths_to(&mut canvas, rtsim_chunks.as_ref());
         }
+        layer::apply_wrecks_to(&mut canvas, &mut dynamic_rng, rtsim_chunks.as_ref());
         if index.features.spots {
             layer::apply_spots_to(&mut canvas, &mut dynamic_rng);
         }
@@ -538,9 +554,32 @@ impl World {
         };
         drop(canvas);
 
-        let gen_entity_pos = |dynamic_rng: &mut ChaCha8Rng| {
-            let lpos2d = TerrainChunkSize::RECT_SIZE
-                .map(|sz| dynamic_rng.gen::<u32>().rem_euclid(sz) as i32);
+        // Towns post bounties on a notice board across from their waypoint shrine
+        for site_id in sim_chunk.sites.iter() {
+            const NOTICE_BOARD_OFFSET: Vec2<i32> = Vec2::new(-4, 4);
+
+            let site = &index.sites[*site_id];
+            let lpos2d = site.get_origin() + NOTICE_BOARD_OFFSET - chunk_wpos2d;
+            if matches!(
+                site.kind.convert_to_meta(),
+                Some(SiteKindMeta::Settlement(_))
+            ) && lpos2d
+                .map2(TerrainChunkSize::RECT_SIZE, |e, sz| e >= 0 && e < sz as i32)
+                .reduce_and()
+            {
+                let mut lpos = Vec3::new(
+                    lpos2d.x,
+                    lpos2d.y,
+                    sample_get(lpos2d).map(|s| s.alt as i32 - 32).unwrap_or(0),
+                );
+                while chunk.get(lpos).map_or(false, Block::is_solid) {
+                    lpos.z += 1;
+                }
+                let _ = chunk.map(lpos, |block| block.with_sprite(SpriteKind::NoticeBoard));
+            }
+        }
+
+        let entity_pos_at = |lpos2d: Vec2<i32>| {
             let mut lpos = Vec3::new(
                 lpos2d.x,
                 lpos2d.y,
@@ -553,6 +592,12 @@ impl World {
 
             (Vec3::from(chunk_wpos2d) + lpos).map(|e: i32| e as f32) + 0.5
         };
+        let gen_entity_pos = |dynamic_rng: &mut ChaCha8Rng| {
+            entity_pos_at(
+                TerrainChunkSize::RECT_SIZE
+                    .map(|sz| dynamic_rng.gen::<u32>().rem_euclid(sz) as i32),
+            )
+        };
 
         if sim_chunk.contains_waypoint {
             let waypoint_pos = gen_entity_pos(&mut dynamic_rng);
@@ -568,6 +613,30 @@ impl World {
             }
         }
 
+        // Waypoint shrines sit next to the center of towns and dungeon entrances. Their
+        // position doesn't depend on the dynamic rng, since characters keep the
+        // location of the shrines they unlocked.
+        for site_id in sim_chunk.sites.iter() {
+            const SHRINE_OFFSET: Vec2<i32> = Vec2::new(4, 4);
+
+            let site = &index.sites[*site_id];
+            let is_major_site = matches!(
+                site.kind.convert_to_meta(),
+                Some(SiteKindMeta::Settlement(_) | SiteKindMeta::Dungeon(_))
+            );
+            let lpos2d = site.get_origin() + SHRINE_OFFSET - chunk_wpos2d;
+            if is_major_site
+                && lpos2d
+                    .map2(TerrainChunkSize::RECT_SIZE, |e, sz| e >= 0 && e < sz as i32)
+                    .reduce_and()
+            {
+                supplement.add_entity(
+                    EntityInfo::at(entity_pos_at(lpos2d))
+                        .into_special(SpecialEntity::Shrine(site_id.id())),
+                );
+            }
+        }
+
         // Apply layer supplement
         layer::apply_caves_supplement(
             &mut dynamic_rng,
@@ -609,7 +678,7 @@ impl World {
         // positions and only keeping those that actually do have resources.
         // Although this looks potentially very expensive, only blocks that are rtsim
         // resources (i.e: a relatively small number of sprites) are processed here.
-        if let Some(rtsim_resources) = rtsim_resources {
+        if let Some(rtsim_chunks) = rtsim_chunks {
             rtsim_resource_blocks.sort_unstable_by_key(|pos| pos.into_array());
             rtsim_resource_blocks.dedup();
             for wpos in rtsim_resource_blocks {
@@ -621,7 +690,7 @@ impl World {
                         supplement.rtsim_max_resources[res] += 1;
                         // Throw a dice to determine whether this resource should actually spawn
                         // TODO: Don't throw a dice, try to generate the *exact* correct number
-                        if dynamic_rng.gen_bool(rtsim_resources[res] as f64) {
+                        if dynamic_rng.gen_bool(rtsim_chunks.here().resources[res] as f64) {
                             block
                         } else {
                             block.into_vacant()
@@ -652,7 +721,15 @@ impl World {
                 .filter_map(|attr| {
                     ColumnGen::new(self.sim())
                         .get((attr.pos, index, self.sim().calendar.as_ref()))
-                        .filter(|col| layer::tree::tree_valid_at(attr.pos, col, None, attr.seed))
+                        .filter(|col| {
+                            layer::tree::tree_valid_at(
+                                attr.pos,
+                                col,
+                                None,
+                                index.layer_tuning,
+                                attr.seed,
+                            )
+                        })
                         .zip(Some(attr))
                 })
                 .filter_map(|(col, tree)| {
@@ -808,4 +885,38 @@ impl World {
         let sim_chunk = self.sim.get(chunk_pos)?;
         sim_chunk.get_location_name(&index.sites, &self.civs.pois, wpos2d)
     }
+
+    /// Picks where to bury the cache of a treasure map read at `origin`
+    pub fn find_treasure_spot(
+        &self,
+        index: IndexRef,
+        origin: Vec2<i32>,
+        rng: &mut impl Rng,
+    ) -> Option<Vec2<i32>> {
+        treasure::find_cache_spot(&ColumnGen::new(&self.sim), index, origin, rng)
+    }
+
+    /// Draws the land around a buried treasure cache, as shown on its map
+    pub fn treasure_map_snippet(
+        &self,
+        index: IndexRef,
+        cache: Vec2<i32>,
+    ) -> world_msg::TreasureMapSnippet {
+        treasure::draw_snippet(&ColumnGen::new(&self.sim), index, cache)
+    }
+
+    /// Where the halls that guilds can claim are in the world
+    pub fn guild_halls(&self, index: IndexRef) -> Vec<common::guild::GuildHallSpot> {
+        index
+            .sites
+            .values()
+            .filter_map(|site| site.site2())
+            .flat_map(|site| {
+                site.plots().filter_map(|plot| match &plot.kind {
+                    site2::plot::PlotKind::GuildHall(guild_hall) => Some(guild_hall.spot()),
+                    _ => None,
+                })
+            })
+            .collect()
+    }
 }
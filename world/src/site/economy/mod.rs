@@ -28,6 +28,8 @@ const INTER_SITE_TRADE: bool = true;
 const DAYS_PER_MONTH: f32 = 30.0;
 const DAYS_PER_YEAR: f32 = 12.0 * DAYS_PER_MONTH;
 const GENERATE_CSV: bool = false;
+/// How much more farmland close to a site yields when it is irrigated
+const IRRIGATION_YIELD: f32 = 1.5;
 
 #[derive(Debug)]
 pub struct TradeOrder {
@@ -263,7 +265,10 @@ impl Economy {
         // info!("resources {:?}", self.stocks);
     }
 
-    pub fn add_chunk(&mut self, ch: &SimChunk, distance_squared: i64) {
+    /// Add the natural resources of a chunk to those available to the site.
+    /// Irrigation canals make the farmland close to the site far more
+    /// productive.
+    pub fn add_chunk(&mut self, ch: &SimChunk, distance_squared: i64, irrigated: bool) {
         // let biome = ch.get_biome();
         // we don't scale by pi, although that would be correct
         let distance_bin = (distance_squared >> 16).min(64) as usize;
@@ -286,7 +291,12 @@ impl Economy {
             add_biome(BiomeKind::Lake, 1.0);
         } else {
             add_biome(BiomeKind::Forest, 0.5 + ch.tree_density);
-            add_biome(BiomeKind::Grassland, 0.5 + ch.humidity);
+            let irrigation = if irrigated && distance_bin == 0 {
+                IRRIGATION_YIELD
+            } else {
+                1.0
+            };
+            add_biome(BiomeKind::Grassland, (0.5 + ch.humidity) * irrigation);
             add_biome(BiomeKind::Jungle, 0.5 + ch.humidity * ch.temp.max(0.0));
             add_biome(BiomeKind::Mountain, 0.5 + (ch.alt / 4000.0).max(0.0));
             add_biome(
@@ -315,6 +315,14 @@ impl Site {
         }
     }
 
+    /// Whether the site's fields are watered by irrigation canals
+    pub fn is_irrigated(&self) -> bool {
+        match &self.kind {
+            SiteKind::Refactor(s) => s.is_irrigated(),
+            _ => false,
+        }
+    }
+
     pub fn spawn_rules(&self, wpos: Vec2<i32>) -> SpawnRules {
         match &self.kind {
             SiteKind::Settlement(s) => s.spawn_rules(wpos),
@@ -21,6 +21,7 @@ use common::{
         inventory::{
             loadout_builder::LoadoutBuilder, slot::ArmorSlot, trade_pricing::TradePricing,
         },
+        item::ItemDefinitionIdOwned,
         quadruped_small, Item,
     },
     generation::{ChunkSupplement, EntityInfo},
@@ -1032,19 +1033,63 @@ pub fn merchant_loadout(
     )
 }
 
+const TRADER_BACKPACK: &str = "common.items.armor.misc.back.backpack";
+const TRADER_BAG: &str = "common.items.armor.misc.bag.sturdy_red_backpack";
+
 pub fn trader_loadout(
     loadout_builder: LoadoutBuilder,
     economy: Option<&SiteInformation>,
-    mut permitted: impl FnMut(Good) -> bool,
+    permitted: impl FnMut(Good) -> bool,
 ) -> LoadoutBuilder {
+    let mut backpack = Item::new_from_asset_expect(TRADER_BACKPACK);
+    let mut bag1 = Item::new_from_asset_expect(TRADER_BAG);
+    let mut bag2 = Item::new_from_asset_expect(TRADER_BAG);
+    let mut bag3 = Item::new_from_asset_expect(TRADER_BAG);
+    let mut bag4 = Item::new_from_asset_expect(TRADER_BAG);
+    // Fill bags with stuff according to unclaimed stock
+    let ability_map = &comp::tool::AbilityMap::load().read();
+    let msm = &comp::item::MaterialStatManifest::load().read();
+    let mut wares: Vec<Item> = trader_wares(economy, permitted)
+        .iter()
+        .filter_map(|(n, a)| {
+            let i = Item::new_from_item_definition_id(n.as_ref(), ability_map, msm).ok();
+            i.map(|mut i| {
+                i.set_amount(*a)
+                    .map_err(|_| tracing::error!("merchant loadout amount failure"))
+                    .ok();
+                i
+            })
+        })
+        .collect();
+    sort_wares(&mut wares);
+    transfer(&mut wares, &mut backpack);
+    transfer(&mut wares, &mut bag1);
+    transfer(&mut wares, &mut bag2);
+    transfer(&mut wares, &mut bag3);
+    transfer(&mut wares, &mut bag4);
+
+    loadout_builder
+        .back(Some(backpack))
+        .bag(ArmorSlot::Bag1, Some(bag1))
+        .bag(ArmorSlot::Bag2, Some(bag2))
+        .bag(ArmorSlot::Bag3, Some(bag3))
+        .bag(ArmorSlot::Bag4, Some(bag4))
+}
+
+/// Number of different wares that fit into the bags of a trader
+pub fn trader_capacity() -> usize {
+    Item::new_from_asset_expect(TRADER_BACKPACK).slots().len()
+        + 4 * Item::new_from_asset_expect(TRADER_BAG).slots().len()
+}
+
+/// Picks the wares of a trader from the unclaimed stock of their site's
+/// economy, as many as fit into their bags
+pub fn trader_wares(
+    economy: Option<&SiteInformation>,
+    mut permitted: impl FnMut(Good) -> bool,
+) -> Vec<(ItemDefinitionIdOwned, u32)> {
     let rng = &mut thread_rng();
 
-    let mut backpack = Item::new_from_asset_expect("common.items.armor.misc.back.backpack");
-    let mut bag1 = Item::new_from_asset_expect("common.items.armor.misc.bag.sturdy_red_backpack");
-    let mut bag2 = Item::new_from_asset_expect("common.items.armor.misc.bag.sturdy_red_backpack");
-    let mut bag3 = Item::new_from_asset_expect("common.items.armor.misc.bag.sturdy_red_backpack");
-    let mut bag4 = Item::new_from_asset_expect("common.items.armor.misc.bag.sturdy_red_backpack");
-    let slots = backpack.slots().len() + 4 * bag1.slots().len();
     let mut stockmap: HashMap<Good, f32> = economy
         .map(|e| {
             e.unconsumed_stock
@@ -1086,35 +1131,7 @@ pub fn trader_loadout(
         .iter_mut()
         .filter(|(good, _amount)| **good != Good::Coin)
         .for_each(|(_good, amount)| *amount *= 0.1);
-    // Fill bags with stuff according to unclaimed stock
-    let ability_map = &comp::tool::AbilityMap::load().read();
-    let msm = &comp::item::MaterialStatManifest::load().read();
-    let mut wares: Vec<Item> =
-        TradePricing::random_items(&mut stockmap, slots as u32, true, true, 16)
-            .iter()
-            .filter_map(|(n, a)| {
-                let i = Item::new_from_item_definition_id(n.as_ref(), ability_map, msm).ok();
-                i.map(|mut i| {
-                    i.set_amount(*a)
-                        .map_err(|_| tracing::error!("merchant loadout amount failure"))
-                        .ok();
-                    i
-                })
-            })
-            .collect();
-    sort_wares(&mut wares);
-    transfer(&mut wares, &mut backpack);
-    transfer(&mut wares, &mut bag1);
-    transfer(&mut wares, &mut bag2);
-    transfer(&mut wares, &mut bag3);
-    transfer(&mut wares, &mut bag4);
-
-    loadout_builder
-        .back(Some(backpack))
-        .bag(ArmorSlot::Bag1, Some(bag1))
-        .bag(ArmorSlot::Bag2, Some(bag2))
-        .bag(ArmorSlot::Bag3, Some(bag3))
-        .bag(ArmorSlot::Bag4, Some(bag4))
+    TradePricing::random_items(&mut stockmap, trader_capacity() as u32, true, true, 16)
 }
 
 fn sort_wares(bag: &mut [Item]) {
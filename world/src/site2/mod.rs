@@ -14,7 +14,7 @@ use crate::{
     config::CONFIG,
     sim::Path,
     site::{namegen::NameGen, SpawnRules},
-    util::{attempt, DHashSet, Grid, CARDINALS, SQUARE_4, SQUARE_9},
+    util::{attempt, DHashSet, Grid, RandomField, Sampler, CARDINALS, SQUARE_4, SQUARE_9},
     Canvas, IndexRef, Land,
 };
 use common::{
@@ -34,6 +34,21 @@ use rand_chacha::ChaChaRng;
 use std::ops::Range;
 use vek::*;
 
+/// Distance between the irrigation ditches that run through watered fields
+const DITCH_SPACING: i32 = 6;
+
+/// The crops that can be grown in fields
+const FIELD_CROPS: [SpriteKind; 8] = [
+    SpriteKind::WheatYellow,
+    SpriteKind::Corn,
+    SpriteKind::Cabbage,
+    SpriteKind::Pumpkin,
+    SpriteKind::Carrot,
+    SpriteKind::Radish,
+    SpriteKind::Turnip,
+    SpriteKind::Flax,
+];
+
 /// Seed a new RNG from an old RNG, thereby making the old RNG indepedent of
 /// changing use of the new RNG. The practical effect of this is to reduce the
 /// extent to which changes to child generation algorithm produce a 'butterfly
@@ -52,6 +67,8 @@ pub struct Site {
     pub plots: Store<Plot>,
     pub plazas: Vec<Id<Plot>>,
     pub roads: Vec<Id<Plot>>,
+    /// Whether the site's fields are watered by canals dug from a nearby river
+    irrigated: bool,
 }
 
 impl Site {
@@ -378,6 +395,78 @@ impl Site {
 
     pub fn name(&self) -> &str { &self.name }
 
+    pub fn is_irrigated(&self) -> bool { self.irrigated }
+
+    /// Dig a canal from the nearest river to the given tile, returning whether
+    /// one could be found.
+    pub fn create_canal(&mut self, land: &Land, index: IndexRef, to: Vec2<i32>) -> bool {
+        const MAX_ITERS: usize = 4096;
+        const SEARCH_RADIUS: i32 = 24;
+
+        let is_water = |tile: Vec2<i32>| {
+            land.column_sample(self.tile_center_wpos(tile), index)
+                .map_or(false, |col| col.water_level > col.alt + 1.0)
+        };
+        let Some(river) = Spiral2d::new()
+            .take((SEARCH_RADIUS * 2 + 1).pow(2) as usize)
+            .map(|rpos| to + rpos)
+            .find(|tile| {
+                matches!(
+                    self.tiles.get(*tile).kind,
+                    TileKind::Hazard(HazardKind::Water)
+                ) && is_water(*tile)
+            })
+        else {
+            return false;
+        };
+
+        let heuristic = |tile: &Vec2<i32>, _: &Vec2<i32>| (tile.distance_squared(to) as f32).sqrt();
+        let Some(path) = Astar::new(MAX_ITERS, river, DefaultHashBuilder::default())
+            .poll(
+                MAX_ITERS,
+                &heuristic,
+                |tile| {
+                    let tile = *tile;
+                    let this = &self;
+                    CARDINALS.iter().filter_map(move |dir| {
+                        let neighbor = tile + *dir;
+                        let kind = &this.tiles.get(neighbor).kind;
+                        // Canals flow around buildings and under roads
+                        let kind_cost = match kind {
+                            TileKind::Empty
+                            | TileKind::Field
+                            | TileKind::Hazard(HazardKind::Water) => 0.0,
+                            TileKind::Road { .. } | TileKind::Path => 10.0,
+                            TileKind::Hazard(HazardKind::Hill { .. }) => 50.0,
+                            _ => return None,
+                        };
+                        let alt_a = land.get_alt_approx(this.tile_center_wpos(tile));
+                        let alt_b = land.get_alt_approx(this.tile_center_wpos(neighbor));
+                        Some((
+                            neighbor,
+                            1.0 + kind_cost + (alt_a - alt_b).abs() / TILE_SIZE as f32,
+                        ))
+                    })
+                },
+                |tile| *tile == to,
+            )
+            .into_path()
+        else {
+            return false;
+        };
+
+        for tile in path.iter() {
+            if matches!(
+                self.tiles.get(*tile).kind,
+                TileKind::Empty | TileKind::Hazard(HazardKind::Water)
+            ) {
+                self.tiles.set(*tile, Tile::free(TileKind::Canal));
+            }
+        }
+
+        true
+    }
+
     pub fn dungeon_difficulty(&self) -> Option<u32> {
         self.plots
             .iter()
@@ -735,15 +824,26 @@ impl Site {
             (5.0, 5),
             (15.0, 6),
             (15.0, 7),
+            (5.0, 8),
         ]);
 
         let mut castles = 0;
 
         let mut workshops = 0;
 
+        // Towns put the land around them to work, depending on what it has to offer
+        let worksite_kind = plot::WorksiteKind::choose(land, &mut rng, origin);
+        let mut worksites = 0;
+
         let mut airship_docks = 0;
 
+        // Towns surrounded by good farmland grow fields around their edges
+        let farmland = is_farmland(land, origin);
+        let mut fields = Vec::new();
+
         let mut taverns = 0;
+
+        let mut guild_halls = 0;
         for _ in 0..(size * 200.0) as i32 {
             match *build_chance.choose_seeded(rng.gen()) {
                 // Workshop
@@ -843,9 +943,8 @@ impl Site {
                     }
                 },
                 // Field
-                /*
-                3 => {
-                    attempt(10, || {
+                3 if farmland && fields.len() < 1 + (size * 4.0) as usize => {
+                    if let Some((tiles, center)) = attempt(10, || {
                         let search_pos = attempt(16, || {
                             let tile =
                                 (Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0))
@@ -861,21 +960,20 @@ impl Site {
                         })
                         .unwrap_or_else(Vec2::zero);
 
-                        site.tiles.find_near(search_pos, |center, _| {
-                            site.tiles.grow_organic(&mut rng, center, 12..64).ok()
+                        site.tiles.find_near(search_pos, |center, tile| {
+                            if tile.is_empty() {
+                                site.tiles.grow_organic(&mut rng, center, 12..64).ok()
+                            } else {
+                                None
+                            }
                         })
-                    })
-                    .map(|(tiles, _)| {
+                    }) {
                         for tile in tiles {
-                            site.tiles.set(tile, Tile {
-                                kind: TileKind::Field,
-                                plot: None,
-                                hard_alt: None,
-                            });
+                            site.tiles.set(tile, Tile::free(TileKind::Field));
                         }
-                    });
+                        fields.push(center);
+                    }
                 },
-                */
                 // Castle
                 4 if castles < 1 => {
                     if let Some((aabr, _entrance_tile, _door_dir)) = attempt(32, || {
@@ -1062,6 +1160,42 @@ impl Site {
                         }
                     }
                 },
+                // Guild hall, only in larger towns
+                6 if size > 0.5 && guild_halls < 1 => {
+                    let size = 4;
+                    if let Some((aabr, door_tile, door_dir)) = attempt(32, || {
+                        site.find_roadside_aabr(
+                            &mut rng,
+                            9..(size + 1).pow(2),
+                            Extent2::broadcast(size),
+                        )
+                    }) {
+                        let guild_hall = plot::GuildHall::generate(
+                            land,
+                            &mut reseed(&mut rng),
+                            &site,
+                            door_tile,
+                            door_dir,
+                            aabr,
+                        );
+                        let guild_hall_alt = guild_hall.alt;
+                        let plot = site.create_plot(Plot {
+                            kind: PlotKind::GuildHall(guild_hall),
+                            root_tile: aabr.center(),
+                            tiles: aabr_tiles(aabr).collect(),
+                            seed: rng.gen(),
+                        });
+
+                        site.blit_aabr(aabr, Tile {
+                            kind: TileKind::Building,
+                            plot: Some(plot),
+                            hard_alt: Some(guild_hall_alt),
+                        });
+                        guild_halls += 1;
+                    } else {
+                        site.make_plaza(land, &mut rng);
+                    }
+                },
                 7 if (size > 0.125 && taverns < 2) => {
                     let size = (3.5 + rng.gen::<f32>().powf(5.0) * 2.0).round() as u32;
                     if let Some((aabr, door_tile, door_dir)) = attempt(32, || {
@@ -1099,10 +1233,52 @@ impl Site {
                         site.make_plaza(land, &mut rng);
                     }
                 },
+                // Worksite
+                8 if worksites < 1 + (size * 2.0) as i32 => {
+                    let size = (3.0 + rng.gen::<f32>().powf(5.0) * 1.5).round() as u32;
+                    if let Some(kind) = worksite_kind
+                        && let Some((aabr, door_tile, door_dir)) = attempt(32, || {
+                            site.find_roadside_aabr(
+                                &mut rng,
+                                4..(size + 1).pow(2),
+                                Extent2::broadcast(size),
+                            )
+                        })
+                    {
+                        let worksite = plot::Worksite::generate(
+                            land,
+                            &mut reseed(&mut rng),
+                            &site,
+                            door_tile,
+                            door_dir,
+                            aabr,
+                            kind,
+                        );
+                        let worksite_alt = worksite.alt;
+                        let plot = site.create_plot(Plot {
+                            kind: PlotKind::Worksite(worksite),
+                            root_tile: aabr.center(),
+                            tiles: aabr_tiles(aabr).collect(),
+                            seed: rng.gen(),
+                        });
+
+                        site.blit_aabr(aabr, Tile {
+                            kind: TileKind::Building,
+                            plot: Some(plot),
+                            hard_alt: Some(worksite_alt),
+                        });
+                        worksites += 1;
+                    }
+                },
                 _ => {},
             }
         }
 
+        // Dig canals from the nearest river out to the fields to water them
+        for field in fields {
+            site.irrigated |= site.create_canal(land, index, field);
+        }
+
         site
     }
 
@@ -1365,6 +1541,46 @@ impl Site {
                 _ => {},
             }
         }
+
+        // Harbor, where ships moor and trade comes in from over the sea
+        if let Some((shore_tile, harbor)) = Spiral2d::new()
+            .take(48usize.pow(2))
+            .filter(|tile| site.tiles.get(*tile).is_empty())
+            .flat_map(|tile| Dir::ALL.map(|dir| (tile, dir)))
+            .filter(|(tile, dir)| {
+                matches!(
+                    site.tiles.get(*tile + dir.to_vec2()).kind,
+                    TileKind::Hazard(HazardKind::Water)
+                )
+            })
+            .find_map(|(tile, dir)| {
+                plot::Harbor::generate(land, &mut reseed(&mut rng), &site, tile, dir)
+                    .map(|harbor| (tile, harbor))
+            })
+        {
+            let harbor_alt = harbor.alt;
+            let tiles = harbor.tiles(&site);
+            let plot = site.create_plot(Plot {
+                kind: PlotKind::Harbor(harbor),
+                root_tile: shore_tile,
+                tiles: tiles.clone(),
+                seed: rng.gen(),
+            });
+            for tile in tiles {
+                if site.tiles.get(tile).is_natural() {
+                    site.tiles.set(tile, Tile {
+                        kind: TileKind::Bridge,
+                        plot: Some(plot),
+                        hard_alt: Some(harbor_alt),
+                    });
+                }
+            }
+            if let Some(&plaza) = site.plazas.first() {
+                let plaza_tile = site.plot(plaza).root_tile;
+                site.create_road(land, &mut rng, plaza_tile, shore_tile, 2);
+            }
+        }
+
         site
     }
 
@@ -2057,25 +2273,57 @@ impl Site {
             let seed = tile.plot.map_or(0, |p| self.plot(p).seed);
             #[allow(clippy::single_match)]
             match tile.kind {
-                TileKind::Field /*| TileKind::Road*/ => (-4..5).for_each(|z| canvas.map(
-                    Vec3::new(wpos2d.x, wpos2d.y, col.alt as i32 + z),
-                    |b| if [
-                        BlockKind::Grass,
-                        BlockKind::Earth,
-                        BlockKind::Sand,
-                        BlockKind::Snow,
-                        BlockKind::Rock,
-                    ]
-                    .contains(&b.kind()) {
-                        match tile.kind {
-                            TileKind::Field => Block::new(BlockKind::Earth, Rgb::new(40, 5 + (seed % 32) as u8, 0)),
-                            TileKind::Road { .. } => Block::new(BlockKind::Rock, Rgb::new(55, 45, 65)),
-                            _ => unreachable!(),
-                        }
-                    } else {
-                        b.with_sprite(SpriteKind::Empty)
-                    },
-                )),
+                TileKind::Field /*| TileKind::Road*/ => {
+                    let alt = col.alt as i32;
+                    // Irrigated fields have ditches running through them and crops that grow
+                    // far more densely
+                    let ditch = self.irrigated && wpos2d.x.rem_euclid(DITCH_SPACING) == 0;
+                    let crop = FIELD_CROPS[RandomField::new(self.origin.x as u32).get((tpos / 4).with_z(self.origin.y)) as usize % FIELD_CROPS.len()];
+                    let grows = wpos2d.y.rem_euclid(2) == 0
+                        && (self.irrigated || RandomField::new(self.origin.y as u32).chance(wpos2d.with_z(alt), 0.4));
+                    let mut on_ground = false;
+                    for z in -4..5 {
+                        canvas.map(
+                            Vec3::new(wpos2d.x, wpos2d.y, alt + z),
+                            |b| if [
+                                BlockKind::Grass,
+                                BlockKind::Earth,
+                                BlockKind::Sand,
+                                BlockKind::Snow,
+                                BlockKind::Rock,
+                            ]
+                            .contains(&b.kind()) {
+                                on_ground = true;
+                                if ditch && z >= -1 {
+                                    Block::water(SpriteKind::Empty)
+                                } else {
+                                    Block::new(BlockKind::Earth, Rgb::new(40, 5 + (seed % 32) as u8, 0))
+                                }
+                            } else if on_ground && !ditch && grows {
+                                on_ground = false;
+                                b.with_sprite(crop)
+                            } else {
+                                on_ground = false;
+                                b.with_sprite(SpriteKind::Empty)
+                            },
+                        );
+                    }
+                },
+                TileKind::Canal => {
+                    let alt = col.alt as i32;
+                    for z in -3..6 {
+                        canvas.map(
+                            Vec3::new(wpos2d.x, wpos2d.y, alt + z),
+                            |b| if b.is_liquid() {
+                                b
+                            } else if z < 0 {
+                                Block::water(SpriteKind::Empty)
+                            } else {
+                                Block::air(SpriteKind::Empty)
+                            },
+                        );
+                    }
+                },
                 // TileKind::Building => {
                 //     let base_alt = tile.plot.map(|p| self.plot(p)).map_or(col.alt as i32, |p| p.base_alt);
                 //     for z in base_alt - 12..base_alt + 16 {
@@ -2145,8 +2393,11 @@ impl Site {
                 PlotKind::CoastalWorkshop(coastal_workshop) => {
                     coastal_workshop.render_collect(self, canvas)
                 },
+                PlotKind::Harbor(harbor) => harbor.render_collect(self, canvas),
                 PlotKind::JungleRuin(jungle_ruin) => jungle_ruin.render_collect(self, canvas),
                 PlotKind::Workshop(workshop) => workshop.render_collect(self, canvas),
+                PlotKind::GuildHall(guild_hall) => guild_hall.render_collect(self, canvas),
+                PlotKind::Worksite(worksite) => worksite.render_collect(self, canvas),
                 PlotKind::Castle(castle) => castle.render_collect(self, canvas),
                 PlotKind::SeaChapel(sea_chapel) => sea_chapel.render_collect(self, canvas),
                 PlotKind::Dungeon(dungeon) => dungeon.render_collect(self, canvas),
@@ -2295,6 +2546,7 @@ pub fn test_site() -> Site {
     let index_ref = IndexRef {
         colors: &index.colors(),
         features: &index.features(),
+        layer_tuning: &index.layer_tuning(),
         index: &index,
     };
     Site::generate_city(
@@ -2320,6 +2572,20 @@ fn wpos_is_hazard(land: &Land, wpos: Vec2<i32>) -> Option<HazardKind> {
     }
 }
 
+/// Whether most of the land around a location is suited to growing crops
+fn is_farmland(land: &Land, wpos: Vec2<i32>) -> bool {
+    let chunk_pos = land.wpos_chunk_pos(wpos);
+    Spiral2d::new()
+        .take(25)
+        .filter(|rpos| {
+            land.get_chunk(chunk_pos + rpos).map_or(false, |c| {
+                c.rockiness < 0.3 && c.surface_veg > 0.5 && c.temp > CONFIG.snow_temp
+            })
+        })
+        .count()
+        > 12
+}
+
 fn temp_at_wpos(land: &Land, wpos: Vec2<i32>) -> f32 {
     land.get_chunk_wpos(wpos)
         .map(|c| c.temp)
@@ -15,7 +15,9 @@ pub mod dungeon;
 mod dwarven_mine;
 mod giant_tree;
 mod gnarling;
+mod guild_hall;
 mod haniwa;
+mod harbor;
 mod house;
 mod jungle_ruin;
 mod pirate_hideout;
@@ -31,19 +33,45 @@ mod terracotta_palace;
 mod terracotta_yard;
 mod troll_cave;
 mod workshop;
+mod worksite;
 
 pub use self::{
-    adlet::AdletStronghold, airship_dock::AirshipDock, bridge::Bridge, camp::Camp, castle::Castle,
-    citadel::Citadel, cliff_tower::CliffTower, coastal_house::CoastalHouse,
-    coastal_workshop::CoastalWorkshop, cultist::Cultist, desert_city_arena::DesertCityArena,
-    desert_city_multiplot::DesertCityMultiPlot, desert_city_temple::DesertCityTemple,
-    dungeon::Dungeon, dwarven_mine::DwarvenMine, giant_tree::GiantTree,
-    gnarling::GnarlingFortification, haniwa::Haniwa, house::House, jungle_ruin::JungleRuin,
-    pirate_hideout::PirateHideout, rock_circle::RockCircle, sahagin::Sahagin,
-    savannah_hut::SavannahHut, savannah_pit::SavannahPit, savannah_workshop::SavannahWorkshop,
-    sea_chapel::SeaChapel, tavern::Tavern, terracotta_house::TerracottaHouse,
-    terracotta_palace::TerracottaPalace, terracotta_yard::TerracottaYard, troll_cave::TrollCave,
+    adlet::AdletStronghold,
+    airship_dock::AirshipDock,
+    bridge::Bridge,
+    camp::Camp,
+    castle::Castle,
+    citadel::Citadel,
+    cliff_tower::CliffTower,
+    coastal_house::CoastalHouse,
+    coastal_workshop::CoastalWorkshop,
+    cultist::Cultist,
+    desert_city_arena::DesertCityArena,
+    desert_city_multiplot::DesertCityMultiPlot,
+    desert_city_temple::DesertCityTemple,
+    dungeon::Dungeon,
+    dwarven_mine::DwarvenMine,
+    giant_tree::GiantTree,
+    gnarling::GnarlingFortification,
+    guild_hall::GuildHall,
+    haniwa::Haniwa,
+    harbor::Harbor,
+    house::House,
+    jungle_ruin::JungleRuin,
+    pirate_hideout::PirateHideout,
+    rock_circle::RockCircle,
+    sahagin::Sahagin,
+    savannah_hut::SavannahHut,
+    savannah_pit::SavannahPit,
+    savannah_workshop::SavannahWorkshop,
+    sea_chapel::SeaChapel,
+    tavern::Tavern,
+    terracotta_house::TerracottaHouse,
+    terracotta_palace::TerracottaPalace,
+    terracotta_yard::TerracottaYard,
+    troll_cave::TrollCave,
     workshop::Workshop,
+    worksite::{Worksite, WorksiteKind},
 };
 
 use super::*;
@@ -89,7 +117,9 @@ pub enum PlotKind {
     Tavern(Tavern),
     CoastalHouse(CoastalHouse),
     CoastalWorkshop(CoastalWorkshop),
+    Harbor(Harbor),
     Workshop(Workshop),
+    GuildHall(GuildHall),
     DesertCityMultiPlot(DesertCityMultiPlot),
     DesertCityTemple(DesertCityTemple),
     DesertCityArena(DesertCityArena),
@@ -119,4 +149,5 @@ pub enum PlotKind {
     TerracottaPalace(TerracottaPalace),
     TerracottaHouse(TerracottaHouse),
     TerracottaYard(TerracottaYard),
+    Worksite(Worksite),
 }
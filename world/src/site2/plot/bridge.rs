@@ -47,6 +47,10 @@ enum BridgeKind {
     Short,
     HeightenedViaduct(HeightenedViaduct),
     HangBridge,
+    /// A long crossing of stone piers carrying wooden spans, used for wide
+    /// rivers that none of the other bridges can cross. The spans can be
+    /// broken to cut off the crossing.
+    Piered,
 }
 
 impl BridgeKind {
@@ -61,7 +65,11 @@ impl BridgeKind {
         let len = (start.xy() - end.xy()).map(|e| e.abs()).reduce_max();
         let height = end.z - start.z;
         let down = start.z - water_alt;
-        (0..=4)
+        // Only piers are sturdy enough to cross the widest rivers
+        if len > MAX_UNSUPPORTED_LEN {
+            return BridgeKind::Piered;
+        }
+        (0..=5)
             .filter_map(|bridge| match bridge {
                 0 if height >= 16 => Some(BridgeKind::Tower(match rng.gen_range(0..=2) {
                     0 => RoofKind::Crenelated,
@@ -80,6 +88,7 @@ impl BridgeKind {
                 },
                 3 if height < 10 && down > 10 => Some(BridgeKind::HangBridge),
                 4 if down > 8 => Some(BridgeKind::Flat),
+                5 if len >= 100 && height < 12 => Some(BridgeKind::Piered),
                 _ => None,
             })
             .collect::<Vec<_>>()
@@ -91,11 +100,15 @@ impl BridgeKind {
     fn width(&self) -> i32 {
         match self {
             BridgeKind::HangBridge => 2,
+            BridgeKind::Piered => 4,
             _ => 8,
         }
     }
 }
 
+/// The longest bridge that can be built without piers in the water
+const MAX_UNSUPPORTED_LEN: i32 = 170;
+
 fn aabb(min: Vec3<i32>, max: Vec3<i32>) -> Aabb<i32> {
     let aabb = Aabb { min, max }.made_valid();
     Aabb {
@@ -861,6 +874,96 @@ fn render_hang(bridge: &Bridge, painter: &Painter) {
         .fill(rock);
 }
 
+fn render_piered(bridge: &Bridge, painter: &Painter) {
+    let stone = Fill::Brick(BlockKind::Rock, Rgb::gray(90), 20);
+    let cap = Fill::Block(Block::new(BlockKind::Rock, Rgb::gray(130)));
+    // Spans are made of wood so that they can be blown up
+    let plank = Fill::Block(Block::new(BlockKind::Wood, Rgb::new(90, 60, 35)));
+    let beam = Fill::Block(Block::new(BlockKind::Wood, Rgb::new(55, 35, 20)));
+
+    let orth_dir = bridge.dir.orthogonal();
+    let orthogonal = orth_dir.to_vec2();
+    let forward = bridge.dir.to_vec2();
+
+    let height = bridge.end.z - bridge.start.z;
+    let deck = bridge.end.z;
+
+    let side = orthogonal * bridge.width();
+    let aabr = Aabr {
+        min: bridge.start.xy() - side,
+        max: bridge.end.xy() + side,
+    }
+    .made_valid();
+
+    // Stone ramp up from the lower bank to the level of the deck
+    let [ramp_aabr, aabr] = bridge.dir.split_aabr_offset(aabr, height);
+    painter
+        .aabb(aabb(
+            ramp_aabr.min.with_z(bridge.start.z - 10),
+            ramp_aabr.max.with_z(bridge.start.z - 1),
+        ))
+        .union(painter.ramp(
+            aabb(
+                ramp_aabr.min.with_z(bridge.start.z),
+                ramp_aabr.max.with_z(deck),
+            ),
+            bridge.dir,
+        ))
+        .fill(stone.clone());
+
+    // Piers, evenly spaced from one bank to the other
+    let pier_spacing = 24;
+    let pier_thickness = 4;
+    let len = bridge.dir.select(aabr.size());
+    let n = (len / pier_spacing).max(1);
+    let p = len / n;
+    let [pier, _] = bridge.dir.split_aabr_offset(aabr, pier_thickness);
+    let pier = orth_dir.trim_aabr(pier, -1);
+    let piers = painter
+        .aabb(aabb(
+            pier.min.with_z(bridge.center.z - 20),
+            pier.max.with_z(deck - 3),
+        ))
+        .repeat((forward * p).with_z(0), n as u32 + 1);
+    piers.fill(stone);
+    painter
+        .aabb(aabb(pier.min.with_z(deck - 2), pier.max.with_z(deck - 2)))
+        .repeat((forward * p).with_z(0), n as u32 + 1)
+        .fill(cap);
+
+    // Beams running between the piers along either side, under the deck
+    let walkway = orth_dir.trim_aabr(aabr, 1);
+    painter
+        .aabb(aabb(aabr.min.with_z(deck - 1), aabr.max.with_z(deck - 1)))
+        .without(painter.aabb(aabb(
+            walkway.min.with_z(deck - 1),
+            walkway.max.with_z(deck - 1),
+        )))
+        .fill(beam);
+
+    // The deck itself, with a railing along each side
+    painter
+        .aabb(aabb(aabr.min.with_z(deck), aabr.max.with_z(deck + 1)))
+        .without(painter.aabb(aabb(
+            walkway.min.with_z(deck + 1),
+            walkway.max.with_z(deck + 1),
+        )))
+        .fill(plank);
+    painter
+        .aabb(aabb(
+            walkway.min.with_z(deck + 1),
+            walkway.max.with_z(deck + 8),
+        ))
+        .clear();
+    painter
+        .aabb(aabb(aabr.min.with_z(deck + 2), aabr.max.with_z(deck + 2)))
+        .without(painter.aabb(aabb(
+            walkway.min.with_z(deck + 2),
+            walkway.max.with_z(deck + 2),
+        )))
+        .fill(Fill::Sprite(SpriteKind::Rope));
+}
+
 pub struct Bridge {
     pub(crate) start: Vec3<i32>,
     pub(crate) end: Vec3<i32>,
@@ -962,6 +1065,7 @@ impl Structure for Bridge {
             BridgeKind::Short => render_short(self, painter),
             BridgeKind::HeightenedViaduct(data) => render_heightened_viaduct(self, painter, data),
             BridgeKind::HangBridge => render_hang(self, painter),
+            BridgeKind::Piered => render_piered(self, painter),
         }
     }
 }
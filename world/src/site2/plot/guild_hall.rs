@@ -0,0 +1,257 @@
+use super::*;
+use crate::Land;
+use common::{
+    guild::{GuildHallSpot, GUILD_BANNER_HEIGHT, GUILD_BANNER_WIDTH},
+    heraldry::Heraldry,
+    terrain::{Block, BlockKind, SpriteKind},
+};
+use rand::prelude::*;
+use vek::*;
+
+/// Height of the walls above the floor
+const WALL_HEIGHT: i32 = 14;
+const DOOR_HEIGHT: i32 = 4;
+
+/// A hall in large towns that guilds can claim. The banner on its front and
+/// what is kept in its chests belong to the guild that claimed it, which the
+/// server takes care of.
+pub struct GuildHall {
+    /// Axis aligned bounding region for the hall
+    bounds: Aabr<i32>,
+    /// Direction the front of the hall faces, towards the road
+    door_dir: Vec2<i32>,
+    /// Approximate altitude of the door tile
+    pub(crate) alt: i32,
+}
+
+impl GuildHall {
+    pub fn generate(
+        land: &Land,
+        _rng: &mut impl Rng,
+        site: &Site,
+        door_tile: Vec2<i32>,
+        door_dir: Vec2<i32>,
+        tile_aabr: Aabr<i32>,
+    ) -> Self {
+        let bounds = Aabr {
+            min: site.tile_wpos(tile_aabr.min),
+            max: site.tile_wpos(tile_aabr.max),
+        };
+
+        Self {
+            bounds,
+            door_dir,
+            alt: land.get_alt_approx(site.tile_center_wpos(door_tile + door_dir)) as i32,
+        }
+    }
+
+    /// Area enclosed by the walls, the walls themselves included
+    fn walls(&self) -> Aabr<i32> {
+        Aabr {
+            min: self.bounds.min + 2,
+            max: self.bounds.max - 2,
+        }
+    }
+
+    /// Where the door is on the front wall
+    fn door(&self) -> Vec2<i32> {
+        let walls = self.walls();
+        let center = walls.center();
+        match (self.door_dir.x, self.door_dir.y) {
+            (x, _) if x > 0 => Vec2::new(walls.max.x - 1, center.y),
+            (x, _) if x < 0 => Vec2::new(walls.min.x, center.y),
+            (_, y) if y > 0 => Vec2::new(center.x, walls.max.y - 1),
+            _ => Vec2::new(center.x, walls.min.y),
+        }
+    }
+
+    /// Direction to the right of someone facing the front of the hall
+    fn right(&self) -> Vec2<i32> { Vec2::new(-self.door_dir.y, self.door_dir.x) }
+
+    /// Distance from the front wall to the back wall
+    fn depth(&self) -> i32 {
+        let size = self.walls().size();
+        if self.door_dir.x == 0 { size.h } else { size.w }
+    }
+
+    /// Distance from the center of the front wall to the side walls
+    fn half_width(&self) -> i32 {
+        let size = self.walls().size();
+        if self.door_dir.x == 0 {
+            size.w / 2
+        } else {
+            size.h / 2
+        }
+    }
+
+    fn base(&self) -> i32 { self.alt + 1 }
+
+    pub fn spot(&self) -> GuildHallSpot {
+        let door = self.door();
+        let right = self.right();
+        GuildHallSpot {
+            board: (door + self.door_dir + right * 3).with_z(self.base()),
+            banner_origin: (door - right * (GUILD_BANNER_WIDTH / 2))
+                .with_z(self.base() + DOOR_HEIGHT + GUILD_BANNER_HEIGHT),
+            banner_right: right,
+        }
+    }
+
+    /// Rotation of sprites so that they face the front of the hall
+    fn sprite_ori(&self) -> u8 {
+        match (self.door_dir.x, self.door_dir.y) {
+            (x, _) if x > 0 => 2,
+            (x, _) if x < 0 => 6,
+            (_, y) if y > 0 => 4,
+            _ => 0,
+        }
+    }
+}
+
+impl Structure for GuildHall {
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"render_guild_hall\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "render_guild_hall")]
+    fn render_inner(&self, _site: &Site, _land: &Land, painter: &Painter) {
+        let stone = Fill::Brick(BlockKind::Rock, Rgb::new(120, 115, 110), 20);
+        let wood = Fill::Block(Block::new(BlockKind::Wood, Rgb::new(70, 40, 20)));
+        let planks = Fill::Block(Block::new(BlockKind::Wood, Rgb::new(110, 75, 45)));
+        let roof_fill = Fill::Brick(BlockKind::Wood, Rgb::new(60, 35, 35), 16);
+
+        let base = self.base();
+        let walls = self.walls();
+        let roof = base + WALL_HEIGHT;
+
+        // Foundation
+        painter
+            .aabb(Aabb {
+                min: (self.bounds.min + 1).with_z(base - 16),
+                max: (self.bounds.max - 1).with_z(base),
+            })
+            .fill(stone.clone());
+
+        // Walls, with the inside hollowed out and floored with planks
+        painter
+            .aabb(Aabb {
+                min: walls.min.with_z(base),
+                max: walls.max.with_z(roof),
+            })
+            .fill(stone.clone());
+        painter
+            .aabb(Aabb {
+                min: (walls.min + 1).with_z(base),
+                max: (walls.max - 1).with_z(roof),
+            })
+            .clear();
+        painter
+            .aabb(Aabb {
+                min: (walls.min + 1).with_z(base - 1),
+                max: (walls.max - 1).with_z(base),
+            })
+            .fill(planks);
+
+        // Pillars at the corners
+        for corner in [
+            walls.min,
+            Vec2::new(walls.max.x - 1, walls.min.y),
+            Vec2::new(walls.min.x, walls.max.y - 1),
+            walls.max - 1,
+        ] {
+            painter
+                .line(corner.with_z(base), corner.with_z(roof + 1), 1.5)
+                .fill(wood.clone());
+        }
+
+        // Roof
+        let roof_dir = if self.door_dir.x == 0 { Dir::Y } else { Dir::X };
+        painter
+            .gable(
+                Aabb {
+                    min: (walls.min - 1).with_z(roof),
+                    max: (walls.max + 1).with_z(roof + walls.size().reduce_min() / 2 + 2),
+                },
+                walls.size().reduce_min() / 2,
+                roof_dir,
+            )
+            .fill(roof_fill);
+
+        // Door
+        let door = self.door();
+        let right = self.right();
+        painter
+            .aabb(Aabb {
+                min: (door - right.map(i32::abs)).with_z(base),
+                max: (door + right.map(i32::abs) + 1).with_z(base + DOOR_HEIGHT),
+            })
+            .clear();
+
+        // Windows along the sides
+        let depth_dir = -self.door_dir;
+        let depth = self.depth();
+        for side in [-1, 1] {
+            let side_wall = door + right * side * self.half_width();
+            for step in [depth / 3, depth * 2 / 3] {
+                let window = side_wall + depth_dir * step;
+                painter
+                    .aabb(Aabb {
+                        min: window.with_z(base + 4),
+                        max: (window + 1).with_z(base + 7),
+                    })
+                    .fill(Fill::Block(Block::air(SpriteKind::Window1)));
+            }
+        }
+
+        // The banner of the hall, as it looks until a guild claims the hall
+        let spot = self.spot();
+        let banner = Heraldry::default();
+        for (pos, uv) in spot.banner_cells() {
+            painter
+                .aabb(Aabb {
+                    min: pos,
+                    max: pos + 1,
+                })
+                .fill(Fill::Block(Block::new(
+                    BlockKind::Misc,
+                    banner.color_at(uv),
+                )));
+        }
+
+        // Notice board of the guild by the door
+        painter.rotated_sprite(spot.board, SpriteKind::GuildBoard, self.sprite_ori());
+
+        // Storage along the back wall, facing the door
+        let back = door + depth_dir * (depth - 2);
+        for offset in [-4, -2, 2, 4] {
+            painter.rotated_sprite(
+                (back + right * offset).with_z(base),
+                SpriteKind::GuildChest,
+                self.sprite_ori(),
+            );
+        }
+
+        // A long table to hold meetings at
+        let table_start = door + depth_dir * 5;
+        for step in 0..(depth - 10).max(1) {
+            let pos = table_start + depth_dir * step;
+            painter.rotated_sprite(pos.with_z(base), SpriteKind::TableDining, self.sprite_ori());
+            for side in [-2, 2] {
+                painter.rotated_sprite(
+                    (pos + right * side).with_z(base),
+                    SpriteKind::Bench,
+                    self.sprite_ori(),
+                );
+            }
+        }
+
+        // Lamps on the walls
+        for side in [-1, 1] {
+            let pos = door + right * side * 4 + depth_dir;
+            painter.rotated_sprite(
+                pos.with_z(base + 6),
+                SpriteKind::WallSconce,
+                self.sprite_ori(),
+            );
+        }
+    }
+}
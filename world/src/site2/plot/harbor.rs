@@ -0,0 +1,189 @@
+use super::*;
+use crate::{util::DHashSet, Land};
+use common::terrain::{Block, BlockKind, SpriteKind};
+use rand::prelude::*;
+use vek::*;
+
+/// The furthest that a pier may reach out from the shore before finding water
+/// deep enough for ships to moor in
+const MAX_PIER_LEN: i32 = 96;
+/// How deep the water needs to be for ships to moor
+const MOORING_DEPTH: f32 = 4.0;
+/// How far the pier reaches beyond the point where the water gets deep enough
+const PIER_OVERHANG: i32 = 12;
+/// Half the width of the pier deck
+const PIER_HALF_WIDTH: i32 = 2;
+/// Radius of the platform at the end of the pier that the lighthouse stands on
+const PLATFORM_RADIUS: i32 = 6;
+
+/// Represents harbor data generated by the `generate()` method
+pub struct Harbor {
+    /// Where the pier meets the shore
+    pub start: Vec2<i32>,
+    /// The end of the pier, on which the lighthouse stands
+    pub end: Vec2<i32>,
+    /// Where ships moor, alongside the end of the pier
+    pub mooring: Vec2<i32>,
+    dir: Dir,
+    /// Altitude of the pier deck
+    pub(crate) alt: i32,
+}
+
+impl Harbor {
+    /// Try to find a spot for a pier that leads out from the given shore tile
+    /// in the given direction into water deep enough for ships to moor.
+    pub fn generate(
+        land: &Land,
+        _rng: &mut impl Rng,
+        site: &Site,
+        shore_tile: Vec2<i32>,
+        dir: Dir,
+    ) -> Option<Self> {
+        let start = site.tile_center_wpos(shore_tile);
+        let forward = dir.to_vec2();
+        let water_alt = land
+            .get_chunk_wpos(start + forward * MAX_PIER_LEN)?
+            .water_alt;
+        let deep_water = (0..MAX_PIER_LEN)
+            .find(|i| land.get_alt_approx(start + forward * *i) < water_alt - MOORING_DEPTH)?;
+        let end = start + forward * (deep_water + PIER_OVERHANG);
+
+        Some(Self {
+            start,
+            end,
+            mooring: end - forward * PLATFORM_RADIUS * 2
+                + dir.orthogonal().to_vec2() * PLATFORM_RADIUS * 2,
+            dir,
+            alt: water_alt as i32 + 2,
+        })
+    }
+
+    /// The tiles that the harbor covers
+    pub fn tiles(&self, site: &Site) -> DHashSet<Vec2<i32>> {
+        let side = self.dir.orthogonal().to_vec2() * PLATFORM_RADIUS;
+        let len = (self.end - self.start).map(|e| e.abs()).reduce_max();
+        (0..=len + PLATFORM_RADIUS)
+            .flat_map(|i| {
+                let pos = self.start + self.dir.to_vec2() * i;
+                [
+                    site.wpos_tile_pos(pos - side),
+                    site.wpos_tile_pos(pos),
+                    site.wpos_tile_pos(pos + side),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Structure for Harbor {
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"render_harbor\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "render_harbor")]
+    fn render_inner(&self, _site: &Site, _land: &Land, painter: &Painter) {
+        let planks = Fill::Block(Block::new(BlockKind::Wood, Rgb::new(110, 75, 45)));
+        let wood = Fill::Block(Block::new(BlockKind::Wood, Rgb::new(60, 40, 25)));
+        let stone = Fill::Brick(BlockKind::Rock, Rgb::new(150, 150, 140), 16);
+        let white = Fill::Block(Block::new(BlockKind::Rock, Rgb::new(225, 225, 215)));
+        let red = Fill::Block(Block::new(BlockKind::Rock, Rgb::new(160, 35, 30)));
+
+        let forward = self.dir.to_vec2();
+        let orth = self.dir.orthogonal().to_vec2();
+        let deck = self.alt;
+        let len = (self.end - self.start).map(|e| e.abs()).reduce_max();
+
+        // Deck of the pier, clearing away any land that it cuts through
+        let pier = Aabr {
+            min: self.start - orth * PIER_HALF_WIDTH,
+            max: self.end + orth * PIER_HALF_WIDTH,
+        }
+        .made_valid();
+        painter
+            .aabb(Aabb {
+                min: pier.min.with_z(deck + 1),
+                max: (pier.max + 1).with_z(deck + 8),
+            })
+            .clear();
+        painter
+            .aabb(Aabb {
+                min: pier.min.with_z(deck),
+                max: (pier.max + 1).with_z(deck + 1),
+            })
+            .fill(planks);
+
+        // Pilings driven into the seabed, with posts along the edges of the deck
+        // for ships to tie up to
+        for i in (0..len).step_by(6) {
+            for side in [-PIER_HALF_WIDTH, PIER_HALF_WIDTH] {
+                let pos = self.start + forward * i + orth * side;
+                painter.column(pos, deck - 24..deck).fill(wood.clone());
+                if i % 12 == 0 {
+                    painter.column(pos, deck + 1..deck + 2).fill(wood.clone());
+                }
+            }
+        }
+        painter.sprite(
+            (self.start + forward * 3 + orth * (PIER_HALF_WIDTH - 1)).with_z(deck + 1),
+            SpriteKind::Crate,
+        );
+        painter.sprite(
+            (self.start + forward * 4 + orth * (PIER_HALF_WIDTH - 1)).with_z(deck + 1),
+            SpriteKind::Barrel,
+        );
+        painter.sprite(
+            (self.start + forward * 3 - orth * (PIER_HALF_WIDTH - 1)).with_z(deck + 1),
+            SpriteKind::Crate,
+        );
+
+        // Stone platform at the end of the pier
+        painter
+            .cylinder(Aabb {
+                min: (self.end - PLATFORM_RADIUS).with_z(deck - 24),
+                max: (self.end + PLATFORM_RADIUS + 1).with_z(deck + 1),
+            })
+            .fill(stone.clone());
+
+        // Lighthouse, striped so that it can be seen from far out to sea
+        let tower_radius = 3;
+        let tower_height = 28;
+        let tower = |z: i32, height: i32| {
+            painter.cylinder(Aabb {
+                min: (self.end - tower_radius).with_z(z),
+                max: (self.end + tower_radius + 1).with_z(z + height),
+            })
+        };
+        for (i, z) in (deck + 1..deck + 1 + tower_height).step_by(4).enumerate() {
+            tower(z, 4).fill(if i % 2 == 0 {
+                white.clone()
+            } else {
+                red.clone()
+            });
+        }
+        let top = deck + 1 + tower_height;
+        // Gallery running around the lamp room
+        painter
+            .cylinder(Aabb {
+                min: (self.end - tower_radius - 1).with_z(top),
+                max: (self.end + tower_radius + 2).with_z(top + 1),
+            })
+            .fill(stone.clone());
+        // Lamp room, open on all sides so that the light can shine out
+        for corner in [
+            Vec2::new(-1, -1),
+            Vec2::new(1, -1),
+            Vec2::new(-1, 1),
+            Vec2::new(1, 1),
+        ] {
+            painter
+                .column(self.end + corner * (tower_radius - 1), top + 1..top + 5)
+                .fill(stone.clone());
+        }
+        painter
+            .cone(Aabb {
+                min: (self.end - tower_radius).with_z(top + 5),
+                max: (self.end + tower_radius + 1).with_z(top + 9),
+            })
+            .fill(red);
+        painter.sprite(self.end.with_z(top + 1), SpriteKind::LighthouseLamp);
+    }
+}
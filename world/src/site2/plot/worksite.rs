@@ -0,0 +1,249 @@
+use super::*;
+use crate::{
+    util::{RandomField, Sampler},
+    Land,
+};
+use common::{
+    rtsim::ChunkResource,
+    terrain::{Block, BlockKind, SpriteKind},
+};
+use rand::prelude::*;
+use vek::*;
+
+/// The raw material that a worksite produces
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WorksiteKind {
+    /// Fells the surrounding forest for timber
+    Sawmill,
+    /// Digs ore out of the mountains
+    Mine,
+    /// Cuts stone out of cliffs and rocky ground
+    Quarry,
+}
+
+impl WorksiteKind {
+    /// The resource that the worksite draws from the land around it
+    pub fn resource(&self) -> ChunkResource {
+        match self {
+            Self::Sawmill => ChunkResource::Tree,
+            Self::Mine => ChunkResource::Ore,
+            Self::Quarry => ChunkResource::Stone,
+        }
+    }
+
+    /// Choose the industry that the land around a site is best suited to, if
+    /// any.
+    pub fn choose(land: &Land, rng: &mut impl Rng, wpos: Vec2<i32>) -> Option<Self> {
+        let chunk_pos = land.wpos_chunk_pos(wpos);
+        let (mut forest, mut mountain, mut rock) = (0.0f32, 0.0f32, 0.0f32);
+        for rpos in Spiral2d::new().take(49) {
+            if let Some(chunk) = land.get_chunk(chunk_pos + rpos) {
+                forest = forest.max(chunk.tree_density);
+                mountain = mountain.max(chunk.chaos);
+                rock = rock.max(chunk.rockiness + chunk.cliff_height / 64.0);
+            }
+        }
+        [
+            (Self::Sawmill, forest - 0.5),
+            (Self::Mine, mountain - 0.35),
+            (Self::Quarry, rock - 0.5),
+        ]
+        .into_iter()
+        .filter(|(_, suitability)| *suitability > 0.0)
+        .collect::<Vec<_>>()
+        .choose_weighted(rng, |(_, suitability)| *suitability)
+        .ok()
+        .map(|(kind, _)| *kind)
+    }
+}
+
+/// Represents worksite data generated by the `generate()` method
+pub struct Worksite {
+    pub kind: WorksiteKind,
+    /// Axis aligned bounding region for the worksite
+    bounds: Aabr<i32>,
+    /// Approximate altitude of the door tile
+    pub(crate) alt: i32,
+}
+
+impl Worksite {
+    pub fn generate(
+        land: &Land,
+        _rng: &mut impl Rng,
+        site: &Site,
+        door_tile: Vec2<i32>,
+        door_dir: Vec2<i32>,
+        tile_aabr: Aabr<i32>,
+        kind: WorksiteKind,
+    ) -> Self {
+        let bounds = Aabr {
+            min: site.tile_wpos(tile_aabr.min),
+            max: site.tile_wpos(tile_aabr.max),
+        };
+
+        Self {
+            kind,
+            bounds,
+            alt: land.get_alt_approx(site.tile_center_wpos(door_tile + door_dir)) as i32,
+        }
+    }
+}
+
+impl Structure for Worksite {
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"render_worksite\0";
+
+    #[cfg_attr(feature = "be-dyn-lib", export_name = "render_worksite")]
+    fn render_inner(&self, _site: &Site, _land: &Land, painter: &Painter) {
+        let base = self.alt + 1;
+        let center = self.bounds.center();
+        let wood = Fill::Block(Block::new(BlockKind::Wood, Rgb::new(55, 25, 8)));
+        let logs = Fill::Block(Block::new(BlockKind::Wood, Rgb::new(90, 60, 35)));
+        let stone = Fill::Brick(BlockKind::Rock, Rgb::new(110, 105, 100), 24);
+
+        // Yard
+        painter
+            .aabb(Aabb {
+                min: (self.bounds.min + 1).with_z(base - 16),
+                max: self.bounds.max.with_z(base),
+            })
+            .fill(match self.kind {
+                WorksiteKind::Sawmill => Fill::Brick(BlockKind::Earth, Rgb::new(80, 60, 40), 12),
+                WorksiteKind::Mine | WorksiteKind::Quarry => {
+                    Fill::Brick(BlockKind::Rock, Rgb::new(85, 80, 75), 12)
+                },
+            });
+        painter
+            .aabb(Aabb {
+                min: (self.bounds.min + 1).with_z(base),
+                max: self.bounds.max.with_z(base + 12),
+            })
+            .clear();
+
+        match self.kind {
+            WorksiteKind::Sawmill => {
+                // Open shed for the saw
+                let shed = Aabr {
+                    min: self.bounds.min + 2,
+                    max: Vec2::new(center.x, self.bounds.max.y - 1),
+                };
+                let roof = base + 5;
+                for pos in [
+                    shed.min,
+                    Vec2::new(shed.max.x - 1, shed.min.y),
+                    Vec2::new(shed.min.x, shed.max.y - 1),
+                    shed.max - 1,
+                ] {
+                    painter
+                        .line(pos.with_z(base), pos.with_z(roof), 1.0)
+                        .fill(wood.clone());
+                }
+                painter
+                    .gable(
+                        Aabb {
+                            min: (shed.min - 1).with_z(roof),
+                            max: (shed.max + 1).with_z(roof + 4),
+                        },
+                        0,
+                        Dir::Y,
+                    )
+                    .fill(Fill::Brick(BlockKind::Wood, Rgb::new(70, 40, 20), 12));
+                painter.sprite(shed.center().with_z(base), SpriteKind::CraftingBench);
+
+                // Stacks of felled logs waiting to be sawn
+                for (i, y) in (self.bounds.min.y + 2..self.bounds.max.y - 3)
+                    .step_by(4)
+                    .enumerate()
+                {
+                    let height = 1 + (RandomField::new(0).get(center.with_z(y)) % 2) as i32;
+                    for layer in 0..height {
+                        painter
+                            .horizontal_cylinder(
+                                Aabb {
+                                    min: Vec2::new(center.x + 2, y).with_z(base + layer * 3),
+                                    max: Vec2::new(self.bounds.max.x - 2, y + 3)
+                                        .with_z(base + layer * 3 + 3),
+                                },
+                                Dir::X,
+                            )
+                            .fill(logs.clone());
+                    }
+                    if i == 0 {
+                        painter.sprite(Vec2::new(center.x + 1, y).with_z(base), SpriteKind::Crate);
+                    }
+                }
+            },
+            WorksiteKind::Mine => {
+                // A mound of rock with a timbered tunnel leading into it
+                let mound = painter.cone(Aabb {
+                    min: (self.bounds.min + 1).with_z(base),
+                    max: self.bounds.max.with_z(base + 10),
+                });
+                mound.fill(stone);
+                let tunnel = Aabb {
+                    min: Vec2::new(center.x - 2, self.bounds.min.y + 1).with_z(base),
+                    max: Vec2::new(center.x + 2, center.y + 2).with_z(base + 5),
+                };
+                painter.aabb(tunnel).clear();
+                for y in (tunnel.min.y..tunnel.max.y).step_by(3) {
+                    for x in [tunnel.min.x, tunnel.max.x - 1] {
+                        painter
+                            .line(
+                                Vec3::new(x, y, base),
+                                Vec3::new(x, y, tunnel.max.z - 1),
+                                0.5,
+                            )
+                            .fill(wood.clone());
+                    }
+                    painter
+                        .aabb(Aabb {
+                            min: Vec3::new(tunnel.min.x, y, tunnel.max.z - 1),
+                            max: Vec3::new(tunnel.max.x, y + 1, tunnel.max.z),
+                        })
+                        .fill(wood.clone());
+                }
+                painter.sprite(
+                    Vec2::new(center.x, tunnel.min.y).with_z(base + 3),
+                    SpriteKind::Lantern,
+                );
+                painter.sprite(
+                    Vec2::new(center.x - 1, center.y + 1).with_z(base),
+                    SpriteKind::Barrel,
+                );
+                painter.sprite(
+                    Vec2::new(center.x + 1, center.y + 1).with_z(base),
+                    SpriteKind::Crate,
+                );
+            },
+            WorksiteKind::Quarry => {
+                // Terraces stepping down into the pit
+                for step in 0..3 {
+                    painter
+                        .aabb(Aabb {
+                            min: (self.bounds.min + 3 + step * 2).with_z(base - (step + 1) * 3),
+                            max: (self.bounds.max - 2 - step * 2).with_z(base),
+                        })
+                        .clear();
+                }
+                // Blocks of cut stone stacked around the rim
+                for pos in [
+                    self.bounds.min + 1,
+                    Vec2::new(self.bounds.max.x - 3, self.bounds.min.y + 1),
+                    Vec2::new(self.bounds.min.x + 1, self.bounds.max.y - 3),
+                ] {
+                    painter
+                        .aabb(Aabb {
+                            min: pos.with_z(base),
+                            max: (pos + 2).with_z(base + 2),
+                        })
+                        .fill(stone.clone());
+                }
+                painter.sprite((self.bounds.max - 2).with_z(base), SpriteKind::Crate);
+                painter.sprite(
+                    Vec2::new(self.bounds.max.x - 2, self.bounds.min.y + 4).with_z(base),
+                    SpriteKind::Lantern,
+                );
+            },
+        }
+    }
+}
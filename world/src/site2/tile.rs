@@ -181,6 +181,8 @@ pub enum TileKind {
     Empty,
     Hazard(HazardKind),
     Field,
+    /// A shallow waterway carrying water from a river out to fields
+    Canal,
     Plaza,
     Road { a: u16, b: u16, w: u16 },
     Path,
@@ -0,0 +1,120 @@
+use crate::{column::ColumnGen, util::Sampler, IndexRef};
+use common::grid::Grid;
+use common_net::msg::world_msg::TreasureMapSnippet;
+use rand::Rng;
+use std::f32::consts::TAU;
+use vek::*;
+
+/// Range of distances from the reader of a treasure map at which its cache is
+/// buried
+const CACHE_DIST: std::ops::Range<f32> = 150.0..500.0;
+/// Width and height of a treasure map snippet, in pixels
+const SNIPPET_SIZE: i32 = 64;
+/// Blocks covered by a pixel of a treasure map snippet
+const SNIPPET_SCALE: i32 = 4;
+/// Height difference between the contour lines drawn on a treasure map
+const CONTOUR_INTERVAL: f32 = 16.0;
+
+const PARCHMENT: Rgb<u8> = Rgb::new(226, 206, 160);
+const INK: Rgb<u8> = Rgb::new(120, 86, 52);
+const WATER: Rgb<u8> = Rgb::new(150, 170, 165);
+const SHORE: Rgb<u8> = Rgb::new(84, 104, 112);
+const FOREST: Rgb<u8> = Rgb::new(118, 128, 76);
+const CROSS: Rgb<u8> = Rgb::new(168, 32, 28);
+
+/// Picks a spot to bury a cache at, somewhere on dry and reasonably flat land
+/// away from sites and paths
+pub fn find_cache_spot(
+    column_gen: &ColumnGen,
+    index: IndexRef,
+    origin: Vec2<i32>,
+    rng: &mut impl Rng,
+) -> Option<Vec2<i32>> {
+    (0..32).find_map(|_| {
+        let angle = rng.gen_range(0.0..TAU);
+        let dir = Vec2::new(angle.cos(), angle.sin());
+        let wpos = origin + (dir * rng.gen_range(CACHE_DIST)).as_::<i32>();
+        let col = column_gen.get((wpos, index, None))?;
+        (col.alt > col.water_level + 2.0
+            && col.chunk.sites.is_empty()
+            && col.chunk.spot.is_none()
+            && col.gradient.map_or(true, |gradient| gradient < 0.5)
+            && col.path.map_or(true, |(dist, ..)| dist > 12.0))
+        .then_some(wpos)
+    })
+}
+
+/// Draws the land around `center` the way a pirate would: contour lines,
+/// stippled forests, dashed paths and a cross at the cache
+pub fn draw_snippet(
+    column_gen: &ColumnGen,
+    index: IndexRef,
+    center: Vec2<i32>,
+) -> TreasureMapSnippet {
+    struct Sample {
+        alt: f32,
+        is_water: bool,
+        is_forest: bool,
+        is_path: bool,
+    }
+
+    let size = Vec2::broadcast(SNIPPET_SIZE);
+    // Rows go from north to south
+    let samples = Grid::populate_from(size, |pos| {
+        let wpos =
+            center + Vec2::new(pos.x - SNIPPET_SIZE / 2, SNIPPET_SIZE / 2 - pos.y) * SNIPPET_SCALE;
+        column_gen.get((wpos, index, None)).map(|col| Sample {
+            alt: col.alt.max(col.water_level),
+            is_water: col.water_level > col.alt,
+            is_forest: col.tree_density > 0.4,
+            is_path: col
+                .path
+                .map_or(false, |(dist, _, path, _)| dist < path.width),
+        })
+    });
+
+    let band = |sample: &Sample| (sample.alt / CONTOUR_INTERVAL).floor() as i32;
+    let rgba = Grid::populate_from(size, |pos| {
+        let edge_dist = pos.reduce_min().min((size - 1 - pos).reduce_min());
+        let cross = pos - size / 2;
+        let color = if edge_dist == 0 {
+            INK
+        } else if (cross.x.abs() - cross.y.abs()).abs() <= 1
+            && cross.map(i32::abs).reduce_max() <= 3
+        {
+            CROSS
+        } else if let Some(sample) = samples.get(pos).and_then(Option::as_ref) {
+            let neighbours = [Vec2::unit_x(), Vec2::unit_y()]
+                .map(|offs| samples.get(pos + offs).and_then(Option::as_ref));
+            if sample.is_water {
+                if neighbours.iter().flatten().any(|n| !n.is_water) {
+                    SHORE
+                } else {
+                    WATER
+                }
+            } else if neighbours.iter().flatten().any(|n| n.is_water) {
+                SHORE
+            } else if sample.is_path && (pos.x + pos.y) % 3 != 0 {
+                INK
+            } else if neighbours
+                .iter()
+                .flatten()
+                .any(|n| !n.is_water && band(n) != band(sample))
+            {
+                INK
+            } else if sample.is_forest && (pos.x + 2 * pos.y) % 4 == 0 {
+                FOREST
+            } else {
+                PARCHMENT
+            }
+        } else {
+            PARCHMENT
+        };
+        // Darken the parchment towards its edges, as if it had been handled a lot
+        let wear = 1.0 - 0.3 * (1.0 - edge_dist as f32 / 6.0).max(0.0);
+        let color = color.map(|e| (e as f32 * wear) as u8);
+        u32::from_le_bytes([color.r, color.g, color.b, 255])
+    });
+
+    TreasureMapSnippet { rgba }
+}